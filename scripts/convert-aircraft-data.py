@@ -10,6 +10,7 @@ Usage: python scripts/convert-aircraft-data.py
 Data source: https://www.faa.gov/airports/engineering/aircraft_char_database
 """
 
+import csv
 import json
 import os
 import urllib.request
@@ -29,6 +30,12 @@ FAA_URL = "https://www.faa.gov/airports/engineering/aircraft_char_database/aircr
 SCRIPT_DIR = Path(__file__).parent
 OUTPUT_PATH = SCRIPT_DIR.parent / "src" / "renderer" / "public" / "aircraft-dimensions.json"
 
+# Output path for the aircraft type database (used by the backend)
+TYPES_OUTPUT_PATH = SCRIPT_DIR.parent / "src-tauri" / "resources" / "aircraft-types.csv"
+
+# ICAO_WTC values to category letters (L/M = Medium)
+WAKE_CATEGORIES = {"light": "L", "light/medium": "M", "medium": "M", "heavy": "H", "super": "J"}
+
 # Conversion factor: feet to meters
 FEET_TO_METERS = 0.3048
 
@@ -178,6 +185,39 @@ def main():
         print(f"\nWritten to: {OUTPUT_PATH}")
         print(f"File size: {len(json_content)} bytes")
 
+        # Write the aircraft type database for the backend: wake category,
+        # approach speed and dimensions per ICAO type
+        def text(row, col):
+            return str(row[col]).strip() if col in df.columns and pd.notna(row[col]) else ""
+
+        type_rows = {}
+        for _, row in df.iterrows():
+            icao = str(row[icao_col]).strip().upper() if pd.notna(row[icao_col]) else None
+            if not icao or icao == "NAN" or len(icao) > 4:
+                continue
+            approach_speed = text(row, "Approach_Speed_knot")
+            wingspan = None
+            if wingspan_with_winglets_col:
+                wingspan = convert_feet_to_meters(row[wingspan_with_winglets_col])
+            if wingspan is None and wingspan_without_winglets_col:
+                wingspan = convert_feet_to_meters(row[wingspan_without_winglets_col])
+            length = convert_feet_to_meters(row[length_col]) if length_col else None
+            type_rows[icao] = [
+                icao,
+                text(row, "Manufacturer"),
+                text(row, "Model_FAA"),
+                WAKE_CATEGORIES.get(text(row, "ICAO_WTC").lower(), ""),
+                round(float(approach_speed)) if approach_speed else "",
+                "" if wingspan is None else wingspan,
+                "" if length is None else length,
+            ]
+        with TYPES_OUTPUT_PATH.open("w", newline="") as f:
+            writer = csv.writer(f, lineterminator="\n")
+            writer.writerow(["icao", "manufacturer", "model", "wtc", "approach_speed_kt", "wingspan_m", "length_m"])
+            for icao in sorted(type_rows):
+                writer.writerow(type_rows[icao])
+        print(f"Aircraft types ({len(type_rows)}) written to: {TYPES_OUTPUT_PATH}")
+
         # Also save full data for reference
         full_data_path = SCRIPT_DIR / "faa_aircraft_full_data.json"
         df_clean = df.copy()
@@ -1,3 +1,17 @@
+use std::path::{Path, PathBuf};
+
+#[path = "build/openapi.rs"]
+mod openapi;
+
 fn main() {
+    // The API description is derived from the server routes and types (see build/openapi.rs)
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=build");
+    let version = std::env::var("CARGO_PKG_VERSION").unwrap_or_default();
+    let spec = openapi::generate(Path::new("src"), &version)
+        .unwrap_or_else(|e| panic!("Failed to generate the OpenAPI spec: {}", e));
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    std::fs::write(out_dir.join("openapi.json"), spec.to_string()).expect("Failed to write openapi.json");
+
     tauri_build::build()
 }
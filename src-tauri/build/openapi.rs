@@ -0,0 +1,1056 @@
+//! OpenAPI spec generator (run by build.rs)
+//!
+//! Derives the remote server's API description from the source instead of a
+//! hand-maintained file, so it can't drift from the code:
+//!
+//! - Paths and methods come from the `.route(...)` calls in `create_router`
+//!   (server.rs). Routes nested under dev-only routers are left out.
+//! - Each handler's doc comment gives the summary ("GET /api/x - Summary") and
+//!   description, and its signature gives the parameters: `Path<...>`,
+//!   `Query<T>` fields, the `Json<T>` body and the `Json<T>` response.
+//! - Schemas come from the `Serialize`/`Deserialize` types in `src/`, following
+//!   their serde attributes (`rename_all`, `rename`, `skip`, `default`,
+//!   `flatten`, `tag`/`content`/`untagged`, `transparent`) and doc comments.
+//!   Only types reachable from an endpoint are included.
+//! - The error body and its codes come from `Error::code()` in error.rs.
+//!
+//! The result is written to `$OUT_DIR/openapi.json` and served at
+//! `/api/docs/openapi.json` (see api_docs.rs).
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::fs;
+use std::path::Path;
+
+use serde_json::{json, Map, Value};
+use syn::parse::ParseStream;
+use syn::visit::{self, Visit};
+use syn::{
+    Attribute, Expr, Fields, FnArg, GenericArgument, ImplItem, Item, ItemEnum, ItemFn, ItemStruct, Lit, LitStr,
+    PathArguments, ReturnType, Type,
+};
+
+const METHODS: [&str; 5] = ["get", "post", "put", "patch", "delete"];
+
+// =============================================================================
+// SERDE ATTRIBUTES
+// =============================================================================
+
+/// Serde attributes of a type, field or variant
+#[derive(Default)]
+struct SerdeAttrs {
+    rename: Option<String>,
+    rename_all: Option<String>,
+    tag: Option<String>,
+    content: Option<String>,
+    untagged: bool,
+    transparent: bool,
+    default: bool,
+    skip: bool,
+    flatten: bool,
+    skip_serializing_if: bool,
+}
+
+/// Consume the rest of an attribute argument we don't interpret (`= value` or `(...)`)
+fn skip_meta_value(input: ParseStream) -> syn::Result<()> {
+    if input.peek(syn::Token![=]) {
+        input.parse::<syn::Token![=]>()?;
+        input.parse::<Expr>()?;
+    } else if input.peek(syn::token::Paren) {
+        let content;
+        syn::parenthesized!(content in input);
+        content.step(|cursor| {
+            let mut rest = *cursor;
+            while let Some((_, next)) = rest.token_tree() {
+                rest = next;
+            }
+            Ok(((), rest))
+        })?;
+    }
+    Ok(())
+}
+
+fn serde_attrs(attrs: &[Attribute]) -> SerdeAttrs {
+    let mut parsed = SerdeAttrs::default();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("serde")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            let name = meta.path.get_ident().map(|i| i.to_string()).unwrap_or_default();
+            match name.as_str() {
+                "rename" | "rename_all" | "tag" | "content" if meta.input.peek(syn::Token![=]) => {
+                    let value: LitStr = meta.value()?.parse()?;
+                    let value = Some(value.value());
+                    match name.as_str() {
+                        "rename" => parsed.rename = value,
+                        "rename_all" => parsed.rename_all = value,
+                        "tag" => parsed.tag = value,
+                        _ => parsed.content = value,
+                    }
+                    return Ok(());
+                }
+                "untagged" => parsed.untagged = true,
+                "transparent" => parsed.transparent = true,
+                "default" => parsed.default = true,
+                "skip" | "skip_serializing" => parsed.skip = true,
+                "flatten" => parsed.flatten = true,
+                "skip_serializing_if" => parsed.skip_serializing_if = true,
+                _ => {}
+            }
+            skip_meta_value(meta.input)
+        });
+    }
+    parsed
+}
+
+/// `///` comment lines
+fn doc_lines(attrs: &[Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|a| a.path().is_ident("doc"))
+        .filter_map(|a| match &a.meta {
+            syn::Meta::NameValue(nv) => match &nv.value {
+                Expr::Lit(lit) => match &lit.lit {
+                    Lit::Str(s) => Some(s.value().trim().to_string()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// Doc comment as one paragraph per blank-line-separated block
+fn doc_text(attrs: &[Attribute]) -> Option<String> {
+    let text = doc_lines(attrs)
+        .split(|line| line.is_empty())
+        .map(|block| block.join(" "))
+        .filter(|block| !block.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    (!text.is_empty()).then_some(text)
+}
+
+fn derives_serde(attrs: &[Attribute]) -> bool {
+    attrs.iter().filter(|a| a.path().is_ident("derive")).any(|a| {
+        let mut found = false;
+        let _ = a.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.segments.last() {
+                found |= ident.ident == "Serialize" || ident.ident == "Deserialize";
+            }
+            Ok(())
+        });
+        found
+    })
+}
+
+/// Apply a serde `rename_all` rule to a field name (snake_case), like serde does
+fn rename_field(name: &str, rule: Option<&str>) -> String {
+    let name = name.trim_start_matches("r#");
+    let pascal = || {
+        let mut out = String::new();
+        let mut upper = true;
+        for c in name.chars() {
+            if c == '_' {
+                upper = true;
+            } else if upper {
+                out.extend(c.to_uppercase());
+                upper = false;
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    };
+    match rule {
+        Some("UPPERCASE") | Some("SCREAMING_SNAKE_CASE") => name.to_uppercase(),
+        Some("PascalCase") => pascal(),
+        Some("camelCase") => {
+            let pascal = pascal();
+            let mut chars = pascal.chars();
+            chars
+                .next()
+                .map(|first| first.to_lowercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+        Some("kebab-case") => name.replace('_', "-"),
+        Some("SCREAMING-KEBAB-CASE") => name.replace('_', "-").to_uppercase(),
+        _ => name.to_string(),
+    }
+}
+
+/// Apply a serde `rename_all` rule to a variant name (PascalCase), like serde does
+fn rename_variant(name: &str, rule: Option<&str>) -> String {
+    let name = name.trim_start_matches("r#");
+    let snake = || {
+        let mut out = String::new();
+        for (i, c) in name.chars().enumerate() {
+            if c.is_uppercase() && i > 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        }
+        out
+    };
+    match rule {
+        Some("lowercase") => name.to_lowercase(),
+        Some("UPPERCASE") => name.to_uppercase(),
+        Some("camelCase") => {
+            let mut chars = name.chars();
+            chars
+                .next()
+                .map(|first| first.to_lowercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+        Some("snake_case") => snake(),
+        Some("SCREAMING_SNAKE_CASE") => snake().to_uppercase(),
+        Some("kebab-case") => snake().replace('_', "-"),
+        Some("SCREAMING-KEBAB-CASE") => snake().replace('_', "-").to_uppercase(),
+        _ => name.to_string(),
+    }
+}
+
+// =============================================================================
+// SCHEMAS
+// =============================================================================
+
+enum Definition {
+    Struct(ItemStruct),
+    Enum(ItemEnum),
+}
+
+/// Serde types of every module, and the schemas generated so far
+struct Schemas {
+    /// Type name -> (module, definition)
+    types: HashMap<String, Vec<(String, Definition)>>,
+    /// Type name -> module it is imported from in server.rs
+    imports: HashMap<String, String>,
+    /// Generated schemas by key
+    generated: BTreeMap<String, Value>,
+    /// Schemas referenced but not generated yet (key, module, name)
+    pending: VecDeque<(String, String, String)>,
+    queued: BTreeSet<String>,
+}
+
+fn integer(format: &str, unsigned: bool) -> Value {
+    let mut schema = json!({ "type": "integer", "format": format });
+    if unsigned {
+        schema["minimum"] = json!(0);
+    }
+    schema
+}
+
+fn nullable(schema: Value) -> Value {
+    match schema.get("type").and_then(Value::as_str) {
+        Some(ty) if schema.get("enum").is_none() => {
+            let mut schema = schema.clone();
+            schema["type"] = json!([ty, "null"]);
+            schema
+        }
+        _ if schema.as_object().is_some_and(|o| o.is_empty()) => schema,
+        _ => json!({ "anyOf": [schema, { "type": "null" }] }),
+    }
+}
+
+fn generic_args(args: &PathArguments) -> Vec<&Type> {
+    match args {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|a| match a {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Last path segment of a type, with its generic type arguments
+fn type_name(ty: &Type) -> Option<(String, Vec<&Type>)> {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|s| (s.ident.to_string(), generic_args(&s.arguments))),
+        Type::Reference(reference) => type_name(&reference.elem),
+        Type::Paren(paren) => type_name(&paren.elem),
+        Type::Group(group) => type_name(&group.elem),
+        _ => None,
+    }
+}
+
+impl Schemas {
+    fn new() -> Self {
+        Self {
+            types: HashMap::new(),
+            imports: HashMap::new(),
+            generated: BTreeMap::new(),
+            pending: VecDeque::new(),
+            queued: BTreeSet::new(),
+        }
+    }
+
+    fn collect(&mut self, module: &str, items: &[Item]) {
+        for item in items {
+            let (name, definition) = match item {
+                Item::Struct(s) if derives_serde(&s.attrs) => (s.ident.to_string(), Definition::Struct(s.clone())),
+                Item::Enum(e) if derives_serde(&e.attrs) => (e.ident.to_string(), Definition::Enum(e.clone())),
+                _ => continue,
+            };
+            self.types
+                .entry(name)
+                .or_default()
+                .push((module.to_string(), definition));
+        }
+    }
+
+    /// Module of the definition a name in `module` refers to
+    fn resolve(&self, name: &str, module: &str, hint: Option<&str>) -> Option<String> {
+        let candidates = self.types.get(name)?;
+        let preferred = [hint, Some(module), self.imports.get(name).map(String::as_str)];
+        preferred
+            .iter()
+            .flatten()
+            .find_map(|m| candidates.iter().find(|(c, _)| c == m))
+            .or_else(|| candidates.first())
+            .map(|(m, _)| m.clone())
+    }
+
+    /// Schema key of a type (prefixed with its module when the name isn't unique)
+    fn key(&self, name: &str, module: &str) -> String {
+        if self.types.get(name).map_or(0, Vec::len) > 1 {
+            format!("{}{}", rename_field(module, Some("PascalCase")), name)
+        } else {
+            name.to_string()
+        }
+    }
+
+    fn reference(&mut self, name: &str, module: &str, hint: Option<&str>) -> Option<Value> {
+        if name == "Error" && hint.map_or(module == "server", |h| h == "error") {
+            return Some(json!({ "$ref": "#/components/schemas/Error" }));
+        }
+        let module = self.resolve(name, module, hint)?;
+        let key = self.key(name, &module);
+        if self.queued.insert(key.clone()) {
+            self.pending.push_back((key.clone(), module, name.to_string()));
+        }
+        Some(json!({ "$ref": format!("#/components/schemas/{}", key) }))
+    }
+
+    /// Schema of a Rust type used in `module`
+    fn schema(&mut self, ty: &Type, module: &str) -> Value {
+        match ty {
+            Type::Reference(reference) => return self.schema(&reference.elem, module),
+            Type::Paren(paren) => return self.schema(&paren.elem, module),
+            Type::Group(group) => return self.schema(&group.elem, module),
+            Type::Slice(slice) => return json!({ "type": "array", "items": self.schema(&slice.elem, module) }),
+            Type::Array(array) => return json!({ "type": "array", "items": self.schema(&array.elem, module) }),
+            Type::Tuple(tuple) if tuple.elems.is_empty() => return json!({ "type": "null" }),
+            Type::Tuple(tuple) => {
+                let items: Vec<Value> = tuple.elems.iter().map(|t| self.schema(t, module)).collect();
+                return json!({ "type": "array", "prefixItems": items, "minItems": items.len(), "maxItems": items.len() });
+            }
+            _ => {}
+        }
+
+        let Type::Path(path) = ty else {
+            return json!({});
+        };
+        let Some(last) = path.path.segments.last() else {
+            return json!({});
+        };
+        let args = generic_args(&last.arguments);
+        let hint = path
+            .path
+            .segments
+            .iter()
+            .rev()
+            .nth(1)
+            .map(|s| s.ident.to_string())
+            .filter(|m| !matches!(m.as_str(), "crate" | "self" | "super" | "std" | "serde_json"));
+        let first = |schemas: &mut Self| args.first().map_or(json!({}), |t| schemas.schema(t, module));
+
+        match last.ident.to_string().as_str() {
+            "String" | "str" | "char" | "PathBuf" | "Path" | "OsString" | "IpAddr" | "Ipv4Addr" | "Ipv6Addr"
+            | "SocketAddr" => json!({ "type": "string" }),
+            "Cow" => args.iter().find_map(|t| Some(self.schema(t, module))).unwrap_or(json!({ "type": "string" })),
+            "Url" => json!({ "type": "string", "format": "uri" }),
+            "NaiveDate" => json!({ "type": "string", "format": "date" }),
+            "DateTime" => json!({ "type": "string", "format": "date-time" }),
+            "bool" => json!({ "type": "boolean" }),
+            "u8" | "u16" | "u32" => integer("int32", true),
+            "i8" | "i16" | "i32" => integer("int32", false),
+            "u64" | "u128" | "usize" => integer("int64", true),
+            "i64" | "i128" | "isize" => integer("int64", false),
+            "f32" => json!({ "type": "number", "format": "float" }),
+            "f64" => json!({ "type": "number", "format": "double" }),
+            "Value" | "Duration" | "SystemTime" | "Instant" => json!({}),
+            "Option" => {
+                let inner = first(self);
+                nullable(inner)
+            }
+            "Box" | "Arc" | "Rc" | "Json" => first(self),
+            "Vec" | "VecDeque" | "HashSet" | "BTreeSet" | "IndexSet" | "SmallVec" => {
+                let items = first(self);
+                json!({ "type": "array", "items": items })
+            }
+            "HashMap" | "BTreeMap" | "IndexMap" => {
+                let values = args.get(1).map_or(json!({}), |t| self.schema(t, module));
+                json!({ "type": "object", "additionalProperties": values })
+            }
+            name => self.reference(name, module, hint.as_deref()).unwrap_or(json!({})),
+        }
+    }
+
+    /// Object schema of named fields; flattened fields are returned separately
+    fn fields_schema(&mut self, fields: &syn::FieldsNamed, module: &str, container: &SerdeAttrs) -> Value {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        let mut flattened = Vec::new();
+        for field in &fields.named {
+            let attrs = serde_attrs(&field.attrs);
+            if attrs.skip {
+                continue;
+            }
+            let mut schema = self.schema(&field.ty, module);
+            if attrs.flatten {
+                flattened.push(schema);
+                continue;
+            }
+            let name = attrs.rename.clone().unwrap_or_else(|| {
+                let ident = field.ident.as_ref().map(|i| i.to_string()).unwrap_or_default();
+                rename_field(&ident, container.rename_all.as_deref())
+            });
+            let optional = type_name(&field.ty).is_some_and(|(n, _)| n == "Option");
+            if !optional && !attrs.default && !container.default && !attrs.skip_serializing_if {
+                required.push(json!(name));
+            }
+            if let Some(description) = doc_text(&field.attrs) {
+                schema = with_description(schema, description);
+            }
+            properties.insert(name, schema);
+        }
+
+        let mut object = json!({ "type": "object", "properties": properties });
+        if !required.is_empty() {
+            object["required"] = json!(required);
+        }
+        if flattened.is_empty() {
+            object
+        } else {
+            flattened.insert(0, object);
+            json!({ "allOf": flattened })
+        }
+    }
+
+    fn unnamed_schema(&mut self, fields: &syn::FieldsUnnamed, module: &str) -> Value {
+        let items: Vec<Value> = fields
+            .unnamed
+            .iter()
+            .filter(|f| !serde_attrs(&f.attrs).skip)
+            .map(|f| self.schema(&f.ty, module))
+            .collect();
+        match items.len() {
+            1 => items.into_iter().next().unwrap_or(json!({})),
+            n => json!({ "type": "array", "prefixItems": items, "minItems": n, "maxItems": n }),
+        }
+    }
+
+    fn struct_schema(&mut self, item: &ItemStruct, module: &str) -> Value {
+        let attrs = serde_attrs(&item.attrs);
+        let schema = match &item.fields {
+            Fields::Named(fields) if attrs.transparent => fields
+                .named
+                .first()
+                .map_or(json!({}), |f| self.schema(&f.ty, module)),
+            Fields::Named(fields) => self.fields_schema(fields, module, &attrs),
+            Fields::Unnamed(fields) => self.unnamed_schema(fields, module),
+            Fields::Unit => json!({ "type": "null" }),
+        };
+        match doc_text(&item.attrs) {
+            Some(description) => with_description(schema, description),
+            None => schema,
+        }
+    }
+
+    fn enum_schema(&mut self, item: &ItemEnum, module: &str) -> Value {
+        let attrs = serde_attrs(&item.attrs);
+        let variants: Vec<_> = item
+            .variants
+            .iter()
+            .filter(|v| !serde_attrs(&v.attrs).skip)
+            .collect();
+        let names: Vec<String> = variants
+            .iter()
+            .map(|v| {
+                serde_attrs(&v.attrs)
+                    .rename
+                    .unwrap_or_else(|| rename_variant(&v.ident.to_string(), attrs.rename_all.as_deref()))
+            })
+            .collect();
+
+        let all_unit = variants.iter().all(|v| matches!(v.fields, Fields::Unit));
+        let schema = if all_unit && attrs.tag.is_none() && !attrs.untagged {
+            json!({ "type": "string", "enum": names })
+        } else {
+            let no_rename = SerdeAttrs::default();
+            let options: Vec<Value> = variants
+                .iter()
+                .zip(&names)
+                .map(|(variant, name)| {
+                    let content = match &variant.fields {
+                        Fields::Named(fields) => self.fields_schema(fields, module, &no_rename),
+                        Fields::Unnamed(fields) => self.unnamed_schema(fields, module),
+                        Fields::Unit => json!({ "type": "null" }),
+                    };
+                    let tag = |key: &str| {
+                        json!({
+                            "type": "object",
+                            "properties": { key: { "const": name } },
+                            "required": [key],
+                        })
+                    };
+                    let option = match (&attrs.tag, &attrs.content) {
+                        _ if attrs.untagged => content,
+                        (Some(tag_key), Some(content_key)) => {
+                            let mut object = tag(tag_key);
+                            if !matches!(variant.fields, Fields::Unit) {
+                                object["properties"][content_key] = content;
+                                object["required"] = json!([tag_key, content_key]);
+                            }
+                            object
+                        }
+                        (Some(tag_key), None) => match &variant.fields {
+                            Fields::Unit => tag(tag_key),
+                            _ => json!({ "allOf": [tag(tag_key), content] }),
+                        },
+                        _ => match &variant.fields {
+                            Fields::Unit => json!({ "type": "string", "const": name }),
+                            _ => json!({
+                                "type": "object",
+                                "properties": { name.as_str(): content },
+                                "required": [name],
+                            }),
+                        },
+                    };
+                    match doc_text(&variant.attrs) {
+                        Some(description) => with_description(option, description),
+                        None => option,
+                    }
+                })
+                .collect();
+            json!({ "oneOf": options })
+        };
+        match doc_text(&item.attrs) {
+            Some(description) => with_description(schema, description),
+            None => schema,
+        }
+    }
+
+    /// Generate every schema referenced so far
+    fn generate_pending(&mut self) {
+        while let Some((key, module, name)) = self.pending.pop_front() {
+            let schema = match self
+                .types
+                .get(&name)
+                .and_then(|defs| defs.iter().find(|(m, _)| *m == module))
+            {
+                Some((_, Definition::Struct(item))) => {
+                    let item = item.clone();
+                    self.struct_schema(&item, &module)
+                }
+                Some((_, Definition::Enum(item))) => {
+                    let item = item.clone();
+                    self.enum_schema(&item, &module)
+                }
+                None => json!({}),
+            };
+            self.generated.insert(key, schema);
+        }
+    }
+}
+
+fn with_description(schema: Value, description: String) -> Value {
+    if schema.get("$ref").is_some() {
+        // Siblings of $ref are allowed in OpenAPI 3.1
+        let mut schema = schema;
+        schema["description"] = json!(description);
+        return schema;
+    }
+    match schema {
+        Value::Object(mut object) => {
+            object.entry("description").or_insert(json!(description));
+            Value::Object(object)
+        }
+        other => other,
+    }
+}
+
+// =============================================================================
+// ROUTES
+// =============================================================================
+
+/// `.route(path, get(a).post(b))` calls of the router, in source order
+#[derive(Default)]
+struct RouteVisitor {
+    routes: Vec<(String, Vec<(String, String)>)>,
+}
+
+/// (method, handler) pairs of a method router expression
+fn method_handlers(expr: &Expr, out: &mut Vec<(String, String)>) {
+    let handler = |args: &syn::punctuated::Punctuated<Expr, syn::Token![,]>| match args.first() {
+        Some(Expr::Path(path)) => path.path.segments.last().map(|s| s.ident.to_string()),
+        _ => None,
+    };
+    match expr {
+        Expr::MethodCall(call) => {
+            method_handlers(&call.receiver, out);
+            let method = call.method.to_string();
+            if METHODS.contains(&method.as_str()) {
+                if let Some(handler) = handler(&call.args) {
+                    out.push((method, handler));
+                }
+            }
+        }
+        Expr::Call(call) => {
+            let Expr::Path(func) = call.func.as_ref() else {
+                return;
+            };
+            let Some(method) = func.path.segments.last().map(|s| s.ident.to_string()) else {
+                return;
+            };
+            if METHODS.contains(&method.as_str()) {
+                if let Some(handler) = handler(&call.args) {
+                    out.push((method, handler));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+impl<'ast> Visit<'ast> for RouteVisitor {
+    fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+        // Nested routers are dev-only (e.g., /api/debug)
+        if call.method == "nest" {
+            self.visit_expr(&call.receiver);
+            return;
+        }
+        visit::visit_expr_method_call(self, call);
+        if call.method == "route" {
+            if let (Some(Expr::Lit(lit)), Some(handlers)) = (call.args.first(), call.args.get(1)) {
+                if let Lit::Str(path) = &lit.lit {
+                    let mut methods = Vec::new();
+                    method_handlers(handlers, &mut methods);
+                    self.routes.push((path.value(), methods));
+                }
+            }
+        }
+    }
+}
+
+/// OpenAPI path template and its parameter names (`/a/:b/*c` -> `/a/{b}/{c}`)
+fn path_template(path: &str) -> (String, Vec<String>) {
+    let mut names = Vec::new();
+    let template = path
+        .split('/')
+        .map(|segment| {
+            let name = segment
+                .strip_prefix(':')
+                .or_else(|| segment.strip_prefix('*'))
+                .or_else(|| segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')));
+            match name {
+                Some(name) => {
+                    names.push(name.to_string());
+                    format!("{{{}}}", name)
+                }
+                None => segment.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    (template, names)
+}
+
+/// Group of an endpoint: the segment after /api (e.g., "weather")
+fn tag(path: &str) -> String {
+    let mut segments = path.trim_start_matches('/').split('/');
+    match segments.next() {
+        Some("api") => segments.next().unwrap_or("api").to_string(),
+        Some(first) => first.to_string(),
+        None => "api".to_string(),
+    }
+}
+
+/// `Json<T>` inside a return type (e.g., `Result<Json<T>, Error>`), searching the success side
+fn find_json(ty: &Type) -> Option<&Type> {
+    let (name, args) = type_name(ty)?;
+    match name.as_str() {
+        "Json" => args.first().copied(),
+        "Result" => args.first().and_then(|t| find_json(t)),
+        _ => args.into_iter().find_map(find_json),
+    }
+}
+
+/// Summary and description from a handler doc comment ("GET /api/x - Summary")
+fn summary(attrs: &[Attribute]) -> (Option<String>, Option<String>) {
+    let Some(text) = doc_text(attrs) else {
+        return (None, None);
+    };
+    let mut paragraphs = text.splitn(2, "\n\n");
+    let first = paragraphs.next().unwrap_or_default();
+    let first = match first.split_once(" - ") {
+        Some((route, rest))
+            if route.split_whitespace().next().is_some_and(|m| {
+                METHODS.iter().any(|method| method.eq_ignore_ascii_case(m))
+            }) =>
+        {
+            rest
+        }
+        _ => first,
+    };
+    (Some(first.to_string()), paragraphs.next().map(str::to_string))
+}
+
+/// OpenAPI operation for a handler
+fn operation(
+    schemas: &mut Schemas,
+    handler: Option<&ItemFn>,
+    handler_name: &str,
+    template: &str,
+    params: &[String],
+    error_codes: bool,
+) -> Value {
+    let mut op = Map::new();
+    op.insert("operationId".into(), json!(handler_name));
+    op.insert("tags".into(), json!([tag(template)]));
+
+    let Some(handler) = handler else {
+        op.insert("responses".into(), json!({ "200": { "description": "Success" } }));
+        return Value::Object(op);
+    };
+    let (summary, description) = summary(&handler.attrs);
+    if let Some(summary) = summary {
+        op.insert("summary".into(), json!(summary));
+    }
+    if let Some(description) = description {
+        op.insert("description".into(), json!(description));
+    }
+
+    let mut parameters: Vec<Value> = Vec::new();
+    let mut path_types: Vec<Option<Value>> = vec![None; params.len()];
+    let mut websocket = false;
+    for input in &handler.sig.inputs {
+        let FnArg::Typed(arg) = input else {
+            continue;
+        };
+        let Some((extractor, args)) = type_name(&arg.ty) else {
+            continue;
+        };
+        let inner = args.first().copied();
+        match (extractor.as_str(), inner) {
+            ("Path", Some(Type::Tuple(tuple))) => {
+                for (slot, ty) in path_types.iter_mut().zip(&tuple.elems) {
+                    *slot = Some(schemas.schema(ty, "server"));
+                }
+            }
+            ("Path", Some(ty)) if params.len() == 1 => path_types[0] = Some(schemas.schema(ty, "server")),
+            ("Query", Some(ty)) => {
+                let Some((name, _)) = type_name(ty) else {
+                    continue;
+                };
+                let Some(module) = schemas.resolve(&name, "server", None) else {
+                    continue;
+                };
+                let Some(Definition::Struct(item)) = schemas
+                    .types
+                    .get(&name)
+                    .and_then(|defs| defs.iter().find(|(m, _)| *m == module))
+                    .map(|(_, d)| d)
+                else {
+                    continue;
+                };
+                let item = item.clone();
+                let Fields::Named(fields) = &item.fields else {
+                    continue;
+                };
+                let container = serde_attrs(&item.attrs);
+                let object = schemas.fields_schema(fields, &module, &container);
+                let required: BTreeSet<String> = object["required"]
+                    .as_array()
+                    .map(|r| r.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+                    .unwrap_or_default();
+                if let Some(properties) = object["properties"].as_object() {
+                    for (name, schema) in properties {
+                        let mut param = json!({
+                            "name": name,
+                            "in": "query",
+                            "required": required.contains(name),
+                            "schema": schema,
+                        });
+                        if let Some(description) = schema.get("description") {
+                            param["description"] = description.clone();
+                        }
+                        parameters.push(param);
+                    }
+                }
+            }
+            ("Json", Some(ty)) => {
+                let schema = schemas.schema(ty, "server");
+                op.insert(
+                    "requestBody".into(),
+                    json!({ "required": true, "content": { "application/json": { "schema": schema } } }),
+                );
+            }
+            ("Option", Some(ty)) if type_name(ty).is_some_and(|(n, _)| n == "Json") => {
+                let schema = find_json(ty).map_or(json!({}), |t| schemas.schema(t, "server"));
+                op.insert(
+                    "requestBody".into(),
+                    json!({ "required": false, "content": { "application/json": { "schema": schema } } }),
+                );
+            }
+            ("Bytes" | "Body", _) => {
+                op.insert(
+                    "requestBody".into(),
+                    json!({ "content": { "application/octet-stream": { "schema": { "type": "string", "format": "binary" } } } }),
+                );
+            }
+            ("Multipart", _) => {
+                op.insert(
+                    "requestBody".into(),
+                    json!({ "content": { "multipart/form-data": { "schema": { "type": "object" } } } }),
+                );
+            }
+            ("WebSocketUpgrade", _) => websocket = true,
+            _ => {}
+        }
+    }
+    for (name, schema) in params.iter().zip(path_types) {
+        parameters.insert(
+            0,
+            json!({
+                "name": name,
+                "in": "path",
+                "required": true,
+                "schema": schema.unwrap_or(json!({ "type": "string" })),
+            }),
+        );
+    }
+    if !parameters.is_empty() {
+        op.insert("parameters".into(), json!(parameters));
+    }
+
+    let mut responses = Map::new();
+    let output = match &handler.sig.output {
+        ReturnType::Type(_, ty) => Some(ty.as_ref()),
+        ReturnType::Default => None,
+    };
+    if websocket {
+        responses.insert("101".into(), json!({ "description": "Switching Protocols (WebSocket)" }));
+    } else if let Some(body) = output.and_then(find_json) {
+        let schema = schemas.schema(body, "server");
+        responses.insert(
+            "200".into(),
+            json!({ "description": "Success", "content": { "application/json": { "schema": schema } } }),
+        );
+    } else {
+        let ok = output.and_then(|ty| match type_name(ty) {
+            Some((name, args)) if name == "Result" => args.first().and_then(|t| type_name(t)).map(|(n, _)| n),
+            other => other.map(|(n, _)| n),
+        });
+        let response = match ok.as_deref() {
+            Some("StatusCode") | None => json!({ "description": "Success (no body)" }),
+            Some("Html") => json!({ "description": "HTML page", "content": { "text/html": {} } }),
+            Some("Sse") => json!({ "description": "Event stream", "content": { "text/event-stream": {} } }),
+            Some("String") => json!({ "description": "Success", "content": { "text/plain": { "schema": { "type": "string" } } } }),
+            _ if template.contains('{') && template.ends_with('}') => json!({
+                "description": "File contents",
+                "content": { "application/octet-stream": { "schema": { "type": "string", "format": "binary" } } },
+            }),
+            _ => json!({ "description": "Success" }),
+        };
+        responses.insert("200".into(), response);
+    }
+
+    let error = output.and_then(|ty| match type_name(ty) {
+        Some((name, args)) if name == "Result" => args.get(1).copied(),
+        _ => None,
+    });
+    if let Some(error) = error {
+        let typed = error_codes && type_name(error).is_some_and(|(n, _)| n == "Error");
+        let response = if typed {
+            json!({ "$ref": "#/components/responses/Error" })
+        } else {
+            json!({ "description": "Error", "content": { "text/plain": { "schema": { "type": "string" } } } })
+        };
+        responses.insert("default".into(), response);
+    }
+    op.insert("responses".into(), Value::Object(responses));
+    Value::Object(op)
+}
+
+/// Codes returned by `Error::code()` in error.rs
+fn error_codes(file: &syn::File) -> Vec<String> {
+    #[derive(Default)]
+    struct Strings(Vec<String>);
+    impl<'ast> Visit<'ast> for Strings {
+        fn visit_lit_str(&mut self, lit: &'ast LitStr) {
+            self.0.push(lit.value());
+        }
+    }
+
+    let mut codes = Strings::default();
+    for item in &file.items {
+        let Item::Impl(block) = item else {
+            continue;
+        };
+        for item in &block.items {
+            if let ImplItem::Fn(f) = item {
+                if f.sig.ident == "code" {
+                    codes.visit_block(&f.block);
+                }
+            }
+        }
+    }
+    codes.0
+}
+
+fn parse(path: &Path) -> Result<syn::File, String> {
+    let source = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    syn::parse_file(&source).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Build the OpenAPI document from the crate sources in `src`
+pub fn generate(src: &Path, version: &str) -> Result<Value, String> {
+    let mut schemas = Schemas::new();
+    let mut entries: Vec<_> = fs::read_dir(src)
+        .map_err(|e| e.to_string())?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "rs"))
+        .collect();
+    entries.sort();
+    for path in &entries {
+        let module = path.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+        let module = if module == "lib" { "crate" } else { module };
+        schemas.collect(module, &parse(path)?.items);
+    }
+
+    let server = parse(&src.join("server.rs"))?;
+    for item in &server.items {
+        let Item::Use(item) = item else {
+            continue;
+        };
+        // use crate::module::{self, TypeA, TypeB};
+        let mut tree = &item.tree;
+        let mut module = None;
+        loop {
+            match tree {
+                syn::UseTree::Path(path) => {
+                    if path.ident != "crate" {
+                        module = Some(path.ident.to_string());
+                    }
+                    tree = &*path.tree;
+                }
+                syn::UseTree::Group(group) => {
+                    for tree in &group.items {
+                        if let (syn::UseTree::Name(name), Some(module)) = (tree, &module) {
+                            schemas.imports.insert(name.ident.to_string(), module.clone());
+                        }
+                    }
+                    break;
+                }
+                syn::UseTree::Name(name) => {
+                    if let Some(module) = &module {
+                        schemas.imports.insert(name.ident.to_string(), module.clone());
+                    }
+                    break;
+                }
+                _ => break,
+            }
+        }
+    }
+
+    let handlers: HashMap<String, &ItemFn> = server
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Fn(f) => Some((f.sig.ident.to_string(), f)),
+            _ => None,
+        })
+        .collect();
+    let router = handlers
+        .get("create_router")
+        .ok_or("create_router not found in server.rs")?;
+    let mut visitor = RouteVisitor::default();
+    visitor.visit_block(&router.block);
+
+    let codes = error_codes(&parse(&src.join("error.rs"))?);
+    let mut paths: BTreeMap<String, Map<String, Value>> = BTreeMap::new();
+    for (route, methods) in &visitor.routes {
+        let (template, params) = path_template(route);
+        for (method, handler) in methods {
+            let op = operation(
+                &mut schemas,
+                handlers.get(handler).copied(),
+                handler,
+                &template,
+                &params,
+                !codes.is_empty(),
+            );
+            paths.entry(template.clone()).or_default().insert(method.clone(), op);
+        }
+    }
+    schemas.generate_pending();
+
+    let tags: BTreeSet<String> = paths.keys().map(|p| tag(p)).collect();
+    let mut components = Map::new();
+    let mut all_schemas = Map::new();
+    all_schemas.insert(
+        "Error".into(),
+        json!({
+            "type": "object",
+            "description": "Error body of typed endpoints; the status code matches the kind (400 invalidInput, 404 notFound, 409 conflict, ...)",
+            "properties": {
+                "code": { "type": "string", "enum": codes },
+                "message": { "type": "string" },
+            },
+            "required": ["code", "message"],
+        }),
+    );
+    all_schemas.extend(schemas.generated);
+    components.insert("schemas".into(), Value::Object(all_schemas));
+    components.insert(
+        "responses".into(),
+        json!({
+            "Error": {
+                "description": "Error",
+                "content": { "application/json": { "schema": { "$ref": "#/components/schemas/Error" } } },
+            },
+        }),
+    );
+    components.insert(
+        "securitySchemes".into(),
+        json!({
+            "bearerAuth": { "type": "http", "scheme": "bearer", "description": "Remote access auth token (Settings → Server)" },
+            "tokenQuery": { "type": "apiKey", "in": "query", "name": "token", "description": "Auth token as a query parameter (WebSockets, browser pages)" },
+        }),
+    );
+
+    Ok(json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "TowerCab 3D API",
+            "version": version,
+            "description": "HTTP and WebSocket API of the TowerCab 3D remote access server. \
+                Generated from the server routes and their request and response types. \
+                When an auth token is set, send it as `Authorization: Bearer <token>` \
+                (or `?token=` for WebSockets and pages).",
+        },
+        "servers": [{ "url": "/" }],
+        "security": [{ "bearerAuth": [] }, { "tokenQuery": [] }, {}],
+        "tags": tags.iter().map(|t| json!({ "name": t })).collect::<Vec<_>>(),
+        "paths": paths,
+        "components": components,
+    }))
+}
@@ -0,0 +1,54 @@
+// Shared helpers for the OBS overlay pages (served at /obs/*)
+//
+// Options come from the page URL, e.g. /obs/arrivals?token=...&scale=1.5:
+//   token  remote access auth token, if one is set in Settings -> Server
+//   scale  text size multiplier (default 1)
+//   color  text color (CSS color, default #7cfc9c)
+
+const params = new URLSearchParams(location.search)
+const token = params.get('token')
+
+document.documentElement.style.setProperty('--scale', params.get('scale') || '1')
+if (params.get('color')) document.documentElement.style.setProperty('--color', params.get('color'))
+
+/** GET a JSON API endpoint (null on 404) */
+async function apiFetch(path) {
+  const headers = token ? { Authorization: `Bearer ${token}` } : {}
+  const response = await fetch(path, { headers })
+  if (response.status === 404) return null
+  if (!response.ok) throw new Error(`${path}: ${response.status} ${await response.text()}`)
+  return response.json()
+}
+
+/** Call `load` now and every `intervalMs`, keeping the last result on errors */
+function poll(load, intervalMs) {
+  const run = () => load().catch((error) => console.error('[Overlay]', error))
+  run()
+  setInterval(run, intervalMs)
+}
+
+/** Subscribe to a WebSocket endpoint, reconnecting after a few seconds if it drops */
+function subscribe(path, onMessage) {
+  const url = new URL(path, location.href)
+  url.protocol = location.protocol === 'https:' ? 'wss:' : 'ws:'
+  // Browsers can't set headers on WebSockets, so the token goes in the query
+  if (token) url.searchParams.set('token', token)
+
+  const connect = () => {
+    const socket = new WebSocket(url)
+    socket.onmessage = (event) => onMessage(JSON.parse(event.data))
+    socket.onclose = () => setTimeout(connect, 3000)
+  }
+  connect()
+}
+
+/** "FL350" above the transition altitude (default 18000 ft), "4500" below */
+function formatAltitude(feet) {
+  const transition = Number(params.get('transition') || 18000)
+  const rounded = Math.round(feet / 100)
+  return feet >= transition ? `FL${String(rounded).padStart(3, '0')}` : String(rounded * 100)
+}
+
+function escapeHtml(text) {
+  return String(text ?? '').replace(/[&<>"']/g, (c) => `&#${c.charCodeAt(0)};`)
+}
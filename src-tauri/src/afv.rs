@@ -0,0 +1,405 @@
+//! Audio-for-VATSIM frequency activity
+//!
+//! Shows which frequencies at the active airport currently carry a
+//! transmission. The frequencies are those of the positions covering the
+//! airport (vatspy.rs); the VATSIM transceivers feed maps every connected
+//! callsign to the frequencies it is tuned to, and the AFV server lists the
+//! callsigns transmitting right now. A frequency is "transmitting" while any
+//! of those callsigns has a transceiver on it.
+//!
+//! Activity is published on change to the desktop UI (`frequency-activity`
+//! event) and remote browsers (`/api/afv/ws`) so they can blink a TX
+//! indicator per frequency. The AFV server is only polled while someone is
+//! watching: a remote WebSocket client, or the desktop UI after
+//! `watch_frequency_activity`.
+//!
+//! The same feed tells which position each aircraft is talking to: the first
+//! of its transceivers tuned to a covering position's frequency (ATIS aside).
+//! The traffic picture carries it per aircraft as `onFrequency`.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use crate::debug_stats;
+use crate::http_client;
+use crate::supervisor::{self, Restart};
+use crate::vatspy::{self, CoveringPosition};
+use crate::weather;
+
+/// Frequencies every connected callsign is tuned to
+const TRANSCEIVERS_URL: &str = "https://data.vatsim.net/v3/transceivers-data.json";
+
+/// Callsigns transmitting right now
+const TRANSMITTING_URL: &str = "https://voice1.vatsim.uk/api/v1/network/online/callsigns";
+
+/// How often transmissions are checked while someone is watching
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How long the transceivers feed and the airport's positions are reused
+const TRANSCEIVERS_MAX_AGE: Duration = Duration::from_secs(30);
+
+/// Transceiver frequencies within this many Hz of a position's frequency are on it
+/// (less than half the 8.33 kHz channel spacing)
+const FREQUENCY_TOLERANCE_HZ: u64 = 2_500;
+
+/// Transmission activity on one of the airport's frequencies
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrequencyActivity {
+    /// MHz as published by the controller (e.g., "118.250")
+    pub frequency: String,
+    /// Controller positions on the frequency
+    pub positions: Vec<String>,
+    pub transmitting: bool,
+    /// Callsigns transmitting on the frequency
+    pub transmitters: Vec<String>,
+}
+
+/// Controller position an aircraft is tuned to
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrequencyContact {
+    /// Position callsign (e.g., "BOS_TWR")
+    pub callsign: String,
+    /// MHz as published by the controller (e.g., "128.800")
+    pub frequency: String,
+    /// "delivery", "ground", "tower", "approach", "center" or "fss"
+    pub level: String,
+}
+
+/// Transmission activity at the active airport
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FrequencyActivitySnapshot {
+    pub icao: Option<String>,
+    /// Unix ms
+    pub updated_at: Option<u64>,
+    pub frequencies: Vec<FrequencyActivity>,
+}
+
+#[derive(Deserialize)]
+struct TransceiverEntry {
+    callsign: String,
+    #[serde(default)]
+    transceivers: Vec<Transceiver>,
+}
+
+#[derive(Deserialize)]
+struct Transceiver {
+    /// Hz
+    frequency: u64,
+}
+
+/// Frequencies (Hz) by callsign
+pub type TunedFrequencies = HashMap<String, Vec<u64>>;
+
+/// AFV state (managed by Tauri)
+pub struct AfvState {
+    snapshot: RwLock<FrequencyActivitySnapshot>,
+    /// Transceivers feed and the airport's positions, with when they were fetched
+    cache: RwLock<Option<(Instant, String, TunedFrequencies, Vec<CoveringPosition>)>>,
+    /// The desktop UI wants activity updates
+    desktop_watching: AtomicBool,
+    /// Activity updates for WebSocket relay
+    pub events: broadcast::Sender<FrequencyActivitySnapshot>,
+}
+
+impl AfvState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            snapshot: RwLock::new(FrequencyActivitySnapshot::default()),
+            cache: RwLock::new(None),
+            desktop_watching: AtomicBool::new(false),
+            events,
+        }
+    }
+}
+
+impl Default for AfvState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Published frequency in Hz ("118.250" -> 118250000)
+pub fn frequency_hz(frequency: &str) -> Option<u64> {
+    let mhz: f64 = frequency.trim().parse().ok()?;
+    (mhz > 0.0).then(|| (mhz * 1_000_000.0).round() as u64)
+}
+
+/// Callsigns from the AFV transmitting list (plain strings or objects with a callsign)
+fn parse_transmitting(value: &Value) -> HashSet<String> {
+    value
+        .as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| entry.as_str().or_else(|| entry.get("callsign").and_then(Value::as_str)))
+                .map(str::to_uppercase)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Activity on the positions' frequencies, in frequency order
+pub fn activity(
+    positions: &[CoveringPosition],
+    tuned: &TunedFrequencies,
+    transmitting: &HashSet<String>,
+) -> Vec<FrequencyActivity> {
+    let mut by_frequency: BTreeMap<u64, FrequencyActivity> = BTreeMap::new();
+    for position in positions {
+        let Some(hz) = frequency_hz(&position.frequency) else {
+            continue;
+        };
+        by_frequency
+            .entry(hz)
+            .or_insert_with(|| FrequencyActivity {
+                frequency: position.frequency.clone(),
+                positions: Vec::new(),
+                transmitting: false,
+                transmitters: Vec::new(),
+            })
+            .positions
+            .push(position.callsign.clone());
+    }
+    for (hz, entry) in by_frequency.iter_mut() {
+        let mut transmitters: Vec<String> = transmitting
+            .iter()
+            .filter(|callsign| {
+                tuned
+                    .get(*callsign)
+                    .is_some_and(|freqs| freqs.iter().any(|f| f.abs_diff(*hz) <= FREQUENCY_TOLERANCE_HZ))
+            })
+            .cloned()
+            .collect();
+        transmitters.sort();
+        entry.transmitting = !transmitters.is_empty();
+        entry.transmitters = transmitters;
+    }
+    by_frequency.into_values().collect()
+}
+
+/// Position an aircraft is talking to: the first of its transceiver frequencies
+/// (COM1 before COM2) that a covering position other than ATIS is on
+pub fn contact(frequencies: &[u64], positions: &[CoveringPosition]) -> Option<FrequencyContact> {
+    frequencies.iter().find_map(|hz| {
+        positions
+            .iter()
+            .filter(|p| p.level != "atis")
+            .find(|p| frequency_hz(&p.frequency).is_some_and(|f| f.abs_diff(*hz) <= FREQUENCY_TOLERANCE_HZ))
+            .map(|p| FrequencyContact {
+                callsign: p.callsign.clone(),
+                frequency: p.frequency.clone(),
+                level: p.level.clone(),
+            })
+    })
+}
+
+async fn fetch_json(url: &str) -> Result<Value, String> {
+    let response = http_client::client()
+        .get(url)
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP error {} for {}", response.status(), url));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse {}: {}", url, e))
+}
+
+/// Tuned frequencies and the airport's positions, refreshed when stale
+pub async fn tuned_and_positions(app: &AppHandle, icao: &str) -> Result<(TunedFrequencies, Vec<CoveringPosition>), String> {
+    if let Some((fetched, cached_icao, tuned, positions)) = app.state::<AfvState>().cache.read().as_ref() {
+        if cached_icao == icao && fetched.elapsed() < TRANSCEIVERS_MAX_AGE {
+            return Ok((tuned.clone(), positions.clone()));
+        }
+    }
+    let entries: Vec<TransceiverEntry> = serde_json::from_value(fetch_json(TRANSCEIVERS_URL).await?)
+        .map_err(|e| format!("Failed to parse transceivers: {}", e))?;
+    let tuned: TunedFrequencies = entries
+        .into_iter()
+        .map(|e| (e.callsign.to_uppercase(), e.transceivers.iter().map(|t| t.frequency).collect()))
+        .collect();
+    let positions = vatspy::get_coverage(app, icao).await?.online;
+    *app.state::<AfvState>().cache.write() = Some((Instant::now(), icao.to_string(), tuned.clone(), positions.clone()));
+    Ok((tuned, positions))
+}
+
+fn publish(app: &AppHandle, snapshot: FrequencyActivitySnapshot) {
+    let state = app.state::<AfvState>();
+    {
+        let current = state.snapshot.read();
+        if current.icao == snapshot.icao && current.frequencies == snapshot.frequencies {
+            return;
+        }
+    }
+    let _ = app.emit("frequency-activity", &snapshot);
+    let _ = state.events.send(snapshot.clone());
+    *state.snapshot.write() = snapshot;
+}
+
+async fn poll(app: &AppHandle, icao: &str) -> Result<(), String> {
+    let (tuned, positions) = tuned_and_positions(app, icao).await?;
+    let transmitting = parse_transmitting(&fetch_json(TRANSMITTING_URL).await?);
+    publish(
+        app,
+        FrequencyActivitySnapshot {
+            icao: Some(icao.to_string()),
+            updated_at: Some(now_ms()),
+            frequencies: activity(&positions, &tuned, &transmitting),
+        },
+    );
+    Ok(())
+}
+
+/// Whether anyone wants activity updates
+fn watched(app: &AppHandle) -> bool {
+    let state = app.state::<AfvState>();
+    state.desktop_watching.load(Ordering::Relaxed) || state.events.receiver_count() > 0
+}
+
+/// Current activity at the active airport
+pub fn get_snapshot(app: &AppHandle) -> FrequencyActivitySnapshot {
+    app.state::<AfvState>().snapshot.read().clone()
+}
+
+/// Poll transmissions at the active airport while someone is watching
+pub fn start_afv_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn("afv", Restart::Always, move || {
+        let app = app.clone();
+        async move {
+            loop {
+                match weather::active_airport(&app).filter(|_| watched(&app)) {
+                    Some(icao) => {
+                        let result = poll(&app, &icao).await;
+                        if let Err(e) = &result {
+                            warn!("[Afv] {}", e);
+                        }
+                        debug_stats::task_ran("afv", POLL_INTERVAL, result.err());
+                    }
+                    None => publish(&app, FrequencyActivitySnapshot::default()),
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    });
+}
+
+/// Initialize AFV state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_afv(app: &AppHandle) {
+    app.manage(AfvState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the transmission activity on the active airport's frequencies
+#[tauri::command]
+pub fn get_frequency_activity(app: AppHandle) -> FrequencyActivitySnapshot {
+    get_snapshot(&app)
+}
+
+/// Start or stop `frequency-activity` updates for the desktop UI
+#[tauri::command]
+pub fn watch_frequency_activity(app: AppHandle, enabled: bool) {
+    app.state::<AfvState>().desktop_watching.store(enabled, Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(callsign: &str, frequency: &str) -> CoveringPosition {
+        CoveringPosition {
+            callsign: callsign.to_string(),
+            name: String::new(),
+            frequency: frequency.to_string(),
+            level: "tower".to_string(),
+            facility: "KBOS".to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_frequencies() {
+        assert_eq!(frequency_hz("118.250"), Some(118_250_000));
+        assert_eq!(frequency_hz("132.005"), Some(132_005_000));
+        assert_eq!(frequency_hz("199.998"), Some(199_998_000));
+        assert_eq!(frequency_hz(""), None);
+    }
+
+    #[test]
+    fn marks_frequencies_with_transmissions() {
+        let positions = [
+            position("BOS_TWR", "128.800"),
+            position("BOS_1_TWR", "128.800"),
+            position("BOS_GND", "121.900"),
+        ];
+        let tuned: TunedFrequencies = [
+            ("DAL123".to_string(), vec![128_800_000]),
+            ("N172SP".to_string(), vec![121_900_000]),
+        ]
+        .into_iter()
+        .collect();
+        let transmitting = parse_transmitting(&serde_json::json!(["dal123", {"callsign": "JBU1"}]));
+        assert!(transmitting.contains("JBU1"));
+
+        let activity = activity(&positions, &tuned, &transmitting);
+        assert_eq!(activity.len(), 2);
+        assert_eq!(activity[0].frequency, "121.900");
+        assert!(!activity[0].transmitting);
+        assert_eq!(activity[1].positions, vec!["BOS_TWR", "BOS_1_TWR"]);
+        assert!(activity[1].transmitting);
+        assert_eq!(activity[1].transmitters, vec!["DAL123"]);
+    }
+
+    #[test]
+    fn finds_the_position_an_aircraft_is_talking_to() {
+        let positions = [
+            CoveringPosition {
+                level: "atis".to_string(),
+                ..position("BOS_ATIS", "135.000")
+            },
+            CoveringPosition {
+                level: "ground".to_string(),
+                ..position("BOS_GND", "121.900")
+            },
+            position("BOS_TWR", "128.800"),
+        ];
+
+        // COM1 on tower (8.33 kHz rounding), COM2 on ground
+        let tower = contact(&[128_801_000, 121_900_000], &positions).unwrap();
+        assert_eq!(tower.callsign, "BOS_TWR");
+        assert_eq!(tower.level, "tower");
+
+        // Listening to the ATIS on COM1 doesn't count
+        assert_eq!(
+            contact(&[135_000_000, 121_900_000], &positions).map(|c| c.callsign),
+            Some("BOS_GND".to_string())
+        );
+        assert_eq!(contact(&[122_800_000], &positions), None);
+    }
+}
@@ -0,0 +1,322 @@
+//! Aircraft photo lookup
+//!
+//! Resolves a callsign to a registration and looks up a photo of that
+//! airframe on planespotters.net for the traffic "spotting card". The
+//! registration comes from the `REG/` item of the VATSIM flight plan remarks,
+//! or from the callsign itself when it is a registration (general aviation).
+//!
+//! Results, including "no photo", are cached per registration, and lookups
+//! are spaced out so clicking through traffic never hammers the photo API.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::error::{Error, Result};
+use crate::{http_client, traffic};
+
+const PHOTOS_URL: &str = "https://api.planespotters.net/pub/photos/reg";
+
+/// How long a found photo is served from cache
+const PHOTO_TTL: Duration = Duration::from_secs(24 * 3600);
+/// How long a registration without photos is remembered
+const NO_PHOTO_TTL: Duration = Duration::from_secs(3600);
+/// Minimum time between requests to the photo API
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Photo of an aircraft (credit the photographer and link back when shown)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AircraftPhoto {
+    pub callsign: String,
+    pub registration: String,
+    pub thumbnail_url: String,
+    pub large_url: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub photographer: String,
+    /// Photo page on the source site
+    pub link: String,
+    pub source: &'static str,
+}
+
+#[derive(Deserialize)]
+struct PhotosResponse {
+    #[serde(default)]
+    photos: Vec<ApiPhoto>,
+}
+
+#[derive(Deserialize)]
+struct ApiPhoto {
+    thumbnail: ApiImage,
+    thumbnail_large: Option<ApiImage>,
+    #[serde(default)]
+    link: String,
+    #[serde(default)]
+    photographer: String,
+}
+
+#[derive(Deserialize)]
+struct ApiImage {
+    src: String,
+    size: Option<ApiSize>,
+}
+
+#[derive(Deserialize)]
+struct ApiSize {
+    width: u32,
+    height: u32,
+}
+
+/// Photo of a registration without the callsign
+#[derive(Debug, Clone)]
+struct Photo {
+    thumbnail_url: String,
+    large_url: Option<String>,
+    size: Option<(u32, u32)>,
+    photographer: String,
+    link: String,
+}
+
+struct CachedPhoto {
+    photo: Option<Photo>,
+    fetched_at: Instant,
+}
+
+/// Photo lookup state managed by Tauri
+pub struct PhotoState {
+    photos: RwLock<HashMap<String, CachedPhoto>>,
+    /// Time of the last request to the photo API (held while requesting)
+    last_request: Mutex<Option<Instant>>,
+}
+
+impl PhotoState {
+    pub fn new() -> Self {
+        Self {
+            photos: RwLock::new(HashMap::new()),
+            last_request: Mutex::new(None),
+        }
+    }
+}
+
+impl Default for PhotoState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Registration from flight plan remarks ("... REG/GEUUA ...")
+pub fn registration_from_remarks(remarks: &str) -> Option<String> {
+    remarks
+        .split_whitespace()
+        .find_map(|item| item.strip_prefix("REG/"))
+        .map(|reg| reg.trim_matches(|c: char| !c.is_ascii_alphanumeric() && c != '-'))
+        .filter(|reg| (2..=10).contains(&reg.len()))
+        .map(str::to_uppercase)
+}
+
+/// Whether a registration can be looked up
+pub fn valid_registration(registration: &str) -> bool {
+    (2..=10).contains(&registration.len()) && registration.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// Whether a callsign is an airline flight number (three-letter designator,
+/// then a digit) rather than a registration
+fn is_airline_callsign(callsign: &str) -> bool {
+    let bytes = callsign.as_bytes();
+    bytes.len() > 3 && bytes[..3].iter().all(u8::is_ascii_alphabetic) && bytes[3].is_ascii_digit()
+}
+
+/// Registration to look up for a callsign
+fn registration_for(callsign: &str, remarks: Option<&str>) -> Option<String> {
+    remarks.and_then(registration_from_remarks).or_else(|| {
+        let callsign = callsign.to_uppercase();
+        let valid = valid_registration(&callsign)
+            && callsign.chars().any(|c| c.is_ascii_digit() || c == '-')
+            && !is_airline_callsign(&callsign);
+        valid.then_some(callsign)
+    })
+}
+
+fn parse_photo(response: PhotosResponse) -> Option<Photo> {
+    let photo = response.photos.into_iter().next()?;
+    Some(Photo {
+        size: photo.thumbnail.size.map(|s| (s.width, s.height)),
+        thumbnail_url: photo.thumbnail.src,
+        large_url: photo.thumbnail_large.map(|image| image.src),
+        photographer: photo.photographer,
+        link: photo.link,
+    })
+}
+
+async fn fetch_photo(registration: &str) -> Result<Option<Photo>> {
+    let response = http_client::client()
+        .get(format!("{}/{}", PHOTOS_URL, registration))
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to fetch aircraft photo: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(Error::Upstream(format!("HTTP error: {}", response.status())));
+    }
+    let response: PhotosResponse = response
+        .json()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to parse aircraft photo: {}", e)))?;
+    Ok(parse_photo(response))
+}
+
+fn cached(state: &PhotoState, registration: &str) -> Option<Option<Photo>> {
+    let photos = state.photos.read();
+    let cached = photos.get(registration)?;
+    let ttl = if cached.photo.is_some() {
+        PHOTO_TTL
+    } else {
+        NO_PHOTO_TTL
+    };
+    (cached.fetched_at.elapsed() < ttl).then(|| cached.photo.clone())
+}
+
+/// Photo of a registration, from cache or the photo API (rate limited)
+async fn photo_for_registration(app: &AppHandle, registration: &str) -> Result<Option<Photo>> {
+    let state = app.state::<PhotoState>();
+    if let Some(photo) = cached(&state, registration) {
+        return Ok(photo);
+    }
+
+    // One request at a time, spaced out; a waiting lookup may find its answer cached
+    let mut last_request = state.last_request.lock().await;
+    if let Some(photo) = cached(&state, registration) {
+        return Ok(photo);
+    }
+    if let Some(wait) = last_request.and_then(|at| MIN_REQUEST_INTERVAL.checked_sub(at.elapsed())) {
+        tokio::time::sleep(wait).await;
+    }
+    let result = fetch_photo(registration).await;
+    *last_request = Some(Instant::now());
+
+    match result {
+        Ok(photo) => {
+            state.photos.write().insert(
+                registration.to_string(),
+                CachedPhoto {
+                    photo: photo.clone(),
+                    fetched_at: Instant::now(),
+                },
+            );
+            Ok(photo)
+        }
+        Err(e) => {
+            warn!("[Photos] {}: {}", registration, e);
+            Err(e)
+        }
+    }
+}
+
+/// Photo of the aircraft flying a callsign (`registration` overrides the lookup).
+/// `Ok(None)` if the registration is unknown or has no photos.
+pub async fn get_photo(
+    app: &AppHandle,
+    callsign: &str,
+    registration: Option<&str>,
+) -> Result<Option<AircraftPhoto>> {
+    let callsign = callsign.trim().to_uppercase();
+    let registration = match registration.map(|r| r.trim().to_uppercase()).filter(|r| !r.is_empty()) {
+        Some(registration) => Some(registration),
+        None => {
+            let feed = traffic::vatsim_feed(app).await.ok();
+            let remarks = feed.as_ref().and_then(|feed| {
+                feed.pilots
+                    .iter()
+                    .find(|pilot| pilot.callsign.eq_ignore_ascii_case(&callsign))
+                    .and_then(|pilot| pilot.flight_plan.as_ref())
+                    .map(|plan| plan.remarks.clone())
+            });
+            registration_for(&callsign, remarks.as_deref())
+        }
+    };
+    let Some(registration) = registration else {
+        return Ok(None);
+    };
+    if !valid_registration(&registration) {
+        return Err(Error::invalid_input(format!("Invalid registration: {}", registration)));
+    }
+
+    let photo = photo_for_registration(app, &registration).await?;
+    Ok(photo.map(|photo| AircraftPhoto {
+        callsign,
+        registration,
+        thumbnail_url: photo.thumbnail_url,
+        large_url: photo.large_url,
+        width: photo.size.map(|(width, _)| width),
+        height: photo.size.map(|(_, height)| height),
+        photographer: photo.photographer,
+        link: photo.link,
+        source: "planespotters.net",
+    }))
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Get a photo of the aircraft flying a callsign (None if none is found)
+#[tauri::command]
+pub async fn get_aircraft_photo(
+    app: AppHandle,
+    callsign: String,
+    registration: Option<String>,
+) -> Result<Option<AircraftPhoto>> {
+    get_photo(&app, &callsign, registration.as_deref()).await
+}
+
+/// Initialize photo lookup state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_aircraft_photos(app: &AppHandle) {
+    app.manage(PhotoState::new());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_registrations() {
+        assert_eq!(
+            registration_from_remarks("PBN/A1B1 DOF/240101 REG/GEUUA EET/EGTT0010"),
+            Some("GEUUA".to_string())
+        );
+        assert_eq!(registration_from_remarks("/V/ SIMBRIEF"), None);
+
+        assert_eq!(
+            registration_for("BAW123", Some("REG/G-EUUA")),
+            Some("G-EUUA".to_string())
+        );
+        assert_eq!(registration_for("BAW123", None), None);
+        assert_eq!(registration_for("N172SP", None), Some("N172SP".to_string()));
+        assert_eq!(registration_for("G-ABCD", Some("/V/")), Some("G-ABCD".to_string()));
+        assert_eq!(registration_for("DLH4AB", None), None);
+    }
+
+    #[test]
+    fn parses_the_first_photo() {
+        let response: PhotosResponse = serde_json::from_str(
+            r#"{"photos":[{"id":"1","thumbnail":{"src":"https://t.plnspttrs.net/1_t.jpg","size":{"width":200,"height":133}},
+                "thumbnail_large":{"src":"https://t.plnspttrs.net/1_280.jpg","size":{"width":280,"height":186}},
+                "link":"https://www.planespotters.net/photo/1","photographer":"A. Spotter"}]}"#,
+        )
+        .unwrap();
+        let photo = parse_photo(response).unwrap();
+        assert_eq!(photo.thumbnail_url, "https://t.plnspttrs.net/1_t.jpg");
+        assert_eq!(photo.large_url.as_deref(), Some("https://t.plnspttrs.net/1_280.jpg"));
+        assert_eq!(photo.size, Some((200, 133)));
+        assert_eq!(photo.photographer, "A. Spotter");
+
+        assert!(parse_photo(serde_json::from_str(r#"{"photos":[]}"#).unwrap()).is_none());
+    }
+}
@@ -0,0 +1,123 @@
+//! Aircraft type performance database
+//!
+//! Wake category, typical approach speed and dimensions per ICAO type code,
+//! from the bundled aircraft-types.csv (generated from the FAA Aircraft
+//! Characteristics Database by scripts/convert-aircraft-data.py). Feeds the
+//! wake and spacing logic and is served at `/api/aircraft-types/:code` so
+//! clients can scale generic models to the real type.
+
+use std::collections::HashMap;
+use std::fs;
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tracing::warn;
+
+use crate::airport_db;
+use crate::runways;
+
+/// Performance and dimensions of an aircraft type
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AircraftType {
+    /// ICAO type designator (e.g., "B738")
+    pub icao: String,
+    pub manufacturer: Option<String>,
+    pub model: Option<String>,
+    /// ICAO wake category: "J" (Super), "H", "M" or "L"
+    pub wake_category: Option<String>,
+    /// Typical speed over the threshold (knots)
+    pub approach_speed_kt: Option<f64>,
+    /// Meters
+    pub wingspan_m: Option<f64>,
+    pub length_m: Option<f64>,
+}
+
+/// Aircraft type table (managed by Tauri)
+pub struct AircraftTypeState {
+    types: RwLock<HashMap<String, AircraftType>>,
+}
+
+/// Parse aircraft-types.csv ("icao,manufacturer,model,wtc,approach_speed_kt,wingspan_m,length_m")
+fn parse_types(text: &str) -> HashMap<String, AircraftType> {
+    text.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields = runways::parse_csv_line(line);
+            let text = |i: usize| fields.get(i).filter(|v| !v.is_empty()).cloned();
+            let number = |i: usize| fields.get(i).and_then(|v| v.parse::<f64>().ok());
+            let icao = text(0)?.to_uppercase();
+            Some((
+                icao.clone(),
+                AircraftType {
+                    icao,
+                    manufacturer: text(1),
+                    model: text(2),
+                    wake_category: text(3).filter(|c| ["L", "M", "H", "J"].contains(&c.as_str())),
+                    approach_speed_kt: number(4),
+                    wingspan_m: number(5),
+                    length_m: number(6),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Look up an aircraft type by ICAO code (case-insensitive)
+pub fn get(app: &AppHandle, code: &str) -> Option<AircraftType> {
+    app.state::<AircraftTypeState>()
+        .types
+        .read()
+        .get(&code.trim().to_uppercase())
+        .cloned()
+}
+
+/// Initialize the aircraft type table for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_aircraft_types(app: &AppHandle) {
+    let types = match airport_db::bundled_resource_path(app, "aircraft-types.csv")
+        .ok_or_else(|| "Bundled aircraft-types.csv not found".to_string())
+        .and_then(|path| fs::read_to_string(path).map_err(|e| format!("Failed to read aircraft-types.csv: {}", e)))
+    {
+        Ok(text) => parse_types(&text),
+        Err(e) => {
+            warn!("[AircraftTypes] {}", e);
+            HashMap::new()
+        }
+    };
+    app.manage(AircraftTypeState {
+        types: RwLock::new(types),
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get wake category, approach speed and dimensions of an aircraft type
+#[tauri::command]
+pub fn get_aircraft_type(app: AppHandle, code: String) -> Option<AircraftType> {
+    get(&app, &code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_type_database() {
+        let types = parse_types(
+            "icao,manufacturer,model,wtc,approach_speed_kt,wingspan_m,length_m\n\
+             A388,AIRBUS,Airbus A380-800,J,138,79.77,72.73\n\
+             c172,CESSNA,\"Cessna 172, Skyhawk\",L,,11.0,8.28\n\
+             ZZZZ,,,?,,,\n",
+        );
+        assert_eq!(types.len(), 3);
+        assert_eq!(types["A388"].wake_category.as_deref(), Some("J"));
+        assert_eq!(types["A388"].approach_speed_kt, Some(138.0));
+        assert_eq!(types["C172"].model.as_deref(), Some("Cessna 172, Skyhawk"));
+        assert_eq!(types["C172"].approach_speed_kt, None);
+        assert_eq!(types["ZZZZ"].wake_category, None);
+    }
+}
@@ -0,0 +1,203 @@
+//! Airline database
+//!
+//! Name, telephony designator and country per ICAO airline code, from the
+//! bundled airlines.csv. Served at `/api/airlines` and used to decode
+//! callsigns ("BAW123" -> "SPEEDBIRD 123") so datablocks and strips don't
+//! each have to ship their own airline list.
+
+use std::collections::HashMap;
+use std::fs;
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tracing::warn;
+
+use crate::airport_db;
+use crate::runways;
+
+/// Airline operating under an ICAO designator
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Airline {
+    /// ICAO airline designator (e.g., "BAW")
+    pub icao: String,
+    pub iata: Option<String>,
+    pub name: String,
+    /// Radio telephony designator (e.g., "SPEEDBIRD")
+    pub telephony: Option<String>,
+    pub country: Option<String>,
+}
+
+/// Callsign split into airline and flight number
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedCallsign {
+    pub callsign: String,
+    pub airline: Option<Airline>,
+    /// Flight number after the airline designator ("123" in "BAW123")
+    pub flight_number: Option<String>,
+    /// Callsign as spoken on frequency ("SPEEDBIRD 123"; the callsign itself for non-airline flights)
+    pub spoken: String,
+}
+
+/// Airline table (managed by Tauri)
+pub struct AirlineState {
+    airlines: RwLock<HashMap<String, Airline>>,
+}
+
+/// Parse airlines.csv ("icao,iata,name,telephony,country")
+fn parse_airlines(text: &str) -> HashMap<String, Airline> {
+    text.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields = runways::parse_csv_line(line);
+            let text = |i: usize| fields.get(i).filter(|v| !v.is_empty()).cloned();
+            let icao = text(0)?.to_uppercase();
+            if icao.len() != 3 {
+                return None;
+            }
+            Some((
+                icao.clone(),
+                Airline {
+                    icao,
+                    iata: text(1).map(|code| code.to_uppercase()),
+                    name: text(2)?,
+                    telephony: text(3).map(|t| t.to_uppercase()),
+                    country: text(4),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Split a callsign into its airline designator and flight number
+/// ("BAW123A" -> ("BAW", "123A"); None for registrations such as "N172SP")
+pub fn split_callsign(callsign: &str) -> Option<(&str, &str)> {
+    let bytes = callsign.as_bytes();
+    let airline = bytes.len() > 3 && bytes[..3].iter().all(u8::is_ascii_alphabetic) && bytes[3].is_ascii_digit();
+    airline.then(|| callsign.split_at(3))
+}
+
+fn decode(airlines: &HashMap<String, Airline>, callsign: &str) -> DecodedCallsign {
+    let callsign = callsign.trim().to_uppercase();
+    let (airline, flight_number) = match split_callsign(&callsign) {
+        Some((code, number)) => match airlines.get(code) {
+            Some(airline) => (Some(airline.clone()), Some(number.to_string())),
+            None => (None, None),
+        },
+        None => (None, None),
+    };
+    let spoken = match (&airline, &flight_number) {
+        (Some(Airline { telephony: Some(telephony), .. }), Some(number)) => format!("{} {}", telephony, number),
+        _ => callsign.clone(),
+    };
+    DecodedCallsign {
+        callsign,
+        airline,
+        flight_number,
+        spoken,
+    }
+}
+
+/// Look up an airline by ICAO designator (case-insensitive)
+pub fn get(app: &AppHandle, code: &str) -> Option<Airline> {
+    app.state::<AirlineState>()
+        .airlines
+        .read()
+        .get(&code.trim().to_uppercase())
+        .cloned()
+}
+
+/// Every airline, sorted by ICAO designator
+pub fn list(app: &AppHandle) -> Vec<Airline> {
+    let mut airlines: Vec<Airline> = app.state::<AirlineState>().airlines.read().values().cloned().collect();
+    airlines.sort_by(|a, b| a.icao.cmp(&b.icao));
+    airlines
+}
+
+/// Decode a callsign into airline, flight number and spoken form
+pub fn decode_callsign(app: &AppHandle, callsign: &str) -> DecodedCallsign {
+    decode(&app.state::<AirlineState>().airlines.read(), callsign)
+}
+
+/// Initialize the airline table for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_airlines(app: &AppHandle) {
+    let airlines = match airport_db::bundled_resource_path(app, "airlines.csv")
+        .ok_or_else(|| "Bundled airlines.csv not found".to_string())
+        .and_then(|path| fs::read_to_string(path).map_err(|e| format!("Failed to read airlines.csv: {}", e)))
+    {
+        Ok(text) => parse_airlines(&text),
+        Err(e) => {
+            warn!("[Airlines] {}", e);
+            HashMap::new()
+        }
+    };
+    app.manage(AirlineState {
+        airlines: RwLock::new(airlines),
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the name, telephony and country of an airline
+#[tauri::command]
+pub fn get_airline(app: AppHandle, code: String) -> Option<Airline> {
+    get(&app, &code)
+}
+
+/// Get the whole airline database
+#[tauri::command]
+pub fn list_airlines(app: AppHandle) -> Vec<Airline> {
+    list(&app)
+}
+
+/// Decode a callsign into airline, flight number and spoken form
+#[tauri::command]
+pub fn get_callsign_info(app: AppHandle, callsign: String) -> DecodedCallsign {
+    decode_callsign(&app, &callsign)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn airlines() -> HashMap<String, Airline> {
+        parse_airlines(
+            "icao,iata,name,telephony,country\n\
+             BAW,BA,British Airways,SPEEDBIRD,United Kingdom\n\
+             vjt,,VistaJet,Vista Malta,Malta\n\
+             XXXX,XX,Invalid,INVALID,\n\
+             NTL,,,NO NAME,\n",
+        )
+    }
+
+    #[test]
+    fn parses_airline_database() {
+        let airlines = airlines();
+        assert_eq!(airlines.len(), 2);
+        assert_eq!(airlines["BAW"].telephony.as_deref(), Some("SPEEDBIRD"));
+        assert_eq!(airlines["VJT"].iata, None);
+        assert_eq!(airlines["VJT"].telephony.as_deref(), Some("VISTA MALTA"));
+    }
+
+    #[test]
+    fn decodes_callsigns() {
+        let airlines = airlines();
+        let decoded = decode(&airlines, "baw123a");
+        assert_eq!(decoded.callsign, "BAW123A");
+        assert_eq!(decoded.flight_number.as_deref(), Some("123A"));
+        assert_eq!(decoded.spoken, "SPEEDBIRD 123A");
+
+        let unknown = decode(&airlines, "ZZZ12");
+        assert_eq!(unknown.airline, None);
+        assert_eq!(unknown.spoken, "ZZZ12");
+
+        let general_aviation = decode(&airlines, "N172SP");
+        assert_eq!(general_aviation.flight_number, None);
+        assert_eq!(general_aviation.spoken, "N172SP");
+    }
+}
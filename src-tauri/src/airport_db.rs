@@ -0,0 +1,617 @@
+//! Airport database (SQLite cache of the bundled airport and runway data)
+//!
+//! The bundled airports.json (mwgg/Airports) and runways.csv (OurAirports) are
+//! ingested into `airports.db` in the app data folder on first use, and rebuilt
+//! whenever the bundled files change (or an airport database content pack
+//! replaces them, see content_packs.rs). Backs fuzzy airport search by ICAO,
+//! IATA, name or city without any external lookup at runtime.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+use crate::content_packs;
+use crate::error::{Error, Result};
+use crate::runways::{self, Runway, RunwayEnd};
+
+/// Bump when the schema or ingestion changes to force a rebuild
+const SCHEMA_VERSION: u32 = 1;
+
+/// Default and maximum number of search results
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+const MAX_SEARCH_LIMIT: usize = 100;
+
+/// Minimum query length for typo-tolerant matching
+const FUZZY_MIN_QUERY_LEN: usize = 3;
+
+/// Minimum Jaro-Winkler similarity for a typo-tolerant match
+const FUZZY_THRESHOLD: f64 = 0.88;
+
+/// Ranking boost for airports with an IATA code
+const IATA_BONUS: f64 = 150.0;
+
+/// Airport with its runways
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AirportRecord {
+    pub icao: String,
+    pub iata: String,
+    pub name: String,
+    pub city: String,
+    pub state: String,
+    pub country: String,
+    pub elevation_ft: f64,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// IANA time zone (e.g., "America/New_York")
+    pub tz: String,
+    pub runways: Vec<Runway>,
+}
+
+/// Search hit, best matches first
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AirportSearchResult {
+    #[serde(flatten)]
+    pub airport: AirportRecord,
+    /// Relevance (higher is better)
+    pub score: f64,
+}
+
+/// Airport entry in airports.json
+#[derive(Deserialize)]
+struct RawAirport {
+    #[serde(default)]
+    iata: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    city: String,
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    country: String,
+    #[serde(default)]
+    elevation: f64,
+    #[serde(default)]
+    lat: f64,
+    #[serde(default)]
+    lon: f64,
+    #[serde(default)]
+    tz: String,
+}
+
+/// Airport database connection (managed by Tauri), opened on first use
+pub struct AirportDbState {
+    conn: Mutex<Option<Connection>>,
+}
+
+impl AirportDbState {
+    pub fn new() -> Self {
+        Self { conn: Mutex::new(None) }
+    }
+}
+
+impl Default for AirportDbState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Find a bundled resource file
+pub fn bundled_resource_path(app: &AppHandle, file_name: &str) -> Option<PathBuf> {
+    let resource_path = app.path().resource_dir().unwrap_or_default();
+    // In dev mode, CARGO_MANIFEST_DIR points to src-tauri/
+    let dev_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("resources")
+        .join(file_name);
+
+    [
+        // Production: bundled resources preserve directory structure
+        resource_path.join("resources").join(file_name),
+        dev_path,
+    ]
+    .into_iter()
+    .find(|p| p.exists())
+}
+
+/// Size and modification time of a file, to detect updated bundled data
+fn file_signature(path: &Path) -> String {
+    let Ok(metadata) = fs::metadata(path) else {
+        return "missing".to_string();
+    };
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    format!("{}:{}", metadata.len(), modified)
+}
+
+fn create_schema(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "DROP TABLE IF EXISTS airports;
+         DROP TABLE IF EXISTS runways;
+         CREATE TABLE IF NOT EXISTS meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+         CREATE TABLE airports (
+             icao TEXT PRIMARY KEY,
+             iata TEXT NOT NULL,
+             name TEXT NOT NULL,
+             city TEXT NOT NULL,
+             state TEXT NOT NULL,
+             country TEXT NOT NULL,
+             elevation_ft REAL NOT NULL,
+             latitude REAL NOT NULL,
+             longitude REAL NOT NULL,
+             tz TEXT NOT NULL
+         );
+         CREATE INDEX airports_iata ON airports (iata);
+         CREATE TABLE runways (
+             airport TEXT NOT NULL,
+             ident TEXT NOT NULL,
+             length_ft REAL,
+             width_ft REAL,
+             surface TEXT NOT NULL,
+             lighted INTEGER NOT NULL,
+             le_ident TEXT NOT NULL,
+             le_latitude REAL,
+             le_longitude REAL,
+             le_heading REAL,
+             le_elevation_ft REAL,
+             le_displaced_ft REAL NOT NULL,
+             he_ident TEXT NOT NULL,
+             he_latitude REAL,
+             he_longitude REAL,
+             he_heading REAL,
+             he_elevation_ft REAL,
+             he_displaced_ft REAL NOT NULL
+         );
+         CREATE INDEX runways_airport ON runways (airport);",
+    )
+    .map_err(|e| format!("Failed to create airport database schema: {}", e))
+}
+
+/// Ingest airports.json and runways.csv text into a fresh schema
+pub fn ingest(conn: &mut Connection, airports_json: &str, runways_csv: &str) -> Result<(usize, usize), String> {
+    let airports: HashMap<String, RawAirport> =
+        serde_json::from_str(airports_json).map_err(|e| format!("Failed to parse airports.json: {}", e))?;
+    let runway_index = runways::parse_runways_csv(runways_csv);
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    create_schema(&tx)?;
+
+    let mut airport_count = 0;
+    let mut runway_count = 0;
+    {
+        let mut insert_airport = tx
+            .prepare("INSERT OR REPLACE INTO airports VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)")
+            .map_err(|e| e.to_string())?;
+        for (icao, airport) in &airports {
+            // Same filter as the frontend: skip airports without coordinates
+            if airport.lat == 0.0 && airport.lon == 0.0 {
+                continue;
+            }
+            insert_airport
+                .execute(params![
+                    icao.to_uppercase(),
+                    airport.iata.to_uppercase(),
+                    airport.name,
+                    airport.city,
+                    airport.state,
+                    airport.country,
+                    airport.elevation,
+                    airport.lat,
+                    airport.lon,
+                    airport.tz,
+                ])
+                .map_err(|e| e.to_string())?;
+            airport_count += 1;
+        }
+
+        let mut insert_runway = tx
+            .prepare(
+                "INSERT INTO runways VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18)",
+            )
+            .map_err(|e| e.to_string())?;
+        for (airport, runways) in &runway_index {
+            for r in runways {
+                insert_runway
+                    .execute(params![
+                        airport,
+                        r.ident,
+                        r.length_ft,
+                        r.width_ft,
+                        r.surface,
+                        r.lighted,
+                        r.low_end.ident,
+                        r.low_end.latitude,
+                        r.low_end.longitude,
+                        r.low_end.heading_true,
+                        r.low_end.elevation_ft,
+                        r.low_end.displaced_threshold_ft,
+                        r.high_end.ident,
+                        r.high_end.latitude,
+                        r.high_end.longitude,
+                        r.high_end.heading_true,
+                        r.high_end.elevation_ft,
+                        r.high_end.displaced_threshold_ft,
+                    ])
+                    .map_err(|e| e.to_string())?;
+                runway_count += 1;
+            }
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok((airport_count, runway_count))
+}
+
+/// Open airports.db, (re)building it from the bundled files if they changed
+fn open_database(app: &AppHandle) -> Result<Connection, String> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    fs::create_dir_all(&app_data).map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    let mut conn = Connection::open(app_data.join("airports.db"))
+        .map_err(|e| format!("Failed to open airport database: {}", e))?;
+    conn.execute_batch("CREATE TABLE IF NOT EXISTS meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);")
+        .map_err(|e| e.to_string())?;
+
+    // An installed airport database content pack replaces the bundled files
+    let source_path = |name: &str| content_packs::airport_db_override(app, name).or_else(|| bundled_resource_path(app, name));
+    let airports_path = source_path("airports.json").ok_or("Bundled airports.json not found")?;
+    let runways_path = source_path("runways.csv").ok_or("Bundled runways.csv not found")?;
+    let signature = format!(
+        "{}|{}|{}",
+        SCHEMA_VERSION,
+        file_signature(&airports_path),
+        file_signature(&runways_path)
+    );
+
+    let current: Option<String> = conn
+        .query_row("SELECT value FROM meta WHERE key = 'source'", [], |row| row.get(0))
+        .optional()
+        .map_err(|e| e.to_string())?;
+    if current.as_deref() == Some(signature.as_str()) {
+        return Ok(conn);
+    }
+
+    info!("[AirportDb] Building airport database from bundled data...");
+    let airports_json =
+        fs::read_to_string(&airports_path).map_err(|e| format!("Failed to read airports.json: {}", e))?;
+    let runways_csv = fs::read_to_string(&runways_path).map_err(|e| format!("Failed to read runways.csv: {}", e))?;
+    let (airports, runways) = ingest(&mut conn, &airports_json, &runways_csv)?;
+    conn.execute(
+        "INSERT OR REPLACE INTO meta (key, value) VALUES ('source', ?1)",
+        params![signature],
+    )
+    .map_err(|e| e.to_string())?;
+    info!("[AirportDb] Indexed {} airports and {} runways", airports, runways);
+
+    Ok(conn)
+}
+
+/// Close the database so the next query reopens it (and rebuilds it if its sources changed)
+pub fn reset(app: &AppHandle) {
+    if let Some(state) = app.try_state::<AirportDbState>() {
+        *state.conn.lock() = None;
+    }
+}
+
+/// Run a query against the database, opening it on first use (blocking)
+pub fn with_db<T>(app: &AppHandle, f: impl FnOnce(&Connection) -> Result<T, String>) -> Result<T, String> {
+    let state = app.state::<AirportDbState>();
+    let mut conn = state.conn.lock();
+    if conn.is_none() {
+        *conn = Some(open_database(app)?);
+    }
+    f(conn.as_ref().expect("airport database opened above"))
+}
+
+fn read_runways(conn: &Connection, icao: &str) -> Result<Vec<Runway>, String> {
+    let mut stmt = conn
+        .prepare_cached("SELECT * FROM runways WHERE airport = ?1 ORDER BY rowid")
+        .map_err(|e| e.to_string())?;
+    let end = |row: &rusqlite::Row, offset: usize| -> rusqlite::Result<RunwayEnd> {
+        Ok(RunwayEnd {
+            ident: row.get(offset)?,
+            latitude: row.get(offset + 1)?,
+            longitude: row.get(offset + 2)?,
+            heading_true: row.get(offset + 3)?,
+            elevation_ft: row.get(offset + 4)?,
+            displaced_threshold_ft: row.get(offset + 5)?,
+        })
+    };
+    let rows = stmt
+        .query_map(params![icao], |row| {
+            Ok(Runway {
+                ident: row.get(1)?,
+                length_ft: row.get(2)?,
+                width_ft: row.get(3)?,
+                surface: row.get(4)?,
+                lighted: row.get(5)?,
+                low_end: end(row, 6)?,
+                high_end: end(row, 12)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+    rows.collect::<Result<_, _>>().map_err(|e| e.to_string())
+}
+
+fn read_airport(conn: &Connection, icao: &str) -> Result<Option<AirportRecord>, String> {
+    let airport = conn
+        .query_row("SELECT * FROM airports WHERE icao = ?1", params![icao], |row| {
+            Ok(AirportRecord {
+                icao: row.get(0)?,
+                iata: row.get(1)?,
+                name: row.get(2)?,
+                city: row.get(3)?,
+                state: row.get(4)?,
+                country: row.get(5)?,
+                elevation_ft: row.get(6)?,
+                latitude: row.get(7)?,
+                longitude: row.get(8)?,
+                tz: row.get(9)?,
+                runways: Vec::new(),
+            })
+        })
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    match airport {
+        Some(mut airport) => {
+            airport.runways = read_runways(conn, &airport.icao)?;
+            Ok(Some(airport))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Runways at an airport. None if the airport is unknown (neither in
+/// airports.json nor with runways in runways.csv).
+fn read_airport_runways(conn: &Connection, icao: &str) -> Result<Option<Vec<Runway>>, String> {
+    let runways = read_runways(conn, icao)?;
+    if !runways.is_empty() {
+        return Ok(Some(runways));
+    }
+    let known = conn
+        .query_row("SELECT 1 FROM airports WHERE icao = ?1", params![icao], |_| Ok(()))
+        .optional()
+        .map_err(|e| e.to_string())?
+        .is_some();
+    Ok(known.then_some(runways))
+}
+
+/// Relevance of an airport for a lowercase query, None if it doesn't match
+pub fn match_score(query: &str, icao: &str, iata: &str, name: &str, city: &str) -> Option<f64> {
+    let icao = icao.to_lowercase();
+    let iata = iata.to_lowercase();
+    let name = name.to_lowercase();
+    let city = city.to_lowercase();
+    let words = || name.split_whitespace().chain(city.split_whitespace());
+
+    let score = if icao == query {
+        1000.0
+    } else if !iata.is_empty() && iata == query {
+        900.0
+    } else if icao.starts_with(query) {
+        800.0 - (icao.len() - query.len()) as f64
+    } else if name.starts_with(query) {
+        600.0
+    } else if city == query {
+        550.0
+    } else if words().any(|w| w.starts_with(query)) {
+        500.0
+    } else if name.contains(query) {
+        300.0
+    } else if city.contains(query) {
+        250.0
+    } else if query.len() >= FUZZY_MIN_QUERY_LEN {
+        // Typo tolerance: closest word, or the whole name for multi-word queries
+        let similarity = words()
+            .chain([name.as_str()])
+            .map(|w| strsim::jaro_winkler(query, w))
+            .fold(0.0, f64::max);
+        if similarity < FUZZY_THRESHOLD {
+            return None;
+        }
+        100.0 * similarity
+    } else {
+        return None;
+    };
+
+    // Airports with an IATA code (scheduled service) are usually the ones people are looking for
+    Some(if iata.is_empty() { score } else { score + IATA_BONUS })
+}
+
+/// Escape LIKE wildcards in user input
+fn escape_like(query: &str) -> String {
+    query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+/// Fuzzy search by ICAO, IATA, name or city, best matches first
+pub fn search(conn: &Connection, query: &str, limit: usize) -> Result<Vec<AirportSearchResult>, String> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    let limit = limit.clamp(1, MAX_SEARCH_LIMIT);
+
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut collect = |sql: &str, args: &[&dyn rusqlite::ToSql]| -> Result<Vec<(f64, String)>, String> {
+        let mut found = Vec::new();
+        let mut stmt = conn.prepare_cached(sql).map_err(|e| e.to_string())?;
+        let mut rows = stmt.query(args).map_err(|e| e.to_string())?;
+        while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+            let icao: String = row.get(0).map_err(|e| e.to_string())?;
+            if seen.contains(&icao) {
+                continue;
+            }
+            let iata: String = row.get(1).map_err(|e| e.to_string())?;
+            let name: String = row.get(2).map_err(|e| e.to_string())?;
+            let city: String = row.get(3).map_err(|e| e.to_string())?;
+            if let Some(score) = match_score(&query, &icao, &iata, &name, &city) {
+                seen.insert(icao.clone());
+                found.push((score, icao));
+            }
+        }
+        Ok(found)
+    };
+
+    // Substring matches straight from SQLite
+    let escaped = escape_like(&query);
+    let mut scored = collect(
+        "SELECT icao, iata, name, city FROM airports
+         WHERE lower(icao) LIKE ?1 ESCAPE '\\' OR lower(iata) = ?2
+            OR lower(name) LIKE ?3 ESCAPE '\\' OR lower(city) LIKE ?3 ESCAPE '\\'",
+        &[&format!("{}%", escaped), &query, &format!("%{}%", escaped)],
+    )?;
+    // No hits: scan for near misses (typos)
+    if scored.is_empty() && query.len() >= FUZZY_MIN_QUERY_LEN {
+        scored.extend(collect("SELECT icao, iata, name, city FROM airports", &[])?);
+    }
+
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    scored.truncate(limit);
+
+    let mut results = Vec::with_capacity(scored.len());
+    for (score, icao) in scored {
+        if let Some(airport) = read_airport(conn, &icao)? {
+            results.push(AirportSearchResult { airport, score });
+        }
+    }
+    Ok(results)
+}
+
+/// Search airports (blocking; opens the database on first use)
+pub fn search_airports_blocking(
+    app: &AppHandle,
+    query: &str,
+    limit: Option<usize>,
+) -> Result<Vec<AirportSearchResult>, String> {
+    with_db(app, |conn| search(conn, query, limit.unwrap_or(DEFAULT_SEARCH_LIMIT)))
+}
+
+/// Look up one airport with its runways (blocking)
+pub fn get_airport_blocking(app: &AppHandle, icao: &str) -> Result<Option<AirportRecord>, String> {
+    with_db(app, |conn| read_airport(conn, &icao.trim().to_uppercase()))
+}
+
+/// Look up the runways at an airport (blocking)
+pub fn get_runways_blocking(app: &AppHandle, icao: &str) -> Result<Option<Vec<Runway>>, String> {
+    with_db(app, |conn| read_airport_runways(conn, &icao.trim().to_uppercase()))
+}
+
+/// Search airports without blocking the async runtime
+pub async fn search_airports(
+    app: &AppHandle,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<AirportSearchResult>, String> {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || search_airports_blocking(&app, &query, limit))
+        .await
+        .map_err(|e| format!("Airport search failed: {}", e))?
+}
+
+/// Look up one airport without blocking the async runtime
+pub async fn get_airport(app: &AppHandle, icao: String) -> Result<Option<AirportRecord>, String> {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || get_airport_blocking(&app, &icao))
+        .await
+        .map_err(|e| format!("Airport lookup failed: {}", e))?
+}
+
+/// Look up the runways at an airport without blocking the async runtime
+pub async fn get_runways(app: &AppHandle, icao: String) -> Result<Option<Vec<Runway>>, String> {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || get_runways_blocking(&app, &icao))
+        .await
+        .map_err(|e| format!("Runway lookup failed: {}", e))?
+}
+
+/// Initialize the airport database state and build/open it in the background.
+/// Call this in the Tauri setup closure.
+pub fn init_airport_db(app: &AppHandle) {
+    app.manage(AirportDbState::new());
+
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(e) = with_db(&app, |_| Ok(())) {
+            warn!("[AirportDb] {}", e);
+        }
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Fuzzy search airports by ICAO, IATA, name or city (with runways)
+#[tauri::command]
+pub async fn search_airport_db(
+    app: AppHandle,
+    query: String,
+    limit: Option<usize>,
+) -> Result<Vec<AirportSearchResult>> {
+    search_airports(&app, query, limit).await.map_err(Error::io)
+}
+
+/// Get an airport with its runways from the airport database
+#[tauri::command]
+pub async fn get_airport_info(app: AppHandle, icao: String) -> Result<Option<AirportRecord>> {
+    get_airport(&app, icao).await.map_err(Error::io)
+}
+
+/// Get runway thresholds, headings, lengths, widths and displaced thresholds at an airport
+#[tauri::command]
+pub async fn get_airport_runways(app: AppHandle, icao: String) -> Result<Option<Vec<Runway>>> {
+    get_runways(&app, icao).await.map_err(Error::io)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn searches_airports() {
+        let airports = r#"{
+            "KSFO": {"icao": "KSFO", "iata": "SFO", "name": "San Francisco International Airport", "city": "San Francisco", "state": "California", "country": "US", "elevation": 13, "lat": 37.619, "lon": -122.375, "tz": "America/Los_Angeles"},
+            "KSQL": {"icao": "KSQL", "iata": "SQL", "name": "San Carlos Airport", "city": "San Carlos", "state": "California", "country": "US", "elevation": 5, "lat": 37.512, "lon": -122.25, "tz": "America/Los_Angeles"},
+            "EGLL": {"icao": "EGLL", "iata": "LHR", "name": "London Heathrow Airport", "city": "London", "state": "England", "country": "GB", "elevation": 83, "lat": 51.4775, "lon": -0.4614, "tz": "Europe/London"},
+            "NULL": {"icao": "NULL", "name": "Nowhere", "lat": 0, "lon": 0}
+        }"#;
+        let runways = "\"id\",\"airport_ident\",\"length_ft\",\"width_ft\",\"surface\",\"lighted\",\"closed\",\"le_ident\",\"le_heading_degT\",\"he_ident\",\"he_heading_degT\",\"he_displaced_threshold_ft\"\n\
+            1,\"KSFO\",11870,200,\"ASPH\",1,0,\"10L\",117.8,\"28R\",297.8,300\n";
+
+        let mut conn = Connection::open_in_memory().unwrap();
+        assert_eq!(ingest(&mut conn, airports, runways).unwrap(), (3, 1));
+
+        let results = search(&conn, "sfo", 10).unwrap();
+        assert_eq!(results[0].airport.icao, "KSFO");
+        assert_eq!(results[0].airport.runways.len(), 1);
+        assert_eq!(results[0].airport.runways[0].high_end.displaced_threshold_ft, 300.0);
+
+        let results = search(&conn, "san", 10).unwrap();
+        assert_eq!(results.len(), 2);
+
+        // Typo in the name
+        let results = search(&conn, "heathrw", 10).unwrap();
+        assert_eq!(results[0].airport.icao, "EGLL");
+
+        assert!(search(&conn, "nowhere", 10).unwrap().is_empty());
+
+        assert_eq!(read_airport_runways(&conn, "KSFO").unwrap().unwrap().len(), 1);
+        assert_eq!(read_airport_runways(&conn, "EGLL").unwrap(), Some(Vec::new()));
+        assert_eq!(read_airport_runways(&conn, "XXXX").unwrap(), None);
+    }
+}
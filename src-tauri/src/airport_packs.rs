@@ -0,0 +1,651 @@
+//! Airport packs
+//!
+//! An airport pack bundles everything set up for one airport so a facility
+//! can hand it out as a single unit, where the pieces otherwise live in the
+//! mods folder, app data and global settings:
+//!
+//! ```text
+//! KBOS/
+//! ├── manifest.json          name, version, preferred and excluded mods
+//! ├── tower-position.json    mods/tower-positions/{ICAO}.json
+//! ├── viewport.json          default views and bookmarks (global settings)
+//! ├── placements.json        static objects (see `placements`)
+//! ├── annotations.geojson    hotspots etc. (see `annotations`)
+//! ├── overlays/{id}/...      overlay mods that apply only to this airport
+//! └── videomaps/...          imported video maps (see `videomaps`)
+//! ```
+//!
+//! Every piece is optional. A pack is exported as a folder or as a
+//! zstd-compressed tar archive of that folder (`{ICAO}.airport-pack.tar.zst`,
+//! the same format as backups), and installs from either. Installing replaces
+//! the airport's pieces that the pack contains, enables its preferred mods
+//! (e.g., the tower model) and disables its excluded mods (e.g., a stock
+//! model the pack replaces).
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_dialog::DialogExt;
+use tracing::info;
+
+use crate::error::{Error, Result};
+use crate::annotations;
+use crate::mods;
+use crate::overlays;
+use crate::placements;
+use crate::safe_path;
+use crate::videomaps::{self, VideoMapIndex};
+use crate::weather;
+use crate::{GlobalAirportViewportConfig, TowerPositionEntry};
+
+/// Bump when the pack layout changes
+const FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_NAME: &str = "manifest.json";
+const TOWER_POSITION_NAME: &str = "tower-position.json";
+const VIEWPORT_NAME: &str = "viewport.json";
+const PLACEMENTS_NAME: &str = "placements.json";
+const ANNOTATIONS_NAME: &str = "annotations.geojson";
+const OVERLAYS_DIR: &str = "overlays";
+const VIDEOMAPS_DIR: &str = "videomaps";
+
+/// Archive file name suffix
+pub const ARCHIVE_SUFFIX: &str = ".airport-pack.tar.zst";
+
+/// zstd level (packs are mostly JSON)
+const LEVEL: i32 = 3;
+
+/// Largest pack accepted for install (uncompressed)
+const MAX_PACK_BYTES: u64 = 512 * 1024 * 1024;
+
+/// Pack manifest (manifest.json)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AirportPackManifest {
+    #[serde(default)]
+    pub format: u32,
+    pub icao: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Mod ids enabled on install (e.g., "towers/KBOS")
+    #[serde(default)]
+    pub preferred_mods: Vec<String>,
+    /// Mod ids disabled on install
+    #[serde(default)]
+    pub excluded_mods: Vec<String>,
+    /// Unix ms
+    #[serde(default)]
+    pub created_at: u64,
+}
+
+/// Export options (metadata written to the manifest)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AirportPackExportOptions {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Default: enabled tower mods for the airport
+    #[serde(default)]
+    pub preferred_mods: Option<Vec<String>>,
+    #[serde(default)]
+    pub excluded_mods: Vec<String>,
+}
+
+/// An exported pack
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AirportPackInfo {
+    pub path: String,
+    pub manifest: AirportPackManifest,
+    /// Files in the pack (relative paths, manifest included)
+    pub files: Vec<String>,
+}
+
+/// An installed pack, emitted as `airport-pack-installed`
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AirportPackInstallResult {
+    pub manifest: AirportPackManifest,
+    /// Pieces installed ("tower position", "viewport", "3 overlays", ...)
+    pub installed: Vec<String>,
+    /// Preferred or excluded mods that aren't installed here
+    pub missing_mods: Vec<String>,
+}
+
+/// A pack's files, by relative path (forward slashes)
+type PackFiles = BTreeMap<String, Vec<u8>>;
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn app_data_dir(app: &AppHandle) -> Result<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))
+}
+
+/// Default export folder (airport-packs/ in app data)
+fn packs_dir(app: &AppHandle) -> Result<PathBuf> {
+    Ok(app_data_dir(app)?.join("airport-packs"))
+}
+
+fn to_json(value: &impl Serialize) -> Result<Vec<u8>> {
+    serde_json::to_vec_pretty(value).map_err(|e| Error::Internal(e.to_string()))
+}
+
+fn parse<T: serde::de::DeserializeOwned>(files: &PackFiles, name: &str) -> Result<Option<T>> {
+    files
+        .get(name)
+        .map(|bytes| serde_json::from_slice(bytes).map_err(|e| Error::InvalidInput(format!("Invalid {}: {}", name, e))))
+        .transpose()
+}
+
+/// Files below `dir` as (relative path, file)
+fn walk(dir: &Path, prefix: &str, files: &mut Vec<(String, PathBuf)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let name = entry.file_name().to_string_lossy().to_string();
+        let path = if prefix.is_empty() { name } else { format!("{}/{}", prefix, name) };
+        if file_type.is_dir() {
+            walk(&entry.path(), &path, files);
+        } else if file_type.is_file() {
+            files.push((path, entry.path()));
+        }
+    }
+}
+
+// =============================================================================
+// EXPORT
+// =============================================================================
+
+/// Enabled tower mods for an airport (the default preferred mods)
+fn airport_tower_mods(app: &AppHandle, icao: &str) -> Vec<String> {
+    mods::enabled_mods(app, "towers")
+        .into_iter()
+        .filter(|m| m.supported_icaos.iter().any(|s| s.eq_ignore_ascii_case(icao)))
+        .map(|m| m.id)
+        .collect()
+}
+
+/// Collect an airport's pack files
+fn collect(app: &AppHandle, icao: &str, options: AirportPackExportOptions) -> Result<PackFiles> {
+    let mut files = PackFiles::new();
+
+    if let Some(position) = crate::read_tower_positions(app.clone())?.get(icao) {
+        files.insert(TOWER_POSITION_NAME.to_string(), to_json(position)?);
+    }
+    let settings = crate::read_global_settings(app.clone())?;
+    if let Some(viewport) = settings.viewports.airport_configs.get(icao) {
+        files.insert(VIEWPORT_NAME.to_string(), to_json(viewport)?);
+    }
+    let objects = placements::get_objects(app, icao)?;
+    if !objects.is_empty() {
+        files.insert(PLACEMENTS_NAME.to_string(), to_json(&placements::AirportPlacements { objects })?);
+    }
+    let notes = annotations::get_annotations(app, icao)?;
+    if !notes.is_empty() {
+        files.insert(ANNOTATIONS_NAME.to_string(), to_json(&annotations::to_geojson(&notes))?);
+    }
+
+    // Overlay folders made for this airport (not the ones shown everywhere)
+    let overlays_root = overlays::overlays_root(app);
+    for overlay in overlays::list_all(app) {
+        let dir = overlays_root.join(&overlay.id);
+        if overlay.airports.is_empty() || !overlay.applies_to(icao) || !dir.is_dir() {
+            continue;
+        }
+        let mut overlay_files = Vec::new();
+        walk(&dir, &format!("{}/{}", OVERLAYS_DIR, overlay.id), &mut overlay_files);
+        for (name, path) in overlay_files {
+            let bytes = fs::read(&path).map_err(|e| Error::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+            files.insert(name, bytes);
+        }
+    }
+
+    if let Some(index) = videomaps::get_index(app, icao)? {
+        for map in &index.maps {
+            if let Some(path) = videomaps::resolve_map(app, icao, &map.id)? {
+                let bytes = fs::read(&path)
+                    .map_err(|e| Error::Io(format!("Failed to read video map {}: {}", map.id, e)))?;
+                files.insert(format!("{}/{}.geojson", VIDEOMAPS_DIR, map.id), bytes);
+            }
+        }
+        files.insert(format!("{}/index.json", VIDEOMAPS_DIR), to_json(&index)?);
+    }
+
+    if files.is_empty() {
+        return Err(Error::NotFound(format!("Nothing is set up for {} yet", icao)));
+    }
+
+    let manifest = AirportPackManifest {
+        format: FORMAT_VERSION,
+        icao: icao.to_string(),
+        name: options.name,
+        author: options.author,
+        version: options.version,
+        description: options.description,
+        preferred_mods: options
+            .preferred_mods
+            .unwrap_or_else(|| airport_tower_mods(app, icao)),
+        excluded_mods: options.excluded_mods,
+        created_at: now_ms(),
+    };
+    files.insert(MANIFEST_NAME.to_string(), to_json(&manifest)?);
+    Ok(files)
+}
+
+/// Pack files as a zstd-compressed tar archive (manifest first)
+fn to_archive(files: &PackFiles) -> Result<Vec<u8>> {
+    let encoder = zstd::Encoder::new(Vec::new(), LEVEL).map_err(|e| Error::Internal(e.to_string()))?;
+    let mut builder = tar::Builder::new(encoder);
+    let names = files
+        .keys()
+        .filter(|n| *n == MANIFEST_NAME)
+        .chain(files.keys().filter(|n| *n != MANIFEST_NAME));
+    for name in names {
+        let bytes = &files[name];
+        let mut header = tar::Header::new_gnu();
+        header.set_size(bytes.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(now_ms() / 1000);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, name, bytes.as_slice())
+            .map_err(|e| Error::Internal(format!("Failed to add {} to the pack: {}", name, e)))?;
+    }
+    let encoder = builder
+        .into_inner()
+        .map_err(|e| Error::Io(format!("Failed to write pack: {}", e)))?;
+    encoder.finish().map_err(|e| Error::Io(format!("Failed to write pack: {}", e)))
+}
+
+/// Build an airport's pack archive
+pub fn archive(app: &AppHandle, icao: &str, options: AirportPackExportOptions) -> Result<(String, Vec<u8>)> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    let files = collect(app, &icao, options)?;
+    Ok((format!("{}{}", icao, ARCHIVE_SUFFIX), to_archive(&files)?))
+}
+
+/// Export an airport's pack into `dir`, as an archive or a folder
+pub fn export(
+    app: &AppHandle,
+    icao: &str,
+    dir: &Path,
+    as_archive: bool,
+    options: AirportPackExportOptions,
+) -> Result<AirportPackInfo> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    let files = collect(app, &icao, options)?;
+    let manifest: AirportPackManifest = parse(&files, MANIFEST_NAME)?.unwrap_or_default();
+    fs::create_dir_all(dir).map_err(|e| Error::Io(format!("Failed to create {}: {}", dir.display(), e)))?;
+
+    let path = if as_archive {
+        let path = dir.join(format!("{}{}", icao, ARCHIVE_SUFFIX));
+        fs::write(&path, to_archive(&files)?).map_err(|e| Error::Io(format!("Failed to save pack: {}", e)))?;
+        path
+    } else {
+        let path = dir.join(&icao);
+        if path.exists() {
+            fs::remove_dir_all(&path).map_err(|e| Error::Io(format!("Failed to replace {}: {}", path.display(), e)))?;
+        }
+        for (name, bytes) in &files {
+            let target = path.join(name);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| Error::Io(format!("Failed to create {}: {}", parent.display(), e)))?;
+            }
+            fs::write(&target, bytes).map_err(|e| Error::Io(format!("Failed to write {}: {}", target.display(), e)))?;
+        }
+        path
+    };
+
+    info!("[AirportPacks] Exported {} ({} files) to {}", icao, files.len(), path.display());
+    Ok(AirportPackInfo {
+        path: path.to_string_lossy().to_string(),
+        manifest,
+        files: files.into_keys().collect(),
+    })
+}
+
+// =============================================================================
+// INSTALL
+// =============================================================================
+
+fn check_size(total: &mut u64, size: u64) -> Result<()> {
+    *total += size;
+    if *total > MAX_PACK_BYTES {
+        return Err(Error::InvalidInput(format!("Pack is larger than {} MB", MAX_PACK_BYTES / (1024 * 1024))));
+    }
+    Ok(())
+}
+
+/// Read a pack archive
+fn from_archive(reader: impl Read) -> Result<PackFiles> {
+    let decoder = zstd::Decoder::new(reader).map_err(|e| Error::InvalidInput(format!("Not an airport pack: {}", e)))?;
+    let mut archive = tar::Archive::new(decoder);
+    let mut files = PackFiles::new();
+    let mut total = 0;
+    for entry in archive.entries().map_err(|e| Error::InvalidInput(format!("Not an airport pack: {}", e)))? {
+        let mut entry = entry.map_err(|e| Error::InvalidInput(format!("Pack is damaged: {}", e)))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry
+            .path()
+            .map_err(|e| Error::InvalidInput(format!("Pack is damaged: {}", e)))?
+            .to_string_lossy()
+            .replace('\\', "/");
+        if !safe_path::valid_relative(&name) {
+            return Err(Error::InvalidInput(format!("Pack contains an unexpected file: {}", name)));
+        }
+        check_size(&mut total, entry.size())?;
+        let mut bytes = Vec::new();
+        entry
+            .read_to_end(&mut bytes)
+            .map_err(|e| Error::InvalidInput(format!("Pack is damaged: {}", e)))?;
+        files.insert(name, bytes);
+    }
+    Ok(files)
+}
+
+/// Read a pack folder
+fn from_dir(dir: &Path) -> Result<PackFiles> {
+    let mut paths = Vec::new();
+    walk(dir, "", &mut paths);
+    let mut files = PackFiles::new();
+    let mut total = 0;
+    for (name, path) in paths {
+        check_size(&mut total, fs::metadata(&path).map(|m| m.len()).unwrap_or(0))?;
+        let bytes = fs::read(&path).map_err(|e| Error::Io(format!("Failed to read {}: {}", path.display(), e)))?;
+        files.insert(name, bytes);
+    }
+    Ok(files)
+}
+
+/// Check a pack's manifest and that every file is one the pack layout knows
+fn validate(files: &PackFiles) -> Result<AirportPackManifest> {
+    let mut manifest: AirportPackManifest =
+        parse(files, MANIFEST_NAME)?.ok_or_else(|| Error::invalid_input("Not an airport pack (no manifest.json)"))?;
+    if manifest.format > FORMAT_VERSION {
+        return Err(Error::invalid_input("Pack was made by a newer version of the app"));
+    }
+    manifest.icao = weather::normalize_icao(&manifest.icao).map_err(Error::InvalidInput)?;
+
+    let index: Option<VideoMapIndex> = parse(files, &format!("{}/index.json", VIDEOMAPS_DIR))?;
+    let valid_map_id = |id: &str| !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if let Some(map) = index.iter().flat_map(|i| &i.maps).find(|m| !valid_map_id(&m.id)) {
+        return Err(Error::InvalidInput(format!("Invalid video map id: {}", map.id)));
+    }
+    for name in files.keys() {
+        let known = match name.split_once('/') {
+            None => [MANIFEST_NAME, TOWER_POSITION_NAME, VIEWPORT_NAME, PLACEMENTS_NAME, ANNOTATIONS_NAME]
+                .contains(&name.as_str()),
+            Some((OVERLAYS_DIR, rest)) => safe_path::valid_relative(name) && rest.contains('/'),
+            Some((VIDEOMAPS_DIR, file)) => {
+                file == "index.json"
+                    || index.as_ref().is_some_and(|index| {
+                        index.maps.iter().any(|m| file == format!("{}.geojson", m.id))
+                    })
+            }
+            _ => false,
+        };
+        if !known {
+            return Err(Error::InvalidInput(format!("Pack contains an unexpected file: {}", name)));
+        }
+    }
+    Ok(manifest)
+}
+
+/// Install a pack's pieces for its airport
+fn install_files(app: &AppHandle, files: &PackFiles) -> Result<AirportPackInstallResult> {
+    let manifest = validate(files)?;
+    let icao = manifest.icao.clone();
+    let mut result = AirportPackInstallResult {
+        manifest: manifest.clone(),
+        ..Default::default()
+    };
+
+    // Parse everything first so a bad piece doesn't leave a half-installed pack
+    let position: Option<TowerPositionEntry> = parse(files, TOWER_POSITION_NAME)?;
+    let viewport: Option<GlobalAirportViewportConfig> = parse(files, VIEWPORT_NAME)?;
+    let objects = parse::<placements::AirportPlacements>(files, PLACEMENTS_NAME)?.map(|p| p.objects);
+    if let Some(objects) = &objects {
+        placements::validate_all(objects)?;
+    }
+    let notes = parse::<Value>(files, ANNOTATIONS_NAME)?.map(|g| annotations::from_geojson(&g));
+    if let Some(notes) = &notes {
+        annotations::validate_all(notes)?;
+    }
+    let index: Option<VideoMapIndex> = parse(files, &format!("{}/index.json", VIDEOMAPS_DIR))?;
+
+    if let Some(position) = position {
+        crate::update_tower_position(app.clone(), icao.clone(), position)?;
+        result.installed.push("tower position".to_string());
+    }
+    if let Some(viewport) = viewport {
+        let mut settings = crate::read_global_settings(app.clone())?;
+        settings.viewports.airport_configs.insert(icao.clone(), viewport);
+        crate::write_global_settings(app.clone(), settings)?;
+        result.installed.push("views and bookmarks".to_string());
+    }
+    if let Some(objects) = objects {
+        let count = placements::set_objects(app, &icao, objects)?.len();
+        result.installed.push(format!("{} static objects", count));
+    }
+    if let Some(notes) = notes {
+        let count = annotations::set_annotations(app, &icao, notes)?.len();
+        result.installed.push(format!("{} annotations", count));
+    }
+
+    let overlays_root = overlays::overlays_root(app);
+    let mut overlay_ids: Vec<&str> = Vec::new();
+    for (name, bytes) in files.range(format!("{}/", OVERLAYS_DIR)..) {
+        let Some((id, file)) = name
+            .strip_prefix(&format!("{}/", OVERLAYS_DIR))
+            .and_then(|rest| rest.split_once('/'))
+        else {
+            break;
+        };
+        let dir = overlays_root.join(id);
+        if !overlay_ids.contains(&id) {
+            // Replace the overlay as a whole, like a mod update
+            if dir.exists() {
+                fs::remove_dir_all(&dir).map_err(|e| Error::Io(format!("Failed to replace overlay {}: {}", id, e)))?;
+            }
+            overlay_ids.push(id);
+        }
+        let target = dir.join(file);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| Error::Io(format!("Failed to create {}: {}", parent.display(), e)))?;
+        }
+        fs::write(&target, bytes).map_err(|e| Error::Io(format!("Failed to write {}: {}", target.display(), e)))?;
+    }
+    if !overlay_ids.is_empty() {
+        result.installed.push(format!("{} overlays", overlay_ids.len()));
+    }
+
+    if let Some(mut index) = index {
+        let dir = videomaps::airport_dir(app, &icao)?;
+        if dir.exists() {
+            fs::remove_dir_all(&dir).map_err(|e| Error::Io(format!("Failed to clear old video maps: {}", e)))?;
+        }
+        fs::create_dir_all(&dir).map_err(|e| Error::Io(format!("Failed to create video maps folder: {}", e)))?;
+        index.maps.retain(|m| files.contains_key(&format!("{}/{}.geojson", VIDEOMAPS_DIR, m.id)));
+        for map in index.maps.iter_mut() {
+            let bytes = &files[&format!("{}/{}.geojson", VIDEOMAPS_DIR, map.id)];
+            fs::write(dir.join(format!("{}.geojson", map.id)), bytes)
+                .map_err(|e| Error::Io(format!("Failed to save video map {}: {}", map.id, e)))?;
+            map.url = format!("/api/videomaps/{}/{}", icao, map.id);
+        }
+        index.icao = icao.clone();
+        fs::write(dir.join("index.json"), to_json(&index)?)
+            .map_err(|e| Error::Io(format!("Failed to save video map index: {}", e)))?;
+        result.installed.push(format!("{} video maps", index.maps.len()));
+    }
+
+    let registry = mods::get_registry(app);
+    let wanted = manifest
+        .preferred_mods
+        .iter()
+        .map(|id| (id, true))
+        .chain(manifest.excluded_mods.iter().map(|id| (id, false)));
+    for (id, enabled) in wanted {
+        match registry.mods.iter().find(|m| &m.id == id) {
+            Some(entry) if entry.enabled != enabled => mods::set_enabled(app, id, enabled)?,
+            Some(_) => {}
+            None => result.missing_mods.push(id.clone()),
+        }
+    }
+
+    info!(
+        "[AirportPacks] Installed {} pack: {}",
+        icao,
+        if result.installed.is_empty() { "nothing".to_string() } else { result.installed.join(", ") }
+    );
+    let _ = app.emit("airport-pack-installed", &result);
+    Ok(result)
+}
+
+/// Install a pack from an archive or a folder
+pub fn install(app: &AppHandle, path: &Path) -> Result<AirportPackInstallResult> {
+    let files = if path.is_dir() {
+        from_dir(path)?
+    } else {
+        let file = fs::File::open(path).map_err(|e| Error::Io(format!("Failed to open {}: {}", path.display(), e)))?;
+        from_archive(file)?
+    };
+    install_files(app, &files)
+}
+
+/// Install a pack from archive bytes (uploaded to the HTTP server)
+pub fn install_archive(app: &AppHandle, bytes: &[u8]) -> Result<AirportPackInstallResult> {
+    install_files(app, &from_archive(bytes)?)
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Export an airport's pack (to `destination` folder, default: airport-packs/ in app data)
+#[tauri::command]
+pub async fn export_airport_pack(
+    app: AppHandle,
+    icao: String,
+    destination: Option<String>,
+    as_archive: bool,
+    options: Option<AirportPackExportOptions>,
+) -> Result<AirportPackInfo> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dir = match destination {
+            Some(dir) => PathBuf::from(dir),
+            None => packs_dir(&app)?,
+        };
+        export(&app, &icao, &dir, as_archive, options.unwrap_or_default())
+    })
+    .await
+    .map_err(|e| Error::Internal(format!("Export failed: {}", e)))?
+}
+
+/// Pick an airport pack archive using native dialog
+#[tauri::command]
+pub async fn pick_airport_pack_file(app: AppHandle) -> Result<Option<String>> {
+    let mut dialog = app.dialog().file().add_filter("Airport pack", &["zst"]);
+    if let Ok(dir) = packs_dir(&app) {
+        dialog = dialog.set_directory(dir);
+    }
+    Ok(dialog.blocking_pick_file().map(|path| path.to_string()))
+}
+
+/// Install an airport pack from an archive or a folder
+#[tauri::command]
+pub async fn install_airport_pack(app: AppHandle, path: String) -> Result<AirportPackInstallResult> {
+    tauri::async_runtime::spawn_blocking(move || install(&app, Path::new(&path)))
+        .await
+        .map_err(|e| Error::Internal(format!("Install failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pack(extra: &[(&str, &[u8])]) -> PackFiles {
+        let manifest = json!({ "format": 1, "icao": "kbos", "preferredMods": ["towers/KBOS"] });
+        let mut files = PackFiles::new();
+        files.insert(MANIFEST_NAME.to_string(), serde_json::to_vec(&manifest).unwrap());
+        for (name, bytes) in extra {
+            files.insert(name.to_string(), bytes.to_vec());
+        }
+        files
+    }
+
+    #[test]
+    fn archives_round_trip() {
+        let files = pack(&[
+            (TOWER_POSITION_NAME, b"{}"),
+            ("overlays/kbos-taxi/map.geojson", b"{\"type\":\"FeatureCollection\"}"),
+        ]);
+        let bytes = to_archive(&files).unwrap();
+        assert_eq!(from_archive(bytes.as_slice()).unwrap(), files);
+        assert!(from_archive(&b"not a pack"[..]).is_err());
+    }
+
+    #[test]
+    fn validates_manifest_and_layout() {
+        let manifest = validate(&pack(&[(VIEWPORT_NAME, b"{}"), ("overlays/kbos-taxi/sub/a.geojson", b"{}")])).unwrap();
+        assert_eq!(manifest.icao, "KBOS");
+        assert_eq!(manifest.preferred_mods, vec!["towers/KBOS".to_string()]);
+
+        assert!(validate(&PackFiles::new()).is_err());
+        assert!(validate(&pack(&[("secrets.json", b"{}")])).is_err());
+        assert!(validate(&pack(&[("overlays/map.geojson", b"{}")])).is_err());
+
+        let mut newer = pack(&[]);
+        newer.insert(MANIFEST_NAME.to_string(), br#"{"format": 99, "icao": "KBOS"}"#.to_vec());
+        assert!(validate(&newer).is_err());
+
+        // Video maps must be listed in the pack's index
+        let index = json!({
+            "icao": "KBOS", "facilityId": "BOS", "facilityName": "Boston", "artcc": "ZBW", "importedAt": 0,
+            "maps": [{ "id": "asdex", "name": "ASDE-X", "tags": [], "kind": "asdex", "featureCount": 1,
+                       "url": "/api/videomaps/KBOS/asdex" }]
+        });
+        let index = serde_json::to_vec(&index).unwrap();
+        assert!(validate(&pack(&[("videomaps/index.json", &index), ("videomaps/asdex.geojson", b"{}")])).is_ok());
+        assert!(validate(&pack(&[("videomaps/index.json", &index), ("videomaps/other.geojson", b"{}")])).is_err());
+    }
+
+    #[test]
+    fn rejects_unsafe_paths() {
+        assert!(validate(&pack(&[("overlays/kbos/map.geojson", b"{}")])).is_ok());
+        assert!(validate(&pack(&[("overlays/../mods/x.json", b"{}")])).is_err());
+        assert!(validate(&pack(&[("overlays/kbos/map.geojson:stream", b"{}")])).is_err());
+    }
+}
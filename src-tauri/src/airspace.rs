@@ -0,0 +1,330 @@
+//! Class B/C/D airspace boundary overlays
+//!
+//! Generates GeoJSON airspace boundaries around an airport and stores them as
+//! an overlay (`mods/overlays/{icao}-airspace/`), so they are listed and served
+//! through the overlays API like any other overlay. US airspace comes from the
+//! FAA's public Class_Airspace feature service; elsewhere OpenAIP is used,
+//! which needs an API key (global settings, `airports.openaipApiKey`).
+//!
+//! Each feature carries normalized properties: `name`, `class`, `lowerFt`,
+//! `upperFt`, `lowerRef` and `upperRef` ("SFC", "AGL", "MSL" or "FL").
+
+use std::fs;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tauri::AppHandle;
+use tracing::{info, warn};
+
+use crate::error::{Error, Result};
+use crate::airport_db;
+use crate::http_client;
+use crate::overlays::{self, OverlayInfo, OverlayManifest};
+use crate::weather;
+
+const FAA_AIRSPACE_URL: &str =
+    "https://services6.arcgis.com/ssFJjBXIUyZDrSYZ/arcgis/rest/services/Class_Airspace/FeatureServer/0/query";
+const OPENAIP_AIRSPACE_URL: &str = "https://api.core.openaip.net/api/airspaces";
+
+/// Airspace within this distance of the airport is included (nautical miles)
+const AIRSPACE_RADIUS_NM: f64 = 40.0;
+
+/// Regenerate overlays older than this
+const CACHE_MAX_AGE: Duration = Duration::from_secs(28 * 24 * 3600);
+
+const AIRSPACE_FILE: &str = "airspace.geojson";
+
+/// OpenAIP airspace list
+#[derive(Deserialize)]
+struct OpenAipResponse {
+    #[serde(default)]
+    items: Vec<OpenAipAirspace>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OpenAipAirspace {
+    name: String,
+    /// 0 = A, 1 = B, 2 = C, 3 = D, ...
+    icao_class: u8,
+    geometry: Value,
+    lower_limit: OpenAipLimit,
+    upper_limit: OpenAipLimit,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OpenAipLimit {
+    value: f64,
+    /// 0 = meters, 1 = feet, 6 = flight level
+    unit: u8,
+    /// 0 = GND, 1 = MSL, 2 = STD
+    reference_datum: u8,
+}
+
+/// Whether an airport's airspace is published by the FAA
+fn is_faa_airport(icao: &str) -> bool {
+    icao.starts_with('K') || ["PA", "PH", "PG", "TJ", "TI"].iter().any(|p| icao.starts_with(p))
+}
+
+/// Normalized feature properties
+fn airspace_properties(name: &str, class: &str, lower: (f64, &str), upper: (f64, &str), source: &str) -> Value {
+    json!({
+        "name": name,
+        "class": class,
+        "lowerFt": lower.0.round(),
+        "lowerRef": lower.1,
+        "upperFt": upper.0.round(),
+        "upperRef": upper.1,
+        "source": source,
+    })
+}
+
+/// Convert an FAA Class_Airspace feature (ArcGIS GeoJSON output)
+fn from_faa_feature(feature: &Value) -> Option<Value> {
+    let props = &feature["properties"];
+    let class = props["CLASS"].as_str()?.trim();
+    if !matches!(class, "B" | "C" | "D") {
+        return None;
+    }
+    let limit = |value: &str, code: &str| {
+        let ft = props[value].as_f64().unwrap_or(0.0);
+        match props[code].as_str().unwrap_or("MSL") {
+            "SFC" => (0.0, "SFC"),
+            _ => (ft, "MSL"),
+        }
+    };
+    Some(json!({
+        "type": "Feature",
+        "geometry": feature["geometry"].clone(),
+        "properties": airspace_properties(
+            props["NAME"].as_str().unwrap_or_default(),
+            class,
+            limit("LOWER_VAL", "LOWER_CODE"),
+            limit("UPPER_VAL", "UPPER_CODE"),
+            "FAA",
+        ),
+    }))
+}
+
+/// Convert an OpenAIP airspace
+fn from_openaip(airspace: OpenAipAirspace) -> Option<Value> {
+    let class = match airspace.icao_class {
+        1 => "B",
+        2 => "C",
+        3 => "D",
+        _ => return None,
+    };
+    let limit = |l: &OpenAipLimit| {
+        let ft = match l.unit {
+            0 => l.value * 3.28084,
+            6 => l.value * 100.0,
+            _ => l.value,
+        };
+        match l.reference_datum {
+            0 if ft == 0.0 => (0.0, "SFC"),
+            0 => (ft, "AGL"),
+            2 => (ft, "FL"),
+            _ => (ft, "MSL"),
+        }
+    };
+    Some(json!({
+        "type": "Feature",
+        "geometry": airspace.geometry,
+        "properties": airspace_properties(
+            &airspace.name,
+            class,
+            limit(&airspace.lower_limit),
+            limit(&airspace.upper_limit),
+            "OpenAIP",
+        ),
+    }))
+}
+
+async fn fetch_faa(latitude: f64, longitude: f64) -> Result<Vec<Value>> {
+    let lat_span = AIRSPACE_RADIUS_NM / 60.0;
+    let lon_span = lat_span / latitude.to_radians().cos().max(0.1);
+    let envelope = format!(
+        "{},{},{},{}",
+        longitude - lon_span,
+        latitude - lat_span,
+        longitude + lon_span,
+        latitude + lat_span
+    );
+    let response = http_client::client()
+        .get(FAA_AIRSPACE_URL)
+        .query(&[
+            ("where", "CLASS IN ('B','C','D')"),
+            ("geometry", envelope.as_str()),
+            ("geometryType", "esriGeometryEnvelope"),
+            ("inSR", "4326"),
+            ("spatialRel", "esriSpatialRelIntersects"),
+            ("outFields", "NAME,CLASS,LOWER_VAL,LOWER_CODE,UPPER_VAL,UPPER_CODE"),
+            ("outSR", "4326"),
+            ("f", "geojson"),
+        ])
+        .timeout(Duration::from_secs(60))
+        .send()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to fetch FAA airspace: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(Error::Upstream(format!("FAA airspace service error: {}", response.status())));
+    }
+    let body: Value = response
+        .json()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to parse FAA airspace: {}", e)))?;
+    let features = body["features"].as_array().cloned().unwrap_or_default();
+    Ok(features.iter().filter_map(from_faa_feature).collect())
+}
+
+async fn fetch_openaip(latitude: f64, longitude: f64, api_key: &str) -> Result<Vec<Value>> {
+    let response = http_client::client()
+        .get(OPENAIP_AIRSPACE_URL)
+        .header("x-openaip-api-key", api_key)
+        .query(&[
+            ("pos", format!("{},{}", latitude, longitude)),
+            ("dist", format!("{}", (AIRSPACE_RADIUS_NM * 1852.0) as u64)),
+            ("icaoClass", "1".to_string()),
+            ("icaoClass", "2".to_string()),
+            ("icaoClass", "3".to_string()),
+            ("limit", "1000".to_string()),
+        ])
+        .timeout(Duration::from_secs(60))
+        .send()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to fetch OpenAIP airspace: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(Error::Upstream(format!("OpenAIP error: {}", response.status())));
+    }
+    let body: OpenAipResponse = response
+        .json()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to parse OpenAIP airspace: {}", e)))?;
+    Ok(body.items.into_iter().filter_map(from_openaip).collect())
+}
+
+/// Generate (or reuse) the airspace overlay for an airport
+pub async fn get_airspace_overlay(app: &AppHandle, icao: &str, refresh: bool) -> Result<OverlayInfo> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    let id = format!("{}-airspace", icao.to_lowercase());
+    let dir = overlays::overlays_root(app).join(&id);
+
+    let fresh = fs::metadata(dir.join(AIRSPACE_FILE))
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < CACHE_MAX_AGE);
+    let existing = || overlays::list_all(app).into_iter().find(|o| o.id == id);
+    if fresh && !refresh {
+        if let Some(overlay) = existing() {
+            return Ok(overlay);
+        }
+    }
+
+    let airport = airport_db::get_airport(app, icao.clone())
+        .await
+        .map_err(Error::io)?
+        .ok_or_else(|| Error::NotFound(format!("Unknown airport: {}", icao)))?;
+    let (features, source) = if is_faa_airport(&icao) {
+        (fetch_faa(airport.latitude, airport.longitude).await, "FAA")
+    } else {
+        let api_key = crate::read_global_settings(app.clone())?.airports.openaip_api_key;
+        if api_key.trim().is_empty() {
+            return Err(Error::invalid_input("An OpenAIP API key is required for airspace outside the US"));
+        }
+        (
+            fetch_openaip(airport.latitude, airport.longitude, api_key.trim()).await,
+            "OpenAIP",
+        )
+    };
+    let features = match features {
+        Ok(features) => features,
+        Err(e) => {
+            // Keep serving the previous overlay when the source is unreachable
+            warn!("[Airspace] Failed to fetch airspace for {}: {}", icao, e);
+            return existing().ok_or(e);
+        }
+    };
+
+    fs::create_dir_all(&dir).map_err(|e| Error::Io(format!("Failed to create overlay folder: {}", e)))?;
+    let collection = json!({"type": "FeatureCollection", "features": features});
+    fs::write(dir.join(AIRSPACE_FILE), collection.to_string())
+        .map_err(|e| Error::Io(format!("Failed to write {}: {}", AIRSPACE_FILE, e)))?;
+    let manifest = OverlayManifest {
+        name: Some(format!("{} airspace", icao)),
+        description: Some(format!(
+            "Class B/C/D airspace within {} nm ({})",
+            AIRSPACE_RADIUS_NM, source
+        )),
+        category: Some("airspace".to_string()),
+        airports: vec![icao.clone()],
+        render: Some("2d".to_string()),
+        files: vec![AIRSPACE_FILE.to_string()],
+        ..Default::default()
+    };
+    let content = serde_json::to_string_pretty(&manifest).map_err(|e| Error::Internal(e.to_string()))?;
+    fs::write(dir.join("manifest.json"), content)
+        .map_err(|e| Error::Io(format!("Failed to write overlay manifest: {}", e)))?;
+
+    info!(
+        "[Airspace] Generated {} airspace boundaries for {} ({})",
+        features.len(),
+        icao,
+        source
+    );
+    existing().ok_or_else(|| Error::Internal(format!("Generated overlay {} could not be loaded", id)))
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Generate the class B/C/D airspace overlay for an airport (cached, refetched
+/// when stale or on request)
+#[tauri::command]
+pub async fn generate_airspace_overlay(
+    app: AppHandle,
+    icao: String,
+    refresh: Option<bool>,
+) -> Result<OverlayInfo> {
+    get_airspace_overlay(&app, &icao, refresh.unwrap_or(false)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_airspace() {
+        let square = json!({"type": "Polygon", "coordinates": [[[0, 0], [0, 1], [1, 1], [0, 0]]]});
+        let faa = json!({
+            "type": "Feature",
+            "geometry": square,
+            "properties": {"NAME": "BOSTON CLASS B", "CLASS": "B", "LOWER_VAL": 0, "LOWER_CODE": "SFC",
+                           "UPPER_VAL": 7000, "UPPER_CODE": "MSL"}
+        });
+        let feature = from_faa_feature(&faa).unwrap();
+        assert_eq!(feature["properties"]["lowerRef"], "SFC");
+        assert_eq!(feature["properties"]["upperFt"], 7000.0);
+        assert!(from_faa_feature(&json!({"properties": {"CLASS": "E"}})).is_none());
+
+        let openaip: OpenAipResponse = serde_json::from_value(json!({"items": [
+            {"name": "LONDON CTR", "icaoClass": 3, "geometry": square,
+             "lowerLimit": {"value": 0, "unit": 1, "referenceDatum": 0},
+             "upperLimit": {"value": 2500, "unit": 1, "referenceDatum": 1}},
+            {"name": "LTMA", "icaoClass": 0, "geometry": square,
+             "lowerLimit": {"value": 25, "unit": 6, "referenceDatum": 2},
+             "upperLimit": {"value": 195, "unit": 6, "referenceDatum": 2}}
+        ]}))
+        .unwrap();
+        let features: Vec<Value> = openaip.items.into_iter().filter_map(from_openaip).collect();
+        assert_eq!(features.len(), 1);
+        assert_eq!(features[0]["properties"]["class"], "D");
+        assert_eq!(features[0]["properties"]["upperFt"], 2500.0);
+        assert_eq!(features[0]["properties"]["source"], "OpenAIP");
+
+        assert!(is_faa_airport("KBOS") && is_faa_airport("PHNL") && !is_faa_airport("EGLL"));
+    }
+}
@@ -0,0 +1,399 @@
+//! Airport annotations
+//!
+//! Facility-drawn annotations for an airport: runway incursion hotspots, ILS
+//! critical areas and non-movement area boundaries, so local knowledge can be
+//! shown to trainees on the scene. They are kept per airport in app data as a
+//! GeoJSON FeatureCollection (`annotations/{ICAO}.geojson`, one feature per
+//! annotation with its `id`, `kind`, `name` and `description` as properties)
+//! and listed by the overlays API as the `{icao}-annotations` overlay.
+//!
+//! Annotations are edited with the `*_annotation` commands or
+//! `/api/annotations/{ICAO}`, and every change is emitted as
+//! `annotations-changed`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+use crate::error::{Error, Result};
+use crate::overlays::{self, OverlayInfo};
+use crate::placements;
+use crate::weather;
+
+/// Annotations folder in app data
+const ANNOTATIONS_DIR: &str = "annotations";
+
+/// Overlay id suffix (`kbos-annotations`)
+const OVERLAY_SUFFIX: &str = "-annotations";
+
+/// Most annotations at one airport
+const MAX_ANNOTATIONS: usize = 500;
+
+/// What an annotation marks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AnnotationKind {
+    /// Runway incursion hotspot (point or area)
+    Hotspot,
+    /// ILS critical/sensitive area (area)
+    IlsCriticalArea,
+    /// Boundary of the non-movement area (line or area)
+    NonMovementBoundary,
+}
+
+impl AnnotationKind {
+    /// GeoJSON geometry types the kind can be drawn with
+    fn geometry_types(self) -> &'static [&'static str] {
+        match self {
+            AnnotationKind::Hotspot => &["Point", "Polygon"],
+            AnnotationKind::IlsCriticalArea => &["Polygon"],
+            AnnotationKind::NonMovementBoundary => &["LineString", "Polygon"],
+        }
+    }
+}
+
+/// An annotation drawn at an airport
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Annotation {
+    /// Identifier, unique per airport (e.g., "hs1")
+    pub id: String,
+    pub kind: AnnotationKind,
+    /// Short label (e.g., "HS 1")
+    #[serde(default)]
+    pub name: Option<String>,
+    /// What to watch out for
+    #[serde(default)]
+    pub description: Option<String>,
+    /// GeoJSON geometry (lon/lat coordinates)
+    pub geometry: Value,
+}
+
+/// Annotation change, emitted as `annotations-changed`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnnotationsChanged {
+    pub icao: String,
+    pub annotations: Vec<Annotation>,
+}
+
+/// Check an annotation
+pub fn validate(annotation: &Annotation) -> Result<()> {
+    if !placements::valid_object_id(&annotation.id) {
+        return Err(Error::invalid_input(format!("Invalid annotation id: {}", annotation.id)));
+    }
+    let geometry_type = annotation.geometry.get("type").and_then(Value::as_str).unwrap_or("");
+    let allowed = annotation.kind.geometry_types();
+    if !allowed.contains(&geometry_type) {
+        return Err(Error::invalid_input(format!(
+            "Annotation {} must be a {} (got {})",
+            annotation.id,
+            allowed.join(" or "),
+            if geometry_type.is_empty() { "no geometry" } else { geometry_type }
+        )));
+    }
+    let coordinates = annotation.geometry.get("coordinates").unwrap_or(&Value::Null);
+    if !valid_coordinates(coordinates) {
+        return Err(Error::invalid_input(format!("Invalid coordinates for {}", annotation.id)));
+    }
+    Ok(())
+}
+
+/// Whether nested GeoJSON coordinates are non-empty lon/lat positions
+fn valid_coordinates(value: &Value) -> bool {
+    let Some(items) = value.as_array() else {
+        return false;
+    };
+    match items.first() {
+        Some(Value::Array(_)) => items.iter().all(valid_coordinates),
+        Some(Value::Number(_)) => {
+            let lon = items[0].as_f64();
+            let lat = items.get(1).and_then(Value::as_f64);
+            matches!((lon, lat), (Some(lon), Some(lat)) if (-180.0..=180.0).contains(&lon) && (-90.0..=90.0).contains(&lat))
+        }
+        _ => false,
+    }
+}
+
+/// Check an airport's annotations (every annotation valid, ids unique)
+pub fn validate_all(annotations: &[Annotation]) -> Result<()> {
+    if annotations.len() > MAX_ANNOTATIONS {
+        return Err(Error::invalid_input(format!(
+            "At most {} annotations can be drawn at an airport",
+            MAX_ANNOTATIONS
+        )));
+    }
+    for (i, annotation) in annotations.iter().enumerate() {
+        validate(annotation)?;
+        if annotations[..i].iter().any(|a| a.id == annotation.id) {
+            return Err(Error::invalid_input(format!("Duplicate annotation id: {}", annotation.id)));
+        }
+    }
+    Ok(())
+}
+
+/// GeoJSON FeatureCollection for annotations
+pub fn to_geojson(annotations: &[Annotation]) -> Value {
+    let features: Vec<Value> = annotations
+        .iter()
+        .map(|a| {
+            json!({
+                "type": "Feature",
+                "geometry": a.geometry,
+                "properties": {
+                    "id": a.id,
+                    "kind": a.kind,
+                    "name": a.name,
+                    "description": a.description,
+                },
+            })
+        })
+        .collect();
+    json!({ "type": "FeatureCollection", "features": features })
+}
+
+/// Annotations from a FeatureCollection written by `to_geojson` (unreadable features are skipped)
+pub fn from_geojson(geojson: &Value) -> Vec<Annotation> {
+    geojson
+        .get("features")
+        .and_then(Value::as_array)
+        .map(|features| {
+            features
+                .iter()
+                .filter_map(|feature| {
+                    let properties = feature.get("properties")?;
+                    Some(Annotation {
+                        id: properties.get("id")?.as_str()?.to_string(),
+                        kind: serde_json::from_value(properties.get("kind")?.clone()).ok()?,
+                        name: properties.get("name").and_then(Value::as_str).map(str::to_string),
+                        description: properties
+                            .get("description")
+                            .and_then(Value::as_str)
+                            .map(str::to_string),
+                        geometry: feature.get("geometry")?.clone(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Annotations folder in app data
+pub fn annotations_root(app: &AppHandle) -> Result<PathBuf> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))?;
+    Ok(app_data.join(ANNOTATIONS_DIR))
+}
+
+fn annotations_path(app: &AppHandle, icao: &str) -> Result<PathBuf> {
+    Ok(annotations_root(app)?.join(format!("{}.geojson", icao)))
+}
+
+fn read_file(path: &Path) -> Result<Vec<Annotation>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path).map_err(|e| Error::Io(format!("Failed to read annotations: {}", e)))?;
+    let geojson: Value = serde_json::from_str(&content)
+        .map_err(|e| Error::Internal(format!("Failed to parse annotations: {}", e)))?;
+    Ok(from_geojson(&geojson))
+}
+
+/// Annotations drawn at an airport (none if it has no annotations file)
+pub fn get_annotations(app: &AppHandle, icao: &str) -> Result<Vec<Annotation>> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    read_file(&annotations_path(app, &icao)?)
+}
+
+/// Replace every annotation at an airport
+pub fn set_annotations(app: &AppHandle, icao: &str, annotations: Vec<Annotation>) -> Result<Vec<Annotation>> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    validate_all(&annotations)?;
+
+    let path = annotations_path(app, &icao)?;
+    if annotations.is_empty() {
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| Error::Io(format!("Failed to remove annotations: {}", e)))?;
+        }
+    } else {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| Error::Io(format!("Failed to create annotations folder: {}", e)))?;
+        }
+        let content = serde_json::to_string_pretty(&to_geojson(&annotations))
+            .map_err(|e| Error::Internal(format!("Failed to serialize annotations: {}", e)))?;
+        fs::write(&path, content).map_err(|e| Error::Io(format!("Failed to write annotations: {}", e)))?;
+    }
+
+    info!("[Annotations] {} annotation(s) at {}", annotations.len(), icao);
+    let _ = app.emit(
+        "annotations-changed",
+        &AnnotationsChanged {
+            icao,
+            annotations: annotations.clone(),
+        },
+    );
+    Ok(annotations)
+}
+
+/// Add an annotation to an airport, replacing the annotation with the same id
+pub fn save_annotation(app: &AppHandle, icao: &str, annotation: Annotation) -> Result<Vec<Annotation>> {
+    validate(&annotation)?;
+    let mut annotations = get_annotations(app, icao)?;
+    match annotations.iter_mut().find(|a| a.id == annotation.id) {
+        Some(existing) => *existing = annotation,
+        None => annotations.push(annotation),
+    }
+    set_annotations(app, icao, annotations)
+}
+
+/// Remove an annotation from an airport
+pub fn delete_annotation(app: &AppHandle, icao: &str, id: &str) -> Result<Vec<Annotation>> {
+    let mut annotations = get_annotations(app, icao)?;
+    let count = annotations.len();
+    annotations.retain(|a| a.id != id);
+    if annotations.len() == count {
+        return Err(Error::not_found(format!("No annotation {} at {}", id, icao.to_uppercase())));
+    }
+    set_annotations(app, icao, annotations)
+}
+
+/// Whether an overlay id belongs to an annotations overlay
+pub fn is_overlay_id(id: &str) -> bool {
+    id.strip_suffix(OVERLAY_SUFFIX)
+        .is_some_and(|icao| weather::normalize_icao(icao).is_ok())
+}
+
+/// Annotations overlays, one per airport with annotations
+pub fn list_overlays(app: &AppHandle) -> Vec<OverlayInfo> {
+    let Ok(root) = annotations_root(app) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&root) else {
+        return Vec::new();
+    };
+
+    let mut result = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.extension().is_some_and(|e| e.eq_ignore_ascii_case("geojson")) {
+            continue;
+        }
+        let Some(icao) = path
+            .file_stem()
+            .and_then(|s| weather::normalize_icao(&s.to_string_lossy()).ok())
+        else {
+            warn!("[Annotations] Ignoring {:?}", path);
+            continue;
+        };
+
+        let id = format!("{}{}", icao.to_lowercase(), OVERLAY_SUFFIX);
+        let file = overlays::overlay_file(&id, format!("{}.geojson", icao), path);
+        result.push(OverlayInfo {
+            name: format!("{} annotations", icao),
+            id,
+            author: None,
+            version: None,
+            description: Some("Hotspots, ILS critical areas and non-movement boundaries".to_string()),
+            category: Some("annotations".to_string()),
+            airports: vec![icao],
+            render: "both".to_string(),
+            style: Some(json!({
+                "hotspot": { "color": "#ff5252" },
+                "ilsCriticalArea": { "color": "#ffd740" },
+                "nonMovementBoundary": { "color": "#ffffff", "dashed": true },
+            })),
+            files: vec![file],
+        });
+    }
+    result
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the annotations drawn at an airport
+#[tauri::command]
+pub fn get_airport_annotations(app: AppHandle, icao: String) -> Result<Vec<Annotation>> {
+    get_annotations(&app, &icao)
+}
+
+/// Add (or edit) an annotation at an airport
+#[tauri::command]
+pub fn save_airport_annotation(app: AppHandle, icao: String, annotation: Annotation) -> Result<Vec<Annotation>> {
+    save_annotation(&app, &icao, annotation)
+}
+
+/// Remove an annotation from an airport
+#[tauri::command]
+pub fn delete_airport_annotation(app: AppHandle, icao: String, id: String) -> Result<Vec<Annotation>> {
+    delete_annotation(&app, &icao, &id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn annotation(id: &str, kind: AnnotationKind, geometry: Value) -> Annotation {
+        Annotation {
+            id: id.to_string(),
+            kind,
+            name: Some("HS 1".to_string()),
+            description: None,
+            geometry,
+        }
+    }
+
+    fn point() -> Value {
+        json!({ "type": "Point", "coordinates": [-71.0096, 42.3656] })
+    }
+
+    fn area() -> Value {
+        json!({ "type": "Polygon", "coordinates": [[
+            [-71.01, 42.36], [-71.00, 42.36], [-71.00, 42.37], [-71.01, 42.36]
+        ]] })
+    }
+
+    #[test]
+    fn validates_geometry_per_kind() {
+        assert!(validate(&annotation("hs1", AnnotationKind::Hotspot, point())).is_ok());
+        assert!(validate(&annotation("hs1", AnnotationKind::Hotspot, area())).is_ok());
+        assert!(validate(&annotation("ils", AnnotationKind::IlsCriticalArea, point())).is_err());
+        assert!(validate(&annotation("ils", AnnotationKind::IlsCriticalArea, area())).is_ok());
+        assert!(validate(&annotation("hs 1", AnnotationKind::Hotspot, point())).is_err());
+        assert!(validate(&annotation(
+            "hs1",
+            AnnotationKind::Hotspot,
+            json!({ "type": "Point", "coordinates": [-71.0, 95.0] })
+        ))
+        .is_err());
+        assert!(validate_all(&[
+            annotation("a", AnnotationKind::Hotspot, point()),
+            annotation("a", AnnotationKind::Hotspot, point()),
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn round_trips_through_geojson() {
+        let annotations = vec![
+            annotation("hs1", AnnotationKind::Hotspot, point()),
+            annotation("ils04r", AnnotationKind::IlsCriticalArea, area()),
+        ];
+        assert_eq!(from_geojson(&to_geojson(&annotations)), annotations);
+    }
+
+    #[test]
+    fn recognizes_overlay_ids() {
+        assert!(is_overlay_id("kbos-annotations"));
+        assert!(!is_overlay_id("kbos-airspace"));
+        assert!(!is_overlay_id("-annotations"));
+    }
+}
@@ -0,0 +1,83 @@
+//! API documentation
+//!
+//! The remote server documents itself for third-party integrators (Stream
+//! Deck plugins, facility tools): `/api/docs` is an interactive reference and
+//! `/api/docs/openapi.json` the OpenAPI 3.1 description behind it.
+//!
+//! The description is generated at build time from the router in server.rs
+//! and the serde types of the requests and responses (see build/openapi.rs),
+//! so new endpoints and fields appear without editing a spec by hand. Both
+//! files are embedded in the binary.
+
+/// OpenAPI description of the HTTP API (generated by build.rs)
+pub const SPEC: &str = include_str!(concat!(env!("OUT_DIR"), "/openapi.json"));
+
+/// Interactive reference page
+pub const PAGE: &str = include_str!("../api-docs/index.html");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn spec() -> Value {
+        serde_json::from_str(SPEC).unwrap()
+    }
+
+    /// Every `$ref` in a value
+    fn refs(value: &Value, out: &mut Vec<String>) {
+        match value {
+            Value::Object(object) => {
+                if let Some(Value::String(r)) = object.get("$ref") {
+                    out.push(r.clone());
+                }
+                object.values().for_each(|v| refs(v, out));
+            }
+            Value::Array(items) => items.iter().for_each(|v| refs(v, out)),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn describes_the_routes() {
+        let spec = spec();
+        assert_eq!(spec["openapi"], "3.1.0");
+        assert_eq!(spec["info"]["version"], env!("CARGO_PKG_VERSION"));
+
+        let paths = spec["paths"].as_object().unwrap();
+        assert!(paths.len() > 50);
+        assert!(paths["/api/docs"]["get"].is_object());
+        assert!(paths["/api/tiles/prefetch"]["post"]["requestBody"].is_object());
+        assert!(paths["/api/tiles/prefetch"]["delete"].is_object());
+        // Path parameters use OpenAPI templates
+        assert!(paths.contains_key("/api/weather/{icao}"));
+        assert!(paths.keys().all(|p| !p.contains(':') && !p.contains('*')));
+        // Dev-only nested routes are left out
+        assert!(!paths.contains_key("/api/debug/tasks"));
+    }
+
+    #[test]
+    fn derives_schemas_from_serde_types() {
+        let spec = spec();
+        let schemas = &spec["components"]["schemas"];
+        let progress = &schemas["PrefetchProgress"]["properties"];
+        assert_eq!(progress["totalTiles"]["type"], "integer");
+        assert!(progress["error"]["type"].as_array().is_some_and(|t| t.contains(&"null".into())));
+
+        let codes = schemas["Error"]["properties"]["code"]["enum"].as_array().unwrap();
+        assert!(codes.contains(&"notFound".into()));
+
+        let mut found = Vec::new();
+        refs(&spec, &mut found);
+        for r in found {
+            let name = r.rsplit('/').next().unwrap();
+            let section = if r.starts_with("#/components/responses/") { "responses" } else { "schemas" };
+            assert!(spec["components"][section][name].is_object(), "unresolved {}", r);
+        }
+    }
+
+    #[test]
+    fn page_loads_the_spec() {
+        assert!(PAGE.contains("/api/docs/openapi.json"));
+    }
+}
@@ -0,0 +1,368 @@
+//! ILS data and approach path geometry per runway
+//!
+//! Localizer courses and glideslope angles are imported from X-Plane navdata
+//! (`earth_nav.dat`, LOC and GS records) into the airport database. For each
+//! runway end the backend serves the extended centerline, the localizer
+//! approach cone and glidepath altitudes, so approach corridors are drawn from
+//! real ILS data. Runways without ILS data fall back to the runway heading and
+//! a 3° glidepath.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use tauri::AppHandle;
+use tracing::info;
+
+use crate::airport_db;
+use crate::error::{Error, Result};
+use crate::geo;
+use crate::runways::{Runway, RunwayEnd};
+use crate::weather;
+
+/// Default glidepath angle (degrees)
+const DEFAULT_GLIDESLOPE_DEG: f64 = 3.0;
+
+/// Threshold crossing height (ft)
+const THRESHOLD_CROSSING_HEIGHT_FT: f64 = 50.0;
+
+/// Length of the extended centerline and approach cone (nm)
+const APPROACH_LENGTH_NM: f64 = 15.0;
+
+/// Half-width of the approach cone (degrees either side of the course)
+const CONE_HALF_ANGLE_DEG: f64 = 2.5;
+
+const FEET_PER_NM: f64 = 6076.12;
+
+/// ILS for a runway end
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ils {
+    pub airport: String,
+    pub runway: String,
+    pub ident: String,
+    pub frequency_mhz: f64,
+    /// Localizer course (degrees true)
+    pub course_true: f64,
+    /// Glideslope angle (None for localizer-only approaches)
+    pub glideslope_angle: Option<f64>,
+}
+
+/// Glidepath altitude at a distance from the threshold
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlidepathPoint {
+    pub distance_nm: f64,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Feet MSL
+    pub altitude_ft: f64,
+}
+
+/// Approach path to a runway end
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApproachPath {
+    /// Runway end (e.g., "27R")
+    pub runway: String,
+    /// ILS, if known
+    pub ils: Option<Ils>,
+    /// Final approach course (degrees true)
+    pub course_true: f64,
+    pub glideslope_angle: f64,
+    /// Landing threshold (after any displacement)
+    pub threshold_latitude: f64,
+    pub threshold_longitude: f64,
+    pub threshold_elevation_ft: f64,
+    /// Extended centerline from the threshold outwards, [longitude, latitude] pairs
+    pub centerline: Vec<[f64; 2]>,
+    /// Approach cone polygon (closed ring), [longitude, latitude] pairs
+    pub cone: Vec<[f64; 2]>,
+    /// Glidepath altitudes every nautical mile
+    pub glidepath: Vec<GlidepathPoint>,
+}
+
+fn ensure_schema(conn: &Connection) -> Result<(), String> {
+    // Kept outside the bundled-data tables so rebuilding those keeps imported navdata
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS ils (
+             airport TEXT NOT NULL,
+             runway TEXT NOT NULL,
+             ident TEXT NOT NULL,
+             frequency_mhz REAL NOT NULL,
+             course_true REAL NOT NULL,
+             glideslope_angle REAL,
+             PRIMARY KEY (airport, runway)
+         );",
+    )
+    .map_err(|e| format!("Failed to create ILS table: {}", e))
+}
+
+/// Parse LOC (4, 5) and GS (6) records from X-Plane earth_nav.dat (810 or 11xx format)
+pub fn parse_earth_nav(text: &str) -> Vec<Ils> {
+    let mut localizers: HashMap<(String, String), Ils> = HashMap::new();
+    let mut glideslopes: HashMap<(String, String), f64> = HashMap::new();
+
+    for line in text.lines() {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let Some(code) = tokens.first().and_then(|c| c.parse::<u32>().ok()) else {
+            continue;
+        };
+        if !matches!(code, 4..=6) || tokens.len() < 10 {
+            continue;
+        }
+        // 11xx adds a region code before the runway; 810 has the runway directly
+        let runway_index = if tokens[9].starts_with(|c: char| c.is_ascii_digit()) {
+            9
+        } else {
+            10
+        };
+        let (Some(runway), Ok(frequency), Ok(bearing)) = (
+            tokens.get(runway_index),
+            tokens[4].parse::<f64>(),
+            tokens[6].parse::<f64>(),
+        ) else {
+            continue;
+        };
+        let key = (tokens[8].to_uppercase(), runway.to_uppercase());
+
+        if code == 6 {
+            // Glideslope angle (hundredths of a degree) packed in front of the course: 300270.100
+            glideslopes.insert(key, (bearing / 1000.0).floor() / 100.0);
+        } else {
+            localizers.insert(
+                key.clone(),
+                Ils {
+                    airport: key.0,
+                    runway: key.1,
+                    ident: tokens[7].to_string(),
+                    frequency_mhz: frequency / 100.0,
+                    course_true: bearing.rem_euclid(360.0),
+                    glideslope_angle: None,
+                },
+            );
+        }
+    }
+
+    let mut ils: Vec<Ils> = localizers
+        .into_iter()
+        .map(|(key, mut ils)| {
+            ils.glideslope_angle = glideslopes.get(&key).copied().filter(|a| *a > 0.0);
+            ils
+        })
+        .collect();
+    ils.sort_by(|a, b| (&a.airport, &a.runway).cmp(&(&b.airport, &b.runway)));
+    ils
+}
+
+/// Replace the stored ILS data
+fn store_ils(conn: &Connection, ils: &[Ils]) -> Result<(), String> {
+    ensure_schema(conn)?;
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    tx.execute("DELETE FROM ils", []).map_err(|e| e.to_string())?;
+    {
+        let mut insert = tx
+            .prepare("INSERT OR REPLACE INTO ils VALUES (?1, ?2, ?3, ?4, ?5, ?6)")
+            .map_err(|e| e.to_string())?;
+        for i in ils {
+            insert
+                .execute(params![
+                    i.airport,
+                    i.runway,
+                    i.ident,
+                    i.frequency_mhz,
+                    i.course_true,
+                    i.glideslope_angle
+                ])
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())
+}
+
+fn read_ils(conn: &Connection, icao: &str) -> Result<Vec<Ils>, String> {
+    ensure_schema(conn)?;
+    let mut stmt = conn
+        .prepare_cached("SELECT * FROM ils WHERE airport = ?1")
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![icao], |row| {
+            Ok(Ils {
+                airport: row.get(0)?,
+                runway: row.get(1)?,
+                ident: row.get(2)?,
+                frequency_mhz: row.get(3)?,
+                course_true: row.get(4)?,
+                glideslope_angle: row.get(5)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+    rows.collect::<Result<_, _>>().map_err(|e| e.to_string())
+}
+
+/// Approach path to one runway end, landing towards `end` from the `far_end` side
+fn approach_path(
+    end: &RunwayEnd,
+    far_end: &RunwayEnd,
+    field_elevation_ft: f64,
+    ils: Option<Ils>,
+) -> Option<ApproachPath> {
+    let (lat, lon) = (end.latitude?, end.longitude?);
+    let runway_heading = end.heading_true.or_else(|| {
+        let (far_lat, far_lon) = (far_end.latitude?, far_end.longitude?);
+        Some(geo::distance_and_bearing(lat, lon, far_lat, far_lon).1)
+    })?;
+
+    // Landing threshold is displaced along the runway
+    let (threshold_latitude, threshold_longitude) =
+        geo::destination(lat, lon, runway_heading, end.displaced_threshold_ft / FEET_PER_NM);
+    let threshold_elevation_ft = end.elevation_ft.unwrap_or(field_elevation_ft);
+
+    let course_true = ils.as_ref().map(|i| i.course_true).unwrap_or(runway_heading);
+    let glideslope_angle = ils
+        .as_ref()
+        .and_then(|i| i.glideslope_angle)
+        .unwrap_or(DEFAULT_GLIDESLOPE_DEG);
+    let outbound = (course_true + 180.0).rem_euclid(360.0);
+    let at = |bearing: f64, distance_nm: f64| {
+        let (lat, lon) = geo::destination(threshold_latitude, threshold_longitude, bearing, distance_nm);
+        [lon, lat]
+    };
+
+    let glidepath = (0..=APPROACH_LENGTH_NM as u32)
+        .map(|nm| {
+            let distance_nm = nm as f64;
+            let [longitude, latitude] = at(outbound, distance_nm);
+            let height = THRESHOLD_CROSSING_HEIGHT_FT + distance_nm * FEET_PER_NM * glideslope_angle.to_radians().tan();
+            GlidepathPoint {
+                distance_nm,
+                latitude,
+                longitude,
+                altitude_ft: (threshold_elevation_ft + height).round(),
+            }
+        })
+        .collect();
+
+    let origin = [threshold_longitude, threshold_latitude];
+    Some(ApproachPath {
+        runway: end.ident.clone(),
+        ils,
+        course_true,
+        glideslope_angle,
+        threshold_latitude,
+        threshold_longitude,
+        threshold_elevation_ft,
+        centerline: vec![origin, at(outbound, APPROACH_LENGTH_NM)],
+        cone: vec![
+            origin,
+            at(outbound - CONE_HALF_ANGLE_DEG, APPROACH_LENGTH_NM),
+            at(outbound + CONE_HALF_ANGLE_DEG, APPROACH_LENGTH_NM),
+            origin,
+        ],
+        glidepath,
+    })
+}
+
+/// Approach paths to every runway end at an airport
+pub fn approaches_for(runways: &[Runway], ils: Vec<Ils>, field_elevation_ft: f64) -> Vec<ApproachPath> {
+    let mut ils: HashMap<String, Ils> = ils.into_iter().map(|i| (i.runway.clone(), i)).collect();
+    runways
+        .iter()
+        .flat_map(|r| [(&r.low_end, &r.high_end), (&r.high_end, &r.low_end)])
+        .filter_map(|(end, far_end)| {
+            let ils = ils.remove(&end.ident.to_uppercase());
+            approach_path(end, far_end, field_elevation_ft, ils)
+        })
+        .collect()
+}
+
+fn get_approaches_blocking(app: &AppHandle, icao: &str) -> Result<Option<Vec<ApproachPath>>> {
+    let Some(airport) = airport_db::get_airport_blocking(app, icao).map_err(Error::io)? else {
+        return Ok(None);
+    };
+    let ils = airport_db::with_db(app, |conn| read_ils(conn, &airport.icao)).map_err(Error::io)?;
+    Ok(Some(approaches_for(&airport.runways, ils, airport.elevation_ft)))
+}
+
+/// Approach paths at an airport (None if the airport is unknown)
+pub async fn get_approaches(app: &AppHandle, icao: &str) -> Result<Option<Vec<ApproachPath>>> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || get_approaches_blocking(&app, &icao))
+        .await
+        .map_err(|e| Error::Internal(format!("Approach lookup failed: {}", e)))?
+}
+
+/// Import ILS data from X-Plane earth_nav.dat, replacing previously imported data
+pub fn import_earth_nav(app: &AppHandle, path: &Path) -> Result<usize> {
+    let bytes = fs::read(path).map_err(|e| Error::Io(format!("Failed to read {:?}: {}", path, e)))?;
+    let ils = parse_earth_nav(&String::from_utf8_lossy(&bytes));
+    if ils.is_empty() {
+        return Err(Error::invalid_input("No localizers found (expected X-Plane earth_nav.dat)"));
+    }
+    airport_db::with_db(app, |conn| store_ils(conn, &ils)).map_err(Error::io)?;
+    info!("[Approach] Imported {} ILS/localizer approaches", ils.len());
+    Ok(ils.len())
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get extended centerlines, approach cones and glidepaths for every runway end at an airport
+#[tauri::command]
+pub async fn get_airport_approaches(app: AppHandle, icao: String) -> Result<Option<Vec<ApproachPath>>> {
+    get_approaches(&app, &icao).await
+}
+
+/// Import localizer courses and glideslope angles from X-Plane earth_nav.dat
+#[tauri::command]
+pub async fn import_ils_navdata(app: AppHandle, path: String) -> Result<usize> {
+    tauri::async_runtime::spawn_blocking(move || import_earth_nav(&app, Path::new(&path)))
+        .await
+        .map_err(|e| Error::Internal(format!("Navdata import failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runways;
+
+    #[test]
+    fn builds_approach_paths() {
+        let nav = "I\n1100 Version\n\
+            4  42.35569 -70.98975 19 11030 18 270.100 IBOS KBOS K6 27 ILS-cat-I\n\
+            6  42.35830 -71.00860 19 11030 10 300270.100 IBOS KBOS K6 27 GS\n\
+            4  47.43538889 -122.29776389 354 11030 18 179.640 ISNQ KSEA 16L ILS-cat-I\n";
+        let ils = parse_earth_nav(nav);
+        assert_eq!(ils.len(), 2);
+        assert_eq!((ils[0].airport.as_str(), ils[0].runway.as_str()), ("KBOS", "27"));
+        assert_eq!(ils[0].frequency_mhz, 110.3);
+        assert_eq!(ils[0].glideslope_angle, Some(3.0));
+        assert_eq!(ils[1].runway, "16L");
+        assert_eq!(ils[1].glideslope_angle, None);
+
+        // Localizer for runway 18 of the test runway
+        let localizer = Ils {
+            airport: "KXYZ".to_string(),
+            runway: "18".to_string(),
+            course_true: 180.1,
+            ..ils[0].clone()
+        };
+        let approaches = approaches_for(&[runways::test_runway()], vec![localizer], 20.0);
+        assert_eq!(approaches.len(), 2);
+
+        // Runway 36 has no ILS: runway heading, 3° default
+        assert!(approaches[0].ils.is_none());
+        assert_eq!(approaches[0].course_true, 0.0);
+        // Runway 18: localizer course, approach from the north
+        let rwy18 = &approaches[1];
+        assert_eq!(rwy18.course_true, 180.1);
+        assert!(rwy18.centerline[1][1] > 42.03);
+        // ~318 ft per nm on a 3° glidepath
+        assert_eq!(rwy18.glidepath[0].altitude_ft, 70.0);
+        assert_eq!(rwy18.glidepath[10].altitude_ft, 3254.0);
+    }
+}
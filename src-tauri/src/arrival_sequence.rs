@@ -0,0 +1,292 @@
+//! Arrival sequencing with threshold ETAs
+//!
+//! After every traffic poll, airborne aircraft filed to the active airport
+//! (or already lined up on one of its finals) within 40 nm are assigned a
+//! runway end and an estimated time over its threshold. Aircraft on a final
+//! keep that runway and fly the remaining distance along the centerline;
+//! others are assigned the suggested runway in use (see `runway_suggestion`,
+//! any runway end in calm wind) with the shortest path via a point 5 nm out
+//! on its final. The ETA assumes groundspeed reduces steadily to the type's
+//! approach speed (see `aircraft_types`, 140 kt if unknown) by the threshold.
+//! The ordered list per runway is published as `arrival-sequence` and over
+//! `/api/arrivals/ws`.
+
+use std::collections::BTreeMap;
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+
+use crate::aircraft_types;
+use crate::geo;
+use crate::runways::{self, Runway, RunwayEnd};
+use crate::traffic::{Aircraft, TrafficSnapshot};
+
+/// Inbound aircraft further than this from the airport are not sequenced (nm)
+const SEQUENCE_RADIUS_NM: f64 = 40.0;
+
+/// Final approach corridor: offset from the extended centerline and length (nm)
+const FINAL_HALF_WIDTH_NM: f64 = 1.0;
+const FINAL_LENGTH_NM: f64 = 20.0;
+
+/// Aircraft not yet on final join it this far from the threshold (nm)
+const FINAL_FIX_NM: f64 = 5.0;
+
+/// Groundspeed over the threshold for types without a known approach speed (knots)
+const APPROACH_SPEED_KT: f64 = 140.0;
+
+/// Arrival in a runway's sequence
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SequencedArrival {
+    pub callsign: String,
+    pub aircraft_type: Option<String>,
+    /// Distance still to fly to the threshold (nm)
+    pub distance_nm: f64,
+    pub groundspeed_kt: f64,
+    /// Estimated time over the threshold (Unix ms)
+    pub eta: u64,
+    /// Seconds until the threshold
+    pub eta_secs: u32,
+    /// Lined up on the runway's final
+    pub on_final: bool,
+    /// Seconds behind the previous arrival
+    pub interval_secs: Option<u32>,
+}
+
+/// Arrivals to one runway end, first to land first
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunwaySequence {
+    pub runway: String,
+    pub arrivals: Vec<SequencedArrival>,
+}
+
+/// Arrival sequence at the airport
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArrivalSequence {
+    pub icao: Option<String>,
+    /// Time of the traffic picture (Unix ms)
+    pub updated_at: Option<u64>,
+    pub runways: Vec<RunwaySequence>,
+}
+
+/// Arrival sequence state (managed by Tauri)
+pub struct ArrivalSequenceState {
+    sequence: RwLock<ArrivalSequence>,
+    /// Sequence updates for WebSocket relay
+    pub events: broadcast::Sender<ArrivalSequence>,
+}
+
+impl ArrivalSequenceState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            sequence: RwLock::new(ArrivalSequence::default()),
+            events,
+        }
+    }
+}
+
+/// Seconds to fly a distance while slowing steadily to approach speed
+fn time_to_threshold_secs(distance_nm: f64, groundspeed_kt: f64, approach_speed_kt: f64) -> f64 {
+    let average_kt = (groundspeed_kt.max(approach_speed_kt) + approach_speed_kt) / 2.0;
+    distance_nm / average_kt * 3600.0
+}
+
+/// Distance to fly to a runway end's threshold via its final fix (nm)
+fn distance_via_final(aircraft: &Aircraft, end: &RunwayEnd) -> Option<f64> {
+    let (lat, lon) = (end.latitude?, end.longitude?);
+    let inbound = (end.heading_true? + 180.0).rem_euclid(360.0);
+    let (fix_lat, fix_lon) = geo::destination(lat, lon, inbound, FINAL_FIX_NM);
+    let (to_fix, _) = geo::distance_and_bearing(aircraft.latitude, aircraft.longitude, fix_lat, fix_lon);
+    Some(to_fix + FINAL_FIX_NM)
+}
+
+/// Runway end and distance to its threshold for an inbound aircraft
+fn assign(aircraft: &Aircraft, runways: &[Runway], active: &[String]) -> Option<(String, f64, bool)> {
+    if let Some(alignment) = runways::aligned_runway_end(
+        runways,
+        aircraft.latitude,
+        aircraft.longitude,
+        aircraft.track.unwrap_or(aircraft.heading),
+        FINAL_HALF_WIDTH_NM,
+        FINAL_LENGTH_NM,
+        0.0,
+    )
+    .filter(|a| a.along_nm <= 0.0)
+    {
+        return Some((alignment.end.ident.clone(), -alignment.along_nm, true));
+    }
+
+    runways
+        .iter()
+        .flat_map(|r| [&r.low_end, &r.high_end])
+        .filter(|end| active.is_empty() || active.contains(&end.ident))
+        .filter_map(|end| distance_via_final(aircraft, end).map(|d| (end.ident.clone(), d, false)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+/// Sequence inbound aircraft per runway end. `active` are the runway ends in
+/// use (empty = any); `approach_speed` gives an aircraft's speed over the
+/// threshold (knots).
+pub fn compute(
+    aircraft: &[Aircraft],
+    icao: &str,
+    runways: &[Runway],
+    active: &[String],
+    approach_speed: impl Fn(&Aircraft) -> f64,
+    now: u64,
+) -> Vec<RunwaySequence> {
+    let mut sequences: BTreeMap<String, Vec<SequencedArrival>> = BTreeMap::new();
+    for a in aircraft
+        .iter()
+        .filter(|a| !a.on_ground && a.distance_nm <= SEQUENCE_RADIUS_NM)
+    {
+        let Some((runway, distance_nm, on_final)) = assign(a, runways, active) else {
+            continue;
+        };
+        // Overflights and departures only count once they are lined up
+        if !on_final && a.arrival.as_deref() != Some(icao) {
+            continue;
+        }
+        let secs = time_to_threshold_secs(distance_nm, a.groundspeed_kt, approach_speed(a));
+        sequences.entry(runway).or_default().push(SequencedArrival {
+            callsign: a.callsign.clone(),
+            aircraft_type: a.aircraft_type.clone(),
+            distance_nm: (distance_nm * 10.0).round() / 10.0,
+            groundspeed_kt: a.groundspeed_kt,
+            eta: now + (secs * 1000.0) as u64,
+            eta_secs: secs.round() as u32,
+            on_final,
+            interval_secs: None,
+        });
+    }
+
+    sequences
+        .into_iter()
+        .map(|(runway, mut arrivals)| {
+            arrivals.sort_by_key(|a| a.eta);
+            for i in 1..arrivals.len() {
+                arrivals[i].interval_secs = Some(arrivals[i].eta_secs - arrivals[i - 1].eta_secs);
+            }
+            RunwaySequence { runway, arrivals }
+        })
+        .collect()
+}
+
+fn publish(app: &AppHandle, sequence: ArrivalSequence) {
+    let _ = app.emit("arrival-sequence", &sequence);
+    let state = app.state::<ArrivalSequenceState>();
+    let _ = state.events.send(sequence.clone());
+    *state.sequence.write() = sequence;
+}
+
+/// Update the arrival sequence from a traffic poll
+pub fn update(app: &AppHandle, snapshot: &TrafficSnapshot, runways: &[Runway], active: &[String]) {
+    let Some(icao) = snapshot.icao.as_deref() else {
+        return;
+    };
+    let now = snapshot.updated_at.unwrap_or_default();
+    let approach_speed = |a: &Aircraft| {
+        a.aircraft_type
+            .as_deref()
+            .and_then(|t| aircraft_types::get(app, t))
+            .and_then(|t| t.approach_speed_kt)
+            .unwrap_or(APPROACH_SPEED_KT)
+    };
+    let runways = compute(&snapshot.aircraft, icao, runways, active, approach_speed, now);
+
+    let current = get_sequence(app);
+    if runways.is_empty() && current.runways.is_empty() && current.icao == snapshot.icao {
+        return;
+    }
+    publish(
+        app,
+        ArrivalSequence {
+            icao: snapshot.icao.clone(),
+            updated_at: snapshot.updated_at,
+            runways,
+        },
+    );
+}
+
+/// Drop the sequence (no active airport or traffic)
+pub fn clear(app: &AppHandle) {
+    let current = get_sequence(app);
+    if current.icao.is_some() || !current.runways.is_empty() {
+        publish(app, ArrivalSequence::default());
+    }
+}
+
+/// Current arrival sequence
+pub fn get_sequence(app: &AppHandle) -> ArrivalSequence {
+    app.state::<ArrivalSequenceState>().sequence.read().clone()
+}
+
+/// Initialize arrival sequence state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_arrival_sequence(app: &AppHandle) {
+    app.manage(ArrivalSequenceState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the arrival sequence per runway at the active airport
+#[tauri::command]
+pub fn get_arrival_sequence(app: AppHandle) -> ArrivalSequence {
+    get_sequence(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traffic::test_aircraft;
+
+    #[test]
+    fn orders_arrivals_by_threshold_eta() {
+        let runway = runways::test_runway();
+        let aircraft = |callsign: &str, latitude: f64, longitude: f64, track: f64, arrival: &str| Aircraft {
+            latitude,
+            longitude,
+            heading: track,
+            track: Some(track),
+            arrival: Some(arrival.to_string()),
+            distance_nm: 15.0,
+            ..test_aircraft(callsign)
+        };
+        let runways = [runway];
+        let active = ["36".to_string()];
+
+        let traffic = vec![
+            // 8 nm on final: 8 nm at an average of 160 kt = 180 s
+            aircraft("FINAL", 42.0 - 8.0 / 60.0, -71.0, 0.0, "KXYZ"),
+            // 10 nm east of the 5 nm final fix, heading west
+            aircraft(
+                "BASE",
+                42.0 - 5.0 / 60.0,
+                -71.0 + 10.0 / (60.0 * 42f64.to_radians().cos()),
+                270.0,
+                "KXYZ",
+            ),
+            // Filed elsewhere and not lined up
+            aircraft("OVER", 42.2, -71.0, 90.0, "KABC"),
+        ];
+        let sequences = compute(&traffic, "KXYZ", &runways, &active, |_| APPROACH_SPEED_KT, 0);
+        assert_eq!(sequences.len(), 1);
+        assert_eq!(sequences[0].runway, "36");
+        let arrivals = &sequences[0].arrivals;
+        assert_eq!(arrivals.len(), 2);
+        assert_eq!(arrivals[0].callsign, "FINAL");
+        assert!(arrivals[0].on_final);
+        assert_eq!(arrivals[0].eta_secs, 180);
+        assert_eq!(arrivals[1].callsign, "BASE");
+        assert!(!arrivals[1].on_final);
+        assert!((arrivals[1].distance_nm - 15.0).abs() < 0.2);
+        assert_eq!(arrivals[1].interval_secs, Some(arrivals[1].eta_secs - 180));
+    }
+}
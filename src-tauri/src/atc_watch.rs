@@ -0,0 +1,330 @@
+//! Controller-online notifications
+//!
+//! After every traffic poll, the VATSIM positions covering the active airport
+//! (the airport's own positions plus approach, center and FSS above it, see
+//! vatspy.rs) are compared with the previous poll. Positions that come online
+//! or go offline are published to the desktop UI (`atc-change` event) and
+//! remote browsers (`/api/atc/ws`), and the recent changes are kept for
+//! `/api/atc/changes`, so top-down coverage changes are noticed.
+//!
+//! Optionally each change is also posted to a webhook (the payload works with
+//! Discord and Slack incoming webhooks) and shown as a desktop notification.
+//! The first poll for an airport only sets the baseline, so switching
+//! airports or starting the app doesn't announce everyone already online.
+
+use std::collections::{HashSet, VecDeque};
+use std::time::{Duration, SystemTime};
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+use crate::http_client;
+use crate::notifications;
+use crate::supervisor::{self, Restart};
+use crate::traffic;
+use crate::vatspy::{self, CoveringPosition};
+
+/// Number of recent changes kept
+const MAX_CHANGES: usize = 50;
+
+/// Controller notification settings in the global settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AtcNotificationSettings {
+    /// Show a desktop notification on the host for each change (default: false)
+    pub desktop: bool,
+    /// POST each change to this URL (Discord/Slack compatible)
+    pub webhook_url: Option<String>,
+    /// Position levels to notify about ("delivery", "ground", "tower",
+    /// "approach", "center", "fss", "atis"); events are sent for every level
+    pub levels: Vec<String>,
+}
+
+impl Default for AtcNotificationSettings {
+    fn default() -> Self {
+        Self {
+            desktop: false,
+            webhook_url: None,
+            levels: ["delivery", "ground", "tower", "approach", "center", "fss"]
+                .iter()
+                .map(|l| l.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// A position covering the airport came online or went offline
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AtcChange {
+    pub icao: String,
+    #[serde(flatten)]
+    pub position: CoveringPosition,
+    /// true: came online, false: went offline
+    pub online: bool,
+    /// Unix ms
+    pub time: u64,
+}
+
+/// Positions online at the watched airport, with recent changes
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AtcWatchStatus {
+    pub icao: Option<String>,
+    pub online: Vec<CoveringPosition>,
+    /// Most recent first
+    pub changes: Vec<AtcChange>,
+}
+
+/// Controller watch state (managed by Tauri)
+pub struct AtcWatchState {
+    /// Airport and the positions online at the last poll
+    online: RwLock<Option<(String, Vec<CoveringPosition>)>>,
+    changes: RwLock<VecDeque<AtcChange>>,
+    /// Changes for WebSocket relay
+    pub events: broadcast::Sender<AtcChange>,
+}
+
+impl AtcWatchState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(32);
+        Self {
+            online: RwLock::new(None),
+            changes: RwLock::new(VecDeque::new()),
+            events,
+        }
+    }
+}
+
+impl Default for AtcWatchState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Positions that came online and went offline between two polls (by callsign)
+pub fn diff(previous: &[CoveringPosition], current: &[CoveringPosition]) -> Vec<(CoveringPosition, bool)> {
+    let before: HashSet<&str> = previous.iter().map(|p| p.callsign.as_str()).collect();
+    let after: HashSet<&str> = current.iter().map(|p| p.callsign.as_str()).collect();
+    let online = current
+        .iter()
+        .filter(|p| !before.contains(p.callsign.as_str()))
+        .map(|p| (p.clone(), true));
+    let offline = previous
+        .iter()
+        .filter(|p| !after.contains(p.callsign.as_str()))
+        .map(|p| (p.clone(), false));
+    online.chain(offline).collect()
+}
+
+/// One-line description of a change ("BOS_APP (Boston Approach, 118.250) is now online at KBOS")
+pub fn describe(change: &AtcChange) -> String {
+    let position = &change.position;
+    let details: Vec<&str> = [position.name.as_str(), position.frequency.as_str()]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect();
+    let details = if details.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", details.join(", "))
+    };
+    format!(
+        "{}{} {} at {}",
+        position.callsign,
+        details,
+        if change.online { "is now online" } else { "went offline" },
+        change.icao
+    )
+}
+
+fn notification_settings(app: &AppHandle) -> AtcNotificationSettings {
+    crate::read_global_settings(app.clone())
+        .map(|s| s.atc_notifications)
+        .unwrap_or_default()
+}
+
+async fn post_webhook(url: &str, change: &AtcChange) -> Result<(), String> {
+    let text = describe(change);
+    let response = http_client::client()
+        .post(url)
+        .timeout(Duration::from_secs(10))
+        .json(&json!({ "content": text, "text": text, "change": change }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to post webhook: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Webhook returned HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+fn notify(app: &AppHandle, settings: &AtcNotificationSettings, change: &AtcChange) {
+    if !settings.levels.iter().any(|l| *l == change.position.level) {
+        return;
+    }
+    if settings.desktop {
+        let title = format!(
+            "{} {}",
+            change.position.callsign,
+            if change.online { "online" } else { "offline" }
+        );
+        notifications::show(app, &title, &describe(change));
+    }
+    if let Some(url) = settings.webhook_url.clone().filter(|u| !u.trim().is_empty()) {
+        let change = change.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = post_webhook(&url, &change).await {
+                warn!("[AtcWatch] {}", e);
+            }
+        });
+    }
+}
+
+/// Compare the positions covering an airport with the last poll and publish changes
+pub async fn update(app: &AppHandle, icao: &str) {
+    let online = match vatspy::get_coverage(app, icao).await {
+        Ok(coverage) => coverage.online,
+        Err(e) => {
+            debug!("[AtcWatch] {}", e);
+            return;
+        }
+    };
+    let state = app.state::<AtcWatchState>();
+    let previous = state.online.write().replace((icao.to_string(), online.clone()));
+    let Some((previous_icao, previous)) = previous else {
+        return;
+    };
+    if previous_icao != icao {
+        state.changes.write().clear();
+        return;
+    }
+
+    let changes = diff(&previous, &online);
+    if changes.is_empty() {
+        return;
+    }
+    let settings = notification_settings(app);
+    let time = now_ms();
+    for (position, is_online) in changes {
+        let change = AtcChange {
+            icao: icao.to_string(),
+            position,
+            online: is_online,
+            time,
+        };
+        info!("[AtcWatch] {}", describe(&change));
+        {
+            let mut recent = state.changes.write();
+            recent.push_front(change.clone());
+            recent.truncate(MAX_CHANGES);
+        }
+        let _ = app.emit("atc-change", &change);
+        let _ = state.events.send(change.clone());
+        notify(app, &settings, &change);
+    }
+}
+
+/// Stop watching (no active airport)
+pub fn clear(app: &AppHandle) {
+    let state = app.state::<AtcWatchState>();
+    *state.online.write() = None;
+    state.changes.write().clear();
+}
+
+/// Positions online at the watched airport and recent changes
+pub fn get_status(app: &AppHandle) -> AtcWatchStatus {
+    let state = app.state::<AtcWatchState>();
+    let online = state.online.read().clone();
+    AtcWatchStatus {
+        icao: online.as_ref().map(|(icao, _)| icao.clone()),
+        online: online.map(|(_, positions)| positions).unwrap_or_default(),
+        changes: state.changes.read().iter().cloned().collect(),
+    }
+}
+
+/// Initialize controller watch state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_atc_watch(app: &AppHandle) {
+    app.manage(AtcWatchState::new());
+}
+
+/// Compare coverage after every traffic poll. The coverage lookup can take a
+/// while, so it runs here instead of in the poll.
+pub fn start_atc_watch_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn("atc-watch", Restart::Always, move || {
+        let app = app.clone();
+        let mut polls = traffic::subscribe_polls(&app);
+        async move {
+            while polls.changed().await.is_ok() {
+                let polled = polls.borrow_and_update().clone();
+                match polled {
+                    Some(polled) => update(&app, &polled.icao).await,
+                    None => clear(&app),
+                }
+            }
+        }
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the positions online at the active airport and recent online/offline changes
+#[tauri::command]
+pub fn get_atc_changes(app: AppHandle) -> AtcWatchStatus {
+    get_status(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(callsign: &str, level: &str) -> CoveringPosition {
+        CoveringPosition {
+            callsign: callsign.to_string(),
+            name: "Boston Approach".to_string(),
+            frequency: "118.250".to_string(),
+            level: level.to_string(),
+            facility: "A90".to_string(),
+        }
+    }
+
+    #[test]
+    fn finds_positions_coming_online_and_going_offline() {
+        let previous = [position("BOS_TWR", "tower"), position("BOS_APP", "approach")];
+        let current = [position("BOS_APP", "approach"), position("BOS_CTR", "center")];
+        let changes = diff(&previous, &current);
+        assert_eq!(changes.len(), 2);
+        assert_eq!((changes[0].0.callsign.as_str(), changes[0].1), ("BOS_CTR", true));
+        assert_eq!((changes[1].0.callsign.as_str(), changes[1].1), ("BOS_TWR", false));
+        assert!(diff(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn describes_changes() {
+        let change = AtcChange {
+            icao: "KBOS".to_string(),
+            position: position("BOS_APP", "approach"),
+            online: true,
+            time: 0,
+        };
+        assert_eq!(
+            describe(&change),
+            "BOS_APP (Boston Approach, 118.250) is now online at KBOS"
+        );
+    }
+}
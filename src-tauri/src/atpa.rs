@@ -0,0 +1,306 @@
+//! ATPA-style final approach spacing
+//!
+//! After every traffic poll, airborne arrivals lined up with a runway (within
+//! 1 nm of the extended centerline, up to 15 nm out) are sequenced per
+//! runway end by distance to the threshold. Each follower gets its in-trail
+//! distance to the aircraft ahead, the required separation (ICAO wake minimum
+//! or minimum radar separation, see `wake`) and the predicted spacing when the
+//! leader crosses the threshold at current groundspeeds. Spacing predicted
+//! below the minimum is a caution, spacing already below it a warning.
+//! Published as `atpa-update` and over `/api/atpa/ws`.
+
+use std::collections::BTreeMap;
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::info;
+
+use crate::runways::{self, Runway};
+use crate::traffic::{Aircraft, TrafficSnapshot};
+use crate::wake;
+
+/// Final approach corridor: offset from the extended centerline and length (nm)
+const CORRIDOR_HALF_WIDTH_NM: f64 = 1.0;
+const CORRIDOR_LENGTH_NM: f64 = 15.0;
+
+/// Leaders slower than this are not used for predictions (knots)
+const MIN_PREDICTION_GROUNDSPEED_KT: f64 = 60.0;
+
+/// Spacing status
+pub const STATUS_OK: &str = "ok";
+pub const STATUS_CAUTION: &str = "caution";
+pub const STATUS_WARNING: &str = "warning";
+
+/// Arrival on a final approach
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalSpacing {
+    pub callsign: String,
+    pub aircraft_type: Option<String>,
+    /// ICAO wake category (J, H, M or L)
+    pub wake_category: String,
+    pub distance_to_threshold_nm: f64,
+    pub groundspeed_kt: f64,
+    /// Aircraft ahead on the same final
+    pub leader: Option<String>,
+    /// Distance behind the leader along the final
+    pub in_trail_nm: Option<f64>,
+    /// Wake minimum or minimum radar separation behind the leader
+    pub required_nm: Option<f64>,
+    /// Spacing when the leader crosses the threshold at current groundspeeds
+    pub predicted_nm: Option<f64>,
+    /// "ok", "caution" (compression below minima predicted) or "warning" (below minima)
+    pub status: String,
+}
+
+/// Arrivals on one runway end's final, closest first
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalApproach {
+    pub runway: String,
+    pub arrivals: Vec<FinalSpacing>,
+}
+
+/// Final approach spacing at the airport
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AtpaSnapshot {
+    pub icao: Option<String>,
+    /// Time of the traffic picture (Unix ms)
+    pub updated_at: Option<u64>,
+    pub finals: Vec<FinalApproach>,
+}
+
+/// ATPA state (managed by Tauri)
+pub struct AtpaState {
+    snapshot: RwLock<AtpaSnapshot>,
+    /// Spacing updates for WebSocket relay
+    pub events: broadcast::Sender<AtpaSnapshot>,
+}
+
+impl AtpaState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            snapshot: RwLock::new(AtpaSnapshot::default()),
+            events,
+        }
+    }
+}
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+/// Spacing behind a leader: in-trail, required and predicted distance, and status
+fn spacing(leader: &FinalSpacing, follower: &FinalSpacing, required_nm: f64) -> (f64, Option<f64>, &'static str) {
+    let in_trail = follower.distance_to_threshold_nm - leader.distance_to_threshold_nm;
+    let predicted = (leader.groundspeed_kt >= MIN_PREDICTION_GROUNDSPEED_KT).then(|| {
+        let hours = leader.distance_to_threshold_nm / leader.groundspeed_kt;
+        in_trail - (follower.groundspeed_kt - leader.groundspeed_kt) * hours
+    });
+    let status = if in_trail < required_nm {
+        STATUS_WARNING
+    } else if predicted.is_some_and(|p| p < required_nm) {
+        STATUS_CAUTION
+    } else {
+        STATUS_OK
+    };
+    (in_trail, predicted, status)
+}
+
+/// Sequence airborne arrivals on each final and compute their spacing.
+/// `categories` gives the wake category of an aircraft.
+pub fn compute(
+    aircraft: &[Aircraft],
+    runways: &[Runway],
+    categories: impl Fn(&Aircraft) -> char,
+) -> Vec<FinalApproach> {
+    let mut finals: BTreeMap<String, Vec<FinalSpacing>> = BTreeMap::new();
+    for a in aircraft.iter().filter(|a| !a.on_ground) {
+        let track = a.track.unwrap_or(a.heading);
+        let Some(alignment) = runways::aligned_runway_end(
+            runways,
+            a.latitude,
+            a.longitude,
+            track,
+            CORRIDOR_HALF_WIDTH_NM,
+            CORRIDOR_LENGTH_NM,
+            0.0,
+        )
+        .filter(|al| al.along_nm <= 0.0) else {
+            continue;
+        };
+        finals
+            .entry(alignment.end.ident.clone())
+            .or_default()
+            .push(FinalSpacing {
+                callsign: a.callsign.clone(),
+                aircraft_type: a.aircraft_type.clone(),
+                wake_category: categories(a).to_string(),
+                distance_to_threshold_nm: round(-alignment.along_nm),
+                groundspeed_kt: a.groundspeed_kt,
+                leader: None,
+                in_trail_nm: None,
+                required_nm: None,
+                predicted_nm: None,
+                status: STATUS_OK.to_string(),
+            });
+    }
+
+    finals
+        .into_iter()
+        .map(|(runway, mut arrivals)| {
+            arrivals.sort_by(|a, b| a.distance_to_threshold_nm.total_cmp(&b.distance_to_threshold_nm));
+            for i in 1..arrivals.len() {
+                let (ahead, behind) = arrivals.split_at_mut(i);
+                let (leader, follower) = (&ahead[i - 1], &mut behind[0]);
+                let leader_category = leader.wake_category.chars().next().unwrap_or('M');
+                let follower_category = follower.wake_category.chars().next().unwrap_or('M');
+                let required = wake::required_separation_nm(leader_category, follower_category);
+                let (in_trail, predicted, status) = spacing(leader, follower, required);
+                follower.leader = Some(leader.callsign.clone());
+                follower.in_trail_nm = Some(round(in_trail));
+                follower.required_nm = Some(required);
+                follower.predicted_nm = predicted.map(round);
+                follower.status = status.to_string();
+            }
+            FinalApproach { runway, arrivals }
+        })
+        .collect()
+}
+
+fn publish(app: &AppHandle, snapshot: AtpaSnapshot) {
+    let _ = app.emit("atpa-update", &snapshot);
+    let state = app.state::<AtpaState>();
+    let _ = state.events.send(snapshot.clone());
+    *state.snapshot.write() = snapshot;
+}
+
+/// Update final approach spacing from a traffic poll
+pub fn update(app: &AppHandle, snapshot: &TrafficSnapshot, runways: &[Runway]) {
+    let finals = compute(&snapshot.aircraft, runways, |a| {
+        wake::category(app, a.aircraft_type.as_deref())
+    });
+
+    // Log newly raised alerts
+    let previous = get_snapshot(app);
+    let previous_status = |callsign: &str| {
+        previous
+            .finals
+            .iter()
+            .flat_map(|f| &f.arrivals)
+            .find(|a| a.callsign == callsign)
+            .map(|a| a.status.clone())
+    };
+    for (runway, arrival) in finals
+        .iter()
+        .flat_map(|f| f.arrivals.iter().map(move |a| (&f.runway, a)))
+    {
+        if arrival.status != STATUS_OK && previous_status(&arrival.callsign).as_deref() != Some(&arrival.status) {
+            info!(
+                "[ATPA] {} {} behind {}: {:.1} nm (predicted {:.1}, required {:.1})",
+                runway,
+                arrival.callsign,
+                arrival.leader.as_deref().unwrap_or_default(),
+                arrival.in_trail_nm.unwrap_or_default(),
+                arrival.predicted_nm.unwrap_or_default(),
+                arrival.required_nm.unwrap_or_default()
+            );
+        }
+    }
+
+    if finals.is_empty() && previous.finals.is_empty() && previous.icao == snapshot.icao {
+        return;
+    }
+    publish(
+        app,
+        AtpaSnapshot {
+            icao: snapshot.icao.clone(),
+            updated_at: snapshot.updated_at,
+            finals,
+        },
+    );
+}
+
+/// Drop all spacing (no active airport or traffic)
+pub fn clear(app: &AppHandle) {
+    let current = get_snapshot(app);
+    if current.icao.is_some() || !current.finals.is_empty() {
+        publish(app, AtpaSnapshot::default());
+    }
+}
+
+/// Current final approach spacing
+pub fn get_snapshot(app: &AppHandle) -> AtpaSnapshot {
+    app.state::<AtpaState>().snapshot.read().clone()
+}
+
+/// Initialize ATPA state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_atpa(app: &AppHandle) {
+    app.manage(AtpaState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get final approach spacing at the active airport
+#[tauri::command]
+pub fn get_final_spacing(app: AppHandle) -> AtpaSnapshot {
+    get_snapshot(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traffic::test_aircraft;
+
+    #[test]
+    fn sequences_arrivals_on_final() {
+        // North-south runway, landing north on 36
+        let runway = runways::test_runway();
+        let aircraft = |callsign: &str, aircraft_type: &str, nm_out: f64, groundspeed_kt: f64| Aircraft {
+            latitude: 42.0 - nm_out / 60.0,
+            altitude_ft: nm_out * 300.0,
+            groundspeed_kt,
+            track: Some(0.0),
+            vertical_rate_fpm: Some(-700.0),
+            aircraft_type: Some(aircraft_type.to_string()),
+            distance_nm: nm_out,
+            ..test_aircraft(callsign)
+        };
+        let traffic = vec![
+            aircraft("HVY1", "B744", 4.0, 150.0),
+            // 5.5 nm behind a heavy (needs 5), but 30 kt faster: 4.7 nm at the threshold
+            aircraft("MED1", "A320", 9.5, 180.0),
+            // 3.5 nm behind a medium, same speed
+            aircraft("MED2", "A320", 13.0, 180.0),
+            // Outbound, not on final
+            Aircraft {
+                track: Some(180.0),
+                ..aircraft("DEP1", "A320", 6.0, 200.0)
+            },
+        ];
+        let categories = |a: &Aircraft| match a.aircraft_type.as_deref() {
+            Some("B744") => 'H',
+            _ => 'M',
+        };
+
+        let finals = compute(&traffic, &[runway], categories);
+        assert_eq!(finals.len(), 1);
+        assert_eq!(finals[0].runway, "36");
+        let arrivals = &finals[0].arrivals;
+        assert_eq!(arrivals.len(), 3);
+        assert_eq!(arrivals[0].leader, None);
+        assert_eq!(arrivals[1].leader.as_deref(), Some("HVY1"));
+        assert_eq!(arrivals[1].required_nm, Some(5.0));
+        assert!(arrivals[1].predicted_nm.is_some_and(|p| (p - 4.7).abs() < 0.05));
+        assert_eq!(arrivals[1].status, STATUS_CAUTION);
+        assert_eq!(arrivals[2].required_nm, Some(wake::MIN_RADAR_SEPARATION_NM));
+        assert_eq!(arrivals[2].status, STATUS_OK);
+    }
+}
@@ -0,0 +1,292 @@
+//! Tower audio relay
+//!
+//! Relays LiveATC (or any other Icecast/Shoutcast) streams configured per
+//! airport in the global settings through the embedded server, so remote
+//! tablets play tower audio from the same origin as the app instead of running
+//! into CORS or mixed-content (https page, http stream) problems.
+//!
+//! Each stream is fetched from upstream once, however many listeners there
+//! are. The most recent audio is buffered so a new listener starts playing
+//! immediately, and the upstream connection is closed shortly after the last
+//! listener leaves.
+
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::body::{Body, Bytes};
+use futures_util::StreamExt;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+use url::Url;
+
+use crate::http_client;
+
+/// Audio kept for new listeners (about 8 seconds at 32 kbit/s)
+const BUFFER_BYTES: usize = 32 * 1024;
+
+/// The upstream connection is closed this long after the last listener leaves
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Upstream connection timeout (the stream itself has no timeout)
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Audio stream configured for an airport
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioStreamConfig {
+    /// Identifier used in the relay URL (e.g., "kbos_twr")
+    pub id: String,
+    pub icao: String,
+    /// Display name (e.g., "Boston Tower")
+    pub name: String,
+    /// Upstream stream URL (e.g., "http://d.liveatc.net/kbos_twr")
+    pub url: String,
+}
+
+/// Tower audio settings in the global settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AudioRelaySettings {
+    pub streams: Vec<AudioStreamConfig>,
+}
+
+/// Audio stream as listed to clients (the upstream URL stays on the host)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioStreamInfo {
+    pub id: String,
+    pub icao: String,
+    pub name: String,
+    /// Relay URL path on the embedded server
+    pub path: String,
+    /// Whether the host is currently connected upstream
+    pub live: bool,
+    pub listeners: usize,
+}
+
+/// Upstream stream shared by its listeners
+struct Relay {
+    content_type: String,
+    chunks: broadcast::Sender<Bytes>,
+    /// Most recent audio for new listeners
+    buffer: Mutex<VecDeque<Bytes>>,
+}
+
+impl Relay {
+    fn push(&self, chunk: Bytes) {
+        {
+            let mut buffer = self.buffer.lock();
+            buffer.push_back(chunk.clone());
+            let mut len: usize = buffer.iter().map(Bytes::len).sum();
+            while len > BUFFER_BYTES && buffer.len() > 1 {
+                len -= buffer.pop_front().map(|c| c.len()).unwrap_or(0);
+            }
+        }
+        let _ = self.chunks.send(chunk);
+    }
+}
+
+/// Audio relay state (managed by Tauri)
+pub struct AudioRelayState {
+    relays: Mutex<HashMap<String, Arc<Relay>>>,
+    /// Held while connecting upstream so concurrent listeners share one connection
+    connecting: tokio::sync::Mutex<()>,
+}
+
+impl AudioRelayState {
+    pub fn new() -> Self {
+        Self {
+            relays: Mutex::new(HashMap::new()),
+            connecting: tokio::sync::Mutex::new(()),
+        }
+    }
+}
+
+impl Default for AudioRelayState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether a stream id can be used in a URL path
+pub fn valid_stream_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 64
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn configured_streams(app: &AppHandle) -> Vec<AudioStreamConfig> {
+    crate::read_global_settings(app.clone())
+        .map(|s| s.audio.streams)
+        .unwrap_or_default()
+}
+
+/// Streams configured for an airport (every stream for `None`)
+pub fn list(app: &AppHandle, icao: Option<&str>) -> Vec<AudioStreamInfo> {
+    let relays = app.state::<AudioRelayState>().relays.lock().clone();
+    configured_streams(app)
+        .into_iter()
+        .filter(|s| icao.map_or(true, |icao| s.icao.eq_ignore_ascii_case(icao)))
+        .map(|s| {
+            let relay = relays.get(&s.id);
+            AudioStreamInfo {
+                path: format!("/api/audio/{}", s.id),
+                live: relay.is_some(),
+                listeners: relay.map(|r| r.chunks.receiver_count()).unwrap_or(0),
+                id: s.id,
+                icao: s.icao.to_uppercase(),
+                name: s.name,
+            }
+        })
+        .collect()
+}
+
+/// Pump audio from upstream to the listeners until the stream ends or nobody listens
+async fn pump(app: AppHandle, id: String, relay: Arc<Relay>, mut response: reqwest::Response) {
+    let mut idle_since: Option<Instant> = None;
+    loop {
+        match tokio::time::timeout(IDLE_TIMEOUT, response.chunk()).await {
+            Ok(Ok(Some(chunk))) => relay.push(chunk),
+            Ok(Ok(None)) => {
+                info!("[AudioRelay] {} ended", id);
+                break;
+            }
+            Ok(Err(e)) => {
+                warn!("[AudioRelay] {}: {}", id, e);
+                break;
+            }
+            Err(_) => {
+                warn!("[AudioRelay] {}: no audio for {}s", id, IDLE_TIMEOUT.as_secs());
+                break;
+            }
+        }
+        if relay.chunks.receiver_count() > 0 {
+            idle_since = None;
+        } else if idle_since.get_or_insert_with(Instant::now).elapsed() >= IDLE_TIMEOUT {
+            info!("[AudioRelay] {} has no listeners, disconnecting", id);
+            break;
+        }
+    }
+    app.state::<AudioRelayState>().relays.lock().remove(&id);
+}
+
+/// Relay of a stream, connecting upstream if nobody is listening yet
+async fn relay_for(app: &AppHandle, id: &str) -> Result<Arc<Relay>, String> {
+    let state = app.state::<AudioRelayState>();
+    if let Some(relay) = state.relays.lock().get(id) {
+        return Ok(relay.clone());
+    }
+    let _connecting = state.connecting.lock().await;
+    if let Some(relay) = state.relays.lock().get(id) {
+        return Ok(relay.clone());
+    }
+
+    let config = configured_streams(app)
+        .into_iter()
+        .find(|s| s.id == id)
+        .ok_or_else(|| format!("Unknown audio stream: {}", id))?;
+    let url = Url::parse(&config.url).map_err(|e| format!("Invalid stream URL: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!("Unsupported stream URL scheme: {}", url.scheme()));
+    }
+
+    let response = tokio::time::timeout(CONNECT_TIMEOUT, http_client::client().get(url).send())
+        .await
+        .map_err(|_| format!("Timed out connecting to {}", config.name))?
+        .map_err(|e| format!("Failed to connect to {}: {}", config.name, e))?;
+    if !response.status().is_success() {
+        return Err(format!("{} returned HTTP {}", config.name, response.status()));
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .filter(|v| v.starts_with("audio/") || v.starts_with("application/ogg"))
+        .unwrap_or("audio/mpeg")
+        .to_string();
+
+    let (chunks, _) = broadcast::channel(64);
+    let relay = Arc::new(Relay {
+        content_type,
+        chunks,
+        buffer: Mutex::new(VecDeque::new()),
+    });
+    state.relays.lock().insert(id.to_string(), relay.clone());
+    info!("[AudioRelay] Connected to {} ({})", config.name, id);
+    tauri::async_runtime::spawn(pump(app.clone(), id.to_string(), relay.clone(), response));
+    Ok(relay)
+}
+
+/// Listen to a stream: its content type and a body starting with the buffered audio
+pub async fn listen(app: &AppHandle, id: &str) -> Result<(String, Body), String> {
+    let relay = relay_for(app, id).await?;
+    // Subscribe before copying the buffer so no chunk falls in between
+    let receiver = relay.chunks.subscribe();
+    let buffered: Vec<Bytes> = relay.buffer.lock().iter().cloned().collect();
+
+    let live = futures_util::stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(chunk) => return Some((Ok::<_, Infallible>(chunk), receiver)),
+                // A slow listener skips audio rather than falling further behind
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+    let body = futures_util::stream::iter(buffered.into_iter().map(Ok::<_, Infallible>)).chain(live);
+    Ok((relay.content_type.clone(), Body::from_stream(body)))
+}
+
+/// Initialize audio relay state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_audio_relay(app: &AppHandle) {
+    app.manage(AudioRelayState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// List the tower audio streams configured for an airport (every stream without one)
+#[tauri::command]
+pub fn list_audio_streams(app: AppHandle, icao: Option<String>) -> Vec<AudioStreamInfo> {
+    list(&app, icao.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_stream_ids() {
+        assert!(valid_stream_id("kbos_twr"));
+        assert!(valid_stream_id("EGLL-Tower-1"));
+        assert!(!valid_stream_id(""));
+        assert!(!valid_stream_id("../etc"));
+        assert!(!valid_stream_id("kbos twr"));
+    }
+
+    #[test]
+    fn keeps_recent_audio_for_new_listeners() {
+        let (chunks, _) = broadcast::channel(4);
+        let relay = Relay {
+            content_type: "audio/mpeg".to_string(),
+            chunks,
+            buffer: Mutex::new(VecDeque::new()),
+        };
+        for _ in 0..10 {
+            relay.push(Bytes::from(vec![0u8; BUFFER_BYTES / 4]));
+        }
+        let buffer = relay.buffer.lock();
+        assert_eq!(buffer.iter().map(Bytes::len).sum::<usize>(), BUFFER_BYTES);
+        assert_eq!(buffer.len(), 4);
+    }
+}
@@ -0,0 +1,258 @@
+//! Audit log of writes made through the HTTP API
+//!
+//! Every request that changes persistent state on the host (global settings,
+//! tower positions, VMR rules, video map imports, the tile cache, shared
+//! replays) is appended to `audit-log.jsonl` in the app data folder, one JSON
+//! entry per line, with the time, the client (IP and browser), the request and
+//! its result. Handlers
+//! describe what they changed by adding an [`AuditSummary`] to the response;
+//! settings and tower position changes are summarized as a diff.
+//!
+//! The log is only ever appended to, so shared installations can see who
+//! changed what. Read it with `get_audit_log`.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+use tracing::warn;
+
+const FILE_NAME: &str = "audit-log.jsonl";
+
+/// API paths whose non-GET requests are audited
+const AUDITED_PREFIXES: [&str; 6] = [
+    "/api/global-settings",
+    "/api/tower-positions",
+    "/api/vmr-rules",
+    "/api/videomaps",
+    "/api/tiles",
+    "/api/replays",
+];
+
+/// Changes listed per entry (the rest are counted)
+const MAX_CHANGES: usize = 40;
+
+/// Longest value shown in a change
+const MAX_VALUE_CHARS: usize = 60;
+
+/// Serializes appends from concurrent requests
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// One audited request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    /// Unix ms
+    pub timestamp: u64,
+    /// IP and browser of the client
+    pub client: String,
+    pub method: String,
+    /// Path and query
+    pub path: String,
+    /// HTTP status of the response
+    pub status: u16,
+    /// What changed, one line per change
+    #[serde(default)]
+    pub summary: Vec<String>,
+}
+
+/// What a request changed, added to the response by its handler
+#[derive(Debug, Clone, Default)]
+pub struct AuditSummary(pub Vec<String>);
+
+/// Whether a request is audited
+pub fn audited(method: &str, path: &str) -> bool {
+    !matches!(method, "GET" | "HEAD" | "OPTIONS") && AUDITED_PREFIXES.iter().any(|prefix| path.starts_with(prefix))
+}
+
+/// Path and query of a request, without the auth token
+pub fn request_path(uri: &axum::http::Uri) -> String {
+    let query: Vec<(String, String)> = url::form_urlencoded::parse(uri.query().unwrap_or_default().as_bytes())
+        .filter(|(key, _)| key != "token")
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    if query.is_empty() {
+        return uri.path().to_string();
+    }
+    let query = url::form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(query)
+        .finish();
+    format!("{}?{}", uri.path(), query)
+}
+
+fn log_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path().app_data_dir().ok().map(|dir| dir.join(FILE_NAME))
+}
+
+fn append(path: &Path, entry: &AuditEntry) -> Result<(), String> {
+    let mut line = serde_json::to_string(entry).map_err(|e| e.to_string())?;
+    line.push('\n');
+    let _guard = WRITE_LOCK.lock();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|e| e.to_string())
+}
+
+/// Append an entry to the audit log
+pub fn record(app: &AppHandle, client: String, method: &str, path: &str, status: u16, summary: Vec<String>) {
+    let Some(log) = log_path(app) else {
+        return;
+    };
+    let entry = AuditEntry {
+        timestamp: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        client,
+        method: method.to_string(),
+        path: path.to_string(),
+        status,
+        summary,
+    };
+    if let Err(e) = append(&log, &entry) {
+        warn!("[Audit] Failed to write audit log: {}", e);
+    }
+}
+
+/// Entries of a log file, newest first (unreadable lines are skipped)
+fn read_entries(path: &Path, limit: usize) -> Vec<AuditEntry> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .take(limit)
+        .collect()
+}
+
+/// Whether a settings key holds a secret (tokens, passwords, keys)
+fn is_secret(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    ["token", "password", "secret", "apikey"]
+        .iter()
+        .any(|word| key.contains(word))
+}
+
+fn short(value: &Value) -> String {
+    let text = match value {
+        Value::Null => "none".to_string(),
+        other => other.to_string(),
+    };
+    let mut chars = text.chars();
+    let truncated: String = chars.by_ref().take(MAX_VALUE_CHARS).collect();
+    if chars.next().is_some() {
+        format!("{}...", truncated)
+    } else {
+        truncated
+    }
+}
+
+/// Changed values between two JSON documents, as `path: old -> new` (secrets
+/// are only reported as changed)
+pub fn diff_summary(prefix: &str, before: &Value, after: &Value) -> Vec<String> {
+    fn walk(path: &str, before: &Value, after: &Value, secret: bool, changes: &mut Vec<String>) {
+        if before == after {
+            return;
+        }
+        if let (Value::Object(old), Value::Object(new)) = (before, after) {
+            let mut keys: Vec<&String> = old.keys().chain(new.keys().filter(|k| !old.contains_key(*k))).collect();
+            keys.sort();
+            for key in keys {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                walk(
+                    &child,
+                    old.get(key).unwrap_or(&Value::Null),
+                    new.get(key).unwrap_or(&Value::Null),
+                    secret || is_secret(key),
+                    changes,
+                );
+            }
+        } else if secret {
+            changes.push(format!("{}: changed", path));
+        } else {
+            changes.push(format!("{}: {} -> {}", path, short(before), short(after)));
+        }
+    }
+
+    let mut changes = Vec::new();
+    walk(prefix, before, after, false, &mut changes);
+    if changes.len() > MAX_CHANGES {
+        let more = changes.len() - MAX_CHANGES;
+        changes.truncate(MAX_CHANGES);
+        changes.push(format!("... and {} more", more));
+    }
+    changes
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Latest audit log entries, newest first (default 200)
+#[tauri::command]
+pub fn get_audit_log(app: AppHandle, limit: Option<usize>) -> Vec<AuditEntry> {
+    log_path(&app)
+        .map(|path| read_entries(&path, limit.unwrap_or(200)))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn diffs_settings_and_appends_entries() {
+        let before = json!({ "server": { "port": 8765, "authToken": "old" }, "units": "ft" });
+        let after = json!({ "server": { "port": 8766, "authToken": "new" }, "units": "ft", "theme": "dark" });
+        assert_eq!(
+            diff_summary("", &before, &after),
+            [
+                "server.authToken: changed",
+                "server.port: 8765 -> 8766",
+                "theme: none -> \"dark\""
+            ]
+        );
+        assert!(audited("PUT", "/api/tower-positions/KBOS"));
+        assert!(audited("DELETE", "/api/replays/kbos-fno-replay"));
+        assert!(!audited("GET", "/api/global-settings"));
+        assert!(!audited("POST", "/api/realtraffic/traffic"));
+        let uri: axum::http::Uri = "/api/vmr-rules/rule?file=a.vmr&token=secret&index=3".parse().unwrap();
+        assert_eq!(request_path(&uri), "/api/vmr-rules/rule?file=a.vmr&index=3");
+
+        let path = std::env::temp_dir()
+            .join(format!("audit-test-{}", std::process::id()))
+            .join(FILE_NAME);
+        for status in [200, 400] {
+            let entry = AuditEntry {
+                timestamp: 0,
+                client: "192.168.1.5 Chrome".to_string(),
+                method: "POST".to_string(),
+                path: "/api/global-settings".to_string(),
+                status,
+                summary: Vec::new(),
+            };
+            append(&path, &entry).unwrap();
+        }
+        let entries = read_entries(&path, 10);
+        assert_eq!(entries.iter().map(|e| e.status).collect::<Vec<_>>(), [400, 200]);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
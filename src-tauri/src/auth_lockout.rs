@@ -0,0 +1,143 @@
+//! Lockout after repeated auth failures
+//!
+//! Wrong bearer, `token` query and admin tokens are counted per client IP.
+//! After [`FREE_ATTEMPTS`] failures the client is locked out for 30 seconds,
+//! doubling with every further failure up to an hour; API requests from a
+//! locked-out client get `429 Too Many Requests` with a `Retry-After` header.
+//! Failures are forgotten after a successful request or an hour without
+//! failures. The host itself (loopback) is never locked out.
+//!
+//! Each lockout is logged and emitted to the host UI as `auth-lockout`, so a
+//! misconfigured client or a device guessing tokens doesn't go unnoticed.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+/// Failures allowed before the first lockout
+pub const FREE_ATTEMPTS: u32 = 5;
+
+/// First lockout (doubles with each further failure)
+const BASE_LOCKOUT: Duration = Duration::from_secs(30);
+
+/// Longest lockout
+const MAX_LOCKOUT: Duration = Duration::from_secs(60 * 60);
+
+/// Failures older than this are forgotten
+const FORGET_AFTER: Duration = Duration::from_secs(60 * 60);
+
+/// Emitted to the host UI when a client is locked out
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LockoutEvent {
+    pub ip: String,
+    pub failures: u32,
+    pub locked_for_secs: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Failures {
+    count: u32,
+    last: Instant,
+    locked_until: Option<Instant>,
+}
+
+/// Lockout length after a number of failures (None below the free attempts)
+fn lockout_duration(failures: u32) -> Option<Duration> {
+    let extra = failures.checked_sub(FREE_ATTEMPTS)?;
+    let factor = 2u32.checked_pow(extra).unwrap_or(u32::MAX);
+    Some(BASE_LOCKOUT.saturating_mul(factor).min(MAX_LOCKOUT))
+}
+
+/// Auth failures per client IP (held in the server state)
+#[derive(Debug, Default)]
+pub struct AuthLockout {
+    clients: Mutex<HashMap<IpAddr, Failures>>,
+}
+
+impl AuthLockout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Time left on a client's lockout, if it's locked out
+    pub fn locked_for(&self, ip: IpAddr, now: Instant) -> Option<Duration> {
+        let clients = self.clients.lock();
+        let until = clients.get(&ip)?.locked_until?;
+        until.checked_duration_since(now).filter(|left| !left.is_zero())
+    }
+
+    /// Record a failed attempt, returning the lockout it started, if any
+    pub fn record_failure(&self, ip: IpAddr, now: Instant) -> Option<LockoutEvent> {
+        if ip.is_loopback() {
+            return None;
+        }
+        let mut clients = self.clients.lock();
+        clients.retain(|_, f| now.saturating_duration_since(f.last) < FORGET_AFTER);
+
+        let failures = clients.entry(ip).or_insert(Failures {
+            count: 0,
+            last: now,
+            locked_until: None,
+        });
+        failures.count += 1;
+        failures.last = now;
+        let duration = lockout_duration(failures.count)?;
+        failures.locked_until = Some(now + duration);
+        Some(LockoutEvent {
+            ip: ip.to_string(),
+            failures: failures.count,
+            locked_for_secs: duration.as_secs(),
+        })
+    }
+
+    /// Forget a client's failures after it authenticated
+    pub fn record_success(&self, ip: IpAddr) {
+        self.clients.lock().remove(&ip);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locks_out_exponentially_after_free_attempts() {
+        let lockout = AuthLockout::new();
+        let ip: IpAddr = "192.168.1.50".parse().unwrap();
+        let start = Instant::now();
+
+        for _ in 0..FREE_ATTEMPTS - 1 {
+            assert_eq!(lockout.record_failure(ip, start), None);
+        }
+        assert_eq!(lockout.locked_for(ip, start), None);
+
+        let first = lockout.record_failure(ip, start).unwrap();
+        assert_eq!(first.locked_for_secs, 30);
+        assert_eq!(lockout.locked_for(ip, start + Duration::from_secs(10)), Some(Duration::from_secs(20)));
+        assert_eq!(lockout.locked_for(ip, start + Duration::from_secs(30)), None);
+
+        let later = start + Duration::from_secs(31);
+        assert_eq!(lockout.record_failure(ip, later).unwrap().locked_for_secs, 60);
+        assert_eq!(lockout.record_failure(ip, later).unwrap().locked_for_secs, 120);
+
+        lockout.record_success(ip);
+        assert_eq!(lockout.locked_for(ip, later), None);
+        assert_eq!(lockout.record_failure(ip, later), None);
+    }
+
+    #[test]
+    fn caps_lockouts_and_exempts_the_host() {
+        assert_eq!(lockout_duration(FREE_ATTEMPTS + 40), Some(MAX_LOCKOUT));
+
+        let lockout = AuthLockout::new();
+        let host: IpAddr = "127.0.0.1".parse().unwrap();
+        for _ in 0..FREE_ATTEMPTS * 2 {
+            assert_eq!(lockout.record_failure(host, Instant::now()), None);
+        }
+        assert_eq!(lockout.locked_for(host, Instant::now()), None);
+    }
+}
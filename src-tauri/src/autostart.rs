@@ -0,0 +1,317 @@
+//! Start at login
+//!
+//! Registers the app to start when the user logs in (`autostart` in global
+//! settings), so a dedicated display or server machine comes back up running
+//! after a reboot. Optionally starts headless (`--headless`: no window, HTTP
+//! server on) for machines that only serve remote browsers. The profile the
+//! app was started with (`--profile`) is kept.
+//!
+//! - Windows: `HKCU\Software\Microsoft\Windows\CurrentVersion\Run` value
+//! - macOS: `~/Library/LaunchAgents/<identifier>.plist` launch agent
+//! - Linux: `~/.config/autostart/towercab-3d.desktop` (XDG autostart)
+//!
+//! The entry is rewritten whenever global settings are saved and at startup
+//! if the executable moved (e.g. after an update to a new install path).
+
+use std::path::PathBuf;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+use crate::error::Error;
+use crate::{cli, GlobalAutostartSettings};
+
+/// Start-at-login status
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutostartStatus {
+    pub enabled: bool,
+    pub headless: bool,
+    /// The OS has an up-to-date entry for this executable
+    pub registered: bool,
+    pub error: Option<String>,
+}
+
+pub struct AutostartState {
+    error: Mutex<Option<String>>,
+}
+
+/// Arguments the app is started with at login
+fn launch_args(headless: bool, profile: Option<&str>) -> Vec<String> {
+    let mut args = Vec::new();
+    if headless {
+        args.push("--headless".to_string());
+    }
+    if let Some(profile) = profile {
+        args.push("--profile".to_string());
+        args.push(profile.to_string());
+    }
+    args
+}
+
+/// The executable to start (the AppImage itself rather than its mount point on Linux)
+fn executable() -> Result<PathBuf, String> {
+    if let Some(appimage) = std::env::var_os("APPIMAGE") {
+        return Ok(PathBuf::from(appimage));
+    }
+    std::env::current_exe().map_err(|e| format!("Failed to find the app executable: {}", e))
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::os::windows::process::CommandExt;
+    use std::path::Path;
+    use std::process::Command;
+
+    use tauri::AppHandle;
+
+    const RUN_KEY: &str = r"HKCU\Software\Microsoft\Windows\CurrentVersion\Run";
+    const VALUE_NAME: &str = "TowerCab 3D";
+
+    fn reg(args: &[&str]) -> Result<String, String> {
+        let output = Command::new("reg")
+            .args(args)
+            .creation_flags(0x08000000) // CREATE_NO_WINDOW
+            .output()
+            .map_err(|e| format!("Failed to run reg: {}", e))?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// Run key command line, with the executable quoted
+    pub fn entry_content(_app: &AppHandle, exe: &Path, args: &[String]) -> String {
+        let mut command = format!("\"{}\"", exe.display());
+        for arg in args {
+            command.push(' ');
+            command.push_str(arg);
+        }
+        command
+    }
+
+    pub fn read_entry(_app: &AppHandle) -> Option<String> {
+        // Output: "    TowerCab 3D    REG_SZ    <command>"
+        let output = reg(&["query", RUN_KEY, "/v", VALUE_NAME]).ok()?;
+        output
+            .lines()
+            .find_map(|line| line.split_once("REG_SZ"))
+            .map(|(_, command)| command.trim().to_string())
+    }
+
+    pub fn write_entry(_app: &AppHandle, content: &str) -> Result<(), String> {
+        reg(&["add", RUN_KEY, "/v", VALUE_NAME, "/t", "REG_SZ", "/d", content, "/f"]).map(|_| ())
+    }
+
+    pub fn remove_entry(_app: &AppHandle) -> Result<(), String> {
+        reg(&["delete", RUN_KEY, "/v", VALUE_NAME, "/f"]).map(|_| ())
+    }
+}
+
+#[cfg(not(windows))]
+mod platform {
+    use std::path::{Path, PathBuf};
+
+    use tauri::{AppHandle, Manager};
+
+    #[cfg(target_os = "macos")]
+    fn entry_path(app: &AppHandle) -> Result<PathBuf, String> {
+        let home = app.path().home_dir().map_err(|e| e.to_string())?;
+        let label = &app.config().identifier;
+        Ok(home.join("Library/LaunchAgents").join(format!("{}.plist", label)))
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn entry_path(app: &AppHandle) -> Result<PathBuf, String> {
+        let config = app.path().config_dir().map_err(|e| e.to_string())?;
+        Ok(config.join("autostart").join("towercab-3d.desktop"))
+    }
+
+    #[cfg(target_os = "macos")]
+    pub fn entry_content(app: &AppHandle, exe: &Path, args: &[String]) -> String {
+        super::launch_agent_plist(&app.config().identifier, exe, args)
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    pub fn entry_content(_app: &AppHandle, exe: &Path, args: &[String]) -> String {
+        super::desktop_entry(exe, args)
+    }
+
+    pub fn read_entry(app: &AppHandle) -> Option<String> {
+        std::fs::read_to_string(entry_path(app).ok()?).ok()
+    }
+
+    pub fn write_entry(app: &AppHandle, content: &str) -> Result<(), String> {
+        let path = entry_path(app)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+        }
+        std::fs::write(&path, content).map_err(|e| format!("Failed to write {:?}: {}", path, e))
+    }
+
+    pub fn remove_entry(app: &AppHandle) -> Result<(), String> {
+        let path = entry_path(app)?;
+        std::fs::remove_file(&path).map_err(|e| format!("Failed to remove {:?}: {}", path, e))
+    }
+}
+
+/// macOS launch agent starting the app at login
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn launch_agent_plist(label: &str, exe: &std::path::Path, args: &[String]) -> String {
+    let escape = |s: &str| s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+    let mut program = format!("    <string>{}</string>\n", escape(&exe.to_string_lossy()));
+    for arg in args {
+        program.push_str(&format!("    <string>{}</string>\n", escape(arg)));
+    }
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+  <key>Label</key>
+  <string>{}</string>
+  <key>ProgramArguments</key>
+  <array>
+{}  </array>
+  <key>RunAtLoad</key>
+  <true/>
+</dict>
+</plist>
+"#,
+        escape(label),
+        program
+    )
+}
+
+/// XDG autostart entry starting the app at login
+#[cfg_attr(any(windows, target_os = "macos"), allow(dead_code))]
+fn desktop_entry(exe: &std::path::Path, args: &[String]) -> String {
+    // Quoting rules from the Desktop Entry spec's Exec key
+    let quote = |s: &str| {
+        if s.is_empty() || s.contains(|c: char| c.is_whitespace() || "\"'`$\\<>|&;*?#()~=%".contains(c)) {
+            let escaped: String = s
+                .chars()
+                .flat_map(|c| match c {
+                    '"' | '`' | '$' | '\\' => vec!['\\', c],
+                    '%' => vec!['%', '%'],
+                    _ => vec![c],
+                })
+                .collect();
+            format!("\"{}\"", escaped)
+        } else {
+            s.to_string()
+        }
+    };
+    let exec: Vec<String> = std::iter::once(quote(&exe.to_string_lossy()))
+        .chain(args.iter().map(|arg| quote(arg)))
+        .collect();
+    format!(
+        "[Desktop Entry]\nType=Application\nName=TowerCab 3D\nExec={}\nX-GNOME-Autostart-enabled=true\n",
+        exec.join(" ")
+    )
+}
+
+/// The entry this executable should have for the settings
+fn wanted_entry(app: &AppHandle, settings: &GlobalAutostartSettings) -> Result<String, String> {
+    let args = launch_args(settings.headless, cli::args().profile.as_deref());
+    Ok(platform::entry_content(app, &executable()?, &args))
+}
+
+/// Register or unregister the app to match the settings
+fn sync(app: &AppHandle, settings: &GlobalAutostartSettings) -> Result<(), String> {
+    let current = platform::read_entry(app);
+    if !settings.enabled {
+        if current.is_some() {
+            platform::remove_entry(app)?;
+            info!("[Autostart] Removed start at login");
+        }
+        return Ok(());
+    }
+    let wanted = wanted_entry(app, settings)?;
+    if current.as_deref() != Some(wanted.as_str()) {
+        platform::write_entry(app, &wanted)?;
+        info!(
+            "[Autostart] Registered start at login{}",
+            if settings.headless { " (headless)" } else { "" }
+        );
+    }
+    Ok(())
+}
+
+/// Register or unregister start at login (called when global settings are written)
+pub fn apply_settings(app: &AppHandle, settings: &GlobalAutostartSettings) {
+    let Some(state) = app.try_state::<AutostartState>() else {
+        return;
+    };
+    let result = sync(app, settings);
+    if let Err(e) = &result {
+        warn!("[Autostart] {}", e);
+    }
+    *state.error.lock() = result.err();
+}
+
+/// Initialize autostart state and refresh the entry.
+/// Call this in the Tauri setup closure.
+pub fn init_autostart(app: &AppHandle) {
+    app.manage(AutostartState {
+        error: Mutex::new(None),
+    });
+    let settings = crate::read_global_settings(app.clone())
+        .map(|s| s.autostart)
+        .unwrap_or_default();
+    apply_settings(app, &settings);
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get whether the app starts at login
+#[tauri::command]
+pub fn get_autostart_status(app: AppHandle) -> AutostartStatus {
+    let settings = crate::read_global_settings(app.clone())
+        .map(|s| s.autostart)
+        .unwrap_or_default();
+    let registered = match wanted_entry(&app, &settings) {
+        Ok(wanted) => platform::read_entry(&app).as_deref() == Some(wanted.as_str()),
+        Err(_) => false,
+    };
+    AutostartStatus {
+        enabled: settings.enabled,
+        headless: settings.headless,
+        registered,
+        error: app
+            .try_state::<AutostartState>()
+            .and_then(|state| state.error.lock().clone()),
+    }
+}
+
+/// Register or unregister the app to start at login and save it to global settings
+#[tauri::command]
+pub fn set_autostart(app: AppHandle, enabled: bool, headless: bool) -> Result<AutostartStatus, Error> {
+    let mut settings = crate::read_global_settings(app.clone())?;
+    settings.autostart = GlobalAutostartSettings { enabled, headless };
+    crate::write_global_settings(app.clone(), settings)?;
+    Ok(get_autostart_status(app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn builds_login_entries() {
+        let args = launch_args(true, Some("event"));
+        assert_eq!(args, ["--headless", "--profile", "event"]);
+
+        let entry = desktop_entry(Path::new("/opt/Tower Cab/towercab-3d"), &args);
+        assert!(entry.contains("\nExec=\"/opt/Tower Cab/towercab-3d\" --headless --profile event\n"));
+
+        let plist = launch_agent_plist("com.towercab.viewer", Path::new("/Applications/A&B.app"), &args);
+        assert!(plist.contains("<string>/Applications/A&amp;B.app</string>"));
+        assert!(plist.contains("<string>--headless</string>"));
+    }
+}
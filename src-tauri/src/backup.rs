@@ -0,0 +1,532 @@
+//! Backup and restore of the app data folder
+//!
+//! A backup is a zstd-compressed tar archive (`towercab-backup-<time>.tar.zst`,
+//! by default in `backups/` in the app data folder) holding:
+//!
+//! - `data/`: the app data folder (global settings, subscriptions, video map
+//!   and other indexes, layouts), optionally with logs and crash reports
+//! - `mods/`: tower positions and VMR files from the mods folder
+//! - `manifest.json` (last entry): app version, time, and the size and SHA-256
+//!   of every file
+//!
+//! Caches that are rebuilt or downloaded again (tile cache, airport database,
+//! content packs, WebView data) are left out. Every file is checked against the
+//! manifest before anything is restored, and the current data is backed up
+//! first (`-pre-restore`), so a restore can be undone. Restart the app after a
+//! restore.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::DialogExt;
+use tracing::info;
+
+use crate::error::{Error, Result};
+use crate::{find_mods_root, safe_path, vmr};
+
+/// Bump when the archive layout changes
+const FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_NAME: &str = "manifest.json";
+
+/// zstd level (fast; settings and indexes compress well at any level)
+const LEVEL: i32 = 3;
+
+/// Top-level app data entries never backed up (caches rebuilt or downloaded again)
+const EXCLUDED: [&str; 9] = [
+    "backups",
+    "tile-cache",
+    "terrain-packages",
+    "osm-buildings",
+    "content-packs",
+    "airports.db",
+    "airports.db-journal",
+    "EBWebView",
+    "zstd-cache",
+];
+
+/// Top-level app data folders only backed up on request
+const LOG_DIRS: [&str; 2] = ["logs", "crash-reports"];
+
+/// A file in a backup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupFile {
+    path: String,
+    size: u64,
+    sha256: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupManifest {
+    format: u32,
+    app_version: String,
+    /// Unix ms
+    created_at: u64,
+    include_logs: bool,
+    files: Vec<BackupFile>,
+}
+
+/// A backup archive
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupInfo {
+    pub path: String,
+    pub file_name: String,
+    /// Archive size in bytes
+    pub size: u64,
+    /// Unix ms (file modification time until the archive was read)
+    pub created_at: u64,
+    /// From the manifest, once the archive was read
+    pub app_version: Option<String>,
+    pub include_logs: Option<bool>,
+    pub file_count: Option<usize>,
+}
+
+/// A completed restore
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreResult {
+    pub restored: BackupInfo,
+    /// Backup of the data that was replaced
+    pub previous: BackupInfo,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+fn app_data_dir(app: &AppHandle) -> Result<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))
+}
+
+fn backups_dir(app: &AppHandle) -> Result<PathBuf> {
+    Ok(app_data_dir(app)?.join("backups"))
+}
+
+/// Files under `root` as (archive path, file), skipping excluded folders
+fn collect_data_files(root: &Path, include_logs: bool) -> Vec<(String, PathBuf)> {
+    fn walk(dir: &Path, prefix: &str, files: &mut Vec<(String, PathBuf)>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let name = entry.file_name().to_string_lossy().to_string();
+            let archive_path = format!("{}/{}", prefix, name);
+            if file_type.is_dir() {
+                walk(&entry.path(), &archive_path, files);
+            } else if file_type.is_file() {
+                files.push((archive_path, entry.path()));
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    let Ok(entries) = fs::read_dir(root) else {
+        return files;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if EXCLUDED.contains(&name.as_str()) || (!include_logs && LOG_DIRS.contains(&name.as_str())) {
+            continue;
+        }
+        let archive_path = format!("data/{}", name);
+        match entry.file_type() {
+            Ok(t) if t.is_dir() => walk(&entry.path(), &archive_path, &mut files),
+            Ok(t) if t.is_file() => files.push((archive_path, entry.path())),
+            _ => {}
+        }
+    }
+    files
+}
+
+/// Tower positions and VMR files from the mods folder
+fn collect_mod_files(mods_root: &Path) -> Vec<(String, PathBuf)> {
+    let relative = |path: &Path| {
+        path.strip_prefix(mods_root)
+            .ok()
+            .map(|rel| format!("mods/{}", rel.to_string_lossy().replace('\\', "/")))
+    };
+    let mut paths = vmr::list_vmr_paths(mods_root);
+    paths.push(mods_root.join("tower-positions.json"));
+    if let Ok(entries) = fs::read_dir(mods_root.join("tower-positions")) {
+        paths.extend(entries.flatten().map(|e| e.path()));
+    }
+    paths
+        .into_iter()
+        .filter(|p| p.is_file())
+        .filter_map(|p| Some((relative(&p)?, p)))
+        .collect()
+}
+
+fn append(builder: &mut tar::Builder<impl std::io::Write>, name: &str, bytes: &[u8]) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(now_ms() / 1000);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, name, bytes)
+        .map_err(|e| Error::Internal(format!("Failed to add {} to the backup: {}", name, e)))
+}
+
+/// Write an archive of the files, with their manifest last
+fn write_archive(
+    dest: &Path,
+    files: &[(String, PathBuf)],
+    app_version: &str,
+    include_logs: bool,
+) -> Result<BackupManifest> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(|e| Error::Io(format!("Failed to create {}: {}", parent.display(), e)))?;
+    }
+    let partial = dest.with_extension("part");
+    let file = fs::File::create(&partial)
+        .map_err(|e| Error::Io(format!("Failed to create {}: {}", partial.display(), e)))?;
+    let encoder = zstd::Encoder::new(file, LEVEL).map_err(|e| Error::Internal(e.to_string()))?;
+    let mut builder = tar::Builder::new(encoder);
+
+    let mut manifest = BackupManifest {
+        format: FORMAT_VERSION,
+        app_version: app_version.to_string(),
+        created_at: now_ms(),
+        include_logs,
+        files: Vec::with_capacity(files.len()),
+    };
+    for (name, path) in files {
+        // Files in use (e.g. the current log) are read as they are now
+        let Ok(bytes) = fs::read(path) else {
+            continue;
+        };
+        append(&mut builder, name, &bytes)?;
+        manifest.files.push(BackupFile {
+            path: name.clone(),
+            size: bytes.len() as u64,
+            sha256: sha256_hex(&bytes),
+        });
+    }
+    let manifest_json = serde_json::to_vec_pretty(&manifest).map_err(|e| Error::Internal(e.to_string()))?;
+    append(&mut builder, MANIFEST_NAME, &manifest_json)?;
+
+    let encoder = builder
+        .into_inner()
+        .map_err(|e| Error::Io(format!("Failed to write backup: {}", e)))?;
+    encoder.finish().map_err(|e| Error::Io(format!("Failed to write backup: {}", e)))?;
+    fs::rename(&partial, dest).map_err(|e| Error::Io(format!("Failed to save backup: {}", e)))?;
+    Ok(manifest)
+}
+
+/// Whether an archive path is a plain relative path under `data/` or `mods/`
+fn safe_entry_path(name: &str) -> bool {
+    safe_path::valid_relative(name) && (name.starts_with("data/") || name.starts_with("mods/"))
+}
+
+/// Read every entry of an archive
+fn read_entries(path: &Path, mut visit: impl FnMut(&str, Vec<u8>) -> Result<()>) -> Result<()> {
+    let file = fs::File::open(path).map_err(|e| Error::Io(format!("Failed to open {}: {}", path.display(), e)))?;
+    let decoder = zstd::Decoder::new(file).map_err(|e| Error::InvalidInput(format!("Not a backup archive: {}", e)))?;
+    let mut archive = tar::Archive::new(decoder);
+    for entry in archive.entries().map_err(|e| Error::InvalidInput(format!("Not a backup archive: {}", e)))? {
+        let mut entry = entry.map_err(|e| Error::InvalidInput(format!("Backup is damaged: {}", e)))?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let name = entry
+            .path()
+            .map_err(|e| Error::InvalidInput(format!("Backup is damaged: {}", e)))?
+            .to_string_lossy()
+            .to_string();
+        let mut bytes = Vec::new();
+        entry
+            .read_to_end(&mut bytes)
+            .map_err(|e| Error::InvalidInput(format!("Backup is damaged: {}", e)))?;
+        visit(&name, bytes)?;
+    }
+    Ok(())
+}
+
+/// Check every file of an archive against its manifest
+fn verify_archive(path: &Path) -> Result<BackupManifest> {
+    let mut hashes = HashMap::new();
+    let mut manifest = None;
+    read_entries(path, |name, bytes| {
+        if name == MANIFEST_NAME {
+            manifest = Some(
+                serde_json::from_slice::<BackupManifest>(&bytes)
+                    .map_err(|e| Error::InvalidInput(format!("Invalid manifest: {}", e)))?,
+            );
+        } else if !safe_entry_path(name) {
+            return Err(Error::InvalidInput(format!("Backup contains an unexpected file: {}", name)));
+        } else {
+            hashes.insert(name.to_string(), (bytes.len() as u64, sha256_hex(&bytes)));
+        }
+        Ok(())
+    })?;
+
+    let manifest =
+        manifest.ok_or_else(|| Error::invalid_input("Backup has no manifest (incomplete or not a TowerCab backup)"))?;
+    if manifest.format > FORMAT_VERSION {
+        return Err(Error::invalid_input("Backup was made by a newer version of the app"));
+    }
+    for file in &manifest.files {
+        match hashes.remove(&file.path) {
+            Some((size, sha256)) if size == file.size && sha256 == file.sha256 => {}
+            Some(_) => return Err(Error::InvalidInput(format!("{} doesn't match the manifest", file.path))),
+            None => return Err(Error::InvalidInput(format!("{} is missing from the backup", file.path))),
+        }
+    }
+    if let Some(extra) = hashes.keys().next() {
+        return Err(Error::InvalidInput(format!("{} is not listed in the manifest", extra)));
+    }
+    Ok(manifest)
+}
+
+/// Extract a verified archive: `data/` into `data_dir`, `mods/` into `mods_root`
+fn extract_archive(path: &Path, data_dir: &Path, mods_root: &Path) -> Result<usize> {
+    let mut count = 0;
+    read_entries(path, |name, bytes| {
+        if !safe_entry_path(name) {
+            return Ok(());
+        }
+        let target = if let Some(rel) = name.strip_prefix("data/") {
+            data_dir.join(rel)
+        } else if let Some(rel) = name.strip_prefix("mods/") {
+            mods_root.join(rel)
+        } else {
+            return Ok(());
+        };
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| Error::Io(format!("Failed to create {}: {}", parent.display(), e)))?;
+        }
+        fs::write(&target, bytes).map_err(|e| Error::Io(format!("Failed to restore {}: {}", target.display(), e)))?;
+        count += 1;
+        Ok(())
+    })?;
+    Ok(count)
+}
+
+fn info_for(path: &Path, manifest: Option<&BackupManifest>) -> BackupInfo {
+    let metadata = fs::metadata(path).ok();
+    let modified = metadata
+        .as_ref()
+        .and_then(|m| m.modified().ok())
+        .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    BackupInfo {
+        path: path.to_string_lossy().to_string(),
+        file_name: path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        size: metadata.map(|m| m.len()).unwrap_or(0),
+        created_at: manifest.map(|m| m.created_at).unwrap_or(modified),
+        app_version: manifest.map(|m| m.app_version.clone()),
+        include_logs: manifest.map(|m| m.include_logs),
+        file_count: manifest.map(|m| m.files.len()),
+    }
+}
+
+/// Back up app data into a new archive in `dir`
+fn create(app: &AppHandle, dir: &Path, include_logs: bool, suffix: &str) -> Result<BackupInfo> {
+    let mut files = collect_data_files(&app_data_dir(app)?, include_logs);
+    files.extend(collect_mod_files(&find_mods_root(app)));
+    let name = format!(
+        "towercab-backup-{}{}.tar.zst",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        suffix
+    );
+    let dest = dir.join(name);
+    let manifest = write_archive(&dest, &files, &app.package_info().version.to_string(), include_logs)?;
+    info!(
+        "[Backup] Backed up {} files to {}",
+        manifest.files.len(),
+        dest.display()
+    );
+    Ok(info_for(&dest, Some(&manifest)))
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Back up app data (to `destination` folder, default: backups/ in app data)
+#[tauri::command]
+pub async fn create_backup(
+    app: AppHandle,
+    include_logs: bool,
+    destination: Option<String>,
+) -> Result<BackupInfo> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dir = match destination {
+            Some(dir) => PathBuf::from(dir),
+            None => backups_dir(&app)?,
+        };
+        create(&app, &dir, include_logs, "")
+    })
+    .await
+    .map_err(|e| Error::Internal(format!("Backup failed: {}", e)))?
+}
+
+/// List backups in the default backups folder, newest first
+#[tauri::command]
+pub fn list_backups(app: AppHandle) -> Vec<BackupInfo> {
+    let Ok(entries) = backups_dir(&app).and_then(|dir| fs::read_dir(dir).map_err(|e| Error::Io(e.to_string()))) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<BackupInfo> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.to_string_lossy().ends_with(".tar.zst"))
+        .map(|p| info_for(&p, None))
+        .collect();
+    backups.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+    backups
+}
+
+/// Pick a backup archive using native dialog (starts in the backups folder)
+#[tauri::command]
+pub async fn pick_backup_file(app: AppHandle) -> Result<Option<String>> {
+    let mut dialog = app.dialog().file().add_filter("TowerCab backup", &["zst"]);
+    if let Ok(dir) = backups_dir(&app) {
+        dialog = dialog.set_directory(dir);
+    }
+    Ok(dialog.blocking_pick_file().map(|path| path.to_string()))
+}
+
+/// Check a backup's files against its manifest
+#[tauri::command]
+pub async fn verify_backup(path: String) -> Result<BackupInfo> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = PathBuf::from(path);
+        let manifest = verify_archive(&path)?;
+        Ok(info_for(&path, Some(&manifest)))
+    })
+    .await
+    .map_err(|e| Error::Internal(format!("Verification failed: {}", e)))?
+}
+
+/// Verify a backup, back up the current data, then restore the backup (restart the app afterwards)
+#[tauri::command]
+pub async fn restore_backup(app: AppHandle, path: String) -> Result<RestoreResult> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let path = PathBuf::from(path);
+        let manifest = verify_archive(&path)?;
+        let previous = create(&app, &backups_dir(&app)?, manifest.include_logs, "-pre-restore")?;
+        let count = extract_archive(&path, &app_data_dir(&app)?, &find_mods_root(&app))?;
+        info!("[Backup] Restored {} files from {}", count, path.display());
+        Ok(RestoreResult {
+            restored: info_for(&path, Some(&manifest)),
+            previous,
+        })
+    })
+    .await
+    .map_err(|e| Error::Internal(format!("Restore failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backs_up_verifies_and_restores() {
+        let root = std::env::temp_dir().join(format!("backup-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let data = root.join("data");
+        fs::create_dir_all(data.join("videomaps/KBOS")).unwrap();
+        fs::create_dir_all(data.join("logs")).unwrap();
+        fs::create_dir_all(data.join("tile-cache")).unwrap();
+        fs::write(data.join("global-settings.json"), r#"{"cesiumIonToken":"x"}"#).unwrap();
+        fs::write(data.join("videomaps/KBOS/index.json"), "{}").unwrap();
+        fs::write(data.join("logs/towercab.log"), "log").unwrap();
+        fs::write(data.join("tile-cache/0.png"), "tile").unwrap();
+
+        let files = collect_data_files(&data, false);
+        let mut names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["data/global-settings.json", "data/videomaps/KBOS/index.json"]);
+
+        let archive = root.join("backup.tar.zst");
+        write_archive(&archive, &files, "1.0.0", false).unwrap();
+        assert_eq!(verify_archive(&archive).unwrap().files.len(), 2);
+
+        let restored = root.join("restored");
+        assert_eq!(extract_archive(&archive, &restored, &root.join("mods")).unwrap(), 2);
+        assert_eq!(
+            fs::read_to_string(restored.join("videomaps/KBOS/index.json")).unwrap(),
+            "{}"
+        );
+
+        // A file that doesn't match the manifest fails verification
+        let tampered = root.join("tampered.tar.zst");
+        let mut builder = tar::Builder::new(zstd::Encoder::new(fs::File::create(&tampered).unwrap(), LEVEL).unwrap());
+        append(&mut builder, "data/global-settings.json", b"{}").unwrap();
+        let manifest = BackupManifest {
+            format: FORMAT_VERSION,
+            app_version: "1.0.0".to_string(),
+            created_at: 0,
+            include_logs: false,
+            files: vec![BackupFile {
+                path: "data/global-settings.json".to_string(),
+                size: 2,
+                sha256: sha256_hex(b"[]"),
+            }],
+        };
+        append(&mut builder, MANIFEST_NAME, &serde_json::to_vec(&manifest).unwrap()).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+        assert!(verify_archive(&tampered).unwrap_err().to_string().contains("doesn't match"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn rejects_entries_outside_data_and_mods() {
+        for name in ["data/../../etc/passwd", "/data/x", "data/C:\\evil", "mods/a\\..\\..\\evil", "logs/x"] {
+            assert!(!safe_entry_path(name), "{}", name);
+        }
+        assert!(safe_entry_path("mods/aircraft/B738/model.glb"));
+
+        // tar::Builder refuses `..`, so the name is written into the header directly
+        let root = std::env::temp_dir().join(format!("backup-malicious-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let archive = root.join("malicious.tar.zst");
+        let mut builder = tar::Builder::new(zstd::Encoder::new(fs::File::create(&archive).unwrap(), LEVEL).unwrap());
+        let name = b"data/../../escaped.txt";
+        let mut header = tar::Header::new_old();
+        header.as_old_mut().name[..name.len()].copy_from_slice(name);
+        header.set_size(4);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append(&header, &b"evil"[..]).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let error = verify_archive(&archive).unwrap_err();
+        assert!(matches!(error, Error::InvalidInput(_)));
+        assert!(error.to_string().contains("unexpected file"));
+        let data = root.join("a").join("data");
+        assert_eq!(extract_archive(&archive, &data, &root.join("a").join("mods")).unwrap(), 0);
+        assert!(!root.join("escaped.txt").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,354 @@
+//! FAA d-TPP charts (airport diagrams and approach plates)
+//!
+//! Downloads the d-TPP metafile for the current 28-day cycle to find an
+//! airport's charts, and fetches chart PDFs on demand from aeronav.faa.gov.
+//! Both are cached under `charts/{cycle}/` in the app data folder; previous
+//! cycles are deleted when a new one is downloaded. Remote clients get the
+//! chart list from `/api/charts/{icao}` and the PDFs from
+//! `/api/charts/{icao}/{pdf}`.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{Datelike, NaiveDate, Utc};
+use parking_lot::RwLock;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+use crate::error::{Error, Result};
+use crate::http_client;
+use crate::weather;
+
+const DTPP_BASE_URL: &str = "https://aeronav.faa.gov/d-tpp";
+
+/// Chart types and their categories, in display order
+const CHART_TYPES: &[(&str, &str)] = &[
+    ("APD", "Airport diagram"),
+    ("HOT", "Hot spots"),
+    ("LAH", "LAHSO"),
+    ("MIN", "Minimums"),
+    ("DP", "Departure"),
+    ("ODP", "Obstacle departure"),
+    ("STAR", "Arrival"),
+    ("IAP", "Approach"),
+    ("DAU", "Diverse vector area"),
+];
+
+/// Chart from the d-TPP
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Chart {
+    /// d-TPP chart code ("APD", "IAP", "DP", "STAR", ...)
+    pub code: String,
+    /// Readable chart type for grouping (e.g., "Approach")
+    pub category: String,
+    /// Chart name (e.g., "ILS OR LOC RWY 04R")
+    pub name: String,
+    pub pdf_name: String,
+    /// URL usable by remote clients (e.g., "/api/charts/KBOS/00058AD.PDF")
+    pub url: String,
+}
+
+/// Charts for an airport
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AirportCharts {
+    pub icao: String,
+    /// d-TPP cycle (e.g., "2410")
+    pub cycle: String,
+    /// URL of the airport diagram, if the airport has one
+    pub airport_diagram: Option<String>,
+    pub charts: Vec<Chart>,
+}
+
+/// Charts by airport for one cycle (pdf_name and code/name only; URLs are built per request)
+type ChartIndex = HashMap<String, Vec<(String, String, String)>>;
+
+/// Chart state (managed by Tauri)
+pub struct ChartState {
+    index: RwLock<Option<(String, Arc<ChartIndex>)>>,
+}
+
+impl ChartState {
+    pub fn new() -> Self {
+        Self {
+            index: RwLock::new(None),
+        }
+    }
+}
+
+impl Default for ChartState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// AIRAC cycle (YYNN) in effect on a date
+pub fn airac_cycle(date: NaiveDate) -> String {
+    // Cycle 2001 became effective on 2 January 2020; cycles are 28 days long
+    let reference = NaiveDate::from_ymd_opt(2020, 1, 2).unwrap_or_default();
+    let cycles = (date - reference).num_days().div_euclid(28);
+    let effective = reference + chrono::Duration::days(cycles * 28);
+    format!("{:02}{:02}", effective.year() % 100, effective.ordinal0() / 28 + 1)
+}
+
+fn text_of(reader: &mut Reader<&[u8]>, element: &BytesStart) -> String {
+    reader
+        .read_text(element.name())
+        .map(|t| t.trim().to_string())
+        .unwrap_or_default()
+}
+
+fn attr(element: &BytesStart, name: &[u8]) -> Option<String> {
+    element
+        .attributes()
+        .with_checks(false)
+        .filter_map(|a| a.ok())
+        .find(|a| a.key.as_ref() == name)
+        .map(|a| String::from_utf8_lossy(&a.value).trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Parse the d-TPP metafile into charts per airport ICAO code
+pub fn parse_metafile(content: &str) -> ChartIndex {
+    let mut reader = Reader::from_str(content);
+    reader.config_mut().trim_text(true);
+
+    let mut index = ChartIndex::new();
+    let mut airport: Option<String> = None;
+    let (mut code, mut name, mut pdf_name) = (String::new(), String::new(), String::new());
+    let mut deleted = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) => match e.name().as_ref() {
+                b"airport_name" => {
+                    // US airports without an ICAO code get the FAA LID with a K prefix
+                    airport = attr(e, b"icao_ident")
+                        .or_else(|| attr(e, b"apt_ident").map(|lid| format!("K{}", lid)))
+                        .map(|i| i.to_uppercase());
+                }
+                b"chart_code" => code = text_of(&mut reader, e),
+                b"chart_name" => name = text_of(&mut reader, e),
+                b"pdf_name" => pdf_name = text_of(&mut reader, e),
+                // "D" marks charts deleted this cycle
+                b"useraction" => deleted = text_of(&mut reader, e) == "D",
+                b"record" => {
+                    (code, name, pdf_name) = (String::new(), String::new(), String::new());
+                    deleted = false;
+                }
+                _ => {}
+            },
+            Ok(Event::End(ref e)) => match e.name().as_ref() {
+                b"record" => {
+                    if let Some(icao) = airport.as_ref().filter(|_| !deleted && !pdf_name.is_empty()) {
+                        index.entry(icao.clone()).or_default().push((
+                            std::mem::take(&mut code),
+                            std::mem::take(&mut name),
+                            std::mem::take(&mut pdf_name),
+                        ));
+                    }
+                }
+                b"airport_name" => airport = None,
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(e) => {
+                warn!("[Charts] Metafile XML error at {}: {}", reader.error_position(), e);
+                break;
+            }
+        }
+    }
+    index
+}
+
+fn charts_root(app: &AppHandle) -> Result<PathBuf> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))?;
+    Ok(app_data.join("charts"))
+}
+
+async fn download(url: &str) -> Result<Vec<u8>> {
+    let response = http_client::client()
+        .get(url)
+        .timeout(Duration::from_secs(300))
+        .send()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to download {}: {}", url, e)))?;
+    if !response.status().is_success() {
+        return Err(Error::Upstream(format!("HTTP error {} for {}", response.status(), url)));
+    }
+    Ok(response.bytes().await.map_err(|e| Error::Upstream(e.to_string()))?.to_vec())
+}
+
+/// Chart index for the current cycle, downloading the metafile on first use
+async fn get_index(app: &AppHandle) -> Result<(String, Arc<ChartIndex>)> {
+    let cycle = airac_cycle(Utc::now().date_naive());
+    if let Some((loaded, index)) = app.state::<ChartState>().index.read().clone() {
+        if loaded == cycle {
+            return Ok((loaded, index));
+        }
+    }
+
+    let root = charts_root(app)?;
+    let dir = root.join(&cycle);
+    let path = dir.join("d-TPP_Metafile.xml");
+    if !path.is_file() {
+        let bytes = download(&format!("{}/{}/xml_data/d-TPP_Metafile.xml", DTPP_BASE_URL, cycle)).await?;
+        fs::create_dir_all(&dir).map_err(|e| Error::Io(format!("Failed to create charts folder: {}", e)))?;
+        fs::write(&path, bytes).map_err(|e| Error::Io(format!("Failed to cache d-TPP metafile: {}", e)))?;
+        info!("[Charts] Downloaded d-TPP metafile for cycle {}", cycle);
+
+        // Charts from previous cycles are superseded
+        for entry in fs::read_dir(&root).into_iter().flatten().filter_map(|e| e.ok()) {
+            if entry.file_name() != cycle.as_str() {
+                let _ = fs::remove_dir_all(entry.path());
+            }
+        }
+    }
+
+    let index = tauri::async_runtime::spawn_blocking(move || {
+        fs::read_to_string(&path)
+            .map(|content| parse_metafile(&content))
+            .map_err(|e| Error::Io(format!("Failed to read d-TPP metafile: {}", e)))
+    })
+    .await
+    .map_err(|e| Error::Internal(format!("Chart index load failed: {}", e)))??;
+    let index = Arc::new(index);
+    *app.state::<ChartState>().index.write() = Some((cycle.clone(), index.clone()));
+    Ok((cycle, index))
+}
+
+/// Charts for an airport (None if the d-TPP has none, e.g., outside the US)
+pub async fn get_charts(app: &AppHandle, icao: &str) -> Result<Option<AirportCharts>> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    let (cycle, index) = get_index(app).await?;
+    let Some(records) = index.get(&icao) else {
+        return Ok(None);
+    };
+
+    let rank = |code: &str| {
+        CHART_TYPES
+            .iter()
+            .position(|(c, _)| *c == code)
+            .unwrap_or(CHART_TYPES.len())
+    };
+    let category = |code: &str| {
+        CHART_TYPES
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map_or("Other", |(_, category)| category)
+            .to_string()
+    };
+    let mut charts: Vec<Chart> = records
+        .iter()
+        .map(|(code, name, pdf_name)| Chart {
+            code: code.clone(),
+            category: category(code),
+            name: name.clone(),
+            url: format!("/api/charts/{}/{}", icao, pdf_name),
+            pdf_name: pdf_name.clone(),
+        })
+        .collect();
+    charts.sort_by(|a, b| rank(&a.code).cmp(&rank(&b.code)).then_with(|| a.name.cmp(&b.name)));
+
+    Ok(Some(AirportCharts {
+        airport_diagram: charts.iter().find(|c| c.code == "APD").map(|c| c.url.clone()),
+        icao,
+        cycle,
+        charts,
+    }))
+}
+
+/// Local path of a chart PDF, downloading it first if needed. Only PDFs listed
+/// for the airport in the current cycle are served.
+pub async fn get_chart_file(app: &AppHandle, icao: &str, pdf_name: &str) -> Result<Option<PathBuf>> {
+    let Some(charts) = get_charts(app, icao).await? else {
+        return Ok(None);
+    };
+    let Some(chart) = charts.charts.iter().find(|c| c.pdf_name.eq_ignore_ascii_case(pdf_name)) else {
+        return Ok(None);
+    };
+
+    let path = charts_root(app)?.join(&charts.cycle).join(&chart.pdf_name);
+    if !path.is_file() {
+        let bytes = download(&format!("{}/{}/{}", DTPP_BASE_URL, charts.cycle, chart.pdf_name)).await?;
+        fs::write(&path, bytes).map_err(|e| Error::Io(format!("Failed to cache chart: {}", e)))?;
+    }
+    Ok(Some(path))
+}
+
+/// Initialize chart state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_charts(app: &AppHandle) {
+    app.manage(ChartState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the d-TPP charts for an airport (None if it has none)
+#[tauri::command]
+pub async fn get_airport_charts(app: AppHandle, icao: String) -> Result<Option<AirportCharts>> {
+    get_charts(&app, &icao).await
+}
+
+/// Get the local path of a chart PDF (downloaded on first use)
+#[tauri::command]
+pub async fn get_chart_pdf_path(app: AppHandle, icao: String, pdf_name: String) -> Result<String> {
+    get_chart_file(&app, &icao, &pdf_name)
+        .await?
+        .map(|p| p.to_string_lossy().replace('\\', "/"))
+        .ok_or_else(|| Error::NotFound(format!("No chart {} for {}", pdf_name, icao)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_metafile() {
+        let xml = r#"<?xml version="1.0"?>
+            <digital_tpp cycle="2410">
+              <state_code ID="MA">
+                <city_name ID="BOSTON">
+                  <airport_name ID="LOGAN" apt_ident="BOS" icao_ident="KBOS">
+                    <record><chart_code>APD</chart_code><chart_name>AIRPORT DIAGRAM</chart_name>
+                      <useraction></useraction><pdf_name>00058AD.PDF</pdf_name></record>
+                    <record><chart_code>IAP</chart_code><chart_name>ILS OR LOC RWY 04R</chart_name>
+                      <useraction>D</useraction><pdf_name>00058IL4R.PDF</pdf_name></record>
+                  </airport_name>
+                  <airport_name ID="TEST" apt_ident="1B9" icao_ident="">
+                    <record><chart_code>IAP</chart_code><chart_name>RNAV (GPS) RWY 34</chart_name>
+                      <pdf_name>05000R34.PDF</pdf_name></record>
+                  </airport_name>
+                </city_name>
+              </state_code>
+            </digital_tpp>"#;
+        let index = parse_metafile(xml);
+        assert_eq!(
+            index["KBOS"],
+            vec![(
+                "APD".to_string(),
+                "AIRPORT DIAGRAM".to_string(),
+                "00058AD.PDF".to_string()
+            )]
+        );
+        assert_eq!(index["K1B9"][0].2, "05000R34.PDF");
+
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        assert_eq!(airac_cycle(date(2024, 1, 25)), "2401");
+        assert_eq!(airac_cycle(date(2024, 1, 24)), "2313");
+        assert_eq!(airac_cycle(date(2024, 10, 3)), "2410");
+    }
+}
@@ -0,0 +1,175 @@
+//! Chunked, resumable downloads of large mod files
+//!
+//! Remote clients on Wi-Fi can lose the connection halfway through a
+//! several-hundred-MB model, and a plain GET then starts over from the first
+//! byte. `/api/chunked/{asset path}` serves the same files as the mod and
+//! FSLTL endpoints (`/api/chunked/mods/aircraft/B738/model.glb` for
+//! `/api/mods/aircraft/B738/model.glb`) in fixed-size chunks instead:
+//!
+//! - without a query it returns the file's [`ChunkManifest`]: size, SHA-256
+//!   of the whole file and of every chunk
+//! - `?chunk=N&hash=H` returns chunk N, or 409 if the file no longer has
+//!   hash H (it changed mid-download and the client must start over)
+//!
+//! Clients keep verified chunks and only fetch the ones they are missing
+//! after a dropped connection (see the content cache in service-worker.js).
+//! Manifests are remembered by path, size and modification time, so a file
+//! is only hashed again when it changes.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+/// Chunk size (bytes)
+pub const CHUNK_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Manifests remembered at most (all are forgotten when full)
+const MAX_MANIFESTS: usize = 256;
+
+/// Remembered manifests by file
+static MANIFESTS: Mutex<Option<HashMap<PathBuf, Arc<ChunkManifest>>>> = Mutex::new(None);
+
+/// How a file is split into chunks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChunkManifest {
+    pub size: u64,
+    /// Unix ms
+    pub modified_ms: u64,
+    pub content_type: String,
+    pub chunk_size: u64,
+    /// SHA-256 of the whole file (hex)
+    pub hash: String,
+    /// SHA-256 of each chunk (hex), in order
+    pub chunks: Vec<String>,
+}
+
+impl ChunkManifest {
+    /// Byte offset and length of a chunk
+    pub fn chunk_range(&self, index: usize) -> Option<(u64, u64)> {
+        if index >= self.chunks.len() {
+            return None;
+        }
+        let offset = index as u64 * self.chunk_size;
+        Some((offset, self.chunk_size.min(self.size - offset)))
+    }
+}
+
+fn modified_ms(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Hash a file in one pass, whole and per chunk
+fn build(path: &Path, metadata: &fs::Metadata, chunk_size: u64) -> io::Result<ChunkManifest> {
+    let mut file = fs::File::open(path)?;
+    let mut whole = Sha256::new();
+    let mut chunks = Vec::new();
+    let mut size = 0;
+    let mut buffer = vec![0u8; chunk_size as usize];
+    loop {
+        let mut filled = 0;
+        while filled < buffer.len() {
+            let read = file.read(&mut buffer[filled..])?;
+            if read == 0 {
+                break;
+            }
+            filled += read;
+        }
+        if filled == 0 {
+            break;
+        }
+        size += filled as u64;
+        whole.update(&buffer[..filled]);
+        chunks.push(hex::encode(Sha256::digest(&buffer[..filled])));
+        if filled < buffer.len() {
+            break;
+        }
+    }
+    Ok(ChunkManifest {
+        size,
+        modified_ms: modified_ms(metadata),
+        content_type: mime_guess::from_path(path).first_or_octet_stream().to_string(),
+        chunk_size,
+        hash: hex::encode(whole.finalize()),
+        chunks,
+    })
+}
+
+/// A file's chunk manifest (blocking: hashes the file unless it is unchanged
+/// since the last request)
+pub fn manifest(path: &Path) -> io::Result<Arc<ChunkManifest>> {
+    let metadata = fs::metadata(path)?;
+    if let Some(manifest) = MANIFESTS.lock().get_or_insert_with(HashMap::new).get(path) {
+        if manifest.size == metadata.len() && manifest.modified_ms == modified_ms(&metadata) {
+            return Ok(manifest.clone());
+        }
+    }
+
+    let manifest = Arc::new(build(path, &metadata, CHUNK_BYTES)?);
+    let mut manifests = MANIFESTS.lock();
+    let manifests = manifests.get_or_insert_with(HashMap::new);
+    if manifests.len() >= MAX_MANIFESTS {
+        manifests.clear();
+    }
+    manifests.insert(path.to_path_buf(), manifest.clone());
+    Ok(manifest)
+}
+
+/// Read one chunk (blocking)
+pub fn read_chunk(path: &Path, manifest: &ChunkManifest, index: usize) -> io::Result<Vec<u8>> {
+    let (offset, len) = manifest
+        .chunk_range(index)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("No chunk {}", index)))?;
+    let mut file = fs::File::open(path)?;
+    file.seek(SeekFrom::Start(offset))?;
+    let mut bytes = vec![0u8; len as usize];
+    file.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_files_into_hashed_chunks() {
+        let dir = std::env::temp_dir().join(format!("chunked-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("model.glb");
+        let content: Vec<u8> = (0..10u8).collect();
+        fs::write(&path, &content).unwrap();
+
+        let manifest = build(&path, &fs::metadata(&path).unwrap(), 4).unwrap();
+        assert_eq!(manifest.size, 10);
+        assert_eq!(manifest.chunks.len(), 3);
+        assert_eq!(manifest.hash, hex::encode(Sha256::digest(&content)));
+        assert_eq!(manifest.chunks[2], hex::encode(Sha256::digest([8u8, 9])));
+        assert_eq!(manifest.chunk_range(1), Some((4, 4)));
+        assert_eq!(manifest.chunk_range(2), Some((8, 2)));
+        assert_eq!(manifest.chunk_range(3), None);
+        assert_eq!(read_chunk(&path, &manifest, 2).unwrap(), [8, 9]);
+
+        // Exact multiples of the chunk size have no empty chunk at the end
+        fs::write(&path, &content[..8]).unwrap();
+        let manifest = build(&path, &fs::metadata(&path).unwrap(), 4).unwrap();
+        assert_eq!(manifest.chunks.len(), 2);
+        assert_eq!(manifest.size, 8);
+
+        fs::write(&path, b"").unwrap();
+        assert!(build(&path, &fs::metadata(&path).unwrap(), 4).unwrap().chunks.is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
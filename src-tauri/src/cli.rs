@@ -0,0 +1,185 @@
+//! Command-line arguments
+//!
+//! Shortcuts and scripts can launch straight into a configured view:
+//!
+//! ```text
+//! towercab-3d --airport KBOS --bookmark "Tower North" --port 9000 --fullscreen --profile event
+//! ```
+//!
+//! - `--airport ICAO`: select the airport once loading finishes
+//! - `--bookmark NAME|SLOT`: load a camera bookmark at that airport (by name or slot number)
+//! - `--port PORT`: start the HTTP server on this port (even if it is disabled in settings)
+//! - `--fullscreen`: open the window fullscreen
+//! - `--headless`: start without a window, with the HTTP server on (for a
+//!   machine that only serves remote browsers, see autostart.rs)
+//! - `--profile NAME`: use `global-settings.NAME.json` instead of `global-settings.json`
+//!   (created from the main settings on first use)
+//!
+//! Arguments are parsed at the start of `run()`; the fullscreen and headless
+//! flags are applied to the window config before the window is created, the rest by the setup
+//! closure and the frontend (`get_startup_args`). Unknown arguments (such as
+//! deep link URLs passed by the OS) are ignored.
+
+use std::sync::OnceLock;
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupArgs {
+    pub airport: Option<String>,
+    pub bookmark: Option<String>,
+    pub port: Option<u16>,
+    pub fullscreen: bool,
+    pub headless: bool,
+    pub profile: Option<String>,
+}
+
+/// Parsed arguments and problems found while parsing (logged once logging is up)
+static ARGS: OnceLock<(StartupArgs, Vec<String>)> = OnceLock::new();
+
+/// Parse arguments (without the program name). Invalid values for known
+/// flags are reported; unknown arguments are skipped.
+pub fn parse(args: impl IntoIterator<Item = String>) -> (StartupArgs, Vec<String>) {
+    let mut parsed = StartupArgs::default();
+    let mut problems = Vec::new();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+        if flag == "--fullscreen" {
+            parsed.fullscreen = true;
+            continue;
+        }
+        if flag == "--headless" {
+            parsed.headless = true;
+            continue;
+        }
+        if !matches!(flag.as_str(), "--airport" | "--bookmark" | "--port" | "--profile") {
+            if flag.starts_with("--") {
+                problems.push(format!("Unknown argument {}", flag));
+            }
+            continue;
+        }
+        let Some(value) = inline_value.or_else(|| args.next()).filter(|v| !v.trim().is_empty()) else {
+            problems.push(format!("{} needs a value", flag));
+            continue;
+        };
+        let value = value.trim().to_string();
+        match flag.as_str() {
+            "--airport" => parsed.airport = Some(value.to_uppercase()),
+            "--bookmark" => parsed.bookmark = Some(value),
+            "--port" => match value.parse::<u16>() {
+                Ok(port) if port > 0 => parsed.port = Some(port),
+                _ => problems.push(format!("Invalid port '{}'", value)),
+            },
+            _ => {
+                if value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+                    parsed.profile = Some(value);
+                } else {
+                    problems.push(format!(
+                        "Invalid profile name '{}' (letters, digits, - and _ only)",
+                        value
+                    ));
+                }
+            }
+        }
+    }
+    (parsed, problems)
+}
+
+fn parsed() -> &'static (StartupArgs, Vec<String>) {
+    ARGS.get_or_init(|| parse(std::env::args().skip(1)))
+}
+
+/// The arguments the app was started with (parsed on first use)
+pub fn args() -> &'static StartupArgs {
+    &parsed().0
+}
+
+/// Global settings file name for the selected profile
+pub fn settings_file_name() -> String {
+    match &args().profile {
+        Some(profile) => format!("global-settings.{}.json", profile),
+        None => "global-settings.json".to_string(),
+    }
+}
+
+/// Log the arguments and create the profile's settings file if needed.
+/// Call in the Tauri setup closure (after logging).
+pub fn apply_startup_args(app: &AppHandle) {
+    let (args, problems) = parsed();
+    for problem in problems {
+        warn!("[CLI] {}", problem);
+    }
+    if *args != StartupArgs::default() {
+        info!("[CLI] Startup arguments: {:?}", args);
+    }
+
+    // A new profile starts from the main settings
+    if let Some(profile) = &args.profile {
+        if let Ok(app_data) = app.path().app_data_dir() {
+            let main = app_data.join("global-settings.json");
+            let target = app_data.join(settings_file_name());
+            if main.exists() && !target.exists() {
+                match std::fs::copy(&main, &target) {
+                    Ok(_) => info!("[CLI] Created profile '{}' from the main settings", profile),
+                    Err(e) => warn!("[CLI] Failed to create profile '{}': {}", profile, e),
+                }
+            }
+        }
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the command-line arguments the app was started with
+#[tauri::command]
+pub fn get_startup_args() -> StartupArgs {
+    args().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_flags_and_skips_unknown_arguments() {
+        let args = [
+            "--airport",
+            "kbos",
+            "--bookmark=Tower North",
+            "--port",
+            "9000",
+            "--fullscreen",
+            "--headless",
+            "tc3d://oauth/callback?code=x",
+            "--profile",
+            "event",
+        ];
+        let (parsed, problems) = parse(args.iter().map(|a| a.to_string()));
+        assert!(problems.is_empty(), "{:?}", problems);
+        assert_eq!(
+            parsed,
+            StartupArgs {
+                airport: Some("KBOS".to_string()),
+                bookmark: Some("Tower North".to_string()),
+                port: Some(9000),
+                fullscreen: true,
+                headless: true,
+                profile: Some("event".to_string()),
+            }
+        );
+
+        let (parsed, problems) = parse(["--port", "99999", "--profile", "../x", "--airport"].map(String::from));
+        assert_eq!(parsed, StartupArgs::default());
+        assert_eq!(problems.len(), 3);
+    }
+}
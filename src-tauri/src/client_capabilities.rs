@@ -0,0 +1,317 @@
+//! Client capability negotiation
+//!
+//! Remote browsers report what they are (`POST /api/client/capabilities`):
+//! device class, an estimate of their bandwidth and the codecs they can
+//! decode. The host turns that into one configuration per device instead of
+//! serving every client for the weakest one:
+//!
+//! - aircraft WebSocket format and update rate (MessagePack + deltas and
+//!   fewer updates for tablets, phones and slow links)
+//! - model variants: a mod or FSLTL model can ship `<name>.low.glb`,
+//!   `<name>.ktx2.glb` or `<name>.draco.glb` next to `<name>.glb`, served in
+//!   its place to clients that want or can decode it
+//! - zstd-compressed models and JSON for clients that accept them
+//!
+//! The reported capabilities are kept in a cookie, so they apply to every
+//! later request from that browser (model loads, WebSocket upgrades) without
+//! the host keeping per-client state.
+
+use std::path::{Path, PathBuf};
+
+use axum::http::{header, HeaderMap};
+use serde::{Deserialize, Serialize};
+
+use crate::ws_encoding::{Encoding, WireFormat};
+use crate::zstd_cache;
+
+/// Cookie holding the reported capabilities
+pub const COOKIE_NAME: &str = "towercab-caps";
+
+/// Cookie lifetime (s)
+const COOKIE_MAX_AGE: u64 = 365 * 24 * 3600;
+
+/// Below this, a link is slow (kbit/s)
+const SLOW_LINK_KBPS: u32 = 5_000;
+/// Below this, a link is very slow (kbit/s)
+const VERY_SLOW_LINK_KBPS: u32 = 1_000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeviceClass {
+    #[default]
+    Desktop,
+    Tablet,
+    Phone,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Codec {
+    Ktx2,
+    Draco,
+    Zstd,
+}
+
+/// What a client reports about itself
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ClientCapabilities {
+    pub device_class: DeviceClass,
+    /// Estimated downlink (kbit/s), if the browser knows it
+    pub bandwidth_kbps: Option<u32>,
+    pub codecs: Vec<Codec>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelQuality {
+    #[default]
+    Full,
+    Reduced,
+}
+
+/// How the host serves a client
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientConfig {
+    pub device_class: DeviceClass,
+    pub model_quality: ModelQuality,
+    /// Model variant suffixes tried in order (e.g., `["low", "ktx2"]`)
+    pub model_variants: Vec<&'static str>,
+    /// Minimum time between aircraft WebSocket updates (ms, 0 = every update)
+    pub update_interval_ms: u64,
+    /// Aircraft WebSocket format
+    pub wire_format: WireFormat,
+    /// Whether models and JSON files are served zstd-compressed
+    pub zstd: bool,
+}
+
+impl ClientCapabilities {
+    /// Cookie value (`tablet:2000:ktx2-draco`)
+    fn to_cookie(&self) -> String {
+        let device = match self.device_class {
+            DeviceClass::Desktop => "desktop",
+            DeviceClass::Tablet => "tablet",
+            DeviceClass::Phone => "phone",
+        };
+        let bandwidth = self.bandwidth_kbps.map(|b| b.to_string()).unwrap_or_default();
+        let codecs: Vec<&str> = self
+            .codecs
+            .iter()
+            .map(|codec| match codec {
+                Codec::Ktx2 => "ktx2",
+                Codec::Draco => "draco",
+                Codec::Zstd => "zstd",
+            })
+            .collect();
+        format!("{}:{}:{}", device, bandwidth, codecs.join("-"))
+    }
+
+    fn from_cookie(value: &str) -> Option<Self> {
+        let mut parts = value.split(':');
+        let device_class = match parts.next()? {
+            "desktop" => DeviceClass::Desktop,
+            "tablet" => DeviceClass::Tablet,
+            "phone" => DeviceClass::Phone,
+            _ => return None,
+        };
+        let bandwidth_kbps = parts.next()?.parse().ok();
+        let codecs = parts
+            .next()
+            .unwrap_or_default()
+            .split('-')
+            .filter_map(|codec| match codec {
+                "ktx2" => Some(Codec::Ktx2),
+                "draco" => Some(Codec::Draco),
+                "zstd" => Some(Codec::Zstd),
+                _ => None,
+            })
+            .collect();
+        Some(Self {
+            device_class,
+            bandwidth_kbps,
+            codecs,
+        })
+    }
+}
+
+/// Configuration for a client (`accepts_zstd`: its requests allow zstd)
+pub fn negotiate(capabilities: &ClientCapabilities, accepts_zstd: bool) -> ClientConfig {
+    let bandwidth = capabilities.bandwidth_kbps.unwrap_or(u32::MAX);
+    let device = capabilities.device_class;
+
+    let model_quality = if device == DeviceClass::Phone || bandwidth < VERY_SLOW_LINK_KBPS {
+        ModelQuality::Reduced
+    } else {
+        ModelQuality::Full
+    };
+    let mut model_variants = Vec::new();
+    if model_quality == ModelQuality::Reduced {
+        model_variants.push("low");
+    }
+    if capabilities.codecs.contains(&Codec::Ktx2) {
+        model_variants.push("ktx2");
+    }
+    if capabilities.codecs.contains(&Codec::Draco) {
+        model_variants.push("draco");
+    }
+
+    let device_interval = match device {
+        DeviceClass::Desktop => 0,
+        DeviceClass::Tablet => 1_000,
+        DeviceClass::Phone => 2_000,
+    };
+    let link_interval = if bandwidth < VERY_SLOW_LINK_KBPS { 3_000 } else { 0 };
+
+    let constrained = device != DeviceClass::Desktop || bandwidth < SLOW_LINK_KBPS;
+    ClientConfig {
+        device_class: device,
+        model_quality,
+        model_variants,
+        update_interval_ms: device_interval.max(link_interval),
+        wire_format: WireFormat {
+            encoding: if constrained { Encoding::Msgpack } else { Encoding::Json },
+            delta: constrained,
+        },
+        zstd: accepts_zstd || capabilities.codecs.contains(&Codec::Zstd),
+    }
+}
+
+/// Capabilities a request's browser reported, if any
+pub fn from_headers(headers: &HeaderMap) -> Option<ClientCapabilities> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == COOKIE_NAME)
+        .and_then(|(_, value)| ClientCapabilities::from_cookie(value))
+}
+
+/// Configuration for a request's browser (`None` if it never negotiated)
+pub fn config_for(headers: &HeaderMap) -> Option<ClientConfig> {
+    from_headers(headers).map(|caps| negotiate(&caps, zstd_cache::accepts_zstd(headers)))
+}
+
+/// `Set-Cookie` value remembering capabilities
+pub fn set_cookie(capabilities: &ClientCapabilities) -> String {
+    format!(
+        "{}={}; Path=/; Max-Age={}; SameSite=Strict",
+        COOKIE_NAME,
+        capabilities.to_cookie(),
+        COOKIE_MAX_AGE
+    )
+}
+
+/// The variant of a model to serve: the first `<stem>.<variant>.<ext>` next
+/// to it in the client's preference order, or the model itself
+pub fn model_variant(path: &Path, config: &ClientConfig) -> PathBuf {
+    let (Some(stem), Some(extension)) = (path.file_stem(), path.extension()) else {
+        return path.to_path_buf();
+    };
+    if !matches!(extension.to_str(), Some("glb" | "gltf")) {
+        return path.to_path_buf();
+    }
+    config
+        .model_variants
+        .iter()
+        .map(|variant| {
+            let mut name = stem.to_os_string();
+            name.push(format!(".{}.", variant));
+            name.push(extension);
+            path.with_file_name(name)
+        })
+        .find(|candidate| candidate.is_file())
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use std::fs;
+
+    #[test]
+    fn tailors_config_to_device_and_link() {
+        let desktop = negotiate(&ClientCapabilities::default(), false);
+        assert_eq!(desktop.update_interval_ms, 0);
+        assert_eq!(desktop.wire_format.encoding, Encoding::Json);
+        assert!(!desktop.wire_format.delta);
+        assert!(desktop.model_variants.is_empty());
+
+        let tablet = negotiate(
+            &ClientCapabilities {
+                device_class: DeviceClass::Tablet,
+                bandwidth_kbps: Some(20_000),
+                codecs: vec![Codec::Ktx2, Codec::Draco],
+            },
+            true,
+        );
+        assert_eq!(tablet.model_quality, ModelQuality::Full);
+        assert_eq!(tablet.model_variants, vec!["ktx2", "draco"]);
+        assert_eq!(tablet.update_interval_ms, 1_000);
+        assert_eq!(tablet.wire_format.encoding, Encoding::Msgpack);
+        assert!(tablet.zstd);
+
+        let slow_desktop = negotiate(
+            &ClientCapabilities {
+                bandwidth_kbps: Some(600),
+                ..Default::default()
+            },
+            false,
+        );
+        assert_eq!(slow_desktop.model_quality, ModelQuality::Reduced);
+        assert_eq!(slow_desktop.model_variants, vec!["low"]);
+        assert_eq!(slow_desktop.update_interval_ms, 3_000);
+        assert!(slow_desktop.wire_format.delta);
+    }
+
+    #[test]
+    fn remembers_capabilities_in_a_cookie() {
+        let capabilities = ClientCapabilities {
+            device_class: DeviceClass::Phone,
+            bandwidth_kbps: None,
+            codecs: vec![Codec::Draco, Codec::Zstd],
+        };
+        let cookie = set_cookie(&capabilities);
+        let value = cookie.split(';').next().unwrap();
+        assert_eq!(value, "towercab-caps=phone::draco-zstd");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_str(&format!("theme=dark; {}", value)).unwrap(),
+        );
+        assert_eq!(from_headers(&headers), Some(capabilities));
+        assert!(config_for(&headers).unwrap().zstd);
+
+        headers.insert(header::COOKIE, HeaderValue::from_static("towercab-caps=fridge:1:"));
+        assert_eq!(from_headers(&headers), None);
+    }
+
+    #[test]
+    fn serves_the_preferred_model_variant() {
+        let dir = std::env::temp_dir().join(format!("caps-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let model = dir.join("model.glb");
+        fs::write(&model, b"glTF").unwrap();
+        fs::write(dir.join("model.draco.glb"), b"glTF").unwrap();
+
+        let mut config = ClientConfig {
+            model_variants: vec!["low", "ktx2", "draco"],
+            ..Default::default()
+        };
+        assert_eq!(model_variant(&model, &config), dir.join("model.draco.glb"));
+        fs::write(dir.join("model.low.glb"), b"glTF").unwrap();
+        assert_eq!(model_variant(&model, &config), dir.join("model.low.glb"));
+        config.model_variants.clear();
+        assert_eq!(model_variant(&model, &config), model);
+        assert_eq!(
+            model_variant(&dir.join("texture.png"), &config),
+            dir.join("texture.png")
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
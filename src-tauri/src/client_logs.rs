@@ -0,0 +1,148 @@
+//! Remote browser log ingestion
+//!
+//! Remote browsers (iPads, other PCs) batch their console errors and warnings
+//! and post them to `POST /api/client-logs`. Each entry is written to the host
+//! log under this module's target, prefixed with the client's identity (IP
+//! address, device and browser from its User-Agent, and a per-tab session ID),
+//! so problems on a device without developer tools end up in the host's log
+//! files next to the server's own messages.
+
+use std::net::IpAddr;
+
+use serde::Deserialize;
+use tracing::{error, info, warn};
+
+/// Entries accepted per batch (the rest are counted but not logged)
+const MAX_ENTRIES: usize = 100;
+
+/// Longest message logged (longer ones are truncated)
+const MAX_MESSAGE_CHARS: usize = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClientLogLevel {
+    Error,
+    Warn,
+    Info,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClientLogEntry {
+    pub level: ClientLogLevel,
+    pub message: String,
+}
+
+/// A batch of console messages from one browser tab
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientLogBatch {
+    /// Random ID for the browser tab, to tell apart tabs on the same device
+    #[serde(default)]
+    pub session_id: Option<String>,
+    /// Page path when the batch was sent
+    #[serde(default)]
+    pub page: Option<String>,
+    pub entries: Vec<ClientLogEntry>,
+}
+
+/// Short device and browser description from a User-Agent
+fn describe_user_agent(user_agent: &str) -> String {
+    let device = if user_agent.contains("iPad") {
+        "iPad"
+    } else if user_agent.contains("iPhone") {
+        "iPhone"
+    } else if user_agent.contains("Android") {
+        "Android"
+    } else if user_agent.contains("Windows") {
+        "Windows"
+    } else if user_agent.contains("Macintosh") {
+        // iPadOS requests the desktop site and reports itself as a Mac
+        "Mac/iPad"
+    } else if user_agent.contains("Linux") {
+        "Linux"
+    } else {
+        "Unknown device"
+    };
+    // Order matters: Edge and Chrome also claim Safari, Chrome on iOS is CriOS
+    let browser = if user_agent.contains("Edg/") {
+        "Edge"
+    } else if user_agent.contains("Firefox/") || user_agent.contains("FxiOS/") {
+        "Firefox"
+    } else if user_agent.contains("Chrome/") || user_agent.contains("CriOS/") {
+        "Chrome"
+    } else if user_agent.contains("Safari/") {
+        "Safari"
+    } else {
+        "unknown browser"
+    };
+    format!("{} {}", device, browser)
+}
+
+/// Client identity shown in the log (`192.168.1.20 iPad Safari #3f9a1c`)
+pub fn describe_client(addr: IpAddr, user_agent: Option<&str>, session_id: Option<&str>) -> String {
+    let mut client = format!("{} {}", addr, describe_user_agent(user_agent.unwrap_or_default()));
+    if let Some(session) = session_id.filter(|s| !s.is_empty()) {
+        let session: String = session.chars().filter(|c| c.is_ascii_alphanumeric()).take(8).collect();
+        client.push_str(&format!(" #{}", session));
+    }
+    client
+}
+
+fn truncate(message: &str) -> String {
+    let mut chars = message.chars();
+    let truncated: String = chars.by_ref().take(MAX_MESSAGE_CHARS).collect();
+    if chars.next().is_some() {
+        format!("{}... [truncated]", truncated)
+    } else {
+        truncated
+    }
+}
+
+/// Write a batch to the host log; returns the number of entries logged
+pub fn ingest(client: &str, batch: &ClientLogBatch) -> usize {
+    let page = batch.page.as_deref().unwrap_or("");
+    for entry in batch.entries.iter().take(MAX_ENTRIES) {
+        let message = truncate(&entry.message);
+        match entry.level {
+            ClientLogLevel::Error => error!("[Client {}] {} {}", client, page, message),
+            ClientLogLevel::Warn => warn!("[Client {}] {} {}", client, page, message),
+            ClientLogLevel::Info => info!("[Client {}] {} {}", client, page, message),
+        }
+    }
+    if batch.entries.len() > MAX_ENTRIES {
+        warn!(
+            "[Client {}] {} more messages dropped (batch limit {})",
+            client,
+            batch.entries.len() - MAX_ENTRIES,
+            MAX_ENTRIES
+        );
+    }
+    batch.entries.len().min(MAX_ENTRIES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_clients_and_limits_batches() {
+        let ipad = "Mozilla/5.0 (iPad; CPU OS 17_2 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) \
+                    Version/17.2 Mobile/15E148 Safari/604.1";
+        let addr: IpAddr = "192.168.1.20".parse().unwrap();
+        assert_eq!(
+            describe_client(addr, Some(ipad), Some("3f9a1c7e-55aa")),
+            "192.168.1.20 iPad Safari #3f9a1c7e"
+        );
+        let edge = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) \
+                    Chrome/120.0 Safari/537.36 Edg/120.0";
+        assert_eq!(describe_client(addr, Some(edge), None), "192.168.1.20 Windows Edge");
+
+        let batch: ClientLogBatch = serde_json::from_str(&format!(
+            r#"{{"sessionId":"abc","entries":[{}]}}"#,
+            vec![r#"{"level":"error","message":"boom"}"#; 150].join(",")
+        ))
+        .unwrap();
+        assert_eq!(ingest("test", &batch), MAX_ENTRIES);
+        assert!(truncate(&"x".repeat(5000)).ends_with("[truncated]"));
+    }
+}
@@ -0,0 +1,192 @@
+//! Simulated clock shared by the host and every remote browser
+//!
+//! The clock runs in real time, at an offset from UTC (e.g., force daytime
+//! during a night event) or frozen at a fixed time. It is persisted in the
+//! global settings (`clock` section) and every change is emitted as
+//! `sim-clock-changed` and relayed to remote browsers over `/api/clock/ws`, so
+//! all clients render the same lighting.
+
+use std::time::SystemTime;
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::info;
+
+use crate::error::{Error, Result};
+use crate::GlobalClockSettings;
+
+/// Clock modes
+pub const MODE_REAL: &str = "real";
+pub const MODE_OFFSET: &str = "offset";
+pub const MODE_FIXED: &str = "fixed";
+
+/// Current simulated clock
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimClock {
+    /// "real", "offset" or "fixed"
+    pub mode: String,
+    /// Offset from UTC in ms (offset mode)
+    pub offset_ms: i64,
+    /// Frozen time (Unix ms, fixed mode)
+    pub fixed_time: Option<u64>,
+    /// Simulated time when this was produced (Unix ms)
+    pub simulated_time: u64,
+    /// Real time when this was produced (Unix ms)
+    pub real_time: u64,
+}
+
+/// Simulated clock state (managed by Tauri)
+pub struct ClockState {
+    settings: RwLock<GlobalClockSettings>,
+    /// Clock changes for WebSocket relay
+    pub events: broadcast::Sender<SimClock>,
+}
+
+impl ClockState {
+    pub fn new(settings: GlobalClockSettings) -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            settings: RwLock::new(settings),
+            events,
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Check a clock configuration
+pub fn validate(settings: &GlobalClockSettings) -> Result<()> {
+    match settings.mode.as_str() {
+        MODE_REAL | MODE_OFFSET => Ok(()),
+        MODE_FIXED if settings.fixed_time.is_some() => Ok(()),
+        MODE_FIXED => Err(Error::invalid_input("Fixed clock mode requires fixedTime")),
+        other => Err(Error::InvalidInput(format!("Unknown clock mode: {}", other))),
+    }
+}
+
+/// Simulated time for a configuration at a real time (Unix ms)
+pub fn simulated_time(settings: &GlobalClockSettings, real_time: u64) -> u64 {
+    match settings.mode.as_str() {
+        MODE_OFFSET => (real_time as i64).saturating_add(settings.offset_ms).max(0) as u64,
+        MODE_FIXED => settings.fixed_time.unwrap_or(real_time),
+        _ => real_time,
+    }
+}
+
+fn snapshot(settings: &GlobalClockSettings) -> SimClock {
+    let real_time = now_ms();
+    SimClock {
+        mode: settings.mode.clone(),
+        offset_ms: settings.offset_ms,
+        fixed_time: settings.fixed_time,
+        simulated_time: simulated_time(settings, real_time),
+        real_time,
+    }
+}
+
+/// Current simulated clock
+pub fn get_clock(app: &AppHandle) -> SimClock {
+    snapshot(&app.state::<ClockState>().settings.read())
+}
+
+/// Current simulated time (Unix ms)
+pub fn now(app: &AppHandle) -> u64 {
+    simulated_time(&app.state::<ClockState>().settings.read(), now_ms())
+}
+
+/// Take the clock from saved global settings, notifying clients if it changed.
+/// Called whenever global settings are written.
+pub fn apply_settings(app: &AppHandle, settings: &GlobalClockSettings) {
+    if validate(settings).is_err() {
+        return;
+    }
+    let state = app.state::<ClockState>();
+    {
+        let mut current = state.settings.write();
+        if *current == *settings {
+            return;
+        }
+        *current = settings.clone();
+    }
+
+    let clock = snapshot(settings);
+    info!(
+        "[Clock] Simulated clock set to {} ({} ms from real time)",
+        clock.mode,
+        clock.simulated_time as i64 - clock.real_time as i64
+    );
+    let _ = app.emit("sim-clock-changed", &clock);
+    let _ = state.events.send(clock);
+}
+
+/// Change the simulated clock, persist it and notify clients
+pub fn set_clock(app: &AppHandle, settings: GlobalClockSettings) -> Result<SimClock> {
+    validate(&settings)?;
+    let mut global = crate::read_global_settings(app.clone())?;
+    global.clock = settings;
+    // Writing global settings applies the clock
+    crate::write_global_settings(app.clone(), global)?;
+    Ok(get_clock(app))
+}
+
+/// Initialize the simulated clock from saved global settings.
+/// Call this in the Tauri setup closure.
+pub fn init_clock(app: &AppHandle) {
+    let settings = crate::read_global_settings(app.clone())
+        .map(|s| s.clock)
+        .ok()
+        .filter(|c| validate(c).is_ok())
+        .unwrap_or_default();
+    if settings.mode != MODE_REAL {
+        info!("[Clock] Using simulated clock ({})", settings.mode);
+    }
+    app.manage(ClockState::new(settings));
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the simulated clock
+#[tauri::command]
+pub fn get_sim_clock(app: AppHandle) -> SimClock {
+    get_clock(&app)
+}
+
+/// Set the simulated clock (real time, offset from UTC or fixed time) for all clients
+#[tauri::command]
+pub fn set_sim_clock(app: AppHandle, clock: GlobalClockSettings) -> Result<SimClock> {
+    set_clock(&app, clock)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_simulated_time() {
+        let real = 1_700_000_000_000;
+        let mut settings = GlobalClockSettings::default();
+        assert_eq!(simulated_time(&settings, real), real);
+
+        settings.mode = MODE_OFFSET.to_string();
+        settings.offset_ms = -12 * 3_600_000;
+        assert_eq!(simulated_time(&settings, real), real - 12 * 3_600_000);
+
+        settings.mode = MODE_FIXED.to_string();
+        assert!(validate(&settings).is_err());
+        settings.fixed_time = Some(1_600_000_000_000);
+        assert_eq!(simulated_time(&settings, real), 1_600_000_000_000);
+
+        settings.mode = "warp".to_string();
+        assert!(validate(&settings).is_err());
+    }
+}
@@ -0,0 +1,301 @@
+//! Content manifest for remote client caches
+//!
+//! `/api/manifest` lists every asset the server serves from disk (enabled
+//! aircraft and tower mods, converted FSLTL models and overlay files) with its
+//! size and SHA-256, so remote browsers can keep them in a persistent cache
+//! between sessions and only fetch what changed. The manifest's own hash is
+//! sent as its `ETag`.
+//!
+//! Hashes are remembered by path, size and modification time in
+//! `manifest-hashes.json` in the app cache folder, so only new or changed
+//! files are read when the manifest is built again.
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Manager};
+use tracing::warn;
+
+use crate::{fsltl_scan, mods, overlays};
+
+/// Remembered hashes, loaded from disk on first use
+static HASHES: Mutex<Option<HashMap<PathBuf, CachedHash>>> = Mutex::new(None);
+
+/// What an asset is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentKind {
+    Aircraft,
+    Towers,
+    Fsltl,
+    Overlay,
+}
+
+/// A servable asset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+    /// Path to request it from (e.g., "/api/mods/aircraft/B738/model.glb")
+    pub url: String,
+    pub kind: ContentKind,
+    pub size: u64,
+    /// SHA-256 of the content (hex)
+    pub hash: String,
+}
+
+/// All servable assets
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentManifest {
+    /// Unix timestamp (ms)
+    pub generated_at: u64,
+    /// SHA-256 over all entries (changes whenever any asset does)
+    pub hash: String,
+    pub entries: Vec<ManifestEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedHash {
+    size: u64,
+    modified_ms: u64,
+    hash: String,
+}
+
+/// A file to list, with the URL it is served at
+struct Asset {
+    url: String,
+    kind: ContentKind,
+    path: PathBuf,
+}
+
+/// URL path from segments (each percent-encoded)
+fn url_path<'a>(segments: impl IntoIterator<Item = &'a str>) -> String {
+    let mut url = url::Url::parse("http://host/").expect("valid base URL");
+    url.path_segments_mut()
+        .expect("base URL has a path")
+        .pop_if_empty()
+        .extend(segments.into_iter().flat_map(|s| s.split('/')));
+    url.path().to_string()
+}
+
+fn hash_file(path: &Path) -> io::Result<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 256 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Size and hash of a file, reusing the remembered hash if it hasn't changed
+fn file_hash(hashes: &mut HashMap<PathBuf, CachedHash>, path: &Path) -> Option<(u64, String)> {
+    let metadata = fs::metadata(path).ok()?;
+    let modified_ms = metadata
+        .modified()
+        .ok()
+        .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    if let Some(cached) = hashes.get(path) {
+        if cached.size == metadata.len() && cached.modified_ms == modified_ms {
+            return Some((cached.size, cached.hash.clone()));
+        }
+    }
+    let hash = match hash_file(path) {
+        Ok(hash) => hash,
+        Err(e) => {
+            warn!("[Manifest] Failed to hash {}: {}", path.display(), e);
+            return None;
+        }
+    };
+    hashes.insert(
+        path.to_path_buf(),
+        CachedHash {
+            size: metadata.len(),
+            modified_ms,
+            hash: hash.clone(),
+        },
+    );
+    Some((metadata.len(), hash))
+}
+
+/// Manifest of assets, forgetting remembered hashes of files no longer listed
+fn build(assets: Vec<Asset>, hashes: &mut HashMap<PathBuf, CachedHash>) -> ContentManifest {
+    let mut entries = Vec::with_capacity(assets.len());
+    let mut listed = HashSet::new();
+    for asset in &assets {
+        if let Some((size, hash)) = file_hash(hashes, &asset.path) {
+            listed.insert(asset.path.as_path());
+            entries.push(ManifestEntry {
+                url: asset.url.clone(),
+                kind: asset.kind,
+                size,
+                hash,
+            });
+        }
+    }
+    hashes.retain(|path, _| listed.contains(path.as_path()));
+    entries.sort_by(|a, b| a.url.cmp(&b.url));
+    entries.dedup_by(|a, b| a.url == b.url);
+
+    let mut hasher = Sha256::new();
+    for entry in &entries {
+        hasher.update(entry.url.as_bytes());
+        hasher.update([0]);
+        hasher.update(entry.hash.as_bytes());
+    }
+    ContentManifest {
+        generated_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        hash: hex::encode(hasher.finalize()),
+        entries,
+    }
+}
+
+/// Files served by the mod, FSLTL and overlay endpoints
+fn assets(app: &AppHandle) -> Vec<Asset> {
+    let mut assets = Vec::new();
+    for (mod_type, kind) in [("aircraft", ContentKind::Aircraft), ("towers", ContentKind::Towers)] {
+        for entry in mods::enabled_mods(app, mod_type) {
+            for file in &entry.files {
+                assets.push(Asset {
+                    url: url_path(["api", "mods", mod_type, &entry.name, &file.name]),
+                    kind,
+                    path: Path::new(&entry.path).join(&file.name),
+                });
+            }
+        }
+    }
+
+    let output_path = crate::read_global_settings(app.clone())
+        .ok()
+        .and_then(|s| s.fsltl.output_path);
+    if let Some(output_path) = output_path {
+        match fsltl_scan::scan(&output_path, |_| {}) {
+            Ok(models) => assets.extend(models.into_iter().map(|model| Asset {
+                url: url_path(["api", "fsltl", &model.relative_path]),
+                kind: ContentKind::Fsltl,
+                path: PathBuf::from(model.model_path),
+            })),
+            Err(e) => warn!("[Manifest] Failed to scan FSLTL models: {}", e),
+        }
+    }
+
+    for overlay in overlays::list_for_airport(app, None) {
+        assets.extend(overlay.files.into_iter().map(|file| Asset {
+            url: file.url,
+            kind: ContentKind::Overlay,
+            path: file.path,
+        }));
+    }
+    assets
+}
+
+fn hashes_file(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_cache_dir()
+        .ok()
+        .map(|dir| dir.join("manifest-hashes.json"))
+}
+
+/// Build the manifest (blocking: hashes new and changed files)
+pub fn manifest(app: &AppHandle) -> ContentManifest {
+    let assets = assets(app);
+    let file = hashes_file(app);
+    let mut guard = HASHES.lock();
+    let hashes = guard.get_or_insert_with(|| {
+        file.as_ref()
+            .and_then(|f| fs::read(f).ok())
+            .and_then(|content| serde_json::from_slice(&content).ok())
+            .unwrap_or_default()
+    });
+
+    let before = hashes.clone();
+    let manifest = build(assets, hashes);
+    if *hashes != before {
+        if let Some(file) = file {
+            let saved = file
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| fs::write(&file, serde_json::to_vec(&*hashes).unwrap_or_default()));
+            if let Err(e) = saved {
+                warn!("[Manifest] Failed to save {}: {}", file.display(), e);
+            }
+        }
+    }
+    manifest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_assets_and_reuses_unchanged_hashes() {
+        let dir = std::env::temp_dir().join(format!("manifest-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let model = dir.join("model.glb");
+        let overlay = dir.join("map.geojson");
+        fs::write(&model, b"glTF").unwrap();
+        fs::write(&overlay, b"{}").unwrap();
+        let assets = || {
+            vec![
+                Asset {
+                    url: url_path(["api", "mods", "aircraft", "B738 MAX", "model.glb"]),
+                    kind: ContentKind::Aircraft,
+                    path: model.clone(),
+                },
+                Asset {
+                    url: "/api/overlays/kbos/map.geojson".to_string(),
+                    kind: ContentKind::Overlay,
+                    path: overlay.clone(),
+                },
+                Asset {
+                    url: "/api/fsltl/missing/model.glb".to_string(),
+                    kind: ContentKind::Fsltl,
+                    path: dir.join("missing.glb"),
+                },
+            ]
+        };
+
+        let mut hashes = HashMap::new();
+        let first = build(assets(), &mut hashes);
+        assert_eq!(first.entries.len(), 2);
+        assert_eq!(first.entries[0].url, "/api/mods/aircraft/B738%20MAX/model.glb");
+        assert_eq!(first.entries[0].size, 4);
+        assert_eq!(
+            first.entries[1].hash,
+            "44136fa355b3678a1146ad16f7e8649e94fb4fc21fe77e8310c060f61caaff8a"
+        );
+        assert_eq!(hashes.len(), 2);
+
+        // A remembered hash is used while size and modification time match
+        hashes.get_mut(&model).unwrap().hash = "remembered".to_string();
+        assert_eq!(build(assets(), &mut hashes).entries[0].hash, "remembered");
+
+        fs::write(&model, b"glTF v2").unwrap();
+        let changed = build(assets(), &mut hashes);
+        assert_ne!(changed.entries[0].hash, "remembered");
+        assert_ne!(changed.hash, first.hash);
+
+        fs::remove_file(&overlay).unwrap();
+        assert_eq!(build(assets(), &mut hashes).entries.len(), 1);
+        assert_eq!(hashes.len(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,582 @@
+//! Content packs
+//!
+//! Auto-updated non-code content published in remote index files (the
+//! `contentPacks.indexUrls` global setting). Each index lists packs with a
+//! version and the SHA-256 of every file:
+//!
+//! ```json
+//! { "packs": [{
+//!     "id": "us-tower-positions", "name": "US tower positions",
+//!     "description": "...", "kind": "tower-positions", "version": "2026.10.1",
+//!     "files": [{ "name": "positions.json", "url": "positions.json", "sha256": "9f86d0..." }]
+//! }] }
+//! ```
+//!
+//! File URLs may be relative to the index. Packs are opt-in
+//! (`contentPacks.enabled`); enabled packs are installed in `content-packs/<id>/`
+//! in the app data folder and updated when the index has a new version, checked
+//! on startup and on demand. Files are verified before the installed copy is
+//! replaced, so a bad download never breaks an installed pack.
+//!
+//! Kinds and where their content is used:
+//!
+//! - `vmr`: `.vmr` model matching rules, loaded after local VMR files (vmr.rs)
+//! - `tower-positions`: JSON maps of ICAO to tower position, used for airports
+//!   without their own file in `mods/tower-positions/`
+//! - `video-maps`: `{ICAO}.json` video map bundles, installed for airports
+//!   without maps imported from CRC (videomaps.rs)
+//! - `airport-db`: `airports.json` and/or `runways.csv` replacing the bundled
+//!   airport database sources (airport_db.rs)
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+use crate::error::Error;
+use crate::supervisor::{self, Restart};
+use crate::{airport_db, http_client, videomaps, vmr};
+
+pub const KIND_VMR: &str = "vmr";
+pub const KIND_TOWER_POSITIONS: &str = "tower-positions";
+pub const KIND_VIDEO_MAPS: &str = "video-maps";
+pub const KIND_AIRPORT_DB: &str = "airport-db";
+
+/// Files an airport database pack may replace
+const AIRPORT_DB_FILES: [&str; 2] = ["airports.json", "runways.csv"];
+
+/// Time allowed to download one file
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Serializes syncs so concurrent checks don't race on the install folders
+static SYNC_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// A file of a pack
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackFile {
+    pub name: String,
+    pub url: String,
+    pub sha256: String,
+}
+
+/// A pack listed in an index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackEntry {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub kind: String,
+    pub version: String,
+    pub files: Vec<PackFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackIndex {
+    packs: Vec<PackEntry>,
+}
+
+/// An installed pack (content-packs/installed.json in app data)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InstalledPack {
+    id: String,
+    kind: String,
+    version: String,
+    files: Vec<String>,
+    /// Airports whose video maps this pack installed
+    #[serde(default)]
+    airports: Vec<String>,
+    /// Unix ms
+    installed_at: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct InstalledFile {
+    #[serde(default)]
+    packs: Vec<InstalledPack>,
+}
+
+/// A pack as shown to the user
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentPackStatus {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub kind: String,
+    /// Index the pack is listed in (None: installed but no longer listed)
+    pub index_url: Option<String>,
+    /// Version in the index
+    pub version: Option<String>,
+    pub installed_version: Option<String>,
+    /// Opted in
+    pub enabled: bool,
+    pub error: Option<String>,
+}
+
+/// Packs and index errors
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentPacks {
+    pub packs: Vec<ContentPackStatus>,
+    /// Indexes that couldn't be read at the last check, with the error
+    pub index_errors: HashMap<String, String>,
+}
+
+/// Packs listed by the indexes at the last check
+pub struct ContentPackState {
+    available: parking_lot::Mutex<Vec<(String, PackEntry)>>,
+    /// Install errors by pack id, and index errors by index URL
+    errors: parking_lot::Mutex<HashMap<String, String>>,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn packs_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    Ok(app_data.join("content-packs"))
+}
+
+fn read_installed(app: &AppHandle) -> Vec<InstalledPack> {
+    packs_dir(app)
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join("installed.json")).ok())
+        .and_then(|content| serde_json::from_str::<InstalledFile>(&content).ok())
+        .map(|file| file.packs)
+        .unwrap_or_default()
+}
+
+fn write_installed(app: &AppHandle, packs: Vec<InstalledPack>) -> Result<(), String> {
+    let dir = packs_dir(app)?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create content packs folder: {}", e))?;
+    let content = serde_json::to_string_pretty(&InstalledFile { packs })
+        .map_err(|e| format!("Failed to serialize installed packs: {}", e))?;
+    fs::write(dir.join("installed.json"), content).map_err(|e| format!("Failed to write installed packs: {}", e))
+}
+
+/// Installed files of packs of a kind, in pack id order
+pub fn installed_files(app: &AppHandle, kind: &str) -> Vec<PathBuf> {
+    let Ok(dir) = packs_dir(app) else {
+        return Vec::new();
+    };
+    let mut packs: Vec<InstalledPack> = read_installed(app).into_iter().filter(|p| p.kind == kind).collect();
+    packs.sort_by(|a, b| a.id.cmp(&b.id));
+    packs
+        .into_iter()
+        .flat_map(|pack| {
+            let pack_dir = dir.join(&pack.id);
+            pack.files.into_iter().map(move |name| pack_dir.join(name))
+        })
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Installed file replacing a bundled airport database source, if any
+pub fn airport_db_override(app: &AppHandle, file_name: &str) -> Option<PathBuf> {
+    installed_files(app, KIND_AIRPORT_DB)
+        .into_iter()
+        .find(|path| path.file_name().is_some_and(|name| name == file_name))
+}
+
+/// Check a pack entry before downloading anything
+fn validate_entry(entry: &PackEntry) -> Result<(), String> {
+    let safe_name = |name: &str| {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            && !name.starts_with('.')
+    };
+    if !safe_name(&entry.id) {
+        return Err(format!("Invalid pack id: {:?}", entry.id));
+    }
+    if entry.files.is_empty() {
+        return Err("Pack has no files".to_string());
+    }
+    for file in &entry.files {
+        if !safe_name(&file.name) {
+            return Err(format!("Invalid file name: {:?}", file.name));
+        }
+        if file.sha256.len() != 64 || !file.sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("Invalid SHA-256 for {}", file.name));
+        }
+        let allowed = match entry.kind.as_str() {
+            KIND_VMR => file.name.to_ascii_lowercase().ends_with(".vmr"),
+            KIND_TOWER_POSITIONS => file.name.to_ascii_lowercase().ends_with(".json"),
+            KIND_VIDEO_MAPS => file.name.len() > 5 && file.name.to_ascii_lowercase().ends_with(".json"),
+            KIND_AIRPORT_DB => AIRPORT_DB_FILES.contains(&file.name.as_str()),
+            kind => return Err(format!("Unknown pack kind: {}", kind)),
+        };
+        if !allowed {
+            return Err(format!("{} can't be part of a {} pack", file.name, entry.kind));
+        }
+    }
+    Ok(())
+}
+
+/// Check the content of a downloaded file
+fn verify_file(kind: &str, file: &PackFile, bytes: &[u8]) -> Result<(), String> {
+    let hash = hex::encode(Sha256::digest(bytes));
+    if !hash.eq_ignore_ascii_case(&file.sha256) {
+        return Err(format!("{} failed verification (SHA-256 {})", file.name, hash));
+    }
+    let text = std::str::from_utf8(bytes).map_err(|_| format!("{} is not UTF-8 text", file.name))?;
+    match kind {
+        KIND_VMR => {
+            let parsed = vmr::parse_vmr_content(text);
+            if parsed.rules.is_empty() {
+                return Err(parsed.error.unwrap_or_else(|| format!("{} has no rules", file.name)));
+            }
+        }
+        KIND_TOWER_POSITIONS | KIND_VIDEO_MAPS => {
+            serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(text)
+                .map_err(|e| format!("{} is not a JSON object: {}", file.name, e))?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+async fn fetch_index(url: &str) -> Result<Vec<PackEntry>, String> {
+    let base = url::Url::parse(url).map_err(|e| format!("Invalid index URL: {}", e))?;
+    let index: PackIndex = http_client::client()
+        .get(url)
+        .timeout(Duration::from_secs(30))
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Failed to fetch index: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Invalid index: {}", e))?;
+    // Resolve file URLs relative to the index
+    Ok(index
+        .packs
+        .into_iter()
+        .map(|mut pack| {
+            for file in &mut pack.files {
+                if let Ok(resolved) = base.join(&file.url) {
+                    file.url = resolved.to_string();
+                }
+            }
+            pack
+        })
+        .collect())
+}
+
+/// Download and verify a pack, then replace the installed copy
+async fn install(
+    app: &AppHandle,
+    entry: &PackEntry,
+    previous: Option<&InstalledPack>,
+) -> Result<InstalledPack, String> {
+    validate_entry(entry)?;
+    let mut downloads = Vec::with_capacity(entry.files.len());
+    for file in &entry.files {
+        let bytes = http_client::client()
+            .get(&file.url)
+            .timeout(DOWNLOAD_TIMEOUT)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("Failed to download {}: {}", file.name, e))?
+            .bytes()
+            .await
+            .map_err(|e| format!("Failed to download {}: {}", file.name, e))?;
+        verify_file(&entry.kind, file, &bytes)?;
+        downloads.push((file.name.clone(), bytes));
+    }
+
+    let dir = packs_dir(app)?;
+    let staging = dir.join(format!("{}.download", entry.id));
+    let _ = fs::remove_dir_all(&staging);
+    fs::create_dir_all(&staging).map_err(|e| format!("Failed to create pack folder: {}", e))?;
+    for (name, bytes) in &downloads {
+        fs::write(staging.join(name), bytes).map_err(|e| format!("Failed to write {}: {}", name, e))?;
+    }
+    let target = dir.join(&entry.id);
+    if target.exists() {
+        fs::remove_dir_all(&target).map_err(|e| format!("Failed to replace installed pack: {}", e))?;
+    }
+    fs::rename(&staging, &target).map_err(|e| format!("Failed to install pack: {}", e))?;
+
+    let mut airports = Vec::new();
+    if entry.kind == KIND_VIDEO_MAPS {
+        let owned = previous.map(|p| p.airports.as_slice()).unwrap_or_default();
+        for (name, bytes) in &downloads {
+            let icao = name[..name.len() - 5].to_uppercase();
+            // Maps imported from CRC take precedence
+            let has_maps = videomaps::get_index(app, &icao).ok().flatten().is_some();
+            if has_maps && !owned.contains(&icao) {
+                info!("[ContentPacks] {} keeps its own video maps", icao);
+                continue;
+            }
+            let bundle = serde_json::from_slice(bytes).map_err(|e| e.to_string())?;
+            match videomaps::import_bundle(app, &icao, bundle) {
+                Ok(_) => airports.push(icao),
+                Err(e) => warn!("[ContentPacks] Video maps for {}: {}", icao, e),
+            }
+        }
+        // Airports dropped from the pack
+        for icao in owned.iter().filter(|icao| !airports.contains(icao)) {
+            let _ = videomaps::remove(app, icao);
+        }
+    }
+
+    Ok(InstalledPack {
+        id: entry.id.clone(),
+        kind: entry.kind.clone(),
+        version: entry.version.clone(),
+        files: entry.files.iter().map(|f| f.name.clone()).collect(),
+        airports,
+        installed_at: now_ms(),
+    })
+}
+
+fn uninstall(app: &AppHandle, pack: &InstalledPack) {
+    if let Ok(dir) = packs_dir(app) {
+        let _ = fs::remove_dir_all(dir.join(&pack.id));
+    }
+    for icao in &pack.airports {
+        let _ = videomaps::remove(app, icao);
+    }
+    info!("[ContentPacks] Removed {}", pack.id);
+}
+
+/// Refresh the indexes (if `check`), then install, update and remove packs to
+/// match the opted-in list
+pub async fn sync(app: &AppHandle, check: bool) -> ContentPacks {
+    let _guard = SYNC_LOCK.lock().await;
+    let settings = crate::read_global_settings(app.clone())
+        .map(|s| s.content_packs)
+        .unwrap_or_default();
+    let state = app.state::<ContentPackState>();
+
+    if check {
+        let mut available = Vec::new();
+        let mut errors = HashMap::new();
+        for url in &settings.index_urls {
+            match fetch_index(url).await {
+                Ok(packs) => available.extend(packs.into_iter().map(|p| (url.clone(), p))),
+                Err(e) => {
+                    warn!("[ContentPacks] Index {}: {}", url, e);
+                    errors.insert(url.clone(), e);
+                }
+            }
+        }
+        *state.available.lock() = available;
+        *state.errors.lock() = errors;
+    }
+
+    let available = state.available.lock().clone();
+    let mut installed = read_installed(app);
+    let mut changed_kinds = Vec::new();
+
+    // Packs no longer opted in
+    installed.retain(|pack| {
+        let keep = settings.enabled.contains(&pack.id);
+        if !keep {
+            uninstall(app, pack);
+            changed_kinds.push(pack.kind.clone());
+        }
+        keep
+    });
+
+    // New packs and new versions (only known after an index check)
+    for (_, entry) in available.iter().filter(|(_, e)| settings.enabled.contains(&e.id)) {
+        let position = installed.iter().position(|p| p.id == entry.id);
+        let previous = position.map(|i| &installed[i]);
+        if previous.is_some_and(|p| p.version == entry.version) {
+            continue;
+        }
+        match install(app, entry, previous).await {
+            Ok(pack) => {
+                info!("[ContentPacks] Installed {} {}", pack.id, pack.version);
+                state.errors.lock().remove(&entry.id);
+                changed_kinds.push(pack.kind.clone());
+                match position {
+                    Some(i) => installed[i] = pack,
+                    None => installed.push(pack),
+                }
+            }
+            Err(e) => {
+                warn!("[ContentPacks] {}: {}", entry.id, e);
+                state.errors.lock().insert(entry.id.clone(), e);
+            }
+        }
+    }
+
+    if !changed_kinds.is_empty() {
+        if let Err(e) = write_installed(app, installed) {
+            warn!("[ContentPacks] {}", e);
+        }
+        if changed_kinds.iter().any(|k| k == KIND_AIRPORT_DB) {
+            airport_db::reset(app);
+        }
+        let _ = app.emit("content-packs-changed", &changed_kinds);
+    }
+    overview(app)
+}
+
+/// Every pack listed by the indexes or installed
+fn overview(app: &AppHandle) -> ContentPacks {
+    let settings = crate::read_global_settings(app.clone())
+        .map(|s| s.content_packs)
+        .unwrap_or_default();
+    let enabled = settings.enabled;
+    let state = app.state::<ContentPackState>();
+    let errors = state.errors.lock().clone();
+    let installed = read_installed(app);
+    let installed_version = |id: &str| installed.iter().find(|p| p.id == id).map(|p| p.version.clone());
+
+    let mut statuses: Vec<ContentPackStatus> = state
+        .available
+        .lock()
+        .iter()
+        .map(|(url, entry)| ContentPackStatus {
+            id: entry.id.clone(),
+            name: entry.name.clone(),
+            description: entry.description.clone(),
+            kind: entry.kind.clone(),
+            index_url: Some(url.clone()),
+            version: Some(entry.version.clone()),
+            installed_version: installed_version(&entry.id),
+            enabled: enabled.contains(&entry.id),
+            error: errors.get(&entry.id).cloned(),
+        })
+        .collect();
+    let unlisted: Vec<&InstalledPack> = installed
+        .iter()
+        .filter(|p| !statuses.iter().any(|s| s.id == p.id))
+        .collect();
+    for pack in unlisted {
+        statuses.push(ContentPackStatus {
+            id: pack.id.clone(),
+            name: pack.id.clone(),
+            description: None,
+            kind: pack.kind.clone(),
+            index_url: None,
+            version: None,
+            installed_version: Some(pack.version.clone()),
+            enabled: enabled.contains(&pack.id),
+            error: errors.get(&pack.id).cloned(),
+        });
+    }
+    ContentPacks {
+        packs: statuses,
+        index_errors: errors
+            .into_iter()
+            .filter(|(key, _)| settings.index_urls.contains(key))
+            .collect(),
+    }
+}
+
+/// Initialize content pack state.
+/// Call this in the Tauri setup closure.
+pub fn init_content_packs(app: &AppHandle) {
+    app.manage(ContentPackState {
+        available: parking_lot::Mutex::new(Vec::new()),
+        errors: parking_lot::Mutex::new(HashMap::new()),
+    });
+}
+
+/// Check the indexes for pack updates on startup (if enabled in settings)
+pub fn start_update_check(app: &AppHandle) {
+    let check_on_startup = crate::read_global_settings(app.clone())
+        .map(|s| s.content_packs.check_on_startup)
+        .unwrap_or(true);
+    if !check_on_startup {
+        return;
+    }
+    let app = app.clone();
+    supervisor::spawn("content-packs", Restart::OnPanic, move || {
+        let app = app.clone();
+        async move {
+            sync(&app, true).await;
+        }
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// List packs from the last index check and installed packs
+#[tauri::command]
+pub fn list_content_packs(app: AppHandle) -> ContentPacks {
+    overview(&app)
+}
+
+/// Check the indexes now and install updates of opted-in packs
+#[tauri::command]
+pub async fn check_content_packs(app: AppHandle) -> ContentPacks {
+    sync(&app, true).await
+}
+
+/// Opt in to (install) or out of (remove) a pack
+#[tauri::command]
+pub async fn set_content_pack_enabled(app: AppHandle, id: String, enabled: bool) -> Result<ContentPacks, Error> {
+    let mut settings = crate::read_global_settings(app.clone())?;
+    settings.content_packs.enabled.retain(|p| *p != id);
+    if enabled {
+        settings.content_packs.enabled.push(id);
+    }
+    crate::write_global_settings(app.clone(), settings)?;
+    // The indexes haven't been read yet if startup checks are off
+    let check = enabled && app.state::<ContentPackState>().available.lock().is_empty();
+    Ok(sync(&app, check).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(kind: &str, name: &str, sha256: &str) -> PackEntry {
+        PackEntry {
+            id: "pack-1".to_string(),
+            name: "Pack".to_string(),
+            description: None,
+            kind: kind.to_string(),
+            version: "1".to_string(),
+            files: vec![PackFile {
+                name: name.to_string(),
+                url: name.to_string(),
+                sha256: sha256.to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn validates_entries_and_verifies_files() {
+        let content = br#"{"KBOS":{"view3d":{"lat":42.36,"lon":-71.01,"aglHeight":60}}}"#;
+        let hash = hex::encode(Sha256::digest(content));
+
+        let pack = entry(KIND_TOWER_POSITIONS, "positions.json", &hash);
+        assert!(validate_entry(&pack).is_ok());
+        assert!(verify_file(KIND_TOWER_POSITIONS, &pack.files[0], content).is_ok());
+        assert!(verify_file(KIND_TOWER_POSITIONS, &pack.files[0], b"{}").is_err());
+
+        assert!(validate_entry(&entry(KIND_TOWER_POSITIONS, "../positions.json", &hash)).is_err());
+        assert!(validate_entry(&entry(KIND_AIRPORT_DB, "airports.json", &hash)).is_ok());
+        assert!(validate_entry(&entry(KIND_AIRPORT_DB, "other.json", &hash)).is_err());
+        assert!(validate_entry(&entry("scripts", "a.json", &hash)).is_err());
+        assert!(validate_entry(&entry(KIND_VMR, "rules.vmr", "abc")).is_err());
+    }
+}
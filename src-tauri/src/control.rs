@@ -0,0 +1,308 @@
+//! Control surface for Bitfocus Companion and Stream Deck
+//!
+//! A small, stable API for hardware button panels (documented in
+//! `docs/control-api.md`):
+//!
+//! - `GET /api/control/actions`: every action a button can trigger, with the
+//!   bookmarks and recent airports expanded into one entry each
+//! - `POST /api/control/trigger`: run an action (`{"action": "bookmark", "value": "3"}`)
+//! - `GET /api/control/state` and `/api/control/ws`: feedback for button LEDs
+//!   (airport, view mode, loaded bookmark, replay and timelapse state); the
+//!   WebSocket also accepts triggers
+//!
+//! Replay actions run on the host. Camera and airport actions are sent to the
+//! main window as `control-action` events; the main window publishes what it
+//! shows with `publish_control_view` so the host can list bookmarks and report
+//! feedback. System-wide hotkeys (hotkeys.rs) trigger actions through here too.
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::info;
+
+use crate::error::Error;
+use crate::{replay, timelapse, window_layouts};
+
+/// Bumped on incompatible changes to the control API
+pub const API_VERSION: u32 = 1;
+
+/// Actions a control surface can trigger
+pub const ACTIONS: [&str; 7] = [
+    "bookmark",
+    "airport",
+    "viewMode",
+    "toggleViewMode",
+    "instantReplay",
+    "replayPlayPause",
+    "stopReplay",
+];
+
+/// Action to run (`control-action` event payload for window actions)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlAction {
+    pub action: String,
+    /// Bookmark name or slot, airport ICAO or view mode ("3d"/"topdown")
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+/// A bookmark at the current airport
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlBookmark {
+    pub slot: u32,
+    pub name: Option<String>,
+}
+
+/// What the main window shows (published by the frontend)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlView {
+    pub airport: Option<String>,
+    /// "3d" or "topdown"
+    pub view_mode: Option<String>,
+    pub bookmarks: Vec<ControlBookmark>,
+}
+
+/// Feedback for button LEDs
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlFeedback {
+    pub api_version: u32,
+    pub airport: Option<String>,
+    pub view_mode: Option<String>,
+    /// Bookmark (name or slot) last loaded in the main window
+    pub bookmark: Option<String>,
+    pub replay_loaded: bool,
+    pub replay_playing: bool,
+    pub timelapse_active: bool,
+}
+
+/// One button-ready action
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlActionInfo {
+    /// Stable ID, e.g. "bookmark:3" or "airport:KBOS"
+    pub id: String,
+    pub label: String,
+    #[serde(flatten)]
+    pub action: ControlAction,
+}
+
+/// Action list with the API version
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControlActions {
+    pub api_version: u32,
+    pub actions: Vec<ControlActionInfo>,
+}
+
+pub struct ControlState {
+    view: RwLock<ControlView>,
+    /// Feedback changes for WebSocket relay
+    pub events: broadcast::Sender<ControlFeedback>,
+}
+
+/// Check an action and its value
+pub fn validate(action: &ControlAction) -> Result<(), String> {
+    if !ACTIONS.contains(&action.action.as_str()) {
+        return Err(format!("Unknown action '{}'", action.action));
+    }
+    let value = action.value.as_deref().map(str::trim).filter(|v| !v.is_empty());
+    match (action.action.as_str(), value) {
+        ("bookmark" | "airport" | "viewMode", None) => Err(format!("Action '{}' needs a value", action.action)),
+        ("viewMode", Some(mode)) if mode != "3d" && mode != "topdown" => {
+            Err(format!("Unknown view mode '{}' (3d or topdown)", mode))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Run an action (host actions directly, the rest in the main window)
+pub fn trigger(app: &AppHandle, action: ControlAction) -> Result<(), Error> {
+    validate(&action).map_err(Error::InvalidInput)?;
+    info!("[Control] {} {}", action.action, action.value.as_deref().unwrap_or(""));
+    match action.action.as_str() {
+        "instantReplay" => {
+            let minutes = crate::read_global_settings(app.clone())
+                .map(|s| s.replay.instant_replay_minutes)
+                .ok();
+            replay::instant_replay(app, minutes).map(|_| ())
+        }
+        "replayPlayPause" => {
+            let status = replay::get_status(app);
+            if !status.loaded {
+                return Err(Error::conflict("No replay loaded"));
+            }
+            let control = replay::ReplayControl {
+                playing: Some(!status.playing),
+                ..Default::default()
+            };
+            replay::control(app, control).map(|_| ())
+        }
+        "stopReplay" => {
+            replay::stop(app);
+            Ok(())
+        }
+        _ => app
+            .emit_to("main", "control-action", &action)
+            .map_err(|e| Error::Internal(format!("Failed to send {} to the main window: {}", action.action, e))),
+    }
+}
+
+/// Current feedback
+pub fn feedback(app: &AppHandle) -> ControlFeedback {
+    let view = app
+        .try_state::<ControlState>()
+        .map(|state| state.view.read().clone())
+        .unwrap_or_default();
+    let replay = replay::get_status(app);
+    ControlFeedback {
+        api_version: API_VERSION,
+        airport: view.airport,
+        view_mode: view.view_mode,
+        bookmark: window_layouts::assigned_bookmark(app, "main"),
+        replay_loaded: replay.loaded,
+        replay_playing: replay.playing,
+        timelapse_active: timelapse::get_status(app).active,
+    }
+}
+
+/// Send feedback to control surfaces (call when anything it reports changes)
+pub fn publish(app: &AppHandle) {
+    if let Some(state) = app.try_state::<ControlState>() {
+        let _ = state.events.send(feedback(app));
+    }
+}
+
+/// Every action, with bookmarks and recent airports expanded
+pub fn actions(app: &AppHandle) -> ControlActions {
+    let view = app
+        .try_state::<ControlState>()
+        .map(|state| state.view.read().clone())
+        .unwrap_or_default();
+    let recent_airports = crate::read_global_settings(app.clone())
+        .map(|s| s.airports.recent_airports)
+        .unwrap_or_default();
+    ControlActions {
+        api_version: API_VERSION,
+        actions: list_actions(&view, &recent_airports),
+    }
+}
+
+fn list_actions(view: &ControlView, recent_airports: &[String]) -> Vec<ControlActionInfo> {
+    let entry = |id: String, label: String, action: &str, value: Option<String>| ControlActionInfo {
+        id,
+        label,
+        action: ControlAction {
+            action: action.to_string(),
+            value,
+        },
+    };
+    let mut actions = Vec::new();
+    for bookmark in &view.bookmarks {
+        let label = match &bookmark.name {
+            Some(name) => format!("{} (.{})", name, bookmark.slot),
+            None => format!("Bookmark .{}", bookmark.slot),
+        };
+        actions.push(entry(
+            format!("bookmark:{}", bookmark.slot),
+            label,
+            "bookmark",
+            Some(bookmark.slot.to_string()),
+        ));
+    }
+    for icao in recent_airports {
+        actions.push(entry(
+            format!("airport:{}", icao),
+            icao.clone(),
+            "airport",
+            Some(icao.clone()),
+        ));
+    }
+    actions.push(entry(
+        "viewMode:3d".into(),
+        "3D view".into(),
+        "viewMode",
+        Some("3d".into()),
+    ));
+    actions.push(entry(
+        "viewMode:topdown".into(),
+        "Top-down view".into(),
+        "viewMode",
+        Some("topdown".into()),
+    ));
+    for (action, label) in [
+        ("toggleViewMode", "Toggle 2D/3D"),
+        ("instantReplay", "Instant replay"),
+        ("replayPlayPause", "Replay play/pause"),
+        ("stopReplay", "Stop replay"),
+    ] {
+        actions.push(entry(action.to_string(), label.to_string(), action, None));
+    }
+    actions
+}
+
+/// Initialize control surface state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_control(app: &AppHandle) {
+    let (events, _) = broadcast::channel(16);
+    app.manage(ControlState {
+        view: RwLock::new(ControlView::default()),
+        events,
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Report what the main window shows (airport, view mode, bookmarks)
+#[tauri::command]
+pub fn publish_control_view(app: AppHandle, view: ControlView) {
+    let Some(state) = app.try_state::<ControlState>() else {
+        return;
+    };
+    {
+        let mut current = state.view.write();
+        if *current == view {
+            return;
+        }
+        *current = view;
+    }
+    publish(&app);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_and_lists_actions() {
+        let action = |action: &str, value: Option<&str>| ControlAction {
+            action: action.to_string(),
+            value: value.map(str::to_string),
+        };
+        assert!(validate(&action("bookmark", Some("3"))).is_ok());
+        assert!(validate(&action("bookmark", Some(" "))).is_err());
+        assert!(validate(&action("viewMode", Some("sideways"))).is_err());
+        assert!(validate(&action("instantReplay", None)).is_ok());
+        assert!(validate(&action("selfDestruct", None)).is_err());
+
+        let view = ControlView {
+            airport: Some("KBOS".to_string()),
+            view_mode: Some("3d".to_string()),
+            bookmarks: vec![ControlBookmark {
+                slot: 3,
+                name: Some("Tower North".to_string()),
+            }],
+        };
+        let actions = list_actions(&view, &["KJFK".to_string()]);
+        assert_eq!(actions[0].id, "bookmark:3");
+        assert_eq!(actions[0].label, "Tower North (.3)");
+        assert_eq!(actions[1].action, action("airport", Some("KJFK")));
+        assert!(actions.iter().all(|a| validate(&a.action).is_ok()));
+    }
+}
@@ -0,0 +1,332 @@
+//! Controller info and ratings
+//!
+//! The coverage panel shows who is staffing the positions around the field.
+//! Controllers are resolved from the VATSIM data feed (CID, name, rating and
+//! logon time of everyone online) and, by CID, from the VATSIM member API
+//! (home region and division, pilot rating, registration date). Member
+//! lookups are cached for 24 hours, failed ones for 10 minutes, so the panel
+//! refreshing doesn't hit the API for every controller on every poll.
+//!
+//! Served at `GET /api/controllers` (the positions covering the active
+//! airport, or `?icao=`) and `GET /api/controllers/:id` (an online callsign
+//! or CID).
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use futures_util::future::join_all;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tracing::debug;
+
+use crate::error::{Error, Result};
+use crate::http_client;
+use crate::traffic::{self, OnlineController};
+use crate::vatspy::{self, CoveringPosition};
+use crate::weather;
+
+/// VATSIM member API (one CID per request)
+const MEMBERS_URL: &str = "https://api.vatsim.net/v2/members";
+
+/// How long a member lookup is used before asking again
+const MEMBER_TTL: Duration = Duration::from_secs(24 * 3600);
+
+/// How long a failed member lookup is remembered
+const FAILED_TTL: Duration = Duration::from_secs(600);
+
+/// Members remembered at most (all are forgotten when full)
+const MAX_MEMBERS: usize = 1024;
+
+/// Short and long name of a VATSIM controller rating
+pub fn rating_name(rating: i32) -> Option<(&'static str, &'static str)> {
+    Some(match rating {
+        -1 => ("INA", "Inactive"),
+        0 => ("SUS", "Suspended"),
+        1 => ("OBS", "Observer"),
+        2 => ("S1", "Tower Trainee"),
+        3 => ("S2", "Tower Controller"),
+        4 => ("S3", "Senior Student"),
+        5 => ("C1", "Enroute Controller"),
+        6 => ("C2", "Controller 2"),
+        7 => ("C3", "Senior Controller"),
+        8 => ("I1", "Instructor"),
+        9 => ("I2", "Instructor 2"),
+        10 => ("I3", "Senior Instructor"),
+        11 => ("SUP", "Supervisor"),
+        12 => ("ADM", "Administrator"),
+        _ => return None,
+    })
+}
+
+/// Member API response (only the parts used)
+#[derive(Deserialize)]
+struct MemberResponse {
+    id: u64,
+    #[serde(default)]
+    rating: Option<i32>,
+    #[serde(default)]
+    pilotrating: Option<u32>,
+    #[serde(default)]
+    region_id: Option<String>,
+    #[serde(default)]
+    division_id: Option<String>,
+    #[serde(default)]
+    subdivision_id: Option<String>,
+    #[serde(default)]
+    reg_date: Option<String>,
+}
+
+/// VATSIM member details (the API doesn't return names)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberInfo {
+    pub cid: u64,
+    /// Controller rating (see `rating_name`)
+    pub rating: Option<i32>,
+    /// Pilot rating bit flags
+    pub pilot_rating: Option<u32>,
+    pub region: Option<String>,
+    pub division: Option<String>,
+    pub subdivision: Option<String>,
+    /// Registration date (ISO 8601)
+    pub registered: Option<String>,
+}
+
+impl From<MemberResponse> for MemberInfo {
+    fn from(response: MemberResponse) -> Self {
+        let present = |value: Option<String>| value.filter(|v| !v.trim().is_empty());
+        Self {
+            cid: response.id,
+            rating: response.rating,
+            pilot_rating: response.pilotrating,
+            region: present(response.region_id),
+            division: present(response.division_id),
+            subdivision: present(response.subdivision_id),
+            registered: present(response.reg_date),
+        }
+    }
+}
+
+/// Who is staffing a position
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ControllerInfo {
+    pub callsign: String,
+    pub cid: Option<u64>,
+    pub name: Option<String>,
+    /// Rating short name, e.g. "S3" or "C1"
+    pub rating: Option<String>,
+    /// Rating long name, e.g. "Senior Student"
+    pub rating_name: Option<String>,
+    pub frequency: String,
+    /// "atis", "delivery", "ground", "tower", "approach", "center" or "fss"
+    /// (positions covering an airport only)
+    pub level: Option<String>,
+    /// Facility id the position belongs to (positions covering an airport only)
+    pub facility: Option<String>,
+    /// Logon time (ISO 8601)
+    pub logon_time: Option<String>,
+    /// Member API details, when the lookup succeeded
+    pub member: Option<MemberInfo>,
+}
+
+/// Controller info state (managed by Tauri)
+pub struct ControllersState {
+    /// Member lookups by CID and when they were made (None when the lookup failed)
+    members: RwLock<HashMap<u64, (Instant, Option<MemberInfo>)>>,
+}
+
+impl ControllersState {
+    pub fn new() -> Self {
+        Self {
+            members: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+async fn fetch_member(cid: u64) -> Result<MemberInfo, String> {
+    let response = http_client::client()
+        .get(format!("{}/{}", MEMBERS_URL, cid))
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch VATSIM member: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("VATSIM member API returned HTTP {}", response.status()));
+    }
+    response
+        .json::<MemberResponse>()
+        .await
+        .map(MemberInfo::from)
+        .map_err(|e| format!("Invalid VATSIM member response: {}", e))
+}
+
+/// A member's details, from the cache while fresh
+pub async fn member(app: &AppHandle, cid: u64) -> Option<MemberInfo> {
+    let state = app.state::<ControllersState>();
+    if let Some((fetched, member)) = state.members.read().get(&cid) {
+        let ttl = if member.is_some() { MEMBER_TTL } else { FAILED_TTL };
+        if fetched.elapsed() < ttl {
+            return member.clone();
+        }
+    }
+    let member = fetch_member(cid)
+        .await
+        .map_err(|e| debug!("[Controllers] {}: {}", cid, e))
+        .ok();
+    let mut members = state.members.write();
+    if members.len() >= MAX_MEMBERS {
+        members.clear();
+    }
+    members.insert(cid, (Instant::now(), member.clone()));
+    member
+}
+
+/// Describe an online controller, optionally as a position covering an airport
+fn describe(controller: &OnlineController, position: Option<&CoveringPosition>) -> ControllerInfo {
+    let rating = controller.rating.and_then(rating_name);
+    ControllerInfo {
+        callsign: controller.callsign.clone(),
+        cid: controller.cid,
+        name: Some(controller.name.trim().to_string()).filter(|n| !n.is_empty()),
+        rating: rating.map(|(short, _)| short.to_string()),
+        rating_name: rating.map(|(_, long)| long.to_string()),
+        frequency: controller.frequency.clone(),
+        level: position.map(|p| p.level.clone()),
+        facility: position.map(|p| p.facility.clone()),
+        logon_time: controller.logon_time.clone(),
+        member: None,
+    }
+}
+
+/// Add member details, and the rating from them when the feed had none
+async fn with_member(app: &AppHandle, mut info: ControllerInfo) -> ControllerInfo {
+    if let Some(cid) = info.cid {
+        info.member = member(app, cid).await;
+    }
+    if info.rating.is_none() {
+        if let Some((short, long)) = info.member.as_ref().and_then(|m| m.rating).and_then(rating_name) {
+            info.rating = Some(short.to_string());
+            info.rating_name = Some(long.to_string());
+        }
+    }
+    info
+}
+
+/// Controllers staffing the positions covering an airport (default: the active airport)
+pub async fn list(app: &AppHandle, icao: Option<&str>) -> Result<Vec<ControllerInfo>> {
+    let icao = match icao {
+        Some(icao) => weather::normalize_icao(icao).map_err(Error::invalid_input)?,
+        None => match weather::active_airport(app) {
+            Some(icao) => icao,
+            None => return Ok(Vec::new()),
+        },
+    };
+    let coverage = vatspy::get_coverage(app, &icao).await?;
+    let facilities = &coverage.facilities;
+    if facilities.name.is_none() && facilities.tracon.is_none() && facilities.fir.is_none() {
+        return Err(Error::not_found(format!("No coverage data for {}", icao)));
+    }
+    let controllers = traffic::online_controllers(app).await.map_err(Error::Upstream)?;
+    let infos = coverage.online.iter().filter_map(|position| {
+        controllers
+            .iter()
+            .find(|c| c.callsign.eq_ignore_ascii_case(&position.callsign))
+            .map(|c| describe(c, Some(position)))
+    });
+    Ok(join_all(infos.map(|info| with_member(app, info))).await)
+}
+
+/// Look up an online controller by callsign or CID
+pub async fn lookup(app: &AppHandle, id: &str) -> Result<ControllerInfo> {
+    let id = id.trim();
+    if id.is_empty() {
+        return Err(Error::invalid_input("Callsign or CID is required"));
+    }
+    let cid = id.parse::<u64>().ok();
+    let controllers = traffic::online_controllers(app).await.map_err(Error::Upstream)?;
+    let Some(controller) = controllers
+        .iter()
+        .find(|c| cid.is_some_and(|cid| c.cid == Some(cid)) || c.callsign.eq_ignore_ascii_case(id))
+    else {
+        return Err(Error::not_found(format!("No controller online as {}", id)));
+    };
+    Ok(with_member(app, describe(controller, None)).await)
+}
+
+/// Initialize controller info state
+pub fn init_controllers(app: &AppHandle) {
+    app.manage(ControllersState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get who is staffing the positions covering an airport (default: the active airport)
+#[tauri::command]
+pub async fn get_controllers(app: AppHandle, icao: Option<String>) -> Result<Vec<ControllerInfo>> {
+    list(&app, icao.as_deref()).await
+}
+
+/// Look up an online controller by callsign or CID
+#[tauri::command]
+pub async fn get_controller(app: AppHandle, id: String) -> Result<ControllerInfo> {
+    lookup(&app, &id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_controllers_and_members() {
+        let controller: OnlineController = serde_json::from_value(serde_json::json!({
+            "cid": 1234567,
+            "name": " Jane Doe ",
+            "callsign": "BOS_TWR",
+            "frequency": "128.800",
+            "facility": 4,
+            "rating": 4,
+            "logon_time": "2026-10-17T12:00:00Z",
+        }))
+        .unwrap();
+        let position = CoveringPosition {
+            callsign: "BOS_TWR".to_string(),
+            name: "Boston Tower".to_string(),
+            frequency: "128.800".to_string(),
+            level: "tower".to_string(),
+            facility: "KBOS".to_string(),
+        };
+
+        let info = describe(&controller, Some(&position));
+        assert_eq!(info.cid, Some(1234567));
+        assert_eq!(info.name.as_deref(), Some("Jane Doe"));
+        assert_eq!(info.rating.as_deref(), Some("S3"));
+        assert_eq!(info.rating_name.as_deref(), Some("Senior Student"));
+        assert_eq!(info.level.as_deref(), Some("tower"));
+        assert_eq!(info.logon_time.as_deref(), Some("2026-10-17T12:00:00Z"));
+
+        // Stations without a rating or name in the feed
+        let atis: OnlineController = serde_json::from_value(serde_json::json!({"callsign": "KBOS_ATIS"})).unwrap();
+        let info = describe(&atis, None);
+        assert_eq!((info.cid, info.name, info.rating, info.facility), (None, None, None, None));
+        assert_eq!(rating_name(13), None);
+
+        let response: MemberResponse = serde_json::from_value(serde_json::json!({
+            "id": 1234567,
+            "rating": 5,
+            "pilotrating": 3,
+            "region_id": "AMAS",
+            "division_id": "USA",
+            "subdivision_id": "",
+            "reg_date": "2015-01-01T00:00:00",
+        }))
+        .unwrap();
+        let member = MemberInfo::from(response);
+        assert_eq!(member.division.as_deref(), Some("USA"));
+        assert_eq!(member.subdivision, None);
+        assert_eq!(member.pilot_rating, Some(3));
+    }
+}
@@ -0,0 +1,206 @@
+//! Coordination messages between displays
+//!
+//! A small message channel for positions sharing one TowerCab host: any
+//! client posts a free-text or canned message ("RWY change in 10", "break
+//! please") with `send_coordination_message` or `POST /api/messages`, the
+//! host numbers it and keeps the most recent ones as history, and every new
+//! message is emitted as `coordination-message` and relayed over
+//! `/api/messages/ws`. History is kept in memory and starts empty with the
+//! app.
+
+use std::collections::VecDeque;
+use std::time::SystemTime;
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::info;
+
+use crate::error::{Error, Result};
+
+/// Messages kept as history
+const MAX_HISTORY: usize = 200;
+
+/// Longest accepted message text
+const MAX_TEXT_LEN: usize = 500;
+
+/// Longest accepted sender label
+const MAX_FROM_LEN: usize = 64;
+
+/// Message request (`POST /api/messages` body)
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MessageRequest {
+    pub text: String,
+    /// Display that sent the message (e.g., "Ground iPad")
+    pub from: Option<String>,
+}
+
+/// Coordination message (`coordination-message` event payload)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoordinationMessage {
+    /// Increasing message number (1 for the first message since startup)
+    pub id: u64,
+    pub text: String,
+    pub from: Option<String>,
+    /// Unix ms
+    pub sent_at: u64,
+}
+
+/// Message history (managed by Tauri)
+pub struct CoordinationState {
+    history: RwLock<VecDeque<CoordinationMessage>>,
+    /// New messages for WebSocket relay
+    pub events: broadcast::Sender<CoordinationMessage>,
+}
+
+impl CoordinationState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(64);
+        Self {
+            history: RwLock::new(VecDeque::new()),
+            events,
+        }
+    }
+}
+
+impl Default for CoordinationState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Check and normalize a message request
+pub fn validate(request: MessageRequest, id: u64, now: u64) -> Result<CoordinationMessage> {
+    let text = request.text.trim();
+    if text.is_empty() {
+        return Err(Error::invalid_input("Message text is required"));
+    }
+    if text.chars().count() > MAX_TEXT_LEN {
+        return Err(Error::invalid_input(format!("Message is longer than {} characters", MAX_TEXT_LEN)));
+    }
+    let from = request
+        .from
+        .map(|s| s.trim().chars().take(MAX_FROM_LEN).collect::<String>())
+        .filter(|s| !s.is_empty());
+    Ok(CoordinationMessage {
+        id,
+        text: text.to_string(),
+        from,
+        sent_at: now,
+    })
+}
+
+/// Add a message to a history, dropping the oldest past `MAX_HISTORY`
+fn push(history: &mut VecDeque<CoordinationMessage>, message: CoordinationMessage) {
+    history.push_back(message);
+    while history.len() > MAX_HISTORY {
+        history.pop_front();
+    }
+}
+
+/// Messages newer than `since` (all of them with `None`), oldest first
+pub fn history(app: &AppHandle, since: Option<u64>) -> Vec<CoordinationMessage> {
+    let since = since.unwrap_or(0);
+    app.state::<CoordinationState>()
+        .history
+        .read()
+        .iter()
+        .filter(|m| m.id > since)
+        .cloned()
+        .collect()
+}
+
+/// Send a message to every display
+pub fn send(app: &AppHandle, request: MessageRequest) -> Result<CoordinationMessage> {
+    let state = app.state::<CoordinationState>();
+    let message = {
+        let mut history = state.history.write();
+        let id = history.back().map_or(1, |m| m.id + 1);
+        let message = validate(request, id, now_ms())?;
+        push(&mut history, message.clone());
+        message
+    };
+    info!(
+        "[Coordination] {}: {}",
+        message.from.as_deref().unwrap_or("Anonymous"),
+        message.text
+    );
+    let _ = app.emit("coordination-message", &message);
+    let _ = state.events.send(message.clone());
+    Ok(message)
+}
+
+/// Initialize coordination state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_coordination(app: &AppHandle) {
+    app.manage(CoordinationState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Recent coordination messages, oldest first
+#[tauri::command]
+pub fn get_coordination_messages(app: AppHandle, since: Option<u64>) -> Vec<CoordinationMessage> {
+    history(&app, since)
+}
+
+/// Send a coordination message to every display
+#[tauri::command]
+pub fn send_coordination_message(app: AppHandle, request: MessageRequest) -> Result<CoordinationMessage> {
+    send(&app, request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(text: &str, from: Option<&str>) -> MessageRequest {
+        MessageRequest {
+            text: text.to_string(),
+            from: from.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn normalizes_messages() {
+        let message = validate(request("  RWY change in 10 ", Some(" Ground ")), 3, 42).unwrap();
+        assert_eq!(message.id, 3);
+        assert_eq!(message.text, "RWY change in 10");
+        assert_eq!(message.from.as_deref(), Some("Ground"));
+        assert_eq!(message.sent_at, 42);
+
+        let anonymous = validate(request("break please", Some("  ")), 1, 0).unwrap();
+        assert_eq!(anonymous.from, None);
+    }
+
+    #[test]
+    fn rejects_empty_and_long_messages() {
+        assert_eq!(validate(request("", None), 1, 0).unwrap_err().code(), "invalidInput");
+        assert!(validate(request("   ", None), 1, 0).is_err());
+        assert!(validate(request(&"x".repeat(MAX_TEXT_LEN + 1), None), 1, 0).is_err());
+        assert!(validate(request(&"x".repeat(MAX_TEXT_LEN), None), 1, 0).is_ok());
+    }
+
+    #[test]
+    fn keeps_the_most_recent_messages() {
+        let mut history = VecDeque::new();
+        for id in 1..=(MAX_HISTORY as u64 + 5) {
+            push(&mut history, validate(request("hi", None), id, 0).unwrap());
+        }
+        assert_eq!(history.len(), MAX_HISTORY);
+        assert_eq!(history.front().map(|m| m.id), Some(6));
+        assert_eq!(history.back().map(|m| m.id), Some(MAX_HISTORY as u64 + 5));
+    }
+}
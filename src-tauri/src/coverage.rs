@@ -0,0 +1,365 @@
+//! Model match coverage report
+//!
+//! Answers "what am I missing?" before an event: every type/airline combination
+//! seen recently (each `match_model` lookup is recorded) or supplied by the
+//! caller (e.g., an expected traffic list) is run through the matcher and
+//! classified by how well it is covered:
+//!
+//! - `exact`: airline livery (or base model for non-airline traffic) of the exact type
+//! - `similarType`: model of a similar type (e.g., B738 for a B38M)
+//! - `baseLivery`: right type, but no livery for the airline
+//! - `fallback`: generic fallback model (airline on a common type, GA or B738 base)
+//! - `missing`: no host model at all; the client uses its built-in models
+//!
+//! The report also lists custom VMR rules whose models aren't installed.
+
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::matching::{self, MatchQuery, MatchResult};
+use crate::vmr::VmrRule;
+
+/// How long a type/airline combination counts as recently seen
+const RECENT_TRAFFIC_WINDOW: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Maximum number of recently seen combinations kept in memory
+const MAX_RECENT_TRAFFIC: usize = 5000;
+
+/// (type code, airline code)
+type TrafficKey = (Option<String>, Option<String>);
+
+/// A type/airline combination seen in traffic
+#[derive(Debug, Clone)]
+struct SeenTraffic {
+    /// Example callsign (used to detect GA traffic when re-matching)
+    callsign: Option<String>,
+    count: u32,
+    last_seen: SystemTime,
+}
+
+/// Recently seen traffic (managed by Tauri)
+pub struct RecentTrafficState {
+    seen: Mutex<HashMap<TrafficKey, SeenTraffic>>,
+}
+
+impl RecentTrafficState {
+    pub fn new() -> Self {
+        Self {
+            seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record the type/airline combination of a match lookup
+    pub fn record(&self, query: &MatchQuery, result: &MatchResult) {
+        if result.type_code.is_none() && result.airline_code.is_none() {
+            return;
+        }
+
+        let now = SystemTime::now();
+        let mut seen = self.seen.lock();
+        let entry = seen
+            .entry((result.type_code.clone(), result.airline_code.clone()))
+            .or_insert_with(|| SeenTraffic {
+                callsign: None,
+                count: 0,
+                last_seen: now,
+            });
+        entry.count = entry.count.saturating_add(1);
+        entry.last_seen = now;
+        if query.callsign.is_some() {
+            entry.callsign = query.callsign.clone();
+        }
+
+        if seen.len() > MAX_RECENT_TRAFFIC {
+            prune(&mut seen, now);
+        }
+    }
+
+    /// Recently seen combinations as match queries, with their counts and last seen times (Unix ms)
+    fn snapshot(&self) -> Vec<(MatchQuery, u32, u64)> {
+        let now = SystemTime::now();
+        let mut seen = self.seen.lock();
+        prune(&mut seen, now);
+
+        seen.iter()
+            .map(|((type_code, airline), traffic)| {
+                let query = MatchQuery {
+                    type_code: type_code.clone(),
+                    callsign: traffic.callsign.clone(),
+                    airline: airline.clone(),
+                };
+                (query, traffic.count, unix_ms(traffic.last_seen))
+            })
+            .collect()
+    }
+}
+
+impl Default for RecentTrafficState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Drop combinations outside the window, then the oldest ones if still over the limit
+fn prune(seen: &mut HashMap<TrafficKey, SeenTraffic>, now: SystemTime) {
+    seen.retain(|_, traffic| {
+        !now.duration_since(traffic.last_seen)
+            .is_ok_and(|age| age > RECENT_TRAFFIC_WINDOW)
+    });
+
+    if seen.len() > MAX_RECENT_TRAFFIC {
+        let mut by_age: Vec<_> = seen
+            .iter()
+            .map(|(key, traffic)| (traffic.last_seen, key.clone()))
+            .collect();
+        by_age.sort();
+        let excess = seen.len() - MAX_RECENT_TRAFFIC;
+        for (_, key) in by_age.into_iter().take(excess) {
+            seen.remove(&key);
+        }
+    }
+}
+
+fn unix_ms(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Coverage of one type/airline combination
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoverageEntry {
+    pub type_code: Option<String>,
+    pub airline_code: Option<String>,
+    /// "exact", "similarType", "baseLivery", "fallback" or "missing"
+    pub status: String,
+    /// Matched model name, if any
+    pub model_name: Option<String>,
+    /// Similar type used, if any
+    pub similar_type: Option<String>,
+    /// Times seen in traffic (0 for combinations only supplied by the caller)
+    pub seen_count: u32,
+    /// Unix timestamp (ms) when last seen in traffic
+    pub last_seen: Option<u64>,
+}
+
+/// Number of combinations per status
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoverageSummary {
+    pub total: usize,
+    pub exact: usize,
+    pub similar_type: usize,
+    pub base_livery: usize,
+    pub fallback: usize,
+    pub missing: usize,
+}
+
+/// Model match coverage report
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoverageReport {
+    pub summary: CoverageSummary,
+    /// Combinations sorted worst-covered first, then by how often they were seen
+    pub entries: Vec<CoverageEntry>,
+    /// Custom VMR rules none of whose models are installed (or enabled)
+    pub unresolved_rules: Vec<VmrRule>,
+}
+
+/// Classify how well a match result covers the requested type/airline
+fn coverage_status(result: &MatchResult) -> &'static str {
+    let Some(model) = &result.model else {
+        return "missing";
+    };
+
+    if matches!(model.match_type.as_str(), "fallback" | "gaFallback" | "airlineFallback") {
+        "fallback"
+    } else if result.similar_type.is_some() {
+        "similarType"
+    } else if result.airline_code.is_some() && model.airline_code != result.airline_code {
+        "baseLivery"
+    } else {
+        "exact"
+    }
+}
+
+/// Sort rank (worst coverage first)
+fn status_rank(status: &str) -> u8 {
+    match status {
+        "missing" => 0,
+        "fallback" => 1,
+        "baseLivery" => 2,
+        "similarType" => 3,
+        _ => 4,
+    }
+}
+
+/// Build the coverage report for recently seen traffic plus any extra combinations
+pub fn build_report(app: &AppHandle, extra: Vec<MatchQuery>) -> CoverageReport {
+    let matcher = matching::get_matcher(app);
+
+    let mut queries = app.state::<RecentTrafficState>().snapshot();
+    queries.extend(extra.into_iter().map(|query| (query, 0, 0)));
+
+    let mut entries: HashMap<TrafficKey, CoverageEntry> = HashMap::new();
+    for (query, seen_count, last_seen) in queries {
+        let result = matcher.match_model(&query);
+        if result.type_code.is_none() && result.airline_code.is_none() {
+            continue;
+        }
+
+        let entry = entries
+            .entry((result.type_code.clone(), result.airline_code.clone()))
+            .or_insert_with(|| CoverageEntry {
+                type_code: result.type_code.clone(),
+                airline_code: result.airline_code.clone(),
+                status: coverage_status(&result).to_string(),
+                model_name: result.model.as_ref().map(|m| m.model_name.clone()),
+                similar_type: result.similar_type.clone(),
+                seen_count: 0,
+                last_seen: None,
+            });
+        entry.seen_count += seen_count;
+        if seen_count > 0 {
+            entry.last_seen = entry.last_seen.max(Some(last_seen));
+        }
+    }
+
+    let mut entries: Vec<CoverageEntry> = entries.into_values().collect();
+    entries.sort_by(|a, b| {
+        status_rank(&a.status)
+            .cmp(&status_rank(&b.status))
+            .then(b.seen_count.cmp(&a.seen_count))
+            .then(a.type_code.cmp(&b.type_code))
+            .then(a.airline_code.cmp(&b.airline_code))
+    });
+
+    let mut summary = CoverageSummary {
+        total: entries.len(),
+        ..Default::default()
+    };
+    for entry in &entries {
+        match entry.status.as_str() {
+            "exact" => summary.exact += 1,
+            "similarType" => summary.similar_type += 1,
+            "baseLivery" => summary.base_livery += 1,
+            "fallback" => summary.fallback += 1,
+            _ => summary.missing += 1,
+        }
+    }
+
+    CoverageReport {
+        summary,
+        entries,
+        unresolved_rules: matcher.unresolved_custom_rules(),
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Coverage report for recently seen traffic, plus optional expected traffic (type/callsign/airline)
+#[tauri::command]
+pub fn get_model_coverage(app: AppHandle, traffic: Option<Vec<MatchQuery>>) -> CoverageReport {
+    build_report(&app, traffic.unwrap_or_default())
+}
+
+/// Initialize recent traffic tracking for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_coverage(app: &AppHandle) {
+    app.manage(RecentTrafficState::new());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matching::MatchedModel;
+
+    fn query(type_code: Option<&str>, callsign: Option<&str>) -> MatchQuery {
+        MatchQuery {
+            type_code: type_code.map(str::to_string),
+            callsign: callsign.map(str::to_string),
+            airline: None,
+        }
+    }
+
+    fn result(airline: Option<&str>, model: Option<(&str, Option<&str>)>, similar_type: Option<&str>) -> MatchResult {
+        MatchResult {
+            type_code: Some("B38M".to_string()),
+            airline_code: airline.map(str::to_string),
+            is_ga: false,
+            model: model.map(|(match_type, model_airline)| MatchedModel {
+                source: "fsltl".to_string(),
+                match_type: match_type.to_string(),
+                model_name: "FSLTL_B738_AAL".to_string(),
+                aircraft_type: "B738".to_string(),
+                airline_code: model_airline.map(str::to_string),
+                model_path: String::new(),
+                url: String::new(),
+                has_animations: false,
+                manifest: None,
+                rule: None,
+            }),
+            similar_type: similar_type.map(str::to_string),
+            trace: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn classifies_coverage() {
+        assert_eq!(coverage_status(&result(Some("AAL"), None, None)), "missing");
+        assert_eq!(coverage_status(&result(Some("AAL"), Some(("fallback", None)), None)), "fallback");
+        assert_eq!(coverage_status(&result(Some("AAL"), Some(("vmr", Some("AAL"))), Some("B738"))), "similarType");
+        assert_eq!(coverage_status(&result(Some("AAL"), Some(("baseLivery", None)), None)), "baseLivery");
+        assert_eq!(coverage_status(&result(Some("AAL"), Some(("vmr", Some("AAL"))), None)), "exact");
+        assert_eq!(coverage_status(&result(None, Some(("registry", None)), None)), "exact");
+        assert!(status_rank("missing") < status_rank("baseLivery"));
+        assert!(status_rank("similarType") < status_rank("exact"));
+    }
+
+    #[test]
+    fn records_recent_traffic() {
+        let state = RecentTrafficState::new();
+        let seen = result(Some("AAL"), None, None);
+        state.record(&query(Some("B38M"), None), &seen);
+        state.record(&query(Some("B38M"), Some("AAL12")), &seen);
+        // Lookups without a type or airline aren't traffic
+        state.record(&query(None, Some("N123")), &MatchResult { type_code: None, ..result(None, None, None) });
+
+        let snapshot = state.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        let (recorded, count, last_seen) = &snapshot[0];
+        assert_eq!(recorded.type_code.as_deref(), Some("B38M"));
+        assert_eq!(recorded.airline.as_deref(), Some("AAL"));
+        assert_eq!(recorded.callsign.as_deref(), Some("AAL12"));
+        assert_eq!(*count, 2);
+        assert!(*last_seen > 0);
+    }
+
+    #[test]
+    fn prunes_old_and_excess_traffic() {
+        let now = SystemTime::now();
+        let traffic = |age_secs: u64| SeenTraffic {
+            callsign: None,
+            count: 1,
+            last_seen: now - Duration::from_secs(age_secs),
+        };
+        let mut seen: HashMap<TrafficKey, SeenTraffic> = (0..MAX_RECENT_TRAFFIC + 2)
+            .map(|i| ((Some(format!("T{}", i)), None), traffic(i as u64)))
+            .collect();
+        seen.insert((Some("OLD".to_string()), None), traffic(RECENT_TRAFFIC_WINDOW.as_secs() + 1));
+
+        prune(&mut seen, now);
+        assert_eq!(seen.len(), MAX_RECENT_TRAFFIC);
+        assert!(!seen.contains_key(&(Some("OLD".to_string()), None)));
+        // The oldest go first
+        assert!(seen.contains_key(&(Some("T0".to_string()), None)));
+        assert!(!seen.contains_key(&(Some(format!("T{}", MAX_RECENT_TRAFFIC + 1)), None)));
+    }
+}
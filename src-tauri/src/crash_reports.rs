@@ -0,0 +1,282 @@
+//! Crash reports
+//!
+//! Backend panics used to vanish into a console nobody was watching. A panic
+//! hook now writes a report (message, location, thread, backtrace, app version
+//! and OS) to `crash-reports/` in the app data directory before the default
+//! hook runs. The HTTP server task is watched as well: if it panics or fails,
+//! the server is marked stopped and `http-server-stopped` is emitted.
+//!
+//! On the next launch, reports written since the last one shown are offered
+//! in a dialog to open the report or submit it as a GitHub issue.
+
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind, MessageDialogResult};
+use tracing::{error, info, warn};
+
+/// New issue page reports are submitted to
+const ISSUES_URL: &str = "https://github.com/leftos/towercab-3d/issues/new";
+
+/// Report text included in a prefilled issue (URLs are limited to about 8 KB)
+const MAX_ISSUE_REPORT_CHARS: usize = 5000;
+
+/// Reports kept (oldest are deleted)
+const MAX_REPORTS: usize = 20;
+
+/// File holding the name of the newest report already shown
+const LAST_SHOWN_FILE: &str = "last-shown";
+
+const OPEN_BUTTON: &str = "Open Report";
+const SUBMIT_BUTTON: &str = "Submit on GitHub";
+const DISMISS_BUTTON: &str = "Dismiss";
+
+fn report_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    Ok(app_data.join("crash-reports"))
+}
+
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "(no message)".to_string()
+    }
+}
+
+/// Format a crash report
+pub fn format_report(message: &str, location: &str, thread: &str, backtrace: &str) -> String {
+    format!(
+        "TowerCab 3D crash report\n\
+         \n\
+         Version:  {}\n\
+         OS:       {} ({})\n\
+         Time:     {}\n\
+         Thread:   {}\n\
+         Location: {}\n\
+         \n\
+         Panic: {}\n\
+         \n\
+         Backtrace:\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        chrono::Utc::now().to_rfc3339(),
+        thread,
+        location,
+        message,
+        backtrace
+    )
+}
+
+/// Write a report as `crash-<UTC time>.txt` and delete the oldest beyond the limit
+fn write_report(dir: &Path, report: &str) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create crash report folder: {}", e))?;
+    let name = format!("crash-{}.txt", chrono::Utc::now().format("%Y%m%d-%H%M%S%.3f"));
+    let path = dir.join(name);
+    // Panic messages can carry URLs and tokens (see secrets.rs)
+    fs::write(&path, crate::secrets::redact(report).as_bytes()).map_err(|e| format!("Failed to write crash report: {}", e))?;
+
+    let reports = list_reports(dir);
+    for old in reports.iter().take(reports.len().saturating_sub(MAX_REPORTS)) {
+        let _ = fs::remove_file(old);
+    }
+    Ok(path)
+}
+
+/// All reports, oldest first (names sort by time)
+fn list_reports(dir: &Path) -> Vec<PathBuf> {
+    let mut reports: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|n| n.starts_with("crash-") && n.ends_with(".txt"))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    reports.sort();
+    reports
+}
+
+/// Reports written after the newest one already shown, oldest first
+pub fn unshown_reports(dir: &Path) -> Vec<PathBuf> {
+    let last_shown = fs::read_to_string(dir.join(LAST_SHOWN_FILE)).unwrap_or_default();
+    let last_shown = last_shown.trim();
+    list_reports(dir)
+        .into_iter()
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n > last_shown)
+        })
+        .collect()
+}
+
+/// Remember the newest report as shown so it isn't offered again
+fn mark_shown(dir: &Path, report: &Path) {
+    if let Some(name) = report.file_name().and_then(|n| n.to_str()) {
+        if let Err(e) = fs::write(dir.join(LAST_SHOWN_FILE), name) {
+            warn!("[Crash] Failed to remember shown report: {}", e);
+        }
+    }
+}
+
+/// Prefilled new-issue URL for a report
+fn issue_url(report: &str) -> String {
+    let title = report
+        .lines()
+        .find_map(|line| line.strip_prefix("Panic: "))
+        .unwrap_or("Crash report");
+    let excerpt: String = report.chars().take(MAX_ISSUE_REPORT_CHARS).collect();
+    let body = format!(
+        "**What were you doing when it crashed?**\n\n\n**Crash report**\n```\n{}{}\n```\n",
+        excerpt,
+        if excerpt.len() < report.len() {
+            "\n[truncated]"
+        } else {
+            ""
+        }
+    );
+    let encode = |s: &str| url::form_urlencoded::byte_serialize(s.as_bytes()).collect::<String>();
+    format!(
+        "{}?title={}&body={}",
+        ISSUES_URL,
+        encode(&format!("Crash: {}", title.chars().take(120).collect::<String>())),
+        encode(&body)
+    )
+}
+
+/// Install the panic hook and offer reports from previous sessions.
+/// Call early in the Tauri setup closure (after logging).
+pub fn init_crash_reports(app: &AppHandle) {
+    let dir = match report_dir(app) {
+        Ok(dir) => dir,
+        Err(e) => {
+            warn!("[Crash] Crash reports disabled: {}", e);
+            return;
+        }
+    };
+
+    let pending = unshown_reports(&dir);
+
+    let hook_dir = dir.clone();
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let thread = std::thread::current();
+        let thread = thread.name().unwrap_or("unnamed").to_string();
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+            .unwrap_or_else(|| "unknown".to_string());
+        let message = panic_message(info.payload());
+        let report = format_report(&message, &location, &thread, &Backtrace::force_capture().to_string());
+        match write_report(&hook_dir, &report) {
+            Ok(path) => error!(
+                "[Crash] Panic in thread '{}' at {}: {} (report: {:?})",
+                thread, location, message, path
+            ),
+            Err(e) => error!(
+                "[Crash] Panic in thread '{}' at {}: {} ({})",
+                thread, location, message, e
+            ),
+        }
+        default_hook(info);
+    }));
+
+    if let Some(newest) = pending.last() {
+        info!("[Crash] {} crash report(s) from previous sessions", pending.len());
+        mark_shown(&dir, newest);
+        offer_report(app, newest.clone(), pending.len());
+    }
+}
+
+/// Ask whether to open or submit the newest report
+fn offer_report(app: &AppHandle, report: PathBuf, count: usize) {
+    let message = if count == 1 {
+        "TowerCab 3D crashed during a previous session. A crash report was saved.\n\n\
+         Submitting it on GitHub helps get the problem fixed."
+            .to_string()
+    } else {
+        format!(
+            "TowerCab 3D crashed {} times since it was last started without a crash. \
+             Crash reports were saved; the newest can be opened or submitted.\n\n\
+             Submitting it on GitHub helps get the problem fixed.",
+            count
+        )
+    };
+    app.dialog()
+        .message(message)
+        .title("Crash Report")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::YesNoCancelCustom(
+            OPEN_BUTTON.to_string(),
+            SUBMIT_BUTTON.to_string(),
+            DISMISS_BUTTON.to_string(),
+        ))
+        .show_with_result(move |result| {
+            let outcome = match result {
+                MessageDialogResult::Yes => crate::open_with_system(report.as_os_str()),
+                MessageDialogResult::Custom(label) if label == OPEN_BUTTON => {
+                    crate::open_with_system(report.as_os_str())
+                }
+                MessageDialogResult::No => submit_report(&report),
+                MessageDialogResult::Custom(label) if label == SUBMIT_BUTTON => submit_report(&report),
+                _ => Ok(()),
+            };
+            if let Err(e) = outcome {
+                warn!("[Crash] {}", e);
+            }
+        });
+}
+
+fn submit_report(report: &Path) -> Result<(), String> {
+    let text = fs::read_to_string(report).map_err(|e| format!("Failed to read crash report: {}", e))?;
+    crate::open_with_system(std::ffi::OsStr::new(&issue_url(&text)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offers_only_unshown_reports() {
+        let dir = std::env::temp_dir().join(format!("crash-reports-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let report = format_report(
+            "index out of bounds",
+            "src/server.rs:10:5",
+            "tokio-runtime-worker",
+            "0: main",
+        );
+        assert!(report.contains("Panic: index out of bounds"));
+
+        let first = write_report(&dir, &report).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let second = write_report(&dir, &report).unwrap();
+        assert_eq!(unshown_reports(&dir), vec![first, second.clone()]);
+
+        mark_shown(&dir, &second);
+        assert!(unshown_reports(&dir).is_empty());
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let third = write_report(&dir, &report).unwrap();
+        assert_eq!(unshown_reports(&dir), vec![third]);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let url = issue_url(&report);
+        assert!(url.starts_with(ISSUES_URL));
+        assert!(url.contains("title=Crash%3A+index+out+of+bounds"));
+    }
+}
@@ -0,0 +1,226 @@
+//! Datablock layout shared by the host and every remote browser
+//!
+//! Which fields each datablock line shows, the leader line length and the font
+//! scale are stored in the global settings (`datablock` section), so every
+//! connected display renders identical datablocks and the layout travels with
+//! exported settings. Every change is emitted as `datablock-layout-changed`
+//! and relayed to remote browsers over `/api/datablock/ws`.
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::info;
+
+use crate::error::{Error, Result};
+
+/// Fields a datablock line can show
+pub const FIELDS: [&str; 10] = [
+    "callsign",
+    "type",
+    "altitude",
+    "speed",
+    "gate",
+    "departure",
+    "arrival",
+    "squawk",
+    "verticalTrend",
+    "freq",
+];
+
+/// Most lines and fields per line a datablock can have
+const MAX_LINES: usize = 4;
+const MAX_FIELDS_PER_LINE: usize = 6;
+
+/// Datablock layout in the global settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DatablockLayout {
+    /// Fields on each line, top to bottom (default: callsign / type altitude speed)
+    pub lines: Vec<Vec<String>>,
+    /// Leader line length (0.5-5 in 0.5 steps, default: 2)
+    pub leader_length: f64,
+    /// Multiplier for each display's datablock font size (0.5-2, default: 1)
+    pub font_scale: f64,
+    /// Color datablocks by the position the aircraft is talking to (default: false)
+    pub color_by_frequency: bool,
+}
+
+impl Default for DatablockLayout {
+    fn default() -> Self {
+        Self {
+            lines: vec![
+                vec!["callsign".to_string()],
+                vec!["type".to_string(), "altitude".to_string(), "speed".to_string()],
+            ],
+            leader_length: 2.0,
+            font_scale: 1.0,
+            color_by_frequency: false,
+        }
+    }
+}
+
+/// Datablock layout state (managed by Tauri)
+pub struct DatablockState {
+    layout: RwLock<DatablockLayout>,
+    /// Layout changes for WebSocket relay
+    pub events: broadcast::Sender<DatablockLayout>,
+}
+
+impl DatablockState {
+    pub fn new(layout: DatablockLayout) -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            layout: RwLock::new(layout),
+            events,
+        }
+    }
+}
+
+/// Check a datablock layout
+pub fn validate(layout: &DatablockLayout) -> Result<()> {
+    if layout.lines.is_empty() || layout.lines.len() > MAX_LINES {
+        return Err(Error::InvalidInput(format!("A datablock needs 1-{} lines", MAX_LINES)));
+    }
+    for line in &layout.lines {
+        if line.len() > MAX_FIELDS_PER_LINE {
+            return Err(Error::InvalidInput(format!(
+                "A datablock line can show at most {} fields",
+                MAX_FIELDS_PER_LINE
+            )));
+        }
+        if let Some(field) = line.iter().find(|f| !FIELDS.contains(&f.as_str())) {
+            return Err(Error::InvalidInput(format!("Unknown datablock field: {}", field)));
+        }
+    }
+    if !(0.5..=5.0).contains(&layout.leader_length) {
+        return Err(Error::invalid_input("Leader length must be between 0.5 and 5"));
+    }
+    if !(0.5..=2.0).contains(&layout.font_scale) {
+        return Err(Error::invalid_input("Font scale must be between 0.5 and 2"));
+    }
+    Ok(())
+}
+
+/// Carry the leader distance of older settings files (`display.leaderDistance`)
+/// over to the datablock layout
+pub fn migrate_settings(settings: &mut Value) {
+    let Some(object) = settings.as_object_mut() else {
+        return;
+    };
+    if object.contains_key("datablock") {
+        return;
+    }
+    if let Some(leader) = object
+        .get("display")
+        .and_then(|d| d.get("leaderDistance"))
+        .and_then(Value::as_f64)
+    {
+        object.insert("datablock".to_string(), json!({ "leaderLength": leader }));
+    }
+}
+
+/// Current datablock layout
+pub fn get_layout(app: &AppHandle) -> DatablockLayout {
+    app.state::<DatablockState>().layout.read().clone()
+}
+
+/// Take the layout from saved global settings, notifying clients if it changed.
+/// Called whenever global settings are written.
+pub fn apply_settings(app: &AppHandle, layout: &DatablockLayout) {
+    if validate(layout).is_err() {
+        return;
+    }
+    let state = app.state::<DatablockState>();
+    {
+        let mut current = state.layout.write();
+        if *current == *layout {
+            return;
+        }
+        *current = layout.clone();
+    }
+
+    info!("[Datablocks] Layout changed ({} lines)", layout.lines.len());
+    let _ = app.emit("datablock-layout-changed", layout);
+    let _ = state.events.send(layout.clone());
+}
+
+/// Change the datablock layout, persist it and notify clients
+pub fn set_layout(app: &AppHandle, layout: DatablockLayout) -> Result<DatablockLayout> {
+    validate(&layout)?;
+    let mut global = crate::read_global_settings(app.clone())?;
+    global.datablock = layout;
+    // Writing global settings applies the layout
+    crate::write_global_settings(app.clone(), global)?;
+    Ok(get_layout(app))
+}
+
+/// Initialize the datablock layout from saved global settings.
+/// Call this in the Tauri setup closure.
+pub fn init_datablocks(app: &AppHandle) {
+    let layout = crate::read_global_settings(app.clone())
+        .map(|s| s.datablock)
+        .ok()
+        .filter(|l| validate(l).is_ok())
+        .unwrap_or_default();
+    app.manage(DatablockState::new(layout));
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the datablock layout shared by all displays
+#[tauri::command]
+pub fn get_datablock_layout(app: AppHandle) -> DatablockLayout {
+    get_layout(&app)
+}
+
+/// Set the datablock layout for all displays
+#[tauri::command]
+pub fn set_datablock_layout(app: AppHandle, layout: DatablockLayout) -> Result<DatablockLayout> {
+    set_layout(&app, layout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_layouts() {
+        let mut layout = DatablockLayout::default();
+        assert!(validate(&layout).is_ok());
+
+        layout.lines.push(vec!["gate".to_string(), "squawk".to_string(), "freq".to_string()]);
+        assert!(validate(&layout).is_ok());
+
+        layout.lines[2].push("heading".to_string());
+        assert!(validate(&layout).is_err());
+
+        layout = DatablockLayout {
+            lines: Vec::new(),
+            ..Default::default()
+        };
+        assert!(validate(&layout).is_err());
+
+        layout = DatablockLayout {
+            leader_length: 8.0,
+            ..Default::default()
+        };
+        assert!(validate(&layout).is_err());
+    }
+
+    #[test]
+    fn migrates_leader_distance() {
+        let mut settings = json!({ "display": { "leaderDistance": 3.5 } });
+        migrate_settings(&mut settings);
+        let layout: DatablockLayout = serde_json::from_value(settings["datablock"].clone()).unwrap();
+        assert_eq!(layout.leader_length, 3.5);
+        assert_eq!(layout.lines, DatablockLayout::default().lines);
+
+        let mut settings = json!({ "display": { "leaderDistance": 3.5 }, "datablock": { "leaderLength": 1 } });
+        migrate_settings(&mut settings);
+        assert_eq!(settings["datablock"]["leaderLength"], 1);
+    }
+}
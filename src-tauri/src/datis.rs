@@ -0,0 +1,382 @@
+//! Real-world D-ATIS compared with the VATSIM ATIS
+//!
+//! For mixed viewing (real-world ADS-B traffic next to VATSIM traffic) it
+//! helps to know whether the network is flowing the same way as the real
+//! airport. On every traffic poll where the active airport has a VATSIM ATIS
+//! naming runways, the real-world D-ATIS is fetched from datis.clowd.io (US
+//! airports with D-ATIS, cached for 5 minutes) and the runways in use on both
+//! are compared. The result is sent as an `atis-comparison` event whenever it
+//! changes and served at `GET /api/atis/comparison`; a new mismatch also
+//! raises a `weather-alert` of kind `datisMismatch` (toggle
+//! `weatherAlerts.datisMismatch`, which also turns the comparison off).
+
+use std::collections::{BTreeSet, HashMap};
+use std::time::{Duration, Instant, SystemTime};
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::debug;
+
+use crate::http_client;
+use crate::runways::Runway;
+use crate::supervisor::{self, Restart};
+use crate::traffic::{self, OnlineController};
+use crate::view_presets;
+use crate::weather_alerts;
+
+/// D-ATIS API (one airport per request)
+const DATIS_URL: &str = "https://datis.clowd.io/api";
+
+/// How long a fetched D-ATIS is used before fetching it again
+const CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// One D-ATIS broadcast ("combined", or separate "arr" and "dep")
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DatisEntry {
+    #[serde(rename = "type", default)]
+    pub kind: String,
+    /// ATIS letter
+    #[serde(default)]
+    pub code: String,
+    /// Broadcast text
+    #[serde(default)]
+    pub datis: String,
+}
+
+/// VATSIM ATIS runways against the real-world D-ATIS
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AtisComparison {
+    pub icao: String,
+    /// Runway ends the VATSIM ATIS names as in use
+    pub vatsim_runways: Vec<String>,
+    /// VATSIM ATIS letter(s), e.g. "A" or "A/C" for separate stations
+    pub vatsim_letter: Option<String>,
+    /// Runway ends the real-world D-ATIS names as in use
+    pub real_runways: Vec<String>,
+    /// D-ATIS letter(s), e.g. "B" or "B/D" for separate arrival and departure ATIS
+    pub real_letter: Option<String>,
+    /// Both name the same runways
+    pub matches: bool,
+    /// In use only on VATSIM / only in the real world
+    pub vatsim_only: Vec<String>,
+    pub real_only: Vec<String>,
+    /// Unix ms
+    pub checked_at: u64,
+}
+
+impl AtisComparison {
+    /// Same runways and letters (the check time aside)
+    fn same_as(&self, other: &AtisComparison) -> bool {
+        AtisComparison {
+            checked_at: other.checked_at,
+            ..self.clone()
+        } == *other
+    }
+}
+
+/// D-ATIS comparison state (managed by Tauri)
+pub struct DatisState {
+    /// D-ATIS by airport and when it was fetched (empty when the airport has none)
+    cache: RwLock<HashMap<String, (Instant, Vec<DatisEntry>)>>,
+    comparison: RwLock<Option<AtisComparison>>,
+}
+
+impl DatisState {
+    pub fn new() -> Self {
+        Self {
+            cache: RwLock::new(HashMap::new()),
+            comparison: RwLock::new(None),
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+async fn fetch(icao: &str) -> Result<Vec<DatisEntry>, String> {
+    let response = http_client::client()
+        .get(format!("{}/{}", DATIS_URL, icao))
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch D-ATIS: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("D-ATIS returned HTTP {}", response.status()));
+    }
+    let body: Value = response.json().await.map_err(|e| format!("Invalid D-ATIS response: {}", e))?;
+    // Airports without D-ATIS get {"error": "..."}
+    if body.get("error").is_some() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_value(body).map_err(|e| format!("Invalid D-ATIS response: {}", e))
+}
+
+/// The airport's D-ATIS, from the cache while fresh (failures are cached too,
+/// so an unreachable API isn't asked on every poll)
+async fn datis(app: &AppHandle, icao: &str) -> Vec<DatisEntry> {
+    let state = app.state::<DatisState>();
+    if let Some((fetched, entries)) = state.cache.read().get(icao) {
+        if fetched.elapsed() < CACHE_TTL {
+            return entries.clone();
+        }
+    }
+    let entries = fetch(icao).await.unwrap_or_else(|e| {
+        debug!("[DATIS] {}: {}", icao, e);
+        Vec::new()
+    });
+    state.cache.write().insert(icao.to_string(), (Instant::now(), entries.clone()));
+    entries
+}
+
+/// Letters joined in order ("A", "B/D")
+fn letters<'a>(codes: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let codes: Vec<String> = codes
+        .into_iter()
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty())
+        .collect();
+    (!codes.is_empty()).then(|| codes.join("/"))
+}
+
+/// The airport's VATSIM ATIS stations ("KBOS_ATIS", "KBOS_D_ATIS"), by callsign
+fn airport_atis<'a>(icao: &str, stations: &'a [OnlineController]) -> Vec<&'a OnlineController> {
+    let mut atis: Vec<&OnlineController> = stations
+        .iter()
+        .filter(|s| {
+            let callsign = s.callsign.to_uppercase();
+            callsign.ends_with("_ATIS") && callsign.split('_').next() == Some(icao)
+        })
+        .collect();
+    atis.sort_by(|a, b| a.callsign.cmp(&b.callsign));
+    atis
+}
+
+fn vatsim_runways(stations: &[&OnlineController], runways: &[Runway]) -> BTreeSet<String> {
+    stations
+        .iter()
+        .filter_map(|s| s.text_atis.as_ref())
+        .flat_map(|text| view_presets::atis_runways(text, runways))
+        .collect()
+}
+
+/// Compare the runways of the airport's VATSIM ATIS stations and its D-ATIS;
+/// `None` unless both name runways
+pub fn compare(
+    icao: &str,
+    stations: &[OnlineController],
+    datis: &[DatisEntry],
+    runways: &[Runway],
+    now: u64,
+) -> Option<AtisComparison> {
+    let vatsim_stations = airport_atis(icao, stations);
+    let vatsim = vatsim_runways(&vatsim_stations, runways);
+    let real: BTreeSet<String> = datis
+        .iter()
+        .flat_map(|entry| view_presets::atis_runways(std::slice::from_ref(&entry.datis), runways))
+        .collect();
+    if vatsim.is_empty() || real.is_empty() {
+        return None;
+    }
+
+    Some(AtisComparison {
+        icao: icao.to_string(),
+        vatsim_letter: letters(vatsim_stations.iter().filter_map(|s| s.atis_code.as_deref())),
+        real_letter: letters(datis.iter().map(|e| e.code.as_str())),
+        matches: vatsim == real,
+        vatsim_only: vatsim.difference(&real).cloned().collect(),
+        real_only: real.difference(&vatsim).cloned().collect(),
+        vatsim_runways: vatsim.into_iter().collect(),
+        real_runways: real.into_iter().collect(),
+        checked_at: now,
+    })
+}
+
+fn with_letter(runways: &[String], letter: &Option<String>) -> String {
+    match letter {
+        Some(letter) => format!("{} ({})", runways.join(", "), letter),
+        None => runways.join(", "),
+    }
+}
+
+fn publish(app: &AppHandle, comparison: Option<AtisComparison>) {
+    let _ = app.emit("atis-comparison", &comparison);
+    *app.state::<DatisState>().comparison.write() = comparison;
+}
+
+/// Compare the active airport's VATSIM ATIS with its D-ATIS (traffic poll)
+pub async fn update(app: &AppHandle, icao: &str, stations: &[OnlineController], runways: &[Runway]) {
+    let settings = weather_alerts::settings(app);
+    // Only ask for the D-ATIS when there is something to compare it with
+    if !settings.datis_mismatch || vatsim_runways(&airport_atis(icao, stations), runways).is_empty() {
+        clear(app);
+        return;
+    }
+
+    let datis = datis(app, icao).await;
+    let Some(comparison) = compare(icao, stations, &datis, runways, now_ms()) else {
+        clear(app);
+        return;
+    };
+    let previous = get_comparison(app);
+    if previous.as_ref().is_some_and(|p| comparison.same_as(p)) {
+        return;
+    }
+    let newly_mismatched = !comparison.matches && previous.as_ref().map_or(true, |p| p.matches || p.icao != icao);
+    if newly_mismatched {
+        weather_alerts::raise(
+            app,
+            &settings,
+            icao,
+            weather_alerts::KIND_DATIS_MISMATCH,
+            format!("{} runways differ from real world", icao),
+            format!(
+                "VATSIM ATIS: {}. D-ATIS: {}",
+                with_letter(&comparison.vatsim_runways, &comparison.vatsim_letter),
+                with_letter(&comparison.real_runways, &comparison.real_letter)
+            ),
+        );
+    }
+    publish(app, Some(comparison));
+}
+
+/// Drop the comparison (no active airport, ATIS or D-ATIS)
+pub fn clear(app: &AppHandle) {
+    if get_comparison(app).is_some() {
+        publish(app, None);
+    }
+}
+
+/// Last comparison at the active airport
+pub fn get_comparison(app: &AppHandle) -> Option<AtisComparison> {
+    app.state::<DatisState>().comparison.read().clone()
+}
+
+/// Initialize D-ATIS comparison state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_datis(app: &AppHandle) {
+    app.manage(DatisState::new());
+}
+
+/// Compare after every traffic poll. The D-ATIS lookup can take a while, so it
+/// runs here instead of in the poll.
+pub fn start_datis_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn("datis", Restart::Always, move || {
+        let app = app.clone();
+        let mut polls = traffic::subscribe_polls(&app);
+        async move {
+            while polls.changed().await.is_ok() {
+                let polled = polls.borrow_and_update().clone();
+                match polled {
+                    Some(polled) => update(&app, &polled.icao, &polled.atis, &polled.runways).await,
+                    None => clear(&app),
+                }
+            }
+        }
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the VATSIM ATIS / real-world D-ATIS runway comparison at the active airport
+#[tauri::command]
+pub fn get_atis_comparison(app: AppHandle) -> Option<AtisComparison> {
+    get_comparison(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runways::RunwayEnd;
+
+    fn runway(low: &str, high: &str) -> Runway {
+        let end = |ident: &str| RunwayEnd {
+            ident: ident.to_string(),
+            latitude: None,
+            longitude: None,
+            heading_true: None,
+            elevation_ft: None,
+            displaced_threshold_ft: 0.0,
+        };
+        Runway {
+            ident: format!("{}/{}", low, high),
+            low_end: end(low),
+            high_end: end(high),
+            length_ft: None,
+            width_ft: None,
+            surface: String::new(),
+            lighted: true,
+        }
+    }
+
+    fn kbos() -> Vec<Runway> {
+        vec![runway("04L", "22R"), runway("04R", "22L"), runway("09", "27"), runway("15R", "33L")]
+    }
+
+    fn station(callsign: &str, code: &str, text: &str) -> OnlineController {
+        serde_json::from_value(serde_json::json!({
+            "callsign": callsign,
+            "atis_code": code,
+            "text_atis": [text],
+        }))
+        .unwrap()
+    }
+
+    fn entry(kind: &str, code: &str, text: &str) -> DatisEntry {
+        DatisEntry {
+            kind: kind.to_string(),
+            code: code.to_string(),
+            datis: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn compares_runway_configurations() {
+        let stations = vec![
+            station("KBOS_ATIS", "A", "BOS ATIS INFO A. LDG RWYS 4L AND 4R, DEPG RWY 9."),
+            station("KJFK_ATIS", "C", "JFK ATIS INFO C. LDG RWY 22L."),
+        ];
+        let same = [entry("combined", "K", "BOS ATIS INFO K 1554Z. ILS RWY 4R APCH IN USE. LANDING RUNWAYS 4L, 4R. DEPARTING RUNWAY 9.")];
+        let comparison = compare("KBOS", &stations, &same, &kbos(), 1_000).unwrap();
+        assert!(comparison.matches);
+        assert_eq!(comparison.vatsim_runways, ["04L", "04R", "09"]);
+        assert_eq!(comparison.vatsim_letter.as_deref(), Some("A"));
+        assert_eq!(comparison.real_letter.as_deref(), Some("K"));
+
+        let split = [
+            entry("arr", "B", "BOS ARR INFO B. LDG RWYS 22L AND 27."),
+            entry("dep", "D", "BOS DEP INFO D. DEPG RWY 22R."),
+        ];
+        let comparison = compare("KBOS", &stations, &split, &kbos(), 1_000).unwrap();
+        assert!(!comparison.matches);
+        assert_eq!(comparison.real_runways, ["22L", "22R", "27"]);
+        assert_eq!(comparison.real_letter.as_deref(), Some("B/D"));
+        assert_eq!(comparison.vatsim_only, ["04L", "04R", "09"]);
+        assert_eq!(comparison.real_only, ["22L", "22R", "27"]);
+
+        // Nothing to compare without runways on both sides
+        assert!(compare("KBOS", &stations, &[], &kbos(), 1_000).is_none());
+        assert!(compare("KBOS", &stations[1..], &same, &kbos(), 1_000).is_none());
+    }
+
+    #[test]
+    fn ignores_the_check_time_when_comparing_results() {
+        let stations = vec![station("KBOS_ATIS", "A", "LDG RWY 27")];
+        let datis = [entry("combined", "B", "LDG RWY 27")];
+        let first = compare("KBOS", &stations, &datis, &kbos(), 1_000).unwrap();
+        let later = compare("KBOS", &stations, &datis, &kbos(), 16_000).unwrap();
+        assert!(first.same_as(&later));
+
+        let new_letter = [entry("combined", "C", "LDG RWY 27")];
+        assert!(!first.same_as(&compare("KBOS", &stations, &new_letter, &kbos(), 16_000).unwrap()));
+    }
+}
@@ -0,0 +1,158 @@
+//! Internal counters for the `/api/debug/*` endpoints
+//!
+//! WebSocket handlers register their connection per stream and count how
+//! often a slow client fell behind its broadcast channel (and how many
+//! messages it skipped). Background tasks report each run, so a task that
+//! stopped or hangs shows up as unhealthy. The counters are cheap and always
+//! on; only the endpoints reading them are dev-only (see `server.rs`).
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+
+/// A task is unhealthy when it hasn't run for this many expected intervals
+const STALE_INTERVALS: u32 = 3;
+
+#[derive(Default)]
+struct Stream {
+    connected: usize,
+    lagged: u64,
+    skipped: u64,
+}
+
+struct Task {
+    interval: Duration,
+    runs: u64,
+    last_run: Instant,
+    last_error: Option<String>,
+}
+
+static STREAMS: Mutex<BTreeMap<&'static str, Stream>> = Mutex::new(BTreeMap::new());
+static TASKS: Mutex<BTreeMap<&'static str, Task>> = Mutex::new(BTreeMap::new());
+
+/// WebSocket stream counters
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamStats {
+    pub name: &'static str,
+    /// Clients connected now
+    pub connected: usize,
+    /// Times a client fell behind the broadcast channel
+    pub lagged: u64,
+    /// Messages skipped by lagging clients
+    pub skipped: u64,
+}
+
+/// Background task health
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskHealth {
+    pub name: &'static str,
+    pub runs: u64,
+    /// Seconds since the task last ran
+    pub last_run_age_s: f64,
+    pub expected_interval_s: f64,
+    /// Ran within the last few expected intervals
+    pub healthy: bool,
+    /// Error from the last run, if it failed
+    pub last_error: Option<String>,
+}
+
+/// Counts a WebSocket client as connected until dropped
+pub struct StreamGuard(&'static str);
+
+impl Drop for StreamGuard {
+    fn drop(&mut self) {
+        if let Some(stream) = STREAMS.lock().get_mut(self.0) {
+            stream.connected = stream.connected.saturating_sub(1);
+        }
+    }
+}
+
+/// Register a WebSocket client on a stream (keep the guard for the connection's lifetime)
+pub fn stream_connected(name: &'static str) -> StreamGuard {
+    STREAMS.lock().entry(name).or_default().connected += 1;
+    StreamGuard(name)
+}
+
+/// Record a client falling behind a broadcast channel
+pub fn record_lag(name: &'static str, skipped: u64) {
+    let mut streams = STREAMS.lock();
+    let stream = streams.entry(name).or_default();
+    stream.lagged += 1;
+    stream.skipped += skipped;
+}
+
+/// Record a background task run (`error` if it failed)
+pub fn task_ran(name: &'static str, interval: Duration, error: Option<String>) {
+    let mut tasks = TASKS.lock();
+    let task = tasks.entry(name).or_insert_with(|| Task {
+        interval,
+        runs: 0,
+        last_run: Instant::now(),
+        last_error: None,
+    });
+    task.interval = interval;
+    task.runs += 1;
+    task.last_run = Instant::now();
+    task.last_error = error;
+}
+
+pub fn streams() -> Vec<StreamStats> {
+    STREAMS
+        .lock()
+        .iter()
+        .map(|(&name, stream)| StreamStats {
+            name,
+            connected: stream.connected,
+            lagged: stream.lagged,
+            skipped: stream.skipped,
+        })
+        .collect()
+}
+
+pub fn tasks() -> Vec<TaskHealth> {
+    TASKS
+        .lock()
+        .iter()
+        .map(|(&name, task)| {
+            let age = task.last_run.elapsed();
+            TaskHealth {
+                name,
+                runs: task.runs,
+                last_run_age_s: age.as_secs_f64(),
+                expected_interval_s: task.interval.as_secs_f64(),
+                healthy: age <= task.interval * STALE_INTERVALS && task.last_error.is_none(),
+                last_error: task.last_error.clone(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_streams_and_tasks() {
+        let first = stream_connected("test stream");
+        let second = stream_connected("test stream");
+        record_lag("test stream", 12);
+        drop(first);
+        let stats = streams().into_iter().find(|s| s.name == "test stream").unwrap();
+        assert_eq!((stats.connected, stats.lagged, stats.skipped), (1, 1, 12));
+        drop(second);
+
+        task_ran("test task", Duration::from_secs(60), None);
+        task_ran(
+            "test failing task",
+            Duration::from_secs(60),
+            Some("timeout".to_string()),
+        );
+        let tasks = tasks();
+        assert!(tasks.iter().any(|t| t.name == "test task" && t.healthy && t.runs == 1));
+        assert!(tasks.iter().any(|t| t.name == "test failing task" && !t.healthy));
+    }
+}
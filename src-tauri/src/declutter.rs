@@ -0,0 +1,197 @@
+//! Per-client server-side decluttering
+//!
+//! Remote clients on small devices can ask the aircraft WebSockets
+//! (`/api/vnas/ws`, `/api/replay/ws`) to send only what they will draw: a
+//! range ring around the active airport, an altitude band and/or aircraft on
+//! the ground only. The profile is given as query parameters when connecting
+//! (e.g., `/api/vnas/ws?rangeNm=10&maxAltitudeFt=5000`) and can be changed at
+//! any time by sending `{"type":"declutter","rangeNm":5,"groundOnly":true}`
+//! (fields left out are not filtered on).
+
+use serde::Deserialize;
+use tauri::AppHandle;
+
+use crate::airport_db;
+use crate::geo;
+use crate::replay::{ReplayEvent, ReplaySnapshot};
+use crate::server::VnasAircraftBroadcast;
+use crate::traffic;
+use crate::weather;
+
+const METERS_TO_FEET: f64 = 1.0 / 0.3048;
+
+/// What a client wants to receive
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DeclutterProfile {
+    /// Only aircraft within this distance of the active airport (nm)
+    pub range_nm: Option<f64>,
+    /// Altitude band (feet MSL)
+    pub min_altitude_ft: Option<f64>,
+    pub max_altitude_ft: Option<f64>,
+    /// Only aircraft on the ground
+    pub ground_only: bool,
+}
+
+/// Client message changing the profile
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum ClientMessage {
+    Declutter(DeclutterProfile),
+}
+
+/// Airport the range ring is drawn around
+#[derive(Debug, Clone, Copy)]
+pub struct Reference {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub elevation_ft: f64,
+}
+
+impl DeclutterProfile {
+    /// Whether the profile filters anything
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Whether an aircraft passes the profile. Without a reference airport,
+    /// the range ring is not applied.
+    pub fn keeps(&self, reference: Option<Reference>, lat: f64, lon: f64, altitude_ft: f64, on_ground: bool) -> bool {
+        if self.ground_only && !on_ground {
+            return false;
+        }
+        if self.min_altitude_ft.is_some_and(|min| altitude_ft < min)
+            || self.max_altitude_ft.is_some_and(|max| altitude_ft > max)
+        {
+            return false;
+        }
+        match (self.range_nm, reference) {
+            (Some(range), Some(r)) => geo::distance_and_bearing(r.latitude, r.longitude, lat, lon).0 <= range,
+            _ => true,
+        }
+    }
+
+    /// vNAS aircraft passing the profile
+    pub fn vnas(&self, aircraft: &[VnasAircraftBroadcast], reference: Option<Reference>) -> Vec<VnasAircraftBroadcast> {
+        let elevation_ft = reference.map_or(0.0, |r| r.elevation_ft);
+        aircraft
+            .iter()
+            .filter(|a| {
+                let on_ground = traffic::is_on_ground(a.altitude, a.groundspeed_kt.unwrap_or(0.0), elevation_ft);
+                self.keeps(reference, a.lat, a.lon, a.altitude, on_ground)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Replay event with only the aircraft states passing the profile
+    pub fn replay(&self, event: ReplayEvent, reference: Option<Reference>) -> ReplayEvent {
+        let ReplayEvent::Snapshot(snapshot) = event else {
+            return event;
+        };
+        let elevation_ft = reference.map_or(0.0, |r| r.elevation_ft);
+        let aircraft_states = snapshot
+            .aircraft_states
+            .into_iter()
+            .filter(|state| {
+                let (Some(lat), Some(lon)) = (state["latitude"].as_f64(), state["longitude"].as_f64()) else {
+                    return true;
+                };
+                let altitude_ft = state["altitude"].as_f64().unwrap_or(0.0) * METERS_TO_FEET;
+                let on_ground = state["onGround"].as_f64().map_or_else(
+                    || traffic::is_on_ground(altitude_ft, state["groundspeed"].as_f64().unwrap_or(0.0), elevation_ft),
+                    |v| v >= 1.0,
+                );
+                self.keeps(reference, lat, lon, altitude_ft, on_ground)
+            })
+            .collect();
+        ReplayEvent::Snapshot(ReplaySnapshot {
+            aircraft_states,
+            ..snapshot
+        })
+    }
+}
+
+/// New profile from a client message, if it is a declutter message
+pub fn parse_message(text: &str) -> Option<DeclutterProfile> {
+    match serde_json::from_str(text) {
+        Ok(ClientMessage::Declutter(profile)) => Some(profile),
+        Err(_) => None,
+    }
+}
+
+/// Reference airport for a client's range ring, looked up again when the
+/// active airport changes
+#[derive(Default)]
+pub struct ReferenceCache {
+    airport: Option<(String, Option<Reference>)>,
+}
+
+impl ReferenceCache {
+    pub async fn get(&mut self, app: &AppHandle) -> Option<Reference> {
+        let icao = weather::active_airport(app)?;
+        if let Some((cached, reference)) = &self.airport {
+            if *cached == icao {
+                return *reference;
+            }
+        }
+        let reference = airport_db::get_airport(app, icao.clone())
+            .await
+            .ok()
+            .flatten()
+            .map(|a| Reference {
+                latitude: a.latitude,
+                longitude: a.longitude,
+                elevation_ft: a.elevation_ft,
+            });
+        self.airport = Some((icao, reference));
+        reference
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::test_vnas_broadcast;
+
+    #[test]
+    fn filters_by_range_altitude_and_ground() {
+        let aircraft = |callsign: &str, lat: f64, altitude: f64, groundspeed_kt: f64| VnasAircraftBroadcast {
+            lat,
+            altitude,
+            groundspeed_kt: Some(groundspeed_kt),
+            ..test_vnas_broadcast(callsign, 0)
+        };
+        let reference = Some(Reference {
+            latitude: 42.0,
+            longitude: -71.0,
+            elevation_ft: 20.0,
+        });
+        let traffic = vec![
+            aircraft("TAXI", 42.0, 20.0, 15.0),
+            aircraft("FINAL", 42.05, 1200.0, 140.0),
+            aircraft("HIGH", 42.1, 12000.0, 300.0),
+            // 30 nm north
+            aircraft("FAR", 42.5, 3000.0, 250.0),
+        ];
+        let callsigns = |profile: &DeclutterProfile| -> Vec<String> {
+            profile
+                .vnas(&traffic, reference)
+                .into_iter()
+                .map(|a| a.callsign)
+                .collect()
+        };
+
+        let profile: DeclutterProfile = serde_json::from_str("{}").unwrap();
+        assert!(profile.is_empty());
+        assert_eq!(callsigns(&profile).len(), 4);
+
+        let profile = parse_message(r#"{"type":"declutter","rangeNm":10,"maxAltitudeFt":10000}"#).unwrap();
+        assert_eq!(callsigns(&profile), vec!["TAXI", "FINAL"]);
+
+        let profile = parse_message(r#"{"type":"declutter","groundOnly":true}"#).unwrap();
+        assert_eq!(callsigns(&profile), vec!["TAXI"]);
+
+        assert!(parse_message(r#"{"type":"ping"}"#).is_none());
+    }
+}
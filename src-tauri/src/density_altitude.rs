@@ -0,0 +1,148 @@
+//! Pressure altitude, density altitude and ISA deviation from the current METAR
+//!
+//! Uses the station elevation reported with the METAR, the altimeter setting
+//! (QNH) and the temperature/dewpoint. Density altitude accounts for humidity
+//! through the virtual temperature, like the NWS density altitude calculator.
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::error::Result;
+use crate::weather::{self, Metar};
+
+const METERS_TO_FEET: f64 = 3.28084;
+
+/// ISA sea level pressure (hPa)
+const ISA_SEA_LEVEL_HPA: f64 = 1013.25;
+
+/// ISA sea level temperature (°C)
+const ISA_SEA_LEVEL_C: f64 = 15.0;
+
+/// ISA lapse rate (°C per 1000 ft)
+const ISA_LAPSE_RATE_C_PER_1000FT: f64 = 1.98;
+
+/// Pressure, density altitude and ISA deviation at a station
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DensityAltitude {
+    pub icao: String,
+    /// Observation time of the source METAR (Unix ms)
+    pub observed_at: Option<u64>,
+    pub elevation_ft: f64,
+    pub temperature_c: i32,
+    pub dewpoint_c: Option<i32>,
+    pub altimeter_hpa: f64,
+    /// Pressure at the field (hPa)
+    pub station_pressure_hpa: f64,
+    pub pressure_altitude_ft: f64,
+    /// ISA temperature at the pressure altitude (°C)
+    pub isa_temperature_c: f64,
+    /// Temperature above (positive) or below ISA (°C)
+    pub isa_deviation_c: f64,
+    pub density_altitude_ft: f64,
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// Pressure altitude (ft) for a field elevation and altimeter setting
+pub fn pressure_altitude_ft(elevation_ft: f64, altimeter_hpa: f64) -> f64 {
+    elevation_ft + 145_366.45 * (1.0 - (altimeter_hpa / ISA_SEA_LEVEL_HPA).powf(0.190_284))
+}
+
+/// Standard atmosphere pressure (hPa) at a pressure altitude
+fn pressure_at_altitude_hpa(pressure_altitude_ft: f64) -> f64 {
+    ISA_SEA_LEVEL_HPA * (1.0 - pressure_altitude_ft / 145_366.45).powf(1.0 / 0.190_284)
+}
+
+/// Saturation vapor pressure (hPa) at a temperature (°C)
+fn vapor_pressure_hpa(temperature_c: f64) -> f64 {
+    6.1078 * 10f64.powf(7.5 * temperature_c / (237.3 + temperature_c))
+}
+
+/// Density altitude (ft) from station pressure, temperature and dewpoint
+pub fn density_altitude_ft(station_pressure_hpa: f64, temperature_c: f64, dewpoint_c: Option<f64>) -> f64 {
+    let temperature_k = temperature_c + 273.15;
+    // Moist air is less dense than dry air at the same temperature
+    let virtual_temperature_k = match dewpoint_c {
+        Some(dewpoint) => {
+            let ratio = vapor_pressure_hpa(dewpoint) / station_pressure_hpa;
+            temperature_k / (1.0 - ratio * (1.0 - 0.622))
+        }
+        None => temperature_k,
+    };
+    let station_pressure_inhg = station_pressure_hpa / 33.8639;
+    let virtual_temperature_r = virtual_temperature_k * 9.0 / 5.0;
+    145_442.16 * (1.0 - (17.326 * station_pressure_inhg / virtual_temperature_r).powf(0.235))
+}
+
+/// Compute from a decoded METAR. None if it lacks elevation, temperature or altimeter.
+pub fn from_metar(metar: &Metar) -> Option<DensityAltitude> {
+    let elevation_ft = metar.elevation_m? * METERS_TO_FEET;
+    let temperature_c = metar.temperature_c?;
+    let altimeter_hpa = metar.altimeter.as_ref()?.hpa;
+
+    let pressure_altitude = pressure_altitude_ft(elevation_ft, altimeter_hpa);
+    let station_pressure = pressure_at_altitude_hpa(pressure_altitude);
+    let isa_temperature = ISA_SEA_LEVEL_C - ISA_LAPSE_RATE_C_PER_1000FT * pressure_altitude / 1000.0;
+    let density_altitude = density_altitude_ft(
+        station_pressure,
+        temperature_c as f64,
+        metar.dewpoint_c.map(|d| d as f64),
+    );
+
+    Some(DensityAltitude {
+        icao: metar.icao.clone(),
+        observed_at: metar.observed_at,
+        elevation_ft: elevation_ft.round(),
+        temperature_c,
+        dewpoint_c: metar.dewpoint_c,
+        altimeter_hpa,
+        station_pressure_hpa: round1(station_pressure),
+        pressure_altitude_ft: pressure_altitude.round(),
+        isa_temperature_c: round1(isa_temperature),
+        isa_deviation_c: round1(temperature_c as f64 - isa_temperature),
+        density_altitude_ft: density_altitude.round(),
+    })
+}
+
+/// Density altitude at a station from its current METAR.
+/// None if it has no report or the report can't be used.
+pub async fn get_density_altitude(app: &AppHandle, icao: &str) -> Result<Option<DensityAltitude>> {
+    let metar = weather::get_metar(app, icao, false).await?;
+    Ok(metar.as_ref().and_then(from_metar))
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get pressure altitude, density altitude and ISA deviation for a station
+#[tauri::command]
+pub async fn get_station_density_altitude(app: AppHandle, icao: String) -> Result<Option<DensityAltitude>> {
+    get_density_altitude(&app, &icao).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::decode_metar;
+
+    #[test]
+    fn computes_density_altitude() {
+        // Denver on a hot day
+        let mut metar = decode_metar("KDEN 121753Z 18008KT 10SM FEW100 33/05 A3012");
+        metar.elevation_m = Some(1655.0);
+
+        let result = from_metar(&metar).unwrap();
+        assert_eq!(result.elevation_ft, 5430.0);
+        assert_eq!(result.pressure_altitude_ft, 5246.0);
+        assert_eq!(result.isa_temperature_c, 4.6);
+        assert_eq!(result.isa_deviation_c, 28.4);
+        assert_eq!(result.density_altitude_ft, 8563.0);
+
+        metar.elevation_m = None;
+        assert!(from_metar(&metar).is_none());
+    }
+}
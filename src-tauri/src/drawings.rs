@@ -0,0 +1,562 @@
+//! Shared drawings
+//!
+//! Ad-hoc drawings made on any display during a session: range/bearing lines,
+//! text labels and polygons, so an instructor can draw on the shared view and
+//! every display sees it. Unlike airport annotations they aren't tied to an
+//! airport; they belong to the active drawing session, which is saved in app
+//! data (`drawings/{session}.json`) after every change and reloaded on
+//! startup. Switching to another session name starts a fresh board or picks
+//! an earlier one back up.
+//!
+//! Drawings are added with `add_drawing` or `POST /api/drawings`, and every
+//! change is emitted as `drawings-changed` and relayed over
+//! `/api/drawings/ws` with the whole board.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+use crate::error::{Error, Result};
+use crate::geo;
+use crate::placements;
+
+/// Drawings folder in app data
+const DRAWINGS_DIR: &str = "drawings";
+
+/// Name of the active session, kept in the drawings folder
+const ACTIVE_SESSION_FILE: &str = "active-session.txt";
+
+/// Session used until another one is started
+pub const DEFAULT_SESSION: &str = "default";
+
+/// Most drawings on one board
+const MAX_DRAWINGS: usize = 500;
+
+/// Most polygon corners
+const MAX_POLYGON_POINTS: usize = 200;
+
+/// Longest label text
+const MAX_TEXT_LEN: usize = 200;
+
+/// Longest author name
+const MAX_AUTHOR_LEN: usize = 64;
+
+/// What a drawing is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DrawingKind {
+    /// Range/bearing line between two points
+    Line,
+    /// Text at a point
+    Label,
+    /// Closed area
+    Polygon,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DrawingPoint {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// New drawing (`POST /api/drawings` body)
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DrawingRequest {
+    pub kind: DrawingKind,
+    /// Two points for a line, one for a label, three or more for a polygon
+    pub points: Vec<DrawingPoint>,
+    /// Label text (optional caption for lines and polygons)
+    #[serde(default)]
+    pub text: Option<String>,
+    /// CSS hex color (e.g., "#ff4081")
+    #[serde(default)]
+    pub color: Option<String>,
+    /// Who drew it (e.g., "Instructor")
+    #[serde(default)]
+    pub author: Option<String>,
+}
+
+/// Range and bearing of a line
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LineMeasure {
+    pub distance_nm: f64,
+    /// Degrees true from the first point to the second
+    pub bearing: f64,
+    /// Degrees true from the second point back to the first
+    pub reciprocal: f64,
+}
+
+/// A drawing on the shared board
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Drawing {
+    /// Identifier, unique per session (e.g., "d12")
+    pub id: String,
+    pub kind: DrawingKind,
+    pub points: Vec<DrawingPoint>,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    /// Unix ms
+    pub created_at: u64,
+    /// Range and bearing (lines only)
+    #[serde(default, skip_deserializing)]
+    pub measure: Option<LineMeasure>,
+}
+
+/// Every drawing in the active session (`drawings-changed` event payload)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DrawingBoard {
+    pub session: String,
+    pub drawings: Vec<Drawing>,
+    /// Unix ms
+    pub updated_at: u64,
+}
+
+impl Default for DrawingBoard {
+    fn default() -> Self {
+        Self {
+            session: DEFAULT_SESSION.to_string(),
+            drawings: Vec::new(),
+            updated_at: 0,
+        }
+    }
+}
+
+/// A saved drawing session
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DrawingSessionInfo {
+    pub name: String,
+    pub drawings: usize,
+    pub active: bool,
+}
+
+/// Drawing state (managed by Tauri)
+pub struct DrawingState {
+    board: RwLock<DrawingBoard>,
+    /// Board updates for WebSocket relay
+    pub events: broadcast::Sender<DrawingBoard>,
+}
+
+impl DrawingState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            board: RwLock::new(DrawingBoard::default()),
+            events,
+        }
+    }
+}
+
+impl Default for DrawingState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn valid_color(color: &str) -> bool {
+    color.len() == 7 && color.starts_with('#') && color[1..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn trimmed(value: Option<String>, max_len: usize) -> Option<String> {
+    value
+        .map(|v| v.trim().chars().take(max_len).collect::<String>())
+        .filter(|v| !v.is_empty())
+}
+
+/// Range and bearing between two points
+pub fn measure(from: DrawingPoint, to: DrawingPoint) -> LineMeasure {
+    let (distance_nm, bearing) = geo::distance_and_bearing(from.latitude, from.longitude, to.latitude, to.longitude);
+    let (_, reciprocal) = geo::distance_and_bearing(to.latitude, to.longitude, from.latitude, from.longitude);
+    LineMeasure {
+        distance_nm,
+        bearing,
+        reciprocal,
+    }
+}
+
+/// Check a drawing request and turn it into a drawing
+pub fn validate(request: DrawingRequest, id: String, now: u64) -> Result<Drawing> {
+    let points = request.points;
+    let count_ok = match request.kind {
+        DrawingKind::Line => points.len() == 2,
+        DrawingKind::Label => points.len() == 1,
+        DrawingKind::Polygon => (3..=MAX_POLYGON_POINTS).contains(&points.len()),
+    };
+    if !count_ok {
+        return Err(Error::invalid_input(match request.kind {
+            DrawingKind::Line => "A line needs two points".to_string(),
+            DrawingKind::Label => "A label needs one point".to_string(),
+            DrawingKind::Polygon => format!("A polygon needs 3 to {} points", MAX_POLYGON_POINTS),
+        }));
+    }
+    if points
+        .iter()
+        .any(|p| !(-90.0..=90.0).contains(&p.latitude) || !(-180.0..=180.0).contains(&p.longitude))
+    {
+        return Err(Error::invalid_input("Invalid coordinates"));
+    }
+
+    let text = trimmed(request.text, MAX_TEXT_LEN);
+    if request.kind == DrawingKind::Label && text.is_none() {
+        return Err(Error::invalid_input("A label needs text"));
+    }
+    let color = request.color.map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+    if color.as_deref().is_some_and(|c| !valid_color(c)) {
+        return Err(Error::invalid_input("Color must be a hex color like #ff4081"));
+    }
+
+    let line_measure = (request.kind == DrawingKind::Line).then(|| measure(points[0], points[1]));
+    Ok(Drawing {
+        id,
+        kind: request.kind,
+        points,
+        text,
+        color,
+        author: trimmed(request.author, MAX_AUTHOR_LEN),
+        created_at: now,
+        measure: line_measure,
+    })
+}
+
+/// Next free drawing id on a board ("d1", "d2", ...)
+fn next_id(drawings: &[Drawing]) -> String {
+    let last = drawings
+        .iter()
+        .filter_map(|d| d.id.strip_prefix('d')?.parse::<u64>().ok())
+        .max()
+        .unwrap_or(0);
+    format!("d{}", last + 1)
+}
+
+/// Drawings folder in app data
+fn drawings_root(app: &AppHandle) -> Result<PathBuf> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    Ok(app_data.join(DRAWINGS_DIR))
+}
+
+/// Drawings of a saved session (none if it was never saved)
+fn read_session(root: &Path, session: &str) -> Result<Vec<Drawing>> {
+    let path = root.join(format!("{}.json", session));
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path).map_err(|e| Error::io(format!("Failed to read drawings: {}", e)))?;
+    let mut drawings: Vec<Drawing> =
+        serde_json::from_str(&content).map_err(|e| Error::io(format!("Failed to parse drawings: {}", e)))?;
+    for drawing in &mut drawings {
+        if drawing.kind == DrawingKind::Line && drawing.points.len() == 2 {
+            drawing.measure = Some(measure(drawing.points[0], drawing.points[1]));
+        }
+    }
+    Ok(drawings)
+}
+
+fn write_session(root: &Path, board: &DrawingBoard) -> Result<()> {
+    fs::create_dir_all(root).map_err(|e| Error::io(format!("Failed to create drawings folder: {}", e)))?;
+    let path = root.join(format!("{}.json", board.session));
+    let content =
+        serde_json::to_string(&board.drawings).map_err(|e| format!("Failed to serialize drawings: {}", e))?;
+    fs::write(&path, content).map_err(|e| Error::io(format!("Failed to write drawings: {}", e)))?;
+    fs::write(root.join(ACTIVE_SESSION_FILE), &board.session)
+        .map_err(|e| Error::io(format!("Failed to write active drawing session: {}", e)))
+}
+
+/// Save and broadcast a board
+fn publish(app: &AppHandle, board: DrawingBoard) -> Result<DrawingBoard> {
+    write_session(&drawings_root(app)?, &board)?;
+    let state = app.state::<DrawingState>();
+    *state.board.write() = board.clone();
+    let _ = app.emit("drawings-changed", &board);
+    let _ = state.events.send(board.clone());
+    Ok(board)
+}
+
+/// Drawings in the active session
+pub fn get(app: &AppHandle) -> DrawingBoard {
+    app.state::<DrawingState>().board.read().clone()
+}
+
+/// Add a drawing to the active session
+pub fn add(app: &AppHandle, request: DrawingRequest) -> Result<Drawing> {
+    let mut board = get(app);
+    if board.drawings.len() >= MAX_DRAWINGS {
+        return Err(Error::conflict(format!(
+            "At most {} drawings can be on the board; clear some first",
+            MAX_DRAWINGS
+        )));
+    }
+    let now = now_ms();
+    let drawing = validate(request, next_id(&board.drawings), now)?;
+    board.drawings.push(drawing.clone());
+    board.updated_at = now;
+    publish(app, board)?;
+    Ok(drawing)
+}
+
+/// Remove a drawing from the active session
+pub fn remove(app: &AppHandle, id: &str) -> Result<DrawingBoard> {
+    let mut board = get(app);
+    let count = board.drawings.len();
+    board.drawings.retain(|d| d.id != id);
+    if board.drawings.len() == count {
+        return Err(Error::not_found(format!("No drawing {}", id)));
+    }
+    board.updated_at = now_ms();
+    publish(app, board)
+}
+
+/// Remove every drawing from the active session
+pub fn clear(app: &AppHandle) -> Result<DrawingBoard> {
+    let mut board = get(app);
+    info!("[Drawings] Cleared {} drawing(s) from {}", board.drawings.len(), board.session);
+    board.drawings.clear();
+    board.updated_at = now_ms();
+    publish(app, board)
+}
+
+/// Make a session active, loading its saved drawings (a new name starts an empty board)
+pub fn switch_session(app: &AppHandle, session: &str) -> Result<DrawingBoard> {
+    let session = session.trim();
+    if !placements::valid_object_id(session) {
+        return Err(Error::invalid_input(format!(
+            "Invalid session name: {:?} (letters, digits, - and _ only)",
+            session
+        )));
+    }
+    let drawings = read_session(&drawings_root(app)?, session)?;
+    info!("[Drawings] Session {} ({} drawing(s))", session, drawings.len());
+    publish(
+        app,
+        DrawingBoard {
+            session: session.to_string(),
+            drawings,
+            updated_at: now_ms(),
+        },
+    )
+}
+
+/// Saved drawing sessions
+pub fn list_sessions(app: &AppHandle) -> Result<Vec<DrawingSessionInfo>> {
+    let root = drawings_root(app)?;
+    let active = get(app).session;
+    let mut sessions: Vec<DrawingSessionInfo> = fs::read_dir(&root)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let path = e.path();
+            if !path.extension().is_some_and(|x| x == "json") {
+                return None;
+            }
+            let name = path.file_stem()?.to_str()?.to_string();
+            placements::valid_object_id(&name).then_some(name)
+        })
+        .map(|name| DrawingSessionInfo {
+            drawings: read_session(&root, &name).map(|d| d.len()).unwrap_or(0),
+            active: name == active,
+            name,
+        })
+        .collect();
+    if !sessions.iter().any(|s| s.active) {
+        sessions.push(DrawingSessionInfo {
+            name: active,
+            drawings: 0,
+            active: true,
+        });
+    }
+    sessions.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(sessions)
+}
+
+/// Load the last active session
+fn load_active_session(app: &AppHandle) -> Result<DrawingBoard> {
+    let root = drawings_root(app)?;
+    let session = fs::read_to_string(root.join(ACTIVE_SESSION_FILE))
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| placements::valid_object_id(s))
+        .unwrap_or_else(|| DEFAULT_SESSION.to_string());
+    Ok(DrawingBoard {
+        drawings: read_session(&root, &session)?,
+        session,
+        updated_at: now_ms(),
+    })
+}
+
+/// Initialize drawing state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_drawings(app: &AppHandle) {
+    let state = DrawingState::new();
+    match load_active_session(app) {
+        Ok(board) => {
+            if !board.drawings.is_empty() {
+                info!("[Drawings] Loaded {} drawing(s) from {}", board.drawings.len(), board.session);
+            }
+            *state.board.write() = board;
+        }
+        Err(e) => warn!("[Drawings] Failed to load drawings: {}", e),
+    }
+    app.manage(state);
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the drawings in the active session
+#[tauri::command]
+pub fn get_drawings(app: AppHandle) -> DrawingBoard {
+    get(&app)
+}
+
+/// Draw a line, label or polygon on every display
+#[tauri::command]
+pub fn add_drawing(app: AppHandle, request: DrawingRequest) -> Result<Drawing> {
+    add(&app, request)
+}
+
+/// Remove a drawing
+#[tauri::command]
+pub fn delete_drawing(app: AppHandle, id: String) -> Result<DrawingBoard> {
+    remove(&app, &id)
+}
+
+/// Remove every drawing in the active session
+#[tauri::command]
+pub fn clear_drawings(app: AppHandle) -> Result<DrawingBoard> {
+    clear(&app)
+}
+
+/// Switch to (or start) a drawing session
+#[tauri::command]
+pub fn set_drawing_session(app: AppHandle, session: String) -> Result<DrawingBoard> {
+    switch_session(&app, &session)
+}
+
+/// List saved drawing sessions
+#[tauri::command]
+pub fn list_drawing_sessions(app: AppHandle) -> Result<Vec<DrawingSessionInfo>> {
+    list_sessions(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(latitude: f64, longitude: f64) -> DrawingPoint {
+        DrawingPoint { latitude, longitude }
+    }
+
+    fn request(kind: DrawingKind, points: Vec<DrawingPoint>, text: Option<&str>) -> DrawingRequest {
+        DrawingRequest {
+            kind,
+            points,
+            text: text.map(str::to_string),
+            color: None,
+            author: None,
+        }
+    }
+
+    #[test]
+    fn measures_lines() {
+        let line = validate(
+            request(DrawingKind::Line, vec![point(42.0, -71.0), point(43.0, -71.0)], None),
+            "d1".to_string(),
+            5,
+        )
+        .unwrap();
+        let measure = line.measure.unwrap();
+        assert!((measure.distance_nm - 60.0).abs() < 0.1);
+        assert!(measure.bearing.abs() < 0.1 || (measure.bearing - 360.0).abs() < 0.1);
+        assert!((measure.reciprocal - 180.0).abs() < 0.1);
+        assert_eq!(line.created_at, 5);
+
+        let label = validate(
+            request(DrawingKind::Label, vec![point(42.0, -71.0)], Some(" Hold short ")),
+            "d2".to_string(),
+            0,
+        )
+        .unwrap();
+        assert_eq!(label.text.as_deref(), Some("Hold short"));
+        assert_eq!(label.measure, None);
+    }
+
+    #[test]
+    fn rejects_invalid_drawings() {
+        let p = point(42.0, -71.0);
+        let check = |r: DrawingRequest| validate(r, "d1".to_string(), 0);
+        assert!(check(request(DrawingKind::Line, vec![p], None)).is_err());
+        assert!(check(request(DrawingKind::Label, vec![p], None)).is_err());
+        assert!(check(request(DrawingKind::Label, vec![p], Some("  "))).is_err());
+        assert!(check(request(DrawingKind::Polygon, vec![p, p], None)).is_err());
+        assert!(check(request(DrawingKind::Polygon, vec![p, p, point(91.0, 0.0)], None)).is_err());
+        assert!(check(request(DrawingKind::Polygon, vec![p, p, p], None)).is_ok());
+
+        let mut colored = request(DrawingKind::Label, vec![p], Some("x"));
+        colored.color = Some("red".to_string());
+        assert!(check(colored.clone()).is_err());
+        colored.color = Some("#FF4081".to_string());
+        assert!(check(colored).is_ok());
+    }
+
+    #[test]
+    fn assigns_increasing_ids() {
+        let p = point(42.0, -71.0);
+        let mut drawings = Vec::new();
+        assert_eq!(next_id(&drawings), "d1");
+        for id in ["d1", "d7", "other"] {
+            drawings.push(validate(request(DrawingKind::Label, vec![p], Some("x")), id.to_string(), 0).unwrap());
+        }
+        assert_eq!(next_id(&drawings), "d8");
+    }
+
+    #[test]
+    fn saves_and_reloads_sessions() {
+        let root = std::env::temp_dir().join(format!("towercab-drawings-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let line = validate(
+            request(DrawingKind::Line, vec![point(42.0, -71.0), point(42.1, -71.0)], None),
+            "d1".to_string(),
+            0,
+        )
+        .unwrap();
+        let board = DrawingBoard {
+            session: "tuesday-training".to_string(),
+            drawings: vec![line.clone()],
+            updated_at: 0,
+        };
+        write_session(&root, &board).unwrap();
+
+        assert_eq!(read_session(&root, "tuesday-training").unwrap(), vec![line]);
+        assert!(read_session(&root, "other").unwrap().is_empty());
+        assert_eq!(
+            fs::read_to_string(root.join(ACTIVE_SESSION_FILE)).unwrap(),
+            "tuesday-training"
+        );
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,406 @@
+//! Import from the old Electron version
+//!
+//! The Electron app kept everything in Chromium localStorage (a LevelDB
+//! database under its own app data folder, e.g. `%APPDATA%\towercab-3d`) and
+//! custom tower positions in its mods folder. This finds that folder, reads the
+//! persisted stores out of the LevelDB files and converts them to global
+//! settings:
+//!
+//! - `settings-store`: Cesium Ion token, FSLTL paths, RealTraffic license key
+//! - `viewport-store` and the older `camera-store`: camera bookmarks, per-airport
+//!   default views and datablock positions, the last airport
+//! - `mods/tower-positions(.json)`: copied to this app's tower positions
+//!
+//! Nothing already set here is overwritten. The rest of `settings-store`
+//! (graphics, camera, UI) is per-browser and returned to the frontend, which
+//! applies it to its local settings. The frontend runs the import once on first
+//! launch and offers to re-run it from the import dialog.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+use crate::error::Error;
+use crate::{
+    find_mods_root, GlobalAirportViewportConfig, GlobalCameraBookmark, GlobalSettings, GlobalViewModeDefaults,
+};
+
+/// Folder names the Electron app used under the OS app data folder
+const ELECTRON_DIR_NAMES: [&str; 3] = ["towercab-3d", "TowerCab 3D", "TowerCab3D"];
+
+/// localStorage keys of the persisted stores
+const STORE_KEYS: [&str; 3] = ["settings-store", "viewport-store", "camera-store"];
+
+/// What was imported
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElectronImportReport {
+    /// Electron data folder, if one was found
+    pub source: Option<String>,
+    /// One line per imported item (e.g. "12 camera bookmarks at 3 airports")
+    pub migrated: Vec<String>,
+    /// Items found but not imported, with the reason
+    pub skipped: Vec<String>,
+    /// Per-browser settings (`settings-store` state) for the frontend to apply
+    pub local_settings: Option<Value>,
+}
+
+/// Find the Electron app data folder (one with a localStorage database)
+fn find_electron_dir(app: &AppHandle) -> Option<PathBuf> {
+    let config_dir = app.path().config_dir().ok()?;
+    ELECTRON_DIR_NAMES
+        .iter()
+        .map(|name| config_dir.join(name))
+        .find(|dir| dir.join("Local Storage").join("leveldb").is_dir())
+}
+
+/// Read a varint-free JSON object starting at `start` (Latin-1 or UTF-16LE, as
+/// Chromium stores localStorage values), ending at its matching brace
+fn read_json_object(bytes: &[u8], start: usize) -> Option<Value> {
+    let utf16 = bytes.get(start + 1) == Some(&0);
+    let step = if utf16 { 2 } else { 1 };
+    let mut text = String::new();
+    let (mut depth, mut in_string, mut escaped) = (0usize, false, false);
+    let mut i = start;
+    while i < bytes.len() {
+        let c = if utf16 {
+            let unit = u16::from_le_bytes([bytes[i], *bytes.get(i + 1)?]);
+            char::from_u32(unit as u32).unwrap_or('\u{FFFD}')
+        } else {
+            bytes[i] as char
+        };
+        text.push(c);
+        i += step;
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return serde_json::from_str(&text).ok();
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Find the newest value of each store in LevelDB file contents (oldest file
+/// first). Values in Snappy-compressed table blocks can't be read this way;
+/// recent writes are in the uncompressed log.
+fn extract_stores(files: &[Vec<u8>]) -> HashMap<&'static str, Value> {
+    let mut stores = HashMap::new();
+    for bytes in files {
+        for key in STORE_KEYS {
+            // Keys are "_<origin>\0\x01<key>"
+            let needle = [b"\x00\x01".as_slice(), key.as_bytes()].concat();
+            let mut from = 0;
+            while let Some(pos) = find(&bytes[from..], &needle) {
+                let key_end = from + pos + needle.len();
+                from = key_end;
+                // The value (after its length, or the table entry trailer) starts with a
+                // storage format byte, then the JSON
+                let window = &bytes[key_end..bytes.len().min(key_end + 24)];
+                let Some(offset) = window.iter().position(|&b| b == b'{') else {
+                    continue;
+                };
+                if let Some(value) = read_json_object(bytes, key_end + offset) {
+                    stores.insert(key, value);
+                }
+            }
+        }
+    }
+    stores
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
+
+/// Read the LevelDB log and table files, oldest first
+fn read_leveldb_files(dir: &Path) -> Vec<Vec<u8>> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(u64, PathBuf)> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == "log" || ext == "ldb"))
+        .filter_map(|p| {
+            let number = p.file_stem()?.to_str()?.parse().ok()?;
+            Some((number, p))
+        })
+        .collect();
+    files.sort();
+    files.into_iter().filter_map(|(_, path)| fs::read(path).ok()).collect()
+}
+
+fn string_at<'a>(value: &'a Value, path: &[&str]) -> Option<&'a str> {
+    path.iter()
+        .try_fold(value, |v, key| v.get(key))?
+        .as_str()
+        .filter(|s| !s.trim().is_empty())
+}
+
+/// Merge the Electron stores into global settings, filling only what is unset
+fn convert(stores: &HashMap<&str, Value>, settings: &mut GlobalSettings, report: &mut ElectronImportReport) {
+    if let Some(state) = stores.get("settings-store").and_then(|s| s.get("state")) {
+        // Old flat format, or the grouped one
+        let token = string_at(state, &["cesiumIonToken"]).or_else(|| string_at(state, &["cesium", "cesiumIonToken"]));
+        if let Some(token) = token {
+            if settings.cesium_ion_token.is_empty() {
+                settings.cesium_ion_token = token.to_string();
+                report.migrated.push("Cesium Ion token".to_string());
+            } else {
+                report.skipped.push("Cesium Ion token (already set)".to_string());
+            }
+        }
+
+        if let Some(source) = string_at(state, &["fsltl", "sourcePath"]) {
+            if settings.fsltl.source_path.is_none() && settings.fsltl.output_path.is_none() {
+                settings.fsltl.source_path = Some(source.to_string());
+                settings.fsltl.output_path = string_at(state, &["fsltl", "outputPath"]).map(str::to_string);
+                if let Some(scale) = string_at(state, &["fsltl", "textureScale"]) {
+                    settings.fsltl.texture_scale = scale.to_string();
+                }
+                report.migrated.push("FSLTL paths".to_string());
+            } else {
+                report.skipped.push("FSLTL paths (already set)".to_string());
+            }
+        }
+
+        if let Some(key) = string_at(state, &["realtraffic", "licenseKey"]) {
+            if settings.realtraffic.license_key.is_empty() {
+                settings.realtraffic.license_key = key.to_string();
+                report.migrated.push("RealTraffic license key".to_string());
+            }
+        }
+        report.local_settings = Some(state.clone());
+    }
+
+    // Bookmarks: the viewport store, then the older camera store for slots it lacks
+    let viewport_configs = stores
+        .get("viewport-store")
+        .and_then(|s| s.pointer("/state/airportViewportConfigs"))
+        .and_then(Value::as_object);
+    let camera_configs = stores
+        .get("camera-store")
+        .and_then(|s| s.pointer("/state/airportSettings"))
+        .and_then(Value::as_object);
+    let (mut bookmarks, mut airports, mut defaults) = (0, std::collections::HashSet::new(), 0);
+    for configs in [viewport_configs, camera_configs].into_iter().flatten() {
+        for (icao, config) in configs {
+            let icao = icao.to_uppercase();
+            let target = settings.viewports.airport_configs.entry(icao.clone()).or_default();
+            for (slot, bookmark) in config.get("bookmarks").and_then(Value::as_object).into_iter().flatten() {
+                if target.bookmarks.contains_key(slot) {
+                    continue;
+                }
+                if let Ok(bookmark) = serde_json::from_value::<GlobalCameraBookmark>(bookmark.clone()) {
+                    target.bookmarks.insert(slot.clone(), bookmark);
+                    bookmarks += 1;
+                    airports.insert(icao.clone());
+                }
+            }
+            defaults += merge_defaults(target, config);
+        }
+    }
+    // Don't leave empty configs behind for airports without anything to import
+    settings.viewports.airport_configs.retain(|_, config| {
+        !config.bookmarks.is_empty()
+            || config.default_3d.is_some()
+            || config.default_2d.is_some()
+            || config.datablock_position.is_some()
+    });
+    if bookmarks > 0 {
+        report
+            .migrated
+            .push(format!("{} camera bookmarks at {} airports", bookmarks, airports.len()));
+    }
+    if defaults > 0 {
+        report.migrated.push(format!("{} default views", defaults));
+    }
+
+    let last_airport = stores
+        .get("viewport-store")
+        .and_then(|s| string_at(s, &["state", "currentAirportIcao"]));
+    if let Some(icao) = last_airport {
+        let icao = icao.to_uppercase();
+        if settings.viewports.last_airport_icao.is_none() {
+            settings.viewports.last_airport_icao = Some(icao.clone());
+        }
+        if !settings.airports.recent_airports.contains(&icao) {
+            settings.airports.recent_airports.push(icao);
+        }
+    }
+}
+
+/// Copy default views and the datablock position the config lacks; returns the number of views copied
+fn merge_defaults(target: &mut GlobalAirportViewportConfig, config: &Value) -> usize {
+    let read = |key: &str| {
+        config
+            .get(key)
+            .and_then(|v| serde_json::from_value::<GlobalViewModeDefaults>(v.clone()).ok())
+    };
+    let mut copied = 0;
+    if target.default_3d.is_none() {
+        target.default_3d = read("default3d");
+        copied += target.default_3d.is_some() as usize;
+    }
+    if target.default_2d.is_none() {
+        target.default_2d = read("default2d");
+        copied += target.default_2d.is_some() as usize;
+    }
+    if target.datablock_position.is_none() {
+        target.datablock_position = config
+            .get("datablockPosition")
+            .and_then(Value::as_u64)
+            .and_then(|p| u8::try_from(p).ok());
+    }
+    copied
+}
+
+/// Copy the Electron app's tower positions for airports without one here
+fn import_tower_positions(app: &AppHandle, electron_dir: &Path, report: &mut ElectronImportReport) {
+    let source_mods = electron_dir.join("mods");
+    let mut positions: Vec<(String, Value)> = Vec::new();
+    if let Ok(content) = fs::read_to_string(source_mods.join("tower-positions.json")) {
+        if let Ok(Value::Object(map)) = serde_json::from_str::<Value>(&content) {
+            positions.extend(map);
+        }
+    }
+    if let Ok(entries) = fs::read_dir(source_mods.join("tower-positions")) {
+        for path in entries.flatten().map(|e| e.path()) {
+            let icao = path.file_stem().and_then(|s| s.to_str()).map(str::to_string);
+            let value = fs::read_to_string(&path)
+                .ok()
+                .and_then(|c| serde_json::from_str(&c).ok());
+            if let (Some(icao), Some(value)) = (icao, value) {
+                positions.push((icao, value));
+            }
+        }
+    }
+    if positions.is_empty() {
+        return;
+    }
+
+    let target_dir = find_mods_root(app).join("tower-positions");
+    if let Err(e) = fs::create_dir_all(&target_dir) {
+        report.skipped.push(format!("Tower positions ({})", e));
+        return;
+    }
+    let mut copied = 0;
+    for (icao, value) in positions {
+        let icao = icao.to_uppercase();
+        if icao.is_empty() || !icao.chars().all(|c| c.is_ascii_alphanumeric()) {
+            continue;
+        }
+        let path = target_dir.join(format!("{}.json", icao));
+        if path.exists() {
+            report
+                .skipped
+                .push(format!("Tower position for {} (already set)", icao));
+            continue;
+        }
+        let written = serde_json::to_string_pretty(&value)
+            .map_err(|e| e.to_string())
+            .and_then(|json| fs::write(&path, json).map_err(|e| e.to_string()));
+        match written {
+            Ok(()) => copied += 1,
+            Err(e) => report.skipped.push(format!("Tower position for {} ({})", icao, e)),
+        }
+    }
+    if copied > 0 {
+        report.migrated.push(format!("{} tower positions", copied));
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Import settings, bookmarks and tower positions from the Electron version
+#[tauri::command]
+pub fn import_electron_settings(app: AppHandle) -> Result<ElectronImportReport, Error> {
+    let mut report = ElectronImportReport::default();
+    let Some(electron_dir) = find_electron_dir(&app) else {
+        info!("[Electron import] No Electron installation found");
+        return Ok(report);
+    };
+    report.source = Some(electron_dir.to_string_lossy().to_string());
+
+    let files = read_leveldb_files(&electron_dir.join("Local Storage").join("leveldb"));
+    let stores = extract_stores(&files);
+    if stores.is_empty() {
+        warn!("[Electron import] No readable settings in {:?}", electron_dir);
+    }
+
+    let mut settings = crate::read_global_settings(app.clone())?;
+    convert(&stores, &mut settings, &mut report);
+    if !report.migrated.is_empty() {
+        crate::write_global_settings(app.clone(), settings)?;
+    }
+    import_tower_positions(&app, &electron_dir, &mut report);
+
+    info!(
+        "[Electron import] Imported from {:?}: {}",
+        electron_dir,
+        if report.migrated.is_empty() {
+            "nothing".to_string()
+        } else {
+            report.migrated.join(", ")
+        }
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_and_converts_stores() {
+        // A LevelDB log record: key, value length, Latin-1 format byte, value
+        let settings = r#"{"state":{"cesiumIonToken":"tok{en}","fsltl":{"sourcePath":"C:\\FSLTL"}},"version":0}"#;
+        let viewport = r#"{"state":{"currentAirportIcao":"kbos","airportViewportConfigs":{"KBOS":{"bookmarks":{"1":{"name":"Tower","viewMode":"3d","heading":90,"pitch":-5,"fov":60,"positionOffsetX":0,"positionOffsetY":0,"positionOffsetZ":0,"topdownAltitude":2000}},"datablockPosition":9}}},"version":0}"#;
+        let mut log = Vec::new();
+        for (key, value) in [("settings-store", settings), ("viewport-store", viewport)] {
+            log.extend_from_slice(b"\x01\x1a_file://\x00\x01");
+            log.extend_from_slice(key.as_bytes());
+            log.extend_from_slice(&[0xB1, 0x01, 0x01]);
+            log.extend_from_slice(value.as_bytes());
+        }
+
+        let stores = extract_stores(&[log]);
+        assert_eq!(stores.len(), 2);
+
+        let mut global = GlobalSettings::default();
+        let mut report = ElectronImportReport::default();
+        convert(&stores, &mut global, &mut report);
+        assert_eq!(global.cesium_ion_token, "tok{en}");
+        assert_eq!(global.fsltl.source_path.as_deref(), Some("C:\\FSLTL"));
+        let kbos = &global.viewports.airport_configs["KBOS"];
+        assert_eq!(kbos.bookmarks["1"].name.as_deref(), Some("Tower"));
+        assert_eq!(kbos.datablock_position, Some(9));
+        assert_eq!(global.viewports.last_airport_icao.as_deref(), Some("KBOS"));
+        assert_eq!(
+            report.migrated,
+            ["Cesium Ion token", "FSLTL paths", "1 camera bookmarks at 1 airports"]
+        );
+    }
+}
@@ -0,0 +1,252 @@
+//! Sun and moon positions for an airport
+//!
+//! Low-precision formulas (Astronomical Almanac for the sun, a truncated lunar
+//! theory for the moon) are accurate to a fraction of a degree, which is plenty
+//! for lighting the scene and placing the sun and moon in the sky. Every client
+//! gets the same answer for the same airport and time.
+
+use std::f64::consts::PI;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::error::{Error, Result};
+use crate::clock;
+use crate::weather;
+
+const DEG: f64 = PI / 180.0;
+
+/// Mean Earth-Sun distance (km)
+const SUN_DISTANCE_KM: f64 = 149_598_000.0;
+
+/// Sun or moon position in the local sky
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkyPosition {
+    /// Degrees true, clockwise from north
+    pub azimuth: f64,
+    /// Degrees above the horizon (negative below)
+    pub elevation: f64,
+}
+
+/// Moon position and phase
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MoonInfo {
+    pub azimuth: f64,
+    pub elevation: f64,
+    /// Illuminated fraction (0 = new, 1 = full)
+    pub illumination: f64,
+    /// Position in the lunar cycle (0 = new, 0.25 = first quarter, 0.5 = full, 0.75 = last quarter)
+    pub phase: f64,
+    /// e.g., "waxing crescent"
+    pub phase_name: String,
+    pub distance_km: f64,
+}
+
+/// Sun and moon at a position and time
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ephemeris {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Time the positions are for (Unix ms)
+    pub time: u64,
+    pub sun: SkyPosition,
+    pub moon: MoonInfo,
+    /// "day", "civil", "nautical", "astronomical" or "night"
+    pub twilight: String,
+}
+
+/// Right ascension and declination (radians)
+struct Equatorial {
+    right_ascension: f64,
+    declination: f64,
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+fn round3(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+/// Days since J2000.0 for a Unix time in ms
+fn days_since_j2000(time_ms: u64) -> f64 {
+    time_ms as f64 / 86_400_000.0 + 2_440_587.5 - 2_451_545.0
+}
+
+/// Obliquity of the ecliptic (radians)
+fn obliquity(days: f64) -> f64 {
+    (23.439 - 0.000_000_4 * days) * DEG
+}
+
+/// Ecliptic longitude/latitude (radians) to equatorial coordinates
+fn ecliptic_to_equatorial(longitude: f64, latitude: f64, obliquity: f64) -> Equatorial {
+    Equatorial {
+        right_ascension: (longitude.sin() * obliquity.cos() - latitude.tan() * obliquity.sin()).atan2(longitude.cos()),
+        declination: (latitude.sin() * obliquity.cos() + latitude.cos() * obliquity.sin() * longitude.sin()).asin(),
+    }
+}
+
+fn sun_coordinates(days: f64) -> Equatorial {
+    let mean_anomaly = (357.529 + 0.985_600_28 * days) * DEG;
+    let mean_longitude = 280.459 + 0.985_647_36 * days;
+    let longitude = (mean_longitude + 1.915 * mean_anomaly.sin() + 0.020 * (2.0 * mean_anomaly).sin()) * DEG;
+    ecliptic_to_equatorial(longitude, 0.0, obliquity(days))
+}
+
+/// Moon coordinates and distance (km)
+fn moon_coordinates(days: f64) -> (Equatorial, f64) {
+    let mean_longitude = (218.316 + 13.176_396 * days) * DEG;
+    let mean_anomaly = (134.963 + 13.064_993 * days) * DEG;
+    let argument_of_latitude = (93.272 + 13.229_350 * days) * DEG;
+
+    let longitude = mean_longitude + 6.289 * DEG * mean_anomaly.sin();
+    let latitude = 5.128 * DEG * argument_of_latitude.sin();
+    let distance = 385_001.0 - 20_905.0 * mean_anomaly.cos();
+    (ecliptic_to_equatorial(longitude, latitude, obliquity(days)), distance)
+}
+
+/// Local azimuth/elevation (degrees) for equatorial coordinates
+fn sky_position(coordinates: &Equatorial, days: f64, latitude: f64, longitude: f64) -> SkyPosition {
+    let sidereal_time = (280.460_618_37 + 360.985_647_366_29 * days + longitude) * DEG;
+    let hour_angle = sidereal_time - coordinates.right_ascension;
+    let phi = latitude * DEG;
+    let dec = coordinates.declination;
+
+    let elevation = (phi.sin() * dec.sin() + phi.cos() * dec.cos() * hour_angle.cos()).asin();
+    // Measured from south, then turned to true
+    let azimuth = hour_angle
+        .sin()
+        .atan2(hour_angle.cos() * phi.sin() - dec.tan() * phi.cos())
+        + PI;
+
+    SkyPosition {
+        azimuth: round2((azimuth / DEG).rem_euclid(360.0)),
+        elevation: round2(elevation / DEG),
+    }
+}
+
+fn phase_name(phase: f64) -> &'static str {
+    match phase {
+        p if !(0.03..=0.97).contains(&p) => "new",
+        p if p < 0.22 => "waxing crescent",
+        p if p < 0.28 => "first quarter",
+        p if p < 0.47 => "waxing gibbous",
+        p if p < 0.53 => "full",
+        p if p < 0.72 => "waning gibbous",
+        p if p < 0.78 => "last quarter",
+        _ => "waning crescent",
+    }
+}
+
+fn twilight(sun_elevation: f64) -> &'static str {
+    match sun_elevation {
+        e if e >= -0.833 => "day",
+        e if e >= -6.0 => "civil",
+        e if e >= -12.0 => "nautical",
+        e if e >= -18.0 => "astronomical",
+        _ => "night",
+    }
+}
+
+/// Sun and moon positions at a position and time (Unix ms)
+pub fn compute(latitude: f64, longitude: f64, time_ms: u64) -> Ephemeris {
+    let days = days_since_j2000(time_ms);
+    let sun = sun_coordinates(days);
+    let (moon, moon_distance) = moon_coordinates(days);
+
+    // Illuminated fraction from the Sun-Moon elongation
+    let elongation = (sun.declination.sin() * moon.declination.sin()
+        + sun.declination.cos() * moon.declination.cos() * (sun.right_ascension - moon.right_ascension).cos())
+    .acos();
+    let inclination = (SUN_DISTANCE_KM * elongation.sin()).atan2(moon_distance - SUN_DISTANCE_KM * elongation.cos());
+    let angle = (sun.declination.cos() * (sun.right_ascension - moon.right_ascension).sin()).atan2(
+        sun.declination.sin() * moon.declination.cos()
+            - sun.declination.cos() * moon.declination.sin() * (sun.right_ascension - moon.right_ascension).cos(),
+    );
+    let illumination = (1.0 + inclination.cos()) / 2.0;
+    let phase = 0.5 + 0.5 * inclination * if angle < 0.0 { -1.0 } else { 1.0 } / PI;
+
+    let sun_position = sky_position(&sun, days, latitude, longitude);
+    let moon_position = sky_position(&moon, days, latitude, longitude);
+
+    Ephemeris {
+        latitude,
+        longitude,
+        time: time_ms,
+        twilight: twilight(sun_position.elevation).to_string(),
+        sun: sun_position,
+        moon: MoonInfo {
+            azimuth: moon_position.azimuth,
+            elevation: moon_position.elevation,
+            illumination: round3(illumination),
+            phase: round3(phase),
+            phase_name: phase_name(phase).to_string(),
+            distance_km: moon_distance.round(),
+        },
+    }
+}
+
+/// Sun and moon for a position, at a time (Unix ms) or the simulated clock's current time
+pub fn get_ephemeris(app: &AppHandle, latitude: f64, longitude: f64, at: Option<u64>) -> Result<Ephemeris> {
+    if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+        return Err(Error::InvalidInput(format!("Invalid position: {}, {}", latitude, longitude)));
+    }
+    Ok(compute(latitude, longitude, at.unwrap_or_else(|| clock::now(app))))
+}
+
+/// Sun and moon at an airport (positioned by its METAR station)
+pub async fn get_airport_ephemeris(app: &AppHandle, icao: &str, at: Option<u64>) -> Result<Ephemeris> {
+    let metar = weather::get_metar(app, icao, false)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("No METAR station position for {}", icao)))?;
+    let (Some(latitude), Some(longitude)) = (metar.latitude, metar.longitude) else {
+        return Err(Error::NotFound(format!("No METAR station position for {}", icao)));
+    };
+    get_ephemeris(app, latitude, longitude, at)
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get sun and moon azimuth/elevation and moon phase for a position,
+/// at a time (Unix ms) or the simulated clock's current time
+#[tauri::command]
+pub fn get_celestial_ephemeris(
+    app: AppHandle,
+    latitude: f64,
+    longitude: f64,
+    at: Option<u64>,
+) -> Result<Ephemeris> {
+    get_ephemeris(&app, latitude, longitude, at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_sun_and_moon() {
+        // June solstice, local noon on the Tropic of Cancer: sun nearly overhead
+        let solstice = compute(23.44, 0.0, 1_718_884_800_000);
+        assert!(solstice.sun.elevation > 88.0);
+        assert_eq!(solstice.twilight, "day");
+
+        // Same time on the other side of the world: night
+        let night = compute(-23.44, 180.0, 1_718_884_800_000);
+        assert!(night.sun.elevation < -60.0);
+        assert_eq!(night.twilight, "night");
+
+        // Full moon 2024-01-25 17:54Z, new moon 2024-01-11 11:57Z
+        let full = compute(37.62, -122.38, 1_706_205_240_000);
+        assert!(full.moon.illumination > 0.99);
+        assert_eq!(full.moon.phase_name, "full");
+        let new = compute(37.62, -122.38, 1_704_974_220_000);
+        assert!(new.moon.illumination < 0.01);
+        assert_eq!(new.moon.phase_name, "new");
+    }
+}
@@ -0,0 +1,184 @@
+//! Typed errors for commands and the HTTP API
+//!
+//! Commands and API handlers return [`Error`], which pairs a message with a
+//! machine-readable code. A failed Tauri command rejects with
+//! `{ "code": "notFound", "message": "No drawing 3" }`, and the HTTP API
+//! answers with the same body and the matching status code (400 for
+//! `invalidInput`, 404 for `notFound`, 409 for `conflict`, ...), so the
+//! frontend can branch on the kind of failure instead of matching English
+//! messages (see `utils/apiError.ts`).
+//!
+//! Modules still returning `Result<_, String>` convert with `?` (a plain
+//! string becomes `internal`) until they are moved over.
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+/// Command and API error
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+    /// The request is malformed or fails validation
+    #[error("{0}")]
+    InvalidInput(String),
+    /// The thing asked for doesn't exist
+    #[error("{0}")]
+    NotFound(String),
+    /// The request doesn't fit the current state (e.g., no replay loaded, board full)
+    #[error("{0}")]
+    Conflict(String),
+    /// The request has no valid credentials
+    #[error("{0}")]
+    Unauthorized(String),
+    /// The caller isn't allowed to do this
+    #[error("{0}")]
+    Forbidden(String),
+    /// An external service (VATSIM, weather, tile servers) failed or timed out
+    #[error("{0}")]
+    Upstream(String),
+    /// Reading or writing local files failed
+    #[error("{0}")]
+    Io(String),
+    #[error("{0}")]
+    Internal(String),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl Error {
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::InvalidInput(message.into())
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::NotFound(message.into())
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::Conflict(message.into())
+    }
+
+    pub fn io(message: impl Into<String>) -> Self {
+        Self::Io(message.into())
+    }
+
+    /// Machine-readable code (the `code` field sent to the frontend)
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::InvalidInput(_) => "invalidInput",
+            Self::NotFound(_) => "notFound",
+            Self::Conflict(_) => "conflict",
+            Self::Unauthorized(_) => "unauthorized",
+            Self::Forbidden(_) => "forbidden",
+            Self::Upstream(_) => "upstream",
+            Self::Io(_) => "io",
+            Self::Internal(_) => "internal",
+        }
+    }
+
+    /// HTTP status for API responses
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::InvalidInput(_) => StatusCode::BAD_REQUEST,
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
+            Self::Upstream(_) => StatusCode::BAD_GATEWAY,
+            Self::Io(_) | Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::InvalidInput(m)
+            | Self::NotFound(m)
+            | Self::Conflict(m)
+            | Self::Unauthorized(m)
+            | Self::Forbidden(m)
+            | Self::Upstream(m)
+            | Self::Io(m)
+            | Self::Internal(m) => m,
+        }
+    }
+}
+
+impl Serialize for Error {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut body = serializer.serialize_struct("Error", 2)?;
+        body.serialize_field("code", self.code())?;
+        body.serialize_field("message", self.message())?;
+        body.end()
+    }
+}
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        (self.status(), Json(self)).into_response()
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Self::Internal(message)
+    }
+}
+
+impl From<&str> for Error {
+    fn from(message: &str) -> Self {
+        Self::Internal(message.to_string())
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        match e.kind() {
+            std::io::ErrorKind::NotFound => Self::NotFound(e.to_string()),
+            std::io::ErrorKind::PermissionDenied => Self::Forbidden(e.to_string()),
+            _ => Self::Io(e.to_string()),
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Self::Upstream(e.to_string())
+    }
+}
+
+/// For callers that still return `Result<_, String>`
+impl From<Error> for String {
+    fn from(e: Error) -> Self {
+        e.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_code_and_message() {
+        let json = serde_json::to_value(Error::not_found("No drawing 3")).unwrap();
+        assert_eq!(json, serde_json::json!({ "code": "notFound", "message": "No drawing 3" }));
+    }
+
+    #[test]
+    fn maps_kinds_to_status_codes() {
+        assert_eq!(Error::invalid_input("x").status(), StatusCode::BAD_REQUEST);
+        assert_eq!(Error::not_found("x").status(), StatusCode::NOT_FOUND);
+        assert_eq!(Error::conflict("x").status(), StatusCode::CONFLICT);
+        assert_eq!(Error::Unauthorized("x".into()).status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(Error::Upstream("x".into()).status(), StatusCode::BAD_GATEWAY);
+        assert_eq!(Error::from("x".to_string()).status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn converts_legacy_errors() {
+        let missing = std::io::Error::new(std::io::ErrorKind::NotFound, "gone");
+        assert_eq!(Error::from(missing).code(), "notFound");
+        assert_eq!(String::from(Error::invalid_input("Bad callsign")), "Bad callsign");
+    }
+}
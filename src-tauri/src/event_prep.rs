@@ -0,0 +1,690 @@
+//! Event preparation (cache priming)
+//!
+//! Before a busy event, `prepare_event` (or `POST /api/event-prep`) warms
+//! every cache the session will hit for an airport, so nothing is downloading
+//! during the rush:
+//!
+//! 1. Weather: the current METAR and TAF, and the last day of METAR history
+//! 2. Charts: the airport's d-TPP chart list and every chart PDF
+//! 3. Overlays: airspace boundaries, the OSM ground layout and OSM buildings
+//! 4. Models: airline/type combinations of the flights filed to or from the
+//!    airport (prefiles and connected pilots) are matched to models, and
+//!    FSLTL liveries that exist in the FSLTL source but weren't converted yet
+//!    are converted with the bundled converter
+//! 5. Tiles: terrain and imagery around the airport (see tile_cache.rs)
+//!
+//! Steps run one after another; a failed step doesn't stop the others.
+//! Progress is emitted as `event-prep-progress` and served at
+//! `GET /api/event-prep`. The event date picks the chart cycle to check:
+//! charts are only published for the current cycle, so an event in a later
+//! cycle is flagged for preparing again once that cycle is effective.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+
+use chrono::{NaiveDate, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+use crate::error::{Error, Result};
+use crate::matching::{self, MatchQuery, ModelMatcherState};
+use crate::tile_cache::{self, PrefetchRequest};
+use crate::traffic::{self, VatsimData};
+use crate::{airport_db, airspace, charts, osm_buildings, osm_layout, taf, weather};
+
+/// How often long-running steps (conversion, tile download) are checked
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// METAR history warmed before the event
+const HISTORY_HOURS: u64 = 24;
+
+const HOUR_MS: u64 = 60 * 60 * 1000;
+
+/// Progress file of conversions started here (in app data)
+const CONVERSION_PROGRESS_FILE: &str = "event_prep_fsltl_progress.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StepState {
+    Pending,
+    Running,
+    Done,
+    /// Nothing to do (e.g., no charts published for the airport)
+    Skipped,
+    Failed,
+}
+
+/// One preparation step
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrepStep {
+    /// "weather", "charts", "overlays", "models" or "tiles"
+    pub id: &'static str,
+    pub label: &'static str,
+    pub state: StepState,
+    /// Items done of `total` (charts, tiles, models, ...)
+    pub done: usize,
+    pub total: usize,
+    /// What was done, or why the step was skipped or failed
+    pub detail: Option<String>,
+}
+
+/// Status of the current or last preparation (`event-prep-progress` payload)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventPrepStatus {
+    pub icao: String,
+    /// Event date (YYYY-MM-DD)
+    pub date: String,
+    pub running: bool,
+    pub cancelled: bool,
+    /// Unix ms
+    pub started_at: u64,
+    pub finished_at: Option<u64>,
+    pub steps: Vec<PrepStep>,
+}
+
+/// Preparation request (`POST /api/event-prep` body)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventPrepRequest {
+    pub icao: String,
+    /// Event date (YYYY-MM-DD, UTC)
+    pub date: String,
+}
+
+/// Preparation state (managed by Tauri)
+pub struct EventPrepState {
+    status: RwLock<Option<EventPrepStatus>>,
+    cancel: AtomicBool,
+}
+
+impl EventPrepState {
+    pub fn new() -> Self {
+        Self {
+            status: RwLock::new(None),
+            cancel: AtomicBool::new(false),
+        }
+    }
+}
+
+impl Default for EventPrepState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// How a step ended
+struct Outcome {
+    state: StepState,
+    detail: String,
+}
+
+impl Outcome {
+    fn done(detail: impl Into<String>) -> Self {
+        Self {
+            state: StepState::Done,
+            detail: detail.into(),
+        }
+    }
+
+    fn skipped(detail: impl Into<String>) -> Self {
+        Self {
+            state: StepState::Skipped,
+            detail: detail.into(),
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn steps() -> Vec<PrepStep> {
+    [
+        ("weather", "METAR history and TAF"),
+        ("charts", "Charts"),
+        ("overlays", "Airspace and ground layout"),
+        ("models", "Aircraft models"),
+        ("tiles", "Terrain and imagery"),
+    ]
+    .into_iter()
+    .map(|(id, label)| PrepStep {
+        id,
+        label,
+        state: StepState::Pending,
+        done: 0,
+        total: 0,
+        detail: None,
+    })
+    .collect()
+}
+
+/// Check a request: a known ICAO code and a date that isn't over
+pub fn validate(request: &EventPrepRequest, today: NaiveDate) -> Result<(String, NaiveDate)> {
+    let icao = weather::normalize_icao(&request.icao).map_err(Error::InvalidInput)?;
+    let date = NaiveDate::parse_from_str(request.date.trim(), "%Y-%m-%d")
+        .map_err(|_| Error::invalid_input(format!("Invalid event date: {} (expected YYYY-MM-DD)", request.date)))?;
+    if date < today {
+        return Err(Error::invalid_input(format!("The event date {} has passed", date)));
+    }
+    Ok((icao, date))
+}
+
+/// Apply a change to the status and broadcast it
+fn update(app: &AppHandle, change: impl FnOnce(&mut EventPrepStatus)) {
+    let state = app.state::<EventPrepState>();
+    let status = {
+        let mut status = state.status.write();
+        let Some(status) = status.as_mut() else {
+            return;
+        };
+        change(status);
+        status.clone()
+    };
+    let _ = app.emit("event-prep-progress", &status);
+}
+
+fn update_step(app: &AppHandle, id: &str, change: impl FnOnce(&mut PrepStep)) {
+    update(app, |status| {
+        if let Some(step) = status.steps.iter_mut().find(|s| s.id == id) {
+            change(step);
+        }
+    });
+}
+
+fn cancelled(app: &AppHandle) -> bool {
+    app.state::<EventPrepState>().cancel.load(Ordering::Relaxed)
+}
+
+// =============================================================================
+// STEPS
+// =============================================================================
+
+async fn prime_weather(app: &AppHandle, icao: &str) -> std::result::Result<Outcome, String> {
+    update_step(app, "weather", |s| s.total = 3);
+    let metar = weather::get_metar(app, icao, false).await?;
+    update_step(app, "weather", |s| s.done = 1);
+    let taf = taf::get_taf(app, icao, false).await.unwrap_or_else(|e| {
+        warn!("[EventPrep] TAF for {}: {}", icao, e);
+        None
+    });
+    update_step(app, "weather", |s| s.done = 2);
+
+    // Ranges that ended over an hour ago are kept in the history cache
+    let to = (now_ms() / HOUR_MS).saturating_sub(1) * HOUR_MS;
+    let history = weather::get_metar_history(app, icao, to - HISTORY_HOURS * HOUR_MS, to).await?;
+    update_step(app, "weather", |s| s.done = 3);
+
+    if metar.is_none() && history.is_empty() {
+        return Ok(Outcome::skipped(format!("{} doesn't report METARs", icao)));
+    }
+    Ok(Outcome::done(format!(
+        "{} METARs from the last {} hours, {}",
+        history.len(),
+        HISTORY_HOURS,
+        if taf.is_some() { "TAF loaded" } else { "no TAF" }
+    )))
+}
+
+async fn prime_charts(app: &AppHandle, icao: &str, date: NaiveDate) -> std::result::Result<Outcome, String> {
+    let Some(airport_charts) = charts::get_charts(app, icao).await? else {
+        return Ok(Outcome::skipped("No d-TPP charts for this airport (US airports only)"));
+    };
+    update_step(app, "charts", |s| s.total = airport_charts.charts.len());
+
+    let mut failed = 0;
+    for (i, chart) in airport_charts.charts.iter().enumerate() {
+        if cancelled(app) {
+            return Err("Cancelled".to_string());
+        }
+        if let Err(e) = charts::get_chart_file(app, icao, &chart.pdf_name).await {
+            warn!("[EventPrep] Chart {}: {}", chart.pdf_name, e);
+            failed += 1;
+        }
+        update_step(app, "charts", |s| s.done = i + 1);
+    }
+
+    let mut detail = format!(
+        "{} charts for cycle {}",
+        airport_charts.charts.len() - failed,
+        airport_charts.cycle
+    );
+    if failed > 0 {
+        detail.push_str(&format!(", {} failed to download", failed));
+    }
+    let event_cycle = charts::airac_cycle(date);
+    if event_cycle != airport_charts.cycle {
+        detail.push_str(&format!(
+            "; the event falls in cycle {}, so prepare again once it is effective",
+            event_cycle
+        ));
+    }
+    if failed == airport_charts.charts.len() && failed > 0 {
+        return Err(detail);
+    }
+    Ok(Outcome::done(detail))
+}
+
+async fn prime_overlays(app: &AppHandle, icao: &str) -> std::result::Result<Outcome, String> {
+    update_step(app, "overlays", |s| s.total = 3);
+    let mut loaded = Vec::new();
+    let mut failed = Vec::new();
+
+    match airspace::get_airspace_overlay(app, icao, false).await {
+        Ok(_) => loaded.push("airspace"),
+        Err(e) => failed.push(format!("airspace ({})", e)),
+    }
+    update_step(app, "overlays", |s| s.done = 1);
+    match osm_layout::get_layout(app, icao, false).await {
+        Ok(_) => loaded.push("ground layout"),
+        Err(e) => failed.push(format!("ground layout ({})", e)),
+    }
+    update_step(app, "overlays", |s| s.done = 2);
+    match osm_buildings::get_buildings(app, icao, false).await {
+        Ok(_) => loaded.push("buildings"),
+        Err(e) => failed.push(format!("buildings ({})", e)),
+    }
+    update_step(app, "overlays", |s| s.done = 3);
+
+    match (loaded.is_empty(), failed.is_empty()) {
+        (true, _) => Err(format!("Failed: {}", failed.join(", "))),
+        (false, true) => Ok(Outcome::done(format!("Loaded {}", loaded.join(", ")))),
+        (false, false) => Ok(Outcome::done(format!(
+            "Loaded {}; failed: {}",
+            loaded.join(", "),
+            failed.join(", ")
+        ))),
+    }
+}
+
+/// Callsign and aircraft type of every flight filed to or from an airport,
+/// one per airline/type combination (GA flights by type)
+pub fn expected_flights(data: &VatsimData, icao: &str) -> Vec<(String, String)> {
+    let plans = data
+        .pilots
+        .iter()
+        .map(|p| (&p.callsign, p.flight_plan.as_ref()))
+        .chain(data.prefiles.iter().map(|p| (&p.callsign, p.flight_plan.as_ref())));
+
+    let mut seen = BTreeSet::new();
+    let mut flights = Vec::new();
+    for (callsign, plan) in plans {
+        let Some(plan) = plan else {
+            continue;
+        };
+        let filed = [&plan.departure, &plan.arrival]
+            .iter()
+            .any(|a| a.trim().eq_ignore_ascii_case(icao));
+        let type_code = matching::extract_base_aircraft_type(&plan.aircraft_short);
+        if !filed || type_code.is_empty() {
+            continue;
+        }
+        let airline = (!matching::is_ga_callsign(callsign))
+            .then(|| matching::extract_airline_code(callsign))
+            .flatten();
+        if seen.insert((airline, type_code.clone())) {
+            flights.push((callsign.clone(), type_code));
+        }
+    }
+    flights
+}
+
+/// FSLTL source folder with the livery for a type and airline, if there is one
+fn fsltl_livery(airplanes: &Path, type_code: &str, airline: &str) -> Option<String> {
+    let name = format!("FSLTL_{}_{}", type_code, airline);
+    airplanes.join(&name).is_dir().then_some(name)
+}
+
+/// Run the converter on FSLTL source folders and wait for it to finish
+/// (returns how many were converted; progress counts on from `ready`)
+async fn convert_liveries(app: &AppHandle, liveries: Vec<String>, ready: usize) -> std::result::Result<usize, String> {
+    let settings = crate::read_global_settings(app.clone())?.fsltl;
+    let Some(source) = settings.source_path else {
+        return Err("The FSLTL source folder isn't set".to_string());
+    };
+    let output = match settings.output_path {
+        Some(output) => output,
+        None => crate::get_fsltl_default_output_path(app.clone())?.0,
+    };
+    if crate::fsltl_conversion_running() {
+        return Err("An FSLTL conversion is already running".to_string());
+    }
+    let progress_file = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?
+        .join(CONVERSION_PROGRESS_FILE);
+    let _ = std::fs::remove_file(&progress_file);
+    let progress_file = progress_file.to_string_lossy().to_string();
+
+    let total = liveries.len();
+    crate::start_fsltl_conversion(
+        app.clone(),
+        source,
+        output,
+        settings.texture_scale,
+        liveries,
+        progress_file.clone(),
+    )?;
+    info!("[EventPrep] Converting {} FSLTL liveries", total);
+
+    let mut completed = 0;
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        if let Ok(progress) = crate::read_conversion_progress(progress_file.clone()) {
+            completed = (progress.completed as usize).min(total);
+            update_step(app, "models", |s| s.done = ready + completed);
+        }
+        if cancelled(app) {
+            let _ = crate::cancel_fsltl_conversion();
+            return Err("Cancelled".to_string());
+        }
+        if !crate::fsltl_conversion_running() {
+            break;
+        }
+    }
+    app.state::<ModelMatcherState>().invalidate();
+    Ok(completed)
+}
+
+async fn prime_models(app: &AppHandle, icao: &str) -> std::result::Result<Outcome, String> {
+    let data = traffic::vatsim_feed(app).await?;
+    let flights = expected_flights(&data, icao);
+    if flights.is_empty() {
+        return Ok(Outcome::skipped(format!("No flights filed to or from {} yet", icao)));
+    }
+
+    let app_handle = app.clone();
+    let results = tauri::async_runtime::spawn_blocking(move || {
+        let matcher = matching::get_matcher(&app_handle);
+        flights
+            .into_iter()
+            .map(|(callsign, type_code)| {
+                matcher.match_model(&MatchQuery {
+                    type_code: Some(type_code),
+                    callsign: Some(callsign),
+                    airline: None,
+                })
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| format!("Model matching failed: {}", e))?;
+
+    // Airline flights need the airline's livery; GA flights only a model for the type
+    let has_model = |r: &matching::MatchResult| match (&r.model, &r.airline_code) {
+        (Some(model), Some(airline)) => model.airline_code.as_deref() == Some(airline.as_str()),
+        (model, _) => model.is_some(),
+    };
+    let ready = results.iter().filter(|r| has_model(r)).count();
+    let total = results.len();
+    update_step(app, "models", |s| {
+        s.total = total;
+        s.done = ready;
+    });
+
+    let settings = crate::read_global_settings(app.clone())?.fsltl;
+    let liveries: Vec<String> = match settings.source_path.filter(|_| settings.enable_fsltl_models) {
+        Some(source) => {
+            let airplanes = PathBuf::from(source).join("SimObjects").join("Airplanes");
+            results
+                .iter()
+                .filter(|r| !has_model(r))
+                .filter_map(|r| fsltl_livery(&airplanes, r.type_code.as_deref()?, r.airline_code.as_deref()?))
+                .collect()
+        }
+        None => Vec::new(),
+    };
+
+    let summary = format!("{} of {} airline/type combinations have a model", ready, total);
+    if liveries.is_empty() {
+        return Ok(Outcome::done(summary));
+    }
+    if crate::get_converter_path(app.clone()).is_err() {
+        return Ok(Outcome::done(format!(
+            "{}; convert {} in the FSLTL panel (converter not available here)",
+            summary,
+            liveries.join(", ")
+        )));
+    }
+    let requested = liveries.len();
+    let converted = convert_liveries(app, liveries, ready).await?;
+    Ok(Outcome::done(format!(
+        "{} of {} airline/type combinations have a model; converted {} of {} FSLTL liveries",
+        ready + converted,
+        total,
+        converted,
+        requested
+    )))
+}
+
+async fn prime_tiles(app: &AppHandle, icao: &str) -> std::result::Result<Outcome, String> {
+    tile_cache::prefetch(
+        app,
+        PrefetchRequest {
+            airports: vec![icao.to_string()],
+            ..Default::default()
+        },
+    )?;
+
+    let progress = loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        if cancelled(app) {
+            tile_cache::cancel_prefetch(app);
+        }
+        let Some(progress) = tile_cache::get_status(app).prefetch else {
+            return Err("Tile download status unavailable".to_string());
+        };
+        update_step(app, "tiles", |s| {
+            s.total = progress.total_tiles;
+            s.done = progress.done_tiles;
+        });
+        if !progress.running {
+            break progress;
+        }
+    };
+
+    if cancelled(app) {
+        return Err("Cancelled".to_string());
+    }
+    if progress.total_tiles > 0 && progress.failed_tiles == progress.total_tiles {
+        return Err(progress.error.unwrap_or_else(|| "Every tile failed to download".to_string()));
+    }
+    let mut detail = format!(
+        "{} tiles downloaded ({:.1} MB), {} already cached",
+        progress.downloaded_tiles,
+        progress.downloaded_bytes as f64 / 1_048_576.0,
+        progress.done_tiles - progress.downloaded_tiles - progress.failed_tiles
+    );
+    if progress.failed_tiles > 0 {
+        detail.push_str(&format!(", {} failed", progress.failed_tiles));
+    }
+    Ok(Outcome::done(detail))
+}
+
+async fn run_step(app: &AppHandle, id: &'static str, icao: &str, date: NaiveDate) {
+    if cancelled(app) {
+        update_step(app, id, |s| {
+            s.state = StepState::Skipped;
+            s.detail = Some("Cancelled".to_string());
+        });
+        return;
+    }
+    update_step(app, id, |s| s.state = StepState::Running);
+    let result = match id {
+        "weather" => prime_weather(app, icao).await,
+        "charts" => prime_charts(app, icao, date).await,
+        "overlays" => prime_overlays(app, icao).await,
+        "models" => prime_models(app, icao).await,
+        _ => prime_tiles(app, icao).await,
+    };
+    let outcome = result.unwrap_or_else(|e| {
+        warn!("[EventPrep] {} for {}: {}", id, icao, e);
+        Outcome {
+            state: StepState::Failed,
+            detail: e,
+        }
+    });
+    update_step(app, id, |s| {
+        s.state = outcome.state;
+        s.detail = Some(outcome.detail);
+    });
+}
+
+/// Start preparing for an event at an airport in the background
+pub async fn prepare(app: &AppHandle, request: EventPrepRequest) -> Result<EventPrepStatus> {
+    let (icao, date) = validate(&request, Utc::now().date_naive())?;
+    let airport = airport_db::get_airport(app, icao.clone()).await.map_err(Error::io)?;
+    if airport.is_none() {
+        return Err(Error::not_found(format!("Unknown airport {}", icao)));
+    }
+
+    let state = app.state::<EventPrepState>();
+    let status = {
+        let mut current = state.status.write();
+        if current.as_ref().is_some_and(|s| s.running) {
+            return Err(Error::conflict(format!(
+                "Already preparing {}",
+                current.as_ref().map_or("", |s| s.icao.as_str())
+            )));
+        }
+        let status = EventPrepStatus {
+            icao: icao.clone(),
+            date: date.to_string(),
+            running: true,
+            cancelled: false,
+            started_at: now_ms(),
+            finished_at: None,
+            steps: steps(),
+        };
+        *current = Some(status.clone());
+        status
+    };
+    state.cancel.store(false, Ordering::Relaxed);
+    let _ = app.emit("event-prep-progress", &status);
+    info!("[EventPrep] Preparing {} for {}", icao, date);
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        for id in ["weather", "charts", "overlays", "models", "tiles"] {
+            run_step(&app, id, &icao, date).await;
+        }
+        let cancelled = cancelled(&app);
+        update(&app, |status| {
+            status.running = false;
+            status.cancelled = cancelled;
+            status.finished_at = Some(now_ms());
+        });
+        info!("[EventPrep] Finished preparing {}{}", icao, if cancelled { " (cancelled)" } else { "" });
+    });
+    Ok(status)
+}
+
+/// Status of the current or last preparation
+pub fn get_status(app: &AppHandle) -> Option<EventPrepStatus> {
+    app.state::<EventPrepState>().status.read().clone()
+}
+
+/// Stop a running preparation after the current item
+pub fn cancel(app: &AppHandle) -> Result<()> {
+    let state = app.state::<EventPrepState>();
+    if !state.status.read().as_ref().is_some_and(|s| s.running) {
+        return Err(Error::conflict("No event preparation is running"));
+    }
+    state.cancel.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Initialize event preparation state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_event_prep(app: &AppHandle) {
+    app.manage(EventPrepState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Warm every cache for an event at an airport (progress as `event-prep-progress`)
+#[tauri::command]
+pub async fn prepare_event(app: AppHandle, request: EventPrepRequest) -> Result<EventPrepStatus> {
+    prepare(&app, request).await
+}
+
+/// Get the status of the current or last event preparation
+#[tauri::command]
+pub fn get_event_prep_status(app: AppHandle) -> Option<EventPrepStatus> {
+    get_status(&app)
+}
+
+/// Stop a running event preparation
+#[tauri::command]
+pub fn cancel_event_prep(app: AppHandle) -> Result<()> {
+    cancel(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traffic::{VatsimFlightPlan, VatsimPrefile};
+
+    fn prefile(callsign: &str, departure: &str, arrival: &str, aircraft: &str) -> VatsimPrefile {
+        VatsimPrefile {
+            callsign: callsign.to_string(),
+            flight_plan: Some(VatsimFlightPlan {
+                departure: departure.to_string(),
+                arrival: arrival.to_string(),
+                aircraft_short: aircraft.to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn validates_requests() {
+        let today = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        let request = |icao: &str, date: &str| EventPrepRequest {
+            icao: icao.to_string(),
+            date: date.to_string(),
+        };
+        assert_eq!(
+            validate(&request(" kbos", "2026-10-24"), today).unwrap(),
+            ("KBOS".to_string(), NaiveDate::from_ymd_opt(2026, 10, 24).unwrap())
+        );
+        assert!(validate(&request("KBOS", "2026-10-17"), today).is_ok());
+        assert_eq!(validate(&request("KBOS", "2026-10-16"), today).unwrap_err().code(), "invalidInput");
+        assert!(validate(&request("KBOS", "24/10/2026"), today).is_err());
+        assert!(validate(&request("K*OS", "2026-10-24"), today).is_err());
+    }
+
+    #[test]
+    fn lists_one_flight_per_airline_and_type() {
+        let data = VatsimData {
+            pilots: Vec::new(),
+            controllers: Vec::new(),
+            atis: Vec::new(),
+            prefiles: vec![
+                prefile("AAL123", "KBOS", "KJFK", "B738"),
+                prefile("AAL456", "KMIA", "kbos", "H/B738/L"),
+                prefile("AAL789", "KBOS", "KORD", "A321"),
+                prefile("DAL1", "KBOS", "KATL", "B738"),
+                prefile("N123AB", "KBOS", "KBED", "C172"),
+                prefile("UAL1", "KSFO", "KORD", "B738"),
+            ],
+        };
+        let flights = expected_flights(&data, "KBOS");
+        let flights: Vec<(&str, &str)> = flights.iter().map(|(c, t)| (c.as_str(), t.as_str())).collect();
+        assert_eq!(
+            flights,
+            vec![("AAL123", "B738"), ("AAL789", "A321"), ("DAL1", "B738"), ("N123AB", "C172")]
+        );
+    }
+}
@@ -0,0 +1,676 @@
+//! VATSIM event schedule
+//!
+//! Fetches upcoming events from the VATSIM events API, and optionally from a
+//! facility's own calendar (an iCal feed, or JSON shaped like the VATSIM
+//! API), every 15 minutes. Events affecting the active airport (listed as an
+//! event airport or route endpoint, or named in a calendar event's title or
+//! location) are served at `GET /api/events`.
+//!
+//! Shortly before such an event starts (`eventSchedule.remindMinutes`, 30 by
+//! default) the host sends an `event-reminder` event and, when enabled, a
+//! desktop notification: "Boston FNO at KBOS starts in 30 minutes — start
+//! recording?". Accepting (`record_event`, `POST /api/events/:id/record`)
+//! records the traffic until the event ends and saves it to the shared
+//! replay library.
+
+use std::collections::HashSet;
+use std::time::{Duration, Instant, SystemTime};
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+use crate::error::{Error, Result};
+use crate::http_client;
+use crate::notifications;
+use crate::replay;
+use crate::replay_library::{self, ShareOptions};
+use crate::scripts;
+use crate::supervisor::{self, Restart};
+use crate::{debug_stats, weather};
+
+/// VATSIM events API (upcoming and ongoing events)
+const VATSIM_EVENTS_URL: &str = "https://my.vatsim.net/api/v2/events/latest";
+
+/// How often the reminders are checked
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long fetched events are used before fetching them again
+const REFRESH_INTERVAL: Duration = Duration::from_secs(900);
+
+/// Length of calendar events without an end time (ms)
+const DEFAULT_DURATION_MS: u64 = 3 * 3600 * 1000;
+
+/// Event schedule settings in the global settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EventScheduleSettings {
+    /// Fetch events and remind before those at the active airport (default: true)
+    pub enabled: bool,
+    /// Facility calendar (iCal, or JSON like the VATSIM events API) fetched along with VATSIM events
+    pub calendar_url: Option<String>,
+    /// Minutes before an event starts to remind (default: 30)
+    pub remind_minutes: u32,
+    /// Show reminders as desktop notifications (default: true); `event-reminder` events are always sent
+    pub desktop: bool,
+}
+
+impl Default for EventScheduleSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            calendar_url: None,
+            remind_minutes: 30,
+            desktop: true,
+        }
+    }
+}
+
+/// An upcoming or ongoing event
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledEvent {
+    /// "vatsim-{id}" or "calendar-{uid}"
+    pub id: String,
+    /// "vatsim" or "calendar"
+    pub source: String,
+    pub name: String,
+    /// Unix ms
+    pub start: u64,
+    /// Unix ms
+    pub end: u64,
+    /// Airports the event affects (ICAO)
+    pub airports: Vec<String>,
+    pub description: Option<String>,
+    pub link: Option<String>,
+    /// Banner image URL (VATSIM events)
+    pub banner: Option<String>,
+}
+
+/// Reminder for an event at the active airport (`event-reminder` event)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventReminder {
+    pub icao: String,
+    pub event: ScheduledEvent,
+    /// Minutes until the event starts
+    pub minutes: u64,
+    /// Traffic is already being recorded (so there is nothing to offer)
+    pub recording: bool,
+    pub message: String,
+}
+
+/// Events affecting an airport (`GET /api/events`)
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventScheduleStatus {
+    pub enabled: bool,
+    pub icao: Option<String>,
+    /// Ongoing and upcoming events, soonest first
+    pub events: Vec<ScheduledEvent>,
+    /// Last successful fetch (Unix ms)
+    pub updated_at: Option<u64>,
+    /// Last fetch error
+    pub error: Option<String>,
+    /// Event being recorded (id)
+    pub recording: Option<String>,
+}
+
+/// Fetched events
+#[derive(Default)]
+struct EventCache {
+    fetched: Option<Instant>,
+    /// Calendar URL the calendar events came from
+    calendar_url: Option<String>,
+    vatsim: Vec<ScheduledEvent>,
+    calendar: Vec<ScheduledEvent>,
+    updated_at: Option<u64>,
+    error: Option<String>,
+}
+
+/// Event schedule state (managed by Tauri)
+pub struct EventScheduleState {
+    cache: RwLock<EventCache>,
+    /// Events already reminded of (ids)
+    reminded: Mutex<HashSet<String>>,
+    /// Event whose traffic is being recorded
+    recording: Mutex<Option<ScheduledEvent>>,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn unix_ms(time: NaiveDateTime) -> Option<u64> {
+    u64::try_from(time.and_utc().timestamp_millis()).ok()
+}
+
+fn upper_airports(airports: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut list: Vec<String> = Vec::new();
+    for airport in airports.into_iter().map(|a| a.trim().to_uppercase()) {
+        if !airport.is_empty() && !list.contains(&airport) {
+            list.push(airport);
+        }
+    }
+    list
+}
+
+// =============================================================================
+// VATSIM EVENTS API
+// =============================================================================
+
+#[derive(Deserialize)]
+struct EventsResponse {
+    #[serde(default)]
+    data: Vec<ApiEvent>,
+}
+
+#[derive(Deserialize)]
+struct ApiEvent {
+    id: serde_json::Value,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    link: Option<String>,
+    #[serde(default)]
+    banner: Option<String>,
+    start_time: String,
+    end_time: String,
+    #[serde(default)]
+    short_description: Option<String>,
+    #[serde(default)]
+    airports: Vec<ApiAirport>,
+    #[serde(default)]
+    routes: Vec<ApiRoute>,
+}
+
+#[derive(Deserialize)]
+struct ApiAirport {
+    icao: String,
+}
+
+#[derive(Deserialize)]
+struct ApiRoute {
+    #[serde(default)]
+    departure: String,
+    #[serde(default)]
+    arrival: String,
+}
+
+fn parse_api_time(time: &str) -> Option<u64> {
+    let time = DateTime::parse_from_rfc3339(time.trim()).ok()?;
+    u64::try_from(time.timestamp_millis()).ok()
+}
+
+/// Events in the VATSIM events API format (events with unreadable times are dropped)
+fn parse_api(text: &str, source: &str) -> std::result::Result<Vec<ScheduledEvent>, String> {
+    let response: EventsResponse =
+        serde_json::from_str(text).map_err(|e| format!("Invalid {} events: {}", source, e))?;
+    Ok(response
+        .data
+        .into_iter()
+        .filter_map(|event| {
+            let id = match &event.id {
+                serde_json::Value::String(id) => id.clone(),
+                id => id.to_string(),
+            };
+            let airports = event
+                .airports
+                .into_iter()
+                .map(|a| a.icao)
+                .chain(event.routes.into_iter().flat_map(|r| [r.departure, r.arrival]));
+            Some(ScheduledEvent {
+                id: format!("{}-{}", source, id),
+                source: source.to_string(),
+                name: event.name.trim().to_string(),
+                start: parse_api_time(&event.start_time)?,
+                end: parse_api_time(&event.end_time)?,
+                airports: upper_airports(airports),
+                description: event.short_description.filter(|d| !d.trim().is_empty()),
+                link: event.link.filter(|l| !l.trim().is_empty()),
+                banner: event.banner.filter(|b| !b.trim().is_empty()),
+            })
+        })
+        .collect())
+}
+
+// =============================================================================
+// ICAL CALENDARS
+// =============================================================================
+
+/// iCal date-time: UTC ("20261023T230000Z"), floating or with a TZID (taken as
+/// host local time, there is no time zone database) or a date ("20261023", UTC)
+fn parse_ical_time(value: &str) -> Option<u64> {
+    let value = value.trim();
+    if let Some(utc) = value.strip_suffix('Z') {
+        return unix_ms(NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?);
+    }
+    if let Ok(time) = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S") {
+        return u64::try_from(Local.from_local_datetime(&time).earliest()?.timestamp_millis()).ok();
+    }
+    unix_ms(NaiveDate::parse_from_str(value, "%Y%m%d").ok()?.and_hms_opt(0, 0, 0)?)
+}
+
+fn unescape_ical(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => result.push('\n'),
+            Some(other) => result.push(other),
+            None => {}
+        }
+    }
+    result.trim().to_string()
+}
+
+/// Words that look like ICAO codes ("Boston FNO (KBOS)" -> KBOS)
+fn icao_words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| {
+            w.len() == 4
+                && w.starts_with(|c: char| c.is_ascii_uppercase())
+                && w.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+        })
+        .map(str::to_string)
+}
+
+/// Events of an iCal calendar (VEVENTs with a start time)
+fn parse_ical(text: &str) -> Vec<ScheduledEvent> {
+    // Unfold continuation lines
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        if line.starts_with([' ', '\t']) {
+            if let Some(last) = lines.last_mut() {
+                last.push_str(&line[1..]);
+                continue;
+            }
+        }
+        lines.push(line.to_string());
+    }
+
+    let mut events = Vec::new();
+    let mut properties: Option<Vec<(String, String)>> = None;
+    for line in lines {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let name = key.split(';').next().unwrap_or_default().trim().to_uppercase();
+        match (name.as_str(), value.trim()) {
+            ("BEGIN", "VEVENT") => properties = Some(Vec::new()),
+            ("END", "VEVENT") => {
+                if let Some(event) = properties.take().and_then(|p| ical_event(&p)) {
+                    events.push(event);
+                }
+            }
+            _ => {
+                if let Some(properties) = properties.as_mut() {
+                    properties.push((name, value.to_string()));
+                }
+            }
+        }
+    }
+    events
+}
+
+fn ical_event(properties: &[(String, String)]) -> Option<ScheduledEvent> {
+    let get = |name: &str| properties.iter().find(|(n, _)| n == name).map(|(_, v)| unescape_ical(v));
+    let name = get("SUMMARY").unwrap_or_default();
+    let start = parse_ical_time(&get("DTSTART")?)?;
+    let end = get("DTEND")
+        .and_then(|end| parse_ical_time(&end))
+        .filter(|end| *end > start)
+        .unwrap_or(start + DEFAULT_DURATION_MS);
+    let location = get("LOCATION").unwrap_or_default();
+    let airports = upper_airports(icao_words(&name).chain(icao_words(&location)).collect::<Vec<_>>());
+    Some(ScheduledEvent {
+        id: format!("calendar-{}", get("UID").unwrap_or_else(|| format!("{}-{}", start, name))),
+        source: "calendar".to_string(),
+        name,
+        start,
+        end,
+        airports,
+        description: get("DESCRIPTION").filter(|d| !d.is_empty()),
+        link: get("URL").filter(|u| !u.is_empty()),
+        banner: None,
+    })
+}
+
+// =============================================================================
+// FETCHING
+// =============================================================================
+
+async fn fetch_text(url: &str) -> std::result::Result<String, String> {
+    let response = http_client::client()
+        .get(url)
+        .timeout(Duration::from_secs(15))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
+    if !response.status().is_success() {
+        return Err(format!("{} returned HTTP {}", url, response.status()));
+    }
+    response.text().await.map_err(|e| format!("Failed to read {}: {}", url, e))
+}
+
+async fn fetch_calendar(url: &str) -> std::result::Result<Vec<ScheduledEvent>, String> {
+    let text = fetch_text(url).await?;
+    if text.trim_start().starts_with('{') {
+        parse_api(&text, "calendar")
+    } else {
+        Ok(parse_ical(&text))
+    }
+}
+
+/// Fetch the events again when out of date or the calendar URL changed.
+/// A source that fails keeps its last events.
+async fn refresh(app: &AppHandle, settings: &EventScheduleSettings) {
+    let calendar_url = settings
+        .calendar_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|u| !u.is_empty())
+        .map(str::to_string);
+    {
+        let cache = app.state::<EventScheduleState>().cache.read();
+        let fresh = cache.fetched.is_some_and(|f| f.elapsed() < REFRESH_INTERVAL);
+        if fresh && cache.calendar_url == calendar_url {
+            return;
+        }
+    }
+
+    let vatsim = match fetch_text(VATSIM_EVENTS_URL).await {
+        Ok(text) => parse_api(&text, "vatsim"),
+        Err(e) => Err(e),
+    };
+    let calendar = match calendar_url.as_deref() {
+        Some(url) => Some(fetch_calendar(url).await),
+        None => None,
+    };
+
+    let state = app.state::<EventScheduleState>();
+    let mut cache = state.cache.write();
+    let mut errors = Vec::new();
+    match vatsim {
+        Ok(events) => cache.vatsim = events,
+        Err(e) => errors.push(e),
+    }
+    match calendar {
+        Some(Ok(events)) => cache.calendar = events,
+        Some(Err(e)) => errors.push(e),
+        None => cache.calendar.clear(),
+    }
+    for error in &errors {
+        warn!("[Events] {}", error);
+    }
+    if errors.is_empty() {
+        cache.updated_at = Some(now_ms());
+    }
+    cache.error = (!errors.is_empty()).then(|| errors.join("; "));
+    cache.fetched = Some(Instant::now());
+    cache.calendar_url = calendar_url;
+}
+
+/// Ongoing and upcoming events affecting an airport, soonest first
+fn affecting(events: &[ScheduledEvent], icao: &str, now: u64) -> Vec<ScheduledEvent> {
+    let mut list: Vec<ScheduledEvent> = events
+        .iter()
+        .filter(|e| e.end > now && e.airports.iter().any(|a| a == icao))
+        .cloned()
+        .collect();
+    list.sort_by(|a, b| a.start.cmp(&b.start).then_with(|| a.name.cmp(&b.name)));
+    list
+}
+
+fn cached_events(app: &AppHandle) -> Vec<ScheduledEvent> {
+    let cache = app.state::<EventScheduleState>().cache.read();
+    cache.vatsim.iter().chain(&cache.calendar).cloned().collect()
+}
+
+// =============================================================================
+// REMINDERS AND RECORDING
+// =============================================================================
+
+/// Events starting within the reminder lead time
+fn due(events: &[ScheduledEvent], now: u64, remind_minutes: u32) -> impl Iterator<Item = &ScheduledEvent> {
+    let lead = remind_minutes as u64 * 60_000;
+    events.iter().filter(move |e| e.start > now && e.start - now <= lead)
+}
+
+fn remind(app: &AppHandle, settings: &EventScheduleSettings, icao: &str, event: &ScheduledEvent, now: u64) {
+    let minutes = (event.start - now).div_ceil(60_000);
+    let recording = replay::is_recording(app);
+    let mut message = format!(
+        "{} at {} starts in {} minute{}",
+        event.name,
+        icao,
+        minutes,
+        if minutes == 1 { "" } else { "s" }
+    );
+    if !recording {
+        message.push_str(" — start recording?");
+    }
+    info!("[Events] {}", message);
+
+    let reminder = EventReminder {
+        icao: icao.to_string(),
+        event: event.clone(),
+        minutes,
+        recording,
+        message,
+    };
+    let _ = app.emit("event-reminder", &reminder);
+    scripts::alert(app, "event", &reminder);
+    if settings.desktop {
+        notifications::show(app, &event.name, &reminder.message);
+    }
+}
+
+/// Record the traffic until an event ends
+pub fn record(app: &AppHandle, id: &str) -> Result<ScheduledEvent> {
+    let event = cached_events(app)
+        .into_iter()
+        .find(|e| e.id == id)
+        .ok_or_else(|| Error::not_found(format!("Unknown event: {}", id)))?;
+    if event.end <= now_ms() {
+        return Err(Error::conflict(format!("\"{}\" is over", event.name)));
+    }
+    if replay::is_recording(app) {
+        return Err(Error::conflict("Traffic is already being recorded"));
+    }
+    replay::start_recording(app);
+    info!("[Events] Recording traffic for \"{}\"", event.name);
+    *app.state::<EventScheduleState>().recording.lock() = Some(event.clone());
+    Ok(event)
+}
+
+/// Save the recording of an event that ended to the replay library
+async fn finish_recording(app: &AppHandle, event: ScheduledEvent) {
+    let (airport, snapshots) = replay::stop_recording(app);
+    if snapshots.is_empty() {
+        info!("[Events] No traffic recorded for \"{}\"", event.name);
+        return;
+    }
+    let date = DateTime::from_timestamp_millis(event.start as i64)
+        .map(|d| d.format(" %Y-%m-%d").to_string())
+        .unwrap_or_default();
+    let options = ShareOptions {
+        title: Some(format!("{}{}", event.name, date)),
+        description: Some(event.description.clone().unwrap_or_else(|| format!("Recorded during \"{}\"", event.name))),
+    };
+    let file = replay::recording_file(airport, snapshots);
+    let app = app.clone();
+    let saved = tokio::task::spawn_blocking(move || replay_library::publish_file(&app, &file, &options)).await;
+    match saved {
+        Ok(Ok(shared)) => info!("[Events] Saved the recording as \"{}\"", shared.manifest.title),
+        Ok(Err(e)) => warn!("[Events] Failed to save the recording: {}", e),
+        Err(e) => warn!("[Events] Failed to save the recording: {}", e),
+    }
+}
+
+/// Refresh the events, finish recordings of events that ended and remind of
+/// events about to start at the active airport
+async fn check(app: &AppHandle) -> std::result::Result<(), String> {
+    let now = now_ms();
+    let ended = {
+        let state = app.state::<EventScheduleState>();
+        let mut recording = state.recording.lock();
+        if recording.as_ref().is_some_and(|e| e.end <= now) {
+            recording.take()
+        } else {
+            None
+        }
+    };
+    if let Some(event) = ended {
+        finish_recording(app, event).await;
+    }
+
+    let settings = crate::read_global_settings(app.clone())?.event_schedule;
+    if !settings.enabled {
+        return Ok(());
+    }
+    refresh(app, &settings).await;
+
+    let events = cached_events(app);
+    if let Some(icao) = weather::active_airport(app) {
+        let upcoming = affecting(&events, &icao, now);
+        for event in due(&upcoming, now, settings.remind_minutes) {
+            let first = app.state::<EventScheduleState>().reminded.lock().insert(event.id.clone());
+            if first {
+                remind(app, &settings, &icao, event, now);
+            }
+        }
+    }
+    // Forget events that are over
+    app.state::<EventScheduleState>()
+        .reminded
+        .lock()
+        .retain(|id| events.iter().any(|e| &e.id == id && e.end > now));
+
+    match app.state::<EventScheduleState>().cache.read().error.clone() {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// Events affecting an airport (default: the active airport)
+pub fn get_status(app: &AppHandle, icao: Option<&str>) -> Result<EventScheduleStatus> {
+    let icao = match icao {
+        Some(icao) => Some(weather::normalize_icao(icao).map_err(Error::invalid_input)?),
+        None => weather::active_airport(app),
+    };
+    let enabled = crate::read_global_settings(app.clone())
+        .map(|s| s.event_schedule.enabled)
+        .unwrap_or_default();
+    let events = icao
+        .as_deref()
+        .map(|icao| affecting(&cached_events(app), icao, now_ms()))
+        .unwrap_or_default();
+    let state = app.state::<EventScheduleState>();
+    let cache = state.cache.read();
+    Ok(EventScheduleStatus {
+        enabled,
+        icao,
+        events,
+        updated_at: cache.updated_at,
+        error: cache.error.clone(),
+        recording: state.recording.lock().as_ref().map(|e| e.id.clone()),
+    })
+}
+
+/// Initialize event schedule state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_event_schedule(app: &AppHandle) {
+    app.manage(EventScheduleState {
+        cache: RwLock::new(EventCache::default()),
+        reminded: Mutex::new(HashSet::new()),
+        recording: Mutex::new(None),
+    });
+}
+
+/// Refresh events and check reminders periodically
+pub fn start_event_schedule_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn("event-schedule", Restart::Always, move || {
+        let app = app.clone();
+        async move {
+            let mut tick = tokio::time::interval(CHECK_INTERVAL);
+            loop {
+                tick.tick().await;
+                let error = check(&app).await.err();
+                debug_stats::task_ran("event-schedule", CHECK_INTERVAL, error);
+            }
+        }
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the ongoing and upcoming events affecting an airport (default: the active airport)
+#[tauri::command]
+pub fn get_upcoming_events(app: AppHandle, icao: Option<String>) -> Result<EventScheduleStatus> {
+    get_status(&app, icao.as_deref())
+}
+
+/// Record the traffic until an event ends and save it to the replay library
+#[tauri::command]
+pub fn record_event(app: AppHandle, id: String) -> Result<ScheduledEvent> {
+    record(&app, &id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_vatsim_events_and_calendars() {
+        let api = r#"{"data": [
+            {"id": 17001, "name": "Boston FNO", "link": "https://my.vatsim.net/events/boston-fno",
+             "start_time": "2026-10-23T23:00:00.000000Z", "end_time": "2026-10-24T03:00:00.000000Z",
+             "short_description": "", "airports": [{"icao": "KBOS"}],
+             "routes": [{"departure": "kjfk", "arrival": "KBOS"}]},
+            {"id": 17002, "name": "Broken", "start_time": "soon", "end_time": "later"}
+        ]}"#;
+        let events = parse_api(api, "vatsim").unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, "vatsim-17001");
+        assert_eq!(events[0].airports, ["KBOS", "KJFK"]);
+        assert_eq!(events[0].start, 1_792_796_400_000);
+        assert_eq!(events[0].end - events[0].start, 4 * 3600 * 1000);
+        assert_eq!(events[0].description, None);
+
+        let ical = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:abc@zbw\r\nSUMMARY:Bean\\, Boston (KBOS) and\r\n  Bradley KBDL\r\n\
+                    DTSTART:20261024T180000Z\r\nLOCATION:KBOS\r\nDESCRIPTION:Line one\\nLine two\r\nEND:VEVENT\r\n\
+                    BEGIN:VEVENT\r\nSUMMARY:No start\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+        let events = parse_ical(ical);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, "calendar-abc@zbw");
+        assert_eq!(events[0].name, "Bean, Boston (KBOS) and Bradley KBDL");
+        assert_eq!(events[0].airports, ["KBOS", "KBDL"]);
+        assert_eq!(events[0].description.as_deref(), Some("Line one\nLine two"));
+        assert_eq!(events[0].end - events[0].start, DEFAULT_DURATION_MS);
+        assert_eq!(parse_ical_time("20261024"), Some(1_792_800_000_000));
+
+        // Reminders only for events at the airport starting within the lead time
+        let mut later = events[0].clone();
+        later.id = "later".to_string();
+        later.start += 3600 * 1000;
+        let now = events[0].start - 20 * 60_000;
+        let upcoming = affecting(&[later, events[0].clone()], "KBOS", now);
+        assert_eq!(upcoming.len(), 2);
+        assert_eq!(due(&upcoming, now, 30).map(|e| e.id.as_str()).collect::<Vec<_>>(), ["calendar-abc@zbw"]);
+        assert!(affecting(&upcoming, "KJFK", now).is_empty());
+        assert!(affecting(&upcoming, "KBOS", now + 6 * 3600 * 1000).is_empty());
+    }
+}
@@ -0,0 +1,364 @@
+//! Multi-host federation
+//!
+//! Lets a display host mirror another TowerCab instance (the data host) so a
+//! facility needs vNAS credentials on one machine only. While enabled in the
+//! global settings, the display host subscribes to the data host's
+//! `/api/vnas/ws` and feeds its aircraft into the local vNAS pipeline
+//! (`vnas_batch`), so the local UI and the display host's own remote browsers
+//! see them like a local vNAS connection. With `mirrorSettings` it also copies
+//! the data host's shared display settings (datablocks, labels, sky, clock,
+//! units, bookmarks) every poll and emits `federation-settings-mirrored`.
+//!
+//! The connection is retried until federation is disabled; the data host's
+//! auth token is passed as the `token` query parameter.
+
+use std::time::{Duration, SystemTime};
+
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{info, warn};
+use url::Url;
+
+use crate::http_client;
+use crate::server::VnasAircraftBroadcast;
+use crate::supervisor::{self, Restart};
+use crate::trails::{self, TrailPoint};
+use crate::vnas::VnasAircraft;
+use crate::vnas_batch;
+use crate::GlobalSettings;
+
+/// How often settings are re-read and mirrored
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Delay before reconnecting after an error
+const RECONNECT_DELAY: Duration = Duration::from_secs(10);
+
+/// Federation settings in the global settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FederationSettings {
+    /// Mirror the data host (default: false)
+    pub enabled: bool,
+    /// Data host address (e.g., "http://192.168.1.10:8765")
+    pub url: String,
+    /// The data host's server auth token, if it requires one
+    pub token: Option<String>,
+    /// Copy the data host's shared display settings (default: true)
+    pub mirror_settings: bool,
+}
+
+impl Default for FederationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: String::new(),
+            token: None,
+            mirror_settings: true,
+        }
+    }
+}
+
+/// Federation connection status
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FederationStatus {
+    pub enabled: bool,
+    /// Data host address
+    pub url: Option<String>,
+    pub connected: bool,
+    /// Unix ms of the last aircraft batch from the data host
+    pub last_update: Option<u64>,
+    /// Aircraft in the last batch
+    pub aircraft: usize,
+    /// Unix ms the data host's settings were last copied
+    pub settings_mirrored_at: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// Federation state (managed by Tauri)
+pub struct FederationState {
+    status: RwLock<FederationStatus>,
+}
+
+impl FederationState {
+    pub fn new() -> Self {
+        Self {
+            status: RwLock::new(FederationStatus::default()),
+        }
+    }
+}
+
+impl Default for FederationState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn settings(app: &AppHandle) -> FederationSettings {
+    crate::read_global_settings(app.clone())
+        .map(|s| s.federation)
+        .unwrap_or_default()
+}
+
+fn update_status(app: &AppHandle, update: impl FnOnce(&mut FederationStatus)) {
+    update(&mut app.state::<FederationState>().status.write());
+}
+
+/// Data host address checked and without a trailing path
+pub fn base_url(url: &str) -> Result<Url, String> {
+    let url = Url::parse(url.trim()).map_err(|e| format!("Invalid data host address: {}", e))?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        return Err(format!("Data host address must be an http(s) URL: {}", url));
+    }
+    let mut url = url;
+    url.set_path("");
+    url.set_query(None);
+    url.set_fragment(None);
+    Ok(url)
+}
+
+/// The data host's vNAS aircraft WebSocket URL
+pub fn stream_url(settings: &FederationSettings) -> Result<Url, String> {
+    let mut url = base_url(&settings.url)?;
+    let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+    url.set_scheme(scheme)
+        .map_err(|_| "Failed to build the WebSocket address".to_string())?;
+    url.set_path("/api/vnas/ws");
+    if let Some(token) = settings.token.as_deref().filter(|t| !t.is_empty()) {
+        url.query_pairs_mut().append_pair("token", token);
+    }
+    Ok(url)
+}
+
+/// Copy the shared display settings from the data host's settings.
+/// Returns whether anything changed.
+pub fn mirror(local: &mut GlobalSettings, upstream: &GlobalSettings) -> bool {
+    let before = serde_json::to_value(&*local).unwrap_or_default();
+    local.display = upstream.display.clone();
+    local.datablock = upstream.datablock.clone();
+    local.viewports = upstream.viewports.clone();
+    local.weather = upstream.weather.clone();
+    local.celestial = upstream.celestial.clone();
+    local.clock = upstream.clock.clone();
+    local.units = upstream.units.clone();
+    local.weather_alerts = upstream.weather_alerts.clone();
+    local.view_presets = upstream.view_presets.clone();
+    serde_json::to_value(&*local).unwrap_or_default() != before
+}
+
+/// Fetch the data host's settings and copy the shared ones
+async fn mirror_settings(app: &AppHandle, settings: &FederationSettings) -> Result<(), String> {
+    let url = base_url(&settings.url)?
+        .join("/api/global-settings")
+        .map_err(|e| e.to_string())?;
+    let mut request = http_client::client().get(url);
+    if let Some(token) = settings.token.as_deref().filter(|t| !t.is_empty()) {
+        request = request.bearer_auth(token);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch the data host's settings: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Data host settings: HTTP {}", response.status()));
+    }
+    let upstream: GlobalSettings = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse the data host's settings: {}", e))?;
+
+    let mut local = crate::read_global_settings(app.clone())?;
+    if mirror(&mut local, &upstream) {
+        crate::write_global_settings(app.clone(), local)?;
+        info!("[Federation] Mirrored settings from {}", settings.url);
+        let _ = app.emit("federation-settings-mirrored", ());
+    }
+    update_status(app, |s| s.settings_mirrored_at = Some(now_ms()));
+    Ok(())
+}
+
+/// Feed a batch from the data host into the local vNAS pipeline
+fn handle_batch(app: &AppHandle, batch: Vec<VnasAircraftBroadcast>) {
+    let count = batch.len();
+    let mut latest = None;
+    for update in batch {
+        let point = TrailPoint {
+            time: update.timestamp,
+            latitude: update.lat,
+            longitude: update.lon,
+            altitude_ft: update.altitude / 0.3048,
+        };
+        latest = latest.max(Some(update.timestamp));
+        vnas_batch::push(
+            app,
+            VnasAircraft {
+                trail_point: trails::record(app, &update.callsign, point),
+                callsign: update.callsign,
+                type_code: update.type_code.unwrap_or_default(),
+                is_heavy: false,
+                lat: update.lat,
+                lon: update.lon,
+                true_heading: update.heading,
+                true_ground_track: None,
+                altitude_true: update.altitude,
+                altitude_agl: 0.0,
+                voice_type: 0,
+                timestamp: update.timestamp,
+                groundspeed_kt: update.groundspeed_kt,
+                vertical_rate_fpm: update.vertical_rate_fpm,
+            },
+        );
+    }
+    if let Some(latest) = latest {
+        trails::prune(app, latest);
+    }
+    update_status(app, |s| {
+        s.last_update = Some(now_ms());
+        s.aircraft = count;
+    });
+}
+
+/// Mirror the data host until the connection drops or the settings change
+async fn run_connection(app: &AppHandle, settings: &FederationSettings) -> Result<(), String> {
+    let url = stream_url(settings)?;
+    let (mut socket, _) = tokio_tungstenite::connect_async(url.as_str())
+        .await
+        .map_err(|e| format!("Failed to connect to {}: {}", settings.url, e))?;
+    info!("[Federation] Connected to {}", settings.url);
+    update_status(app, |s| {
+        s.connected = true;
+        s.error = None;
+    });
+
+    let mut check = tokio::time::interval(CHECK_INTERVAL);
+    loop {
+        tokio::select! {
+            message = socket.next() => match message {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str::<Vec<VnasAircraftBroadcast>>(&text) {
+                    Ok(batch) => handle_batch(app, batch),
+                    Err(e) => warn!("[Federation] Failed to parse aircraft batch: {}", e),
+                },
+                Some(Ok(Message::Close(_))) | None => return Err("Connection closed by the data host".to_string()),
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(format!("Connection error: {}", e)),
+            },
+            _ = check.tick() => {
+                let current = self::settings(app);
+                if current != *settings {
+                    let _ = socket.close(None).await;
+                    info!("[Federation] Settings changed, disconnecting from {}", settings.url);
+                    return Ok(());
+                }
+                if settings.mirror_settings {
+                    if let Err(e) = mirror_settings(app, settings).await {
+                        warn!("[Federation] {}", e);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Keep the data host mirrored while federation is enabled
+pub fn start_federation_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn("federation", Restart::Always, move || {
+        let app = app.clone();
+        async move {
+            loop {
+                let settings = settings(&app);
+                let active = settings.enabled && !settings.url.trim().is_empty();
+                update_status(&app, |s| {
+                    s.enabled = active;
+                    s.url = active.then(|| settings.url.clone());
+                });
+                if !active {
+                    tokio::time::sleep(CHECK_INTERVAL).await;
+                    continue;
+                }
+
+                let result = run_connection(&app, &settings).await;
+                update_status(&app, |s| {
+                    s.connected = false;
+                    s.error = result.as_ref().err().cloned();
+                });
+                if let Err(e) = result {
+                    warn!("[Federation] {}", e);
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                }
+            }
+        }
+    });
+}
+
+/// Current federation status
+pub fn get_status(app: &AppHandle) -> FederationStatus {
+    app.state::<FederationState>().status.read().clone()
+}
+
+/// Initialize federation state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_federation(app: &AppHandle) {
+    app.manage(FederationState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the connection status to the data host
+#[tauri::command]
+pub fn get_federation_status(app: AppHandle) -> FederationStatus {
+    get_status(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn federation(url: &str, token: Option<&str>) -> FederationSettings {
+        FederationSettings {
+            enabled: true,
+            url: url.to_string(),
+            token: token.map(str::to_string),
+            mirror_settings: true,
+        }
+    }
+
+    #[test]
+    fn builds_stream_urls() {
+        let url = stream_url(&federation("http://192.168.1.10:8765/", None)).unwrap();
+        assert_eq!(url.as_str(), "ws://192.168.1.10:8765/api/vnas/ws");
+
+        let url = stream_url(&federation("https://tower.example.com/app?x=1", Some("a b&c"))).unwrap();
+        assert_eq!(url.as_str(), "wss://tower.example.com/api/vnas/ws?token=a+b%26c");
+
+        assert!(stream_url(&federation("ftp://tower.example.com", None)).is_err());
+        assert!(stream_url(&federation("tower.example.com", None)).is_err());
+    }
+
+    #[test]
+    fn mirrors_shared_settings_only() {
+        let mut upstream = GlobalSettings::default();
+        upstream.display.label_visibility_distance = 12.0;
+        upstream.server.port = 9000;
+        upstream.cesium_ion_token = "upstream-token".to_string();
+
+        let mut local = GlobalSettings::default();
+        assert!(mirror(&mut local, &upstream));
+        assert_eq!(local.display.label_visibility_distance, 12.0);
+        assert_eq!(local.server.port, 8765);
+        assert!(local.cesium_ion_token.is_empty());
+        assert!(!mirror(&mut local, &upstream));
+    }
+}
@@ -0,0 +1,239 @@
+//! In-memory LRU cache for files served over HTTP
+//!
+//! Remote clients request the same files over and over (index.html, JS
+//! chunks, aircraft and tower models). The server keeps recently served files
+//! in memory, bounded by total size, and evicts the least recently used ones
+//! first. Entries are checked against the file's modification time and size
+//! on every request, so edited files (mods, rebuilt frontend) are re-read.
+//! Hit/miss counts are reported at `/api/metrics`.
+//!
+//! Files too large to cache (e.g., multi-hundred-MB models or terrain) are
+//! memory-mapped and streamed in chunks instead of being read into the heap,
+//! so several clients downloading them at once share the OS page cache.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use axum::body::{Body, Bytes};
+use memmap2::Mmap;
+use parking_lot::Mutex;
+use serde::Serialize;
+
+/// Total size of cached files (bytes)
+const DEFAULT_CAPACITY_BYTES: usize = 256 * 1024 * 1024;
+
+/// Larger files are streamed from a memory map instead of cached (bytes)
+const MAX_ENTRY_BYTES: usize = 32 * 1024 * 1024;
+
+/// Chunk size when streaming a memory-mapped file (bytes)
+const STREAM_CHUNK_BYTES: usize = 1024 * 1024;
+
+/// Cached file contents
+struct Entry {
+    content: Bytes,
+    modified: Option<SystemTime>,
+    /// Access counter value at the last hit (higher = more recent)
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct Entries {
+    files: HashMap<PathBuf, Entry>,
+    bytes: usize,
+    clock: u64,
+}
+
+/// Cache hit/miss counts and size
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Large files streamed from a memory map
+    pub mapped: u64,
+    pub entries: usize,
+    pub bytes: usize,
+    pub capacity_bytes: usize,
+}
+
+/// Size-bounded LRU cache of file contents
+pub struct FileCache {
+    entries: Mutex<Entries>,
+    capacity_bytes: usize,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    mapped: AtomicU64,
+}
+
+impl FileCache {
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY_BYTES)
+    }
+
+    pub fn with_capacity(capacity_bytes: usize) -> Self {
+        Self {
+            entries: Mutex::new(Entries::default()),
+            capacity_bytes,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            mapped: AtomicU64::new(0),
+        }
+    }
+
+    /// Cached contents of a file, if still current
+    fn lookup(&self, path: &Path, modified: Option<SystemTime>, len: u64) -> Option<Bytes> {
+        let mut entries = self.entries.lock();
+        entries.clock += 1;
+        let clock = entries.clock;
+        let entry = entries.files.get_mut(path)?;
+        if entry.modified != modified || entry.content.len() as u64 != len {
+            return None;
+        }
+        entry.last_used = clock;
+        Some(entry.content.clone())
+    }
+
+    /// Add a file's contents, evicting least recently used files to make room
+    fn insert(&self, path: PathBuf, content: Bytes, modified: Option<SystemTime>) {
+        if content.len() > MAX_ENTRY_BYTES || content.len() > self.capacity_bytes {
+            return;
+        }
+        let mut entries = self.entries.lock();
+        if let Some(old) = entries.files.remove(&path) {
+            entries.bytes -= old.content.len();
+        }
+        while entries.bytes + content.len() > self.capacity_bytes {
+            let Some(oldest) = entries
+                .files
+                .iter()
+                .min_by_key(|(_, e)| e.last_used)
+                .map(|(p, _)| p.clone())
+            else {
+                break;
+            };
+            if let Some(evicted) = entries.files.remove(&oldest) {
+                entries.bytes -= evicted.content.len();
+            }
+        }
+        entries.clock += 1;
+        let last_used = entries.clock;
+        entries.bytes += content.len();
+        entries.files.insert(
+            path,
+            Entry {
+                content,
+                modified,
+                last_used,
+            },
+        );
+    }
+
+    /// Contents of a file, from the cache or read from disk
+    pub async fn read(&self, path: &Path) -> Result<Bytes, String> {
+        let metadata = tokio::fs::metadata(path)
+            .await
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        let modified = metadata.modified().ok();
+        if let Some(content) = self.lookup(path, modified, metadata.len()) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(content);
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let content = Bytes::from(
+            tokio::fs::read(path)
+                .await
+                .map_err(|e| format!("Failed to read file: {}", e))?,
+        );
+        self.insert(path.to_path_buf(), content.clone(), modified);
+        Ok(content)
+    }
+
+    /// A file as a response body with its length: cached contents, or a
+    /// chunked stream from a memory map for files too large to cache
+    pub async fn body(&self, path: &Path) -> Result<(Body, u64), String> {
+        let len = tokio::fs::metadata(path)
+            .await
+            .map_err(|e| format!("Failed to read file: {}", e))?
+            .len();
+        if len <= MAX_ENTRY_BYTES as u64 {
+            let content = self.read(path).await?;
+            let len = content.len() as u64;
+            return Ok((Body::from(content), len));
+        }
+
+        let mmap = map_file(path).await?;
+        self.mapped.fetch_add(1, Ordering::Relaxed);
+        let len = mmap.len() as u64;
+        Ok((Body::from_stream(chunks(mmap)), len))
+    }
+
+    pub fn stats(&self) -> FileCacheStats {
+        let entries = self.entries.lock();
+        FileCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            mapped: self.mapped.load(Ordering::Relaxed),
+            entries: entries.files.len(),
+            bytes: entries.bytes,
+            capacity_bytes: self.capacity_bytes,
+        }
+    }
+}
+
+/// Memory-map a file (read-only)
+async fn map_file(path: &Path) -> Result<Arc<Mmap>, String> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let file = std::fs::File::open(&path).map_err(|e| format!("Failed to read file: {}", e))?;
+        // Safety: the map is read-only. Files served this way are large assets
+        // that aren't rewritten in place while the app is serving them.
+        unsafe { Mmap::map(&file) }
+            .map(Arc::new)
+            .map_err(|e| format!("Failed to map file: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Failed to map file: {}", e))?
+}
+
+/// A memory-mapped file as a stream of chunks (only the chunk being sent is
+/// copied to the heap)
+fn chunks(mmap: Arc<Mmap>) -> impl futures_util::Stream<Item = Result<Bytes, Infallible>> {
+    let starts = (0..mmap.len()).step_by(STREAM_CHUNK_BYTES);
+    futures_util::stream::iter(starts.map(move |start| {
+        let end = (start + STREAM_CHUNK_BYTES).min(mmap.len());
+        Ok(Bytes::copy_from_slice(&mmap[start..end]))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = FileCache::with_capacity(10);
+        let file = |name: &str| PathBuf::from(name);
+        let content = |len: usize| Bytes::from(vec![0u8; len]);
+
+        cache.insert(file("a.js"), content(4), None);
+        cache.insert(file("b.js"), content(4), None);
+        // Using a.js makes b.js the least recently used
+        assert!(cache.lookup(&file("a.js"), None, 4).is_some());
+        cache.insert(file("c.glb"), content(4), None);
+        assert!(cache.lookup(&file("b.js"), None, 4).is_none());
+        assert!(cache.lookup(&file("a.js"), None, 4).is_some());
+        assert_eq!(cache.stats().bytes, 8);
+
+        // Changed files are not served from the cache
+        assert!(cache.lookup(&file("a.js"), None, 5).is_none());
+        assert!(cache.lookup(&file("a.js"), Some(SystemTime::UNIX_EPOCH), 4).is_none());
+
+        // Files larger than the cache are never kept
+        cache.insert(file("big.glb"), content(11), None);
+        assert_eq!(cache.stats().entries, 2);
+    }
+}
@@ -0,0 +1,405 @@
+//! Host-side aircraft following
+//!
+//! Following an aircraft can be set on the host (`set_follow` command,
+//! `PUT /api/follow`) instead of in one window: a callsign plus the camera
+//! offsets (tower or orbit mode, zoom, orbit distance/heading/pitch). While
+//! set, the host computes the aircraft's position from the latest traffic
+//! picture, dead-reckoned to now, and for orbit mode the camera position and
+//! orientation, once a second. Every update is emitted as `follow-changed`
+//! and relayed over `/api/follow/ws`, so the desktop app and remote displays
+//! follow the same aircraft the same way, and a reloaded client picks the
+//! follow back up on connect.
+
+use std::time::{Duration, SystemTime};
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::info;
+
+use crate::error::{Error, Result};
+use crate::geo;
+use crate::supervisor::{self, Restart};
+use crate::traffic::{self, Aircraft, TrafficSnapshot};
+
+/// How often camera targets are recomputed
+const UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Positions older than this are not extrapolated further (ms)
+const MAX_EXTRAPOLATION_MS: u64 = 30_000;
+
+const FEET_PER_METER: f64 = 3.28084;
+const METERS_PER_NM: f64 = 1852.0;
+
+/// Lowest camera height in orbit mode (meters MSL)
+const MIN_ORBIT_HEIGHT_M: f64 = 10.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FollowMode {
+    /// Camera stays in the tower and turns to track the aircraft
+    #[default]
+    Tower,
+    /// Camera orbits the aircraft
+    Orbit,
+}
+
+/// Aircraft to follow and how
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FollowTarget {
+    pub callsign: String,
+    pub mode: FollowMode,
+    /// Zoom factor (0.5-10, 1 = default field of view)
+    pub zoom: f64,
+    /// Distance from the aircraft in orbit mode (50-5000 m)
+    pub orbit_distance: f64,
+    /// Angle around the aircraft from behind it in orbit mode (degrees)
+    pub orbit_heading: f64,
+    /// Angle above the aircraft in orbit mode (-89 to 89 degrees)
+    pub orbit_pitch: f64,
+}
+
+impl Default for FollowTarget {
+    fn default() -> Self {
+        Self {
+            callsign: String::new(),
+            mode: FollowMode::Tower,
+            zoom: 1.0,
+            orbit_distance: 500.0,
+            orbit_heading: 315.0,
+            orbit_pitch: 15.0,
+        }
+    }
+}
+
+/// Followed aircraft's position, dead-reckoned to now
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowedAircraft {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Feet MSL
+    pub altitude_ft: f64,
+    /// Degrees true
+    pub heading: f64,
+    pub groundspeed_kt: f64,
+    pub on_ground: bool,
+    /// Time since the position was reported (ms)
+    pub extrapolated_ms: u64,
+}
+
+/// Camera pose in orbit mode
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CameraPose {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Meters MSL
+    pub height_m: f64,
+    /// Degrees true
+    pub heading: f64,
+    /// Degrees (negative = looking down)
+    pub pitch: f64,
+}
+
+/// What clients should show (`follow-changed` event payload)
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowStatus {
+    /// `None` when not following
+    pub target: Option<FollowTarget>,
+    /// `None` while the aircraft isn't in the traffic picture
+    pub aircraft: Option<FollowedAircraft>,
+    /// Orbit camera; in tower mode each client aims from its own tower
+    pub camera: Option<CameraPose>,
+    /// Unix ms
+    pub updated_at: u64,
+}
+
+/// Follow state (managed by Tauri)
+pub struct FollowState {
+    status: RwLock<FollowStatus>,
+    /// Status updates for WebSocket relay
+    pub events: broadcast::Sender<FollowStatus>,
+}
+
+impl FollowState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            status: RwLock::new(FollowStatus::default()),
+            events,
+        }
+    }
+}
+
+impl Default for FollowState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Check and normalize a follow target
+pub fn validate(mut target: FollowTarget) -> Result<FollowTarget> {
+    target.callsign = target.callsign.trim().to_uppercase();
+    if target.callsign.is_empty() {
+        return Err(Error::invalid_input("Callsign is required"));
+    }
+    if !(0.5..=10.0).contains(&target.zoom) {
+        return Err(Error::invalid_input("Zoom must be between 0.5 and 10"));
+    }
+    if !(50.0..=5000.0).contains(&target.orbit_distance) {
+        return Err(Error::invalid_input("Orbit distance must be between 50 and 5000 m"));
+    }
+    if !(-89.0..=89.0).contains(&target.orbit_pitch) {
+        return Err(Error::invalid_input("Orbit pitch must be between -89 and 89 degrees"));
+    }
+    target.orbit_heading = target.orbit_heading.rem_euclid(360.0);
+    Ok(target)
+}
+
+/// Aircraft position moved along its track for the time since it was reported
+fn extrapolate(aircraft: &Aircraft, reported_at: u64, now: u64) -> FollowedAircraft {
+    let elapsed_ms = now.saturating_sub(reported_at).min(MAX_EXTRAPOLATION_MS);
+    let hours = elapsed_ms as f64 / 3_600_000.0;
+    let track = aircraft.track.unwrap_or(aircraft.heading);
+    let (latitude, longitude) = if aircraft.groundspeed_kt > 0.0 {
+        geo::destination(
+            aircraft.latitude,
+            aircraft.longitude,
+            track,
+            aircraft.groundspeed_kt * hours,
+        )
+    } else {
+        (aircraft.latitude, aircraft.longitude)
+    };
+    let climb_ft = aircraft.vertical_rate_fpm.unwrap_or(0.0) * hours * 60.0;
+    FollowedAircraft {
+        latitude,
+        longitude,
+        altitude_ft: if aircraft.on_ground {
+            aircraft.altitude_ft
+        } else {
+            aircraft.altitude_ft + climb_ft
+        },
+        heading: aircraft.heading,
+        groundspeed_kt: aircraft.groundspeed_kt,
+        on_ground: aircraft.on_ground,
+        extrapolated_ms: elapsed_ms,
+    }
+}
+
+/// Orbit camera around an aircraft (same geometry as the viewer's orbit mode)
+fn orbit_camera(aircraft: &FollowedAircraft, target: &FollowTarget) -> CameraPose {
+    let altitude_m = aircraft.altitude_ft / FEET_PER_METER;
+    // Orbit heading is measured from behind the aircraft
+    let angle = (aircraft.heading + 180.0 + target.orbit_heading).rem_euclid(360.0);
+    let pitch = target.orbit_pitch.to_radians();
+    let horizontal_m = target.orbit_distance * pitch.cos();
+    let (latitude, longitude) = geo::destination(
+        aircraft.latitude,
+        aircraft.longitude,
+        angle,
+        horizontal_m / METERS_PER_NM,
+    );
+    let height_m = (altitude_m + target.orbit_distance * pitch.sin()).max(MIN_ORBIT_HEIGHT_M);
+    CameraPose {
+        latitude,
+        longitude,
+        height_m,
+        heading: (angle + 180.0).rem_euclid(360.0),
+        pitch: (altitude_m - height_m).atan2(horizontal_m).to_degrees(),
+    }
+}
+
+/// Follow status for a target from a traffic picture
+fn compute(target: Option<FollowTarget>, snapshot: &TrafficSnapshot, now: u64) -> FollowStatus {
+    let aircraft = target.as_ref().and_then(|target| {
+        snapshot
+            .aircraft
+            .iter()
+            .find(|a| a.callsign == target.callsign)
+            .map(|a| extrapolate(a, snapshot.updated_at.unwrap_or(now), now))
+    });
+    let camera = match (&target, &aircraft) {
+        (Some(target), Some(aircraft)) if target.mode == FollowMode::Orbit => Some(orbit_camera(aircraft, target)),
+        _ => None,
+    };
+    FollowStatus {
+        target,
+        aircraft,
+        camera,
+        updated_at: now,
+    }
+}
+
+fn publish(app: &AppHandle, status: FollowStatus) {
+    let state = app.state::<FollowState>();
+    *state.status.write() = status.clone();
+    let _ = app.emit("follow-changed", &status);
+    let _ = state.events.send(status);
+}
+
+/// Current follow status
+pub fn get_status(app: &AppHandle) -> FollowStatus {
+    app.state::<FollowState>().status.read().clone()
+}
+
+/// Start following an aircraft (or change the camera offsets)
+pub fn set(app: &AppHandle, target: FollowTarget) -> Result<FollowStatus> {
+    let target = validate(target)?;
+    if get_status(app).target.as_ref().map(|t| &t.callsign) != Some(&target.callsign) {
+        info!("[Follow] Following {} ({:?})", target.callsign, target.mode);
+    }
+    let status = compute(Some(target), &traffic::get_snapshot(app), now_ms());
+    publish(app, status.clone());
+    Ok(status)
+}
+
+/// Stop following
+pub fn stop(app: &AppHandle) {
+    if let Some(target) = get_status(app).target {
+        info!("[Follow] Stopped following {}", target.callsign);
+        publish(
+            app,
+            FollowStatus {
+                updated_at: now_ms(),
+                ..Default::default()
+            },
+        );
+    }
+}
+
+/// Recompute camera targets while following
+fn start_follow_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn("follow", Restart::Always, move || {
+        let app = app.clone();
+        async move {
+            let mut interval = tokio::time::interval(UPDATE_INTERVAL);
+            loop {
+                interval.tick().await;
+                let Some(target) = get_status(&app).target else {
+                    continue;
+                };
+                let status = compute(Some(target), &traffic::get_snapshot(&app), now_ms());
+                publish(&app, status);
+            }
+        }
+    });
+}
+
+/// Initialize follow state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_follow(app: &AppHandle) {
+    app.manage(FollowState::new());
+    start_follow_task(app);
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Current host-side follow target and camera
+#[tauri::command]
+pub fn get_follow(app: AppHandle) -> FollowStatus {
+    get_status(&app)
+}
+
+/// Follow an aircraft on every display
+#[tauri::command]
+pub fn set_follow(app: AppHandle, target: FollowTarget) -> Result<FollowStatus> {
+    set(&app, target)
+}
+
+/// Stop following on every display
+#[tauri::command]
+pub fn stop_follow(app: AppHandle) {
+    stop(&app);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traffic::test_aircraft;
+
+    fn aircraft(callsign: &str) -> Aircraft {
+        Aircraft {
+            groundspeed_kt: 120.0,
+            heading: 90.0,
+            track: Some(90.0),
+            vertical_rate_fpm: Some(-600.0),
+            distance_nm: 5.0,
+            ..test_aircraft(callsign)
+        }
+    }
+
+    #[test]
+    fn dead_reckons_the_followed_aircraft() {
+        let snapshot = TrafficSnapshot {
+            updated_at: Some(1_000_000),
+            aircraft: vec![aircraft("AAL1"), aircraft("DAL2")],
+            ..Default::default()
+        };
+        let target = validate(FollowTarget {
+            callsign: " dal2 ".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        // 30 s at 120 kt east, descending 600 fpm: 1 nm east, 300 ft lower
+        let status = compute(Some(target.clone()), &snapshot, 1_030_000);
+        let followed = status.aircraft.unwrap();
+        assert_eq!(followed.extrapolated_ms, 30_000);
+        let (distance, bearing) = geo::distance_and_bearing(42.0, -71.0, followed.latitude, followed.longitude);
+        assert!((distance - 1.0).abs() < 0.01);
+        assert!((bearing - 90.0).abs() < 0.1);
+        assert!((followed.altitude_ft - 2700.0).abs() < 0.01);
+        assert_eq!(status.camera, None);
+
+        // Extrapolation stops after 30 s
+        let stale = compute(Some(target.clone()), &snapshot, 1_300_000).aircraft.unwrap();
+        assert!((stale.latitude - followed.latitude).abs() < 1e-9);
+
+        let missing = FollowTarget {
+            callsign: "UAL3".to_string(),
+            ..target
+        };
+        assert_eq!(compute(Some(missing), &snapshot, 1_000_000).aircraft, None);
+        assert!(validate(FollowTarget::default()).is_err());
+    }
+
+    #[test]
+    fn places_the_orbit_camera_behind_the_aircraft() {
+        let snapshot = TrafficSnapshot {
+            updated_at: Some(0),
+            aircraft: vec![aircraft("AAL1")],
+            ..Default::default()
+        };
+        let target = FollowTarget {
+            callsign: "AAL1".to_string(),
+            mode: FollowMode::Orbit,
+            orbit_distance: 1000.0,
+            orbit_heading: 0.0,
+            orbit_pitch: 30.0,
+            ..Default::default()
+        };
+        let camera = compute(Some(target), &snapshot, 0).camera.unwrap();
+        // Heading east, so the camera is west of it looking east and down
+        let (distance, bearing) = geo::distance_and_bearing(42.0, -71.0, camera.latitude, camera.longitude);
+        assert!((distance * METERS_PER_NM - 866.0).abs() < 1.0);
+        assert!((bearing - 270.0).abs() < 0.1);
+        assert!((camera.heading - 90.0).abs() < 1e-6);
+        assert!((camera.height_m - (3000.0 / FEET_PER_METER + 500.0)).abs() < 0.01);
+        assert!((camera.pitch + 30.0).abs() < 1e-6);
+    }
+}
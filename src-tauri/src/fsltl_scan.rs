@@ -0,0 +1,178 @@
+//! Parallel scan of the FSLTL output directory
+//!
+//! Converted models live at `outputPath/TYPE/AIRLINE/model.glb` (or
+//! `TYPE/base/model.glb`). Large libraries on network shares take tens of
+//! seconds to walk one directory at a time, so type directories are scanned
+//! on a dedicated thread pool. Each finished type directory is reported with
+//! the models found in it, which the `scan_fsltl_models` command streams to
+//! the UI as `fsltl-scan-progress` events.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rayon::prelude::*;
+use serde::Serialize;
+use tracing::info;
+
+use crate::{normalize_path_string, ScannedFSLTLModel};
+
+/// Threads walking type directories. Scanning is I/O bound (especially over
+/// SMB), so this is not tied to the CPU count.
+const SCAN_THREADS: usize = 16;
+
+/// Scan progress, reported after each type directory
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanProgress<'a> {
+    pub output_path: &'a str,
+    /// Type directories scanned so far
+    pub scanned_types: usize,
+    pub total_types: usize,
+    /// Models found so far
+    pub models_found: usize,
+    /// Models found in the directory that just finished
+    pub models: &'a [ScannedFSLTLModel],
+}
+
+/// Whether a manifest.json next to the model says it has animations
+fn has_animations(airline_path: &Path) -> bool {
+    fs::read_to_string(airline_path.join("manifest.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|json| json.get("hasAnimations").and_then(|v| v.as_bool()))
+        .unwrap_or(false)
+}
+
+/// Models in one type directory (e.g., B738/UAL/model.glb, B738/base/model.glb)
+fn scan_type_dir(type_path: &Path, aircraft_type: &str) -> Vec<ScannedFSLTLModel> {
+    let Ok(airline_dirs) = fs::read_dir(type_path) else {
+        return Vec::new();
+    };
+
+    let mut models = Vec::new();
+    for airline_entry in airline_dirs.filter_map(|e| e.ok()) {
+        let airline_path = airline_entry.path();
+        if !airline_path.is_dir() {
+            continue;
+        }
+        let Ok(airline_folder) = airline_entry.file_name().into_string() else {
+            continue;
+        };
+
+        let model_file = airline_path.join("model.glb");
+        let Ok(metadata) = fs::metadata(&model_file) else {
+            continue;
+        };
+
+        // Airline code is None for the "base" folder
+        let airline_code = (!airline_folder.eq_ignore_ascii_case("base")).then(|| airline_folder.clone());
+
+        // Model name like FSLTL_B738_AAL or FSLTL_B738_ZZZZ
+        let model_name = format!("FSLTL_{}_{}", aircraft_type, airline_code.as_deref().unwrap_or("ZZZZ"));
+
+        models.push(ScannedFSLTLModel {
+            model_name,
+            model_path: normalize_path_string(&model_file),
+            // For HTTP API access (e.g., "B738/AAL/model.glb")
+            relative_path: format!("{}/{}/model.glb", aircraft_type, airline_folder),
+            aircraft_type: aircraft_type.to_string(),
+            airline_code,
+            has_animations: has_animations(&airline_path),
+            file_size: metadata.len(),
+        });
+    }
+    models
+}
+
+/// Scan an FSLTL output directory for converted models, calling `on_progress`
+/// (from worker threads) as each type directory finishes. Models are returned
+/// sorted by relative path.
+pub fn scan(output_path: &str, on_progress: impl Fn(&ScanProgress) + Sync) -> Result<Vec<ScannedFSLTLModel>, String> {
+    let base_path = PathBuf::from(output_path);
+    if !base_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    // Aircraft type directories (e.g., B738, A320), skipping hidden/system ones
+    let type_dirs: Vec<(PathBuf, String)> = fs::read_dir(&base_path)
+        .map_err(|e| format!("Failed to read output directory: {}", e))?
+        .filter_map(|e| e.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let path = entry.path();
+            (!name.starts_with('.') && !name.starts_with('_') && path.is_dir()).then_some((path, name))
+        })
+        .collect();
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(SCAN_THREADS)
+        .thread_name(|i| format!("fsltl-scan-{}", i))
+        .build()
+        .map_err(|e| format!("Failed to start FSLTL scan: {}", e))?;
+
+    let scanned_types = AtomicUsize::new(0);
+    let models_found = AtomicUsize::new(0);
+    let mut models: Vec<ScannedFSLTLModel> = pool.install(|| {
+        type_dirs
+            .par_iter()
+            .flat_map_iter(|(path, aircraft_type)| {
+                let models = scan_type_dir(path, aircraft_type);
+                on_progress(&ScanProgress {
+                    output_path,
+                    scanned_types: scanned_types.fetch_add(1, Ordering::Relaxed) + 1,
+                    total_types: type_dirs.len(),
+                    models_found: models_found.fetch_add(models.len(), Ordering::Relaxed) + models.len(),
+                    models: &models,
+                });
+                models
+            })
+            .collect()
+    });
+    models.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+
+    info!("[FSLTL] Scanned {} existing models from {}", models.len(), output_path);
+    Ok(models)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn scans_type_and_airline_directories() {
+        let root = std::env::temp_dir().join(format!("fsltl-scan-test-{}", std::process::id()));
+        let model = |dir: &str, manifest: Option<&str>| {
+            let path = root.join(dir);
+            fs::create_dir_all(&path).unwrap();
+            fs::write(path.join("model.glb"), b"glTF").unwrap();
+            if let Some(manifest) = manifest {
+                fs::write(path.join("manifest.json"), manifest).unwrap();
+            }
+        };
+        model("B738/UAL", Some(r#"{"hasAnimations":true}"#));
+        model("B738/base", None);
+        model("A320/DLH", Some("not json"));
+        model("_converter/base", None);
+        fs::create_dir_all(root.join("A320/empty")).unwrap();
+
+        let progress = Mutex::new(Vec::new());
+        let root_path = normalize_path_string(&root);
+        let models = scan(&root_path, |p| {
+            progress.lock().unwrap().push((p.scanned_types, p.total_types))
+        });
+        fs::remove_dir_all(&root).unwrap();
+        let models = models.unwrap();
+
+        let names: Vec<&str> = models.iter().map(|m| m.model_name.as_str()).collect();
+        assert_eq!(names, vec!["FSLTL_A320_DLH", "FSLTL_B738_UAL", "FSLTL_B738_ZZZZ"]);
+        assert!(models[1].has_animations && !models[0].has_animations);
+        assert_eq!(models[1].relative_path, "B738/UAL/model.glb");
+        assert_eq!(models[2].airline_code, None);
+
+        let mut progress = progress.into_inner().unwrap();
+        progress.sort();
+        assert_eq!(progress, vec![(1, 2), (2, 2)]);
+    }
+}
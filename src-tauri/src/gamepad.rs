@@ -0,0 +1,250 @@
+//! Gamepad and joystick camera input
+//!
+//! The WebView's Gamepad API is unreliable (WebView2 only reports pads after a
+//! button press while focused, WKWebView not at all), so the host reads
+//! controllers with gilrs on a dedicated thread. Sticks and analog triggers are
+//! mapped to camera motions (`gamepad` in global settings) and sent to the
+//! main window ~60 times a second as `gamepad-input` while deflected; the
+//! frontend feeds them into the same smoothed velocities as the keyboard.
+//! Buttons run control actions (see control.rs).
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use gilrs::{EventType, Gilrs};
+use parking_lot::RwLock;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+use crate::control::{self, ControlAction};
+use crate::supervisor::{self, Restart};
+use crate::{GamepadAxisBinding, GlobalGamepadSettings};
+
+/// How often input is polled and sent while a stick is deflected
+const POLL_INTERVAL: Duration = Duration::from_millis(16);
+
+/// How often the thread checks whether input was enabled
+const DISABLED_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Camera motion rates, each -1 to 1 (`gamepad-input` event)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GamepadAxes {
+    pub forward: f32,
+    pub right: f32,
+    pub up: f32,
+    pub heading: f32,
+    pub pitch: f32,
+    /// Positive zooms in
+    pub zoom: f32,
+}
+
+impl GamepadAxes {
+    fn target_mut(&mut self, target: &str) -> Option<&mut f32> {
+        match target {
+            "forward" => Some(&mut self.forward),
+            "right" => Some(&mut self.right),
+            "up" => Some(&mut self.up),
+            "heading" => Some(&mut self.heading),
+            "pitch" => Some(&mut self.pitch),
+            "zoom" => Some(&mut self.zoom),
+            _ => None,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == GamepadAxes::default()
+    }
+}
+
+/// Gamepad reader status
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GamepadStatus {
+    pub enabled: bool,
+    /// Names of connected controllers
+    pub gamepads: Vec<String>,
+    /// Why gamepads can't be read on this system
+    pub error: Option<String>,
+}
+
+pub struct GamepadState {
+    settings: RwLock<GlobalGamepadSettings>,
+    status: RwLock<GamepadStatus>,
+}
+
+/// Remove the deadzone and rescale the rest to the full range
+fn apply_deadzone(value: f32, deadzone: f32) -> f32 {
+    let deadzone = deadzone.clamp(0.0, 0.95);
+    if value.abs() <= deadzone {
+        return 0.0;
+    }
+    value.signum() * (value.abs() - deadzone) / (1.0 - deadzone)
+}
+
+/// Combine raw input values (by gilrs axis or button name) into camera motions
+fn axes_from_inputs(inputs: &HashMap<String, f32>, bindings: &[GamepadAxisBinding], deadzone: f32) -> GamepadAxes {
+    let mut axes = GamepadAxes::default();
+    for binding in bindings {
+        let Some(&value) = inputs.get(&binding.input) else {
+            continue;
+        };
+        if let Some(target) = axes.target_mut(&binding.target) {
+            *target = (*target + apply_deadzone(value, deadzone) * binding.scale).clamp(-1.0, 1.0);
+        }
+    }
+    axes
+}
+
+fn connected_gamepads(gilrs: &Gilrs) -> Vec<String> {
+    gilrs.gamepads().map(|(_, pad)| pad.name().to_string()).collect()
+}
+
+fn press_button(app: &AppHandle, button: &str) {
+    let Some(binding) = app
+        .state::<GamepadState>()
+        .settings
+        .read()
+        .buttons
+        .iter()
+        .find(|b| b.button == button)
+        .cloned()
+    else {
+        return;
+    };
+    let action = ControlAction {
+        action: binding.action,
+        value: binding.value,
+    };
+    if let Err(e) = control::trigger(app, action) {
+        warn!("[Gamepad] {}: {}", button, e);
+    }
+}
+
+fn run(app: &AppHandle, mut gilrs: Gilrs) {
+    let state = app.state::<GamepadState>();
+    let mut inputs: HashMap<String, f32> = HashMap::new();
+    let mut last_sent = GamepadAxes::default();
+    state.status.write().gamepads = connected_gamepads(&gilrs);
+
+    loop {
+        let settings = state.settings.read().clone();
+        if !settings.enabled {
+            // Drain events so a backlog isn't replayed when input is enabled
+            while gilrs.next_event().is_some() {}
+            inputs.clear();
+            if !last_sent.is_zero() {
+                last_sent = GamepadAxes::default();
+                let _ = app.emit_to("main", "gamepad-input", last_sent);
+            }
+            std::thread::sleep(DISABLED_POLL_INTERVAL);
+            continue;
+        }
+
+        while let Some(event) = gilrs.next_event() {
+            match event.event {
+                EventType::AxisChanged(axis, value, _) => {
+                    inputs.insert(format!("{:?}", axis), value);
+                }
+                EventType::ButtonChanged(button, value, _) => {
+                    inputs.insert(format!("{:?}", button), value);
+                }
+                EventType::ButtonPressed(button, _) => press_button(app, &format!("{:?}", button)),
+                EventType::Connected | EventType::Disconnected => {
+                    let name = gilrs.gamepad(event.id).name().to_string();
+                    let connected = matches!(event.event, EventType::Connected);
+                    info!(
+                        "[Gamepad] {} {}",
+                        name,
+                        if connected { "connected" } else { "disconnected" }
+                    );
+                    // Stop any motion from a controller that went away
+                    inputs.clear();
+                    state.status.write().gamepads = connected_gamepads(&gilrs);
+                }
+                _ => {}
+            }
+        }
+
+        // Send while deflected, plus once when the sticks return to center
+        let axes = axes_from_inputs(&inputs, &settings.axes, settings.deadzone);
+        if !axes.is_zero() || !last_sent.is_zero() {
+            let _ = app.emit_to("main", "gamepad-input", axes);
+            last_sent = axes;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Apply saved gamepad settings (called when global settings are written)
+pub fn apply_settings(app: &AppHandle, settings: &GlobalGamepadSettings) {
+    if let Some(state) = app.try_state::<GamepadState>() {
+        *state.settings.write() = settings.clone();
+        state.status.write().enabled = settings.enabled;
+    }
+}
+
+/// Initialize gamepad state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_gamepad(app: &AppHandle) {
+    let settings = crate::read_global_settings(app.clone())
+        .map(|s| s.gamepad)
+        .unwrap_or_default();
+    app.manage(GamepadState {
+        status: RwLock::new(GamepadStatus {
+            enabled: settings.enabled,
+            ..Default::default()
+        }),
+        settings: RwLock::new(settings),
+    });
+}
+
+/// Read controllers on a dedicated thread (gilrs isn't Send on every platform)
+pub fn start_gamepad_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn_blocking("gamepad", Restart::OnPanic, move || match Gilrs::new() {
+        Ok(gilrs) => run(&app, gilrs),
+        Err(e) => {
+            warn!("[Gamepad] Gamepads unavailable: {}", e);
+            app.state::<GamepadState>().status.write().error = Some(e.to_string());
+        }
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get whether gamepad input is enabled and which controllers are connected
+#[tauri::command]
+pub fn get_gamepad_status(app: AppHandle) -> GamepadStatus {
+    app.try_state::<GamepadState>()
+        .map(|state| state.status.read().clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_inputs_with_deadzone_and_scale() {
+        let bindings = GlobalGamepadSettings::default().axes;
+        let inputs: HashMap<String, f32> = [
+            ("LeftStickY", 0.1),
+            ("RightStickX", -1.0),
+            ("RightTrigger2", 0.575),
+            ("LeftTrigger2", 0.15),
+        ]
+        .into_iter()
+        .map(|(input, value)| (input.to_string(), value))
+        .collect();
+
+        let axes = axes_from_inputs(&inputs, &bindings, 0.15);
+        assert_eq!(axes.forward, 0.0);
+        assert_eq!(axes.heading, -1.0);
+        assert!((axes.zoom - 0.5).abs() < 1e-6);
+        assert!(axes_from_inputs(&HashMap::new(), &bindings, 0.15).is_zero());
+    }
+}
@@ -0,0 +1,380 @@
+//! Parking suggestions for arrivals
+//!
+//! Every arrival to the active airport that isn't parked yet gets a plausible
+//! free gate, so the view can show "DAL123 → B7" as it vacates the runway.
+//! Gates are scored for the arrival's airline: gates listed for the airline in
+//! an imported gates file, gates the airline has used this session, and gates
+//! next to where its other aircraft are parked. Gates too small for the type
+//! (`maxWingspan` in imported files) are skipped, and gates listed for other
+//! airlines are avoided.
+//!
+//! Suggestions are kept while their gate stays free, so an arrival isn't
+//! moved around from poll to poll. They are served at
+//! `/api/airports/{ICAO}/gates/suggestions`.
+
+use std::collections::{HashMap, HashSet};
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+
+use crate::error::{Error, Result};
+use crate::aircraft_types;
+use crate::airlines;
+use crate::gates::{self, Gate};
+use crate::geo;
+use crate::traffic::TrafficSnapshot;
+use crate::weather;
+
+/// Gates this close to one where the airline is parked count as its area (meters)
+const NEIGHBOR_RADIUS_M: f64 = 250.0;
+
+const METERS_PER_NM: f64 = 1852.0;
+
+/// Score for a gate listed for the arrival's airline
+const LISTED_AIRLINE_SCORE: i32 = 100;
+/// Penalty for a gate listed only for other airlines
+const OTHER_AIRLINE_PENALTY: i32 = 50;
+/// Score per earlier use by the airline this session (capped)
+const USAGE_SCORE: i32 = 10;
+const MAX_USAGE_SCORE: i32 = 50;
+/// Score per aircraft of the airline parked nearby (capped)
+const NEIGHBOR_SCORE: i32 = 20;
+const MAX_NEIGHBOR_SCORE: i32 = 60;
+
+/// Suggested gate for an arrival
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GateSuggestion {
+    pub callsign: String,
+    pub gate: String,
+    /// Why this gate: "airline", "history", "nearby" or "free"
+    pub reason: String,
+}
+
+/// Arrival waiting for a gate
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arrival {
+    pub callsign: String,
+    /// ICAO airline designator, for airline flights
+    pub airline: Option<String>,
+    pub wingspan_m: Option<f64>,
+}
+
+/// Suggestions at the active airport
+#[derive(Default)]
+struct AirportSuggestions {
+    icao: String,
+    suggestions: Vec<GateSuggestion>,
+    /// Times each airline parked at each gate this session
+    usage: HashMap<String, HashMap<String, u32>>,
+    /// Aircraft seen parked, by gate (to count each parking once)
+    parked: HashMap<String, String>,
+}
+
+/// Gate suggestion state (managed by Tauri)
+pub struct GateSuggestionState {
+    active: RwLock<Option<AirportSuggestions>>,
+}
+
+impl GateSuggestionState {
+    pub fn new() -> Self {
+        Self {
+            active: RwLock::new(None),
+        }
+    }
+}
+
+impl Default for GateSuggestionState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn airline_of(callsign: &str) -> Option<String> {
+    airlines::split_callsign(callsign).map(|(code, _)| code.to_string())
+}
+
+/// Score a free gate for an arrival, with the reason that weighed most
+fn score_gate(
+    gate: &Gate,
+    arrival: &Arrival,
+    usage: &HashMap<String, HashMap<String, u32>>,
+    parked_by_airline: &[(&Gate, String)],
+) -> (i32, &'static str) {
+    let Some(airline) = arrival.airline.as_deref() else {
+        return (0, "free");
+    };
+
+    let mut score = 0;
+    let mut reason = "free";
+    if gate.airlines.iter().any(|a| a == airline) {
+        score += LISTED_AIRLINE_SCORE;
+        reason = "airline";
+    } else if !gate.airlines.is_empty() {
+        score -= OTHER_AIRLINE_PENALTY;
+    }
+
+    let used = usage.get(&gate.id).and_then(|u| u.get(airline)).copied().unwrap_or(0) as i32;
+    let usage_score = (used * USAGE_SCORE).min(MAX_USAGE_SCORE);
+    if usage_score > 0 && reason == "free" {
+        reason = "history";
+    }
+    score += usage_score;
+
+    let neighbors = parked_by_airline
+        .iter()
+        .filter(|(other, parked_airline)| {
+            parked_airline == airline && {
+                let (distance_nm, _) =
+                    geo::distance_and_bearing(gate.latitude, gate.longitude, other.latitude, other.longitude);
+                distance_nm * METERS_PER_NM <= NEIGHBOR_RADIUS_M
+            }
+        })
+        .count() as i32;
+    let neighbor_score = (neighbors * NEIGHBOR_SCORE).min(MAX_NEIGHBOR_SCORE);
+    if neighbor_score > 0 && reason == "free" {
+        reason = "nearby";
+    }
+    (score + neighbor_score, reason)
+}
+
+/// Stable per-arrival tie-breaker, so equally good gates are spread out
+fn spread(callsign: &str, gate: &str) -> u32 {
+    callsign
+        .bytes()
+        .chain(gate.bytes())
+        .fold(2_166_136_261u32, |hash, b| (hash ^ b as u32).wrapping_mul(16_777_619))
+}
+
+/// Suggest a free gate for each arrival (in order), keeping earlier
+/// suggestions whose gate is still free and fits
+pub fn suggest(
+    gates: &[Gate],
+    arrivals: &[Arrival],
+    usage: &HashMap<String, HashMap<String, u32>>,
+    previous: &[GateSuggestion],
+) -> Vec<GateSuggestion> {
+    let parked_by_airline: Vec<(&Gate, String)> = gates
+        .iter()
+        .filter_map(|g| Some((g, airline_of(g.occupied_by.as_deref()?)?)))
+        .collect();
+    let fits = |gate: &Gate, arrival: &Arrival| match (gate.max_wingspan_m, arrival.wingspan_m) {
+        (Some(max), Some(span)) => span <= max,
+        _ => true,
+    };
+
+    let mut taken: HashSet<String> = HashSet::new();
+    let mut suggestions = Vec::new();
+    for arrival in arrivals {
+        let kept = previous.iter().find(|s| s.callsign == arrival.callsign).filter(|s| {
+            !taken.contains(&s.gate)
+                && gates
+                    .iter()
+                    .any(|g| g.id == s.gate && g.occupied_by.is_none() && fits(g, arrival))
+        });
+        if let Some(kept) = kept {
+            taken.insert(kept.gate.clone());
+            suggestions.push(kept.clone());
+            continue;
+        }
+
+        let best = gates
+            .iter()
+            .filter(|g| g.occupied_by.is_none() && !taken.contains(&g.id) && fits(g, arrival))
+            .map(|g| {
+                let (score, reason) = score_gate(g, arrival, usage, &parked_by_airline);
+                (score, spread(&arrival.callsign, &g.id), g, reason)
+            })
+            .max_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+        if let Some((_, _, gate, reason)) = best {
+            taken.insert(gate.id.clone());
+            suggestions.push(GateSuggestion {
+                callsign: arrival.callsign.clone(),
+                gate: gate.id.clone(),
+                reason: reason.to_string(),
+            });
+        }
+    }
+    suggestions
+}
+
+/// Update suggestions from a traffic snapshot (called after gate occupancy is updated)
+pub fn update(app: &AppHandle, snapshot: &TrafficSnapshot) {
+    let Some(icao) = snapshot.icao.clone() else {
+        return;
+    };
+    let Some(gates) = gates::active_gates(app, &icao) else {
+        return;
+    };
+
+    let parked: HashSet<&str> = gates.iter().filter_map(|g| g.occupied_by.as_deref()).collect();
+    let mut arrivals: Vec<(f64, Arrival)> = snapshot
+        .aircraft
+        .iter()
+        .filter(|a| a.arrival.as_deref() == Some(icao.as_str()) && !parked.contains(a.callsign.as_str()))
+        .map(|a| {
+            let wingspan_m = a
+                .aircraft_type
+                .as_deref()
+                .and_then(|t| aircraft_types::get(app, t))
+                .and_then(|t| t.wingspan_m);
+            let arrival = Arrival {
+                callsign: a.callsign.clone(),
+                airline: airline_of(&a.callsign),
+                wingspan_m,
+            };
+            (a.distance_nm, arrival)
+        })
+        .collect();
+    // Closest arrivals pick first
+    arrivals.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let arrivals: Vec<Arrival> = arrivals.into_iter().map(|(_, a)| a).collect();
+
+    let state = app.state::<GateSuggestionState>();
+    let mut active = state.active.write();
+    if active.as_ref().map_or(true, |a| a.icao != icao) {
+        *active = Some(AirportSuggestions {
+            icao: icao.clone(),
+            ..Default::default()
+        });
+    }
+    let Some(airport) = active.as_mut() else {
+        return;
+    };
+
+    // Learn which gates each airline uses
+    for gate in &gates {
+        let Some(callsign) = gate.occupied_by.as_deref() else {
+            airport.parked.remove(&gate.id);
+            continue;
+        };
+        if airport.parked.get(&gate.id).map(String::as_str) == Some(callsign) {
+            continue;
+        }
+        airport.parked.insert(gate.id.clone(), callsign.to_string());
+        if let Some(airline) = airline_of(callsign) {
+            *airport.usage.entry(gate.id.clone()).or_default().entry(airline).or_default() += 1;
+        }
+    }
+
+    airport.suggestions = suggest(&gates, &arrivals, &airport.usage, &airport.suggestions);
+}
+
+/// Forget suggestions (traffic stopped)
+pub fn clear(app: &AppHandle) {
+    *app.state::<GateSuggestionState>().active.write() = None;
+}
+
+/// Gate suggestions for arrivals to an airport (none unless it is the active airport)
+pub fn get_suggestions(app: &AppHandle, icao: &str) -> Result<Vec<GateSuggestion>> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    Ok(app
+        .state::<GateSuggestionState>()
+        .active
+        .read()
+        .as_ref()
+        .filter(|a| a.icao == icao)
+        .map(|a| a.suggestions.clone())
+        .unwrap_or_default())
+}
+
+/// Initialize gate suggestion state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_gate_suggestions(app: &AppHandle) {
+    app.manage(GateSuggestionState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get suggested gates for arrivals to an airport
+#[tauri::command]
+pub fn get_gate_suggestions(app: AppHandle, icao: String) -> Result<Vec<GateSuggestion>> {
+    get_suggestions(&app, &icao)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gate(id: &str, longitude: f64, airlines: &[&str], occupied_by: Option<&str>) -> Gate {
+        Gate {
+            id: id.to_string(),
+            name: None,
+            latitude: 42.3600,
+            longitude,
+            source: "imported".to_string(),
+            airlines: airlines.iter().map(|a| a.to_string()).collect(),
+            max_wingspan_m: None,
+            occupied_by: occupied_by.map(str::to_string),
+            occupied_since: None,
+        }
+    }
+
+    fn arrival(callsign: &str, wingspan_m: Option<f64>) -> Arrival {
+        Arrival {
+            callsign: callsign.to_string(),
+            airline: airline_of(callsign),
+            wingspan_m,
+        }
+    }
+
+    #[test]
+    fn prefers_airline_gates_and_neighbors() {
+        let gates = [
+            gate("A1", -71.0000, &[], None),
+            gate("B5", -71.0200, &["DAL"], None),
+            gate("B6", -71.0203, &["DAL"], Some("DAL9")),
+            gate("C1", -71.0400, &["JBU"], None),
+            gate("C2", -71.0403, &[], Some("JBU5")),
+        ];
+        let suggestions = suggest(
+            &gates,
+            &[arrival("DAL123", None), arrival("JBU77", None), arrival("DAL456", None)],
+            &HashMap::new(),
+            &[],
+        );
+        let by_callsign: HashMap<&str, (&str, &str)> = suggestions
+            .iter()
+            .map(|s| (s.callsign.as_str(), (s.gate.as_str(), s.reason.as_str())))
+            .collect();
+        assert_eq!(by_callsign["DAL123"], ("B5", "airline"));
+        assert_eq!(by_callsign["JBU77"], ("C1", "airline"));
+        // Only the open gate is left
+        assert_eq!(by_callsign["DAL456"].0, "A1");
+    }
+
+    #[test]
+    fn keeps_suggestions_and_respects_size() {
+        let mut gates = vec![gate("A1", -71.0000, &[], None), gate("A2", -71.0010, &[], None)];
+        gates[0].max_wingspan_m = Some(36.0);
+
+        // A 777 doesn't fit A1
+        let heavy = suggest(&gates, &[arrival("UAL1", Some(64.8))], &HashMap::new(), &[]);
+        assert_eq!(heavy[0].gate, "A2");
+
+        let previous = vec![GateSuggestion {
+            callsign: "N123AB".to_string(),
+            gate: "A1".to_string(),
+            reason: "free".to_string(),
+        }];
+        let kept = suggest(&gates, &[arrival("N123AB", None)], &HashMap::new(), &previous);
+        assert_eq!(kept, previous);
+
+        // Moved once the gate is taken
+        gates[0].occupied_by = Some("DAL9".to_string());
+        let moved = suggest(&gates, &[arrival("N123AB", None)], &HashMap::new(), &previous);
+        assert_eq!(moved[0].gate, "A2");
+    }
+
+    #[test]
+    fn learns_from_usage() {
+        let gates = [gate("A1", -71.0000, &[], None), gate("A2", -71.0100, &[], None)];
+        let usage = HashMap::from([("A2".to_string(), HashMap::from([("AAL".to_string(), 2)]))]);
+        let suggestions = suggest(&gates, &[arrival("AAL10", None)], &usage, &[]);
+        assert_eq!(suggestions[0].gate, "A2");
+        assert_eq!(suggestions[0].reason, "history");
+    }
+}
@@ -0,0 +1,409 @@
+//! Gate and stand positions with occupancy detection
+//!
+//! Gates come from an imported GeoJSON file (`gates/{ICAO}.geojson` in the app
+//! data folder, points with a `ref` or `name`) or, failing that, from the
+//! OpenStreetMap ground layout (`gate` and `parking_position` features).
+//!
+//! After every traffic poll, aircraft stopped on the ground are matched to the
+//! nearest gate within 40 m. Changes are emitted as `gate-occupancy-changed`
+//! and relayed to remote browsers over `/api/gates/ws`. Imported gates may
+//! list the airlines using them (`airlines`) and the largest wingspan they
+//! take (`maxWingspan`), which steer arrival gate suggestions.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+
+use crate::error::{Error, Result};
+use crate::geo;
+use crate::osm_layout;
+use crate::traffic::{Aircraft, TrafficSnapshot};
+use crate::weather;
+
+/// Aircraft within this distance of a gate position occupy it (meters)
+const GATE_MATCH_RADIUS_M: f64 = 40.0;
+
+/// Aircraft slower than this are considered stopped (knots)
+const STOPPED_MAX_GROUNDSPEED_KT: f64 = 3.0;
+
+const METERS_PER_NM: f64 = 1852.0;
+
+/// Gate or stand
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Gate {
+    /// Gate designator (e.g., "B5")
+    pub id: String,
+    pub name: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// "imported" or "osm"
+    pub source: String,
+    /// ICAO airline designators the gate is used by (imported `airlines`)
+    pub airlines: Vec<String>,
+    /// Largest wingspan the gate takes (imported `maxWingspan`, meters)
+    pub max_wingspan_m: Option<f64>,
+    /// Callsign of the aircraft at the gate
+    pub occupied_by: Option<String>,
+    /// When the aircraft was first seen at the gate (Unix ms)
+    pub occupied_since: Option<u64>,
+}
+
+/// Gate occupancy change, relayed to clients
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GateOccupancyEvent {
+    pub icao: String,
+    pub gate: String,
+    /// Aircraft now at the gate (None when it was vacated)
+    pub occupied_by: Option<String>,
+    /// Aircraft that left the gate
+    pub vacated_by: Option<String>,
+    /// Unix ms
+    pub time: u64,
+}
+
+/// Gates at the active airport
+struct AirportGates {
+    icao: String,
+    gates: Vec<Gate>,
+}
+
+/// Gate state (managed by Tauri)
+pub struct GateState {
+    active: RwLock<Option<AirportGates>>,
+    /// Occupancy changes for WebSocket relay
+    pub events: broadcast::Sender<GateOccupancyEvent>,
+}
+
+impl GateState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(256);
+        Self {
+            active: RwLock::new(None),
+            events,
+        }
+    }
+}
+
+impl Default for GateState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Get the imported gates file for an airport
+fn imported_path(app: &AppHandle, icao: &str) -> Result<PathBuf> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))?;
+    Ok(app_data.join("gates").join(format!("{}.geojson", icao)))
+}
+
+/// Representative position of a GeoJSON geometry (points as-is, lines by their middle vertex)
+fn geometry_position(geometry: &Value) -> Option<(f64, f64)> {
+    let coordinates = geometry.get("coordinates")?;
+    let point = match geometry.get("type")?.as_str()? {
+        "Point" => coordinates,
+        "LineString" => {
+            let points = coordinates.as_array()?;
+            points.get(points.len() / 2)?
+        }
+        _ => return None,
+    };
+    Some((point.get(1)?.as_f64()?, point.get(0)?.as_f64()?))
+}
+
+/// Extract gates from GeoJSON. OSM layouts are filtered to gates and stands;
+/// imported files may use any point with a ref, name or id.
+pub fn gates_from_geojson(geojson: &Value, source: &str) -> Vec<Gate> {
+    let mut gates: Vec<Gate> = geojson
+        .get("features")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|feature| {
+            let properties = feature.get("properties")?;
+            let text = |key: &str| properties.get(key).and_then(Value::as_str).map(str::to_string);
+            if source == "osm" && !matches!(text("kind").as_deref(), Some("gate") | Some("parking_position")) {
+                return None;
+            }
+            let (latitude, longitude) = geometry_position(feature.get("geometry")?)?;
+            let name = text("name");
+            let id = text("ref").or_else(|| name.clone()).or_else(|| text("id"))?;
+            // Airlines as a list or a comma/space separated string
+            let airlines: Vec<String> = match properties.get("airlines") {
+                Some(Value::Array(codes)) => codes.iter().filter_map(Value::as_str).map(str::to_string).collect(),
+                Some(Value::String(codes)) => codes.split([',', ' ']).map(str::to_string).collect(),
+                _ => Vec::new(),
+            };
+            Some(Gate {
+                id,
+                name,
+                latitude,
+                longitude,
+                source: source.to_string(),
+                airlines: airlines
+                    .into_iter()
+                    .map(|a| a.trim().to_uppercase())
+                    .filter(|a| !a.is_empty())
+                    .collect(),
+                max_wingspan_m: properties.get("maxWingspan").and_then(Value::as_f64),
+                occupied_by: None,
+                occupied_since: None,
+            })
+        })
+        .collect();
+
+    // OSM often maps the same stand as both a gate node and a parking position
+    gates.sort_by(|a, b| a.id.cmp(&b.id));
+    gates.dedup_by(|a, b| a.id == b.id);
+    gates
+}
+
+/// Load an airport's gates (imported file first, then the OSM layout)
+pub async fn load_gates(app: &AppHandle, icao: &str) -> Result<Vec<Gate>> {
+    let path = imported_path(app, icao)?;
+    if path.exists() {
+        let content = fs::read_to_string(&path)
+            .map_err(|e| Error::Io(format!("Failed to read imported gates: {}", e)))?;
+        let geojson: Value =
+            serde_json::from_str(&content)
+                .map_err(|e| Error::Internal(format!("Failed to parse imported gates: {}", e)))?;
+        return Ok(gates_from_geojson(&geojson, "imported"));
+    }
+    let layout = osm_layout::get_layout(app, icao, false).await?;
+    Ok(gates_from_geojson(&layout, "osm"))
+}
+
+/// Match stopped aircraft to the nearest free gate within range
+fn match_aircraft(gates: &[Gate], aircraft: &[Aircraft]) -> HashMap<String, String> {
+    let mut candidates: Vec<(f64, &str, &str)> = Vec::new();
+    for a in aircraft
+        .iter()
+        .filter(|a| a.on_ground && a.groundspeed_kt <= STOPPED_MAX_GROUNDSPEED_KT)
+    {
+        for gate in gates {
+            let (distance_nm, _) = geo::distance_and_bearing(gate.latitude, gate.longitude, a.latitude, a.longitude);
+            let distance_m = distance_nm * METERS_PER_NM;
+            if distance_m <= GATE_MATCH_RADIUS_M {
+                candidates.push((distance_m, gate.id.as_str(), a.callsign.as_str()));
+            }
+        }
+    }
+
+    // Closest pairs first, each gate and aircraft used once
+    candidates.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let mut occupancy: HashMap<String, String> = HashMap::new();
+    for (_, gate, callsign) in candidates {
+        if occupancy.contains_key(gate) || occupancy.values().any(|c| c == callsign) {
+            continue;
+        }
+        occupancy.insert(gate.to_string(), callsign.to_string());
+    }
+    occupancy
+}
+
+/// Update gate occupancy from a traffic snapshot (called after every traffic poll)
+pub async fn update(app: &AppHandle, snapshot: &TrafficSnapshot) {
+    let Some(icao) = snapshot.icao.clone() else {
+        return;
+    };
+    let state = app.state::<GateState>();
+
+    let loaded = state.active.read().as_ref().is_some_and(|a| a.icao == icao);
+    if !loaded {
+        match load_gates(app, &icao).await {
+            Ok(gates) => {
+                info!("[Gates] Loaded {} gates for {}", gates.len(), icao);
+                *state.active.write() = Some(AirportGates {
+                    icao: icao.clone(),
+                    gates,
+                });
+            }
+            Err(e) => {
+                warn!("[Gates] Failed to load gates for {}: {}", icao, e);
+                return;
+            }
+        }
+    }
+
+    let now = now_ms();
+    let mut events = Vec::new();
+    {
+        let mut active = state.active.write();
+        let Some(airport) = active.as_mut().filter(|a| a.icao == icao) else {
+            return;
+        };
+        let occupancy = match_aircraft(&airport.gates, &snapshot.aircraft);
+        for gate in &mut airport.gates {
+            let current = occupancy.get(&gate.id).cloned();
+            if current == gate.occupied_by {
+                continue;
+            }
+            events.push(GateOccupancyEvent {
+                icao: icao.clone(),
+                gate: gate.id.clone(),
+                occupied_by: current.clone(),
+                vacated_by: gate.occupied_by.take(),
+                time: now,
+            });
+            gate.occupied_since = current.as_ref().map(|_| now);
+            gate.occupied_by = current;
+        }
+    }
+
+    for event in events {
+        let _ = app.emit("gate-occupancy-changed", &event);
+        let _ = state.events.send(event);
+    }
+}
+
+/// Gates at the active airport with current occupancy (None if not loaded for it)
+pub fn active_gates(app: &AppHandle, icao: &str) -> Option<Vec<Gate>> {
+    app.state::<GateState>()
+        .active
+        .read()
+        .as_ref()
+        .filter(|a| a.icao == icao)
+        .map(|a| a.gates.clone())
+}
+
+/// Gates at an airport, with occupancy if it is the active airport
+pub async fn get_gates(app: &AppHandle, icao: &str) -> Result<Vec<Gate>> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    if let Some(airport) = app
+        .state::<GateState>()
+        .active
+        .read()
+        .as_ref()
+        .filter(|a| a.icao == icao)
+    {
+        return Ok(airport.gates.clone());
+    }
+    load_gates(app, &icao).await
+}
+
+/// Import gate positions from a GeoJSON file (points with a ref or name),
+/// replacing the OpenStreetMap gates for an airport
+pub fn import_gates_file(app: &AppHandle, icao: &str, source: &Path) -> Result<usize> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    let content = fs::read_to_string(source).map_err(|e| Error::Io(format!("Failed to read {:?}: {}", source, e)))?;
+    let geojson: Value = serde_json::from_str(&content)
+        .map_err(|e| Error::InvalidInput(format!("Failed to parse GeoJSON: {}", e)))?;
+    let count = gates_from_geojson(&geojson, "imported").len();
+    if count == 0 {
+        return Err(Error::invalid_input("No gates found (expected points with a ref or name)"));
+    }
+
+    let path = imported_path(app, &icao)?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| Error::Io(format!("Failed to create gates folder: {}", e)))?;
+    }
+    fs::write(&path, content).map_err(|e| Error::Io(format!("Failed to save gates: {}", e)))?;
+
+    // Reload on the next traffic update
+    let state = app.state::<GateState>();
+    let mut active = state.active.write();
+    if active.as_ref().is_some_and(|a| a.icao == icao) {
+        *active = None;
+    }
+    info!("[Gates] Imported {} gates for {}", count, icao);
+    Ok(count)
+}
+
+/// Initialize gate state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_gates(app: &AppHandle) {
+    app.manage(GateState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get gate positions at an airport, with the aircraft occupying them
+#[tauri::command]
+pub async fn get_airport_gates(app: AppHandle, icao: String) -> Result<Vec<Gate>> {
+    get_gates(&app, &icao).await
+}
+
+/// Import gate positions for an airport from a GeoJSON file
+#[tauri::command]
+pub fn import_gates(app: AppHandle, icao: String, path: String) -> Result<usize> {
+    import_gates_file(&app, &icao, Path::new(&path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traffic::test_aircraft;
+    use serde_json::json;
+
+    #[test]
+    fn matches_stopped_aircraft_to_gates() {
+        let layout = json!({"features": [
+            {"geometry": {"type": "Point", "coordinates": [-71.0100, 42.3600]}, "properties": {"kind": "gate", "ref": "B5"}},
+            {"geometry": {"type": "LineString", "coordinates": [[-71.0110, 42.3600], [-71.0105, 42.3600], [-71.0100, 42.3600]]},
+             "properties": {"kind": "parking_position", "ref": "B6"}},
+            {"geometry": {"type": "Point", "coordinates": [-71.0100, 42.3600]}, "properties": {"kind": "gate", "ref": "B5"}},
+            {"geometry": {"type": "Point", "coordinates": [-71.0, 42.0]}, "properties": {"kind": "holding_position", "ref": "K1"}}
+        ]});
+        let gates = gates_from_geojson(&layout, "osm");
+        assert_eq!(gates.iter().map(|g| g.id.as_str()).collect::<Vec<_>>(), ["B5", "B6"]);
+        assert_eq!(gates[1].longitude, -71.0105);
+
+        let aircraft = |callsign: &str, longitude: f64, groundspeed_kt: f64| Aircraft {
+            latitude: 42.3600,
+            longitude,
+            altitude_ft: 20.0,
+            groundspeed_kt,
+            squawk: "2000".to_string(),
+            distance_nm: 0.0,
+            on_ground: true,
+            ..test_aircraft(callsign)
+        };
+        let occupancy = match_aircraft(
+            &gates,
+            &[
+                aircraft("DAL123", -71.0101, 0.0),
+                aircraft("JBU9", -71.0104, 0.0),
+                aircraft("TAXI1", -71.0105, 15.0),
+            ],
+        );
+        assert_eq!(occupancy.get("B5").map(String::as_str), Some("DAL123"));
+        assert_eq!(occupancy.get("B6").map(String::as_str), Some("JBU9"));
+        assert_eq!(occupancy.len(), 2);
+    }
+
+    #[test]
+    fn parses_imported_gate_attributes() {
+        let imported = json!({"features": [
+            {"geometry": {"type": "Point", "coordinates": [-71.01, 42.36]},
+             "properties": {"ref": "B5", "airlines": "dal, JBU", "maxWingspan": 36}},
+            {"geometry": {"type": "Point", "coordinates": [-71.02, 42.36]},
+             "properties": {"ref": "E1", "airlines": ["BAW"]}}
+        ]});
+        let gates = gates_from_geojson(&imported, "imported");
+        assert_eq!(gates[0].airlines, ["DAL", "JBU"]);
+        assert_eq!(gates[0].max_wingspan_m, Some(36.0));
+        assert_eq!(gates[1].airlines, ["BAW"]);
+        assert_eq!(gates[1].max_wingspan_m, None);
+    }
+}
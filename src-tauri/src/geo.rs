@@ -0,0 +1,74 @@
+//! Great-circle geometry shared by the position-based services
+
+/// Mean Earth radius (nm)
+pub const EARTH_RADIUS_NM: f64 = 3440.065;
+
+/// Great-circle distance (nm) and initial bearing (degrees true) between two points
+pub fn distance_and_bearing(from_lat: f64, from_lon: f64, to_lat: f64, to_lon: f64) -> (f64, f64) {
+    let (lat1, lat2) = (from_lat.to_radians(), to_lat.to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = (to_lon - from_lon).to_radians();
+
+    let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    let distance = 2.0 * EARTH_RADIUS_NM * a.sqrt().atan2((1.0 - a).sqrt());
+
+    let y = dlon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos();
+    let bearing = y.atan2(x).to_degrees().rem_euclid(360.0);
+
+    (distance, bearing)
+}
+
+/// Point reached from a start point along a bearing (degrees true) after a distance (nm)
+pub fn destination(lat: f64, lon: f64, bearing: f64, distance_nm: f64) -> (f64, f64) {
+    let angular = distance_nm / EARTH_RADIUS_NM;
+    let (lat1, lon1, bearing) = (lat.to_radians(), lon.to_radians(), bearing.to_radians());
+
+    let lat2 = (lat1.sin() * angular.cos() + lat1.cos() * angular.sin() * bearing.cos()).asin();
+    let lon2 = lon1 + (bearing.sin() * angular.sin() * lat1.cos()).atan2(angular.cos() - lat1.sin() * lat2.sin());
+
+    (lat2.to_degrees(), (lon2.to_degrees() + 540.0).rem_euclid(360.0) - 180.0)
+}
+
+/// Smallest angle between two headings (degrees)
+pub fn heading_difference(a: f64, b: f64) -> f64 {
+    let diff = (a - b).rem_euclid(360.0);
+    diff.min(360.0 - diff)
+}
+
+/// Whether a point is inside a polygon ring of [longitude, latitude] pairs (ray casting)
+pub fn point_in_ring(lat: f64, lon: f64, ring: &[[f64; 2]]) -> bool {
+    let mut inside = false;
+    let mut j = ring.len().wrapping_sub(1);
+    for i in 0..ring.len() {
+        let ([xi, yi], [xj, yj]) = (ring[i], ring[j]);
+        if (yi > lat) != (yj > lat) && lon < (xj - xi) * (lat - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+        j = i;
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn computes_distance_and_bearing() {
+        // 1 degree of latitude is 60 nm
+        let (distance, bearing) = distance_and_bearing(37.0, -122.0, 38.0, -122.0);
+        assert!((distance - 60.0).abs() < 0.1);
+        assert!(bearing.abs() < 0.01);
+
+        let (_, bearing) = distance_and_bearing(0.0, 0.0, 0.0, 1.0);
+        assert!((bearing - 90.0).abs() < 0.01);
+
+        let (lat, lon) = destination(37.0, -122.0, 0.0, 60.0);
+        assert!((lat - 38.0).abs() < 0.01 && (lon + 122.0).abs() < 1e-9);
+
+        let square = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [0.0, 0.0]];
+        assert!(point_in_ring(0.5, 0.5, &square));
+        assert!(!point_in_ring(1.5, 0.5, &square));
+    }
+}
@@ -0,0 +1,281 @@
+//! Go-around detection
+//!
+//! Airborne aircraft lined up with a runway end, before its threshold and
+//! below 1500 ft above the airport, are on short final. While they stay lined
+//! up (up to 2 nm past the far end), their lowest altitude is kept; climbing
+//! 200 ft above it without landing is a go-around. Detected go-arounds are
+//! emitted as `go-around` and relayed over `/api/go-arounds/ws`, and kept for
+//! the session (`/api/go-arounds`). Session statistics count them from the
+//! recording with the same rule.
+
+use std::collections::HashMap;
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::info;
+
+use crate::runways::{self, Runway};
+use crate::scripts;
+use crate::traffic::TrafficSnapshot;
+
+/// Go-arounds are still seen this far past the far end of the runway (nm)
+const MISSED_APPROACH_MAX_AFTER_NM: f64 = 2.0;
+
+/// Climb above the lowest altitude on short final that makes a go-around (feet)
+const GO_AROUND_MIN_CLIMB_FT: f64 = 200.0;
+
+/// Go-arounds kept for the session
+const MAX_LOGGED: usize = 500;
+
+/// Aircraft on short final
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortFinal {
+    /// Runway end (e.g., "27R")
+    pub runway: String,
+    /// Lowest altitude seen since joining short final (feet MSL)
+    pub lowest_ft: f64,
+}
+
+/// Position sample of an aircraft
+#[derive(Debug, Clone, Copy)]
+pub struct Sample {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Feet MSL
+    pub altitude_ft: f64,
+    /// Track or heading (degrees true)
+    pub track: f64,
+    pub on_ground: bool,
+}
+
+/// Aircraft going around
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoAround {
+    pub icao: String,
+    pub callsign: String,
+    pub aircraft_type: Option<String>,
+    /// Runway end the approach was to (e.g., "27R")
+    pub runway: String,
+    /// Unix ms
+    pub time: u64,
+    /// Lowest altitude before climbing away (feet MSL)
+    pub lowest_altitude_ft: f64,
+}
+
+/// Go-arounds in this session
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GoArounds {
+    pub go_arounds: Vec<GoAround>,
+}
+
+/// Go-around state (managed by Tauri)
+pub struct GoAroundState {
+    icao: RwLock<Option<String>>,
+    /// Aircraft on short final at the active airport
+    approaches: RwLock<HashMap<String, ShortFinal>>,
+    log: RwLock<Vec<GoAround>>,
+    /// Go-arounds for WebSocket relay
+    pub events: broadcast::Sender<GoAround>,
+}
+
+impl GoAroundState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(256);
+        Self {
+            icao: RwLock::new(None),
+            approaches: RwLock::new(HashMap::new()),
+            log: RwLock::new(Vec::new()),
+            events,
+        }
+    }
+}
+
+/// Follow an aircraft's approach with a new sample. Returns the short final
+/// it went around from, if it just did.
+pub fn observe(
+    approach: &mut Option<ShortFinal>,
+    sample: Sample,
+    runways: &[Runway],
+    elevation_ft: f64,
+) -> Option<ShortFinal> {
+    if sample.on_ground {
+        *approach = None;
+        return None;
+    }
+    let alignment = runways::final_approach_end(
+        runways,
+        sample.latitude,
+        sample.longitude,
+        sample.track,
+        MISSED_APPROACH_MAX_AFTER_NM,
+    );
+
+    match (approach.as_mut(), alignment) {
+        (Some(current), Some(a)) if a.end.ident == current.runway => {
+            if sample.altitude_ft >= current.lowest_ft + GO_AROUND_MIN_CLIMB_FT {
+                return approach.take();
+            }
+            current.lowest_ft = current.lowest_ft.min(sample.altitude_ft);
+            None
+        }
+        (_, Some(a)) if runways::is_short_final(&a, sample.altitude_ft - elevation_ft) => {
+            *approach = Some(ShortFinal {
+                runway: a.end.ident.clone(),
+                lowest_ft: sample.altitude_ft,
+            });
+            None
+        }
+        _ => {
+            *approach = None;
+            None
+        }
+    }
+}
+
+/// Update approaches from a traffic poll and emit go-arounds
+pub fn update(app: &AppHandle, snapshot: &TrafficSnapshot, runways: &[Runway], elevation_ft: f64) {
+    let Some(icao) = snapshot.icao.clone() else {
+        return;
+    };
+    let now = snapshot.updated_at.unwrap_or_default();
+    let state = app.state::<GoAroundState>();
+    let mut approaches = state.approaches.write();
+    {
+        let mut current = state.icao.write();
+        if current.as_deref() != Some(icao.as_str()) {
+            approaches.clear();
+            *current = Some(icao.clone());
+        }
+    }
+
+    let mut go_arounds = Vec::new();
+    let mut next = HashMap::new();
+    for a in &snapshot.aircraft {
+        let mut approach = approaches.remove(&a.callsign);
+        let sample = Sample {
+            latitude: a.latitude,
+            longitude: a.longitude,
+            altitude_ft: a.altitude_ft,
+            track: a.track.unwrap_or(a.heading),
+            on_ground: a.on_ground,
+        };
+        if let Some(missed) = observe(&mut approach, sample, runways, elevation_ft) {
+            go_arounds.push(GoAround {
+                icao: icao.clone(),
+                callsign: a.callsign.clone(),
+                aircraft_type: a.aircraft_type.clone(),
+                runway: missed.runway,
+                time: now,
+                lowest_altitude_ft: missed.lowest_ft.round(),
+            });
+        }
+        if let Some(approach) = approach {
+            next.insert(a.callsign.clone(), approach);
+        }
+    }
+    *approaches = next;
+    drop(approaches);
+
+    for go_around in go_arounds {
+        info!(
+            "[GoAround] {} went around from runway {} at {}",
+            go_around.callsign, go_around.runway, icao
+        );
+        let _ = app.emit("go-around", &go_around);
+        scripts::alert(app, "go-around", &go_around);
+        let _ = state.events.send(go_around.clone());
+        let mut log = state.log.write();
+        log.push(go_around);
+        if log.len() > MAX_LOGGED {
+            log.remove(0);
+        }
+    }
+}
+
+/// Forget aircraft on short final (no active airport or traffic)
+pub fn clear(app: &AppHandle) {
+    let state = app.state::<GoAroundState>();
+    state.approaches.write().clear();
+    *state.icao.write() = None;
+}
+
+/// Go-arounds seen in this session
+pub fn get_go_arounds(app: &AppHandle) -> GoArounds {
+    GoArounds {
+        go_arounds: app.state::<GoAroundState>().log.read().clone(),
+    }
+}
+
+/// Initialize go-around state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_go_arounds(app: &AppHandle) {
+    app.manage(GoAroundState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the go-arounds seen in this session
+#[tauri::command]
+pub fn get_session_go_arounds(app: AppHandle) -> GoArounds {
+    get_go_arounds(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_climb_on_short_final() {
+        let runways = [runways::test_runway()];
+        // Nautical miles south of the runway 36 threshold
+        let sample = |nm_before: f64, altitude_ft: f64, on_ground: bool| Sample {
+            latitude: 42.0 - nm_before / 60.0,
+            longitude: -71.0,
+            altitude_ft,
+            track: 0.0,
+            on_ground,
+        };
+        let fly = |samples: &[Sample]| {
+            let mut approach = None;
+            samples
+                .iter()
+                .filter_map(|s| observe(&mut approach, *s, &runways, 20.0))
+                .collect::<Vec<_>>()
+        };
+
+        // Descends to 300 ft over the threshold, then climbs out over the runway
+        let missed = fly(&[
+            sample(3.0, 1000.0, false),
+            sample(1.0, 350.0, false),
+            sample(0.0, 300.0, false),
+            sample(-1.0, 800.0, false),
+        ]);
+        assert_eq!(
+            missed,
+            vec![ShortFinal {
+                runway: "36".to_string(),
+                lowest_ft: 300.0
+            }]
+        );
+
+        // Landing, and a departure climbing off the same runway
+        assert!(fly(&[
+            sample(1.0, 350.0, false),
+            sample(0.0, 20.0, true),
+            sample(-1.0, 20.0, true)
+        ])
+        .is_empty());
+        assert!(fly(&[
+            sample(-0.5, 20.0, true),
+            sample(-1.0, 300.0, false),
+            sample(-2.0, 1200.0, false)
+        ])
+        .is_empty());
+    }
+}
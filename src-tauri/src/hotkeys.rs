@@ -0,0 +1,138 @@
+//! System-wide hotkeys
+//!
+//! Registers the bindings from `hotkeys` in global settings with the OS, so
+//! bookmarks, the 2D/3D toggle and instant replay can be triggered while
+//! another app (a radar client, voice software) has focus. Each binding runs a
+//! control action (see control.rs). Bindings are re-registered whenever global
+//! settings are saved; ones that fail (invalid accelerator, taken by another
+//! app) are reported by `get_hotkey_status`.
+
+use std::str::FromStr;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+use tracing::{info, warn};
+
+use crate::control::{self, ControlAction};
+use crate::{GlobalHotkeySettings, HotkeyBinding};
+
+/// Registration result for one binding
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeyStatus {
+    #[serde(flatten)]
+    pub binding: HotkeyBinding,
+    pub registered: bool,
+    pub error: Option<String>,
+}
+
+pub struct HotkeyState {
+    status: Mutex<Vec<HotkeyStatus>>,
+}
+
+fn control_action(binding: &HotkeyBinding) -> ControlAction {
+    ControlAction {
+        action: binding.action.clone(),
+        value: binding.value.clone(),
+    }
+}
+
+/// Check a binding's action and parse its accelerator
+fn parse_binding(binding: &HotkeyBinding) -> Result<Shortcut, String> {
+    control::validate(&control_action(binding))?;
+    Shortcut::from_str(&binding.shortcut).map_err(|e| format!("Invalid shortcut '{}': {}", binding.shortcut, e))
+}
+
+fn run(app: &AppHandle, binding: &HotkeyBinding) {
+    if let Err(e) = control::trigger(app, control_action(binding)) {
+        warn!("[Hotkeys] {}: {}", binding.shortcut, e);
+    }
+}
+
+/// Register the configured hotkeys, replacing the previous ones
+/// (called when global settings are written)
+pub fn apply_settings(app: &AppHandle, settings: &GlobalHotkeySettings) {
+    let Some(state) = app.try_state::<HotkeyState>() else {
+        return;
+    };
+    let shortcuts = app.global_shortcut();
+    if let Err(e) = shortcuts.unregister_all() {
+        warn!("[Hotkeys] Failed to unregister hotkeys: {}", e);
+    }
+
+    let mut status = Vec::with_capacity(settings.bindings.len());
+    for binding in &settings.bindings {
+        let result = if settings.enabled {
+            parse_binding(binding).and_then(|shortcut| {
+                let handler_binding = binding.clone();
+                shortcuts
+                    .on_shortcut(shortcut, move |app, _, event| {
+                        if event.state() == ShortcutState::Pressed {
+                            run(app, &handler_binding);
+                        }
+                    })
+                    .map_err(|e| format!("Failed to register '{}': {}", binding.shortcut, e))
+            })
+        } else {
+            Ok(())
+        };
+        if let Err(e) = &result {
+            warn!("[Hotkeys] {}", e);
+        }
+        status.push(HotkeyStatus {
+            binding: binding.clone(),
+            registered: settings.enabled && result.is_ok(),
+            error: result.err(),
+        });
+    }
+
+    if settings.enabled {
+        let registered = status.iter().filter(|s| s.registered).count();
+        info!("[Hotkeys] Registered {} of {} hotkeys", registered, status.len());
+    }
+    *state.status.lock() = status;
+}
+
+/// Initialize hotkey state and register the saved hotkeys.
+/// Call this in the Tauri setup closure (after the global shortcut plugin).
+pub fn init_hotkeys(app: &AppHandle) {
+    app.manage(HotkeyState {
+        status: Mutex::new(Vec::new()),
+    });
+    let settings = crate::read_global_settings(app.clone())
+        .map(|s| s.hotkeys)
+        .unwrap_or_default();
+    apply_settings(app, &settings);
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get each configured hotkey and whether it is registered
+#[tauri::command]
+pub fn get_hotkey_status(app: AppHandle) -> Vec<HotkeyStatus> {
+    app.try_state::<HotkeyState>()
+        .map(|state| state.status.lock().clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_incomplete_bindings() {
+        let binding = |action: &str, value: Option<&str>| HotkeyBinding {
+            shortcut: "CommandOrControl+Alt+1".to_string(),
+            action: action.to_string(),
+            value: value.map(str::to_string),
+        };
+        assert!(parse_binding(&binding("explode", None)).is_err());
+        assert!(parse_binding(&binding("bookmark", Some(" "))).is_err());
+        assert!(parse_binding(&binding("bookmark", Some("Tower North"))).is_ok());
+        assert!(parse_binding(&binding("instantReplay", None)).is_ok());
+    }
+}
@@ -0,0 +1,38 @@
+//! Shared HTTP client for outbound requests
+//!
+//! All outbound HTTP (weather, charts, NOTAMs, RealTraffic, tiles, the
+//! `fetch_url` command and `/api/proxy`) goes through one `reqwest::Client`,
+//! so connections to the same host are pooled and TLS sessions are reused
+//! instead of being set up again for every request. Most fetches are plain
+//! functions without an `AppHandle`, so the client lives in a process-wide
+//! static rather than in managed state.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use tracing::warn;
+
+/// Time allowed to connect (requests that download large files set their own
+/// overall timeouts)
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// How long idle pooled connections are kept open
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+/// The shared client (created on first use)
+pub fn client() -> &'static reqwest::Client {
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .user_agent(concat!("TowerCab3D/", env!("CARGO_PKG_VERSION")))
+            .connect_timeout(CONNECT_TIMEOUT)
+            .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+            .tcp_keepalive(Duration::from_secs(60))
+            .build()
+            .unwrap_or_else(|e| {
+                warn!("[Http] Failed to configure HTTP client, using defaults: {}", e);
+                reqwest::Client::new()
+            })
+    })
+}
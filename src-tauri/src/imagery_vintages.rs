@@ -0,0 +1,234 @@
+//! Historical imagery vintages
+//!
+//! Some imagery providers keep earlier versions of their imagery:
+//!
+//! - Sentinel-2 cloudless (EOX) has a mosaic per year
+//! - Esri World Imagery Wayback has every release of World Imagery since 2014,
+//!   listed in its config file
+//!
+//! A vintage can be chosen per airport (`mapProviders.imageryVintages`, ICAO
+//! to vintage ID), so the view can match current construction or avoid snow
+//! in summer. Vintage IDs name their provider (`sentinel-2020`,
+//! `wayback-12457`) and are ignored while another provider is selected.
+//! Clients request imagery tiles with `?vintage=<id>`; the host turns the ID
+//! into the provider's URL, so only known layers are ever proxied.
+
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use serde_json::Value;
+use tauri::AppHandle;
+use tracing::warn;
+
+use crate::error::{Error, Result};
+use crate::http_client;
+use crate::map_providers::{self, ImageryProvider, MapProviderSettings};
+use crate::weather;
+
+const SENTINEL_URL: &str = "https://tiles.maps.eox.at/wmts/1.0.0/{layer}/default/g/{z}/{y}/{x}.jpg";
+
+/// Years with a Sentinel-2 cloudless mosaic (there is none for 2017)
+const SENTINEL_YEARS: [u32; 8] = [2016, 2018, 2019, 2020, 2021, 2022, 2023, 2024];
+
+const WAYBACK_CONFIG_URL: &str = "https://s3-us-west-2.amazonaws.com/config.maptiles.arcgis.com/waybackconfig.json";
+const WAYBACK_TILE_URL: &str = "https://wayback.maptiles.arcgis.com/arcgis/rest/services/World_Imagery/WMTS/1.0.0/default028mm/MapServer/tile/{release}/{z}/{y}/{x}";
+
+/// Refetch the Wayback release list after this
+const WAYBACK_CONFIG_MAX_AGE: Duration = Duration::from_secs(24 * 3600);
+
+/// Wayback releases with when they were fetched
+static WAYBACK_RELEASES: Mutex<Option<(Instant, Vec<ImageryVintage>)>> = Mutex::new(None);
+
+/// An earlier version of a provider's imagery
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageryVintage {
+    /// `<provider>-<release>`, stored in `mapProviders.imageryVintages`
+    pub id: String,
+    /// Capture year or release date (YYYY-MM-DD)
+    pub date: String,
+}
+
+/// Whether a provider has vintages to choose from
+pub fn has_vintages(provider: ImageryProvider) -> bool {
+    matches!(provider, ImageryProvider::Sentinel | ImageryProvider::Wayback)
+}
+
+fn sentinel_layer(year: u32) -> String {
+    // The first mosaic predates the year suffix
+    if year == 2016 {
+        "s2cloudless_3857".to_string()
+    } else {
+        format!("s2cloudless-{}_3857", year)
+    }
+}
+
+/// URL template of a vintage, if the ID is a known vintage of the provider
+pub fn template(provider: ImageryProvider, id: &str) -> Option<String> {
+    let (prefix, release) = id.split_once('-')?;
+    let release: u32 = release.parse().ok()?;
+    match (provider, prefix) {
+        (ImageryProvider::Sentinel, "sentinel") if SENTINEL_YEARS.contains(&release) => {
+            Some(SENTINEL_URL.replace("{layer}", &sentinel_layer(release)))
+        }
+        (ImageryProvider::Wayback, "wayback") => Some(WAYBACK_TILE_URL.replace("{release}", &release.to_string())),
+        _ => None,
+    }
+}
+
+/// The vintage chosen for an airport, if it belongs to the selected provider
+pub fn for_airport<'a>(settings: &'a MapProviderSettings, icao: &str) -> Option<&'a str> {
+    let icao = weather::normalize_icao(icao).ok()?;
+    let id = settings.imagery_vintages.get(&icao)?;
+    template(settings.imagery, id).map(|_| id.as_str())
+}
+
+/// Wayback releases from its config, newest first
+fn parse_wayback_config(config: &Value) -> Vec<ImageryVintage> {
+    let Some(releases) = config.as_object() else {
+        return Vec::new();
+    };
+    let mut vintages: Vec<ImageryVintage> = releases
+        .iter()
+        .filter_map(|(release, item)| {
+            let release: u32 = release.parse().ok()?;
+            // "World Imagery (Wayback 2014-02-20)"
+            let title = item["itemTitle"].as_str()?;
+            let date = title.rsplit(' ').next()?.trim_end_matches(')');
+            Some(ImageryVintage {
+                id: format!("wayback-{}", release),
+                date: date.to_string(),
+            })
+        })
+        .collect();
+    vintages.sort_by(|a, b| b.date.cmp(&a.date));
+    vintages
+}
+
+async fn wayback_releases() -> Result<Vec<ImageryVintage>> {
+    if let Some((fetched, releases)) = WAYBACK_RELEASES.lock().as_ref() {
+        if fetched.elapsed() < WAYBACK_CONFIG_MAX_AGE {
+            return Ok(releases.clone());
+        }
+    }
+    let fetched = async {
+        let response = http_client::client()
+            .get(WAYBACK_CONFIG_URL)
+            .timeout(Duration::from_secs(30))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| Error::Upstream(format!("Failed to fetch Wayback releases: {}", e)))?;
+        let config: Value = response
+            .json()
+            .await
+            .map_err(|e| Error::Upstream(format!("Invalid Wayback config: {}", e)))?;
+        Ok::<_, Error>(parse_wayback_config(&config))
+    }
+    .await;
+
+    let mut cached = WAYBACK_RELEASES.lock();
+    match fetched {
+        Ok(releases) => {
+            *cached = Some((Instant::now(), releases.clone()));
+            Ok(releases)
+        }
+        // Keep offering the releases we know of while offline
+        Err(e) => match cached.as_ref() {
+            Some((_, releases)) => {
+                warn!("[ImageryVintages] {}", e);
+                Ok(releases.clone())
+            }
+            None => Err(e),
+        },
+    }
+}
+
+/// Vintages of a provider, newest first (empty if it has none)
+pub async fn list(provider: ImageryProvider) -> Result<Vec<ImageryVintage>> {
+    match provider {
+        ImageryProvider::Sentinel => Ok(SENTINEL_YEARS
+            .iter()
+            .rev()
+            .map(|year| ImageryVintage {
+                id: format!("sentinel-{}", year),
+                date: year.to_string(),
+            })
+            .collect()),
+        ImageryProvider::Wayback => wayback_releases().await,
+        _ => Ok(Vec::new()),
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Imagery vintages of a provider (default: the selected one)
+#[tauri::command]
+pub async fn list_imagery_vintages(
+    app: AppHandle,
+    provider: Option<ImageryProvider>,
+) -> Result<Vec<ImageryVintage>> {
+    list(provider.unwrap_or_else(|| map_providers::settings(&app).imagery)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn resolves_vintages_of_the_selected_provider() {
+        assert_eq!(
+            template(ImageryProvider::Sentinel, "sentinel-2020").as_deref(),
+            Some("https://tiles.maps.eox.at/wmts/1.0.0/s2cloudless-2020_3857/default/g/{z}/{y}/{x}.jpg")
+        );
+        assert!(template(ImageryProvider::Sentinel, "sentinel-2016")
+            .unwrap()
+            .contains("/s2cloudless_3857/"));
+        assert_eq!(template(ImageryProvider::Sentinel, "sentinel-2017"), None);
+        assert!(template(ImageryProvider::Wayback, "wayback-12457")
+            .unwrap()
+            .contains("/tile/12457/{z}/{y}/{x}"));
+        assert_eq!(template(ImageryProvider::Wayback, "wayback-../x"), None);
+        assert_eq!(template(ImageryProvider::Maptiler, "wayback-12457"), None);
+
+        let mut settings = MapProviderSettings {
+            imagery: ImageryProvider::Sentinel,
+            ..Default::default()
+        };
+        settings
+            .imagery_vintages
+            .insert("KSFO".to_string(), "sentinel-2019".to_string());
+        settings
+            .imagery_vintages
+            .insert("KOAK".to_string(), "wayback-12457".to_string());
+        assert_eq!(for_airport(&settings, "ksfo"), Some("sentinel-2019"));
+        assert_eq!(for_airport(&settings, "KOAK"), None);
+        assert_eq!(for_airport(&settings, "KLAX"), None);
+    }
+
+    #[test]
+    fn lists_wayback_releases_newest_first() {
+        let config = json!({
+            "10": {"itemTitle": "World Imagery (Wayback 2014-02-20)"},
+            "26120": {"itemTitle": "World Imagery (Wayback 2023-08-10)"},
+            "meta": {"itemTitle": "Not a release"}
+        });
+        assert_eq!(
+            parse_wayback_config(&config),
+            vec![
+                ImageryVintage {
+                    id: "wayback-26120".to_string(),
+                    date: "2023-08-10".to_string()
+                },
+                ImageryVintage {
+                    id: "wayback-10".to_string(),
+                    date: "2014-02-20".to_string()
+                },
+            ]
+        );
+    }
+}
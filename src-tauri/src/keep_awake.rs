@@ -0,0 +1,277 @@
+//! Keep the display and system awake
+//!
+//! A wall display or cab PC blanking mid-event is hard to recover from, so the
+//! app can hold a sleep inhibitor either for as long as it runs ("always") or
+//! only while a replay is playing or a timelapse is recording ("whileActive").
+//! A background task re-checks every few seconds; settings changes apply
+//! immediately.
+//!
+//! - Windows: `SetThreadExecutionState` on a thread that holds the request
+//! - macOS: a `caffeinate -d -i -w <pid>` child process
+//! - Linux: `systemd-inhibit` wrapping a process that exits with the app
+//!
+//! The child processes watch the app's PID, so the inhibitor is released even
+//! if the app crashes.
+
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+use crate::error::Error;
+use crate::supervisor::{self, Restart};
+use crate::{debug_stats, replay, timelapse, GlobalPowerSettings};
+
+/// How often the task re-checks replay and timelapse activity
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+const MODES: [&str; 3] = ["off", "always", "whileActive"];
+
+/// Keep-awake status
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeepAwakeStatus {
+    pub mode: String,
+    /// An inhibitor is currently held
+    pub active: bool,
+    /// Why (e.g. "replay playing"), when active
+    pub reason: Option<String>,
+    /// Last error acquiring the inhibitor
+    pub error: Option<String>,
+}
+
+struct KeepAwake {
+    mode: String,
+    inhibitor: Option<Inhibitor>,
+    reason: Option<String>,
+    error: Option<String>,
+}
+
+pub struct KeepAwakeState {
+    inner: Mutex<KeepAwake>,
+}
+
+/// Why the app should stay awake in `mode`, or None
+fn wanted_reason(mode: &str, replay_playing: bool, timelapse_active: bool) -> Option<&'static str> {
+    match mode {
+        "always" => Some("app running"),
+        "whileActive" if replay_playing => Some("replay playing"),
+        "whileActive" if timelapse_active => Some("timelapse recording"),
+        _ => None,
+    }
+}
+
+/// Acquire or release the inhibitor to match the mode and current activity
+fn refresh(app: &AppHandle) {
+    let Some(state) = app.try_state::<KeepAwakeState>() else {
+        return;
+    };
+    let replay_playing = replay::get_status(app).playing;
+    let timelapse_active = timelapse::get_status(app).active;
+
+    let mut inner = state.inner.lock();
+    let reason = wanted_reason(&inner.mode, replay_playing, timelapse_active);
+    match (reason, inner.inhibitor.is_some()) {
+        (Some(reason), false) => match Inhibitor::acquire(reason) {
+            Ok(inhibitor) => {
+                info!("[KeepAwake] Preventing sleep ({})", reason);
+                inner.inhibitor = Some(inhibitor);
+                inner.error = None;
+            }
+            Err(e) => {
+                // Only log once per failure, the task retries every few seconds
+                if inner.error.as_deref() != Some(e.as_str()) {
+                    warn!("[KeepAwake] {}", e);
+                }
+                inner.error = Some(e);
+            }
+        },
+        (None, true) => {
+            info!("[KeepAwake] Allowing sleep again");
+            inner.inhibitor = None;
+        }
+        _ => {}
+    }
+    inner.reason = reason.filter(|_| inner.inhibitor.is_some()).map(str::to_string);
+}
+
+/// Apply saved power settings (called when global settings are written)
+pub fn apply_settings(app: &AppHandle, settings: &GlobalPowerSettings) {
+    if let Some(state) = app.try_state::<KeepAwakeState>() {
+        state.inner.lock().mode = settings.keep_awake.clone();
+    }
+    refresh(app);
+}
+
+/// Initialize keep-awake state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_keep_awake(app: &AppHandle) {
+    let mode = crate::read_global_settings(app.clone())
+        .map(|s| s.power.keep_awake)
+        .unwrap_or_else(|_| GlobalPowerSettings::default().keep_awake);
+    app.manage(KeepAwakeState {
+        inner: Mutex::new(KeepAwake {
+            mode,
+            inhibitor: None,
+            reason: None,
+            error: None,
+        }),
+    });
+}
+
+/// Follow replay and timelapse activity
+pub fn start_keep_awake_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn("keep-awake", Restart::Always, move || {
+        let app = app.clone();
+        async move {
+            let mut tick = tokio::time::interval(CHECK_INTERVAL);
+            loop {
+                tick.tick().await;
+                refresh(&app);
+                let error = app
+                    .try_state::<KeepAwakeState>()
+                    .and_then(|state| state.inner.lock().error.clone());
+                debug_stats::task_ran("keep-awake", CHECK_INTERVAL, error);
+            }
+        }
+    });
+}
+
+// =============================================================================
+// PLATFORM INHIBITORS
+// =============================================================================
+
+/// Holds a sleep inhibitor until dropped
+#[cfg(windows)]
+struct Inhibitor {
+    // Dropping the sender wakes the holding thread, which clears the request
+    _release: std::sync::mpsc::Sender<()>,
+}
+
+#[cfg(windows)]
+impl Inhibitor {
+    fn acquire(_reason: &str) -> Result<Self, String> {
+        use windows_sys::Win32::System::Power::{
+            SetThreadExecutionState, ES_CONTINUOUS, ES_DISPLAY_REQUIRED, ES_SYSTEM_REQUIRED,
+        };
+
+        // The execution state belongs to the thread that set it, so hold it on a dedicated thread
+        let (release, released) = std::sync::mpsc::channel::<()>();
+        let (ready, acquired) = std::sync::mpsc::channel::<bool>();
+        std::thread::Builder::new()
+            .name("keep-awake".to_string())
+            .spawn(move || {
+                // SAFETY: plain Win32 call without pointers
+                let previous =
+                    unsafe { SetThreadExecutionState(ES_CONTINUOUS | ES_SYSTEM_REQUIRED | ES_DISPLAY_REQUIRED) };
+                let _ = ready.send(previous != 0);
+                let _ = released.recv();
+                // SAFETY: as above
+                unsafe { SetThreadExecutionState(ES_CONTINUOUS) };
+            })
+            .map_err(|e| format!("Failed to start keep-awake thread: {}", e))?;
+
+        match acquired.recv() {
+            Ok(true) => Ok(Inhibitor { _release: release }),
+            _ => Err("SetThreadExecutionState failed".to_string()),
+        }
+    }
+}
+
+/// Holds a sleep inhibitor until dropped
+#[cfg(not(windows))]
+struct Inhibitor {
+    child: std::process::Child,
+}
+
+#[cfg(not(windows))]
+impl Inhibitor {
+    fn acquire(reason: &str) -> Result<Self, String> {
+        use std::process::{Command, Stdio};
+
+        let pid = std::process::id().to_string();
+        let mut command = if cfg!(target_os = "macos") {
+            // -d: display, -i: idle system sleep, -w: until this process exits
+            let mut command = Command::new("caffeinate");
+            command.args(["-d", "-i", "-w", &pid]);
+            command
+        } else {
+            // `tail --pid` exits with the app, which ends the inhibitor
+            let mut command = Command::new("systemd-inhibit");
+            command.args([
+                "--what=idle:sleep",
+                "--who=TowerCab 3D",
+                &format!("--why=Keeping the display awake ({})", reason),
+                "--mode=block",
+                "tail",
+                &format!("--pid={}", pid),
+                "-f",
+                "/dev/null",
+            ]);
+            command
+        };
+        let program = command.get_program().to_string_lossy().into_owned();
+        let child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| format!("Failed to start {}: {}", program, e))?;
+        Ok(Inhibitor { child })
+    }
+}
+
+#[cfg(not(windows))]
+impl Drop for Inhibitor {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the keep-awake mode and whether sleep is currently prevented
+#[tauri::command]
+pub fn get_keep_awake_status(app: AppHandle) -> KeepAwakeStatus {
+    let Some(state) = app.try_state::<KeepAwakeState>() else {
+        return KeepAwakeStatus::default();
+    };
+    let inner = state.inner.lock();
+    KeepAwakeStatus {
+        mode: inner.mode.clone(),
+        active: inner.inhibitor.is_some(),
+        reason: inner.reason.clone(),
+        error: inner.error.clone(),
+    }
+}
+
+/// Change the keep-awake mode now and save it to global settings
+#[tauri::command]
+pub fn set_keep_awake(app: AppHandle, mode: String) -> Result<KeepAwakeStatus, Error> {
+    if !MODES.contains(&mode.as_str()) {
+        return Err(Error::InvalidInput(format!("Unknown keep-awake mode '{}'", mode)));
+    }
+    let mut settings = crate::read_global_settings(app.clone())?;
+    settings.power.keep_awake = mode;
+    crate::write_global_settings(app.clone(), settings)?;
+    Ok(get_keep_awake_status(app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn follows_mode_and_activity() {
+        assert_eq!(wanted_reason("off", true, true), None);
+        assert_eq!(wanted_reason("always", false, false), Some("app running"));
+        assert_eq!(wanted_reason("whileActive", false, false), None);
+        assert_eq!(wanted_reason("whileActive", false, true), Some("timelapse recording"));
+    }
+}
@@ -0,0 +1,160 @@
+//! Groundspeed, track and vertical rate from position history
+//!
+//! Not every traffic source reports velocities: the VATSIM data feed has a
+//! groundspeed but no ground track or vertical rate, and vNAS updates have
+//! neither groundspeed nor vertical rate. Keeping a short position history per
+//! aircraft lets the backend fill the gaps the same way for every source, so
+//! datablocks show consistent values whichever source is active. Values the
+//! source does report always take precedence.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use crate::geo;
+
+/// Positions this far apart are needed for a velocity (ms)
+const MIN_SPAN_MS: u64 = 1_000;
+
+/// Below this distance between fixes the track is noise (nm, about 10 m)
+const MIN_TRACK_DISTANCE_NM: f64 = 0.005;
+
+/// Aircraft not heard from for this long are forgotten (ms)
+const STALE_MS: u64 = 120_000;
+
+/// Position report
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionFix {
+    /// Time of the report at the source (Unix ms)
+    pub time_ms: u64,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Feet MSL
+    pub altitude_ft: f64,
+}
+
+/// Groundspeed, track and vertical rate, reported by the source or derived
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Kinematics {
+    pub groundspeed_kt: Option<f64>,
+    /// Degrees true
+    pub track: Option<f64>,
+    /// Feet per minute (positive = climbing)
+    pub vertical_rate_fpm: Option<f64>,
+}
+
+impl Kinematics {
+    /// Fill missing values from another estimate
+    fn or(self, other: Kinematics) -> Kinematics {
+        Kinematics {
+            groundspeed_kt: self.groundspeed_kt.or(other.groundspeed_kt),
+            track: self.track.or(other.track),
+            vertical_rate_fpm: self.vertical_rate_fpm.or(other.vertical_rate_fpm),
+        }
+    }
+}
+
+/// Recent positions per aircraft
+pub struct PositionHistory {
+    /// Velocities are averaged over this much history (ms)
+    window_ms: u64,
+    fixes: HashMap<String, VecDeque<PositionFix>>,
+}
+
+impl PositionHistory {
+    /// History averaging velocities over `window` (should cover at least two
+    /// reports of the source)
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window_ms: window.as_millis() as u64,
+            fixes: HashMap::new(),
+        }
+    }
+
+    /// Add a position report and return the aircraft's velocities: the ones
+    /// reported by the source, the rest derived from the history
+    pub fn update(&mut self, callsign: &str, fix: PositionFix, reported: Kinematics) -> Kinematics {
+        let fixes = self.fixes.entry(callsign.to_string()).or_default();
+        // The same report can be seen again before the source refreshes it
+        if fixes.back().map_or(true, |last| fix.time_ms > last.time_ms) {
+            fixes.push_back(fix);
+        }
+        let latest = fixes.back().map_or(fix.time_ms, |f| f.time_ms);
+        while fixes.front().is_some_and(|f| latest - f.time_ms > self.window_ms) {
+            fixes.pop_front();
+        }
+        reported.or(derive(fixes))
+    }
+
+    /// Forget aircraft without a report since a while before `now_ms`
+    pub fn prune(&mut self, now_ms: u64) {
+        self.fixes.retain(|_, fixes| {
+            fixes
+                .back()
+                .is_some_and(|f| now_ms.saturating_sub(f.time_ms) <= STALE_MS)
+        });
+    }
+}
+
+/// Velocities between the oldest and newest fix
+fn derive(fixes: &VecDeque<PositionFix>) -> Kinematics {
+    let (Some(first), Some(last)) = (fixes.front(), fixes.back()) else {
+        return Kinematics::default();
+    };
+    let span_ms = last.time_ms - first.time_ms;
+    if span_ms < MIN_SPAN_MS {
+        return Kinematics::default();
+    }
+
+    let (distance_nm, bearing) =
+        geo::distance_and_bearing(first.latitude, first.longitude, last.latitude, last.longitude);
+    let minutes = span_ms as f64 / 60_000.0;
+    Kinematics {
+        groundspeed_kt: Some((distance_nm / minutes * 60.0).round()),
+        track: (distance_nm >= MIN_TRACK_DISTANCE_NM).then(|| (bearing * 10.0).round() / 10.0),
+        vertical_rate_fpm: Some(((last.altitude_ft - first.altitude_ft) / minutes / 10.0).round() * 10.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derives_missing_velocities() {
+        let fix = |seconds: u64, latitude: f64, altitude_ft: f64| PositionFix {
+            time_ms: 1_700_000_000_000 + seconds * 1000,
+            latitude,
+            longitude: -71.0,
+            altitude_ft,
+        };
+        let mut history = PositionHistory::new(Duration::from_secs(35));
+        let reported = Kinematics {
+            groundspeed_kt: Some(200.0),
+            ..Default::default()
+        };
+
+        // A single fix has no history
+        assert_eq!(history.update("DAL1", fix(0, 42.0, 3000.0), reported), reported);
+
+        // 1 nm north in 15 seconds while climbing 300 ft: 240 kt, 1200 fpm
+        let one_nm = 1.0 / 60.0;
+        let derived = history.update("DAL1", fix(15, 42.0 + one_nm, 3300.0), Kinematics::default());
+        assert_eq!(derived.groundspeed_kt, Some(240.0));
+        assert_eq!(derived.track, Some(0.0));
+        assert_eq!(derived.vertical_rate_fpm, Some(1200.0));
+
+        // Reported values win; repeated reports don't add history
+        let mixed = history.update("DAL1", fix(15, 42.0 + one_nm, 3300.0), reported);
+        assert_eq!(mixed.groundspeed_kt, Some(200.0));
+        assert_eq!(mixed.vertical_rate_fpm, Some(1200.0));
+
+        // Old fixes fall out of the window; stationary aircraft have no track
+        let parked = history.update("DAL1", fix(45, 42.0 + one_nm, 3300.0), Kinematics::default());
+        assert_eq!(parked.groundspeed_kt, Some(0.0));
+        assert_eq!(parked.track, None);
+        assert_eq!(parked.vertical_rate_fpm, Some(0.0));
+
+        history.prune(fix(45, 0.0, 0.0).time_ms + STALE_MS + 1);
+        assert!(history.fixes.is_empty());
+    }
+}
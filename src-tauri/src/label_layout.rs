@@ -0,0 +1,461 @@
+//! Datablock label deconfliction computed by the host
+//!
+//! After every traffic poll the host picks, for each aircraft, the direction
+//! its datablock is drawn in (numpad positions, as in the label settings) and
+//! how far out along the leader line, so labels don't cover each other or
+//! other aircraft. The layout is worked out in the tower's view: the azimuth
+//! and elevation of each aircraft as seen from the tower cab, scaled to pixels
+//! at the default 60° field of view on a 1920 px wide display. That view is
+//! the same for every display looking out of the tower, so all displays
+//! resolve clutter identically, and cheap clients only draw the result
+//! (Tauri event `label-layout`, `/api/labels/ws`) instead of running the
+//! layout themselves.
+//!
+//! Labels keep their direction while it stays clear, so they don't jump
+//! around from poll to poll. Directions typed for an aircraft on a display
+//! still win on that display.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+
+use crate::datablocks;
+use crate::follow;
+use crate::geo;
+use crate::traffic::{Aircraft, TrafficSnapshot};
+
+/// Tower cab height when the airport has no tower position (m above the field)
+const DEFAULT_TOWER_HEIGHT_M: f64 = 35.0;
+
+/// Screen scale of the tower view: 1920 px across a 60° field of view
+const PX_PER_DEGREE: f64 = 1920.0 / 60.0;
+
+/// Label and aircraft sizes, as drawn by the clients (px)
+const LABEL_WIDTH_PX: f64 = 90.0;
+const LINE_HEIGHT_PX: f64 = 18.0;
+const MODEL_RADIUS_PX: f64 = 15.0;
+const LABEL_MARGIN_PX: f64 = 3.0;
+/// Leader line length per unit of the datablock leader length setting
+const LEADER_PX_PER_UNIT: f64 = 10.0;
+
+/// Leader lengths tried, as multiples of the configured length
+const LEADER_SCALES: [f64; 4] = [1.0, 2.0, 3.0, 4.0];
+
+/// Numpad directions (5 is the aircraft itself)
+const DIRECTIONS: [u8; 8] = [1, 2, 3, 4, 6, 7, 8, 9];
+
+/// Where an aircraft's datablock goes
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelPlacement {
+    /// Numpad direction from the aircraft (1-9 without 5)
+    pub direction: u8,
+    /// Leader line length as a multiple of the configured length
+    pub leader_scale: f64,
+}
+
+/// Label placements at the active airport
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelLayout {
+    pub icao: Option<String>,
+    /// Time of the traffic picture the layout is based on (Unix ms)
+    pub updated_at: Option<u64>,
+    /// By callsign
+    pub placements: BTreeMap<String, LabelPlacement>,
+}
+
+/// Label layout state (managed by Tauri)
+pub struct LabelLayoutState {
+    layout: RwLock<LabelLayout>,
+    /// Layout updates for WebSocket relay
+    pub events: broadcast::Sender<LabelLayout>,
+}
+
+impl LabelLayoutState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            layout: RwLock::new(LabelLayout::default()),
+            events,
+        }
+    }
+}
+
+/// Eye point of the tower view
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewpoint {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Feet MSL
+    pub altitude_ft: f64,
+}
+
+/// What the layout needs from the display and datablock settings
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutOptions {
+    /// Direction labels go in when nothing is in the way
+    pub preferred: u8,
+    /// Datablock lines (label height)
+    pub lines: usize,
+    /// Datablock leader length setting
+    pub leader_length: f64,
+    /// Labels further than this from the tower aren't drawn (nm)
+    pub max_distance_nm: f64,
+    pub show_ground: bool,
+    pub show_airborne: bool,
+    /// Followed aircraft, placed first
+    pub followed: Option<String>,
+}
+
+/// Aircraft label in the tower view
+struct Label<'a> {
+    callsign: &'a str,
+    /// Pixels right of north / below the horizon
+    x: f64,
+    y: f64,
+    priority: f64,
+}
+
+/// Horizontal distance in the tower view, across the 360° seam
+fn wrapped_dx(a: f64, b: f64) -> f64 {
+    let span = 360.0 * PX_PER_DEGREE;
+    let dx = (a - b).rem_euclid(span);
+    if dx > span / 2.0 {
+        dx - span
+    } else {
+        dx
+    }
+}
+
+/// Screen angle of a numpad direction (0 = right, Y down)
+fn direction_angle(direction: u8) -> f64 {
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+    match direction {
+        9 => -FRAC_PI_4,
+        6 => 0.0,
+        3 => FRAC_PI_4,
+        8 => -FRAC_PI_2,
+        2 => FRAC_PI_2,
+        7 => -3.0 * FRAC_PI_4,
+        4 => PI,
+        1 => 3.0 * FRAC_PI_4,
+        _ => -3.0 * FRAC_PI_4,
+    }
+}
+
+/// Distance from a label's center to its edge along an angle
+fn edge_distance(angle: f64, width: f64, height: f64) -> f64 {
+    let (cos, sin) = (angle.cos().abs(), angle.sin().abs());
+    if cos < 0.001 {
+        return height / 2.0;
+    }
+    if sin < 0.001 {
+        return width / 2.0;
+    }
+    (width / 2.0 / cos).min(height / 2.0 / sin)
+}
+
+/// Directions ordered by how far they turn from the preferred one (ties clockwise first)
+fn search_order(preferred: u8) -> Vec<u8> {
+    let start = direction_angle(preferred);
+    let mut directions = DIRECTIONS.to_vec();
+    let turn = |d: u8| {
+        let diff = (direction_angle(d) - start).rem_euclid(std::f64::consts::TAU);
+        let away = diff.min(std::f64::consts::TAU - diff);
+        // Y points down, so a positive screen angle turns clockwise
+        (away, diff > std::f64::consts::PI)
+    };
+    directions.sort_by(|a, b| turn(*a).partial_cmp(&turn(*b)).unwrap_or(Ordering::Equal));
+    directions
+}
+
+/// Label center for a placement
+fn label_center(label: &Label, placement: LabelPlacement, width: f64, height: f64, gap: f64) -> (f64, f64) {
+    let angle = direction_angle(placement.direction);
+    let distance = MODEL_RADIUS_PX + gap * placement.leader_scale + edge_distance(angle, width, height);
+    (label.x + angle.cos() * distance, label.y + angle.sin() * distance)
+}
+
+/// Whether a label centered at `center` is clear of the placed labels and other aircraft
+fn is_clear(center: (f64, f64), width: f64, height: f64, placed: &[(f64, f64)], labels: &[Label], own: &str) -> bool {
+    let overlaps_label = placed.iter().any(|other| {
+        wrapped_dx(center.0, other.0).abs() < width + LABEL_MARGIN_PX && (center.1 - other.1).abs() < height + LABEL_MARGIN_PX
+    });
+    let covers_aircraft = labels.iter().filter(|l| l.callsign != own).any(|aircraft| {
+        // Closest point of the label to the aircraft
+        let dx = wrapped_dx(aircraft.x, center.0);
+        let dy = aircraft.y - center.1;
+        let nearest_dx = dx - dx.clamp(-width / 2.0, width / 2.0);
+        let nearest_dy = dy - dy.clamp(-height / 2.0, height / 2.0);
+        nearest_dx.hypot(nearest_dy) < MODEL_RADIUS_PX
+    });
+    !overlaps_label && !covers_aircraft
+}
+
+/// Aircraft position in the tower view and its label priority
+fn project<'a>(aircraft: &'a Aircraft, eye: &Viewpoint, options: &LayoutOptions) -> Option<Label<'a>> {
+    if (aircraft.on_ground && !options.show_ground) || (!aircraft.on_ground && !options.show_airborne) {
+        return None;
+    }
+    let (distance_nm, bearing) = geo::distance_and_bearing(eye.latitude, eye.longitude, aircraft.latitude, aircraft.longitude);
+    if distance_nm > options.max_distance_nm {
+        return None;
+    }
+    let elevation = (aircraft.altitude_ft - eye.altitude_ft).atan2(distance_nm * 6076.12).to_degrees();
+    // Followed first, then airborne, then closer aircraft (like the clients' own layout)
+    let followed = options.followed.as_deref() == Some(aircraft.callsign.as_str());
+    let priority = if followed { 10_000.0 } else { 0.0 }
+        + if aircraft.on_ground { 0.0 } else { 100.0 }
+        + (50.0 - distance_nm * 1.852).max(0.0);
+    Some(Label {
+        callsign: &aircraft.callsign,
+        x: bearing * PX_PER_DEGREE,
+        y: -elevation * PX_PER_DEGREE,
+        priority,
+    })
+}
+
+/// Place every label: in priority order, the first clear spot trying the
+/// previous direction, then the preferred one and those next to it, at
+/// increasing leader lengths; the preferred direction if nothing is clear
+pub fn layout(
+    aircraft: &[Aircraft],
+    eye: &Viewpoint,
+    options: &LayoutOptions,
+    previous: &BTreeMap<String, LabelPlacement>,
+) -> BTreeMap<String, LabelPlacement> {
+    let mut labels: Vec<Label> = aircraft.iter().filter_map(|a| project(a, eye, options)).collect();
+    labels.sort_by(|a, b| {
+        b.priority
+            .partial_cmp(&a.priority)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.callsign.cmp(b.callsign))
+    });
+
+    let width = LABEL_WIDTH_PX;
+    let height = LINE_HEIGHT_PX * options.lines.max(1) as f64;
+    let gap = options.leader_length * LEADER_PX_PER_UNIT;
+    let preferred = if DIRECTIONS.contains(&options.preferred) { options.preferred } else { 7 };
+    let order = search_order(preferred);
+
+    let mut placements = BTreeMap::new();
+    let mut placed = Vec::with_capacity(labels.len());
+    for label in &labels {
+        let candidates = previous.get(label.callsign).copied().into_iter().chain(
+            LEADER_SCALES
+                .iter()
+                .flat_map(|&leader_scale| order.iter().map(move |&direction| LabelPlacement { direction, leader_scale })),
+        );
+        let mut chosen = None;
+        for candidate in candidates {
+            let center = label_center(label, candidate, width, height, gap);
+            if is_clear(center, width, height, &placed, &labels, label.callsign) {
+                chosen = Some((candidate, center));
+                break;
+            }
+        }
+        let (placement, center) = chosen.unwrap_or_else(|| {
+            let fallback = LabelPlacement {
+                direction: preferred,
+                leader_scale: 1.0,
+            };
+            (fallback, label_center(label, fallback, width, height, gap))
+        });
+        placed.push(center);
+        placements.insert(label.callsign.to_string(), placement);
+    }
+    placements
+}
+
+/// The tower cab of an airport: its 3D tower position, or above the airport
+/// reference point (airport position and elevation in feet)
+fn viewpoint(app: &AppHandle, icao: &str, airport: (f64, f64, f64)) -> Viewpoint {
+    let (latitude, longitude, elevation_ft) = airport;
+    let position = crate::read_tower_positions(app.clone())
+        .ok()
+        .and_then(|positions| positions.get(icao).cloned())
+        .and_then(|entry| serde_json::from_value::<crate::TowerPositionEntry>(entry).ok())
+        .and_then(|entry| entry.view_3d);
+    let Some(position) = position else {
+        return Viewpoint {
+            latitude,
+            longitude,
+            altitude_ft: elevation_ft + DEFAULT_TOWER_HEIGHT_M / 0.3048,
+        };
+    };
+    // Fine-tuning offsets move the cab a few meters
+    let north_m = position.lat_offset_meters.unwrap_or(0.0);
+    let east_m = position.lon_offset_meters.unwrap_or(0.0);
+    let (latitude, longitude) = if north_m == 0.0 && east_m == 0.0 {
+        (position.lat, position.lon)
+    } else {
+        let bearing = east_m.atan2(north_m).to_degrees();
+        geo::destination(position.lat, position.lon, bearing, north_m.hypot(east_m) / 1852.0)
+    };
+    Viewpoint {
+        latitude,
+        longitude,
+        altitude_ft: elevation_ft + position.agl_height / 0.3048,
+    }
+}
+
+fn publish(app: &AppHandle, layout: LabelLayout) {
+    let _ = app.emit("label-layout", &layout);
+    let state = app.state::<LabelLayoutState>();
+    let _ = state.events.send(layout.clone());
+    *state.layout.write() = layout;
+}
+
+/// Lay out the labels for a traffic poll (airport position and elevation in feet)
+pub fn update(app: &AppHandle, snapshot: &TrafficSnapshot, airport: (f64, f64, f64)) {
+    let Some(icao) = snapshot.icao.as_deref() else {
+        return;
+    };
+    let Ok(settings) = crate::read_global_settings(app.clone()) else {
+        return;
+    };
+    let datablock = datablocks::get_layout(app);
+    let options = LayoutOptions {
+        preferred: settings.display.default_datablock_direction,
+        lines: datablock.lines.len(),
+        leader_length: datablock.leader_length,
+        max_distance_nm: settings.display.label_visibility_distance,
+        show_ground: settings.display.show_ground_traffic,
+        show_airborne: settings.display.show_airborne_traffic,
+        followed: follow::get_status(app).target.map(|t| t.callsign),
+    };
+    let current = get_layout(app);
+    let previous = if current.icao.as_deref() == Some(icao) {
+        current.placements
+    } else {
+        BTreeMap::new()
+    };
+    let eye = viewpoint(app, icao, airport);
+    publish(
+        app,
+        LabelLayout {
+            icao: Some(icao.to_string()),
+            updated_at: snapshot.updated_at,
+            placements: layout(&snapshot.aircraft, &eye, &options, &previous),
+        },
+    );
+}
+
+/// Drop the layout (no active airport or traffic)
+pub fn clear(app: &AppHandle) {
+    if get_layout(app).icao.is_some() {
+        publish(app, LabelLayout::default());
+    }
+}
+
+/// Current label layout
+pub fn get_layout(app: &AppHandle) -> LabelLayout {
+    app.state::<LabelLayoutState>().layout.read().clone()
+}
+
+/// Initialize label layout state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_label_layout(app: &AppHandle) {
+    app.manage(LabelLayoutState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the datablock placements worked out by the host
+#[tauri::command]
+pub fn get_label_layout(app: AppHandle) -> LabelLayout {
+    get_layout(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traffic::test_aircraft;
+
+    fn aircraft(callsign: &str, latitude: f64, longitude: f64, altitude_ft: f64) -> Aircraft {
+        Aircraft {
+            latitude,
+            longitude,
+            altitude_ft,
+            groundspeed_kt: 0.0,
+            on_ground: altitude_ft < 100.0,
+            ..test_aircraft(callsign)
+        }
+    }
+
+    fn options() -> LayoutOptions {
+        LayoutOptions {
+            preferred: 9,
+            lines: 2,
+            leader_length: 2.0,
+            max_distance_nm: 30.0,
+            show_ground: true,
+            show_airborne: true,
+            followed: None,
+        }
+    }
+
+    const EYE: Viewpoint = Viewpoint {
+        latitude: 42.0,
+        longitude: -71.0,
+        altitude_ft: 150.0,
+    };
+
+    #[test]
+    fn orders_directions_around_the_preferred_one() {
+        assert_eq!(search_order(9), vec![9, 6, 8, 3, 7, 2, 4, 1]);
+        assert_eq!(search_order(7)[..3], [7, 8, 4]);
+        assert!((wrapped_dx(359.0 * PX_PER_DEGREE, 1.0 * PX_PER_DEGREE) + 2.0 * PX_PER_DEGREE).abs() < 1e-9);
+    }
+
+    #[test]
+    fn spreads_crowded_labels() {
+        // A line of aircraft waiting at a holding point, a few meters apart
+        let traffic: Vec<Aircraft> = (0..4)
+            .map(|i| aircraft(&format!("AAL{}", i), 42.01, -71.0 + i as f64 * 0.0002, 20.0))
+            .collect();
+        let placements = layout(&traffic, &EYE, &options(), &BTreeMap::new());
+        assert_eq!(placements.len(), 4);
+
+        let labels: Vec<Label> = traffic.iter().filter_map(|a| project(a, &EYE, &options())).collect();
+        let centers: Vec<(f64, f64)> = labels
+            .iter()
+            .map(|l| label_center(l, placements[l.callsign], LABEL_WIDTH_PX, 2.0 * LINE_HEIGHT_PX, 20.0))
+            .collect();
+        for (i, a) in centers.iter().enumerate() {
+            for b in &centers[i + 1..] {
+                let apart = wrapped_dx(a.0, b.0).abs() >= LABEL_WIDTH_PX || (a.1 - b.1).abs() >= 2.0 * LINE_HEIGHT_PX;
+                assert!(apart, "{:?} {:?} {:?}", a, b, placements);
+            }
+        }
+
+        // An aircraft on its own keeps the preferred direction
+        let alone = layout(&traffic[..1], &EYE, &options(), &BTreeMap::new());
+        assert_eq!(alone["AAL0"], LabelPlacement { direction: 9, leader_scale: 1.0 });
+    }
+
+    #[test]
+    fn keeps_previous_directions_and_skips_hidden_traffic() {
+        let traffic = vec![aircraft("DAL1", 42.01, -71.0, 20.0), aircraft("UAL2", 42.2, -71.0, 3000.0)];
+        let previous = BTreeMap::from([("DAL1".to_string(), LabelPlacement { direction: 1, leader_scale: 2.0 })]);
+        let placements = layout(&traffic, &EYE, &options(), &previous);
+        assert_eq!(placements["DAL1"], previous["DAL1"]);
+        assert_eq!(placements["UAL2"].direction, 9);
+
+        let ground_only = LayoutOptions {
+            show_airborne: false,
+            max_distance_nm: 5.0,
+            ..options()
+        };
+        let far = aircraft("FAR3", 43.0, -71.0, 20.0);
+        let placements = layout(&[traffic[0].clone(), traffic[1].clone(), far], &EYE, &ground_only, &BTreeMap::new());
+        assert_eq!(placements.keys().cloned().collect::<Vec<_>>(), ["DAL1"]);
+    }
+}
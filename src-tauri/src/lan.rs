@@ -0,0 +1,292 @@
+//! LAN address discovery
+//!
+//! Finds the address other devices on the local network use to reach the
+//! host, shown as the server's LAN URL. The network interfaces are enumerated
+//! directly (the same way on Windows, Linux and macOS) and the best address
+//! is picked: private ranges first, skipping loopback, link-local and the
+//! virtual adapters of Docker, WSL/Hyper-V, VMs and VPN tunnels, which
+//! tablets on the Wi-Fi can't reach. IPv4 is preferred; on IPv6-only networks
+//! a unique local (fc00::/7) or global address is used instead. When no
+//! interface qualifies, the address of the default route is used.
+//!
+//! Also decides which clients count as local network for
+//! `server.requireLocalNetwork`: private IPv4 ranges, IPv6 unique local and
+//! link-local addresses, and global IPv6 addresses on one of the host's own
+//! subnets (home networks often hand out only global addresses). Tailscale's
+//! ULA prefix reaches the whole tailnet, so it only counts when Tailscale is
+//! trusted (see `remote_access::TrustedNetworks`).
+
+use std::net::{IpAddr, Ipv6Addr, UdpSocket};
+use std::time::{Duration, Instant};
+
+use if_addrs::{IfAddr, Interface};
+use parking_lot::Mutex;
+use tracing::debug;
+
+use crate::remote_access::{self, IpNet};
+
+/// Interface name prefixes of virtual adapters (lowercase)
+const VIRTUAL_PREFIXES: &[&str] = &[
+    "docker", "br-", "veth", "virbr", "vmnet", "vboxnet", "vethernet", "utun", "tun", "tap", "wg", "zt",
+    "tailscale", "awdl", "llw", "bridge",
+];
+
+/// Interface name fragments of virtual adapters (lowercase, Windows adapter names)
+const VIRTUAL_NAMES: &[&str] = &["virtualbox", "vmware", "hyper-v", "wsl", "vpn", "loopback"];
+
+/// Shortest IPv6 prefix treated as an on-link subnet (shorter ones are routed blocks)
+const MIN_SUBNET_PREFIX: u8 = 48;
+
+/// How long the host's subnets are reused before the interfaces are listed again
+const SUBNET_TTL: Duration = Duration::from_secs(30);
+
+/// Host subnets and when they were listed
+static SUBNETS: Mutex<Option<(Instant, Vec<IpNet>)>> = Mutex::new(None);
+
+fn is_virtual(name: &str) -> bool {
+    let name = name.to_lowercase();
+    VIRTUAL_PREFIXES.iter().any(|p| name.starts_with(p)) || VIRTUAL_NAMES.iter().any(|n| name.contains(n))
+}
+
+/// fe80::/10
+fn is_link_local_v6(ip: &Ipv6Addr) -> bool {
+    (ip.segments()[0] & 0xffc0) == 0xfe80
+}
+
+/// fc00::/7
+fn is_unique_local_v6(ip: &Ipv6Addr) -> bool {
+    (ip.segments()[0] & 0xfe00) == 0xfc00
+}
+
+/// Preference for an address on an interface (lower is better), `None` if unusable
+fn rank(name: &str, ip: IpAddr) -> Option<u8> {
+    if ip.is_loopback() || ip.is_unspecified() || ip.is_multicast() {
+        return None;
+    }
+    let range = match ip {
+        IpAddr::V4(ip) if ip.is_link_local() => return None,
+        IpAddr::V4(ip) => match ip.octets() {
+            [192, 168, ..] => 0,
+            [10, ..] => 1,
+            [172, b, ..] if (16..=31).contains(&b) => 2,
+            // CGNAT (also Tailscale)
+            [100, b, ..] if (64..=127).contains(&b) => 4,
+            _ => 3,
+        },
+        // Link-local needs a zone index browsers don't accept
+        IpAddr::V6(ip) if is_link_local_v6(&ip) || ip.to_ipv4_mapped().is_some() => return None,
+        // Unique local addresses survive the ISP changing the delegated prefix
+        IpAddr::V6(ip) if is_unique_local_v6(&ip) => 0,
+        IpAddr::V6(_) => 1,
+    };
+    Some(range + if is_virtual(name) { 10 } else { 0 })
+}
+
+/// Best LAN address among `(interface name, address)` pairs
+fn pick(interfaces: impl IntoIterator<Item = (String, IpAddr)>) -> Option<IpAddr> {
+    interfaces
+        .into_iter()
+        .filter_map(|(name, ip)| rank(&name, ip).map(|rank| (rank, ip)))
+        .min_by_key(|&(rank, _)| rank)
+        .map(|(_, ip)| ip)
+}
+
+/// Local address of the default route (no packets are sent)
+fn default_route_ip(bind: &str, target: &str) -> Option<IpAddr> {
+    let socket = UdpSocket::bind(bind).ok()?;
+    socket.connect(target).ok()?;
+    Some(socket.local_addr().ok()?.ip()).filter(|ip| !ip.is_unspecified())
+}
+
+fn interfaces() -> Vec<Interface> {
+    if_addrs::get_if_addrs().unwrap_or_else(|e| {
+        debug!("[LAN] Failed to list network interfaces: {}", e);
+        Vec::new()
+    })
+}
+
+/// `(interface name, address)` pairs of one address family
+fn addresses(interfaces: &[Interface], ipv6: bool) -> impl Iterator<Item = (String, IpAddr)> + '_ {
+    interfaces
+        .iter()
+        .filter(move |iface| iface.ip().is_ipv6() == ipv6)
+        .map(|iface| (iface.name.clone(), iface.ip()))
+}
+
+/// Best IPv6 address other devices on the LAN can reach this host at
+pub fn lan_ipv6() -> Option<IpAddr> {
+    pick(addresses(&interfaces(), true)).or_else(|| default_route_ip("[::]:0", "[2001:4860:4860::8888]:80"))
+}
+
+/// Address other devices on the LAN can reach this host at (IPv4 unless the
+/// network is IPv6-only)
+pub fn lan_ip() -> Option<IpAddr> {
+    pick(addresses(&interfaces(), false))
+        .or_else(|| default_route_ip("0.0.0.0:0", "8.8.8.8:80"))
+        .or_else(lan_ipv6)
+}
+
+/// On-link IPv6 subnet of an interface address, `None` for loopback, virtual
+/// adapters, IPv4 and routed blocks
+fn subnet(name: &str, ip: IpAddr, netmask: IpAddr) -> Option<IpNet> {
+    let (IpAddr::V6(ip), IpAddr::V6(netmask)) = (ip, netmask) else {
+        return None;
+    };
+    let prefix = u128::from(netmask).leading_ones() as u8;
+    if ip.is_loopback() || is_virtual(name) || prefix < MIN_SUBNET_PREFIX {
+        return None;
+    }
+    IpNet::new(IpAddr::V6(ip), prefix)
+}
+
+/// The host's own IPv6 subnets (cached for a short while)
+fn host_subnets() -> Vec<IpNet> {
+    let mut cached = SUBNETS.lock();
+    if let Some((listed, subnets)) = cached.as_ref() {
+        if listed.elapsed() < SUBNET_TTL {
+            return subnets.clone();
+        }
+    }
+    let subnets: Vec<IpNet> = interfaces()
+        .iter()
+        .filter_map(|iface| match &iface.addr {
+            IfAddr::V6(addr) => subnet(&iface.name, IpAddr::V6(addr.ip), IpAddr::V6(addr.netmask)),
+            IfAddr::V4(_) => None,
+        })
+        .collect();
+    *cached = Some((Instant::now(), subnets.clone()));
+    subnets
+}
+
+/// Loopback, private and link-local ranges of both families, and IPv6 ULA
+/// (except Tailscale's)
+fn is_private_ip(ip: &IpAddr) -> bool {
+    match ip.to_canonical() {
+        IpAddr::V4(ipv4) => {
+            let octets = ipv4.octets();
+            // 127.x.x.x (localhost)
+            octets[0] == 127
+            // 10.x.x.x (Class A private)
+            || octets[0] == 10
+            // 172.16.x.x - 172.31.x.x (Class B private)
+            || (octets[0] == 172 && (16..=31).contains(&octets[1]))
+            // 192.168.x.x (Class C private)
+            || (octets[0] == 192 && octets[1] == 168)
+            // 169.254.x.x (link-local)
+            || (octets[0] == 169 && octets[1] == 254)
+        }
+        IpAddr::V6(ipv6) => {
+            ipv6.is_loopback()
+                || is_link_local_v6(&ipv6)
+                || (is_unique_local_v6(&ipv6) && !remote_access::is_tailscale_ip(&IpAddr::V6(ipv6)))
+        }
+    }
+}
+
+/// Whether an address is in one of the subnets
+fn on_subnet(ip: &IpAddr, subnets: &[IpNet]) -> bool {
+    ip.is_ipv6() && subnets.iter().any(|net| net.contains(ip))
+}
+
+/// Whether a client address is on the local network
+pub fn is_local_network_ip(ip: &IpAddr) -> bool {
+    let ip = ip.to_canonical();
+    is_private_ip(&ip) || (ip.is_ipv6() && on_subnet(&ip, &host_subnets()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iface(name: &str, ip: &str) -> (String, IpAddr) {
+        (name.to_string(), ip.parse().unwrap())
+    }
+
+    fn ip(text: &str) -> IpAddr {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn prefers_private_physical_interfaces() {
+        let picked = pick([
+            iface("lo", "127.0.0.1"),
+            iface("docker0", "172.17.0.1"),
+            iface("eth0", "10.0.0.12"),
+            iface("wlan0", "192.168.1.20"),
+        ]);
+        assert_eq!(picked, Some(ip("192.168.1.20")));
+    }
+
+    #[test]
+    fn skips_virtual_and_unusable_addresses() {
+        assert_eq!(rank("en0", ip("169.254.10.2")), None);
+        assert_eq!(rank("lo0", ip("127.0.0.1")), None);
+        assert_eq!(rank("en0", ip("fe80::1c2b:3ff:fe4d:5e6f")), None);
+        assert_eq!(rank("lo0", ip("::1")), None);
+
+        let picked = pick([
+            iface("vEthernet (WSL)", "192.168.80.1"),
+            iface("tailscale0", "100.101.1.2"),
+            iface("Ethernet", "10.1.2.3"),
+        ]);
+        assert_eq!(picked, Some(ip("10.1.2.3")));
+
+        // A virtual adapter is still better than nothing
+        assert_eq!(pick([iface("utun3", "10.8.0.2")]), Some(ip("10.8.0.2")));
+        assert_eq!(pick([iface("lo", "127.0.0.1")]), None);
+    }
+
+    #[test]
+    fn picks_ipv6_addresses() {
+        let picked = pick([
+            iface("en0", "fe80::1"),
+            iface("en0", "2001:db8:1:2::20"),
+            iface("tailscale0", "fd7a:115c:a1e0::5"),
+            iface("en0", "fd12:3456:789a::20"),
+        ]);
+        assert_eq!(picked, Some(ip("fd12:3456:789a::20")));
+        assert_eq!(
+            pick([iface("utun2", "fd00::2"), iface("Wi-Fi", "2001:db8:1:2::20")]),
+            Some(ip("2001:db8:1:2::20"))
+        );
+    }
+
+    #[test]
+    fn classifies_local_network_addresses() {
+        for local in ["127.0.0.1", "10.1.2.3", "172.20.0.1", "192.168.1.5", "169.254.3.4"] {
+            assert!(is_private_ip(&ip(local)), "{}", local);
+        }
+        for local in ["::1", "fe80::1", "fd12:3456:789a::20", "fc00::1", "::ffff:192.168.1.5", "::ffff:127.0.0.1"] {
+            assert!(is_private_ip(&ip(local)), "{}", local);
+        }
+        for public in ["8.8.8.8", "172.32.0.1", "2001:db8:1:2::20", "::ffff:8.8.8.8", "fec0::1"] {
+            assert!(!is_private_ip(&ip(public)), "{}", public);
+        }
+        // Tailscale's ULA prefix and CGNAT range are only trusted on request
+        for tailscale in ["fd7a:115c:a1e0::5", "100.101.1.2"] {
+            assert!(!is_private_ip(&ip(tailscale)), "{}", tailscale);
+            assert!(!is_local_network_ip(&ip(tailscale)), "{}", tailscale);
+        }
+    }
+
+    #[test]
+    fn global_addresses_on_the_host_subnet_are_local() {
+        let mask = |prefix: u32| IpAddr::V6(Ipv6Addr::from(u128::MAX << (128 - prefix)));
+        let subnets: Vec<IpNet> = [
+            subnet("en0", ip("2001:db8:1:2::20"), mask(64)),
+            // Routed blocks, tunnels and IPv4 aren't on-link subnets
+            subnet("en0", ip("2001:db8:ff::1"), mask(32)),
+            subnet("wg0", ip("2001:db8:9:9::1"), mask(64)),
+            subnet("en0", ip("192.168.1.20"), ip("255.255.255.0")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        assert_eq!(subnets.len(), 1);
+
+        assert!(on_subnet(&ip("2001:db8:1:2:a:b:c:d"), &subnets));
+        assert!(!on_subnet(&ip("2001:db8:1:3::20"), &subnets));
+        assert!(!on_subnet(&ip("2001:db8:9:9::2"), &subnets));
+        assert!(!on_subnet(&ip("2001:db8:ff::2"), &subnets));
+    }
+}
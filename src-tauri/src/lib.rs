@@ -1,65 +1,153 @@
 use std::fs;
+use std::net::{IpAddr, SocketAddr};
 use std::path::PathBuf;
-use std::process::{Command, Child};
+use std::process::Command;
 use std::sync::Mutex;
 
-#[cfg(windows)]
-use std::os::windows::process::CommandExt;
-#[cfg(windows)]
-use std::os::windows::io::AsRawHandle;
-use tauri::Manager;
+use tauri::{Emitter, Manager};
 use tauri_plugin_dialog::DialogExt;
+use tauri_plugin_window_state::StateFlags;
 use serde::{Deserialize, Serialize};
 use tokio::sync::broadcast;
-
+use tracing::{error, info};
+
+use process_tree::ProcessTree;
+
+use crate::error::Error;
+
+mod afv;
+mod aircraft_photos;
+mod aircraft_types;
+mod airlines;
+mod airport_db;
+mod airport_packs;
+mod airspace;
+mod annotations;
+mod api_docs;
+mod approach;
+mod arrival_sequence;
+mod atc_watch;
+mod atpa;
+mod audio_relay;
+mod audit_log;
+mod auth_lockout;
+mod autostart;
+mod backup;
+mod charts;
+mod chunked_downloads;
+mod client_capabilities;
+mod client_logs;
+mod cli;
+mod clock;
+mod content_manifest;
+mod control;
+mod controllers;
+mod content_packs;
+mod coordination;
+mod coverage;
+mod crash_reports;
+mod datablocks;
+mod datis;
+mod debug_stats;
+mod declutter;
+mod drawings;
+mod density_altitude;
+mod electron_import;
+mod ephemeris;
+mod error;
+mod event_schedule;
+mod event_prep;
+mod federation;
+mod file_cache;
+mod follow;
+mod fsltl_scan;
+mod gamepad;
+mod gate_suggestions;
+mod gates;
+mod geo;
+mod go_arounds;
+mod hotkeys;
+mod http_client;
+mod imagery_vintages;
+mod keep_awake;
+mod kinematics;
+mod label_layout;
+mod lan;
+mod lightning;
+mod lod;
+mod logging;
+mod map_providers;
+mod matching;
+mod midi;
+#[cfg(feature = "mock-traffic")]
+mod mock_traffic;
+mod mod_variants;
+mod model_usage;
+mod mods;
+mod notams;
+mod notifications;
+mod obs;
+mod osm_buildings;
+mod osm_layout;
+mod overlays;
+mod placements;
+mod plugins;
+mod process_tree;
+mod remote_access;
+mod replay;
+mod replay_library;
+mod runway_holds;
+mod runway_config;
+mod runway_occupancy;
+mod runway_suggestion;
+mod runways;
+mod safe_path;
+mod scripts;
+mod search;
+mod secrets;
+mod selection;
+mod sector_file;
 mod server;
+mod server_schedule;
+mod session_export;
+mod session_stats;
+mod similar_types;
+mod sound_packs;
+mod squawk_alerts;
+mod startup;
+mod stca;
+mod strips;
+mod supervisor;
+mod taf;
+mod taxi_routes;
+mod terrain_packages;
+mod tile_cache;
+mod timelapse;
+mod touchdown;
+mod traffic;
+mod trails;
+mod udp_output;
+mod units;
+mod updater;
+mod vatspy;
+mod vmr;
+mod videomaps;
+mod view_presets;
+mod vmr_remote;
 mod vnas;
-
-#[cfg(windows)]
-use windows_sys::Win32::Foundation::CloseHandle;
-#[cfg(windows)]
-use windows_sys::Win32::System::JobObjects::{
-    AssignProcessToJobObject, CreateJobObjectW, SetInformationJobObject,
-    JobObjectExtendedLimitInformation, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
-    JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
-};
-
-/// Wrapper for a Windows HANDLE that is Send-safe
-/// Job handles are thread-safe kernel objects, safe to send between threads
-#[cfg(windows)]
-struct SendableHandle(*mut std::ffi::c_void);
-
-#[cfg(windows)]
-unsafe impl Send for SendableHandle {}
-
-/// Wrapper for a process and its associated job object (Windows)
-/// The job object ensures all child processes are killed when we terminate
-struct ProcessWithJob {
-    child: Child,
-    #[cfg(windows)]
-    job_handle: SendableHandle,
-}
-
-impl Drop for ProcessWithJob {
-    fn drop(&mut self) {
-        #[cfg(windows)]
-        {
-            // Closing the job handle terminates all processes in the job
-            // due to JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE flag
-            if !self.job_handle.0.is_null() {
-                unsafe { CloseHandle(self.job_handle.0) };
-            }
-        }
-        #[cfg(not(windows))]
-        {
-            // On non-Windows, explicitly kill the child process
-            let _ = self.child.kill();
-        }
-    }
-}
+mod vnas_batch;
+mod wake;
+mod wake_timers;
+mod weather;
+mod weather_alerts;
+mod weather_scene;
+mod window_layouts;
+mod winds_aloft;
+mod ws_encoding;
+mod zstd_cache;
 
 // Global storage for the FSLTL converter process so we can cancel it
-static FSLTL_CONVERTER_PROCESS: Mutex<Option<ProcessWithJob>> = Mutex::new(None);
+static FSLTL_CONVERTER_PROCESS: Mutex<Option<ProcessTree>> = Mutex::new(None);
 
 // Global storage for the HTTP server shutdown channel
 static HTTP_SERVER_SHUTDOWN: Mutex<Option<broadcast::Sender<()>>> = Mutex::new(None);
@@ -87,90 +175,79 @@ fn find_mods_root(app: &tauri::AppHandle) -> PathBuf {
 
 /// Get the path to a mod type directory (aircraft or towers)
 #[tauri::command]
-fn get_mods_path(app: tauri::AppHandle, mod_type: String) -> Result<String, String> {
+fn get_mods_path(app: tauri::AppHandle, mod_type: String) -> Result<String, Error> {
     let mods_root = find_mods_root(&app);
     let mods_path = mods_root.join(&mod_type);
     Ok(mods_path.to_string_lossy().to_string())
 }
 
-/// List all mod directories for a given type (aircraft or towers)
+/// List all enabled mod directories for a given type (aircraft or towers), in load order
 #[tauri::command]
-fn list_mod_directories(app: tauri::AppHandle, mod_type: String) -> Result<Vec<String>, String> {
-    let mods_root = find_mods_root(&app);
-    let mods_path = mods_root.join(&mod_type);
-
-    if !mods_path.exists() {
-        return Ok(Vec::new());
-    }
-
-    let entries = fs::read_dir(&mods_path)
-        .map_err(|e| format!("Failed to read mods directory: {}", e))?
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().is_dir())
-        .filter_map(|e| e.file_name().into_string().ok())
+fn list_mod_directories(app: tauri::AppHandle, mod_type: String) -> Result<Vec<String>, Error> {
+    let entries = mods::enabled_mods(&app, &mod_type)
+        .into_iter()
+        .map(|m| m.name)
         .collect();
 
     Ok(entries)
 }
 
-/// Read a mod manifest JSON file
+/// Read a mod manifest JSON file, pointed at its active seasonal/time-based variant
 #[tauri::command]
-fn read_mod_manifest(path: String) -> Result<serde_json::Value, String> {
-    let manifest_path = PathBuf::from(&path).join("manifest.json");
+fn read_mod_manifest(app: tauri::AppHandle, path: String) -> Result<serde_json::Value, Error> {
+    let mod_path = PathBuf::from(&path);
+    let manifest_path = mod_path.join("manifest.json");
     let content = fs::read_to_string(&manifest_path)
-        .map_err(|e| format!("Failed to read manifest at {:?}: {}", manifest_path, e))?;
-    serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse manifest JSON: {}", e))
+        .map_err(|e| Error::io(format!("Failed to read manifest at {:?}: {}", manifest_path, e)))?;
+    let mut manifest = serde_json::from_str(&content)
+        .map_err(|e| Error::invalid_input(format!("Failed to parse manifest JSON: {}", e)))?;
+
+    // Mod folders live in mods/{modType}/
+    let mod_type = mod_path
+        .parent()
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    mod_variants::apply_to_manifest(&app, &mod_type, &mut manifest);
+    Ok(manifest)
 }
 
 /// List all VMR (Visual Model Rules) files in the mods directory
 /// Scans both mods/ root and mods/aircraft/ for .vmr files
 #[tauri::command]
-fn list_vmr_files(app: tauri::AppHandle) -> Result<Vec<String>, String> {
+fn list_vmr_files(app: tauri::AppHandle) -> Result<Vec<String>, Error> {
     let mods_root = find_mods_root(&app);
 
-    let mut vmr_files = Vec::new();
-
-    // Helper to scan a directory for .vmr files
-    let scan_dir = |dir: &PathBuf, files: &mut Vec<String>| {
-        if dir.exists() {
-            if let Ok(entries) = fs::read_dir(dir) {
-                for entry in entries.filter_map(|e| e.ok()) {
-                    let path = entry.path();
-                    if path.is_file() {
-                        if let Some(ext) = path.extension() {
-                            if ext.to_string_lossy().to_lowercase() == "vmr" {
-                                files.push(path.to_string_lossy().to_string());
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    };
-
-    // Scan mods/ root
-    scan_dir(&mods_root, &mut vmr_files);
-
-    // Scan mods/aircraft/
-    let aircraft_path = mods_root.join("aircraft");
-    scan_dir(&aircraft_path, &mut vmr_files);
-
-    // Sort for consistent load order
-    vmr_files.sort();
+    // Sorted for consistent load order
+    let vmr_files = vmr::list_vmr_paths(&mods_root)
+        .iter()
+        .map(|p| p.to_string_lossy().to_string())
+        .collect();
 
     Ok(vmr_files)
 }
 
 /// Read custom tower positions from mods/tower-positions/*.json files
 /// Each file is named {ICAO}.json (case-insensitive)
-/// Also reads legacy mods/tower-positions.json for backward compatibility
+/// Also reads legacy mods/tower-positions.json for backward compatibility,
+/// and tower position content packs (lowest priority)
 /// Returns the merged JSON as a serde_json::Value
 #[tauri::command]
-fn read_tower_positions(app: tauri::AppHandle) -> Result<serde_json::Value, String> {
+fn read_tower_positions(app: tauri::AppHandle) -> Result<serde_json::Value, Error> {
     let mods_root = find_mods_root(&app);
     let mut positions: serde_json::Map<String, serde_json::Value> = serde_json::Map::new();
 
+    // Content packs (lowest priority)
+    for path in content_packs::installed_files(&app, content_packs::KIND_TOWER_POSITIONS) {
+        if let Ok(content) = fs::read_to_string(&path) {
+            if let Ok(pack_positions) = serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(&content) {
+                for (icao, pos) in pack_positions {
+                    positions.insert(icao.to_uppercase(), pos);
+                }
+            }
+        }
+    }
+
     // Read legacy tower-positions.json if it exists (lower priority)
     let legacy_path = mods_root.join("tower-positions.json");
     if legacy_path.exists() {
@@ -256,13 +333,13 @@ fn update_tower_position(
     app: tauri::AppHandle,
     icao: String,
     position: TowerPositionEntry,
-) -> Result<(), String> {
+) -> Result<(), Error> {
     let mods_root = find_mods_root(&app);
     let tower_positions_dir = mods_root.join("tower-positions");
 
     // Create tower-positions directory if it doesn't exist
     fs::create_dir_all(&tower_positions_dir)
-        .map_err(|e| format!("Failed to create tower-positions directory: {}", e))?;
+        .map_err(|e| Error::io(format!("Failed to create tower-positions directory: {}", e)))?;
 
     // Write to individual file named {ICAO}.json
     let file_path = tower_positions_dir.join(format!("{}.json", icao.to_uppercase()));
@@ -270,7 +347,7 @@ fn update_tower_position(
     // If file exists, merge with existing data (preserve other view if only updating one)
     let mut entry = if file_path.exists() {
         let content = fs::read_to_string(&file_path)
-            .map_err(|e| format!("Failed to read existing position file: {}", e))?;
+            .map_err(|e| Error::io(format!("Failed to read existing position file: {}", e)))?;
         serde_json::from_str::<TowerPositionEntry>(&content).unwrap_or(TowerPositionEntry {
             view_3d: None,
             view_2d: None,
@@ -292,9 +369,9 @@ fn update_tower_position(
 
     // Write to file with pretty formatting
     let output = serde_json::to_string_pretty(&entry)
-        .map_err(|e| format!("Failed to serialize position: {}", e))?;
+        .map_err(|e| Error::Internal(format!("Failed to serialize position: {}", e)))?;
     fs::write(&file_path, output)
-        .map_err(|e| format!("Failed to write position file: {}", e))?;
+        .map_err(|e| Error::io(format!("Failed to write position file: {}", e)))?;
 
     Ok(())
 }
@@ -320,6 +397,14 @@ pub struct GlobalAirportSettings {
     pub default_icao: String,
     #[serde(default)]
     pub recent_airports: Vec<String>,
+    /// OpenAIP API key for airspace boundaries outside the US
+    #[serde(default)]
+    pub openaip_api_key: String,
+    /// FAA NOTAM API credentials (NOTAMs fall back to FAA NOTAM Search without them)
+    #[serde(default)]
+    pub notam_client_id: String,
+    #[serde(default)]
+    pub notam_client_secret: String,
 }
 
 /// Server configuration within global settings
@@ -332,9 +417,28 @@ pub struct GlobalServerSettings {
     /// When set, clients must send this as Bearer token in Authorization header
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub auth_token: Option<String>,
+    /// Optional token for admin endpoints (`/api/debug/*`), sent in the
+    /// X-Admin-Token header. Requests from the host itself don't need it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admin_token: Option<String>,
     /// If true, only allow connections from local network (192.168.x.x, 10.x.x.x, 172.16-31.x.x)
     #[serde(default)]
     pub require_local_network: bool,
+    /// How often batched vNAS aircraft updates are sent to the app and remote clients (Hz, see vnas_batch.rs)
+    #[serde(default = "default_broadcast_rate_hz")]
+    pub broadcast_rate_hz: f64,
+    /// Extra folders that symlinks in served folders (mods, FSLTL output) may
+    /// point into, e.g. a network share (see safe_path.rs). Restart the server to apply.
+    #[serde(default)]
+    pub allowed_file_roots: Vec<String>,
+    /// Access from outside the LAN over Tailscale/WireGuard or with client
+    /// certificates (see remote_access.rs). Restart the server to apply.
+    #[serde(default)]
+    pub remote_access: remote_access::RemoteAccessSettings,
+}
+
+fn default_broadcast_rate_hz() -> f64 {
+    2.0
 }
 
 /// RealTraffic data source settings
@@ -403,6 +507,9 @@ pub struct GlobalCameraBookmark {
     pub view_mode: String,  // "3d" or "topdown"
     #[serde(skip_serializing_if = "Option::is_none")]
     pub topdown_altitude: Option<f64>,
+    /// Runway ends the view is for (e.g., ["04L", "04R"]), see view_presets.rs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub runways: Vec<String>,
 }
 
 /// Per-airport viewport configuration
@@ -461,13 +568,10 @@ impl Default for GlobalViewportSettings {
 }
 
 /// Display settings shared across all browsers for consistent appearance
-/// These control datablock labels, leader lines, and filtering
+/// These control datablock labels and filtering (layout: see datablocks.rs)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GlobalDisplaySettings {
-    /// Leader line distance (1-5, default: 2)
-    #[serde(default = "default_leader_distance")]
-    pub leader_distance: u8,
     /// Default datablock direction (numpad-style position 1-9, default: 7)
     #[serde(default = "default_datablock_direction")]
     pub default_datablock_direction: u8,
@@ -492,11 +596,11 @@ pub struct GlobalDisplaySettings {
     /// Minimum groundspeed (kts) for ground labels when mode is "moving" (default: 2)
     #[serde(default = "default_ground_label_min_speed")]
     pub ground_label_min_speed: f64,
+    /// Positions kept per aircraft for trails (default: 60, see trails.rs)
+    #[serde(default = "default_trail_length")]
+    pub trail_length: u32,
 }
 
-fn default_leader_distance() -> u8 {
-    2
-}
 fn default_datablock_direction() -> u8 {
     7
 }
@@ -515,11 +619,13 @@ fn default_ground_label_mode() -> String {
 fn default_ground_label_min_speed() -> f64 {
     2.0
 }
+fn default_trail_length() -> u32 {
+    60
+}
 
 impl Default for GlobalDisplaySettings {
     fn default() -> Self {
         GlobalDisplaySettings {
-            leader_distance: 2,
             default_datablock_direction: 7,
             datablock_mode: "full".to_string(),
             label_visibility_distance: 30.0,
@@ -528,6 +634,489 @@ impl Default for GlobalDisplaySettings {
             auto_avoid_overlaps: true,
             ground_label_mode: "all".to_string(),
             ground_label_min_speed: 2.0,
+            trail_length: 60,
+        }
+    }
+}
+
+/// Weather feature settings shared across all browsers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalWeatherSettings {
+    /// Track lightning strikes around the active airport (Blitzortung.org, default: false)
+    #[serde(default)]
+    pub lightning_enabled: bool,
+    /// Radius around the airport to track strikes in nautical miles (default: 50)
+    #[serde(default = "default_lightning_radius_nm")]
+    pub lightning_radius_nm: f64,
+}
+
+fn default_lightning_radius_nm() -> f64 {
+    50.0
+}
+
+impl Default for GlobalWeatherSettings {
+    fn default() -> Self {
+        GlobalWeatherSettings {
+            lightning_enabled: false,
+            lightning_radius_nm: 50.0,
+        }
+    }
+}
+
+/// Sky rendering settings shared across all browsers so every client shows the same sky
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalCelestialSettings {
+    /// Show the moon (default: true)
+    #[serde(default = "default_true")]
+    pub show_moon: bool,
+    /// Show stars at night (default: true)
+    #[serde(default = "default_true")]
+    pub show_stars: bool,
+    /// Render the sun's glare/glow (default: true)
+    #[serde(default = "default_true")]
+    pub sun_glare: bool,
+}
+
+impl Default for GlobalCelestialSettings {
+    fn default() -> Self {
+        GlobalCelestialSettings {
+            show_moon: true,
+            show_stars: true,
+            sun_glare: true,
+        }
+    }
+}
+
+/// Simulated clock shared by all browsers (see clock.rs)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalClockSettings {
+    /// "real", "offset" (offset from UTC) or "fixed" (frozen time) (default: "real")
+    #[serde(default = "default_clock_mode")]
+    pub mode: String,
+    /// Offset from UTC in milliseconds for "offset" mode (default: 0)
+    #[serde(default)]
+    pub offset_ms: i64,
+    /// Frozen time (Unix ms) for "fixed" mode
+    #[serde(default)]
+    pub fixed_time: Option<u64>,
+}
+
+fn default_clock_mode() -> String {
+    "real".to_string()
+}
+
+impl Default for GlobalClockSettings {
+    fn default() -> Self {
+        GlobalClockSettings {
+            mode: "real".to_string(),
+            offset_ms: 0,
+            fixed_time: None,
+        }
+    }
+}
+
+/// Instant replay settings (see replay.rs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalReplaySettings {
+    /// Minutes of traffic kept in the host's rolling instant replay buffer (default: 10)
+    #[serde(default = "default_instant_replay_minutes")]
+    pub instant_replay_minutes: u32,
+}
+
+fn default_instant_replay_minutes() -> u32 {
+    10
+}
+
+impl Default for GlobalReplaySettings {
+    fn default() -> Self {
+        GlobalReplaySettings {
+            instant_replay_minutes: 10,
+        }
+    }
+}
+
+/// Logging and diagnostics settings (see logging.rs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalDiagnosticsSettings {
+    /// Log level filter, e.g. "info" or "info,app_lib::vnas=debug" (default: "info")
+    #[serde(default = "default_log_level")]
+    pub log_level: String,
+}
+
+fn default_log_level() -> String {
+    "info".to_string()
+}
+
+impl Default for GlobalDiagnosticsSettings {
+    fn default() -> Self {
+        GlobalDiagnosticsSettings {
+            log_level: default_log_level(),
+        }
+    }
+}
+
+/// Power settings (see keep_awake.rs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalPowerSettings {
+    /// Keep the display and system awake: "off", "always" (while the app runs)
+    /// or "whileActive" (during replay playback or a timelapse) (default: "off")
+    #[serde(default = "default_keep_awake")]
+    pub keep_awake: String,
+}
+
+fn default_keep_awake() -> String {
+    "off".to_string()
+}
+
+impl Default for GlobalPowerSettings {
+    fn default() -> Self {
+        GlobalPowerSettings {
+            keep_awake: default_keep_awake(),
+        }
+    }
+}
+
+/// Start at login (see autostart.rs)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalAutostartSettings {
+    /// Start the app when the user logs in (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Start without a window, with the HTTP server on (default: false)
+    #[serde(default)]
+    pub headless: bool,
+}
+
+/// Content packs (see content_packs.rs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalContentPackSettings {
+    /// Index files listing the available packs
+    #[serde(default)]
+    pub index_urls: Vec<String>,
+    /// Ids of the packs to install and keep up to date
+    #[serde(default)]
+    pub enabled: Vec<String>,
+    /// Check the indexes for updates on startup (default: true)
+    #[serde(default = "default_true")]
+    pub check_on_startup: bool,
+}
+
+impl Default for GlobalContentPackSettings {
+    fn default() -> Self {
+        GlobalContentPackSettings {
+            index_urls: Vec::new(),
+            enabled: Vec::new(),
+            check_on_startup: true,
+        }
+    }
+}
+
+/// App update settings (see updater.rs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalUpdateSettings {
+    /// Release channel: "stable" or "beta" (pre-releases too) (default: "stable")
+    #[serde(default = "default_update_channel")]
+    pub channel: String,
+    /// Version not offered again by automatic checks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skipped_version: Option<String>,
+    /// No updates offered by automatic checks before this time (Unix ms)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deferred_until: Option<u64>,
+}
+
+fn default_update_channel() -> String {
+    "stable".to_string()
+}
+
+impl Default for GlobalUpdateSettings {
+    fn default() -> Self {
+        GlobalUpdateSettings {
+            channel: default_update_channel(),
+            skipped_version: None,
+            deferred_until: None,
+        }
+    }
+}
+
+/// One system-wide hotkey (see hotkeys.rs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HotkeyBinding {
+    /// Accelerator such as "CommandOrControl+Alt+1"
+    pub shortcut: String,
+    /// Control action, e.g. "bookmark", "toggleViewMode" or "instantReplay" (see control.rs)
+    pub action: String,
+    /// Action value, e.g. the bookmark name or slot for "bookmark"
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+/// System-wide hotkeys that work while the window isn't focused (see hotkeys.rs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalHotkeySettings {
+    /// Register the hotkeys (default: false, they take the keys from other apps)
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_hotkey_bindings")]
+    pub bindings: Vec<HotkeyBinding>,
+}
+
+fn default_hotkey_bindings() -> Vec<HotkeyBinding> {
+    let binding = |shortcut: &str, action: &str, value: Option<&str>| HotkeyBinding {
+        shortcut: shortcut.to_string(),
+        action: action.to_string(),
+        value: value.map(str::to_string),
+    };
+    vec![
+        binding("CommandOrControl+Alt+1", "bookmark", Some("1")),
+        binding("CommandOrControl+Alt+2", "bookmark", Some("2")),
+        binding("CommandOrControl+Alt+3", "bookmark", Some("3")),
+        binding("CommandOrControl+Alt+4", "bookmark", Some("4")),
+        binding("CommandOrControl+Alt+T", "toggleViewMode", None),
+        binding("CommandOrControl+Alt+R", "instantReplay", None),
+    ]
+}
+
+impl Default for GlobalHotkeySettings {
+    fn default() -> Self {
+        GlobalHotkeySettings {
+            enabled: false,
+            bindings: default_hotkey_bindings(),
+        }
+    }
+}
+
+/// Gamepad stick or analog trigger driving a camera motion (see gamepad.rs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GamepadAxisBinding {
+    /// Axis ("LeftStickX", "RightStickY", ...) or analog button ("RightTrigger2")
+    pub input: String,
+    /// "forward", "right", "up", "heading", "pitch" or "zoom" (positive zooms in)
+    pub target: String,
+    /// Multiplier, negative to invert (default: 1)
+    #[serde(default = "default_gamepad_scale")]
+    pub scale: f32,
+}
+
+fn default_gamepad_scale() -> f32 {
+    1.0
+}
+
+/// Gamepad button running a control action (see gamepad.rs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GamepadButtonBinding {
+    /// Button ("South", "North", "DPadUp", ...)
+    pub button: String,
+    /// Control action (see control.rs)
+    pub action: String,
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+/// USB joystick / game controller camera input (see gamepad.rs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalGamepadSettings {
+    /// Read gamepads on the host (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Stick deflection ignored around center, 0-1 (default: 0.15)
+    #[serde(default = "default_gamepad_deadzone")]
+    pub deadzone: f32,
+    #[serde(default = "default_gamepad_axes")]
+    pub axes: Vec<GamepadAxisBinding>,
+    #[serde(default = "default_gamepad_buttons")]
+    pub buttons: Vec<GamepadButtonBinding>,
+}
+
+fn default_gamepad_deadzone() -> f32 {
+    0.15
+}
+
+fn default_gamepad_axes() -> Vec<GamepadAxisBinding> {
+    let axis = |input: &str, target: &str, scale: f32| GamepadAxisBinding {
+        input: input.to_string(),
+        target: target.to_string(),
+        scale,
+    };
+    vec![
+        axis("LeftStickY", "forward", 1.0),
+        axis("LeftStickX", "right", 1.0),
+        axis("RightStickX", "heading", 1.0),
+        axis("RightStickY", "pitch", 1.0),
+        axis("RightTrigger2", "zoom", 1.0),
+        axis("LeftTrigger2", "zoom", -1.0),
+    ]
+}
+
+fn default_gamepad_buttons() -> Vec<GamepadButtonBinding> {
+    let button = |button: &str, action: &str, value: Option<&str>| GamepadButtonBinding {
+        button: button.to_string(),
+        action: action.to_string(),
+        value: value.map(str::to_string),
+    };
+    vec![
+        button("DPadUp", "bookmark", Some("1")),
+        button("DPadRight", "bookmark", Some("2")),
+        button("DPadDown", "bookmark", Some("3")),
+        button("DPadLeft", "bookmark", Some("4")),
+        button("North", "toggleViewMode", None),
+    ]
+}
+
+impl Default for GlobalGamepadSettings {
+    fn default() -> Self {
+        GlobalGamepadSettings {
+            enabled: false,
+            deadzone: default_gamepad_deadzone(),
+            axes: default_gamepad_axes(),
+            buttons: default_gamepad_buttons(),
+        }
+    }
+}
+
+/// MIDI knob or fader setting a camera value (see midi.rs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MidiControlBinding {
+    /// MIDI channel 1-16 (None: any)
+    #[serde(default)]
+    pub channel: Option<u8>,
+    /// Control change number
+    pub control: u8,
+    /// "heading", "pitch" or "zoom" (fader up zooms in)
+    pub target: String,
+}
+
+/// MIDI button (note or control change) running a control action (see midi.rs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MidiButtonBinding {
+    /// MIDI channel 1-16 (None: any)
+    #[serde(default)]
+    pub channel: Option<u8>,
+    /// "note" or "cc"
+    pub kind: String,
+    /// Note or control change number
+    pub number: u8,
+    /// Control action (see control.rs)
+    pub action: String,
+    #[serde(default)]
+    pub value: Option<String>,
+}
+
+/// MIDI controller camera input (see midi.rs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalMidiSettings {
+    /// Listen to a MIDI controller (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Input port name, or part of it (None: the first port)
+    #[serde(default)]
+    pub port: Option<String>,
+    #[serde(default = "default_midi_controls")]
+    pub controls: Vec<MidiControlBinding>,
+    #[serde(default = "default_midi_buttons")]
+    pub buttons: Vec<MidiButtonBinding>,
+}
+
+/// Knobs 1-3 of a KORG nanoKONTROL2 (factory scene)
+fn default_midi_controls() -> Vec<MidiControlBinding> {
+    ["heading", "pitch", "zoom"]
+        .iter()
+        .zip(16u8..)
+        .map(|(target, control)| MidiControlBinding {
+            channel: None,
+            control,
+            target: target.to_string(),
+        })
+        .collect()
+}
+
+/// Solo buttons 1-8 of a KORG nanoKONTROL2 load bookmarks 1-8
+fn default_midi_buttons() -> Vec<MidiButtonBinding> {
+    (1..=8u8)
+        .map(|slot| MidiButtonBinding {
+            channel: None,
+            kind: "cc".to_string(),
+            number: 31 + slot,
+            action: "bookmark".to_string(),
+            value: Some(slot.to_string()),
+        })
+        .collect()
+}
+
+impl Default for GlobalMidiSettings {
+    fn default() -> Self {
+        GlobalMidiSettings {
+            enabled: false,
+            port: None,
+            controls: default_midi_controls(),
+            buttons: default_midi_buttons(),
+        }
+    }
+}
+
+/// Offline terrain and imagery cache settings (see tile_cache.rs)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalTileCacheSettings {
+    /// Load terrain and imagery through the host's disk cache (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Disk quota in gigabytes (default: 2)
+    #[serde(default = "default_tile_cache_max_size_gb")]
+    pub max_size_gb: f64,
+    /// Radius downloaded around each airport (nm, default: 10)
+    #[serde(default = "default_tile_cache_radius_nm")]
+    pub radius_nm: f64,
+    /// Airports to download tiles for
+    #[serde(default)]
+    pub airports: Vec<String>,
+    /// Download the final approach corridors of the runways in use at the
+    /// active airport when they change (default: true)
+    #[serde(default = "default_true")]
+    pub prefetch_approaches: bool,
+    /// Length of the approach corridors from the threshold (nm, default: 12)
+    #[serde(default = "default_approach_corridor_nm")]
+    pub approach_corridor_nm: f64,
+}
+
+fn default_tile_cache_max_size_gb() -> f64 {
+    2.0
+}
+
+fn default_tile_cache_radius_nm() -> f64 {
+    10.0
+}
+
+fn default_approach_corridor_nm() -> f64 {
+    12.0
+}
+
+impl Default for GlobalTileCacheSettings {
+    fn default() -> Self {
+        GlobalTileCacheSettings {
+            enabled: false,
+            max_size_gb: default_tile_cache_max_size_gb(),
+            radius_nm: default_tile_cache_radius_nm(),
+            airports: Vec::new(),
+            prefetch_approaches: true,
+            approach_corridor_nm: default_approach_corridor_nm(),
         }
     }
 }
@@ -547,6 +1136,58 @@ pub struct GlobalSettings {
     pub viewports: GlobalViewportSettings,
     #[serde(default)]
     pub display: GlobalDisplaySettings,
+    #[serde(default)]
+    pub weather: GlobalWeatherSettings,
+    #[serde(default)]
+    pub celestial: GlobalCelestialSettings,
+    #[serde(default)]
+    pub clock: GlobalClockSettings,
+    #[serde(default)]
+    pub replay: GlobalReplaySettings,
+    #[serde(default)]
+    pub tile_cache: GlobalTileCacheSettings,
+    #[serde(default)]
+    pub map_providers: map_providers::MapProviderSettings,
+    #[serde(default)]
+    pub diagnostics: GlobalDiagnosticsSettings,
+    #[serde(default)]
+    pub power: GlobalPowerSettings,
+    #[serde(default)]
+    pub autostart: GlobalAutostartSettings,
+    #[serde(default)]
+    pub update: GlobalUpdateSettings,
+    #[serde(default)]
+    pub content_packs: GlobalContentPackSettings,
+    #[serde(default)]
+    pub hotkeys: GlobalHotkeySettings,
+    #[serde(default)]
+    pub gamepad: GlobalGamepadSettings,
+    #[serde(default)]
+    pub midi: GlobalMidiSettings,
+    #[serde(default)]
+    pub units: units::UnitSettings,
+    #[serde(default)]
+    pub atc_notifications: atc_watch::AtcNotificationSettings,
+    #[serde(default)]
+    pub audio: audio_relay::AudioRelaySettings,
+    #[serde(default)]
+    pub weather_alerts: weather_alerts::WeatherAlertSettings,
+    #[serde(default)]
+    pub datablock: datablocks::DatablockLayout,
+    #[serde(default)]
+    pub view_presets: view_presets::ViewPresetSettings,
+    #[serde(default)]
+    pub federation: federation::FederationSettings,
+    #[serde(default)]
+    pub udp_output: udp_output::UdpOutputSettings,
+    #[serde(default)]
+    pub alert_sounds: sound_packs::AlertSoundSettings,
+    #[serde(default)]
+    pub server_schedule: server_schedule::ServerScheduleSettings,
+    #[serde(default)]
+    pub event_schedule: event_schedule::EventScheduleSettings,
+    #[serde(default)]
+    pub lod: lod::LodSettings,
 }
 
 impl Default for GlobalSettings {
@@ -562,37 +1203,70 @@ impl Default for GlobalSettings {
             airports: GlobalAirportSettings {
                 default_icao: String::new(),
                 recent_airports: Vec::new(),
+                openaip_api_key: String::new(),
+                notam_client_id: String::new(),
+                notam_client_secret: String::new(),
             },
             server: GlobalServerSettings {
                 port: 8765,
                 enabled: false,
                 auth_token: None,
+                admin_token: None,
                 require_local_network: false,
+                broadcast_rate_hz: default_broadcast_rate_hz(),
+                allowed_file_roots: Vec::new(),
+                remote_access: remote_access::RemoteAccessSettings::default(),
             },
             realtraffic: GlobalRealTrafficSettings::default(),
             viewports: GlobalViewportSettings::default(),
             display: GlobalDisplaySettings::default(),
+            weather: GlobalWeatherSettings::default(),
+            celestial: GlobalCelestialSettings::default(),
+            clock: GlobalClockSettings::default(),
+            replay: GlobalReplaySettings::default(),
+            tile_cache: GlobalTileCacheSettings::default(),
+            map_providers: map_providers::MapProviderSettings::default(),
+            diagnostics: GlobalDiagnosticsSettings::default(),
+            power: GlobalPowerSettings::default(),
+            autostart: GlobalAutostartSettings::default(),
+            update: GlobalUpdateSettings::default(),
+            content_packs: GlobalContentPackSettings::default(),
+            hotkeys: GlobalHotkeySettings::default(),
+            gamepad: GlobalGamepadSettings::default(),
+            midi: GlobalMidiSettings::default(),
+            units: units::UnitSettings::default(),
+            atc_notifications: atc_watch::AtcNotificationSettings::default(),
+            audio: audio_relay::AudioRelaySettings::default(),
+            weather_alerts: weather_alerts::WeatherAlertSettings::default(),
+            datablock: datablocks::DatablockLayout::default(),
+            view_presets: view_presets::ViewPresetSettings::default(),
+            federation: federation::FederationSettings::default(),
+            udp_output: udp_output::UdpOutputSettings::default(),
+            alert_sounds: sound_packs::AlertSoundSettings::default(),
+            server_schedule: server_schedule::ServerScheduleSettings::default(),
+            event_schedule: event_schedule::EventScheduleSettings::default(),
+            lod: lod::LodSettings::default(),
         }
     }
 }
 
 /// Get the path to the global settings file
-fn get_global_settings_file(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+fn get_global_settings_file(app: &tauri::AppHandle) -> Result<PathBuf, Error> {
     let app_data = app
         .path()
         .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))?;
 
     // Ensure directory exists
     fs::create_dir_all(&app_data)
-        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+        .map_err(|e| Error::io(format!("Failed to create app data directory: {}", e)))?;
 
-    Ok(app_data.join("global-settings.json"))
+    Ok(app_data.join(cli::settings_file_name()))
 }
 
 /// Get the path to the global settings file (for diagnostics)
 #[tauri::command]
-fn get_global_settings_path(app: tauri::AppHandle) -> Result<String, String> {
+fn get_global_settings_path(app: tauri::AppHandle) -> Result<String, Error> {
     let path = get_global_settings_file(&app)?;
     Ok(normalize_path_string(&path))
 }
@@ -600,7 +1274,7 @@ fn get_global_settings_path(app: tauri::AppHandle) -> Result<String, String> {
 /// Read global settings from disk
 /// Returns default settings if file doesn't exist
 #[tauri::command]
-fn read_global_settings(app: tauri::AppHandle) -> Result<GlobalSettings, String> {
+fn read_global_settings(app: tauri::AppHandle) -> Result<GlobalSettings, Error> {
     let settings_file = get_global_settings_file(&app)?;
 
     if !settings_file.exists() {
@@ -609,27 +1283,43 @@ fn read_global_settings(app: tauri::AppHandle) -> Result<GlobalSettings, String>
     }
 
     let content = fs::read_to_string(&settings_file)
-        .map_err(|e| format!("Failed to read global settings: {}", e))?;
+        .map_err(|e| Error::io(format!("Failed to read global settings: {}", e)))?;
 
     // Parse with defaults for missing fields (for forward compatibility)
-    let settings: GlobalSettings = serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse global settings: {}", e))?;
+    let mut value: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| Error::Internal(format!("Failed to parse global settings: {}", e)))?;
+    datablocks::migrate_settings(&mut value);
+    let settings: GlobalSettings = serde_json::from_value(value)
+        .map_err(|e| Error::Internal(format!("Failed to parse global settings: {}", e)))?;
 
     Ok(settings)
 }
 
 /// Write global settings to disk
 #[tauri::command]
-fn write_global_settings(app: tauri::AppHandle, settings: GlobalSettings) -> Result<(), String> {
+fn write_global_settings(app: tauri::AppHandle, settings: GlobalSettings) -> Result<(), Error> {
     let settings_file = get_global_settings_file(&app)?;
 
     let content = serde_json::to_string_pretty(&settings)
-        .map_err(|e| format!("Failed to serialize global settings: {}", e))?;
+        .map_err(|e| Error::Internal(format!("Failed to serialize global settings: {}", e)))?;
 
     fs::write(&settings_file, content)
-        .map_err(|e| format!("Failed to write global settings: {}", e))?;
-
-    println!("[Settings] Global settings saved to {:?}", settings_file);
+        .map_err(|e| Error::io(format!("Failed to write global settings: {}", e)))?;
+
+    info!("[Settings] Global settings saved to {:?}", settings_file);
+    secrets::register_settings(&settings);
+    clock::apply_settings(&app, &settings.clock);
+    datablocks::apply_settings(&app, &settings.datablock);
+    trails::apply_settings(&app, &settings.display);
+    vnas_batch::apply_settings(&app, &settings.server);
+    lod::apply_settings(&app, &settings.lod);
+    udp_output::apply_settings(&app, &settings.udp_output);
+    logging::apply_settings(&app, &settings.diagnostics);
+    keep_awake::apply_settings(&app, &settings.power);
+    autostart::apply_settings(&app, &settings.autostart);
+    hotkeys::apply_settings(&app, &settings.hotkeys);
+    gamepad::apply_settings(&app, &settings.gamepad);
+    midi::apply_settings(&app, &settings.midi);
     Ok(())
 }
 
@@ -645,56 +1335,62 @@ pub struct ServerStatus {
     pub port: u16,
     pub local_url: Option<String>,
     pub lan_url: Option<String>,
+    /// IPv6 LAN URL, when the LAN URL is IPv4 and the host also has IPv6
+    pub lan_url_v6: Option<String>,
 }
 
-/// Get the LAN IP address for display
-fn get_lan_ip() -> Option<String> {
-    // Try to get the local IP address
-    #[cfg(windows)]
-    {
-        use std::process::Command;
-        // Use hostname command to get IP
-        if let Ok(output) = Command::new("hostname")
-            .creation_flags(0x08000000) // CREATE_NO_WINDOW
-            .output()
-        {
-            let hostname = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            // Try to resolve hostname to IP
-            use std::net::ToSocketAddrs;
-            if let Ok(mut addrs) = format!("{}:0", hostname).to_socket_addrs() {
-                while let Some(addr) = addrs.next() {
-                    if let std::net::SocketAddr::V4(v4) = addr {
-                        let ip = v4.ip().to_string();
-                        if !ip.starts_with("127.") {
-                            return Some(ip);
-                        }
-                    }
-                }
-            }
+impl ServerStatus {
+    fn running(port: u16) -> Self {
+        let url = |ip: IpAddr| format!("http://{}", SocketAddr::new(ip, port));
+        let lan_ip = lan::lan_ip();
+        ServerStatus {
+            running: true,
+            port,
+            local_url: Some(format!("http://localhost:{}", port)),
+            lan_url: lan_ip.map(url),
+            lan_url_v6: lan_ip.filter(IpAddr::is_ipv4).and_then(|_| lan::lan_ipv6()).map(url),
         }
     }
+}
 
-    // Fallback: try to connect to a public DNS and get the local address
-    use std::net::UdpSocket;
-    if let Ok(socket) = UdpSocket::bind("0.0.0.0:0") {
-        if socket.connect("8.8.8.8:80").is_ok() {
-            if let Ok(addr) = socket.local_addr() {
-                return Some(addr.ip().to_string());
-            }
-        }
+/// Open a file, folder or URL with the system's default handler
+pub(crate) fn open_with_system(target: &std::ffi::OsStr) -> Result<(), String> {
+    let program = if cfg!(target_os = "windows") {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    Command::new(program)
+        .arg(target)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open {}: {}", target.to_string_lossy(), e))
+}
+
+/// Forget a server that stopped on its own (crashed or failed), so the status
+/// shows it stopped and it can be started again
+pub(crate) fn mark_http_server_stopped() {
+    if let Ok(mut guard) = HTTP_SERVER_SHUTDOWN.lock() {
+        *guard = None;
+    }
+    if let Ok(mut port_guard) = HTTP_SERVER_PORT.lock() {
+        *port_guard = None;
+    }
+    if let Ok(mut vnas_guard) = VNAS_WEBSOCKET_TX.lock() {
+        *vnas_guard = None;
     }
-
-    None
 }
 
 /// Start the HTTP server for remote browser access
 #[tauri::command]
-async fn start_http_server(app: tauri::AppHandle, port: u16) -> Result<ServerStatus, String> {
+async fn start_http_server(app: tauri::AppHandle, port: u16) -> Result<ServerStatus, Error> {
     // Check if server is already running
     {
         let guard = HTTP_SERVER_SHUTDOWN.lock().map_err(|e| e.to_string())?;
         if guard.is_some() {
-            return Err("Server is already running".to_string());
+            return Err(Error::conflict("Server is already running"));
         }
     }
 
@@ -715,13 +1411,7 @@ async fn start_http_server(app: tauri::AppHandle, port: u16) -> Result<ServerSta
         *port_guard = Some(port);
     }
 
-    let lan_ip = get_lan_ip();
-    Ok(ServerStatus {
-        running: true,
-        port,
-        local_url: Some(format!("http://localhost:{}", port)),
-        lan_url: lan_ip.map(|ip| format!("http://{}:{}", ip, port)),
-    })
+    Ok(ServerStatus::running(port))
 }
 
 /// Broadcast vNAS aircraft updates to WebSocket clients (for remote browser access)
@@ -736,7 +1426,7 @@ pub fn broadcast_vnas_to_websocket(updates: Vec<server::VnasAircraftBroadcast>)
 
 /// Stop the HTTP server
 #[tauri::command]
-fn stop_http_server() -> Result<(), String> {
+fn stop_http_server() -> Result<(), Error> {
     let mut guard = HTTP_SERVER_SHUTDOWN.lock().map_err(|e| e.to_string())?;
 
     if let Some(shutdown_tx) = guard.take() {
@@ -745,10 +1435,10 @@ fn stop_http_server() -> Result<(), String> {
         if let Ok(mut port_guard) = HTTP_SERVER_PORT.lock() {
             *port_guard = None;
         }
-        println!("[Server] Shutdown signal sent");
+        info!("[Server] Shutdown signal sent");
         Ok(())
     } else {
-        Err("Server is not running".to_string())
+        Err(Error::conflict("Server is not running"))
     }
 }
 
@@ -768,19 +1458,14 @@ fn get_http_server_status() -> ServerStatus {
         .unwrap_or(8765);
 
     if is_running {
-        let lan_ip = get_lan_ip();
-        ServerStatus {
-            running: true,
-            port,
-            local_url: Some(format!("http://localhost:{}", port)),
-            lan_url: lan_ip.map(|ip| format!("http://{}:{}", ip, port)),
-        }
+        ServerStatus::running(port)
     } else {
         ServerStatus {
             running: false,
             port,
             local_url: None,
             lan_url: None,
+            lan_url_v6: None,
         }
     }
 }
@@ -791,22 +1476,22 @@ fn get_http_server_status() -> ServerStatus {
 
 /// Fetch a URL and return the response as text (bypasses CORS)
 #[tauri::command]
-async fn fetch_url(url: String) -> Result<String, String> {
-    let client = reqwest::Client::new();
+async fn fetch_url(url: String) -> Result<String, Error> {
+    let client = http_client::client();
     let response = client
         .get(&url)
         .send()
         .await
-        .map_err(|e| format!("Failed to fetch URL: {}", e))?;
+        .map_err(|e| Error::Upstream(format!("Failed to fetch URL: {}", e)))?;
 
     if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
+        return Err(Error::Upstream(format!("HTTP error: {}", response.status())));
     }
 
     response
         .text()
         .await
-        .map_err(|e| format!("Failed to read response: {}", e))
+        .map_err(|e| Error::Upstream(format!("Failed to read response: {}", e)))
 }
 
 // =============================================================================
@@ -834,8 +1519,9 @@ pub struct RealTrafficAuthResult {
 
 /// Authenticate with RealTraffic API
 #[tauri::command]
-async fn realtraffic_auth(license_key: String) -> Result<RealTrafficAuthResult, String> {
-    let client = reqwest::Client::new();
+async fn realtraffic_auth(license_key: String) -> Result<RealTrafficAuthResult, Error> {
+    secrets::add("realtraffic", &license_key);
+    let client = http_client::client();
 
     // RealTraffic API expects form data, not JSON
     let mut form_data = std::collections::HashMap::new();
@@ -848,17 +1534,17 @@ async fn realtraffic_auth(license_key: String) -> Result<RealTrafficAuthResult,
         .form(&form_data)
         .send()
         .await
-        .map_err(|e| format!("RealTraffic auth request failed: {}", e))?;
+        .map_err(|e| Error::Upstream(format!("RealTraffic auth request failed: {}", e)))?;
 
     // Read response as text first for better error messages
     let response_text = response
         .text()
         .await
-        .map_err(|e| format!("Failed to read RealTraffic response: {}", e))?;
+        .map_err(|e| Error::Upstream(format!("Failed to read RealTraffic response: {}", e)))?;
 
     // Parse as JSON
     let data: serde_json::Value = serde_json::from_str(&response_text)
-        .map_err(|e| format!("Failed to parse RealTraffic JSON: {} - Response was: {}", e, response_text))?;
+        .map_err(|e| Error::Upstream(format!("Failed to parse RealTraffic JSON: {} - Response was: {}", e, response_text)))?;
 
     // Check API status code (200 = success)
     let status = data.get("status").and_then(|v| v.as_i64()).unwrap_or(0);
@@ -880,9 +1566,13 @@ async fn realtraffic_auth(license_key: String) -> Result<RealTrafficAuthResult,
     let license_type = data.get("type").and_then(|v| v.as_i64()).unwrap_or(0);
     let is_pro = license_type == 2;
 
+    let guid = data.get("GUID").and_then(|v| v.as_str()).map(|s| s.to_string());
+    if let Some(ref guid) = guid {
+        secrets::add("realtraffic", guid);
+    }
     Ok(RealTrafficAuthResult {
         success: true,
-        guid: data.get("GUID").and_then(|v| v.as_str()).map(|s| s.to_string()),
+        guid,
         is_pro: Some(is_pro),
         traffic_rate_limit: data.get("rrl").and_then(|v| v.as_u64()).map(|n| n as u32),
         weather_rate_limit: data.get("wrrl").and_then(|v| v.as_u64()).map(|n| n as u32),
@@ -905,8 +1595,8 @@ pub struct RealTrafficTrafficParams {
 
 /// Fetch traffic data from RealTraffic API
 #[tauri::command]
-async fn realtraffic_traffic(params: RealTrafficTrafficParams) -> Result<String, String> {
-    let client = reqwest::Client::new();
+async fn realtraffic_traffic(params: RealTrafficTrafficParams) -> Result<String, Error> {
+    let client = http_client::client();
 
     // RealTraffic API expects form data, not JSON
     // Field names: GUID (uppercase), querytype, top/bottom/left/right for bbox
@@ -929,16 +1619,16 @@ async fn realtraffic_traffic(params: RealTrafficTrafficParams) -> Result<String,
         .form(&form_data)
         .send()
         .await
-        .map_err(|e| format!("RealTraffic traffic request failed: {}", e))?;
+        .map_err(|e| Error::Upstream(format!("RealTraffic traffic request failed: {}", e)))?;
 
     if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
+        return Err(Error::Upstream(format!("HTTP error: {}", response.status())));
     }
 
     response
         .text()
         .await
-        .map_err(|e| format!("Failed to read RealTraffic response: {}", e))
+        .map_err(|e| Error::Upstream(format!("Failed to read RealTraffic response: {}", e)))
 }
 
 /// RealTraffic parked traffic request parameters
@@ -955,8 +1645,8 @@ pub struct RealTrafficParkedParams {
 /// Fetch parked aircraft data from RealTraffic API
 /// Returns aircraft with zero groundspeed that haven't moved for 10min-24h
 #[tauri::command]
-async fn realtraffic_parked_traffic(params: RealTrafficParkedParams) -> Result<String, String> {
-    let client = reqwest::Client::new();
+async fn realtraffic_parked_traffic(params: RealTrafficParkedParams) -> Result<String, Error> {
+    let client = http_client::client();
 
     // RealTraffic API expects form data, not JSON
     // Field names: GUID (uppercase), querytype, top/bottom/left/right for bbox
@@ -975,23 +1665,23 @@ async fn realtraffic_parked_traffic(params: RealTrafficParkedParams) -> Result<S
         .form(&form_data)
         .send()
         .await
-        .map_err(|e| format!("RealTraffic parked traffic request failed: {}", e))?;
+        .map_err(|e| Error::Upstream(format!("RealTraffic parked traffic request failed: {}", e)))?;
 
     if !response.status().is_success() {
-        return Err(format!("HTTP error: {}", response.status()));
+        return Err(Error::Upstream(format!("HTTP error: {}", response.status())));
     }
 
     response
         .text()
         .await
-        .map_err(|e| format!("Failed to read RealTraffic response: {}", e))
+        .map_err(|e| Error::Upstream(format!("Failed to read RealTraffic response: {}", e)))
 }
 
 /// Deauthenticate from RealTraffic API
 /// Releases the session on the server, allowing immediate reconnection
 #[tauri::command]
-async fn realtraffic_deauth(guid: String) -> Result<(), String> {
-    let client = reqwest::Client::new();
+async fn realtraffic_deauth(guid: String) -> Result<(), Error> {
+    let client = http_client::client();
 
     // RealTraffic API expects form data, not JSON
     let mut form_data = std::collections::HashMap::new();
@@ -1003,21 +1693,21 @@ async fn realtraffic_deauth(guid: String) -> Result<(), String> {
         .form(&form_data)
         .send()
         .await
-        .map_err(|e| format!("RealTraffic deauth request failed: {}", e))?;
+        .map_err(|e| Error::Upstream(format!("RealTraffic deauth request failed: {}", e)))?;
 
     // Read response for logging purposes
     let response_text = response
         .text()
         .await
-        .map_err(|e| format!("Failed to read RealTraffic response: {}", e))?;
+        .map_err(|e| Error::Upstream(format!("Failed to read RealTraffic response: {}", e)))?;
 
     // Parse to check status (optional - deauth is fire-and-forget)
     if let Ok(data) = serde_json::from_str::<serde_json::Value>(&response_text) {
         let status = data.get("status").and_then(|v| v.as_i64()).unwrap_or(0);
         if status == 200 {
-            println!("[RealTraffic] Deauth successful");
+            info!("[RealTraffic] Deauth successful");
         } else {
-            println!("[RealTraffic] Deauth returned status {}: {}", status, response_text);
+            info!("[RealTraffic] Deauth returned status {}: {}", status, response_text);
         }
     }
 
@@ -1056,7 +1746,7 @@ pub struct FSLTLProgress {
 
 /// Pick a folder using native dialog
 #[tauri::command]
-async fn pick_folder(app: tauri::AppHandle) -> Result<Option<String>, String> {
+async fn pick_folder(app: tauri::AppHandle) -> Result<Option<String>, Error> {
     let folder = app.dialog()
         .file()
         .blocking_pick_folder();
@@ -1069,15 +1759,15 @@ async fn pick_folder(app: tauri::AppHandle) -> Result<Option<String>, String> {
 
 /// Read a text file from disk
 #[tauri::command]
-fn read_text_file(path: String) -> Result<String, String> {
+fn read_text_file(path: String) -> Result<String, Error> {
     fs::read_to_string(&path)
-        .map_err(|e| format!("Failed to read file {}: {}", path, e))
+        .map_err(|e| Error::io(format!("Failed to read file {}: {}", path, e)))
 }
 
 /// Load and parse a model manifest.json file from a model directory
 /// Returns the manifest JSON or null if file doesn't exist
 #[tauri::command]
-fn load_model_manifest(model_path: String) -> Result<Option<serde_json::Value>, String> {
+fn load_model_manifest(model_path: String) -> Result<Option<serde_json::Value>, Error> {
     let manifest_file = PathBuf::from(&model_path).join("manifest.json");
 
     if !manifest_file.exists() {
@@ -1085,24 +1775,24 @@ fn load_model_manifest(model_path: String) -> Result<Option<serde_json::Value>,
     }
 
     let content = fs::read_to_string(&manifest_file)
-        .map_err(|e| format!("Failed to read manifest at {:?}: {}", manifest_file, e))?;
+        .map_err(|e| Error::io(format!("Failed to read manifest at {:?}: {}", manifest_file, e)))?;
 
     serde_json::from_str(&content)
         .map(Some)
-        .map_err(|e| format!("Failed to parse manifest JSON: {}", e))
+        .map_err(|e| Error::invalid_input(format!("Failed to parse manifest JSON: {}", e)))
 }
 
 /// Write a text file to disk
 #[tauri::command]
-fn write_text_file(path: String, content: String) -> Result<(), String> {
+fn write_text_file(path: String, content: String) -> Result<(), Error> {
     // Create parent directories if needed
     if let Some(parent) = PathBuf::from(&path).parent() {
         fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to create directories: {}", e))?;
+            .map_err(|e| Error::io(format!("Failed to create directories: {}", e)))?;
     }
 
     fs::write(&path, content)
-        .map_err(|e| format!("Failed to write file {}: {}", path, e))
+        .map_err(|e| Error::io(format!("Failed to write file {}: {}", path, e)))
 }
 
 /// Check if a directory path is writable
@@ -1132,7 +1822,7 @@ fn normalize_path_string(path: &PathBuf) -> String {
 /// Get smart default output path for FSLTL models
 /// Returns (default_path, is_writable)
 #[tauri::command]
-fn get_fsltl_default_output_path(app: tauri::AppHandle) -> Result<(String, bool), String> {
+fn get_fsltl_default_output_path(app: tauri::AppHandle) -> Result<(String, bool), Error> {
     let mods_root = find_mods_root(&app);
     let mods_path = mods_root.join("aircraft").join("fsltl");
 
@@ -1147,25 +1837,25 @@ fn get_fsltl_default_output_path(app: tauri::AppHandle) -> Result<(String, bool)
     let appdata_path = app
         .path()
         .app_data_dir()
-        .map_err(|e| format!("Failed to get app data directory: {}", e))?
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))?
         .join("fsltl_models");
 
     fs::create_dir_all(&appdata_path)
-        .map_err(|e| format!("Failed to create APPDATA FSLTL directory: {}", e))?;
+        .map_err(|e| Error::io(format!("Failed to create APPDATA FSLTL directory: {}", e)))?;
 
     Ok((normalize_path_string(&appdata_path), true))
 }
 
 /// Get the FSLTL models output path (legacy - always returns mods path)
 #[tauri::command]
-fn get_fsltl_output_path(app: tauri::AppHandle) -> Result<String, String> {
+fn get_fsltl_output_path(app: tauri::AppHandle) -> Result<String, Error> {
     let (path, _) = get_fsltl_default_output_path(app)?;
     Ok(path)
 }
 
 /// Check if FSLTL source path is valid (contains FSLTL_Rules.vmr)
 #[tauri::command]
-fn validate_fsltl_source(source_path: String) -> Result<bool, String> {
+fn validate_fsltl_source(source_path: String) -> Result<bool, Error> {
     let path = PathBuf::from(&source_path);
     let vmr_path = path.join("FSLTL_Rules.vmr");
     let airplanes_path = path.join("SimObjects").join("Airplanes");
@@ -1175,7 +1865,7 @@ fn validate_fsltl_source(source_path: String) -> Result<bool, String> {
 
 /// List available aircraft folders in FSLTL source
 #[tauri::command]
-fn list_fsltl_aircraft(source_path: String) -> Result<Vec<String>, String> {
+fn list_fsltl_aircraft(source_path: String) -> Result<Vec<String>, Error> {
     let airplanes_path = PathBuf::from(&source_path)
         .join("SimObjects")
         .join("Airplanes");
@@ -1185,7 +1875,7 @@ fn list_fsltl_aircraft(source_path: String) -> Result<Vec<String>, String> {
     }
 
     let entries = fs::read_dir(&airplanes_path)
-        .map_err(|e| format!("Failed to read airplanes directory: {}", e))?
+        .map_err(|e| Error::io(format!("Failed to read airplanes directory: {}", e)))?
         .filter_map(|e| e.ok())
         .filter(|e| e.path().is_dir())
         .filter_map(|e| {
@@ -1204,11 +1894,11 @@ fn list_fsltl_aircraft(source_path: String) -> Result<Vec<String>, String> {
 
 /// Get the bundled converter executable path
 #[tauri::command]
-fn get_converter_path(app: tauri::AppHandle) -> Result<String, String> {
+fn get_converter_path(app: tauri::AppHandle) -> Result<String, Error> {
     let resource_path = app
         .path()
         .resource_dir()
-        .map_err(|e| format!("Failed to get resource directory: {}", e))?;
+        .map_err(|e| Error::Internal(format!("Failed to get resource directory: {}", e)))?;
 
     // In dev mode, CARGO_MANIFEST_DIR points to src-tauri/
     let dev_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("resources/fsltl_converter.exe");
@@ -1224,7 +1914,7 @@ fn get_converter_path(app: tauri::AppHandle) -> Result<String, String> {
         .iter()
         .find(|p| p.exists())
         .map(|p| p.to_string_lossy().to_string())
-        .ok_or_else(|| "Converter executable not found".to_string())
+        .ok_or_else(|| Error::not_found("Converter executable not found"))
 }
 
 /// Start FSLTL conversion process in background
@@ -1236,7 +1926,7 @@ fn start_fsltl_conversion(
     texture_scale: String,
     models: Vec<String>,
     progress_file: String,
-) -> Result<(), String> {
+) -> Result<(), Error> {
     // Try multiple locations for the converter:
     // 1. Resource directory (production build - bundled resources preserve directory structure)
     // 2. src-tauri/resources (dev mode)
@@ -1244,7 +1934,7 @@ fn start_fsltl_conversion(
     let resource_path = app
         .path()
         .resource_dir()
-        .map_err(|e| format!("Failed to get resource directory: {}", e))?;
+        .map_err(|e| Error::Internal(format!("Failed to get resource directory: {}", e)))?;
 
     // In dev mode, CARGO_MANIFEST_DIR points to src-tauri/
     let dev_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("resources/fsltl_converter.exe");
@@ -1263,10 +1953,10 @@ fn start_fsltl_conversion(
         .iter()
         .find(|p| p.exists())
         .ok_or_else(|| {
-            format!(
+            Error::NotFound(format!(
                 "Converter executable not found. Tried: {:?}. Run 'npm run build:converter' first.",
                 possible_paths.iter().map(|p| p.display().to_string()).collect::<Vec<_>>()
-            )
+            ))
         })?
         .clone();
 
@@ -1285,122 +1975,64 @@ fn start_fsltl_conversion(
         // Write models to a temp file to avoid command line length limits
         let models_file = PathBuf::from(&output_path).join("_models_list.txt");
         fs::write(&models_file, models.join("\n"))
-            .map_err(|e| format!("Failed to write models list: {}", e))?;
+            .map_err(|e| Error::io(format!("Failed to write models list: {}", e)))?;
         cmd.args(["--models-file", &models_file.to_string_lossy()]);
     }
 
-    // Hide console window on Windows (CREATE_NO_WINDOW = 0x08000000)
-    #[cfg(windows)]
-    cmd.creation_flags(0x08000000);
-
     // Kill any existing converter process first
     if let Ok(mut guard) = FSLTL_CONVERTER_PROCESS.lock() {
         if let Some(proc) = guard.take() {
-            drop(proc); // Drop closes the job handle, killing all processes
+            drop(proc); // Dropping kills the whole process tree
         }
     }
 
-    // Start the new process
-    let child = cmd.spawn()
-        .map_err(|e| format!("Failed to start converter: {}", e))?;
-
-    // On Windows, create a job object and assign the process to it
-    // This ensures all child processes (gltf-transform, etc.) are killed together
-    #[cfg(windows)]
-    let process_with_job = {
-        let job_handle = unsafe { CreateJobObjectW(std::ptr::null(), std::ptr::null()) };
-        if job_handle.is_null() {
-            return Err("Failed to create job object".to_string());
-        }
-
-        // Configure job to kill all processes when the job handle is closed
-        let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = unsafe { std::mem::zeroed() };
-        info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
-
-        let success = unsafe {
-            SetInformationJobObject(
-                job_handle,
-                JobObjectExtendedLimitInformation,
-                &info as *const _ as *const _,
-                std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
-            )
-        };
-
-        if success == 0 {
-            unsafe { CloseHandle(job_handle) };
-            return Err("Failed to configure job object".to_string());
-        }
-
-        // Assign the process to the job
-        let process_handle = child.as_raw_handle();
-        let success = unsafe { AssignProcessToJobObject(job_handle, process_handle) };
-
-        if success == 0 {
-            unsafe { CloseHandle(job_handle) };
-            return Err("Failed to assign process to job object".to_string());
-        }
-
-        ProcessWithJob { child, job_handle: SendableHandle(job_handle) }
-    };
-
-    #[cfg(not(windows))]
-    let process_with_job = ProcessWithJob { child };
+    // Start the new process in its own process tree, so cancelling also stops
+    // the helpers it starts (gltf-transform, etc.)
+    let process = ProcessTree::spawn(&mut cmd)
+        .map_err(|e| Error::Internal(format!("Failed to start converter: {}", e)))?;
 
     if let Ok(mut guard) = FSLTL_CONVERTER_PROCESS.lock() {
-        *guard = Some(process_with_job);
+        *guard = Some(process);
     }
 
     Ok(())
 }
 
-/// Cancel the running FSLTL conversion process
-/// On Windows, closes the job object which terminates all child processes
+/// Cancel the running FSLTL conversion process and everything it started
 #[tauri::command]
-fn cancel_fsltl_conversion() -> Result<(), String> {
+fn cancel_fsltl_conversion() -> Result<(), Error> {
     if let Ok(mut guard) = FSLTL_CONVERTER_PROCESS.lock() {
         if let Some(mut proc) = guard.take() {
-            let pid = proc.child.id();
-
-            // Close job handle FIRST to kill all processes in the job
-            // The JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE flag terminates all processes
-            // when the handle is closed
-            #[cfg(windows)]
-            {
-                if !proc.job_handle.0.is_null() {
-                    unsafe { CloseHandle(proc.job_handle.0) };
-                    proc.job_handle.0 = std::ptr::null_mut(); // Prevent double-close in Drop
-                }
-            }
-
-            // On non-Windows, explicitly kill the parent process
-            #[cfg(not(windows))]
-            {
-                let _ = proc.child.kill();
-            }
-
-            // Now wait for the child process to fully exit (should be quick since we killed it)
-            let _ = proc.child.wait();
-
-            println!("[FSLTL] Converter process tree terminated (PID {})", pid);
+            let pid = proc.id();
+            proc.kill();
+            info!("[FSLTL] Converter process tree terminated (PID {})", pid);
             return Ok(());
         }
     }
-    Err("No conversion process running".to_string())
+    Err(Error::conflict("No conversion process running"))
+}
+
+/// Whether an FSLTL conversion is running
+fn fsltl_conversion_running() -> bool {
+    FSLTL_CONVERTER_PROCESS
+        .lock()
+        .map(|mut guard| guard.as_mut().is_some_and(|proc| proc.is_running()))
+        .unwrap_or(false)
 }
 
 /// Read conversion progress from JSON file
 #[tauri::command]
-fn read_conversion_progress(progress_file: String) -> Result<FSLTLProgress, String> {
+fn read_conversion_progress(progress_file: String) -> Result<FSLTLProgress, Error> {
     let content = fs::read_to_string(&progress_file)
-        .map_err(|e| format!("Failed to read progress file: {}", e))?;
+        .map_err(|e| Error::io(format!("Failed to read progress file: {}", e)))?;
 
     serde_json::from_str(&content)
-        .map_err(|e| format!("Failed to parse progress JSON: {}", e))
+        .map_err(|e| Error::Internal(format!("Failed to parse progress JSON: {}", e)))
 }
 
 /// Check if a model GLB file exists in the output directory
 #[tauri::command]
-fn check_fsltl_model_exists(output_path: String, model_name: String) -> Result<bool, String> {
+fn check_fsltl_model_exists(output_path: String, model_name: String) -> Result<bool, Error> {
     // FSLTL models are stored as: output_path/TYPE/AIRLINE/model.glb or output_path/TYPE/base/model.glb
     // For now, check if any matching GLB exists
     let path = PathBuf::from(&output_path);
@@ -1425,9 +2057,9 @@ fn check_fsltl_model_exists(output_path: String, model_name: String) -> Result<b
 
 /// Delete a file from disk
 #[tauri::command]
-fn delete_file(path: String) -> Result<(), String> {
+fn delete_file(path: String) -> Result<(), Error> {
     fs::remove_file(&path)
-        .map_err(|e| format!("Failed to delete file {}: {}", path, e))
+        .map_err(|e| Error::io(format!("Failed to delete file {}: {}", path, e)))
 }
 
 /// Scanned model info from existing FSLTL output directory
@@ -1447,115 +2079,17 @@ pub struct ScannedFSLTLModel {
 /// Scan an FSLTL output directory for existing converted models
 /// Returns info about all model.glb files found
 /// Directory structure: outputPath/TYPE/AIRLINE/model.glb or outputPath/TYPE/base/model.glb
+/// Models are streamed as `fsltl-scan-progress` events while the scan runs
 #[tauri::command]
-fn scan_fsltl_models(output_path: String) -> Result<Vec<ScannedFSLTLModel>, String> {
-    let base_path = PathBuf::from(&output_path);
-
-    if !base_path.exists() {
-        return Ok(Vec::new());
-    }
-
-    let mut models = Vec::new();
-
-    // Iterate through aircraft type directories (e.g., B738, A320)
-    let type_dirs = fs::read_dir(&base_path)
-        .map_err(|e| format!("Failed to read output directory: {}", e))?;
-
-    for type_entry in type_dirs.filter_map(|e| e.ok()) {
-        let type_path = type_entry.path();
-        if !type_path.is_dir() {
-            continue;
-        }
-
-        let aircraft_type = match type_entry.file_name().into_string() {
-            Ok(name) => name,
-            Err(_) => continue,
-        };
-
-        // Skip hidden/system directories
-        if aircraft_type.starts_with('.') || aircraft_type.starts_with('_') {
-            continue;
-        }
-
-        // Iterate through airline directories (e.g., UAL, SWA, base)
-        let airline_dirs = match fs::read_dir(&type_path) {
-            Ok(dirs) => dirs,
-            Err(_) => continue,
-        };
-
-        for airline_entry in airline_dirs.filter_map(|e| e.ok()) {
-            let airline_path = airline_entry.path();
-            if !airline_path.is_dir() {
-                continue;
-            }
-
-            let airline_folder = match airline_entry.file_name().into_string() {
-                Ok(name) => name,
-                Err(_) => continue,
-            };
-
-            // Check for model.glb
-            let model_file = airline_path.join("model.glb");
-            if !model_file.exists() {
-                continue;
-            }
-
-            // Get file size
-            let file_size = fs::metadata(&model_file)
-                .map(|m| m.len())
-                .unwrap_or(0);
-
-            // Determine airline code (None if "base" folder)
-            let airline_code = if airline_folder.to_lowercase() == "base" {
-                None
-            } else {
-                Some(airline_folder.clone())
-            };
-
-            // Build model name like FSLTL_B738_AAL or FSLTL_B738_ZZZZ
-            let model_name = if let Some(ref code) = airline_code {
-                format!("FSLTL_{}_{}", aircraft_type, code)
-            } else {
-                format!("FSLTL_{}_ZZZZ", aircraft_type)
-            };
-
-            // Check for animations by reading manifest.json if it exists
-            let has_animations = {
-                let manifest_path = airline_path.join("manifest.json");
-                if manifest_path.exists() {
-                    if let Ok(content) = fs::read_to_string(&manifest_path) {
-                        if let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) {
-                            json.get("hasAnimations")
-                                .and_then(|v| v.as_bool())
-                                .unwrap_or(false)
-                        } else {
-                            false
-                        }
-                    } else {
-                        false
-                    }
-                } else {
-                    false
-                }
-            };
-
-            // Relative path for HTTP API access (e.g., "B738/AAL/model.glb")
-            let relative_path = format!("{}/{}/model.glb", aircraft_type, airline_folder);
-
-            models.push(ScannedFSLTLModel {
-                model_name,
-                model_path: normalize_path_string(&model_file),
-                relative_path,
-                aircraft_type: aircraft_type.clone(),
-                airline_code,
-                has_animations,
-                file_size,
-            });
-        }
-    }
-
-    println!("[FSLTL] Scanned {} existing models from {}", models.len(), output_path);
-    Ok(models)
+async fn scan_fsltl_models(app: tauri::AppHandle, output_path: String) -> Result<Vec<ScannedFSLTLModel>, Error> {
+    tauri::async_runtime::spawn_blocking(move || {
+        fsltl_scan::scan(&output_path, |progress| {
+            let _ = app.emit("fsltl-scan-progress", progress);
+        })
+    })
+    .await
+    .map_err(|e| Error::Internal(format!("FSLTL scan failed: {}", e)))?
+    .map_err(Error::io)
 }
 
 /// Set WebView2 browser arguments for GPU optimization
@@ -1580,27 +2114,66 @@ fn set_webview2_args() {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    startup::mark_process_start();
+    let startup_args = cli::args();
+
     // Set WebView2 GPU flags before creating the window
     set_webview2_args();
 
+    let mut context = tauri::generate_context!();
+    let mut restored_state = StateFlags::all();
+    if startup_args.fullscreen {
+        for window in &mut context.config_mut().app.windows {
+            window.fullscreen = true;
+        }
+        // Don't restore the last session's windowed state over it
+        restored_state -= StateFlags::FULLSCREEN;
+    }
+    if startup_args.headless {
+        for window in &mut context.config_mut().app.windows {
+            window.visible = false;
+        }
+        // Don't restore the last session's visibility over it
+        restored_state -= StateFlags::VISIBLE;
+    }
+    let window_state = tauri_plugin_window_state::Builder::new().with_state_flags(restored_state);
+
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_fs::init())
-        .plugin(tauri_plugin_window_state::Builder::new().build())
+        .plugin(window_state.build())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_notification::init())
+        // Terrain and imagery through the offline tile cache (see tile_cache.rs)
+        .register_asynchronous_uri_scheme_protocol("tiles", |ctx, request, responder| {
+            let app = ctx.app_handle().clone();
+            // With the query, which may name an imagery vintage
+            let path = request.uri().path_and_query().map(|p| p.to_string()).unwrap_or_default();
+            tauri::async_runtime::spawn(async move {
+                responder.respond(tile_cache::protocol_response(&app, &path).await);
+            });
+        })
+        .register_asynchronous_uri_scheme_protocol("buildings", |ctx, request, responder| {
+            let app = ctx.app_handle().clone();
+            let path = request.uri().path().to_string();
+            tauri::async_runtime::spawn(async move {
+                responder.respond(osm_buildings::protocol_response(&app, &path).await);
+            });
+        })
         .setup(|app| {
-            if cfg!(debug_assertions) {
-                app.handle().plugin(
-                    tauri_plugin_log::Builder::default()
-                        .level(log::LevelFilter::Info)
-                        .build(),
-                )?;
-            }
+            let setup_started = std::time::Instant::now();
+            logging::init_logging(app.handle());
+            crash_reports::init_crash_reports(app.handle());
+            cli::apply_startup_args(app.handle());
 
-            // Register updater plugin (desktop only)
+            // Register updater and global shortcut plugins (desktop only)
             #[cfg(desktop)]
             app.handle().plugin(tauri_plugin_updater::Builder::new().build())?;
+            #[cfg(desktop)]
+            updater::init_updater(app.handle());
+            #[cfg(desktop)]
+            app.handle().plugin(tauri_plugin_global_shortcut::Builder::new().build())?;
 
             // Set window title with version from config
             if let Some(window) = app.get_webview_window("main") {
@@ -1610,7 +2183,125 @@ pub fn run() {
             }
 
             // Initialize vNAS state
-            vnas::init_vnas_state(app.handle());
+            startup::time("vNAS", || {
+                vnas::init_vnas_state(app.handle());
+                vnas_batch::init_vnas_batching(app.handle());
+                lod::init_lod(app.handle());
+                udp_output::init_udp_output(app.handle());
+            });
+
+            startup::time("Mod registry", || {
+                // Initialize mod registry (index is built lazily on first request)
+                mods::init_mod_registry(app.handle());
+
+                // Initialize model matcher (built lazily on first match)
+                matching::init_model_matcher(app.handle());
+                coverage::init_coverage(app.handle());
+                model_usage::init_model_usage(app.handle());
+
+                // Cache parsed VMR files
+                vmr::init_vmr_cache(app.handle());
+            });
+
+            // Watch the mods folder for changes. Registering a recursive watch
+            // walks the whole folder, so it runs after the window is up.
+            let app_handle = app.handle().clone();
+            supervisor::spawn_blocking("mods-watcher", supervisor::Restart::OnPanic, move || {
+                let started = std::time::Instant::now();
+                mods::start_mods_watcher(&app_handle);
+                startup::record("Mods watcher", started, true);
+            });
+
+            // Shared simulated clock (persisted in global settings)
+            startup::time("Clock", || clock::init_clock(app.handle()));
+            startup::time("Datablocks", || datablocks::init_datablocks(app.handle()));
+
+            startup::time("Services", || {
+                // Keep the active airport's METAR fresh
+                weather::init_weather(app.handle());
+                taf::init_taf(app.handle());
+                taxi_routes::init_taxi_routes(app.handle());
+                notams::init_notams(app.handle());
+                charts::init_charts(app.handle());
+                airport_db::init_airport_db(app.handle());
+                lightning::init_lightning(app.handle());
+                federation::init_federation(app.handle());
+                traffic::init_traffic(app.handle());
+                gates::init_gates(app.handle());
+                gate_suggestions::init_gate_suggestions(app.handle());
+                vatspy::init_vatspy(app.handle());
+                replay::init_replay(app.handle());
+                timelapse::init_timelapse(app.handle());
+                squawk_alerts::init_squawk_alerts(app.handle());
+                stca::init_stca(app.handle());
+                label_layout::init_label_layout(app.handle());
+                aircraft_types::init_aircraft_types(app.handle());
+                aircraft_photos::init_aircraft_photos(app.handle());
+                airlines::init_airlines(app.handle());
+                atc_watch::init_atc_watch(app.handle());
+                weather_alerts::init_weather_alerts(app.handle());
+                datis::init_datis(app.handle());
+                controllers::init_controllers(app.handle());
+                view_presets::init_view_presets(app.handle());
+                audio_relay::init_audio_relay(app.handle());
+                afv::init_afv(app.handle());
+                runway_occupancy::init_runway_occupancy(app.handle());
+                runway_config::init_runway_config(app.handle());
+                runway_holds::init_runway_holds(app.handle());
+                go_arounds::init_go_arounds(app.handle());
+                atpa::init_atpa(app.handle());
+                arrival_sequence::init_arrival_sequence(app.handle());
+                wake_timers::init_wake_timers(app.handle());
+                follow::init_follow(app.handle());
+                trails::init_trails(app.handle());
+                tile_cache::init_tile_cache(app.handle());
+                winds_aloft::init_winds_aloft(app.handle());
+                window_layouts::init_window_layouts(app.handle());
+                keep_awake::init_keep_awake(app.handle());
+                server_schedule::init_server_schedule(app.handle());
+                event_schedule::init_event_schedule(app.handle());
+                autostart::init_autostart(app.handle());
+                control::init_control(app.handle());
+                hotkeys::init_hotkeys(app.handle());
+                gamepad::init_gamepad(app.handle());
+                midi::init_midi(app.handle());
+                content_packs::init_content_packs(app.handle());
+                scripts::init_scripts(app.handle());
+                search::init_search(app.handle());
+                selection::init_selection(app.handle());
+                drawings::init_drawings(app.handle());
+                coordination::init_coordination(app.handle());
+                event_prep::init_event_prep(app.handle());
+            });
+
+            startup::time("Background tasks", || {
+                weather::start_weather_task(app.handle());
+                lightning::start_lightning_task(app.handle());
+                traffic::start_traffic_task(app.handle());
+                atc_watch::start_atc_watch_task(app.handle());
+                datis::start_datis_task(app.handle());
+                weather_alerts::start_atis_alerts_task(app.handle());
+                afv::start_afv_task(app.handle());
+                replay::start_replay_task(app.handle());
+                vnas_batch::start_vnas_batch_task(app.handle());
+                model_usage::start_model_usage_task(app.handle());
+                federation::start_federation_task(app.handle());
+                keep_awake::start_keep_awake_task(app.handle());
+                server_schedule::start_server_schedule_task(app.handle());
+                event_schedule::start_event_schedule_task(app.handle());
+                gamepad::start_gamepad_task(app.handle());
+                midi::start_midi_task(app.handle());
+                scripts::start_scripts_task(app.handle());
+
+                // Synthetic traffic in place of VATSIM and vNAS (TOWERCAB_MOCK_TRAFFIC)
+                #[cfg(feature = "mock-traffic")]
+                mock_traffic::init_mock_traffic(app.handle());
+
+                // Download remote VMR subscriptions now and whenever they are due
+                vmr_remote::start_refresh_task(app.handle());
+                // Install updates of opted-in content packs
+                content_packs::start_update_check(app.handle());
+            });
 
             // Auto-start HTTP server if enabled in global settings or via env var
             let app_handle = app.handle().clone();
@@ -1619,6 +2310,7 @@ pub fn run() {
                 let force_start = std::env::var("TOWERCAB_AUTO_SERVER").is_ok();
 
                 // Load settings to get port (and check enabled flag if not force-starting)
+                let settings_started = std::time::Instant::now();
                 let (should_start, port) = if let Ok(settings_file) = get_global_settings_file(&app_handle) {
                     if settings_file.exists() {
                         if let Ok(content) = std::fs::read_to_string(&settings_file) {
@@ -1636,10 +2328,18 @@ pub fn run() {
                 } else {
                     (force_start, 8765)
                 };
+                startup::record("Settings load", settings_started, true);
+
+                // --port and --headless start the server regardless of settings
+                let cli_port = cli::args().port;
+                let headless = cli::args().headless;
+                let should_start = should_start || cli_port.is_some() || headless;
+                let port = cli_port.unwrap_or(port);
 
                 if should_start {
-                    println!("[Server] Auto-starting HTTP server on port {}{}", port,
-                        if force_start { " (via TOWERCAB_AUTO_SERVER)" } else { "" });
+                    let server_started = std::time::Instant::now();
+                    info!("[Server] Auto-starting HTTP server on port {}{}", port,
+                        if cli_port.is_some() { " (via --port)" } else if headless { " (via --headless)" } else if force_start { " (via TOWERCAB_AUTO_SERVER)" } else { "" });
                     match server::start_server(app_handle.clone(), port).await {
                         Ok(handles) => {
                             if let Ok(mut guard) = HTTP_SERVER_SHUTDOWN.lock() {
@@ -1648,24 +2348,29 @@ pub fn run() {
                             if let Ok(mut vnas_guard) = VNAS_WEBSOCKET_TX.lock() {
                                 *vnas_guard = Some(handles.vnas_tx);
                             }
-                            println!("[Server] Auto-started successfully");
+                            if let Ok(mut port_guard) = HTTP_SERVER_PORT.lock() {
+                                *port_guard = Some(port);
+                            }
+                            info!("[Server] Auto-started successfully");
+                            startup::record("Server start", server_started, true);
                         }
                         Err(e) => {
-                            eprintln!("[Server] Auto-start failed: {}", e);
+                            error!("[Server] Auto-start failed: {}", e);
                         }
                     }
                 }
             });
 
+            startup::record("Setup", setup_started, false);
             Ok(())
         })
-        .on_window_event(|_window, event| {
+        .on_window_event(|window, event| {
             // Kill FSLTL converter process when app window is closed
             if let tauri::WindowEvent::Destroyed = event {
+                model_usage::save(window.app_handle());
                 if let Ok(mut guard) = FSLTL_CONVERTER_PROCESS.lock() {
-                    // Taking and dropping the ProcessWithJob terminates all child processes:
-                    // - Windows: closes job handle (JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE)
-                    // - Other: Drop impl calls child.kill()
+                    // Dropping the ProcessTree kills the converter and its helpers
+                    // (job object on Windows, process group on Linux/macOS)
                     let _ = guard.take();
                 }
             }
@@ -1676,6 +2381,201 @@ pub fn run() {
             list_mod_directories,
             read_mod_manifest,
             list_vmr_files,
+            vmr::get_vmr_rules,
+            vmr::get_all_vmr_rules,
+            vmr::get_effective_vmr_rules,
+            vmr::get_vmr_state,
+            vmr::set_vmr_file_enabled,
+            vmr::set_vmr_rule_enabled,
+            vmr::add_vmr_rule,
+            vmr::update_vmr_rule,
+            vmr::delete_vmr_rule,
+            // Remote VMR subscription commands
+            vmr_remote::list_vmr_subscriptions,
+            vmr_remote::add_vmr_subscription,
+            vmr_remote::remove_vmr_subscription,
+            vmr_remote::set_vmr_subscription_enabled,
+            vmr_remote::refresh_vmr_subscriptions,
+            // Content pack commands
+            content_packs::list_content_packs,
+            content_packs::check_content_packs,
+            content_packs::set_content_pack_enabled,
+            // Model matching commands
+            matching::match_model,
+            matching::refresh_model_matcher,
+            coverage::get_model_coverage,
+            model_usage::get_model_usage_report,
+            model_usage::reset_model_usage,
+            weather::get_station_metar,
+            weather::get_station_metar_history,
+            weather::set_weather_airport,
+            taf::get_station_taf,
+            taxi_routes::get_taxi_routes,
+            touchdown::get_landing_predictions,
+            notams::get_airport_notams,
+            charts::get_airport_charts,
+            charts::get_chart_pdf_path,
+            weather_scene::get_weather_scene,
+            density_altitude::get_station_density_altitude,
+            ephemeris::get_celestial_ephemeris,
+            clock::get_sim_clock,
+            datablocks::get_datablock_layout,
+            airport_db::search_airport_db,
+            airport_db::get_airport_info,
+            airport_db::get_airport_runways,
+            airspace::generate_airspace_overlay,
+            approach::get_airport_approaches,
+            approach::import_ils_navdata,
+            gates::get_airport_gates,
+            gates::import_gates,
+            gate_suggestions::get_gate_suggestions,
+            osm_buildings::get_osm_buildings,
+            osm_layout::get_osm_layout,
+            traffic::get_traffic,
+            strips::get_airport_strips,
+            replay::get_replay_status,
+            replay::get_replay_recording,
+            replay::load_replay_file,
+            replay::start_instant_replay,
+            replay::control_replay,
+            replay::stop_replay,
+            replay_library::list_shared_replays,
+            replay_library::share_replay,
+            replay_library::pick_shared_replay_file,
+            replay_library::import_shared_replay,
+            replay_library::load_shared_replay,
+            replay_library::delete_shared_replay,
+            session_export::export_session,
+            session_stats::get_session_stats,
+            session_stats::export_session_stats,
+            timelapse::get_timelapse_status,
+            timelapse::start_timelapse,
+            timelapse::stop_timelapse,
+            timelapse::save_timelapse_frame,
+            squawk_alerts::get_squawk_alerts,
+            stca::get_stca_alerts,
+            label_layout::get_label_layout,
+            runway_occupancy::get_runway_occupancy,
+            runway_config::get_runway_configuration,
+            runway_holds::get_hold_timers,
+            go_arounds::get_session_go_arounds,
+            atpa::get_final_spacing,
+            arrival_sequence::get_arrival_sequence,
+            wake_timers::get_wake_timers,
+            follow::get_follow,
+            follow::set_follow,
+            follow::stop_follow,
+            trails::get_aircraft_trail,
+            aircraft_types::get_aircraft_type,
+            aircraft_photos::get_aircraft_photo,
+            airlines::get_airline,
+            airlines::list_airlines,
+            airlines::get_callsign_info,
+            vatspy::get_airport_atc,
+            atc_watch::get_atc_changes,
+            audio_relay::list_audio_streams,
+            afv::get_frequency_activity,
+            afv::watch_frequency_activity,
+            vatspy::import_vatspy_data,
+            sector_file::import_sector_file_overlay,
+            videomaps::import_video_maps,
+            videomaps::list_video_maps,
+            videomaps::read_video_map,
+            clock::set_sim_clock,
+            datablocks::set_datablock_layout,
+            placements::list_static_object_models,
+            placements::get_static_objects,
+            placements::save_static_object,
+            placements::delete_static_object,
+            annotations::get_airport_annotations,
+            annotations::save_airport_annotation,
+            annotations::delete_airport_annotation,
+            lightning::get_lightning_status,
+            federation::get_federation_status,
+            scripts::list_scripts,
+            scripts::reload_scripts,
+            plugins::list_plugins,
+            search::search_content,
+            selection::get_selection,
+            selection::set_selection,
+            selection::clear_selection,
+            drawings::get_drawings,
+            drawings::add_drawing,
+            drawings::delete_drawing,
+            drawings::clear_drawings,
+            drawings::set_drawing_session,
+            drawings::list_drawing_sessions,
+            coordination::get_coordination_messages,
+            coordination::send_coordination_message,
+            runway_suggestion::get_runway_suggestion,
+            view_presets::get_view_preset_suggestion,
+            datis::get_atis_comparison,
+            controllers::get_controllers,
+            controllers::get_controller,
+            winds_aloft::get_station_winds_aloft,
+            similar_types::get_similar_types,
+            similar_types::get_similar_type_overrides,
+            similar_types::set_similar_type_overrides,
+            logging::set_log_level,
+            logging::open_log_folder,
+            startup::get_startup_profile,
+            supervisor::get_task_status,
+            cli::get_startup_args,
+            tile_cache::get_tile_cache_status,
+            tile_cache::prefetch_tiles,
+            tile_cache::cancel_tile_prefetch,
+            tile_cache::clear_tile_cache,
+            event_prep::prepare_event,
+            event_prep::get_event_prep_status,
+            event_prep::cancel_event_prep,
+            map_providers::get_map_providers,
+            imagery_vintages::list_imagery_vintages,
+            terrain_packages::list_terrain_packages,
+            terrain_packages::pick_terrain_package,
+            terrain_packages::import_terrain_package,
+            terrain_packages::delete_terrain_package,
+            window_layouts::list_window_layouts,
+            window_layouts::save_window_layout,
+            window_layouts::apply_window_layout,
+            window_layouts::delete_window_layout,
+            window_layouts::open_cab_window,
+            window_layouts::assign_window_bookmark,
+            window_layouts::get_window_bookmark,
+            keep_awake::get_keep_awake_status,
+            keep_awake::set_keep_awake,
+            autostart::get_autostart_status,
+            autostart::set_autostart,
+            audit_log::get_audit_log,
+            backup::create_backup,
+            backup::list_backups,
+            backup::pick_backup_file,
+            backup::verify_backup,
+            backup::restore_backup,
+            airport_packs::export_airport_pack,
+            airport_packs::pick_airport_pack_file,
+            airport_packs::install_airport_pack,
+            updater::check_for_update,
+            updater::get_release_notes,
+            updater::install_update,
+            updater::set_update_channel,
+            updater::defer_updates,
+            updater::skip_update_version,
+            hotkeys::get_hotkey_status,
+            control::publish_control_view,
+            gamepad::get_gamepad_status,
+            midi::get_midi_status,
+            electron_import::import_electron_settings,
+            // Mod registry commands
+            mods::get_mod_registry,
+            mods::refresh_mod_registry,
+            mods::set_mod_enabled,
+            mods::set_mod_load_order,
+            // Overlay commands
+            overlays::list_overlays,
+            overlays::read_overlay_file,
+            // Alert sound pack commands
+            sound_packs::list_sound_packs,
+            sound_packs::get_sound_file_path,
             read_tower_positions,
             update_tower_position,
             // Global settings commands
@@ -1686,6 +2586,10 @@ pub fn run() {
             start_http_server,
             stop_http_server,
             get_http_server_status,
+            server_schedule::get_server_schedule_status,
+            event_schedule::get_upcoming_events,
+            event_schedule::record_event,
+            lod::get_lod_policy,
             fetch_url,
             // RealTraffic commands
             realtraffic_auth,
@@ -1720,6 +2624,6 @@ pub fn run() {
             vnas::vnas_is_connected,
             vnas::vnas_is_authenticated,
         ])
-        .run(tauri::generate_context!())
+        .run(context)
         .expect("error while running tauri application");
 }
@@ -0,0 +1,421 @@
+//! Lightning strikes near the active airport
+//!
+//! Connects to the Blitzortung.org community lightning network while lightning
+//! is enabled in the global weather settings and an airport is active, keeps
+//! strikes within the configured radius, and broadcasts them to the desktop UI
+//! (`lightning-strike` event) and remote browsers (`/api/lightning/ws`).
+//!
+//! Also tracks a "lightning within 5 nm" alert like the ones used for ramp
+//! closures: raised on the first strike within 5 nm, cleared 15 minutes after
+//! the last one (`lightning-alert` event).
+//!
+//! Blitzortung data is provided for private, non-commercial use.
+
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+
+use futures_util::{SinkExt, StreamExt};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{info, warn};
+
+use crate::geo;
+use crate::scripts;
+use crate::supervisor::{self, Restart};
+use crate::weather;
+
+/// Blitzortung WebSocket servers (tried in turn)
+const BLITZORTUNG_SERVERS: [&str; 3] = [
+    "wss://ws1.blitzortung.org/",
+    "wss://ws7.blitzortung.org/",
+    "wss://ws8.blitzortung.org/",
+];
+
+/// Subscription message sent after connecting
+const SUBSCRIBE_MESSAGE: &str = r#"{"a":111}"#;
+
+/// Strikes within this distance raise the alert (nautical miles)
+pub const ALERT_RADIUS_NM: f64 = 5.0;
+
+/// The alert clears this long after the last strike within the alert radius
+const ALERT_CLEAR_AFTER: Duration = Duration::from_secs(15 * 60);
+
+/// How long strikes are kept
+const STRIKE_RETENTION: Duration = Duration::from_secs(30 * 60);
+
+/// Maximum number of strikes kept in memory
+const MAX_STRIKES: usize = 2000;
+
+/// How often settings, the active airport and the alert are re-checked
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Delay before reconnecting after a dropped connection
+const RECONNECT_DELAY: Duration = Duration::from_secs(10);
+
+/// Lightning strike near the airport
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LightningStrike {
+    /// Unix ms
+    pub time: u64,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Distance from the airport (nautical miles)
+    pub distance_nm: f64,
+    /// Bearing from the airport (degrees true)
+    pub bearing: f64,
+}
+
+/// "Lightning within 5 nm" alert state
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LightningAlert {
+    pub active: bool,
+    /// Time of the last strike within the alert radius (Unix ms)
+    pub last_strike_at: Option<u64>,
+    /// When the alert clears if no further strikes occur (Unix ms)
+    pub clears_at: Option<u64>,
+}
+
+/// Lightning status for an airport
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LightningStatus {
+    pub enabled: bool,
+    /// Airport strikes are tracked around
+    pub icao: Option<String>,
+    pub radius_nm: f64,
+    pub connected: bool,
+    /// Strikes in the last 30 minutes, oldest first
+    pub strikes: Vec<LightningStrike>,
+    pub alert: LightningAlert,
+}
+
+/// Message relayed to remote browsers
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum LightningEvent {
+    Strike(LightningStrike),
+    Alert(LightningAlert),
+}
+
+/// Airport strikes are tracked around
+#[derive(Debug, Clone, PartialEq)]
+struct Center {
+    icao: String,
+    latitude: f64,
+    longitude: f64,
+    radius_nm: f64,
+}
+
+/// Lightning state managed by Tauri
+pub struct LightningState {
+    center: RwLock<Option<Center>>,
+    connected: RwLock<bool>,
+    strikes: Mutex<VecDeque<LightningStrike>>,
+    alert: Mutex<LightningAlert>,
+    /// Strike and alert updates for WebSocket relay
+    pub events: broadcast::Sender<LightningEvent>,
+}
+
+impl LightningState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(256);
+        Self {
+            center: RwLock::new(None),
+            connected: RwLock::new(false),
+            strikes: Mutex::new(VecDeque::new()),
+            alert: Mutex::new(LightningAlert::default()),
+            events,
+        }
+    }
+}
+
+impl Default for LightningState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Decode Blitzortung's LZW-compressed message text
+fn decode_message(encoded: &str) -> String {
+    let chars: Vec<char> = encoded.chars().collect();
+    let Some(&first) = chars.first() else {
+        return String::new();
+    };
+
+    let mut dictionary: Vec<String> = Vec::new();
+    let mut previous = first.to_string();
+    let mut output = previous.clone();
+    for &c in &chars[1..] {
+        let code = c as usize;
+        let entry = if code < 256 {
+            c.to_string()
+        } else if let Some(known) = dictionary.get(code - 256) {
+            known.clone()
+        } else {
+            // Code for the entry being defined: previous + its first char
+            let mut entry = previous.clone();
+            entry.extend(previous.chars().next());
+            entry
+        };
+        output.push_str(&entry);
+
+        let mut new_entry = previous;
+        new_entry.extend(entry.chars().next());
+        dictionary.push(new_entry);
+        previous = entry;
+    }
+    output
+}
+
+/// Strike as sent by Blitzortung
+#[derive(Deserialize)]
+struct RawStrike {
+    /// Unix ns
+    time: u64,
+    lat: f64,
+    lon: f64,
+}
+
+/// Airport to track strikes around: the active airport, if lightning is enabled
+async fn resolve_center(app: &AppHandle) -> Option<Center> {
+    let settings = crate::read_global_settings(app.clone()).ok()?.weather;
+    if !settings.lightning_enabled {
+        return None;
+    }
+
+    let icao = weather::active_airport(app)?;
+    let metar = weather::get_metar(app, &icao, false).await.ok()??;
+    Some(Center {
+        icao,
+        latitude: metar.latitude?,
+        longitude: metar.longitude?,
+        radius_nm: settings.lightning_radius_nm,
+    })
+}
+
+fn set_alert(app: &AppHandle, alert: LightningAlert) {
+    let state = app.state::<LightningState>();
+    *state.alert.lock() = alert.clone();
+    let _ = app.emit("lightning-alert", &alert);
+    scripts::alert(app, "lightning", &alert);
+    let _ = state.events.send(LightningEvent::Alert(alert));
+}
+
+/// Record a strike if it is within the radius, updating the alert
+fn handle_strike(app: &AppHandle, raw: RawStrike) {
+    let state = app.state::<LightningState>();
+    let Some(center) = state.center.read().clone() else {
+        return;
+    };
+
+    let (distance_nm, bearing) = geo::distance_and_bearing(center.latitude, center.longitude, raw.lat, raw.lon);
+    if distance_nm > center.radius_nm {
+        return;
+    }
+
+    let strike = LightningStrike {
+        time: raw.time / 1_000_000,
+        latitude: raw.lat,
+        longitude: raw.lon,
+        distance_nm: (distance_nm * 10.0).round() / 10.0,
+        bearing: bearing.round(),
+    };
+    {
+        let mut strikes = state.strikes.lock();
+        strikes.push_back(strike.clone());
+        while strikes.len() > MAX_STRIKES {
+            strikes.pop_front();
+        }
+    }
+    let _ = app.emit("lightning-strike", &strike);
+    let _ = state.events.send(LightningEvent::Strike(strike.clone()));
+
+    if distance_nm <= ALERT_RADIUS_NM {
+        let was_active = state.alert.lock().active;
+        if !was_active {
+            info!(
+                "[Lightning] Strike {:.1} nm from {}, alert raised",
+                distance_nm, center.icao
+            );
+        }
+        set_alert(
+            app,
+            LightningAlert {
+                active: true,
+                last_strike_at: Some(strike.time),
+                clears_at: Some(strike.time + ALERT_CLEAR_AFTER.as_millis() as u64),
+            },
+        );
+    }
+}
+
+/// Drop old strikes and clear the alert once it has expired
+fn expire(app: &AppHandle) {
+    let state = app.state::<LightningState>();
+    let now = now_ms();
+    let cutoff = now.saturating_sub(STRIKE_RETENTION.as_millis() as u64);
+    state.strikes.lock().retain(|s| s.time >= cutoff);
+
+    let alert = state.alert.lock().clone();
+    if alert.active && alert.clears_at.is_some_and(|t| t <= now) {
+        info!(
+            "[Lightning] No strikes within {} nm for 15 minutes, alert cleared",
+            ALERT_RADIUS_NM
+        );
+        set_alert(app, LightningAlert { active: false, ..alert });
+    }
+}
+
+/// Switch to a new center, forgetting strikes around the previous airport
+fn set_center(app: &AppHandle, center: Option<Center>) {
+    let state = app.state::<LightningState>();
+    let changed = {
+        let mut current = state.center.write();
+        let changed = current.as_ref().map(|c| &c.icao) != center.as_ref().map(|c| &c.icao);
+        *current = center;
+        changed
+    };
+    if changed {
+        state.strikes.lock().clear();
+        if state.alert.lock().active {
+            set_alert(app, LightningAlert::default());
+        }
+    }
+}
+
+/// Receive strikes from one server until the connection drops or lightning is no longer needed
+async fn run_connection(app: &AppHandle, server: &str) -> Result<(), String> {
+    let (mut socket, _) = tokio_tungstenite::connect_async(server)
+        .await
+        .map_err(|e| format!("Failed to connect to {}: {}", server, e))?;
+    socket
+        .send(Message::Text(SUBSCRIBE_MESSAGE.to_string()))
+        .await
+        .map_err(|e| format!("Failed to subscribe: {}", e))?;
+    info!("[Lightning] Connected to {}", server);
+
+    let mut check = tokio::time::interval(CHECK_INTERVAL);
+    check.tick().await;
+    loop {
+        tokio::select! {
+            message = socket.next() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    match serde_json::from_str::<RawStrike>(&decode_message(&text)) {
+                        Ok(raw) => handle_strike(app, raw),
+                        Err(e) => warn!("[Lightning] Failed to parse strike: {}", e),
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => return Err("Connection closed".to_string()),
+                Some(Ok(_)) => {}
+                Some(Err(e)) => return Err(format!("Connection error: {}", e)),
+            },
+            _ = check.tick() => {
+                expire(app);
+                let center = resolve_center(app).await;
+                let done = center.is_none();
+                set_center(app, center);
+                if done {
+                    let _ = socket.close(None).await;
+                    info!("[Lightning] Disconnected (disabled or no airport)");
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+/// Keep the lightning feed connected while it is enabled and an airport is active
+pub fn start_lightning_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn("lightning", Restart::Always, move || {
+        let app = app.clone();
+        async move {
+            let mut server_index = 0;
+            loop {
+                expire(&app);
+                let center = resolve_center(&app).await;
+                let active = center.is_some();
+                set_center(&app, center);
+                if !active {
+                    tokio::time::sleep(CHECK_INTERVAL).await;
+                    continue;
+                }
+
+                let server = BLITZORTUNG_SERVERS[server_index % BLITZORTUNG_SERVERS.len()];
+                *app.state::<LightningState>().connected.write() = true;
+                let result = run_connection(&app, server).await;
+                *app.state::<LightningState>().connected.write() = false;
+                if let Err(e) = result {
+                    warn!("[Lightning] {}", e);
+                    server_index += 1;
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                }
+            }
+        }
+    });
+}
+
+/// Current lightning status
+pub fn get_status(app: &AppHandle) -> LightningStatus {
+    expire(app);
+    let state = app.state::<LightningState>();
+    let center = state.center.read().clone();
+    let enabled = crate::read_global_settings(app.clone())
+        .map(|s| s.weather.lightning_enabled)
+        .unwrap_or(false);
+
+    let strikes = state.strikes.lock().iter().cloned().collect();
+    let alert = state.alert.lock().clone();
+    let connected = *state.connected.read();
+
+    LightningStatus {
+        enabled,
+        radius_nm: center.as_ref().map_or(0.0, |c| c.radius_nm),
+        icao: center.map(|c| c.icao),
+        connected,
+        strikes,
+        alert,
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get recent lightning strikes and the alert state for the active airport
+#[tauri::command]
+pub fn get_lightning_status(app: AppHandle) -> LightningStatus {
+    get_status(&app)
+}
+
+/// Initialize lightning state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_lightning(app: &AppHandle) {
+    app.manage(LightningState::new());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_lzw_messages() {
+        // "ABABABA": A, B, then codes for "AB" (256) and "ABA" (258, not yet defined)
+        let encoded: String = ['A', 'B', '\u{100}', '\u{102}'].iter().collect();
+        assert_eq!(decode_message(&encoded), "ABABABA");
+        assert_eq!(decode_message("plain"), "plain");
+        assert_eq!(decode_message(""), "");
+    }
+}
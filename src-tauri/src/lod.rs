@@ -0,0 +1,296 @@
+//! Density-based model level of detail
+//!
+//! At a 300-aircraft event, every display loading the full livery model of
+//! every aircraft is what drops the frame rate. The host ranks the aircraft
+//! it sees (traffic polls and vNAS batches) by distance from the active
+//! airport, and each client gets a model detail per aircraft from a budget
+//! that fits it:
+//!
+//! - `full`: the matched livery model, for the closest `lod.fullModels`
+//! - `generic`: the type's generic model, up to `lod.genericModels` in total
+//! - `billboard`: a camera-facing sprite for everything farther out
+//!
+//! The budgets in the global settings are for desktops and are scaled down
+//! for tablets, phones and clients on reduced model quality (see
+//! client_capabilities.rs), so everything is `full` until an event gets busy.
+//! The decision goes out with the traffic (`modelDetail` in `GET
+//! /api/traffic` and the `/api/vnas/ws` broadcasts) and is served at `GET
+//! /api/lod`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::client_capabilities::{ClientConfig, DeviceClass, ModelQuality};
+use crate::server::VnasAircraftBroadcast;
+use crate::traffic::TrafficSnapshot;
+
+/// Aircraft not seen for this long are no longer counted
+const STALE_AFTER: Duration = Duration::from_secs(60);
+
+/// Level of detail settings in the global settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LodSettings {
+    /// Decide model detail by traffic density (default: true)
+    pub enabled: bool,
+    /// Aircraft drawn with their full livery model on a desktop (default: 100)
+    pub full_models: usize,
+    /// Aircraft drawn with a 3D model (full or generic) on a desktop; the
+    /// rest are billboards (default: 250)
+    pub generic_models: usize,
+}
+
+impl Default for LodSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            full_models: 100,
+            generic_models: 250,
+        }
+    }
+}
+
+/// How an aircraft is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelDetail {
+    Full,
+    Generic,
+    Billboard,
+}
+
+/// Aircraft a client draws at each detail
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LodBudget {
+    /// Closest aircraft drawn with their full model
+    pub full: usize,
+    /// Closest aircraft drawn with any 3D model (including `full`)
+    pub models: usize,
+}
+
+impl LodBudget {
+    /// Detail of the aircraft at a rank (0 = closest)
+    pub fn detail(&self, rank: usize) -> ModelDetail {
+        if rank < self.full {
+            ModelDetail::Full
+        } else if rank < self.models {
+            ModelDetail::Generic
+        } else {
+            ModelDetail::Billboard
+        }
+    }
+}
+
+/// A client's budget: the settings scaled to its device and model quality
+/// (`None`: the host app, or a browser that never negotiated)
+pub fn budget(settings: &LodSettings, config: Option<&ClientConfig>) -> LodBudget {
+    let device = match config.map(|c| c.device_class) {
+        None | Some(DeviceClass::Desktop) => 1.0,
+        Some(DeviceClass::Tablet) => 0.5,
+        Some(DeviceClass::Phone) => 0.25,
+    };
+    let quality = match config.map(|c| c.model_quality) {
+        Some(ModelQuality::Reduced) => 0.25,
+        _ => 1.0,
+    };
+    let scale = |count: usize| (count as f64 * f64::min(device, quality)).round() as usize;
+    let full = scale(settings.full_models);
+    LodBudget {
+        full,
+        models: scale(settings.generic_models).max(full),
+    }
+}
+
+/// Rank aircraft by distance (nm), closest first (ties by callsign)
+fn rank<'a>(distances: impl IntoIterator<Item = (&'a String, f64)>) -> HashMap<String, usize> {
+    let mut sorted: Vec<(&String, f64)> = distances.into_iter().collect();
+    sorted.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.cmp(b.0)));
+    sorted
+        .into_iter()
+        .enumerate()
+        .map(|(rank, (callsign, _))| (callsign.clone(), rank))
+        .collect()
+}
+
+/// Level of detail state (managed by Tauri)
+pub struct LodState {
+    settings: RwLock<LodSettings>,
+    /// Distance from the active airport (nm) and when each aircraft was last seen
+    seen: Mutex<HashMap<String, (f64, Instant)>>,
+    /// Rank of each aircraft seen, closest first
+    ranks: RwLock<Arc<HashMap<String, usize>>>,
+}
+
+/// Record aircraft positions (callsign, distance from the active airport in
+/// nm) and rank everything seen recently again
+pub fn observe<'a>(app: &AppHandle, aircraft: impl IntoIterator<Item = (&'a str, f64)>) {
+    let Some(state) = app.try_state::<LodState>() else {
+        return;
+    };
+    let now = Instant::now();
+    let ranks = {
+        let mut seen = state.seen.lock();
+        for (callsign, distance_nm) in aircraft {
+            seen.insert(callsign.to_string(), (distance_nm, now));
+        }
+        seen.retain(|_, (_, at)| now.duration_since(*at) < STALE_AFTER);
+        rank(seen.iter().map(|(callsign, (distance, _))| (callsign, *distance)))
+    };
+    *state.ranks.write() = Arc::new(ranks);
+}
+
+/// Decides the detail of each aircraft for one client
+pub struct LodDecider {
+    ranks: Arc<HashMap<String, usize>>,
+    pub budget: LodBudget,
+}
+
+impl LodDecider {
+    /// Detail of an aircraft (ones not ranked yet count as the farthest)
+    pub fn detail(&self, callsign: &str) -> ModelDetail {
+        self.budget
+            .detail(self.ranks.get(callsign).copied().unwrap_or(self.ranks.len()))
+    }
+}
+
+/// The decider for a client (`None` when level of detail is off)
+pub fn decider(app: &AppHandle, config: Option<&ClientConfig>) -> Option<LodDecider> {
+    let state = app.try_state::<LodState>()?;
+    let settings = state.settings.read();
+    settings.enabled.then(|| LodDecider {
+        ranks: state.ranks.read().clone(),
+        budget: budget(&settings, config),
+    })
+}
+
+/// Set the detail of every aircraft in a traffic snapshot for a client
+pub fn apply(app: &AppHandle, config: Option<&ClientConfig>, snapshot: &mut TrafficSnapshot) {
+    let decider = decider(app, config);
+    for aircraft in &mut snapshot.aircraft {
+        aircraft.model_detail = decider.as_ref().map(|d| d.detail(&aircraft.callsign));
+    }
+}
+
+/// Set the detail of every aircraft in a vNAS batch for a client
+pub fn apply_vnas(app: &AppHandle, config: Option<&ClientConfig>, aircraft: &mut [VnasAircraftBroadcast]) {
+    let decider = decider(app, config);
+    for aircraft in aircraft {
+        aircraft.model_detail = decider.as_ref().map(|d| d.detail(&aircraft.callsign));
+    }
+}
+
+/// Level of detail decisions for a client (`GET /api/lod`)
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LodPolicy {
+    pub enabled: bool,
+    /// Aircraft seen in the last minute
+    pub aircraft_count: usize,
+    pub budget: Option<LodBudget>,
+    /// Detail by callsign
+    pub aircraft: BTreeMap<String, ModelDetail>,
+}
+
+/// Current decisions for a client
+pub fn policy(app: &AppHandle, config: Option<&ClientConfig>) -> LodPolicy {
+    let Some(decider) = decider(app, config) else {
+        return LodPolicy::default();
+    };
+    LodPolicy {
+        enabled: true,
+        aircraft_count: decider.ranks.len(),
+        budget: Some(decider.budget),
+        aircraft: decider
+            .ranks
+            .iter()
+            .map(|(callsign, rank)| (callsign.clone(), decider.budget.detail(*rank)))
+            .collect(),
+    }
+}
+
+/// Apply changed settings
+pub fn apply_settings(app: &AppHandle, settings: &LodSettings) {
+    if let Some(state) = app.try_state::<LodState>() {
+        *state.settings.write() = settings.clone();
+    }
+}
+
+/// Initialize level of detail state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_lod(app: &AppHandle) {
+    let settings = crate::read_global_settings(app.clone())
+        .map(|s| s.lod)
+        .unwrap_or_default();
+    app.manage(LodState {
+        settings: RwLock::new(settings),
+        seen: Mutex::new(HashMap::new()),
+        ranks: RwLock::new(Arc::new(HashMap::new())),
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the model detail decisions for this app
+#[tauri::command]
+pub fn get_lod_policy(app: AppHandle) -> LodPolicy {
+    policy(&app, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client_capabilities::{negotiate, ClientCapabilities};
+
+    #[test]
+    fn scales_budgets_and_ranks_by_distance() {
+        let settings = LodSettings::default();
+        let desktop = budget(&settings, None);
+        assert_eq!((desktop.full, desktop.models), (100, 250));
+
+        let tablet = ClientCapabilities {
+            device_class: DeviceClass::Tablet,
+            ..Default::default()
+        };
+        let tablet = budget(&settings, Some(&negotiate(&tablet, false)));
+        assert_eq!((tablet.full, tablet.models), (50, 125));
+
+        let phone = ClientCapabilities {
+            device_class: DeviceClass::Phone,
+            ..Default::default()
+        };
+        let phone = budget(&settings, Some(&negotiate(&phone, false)));
+        assert_eq!((phone.full, phone.models), (25, 63));
+
+        // Generic never below full
+        let odd = LodSettings {
+            full_models: 10,
+            generic_models: 4,
+            ..Default::default()
+        };
+        assert_eq!(budget(&odd, None).models, 10);
+
+        let callsigns: Vec<String> = ["FAR", "NEAR", "MID", "TIE"].iter().map(|c| c.to_string()).collect();
+        let ranks = rank(callsigns.iter().zip([30.0, 0.5, 12.0, 12.0]));
+        assert_eq!(ranks["NEAR"], 0);
+        assert_eq!(ranks["MID"], 1);
+        assert_eq!(ranks["TIE"], 2);
+        assert_eq!(ranks["FAR"], 3);
+
+        let decider = LodDecider {
+            ranks: Arc::new(ranks),
+            budget: LodBudget { full: 1, models: 3 },
+        };
+        assert_eq!(decider.detail("NEAR"), ModelDetail::Full);
+        assert_eq!(decider.detail("TIE"), ModelDetail::Generic);
+        assert_eq!(decider.detail("FAR"), ModelDetail::Billboard);
+        assert_eq!(decider.detail("UNKNOWN"), ModelDetail::Billboard);
+    }
+}
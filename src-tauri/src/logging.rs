@@ -0,0 +1,155 @@
+//! Structured logging
+//!
+//! Backend log output goes through `tracing`, to the console and to daily
+//! rotating files in `logs/` in the app data directory (the last week is
+//! kept). Each module logs under its own target (`app_lib::server`,
+//! `app_lib::vnas`, `app_lib::fsltl_scan`, ...), so the level filter in
+//! `diagnostics.logLevel` can be a single level (`info`) or per-module
+//! directives (`info,app_lib::vnas=debug`). The filter can be changed at
+//! runtime; `TOWERCAB_LOG` overrides it at startup. Both outputs pass through
+//! the secrets filter (see secrets.rs).
+
+use std::path::PathBuf;
+
+use tauri::{AppHandle, Manager};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+use crate::error::{Error, Result};
+use crate::secrets::{self, Redacting};
+use crate::GlobalDiagnosticsSettings;
+
+/// Rotated log files kept (one per day)
+const MAX_LOG_FILES: usize = 7;
+
+/// Logging state (managed by Tauri)
+pub struct LoggingState {
+    filter: reload::Handle<EnvFilter, Registry>,
+    dir: PathBuf,
+    /// Flushes buffered file output when dropped (None if files couldn't be opened)
+    _guard: Option<WorkerGuard>,
+}
+
+/// Parse a level filter (`info`, `warn,app_lib::server=debug`, ...)
+pub fn parse_filter(directives: &str) -> Result<EnvFilter> {
+    let directives = directives.trim();
+    EnvFilter::try_new(if directives.is_empty() { "info" } else { directives })
+        .map_err(|e| Error::InvalidInput(format!("Invalid log level '{}': {}", directives, e)))
+}
+
+fn log_dir(app: &AppHandle) -> Result<PathBuf> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))?;
+    Ok(app_data.join("logs"))
+}
+
+/// Apply a changed log level
+pub fn apply_settings(app: &AppHandle, settings: &GlobalDiagnosticsSettings) {
+    if let Err(e) = set_level(app, &settings.log_level) {
+        tracing::warn!("[Logging] {}", e);
+    }
+}
+
+fn set_level(app: &AppHandle, directives: &str) -> Result<()> {
+    let filter = parse_filter(directives)?;
+    let state = app
+        .try_state::<LoggingState>()
+        .ok_or_else(|| Error::Internal("Logging is not initialized".to_string()))?;
+    state
+        .filter
+        .reload(filter)
+        .map_err(|e| Error::Internal(format!("Failed to change log level: {}", e)))?;
+    tracing::info!("[Logging] Log level set to {}", directives);
+    Ok(())
+}
+
+/// Initialize logging to the console and rotating files.
+/// Call this first in the Tauri setup closure.
+pub fn init_logging(app: &AppHandle) {
+    let settings = crate::read_global_settings(app.clone()).unwrap_or_default();
+    secrets::register_settings(&settings);
+    let configured = settings.diagnostics.log_level;
+    let filter = std::env::var("TOWERCAB_LOG")
+        .ok()
+        .and_then(|env| parse_filter(&env).ok())
+        .or_else(|| parse_filter(&configured).ok())
+        .unwrap_or_else(|| EnvFilter::new("info"));
+    let (filter, filter_handle) = reload::Layer::new(filter);
+
+    let dir = log_dir(app).unwrap_or_else(|_| std::env::temp_dir().join("towercab-logs"));
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("towercab")
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(&dir);
+    let (file_layer, guard) = match appender {
+        Ok(appender) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            (Some(fmt::layer().with_writer(Redacting(writer)).with_ansi(false)), Some(guard))
+        }
+        Err(e) => {
+            eprintln!("[Logging] Failed to open log files in {:?}: {}", dir, e);
+            (None, None)
+        }
+    };
+
+    if let Err(e) = tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt::layer().with_writer(Redacting(std::io::stdout)))
+        .with(file_layer)
+        .try_init()
+    {
+        eprintln!("[Logging] Failed to initialize logging: {}", e);
+        return;
+    }
+
+    app.manage(LoggingState {
+        filter: filter_handle,
+        dir: dir.clone(),
+        _guard: guard,
+    });
+    tracing::info!("[Logging] Writing logs to {:?}", dir);
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Change the log level now and save it to global settings
+#[tauri::command]
+pub fn set_log_level(app: AppHandle, level: String) -> Result<()> {
+    parse_filter(&level)?;
+    let mut settings = crate::read_global_settings(app.clone())?;
+    settings.diagnostics.log_level = level;
+    crate::write_global_settings(app, settings)
+}
+
+/// Open the log folder in the system file manager
+#[tauri::command]
+pub fn open_log_folder(app: AppHandle) -> Result<()> {
+    let dir = match app.try_state::<LoggingState>() {
+        Some(state) => state.dir.clone(),
+        None => log_dir(&app)?,
+    };
+    std::fs::create_dir_all(&dir).map_err(|e| Error::Io(format!("Failed to create log folder: {}", e)))?;
+    crate::open_with_system(dir.as_os_str()).map_err(Error::Io)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_levels_and_module_directives() {
+        assert!(parse_filter("debug").is_ok());
+        assert!(parse_filter("warn,app_lib::vnas=trace").is_ok());
+        assert_eq!(parse_filter("  ").unwrap().to_string(), "info");
+        assert!(parse_filter("app_lib::server=loud").is_err());
+    }
+}
@@ -0,0 +1,377 @@
+//! Terrain and imagery providers
+//!
+//! Cesium Ion (World Terrain and Bing aerial imagery) is the default. For
+//! users without an Ion subscription, `mapProviders` in global settings
+//! selects alternatives per layer:
+//!
+//! - Terrain: MapTiler quantized-mesh terrain, a self-hosted Cesium terrain
+//!   server, an imported terrain package (terrain_packages.rs), or none
+//!   (smooth ellipsoid)
+//! - Imagery: Bing Maps with the user's own key, MapTiler satellite,
+//!   Sentinel-2 cloudless (EOX, no key needed), Esri World Imagery Wayback
+//!   (no key needed) or a custom XYZ URL template
+//!
+//! Sentinel-2 and Wayback imagery can be switched to an earlier vintage per
+//! airport (imagery_vintages.rs).
+//!
+//! Layers from providers other than Ion are always loaded through the host
+//! (`tiles://` in the app, `/api/tiles/*` for remote browsers, see
+//! tile_cache.rs), so API keys never leave the host. Their tiles are cached
+//! in their own folders when the tile cache is enabled. Which layers are
+//! proxied and the attribution to show are served at `/api/map-providers`.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::AppHandle;
+
+use crate::imagery_vintages;
+use crate::tile_cache::Layer;
+
+const MAPTILER_TERRAIN_URL: &str = "https://api.maptiler.com/tiles/terrain-quantized-mesh-v2/";
+const MAPTILER_SATELLITE_URL: &str = "https://api.maptiler.com/tiles/satellite-v2/{z}/{x}/{y}.jpg";
+const SENTINEL_URL: &str = "https://tiles.maps.eox.at/wmts/1.0.0/s2cloudless-2021_3857/default/g/{z}/{y}/{x}.jpg";
+const WAYBACK_URL: &str =
+    "https://server.arcgisonline.com/ArcGIS/rest/services/World_Imagery/MapServer/tile/{z}/{y}/{x}";
+
+const MAPTILER_CREDIT: &str = "<a href=\"https://www.maptiler.com/copyright/\">&copy; MapTiler</a> \
+     <a href=\"https://www.openstreetmap.org/copyright\">&copy; OpenStreetMap contributors</a>";
+const BING_CREDIT: &str = "Imagery &copy; Microsoft Bing Maps";
+const SENTINEL_CREDIT: &str = "<a href=\"https://s2maps.eu\">Sentinel-2 cloudless - https://s2maps.eu</a> \
+     by EOX IT Services GmbH (Contains modified Copernicus Sentinel data)";
+const WAYBACK_CREDIT: &str =
+    "Imagery &copy; Esri World Imagery: Esri, Maxar, Earthstar Geographics, and the GIS User Community";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TerrainProvider {
+    /// Cesium World Terrain
+    #[default]
+    Ion,
+    Maptiler,
+    /// Self-hosted quantized-mesh terrain server (e.g. Cesium Terrain Server)
+    Custom,
+    /// Imported offline terrain package
+    Package,
+    /// No terrain
+    Ellipsoid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageryProvider {
+    /// Bing Maps Aerial through Cesium Ion
+    #[default]
+    Ion,
+    /// Bing Maps Aerial with the user's own key
+    Bing,
+    Maptiler,
+    /// Sentinel-2 cloudless mosaic (EOX)
+    Sentinel,
+    /// Esri World Imagery, with its earlier releases (Wayback)
+    Wayback,
+    /// XYZ URL template
+    Custom,
+}
+
+/// Terrain and imagery provider settings within global settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MapProviderSettings {
+    pub terrain: TerrainProvider,
+    pub imagery: ImageryProvider,
+    pub maptiler_key: String,
+    pub bing_key: String,
+    /// Base URL of a quantized-mesh terrain server (the folder with layer.json)
+    pub custom_terrain_url: String,
+    /// ID of the imported terrain package to use
+    pub terrain_package: String,
+    /// Imagery URL template with {z}, {x} and {y}
+    pub custom_imagery_url: String,
+    /// Attribution shown for the custom terrain or imagery (HTML allowed)
+    pub custom_attribution: String,
+    /// Imagery vintage per airport (ICAO to vintage ID, see imagery_vintages.rs)
+    pub imagery_vintages: BTreeMap<String, String>,
+}
+
+/// Where a layer's tiles come from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// Resolved through Cesium Ion
+    Ion,
+    /// Quantized-mesh terrain server (ends with '/'), with an optional `key` query parameter
+    QuantizedMesh { url: String, key: Option<String> },
+    /// Imported terrain package, read from disk
+    Package { id: String },
+    /// Bing Maps with the user's key
+    Bing { key: String },
+    /// XYZ template with {z}, {x} and {y}
+    Xyz { template: String },
+    /// No tiles (ellipsoid terrain)
+    Disabled,
+}
+
+fn require<'a>(value: &'a str, what: &str) -> Result<&'a str, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        Err(format!("No {} configured in map provider settings", what))
+    } else {
+        Ok(value)
+    }
+}
+
+/// Tile source of a layer, with imagery from an earlier vintage if one of the
+/// selected provider is given
+pub fn source(settings: &MapProviderSettings, layer: Layer, vintage: Option<&str>) -> Result<Source, String> {
+    if layer == Layer::Imagery {
+        if let Some(template) = vintage.and_then(|id| imagery_vintages::template(settings.imagery, id)) {
+            return Ok(Source::Xyz { template });
+        }
+    }
+    match layer {
+        Layer::Terrain => match settings.terrain {
+            TerrainProvider::Ion => Ok(Source::Ion),
+            TerrainProvider::Maptiler => Ok(Source::QuantizedMesh {
+                url: MAPTILER_TERRAIN_URL.to_string(),
+                key: Some(require(&settings.maptiler_key, "MapTiler key")?.to_string()),
+            }),
+            TerrainProvider::Custom => {
+                let url = require(&settings.custom_terrain_url, "custom terrain URL")?;
+                Ok(Source::QuantizedMesh {
+                    url: format!("{}/", url.trim_end_matches('/')),
+                    key: None,
+                })
+            }
+            TerrainProvider::Package => Ok(Source::Package {
+                id: require(&settings.terrain_package, "terrain package")?.to_string(),
+            }),
+            TerrainProvider::Ellipsoid => Ok(Source::Disabled),
+        },
+        Layer::Imagery => match settings.imagery {
+            ImageryProvider::Ion => Ok(Source::Ion),
+            ImageryProvider::Bing => Ok(Source::Bing {
+                key: require(&settings.bing_key, "Bing Maps key")?.to_string(),
+            }),
+            ImageryProvider::Maptiler => Ok(Source::Xyz {
+                template: format!(
+                    "{}?key={}",
+                    MAPTILER_SATELLITE_URL,
+                    require(&settings.maptiler_key, "MapTiler key")?
+                ),
+            }),
+            ImageryProvider::Sentinel => Ok(Source::Xyz {
+                template: SENTINEL_URL.to_string(),
+            }),
+            ImageryProvider::Wayback => Ok(Source::Xyz {
+                template: WAYBACK_URL.to_string(),
+            }),
+            ImageryProvider::Custom => {
+                let template = require(&settings.custom_imagery_url, "custom imagery URL")?;
+                if !["{z}", "{x}", "{y}"].iter().all(|p| template.contains(p)) {
+                    return Err("Custom imagery URL must contain {z}, {x} and {y}".to_string());
+                }
+                Ok(Source::Xyz {
+                    template: template.to_string(),
+                })
+            }
+        },
+    }
+}
+
+/// Cache folder of a layer's provider (and imagery vintage), relative to the
+/// tile cache root (empty for Ion, whose tiles are at the root)
+pub fn cache_folder(settings: &MapProviderSettings, layer: Layer, vintage: Option<&str>) -> String {
+    if let Some(id) =
+        vintage.filter(|id| layer == Layer::Imagery && imagery_vintages::template(settings.imagery, id).is_some())
+    {
+        return format!("providers/{}", id);
+    }
+    let custom = |url: &str| {
+        let digest = Sha256::digest(url.trim().as_bytes());
+        format!("providers/custom-{}", &hex::encode(digest)[..12])
+    };
+    match layer {
+        Layer::Terrain => match settings.terrain {
+            TerrainProvider::Ion => String::new(),
+            TerrainProvider::Maptiler => "providers/maptiler".to_string(),
+            TerrainProvider::Custom => custom(&settings.custom_terrain_url),
+            // Not cached (read from the package)
+            TerrainProvider::Package => format!("providers/package-{}", settings.terrain_package.trim()),
+            TerrainProvider::Ellipsoid => "providers/ellipsoid".to_string(),
+        },
+        Layer::Imagery => match settings.imagery {
+            ImageryProvider::Ion => String::new(),
+            ImageryProvider::Bing => "providers/bing".to_string(),
+            ImageryProvider::Maptiler => "providers/maptiler".to_string(),
+            ImageryProvider::Sentinel => "providers/sentinel".to_string(),
+            ImageryProvider::Wayback => "providers/wayback".to_string(),
+            ImageryProvider::Custom => custom(&settings.custom_imagery_url),
+        },
+    }
+}
+
+/// What clients need to set up their terrain and imagery (no keys)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MapProviderInfo {
+    pub terrain: TerrainProvider,
+    pub imagery: ImageryProvider,
+    /// Terrain is loaded through the host
+    pub terrain_proxied: bool,
+    /// Imagery is loaded through the host
+    pub imagery_proxied: bool,
+    pub imagery_max_zoom: u32,
+    /// The imagery provider has earlier vintages to choose from
+    pub has_imagery_vintages: bool,
+    /// Attribution HTML to show in the viewer (Ion layers credit themselves)
+    pub credits: Vec<String>,
+}
+
+pub fn info(settings: &MapProviderSettings) -> MapProviderInfo {
+    let mut credits = Vec::new();
+    let mut credit = |text: &str| {
+        if !text.trim().is_empty() && !credits.iter().any(|c| c == text) {
+            credits.push(text.to_string());
+        }
+    };
+    match settings.terrain {
+        TerrainProvider::Maptiler => credit(MAPTILER_CREDIT),
+        TerrainProvider::Custom | TerrainProvider::Package => credit(&settings.custom_attribution),
+        TerrainProvider::Ion | TerrainProvider::Ellipsoid => {}
+    }
+    match settings.imagery {
+        ImageryProvider::Bing => credit(BING_CREDIT),
+        ImageryProvider::Maptiler => credit(MAPTILER_CREDIT),
+        ImageryProvider::Sentinel => credit(SENTINEL_CREDIT),
+        ImageryProvider::Wayback => credit(WAYBACK_CREDIT),
+        ImageryProvider::Custom => credit(&settings.custom_attribution),
+        ImageryProvider::Ion => {}
+    }
+    MapProviderInfo {
+        terrain: settings.terrain,
+        imagery: settings.imagery,
+        terrain_proxied: matches!(
+            settings.terrain,
+            TerrainProvider::Maptiler | TerrainProvider::Custom | TerrainProvider::Package
+        ),
+        imagery_proxied: settings.imagery != ImageryProvider::Ion,
+        imagery_max_zoom: match settings.imagery {
+            ImageryProvider::Sentinel => 15,
+            ImageryProvider::Maptiler => 20,
+            _ => 19,
+        },
+        has_imagery_vintages: imagery_vintages::has_vintages(settings.imagery),
+        credits,
+    }
+}
+
+/// Map provider settings from global settings
+pub fn settings(app: &AppHandle) -> MapProviderSettings {
+    crate::read_global_settings(app.clone())
+        .map(|s| s.map_providers)
+        .unwrap_or_default()
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Selected terrain and imagery providers and their attribution
+#[tauri::command]
+pub fn get_map_providers(app: AppHandle) -> MapProviderInfo {
+    info(&settings(&app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_sources_and_requires_keys() {
+        let mut settings = MapProviderSettings::default();
+        assert_eq!(source(&settings, Layer::Terrain, None), Ok(Source::Ion));
+        assert_eq!(cache_folder(&settings, Layer::Imagery, None), "");
+
+        settings.terrain = TerrainProvider::Maptiler;
+        settings.imagery = ImageryProvider::Maptiler;
+        assert!(source(&settings, Layer::Terrain, None).is_err());
+        settings.maptiler_key = "abc123".to_string();
+        assert_eq!(
+            source(&settings, Layer::Imagery, None),
+            Ok(Source::Xyz {
+                template: "https://api.maptiler.com/tiles/satellite-v2/{z}/{x}/{y}.jpg?key=abc123".to_string()
+            })
+        );
+
+        settings.terrain = TerrainProvider::Custom;
+        settings.custom_terrain_url = "http://nas:8080/tilesets/terrain".to_string();
+        assert_eq!(
+            source(&settings, Layer::Terrain, None),
+            Ok(Source::QuantizedMesh {
+                url: "http://nas:8080/tilesets/terrain/".to_string(),
+                key: None
+            })
+        );
+        assert!(cache_folder(&settings, Layer::Terrain, None).starts_with("providers/custom-"));
+
+        settings.terrain = TerrainProvider::Package;
+        assert!(source(&settings, Layer::Terrain, None).is_err());
+        settings.terrain_package = "ksfo-terrain".to_string();
+        assert_eq!(
+            source(&settings, Layer::Terrain, None),
+            Ok(Source::Package {
+                id: "ksfo-terrain".to_string()
+            })
+        );
+        assert!(info(&settings).terrain_proxied);
+
+        settings.imagery = ImageryProvider::Wayback;
+        assert_eq!(
+            source(&settings, Layer::Imagery, Some("wayback-10")),
+            Ok(Source::Xyz {
+                template: imagery_vintages::template(ImageryProvider::Wayback, "wayback-10").unwrap()
+            })
+        );
+        assert_eq!(
+            cache_folder(&settings, Layer::Imagery, Some("wayback-10")),
+            "providers/wayback-10"
+        );
+        // Vintages of other providers are ignored
+        assert_eq!(
+            cache_folder(&settings, Layer::Imagery, Some("sentinel-2020")),
+            "providers/wayback"
+        );
+
+        settings.imagery = ImageryProvider::Custom;
+        settings.custom_imagery_url = "https://tiles.example.com/{z}/{x}.png".to_string();
+        assert!(source(&settings, Layer::Imagery, None).is_err());
+    }
+
+    #[test]
+    fn reports_proxied_layers_and_credits() {
+        let ion = info(&MapProviderSettings::default());
+        assert!(!ion.terrain_proxied && !ion.imagery_proxied);
+        assert!(ion.credits.is_empty());
+
+        let settings = MapProviderSettings {
+            terrain: TerrainProvider::Maptiler,
+            imagery: ImageryProvider::Maptiler,
+            ..Default::default()
+        };
+        let maptiler = info(&settings);
+        assert!(maptiler.terrain_proxied && maptiler.imagery_proxied);
+        assert_eq!(maptiler.credits, vec![MAPTILER_CREDIT.to_string()]);
+
+        let settings = MapProviderSettings {
+            terrain: TerrainProvider::Ellipsoid,
+            imagery: ImageryProvider::Sentinel,
+            ..Default::default()
+        };
+        let sentinel = info(&settings);
+        assert!(!sentinel.terrain_proxied);
+        assert_eq!(sentinel.imagery_max_zoom, 15);
+        assert!(sentinel.has_imagery_vintages);
+        assert_eq!(sentinel.credits.len(), 1);
+    }
+}
@@ -0,0 +1,1000 @@
+//! Model matching engine
+//!
+//! Resolves an aircraft type + airline to a 3D model on the host, so every
+//! client (desktop webview and remote browsers) gets the same answer without
+//! re-implementing the matching rules. Sources, in priority order:
+//!
+//! 1. Custom VMR rules from `mods/` pointing at enabled aircraft mods
+//! 2. FSLTL_Rules.vmr + converted FSLTL models (airline livery, then base livery),
+//!    trying similar types (see `similar_types`) when the exact type has no model
+//! 3. Airline liveries on common narrowbody types (unknown type), then GA/B738 base models
+//!
+//! The first VMR rule in load order that applies wins. Rules with a
+//! `FlightNumberRange` only apply to callsigns whose flight number is in it,
+//! and airline rules without a type apply to any type the airline has no
+//! airline+type rule for.
+//!
+//! Every lookup returns a trace of the steps that were tried, which makes
+//! mismatches easy to debug from the browser (`/api/match?typeCode=B38M&callsign=AAL123`).
+//! If nothing matches, the client falls back to its built-in models.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Instant, SystemTime};
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager, State};
+use tracing::{info, warn};
+
+use crate::coverage::RecentTrafficState;
+use crate::model_usage::ModelUsageState;
+use crate::fsltl_scan;
+use crate::mods::{self, ModRegistryEntry};
+use crate::similar_types::{self, SimilarTypes};
+use crate::startup;
+use crate::vmr::{self, VmrRule};
+use crate::{normalize_path_string, ScannedFSLTLModel};
+
+/// Common narrowbody types searched for airline liveries (in preference order)
+const AIRLINE_FALLBACK_TYPES: [&str; 12] = [
+    "B738", "A320", "B739", "A321", "A319", "B737", "A20N", "A21N", "A19N", "B38M", "B39M", "B73X",
+];
+
+/// GA fallback types in preference order (C172 is the best generic GA representation)
+const GA_FALLBACK_TYPES: [&str; 4] = ["C172", "C152", "DA40", "TBM9"];
+
+/// Model selected by the matcher
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchedModel {
+    /// "custom" (mods/aircraft) or "fsltl" (converted FSLTL models)
+    pub source: String,
+    /// How the model was found: "vmr", "registry", "airlineFallback", "baseLivery", "gaFallback" or "fallback"
+    pub match_type: String,
+    /// VMR model name or FSLTL model name (e.g., "FSLTL_B738_AAL")
+    pub model_name: String,
+    /// Aircraft type the model represents (may differ from the requested type)
+    pub aircraft_type: String,
+    pub airline_code: Option<String>,
+    /// Absolute path to model.glb on the host (for Tauri mode)
+    pub model_path: String,
+    /// URL usable by remote clients (e.g., "/api/fsltl/B738/AAL/model.glb")
+    pub url: String,
+    pub has_animations: bool,
+    /// Aircraft mod manifest (scale, rotationOffset, ...) for custom models
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<serde_json::Value>,
+    /// VMR rule that selected the model, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule: Option<VmrRule>,
+}
+
+/// One step of the matching process
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchTraceStep {
+    pub step: String,
+    /// "matched", "noMatch" or "skipped"
+    pub result: String,
+    pub detail: String,
+}
+
+/// Result of a model match, including the trace of steps tried
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchResult {
+    /// Normalized aircraft type (equipment suffixes stripped)
+    pub type_code: Option<String>,
+    /// Airline code used for matching (explicit or derived from the callsign)
+    pub airline_code: Option<String>,
+    pub is_ga: bool,
+    /// Matched model, or None if the client should use its built-in models
+    pub model: Option<MatchedModel>,
+    /// Similar type used because no model exists for the requested type (e.g., "B738" for a B38M)
+    pub similar_type: Option<String>,
+    pub trace: Vec<MatchTraceStep>,
+}
+
+/// Match request parameters (Tauri command and /api/match query)
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchQuery {
+    pub type_code: Option<String>,
+    pub callsign: Option<String>,
+    pub airline: Option<String>,
+}
+
+/// Rules indexed by airline+type, by type and by airline (rules without a type)
+#[derive(Default)]
+struct RuleIndex {
+    airline: HashMap<(String, String), Vec<VmrRule>>,
+    default: HashMap<String, Vec<VmrRule>>,
+    /// Airline rules without a type, used for any type without an airline+type rule
+    airline_any: HashMap<String, Vec<VmrRule>>,
+}
+
+impl RuleIndex {
+    /// Index rules in load order; the first rule that applies to a flight wins
+    fn build(rules: Vec<VmrRule>) -> Self {
+        let mut index = Self::default();
+        for rule in rules {
+            match (rule.callsign_prefix.clone(), rule.type_code.clone()) {
+                (Some(airline), Some(type_code)) => index.airline.entry((airline, type_code)).or_default().push(rule),
+                (None, Some(type_code)) => index.default.entry(type_code).or_default().push(rule),
+                (Some(airline), None) => index.airline_any.entry(airline).or_default().push(rule),
+                (None, None) => {}
+            }
+        }
+        index
+    }
+
+    /// Airline+type rule for a flight, falling back to the airline's rules for any type
+    fn airline_rule(&self, airline: &str, type_code: &str, flight: Option<u32>) -> Option<&VmrRule> {
+        first_applying(self.airline.get(&(airline.to_string(), type_code.to_string())), flight)
+            .or_else(|| first_applying(self.airline_any.get(airline), flight))
+    }
+
+    /// Type rule for a flight
+    fn default_rule(&self, type_code: &str, flight: Option<u32>) -> Option<&VmrRule> {
+        first_applying(self.default.get(type_code), flight)
+    }
+
+    fn rules(&self) -> impl Iterator<Item = &VmrRule> {
+        self.airline
+            .values()
+            .chain(self.default.values())
+            .chain(self.airline_any.values())
+            .flatten()
+    }
+
+    fn len(&self) -> usize {
+        self.rules().count()
+    }
+}
+
+/// First rule that applies to a flight
+fn first_applying(rules: Option<&Vec<VmrRule>>, flight: Option<u32>) -> Option<&VmrRule> {
+    rules?.iter().find(|rule| in_flight_number_range(rule, flight))
+}
+
+/// Whether a flight number is in a rule's FlightNumberRange ("1-999" or a single
+/// number). Rules without a range apply to every flight; rules with one don't
+/// apply without a flight number or when the range can't be parsed.
+fn in_flight_number_range(rule: &VmrRule, flight: Option<u32>) -> bool {
+    let Some(range) = rule.flight_number_range.as_deref() else {
+        return true;
+    };
+    let Some(flight) = flight else {
+        return false;
+    };
+    let (low, high) = range.split_once('-').unwrap_or((range, range));
+    match (low.trim().parse::<u32>(), high.trim().parse::<u32>()) {
+        (Ok(low), Ok(high)) => (low..=high).contains(&flight),
+        _ => false,
+    }
+}
+
+/// Indexed matcher built from VMR rules and available models
+pub struct ModelMatcher {
+    custom_rules: RuleIndex,
+    /// Enabled aircraft mods with a model.glb, keyed by uppercase folder name
+    custom_models: HashMap<String, ModRegistryEntry>,
+    fsltl_rules: RuleIndex,
+    /// Type aliases from FSLTL default rules (e.g., B38M → B738)
+    type_aliases: HashMap<String, String>,
+    /// Converted FSLTL models keyed by aircraft type
+    fsltl_models: HashMap<String, Vec<ScannedFSLTLModel>>,
+    /// Similar types tried when no model exists for a type (B38M → B738, ...)
+    similar_types: SimilarTypes,
+}
+
+/// Extract the base type from FAA format strings ("B738/L", "H/B738/L" → "B738")
+pub fn extract_base_aircraft_type(aircraft_type: &str) -> String {
+    let trimmed = aircraft_type.trim().to_uppercase();
+    if !trimmed.contains('/') {
+        return trimmed;
+    }
+
+    let parts: Vec<&str> = trimmed.split('/').collect();
+    parts
+        .iter()
+        .find(|p| (2..=4).contains(&p.len()) && p.starts_with(|c: char| c.is_ascii_alphabetic()))
+        .unwrap_or(&parts[0])
+        .to_string()
+}
+
+/// Extract the airline ICAO code from a callsign (3 letters followed by a digit)
+pub fn extract_airline_code(callsign: &str) -> Option<String> {
+    let upper = callsign.trim().to_uppercase();
+    let bytes = upper.as_bytes();
+    if bytes.len() >= 4 && bytes[..3].iter().all(u8::is_ascii_uppercase) && bytes[3].is_ascii_digit() {
+        Some(upper[..3].to_string())
+    } else {
+        None
+    }
+}
+
+/// Extract the flight number from an airline callsign ("AAL123" → 123, "BAW12A" → 12)
+pub fn extract_flight_number(callsign: &str) -> Option<u32> {
+    extract_airline_code(callsign)?;
+    let upper = callsign.trim().to_uppercase();
+    let digits: String = upper[3..].chars().take_while(char::is_ascii_digit).collect();
+    digits.parse().ok()
+}
+
+/// Whether a callsign looks like a GA registration rather than an airline callsign
+pub fn is_ga_callsign(callsign: &str) -> bool {
+    let upper = callsign.trim().to_uppercase();
+    if upper.is_empty() {
+        return false;
+    }
+
+    // N-numbers (US): N followed by a digit and up to 4 alphanumerics
+    let is_n_number = upper.len() <= 6
+        && upper.starts_with('N')
+        && upper[1..].starts_with(|c: char| c.is_ascii_digit())
+        && upper[1..].chars().all(|c| c.is_ascii_alphanumeric());
+
+    // Country prefix with hyphen: C-FABC, G-ABCD, VH-ABC, ...
+    let is_hyphenated = upper.split_once('-').is_some_and(|(prefix, rest)| {
+        (1..=2).contains(&prefix.len())
+            && prefix.chars().all(|c| c.is_ascii_uppercase())
+            && (2..=5).contains(&rest.len())
+            && rest.chars().all(|c| c.is_ascii_alphanumeric())
+    });
+
+    // Anything that isn't an airline callsign is treated as GA
+    is_n_number || is_hyphenated || extract_airline_code(&upper).is_none()
+}
+
+/// Parse the aircraft type from an FSLTL model name ("FSLTL_FAIB_B738_AAL" → "B738")
+fn fsltl_model_type(model_name: &str) -> Option<String> {
+    let name = model_name.strip_prefix("FSLTL_").unwrap_or(model_name);
+    let name = name.strip_prefix("FAIB_").unwrap_or(name);
+    name.split('_')
+        .next()
+        .filter(|t| !t.is_empty())
+        .map(|t| t.to_uppercase())
+}
+
+/// Tracks the steps tried while matching
+struct Trace(Vec<MatchTraceStep>);
+
+impl Trace {
+    fn push(&mut self, step: &str, result: &str, detail: String) {
+        self.0.push(MatchTraceStep {
+            step: step.to_string(),
+            result: result.to_string(),
+            detail,
+        });
+    }
+}
+
+impl ModelMatcher {
+    /// Build the matcher from custom VMR rules, FSLTL rules and available models
+    pub fn build(
+        custom_rules: Vec<VmrRule>,
+        aircraft_mods: Vec<ModRegistryEntry>,
+        fsltl_rules: Vec<VmrRule>,
+        fsltl_models: Vec<ScannedFSLTLModel>,
+        similar_types: SimilarTypes,
+    ) -> Self {
+        let custom_models = aircraft_mods
+            .into_iter()
+            .filter(|m| m.files.iter().any(|f| f.name.eq_ignore_ascii_case("model.glb")))
+            .map(|m| (m.name.to_uppercase(), m))
+            .collect();
+
+        let fsltl_rules = RuleIndex::build(fsltl_rules);
+        let type_aliases = fsltl_rules
+            .default
+            .iter()
+            .filter_map(|(type_code, rules)| {
+                let model_type = fsltl_model_type(rules.first()?.model_names.first()?)?;
+                (&model_type != type_code).then(|| (type_code.clone(), model_type))
+            })
+            .collect();
+
+        let mut by_type: HashMap<String, Vec<ScannedFSLTLModel>> = HashMap::new();
+        for model in fsltl_models {
+            by_type
+                .entry(model.aircraft_type.to_uppercase())
+                .or_default()
+                .push(model);
+        }
+        // Base liveries first, then airlines alphabetically, for deterministic results
+        for models in by_type.values_mut() {
+            models.sort_by(|a, b| a.airline_code.cmp(&b.airline_code));
+        }
+
+        Self {
+            custom_rules: RuleIndex::build(custom_rules),
+            custom_models,
+            fsltl_rules,
+            type_aliases,
+            fsltl_models: by_type,
+            similar_types,
+        }
+    }
+
+    /// Resolve a custom rule to the first model alternative that is installed
+    fn resolve_custom(&self, rule: &VmrRule, type_code: &str, airline: Option<&str>) -> Option<MatchedModel> {
+        let entry = rule
+            .model_names
+            .iter()
+            .find_map(|name| self.custom_models.get(&name.to_uppercase()))?;
+
+        let model_path = normalize_path_string(&PathBuf::from(&entry.path).join("model.glb"));
+        Some(MatchedModel {
+            source: "custom".to_string(),
+            match_type: "vmr".to_string(),
+            model_name: entry.name.clone(),
+            aircraft_type: rule.type_code.clone().unwrap_or_else(|| type_code.to_string()),
+            airline_code: airline.map(str::to_string),
+            model_path,
+            url: format!("/api/mods/aircraft/{}/model.glb", entry.name),
+            has_animations: entry
+                .manifest
+                .as_ref()
+                .and_then(|m| m.get("hasAnimations"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false),
+            manifest: entry.manifest.clone(),
+            rule: Some(rule.clone()),
+        })
+    }
+
+    /// Custom rules none of whose model alternatives are installed (or enabled)
+    pub fn unresolved_custom_rules(&self) -> Vec<VmrRule> {
+        let mut rules: Vec<VmrRule> = self
+            .custom_rules
+            .rules()
+            .filter(|rule| {
+                !rule
+                    .model_names
+                    .iter()
+                    .any(|name| self.custom_models.contains_key(&name.to_uppercase()))
+            })
+            .cloned()
+            .collect();
+        rules.sort_by(|a, b| a.source_file.cmp(&b.source_file).then(a.index.cmp(&b.index)));
+        rules
+    }
+
+    /// Converted FSLTL models for a type, trying the alias first
+    fn fsltl_models_for(&self, type_code: &str) -> Option<&Vec<ScannedFSLTLModel>> {
+        let base_type = self
+            .type_aliases
+            .get(type_code)
+            .map(String::as_str)
+            .unwrap_or(type_code);
+        self.fsltl_models
+            .get(base_type)
+            .or_else(|| self.fsltl_models.get(type_code))
+    }
+
+    fn fsltl_match(model: &ScannedFSLTLModel, match_type: &str, rule: Option<&VmrRule>) -> MatchedModel {
+        MatchedModel {
+            source: "fsltl".to_string(),
+            match_type: match_type.to_string(),
+            model_name: model.model_name.clone(),
+            aircraft_type: model.aircraft_type.clone(),
+            airline_code: model.airline_code.clone(),
+            model_path: model.model_path.clone(),
+            url: format!("/api/fsltl/{}", model.relative_path),
+            has_animations: model.has_animations,
+            manifest: None,
+            rule: rule.cloned(),
+        }
+    }
+
+    /// Find a converted FSLTL model for a type, with an airline livery or the base livery
+    fn find_fsltl(&self, type_code: &str, airline: Option<&str>) -> Option<&ScannedFSLTLModel> {
+        let models = self.fsltl_models_for(type_code)?;
+        models.iter().find(|m| m.airline_code.as_deref() == airline)
+    }
+
+    /// Resolve an FSLTL rule to the first model alternative that has been converted.
+    /// Models are converted per type and airline, so an alternative
+    /// ("FSLTL_FAIB_B738_American") is found by the type it names.
+    fn resolve_fsltl(&self, rule: &VmrRule, airline: &str) -> Option<&ScannedFSLTLModel> {
+        rule.model_names.iter().find_map(|name| {
+            let models = self.fsltl_models.get(&fsltl_model_type(name)?)?;
+            models
+                .iter()
+                .find(|m| m.model_name.eq_ignore_ascii_case(name))
+                .or_else(|| models.iter().find(|m| m.airline_code.as_deref() == Some(airline)))
+        })
+    }
+
+    /// Match a type/callsign/airline to a model, recording every step tried
+    pub fn match_model(&self, query: &MatchQuery) -> MatchResult {
+        let mut trace = Trace(Vec::new());
+
+        let type_code = query
+            .type_code
+            .as_deref()
+            .map(extract_base_aircraft_type)
+            .filter(|t| !t.is_empty());
+        let airline = query
+            .airline
+            .as_deref()
+            .map(|a| a.trim().to_uppercase())
+            .filter(|a| !a.is_empty())
+            .or_else(|| query.callsign.as_deref().and_then(extract_airline_code));
+        let is_ga = query.callsign.as_deref().is_some_and(is_ga_callsign);
+        let flight = query.callsign.as_deref().and_then(extract_flight_number);
+
+        let (model, similar_type) =
+            self.find_match(type_code.as_deref(), airline.as_deref(), flight, is_ga, &mut trace);
+        if model.is_none() {
+            trace.push(
+                "builtin",
+                "skipped",
+                "No host model matched; client uses built-in models".to_string(),
+            );
+        }
+
+        MatchResult {
+            type_code,
+            airline_code: airline,
+            is_ga,
+            model,
+            similar_type,
+            trace: trace.0,
+        }
+    }
+
+    /// Custom VMR rule for a type (airline rule when an airline is given, else default rule)
+    fn try_custom(
+        &self,
+        type_code: &str,
+        airline: Option<&str>,
+        flight: Option<u32>,
+        trace: &mut Trace,
+    ) -> Option<MatchedModel> {
+        let (step, rule) = match airline {
+            Some(airline) => (
+                "customAirlineRule",
+                self.custom_rules.airline_rule(airline, type_code, flight),
+            ),
+            None => ("customDefaultRule", self.custom_rules.default_rule(type_code, flight)),
+        };
+        let label = match airline {
+            Some(airline) => format!("{} {}", airline, type_code),
+            None => type_code.to_string(),
+        };
+
+        let Some(rule) = rule else {
+            trace.push(step, "noMatch", format!("No custom rule for {}", label));
+            return None;
+        };
+        match self.resolve_custom(rule, type_code, airline) {
+            Some(model) => {
+                trace.push(step, "matched", format!("{} → {}", label, model.model_name));
+                Some(model)
+            }
+            None => {
+                trace.push(
+                    step,
+                    "noMatch",
+                    format!("Rule found but none of {:?} are installed", rule.model_names),
+                );
+                None
+            }
+        }
+    }
+
+    /// Converted FSLTL airline livery for a type (VMR rule alternatives, then direct registry lookup)
+    fn try_fsltl_airline(
+        &self,
+        type_code: &str,
+        airline: &str,
+        flight: Option<u32>,
+        trace: &mut Trace,
+    ) -> Option<MatchedModel> {
+        let rule = self.fsltl_rules.airline_rule(airline, type_code, flight);
+        let found = match rule.and_then(|r| self.resolve_fsltl(r, airline)) {
+            Some(model) => Some((model, "vmr", rule)),
+            None => self.find_fsltl(type_code, Some(airline)).map(|m| (m, "registry", None)),
+        };
+        match found {
+            Some((model, match_type, rule)) => {
+                trace.push(
+                    "fsltlAirline",
+                    "matched",
+                    format!("{} ({})", model.model_name, match_type),
+                );
+                Some(Self::fsltl_match(model, match_type, rule))
+            }
+            None => {
+                trace.push(
+                    "fsltlAirline",
+                    "noMatch",
+                    format!(
+                        "No converted {} livery for {}{}",
+                        airline,
+                        type_code,
+                        if rule.is_some() {
+                            " (VMR rule exists, model not converted)"
+                        } else {
+                            ""
+                        }
+                    ),
+                );
+                None
+            }
+        }
+    }
+
+    /// Converted FSLTL base livery for a type, then any livery for the type
+    fn try_fsltl_type(&self, type_code: &str, airline: Option<&str>, trace: &mut Trace) -> Option<MatchedModel> {
+        let rule = self.fsltl_rules.default.get(type_code).and_then(|rules| rules.first());
+        let base = self
+            .find_fsltl(type_code, None)
+            .or_else(|| self.fsltl_models_for(type_code).and_then(|m| m.first()));
+        match base {
+            Some(model) => {
+                let match_type = if airline.is_some() {
+                    "baseLivery"
+                } else if rule.is_some() {
+                    "vmr"
+                } else {
+                    "registry"
+                };
+                trace.push("fsltlType", "matched", format!("{} ({})", model.model_name, match_type));
+                Some(Self::fsltl_match(model, match_type, rule))
+            }
+            None => {
+                trace.push("fsltlType", "noMatch", format!("No converted models for {}", type_code));
+                None
+            }
+        }
+    }
+
+    /// Try similar types in preference order with the given lookup
+    fn try_similar(
+        &self,
+        type_code: &str,
+        trace: &mut Trace,
+        mut lookup: impl FnMut(&Self, &str, &mut Trace) -> Option<MatchedModel>,
+    ) -> Option<(String, MatchedModel)> {
+        for similar in self.similar_types.get(type_code).into_iter().flatten() {
+            match lookup(self, similar, trace) {
+                Some(model) => {
+                    trace.push(
+                        "similarType",
+                        "matched",
+                        format!("No {} model; using similar type {}", type_code, similar),
+                    );
+                    return Some((similar.clone(), model));
+                }
+                None => trace.push(
+                    "similarType",
+                    "noMatch",
+                    format!("No model for similar type {}", similar),
+                ),
+            }
+        }
+        None
+    }
+
+    fn find_match(
+        &self,
+        type_code: Option<&str>,
+        airline: Option<&str>,
+        flight: Option<u32>,
+        is_ga: bool,
+        trace: &mut Trace,
+    ) -> (Option<MatchedModel>, Option<String>) {
+        if let Some(type_code) = type_code {
+            // 1. Custom VMR rules (airline rule, then default rule)
+            match airline {
+                Some(airline) => {
+                    if let Some(model) = self.try_custom(type_code, Some(airline), flight, trace) {
+                        return (Some(model), None);
+                    }
+                }
+                None => trace.push("customAirlineRule", "skipped", "No airline code".to_string()),
+            }
+            if let Some(model) = self.try_custom(type_code, None, flight, trace) {
+                return (Some(model), None);
+            }
+
+            if let Some(alias) = self.type_aliases.get(type_code) {
+                trace.push("typeAlias", "matched", format!("{} uses {} models", type_code, alias));
+            }
+
+            if let Some(airline) = airline {
+                // 2. FSLTL airline livery for the type
+                if let Some(model) = self.try_fsltl_airline(type_code, airline, flight, trace) {
+                    return (Some(model), None);
+                }
+
+                // 3. Airline livery on a similar type (e.g., AAL B738 for AAL B38M)
+                if let Some((similar, model)) = self.try_similar(type_code, trace, |m, t, trace| {
+                    m.try_custom(t, Some(airline), flight, trace)
+                        .or_else(|| m.try_fsltl_airline(t, airline, flight, trace))
+                }) {
+                    return (Some(model), Some(similar));
+                }
+            }
+
+            // 4. FSLTL base livery for the type
+            if let Some(model) = self.try_fsltl_type(type_code, airline, trace) {
+                return (Some(model), None);
+            }
+
+            // 5. Base livery on a similar type
+            if let Some((similar, model)) = self.try_similar(type_code, trace, |m, t, trace| {
+                m.try_custom(t, None, flight, trace)
+                    .or_else(|| m.try_fsltl_type(t, airline, trace))
+            }) {
+                return (Some(model), Some(similar));
+            }
+        } else {
+            trace.push("type", "skipped", "No aircraft type".to_string());
+
+            // Airline livery on a common narrowbody type
+            if let Some(airline) = airline {
+                if let Some(model) = AIRLINE_FALLBACK_TYPES
+                    .iter()
+                    .find_map(|t| self.find_fsltl(t, Some(airline)))
+                {
+                    trace.push(
+                        "airlineFallback",
+                        "matched",
+                        format!("{} livery on {}", airline, model.aircraft_type),
+                    );
+                    return (Some(Self::fsltl_match(model, "airlineFallback", None)), None);
+                }
+                trace.push(
+                    "airlineFallback",
+                    "noMatch",
+                    format!("No converted {} liveries on common types", airline),
+                );
+            }
+        }
+
+        // 6. Generic fallback: GA base model or B738 base livery
+        if is_ga {
+            let ga = GA_FALLBACK_TYPES.iter().find_map(|t| {
+                let models = self.fsltl_models.get(*t)?;
+                models
+                    .iter()
+                    .find(|m| m.airline_code.is_none())
+                    .or_else(|| models.first())
+            });
+            match ga {
+                Some(model) => {
+                    trace.push("gaFallback", "matched", model.model_name.clone());
+                    return (Some(Self::fsltl_match(model, "gaFallback", None)), None);
+                }
+                None => trace.push("gaFallback", "noMatch", "No converted GA models".to_string()),
+            }
+        } else {
+            match self.find_fsltl("B738", None) {
+                Some(model) => {
+                    trace.push("fallback", "matched", model.model_name.clone());
+                    return (Some(Self::fsltl_match(model, "fallback", None)), None);
+                }
+                None => trace.push("fallback", "noMatch", "No converted B738 base livery".to_string()),
+            }
+        }
+
+        (None, None)
+    }
+}
+
+/// Cached matcher with the fingerprint it was built from
+struct CachedMatcher {
+    fingerprint: Vec<(PathBuf, Option<SystemTime>)>,
+    indexed_at: u64,
+    matcher: Arc<ModelMatcher>,
+}
+
+/// Model matcher state managed by Tauri
+pub struct ModelMatcherState {
+    cache: RwLock<Option<CachedMatcher>>,
+}
+
+impl ModelMatcherState {
+    pub fn new() -> Self {
+        Self {
+            cache: RwLock::new(None),
+        }
+    }
+
+    /// Drop the cached matcher so the next lookup rebuilds it
+    pub fn invalidate(&self) {
+        *self.cache.write() = None;
+    }
+}
+
+impl Default for ModelMatcherState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// FSLTL paths from global settings: (output path, VMR path), None if FSLTL is disabled
+fn fsltl_paths(app: &AppHandle) -> Option<(PathBuf, Option<PathBuf>)> {
+    let settings = crate::read_global_settings(app.clone()).ok()?;
+    if !settings.fsltl.enable_fsltl_models {
+        return None;
+    }
+    let output = PathBuf::from(settings.fsltl.output_path?);
+
+    // The converter copies FSLTL_Rules.vmr to the output folder; fall back to the source folder
+    let vmr = std::iter::once(output.join("FSLTL_Rules.vmr"))
+        .chain(
+            settings
+                .fsltl
+                .source_path
+                .map(|s| PathBuf::from(s).join("FSLTL_Rules.vmr")),
+        )
+        .find(|p| p.exists());
+
+    Some((output, vmr))
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Modification times of everything the matcher is built from
+fn compute_fingerprint(
+    app: &AppHandle,
+    fsltl: Option<&(PathBuf, Option<PathBuf>)>,
+) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut paths = vmr::all_vmr_paths(app);
+    paths.extend(similar_types::get_overrides_file(app).ok());
+    paths.extend(vmr::get_vmr_state_file(app).ok());
+
+    if let Some((output, vmr_path)) = fsltl {
+        paths.extend(vmr_path.clone());
+        paths.push(output.clone());
+        // Type folders change when new liveries are converted
+        if let Ok(entries) = fs::read_dir(output) {
+            paths.extend(entries.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.is_dir()));
+        }
+    }
+
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|p| {
+            let mtime = modified_time(&p);
+            (p, mtime)
+        })
+        .collect()
+}
+
+/// Get the model matcher, rebuilding it if rules, mods or converted models changed
+pub fn get_matcher(app: &AppHandle) -> Arc<ModelMatcher> {
+    let registry = mods::get_registry(app);
+    let fsltl = fsltl_paths(app);
+    let fingerprint = compute_fingerprint(app, fsltl.as_ref());
+
+    let matcher_state = app.state::<ModelMatcherState>();
+    if let Some(cached) = matcher_state.cache.read().as_ref() {
+        if cached.fingerprint == fingerprint && cached.indexed_at == registry.indexed_at {
+            return cached.matcher.clone();
+        }
+    }
+
+    let started = Instant::now();
+    let aircraft_mods = registry
+        .mods
+        .into_iter()
+        .filter(|m| m.mod_type == "aircraft" && m.enabled)
+        .collect();
+
+    let (fsltl_rules, fsltl_models) = match &fsltl {
+        Some((output, vmr_path)) => (
+            vmr_path
+                .as_deref()
+                .and_then(|p| vmr::parse_vmr_file(p).map_err(|e| warn!("[Matching] {}", e)).ok())
+                .unwrap_or_default(),
+            fsltl_scan::scan(&normalize_path_string(output), |_| {}).unwrap_or_default(),
+        ),
+        None => (Vec::new(), Vec::new()),
+    };
+
+    let matcher = Arc::new(ModelMatcher::build(
+        vmr::collect_mod_rules(app),
+        aircraft_mods,
+        fsltl_rules,
+        fsltl_models,
+        similar_types::effective(app),
+    ));
+    info!(
+        "[Matching] Built matcher: {} custom rules, {} custom models, {} FSLTL rules, {} FSLTL types",
+        matcher.custom_rules.len(),
+        matcher.custom_models.len(),
+        matcher.fsltl_rules.len(),
+        matcher.fsltl_models.len()
+    );
+    startup::record_first("Model matcher and FSLTL index (first use)", started);
+
+    *matcher_state.cache.write() = Some(CachedMatcher {
+        fingerprint,
+        indexed_at: registry.indexed_at,
+        matcher: matcher.clone(),
+    });
+
+    matcher
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Match an aircraft type/callsign/airline to a model, with a trace of the steps tried
+#[tauri::command]
+pub fn match_model(
+    app: AppHandle,
+    type_code: Option<String>,
+    callsign: Option<String>,
+    airline: Option<String>,
+) -> MatchResult {
+    let query = MatchQuery {
+        type_code,
+        callsign,
+        airline,
+    };
+    let result = get_matcher(&app).match_model(&query);
+    app.state::<RecentTrafficState>().record(&query, &result);
+    app.state::<ModelUsageState>().record(&result);
+    result
+}
+
+/// Force the model matcher to be rebuilt (e.g., after converting FSLTL models)
+#[tauri::command]
+pub fn refresh_model_matcher(state: State<'_, ModelMatcherState>) {
+    state.invalidate();
+}
+
+/// Initialize model matcher state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_model_matcher(app: &AppHandle) {
+    app.manage(ModelMatcherState::new());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mods::ModFileInfo;
+
+    const FSLTL_RULES: &str = include_str!("../tests/fixtures/vmr/FSLTL_Rules.vmr");
+    const AIG_RULES: &str = include_str!("../tests/fixtures/vmr/AIG_Rules.vmr");
+
+    fn aircraft_mod(name: &str) -> ModRegistryEntry {
+        ModRegistryEntry {
+            id: format!("aircraft/{}", name),
+            name: name.to_string(),
+            mod_type: "aircraft".to_string(),
+            path: format!("/mods/aircraft/{}", name),
+            display_name: None,
+            version: None,
+            author: None,
+            description: None,
+            supported_icaos: Vec::new(),
+            files: vec![ModFileInfo {
+                name: "model.glb".to_string(),
+                size: 1,
+            }],
+            total_size: 1,
+            enabled: true,
+            load_order: 0,
+            manifest: None,
+            active_variant: None,
+            error: None,
+        }
+    }
+
+    fn converted(aircraft_type: &str, airline: Option<&str>) -> ScannedFSLTLModel {
+        let folder = airline.unwrap_or("base");
+        ScannedFSLTLModel {
+            model_name: format!("FSLTL_{}_{}", aircraft_type, airline.unwrap_or("ZZZZ")),
+            model_path: format!("/fsltl/{}/{}/model.glb", aircraft_type, folder),
+            relative_path: format!("{}/{}/model.glb", aircraft_type, folder),
+            aircraft_type: aircraft_type.to_string(),
+            airline_code: airline.map(str::to_string),
+            has_animations: false,
+            file_size: 1,
+        }
+    }
+
+    fn matcher(mod_names: &[&str], models: Vec<ScannedFSLTLModel>) -> ModelMatcher {
+        ModelMatcher::build(
+            vmr::parse_vmr_content(AIG_RULES).rules,
+            mod_names.iter().map(|name| aircraft_mod(name)).collect(),
+            vmr::parse_vmr_content(FSLTL_RULES).rules,
+            models,
+            SimilarTypes::new(),
+        )
+    }
+
+    fn query(type_code: &str, callsign: &str) -> MatchQuery {
+        MatchQuery {
+            type_code: Some(type_code.to_string()),
+            callsign: Some(callsign.to_string()),
+            airline: None,
+        }
+    }
+
+    #[test]
+    fn extracts_flight_numbers() {
+        assert_eq!(extract_flight_number("BAW123"), Some(123));
+        assert_eq!(extract_flight_number("baw12a"), Some(12));
+        assert_eq!(extract_flight_number("N123AB"), None);
+        assert_eq!(extract_flight_number("BAW"), None);
+    }
+
+    #[test]
+    fn selects_exact_type_rules() {
+        let matcher = matcher(
+            &["AIG Airbus A321-200 American Airlines N101NN"],
+            vec![converted("B738", None), converted("B738", Some("AAL"))],
+        );
+
+        // Custom airline+type rule
+        let result = matcher.match_model(&query("A321/L", "AAL12"));
+        let model = result.model.unwrap();
+        assert_eq!(model.source, "custom");
+        assert_eq!(model.model_name, "AIG Airbus A321-200 American Airlines N101NN");
+        assert_eq!(model.aircraft_type, "A321");
+
+        // FSLTL rule alternatives resolve to the converted airline livery of their type
+        let model = matcher.match_model(&query("B738", "AAL34")).model.unwrap();
+        assert_eq!((model.source.as_str(), model.match_type.as_str()), ("fsltl", "vmr"));
+        assert_eq!(model.model_name, "FSLTL_B738_AAL");
+        assert_eq!(model.rule.unwrap().callsign_prefix.as_deref(), Some("AAL"));
+
+        // No airline rule for UAL: base livery for the type
+        let model = matcher.match_model(&query("B738", "UAL56")).model.unwrap();
+        assert_eq!(model.match_type, "baseLivery");
+        assert_eq!(model.model_name, "FSLTL_B738_ZZZZ");
+    }
+
+    #[test]
+    fn falls_back_to_airline_only_rules() {
+        let matcher = matcher(&["AIG Boeing 767-300F FedEx N101FE"], Vec::new());
+
+        // FDX has no rule for B77L; its airline-only rule applies to any type
+        let result = matcher.match_model(&query("B77L", "FDX1"));
+        let model = result.model.unwrap();
+        assert_eq!(model.model_name, "AIG Boeing 767-300F FedEx N101FE");
+        assert_eq!(model.aircraft_type, "B77L");
+        assert!(result.trace.iter().any(|s| s.step == "customAirlineRule" && s.result == "matched"));
+
+        // Other airlines don't use it
+        assert!(matcher.match_model(&query("B77L", "UPS1")).model.is_none());
+        assert!(matcher.unresolved_custom_rules().iter().all(|r| r.callsign_prefix.as_deref() != Some("FDX")));
+    }
+
+    #[test]
+    fn selects_rules_by_flight_number_range() {
+        let matcher = matcher(
+            &["AIG Airbus A320-200 British Airways G-EUYA", "AIG Airbus A320-200 BA CityFlyer G-EUYR"],
+            Vec::new(),
+        );
+
+        let model = |callsign: &str| matcher.match_model(&query("A320", callsign)).model.map(|m| m.model_name);
+        // Second alternative of the 1-999 rule (the first isn't installed)
+        assert_eq!(model("BAW123").as_deref(), Some("AIG Airbus A320-200 British Airways G-EUYA"));
+        assert_eq!(model("BAW4567").as_deref(), Some("AIG Airbus A320-200 BA CityFlyer G-EUYR"));
+        // Out of every range, or no flight number to check
+        assert_eq!(model("BAW12345"), None);
+        let by_airline = MatchQuery {
+            type_code: Some("A320".to_string()),
+            callsign: None,
+            airline: Some("BAW".to_string()),
+        };
+        assert!(matcher.match_model(&by_airline).model.is_none());
+    }
+}
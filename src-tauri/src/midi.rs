@@ -0,0 +1,279 @@
+//! MIDI controller camera input
+//!
+//! Maps knobs and faders of a MIDI controller (a nanoKONTROL2 by default) to
+//! absolute camera heading, pitch and zoom, and its buttons to control actions
+//! (see control.rs), for tactile camera control in the cab. The mapping lives
+//! in `midi` in global settings.
+//!
+//! A background task connects to the configured input port (reconnecting if
+//! it is unplugged); midir calls back on its own thread for each message.
+//! Knob positions are sent to the main window as `midi-camera` events
+//! (0-1, mapped to the camera's ranges by the frontend).
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use midir::{MidiInput, MidiInputConnection};
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+use crate::control::{self, ControlAction};
+use crate::supervisor::{self, Restart};
+use crate::{debug_stats, GlobalMidiSettings};
+
+/// How often the task checks the port (connects, notices unplugging)
+const CHECK_INTERVAL: Duration = Duration::from_secs(3);
+
+const CLIENT_NAME: &str = "TowerCab 3D";
+
+/// Parsed channel message (channel 1-16)
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MidiMessage {
+    ControlChange { channel: u8, control: u8, value: u8 },
+    NoteOn { channel: u8, note: u8 },
+}
+
+/// Camera value from a knob or fader (`midi-camera` event)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MidiCameraInput {
+    /// "heading", "pitch" or "zoom"
+    pub target: String,
+    /// Knob position, 0-1
+    pub value: f32,
+}
+
+/// MIDI input status
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MidiStatus {
+    pub enabled: bool,
+    /// Available input ports
+    pub ports: Vec<String>,
+    /// Port currently listened to
+    pub connected: Option<String>,
+    pub error: Option<String>,
+}
+
+pub struct MidiState {
+    settings: RwLock<GlobalMidiSettings>,
+    connection: Mutex<Option<(String, MidiInputConnection<()>)>>,
+    status: RwLock<MidiStatus>,
+}
+
+fn parse_message(bytes: &[u8]) -> Option<MidiMessage> {
+    let (&status, data) = bytes.split_first()?;
+    let channel = (status & 0x0F) + 1;
+    match (status & 0xF0, data) {
+        (0xB0, &[control, value, ..]) => Some(MidiMessage::ControlChange {
+            channel,
+            control,
+            value,
+        }),
+        // Note on with velocity 0 is a note off
+        (0x90, &[note, velocity, ..]) if velocity > 0 => Some(MidiMessage::NoteOn { channel, note }),
+        _ => None,
+    }
+}
+
+fn channel_matches(binding: Option<u8>, channel: u8) -> bool {
+    binding.map_or(true, |c| c == channel)
+}
+
+/// Handle one message. `pressed` tracks control change buttons so an action
+/// runs once per press (buttons send 127 on press and 0 on release).
+fn handle_message(app: &AppHandle, bytes: &[u8], pressed: &mut HashMap<(u8, u8), bool>) {
+    let Some(message) = parse_message(bytes) else {
+        return;
+    };
+    let settings = app.state::<MidiState>().settings.read().clone();
+
+    let button = match message {
+        MidiMessage::ControlChange {
+            channel,
+            control,
+            value,
+        } => {
+            for binding in &settings.controls {
+                if binding.control == control && channel_matches(binding.channel, channel) {
+                    let input = MidiCameraInput {
+                        target: binding.target.clone(),
+                        value: value as f32 / 127.0,
+                    };
+                    let _ = app.emit_to("main", "midi-camera", input);
+                }
+            }
+            let is_down = value >= 64;
+            let was_down = pressed.insert((channel, control), is_down).unwrap_or(false);
+            if !is_down || was_down {
+                return;
+            }
+            settings
+                .buttons
+                .iter()
+                .find(|b| b.kind == "cc" && b.number == control && channel_matches(b.channel, channel))
+        }
+        MidiMessage::NoteOn { channel, note } => settings
+            .buttons
+            .iter()
+            .find(|b| b.kind == "note" && b.number == note && channel_matches(b.channel, channel)),
+    };
+
+    if let Some(binding) = button {
+        let action = ControlAction {
+            action: binding.action.clone(),
+            value: binding.value.clone(),
+        };
+        if let Err(e) = control::trigger(app, action) {
+            warn!("[MIDI] {} {}: {}", binding.kind, binding.number, e);
+        }
+    }
+}
+
+/// Connect, reconnect or disconnect to match the settings and available ports
+fn refresh(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<MidiState>();
+    let settings = state.settings.read().clone();
+
+    let input = MidiInput::new(CLIENT_NAME).map_err(|e| format!("MIDI unavailable: {}", e))?;
+    let ports: Vec<_> = input
+        .ports()
+        .into_iter()
+        .filter_map(|port| input.port_name(&port).ok().map(|name| (name, port)))
+        .collect();
+    state.status.write().ports = ports.iter().map(|(name, _)| name.clone()).collect();
+
+    let mut connection = state.connection.lock();
+    let still_present = connection
+        .as_ref()
+        .is_some_and(|(name, _)| ports.iter().any(|(port, _)| port == name));
+    if connection.is_some() && (!settings.enabled || !still_present) {
+        if let Some((name, _)) = connection.take() {
+            info!("[MIDI] Disconnected from {}", name);
+        }
+    }
+    if !settings.enabled || connection.is_some() {
+        state.status.write().connected = connection.as_ref().map(|(name, _)| name.clone());
+        return Ok(());
+    }
+
+    let wanted = settings.port.as_deref().map(str::to_lowercase);
+    let Some((name, port)) = ports
+        .into_iter()
+        .find(|(name, _)| wanted.as_deref().map_or(true, |w| name.to_lowercase().contains(w)))
+    else {
+        state.status.write().connected = None;
+        return Ok(());
+    };
+
+    let handler_app = app.clone();
+    let mut pressed = HashMap::new();
+    let conn = input
+        .connect(
+            &port,
+            "towercab-3d-input",
+            move |_, bytes, _| handle_message(&handler_app, bytes, &mut pressed),
+            (),
+        )
+        .map_err(|e| format!("Failed to open {}: {}", name, e))?;
+    info!("[MIDI] Listening to {}", name);
+    state.status.write().connected = Some(name.clone());
+    *connection = Some((name, conn));
+    Ok(())
+}
+
+/// Apply saved MIDI settings (called when global settings are written)
+pub fn apply_settings(app: &AppHandle, settings: &GlobalMidiSettings) {
+    let Some(state) = app.try_state::<MidiState>() else {
+        return;
+    };
+    let port_changed = state.settings.read().port != settings.port;
+    *state.settings.write() = settings.clone();
+    state.status.write().enabled = settings.enabled;
+    if port_changed {
+        // Reconnected by the next check
+        state.connection.lock().take();
+    }
+}
+
+/// Initialize MIDI state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_midi(app: &AppHandle) {
+    let settings = crate::read_global_settings(app.clone())
+        .map(|s| s.midi)
+        .unwrap_or_default();
+    app.manage(MidiState {
+        status: RwLock::new(MidiStatus {
+            enabled: settings.enabled,
+            ..Default::default()
+        }),
+        settings: RwLock::new(settings),
+        connection: Mutex::new(None),
+    });
+}
+
+/// Keep the MIDI connection matching the settings
+pub fn start_midi_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn("midi", Restart::Always, move || {
+        let app = app.clone();
+        async move {
+            let mut tick = tokio::time::interval(CHECK_INTERVAL);
+            loop {
+                tick.tick().await;
+                let refresh_app = app.clone();
+                // Port enumeration can block (CoreMIDI, ALSA), keep it off the async workers
+                let result = tauri::async_runtime::spawn_blocking(move || refresh(&refresh_app))
+                    .await
+                    .unwrap_or_else(|e| Err(e.to_string()));
+                let error = result.err();
+                {
+                    let state = app.state::<MidiState>();
+                    let mut status = state.status.write();
+                    if error.is_some() && status.error != error {
+                        warn!("[MIDI] {}", error.as_deref().unwrap_or_default());
+                    }
+                    status.error = error.clone();
+                }
+                debug_stats::task_ran("midi", CHECK_INTERVAL, error);
+            }
+        }
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get MIDI ports and the connection state
+#[tauri::command]
+pub fn get_midi_status(app: AppHandle) -> MidiStatus {
+    app.try_state::<MidiState>()
+        .map(|state| state.status.read().clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_channel_messages() {
+        assert_eq!(
+            parse_message(&[0xB0, 16, 127]),
+            Some(MidiMessage::ControlChange {
+                channel: 1,
+                control: 16,
+                value: 127
+            })
+        );
+        assert_eq!(
+            parse_message(&[0x93, 60, 100]),
+            Some(MidiMessage::NoteOn { channel: 4, note: 60 })
+        );
+        assert_eq!(parse_message(&[0x90, 60, 0]), None);
+        assert_eq!(parse_message(&[0xF8]), None);
+    }
+}
@@ -0,0 +1,588 @@
+//! Mock traffic source (`mock-traffic` feature)
+//!
+//! Validating a new subsystem or a facility setup shouldn't need VATSIM or
+//! vNAS. With the feature built in and `TOWERCAB_MOCK_TRAFFIC=ICAO` (or
+//! `ICAO:SEED`) set, the app serves a synthetic VATSIM data feed from a local
+//! server and polls that instead of the real one, and pushes 1 Hz vNAS
+//! updates for the same aircraft into the vNAS batching, so the whole
+//! pipeline runs as usual: traffic ingest and derived services, the vNAS
+//! merge and broadcast, `/api/traffic` and the aircraft WebSockets.
+//!
+//! Traffic is deterministic for a seed: straight-in arrivals descending on a
+//! 3 degree path, departures climbing out, overflights at cruise and aircraft
+//! parked on the field, each at a position that is a function of the time
+//! since the scenario started. `npm run dev:mock` starts the app this way at
+//! KBOS; the tests below drive the same source through ingest and encoding,
+//! and through the API server's `/api/traffic` and `/api/vnas/ws`.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::DateTime;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+use crate::airport_db;
+use crate::geo;
+use crate::supervisor::{self, Restart};
+use crate::vnas::VnasAircraft;
+use crate::vnas_batch;
+use crate::weather;
+
+/// Environment variable selecting the airport (and seed) to simulate
+const ENV_VAR: &str = "TOWERCAB_MOCK_TRAFFIC";
+
+/// Seed when none is given
+const DEFAULT_SEED: u64 = 1;
+
+/// Feed path, as on data.vatsim.net
+const FEED_PATH: &str = "/v3/vatsim-data.json";
+
+const FEET_PER_METER: f64 = 3.28084;
+
+/// Arrivals join the final approach this far out (nm)
+const ARRIVAL_START_NM: f64 = 30.0;
+
+/// Departures are followed this far out (nm)
+const DEPARTURE_END_NM: f64 = 40.0;
+
+/// Overflights cross this much of the sky around the airport (nm)
+const OVERFLIGHT_SPAN_NM: f64 = 110.0;
+
+/// 3 degree descent path (feet per nm)
+const DESCENT_FT_PER_NM: f64 = 318.0;
+
+const TYPES: [&str; 6] = ["B738", "A320", "B77W", "E175", "A21N", "C172"];
+
+/// What is simulated
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockScenario {
+    pub icao: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub elevation_ft: f64,
+    pub seed: u64,
+    pub arrivals: usize,
+    pub departures: usize,
+    pub overflights: usize,
+    pub parked: usize,
+    /// Unix ms at which the scenario starts
+    pub epoch_ms: u64,
+}
+
+impl MockScenario {
+    /// A scenario at an airport with a moderate mix of traffic
+    pub fn new(icao: &str, latitude: f64, longitude: f64, elevation_ft: f64, seed: u64, epoch_ms: u64) -> Self {
+        Self {
+            icao: icao.to_uppercase(),
+            latitude,
+            longitude,
+            elevation_ft,
+            seed,
+            arrivals: 8,
+            departures: 6,
+            overflights: 4,
+            parked: 6,
+            epoch_ms,
+        }
+    }
+}
+
+/// Deterministic pseudo-random numbers (SplitMix64)
+struct Random(u64);
+
+impl Random {
+    fn new(seed: u64, stream: u64) -> Self {
+        Self(seed ^ stream.wrapping_mul(0x9E37_79B9_7F4A_7C15))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [low, high)
+    fn range(&mut self, low: f64, high: f64) -> f64 {
+        low + (self.next() >> 11) as f64 / (1u64 << 53) as f64 * (high - low)
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[(self.next() % items.len() as u64) as usize]
+    }
+}
+
+/// One simulated aircraft at a moment
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockAircraft {
+    pub callsign: String,
+    pub cid: u64,
+    pub aircraft_type: String,
+    pub departure: String,
+    pub arrival: String,
+    pub squawk: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub altitude_ft: f64,
+    pub groundspeed_kt: f64,
+    pub heading: f64,
+    /// Feet per minute
+    pub vertical_rate_fpm: f64,
+}
+
+/// Where every simulated aircraft is `seconds` after the scenario started
+pub fn aircraft_at(scenario: &MockScenario, seconds: f64) -> Vec<MockAircraft> {
+    let airport = (scenario.latitude, scenario.longitude);
+    let field = scenario.elevation_ft;
+    let mut aircraft = Vec::new();
+    let mut add = |kind: &str, index: usize, position: (f64, f64), altitude_ft: f64, speed: f64, heading: f64, vs: f64| {
+        let n = aircraft.len() as u64;
+        let mut random = Random::new(scenario.seed, 1000 + n);
+        let (departure, arrival) = match kind {
+            "arrival" => ("KZZZ".to_string(), scenario.icao.clone()),
+            "departure" | "parked" => (scenario.icao.clone(), "KZZZ".to_string()),
+            _ => ("KZZZ".to_string(), "KYYY".to_string()),
+        };
+        aircraft.push(MockAircraft {
+            callsign: format!("MCK{}{:02}", &kind[..1].to_uppercase(), index + 1),
+            cid: 1_500_000 + n,
+            aircraft_type: random.pick(&TYPES).to_string(),
+            departure,
+            arrival,
+            squawk: format!("{:04o}", 0o2000 + n),
+            latitude: position.0,
+            longitude: position.1,
+            altitude_ft: altitude_ft.round(),
+            groundspeed_kt: speed,
+            heading: heading.rem_euclid(360.0),
+            vertical_rate_fpm: vs.round(),
+        });
+    };
+
+    // Straight-in arrivals from all around, spread along the approach
+    for i in 0..scenario.arrivals {
+        let mut random = Random::new(scenario.seed, i as u64);
+        let inbound_from = random.range(0.0, 360.0);
+        let speed = random.range(140.0, 180.0);
+        let travelled = random.range(0.0, ARRIVAL_START_NM) + speed * seconds / 3600.0;
+        let remaining = ARRIVAL_START_NM - travelled.rem_euclid(ARRIVAL_START_NM);
+        let position = geo::destination(airport.0, airport.1, inbound_from, remaining);
+        let vs = -speed / 60.0 * DESCENT_FT_PER_NM;
+        add("arrival", i, position, field + remaining * DESCENT_FT_PER_NM, speed, inbound_from + 180.0, vs);
+    }
+
+    // Departures climbing out at 2000 ft/min
+    for i in 0..scenario.departures {
+        let mut random = Random::new(scenario.seed, 100 + i as u64);
+        let outbound = random.range(0.0, 360.0);
+        let speed = random.range(220.0, 260.0);
+        let distance = (random.range(0.0, DEPARTURE_END_NM) + speed * seconds / 3600.0).rem_euclid(DEPARTURE_END_NM);
+        let position = geo::destination(airport.0, airport.1, outbound, distance);
+        let altitude = field + distance / speed * 60.0 * 2000.0;
+        let vs = if altitude < field + 15000.0 { 2000.0 } else { 0.0 };
+        add("departure", i, position, altitude.min(field + 15000.0), speed, outbound, vs);
+    }
+
+    // Overflights at cruise on parallel tracks past the airport
+    for i in 0..scenario.overflights {
+        let mut random = Random::new(scenario.seed, 200 + i as u64);
+        let track = random.range(0.0, 360.0);
+        let lateral = random.range(-20.0, 20.0);
+        let speed = random.range(420.0, 480.0);
+        let along = (random.range(0.0, OVERFLIGHT_SPAN_NM) + speed * seconds / 3600.0).rem_euclid(OVERFLIGHT_SPAN_NM)
+            - OVERFLIGHT_SPAN_NM / 2.0;
+        let abeam = geo::destination(airport.0, airport.1, track + 90.0, lateral);
+        let position = geo::destination(abeam.0, abeam.1, track, along);
+        add("overflight", i, position, 35000.0, speed, track, 0.0);
+    }
+
+    // Parked around the field
+    for i in 0..scenario.parked {
+        let mut random = Random::new(scenario.seed, 300 + i as u64);
+        let position = geo::destination(airport.0, airport.1, random.range(0.0, 360.0), random.range(0.2, 0.6));
+        add("parked", i, position, field, 0.0, random.range(0.0, 360.0), 0.0);
+    }
+    aircraft
+}
+
+/// The VATSIM v3 data feed `seconds` after the scenario started
+pub fn feed_at(scenario: &MockScenario, seconds: u64) -> Value {
+    let now_ms = scenario.epoch_ms + seconds * 1000;
+    let timestamp = |ms: u64| {
+        DateTime::from_timestamp_millis(ms as i64)
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_default()
+    };
+    let pilots: Vec<Value> = aircraft_at(scenario, seconds as f64)
+        .into_iter()
+        .map(|a| {
+            json!({
+                "cid": a.cid,
+                "name": "Mock Pilot",
+                "callsign": a.callsign,
+                "latitude": a.latitude,
+                "longitude": a.longitude,
+                "altitude": a.altitude_ft,
+                "groundspeed": a.groundspeed_kt,
+                "heading": a.heading.round(),
+                "transponder": a.squawk,
+                "last_updated": timestamp(now_ms),
+                "flight_plan": {
+                    "flight_rules": "I",
+                    "aircraft_short": a.aircraft_type,
+                    "departure": a.departure,
+                    "arrival": a.arrival,
+                    "altitude": "35000",
+                    "route": "DCT",
+                    "assigned_transponder": a.squawk,
+                },
+            })
+        })
+        .collect();
+    let station = |suffix: &str, cid: u64, frequency: &str, facility: u8| {
+        json!({
+            "cid": cid,
+            "name": "Mock Controller",
+            "callsign": format!("{}_{}", scenario.icao, suffix),
+            "frequency": frequency,
+            "facility": facility,
+            "rating": 4,
+            "logon_time": timestamp(scenario.epoch_ms),
+        })
+    };
+    let mut atis = station("ATIS", 1_400_002, "135.000", 4);
+    atis["atis_code"] = json!("A");
+    atis["text_atis"] = json!([format!("{} INFORMATION ALPHA. MOCK TRAFFIC.", scenario.icao)]);
+    json!({
+        "general": { "update_timestamp": timestamp(now_ms) },
+        "pilots": pilots,
+        "controllers": [station("TWR", 1_400_001, "118.700", 4)],
+        "atis": [atis],
+        "prefiles": [],
+    })
+}
+
+/// vNAS updates for the simulated aircraft `seconds` after the scenario started
+pub fn vnas_at(scenario: &MockScenario, seconds: u64) -> Vec<VnasAircraft> {
+    aircraft_at(scenario, seconds as f64)
+        .into_iter()
+        .filter(|a| a.groundspeed_kt > 0.0)
+        .map(|a| VnasAircraft {
+            is_heavy: a.aircraft_type == "B77W",
+            callsign: a.callsign,
+            type_code: a.aircraft_type,
+            lat: a.latitude,
+            lon: a.longitude,
+            true_heading: a.heading,
+            true_ground_track: Some(a.heading),
+            altitude_true: a.altitude_ft / FEET_PER_METER,
+            altitude_agl: (a.altitude_ft - scenario.elevation_ft).max(0.0) / FEET_PER_METER,
+            voice_type: 1,
+            timestamp: scenario.epoch_ms + seconds * 1000,
+            groundspeed_kt: Some(a.groundspeed_kt),
+            vertical_rate_fpm: Some(a.vertical_rate_fpm),
+            trail_point: None,
+        })
+        .collect()
+}
+
+/// A running mock feed server
+pub struct MockServer {
+    /// URL of the data feed
+    pub url: String,
+    scenario: Arc<MockScenario>,
+    /// Seconds since the scenario started
+    clock: Arc<AtomicU64>,
+}
+
+impl MockServer {
+    /// Move the scenario forward
+    pub fn advance(&self, seconds: u64) -> u64 {
+        self.clock.fetch_add(seconds, Ordering::Relaxed) + seconds
+    }
+
+    pub fn seconds(&self) -> u64 {
+        self.clock.load(Ordering::Relaxed)
+    }
+}
+
+async fn serve_feed(State((scenario, clock)): State<(Arc<MockScenario>, Arc<AtomicU64>)>) -> Json<Value> {
+    Json(feed_at(&scenario, clock.load(Ordering::Relaxed)))
+}
+
+/// Serve a scenario's data feed on a free local port
+pub async fn serve(scenario: MockScenario) -> Result<MockServer, String> {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .map_err(|e| format!("Failed to bind mock traffic server: {}", e))?;
+    let address = listener.local_addr().map_err(|e| e.to_string())?;
+    let scenario = Arc::new(scenario);
+    let clock = Arc::new(AtomicU64::new(0));
+    let router = Router::new()
+        .route(FEED_PATH, get(serve_feed))
+        .with_state((scenario.clone(), clock.clone()));
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, router).await {
+            warn!("[MockTraffic] Server stopped: {}", e);
+        }
+    });
+    Ok(MockServer {
+        url: format!("http://{}{}", address, FEED_PATH),
+        scenario,
+        clock,
+    })
+}
+
+/// Mock traffic state (managed by Tauri while mock traffic runs)
+pub struct MockTrafficState {
+    server: MockServer,
+}
+
+/// Feed URL to poll instead of VATSIM's, while mock traffic runs
+pub fn feed_url(app: &AppHandle) -> Option<String> {
+    app.try_state::<MockTrafficState>().map(|s| s.server.url.clone())
+}
+
+/// Parse `ICAO` or `ICAO:SEED`
+fn parse_env(value: &str) -> Option<(String, u64)> {
+    let (icao, seed) = match value.split_once(':') {
+        Some((icao, seed)) => (icao, seed.trim().parse().ok()?),
+        None => (value, DEFAULT_SEED),
+    };
+    weather::normalize_icao(icao).ok().map(|icao| (icao, seed))
+}
+
+/// Start mock traffic if `TOWERCAB_MOCK_TRAFFIC` is set: serve the feed,
+/// make the airport active and push vNAS updates every second.
+/// Call this in the Tauri setup closure.
+pub fn init_mock_traffic(app: &AppHandle) {
+    let Ok(value) = std::env::var(ENV_VAR) else {
+        return;
+    };
+    let Some((icao, seed)) = parse_env(&value) else {
+        warn!("[MockTraffic] Invalid {}='{}' (expected ICAO or ICAO:SEED)", ENV_VAR, value);
+        return;
+    };
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let airport = match airport_db::get_airport(&app, icao.clone()).await {
+            Ok(Some(airport)) => airport,
+            Ok(None) => {
+                warn!("[MockTraffic] Unknown airport {}", icao);
+                return;
+            }
+            Err(e) => {
+                warn!("[MockTraffic] {}", e);
+                return;
+            }
+        };
+        let epoch_ms = chrono::Utc::now().timestamp_millis().max(0) as u64;
+        let scenario = MockScenario::new(&icao, airport.latitude, airport.longitude, airport.elevation_ft, seed, epoch_ms);
+        let server = match serve(scenario).await {
+            Ok(server) => server,
+            Err(e) => {
+                warn!("[MockTraffic] {}", e);
+                return;
+            }
+        };
+        info!("[MockTraffic] Serving mock traffic at {} (seed {}) on {}", icao, seed, server.url);
+        app.manage(MockTrafficState { server });
+        if let Err(e) = weather::set_weather_airport(app.clone(), Some(icao)).await {
+            warn!("[MockTraffic] Couldn't fetch weather for the airport: {}", e);
+        }
+
+        supervisor::spawn("mock-traffic", Restart::Always, move || {
+            let app = app.clone();
+            async move {
+                let mut ticks = tokio::time::interval(Duration::from_secs(1));
+                loop {
+                    ticks.tick().await;
+                    let state = app.state::<MockTrafficState>();
+                    let seconds = state.server.advance(1);
+                    for aircraft in vnas_at(&state.server.scenario, seconds) {
+                        vnas_batch::push(&app, aircraft);
+                    }
+                }
+            }
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::airport_db::AirportRecord;
+    use crate::kinematics::PositionHistory;
+    use crate::server::{self, VnasAircraftBroadcast};
+    use crate::traffic::{self, VatsimData};
+    use crate::vnas_batch::Batch;
+    use crate::ws_encoding::DeltaEncoder;
+    use crate::{lod, trails};
+    use futures_util::StreamExt;
+    use tokio_tungstenite::tungstenite;
+
+    fn kbos() -> MockScenario {
+        MockScenario::new("kbos", 42.3656, -71.0096, 20.0, 7, 1_800_000_000_000)
+    }
+
+    #[test]
+    fn drives_ingest_and_broadcast_from_the_mock_feed() {
+        let scenario = kbos();
+        assert_eq!(aircraft_at(&scenario, 42.0), aircraft_at(&scenario.clone(), 42.0));
+        assert_ne!(aircraft_at(&scenario, 42.0), aircraft_at(&MockScenario { seed: 8, ..scenario.clone() }, 42.0));
+        assert_eq!(parse_env("kbos:42"), Some(("KBOS".to_string(), 42)));
+        assert_eq!(parse_env("KBOS"), Some(("KBOS".to_string(), DEFAULT_SEED)));
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let server = serve(scenario.clone()).await.unwrap();
+            let mut history = PositionHistory::new(Duration::from_secs(35));
+            let mut polls = Vec::new();
+            for _ in 0..2 {
+                // Ingest over HTTP, as the traffic task does
+                let data: VatsimData = reqwest::get(&server.url).await.unwrap().json().await.unwrap();
+                assert_eq!(data.controllers[0].callsign, "KBOS_TWR");
+                let now = scenario.epoch_ms + server.seconds() * 1000;
+                polls.push(traffic::nearby_aircraft(
+                    &data.pilots,
+                    scenario.latitude,
+                    scenario.longitude,
+                    scenario.elevation_ft,
+                    &mut history,
+                    now,
+                ));
+                server.advance(15);
+            }
+
+            // Everything within the traffic radius is kept; the second poll derives velocities
+            let (first, second) = (&polls[0], &polls[1]);
+            assert_eq!(first.len(), second.len());
+            assert!(first.len() >= scenario.arrivals + scenario.departures + scenario.parked);
+            let parked: Vec<_> = second.iter().filter(|a| a.callsign.starts_with("MCKP")).collect();
+            assert_eq!(parked.len(), scenario.parked);
+            assert!(parked.iter().all(|a| a.on_ground));
+            let arrivals: Vec<_> = second.iter().filter(|a| a.callsign.starts_with("MCKA")).collect();
+            assert!(arrivals.iter().all(|a| a.arrival.as_deref() == Some("KBOS")));
+            assert!(arrivals.iter().any(|a| a.vertical_rate_fpm.is_some_and(|v| v < -300.0)));
+        });
+
+        // vNAS updates merge per callsign and go out as deltas
+        let mut batch = Batch::default();
+        for seconds in [1, 2] {
+            for aircraft in vnas_at(&scenario, seconds) {
+                batch.push(aircraft);
+            }
+        }
+        let sent: Vec<VnasAircraftBroadcast> = batch.take().iter().map(VnasAircraftBroadcast::from).collect();
+        assert_eq!(sent.len(), scenario.arrivals + scenario.departures + scenario.overflights);
+        assert!(sent.iter().all(|a| a.timestamp == scenario.epoch_ms + 2000));
+
+        let mut encoder = DeltaEncoder::default();
+        assert!(encoder.encode(&sent).iter().all(|d| d.lat.is_some() && d.type_code.is_some()));
+        let next: Vec<VnasAircraftBroadcast> = vnas_at(&scenario, 3).iter().map(VnasAircraftBroadcast::from).collect();
+        let deltas = encoder.encode(&next);
+        assert!(deltas.iter().all(|d| d.lat.is_some() && d.type_code.is_none()));
+    }
+
+    /// An app with the state the traffic routes need
+    #[cfg(any(windows, target_os = "linux"))]
+    fn harness_app() -> tauri::App {
+        let mut context = tauri::generate_context!();
+        context.config_mut().app.windows.clear();
+        let app = tauri::Builder::default().any_thread().build(context).unwrap();
+        traffic::init_traffic(app.handle());
+        trails::init_trails(app.handle());
+        lod::init_lod(app.handle());
+        app
+    }
+
+    // The routes take a Wry `AppHandle`, so `tauri::test::mock_builder()` can't stand in
+    #[cfg(any(windows, target_os = "linux"))]
+    #[test]
+    #[ignore = "starts a real Tauri app, which needs a display (run with --ignored)"]
+    fn serves_mock_traffic_over_http_and_websocket() {
+        let app = harness_app();
+        let app = app.handle().clone();
+        let scenario = kbos();
+        let airport = AirportRecord {
+            icao: scenario.icao.clone(),
+            iata: "BOS".to_string(),
+            name: "Boston Logan".to_string(),
+            city: "Boston".to_string(),
+            state: "MA".to_string(),
+            country: "US".to_string(),
+            elevation_ft: scenario.elevation_ft,
+            latitude: scenario.latitude,
+            longitude: scenario.longitude,
+            tz: "America/New_York".to_string(),
+            runways: Vec::new(),
+        };
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            // The traffic poll fetches the mock feed in place of VATSIM's
+            app.manage(MockTrafficState { server: serve(scenario.clone()).await.unwrap() });
+            let (_, snapshot) = traffic::ingest(&app, &scenario.icao, &airport).await.unwrap();
+            assert!(snapshot.aircraft.len() >= scenario.arrivals + scenario.departures + scenario.parked);
+            let (addr, vnas_tx) = server::serve_for_test(app.clone()).await;
+
+            // GET /api/traffic serves the ingested picture
+            let traffic: Value = reqwest::get(format!("http://{}/api/traffic", addr))
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            assert_eq!(traffic["icao"], "KBOS");
+            let callsigns: Vec<&str> = traffic["aircraft"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|a| a["callsign"].as_str().unwrap())
+                .collect();
+            let expected: Vec<&str> = snapshot.aircraft.iter().map(|a| a.callsign.as_str()).collect();
+            assert_eq!(callsigns, expected);
+            let parked: Vec<&Value> = traffic["aircraft"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|a| a["callsign"].as_str().unwrap().starts_with("MCKP"))
+                .collect();
+            assert_eq!(parked.len(), scenario.parked);
+            assert!(parked.iter().all(|a| a["onGround"] == true));
+
+            // /api/vnas/ws relays the vNAS batches
+            let (mut socket, _) = tokio_tungstenite::connect_async(format!("ws://{}/api/vnas/ws", addr))
+                .await
+                .unwrap();
+            let mut batch = Batch::default();
+            for aircraft in vnas_at(&scenario, 1) {
+                batch.push(aircraft);
+            }
+            let sent: Vec<VnasAircraftBroadcast> = batch.take().iter().map(VnasAircraftBroadcast::from).collect();
+            // The server subscribes once the upgrade completes; resend until it has
+            let mut received = None;
+            for _ in 0..50 {
+                let _ = vnas_tx.send(sent.clone());
+                if let Ok(Some(Ok(tungstenite::Message::Text(text)))) =
+                    tokio::time::timeout(Duration::from_millis(100), socket.next()).await
+                {
+                    received = Some(text);
+                    break;
+                }
+            }
+            let received: Vec<VnasAircraftBroadcast> =
+                serde_json::from_str(&received.expect("no vNAS update relayed")).unwrap();
+            assert_eq!(received.len(), sent.len());
+            assert!(received.iter().zip(&sent).all(|(r, s)| r.callsign == s.callsign && r.lat == s.lat));
+            assert!(received.iter().all(|a| a.timestamp == scenario.epoch_ms + 1000));
+        });
+    }
+}
@@ -0,0 +1,421 @@
+//! Seasonal and time-based mod variants
+//!
+//! A mod manifest can list conditional variants (winter textures, night-only
+//! lit signs, event banners between dates) that swap the model or other files
+//! while their conditions hold:
+//!
+//! ```json
+//! "variants": [
+//!   { "name": "winter", "months": [12, 1, 2], "weather": ["snow"],
+//!     "files": { "textures/apron.png": "textures/apron-snow.png" } },
+//!   { "name": "holidays", "from": "12-15", "until": "01-05", "modelFile": "model-banner.glb" },
+//!   { "name": "night", "time": "night", "modelFile": "model-lit.glb" }
+//! ]
+//! ```
+//!
+//! The first variant whose conditions all hold is active. Conditions are
+//! evaluated by the backend for the mod's airport (a tower mod's first airport,
+//! otherwise the airport being viewed) at the simulated clock's time, so every
+//! client sees the same variant: the desktop app loads it through
+//! `read_mod_manifest`, and the server swaps the files when remote browsers
+//! request the base ones. The active variant is reported in the mod listing.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate};
+use serde::Deserialize;
+use serde_json::Value;
+use tauri::AppHandle;
+use tracing::warn;
+
+use crate::airport_db;
+use crate::clock;
+use crate::ephemeris;
+use crate::mods::{self, ModRegistryEntry};
+use crate::safe_path;
+use crate::weather::{self, Metar};
+
+/// Sun elevation (degrees) below which it counts as night: sunset, like the ephemeris
+const SUNSET_ELEVATION: f64 = -0.833;
+
+/// Model file mods use when the manifest doesn't name one
+const DEFAULT_MODEL_FILE: &str = "model.glb";
+
+/// A conditional variant from a mod manifest (`variants`)
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModVariant {
+    pub name: String,
+    /// Model file used instead of the manifest's `modelFile`
+    #[serde(default)]
+    pub model_file: Option<String>,
+    /// Other files replaced while active (base path -> variant path, relative to the mod folder)
+    #[serde(default)]
+    pub files: HashMap<String, String>,
+    /// Months (1-12) the variant applies in
+    #[serde(default)]
+    pub months: Vec<u32>,
+    /// First day ("MM-DD" every year, or "YYYY-MM-DD")
+    #[serde(default)]
+    pub from: Option<String>,
+    /// Last day ("MM-DD" every year, or "YYYY-MM-DD")
+    #[serde(default)]
+    pub until: Option<String>,
+    /// "day" or "night" at the airport
+    #[serde(default)]
+    pub time: Option<String>,
+    /// Any of these reported at the airport: "snow", "rain", "fog", "thunderstorm" or METAR codes ("FZRA")
+    #[serde(default)]
+    pub weather: Vec<String>,
+}
+
+/// What variant conditions are checked against
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantContext {
+    /// Local date at the airport (UTC if the airport is unknown)
+    pub date: NaiveDate,
+    /// Whether the sun is down (None if the airport is unknown)
+    pub night: Option<bool>,
+    /// Weather reported at the airport (None without a METAR)
+    pub weather: Option<Vec<String>>,
+}
+
+/// A day bound: recurring every year without a year
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct DayBound {
+    year: Option<i32>,
+    month: u32,
+    day: u32,
+}
+
+fn parse_day(text: &str) -> Option<DayBound> {
+    let parts: Vec<&str> = text.trim().split('-').collect();
+    let (year, month, day) = match parts.as_slice() {
+        [month, day] => (None, month.parse().ok()?, day.parse().ok()?),
+        [year, month, day] => (Some(year.parse().ok()?), month.parse().ok()?, day.parse().ok()?),
+        _ => return None,
+    };
+    // Feb 29 is allowed for recurring bounds
+    NaiveDate::from_ymd_opt(year.unwrap_or(2000), month, day)?;
+    Some(DayBound { year, month, day })
+}
+
+/// Whether a date is on or after (`after`) / on or before a bound
+fn past_bound(date: NaiveDate, bound: DayBound, after: bool) -> bool {
+    let ordering = match bound.year {
+        Some(year) => (date.year(), date.month(), date.day()).cmp(&(year, bound.month, bound.day)),
+        None => (date.month(), date.day()).cmp(&(bound.month, bound.day)),
+    };
+    if after {
+        ordering.is_ge()
+    } else {
+        ordering.is_le()
+    }
+}
+
+fn in_date_range(date: NaiveDate, from: Option<DayBound>, until: Option<DayBound>) -> bool {
+    match (from, until) {
+        // Recurring range across the new year (e.g., 12-15 to 01-05)
+        (Some(from), Some(until))
+            if from.year.is_none() && until.year.is_none() && (from.month, from.day) > (until.month, until.day) =>
+        {
+            past_bound(date, from, true) || past_bound(date, until, false)
+        }
+        _ => from.map_or(true, |b| past_bound(date, b, true)) && until.map_or(true, |b| past_bound(date, b, false)),
+    }
+}
+
+/// Check a variant's fields
+pub fn validate(variant: &ModVariant) -> Result<(), String> {
+    if variant.name.trim().is_empty() {
+        return Err("Variant name is required".to_string());
+    }
+    let mut paths = variant.model_file.iter().chain(variant.files.keys()).chain(variant.files.values());
+    if let Some(path) = paths.find(|p| !safe_path::valid_relative(p)) {
+        return Err(format!("Variant {}: invalid file path {}", variant.name, path));
+    }
+    if variant.months.iter().any(|m| !(1..=12).contains(m)) {
+        return Err(format!("Variant {}: months must be 1-12", variant.name));
+    }
+    for day in variant.from.iter().chain(variant.until.iter()) {
+        if parse_day(day).is_none() {
+            return Err(format!("Variant {}: invalid day {} (use MM-DD or YYYY-MM-DD)", variant.name, day));
+        }
+    }
+    if let Some(time) = &variant.time {
+        if time != "day" && time != "night" {
+            return Err(format!("Variant {}: time must be \"day\" or \"night\"", variant.name));
+        }
+    }
+    Ok(())
+}
+
+/// Valid variants listed in a manifest, in order (invalid ones are skipped with a warning)
+pub fn parse_variants(manifest: &Value) -> Vec<ModVariant> {
+    let Some(entries) = manifest.get("variants").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let variant = serde_json::from_value::<ModVariant>(entry.clone())
+                .map_err(|e| e.to_string())
+                .and_then(|v| validate(&v).map(|_| v));
+            variant.map_err(|e| warn!("[Mods] Skipping variant: {}", e)).ok()
+        })
+        .collect()
+}
+
+/// Whether every condition of a variant holds
+pub fn matches(variant: &ModVariant, context: &VariantContext) -> bool {
+    if !variant.months.is_empty() && !variant.months.contains(&context.date.month()) {
+        return false;
+    }
+    let from = variant.from.as_deref().and_then(parse_day);
+    let until = variant.until.as_deref().and_then(parse_day);
+    if !in_date_range(context.date, from, until) {
+        return false;
+    }
+    if let Some(time) = &variant.time {
+        if context.night != Some(time == "night") {
+            return false;
+        }
+    }
+    if !variant.weather.is_empty() {
+        let Some(reported) = &context.weather else {
+            return false;
+        };
+        if !variant.weather.iter().any(|w| reported.iter().any(|r| r.eq_ignore_ascii_case(w))) {
+            return false;
+        }
+    }
+    true
+}
+
+/// First variant whose conditions hold
+pub fn select<'a>(variants: &'a [ModVariant], context: &VariantContext) -> Option<&'a ModVariant> {
+    variants.iter().find(|v| matches(v, context))
+}
+
+/// Weather tags for a METAR: general kinds plus the raw present weather codes
+pub fn weather_tags(metar: &Metar) -> Vec<String> {
+    let mut tags = Vec::new();
+    for phenomenon in &metar.conditions.weather {
+        if phenomenon.intensity == "vicinity" {
+            continue;
+        }
+        let code = phenomenon.code.trim_start_matches(['+', '-']).to_string();
+        for kind in &phenomenon.phenomena {
+            let tag = match kind.as_str() {
+                "SN" | "SG" | "PL" => Some("snow"),
+                "RA" | "DZ" => Some("rain"),
+                "FG" | "BR" => Some("fog"),
+                _ => None,
+            };
+            tags.extend(tag.map(str::to_string));
+        }
+        if phenomenon.descriptor.as_deref() == Some("TS") {
+            tags.push("thunderstorm".to_string());
+        }
+        tags.push(code);
+    }
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+/// Conditions at an airport (or just the date, if there is none) right now
+pub fn context_for(app: &AppHandle, icao: Option<&str>) -> VariantContext {
+    let now = clock::now(app);
+    let position = icao
+        .and_then(|icao| airport_db::get_airport_blocking(app, icao).ok().flatten())
+        .map(|airport| (airport.latitude, airport.longitude));
+
+    // Local mean time from the longitude is close enough to pick the day
+    let utc = DateTime::from_timestamp_millis(now as i64).unwrap_or_default();
+    let offset = position.map_or(0, |(_, lon)| (lon * 240.0) as i64);
+    let date = (utc + Duration::seconds(offset)).date_naive();
+
+    VariantContext {
+        date,
+        night: position.map(|(lat, lon)| ephemeris::compute(lat, lon, now).sun.elevation < SUNSET_ELEVATION),
+        weather: icao.and_then(|icao| weather::cached_metar(app, icao)).map(|m| weather_tags(&m)),
+    }
+}
+
+/// Airport a mod's conditions are checked at: a tower mod's first airport,
+/// otherwise the airport being viewed
+fn airport_for(app: &AppHandle, mod_type: &str, manifest: &Value) -> Option<String> {
+    let first_airport = manifest
+        .get("airports")
+        .and_then(Value::as_array)
+        .and_then(|a| a.first())
+        .and_then(Value::as_str)
+        .map(str::to_uppercase);
+    match mod_type {
+        "towers" => first_airport,
+        _ => weather::active_airport(app),
+    }
+}
+
+/// Active variant of a mod, reusing the conditions already looked up per airport
+fn active_variant_cached(
+    app: &AppHandle,
+    mod_type: &str,
+    manifest: &Value,
+    contexts: &mut HashMap<Option<String>, VariantContext>,
+) -> Option<ModVariant> {
+    let variants = parse_variants(manifest);
+    if variants.is_empty() {
+        return None;
+    }
+    let airport = airport_for(app, mod_type, manifest);
+    let context = contexts
+        .entry(airport)
+        .or_insert_with_key(|airport| context_for(app, airport.as_deref()));
+    select(&variants, context).cloned()
+}
+
+/// Variant of a mod that is active right now, if any
+pub fn active_variant(app: &AppHandle, mod_type: &str, manifest: &Value) -> Option<ModVariant> {
+    active_variant_cached(app, mod_type, manifest, &mut HashMap::new())
+}
+
+/// Point a manifest at its active variant (`modelFile`, `activeVariant`)
+pub fn apply_to_manifest(app: &AppHandle, mod_type: &str, manifest: &mut Value) {
+    let Some(variant) = active_variant(app, mod_type, manifest) else {
+        return;
+    };
+    if let Some(object) = manifest.as_object_mut() {
+        if let Some(model_file) = variant.model_file {
+            object.insert("modelFile".to_string(), Value::String(model_file));
+        }
+        object.insert("activeVariant".to_string(), Value::String(variant.name));
+    }
+}
+
+/// File a variant serves in place of a base file (relative to the mod folder)
+pub fn replacement<'a>(variant: &'a ModVariant, manifest: &Value, file: &str) -> Option<&'a str> {
+    let model_file = manifest
+        .get("modelFile")
+        .and_then(Value::as_str)
+        .unwrap_or(DEFAULT_MODEL_FILE);
+    if file == model_file {
+        if let Some(variant_model) = variant.model_file.as_deref() {
+            return Some(variant_model);
+        }
+    }
+    variant.files.get(file).map(String::as_str)
+}
+
+/// Path to serve for a request under a mod type ("{mod}/{file}"), with the
+/// active variant's file swapped in
+pub fn resolve_file(app: &AppHandle, mod_type: &str, path: &str) -> String {
+    let Some((name, file)) = path.split_once('/') else {
+        return path.to_string();
+    };
+    let id = format!("{}/{}", mod_type, name);
+    let registry = mods::get_registry(app);
+    let Some(manifest) = registry
+        .mods
+        .iter()
+        .find(|m| m.id == id && m.enabled)
+        .and_then(|m| m.manifest.as_ref())
+    else {
+        return path.to_string();
+    };
+
+    active_variant(app, mod_type, manifest)
+        .and_then(|variant| replacement(&variant, manifest, file).map(|f| format!("{}/{}", name, f)))
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// Fill in the active variant of listed mods
+pub fn annotate(app: &AppHandle, entries: &mut [ModRegistryEntry]) {
+    let mut contexts = HashMap::new();
+    for entry in entries.iter_mut() {
+        entry.active_variant = entry
+            .manifest
+            .as_ref()
+            .and_then(|m| active_variant_cached(app, &entry.mod_type, m, &mut contexts))
+            .map(|v| v.name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn context(date: &str) -> VariantContext {
+        VariantContext {
+            date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+            night: Some(false),
+            weather: Some(Vec::new()),
+        }
+    }
+
+    fn variant(value: Value) -> ModVariant {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn matches_date_ranges() {
+        let holidays = variant(json!({ "name": "holidays", "from": "12-15", "until": "01-05" }));
+        assert!(matches(&holidays, &context("2026-12-20")));
+        assert!(matches(&holidays, &context("2027-01-05")));
+        assert!(!matches(&holidays, &context("2027-01-06")));
+
+        let event = variant(json!({ "name": "event", "from": "2026-06-01", "until": "2026-06-03" }));
+        assert!(matches(&event, &context("2026-06-02")));
+        assert!(!matches(&event, &context("2027-06-02")));
+
+        let winter = variant(json!({ "name": "winter", "months": [12, 1, 2] }));
+        assert!(matches(&winter, &context("2026-02-10")));
+        assert!(!matches(&winter, &context("2026-03-01")));
+    }
+
+    #[test]
+    fn matches_time_and_weather() {
+        let night = variant(json!({ "name": "night", "time": "night" }));
+        let snow = variant(json!({ "name": "snow", "weather": ["snow"] }));
+        let mut ctx = context("2026-01-10");
+        assert!(!matches(&night, &ctx));
+        assert!(!matches(&snow, &ctx));
+
+        ctx.night = Some(true);
+        ctx.weather = Some(vec!["snow".to_string(), "SN".to_string()]);
+        assert!(matches(&night, &ctx));
+        assert!(matches(&snow, &ctx));
+
+        // Unknown airport: no time or weather based variant applies
+        ctx.night = None;
+        ctx.weather = None;
+        assert!(!matches(&night, &ctx));
+        assert!(!matches(&snow, &ctx));
+    }
+
+    #[test]
+    fn parses_and_resolves_variants() {
+        let manifest = json!({
+            "modelFile": "tower.glb",
+            "variants": [
+                { "name": "broken", "from": "13-40" },
+                { "name": "escape", "modelFile": "../other/model.glb" },
+                { "name": "winter", "months": [1], "modelFile": "tower-winter.glb",
+                  "files": { "textures/roof.png": "textures/roof-snow.png" } },
+                { "name": "always", "modelFile": "tower-alt.glb" }
+            ]
+        });
+        let variants = parse_variants(&manifest);
+        assert_eq!(variants.iter().map(|v| v.name.as_str()).collect::<Vec<_>>(), ["winter", "always"]);
+
+        let winter = select(&variants, &context("2026-01-10")).unwrap();
+        assert_eq!(winter.name, "winter");
+        assert_eq!(replacement(winter, &manifest, "tower.glb"), Some("tower-winter.glb"));
+        assert_eq!(replacement(winter, &manifest, "textures/roof.png"), Some("textures/roof-snow.png"));
+        assert_eq!(replacement(winter, &manifest, "textures/wall.png"), None);
+
+        assert_eq!(select(&variants, &context("2026-07-10")).unwrap().name, "always");
+    }
+}
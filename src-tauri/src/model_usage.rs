@@ -0,0 +1,475 @@
+//! FSLTL model usage analytics
+//!
+//! A full FSLTL conversion is around 60 GB, most of it liveries of airlines
+//! that never show up at the airports a user watches. Every model match
+//! (`match_model` and `/api/match`) is counted here, across sessions:
+//!
+//! - `models`: converted FSLTL models that were handed out, with how often,
+//!   in how many sessions (app runs) and when they were last used
+//! - `wanted`: airline liveries traffic asked for but that aren't available,
+//!   so the matcher fell back to another livery or type
+//!
+//! The usage report joins the counts with the converted models on disk: models
+//! never used since tracking started (with their size) are candidates for
+//! pruning, and the most wanted missing liveries (flagged when the FSLTL source
+//! has them) are the conversions worth running. Counts are kept in
+//! `model-usage.json` in the app data folder, written every few minutes and
+//! when a window closes.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tracing::warn;
+
+use crate::debug_stats;
+use crate::error::{Error, Result};
+use crate::fsltl_scan;
+use crate::matching::MatchResult;
+use crate::supervisor::{self, Restart};
+use crate::ScannedFSLTLModel;
+
+/// How often changed counts are written to disk
+const SAVE_INTERVAL: Duration = Duration::from_secs(300);
+
+const USAGE_FILE: &str = "model-usage.json";
+
+/// Requests for one model or livery
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageCount {
+    pub requests: u64,
+    /// App runs it was requested in
+    pub sessions: u32,
+    /// Unix ms
+    pub first_used: u64,
+    pub last_used: u64,
+}
+
+impl UsageCount {
+    fn add(&mut self, new_session: bool, now: u64) {
+        if self.requests == 0 {
+            self.first_used = now;
+        }
+        self.requests = self.requests.saturating_add(1);
+        self.last_used = now;
+        if new_session {
+            self.sessions = self.sessions.saturating_add(1);
+        }
+    }
+}
+
+/// Persisted counts (`model-usage.json`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+struct UsageFile {
+    /// When tracking started (Unix ms)
+    since: u64,
+    /// Converted FSLTL models handed out, by model name (FSLTL_B738_AAL)
+    models: BTreeMap<String, UsageCount>,
+    /// Missing airline liveries, by the model name they would have
+    wanted: BTreeMap<String, UsageCount>,
+}
+
+/// Counts plus what was already counted in this session
+#[derive(Debug, Default)]
+struct UsageLog {
+    file: UsageFile,
+    session_models: HashSet<String>,
+    session_wanted: HashSet<String>,
+}
+
+impl UsageLog {
+    /// Count a match: the FSLTL model handed out, and the airline livery if it was missing
+    fn record(&mut self, result: &MatchResult, now: u64) -> bool {
+        let mut changed = false;
+        if let Some(model) = result.model.as_ref().filter(|m| m.source == "fsltl") {
+            let new_session = self.session_models.insert(model.model_name.clone());
+            self.file
+                .models
+                .entry(model.model_name.clone())
+                .or_default()
+                .add(new_session, now);
+            changed = true;
+        }
+
+        if let (Some(type_code), Some(airline)) = (&result.type_code, &result.airline_code) {
+            let has_livery = result
+                .model
+                .as_ref()
+                .is_some_and(|m| m.airline_code.as_deref() == Some(airline.as_str()));
+            if !result.is_ga && !has_livery {
+                let name = format!("FSLTL_{}_{}", type_code, airline);
+                let new_session = self.session_wanted.insert(name.clone());
+                self.file.wanted.entry(name).or_default().add(new_session, now);
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// Model usage state (managed by Tauri)
+pub struct ModelUsageState {
+    log: Mutex<UsageLog>,
+    dirty: AtomicBool,
+}
+
+impl ModelUsageState {
+    fn new(file: UsageFile) -> Self {
+        Self {
+            log: Mutex::new(UsageLog {
+                file,
+                ..Default::default()
+            }),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    /// Count the model a match lookup resolved to
+    pub fn record(&self, result: &MatchResult) {
+        if self.log.lock().record(result, unix_ms(SystemTime::now())) {
+            self.dirty.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// A converted FSLTL model and its usage
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelUsageEntry {
+    pub model_name: String,
+    pub aircraft_type: String,
+    /// None for the base livery
+    pub airline_code: Option<String>,
+    /// Size of model.glb in bytes
+    pub file_size: u64,
+    pub requests: u64,
+    pub sessions: u32,
+    /// Unix ms; None if never used since tracking started
+    pub last_used: Option<u64>,
+}
+
+/// An airline livery traffic asked for that isn't converted
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WantedLivery {
+    pub model_name: String,
+    pub aircraft_type: String,
+    pub airline_code: String,
+    pub requests: u64,
+    pub sessions: u32,
+    pub last_used: u64,
+    /// The FSLTL source has this livery, so converting it would fill the gap
+    pub in_source: bool,
+}
+
+/// Usage of the converted FSLTL models
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelUsageReport {
+    /// When tracking started (Unix ms)
+    pub tracking_since: u64,
+    pub converted_models: usize,
+    pub used_models: usize,
+    pub total_bytes: u64,
+    /// Size of the models never used (what pruning would free)
+    pub unused_bytes: u64,
+    /// Converted models, never used first (largest first), then least used
+    pub models: Vec<ModelUsageEntry>,
+    /// Missing liveries, most wanted first
+    pub wanted: Vec<WantedLivery>,
+}
+
+fn unix_ms(time: SystemTime) -> u64 {
+    time.duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Join usage counts with the converted models
+fn build(
+    usage: &UsageFile,
+    converted: &[ScannedFSLTLModel],
+    in_source: impl Fn(&str) -> bool,
+) -> ModelUsageReport {
+    let mut models: Vec<ModelUsageEntry> = converted
+        .iter()
+        .map(|model| {
+            let count = usage.models.get(&model.model_name);
+            ModelUsageEntry {
+                model_name: model.model_name.clone(),
+                aircraft_type: model.aircraft_type.clone(),
+                airline_code: model.airline_code.clone(),
+                file_size: model.file_size,
+                requests: count.map_or(0, |c| c.requests),
+                sessions: count.map_or(0, |c| c.sessions),
+                last_used: count.map(|c| c.last_used),
+            }
+        })
+        .collect();
+    models.sort_by(|a, b| {
+        a.requests
+            .cmp(&b.requests)
+            .then(if a.requests == 0 { b.file_size.cmp(&a.file_size) } else { a.sessions.cmp(&b.sessions) })
+            .then(a.model_name.cmp(&b.model_name))
+    });
+
+    let converted_names: HashSet<&str> = converted.iter().map(|m| m.model_name.as_str()).collect();
+    let mut wanted: Vec<WantedLivery> = usage
+        .wanted
+        .iter()
+        .filter(|(name, _)| !converted_names.contains(name.as_str()))
+        .filter_map(|(name, count)| {
+            let (aircraft_type, airline_code) = name.strip_prefix("FSLTL_")?.split_once('_')?;
+            Some(WantedLivery {
+                model_name: name.clone(),
+                aircraft_type: aircraft_type.to_string(),
+                airline_code: airline_code.to_string(),
+                requests: count.requests,
+                sessions: count.sessions,
+                last_used: count.last_used,
+                in_source: in_source(name),
+            })
+        })
+        .collect();
+    wanted.sort_by(|a, b| {
+        b.sessions
+            .cmp(&a.sessions)
+            .then(b.requests.cmp(&a.requests))
+            .then(a.model_name.cmp(&b.model_name))
+    });
+
+    let unused = models.iter().filter(|m| m.requests == 0);
+    ModelUsageReport {
+        tracking_since: usage.since,
+        converted_models: models.len(),
+        used_models: models.len() - unused.clone().count(),
+        total_bytes: models.iter().map(|m| m.file_size).sum(),
+        unused_bytes: unused.map(|m| m.file_size).sum(),
+        models,
+        wanted,
+    }
+}
+
+fn usage_file(app: &AppHandle) -> std::result::Result<PathBuf, String> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    fs::create_dir_all(&app_data).map_err(|e| format!("Failed to create app data directory: {}", e))?;
+    Ok(app_data.join(USAGE_FILE))
+}
+
+fn read_usage(app: &AppHandle) -> UsageFile {
+    let mut usage: UsageFile = usage_file(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    if usage.since == 0 {
+        usage.since = unix_ms(SystemTime::now());
+    }
+    usage
+}
+
+/// Write the counts if they changed since the last save
+pub fn save(app: &AppHandle) {
+    let state = app.state::<ModelUsageState>();
+    if !state.dirty.swap(false, Ordering::Relaxed) {
+        return;
+    }
+    let content = serde_json::to_string(&state.log.lock().file);
+    let result = content
+        .map_err(|e| e.to_string())
+        .and_then(|content| fs::write(usage_file(app)?, content).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        warn!("[ModelUsage] Failed to save usage counts: {}", e);
+        state.dirty.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Usage report for the converted FSLTL models (scans the output folder)
+pub fn build_report(app: &AppHandle) -> std::result::Result<ModelUsageReport, String> {
+    let settings = crate::read_global_settings(app.clone())?.fsltl;
+    let output = match settings.output_path {
+        Some(output) => output,
+        None => crate::get_fsltl_default_output_path(app.clone())?.0,
+    };
+    let converted = fsltl_scan::scan(&output, |_| {})?;
+    let airplanes = settings
+        .source_path
+        .map(|source| Path::new(&source).join("SimObjects").join("Airplanes"));
+
+    let state = app.state::<ModelUsageState>();
+    let log = state.log.lock();
+    Ok(build(&log.file, &converted, |name| {
+        airplanes.as_ref().is_some_and(|dir| dir.join(name).is_dir())
+    }))
+}
+
+/// Forget all counts and start tracking again
+pub fn reset(app: &AppHandle) -> Result<()> {
+    let state = app.state::<ModelUsageState>();
+    *state.log.lock() = UsageLog {
+        file: UsageFile {
+            since: unix_ms(SystemTime::now()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    state.dirty.store(true, Ordering::Relaxed);
+    save(app);
+    if state.dirty.load(Ordering::Relaxed) {
+        return Err(Error::io("Failed to save model usage"));
+    }
+    Ok(())
+}
+
+/// Write changed counts periodically
+pub fn start_model_usage_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn("model-usage", Restart::Always, move || {
+        let app = app.clone();
+        async move {
+            loop {
+                tokio::time::sleep(SAVE_INTERVAL).await;
+                save(&app);
+                debug_stats::task_ran("model-usage", SAVE_INTERVAL, None);
+            }
+        }
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Usage of the converted FSLTL models: never-used models to prune and missing liveries to convert
+#[tauri::command]
+pub async fn get_model_usage_report(app: AppHandle) -> Result<ModelUsageReport> {
+    tauri::async_runtime::spawn_blocking(move || build_report(&app))
+        .await
+        .map_err(|e| Error::Internal(format!("Background task failed: {}", e)))?
+        .map_err(Error::from)
+}
+
+/// Forget all model usage counts
+#[tauri::command]
+pub fn reset_model_usage(app: AppHandle) -> Result<()> {
+    reset(&app)
+}
+
+/// Initialize model usage tracking for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_model_usage(app: &AppHandle) {
+    app.manage(ModelUsageState::new(read_usage(app)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matching::MatchedModel;
+
+    fn result(type_code: &str, airline: Option<&str>, model: Option<(&str, Option<&str>)>) -> MatchResult {
+        MatchResult {
+            type_code: Some(type_code.to_string()),
+            airline_code: airline.map(str::to_string),
+            is_ga: airline.is_none(),
+            model: model.map(|(name, livery)| MatchedModel {
+                source: "fsltl".to_string(),
+                match_type: "exact".to_string(),
+                model_name: name.to_string(),
+                aircraft_type: type_code.to_string(),
+                airline_code: livery.map(str::to_string),
+                model_path: String::new(),
+                url: String::new(),
+                has_animations: false,
+                manifest: None,
+                rule: None,
+            }),
+            similar_type: None,
+            trace: Vec::new(),
+        }
+    }
+
+    fn converted(name: &str, airline: Option<&str>, file_size: u64) -> ScannedFSLTLModel {
+        ScannedFSLTLModel {
+            model_name: name.to_string(),
+            model_path: String::new(),
+            relative_path: String::new(),
+            aircraft_type: "B738".to_string(),
+            airline_code: airline.map(str::to_string),
+            has_animations: false,
+            file_size,
+        }
+    }
+
+    #[test]
+    fn counts_requests_and_sessions() {
+        let mut log = UsageLog::default();
+        let aal = result("B738", Some("AAL"), Some(("FSLTL_B738_AAL", Some("AAL"))));
+        assert!(log.record(&aal, 1000));
+        assert!(log.record(&aal, 2000));
+
+        // Next app run
+        log.session_models.clear();
+        log.record(&aal, 3000);
+
+        let count = &log.file.models["FSLTL_B738_AAL"];
+        assert_eq!((count.requests, count.sessions, count.first_used, count.last_used), (3, 2, 1000, 3000));
+        assert!(log.file.wanted.is_empty());
+    }
+
+    #[test]
+    fn tracks_missing_liveries() {
+        let mut log = UsageLog::default();
+        // Base livery handed out for an airline without a converted livery
+        log.record(&result("B738", Some("SWA"), Some(("FSLTL_B738_ZZZZ", None))), 1000);
+        // No model at all
+        log.record(&result("E75L", Some("SKW"), None), 1000);
+        // GA traffic doesn't need a livery
+        assert!(!log.record(&result("C172", None, None), 1000));
+
+        assert_eq!(log.file.models["FSLTL_B738_ZZZZ"].requests, 1);
+        let wanted: Vec<&str> = log.file.wanted.keys().map(String::as_str).collect();
+        assert_eq!(wanted, vec!["FSLTL_B738_SWA", "FSLTL_E75L_SKW"]);
+    }
+
+    #[test]
+    fn lists_unused_models_and_wanted_liveries() {
+        let mut log = UsageLog::default();
+        log.record(&result("B738", Some("AAL"), Some(("FSLTL_B738_AAL", Some("AAL")))), 1000);
+        log.record(&result("B738", Some("SWA"), Some(("FSLTL_B738_ZZZZ", None))), 1000);
+        log.record(&result("B738", Some("SWA"), Some(("FSLTL_B738_ZZZZ", None))), 2000);
+        log.record(&result("B738", Some("RYR"), Some(("FSLTL_B738_ZZZZ", None))), 2000);
+        // Converted since it was wanted
+        log.file.wanted.insert("FSLTL_B738_DAL".to_string(), UsageCount::default());
+
+        let models = [
+            converted("FSLTL_B738_AAL", Some("AAL"), 30),
+            converted("FSLTL_B738_DAL", Some("DAL"), 20),
+            converted("FSLTL_B738_KLM", Some("KLM"), 40),
+            converted("FSLTL_B738_ZZZZ", None, 10),
+        ];
+        let report = build(&log.file, &models, |name| name == "FSLTL_B738_RYR");
+
+        assert_eq!((report.converted_models, report.used_models), (4, 2));
+        assert_eq!((report.total_bytes, report.unused_bytes), (100, 60));
+        let order: Vec<&str> = report.models.iter().map(|m| m.model_name.as_str()).collect();
+        assert_eq!(order, vec!["FSLTL_B738_KLM", "FSLTL_B738_DAL", "FSLTL_B738_AAL", "FSLTL_B738_ZZZZ"]);
+        assert_eq!(report.models[0].last_used, None);
+
+        let wanted: Vec<(&str, u64, bool)> = report
+            .wanted
+            .iter()
+            .map(|w| (w.airline_code.as_str(), w.requests, w.in_source))
+            .collect();
+        assert_eq!(wanted, vec![("SWA", 2, false), ("RYR", 1, true)]);
+    }
+}
@@ -0,0 +1,530 @@
+//! Mod registry with rich metadata
+//!
+//! Builds a single index of every installed aircraft and tower mod (manifest
+//! metadata, file sizes, enabled state and load order) so the desktop app and
+//! remote browsers get the complete picture in one call instead of listing
+//! directories and fetching manifests one by one.
+//!
+//! The index is cached and rebuilt only when a mod directory, manifest or the
+//! persisted mod state changes (detected via modification times). A watcher on
+//! the mods folder also drops the registry, parsed VMR files and the model
+//! matcher as soon as anything changes, reloads scripts when one changed, and
+//! notifies the frontend.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tracing::{debug, info, warn};
+
+use crate::error::{Error, Result};
+use crate::matching::ModelMatcherState;
+use crate::mod_variants;
+use crate::scripts;
+use crate::startup;
+use crate::vmr::VmrCacheState;
+use crate::{find_mods_root, normalize_path_string};
+
+/// Mod types that are indexed by the registry
+pub const MOD_TYPES: [&str; 2] = ["aircraft", "towers"];
+
+/// A single file inside a mod folder
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModFileInfo {
+    /// Path relative to the mod folder (forward slashes)
+    pub name: String,
+    pub size: u64,
+}
+
+/// Registry entry describing one installed mod
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModRegistryEntry {
+    /// Stable identifier: "{modType}/{folder}" (e.g., "aircraft/B738")
+    pub id: String,
+    /// Folder name
+    pub name: String,
+    /// "aircraft" or "towers"
+    pub mod_type: String,
+    pub path: String,
+    pub display_name: Option<String>,
+    pub version: Option<String>,
+    pub author: Option<String>,
+    pub description: Option<String>,
+    /// ICAO codes the mod applies to (airports for towers, type designators for aircraft)
+    pub supported_icaos: Vec<String>,
+    pub files: Vec<ModFileInfo>,
+    pub total_size: u64,
+    pub enabled: bool,
+    /// Position in the load order (0 = loaded first), per mod type
+    pub load_order: usize,
+    pub manifest: Option<serde_json::Value>,
+    /// Manifest variant active right now (see mod_variants.rs)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_variant: Option<String>,
+    /// Manifest parse error, if the manifest exists but is invalid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Complete mod registry returned to clients
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModRegistry {
+    pub mods: Vec<ModRegistryEntry>,
+    /// Unix timestamp (ms) when the index was built
+    pub indexed_at: u64,
+}
+
+/// Persisted enabled state and load order (mod-state.json in app data)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModStateFile {
+    /// Mod ids that are disabled
+    #[serde(default)]
+    pub disabled: Vec<String>,
+    /// Explicit load order (mod ids); mods not listed load afterwards alphabetically
+    #[serde(default)]
+    pub load_order: Vec<String>,
+}
+
+/// Cached registry with the fingerprint it was built from
+struct CachedRegistry {
+    fingerprint: Vec<(PathBuf, Option<SystemTime>)>,
+    registry: ModRegistry,
+}
+
+/// Mod registry state managed by Tauri
+pub struct ModRegistryState {
+    cache: RwLock<Option<CachedRegistry>>,
+    /// Mods folder watcher (kept alive for the lifetime of the app)
+    watcher: Mutex<Option<Debouncer<RecommendedWatcher>>>,
+}
+
+impl ModRegistryState {
+    pub fn new() -> Self {
+        Self {
+            cache: RwLock::new(None),
+            watcher: Mutex::new(None),
+        }
+    }
+
+    /// Drop the cached index so the next request rebuilds it
+    pub fn invalidate(&self) {
+        *self.cache.write() = None;
+    }
+}
+
+impl Default for ModRegistryState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Get the path to the persisted mod state file
+fn get_mod_state_file(app: &AppHandle) -> Result<PathBuf> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))?;
+
+    fs::create_dir_all(&app_data)
+        .map_err(|e| Error::Io(format!("Failed to create app data directory: {}", e)))?;
+
+    Ok(app_data.join("mod-state.json"))
+}
+
+/// Read the persisted mod state (defaults if missing or invalid)
+pub fn read_mod_state(app: &AppHandle) -> ModStateFile {
+    get_mod_state_file(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Write the persisted mod state
+fn write_mod_state(app: &AppHandle, mod_state: &ModStateFile) -> Result<()> {
+    let path = get_mod_state_file(app)?;
+    let content = serde_json::to_string_pretty(mod_state)
+        .map_err(|e| Error::Internal(format!("Failed to serialize mod state: {}", e)))?;
+    fs::write(&path, content).map_err(|e| Error::Io(format!("Failed to write mod state: {}", e)))
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// List mod folders of a given type (unsorted)
+fn list_mod_folders(mods_root: &Path, mod_type: &str) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(mods_root.join(mod_type)) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| Some((e.file_name().into_string().ok()?, e.path())))
+        .collect()
+}
+
+/// Compute a cheap fingerprint of everything the index depends on
+fn compute_fingerprint(mods_root: &Path, state_file: Option<&Path>) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let mut fingerprint = Vec::new();
+
+    if let Some(state_file) = state_file {
+        fingerprint.push((state_file.to_path_buf(), modified_time(state_file)));
+    }
+
+    for mod_type in MOD_TYPES {
+        let type_dir = mods_root.join(mod_type);
+        fingerprint.push((type_dir.clone(), modified_time(&type_dir)));
+
+        for (_, path) in list_mod_folders(mods_root, mod_type) {
+            let manifest = path.join("manifest.json");
+            fingerprint.push((path.clone(), modified_time(&path)));
+            fingerprint.push((manifest.clone(), modified_time(&manifest)));
+        }
+    }
+
+    fingerprint.sort_by(|a, b| a.0.cmp(&b.0));
+    fingerprint
+}
+
+/// Recursively collect files in a mod folder
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<ModFileInfo>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(root, &path, files);
+        } else if let Ok(meta) = entry.metadata() {
+            let relative = path
+                .strip_prefix(root)
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_default();
+            files.push(ModFileInfo {
+                name: relative,
+                size: meta.len(),
+            });
+        }
+    }
+}
+
+/// Extract a string field from a manifest
+fn manifest_str(manifest: &serde_json::Value, key: &str) -> Option<String> {
+    manifest.get(key).and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+/// Extract the ICAO codes a mod applies to from its manifest
+fn manifest_icaos(manifest: &serde_json::Value, mod_type: &str) -> Vec<String> {
+    let key = if mod_type == "towers" { "airports" } else { "aircraftTypes" };
+    manifest
+        .get(key)
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str())
+                .map(|s| s.to_uppercase())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Build a registry entry for a single mod folder
+fn build_entry(mod_type: &str, name: String, path: &Path, mod_state: &ModStateFile) -> ModRegistryEntry {
+    let id = format!("{}/{}", mod_type, name);
+
+    let manifest_path = path.join("manifest.json");
+    let (manifest, error) = if manifest_path.exists() {
+        match fs::read_to_string(&manifest_path)
+            .map_err(|e| format!("Failed to read manifest: {}", e))
+            .and_then(|c| {
+                serde_json::from_str::<serde_json::Value>(&c)
+                    .map_err(|e| format!("Failed to parse manifest JSON: {}", e))
+            }) {
+            Ok(value) => (Some(value), None),
+            Err(e) => (None, Some(e)),
+        }
+    } else {
+        (None, Some("manifest.json not found".to_string()))
+    };
+
+    let mut files = Vec::new();
+    collect_files(path, path, &mut files);
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    let total_size = files.iter().map(|f| f.size).sum();
+
+    ModRegistryEntry {
+        enabled: !mod_state.disabled.contains(&id),
+        id,
+        name,
+        mod_type: mod_type.to_string(),
+        path: normalize_path_string(&path.to_path_buf()),
+        display_name: manifest.as_ref().and_then(|m| manifest_str(m, "name")),
+        version: manifest.as_ref().and_then(|m| manifest_str(m, "version")),
+        author: manifest.as_ref().and_then(|m| manifest_str(m, "author")),
+        description: manifest.as_ref().and_then(|m| manifest_str(m, "description")),
+        supported_icaos: manifest
+            .as_ref()
+            .map(|m| manifest_icaos(m, mod_type))
+            .unwrap_or_default(),
+        files,
+        total_size,
+        load_order: 0,
+        manifest,
+        active_variant: None,
+        error,
+    }
+}
+
+/// Build the full registry by scanning the mods folder
+fn build_registry(mods_root: &Path, mod_state: &ModStateFile) -> ModRegistry {
+    let order_index: HashMap<&str, usize> = mod_state
+        .load_order
+        .iter()
+        .enumerate()
+        .map(|(i, id)| (id.as_str(), i))
+        .collect();
+
+    let mut mods = Vec::new();
+    for mod_type in MOD_TYPES {
+        let mut entries: Vec<ModRegistryEntry> = list_mod_folders(mods_root, mod_type)
+            .into_iter()
+            .map(|(name, path)| build_entry(mod_type, name, &path, mod_state))
+            .collect();
+
+        // Explicitly ordered mods first, then the rest alphabetically
+        entries.sort_by(|a, b| {
+            let a_key = order_index.get(a.id.as_str()).copied().unwrap_or(usize::MAX);
+            let b_key = order_index.get(b.id.as_str()).copied().unwrap_or(usize::MAX);
+            a_key.cmp(&b_key).then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+
+        for (i, entry) in entries.iter_mut().enumerate() {
+            entry.load_order = i;
+        }
+        mods.extend(entries);
+    }
+
+    let indexed_at = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+
+    ModRegistry { mods, indexed_at }
+}
+
+/// Get the mod registry, rebuilding the cached index if anything changed
+pub fn get_registry(app: &AppHandle) -> ModRegistry {
+    let mods_root = find_mods_root(app);
+    let state_file = get_mod_state_file(app).ok();
+    let fingerprint = compute_fingerprint(&mods_root, state_file.as_deref());
+
+    let registry_state = app.state::<ModRegistryState>();
+    if let Some(cached) = registry_state.cache.read().as_ref() {
+        if cached.fingerprint == fingerprint {
+            return cached.registry.clone();
+        }
+    }
+
+    let started = Instant::now();
+    let registry = build_registry(&mods_root, &read_mod_state(app));
+    info!("[Mods] Indexed {} mods", registry.mods.len());
+    startup::record_first("Mod index (first use)", started);
+
+    *registry_state.cache.write() = Some(CachedRegistry {
+        fingerprint,
+        registry: registry.clone(),
+    });
+
+    registry
+}
+
+/// Get the mod registry with the currently active manifest variants filled in
+pub fn get_registry_with_variants(app: &AppHandle) -> ModRegistry {
+    let mut registry = get_registry(app);
+    mod_variants::annotate(app, &mut registry.mods);
+    registry
+}
+
+/// Enabled mods of a given type, in load order, with their active variants
+pub fn enabled_mods(app: &AppHandle, mod_type: &str) -> Vec<ModRegistryEntry> {
+    let mut mods: Vec<ModRegistryEntry> = get_registry(app)
+        .mods
+        .into_iter()
+        .filter(|m| m.mod_type == mod_type && m.enabled)
+        .collect();
+    mod_variants::annotate(app, &mut mods);
+    mods
+}
+
+/// Enable or disable a mod by id, persisting the change
+pub fn set_enabled(app: &AppHandle, id: &str, enabled: bool) -> Result<()> {
+    let mut mod_state = read_mod_state(app);
+    mod_state.disabled.retain(|d| d != id);
+    if !enabled {
+        mod_state.disabled.push(id.to_string());
+    }
+    write_mod_state(app, &mod_state)?;
+    app.state::<ModRegistryState>().invalidate();
+
+    info!("[Mods] {} {}", if enabled { "Enabled" } else { "Disabled" }, id);
+    Ok(())
+}
+
+/// Replace the explicit load order, persisting the change
+pub fn set_load_order(app: &AppHandle, order: Vec<String>) -> Result<()> {
+    let mut mod_state = read_mod_state(app);
+    mod_state.load_order = order;
+    write_mod_state(app, &mod_state)?;
+    app.state::<ModRegistryState>().invalidate();
+    Ok(())
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the full mod registry (all mod types, including disabled mods)
+#[tauri::command]
+pub fn get_mod_registry(app: AppHandle) -> ModRegistry {
+    get_registry_with_variants(&app)
+}
+
+/// Force the mod registry to be rebuilt on next access
+#[tauri::command]
+pub fn refresh_mod_registry(app: AppHandle, state: State<'_, ModRegistryState>) -> ModRegistry {
+    state.invalidate();
+    get_registry_with_variants(&app)
+}
+
+/// Enable or disable a mod by id (e.g., "aircraft/B738")
+#[tauri::command]
+pub fn set_mod_enabled(app: AppHandle, id: String, enabled: bool) -> Result<()> {
+    set_enabled(&app, &id, enabled)
+}
+
+/// Set the explicit mod load order (list of mod ids)
+#[tauri::command]
+pub fn set_mod_load_order(app: AppHandle, order: Vec<String>) -> Result<()> {
+    set_load_order(&app, order)
+}
+
+/// Initialize mod registry state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_mod_registry(app: &AppHandle) {
+    app.manage(ModRegistryState::new());
+}
+
+/// Watch the mods folder and drop cached indexes whenever something in it changes.
+/// Call this in the Tauri setup closure after the mod, VMR and matcher state is managed.
+pub fn start_mods_watcher(app: &AppHandle) {
+    let mods_root = find_mods_root(app);
+    if !mods_root.exists() {
+        info!("[Mods] Mods folder not found, not watching: {:?}", mods_root);
+        return;
+    }
+
+    let handle = app.clone();
+    let debouncer = new_debouncer(Duration::from_millis(500), move |result: DebounceEventResult| match result {
+        Ok(events) => {
+            handle.state::<ModRegistryState>().invalidate();
+            handle.state::<VmrCacheState>().invalidate();
+            handle.state::<ModelMatcherState>().invalidate();
+
+            // Scripts write their output under mods/scripts/ too; only reload on script changes
+            let scripts_root = scripts::scripts_root(&handle);
+            if events
+                .iter()
+                .any(|e| e.path.starts_with(&scripts_root) && e.path.extension().is_some_and(|ext| ext == "rhai"))
+            {
+                scripts::reload(&handle);
+            }
+
+            let paths: Vec<String> = events.iter().map(|e| normalize_path_string(&e.path)).collect();
+            debug!("[Mods] {} change(s) detected, caches invalidated", paths.len());
+            let _ = handle.emit("mods-changed", &paths);
+        }
+        Err(e) => warn!("[Mods] Watcher error: {}", e),
+    });
+
+    let mut debouncer = match debouncer {
+        Ok(debouncer) => debouncer,
+        Err(e) => {
+            warn!("[Mods] Failed to create mods watcher: {}", e);
+            return;
+        }
+    };
+    if let Err(e) = debouncer.watcher().watch(&mods_root, RecursiveMode::Recursive) {
+        warn!("[Mods] Failed to watch {:?}: {}", mods_root, e);
+        return;
+    }
+
+    info!("[Mods] Watching {:?} for changes", mods_root);
+    *app.state::<ModRegistryState>().watcher.lock() = Some(debouncer);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexes_mods_in_load_order() {
+        let root = std::env::temp_dir().join(format!("mods-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for folder in ["aircraft/B738/textures", "aircraft/a320", "aircraft/E175", "towers/KBOS"] {
+            fs::create_dir_all(root.join(folder)).unwrap();
+        }
+        fs::write(
+            root.join("aircraft/B738/manifest.json"),
+            r#"{"name": "Boeing 737-800", "version": "1.2", "aircraftTypes": ["b738", "B38M"]}"#,
+        )
+        .unwrap();
+        fs::write(root.join("aircraft/B738/model.glb"), "glb").unwrap();
+        fs::write(root.join("aircraft/B738/textures/livery.png"), "png").unwrap();
+        fs::write(root.join("aircraft/a320/manifest.json"), "{ not json").unwrap();
+        fs::write(root.join("towers/KBOS/manifest.json"), r#"{"airports": ["kbos"]}"#).unwrap();
+
+        let mod_state = ModStateFile {
+            disabled: vec!["aircraft/a320".to_string()],
+            load_order: vec!["aircraft/E175".to_string()],
+        };
+        let registry = build_registry(&root, &mod_state);
+        let ids: Vec<&str> = registry.mods.iter().map(|m| m.id.as_str()).collect();
+        // Explicit order first, then the rest alphabetically (case-insensitive), per type
+        assert_eq!(ids, ["aircraft/E175", "aircraft/a320", "aircraft/B738", "towers/KBOS"]);
+        assert_eq!(registry.mods.iter().map(|m| m.load_order).collect::<Vec<_>>(), [0, 1, 2, 0]);
+
+        let b738 = &registry.mods[2];
+        assert!(b738.enabled && b738.error.is_none());
+        assert_eq!(b738.display_name.as_deref(), Some("Boeing 737-800"));
+        assert_eq!(b738.supported_icaos, ["B738", "B38M"]);
+        let files: Vec<&str> = b738.files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(files, ["manifest.json", "model.glb", "textures/livery.png"]);
+        assert_eq!(b738.total_size, b738.files.iter().map(|f| f.size).sum::<u64>());
+
+        let a320 = &registry.mods[1];
+        assert!(!a320.enabled);
+        assert!(a320.error.as_deref().unwrap().starts_with("Failed to parse manifest JSON"));
+        assert_eq!(registry.mods[0].error.as_deref(), Some("manifest.json not found"));
+        assert_eq!(registry.mods[3].supported_icaos, ["KBOS"]);
+
+        // Adding a manifest changes the fingerprint, so the cached index is rebuilt
+        let before = compute_fingerprint(&root, None);
+        fs::write(root.join("aircraft/E175/manifest.json"), "{}").unwrap();
+        assert_ne!(compute_fingerprint(&root, None), before);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
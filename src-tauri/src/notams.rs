@@ -0,0 +1,431 @@
+//! NOTAM fetch and parsing
+//!
+//! Fetches NOTAMs for an airport from the FAA NOTAM API when API credentials
+//! are configured (global settings, `airports.notamClientId`/`notamClientSecret`),
+//! falling back to the public FAA NOTAM Search service. Runway and taxiway
+//! closures are parsed out of the NOTAM text so closed runways can be drawn
+//! crosshatched; everything else is passed through as text.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tracing::warn;
+
+use crate::error::{Error, Result};
+use crate::http_client;
+use crate::weather;
+
+const FAA_NOTAM_API_URL: &str = "https://external-api.faa.gov/notamapi/v1/notams";
+const NOTAM_SEARCH_URL: &str = "https://notams.aim.faa.gov/notamSearch/search";
+
+/// How long fetched NOTAMs are served from cache
+const NOTAM_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Words that end a taxiway list ("TWY A, B BTN ...", "TWY K CLSD")
+const TAXIWAY_LIST_END: &[&str] = &[
+    "BTN", "CLSD", "FM", "FROM", "AT", "EXC", "WEST", "EAST", "NORTH", "SOUTH",
+];
+
+/// Parsed NOTAM
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Notam {
+    /// NOTAM number (e.g., "01/234")
+    pub number: String,
+    /// NOTAM text (e.g., "RWY 4R/22L CLSD")
+    pub text: String,
+    /// "runwayClosure", "taxiwayClosure", "runway", "taxiway", "apron", "navaid" or "other"
+    pub kind: String,
+    /// Runway ends the NOTAM applies to (e.g., ["04R", "22L"])
+    pub runways: Vec<String>,
+    /// Taxiways the NOTAM applies to
+    pub taxiways: Vec<String>,
+    /// Effective from (Unix ms)
+    pub starts_at: Option<u64>,
+    /// Effective until (Unix ms; None = permanent or unknown)
+    pub ends_at: Option<u64>,
+    /// End time is an estimate
+    pub ends_estimated: bool,
+}
+
+impl Notam {
+    /// Whether the NOTAM is in effect at a time (Unix ms)
+    pub fn active_at(&self, time: u64) -> bool {
+        self.starts_at.map_or(true, |s| s <= time) && self.ends_at.map_or(true, |e| time < e)
+    }
+}
+
+/// NOTAMs for an airport
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AirportNotams {
+    pub icao: String,
+    /// "faa" (NOTAM API) or "notamSearch"
+    pub source: String,
+    /// Fetch time (Unix ms)
+    pub fetched_at: u64,
+    pub notams: Vec<Notam>,
+    /// Runway ends closed right now
+    pub closed_runways: Vec<String>,
+    /// Taxiways closed right now
+    pub closed_taxiways: Vec<String>,
+}
+
+/// FAA NOTAM API response (only the fields we use)
+#[derive(Deserialize)]
+struct FaaResponse {
+    #[serde(default)]
+    items: Vec<FaaItem>,
+}
+
+#[derive(Deserialize)]
+struct FaaItem {
+    properties: FaaProperties,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FaaProperties {
+    core_notam_data: FaaCoreData,
+}
+
+#[derive(Deserialize)]
+struct FaaCoreData {
+    notam: FaaNotam,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FaaNotam {
+    #[serde(default)]
+    number: String,
+    #[serde(default)]
+    text: String,
+    effective_start: Option<String>,
+    effective_end: Option<String>,
+}
+
+/// FAA NOTAM Search response (only the fields we use)
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchResponse {
+    #[serde(default)]
+    notam_list: Vec<SearchNotam>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchNotam {
+    #[serde(default)]
+    notam_number: String,
+    #[serde(default)]
+    traditional_message_from4th_word: String,
+    start_date: Option<String>,
+    end_date: Option<String>,
+}
+
+/// Cached fetch result
+struct CachedNotams {
+    notams: AirportNotams,
+    fetched_at: Instant,
+}
+
+/// NOTAM cache managed by Tauri
+pub struct NotamState {
+    notams: RwLock<HashMap<String, CachedNotams>>,
+}
+
+impl NotamState {
+    pub fn new() -> Self {
+        Self {
+            notams: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for NotamState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_ms() -> u64 {
+    Utc::now().timestamp_millis().max(0) as u64
+}
+
+/// Parse a NOTAM time ("2024-01-15T12:00:00.000Z" or "01/15/2024 1200"), with
+/// whether it is an estimate ("EST" suffix). "PERM" and unparseable times are None.
+fn parse_time(value: &str) -> (Option<u64>, bool) {
+    let value = value.trim();
+    let estimated = value.ends_with("EST");
+    let value = value.trim_end_matches("EST").trim();
+    let time = DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&Utc))
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%m/%d/%Y %H%M").map(|t| t.and_utc()))
+        .ok()
+        .map(|t| t.timestamp_millis().max(0) as u64);
+    (time, estimated)
+}
+
+/// Normalize a runway end designator ("4R" -> "04R")
+fn runway_end(ident: &str) -> Option<String> {
+    let digits: String = ident.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let suffix = &ident[digits.len()..];
+    let number: u8 = digits.parse().ok()?;
+    if !(1..=36).contains(&number) || !matches!(suffix, "" | "L" | "R" | "C") {
+        return None;
+    }
+    Some(format!("{:02}{}", number, suffix))
+}
+
+/// Whether a token is a taxiway designator (e.g., "A", "K1", "AA")
+fn is_taxiway(token: &str) -> bool {
+    (1..=4).contains(&token.len())
+        && token.starts_with(|c: char| c.is_ascii_alphabetic())
+        && token.chars().all(|c| c.is_ascii_alphanumeric())
+        && !TAXIWAY_LIST_END.contains(&token)
+        && !matches!(token, "AND" | "TWY" | "RWY")
+}
+
+/// Parse the text of a NOTAM
+pub fn parse_notam(number: &str, text: &str, start: Option<&str>, end: Option<&str>) -> Notam {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let upper = text.to_uppercase();
+    let tokens: Vec<&str> = upper.split(' ').collect();
+    let closed = tokens.contains(&"CLSD");
+
+    // The subject is the first keyword; later RWY/TWY mentions only bound it
+    let subject = tokens.first().copied().unwrap_or_default();
+    let mut runways = Vec::new();
+    let mut taxiways = Vec::new();
+    if let Some(i) = tokens.iter().position(|t| *t == "RWY") {
+        if let Some(designator) = tokens.get(i + 1) {
+            runways = designator.split('/').filter_map(runway_end).collect();
+        }
+    }
+    if subject == "TWY" {
+        for token in &tokens[1..] {
+            let token = token.trim_end_matches(',');
+            if !is_taxiway(token) {
+                if token == "AND" || token == "TWY" {
+                    continue;
+                }
+                break;
+            }
+            taxiways.push(token.to_string());
+        }
+    }
+
+    let kind = match (subject, closed) {
+        ("RWY", true) => "runwayClosure",
+        ("RWY", false) => "runway",
+        ("TWY", true) => "taxiwayClosure",
+        ("TWY", false) => "taxiway",
+        ("APRON", _) => "apron",
+        ("NAV", _) => "navaid",
+        _ => "other",
+    };
+    let (starts_at, _) = start.map(parse_time).unwrap_or_default();
+    let (ends_at, ends_estimated) = end.map(parse_time).unwrap_or_default();
+
+    Notam {
+        number: number.to_string(),
+        text,
+        kind: kind.to_string(),
+        runways,
+        taxiways,
+        starts_at,
+        ends_at,
+        ends_estimated,
+    }
+}
+
+async fn fetch_faa(icao: &str, client_id: &str, client_secret: &str) -> Result<Vec<Notam>> {
+    let response = http_client::client()
+        .get(FAA_NOTAM_API_URL)
+        .header("client_id", client_id)
+        .header("client_secret", client_secret)
+        .query(&[("icaoLocation", icao), ("pageSize", "1000")])
+        .timeout(Duration::from_secs(30))
+        .send()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to fetch NOTAMs: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(Error::Upstream(format!("FAA NOTAM API error: {}", response.status())));
+    }
+    let body: FaaResponse = response
+        .json()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to parse NOTAM response: {}", e)))?;
+    Ok(body
+        .items
+        .into_iter()
+        .map(|item| {
+            let n = item.properties.core_notam_data.notam;
+            parse_notam(
+                &n.number,
+                &n.text,
+                n.effective_start.as_deref(),
+                n.effective_end.as_deref(),
+            )
+        })
+        .collect())
+}
+
+async fn fetch_search(icao: &str) -> Result<Vec<Notam>> {
+    let response = http_client::client()
+        .post(NOTAM_SEARCH_URL)
+        .form(&[("searchType", "0"), ("designatorsForLocation", icao), ("offset", "0")])
+        .timeout(Duration::from_secs(30))
+        .send()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to fetch NOTAMs: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(Error::Upstream(format!("NOTAM Search error: {}", response.status())));
+    }
+    let body: SearchResponse = response
+        .json()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to parse NOTAM Search response: {}", e)))?;
+    Ok(body
+        .notam_list
+        .into_iter()
+        .map(|n| {
+            parse_notam(
+                &n.notam_number,
+                n.traditional_message_from4th_word.trim_end_matches('.'),
+                n.start_date.as_deref(),
+                n.end_date.as_deref(),
+            )
+        })
+        .collect())
+}
+
+/// Fetch NOTAMs from the FAA API if configured, else (or on failure) from NOTAM Search
+async fn fetch_notams(app: &AppHandle, icao: &str) -> Result<AirportNotams> {
+    let settings = crate::read_global_settings(app.clone())?.airports;
+    let (client_id, client_secret) = (settings.notam_client_id.trim(), settings.notam_client_secret.trim());
+
+    let mut fetched = None;
+    if !client_id.is_empty() && !client_secret.is_empty() {
+        match fetch_faa(icao, client_id, client_secret).await {
+            Ok(notams) => fetched = Some((notams, "faa")),
+            Err(e) => warn!("[Notams] {}: {}, falling back to NOTAM Search", icao, e),
+        }
+    }
+    let (mut notams, source) = match fetched {
+        Some(fetched) => fetched,
+        None => (fetch_search(icao).await?, "notamSearch"),
+    };
+    notams.sort_by(|a, b| a.number.cmp(&b.number));
+
+    let now = now_ms();
+    let mut closed_runways: Vec<String> = Vec::new();
+    let mut closed_taxiways: Vec<String> = Vec::new();
+    for notam in notams.iter().filter(|n| n.active_at(now)) {
+        match notam.kind.as_str() {
+            "runwayClosure" => closed_runways.extend(notam.runways.iter().cloned()),
+            "taxiwayClosure" => closed_taxiways.extend(notam.taxiways.iter().cloned()),
+            _ => {}
+        }
+    }
+    for list in [&mut closed_runways, &mut closed_taxiways] {
+        list.sort();
+        list.dedup();
+    }
+
+    Ok(AirportNotams {
+        icao: icao.to_string(),
+        source: source.to_string(),
+        fetched_at: now,
+        notams,
+        closed_runways,
+        closed_taxiways,
+    })
+}
+
+/// Get NOTAMs for an airport, from cache if fresh
+pub async fn get_notams(app: &AppHandle, icao: &str, force: bool) -> Result<AirportNotams> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    let state = app.state::<NotamState>();
+
+    let previous = {
+        let notams = state.notams.read();
+        match notams.get(&icao) {
+            Some(cached) if !force && cached.fetched_at.elapsed() < NOTAM_TTL => return Ok(cached.notams.clone()),
+            Some(cached) => Some(cached.notams.clone()),
+            None => None,
+        }
+    };
+
+    let notams = match fetch_notams(app, &icao).await {
+        Ok(notams) => notams,
+        Err(e) => {
+            warn!("[Notams] {}: {}", icao, e);
+            return previous.ok_or(e);
+        }
+    };
+    state.notams.write().insert(
+        icao,
+        CachedNotams {
+            notams: notams.clone(),
+            fetched_at: Instant::now(),
+        },
+    );
+    Ok(notams)
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get parsed NOTAMs for an airport, with the runways and taxiways closed now
+#[tauri::command]
+pub async fn get_airport_notams(app: AppHandle, icao: String, force: Option<bool>) -> Result<AirportNotams> {
+    get_notams(&app, &icao, force.unwrap_or(false)).await
+}
+
+/// Initialize NOTAM cache for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_notams(app: &AppHandle) {
+    app.manage(NotamState::new());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_closures() {
+        let rwy = parse_notam(
+            "01/234",
+            "RWY 4R/22L CLSD",
+            Some("2024-01-15T12:00:00.000Z"),
+            Some("01/16/2024 1200EST"),
+        );
+        assert_eq!(rwy.kind, "runwayClosure");
+        assert_eq!(rwy.runways, vec!["04R", "22L"]);
+        assert_eq!(rwy.starts_at, Some(1_705_320_000_000));
+        assert_eq!(rwy.ends_at, Some(1_705_406_400_000));
+        assert!(rwy.ends_estimated);
+        assert!(rwy.active_at(1_705_320_000_000) && !rwy.active_at(1_705_406_400_000));
+
+        let twy = parse_notam(
+            "01/235",
+            "TWY A, B AND C BTN TWY K AND RWY 9/27 CLSD",
+            None,
+            Some("PERM"),
+        );
+        assert_eq!(twy.kind, "taxiwayClosure");
+        assert_eq!(twy.taxiways, vec!["A", "B", "C"]);
+        assert_eq!(twy.runways, vec!["09", "27"]);
+        assert_eq!(twy.ends_at, None);
+
+        let other = parse_notam("01/236", "NAV ILS RWY 33L U/S", None, None);
+        assert_eq!(other.kind, "navaid");
+    }
+}
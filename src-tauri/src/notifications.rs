@@ -0,0 +1,17 @@
+//! Desktop notifications
+//!
+//! Shows OS-level notifications on the host through the Tauri notification
+//! plugin, for background watchers (controller coverage, weather and ATIS
+//! changes) that should get the user's attention even when the app window
+//! isn't in front.
+
+use tauri::AppHandle;
+use tauri_plugin_notification::NotificationExt;
+use tracing::warn;
+
+/// Show a desktop notification (failures are logged, not returned)
+pub fn show(app: &AppHandle, title: &str, body: &str) {
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        warn!("[Notifications] Failed to show \"{}\": {}", title, e);
+    }
+}
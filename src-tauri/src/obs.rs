@@ -0,0 +1,50 @@
+//! OBS browser source overlays
+//!
+//! Small self-contained pages for streamers to composite tower data over their
+//! feed as OBS browser sources, served by the remote access server at
+//! `/obs/<page>`:
+//!
+//! - `datablocks`: a strip of datablocks for the traffic around the airport
+//! - `arrivals`: the arrival board (arrival sequence per runway)
+//! - `metar`: a scrolling METAR ticker
+//!
+//! The pages have a transparent background and read the same API endpoints and
+//! WebSockets as remote browsers (/api/traffic, /api/arrivals/ws,
+//! /api/weather). They are embedded in the binary so they work without the
+//! frontend build. Options are URL query parameters (see obs/overlay.js).
+
+/// An embedded overlay file
+pub struct ObsAsset {
+    pub content: &'static str,
+    pub content_type: &'static str,
+}
+
+/// Look up an overlay page (by name, without extension) or shared asset
+pub fn asset(name: &str) -> Option<ObsAsset> {
+    let (content, content_type) = match name {
+        "datablocks" => (include_str!("../obs/datablocks.html"), "text/html; charset=utf-8"),
+        "arrivals" => (include_str!("../obs/arrivals.html"), "text/html; charset=utf-8"),
+        "metar" => (include_str!("../obs/metar.html"), "text/html; charset=utf-8"),
+        "overlay.css" => (include_str!("../obs/overlay.css"), "text/css; charset=utf-8"),
+        "overlay.js" => (include_str!("../obs/overlay.js"), "text/javascript; charset=utf-8"),
+        _ => return None,
+    };
+    Some(ObsAsset { content, content_type })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serves_every_page_and_nothing_else() {
+        for page in ["datablocks", "arrivals", "metar"] {
+            let asset = asset(page).unwrap();
+            assert!(asset.content.contains("overlay.js"));
+            assert!(asset.content_type.starts_with("text/html"));
+        }
+        assert!(asset("overlay.js").is_some());
+        assert!(asset("../Cargo.toml").is_none());
+        assert!(asset("datablocks.html").is_none());
+    }
+}
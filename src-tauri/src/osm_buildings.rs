@@ -0,0 +1,797 @@
+//! Self-hosted 3D buildings from OpenStreetMap
+//!
+//! At airports where Cesium OSM Buildings (Ion) or Google coverage is poor or
+//! unavailable, building footprints around the airport are fetched from the
+//! Overpass API and extruded into a 3D Tiles 1.1 tileset:
+//!
+//! - `tileset.json` with one glTF (`tiles/{i}_{j}.glb`) tile per 1 km cell,
+//!   in a local east-north-up frame at the airport reference point
+//! - Heights from `height`/`min_height`, else `building:levels` (3 m per
+//!   level), else a default for the building type
+//! - Walls start below the airport elevation so they meet sloping terrain;
+//!   the viewer shifts the tileset onto its terrain (see `extras.origin`)
+//!
+//! Tilesets are cached per airport in `osm-buildings/{ICAO}/` in the app data
+//! folder, and served at `/api/buildings/{ICAO}/` and through the
+//! `buildings://` protocol in the app. Requesting a tileset that hasn't been
+//! generated yet generates it.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+use crate::error::{Error, Result};
+use crate::airport_db;
+use crate::http_client;
+use crate::weather;
+
+const OVERPASS_API_URL: &str = "https://overpass-api.de/api/interpreter";
+
+/// Regenerate tilesets older than this
+const CACHE_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 3600);
+
+/// Buildings are fetched within this distance of the airport reference point
+const RADIUS_M: f64 = 5000.0;
+
+/// Size of the square cell covered by each tile
+const CELL_SIZE_M: f64 = 1000.0;
+
+/// Height of a building level
+const LEVEL_HEIGHT_M: f64 = 3.0;
+
+/// How far walls of buildings standing on the ground extend below the airport elevation
+const BELOW_GROUND_M: f64 = 15.0;
+
+/// Beyond roughly this distance (m) the buildings are no longer drawn
+const ROOT_GEOMETRIC_ERROR: f64 = 200.0;
+
+const METERS_PER_DEG_LAT: f64 = 111_320.0;
+
+/// WGS84 ellipsoid
+const WGS84_A: f64 = 6_378_137.0;
+const WGS84_E2: f64 = 6.694_379_990_14e-3;
+
+const BUILDING_COLOR: [f64; 4] = [0.82, 0.8, 0.77, 1.0];
+
+const ATTRIBUTION: &str = "© OpenStreetMap contributors (ODbL)";
+
+/// Overpass response
+#[derive(Deserialize)]
+struct OverpassResponse {
+    #[serde(default)]
+    elements: Vec<OverpassElement>,
+}
+
+#[derive(Deserialize)]
+struct OverpassElement {
+    #[serde(default)]
+    geometry: Vec<OverpassPoint>,
+    /// Relation members (multipolygon buildings)
+    #[serde(default)]
+    members: Vec<OverpassMember>,
+    #[serde(default)]
+    tags: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct OverpassMember {
+    #[serde(default)]
+    role: String,
+    #[serde(default)]
+    geometry: Vec<OverpassPoint>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+struct OverpassPoint {
+    lat: f64,
+    lon: f64,
+}
+
+/// Where a tileset's local frame is anchored
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Origin {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Airport elevation (m above mean sea level); the frame's ground level
+    pub elevation_m: f64,
+}
+
+/// A generated tileset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildingsInfo {
+    pub icao: String,
+    pub buildings: usize,
+    pub tiles: usize,
+    /// Unix time (ms)
+    pub fetched_at: u64,
+    pub origin: Origin,
+    pub attribution: String,
+}
+
+/// A building footprint in the local frame (meters east and north of the origin)
+#[derive(Debug, Clone, PartialEq)]
+struct Footprint {
+    ring: Vec<[f64; 2]>,
+    /// Bottom of the building above ground (0 = on the ground)
+    base: f64,
+    /// Top of the building above ground
+    top: f64,
+}
+
+/// Triangles of one tile, in the local east-north-up frame
+#[derive(Debug, Default)]
+struct Mesh {
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    indices: Vec<u32>,
+}
+
+fn cache_dir(app: &AppHandle, icao: &str) -> Result<PathBuf> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))?;
+    Ok(app_data.join("osm-buildings").join(icao))
+}
+
+/// Parse an OSM length ("12", "12 m", "12.5m", "40'", "40 ft") in meters
+fn parse_length(value: &str) -> Option<f64> {
+    let value = value.trim().replace(',', ".");
+    let (number, feet) = if let Some(n) = value.strip_suffix("ft").or_else(|| value.strip_suffix('\'')) {
+        (n.trim().to_string(), true)
+    } else {
+        (value.trim_end_matches('m').trim().to_string(), false)
+    };
+    let meters = number.parse::<f64>().ok()?;
+    let meters = if feet { meters * 0.3048 } else { meters };
+    (meters.is_finite() && meters >= 0.0).then_some(meters)
+}
+
+/// Default height when a building has neither height nor levels
+fn default_height(tags: &Map<String, Value>) -> f64 {
+    let building = tags.get("building").and_then(Value::as_str).unwrap_or("yes");
+    let aeroway = tags.get("aeroway").and_then(Value::as_str);
+    match (aeroway, building) {
+        (Some("terminal"), _) | (_, "terminal") => 16.0,
+        (Some("hangar"), _) | (_, "hangar") => 14.0,
+        (_, "house" | "detached" | "semidetached_house" | "bungalow") => 7.0,
+        (_, "garage" | "garages" | "shed" | "carport" | "hut" | "kiosk") => 3.0,
+        (_, "industrial" | "warehouse" | "retail" | "supermarket") => 10.0,
+        (_, "apartments" | "office" | "commercial" | "hotel") => 15.0,
+        _ => 8.0,
+    }
+}
+
+/// Bottom and top of a building above ground from its tags
+fn building_heights(tags: &Map<String, Value>) -> (f64, f64) {
+    let tag = |key: &str| tags.get(key).and_then(Value::as_str);
+    let levels = |key: &str| tag(key).and_then(parse_length).map(|l| l * LEVEL_HEIGHT_M);
+    let base = tag("min_height")
+        .and_then(parse_length)
+        .or_else(|| levels("building:min_level"))
+        .unwrap_or(0.0);
+    let top = tag("height")
+        .and_then(parse_length)
+        .or_else(|| levels("building:levels").map(|h| h + levels("roof:levels").unwrap_or(0.0)))
+        .unwrap_or_else(|| base + default_height(tags));
+    let top = top.clamp(2.0, 600.0);
+    (base.min(top - 1.0).max(0.0), top)
+}
+
+/// Project a point into the local frame
+fn to_local(origin: &Origin, lat: f64, lon: f64) -> [f64; 2] {
+    [
+        (lon - origin.longitude) * METERS_PER_DEG_LAT * origin.latitude.to_radians().cos(),
+        (lat - origin.latitude) * METERS_PER_DEG_LAT,
+    ]
+}
+
+/// Twice the signed area of a ring (positive when counterclockwise)
+fn signed_area2(ring: &[[f64; 2]]) -> f64 {
+    (0..ring.len())
+        .map(|i| {
+            let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+            a[0] * b[1] - b[0] * a[1]
+        })
+        .sum()
+}
+
+/// Counterclockwise ring without the closing point, or None if degenerate
+fn normalize_ring(points: &[OverpassPoint], origin: &Origin) -> Option<Vec<[f64; 2]>> {
+    let mut ring: Vec<[f64; 2]> = points.iter().map(|p| to_local(origin, p.lat, p.lon)).collect();
+    if ring.len() < 4 || ring.first() != ring.last() {
+        return None;
+    }
+    ring.pop();
+    ring.dedup();
+    if ring.len() < 3 {
+        return None;
+    }
+    let area2 = signed_area2(&ring);
+    if area2.abs() < 2.0 {
+        return None;
+    }
+    if area2 < 0.0 {
+        ring.reverse();
+    }
+    Some(ring)
+}
+
+/// Footprints from Overpass elements (closed ways, and outer rings of multipolygons)
+fn footprints(elements: Vec<OverpassElement>, origin: &Origin) -> Vec<Footprint> {
+    let mut result = Vec::new();
+    for element in elements {
+        if element.tags.get("building").and_then(Value::as_str) == Some("no") {
+            continue;
+        }
+        let (base, top) = building_heights(&element.tags);
+        let rings: Vec<&[OverpassPoint]> = if element.members.is_empty() {
+            vec![&element.geometry]
+        } else {
+            element
+                .members
+                .iter()
+                .filter(|m| m.role == "outer")
+                .map(|m| m.geometry.as_slice())
+                .collect()
+        };
+        for ring in rings {
+            if let Some(ring) = normalize_ring(ring, origin) {
+                if ring.iter().all(|p| p[0].hypot(p[1]) <= RADIUS_M * 1.5) {
+                    result.push(Footprint { ring, base, top });
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Triangulate a counterclockwise ring (ear clipping)
+fn triangulate(ring: &[[f64; 2]]) -> Vec<[usize; 3]> {
+    let cross = |o: [f64; 2], a: [f64; 2], b: [f64; 2]| (a[0] - o[0]) * (b[1] - o[1]) - (a[1] - o[1]) * (b[0] - o[0]);
+    let inside = |p: [f64; 2], a: [f64; 2], b: [f64; 2], c: [f64; 2]| {
+        cross(a, b, p) >= 0.0 && cross(b, c, p) >= 0.0 && cross(c, a, p) >= 0.0
+    };
+
+    let mut remaining: Vec<usize> = (0..ring.len()).collect();
+    let mut triangles = Vec::with_capacity(ring.len().saturating_sub(2));
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let ear = (0..n).find(|&i| {
+            let (a, b, c) = (remaining[(i + n - 1) % n], remaining[i], remaining[(i + 1) % n]);
+            cross(ring[a], ring[b], ring[c]) > 0.0
+                && remaining
+                    .iter()
+                    .filter(|&&p| p != a && p != b && p != c)
+                    .all(|&p| !inside(ring[p], ring[a], ring[b], ring[c]))
+        });
+        // Self-intersecting rings have no ear left: fan out the rest
+        let i = ear.unwrap_or(1);
+        triangles.push([remaining[(i + n - 1) % n], remaining[i], remaining[(i + 1) % n]]);
+        remaining.remove(i);
+    }
+    if remaining.len() == 3 {
+        triangles.push([remaining[0], remaining[1], remaining[2]]);
+    }
+    triangles
+}
+
+impl Mesh {
+    fn push(&mut self, position: [f64; 3], normal: [f64; 3]) -> u32 {
+        self.positions
+            .push([position[0] as f32, position[1] as f32, position[2] as f32]);
+        self.normals
+            .push([normal[0] as f32, normal[1] as f32, normal[2] as f32]);
+        (self.positions.len() - 1) as u32
+    }
+
+    /// Extrude a footprint: walls with flat normals and a flat roof
+    fn add(&mut self, footprint: &Footprint) {
+        let ring = &footprint.ring;
+        let bottom = if footprint.base > 0.0 {
+            footprint.base
+        } else {
+            -BELOW_GROUND_M
+        };
+        let top = footprint.top;
+
+        for i in 0..ring.len() {
+            let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+            let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+            let length = dx.hypot(dy);
+            if length < 1e-6 {
+                continue;
+            }
+            // Outward for a counterclockwise ring
+            let normal = [dy / length, -dx / length, 0.0];
+            let first = self.push([a[0], a[1], bottom], normal);
+            self.push([b[0], b[1], bottom], normal);
+            self.push([b[0], b[1], top], normal);
+            self.push([a[0], a[1], top], normal);
+            self.indices
+                .extend([first, first + 1, first + 2, first, first + 2, first + 3]);
+        }
+
+        let first = self.positions.len() as u32;
+        for point in ring {
+            self.push([point[0], point[1], top], [0.0, 0.0, 1.0]);
+        }
+        for [a, b, c] in triangulate(ring) {
+            self.indices
+                .extend([first + a as u32, first + b as u32, first + c as u32]);
+        }
+    }
+
+    /// Minimum and maximum corner (east, north, up)
+    fn bounds(&self) -> ([f64; 3], [f64; 3]) {
+        let mut min = [f64::MAX; 3];
+        let mut max = [f64::MIN; 3];
+        for p in &self.positions {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(p[axis] as f64);
+                max[axis] = max[axis].max(p[axis] as f64);
+            }
+        }
+        (min, max)
+    }
+
+    /// Binary glTF (y-up, so east-north-up becomes east-up-south)
+    fn to_glb(&self) -> Vec<u8> {
+        let y_up = |v: &[f32; 3]| [v[0], v[2], -v[1]];
+        let mut bin = Vec::new();
+        let (mut min, mut max) = ([f32::MAX; 3], [f32::MIN; 3]);
+        for p in &self.positions {
+            let p = y_up(p);
+            for axis in 0..3 {
+                min[axis] = min[axis].min(p[axis]);
+                max[axis] = max[axis].max(p[axis]);
+            }
+            bin.extend(p.iter().flat_map(|c| c.to_le_bytes()));
+        }
+        let positions_len = bin.len();
+        for n in &self.normals {
+            bin.extend(y_up(n).iter().flat_map(|c| c.to_le_bytes()));
+        }
+        let normals_len = bin.len() - positions_len;
+        bin.extend(self.indices.iter().flat_map(|i| i.to_le_bytes()));
+        let indices_len = bin.len() - positions_len - normals_len;
+
+        let count = self.positions.len();
+        let gltf = json!({
+            "asset": {"version": "2.0", "generator": "TowerCab 3D"},
+            "scene": 0,
+            "scenes": [{"nodes": [0]}],
+            "nodes": [{"mesh": 0}],
+            "meshes": [{"primitives": [{"attributes": {"POSITION": 0, "NORMAL": 1}, "indices": 2, "material": 0}]}],
+            "materials": [{"pbrMetallicRoughness": {
+                "baseColorFactor": BUILDING_COLOR, "metallicFactor": 0.0, "roughnessFactor": 0.9
+            }}],
+            "accessors": [
+                {"bufferView": 0, "componentType": 5126, "count": count, "type": "VEC3", "min": min, "max": max},
+                {"bufferView": 1, "componentType": 5126, "count": count, "type": "VEC3"},
+                {"bufferView": 2, "componentType": 5125, "count": self.indices.len(), "type": "SCALAR"}
+            ],
+            "bufferViews": [
+                {"buffer": 0, "byteOffset": 0, "byteLength": positions_len, "target": 34962},
+                {"buffer": 0, "byteOffset": positions_len, "byteLength": normals_len, "target": 34962},
+                {"buffer": 0, "byteOffset": positions_len + normals_len, "byteLength": indices_len, "target": 34963}
+            ],
+            "buffers": [{"byteLength": bin.len()}]
+        });
+
+        let mut json = gltf.to_string().into_bytes();
+        json.resize(json.len().next_multiple_of(4), b' ');
+        bin.resize(bin.len().next_multiple_of(4), 0);
+        let total = 12 + 8 + json.len() + 8 + bin.len();
+        let mut glb = Vec::with_capacity(total);
+        glb.extend(b"glTF");
+        glb.extend(2u32.to_le_bytes());
+        glb.extend((total as u32).to_le_bytes());
+        glb.extend((json.len() as u32).to_le_bytes());
+        glb.extend(b"JSON");
+        glb.extend(json);
+        glb.extend((bin.len() as u32).to_le_bytes());
+        glb.extend(b"BIN\0");
+        glb.extend(bin);
+        glb
+    }
+}
+
+/// Earth-centered transform of the local east-north-up frame (column-major)
+fn enu_transform(origin: &Origin) -> [f64; 16] {
+    let (lat, lon) = (origin.latitude.to_radians(), origin.longitude.to_radians());
+    let (sin_lat, cos_lat, sin_lon, cos_lon) = (lat.sin(), lat.cos(), lon.sin(), lon.cos());
+    let n = WGS84_A / (1.0 - WGS84_E2 * sin_lat * sin_lat).sqrt();
+    let h = origin.elevation_m;
+    [
+        -sin_lon,
+        cos_lon,
+        0.0,
+        0.0,
+        -sin_lat * cos_lon,
+        -sin_lat * sin_lon,
+        cos_lat,
+        0.0,
+        cos_lat * cos_lon,
+        cos_lat * sin_lon,
+        sin_lat,
+        0.0,
+        (n + h) * cos_lat * cos_lon,
+        (n + h) * cos_lat * sin_lon,
+        (n * (1.0 - WGS84_E2) + h) * sin_lat,
+        1.0,
+    ]
+}
+
+/// 3D Tiles bounding box from corners
+fn bounding_box(min: [f64; 3], max: [f64; 3]) -> Value {
+    let center = [0, 1, 2].map(|a| (min[a] + max[a]) / 2.0);
+    let half = [0, 1, 2].map(|a| ((max[a] - min[a]) / 2.0).max(0.5));
+    json!({"box": [
+        center[0], center[1], center[2],
+        half[0], 0.0, 0.0,
+        0.0, half[1], 0.0,
+        0.0, 0.0, half[2]
+    ]})
+}
+
+/// Tile files (name, glb) and the tileset.json for footprints
+fn build_tileset(
+    icao: &str,
+    origin: &Origin,
+    footprints: &[Footprint],
+    fetched_at: u64,
+) -> (Vec<(String, Vec<u8>)>, Value) {
+    let mut cells: BTreeMap<(i64, i64), Mesh> = BTreeMap::new();
+    for footprint in footprints {
+        let n = footprint.ring.len() as f64;
+        let east = footprint.ring.iter().map(|p| p[0]).sum::<f64>() / n;
+        let north = footprint.ring.iter().map(|p| p[1]).sum::<f64>() / n;
+        let cell = (
+            (east / CELL_SIZE_M).floor() as i64,
+            (north / CELL_SIZE_M).floor() as i64,
+        );
+        cells.entry(cell).or_default().add(footprint);
+    }
+
+    let mut files = Vec::new();
+    let mut children = Vec::new();
+    let (mut min, mut max) = ([0.0f64; 3], [0.0f64; 3]);
+    for ((i, j), mesh) in &cells {
+        let name = format!("tiles/{}_{}.glb", i, j);
+        let (tile_min, tile_max) = mesh.bounds();
+        for axis in 0..3 {
+            min[axis] = min[axis].min(tile_min[axis]);
+            max[axis] = max[axis].max(tile_max[axis]);
+        }
+        children.push(json!({
+            "boundingVolume": bounding_box(tile_min, tile_max),
+            "geometricError": 0.0,
+            "content": {"uri": name}
+        }));
+        files.push((name, mesh.to_glb()));
+    }
+
+    let tileset = json!({
+        "asset": {"version": "1.1", "generator": "TowerCab 3D"},
+        "geometricError": ROOT_GEOMETRIC_ERROR,
+        "extras": {
+            "icao": icao,
+            "buildings": footprints.len(),
+            "fetchedAt": fetched_at,
+            "origin": origin,
+            "attribution": ATTRIBUTION
+        },
+        "root": {
+            "transform": enu_transform(origin),
+            "boundingVolume": bounding_box(min, max),
+            "geometricError": ROOT_GEOMETRIC_ERROR,
+            "refine": "ADD",
+            "children": children
+        }
+    });
+    (files, tileset)
+}
+
+/// Overpass query for buildings in a box around a point
+fn buildings_query(origin: &Origin) -> String {
+    let lat_span = RADIUS_M / METERS_PER_DEG_LAT;
+    let lon_span = lat_span / origin.latitude.to_radians().cos().max(0.1);
+    let bbox = format!(
+        "{},{},{},{}",
+        origin.latitude - lat_span,
+        origin.longitude - lon_span,
+        origin.latitude + lat_span,
+        origin.longitude + lon_span
+    );
+    format!(
+        "[out:json][timeout:120];\
+         (way[\"building\"]({bbox});\
+          relation[\"building\"][\"type\"=\"multipolygon\"]({bbox}););\
+         out geom;",
+        bbox = bbox
+    )
+}
+
+async fn fetch_elements(origin: &Origin) -> Result<Vec<OverpassElement>> {
+    let response = http_client::client()
+        .post(OVERPASS_API_URL)
+        .form(&[("data", buildings_query(origin))])
+        .timeout(Duration::from_secs(150))
+        .send()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to query OpenStreetMap: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(Error::Upstream(format!("Overpass API error: {}", response.status())));
+    }
+    let body: OverpassResponse = response
+        .json()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to parse Overpass response: {}", e)))?;
+    Ok(body.elements)
+}
+
+/// Info of a generated tileset from its tileset.json
+fn read_info(dir: &std::path::Path) -> Option<BuildingsInfo> {
+    let tileset: Value = serde_json::from_slice(&fs::read(dir.join("tileset.json")).ok()?).ok()?;
+    let extras = &tileset["extras"];
+    Some(BuildingsInfo {
+        icao: extras["icao"].as_str()?.to_string(),
+        buildings: extras["buildings"].as_u64()? as usize,
+        tiles: tileset["root"]["children"].as_array().map(Vec::len).unwrap_or(0),
+        fetched_at: extras["fetchedAt"].as_u64()?,
+        origin: serde_json::from_value(extras["origin"].clone()).ok()?,
+        attribution: extras["attribution"].as_str().unwrap_or(ATTRIBUTION).to_string(),
+    })
+}
+
+/// Fetch footprints and write a new tileset, replacing the old one
+async fn generate(app: &AppHandle, icao: &str) -> Result<BuildingsInfo> {
+    let airport = airport_db::get_airport(app, icao.to_string())
+        .await
+        .map_err(Error::io)?
+        .ok_or_else(|| Error::NotFound(format!("Unknown airport: {}", icao)))?;
+    let origin = Origin {
+        latitude: airport.latitude,
+        longitude: airport.longitude,
+        elevation_m: airport.elevation_ft * 0.3048,
+    };
+    let footprints = footprints(fetch_elements(&origin).await?, &origin);
+    let fetched_at = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    let (files, tileset) = build_tileset(icao, &origin, &footprints, fetched_at);
+
+    // Written next to the old tileset and swapped in, so it's never served half-written
+    let dir = cache_dir(app, icao)?;
+    let staging = dir.with_extension(format!("tmp-{}", fetched_at));
+    let icao = icao.to_string();
+    tauri::async_runtime::spawn_blocking(move || {
+        let write = || -> Result<()> {
+            fs::create_dir_all(staging.join("tiles"))
+                .map_err(|e| Error::Io(format!("Failed to create {}: {}", staging.display(), e)))?;
+            for (name, glb) in &files {
+                fs::write(staging.join(name), glb)
+                    .map_err(|e| Error::Io(format!("Failed to write building tile: {}", e)))?;
+            }
+            fs::write(staging.join("tileset.json"), tileset.to_string())
+                .map_err(|e| Error::Io(format!("Failed to write tileset: {}", e)))?;
+            let _ = fs::remove_dir_all(&dir);
+            fs::rename(&staging, &dir).map_err(|e| Error::Io(format!("Failed to save tileset: {}", e)))
+        };
+        if let Err(e) = write() {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+        info!(
+            "[OsmBuildings] Generated {} buildings in {} tiles for {}",
+            footprints.len(),
+            files.len(),
+            icao
+        );
+        read_info(&dir).ok_or_else(|| Error::Internal("Failed to read generated tileset".to_string()))
+    })
+    .await
+    .map_err(|e| Error::Internal(format!("Failed to generate buildings: {}", e)))?
+}
+
+/// An airport's building tileset, generating it if it's missing, stale or a
+/// refresh is requested
+pub async fn get_buildings(app: &AppHandle, icao: &str, refresh: bool) -> Result<BuildingsInfo> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    let dir = cache_dir(app, &icao)?;
+    let existing = read_info(&dir);
+    let fresh = fs::metadata(dir.join("tileset.json"))
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < CACHE_MAX_AGE);
+    if let (Some(info), true, false) = (&existing, fresh, refresh) {
+        return Ok(info.clone());
+    }
+    match generate(app, &icao).await {
+        Ok(info) => Ok(info),
+        Err(e) => {
+            // Keep serving a stale tileset when OSM is unreachable
+            warn!("[OsmBuildings] Failed to generate buildings for {}: {}", icao, e);
+            existing.ok_or(e)
+        }
+    }
+}
+
+/// A tileset file (`tileset.json` or `tiles/{i}_{j}.glb`), generating the
+/// tileset first if needed. `None` if there's no such file.
+pub async fn read_file(app: &AppHandle, icao: &str, relative: &str) -> Result<Option<Vec<u8>>> {
+    let relative = relative.split('?').next().unwrap_or_default().trim_start_matches('/');
+    let valid_tile = relative
+        .strip_prefix("tiles/")
+        .and_then(|name| name.strip_suffix(".glb"))
+        .and_then(|cell| cell.split_once('_'))
+        .is_some_and(|(i, j)| i.parse::<i64>().is_ok() && j.parse::<i64>().is_ok());
+    if relative != "tileset.json" && !valid_tile {
+        return Ok(None);
+    }
+    let info = get_buildings(app, icao, false).await?;
+    let path = cache_dir(app, &info.icao)?.join(relative);
+    match tokio::fs::read(path).await {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(Error::Io(format!("Failed to read building tile: {}", e))),
+    }
+}
+
+/// Content type of a tileset file
+pub fn content_type(relative: &str) -> &'static str {
+    if relative.ends_with(".glb") {
+        "model/gltf-binary"
+    } else {
+        "application/json"
+    }
+}
+
+/// Response for the `buildings://` protocol used by the app's viewer
+/// (`buildings://localhost/{ICAO}/tileset.json`)
+pub async fn protocol_response(app: &AppHandle, path: &str) -> tauri::http::Response<Vec<u8>> {
+    let (status, content_type, body) = match path.trim_start_matches('/').split_once('/') {
+        None => (404, "text/plain", b"Unknown file".to_vec()),
+        Some((icao, relative)) => match read_file(app, icao, relative).await {
+            Ok(Some(content)) => (200, content_type(relative), content),
+            Ok(None) => (404, "text/plain", b"Unknown file".to_vec()),
+            Err(e) => (502, "text/plain", e.to_string().into_bytes()),
+        },
+    };
+    tauri::http::Response::builder()
+        .status(status)
+        .header("Content-Type", content_type)
+        .header("Access-Control-Allow-Origin", "*")
+        .body(body)
+        .unwrap_or_default()
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Generate (or get the cached) 3D building tileset around an airport from OpenStreetMap
+#[tauri::command]
+pub async fn get_osm_buildings(app: AppHandle, icao: String, refresh: Option<bool>) -> Result<BuildingsInfo> {
+    get_buildings(&app, &icao, refresh.unwrap_or(false)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORIGIN: Origin = Origin {
+        latitude: 42.0,
+        longitude: -71.0,
+        elevation_m: 6.0,
+    };
+
+    fn square(size_deg: f64, clockwise: bool) -> Vec<OverpassPoint> {
+        let mut corners = vec![
+            (0.0, 0.0),
+            (0.0, size_deg),
+            (size_deg, size_deg),
+            (size_deg, 0.0),
+            (0.0, 0.0),
+        ];
+        if !clockwise {
+            corners.reverse();
+        }
+        corners
+            .into_iter()
+            .map(|(lat, lon)| OverpassPoint {
+                lat: ORIGIN.latitude + lat,
+                lon: ORIGIN.longitude + lon,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reads_heights_from_tags() {
+        let tags = |value: Value| value.as_object().unwrap().clone();
+        assert_eq!(
+            building_heights(&tags(json!({"building": "yes", "height": "12 m"}))),
+            (0.0, 12.0)
+        );
+        assert_eq!(
+            building_heights(&tags(json!({"building": "yes", "building:levels": "4"}))),
+            (0.0, 12.0)
+        );
+        assert_eq!(building_heights(&tags(json!({"building": "hangar"}))), (0.0, 14.0));
+        assert_eq!(
+            building_heights(&tags(json!({"building": "roof", "min_height": "4", "height": "7"}))),
+            (4.0, 7.0)
+        );
+        assert_eq!(parse_length("40'"), Some(40.0 * 0.3048));
+        assert_eq!(parse_length("tall"), None);
+    }
+
+    #[test]
+    fn extrudes_footprints_into_tiles() {
+        let elements = vec![
+            OverpassElement {
+                geometry: square(0.0005, true),
+                members: Vec::new(),
+                tags: json!({"building": "yes", "height": "10"}).as_object().unwrap().clone(),
+            },
+            OverpassElement {
+                geometry: square(0.0005, false),
+                members: Vec::new(),
+                tags: json!({"building": "no"}).as_object().unwrap().clone(),
+            },
+        ];
+        let footprints = footprints(elements, &ORIGIN);
+        assert_eq!(footprints.len(), 1);
+        // Clockwise input is made counterclockwise
+        assert!(signed_area2(&footprints[0].ring) > 0.0);
+        assert_eq!(triangulate(&footprints[0].ring).len(), 2);
+
+        let mut mesh = Mesh::default();
+        mesh.add(&footprints[0]);
+        // 4 walls (4 vertices each) and a 4-vertex roof
+        assert_eq!(mesh.positions.len(), 20);
+        assert_eq!(mesh.indices.len(), 4 * 6 + 2 * 3);
+        let (min, max) = mesh.bounds();
+        assert_eq!((min[2], max[2]), (-BELOW_GROUND_M, 10.0));
+
+        let glb = mesh.to_glb();
+        assert_eq!(&glb[0..4], b"glTF");
+        assert_eq!(u32::from_le_bytes(glb[8..12].try_into().unwrap()) as usize, glb.len());
+
+        let (files, tileset) = build_tileset("KBOS", &ORIGIN, &footprints, 1);
+        assert_eq!(files.len(), 1);
+        assert_eq!(tileset["root"]["children"][0]["content"]["uri"], files[0].0);
+        assert_eq!(tileset["extras"]["origin"]["elevationM"], 6.0);
+    }
+
+    #[test]
+    fn triangulates_concave_rings() {
+        // L-shaped building
+        let ring = [
+            [0.0, 0.0],
+            [20.0, 0.0],
+            [20.0, 10.0],
+            [10.0, 10.0],
+            [10.0, 20.0],
+            [0.0, 20.0],
+        ];
+        let triangles = triangulate(&ring);
+        assert_eq!(triangles.len(), 4);
+        let area: f64 = triangles
+            .iter()
+            .map(|t| signed_area2(&[ring[t[0]], ring[t[1]], ring[t[2]]]))
+            .sum();
+        assert!((area - signed_area2(&ring)).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,280 @@
+//! Airport ground layout from OpenStreetMap
+//!
+//! Queries the Overpass API for an airport's runways, taxiways, aprons, gates,
+//! stands and buildings, converts them to GeoJSON and caches the result per
+//! airport in `osm/{ICAO}.geojson` in the app data folder. Gives ground-layout
+//! context at airports without custom scenery.
+//!
+//! The query is limited to the aerodrome's mapped boundary when OSM has one,
+//! otherwise to a box around the airport reference point.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+use crate::error::{Error, Result};
+use crate::airport_db;
+use crate::http_client;
+use crate::weather;
+
+const OVERPASS_API_URL: &str = "https://overpass-api.de/api/interpreter";
+
+/// Refetch cached layouts older than this
+const CACHE_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 3600);
+
+/// Half-size of the fallback query box around the airport (degrees latitude, ~3 nm)
+const FALLBACK_BOX_DEG: f64 = 0.05;
+
+/// Aeroway features included in the layout
+const AEROWAY_WAYS: &str = "runway|taxiway|taxilane|apron|gate|parking_position|holding_position|terminal|hangar";
+const AEROWAY_NODES: &str = "gate|parking_position|holding_position";
+
+/// Kinds drawn as areas when their way is closed
+const AREA_KINDS: &[&str] = &["apron", "terminal", "hangar", "building"];
+
+/// Tags copied to feature properties
+const COPIED_TAGS: &[&str] = &["ref", "name", "building", "surface", "width", "operator"];
+
+/// Overpass response
+#[derive(Deserialize)]
+struct OverpassResponse {
+    #[serde(default)]
+    elements: Vec<OverpassElement>,
+}
+
+#[derive(Deserialize)]
+struct OverpassElement {
+    #[serde(rename = "type")]
+    kind: String,
+    id: u64,
+    lat: Option<f64>,
+    lon: Option<f64>,
+    #[serde(default)]
+    geometry: Vec<OverpassPoint>,
+    #[serde(default)]
+    tags: Map<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct OverpassPoint {
+    lat: f64,
+    lon: f64,
+}
+
+/// Get the cached layout file for an airport
+fn cache_path(app: &AppHandle, icao: &str) -> Result<PathBuf> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))?;
+    Ok(app_data.join("osm").join(format!("{}.geojson", icao)))
+}
+
+/// Overpass query for an airport's aerodrome area
+fn area_query(icao: &str) -> String {
+    format!(
+        "[out:json][timeout:90];\
+         wr[\"aeroway\"=\"aerodrome\"][\"icao\"=\"{icao}\"]->.airport;\
+         .airport map_to_area->.a;\
+         (way[\"aeroway\"~\"^({ways})$\"](area.a);\
+          node[\"aeroway\"~\"^({nodes})$\"](area.a);\
+          way[\"building\"](area.a););\
+         out geom;",
+        icao = icao,
+        ways = AEROWAY_WAYS,
+        nodes = AEROWAY_NODES
+    )
+}
+
+/// Overpass query for a box around a point
+fn box_query(latitude: f64, longitude: f64) -> String {
+    let lat_span = FALLBACK_BOX_DEG;
+    let lon_span = FALLBACK_BOX_DEG / latitude.to_radians().cos().max(0.1);
+    let bbox = format!(
+        "{},{},{},{}",
+        latitude - lat_span,
+        longitude - lon_span,
+        latitude + lat_span,
+        longitude + lon_span
+    );
+    format!(
+        "[out:json][timeout:90];\
+         (way[\"aeroway\"~\"^({ways})$\"]({bbox});\
+          node[\"aeroway\"~\"^({nodes})$\"]({bbox});\
+          way[\"building\"][\"aeroway\"]({bbox}););\
+         out geom;",
+        ways = AEROWAY_WAYS,
+        nodes = AEROWAY_NODES,
+        bbox = bbox
+    )
+}
+
+async fn run_query(query: String) -> Result<Vec<OverpassElement>> {
+    let response = http_client::client()
+        .post(OVERPASS_API_URL)
+        .form(&[("data", query)])
+        .timeout(Duration::from_secs(120))
+        .send()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to query OpenStreetMap: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(Error::Upstream(format!("Overpass API error: {}", response.status())));
+    }
+    let body: OverpassResponse = response
+        .json()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to parse Overpass response: {}", e)))?;
+    Ok(body.elements)
+}
+
+/// Convert an Overpass element to a GeoJSON feature
+fn to_feature(element: OverpassElement) -> Option<Value> {
+    let tag = |key: &str| element.tags.get(key).and_then(Value::as_str);
+    let kind = match (tag("aeroway"), tag("building")) {
+        (Some(aeroway), _) => aeroway.to_string(),
+        (None, Some(_)) => "building".to_string(),
+        (None, None) => return None,
+    };
+
+    let geometry = match element.kind.as_str() {
+        "node" => json!({"type": "Point", "coordinates": [element.lon?, element.lat?]}),
+        "way" if element.geometry.len() >= 2 => {
+            let coordinates: Vec<[f64; 2]> = element.geometry.iter().map(|p| [p.lon, p.lat]).collect();
+            let closed = element.geometry.len() >= 4 && coordinates.first() == coordinates.last();
+            if closed && (AREA_KINDS.contains(&kind.as_str()) || tag("area") == Some("yes")) {
+                json!({"type": "Polygon", "coordinates": [coordinates]})
+            } else {
+                json!({"type": "LineString", "coordinates": coordinates})
+            }
+        }
+        _ => return None,
+    };
+
+    let mut properties = Map::new();
+    properties.insert("osmId".to_string(), json!(format!("{}/{}", element.kind, element.id)));
+    properties.insert("kind".to_string(), json!(kind));
+    for key in COPIED_TAGS {
+        if let Some(value) = tag(key) {
+            properties.insert(key.to_string(), json!(value));
+        }
+    }
+    Some(json!({"type": "Feature", "geometry": geometry, "properties": properties}))
+}
+
+/// Build the layout FeatureCollection from Overpass elements
+fn to_feature_collection(icao: &str, elements: Vec<OverpassElement>) -> Value {
+    let features: Vec<Value> = elements.into_iter().filter_map(to_feature).collect();
+    let fetched_at = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    json!({
+        "type": "FeatureCollection",
+        "metadata": {
+            "icao": icao,
+            "fetchedAt": fetched_at,
+            "attribution": "© OpenStreetMap contributors (ODbL)",
+        },
+        "features": features,
+    })
+}
+
+/// Fetch an airport's layout from OpenStreetMap
+async fn fetch_layout(app: &AppHandle, icao: &str) -> Result<Value> {
+    let mut elements = run_query(area_query(icao)).await?;
+    if elements.is_empty() {
+        // Aerodrome boundary not mapped (or not tagged with the ICAO code)
+        let airport = airport_db::get_airport(app, icao.to_string())
+            .await
+            .map_err(Error::io)?
+            .ok_or_else(|| Error::NotFound(format!("Unknown airport: {}", icao)))?;
+        elements = run_query(box_query(airport.latitude, airport.longitude)).await?;
+    }
+    Ok(to_feature_collection(icao, elements))
+}
+
+/// Get an airport's ground layout as GeoJSON, from the cache unless it is
+/// stale or a refresh is requested
+pub async fn get_layout(app: &AppHandle, icao: &str, refresh: bool) -> Result<Value> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    let path = cache_path(app, &icao)?;
+
+    let fresh = fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < CACHE_MAX_AGE);
+    if fresh && !refresh {
+        if let Some(layout) = fs::read_to_string(&path)
+            .ok()
+            .and_then(|c| serde_json::from_str(&c).ok())
+        {
+            return Ok(layout);
+        }
+    }
+
+    let layout = match fetch_layout(app, &icao).await {
+        Ok(layout) => layout,
+        Err(e) => {
+            // Keep serving a stale copy when OSM is unreachable
+            warn!("[OsmLayout] Failed to fetch layout for {}: {}", icao, e);
+            return fs::read_to_string(&path)
+                .ok()
+                .and_then(|c| serde_json::from_str(&c).ok())
+                .ok_or(e);
+        }
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| Error::Io(format!("Failed to create OSM cache folder: {}", e)))?;
+    }
+    fs::write(&path, layout.to_string()).map_err(|e| Error::Io(format!("Failed to cache OSM layout: {}", e)))?;
+    let count = layout["features"].as_array().map(Vec::len).unwrap_or(0);
+    info!("[OsmLayout] Cached {} ground layout features for {}", count, icao);
+    Ok(layout)
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get an airport's taxiways, aprons, gates and buildings from OpenStreetMap (cached)
+#[tauri::command]
+pub async fn get_osm_layout(app: AppHandle, icao: String, refresh: Option<bool>) -> Result<Value> {
+    get_layout(&app, &icao, refresh.unwrap_or(false)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_overpass_elements() {
+        let response: OverpassResponse = serde_json::from_value(json!({
+            "elements": [
+                {"type": "node", "id": 1, "lat": 42.36, "lon": -71.01, "tags": {"aeroway": "gate", "ref": "B5"}},
+                {"type": "way", "id": 2, "tags": {"aeroway": "taxiway", "ref": "K"},
+                 "geometry": [{"lat": 42.36, "lon": -71.01}, {"lat": 42.37, "lon": -71.02}]},
+                {"type": "way", "id": 3, "tags": {"aeroway": "apron"},
+                 "geometry": [{"lat": 0.0, "lon": 0.0}, {"lat": 0.0, "lon": 1.0}, {"lat": 1.0, "lon": 1.0}, {"lat": 0.0, "lon": 0.0}]},
+                {"type": "way", "id": 4, "tags": {"highway": "service"},
+                 "geometry": [{"lat": 0.0, "lon": 0.0}, {"lat": 0.0, "lon": 1.0}]}
+            ]
+        }))
+        .unwrap();
+
+        let layout = to_feature_collection("KBOS", response.elements);
+        let features = layout["features"].as_array().unwrap();
+        assert_eq!(features.len(), 3);
+        assert_eq!(features[0]["geometry"]["coordinates"], json!([-71.01, 42.36]));
+        assert_eq!(features[0]["properties"]["ref"], "B5");
+        assert_eq!(features[1]["geometry"]["type"], "LineString");
+        assert_eq!(features[2]["geometry"]["type"], "Polygon");
+        assert_eq!(features[2]["properties"]["osmId"], "way/3");
+    }
+}
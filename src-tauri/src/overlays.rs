@@ -0,0 +1,313 @@
+//! GeoJSON overlay mods
+//!
+//! Overlays are 2D/3D vector layers (video maps, range rings, taxi diagrams,
+//! facility boundaries) loaded from `mods/overlays/`. Each overlay is either a
+//! folder with a `manifest.json` and one or more GeoJSON files, or a loose
+//! `.geojson` file (which gets a default manifest derived from its filename).
+//!
+//! ```text
+//! mods/overlays/
+//! ├── kbos-video-map/
+//! │   ├── manifest.json
+//! │   └── map.geojson
+//! └── range-rings.geojson
+//! ```
+//!
+//! Airport annotations kept in app data (see `annotations`) are listed
+//! alongside the mods as `{icao}-annotations` overlays.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tracing::warn;
+
+use crate::annotations;
+use crate::error::Error;
+use crate::find_mods_root;
+
+/// Overlay manifest (mods/overlays/{name}/manifest.json)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayManifest {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Free-form category: "videoMap", "rangeRings", "taxiDiagram", "boundary", ...
+    #[serde(default)]
+    pub category: Option<String>,
+    /// ICAO codes this overlay applies to (empty = shown at every airport)
+    #[serde(default)]
+    pub airports: Vec<String>,
+    /// Rendering target: "2d", "3d" or "both" (default)
+    #[serde(default)]
+    pub render: Option<String>,
+    /// Default styling hints passed through to the client (colors, line width, ...)
+    #[serde(default)]
+    pub style: Option<serde_json::Value>,
+    /// GeoJSON files to load (relative to the overlay folder); all *.geojson if empty
+    #[serde(default)]
+    pub files: Vec<String>,
+}
+
+/// GeoJSON file belonging to an overlay
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayFile {
+    pub name: String,
+    /// URL usable by remote clients (e.g., "/api/overlays/kbos-video-map/map.geojson")
+    pub url: String,
+    pub size: u64,
+    /// Absolute path on the host (for Tauri mode)
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+/// Overlay entry returned by /api/overlays
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayInfo {
+    pub id: String,
+    pub name: String,
+    pub author: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub airports: Vec<String>,
+    pub render: String,
+    pub style: Option<serde_json::Value>,
+    pub files: Vec<OverlayFile>,
+}
+
+impl OverlayInfo {
+    /// Whether this overlay should be shown at the given airport
+    pub fn applies_to(&self, icao: &str) -> bool {
+        self.airports.is_empty() || self.airports.iter().any(|a| a.eq_ignore_ascii_case(icao))
+    }
+}
+
+/// Get the overlays mods directory
+pub fn overlays_root(app: &AppHandle) -> PathBuf {
+    find_mods_root(app).join("overlays")
+}
+
+fn is_geojson_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("geojson") || ext.eq_ignore_ascii_case("json"))
+        && !path
+            .file_name()
+            .is_some_and(|n| n.eq_ignore_ascii_case("manifest.json"))
+}
+
+pub fn overlay_file(id: &str, name: String, path: PathBuf) -> OverlayFile {
+    let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    OverlayFile {
+        url: format!("/api/overlays/{}/{}", id, name),
+        name,
+        size,
+        path,
+    }
+}
+
+/// Build an overlay entry from a manifest and its files
+fn build_overlay(id: String, manifest: OverlayManifest, files: Vec<OverlayFile>) -> OverlayInfo {
+    OverlayInfo {
+        name: manifest.name.unwrap_or_else(|| id.clone()),
+        id,
+        author: manifest.author,
+        version: manifest.version,
+        description: manifest.description,
+        category: manifest.category,
+        airports: manifest.airports.iter().map(|a| a.to_uppercase()).collect(),
+        render: manifest.render.unwrap_or_else(|| "both".to_string()),
+        style: manifest.style,
+        files,
+    }
+}
+
+/// Load an overlay folder (manifest.json + GeoJSON files)
+fn load_overlay_folder(id: &str, dir: &Path) -> Option<OverlayInfo> {
+    let manifest_path = dir.join("manifest.json");
+    let manifest: OverlayManifest = if manifest_path.exists() {
+        match fs::read_to_string(&manifest_path)
+            .map_err(|e| e.to_string())
+            .and_then(|c| serde_json::from_str(&c).map_err(|e| e.to_string()))
+        {
+            Ok(m) => m,
+            Err(e) => {
+                warn!("[Overlays] Invalid manifest for {}: {}", id, e);
+                return None;
+            }
+        }
+    } else {
+        OverlayManifest::default()
+    };
+
+    let mut files: Vec<OverlayFile> = if manifest.files.is_empty() {
+        fs::read_dir(dir)
+            .ok()?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| is_geojson_file(p))
+            .filter_map(|p| {
+                let name = p.file_name()?.to_string_lossy().to_string();
+                Some(overlay_file(id, name, p))
+            })
+            .collect()
+    } else {
+        manifest
+            .files
+            .iter()
+            .map(|f| f.replace('\\', "/"))
+            .filter(|f| !f.split('/').any(|part| part == ".."))
+            .map(|f| (dir.join(&f), f))
+            .filter(|(p, _)| is_geojson_file(p))
+            .map(|(p, f)| overlay_file(id, f, p))
+            .collect()
+    };
+
+    if files.is_empty() {
+        return None;
+    }
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Some(build_overlay(id.to_string(), manifest, files))
+}
+
+/// List all overlays in mods/overlays/ and the airport annotations
+pub fn list_all(app: &AppHandle) -> Vec<OverlayInfo> {
+    let mut overlays = annotations::list_overlays(app);
+    let entries = fs::read_dir(overlays_root(app)).into_iter().flatten();
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let Ok(file_name) = entry.file_name().into_string() else {
+            continue;
+        };
+
+        if path.is_dir() {
+            if let Some(overlay) = load_overlay_folder(&file_name, &path) {
+                overlays.push(overlay);
+            }
+        } else if is_geojson_file(&path) {
+            // Loose GeoJSON file: id is the file stem, default manifest
+            let id = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| file_name.clone());
+            let files = vec![overlay_file(&id, file_name, path)];
+            overlays.push(build_overlay(id, OverlayManifest::default(), files));
+        }
+    }
+
+    overlays.sort_by(|a, b| a.id.cmp(&b.id));
+    overlays
+}
+
+/// List overlays, optionally filtered to those applying to an airport
+pub fn list_for_airport(app: &AppHandle, icao: Option<&str>) -> Vec<OverlayInfo> {
+    let overlays = list_all(app);
+    match icao {
+        Some(icao) => overlays.into_iter().filter(|o| o.applies_to(icao)).collect(),
+        None => overlays,
+    }
+}
+
+/// Resolve an overlay file by overlay id and file name.
+/// Only files listed by the overlay can be resolved, so arbitrary paths are rejected.
+pub fn resolve_file(app: &AppHandle, id: &str, file: &str) -> Option<PathBuf> {
+    list_all(app)
+        .into_iter()
+        .find(|o| o.id == id)?
+        .files
+        .into_iter()
+        .find(|f| f.name == file)
+        .map(|f| f.path)
+}
+
+/// Folder an overlay's files are served from (app data for annotations, the mods folder otherwise)
+pub fn file_root(app: &AppHandle, id: &str) -> PathBuf {
+    if annotations::is_overlay_id(id) {
+        if let Ok(root) = annotations::annotations_root(app) {
+            return root;
+        }
+    }
+    overlays_root(app)
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// List GeoJSON overlays (optionally only those applying to an airport)
+#[tauri::command]
+pub fn list_overlays(app: AppHandle, icao: Option<String>) -> Vec<OverlayInfo> {
+    list_for_airport(&app, icao.as_deref())
+}
+
+/// Read a GeoJSON overlay file as JSON
+#[tauri::command]
+pub fn read_overlay_file(app: AppHandle, id: String, file: String) -> Result<serde_json::Value, Error> {
+    let path = resolve_file(&app, &id, &file)
+        .ok_or_else(|| Error::NotFound(format!("Overlay file not found: {}/{}", id, file)))?;
+    let content = fs::read_to_string(&path)
+        .map_err(|e| Error::Io(format!("Failed to read overlay {:?}: {}", path, e)))?;
+    serde_json::from_str(&content).map_err(|e| Error::InvalidInput(format!("Failed to parse GeoJSON: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_overlay_folders() {
+        let root = std::env::temp_dir().join(format!("overlays-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let video_map = root.join("kbos-video-map");
+        fs::create_dir_all(video_map.join("extra")).unwrap();
+        fs::write(video_map.join("map.geojson"), "{}").unwrap();
+        fs::write(video_map.join("rings.json"), "{}").unwrap();
+        fs::write(video_map.join("notes.txt"), "").unwrap();
+        fs::write(video_map.join("extra/taxi.geojson"), "{}").unwrap();
+
+        // Without a manifest: every GeoJSON file in the folder, default name and render target
+        let overlay = load_overlay_folder("kbos-video-map", &video_map).unwrap();
+        let names: Vec<&str> = overlay.files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["map.geojson", "rings.json"]);
+        assert_eq!(overlay.name, "kbos-video-map");
+        assert_eq!(overlay.render, "both");
+        assert_eq!(overlay.files[0].url, "/api/overlays/kbos-video-map/map.geojson");
+        assert!(overlay.applies_to("LFPG"));
+
+        // Listed files only, never outside the folder
+        fs::write(root.join("secret.geojson"), "{}").unwrap();
+        fs::write(
+            video_map.join("manifest.json"),
+            r#"{"name": "KBOS Video Map", "airports": ["kbos"], "render": "2d",
+                "files": ["extra\\taxi.geojson", "../secret.geojson", "missing.geojson"]}"#,
+        )
+        .unwrap();
+        let overlay = load_overlay_folder("kbos-video-map", &video_map).unwrap();
+        let names: Vec<&str> = overlay.files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["extra/taxi.geojson"]);
+        assert_eq!((overlay.name.as_str(), overlay.render.as_str()), ("KBOS Video Map", "2d"));
+        assert_eq!(overlay.airports, ["KBOS"]);
+        assert!(overlay.applies_to("kbos") && !overlay.applies_to("KJFK"));
+
+        // An invalid manifest or no GeoJSON at all skips the overlay
+        fs::write(video_map.join("manifest.json"), "{ not json").unwrap();
+        assert!(load_overlay_folder("kbos-video-map", &video_map).is_none());
+        let empty = root.join("empty");
+        fs::create_dir_all(&empty).unwrap();
+        assert!(load_overlay_folder("empty", &empty).is_none());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,293 @@
+//! Static object placements
+//!
+//! Dresses up an airport with static GLB models (jet bridges, hangars, ground
+//! equipment, the facility's logo board) without editing scenery. Models live
+//! in `mods/objects/` and are served like tower mods at
+//! `/api/mods/objects/...`; where they stand is kept per airport in
+//! `mods/placements/{ICAO}.json`:
+//!
+//! ```json
+//! { "objects": [{ "id": "logo", "model": "boards/logo.glb",
+//!   "latitude": 42.3656, "longitude": -71.0096, "heading": 270 }] }
+//! ```
+//!
+//! Placements are edited with the `*_static_object` commands or
+//! `/api/placements/{ICAO}`, and every change is emitted as
+//! `static-objects-changed`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use tracing::info;
+
+use crate::error::{Error, Result};
+use crate::find_mods_root;
+use crate::safe_path;
+use crate::weather;
+
+/// Models folder in the mods folder
+pub const MODELS_DIR: &str = "objects";
+
+/// Placement files folder in the mods folder
+const PLACEMENTS_DIR: &str = "placements";
+
+/// Most objects placed at one airport
+const MAX_OBJECTS: usize = 500;
+
+fn default_scale() -> f64 {
+    1.0
+}
+
+/// A static model placed at an airport
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StaticObject {
+    /// Identifier, unique per airport (e.g., "bridge-b5")
+    pub id: String,
+    /// Model path relative to `mods/objects/` (e.g., "jetbridges/standard.glb")
+    pub model: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Degrees true the model's front faces
+    #[serde(default)]
+    pub heading: f64,
+    /// Meters above the ground
+    #[serde(default)]
+    pub height_m: f64,
+    #[serde(default = "default_scale")]
+    pub scale: f64,
+}
+
+/// Objects placed at an airport (`mods/placements/{ICAO}.json`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AirportPlacements {
+    #[serde(default)]
+    pub objects: Vec<StaticObject>,
+}
+
+/// Placement change, emitted as `static-objects-changed`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlacementsChanged {
+    pub icao: String,
+    pub objects: Vec<StaticObject>,
+}
+
+/// Whether an object id can be used in a URL path
+pub fn valid_object_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 64
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Check a placed object
+pub fn validate(object: &StaticObject) -> Result<()> {
+    if !valid_object_id(&object.id) {
+        return Err(Error::InvalidInput(format!("Invalid object id: {}", object.id)));
+    }
+    if !safe_path::valid_relative(&object.model) || !object.model.to_lowercase().ends_with(".glb") {
+        return Err(Error::InvalidInput(format!("Model must be a .glb path inside mods/objects: {}", object.model)));
+    }
+    if !(-90.0..=90.0).contains(&object.latitude) || !(-180.0..=180.0).contains(&object.longitude) {
+        return Err(Error::InvalidInput(format!("Invalid position for {}", object.id)));
+    }
+    if !object.heading.is_finite() || !object.height_m.is_finite() || object.height_m.abs() > 1000.0 {
+        return Err(Error::InvalidInput(format!("Invalid heading or height for {}", object.id)));
+    }
+    if !(object.scale > 0.0 && object.scale <= 100.0) {
+        return Err(Error::InvalidInput(format!("Scale must be between 0 and 100 for {}", object.id)));
+    }
+    Ok(())
+}
+
+/// Check an airport's placements (every object valid, ids unique)
+pub fn validate_all(objects: &[StaticObject]) -> Result<()> {
+    if objects.len() > MAX_OBJECTS {
+        return Err(Error::InvalidInput(format!("At most {} objects can be placed at an airport", MAX_OBJECTS)));
+    }
+    for (i, object) in objects.iter().enumerate() {
+        validate(object)?;
+        if objects[..i].iter().any(|o| o.id == object.id) {
+            return Err(Error::InvalidInput(format!("Duplicate object id: {}", object.id)));
+        }
+    }
+    Ok(())
+}
+
+fn placements_path(app: &AppHandle, icao: &str) -> PathBuf {
+    find_mods_root(app).join(PLACEMENTS_DIR).join(format!("{}.json", icao))
+}
+
+fn read_file(path: &Path) -> Result<AirportPlacements> {
+    if !path.exists() {
+        return Ok(AirportPlacements::default());
+    }
+    let content = fs::read_to_string(path).map_err(|e| Error::Io(format!("Failed to read placements: {}", e)))?;
+    serde_json::from_str(&content).map_err(|e| Error::Internal(format!("Failed to parse placements: {}", e)))
+}
+
+/// Objects placed at an airport (none if it has no placements file)
+pub fn get_objects(app: &AppHandle, icao: &str) -> Result<Vec<StaticObject>> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    Ok(read_file(&placements_path(app, &icao))?.objects)
+}
+
+/// Replace every object placed at an airport
+pub fn set_objects(app: &AppHandle, icao: &str, mut objects: Vec<StaticObject>) -> Result<Vec<StaticObject>> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    for object in objects.iter_mut() {
+        object.heading = object.heading.rem_euclid(360.0);
+    }
+    validate_all(&objects)?;
+
+    let path = placements_path(app, &icao);
+    if objects.is_empty() {
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| Error::Io(format!("Failed to remove placements: {}", e)))?;
+        }
+    } else {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| Error::Io(format!("Failed to create placements folder: {}", e)))?;
+        }
+        let content = serde_json::to_string_pretty(&AirportPlacements {
+            objects: objects.clone(),
+        })
+        .map_err(|e| Error::Internal(format!("Failed to serialize placements: {}", e)))?;
+        fs::write(&path, content).map_err(|e| Error::Io(format!("Failed to write placements: {}", e)))?;
+    }
+
+    info!("[Placements] {} object(s) placed at {}", objects.len(), icao);
+    let _ = app.emit(
+        "static-objects-changed",
+        &PlacementsChanged {
+            icao,
+            objects: objects.clone(),
+        },
+    );
+    Ok(objects)
+}
+
+/// Place an object at an airport, replacing the object with the same id
+pub fn save_object(app: &AppHandle, icao: &str, object: StaticObject) -> Result<Vec<StaticObject>> {
+    validate(&object)?;
+    let mut objects = get_objects(app, icao)?;
+    match objects.iter_mut().find(|o| o.id == object.id) {
+        Some(existing) => *existing = object,
+        None => objects.push(object),
+    }
+    set_objects(app, icao, objects)
+}
+
+/// Remove an object from an airport
+pub fn delete_object(app: &AppHandle, icao: &str, id: &str) -> Result<Vec<StaticObject>> {
+    let mut objects = get_objects(app, icao)?;
+    let count = objects.len();
+    objects.retain(|o| o.id != id);
+    if objects.len() == count {
+        return Err(Error::NotFound(format!("No object {} at {}", id, icao.to_uppercase())));
+    }
+    set_objects(app, icao, objects)
+}
+
+fn collect_models(root: &Path, dir: &Path, models: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_models(root, &path, models);
+        } else if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("glb")) {
+            if let Ok(relative) = path.strip_prefix(root) {
+                models.push(relative.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+}
+
+/// Models available in `mods/objects/` (paths relative to it, sorted)
+pub fn list_models(app: &AppHandle) -> Vec<String> {
+    let root = find_mods_root(app).join(MODELS_DIR);
+    let mut models = Vec::new();
+    collect_models(&root, &root, &mut models);
+    models.sort();
+    models
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// List the static object models in mods/objects
+#[tauri::command]
+pub fn list_static_object_models(app: AppHandle) -> Vec<String> {
+    list_models(&app)
+}
+
+/// Get the static objects placed at an airport
+#[tauri::command]
+pub fn get_static_objects(app: AppHandle, icao: String) -> Result<Vec<StaticObject>> {
+    get_objects(&app, &icao)
+}
+
+/// Place (or move) a static object at an airport
+#[tauri::command]
+pub fn save_static_object(app: AppHandle, icao: String, object: StaticObject) -> Result<Vec<StaticObject>> {
+    save_object(&app, &icao, object)
+}
+
+/// Remove a static object from an airport
+#[tauri::command]
+pub fn delete_static_object(app: AppHandle, icao: String, id: String) -> Result<Vec<StaticObject>> {
+    delete_object(&app, &icao, &id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(id: &str, model: &str) -> StaticObject {
+        StaticObject {
+            id: id.to_string(),
+            model: model.to_string(),
+            name: None,
+            latitude: 42.3656,
+            longitude: -71.0096,
+            heading: 270.0,
+            height_m: 0.0,
+            scale: 1.0,
+        }
+    }
+
+    #[test]
+    fn validates_objects() {
+        assert!(validate(&object("logo", "boards/logo.glb")).is_ok());
+        assert!(validate(&object("logo board", "boards/logo.glb")).is_err());
+        assert!(validate(&object("logo", "../towers/KBOS/model.glb")).is_err());
+        assert!(validate(&object("logo", "boards/logo.gltf")).is_err());
+
+        let mut far = object("logo", "boards/logo.glb");
+        far.latitude = 95.0;
+        assert!(validate(&far).is_err());
+
+        assert!(validate_all(&[object("a", "a.glb"), object("b", "a.glb")]).is_ok());
+        assert!(validate_all(&[object("a", "a.glb"), object("a", "b.glb")]).is_err());
+    }
+
+    #[test]
+    fn parses_placements_with_defaults() {
+        let placements: AirportPlacements = serde_json::from_str(
+            r#"{"objects":[{"id":"logo","model":"logo.glb","latitude":42.36,"longitude":-71.0}]}"#,
+        )
+        .unwrap();
+        assert_eq!(placements.objects[0].scale, 1.0);
+        assert_eq!(placements.objects[0].heading, 0.0);
+    }
+}
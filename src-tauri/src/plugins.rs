@@ -0,0 +1,282 @@
+//! Plugin-provided HTTP routes
+//!
+//! Plugins are folders in `mods/plugins/` whose `manifest.json` declares
+//! routes the backend serves under `/api/ext/{plugin}/...`, so facility
+//! panels (a local gate chart, a staffing board) can ship as mods. A route
+//! serves one file, a folder of files, or inline JSON:
+//!
+//! ```json
+//! {
+//!   "name": "KBOS gate chart",
+//!   "routes": [
+//!     { "path": "chart", "file": "chart.html" },
+//!     { "path": "assets", "dir": "assets" },
+//!     { "path": "gates.json", "json": { "terminals": ["A", "B", "C", "E"] } }
+//!   ]
+//! }
+//! ```
+//!
+//! `dir` routes serve the folder's files below the route path and its
+//! `index.html` at the route path itself. Files are resolved inside the
+//! plugin folder like every other served file (see `safe_path`).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::AppHandle;
+use tracing::warn;
+
+use crate::find_mods_root;
+use crate::safe_path;
+
+/// A route declared by a plugin manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginRoute {
+    /// Path below /api/ext/{plugin}/ (e.g., "chart" or "data/gates.json")
+    pub path: String,
+    /// File to serve (relative to the plugin folder)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// Folder to serve (relative to the plugin folder)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+    /// JSON to return
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json: Option<Value>,
+}
+
+/// Plugin manifest (mods/plugins/{id}/manifest.json)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginManifest {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub routes: Vec<PluginRoute>,
+}
+
+/// Route entry returned by /api/ext
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginRouteInfo {
+    /// e.g., "/api/ext/kbos-gates/chart"
+    pub url: String,
+    /// "file", "dir" or "json"
+    pub kind: String,
+}
+
+/// Plugin entry returned by /api/ext
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginInfo {
+    pub id: String,
+    pub name: String,
+    pub author: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    pub routes: Vec<PluginRouteInfo>,
+    /// Manifest or route error (the plugin serves nothing)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// What a request resolves to
+#[derive(Debug, Clone, PartialEq)]
+pub enum PluginResponse {
+    /// File path relative to the plugin folder
+    File(String),
+    Json(Value),
+}
+
+/// Get the plugins mods directory
+pub fn plugins_root(app: &AppHandle) -> PathBuf {
+    find_mods_root(app).join("plugins")
+}
+
+/// Plugin ids are folder names: letters, digits, `-` and `_`
+pub fn valid_plugin_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 64
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn kind(route: &PluginRoute) -> Option<&'static str> {
+    match (&route.file, &route.dir, &route.json) {
+        (Some(_), None, None) => Some("file"),
+        (None, Some(_), None) => Some("dir"),
+        (None, None, Some(_)) => Some("json"),
+        _ => None,
+    }
+}
+
+/// Check a manifest's routes: valid relative paths, one target each, no duplicates
+pub fn validate(manifest: &PluginManifest) -> Result<(), String> {
+    for (i, route) in manifest.routes.iter().enumerate() {
+        if !safe_path::valid_relative(&route.path) {
+            return Err(format!("Invalid route path: {:?}", route.path));
+        }
+        if kind(route).is_none() {
+            return Err(format!("Route {} needs exactly one of file, dir or json", route.path));
+        }
+        let target = route.file.as_ref().or(route.dir.as_ref());
+        if target.is_some_and(|t| !safe_path::valid_relative(t)) {
+            return Err(format!("Invalid target for route {}", route.path));
+        }
+        if manifest.routes[..i].iter().any(|r| r.path == route.path) {
+            return Err(format!("Duplicate route: {}", route.path));
+        }
+    }
+    Ok(())
+}
+
+/// Resolve a request path against a plugin's routes (exact routes win over folders)
+pub fn match_route(routes: &[PluginRoute], path: &str) -> Option<PluginResponse> {
+    if let Some(route) = routes.iter().find(|r| r.path == path) {
+        if let Some(file) = &route.file {
+            return Some(PluginResponse::File(file.clone()));
+        }
+        if let Some(json) = &route.json {
+            return Some(PluginResponse::Json(json.clone()));
+        }
+        if let Some(dir) = &route.dir {
+            return Some(PluginResponse::File(format!("{}/index.html", dir)));
+        }
+    }
+    // Longest folder route containing the path
+    routes
+        .iter()
+        .filter_map(|r| {
+            let dir = r.dir.as_ref()?;
+            let rest = path.strip_prefix(&r.path)?.strip_prefix('/')?;
+            Some((r.path.len(), format!("{}/{}", dir, rest)))
+        })
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, file)| PluginResponse::File(file))
+}
+
+fn read_manifest(dir: &Path) -> Result<PluginManifest, String> {
+    let content =
+        fs::read_to_string(dir.join("manifest.json")).map_err(|e| format!("Failed to read manifest: {}", e))?;
+    let manifest: PluginManifest =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid manifest: {}", e))?;
+    validate(&manifest)?;
+    Ok(manifest)
+}
+
+/// Load a plugin's manifest (None if it isn't installed or its manifest is invalid)
+pub fn load(app: &AppHandle, id: &str) -> Option<PluginManifest> {
+    if !valid_plugin_id(id) {
+        return None;
+    }
+    match read_manifest(&plugins_root(app).join(id)) {
+        Ok(manifest) => Some(manifest),
+        Err(e) => {
+            warn!("[Plugins] {}: {}", id, e);
+            None
+        }
+    }
+}
+
+/// List installed plugins and their routes
+pub fn list(app: &AppHandle) -> Vec<PluginInfo> {
+    let mut plugins: Vec<PluginInfo> = fs::read_dir(plugins_root(app))
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| e.file_name().into_string().ok())
+        .filter(|id| valid_plugin_id(id))
+        .map(|id| {
+            let (manifest, error) = match read_manifest(&plugins_root(app).join(&id)) {
+                Ok(manifest) => (manifest, None),
+                Err(e) => (PluginManifest::default(), Some(e)),
+            };
+            PluginInfo {
+                name: manifest.name.clone().unwrap_or_else(|| id.clone()),
+                routes: manifest
+                    .routes
+                    .iter()
+                    .filter_map(|r| {
+                        Some(PluginRouteInfo {
+                            url: format!("/api/ext/{}/{}", id, r.path),
+                            kind: kind(r)?.to_string(),
+                        })
+                    })
+                    .collect(),
+                id,
+                author: manifest.author,
+                version: manifest.version,
+                description: manifest.description,
+                error,
+            }
+        })
+        .collect();
+    plugins.sort_by(|a, b| a.id.cmp(&b.id));
+    plugins
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// List plugins in mods/plugins and the routes they serve
+#[tauri::command]
+pub fn list_plugins(app: AppHandle) -> Vec<PluginInfo> {
+    list(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn manifest(routes: Value) -> PluginManifest {
+        serde_json::from_value(json!({ "name": "Test", "routes": routes })).unwrap()
+    }
+
+    #[test]
+    fn validates_routes() {
+        assert!(validate(&manifest(json!([
+            { "path": "chart", "file": "chart.html" },
+            { "path": "assets", "dir": "www/assets" },
+            { "path": "data/gates.json", "json": [] }
+        ])))
+        .is_ok());
+        assert!(validate(&manifest(json!([{ "path": "../x", "file": "a" }]))).is_err());
+        assert!(validate(&manifest(json!([{ "path": "x", "file": "../../secret" }]))).is_err());
+        assert!(validate(&manifest(json!([{ "path": "x", "file": "a", "json": 1 }]))).is_err());
+        assert!(validate(&manifest(json!([{ "path": "x" }]))).is_err());
+        assert!(validate(&manifest(json!([{ "path": "x", "file": "a" }, { "path": "x", "json": 1 }]))).is_err());
+    }
+
+    #[test]
+    fn matches_requests_to_routes() {
+        let routes = manifest(json!([
+            { "path": "chart", "file": "chart.html" },
+            { "path": "panel", "dir": "www" },
+            { "path": "panel/data", "dir": "data" },
+            { "path": "panel/config.json", "json": { "refresh": 30 } }
+        ]))
+        .routes;
+
+        assert_eq!(match_route(&routes, "chart"), Some(PluginResponse::File("chart.html".into())));
+        assert_eq!(match_route(&routes, "panel"), Some(PluginResponse::File("www/index.html".into())));
+        assert_eq!(match_route(&routes, "panel/app.js"), Some(PluginResponse::File("www/app.js".into())));
+        assert_eq!(match_route(&routes, "panel/data/a.json"), Some(PluginResponse::File("data/a.json".into())));
+        assert_eq!(
+            match_route(&routes, "panel/config.json"),
+            Some(PluginResponse::Json(json!({ "refresh": 30 })))
+        );
+        assert_eq!(match_route(&routes, "chart/x"), None);
+        assert_eq!(match_route(&routes, "panelx"), None);
+    }
+}
@@ -0,0 +1,209 @@
+//! Child process trees
+//!
+//! External tools the app runs in the background (the FSLTL converter) start
+//! helpers of their own (gltf-transform, texture tools), so cancelling one has
+//! to stop everything it started, not just the direct child. A
+//! [`ProcessTree`] owns the child and its descendants:
+//!
+//! - Windows: the child is assigned to a job object with
+//!   `JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE`, and closing the job ends every
+//!   process in it.
+//! - Unix (Linux/macOS): the child leads a new process group, which its
+//!   helpers inherit, and the whole group is sent `SIGKILL`.
+//!
+//! The tree is killed on [`ProcessTree::kill`] or when it is dropped (e.g.,
+//! when the main window closes).
+
+use std::process::{Child, Command};
+
+#[cfg(windows)]
+use std::os::windows::io::AsRawHandle;
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+#[cfg(unix)]
+use std::os::unix::process::CommandExt;
+
+#[cfg(windows)]
+use windows_sys::Win32::Foundation::CloseHandle;
+#[cfg(windows)]
+use windows_sys::Win32::System::JobObjects::{
+    AssignProcessToJobObject, CreateJobObjectW, JobObjectExtendedLimitInformation, SetInformationJobObject,
+    JOBOBJECT_EXTENDED_LIMIT_INFORMATION, JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+};
+
+/// Hide the console window of console programs (CREATE_NO_WINDOW)
+#[cfg(windows)]
+const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+/// Job object handle; closing it terminates every process in the job
+#[cfg(windows)]
+struct Job(*mut std::ffi::c_void);
+
+// Job handles are thread-safe kernel objects, safe to send between threads
+#[cfg(windows)]
+unsafe impl Send for Job {}
+
+#[cfg(windows)]
+impl Job {
+    /// Create a job that kills its processes on close and assign `child` to it
+    fn for_child(child: &Child) -> Result<Self, String> {
+        let handle = unsafe { CreateJobObjectW(std::ptr::null(), std::ptr::null()) };
+        if handle.is_null() {
+            return Err("Failed to create job object".to_string());
+        }
+        // Closes the handle on the error paths below
+        let job = Job(handle);
+
+        let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = unsafe { std::mem::zeroed() };
+        info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+        let success = unsafe {
+            SetInformationJobObject(
+                job.0,
+                JobObjectExtendedLimitInformation,
+                &info as *const _ as *const _,
+                std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+            )
+        };
+        if success == 0 {
+            return Err("Failed to configure job object".to_string());
+        }
+
+        if unsafe { AssignProcessToJobObject(job.0, child.as_raw_handle()) } == 0 {
+            return Err("Failed to assign process to job object".to_string());
+        }
+        Ok(job)
+    }
+}
+
+#[cfg(windows)]
+impl Drop for Job {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            unsafe { CloseHandle(self.0) };
+        }
+    }
+}
+
+/// A child process and everything it starts
+pub struct ProcessTree {
+    child: Child,
+    #[cfg(windows)]
+    job: Option<Job>,
+    killed: bool,
+}
+
+impl ProcessTree {
+    /// Start `cmd` as the root of its own process tree
+    pub fn spawn(cmd: &mut Command) -> Result<Self, String> {
+        #[cfg(windows)]
+        cmd.creation_flags(CREATE_NO_WINDOW);
+        // Helpers inherit the group, so they can be signalled together
+        #[cfg(unix)]
+        cmd.process_group(0);
+
+        let child = cmd.spawn().map_err(|e| e.to_string())?;
+
+        #[cfg(windows)]
+        let job = match Job::for_child(&child) {
+            Ok(job) => Some(job),
+            Err(e) => {
+                let mut child = child;
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(e);
+            }
+        };
+
+        Ok(Self {
+            child,
+            #[cfg(windows)]
+            job,
+            killed: false,
+        })
+    }
+
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Whether the process (the root of the tree) is still running
+    pub fn is_running(&mut self) -> bool {
+        !self.killed && matches!(self.child.try_wait(), Ok(None))
+    }
+
+    /// Kill the process and everything it started, and wait for it to exit
+    pub fn kill(&mut self) {
+        if self.killed {
+            return;
+        }
+        self.killed = true;
+
+        #[cfg(windows)]
+        {
+            // Closing the job terminates every process in it
+            drop(self.job.take());
+        }
+        #[cfg(unix)]
+        {
+            // The child isn't reaped yet, so its pid still names the group
+            let group = self.child.id() as libc::pid_t;
+            if unsafe { libc::killpg(group, libc::SIGKILL) } != 0 {
+                let _ = self.child.kill();
+            }
+        }
+        #[cfg(not(any(windows, unix)))]
+        {
+            let _ = self.child.kill();
+        }
+
+        let _ = self.child.wait();
+    }
+}
+
+impl Drop for ProcessTree {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    /// Whether a process is still running (zombies waiting to be reaped count as gone)
+    fn alive(pid: u32) -> bool {
+        std::fs::read_to_string(format!("/proc/{}/stat", pid))
+            .ok()
+            .and_then(|stat| stat.rsplit(')').next().map(|rest| !rest.trim_start().starts_with('Z')))
+            .unwrap_or(false)
+    }
+
+    #[test]
+    fn kills_the_whole_group() {
+        // The shell starts a grandchild in the same group and reports its pid
+        let dir = std::env::temp_dir().join(format!("towercab-process-tree-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let pid_file = dir.join("grandchild.pid");
+        let script = format!("sleep 30 & echo $! > '{}'; wait", pid_file.display());
+        let mut tree = ProcessTree::spawn(Command::new("sh").args(["-c", &script])).unwrap();
+
+        let started = Instant::now();
+        let grandchild = loop {
+            if let Some(pid) = std::fs::read_to_string(&pid_file).ok().and_then(|s| s.trim().parse().ok()) {
+                break pid;
+            }
+            assert!(started.elapsed() < Duration::from_secs(5), "grandchild didn't start");
+            std::thread::sleep(Duration::from_millis(20));
+        };
+        assert!(alive(grandchild));
+
+        tree.kill();
+        let killed = Instant::now();
+        while alive(grandchild) && killed.elapsed() < Duration::from_secs(5) {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert!(!alive(grandchild));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,277 @@
+//! Access for facility staff viewing the cab from outside the LAN, without
+//! port forwarding the unauthenticated LAN server
+//!
+//! - Overlay networks: Tailscale addresses (100.64.0.0/10 and
+//!   fd7a:115c:a1e0::/48) and extra CIDR ranges (e.g. a WireGuard subnet)
+//!   count as local network when `server.requireLocalNetwork` is set
+//! - Client certificates: a second HTTPS listener that only completes the TLS
+//!   handshake for clients presenting a certificate signed by the configured
+//!   CA. Requests on it count as authenticated
+
+use std::fs::File;
+use std::io::BufReader;
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::Arc;
+
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use serde::{Deserialize, Serialize};
+
+use crate::lan;
+
+/// Remote access settings within the server settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteAccessSettings {
+    /// Treat Tailscale addresses as local network
+    #[serde(default)]
+    pub trust_tailscale: bool,
+    /// Extra CIDR ranges treated as local network (e.g. "10.8.0.0/24" for WireGuard)
+    #[serde(default)]
+    pub trusted_networks: Vec<String>,
+    #[serde(default)]
+    pub client_cert: ClientCertSettings,
+}
+
+/// HTTPS listener requiring client certificates (mTLS)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientCertSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_client_cert_port")]
+    pub port: u16,
+    /// PEM server certificate chain
+    #[serde(default)]
+    pub cert_path: String,
+    /// PEM server private key
+    #[serde(default)]
+    pub key_path: String,
+    /// PEM CA certificate(s) client certificates must be signed by
+    #[serde(default)]
+    pub client_ca_path: String,
+}
+
+fn default_client_cert_port() -> u16 {
+    8766
+}
+
+impl Default for ClientCertSettings {
+    fn default() -> Self {
+        ClientCertSettings {
+            enabled: false,
+            port: default_client_cert_port(),
+            cert_path: String::new(),
+            key_path: String::new(),
+            client_ca_path: String::new(),
+        }
+    }
+}
+
+/// Request extension marking requests that arrived on the client certificate listener
+#[derive(Debug, Clone, Copy)]
+pub struct ClientCertConnection;
+
+/// An IP network in CIDR notation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IpNet {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl IpNet {
+    /// Parse "a.b.c.d/n" or "v6::/n" (a bare address is a single host)
+    pub fn parse(text: &str) -> Option<IpNet> {
+        let text = text.trim();
+        let (addr, prefix) = match text.split_once('/') {
+            Some((addr, prefix)) => (addr.parse::<IpAddr>().ok()?, Some(prefix.parse::<u8>().ok()?)),
+            None => (text.parse::<IpAddr>().ok()?, None),
+        };
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        IpNet::new(addr, prefix.unwrap_or(max))
+    }
+
+    /// Network of `prefix` bits around an address, `None` if the prefix is too long
+    pub fn new(addr: IpAddr, prefix: u8) -> Option<IpNet> {
+        let max = if addr.is_ipv4() { 32 } else { 128 };
+        (prefix <= max).then_some(IpNet { addr, prefix })
+    }
+
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        match (self.addr, ip) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or(0);
+                u32::from(net) & mask == u32::from(*ip) & mask
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix as u32).unwrap_or(0);
+                u128::from(net) & mask == u128::from(*ip) & mask
+            }
+            // IPv4-mapped IPv6 peers (dual-stack sockets)
+            (IpAddr::V4(_), IpAddr::V6(ip)) => ip.to_ipv4_mapped().is_some_and(|v4| self.contains(&IpAddr::V4(v4))),
+            _ => false,
+        }
+    }
+}
+
+/// Tailscale's address ranges (CGNAT space and its IPv6 ULA prefix)
+const TAILSCALE_NETWORKS: [&str; 2] = ["100.64.0.0/10", "fd7a:115c:a1e0::/48"];
+
+/// Whether an address is in Tailscale's ranges
+pub fn is_tailscale_ip(ip: &IpAddr) -> bool {
+    TAILSCALE_NETWORKS
+        .iter()
+        .filter_map(|net| IpNet::parse(net))
+        .any(|net| net.contains(ip))
+}
+
+/// Overlay networks treated like the local network
+#[derive(Debug, Clone, Default)]
+pub struct TrustedNetworks {
+    tailscale: bool,
+    networks: Vec<IpNet>,
+}
+
+impl TrustedNetworks {
+    /// From settings; unparseable ranges are skipped with a warning
+    pub fn from_settings(settings: &RemoteAccessSettings) -> Self {
+        let networks = settings
+            .trusted_networks
+            .iter()
+            .filter_map(|text| {
+                let net = IpNet::parse(text);
+                if net.is_none() {
+                    tracing::warn!("[RemoteAccess] Ignoring invalid trusted network {:?}", text);
+                }
+                net
+            })
+            .collect();
+        TrustedNetworks {
+            tailscale: settings.trust_tailscale,
+            networks,
+        }
+    }
+
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        (self.tailscale && is_tailscale_ip(ip)) || self.networks.iter().any(|net| net.contains(ip))
+    }
+
+    /// Whether a client is on the local network or a trusted one (`server.requireLocalNetwork`)
+    pub fn allows_client(&self, ip: &IpAddr) -> bool {
+        lan::is_local_network_ip(ip) || self.contains(ip)
+    }
+
+    /// Whether a browser origin host is on a trusted network (an address, or a Tailscale MagicDNS name)
+    pub fn allows_origin_host(&self, host: &str) -> bool {
+        if self.tailscale && host.ends_with(".ts.net") {
+            return true;
+        }
+        host.trim_matches(['[', ']'])
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| self.contains(&ip))
+    }
+}
+
+fn open_pem(path: &str, what: &str) -> Result<BufReader<File>, String> {
+    if path.is_empty() {
+        return Err(format!("No {} file configured", what));
+    }
+    File::open(Path::new(path))
+        .map(BufReader::new)
+        .map_err(|e| format!("Failed to open {} {}: {}", what, path, e))
+}
+
+/// TLS config for the client certificate listener: serves the configured
+/// certificate and rejects clients without a certificate signed by the CA
+pub fn tls_config(settings: &ClientCertSettings) -> Result<Arc<ServerConfig>, String> {
+    let certs = rustls_pemfile::certs(&mut open_pem(&settings.cert_path, "certificate")?)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Invalid certificate: {}", e))?;
+    if certs.is_empty() {
+        return Err(format!("No certificates found in {}", settings.cert_path));
+    }
+    let key = rustls_pemfile::private_key(&mut open_pem(&settings.key_path, "private key")?)
+        .map_err(|e| format!("Invalid private key: {}", e))?
+        .ok_or_else(|| format!("No private key found in {}", settings.key_path))?;
+
+    let mut roots = RootCertStore::empty();
+    for ca in rustls_pemfile::certs(&mut open_pem(&settings.client_ca_path, "client CA")?) {
+        let ca = ca.map_err(|e| format!("Invalid client CA: {}", e))?;
+        roots.add(ca).map_err(|e| format!("Invalid client CA: {}", e))?;
+    }
+    if roots.is_empty() {
+        return Err(format!("No CA certificates found in {}", settings.client_ca_path));
+    }
+    let verifier = WebPkiClientVerifier::builder(Arc::new(roots))
+        .build()
+        .map_err(|e| format!("Invalid client CA: {}", e))?;
+
+    let mut config = ServerConfig::builder()
+        .with_client_cert_verifier(verifier)
+        .with_single_cert(certs, key)
+        .map_err(|e| format!("Invalid certificate or key: {}", e))?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    Ok(Arc::new(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(text: &str) -> IpAddr {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn matches_cidr_ranges() {
+        let wireguard = IpNet::parse("10.8.0.0/24").unwrap();
+        assert!(wireguard.contains(&ip("10.8.0.17")));
+        assert!(!wireguard.contains(&ip("10.8.1.1")));
+        assert!(wireguard.contains(&ip("::ffff:10.8.0.2")));
+        assert!(IpNet::parse("0.0.0.0/0").unwrap().contains(&ip("8.8.8.8")));
+        assert!(IpNet::parse("203.0.113.5").unwrap().contains(&ip("203.0.113.5")));
+        assert!(!IpNet::parse("203.0.113.5").unwrap().contains(&ip("203.0.113.6")));
+        for bad in ["10.8.0.0/33", "10.8.0/24", "fe80::/129", "host/8", ""] {
+            assert_eq!(IpNet::parse(bad), None, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn trusts_tailscale_only_when_enabled() {
+        assert!(is_tailscale_ip(&ip("100.101.102.103")));
+        assert!(is_tailscale_ip(&ip("fd7a:115c:a1e0::1")));
+        assert!(!is_tailscale_ip(&ip("100.128.0.1")));
+
+        let mut settings = RemoteAccessSettings {
+            trusted_networks: vec!["10.8.0.0/24".to_string(), "nonsense".to_string()],
+            ..Default::default()
+        };
+        let trusted = TrustedNetworks::from_settings(&settings);
+        assert!(!trusted.contains(&ip("100.101.102.103")));
+        assert!(trusted.contains(&ip("10.8.0.3")));
+        assert!(!trusted.allows_origin_host("cab.tailnet-1234.ts.net"));
+
+        settings.trust_tailscale = true;
+        let trusted = TrustedNetworks::from_settings(&settings);
+        assert!(trusted.contains(&ip("100.101.102.103")));
+        assert!(trusted.allows_origin_host("cab.tailnet-1234.ts.net"));
+        assert!(trusted.allows_origin_host("[fd7a:115c:a1e0::1]"));
+        assert!(!trusted.allows_origin_host("example.com"));
+    }
+
+    #[test]
+    fn admits_tailscale_clients_only_when_trusted() {
+        let mut settings = RemoteAccessSettings::default();
+        let trusted = TrustedNetworks::from_settings(&settings);
+        assert!(trusted.allows_client(&ip("192.168.1.5")));
+        assert!(trusted.allows_client(&ip("fd12:3456:789a::20")));
+        assert!(!trusted.allows_client(&ip("fd7a:115c:a1e0::5")));
+        assert!(!trusted.allows_client(&ip("100.101.102.103")));
+
+        settings.trust_tailscale = true;
+        let trusted = TrustedNetworks::from_settings(&settings);
+        assert!(trusted.allows_client(&ip("fd7a:115c:a1e0::5")));
+        assert!(trusted.allows_client(&ip("100.101.102.103")));
+    }
+}
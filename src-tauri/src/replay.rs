@@ -0,0 +1,736 @@
+//! Replay server mode
+//!
+//! Plays a recorded session (a replay export file, the same format the
+//! renderer exports) back from the host so every client sees it in lockstep.
+//! Snapshots are emitted as `replay-snapshot` when playback reaches them and
+//! relayed to remote browsers over `/api/replay/ws` together with
+//! `replay-status` changes (load, play, pause, seek, speed, stop). Clients
+//! render the snapshots exactly as they would a live VATSIM update.
+//!
+//! The traffic task also records every poll into a rolling buffer (length set
+//! in the global settings), so the last few minutes can be replayed to every
+//! client on demand. Instant replays return clients to live traffic when they
+//! catch up with the end of the buffer.
+//! Scheduled server windows can also record every poll while they are open
+//! (see `server_schedule`), without the buffer's length limit.
+//!
+//! Displays that render the replay themselves fetch the whole recording once
+//! (`get_replay_recording`, `GET /api/replay/recording`) and place their
+//! playback clock from the latest status: `position` plus the host time
+//! elapsed since `real_time`, times `speed`. Play, pause, seek and speed
+//! changes from any display go through `control`, so a wall display and the
+//! tablets around it stay on the same recorded second.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime};
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::info;
+
+use crate::debug_stats;
+use crate::replay_library;
+use crate::error::{Error, Result};
+use crate::supervisor::{self, Restart};
+use crate::traffic::{Aircraft, TrafficSnapshot};
+
+/// Replay export format version understood by the server
+const REPLAY_VERSION: u32 = 1;
+
+/// Playback speeds (matches the renderer's replay controls)
+pub const PLAYBACK_SPEEDS: [f64; 4] = [0.5, 1.0, 2.0, 4.0];
+
+/// Instant replay length when none is given (minutes)
+const INSTANT_REPLAY_DEFAULT_MINUTES: u32 = 2;
+
+/// Longest instant replay buffer (minutes)
+const INSTANT_REPLAY_MAX_MINUTES: u32 = 60;
+
+/// Most snapshots kept by a window recording (over 40 hours at 15 s polls)
+const RECORDING_MAX_SNAPSHOTS: usize = 10_000;
+
+/// How often the playback task checks for the next snapshot
+const TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Snapshot of VATSIM state at a point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplaySnapshot {
+    /// Local time the snapshot was captured (Unix ms)
+    pub timestamp: u64,
+    #[serde(default)]
+    pub vatsim_timestamp: u64,
+    /// Aircraft states, passed through to clients untouched
+    #[serde(default)]
+    pub aircraft_states: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub last_update_interval: u64,
+}
+
+/// Replay export file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayFile {
+    pub version: u32,
+    #[serde(default)]
+    pub export_date: String,
+    #[serde(default)]
+    pub app_version: String,
+    #[serde(default)]
+    pub airport: Option<String>,
+    pub snapshots: Vec<ReplaySnapshot>,
+}
+
+/// Playback control from a client; unset fields are left unchanged
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayControl {
+    pub playing: Option<bool>,
+    pub speed: Option<f64>,
+    /// Seek to this recorded time (Unix ms)
+    pub position: Option<u64>,
+}
+
+/// Current replay state
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayStatus {
+    pub loaded: bool,
+    pub airport: Option<String>,
+    pub playing: bool,
+    pub speed: f64,
+    /// First and last recorded times (Unix ms)
+    pub start_time: u64,
+    pub end_time: u64,
+    /// Current recorded time (Unix ms)
+    pub position: u64,
+    pub snapshot_count: usize,
+    /// Replaying the instant replay buffer (ends by returning to live traffic)
+    pub instant: bool,
+    /// Real time when this was produced (Unix ms), to extrapolate the position
+    pub real_time: u64,
+}
+
+/// Replay event for WebSocket relay
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ReplayEvent {
+    Status(ReplayStatus),
+    Snapshot(ReplaySnapshot),
+}
+
+/// Loaded session and playback position
+struct ReplaySession {
+    airport: Option<String>,
+    snapshots: Vec<ReplaySnapshot>,
+    playing: bool,
+    speed: f64,
+    /// Recorded time at `anchor` (Unix ms)
+    anchor_position: u64,
+    anchor: Instant,
+    /// Index of the last snapshot sent to clients
+    emitted: Option<usize>,
+    instant: bool,
+}
+
+impl ReplaySession {
+    /// New session, paused at the start of the recording
+    fn new(airport: Option<String>, snapshots: Vec<ReplaySnapshot>, instant: bool) -> Self {
+        let mut session = Self {
+            airport,
+            snapshots,
+            playing: false,
+            speed: 1.0,
+            anchor_position: 0,
+            anchor: Instant::now(),
+            emitted: None,
+            instant,
+        };
+        session.set_position(session.start_time());
+        session
+    }
+
+    fn start_time(&self) -> u64 {
+        self.snapshots.first().map_or(0, |s| s.timestamp)
+    }
+
+    fn end_time(&self) -> u64 {
+        self.snapshots.last().map_or(0, |s| s.timestamp)
+    }
+
+    fn position(&self) -> u64 {
+        if !self.playing {
+            return self.anchor_position;
+        }
+        advance(self.anchor_position, self.anchor.elapsed(), self.speed).min(self.end_time())
+    }
+
+    /// Re-anchor the playback position at the current instant
+    fn set_position(&mut self, position: u64) {
+        self.anchor_position = position.clamp(self.start_time(), self.end_time());
+        self.anchor = Instant::now();
+    }
+
+    fn status(&self) -> ReplayStatus {
+        ReplayStatus {
+            loaded: true,
+            airport: self.airport.clone(),
+            playing: self.playing,
+            speed: self.speed,
+            start_time: self.start_time(),
+            end_time: self.end_time(),
+            position: self.position(),
+            snapshot_count: self.snapshots.len(),
+            instant: self.instant,
+            real_time: now_ms(),
+        }
+    }
+}
+
+/// Rolling buffer of recent traffic at the active airport
+#[derive(Default)]
+struct ReplayBuffer {
+    icao: Option<String>,
+    snapshots: VecDeque<ReplaySnapshot>,
+}
+
+/// Replay state (managed by Tauri)
+pub struct ReplayState {
+    session: RwLock<Option<ReplaySession>>,
+    buffer: RwLock<ReplayBuffer>,
+    /// Open-ended recording (scheduled windows, events), when started
+    recording: RwLock<Option<ReplayBuffer>>,
+    /// Replay events for WebSocket relay
+    pub events: broadcast::Sender<ReplayEvent>,
+}
+
+impl ReplayState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            session: RwLock::new(None),
+            buffer: RwLock::new(ReplayBuffer::default()),
+            recording: RwLock::new(None),
+            events,
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Recorded time after playing for `elapsed` real time at `speed`
+fn advance(position: u64, elapsed: Duration, speed: f64) -> u64 {
+    position + (elapsed.as_millis() as f64 * speed) as u64
+}
+
+/// Index of the snapshot shown at a recorded time (the last one at or before it)
+fn snapshot_index_at(snapshots: &[ReplaySnapshot], position: u64) -> Option<usize> {
+    snapshots.partition_point(|s| s.timestamp <= position).checked_sub(1)
+}
+
+/// Drop snapshots older than `keep_ms` before the newest one
+fn trim_buffer(snapshots: &mut VecDeque<ReplaySnapshot>, keep_ms: u64) {
+    let Some(newest) = snapshots.back().map(|s| s.timestamp) else {
+        return;
+    };
+    while snapshots.front().is_some_and(|s| s.timestamp + keep_ms < newest) {
+        snapshots.pop_front();
+    }
+}
+
+/// Aircraft in the renderer's serialized aircraft state format (altitude in meters)
+fn aircraft_state(aircraft: &Aircraft, timestamp: u64) -> serde_json::Value {
+    serde_json::json!({
+        "callsign": aircraft.callsign,
+        "cid": aircraft.cid,
+        "latitude": aircraft.latitude,
+        "longitude": aircraft.longitude,
+        "altitude": aircraft.altitude_ft * 0.3048,
+        "groundspeed": aircraft.groundspeed_kt,
+        "heading": aircraft.heading,
+        "groundTrack": aircraft.track,
+        "baroRate": aircraft.vertical_rate_fpm,
+        "transponder": aircraft.squawk,
+        "aircraftType": aircraft.aircraft_type,
+        "departure": aircraft.departure,
+        "arrival": aircraft.arrival,
+        "timestamp": timestamp,
+        "onGround": u8::from(aircraft.on_ground),
+    })
+}
+
+fn validate_speed(speed: f64) -> Result<f64> {
+    if PLAYBACK_SPEEDS.contains(&speed) {
+        Ok(speed)
+    } else {
+        Err(Error::invalid_input(format!("Unsupported playback speed: {}", speed)))
+    }
+}
+
+fn publish(app: &AppHandle, event: ReplayEvent) {
+    let _ = match &event {
+        ReplayEvent::Status(status) => app.emit("replay-status", status),
+        ReplayEvent::Snapshot(snapshot) => app.emit("replay-snapshot", snapshot),
+    };
+    let _ = app.state::<ReplayState>().events.send(event);
+}
+
+/// Send the snapshot for the current position if it has not been sent yet.
+/// Pauses at the end of the recording.
+fn sync(app: &AppHandle, force: bool) {
+    let (snapshot, ended, instant) = {
+        let state = app.state::<ReplayState>();
+        let mut session = state.session.write();
+        let Some(session) = session.as_mut() else {
+            return;
+        };
+        let position = session.position();
+        let index = snapshot_index_at(&session.snapshots, position);
+        let snapshot = index
+            .filter(|_| force || index != session.emitted)
+            .map(|i| session.snapshots[i].clone());
+        session.emitted = index;
+
+        let ended = session.playing && position >= session.end_time();
+        if ended {
+            session.playing = false;
+            session.set_position(position);
+        }
+        (snapshot, ended.then(|| session.status()), session.instant)
+    };
+
+    if let Some(snapshot) = snapshot {
+        publish(app, ReplayEvent::Snapshot(snapshot));
+    }
+    match ended {
+        // Caught up with live traffic
+        Some(_) if instant => {
+            stop(app);
+        }
+        Some(status) => {
+            info!("[Replay] Reached the end of the recording");
+            publish(app, ReplayEvent::Status(status));
+        }
+        None => {}
+    }
+}
+
+/// Position along a track
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackPoint {
+    /// Unix ms
+    pub time: u64,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Meters MSL (as recorded)
+    pub altitude_m: f64,
+    pub groundspeed_kt: f64,
+    pub heading: f64,
+    /// Recorded on-ground flag (missing in older exports)
+    pub on_ground: Option<bool>,
+}
+
+/// Recorded track of one aircraft
+#[derive(Debug, Clone, Default)]
+pub struct Track {
+    pub callsign: String,
+    pub aircraft_type: Option<String>,
+    pub departure: Option<String>,
+    pub arrival: Option<String>,
+    pub points: Vec<TrackPoint>,
+}
+
+fn text(state: &serde_json::Value, key: &str) -> Option<String> {
+    state[key]
+        .as_str()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+/// Group snapshot aircraft states into tracks, sorted by callsign
+pub fn build_tracks(snapshots: &[ReplaySnapshot]) -> Vec<Track> {
+    let mut tracks: BTreeMap<String, Track> = BTreeMap::new();
+    for snapshot in snapshots {
+        for state in &snapshot.aircraft_states {
+            let (Some(callsign), Some(latitude), Some(longitude)) = (
+                text(state, "callsign"),
+                state["latitude"].as_f64(),
+                state["longitude"].as_f64(),
+            ) else {
+                continue;
+            };
+            let track = tracks.entry(callsign.clone()).or_insert_with(|| Track {
+                callsign,
+                ..Default::default()
+            });
+            track.aircraft_type = track.aircraft_type.take().or_else(|| text(state, "aircraftType"));
+            track.departure = track.departure.take().or_else(|| text(state, "departure"));
+            track.arrival = track.arrival.take().or_else(|| text(state, "arrival"));
+            track.points.push(TrackPoint {
+                time: snapshot.timestamp,
+                latitude,
+                longitude,
+                altitude_m: state["altitude"].as_f64().unwrap_or(0.0),
+                groundspeed_kt: state["groundspeed"].as_f64().unwrap_or(0.0),
+                heading: state["heading"].as_f64().unwrap_or(0.0),
+                on_ground: state["onGround"].as_f64().map(|v| v >= 1.0),
+            });
+        }
+    }
+    let mut tracks: Vec<Track> = tracks.into_values().collect();
+    for track in &mut tracks {
+        track.points.sort_by_key(|p| p.time);
+        track.points.dedup_by_key(|p| p.time);
+    }
+    tracks
+}
+
+/// Current replay status
+pub fn get_status(app: &AppHandle) -> ReplayStatus {
+    app.state::<ReplayState>()
+        .session
+        .read()
+        .as_ref()
+        .map(ReplaySession::status)
+        .unwrap_or_default()
+}
+
+/// Snapshot shown at the current position, for clients joining mid-replay
+pub fn current_snapshot(app: &AppHandle) -> Option<ReplaySnapshot> {
+    let state = app.state::<ReplayState>();
+    let session = state.session.read();
+    let session = session.as_ref()?;
+    snapshot_index_at(&session.snapshots, session.position()).map(|i| session.snapshots[i].clone())
+}
+
+/// Airport and snapshots of the loaded replay
+pub fn loaded_recording(app: &AppHandle) -> Option<(Option<String>, Vec<ReplaySnapshot>)> {
+    let state = app.state::<ReplayState>();
+    let session = state.session.read();
+    session.as_ref().map(|s| (s.airport.clone(), s.snapshots.clone()))
+}
+
+/// The loaded replay as an export file, for displays that play it locally
+pub fn loaded_file(app: &AppHandle) -> Option<ReplayFile> {
+    loaded_recording(app).map(|(airport, snapshots)| recording_file(airport, snapshots))
+}
+
+/// Wrap recorded snapshots in the replay export format
+pub fn recording_file(airport: Option<String>, snapshots: Vec<ReplaySnapshot>) -> ReplayFile {
+    ReplayFile {
+        version: REPLAY_VERSION,
+        export_date: chrono::Utc::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        airport,
+        snapshots,
+    }
+}
+
+/// Airport and snapshots of the instant replay buffer
+pub fn buffered_recording(app: &AppHandle) -> (Option<String>, Vec<ReplaySnapshot>) {
+    let state = app.state::<ReplayState>();
+    let buffer = state.buffer.read();
+    (buffer.icao.clone(), buffer.snapshots.iter().cloned().collect())
+}
+
+/// Load a recorded session, paused at its start, replacing any loaded replay
+pub fn load(app: &AppHandle, mut file: ReplayFile) -> Result<ReplayStatus> {
+    if file.version != REPLAY_VERSION {
+        return Err(Error::invalid_input(format!("Unsupported replay version: {}", file.version)));
+    }
+    if file.snapshots.is_empty() {
+        return Err(Error::invalid_input("Replay contains no snapshots"));
+    }
+    file.snapshots.sort_by_key(|s| s.timestamp);
+
+    let airport = file.airport.map(|a| a.trim().to_uppercase()).filter(|a| !a.is_empty());
+    let session = ReplaySession::new(airport, file.snapshots, false);
+    let status = session.status();
+    info!(
+        "[Replay] Loaded {} snapshots ({} s){}",
+        status.snapshot_count,
+        (status.end_time - status.start_time) / 1000,
+        status
+            .airport
+            .as_deref()
+            .map(|a| format!(" at {}", a))
+            .unwrap_or_default()
+    );
+    Ok(start_session(app, session))
+}
+
+/// Make a session the loaded replay and send its first snapshot
+fn start_session(app: &AppHandle, session: ReplaySession) -> ReplayStatus {
+    let status = session.status();
+    *app.state::<ReplayState>().session.write() = Some(session);
+    publish(app, ReplayEvent::Status(status.clone()));
+    sync(app, true);
+    status
+}
+
+/// Record a traffic poll into the instant replay buffer.
+/// Called by the traffic task after every poll.
+pub fn record(app: &AppHandle, traffic: &TrafficSnapshot) {
+    let Some(icao) = traffic.icao.clone() else {
+        return;
+    };
+    let minutes = crate::read_global_settings(app.clone())
+        .map(|s| s.replay.instant_replay_minutes)
+        .unwrap_or(10)
+        .clamp(1, INSTANT_REPLAY_MAX_MINUTES);
+    let timestamp = traffic.updated_at.unwrap_or_else(now_ms);
+
+    let state = app.state::<ReplayState>();
+    let mut buffer = state.buffer.write();
+    // The buffer only covers one airport
+    if buffer.icao.as_deref() != Some(icao.as_str()) {
+        buffer.snapshots.clear();
+        buffer.icao = Some(icao);
+    }
+    let previous = buffer.snapshots.back().map_or(timestamp, |s| s.timestamp);
+    let snapshot = ReplaySnapshot {
+        timestamp,
+        vatsim_timestamp: timestamp,
+        aircraft_states: traffic.aircraft.iter().map(|a| aircraft_state(a, timestamp)).collect(),
+        last_update_interval: timestamp.saturating_sub(previous),
+    };
+
+    if let Some(recording) = state.recording.write().as_mut() {
+        // A recording stays with the airport it started at
+        if *recording.icao.get_or_insert_with(|| icao.clone()) == icao
+            && recording.snapshots.len() < RECORDING_MAX_SNAPSHOTS
+        {
+            let previous = recording.snapshots.back().map_or(timestamp, |s| s.timestamp);
+            recording.snapshots.push_back(ReplaySnapshot {
+                last_update_interval: timestamp.saturating_sub(previous),
+                ..snapshot.clone()
+            });
+        }
+    }
+
+    buffer.snapshots.push_back(snapshot);
+    trim_buffer(&mut buffer.snapshots, minutes as u64 * 60_000);
+}
+
+/// Start recording every traffic poll until `stop_recording` (restarts a running recording)
+pub fn start_recording(app: &AppHandle) {
+    *app.state::<ReplayState>().recording.write() = Some(ReplayBuffer::default());
+}
+
+/// A recording is running
+pub fn is_recording(app: &AppHandle) -> bool {
+    app.state::<ReplayState>().recording.read().is_some()
+}
+
+/// Stop recording and return the airport and snapshots recorded
+pub fn stop_recording(app: &AppHandle) -> (Option<String>, Vec<ReplaySnapshot>) {
+    app.state::<ReplayState>()
+        .recording
+        .write()
+        .take()
+        .map(|r| (r.icao, r.snapshots.into()))
+        .unwrap_or_default()
+}
+
+/// Replay the last minutes of the instant replay buffer to every client (default: 2 minutes)
+pub fn instant_replay(app: &AppHandle, minutes: Option<u32>) -> Result<ReplayStatus> {
+    let minutes = minutes
+        .unwrap_or(INSTANT_REPLAY_DEFAULT_MINUTES)
+        .clamp(1, INSTANT_REPLAY_MAX_MINUTES);
+    let (airport, snapshots) = {
+        let state = app.state::<ReplayState>();
+        let buffer = state.buffer.read();
+        let newest = buffer
+            .snapshots
+            .back()
+            .ok_or_else(|| Error::conflict("No traffic recorded yet"))?
+            .timestamp;
+        let from = newest.saturating_sub(minutes as u64 * 60_000);
+        let snapshots: Vec<ReplaySnapshot> = buffer
+            .snapshots
+            .iter()
+            .filter(|s| s.timestamp >= from)
+            .cloned()
+            .collect();
+        (buffer.icao.clone(), snapshots)
+    };
+    if snapshots.len() < 2 {
+        return Err(Error::conflict("Not enough traffic recorded yet"));
+    }
+
+    let mut session = ReplaySession::new(airport, snapshots, true);
+    session.playing = true;
+    info!("[Replay] Instant replay of the last {} minutes", minutes);
+    Ok(start_session(app, session))
+}
+
+/// Load a replay export or a shared replay (`.tcreplay`) from disk
+pub fn load_file(app: &AppHandle, path: &Path) -> Result<ReplayStatus> {
+    let bytes = std::fs::read(path).map_err(|e| Error::io(format!("Failed to read {}: {}", path.display(), e)))?;
+    let (_, file) = replay_library::decode(&bytes)?;
+    load(app, file)
+}
+
+/// Play, pause, seek or change the speed of the loaded replay
+pub fn control(app: &AppHandle, control: ReplayControl) -> Result<ReplayStatus> {
+    let speed = control.speed.map(validate_speed).transpose()?;
+    let status = {
+        let state = app.state::<ReplayState>();
+        let mut session = state.session.write();
+        let session = session.as_mut().ok_or_else(|| Error::conflict("No replay loaded"))?;
+
+        let position = control.position.unwrap_or_else(|| session.position());
+        if let Some(speed) = speed {
+            session.speed = speed;
+        }
+        if let Some(playing) = control.playing {
+            session.playing = playing;
+        }
+        // Playing from the end restarts the recording
+        let restart = control.playing == Some(true) && control.position.is_none() && position >= session.end_time();
+        session.set_position(if restart { session.start_time() } else { position });
+        session.status()
+    };
+
+    publish(app, ReplayEvent::Status(status.clone()));
+    sync(app, control.position.is_some());
+    Ok(status)
+}
+
+/// Unload the replay; clients return to live traffic
+pub fn stop(app: &AppHandle) -> ReplayStatus {
+    if app.state::<ReplayState>().session.write().take().is_some() {
+        info!("[Replay] Stopped");
+    }
+    let status = ReplayStatus {
+        real_time: now_ms(),
+        ..Default::default()
+    };
+    publish(app, ReplayEvent::Status(status.clone()));
+    status
+}
+
+/// Advance playback and send snapshots as the position reaches them
+pub fn start_replay_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn("replay", Restart::Always, move || {
+        let app = app.clone();
+        async move {
+            let mut tick = tokio::time::interval(TICK_INTERVAL);
+            loop {
+                tick.tick().await;
+                sync(&app, false);
+                debug_stats::task_ran("replay", TICK_INTERVAL, None);
+            }
+        }
+    });
+}
+
+/// Initialize replay state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_replay(app: &AppHandle) {
+    app.manage(ReplayState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the replay status
+#[tauri::command]
+pub fn get_replay_status(app: AppHandle) -> ReplayStatus {
+    get_status(&app)
+}
+
+/// Get the loaded replay so this display can play it in sync with the host
+#[tauri::command]
+pub fn get_replay_recording(app: AppHandle) -> Option<ReplayFile> {
+    loaded_file(&app)
+}
+
+/// Load a replay export or shared replay file and stream it to all clients
+#[tauri::command]
+pub fn load_replay_file(app: AppHandle, path: String) -> Result<ReplayStatus> {
+    load_file(&app, Path::new(&path))
+}
+
+/// Replay the last minutes of traffic to all clients, then return to live traffic
+#[tauri::command]
+pub fn start_instant_replay(app: AppHandle, minutes: Option<u32>) -> Result<ReplayStatus> {
+    instant_replay(&app, minutes)
+}
+
+/// Play, pause, seek or change the speed of the loaded replay
+#[tauri::command]
+pub fn control_replay(app: AppHandle, control: ReplayControl) -> Result<ReplayStatus> {
+    self::control(&app, control)
+}
+
+/// Stop the replay and return all clients to live traffic
+#[tauri::command]
+pub fn stop_replay(app: AppHandle) -> ReplayStatus {
+    stop(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_snapshot_for_position() {
+        let snapshot = |timestamp| ReplaySnapshot {
+            timestamp,
+            vatsim_timestamp: timestamp,
+            aircraft_states: Vec::new(),
+            last_update_interval: 15_000,
+        };
+        let snapshots = vec![snapshot(1_000), snapshot(16_000), snapshot(31_000)];
+
+        assert_eq!(snapshot_index_at(&snapshots, 500), None);
+        assert_eq!(snapshot_index_at(&snapshots, 1_000), Some(0));
+        assert_eq!(snapshot_index_at(&snapshots, 15_999), Some(0));
+        assert_eq!(snapshot_index_at(&snapshots, 16_000), Some(1));
+        assert_eq!(snapshot_index_at(&snapshots, 99_000), Some(2));
+
+        // 7.5 s of real time at 2x covers 15 s of the recording
+        assert_eq!(advance(1_000, Duration::from_millis(7_500), 2.0), 16_000);
+        assert!(validate_speed(4.0).is_ok());
+        assert!(validate_speed(3.0).is_err());
+
+        // The buffer keeps the newest snapshot and everything within the window before it
+        let mut buffer: VecDeque<ReplaySnapshot> = snapshots.into_iter().collect();
+        trim_buffer(&mut buffer, 15_000);
+        assert_eq!(
+            buffer.iter().map(|s| s.timestamp).collect::<Vec<_>>(),
+            vec![16_000, 31_000]
+        );
+    }
+
+    #[test]
+    fn wraps_recording_in_export_format() {
+        let snapshots = vec![ReplaySnapshot {
+            timestamp: 1_000,
+            vatsim_timestamp: 1_000,
+            aircraft_states: Vec::new(),
+            last_update_interval: 15_000,
+        }];
+        let file = recording_file(Some("KBOS".to_string()), snapshots);
+        assert_eq!(file.version, REPLAY_VERSION);
+        assert_eq!(file.airport.as_deref(), Some("KBOS"));
+        assert_eq!(file.snapshots.len(), 1);
+
+        // Serialized the way the renderer imports replay files
+        let json = serde_json::to_value(&file).unwrap();
+        assert_eq!(json["snapshots"][0]["lastUpdateInterval"], 15_000);
+        assert!(json.get("exportDate").is_some());
+    }
+}
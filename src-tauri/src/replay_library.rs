@@ -0,0 +1,518 @@
+//! Shared replays
+//!
+//! A recorded session (the loaded replay or the instant replay buffer) can be
+//! exported as a single `.tcreplay` file so a facility can publish, say, its
+//! "KBOS FNO replay" for members to load into their own installations. The
+//! file is a zstd-compressed tar archive (the same container as backups and
+//! airport packs):
+//!
+//! ```text
+//! manifest.json   title, description, airport, recorded times
+//! replay.json     the replay export the renderer reads (see `replay`)
+//! ```
+//!
+//! The manifest comes first so the library can be listed without
+//! decompressing whole recordings. Imported and published files are kept in
+//! `replays/` in app data and served over HTTP (`/api/replays`), where other
+//! installations download them or the host loads them for every client.
+//! Imports also accept the renderer's plain JSON replay exports.
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::DialogExt;
+use tracing::{info, warn};
+
+use crate::error::{Error, Result};
+use crate::replay::{self, ReplayFile, ReplayStatus};
+use crate::session_export::{SOURCE_BUFFER, SOURCE_REPLAY};
+
+/// Bump when the archive layout changes
+const FORMAT_VERSION: u32 = 1;
+
+/// File extension of shared replays
+pub const EXTENSION: &str = "tcreplay";
+
+const MANIFEST_NAME: &str = "manifest.json";
+const REPLAY_NAME: &str = "replay.json";
+
+/// zstd level (recordings are repetitive JSON)
+const LEVEL: i32 = 9;
+
+/// Largest recording read from an archive (uncompressed)
+const MAX_REPLAY_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+/// Longest title and description kept in a manifest
+const MAX_TITLE_CHARS: usize = 120;
+const MAX_DESCRIPTION_CHARS: usize = 2000;
+
+/// What a shared replay contains, stored first in the archive
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ShareManifest {
+    pub format: u32,
+    /// E.g., "KBOS FNO replay"
+    pub title: String,
+    pub description: String,
+    pub airport: Option<String>,
+    /// First and last recorded times (Unix ms)
+    pub start_time: u64,
+    pub end_time: u64,
+    pub snapshot_count: usize,
+    pub export_date: String,
+    pub app_version: String,
+}
+
+/// Shared replay in the library
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SharedReplay {
+    /// File name without the extension (e.g., "kbos-fno-replay")
+    pub name: String,
+    /// Archive size in bytes
+    pub size: u64,
+    #[serde(flatten)]
+    pub manifest: ShareManifest,
+}
+
+/// Title and description of a shared replay
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ShareOptions {
+    /// Default: airport and start time
+    pub title: Option<String>,
+    pub description: Option<String>,
+}
+
+fn library_dir(app: &AppHandle) -> Result<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("replays"))
+        .map_err(|e| Error::io(format!("Failed to get app data directory: {}", e)))
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    text.trim().chars().take(max).collect()
+}
+
+/// Library file names: lowercase letters, digits and dashes
+fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 100
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// File name for a title ("KBOS FNO replay" -> "kbos-fno-replay")
+fn slug(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug: String = slug.chars().take(80).collect();
+    match slug.trim_end_matches('-') {
+        "" => "replay".to_string(),
+        slug => slug.to_string(),
+    }
+}
+
+/// Manifest for a recording
+fn manifest(file: &ReplayFile, options: &ShareOptions) -> ShareManifest {
+    let start_time = file.snapshots.first().map_or(0, |s| s.timestamp);
+    let title = options
+        .title
+        .as_deref()
+        .map(|t| truncate(t, MAX_TITLE_CHARS))
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| {
+            let start = DateTime::from_timestamp_millis(start_time as i64)
+                .map(|t| t.format("%Y-%m-%d %H%MZ").to_string())
+                .unwrap_or_default();
+            format!("{} {}", file.airport.as_deref().unwrap_or("Session"), start)
+                .trim()
+                .to_string()
+        });
+    ShareManifest {
+        format: FORMAT_VERSION,
+        title,
+        description: truncate(options.description.as_deref().unwrap_or_default(), MAX_DESCRIPTION_CHARS),
+        airport: file.airport.clone(),
+        start_time,
+        end_time: file.snapshots.last().map_or(0, |s| s.timestamp),
+        snapshot_count: file.snapshots.len(),
+        export_date: file.export_date.clone(),
+        app_version: file.app_version.clone(),
+    }
+}
+
+fn append(builder: &mut tar::Builder<impl std::io::Write>, name: &str, bytes: &[u8]) -> Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+    builder
+        .append_data(&mut header, name, bytes)
+        .map_err(|e| Error::io(format!("Failed to write replay: {}", e)))
+}
+
+/// Build a `.tcreplay` archive
+pub fn encode(manifest: &ShareManifest, file: &ReplayFile) -> Result<Vec<u8>> {
+    let encoder = zstd::Encoder::new(Vec::new(), LEVEL).map_err(|e| Error::io(format!("Failed to write replay: {}", e)))?;
+    let mut builder = tar::Builder::new(encoder);
+    let manifest = serde_json::to_vec_pretty(manifest).map_err(|e| e.to_string())?;
+    let replay = serde_json::to_vec(file).map_err(|e| e.to_string())?;
+    append(&mut builder, MANIFEST_NAME, &manifest)?;
+    append(&mut builder, REPLAY_NAME, &replay)?;
+    let encoder = builder
+        .into_inner()
+        .map_err(|e| Error::io(format!("Failed to write replay: {}", e)))?;
+    encoder
+        .finish()
+        .map_err(|e| Error::io(format!("Failed to write replay: {}", e)))
+}
+
+fn invalid(e: impl std::fmt::Display) -> Error {
+    Error::invalid_input(format!("Not a shared replay: {}", e))
+}
+
+/// Read an archive's manifest and, with `with_replay`, its recording
+fn read_archive(reader: impl Read, with_replay: bool) -> Result<(ShareManifest, Option<ReplayFile>)> {
+    let decoder = zstd::Decoder::new(reader).map_err(invalid)?;
+    let mut archive = tar::Archive::new(decoder);
+    let mut manifest = None;
+    for entry in archive.entries().map_err(invalid)? {
+        let mut entry = entry.map_err(invalid)?;
+        let name = entry.path().map_err(invalid)?.to_string_lossy().to_string();
+        if entry.size() > MAX_REPLAY_BYTES {
+            return Err(Error::invalid_input(format!(
+                "Replay is larger than {} GB",
+                MAX_REPLAY_BYTES / (1024 * 1024 * 1024)
+            )));
+        }
+        match name.as_str() {
+            MANIFEST_NAME => {
+                let manifest_value: ShareManifest = serde_json::from_reader(&mut entry).map_err(invalid)?;
+                if manifest_value.format > FORMAT_VERSION {
+                    return Err(Error::invalid_input(format!(
+                        "Replay was shared by a newer version (format {})",
+                        manifest_value.format
+                    )));
+                }
+                if !with_replay {
+                    return Ok((manifest_value, None));
+                }
+                manifest = Some(manifest_value);
+            }
+            REPLAY_NAME if with_replay => {
+                let manifest = manifest.ok_or_else(|| invalid("missing manifest"))?;
+                let file: ReplayFile = serde_json::from_reader(&mut entry).map_err(invalid)?;
+                return Ok((manifest, Some(file)));
+            }
+            _ => {}
+        }
+    }
+    Err(invalid("missing recording"))
+}
+
+/// Read a `.tcreplay` archive or a plain JSON replay export
+pub fn decode(bytes: &[u8]) -> Result<(ShareManifest, ReplayFile)> {
+    // zstd frame magic
+    if bytes.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+        let (manifest, file) = read_archive(bytes, true)?;
+        return Ok((manifest, file.ok_or_else(|| invalid("missing recording"))?));
+    }
+    let file: ReplayFile =
+        serde_json::from_slice(bytes).map_err(|e| Error::invalid_input(format!("Invalid replay file: {}", e)))?;
+    Ok((manifest(&file, &ShareOptions::default()), file))
+}
+
+/// The loaded replay ("replay") or the instant replay buffer ("buffer") as a
+/// replay file. Without a source, the loaded replay if there is one, otherwise the buffer.
+fn recording(app: &AppHandle, source: Option<&str>) -> Result<ReplayFile> {
+    let (airport, snapshots) = match source {
+        Some(SOURCE_REPLAY) => replay::loaded_recording(app).ok_or_else(|| Error::conflict("No replay loaded"))?,
+        Some(SOURCE_BUFFER) => replay::buffered_recording(app),
+        Some(other) => return Err(Error::invalid_input(format!("Unknown replay source: {}", other))),
+        None => replay::loaded_recording(app).unwrap_or_else(|| replay::buffered_recording(app)),
+    };
+    if snapshots.is_empty() {
+        return Err(Error::conflict("No traffic recorded"));
+    }
+    Ok(replay::recording_file(airport, snapshots))
+}
+
+/// Export a recorded session as a `.tcreplay` archive (file name and bytes)
+pub fn share(app: &AppHandle, source: Option<&str>, options: &ShareOptions) -> Result<(String, Vec<u8>)> {
+    let file = recording(app, source)?;
+    let manifest = manifest(&file, options);
+    let bytes = encode(&manifest, &file)?;
+    Ok((format!("{}.{}", slug(&manifest.title), EXTENSION), bytes))
+}
+
+/// Library entry for a file
+fn entry(path: &Path) -> Result<SharedReplay> {
+    let name = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let file = fs::File::open(path).map_err(|e| Error::io(format!("Failed to open {}: {}", path.display(), e)))?;
+    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+    let (manifest, _) = read_archive(file, false)?;
+    Ok(SharedReplay { name, size, manifest })
+}
+
+/// Save an archive in the library under a free name based on its title
+fn save(app: &AppHandle, manifest: &ShareManifest, bytes: &[u8]) -> Result<SharedReplay> {
+    let dir = library_dir(app)?;
+    fs::create_dir_all(&dir).map_err(|e| Error::io(format!("Failed to create {}: {}", dir.display(), e)))?;
+    let base = slug(&manifest.title);
+    let mut name = base.clone();
+    let mut n = 2;
+    while dir.join(format!("{}.{}", name, EXTENSION)).exists() {
+        name = format!("{}-{}", base, n);
+        n += 1;
+    }
+    let path = dir.join(format!("{}.{}", name, EXTENSION));
+    fs::write(&path, bytes).map_err(|e| Error::io(format!("Failed to save replay: {}", e)))?;
+    info!(
+        "[Replays] Saved \"{}\" ({} snapshots) as {}",
+        manifest.title,
+        manifest.snapshot_count,
+        path.display()
+    );
+    Ok(SharedReplay {
+        name,
+        size: bytes.len() as u64,
+        manifest: manifest.clone(),
+    })
+}
+
+/// Publish a recorded session to the library
+pub fn publish(app: &AppHandle, source: Option<&str>, options: &ShareOptions) -> Result<SharedReplay> {
+    publish_file(app, &recording(app, source)?, options)
+}
+
+/// Add a recording made elsewhere (e.g., a scheduled window) to the library
+pub fn publish_file(app: &AppHandle, file: &ReplayFile, options: &ShareOptions) -> Result<SharedReplay> {
+    let manifest = manifest(file, options);
+    save(app, &manifest, &encode(&manifest, file)?)
+}
+
+/// Add a `.tcreplay` archive or a JSON replay export to the library
+pub fn import(app: &AppHandle, bytes: &[u8]) -> Result<SharedReplay> {
+    let (manifest, file) = decode(bytes)?;
+    if file.snapshots.is_empty() {
+        return Err(Error::invalid_input("Replay contains no snapshots"));
+    }
+    // JSON exports are stored as archives like everything else in the library
+    let manifest = ShareManifest {
+        snapshot_count: file.snapshots.len(),
+        ..manifest
+    };
+    let bytes = if bytes.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+        bytes.to_vec()
+    } else {
+        encode(&manifest, &file)?
+    };
+    save(app, &manifest, &bytes)
+}
+
+/// Shared replays in the library, newest recording first
+pub fn list(app: &AppHandle) -> Result<Vec<SharedReplay>> {
+    let dir = library_dir(app)?;
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+    let mut replays: Vec<SharedReplay> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == EXTENSION))
+        .filter_map(|p| match entry(&p) {
+            Ok(replay) => Some(replay),
+            Err(e) => {
+                warn!("[Replays] Skipping {}: {}", p.display(), e.message());
+                None
+            }
+        })
+        .collect();
+    replays.sort_by(|a, b| b.manifest.start_time.cmp(&a.manifest.start_time).then(a.name.cmp(&b.name)));
+    Ok(replays)
+}
+
+fn library_path(app: &AppHandle, name: &str) -> Result<PathBuf> {
+    if !valid_name(name) {
+        return Err(Error::invalid_input(format!("Invalid replay name: {}", name)));
+    }
+    let path = library_dir(app)?.join(format!("{}.{}", name, EXTENSION));
+    if !path.is_file() {
+        return Err(Error::not_found(format!("No shared replay {}", name)));
+    }
+    Ok(path)
+}
+
+/// A library archive (file name and bytes) for download
+pub fn read(app: &AppHandle, name: &str) -> Result<(String, Vec<u8>)> {
+    let path = library_path(app, name)?;
+    let bytes = fs::read(&path).map_err(|e| Error::io(format!("Failed to read {}: {}", path.display(), e)))?;
+    Ok((format!("{}.{}", name, EXTENSION), bytes))
+}
+
+/// Load a library replay and stream it to all clients
+pub fn load(app: &AppHandle, name: &str) -> Result<ReplayStatus> {
+    let (_, bytes) = read(app, name)?;
+    let (_, file) = decode(&bytes)?;
+    replay::load(app, file)
+}
+
+/// Remove a replay from the library
+pub fn delete(app: &AppHandle, name: &str) -> Result<()> {
+    let path = library_path(app, name)?;
+    fs::remove_file(&path).map_err(|e| Error::io(format!("Failed to delete {}: {}", path.display(), e)))?;
+    info!("[Replays] Deleted {}", name);
+    Ok(())
+}
+
+async fn background<T: Send + 'static>(work: impl FnOnce() -> Result<T> + Send + 'static) -> Result<T> {
+    tauri::async_runtime::spawn_blocking(work)
+        .await
+        .map_err(|e| Error::from(format!("Background task failed: {}", e)))?
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// List the shared replays in the library
+#[tauri::command]
+pub async fn list_shared_replays(app: AppHandle) -> Result<Vec<SharedReplay>> {
+    background(move || list(&app)).await
+}
+
+/// Export a recorded session as a `.tcreplay` file (to `path`, default: publish it to the library)
+#[tauri::command]
+pub async fn share_replay(
+    app: AppHandle,
+    source: Option<String>,
+    options: Option<ShareOptions>,
+    path: Option<String>,
+) -> Result<SharedReplay> {
+    background(move || {
+        let options = options.unwrap_or_default();
+        let Some(path) = path else {
+            return publish(&app, source.as_deref(), &options);
+        };
+        let file = recording(&app, source.as_deref())?;
+        let manifest = manifest(&file, &options);
+        let bytes = encode(&manifest, &file)?;
+        fs::write(&path, &bytes).map_err(|e| Error::io(format!("Failed to write {}: {}", path, e)))?;
+        info!("[Replays] Exported \"{}\" to {}", manifest.title, path);
+        Ok(SharedReplay {
+            name: Path::new(&path)
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            size: bytes.len() as u64,
+            manifest,
+        })
+    })
+    .await
+}
+
+/// Pick a `.tcreplay` file (or a JSON replay export) using native dialog
+#[tauri::command]
+pub async fn pick_shared_replay_file(app: AppHandle) -> Result<Option<String>> {
+    Ok(app
+        .dialog()
+        .file()
+        .add_filter("Replay", &[EXTENSION, "json"])
+        .blocking_pick_file()
+        .map(|path| path.to_string()))
+}
+
+/// Add a `.tcreplay` file or a JSON replay export from disk to the library
+#[tauri::command]
+pub async fn import_shared_replay(app: AppHandle, path: String) -> Result<SharedReplay> {
+    background(move || {
+        let bytes = fs::read(&path).map_err(|e| Error::io(format!("Failed to read {}: {}", path, e)))?;
+        import(&app, &bytes)
+    })
+    .await
+}
+
+/// Load a shared replay from the library and stream it to all clients
+#[tauri::command]
+pub async fn load_shared_replay(app: AppHandle, name: String) -> Result<ReplayStatus> {
+    background(move || load(&app, &name)).await
+}
+
+/// Remove a shared replay from the library
+#[tauri::command]
+pub fn delete_shared_replay(app: AppHandle, name: String) -> Result<()> {
+    delete(&app, &name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::ReplaySnapshot;
+
+    fn recording() -> ReplayFile {
+        let snapshot = |timestamp| ReplaySnapshot {
+            timestamp,
+            vatsim_timestamp: timestamp,
+            aircraft_states: vec![serde_json::json!({ "callsign": "DAL123" })],
+            last_update_interval: 15_000,
+        };
+        replay::recording_file(
+            Some("KBOS".to_string()),
+            vec![snapshot(1_776_034_800_000), snapshot(1_776_034_815_000)],
+        )
+    }
+
+    #[test]
+    fn round_trips_archives_and_reads_json_exports() {
+        let file = recording();
+        let options = ShareOptions {
+            title: Some("  KBOS FNO replay ".to_string()),
+            description: Some("Boston FNO, April 2026".to_string()),
+        };
+        let manifest = manifest(&file, &options);
+        assert_eq!(manifest.title, "KBOS FNO replay");
+        assert_eq!(manifest.snapshot_count, 2);
+        assert_eq!(manifest.end_time - manifest.start_time, 15_000);
+
+        let bytes = encode(&manifest, &file).unwrap();
+        let (decoded, replay) = decode(&bytes).unwrap();
+        assert_eq!(decoded, manifest);
+        assert_eq!(replay.snapshots.len(), 2);
+        assert_eq!(replay.snapshots[0].aircraft_states[0]["callsign"], "DAL123");
+
+        // The manifest alone, without reading the recording
+        let (listed, replay) = read_archive(bytes.as_slice(), false).unwrap();
+        assert_eq!(listed, manifest);
+        assert!(replay.is_none());
+
+        // Renderer JSON exports get a manifest from the recording
+        let json = serde_json::to_vec(&file).unwrap();
+        let (manifest, _) = decode(&json).unwrap();
+        assert_eq!(manifest.title, "KBOS 2026-04-12 2300Z");
+        assert!(decode(b"not a replay").is_err());
+    }
+
+    #[test]
+    fn names_files_from_titles() {
+        assert_eq!(slug("KBOS FNO replay"), "kbos-fno-replay");
+        assert_eq!(slug("  EGLL / 27L arrivals!! "), "egll-27l-arrivals");
+        assert_eq!(slug("***"), "replay");
+        assert!(valid_name("kbos-fno-replay-2"));
+        assert!(!valid_name("../settings"));
+        assert!(!valid_name(""));
+    }
+}
@@ -0,0 +1,432 @@
+//! Runway configuration detection from traffic
+//!
+//! After every traffic poll, aircraft on final (lined up before a threshold,
+//! below 1500 ft above the airport and not climbing) count as arrivals to that
+//! runway end, and aircraft climbing out (lined up past the threshold, below
+//! 2500 ft) as departures from it. Each aircraft counts once per kind, at its
+//! latest sighting, for the last 20 minutes. Ends with at least two movements
+//! are in use; when both ends of a runway qualify, the one used last wins, so
+//! a flow change shows as soon as two aircraft use the new direction.
+//!
+//! The detected configuration is cross-checked against the METAR wind
+//! (`runway_suggestion`): ends in use with more tailwind than the suggestion
+//! allows are listed. Changes are emitted as `runway-config-changed`; the
+//! arrival sequence and view presets use the detected runways before falling
+//! back to the wind-based suggestion.
+
+use std::collections::{BTreeMap, HashMap};
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::info;
+
+use crate::runway_suggestion::RunwaySuggestion;
+use crate::runways::{self, Runway};
+use crate::traffic::{Aircraft, TrafficSnapshot};
+
+/// Movements older than this no longer count (ms)
+const WINDOW_MS: u64 = 20 * 60 * 1000;
+
+/// Movements a runway end needs in the window to be in use
+const MIN_MOVEMENTS: usize = 2;
+
+/// Arrivals: on final below this height above the airport, up to this far out (nm)
+const ARRIVAL_MAX_AGL_FT: f64 = 1500.0;
+const ARRIVAL_MAX_BEFORE_NM: f64 = 5.0;
+
+/// Departures: climbing out below this height, up to this far past the far end (nm)
+const DEPARTURE_MAX_AGL_FT: f64 = 2500.0;
+const DEPARTURE_MAX_AFTER_NM: f64 = 3.0;
+
+/// Largest distance from the extended centerline (nm)
+const MAX_OFFSET_NM: f64 = 0.5;
+
+/// Vertical rate that counts as climbing (feet per minute)
+const CLIMB_MIN_FPM: f64 = 300.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MovementKind {
+    Arrival,
+    Departure,
+}
+
+/// Aircraft seen arriving to or departing from a runway end
+#[derive(Debug, Clone, PartialEq)]
+pub struct Movement {
+    /// Runway end (e.g., "27R")
+    pub end: String,
+    /// Latest sighting (Unix ms)
+    pub time: u64,
+}
+
+/// Use of one runway end in the window
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunwayEndUse {
+    pub ident: String,
+    pub arrivals: usize,
+    pub departures: usize,
+    /// Unix ms
+    pub last_used: u64,
+}
+
+/// Detected configuration against the METAR wind
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindCheck {
+    pub metar: String,
+    pub calm: bool,
+    /// Ends the wind suggests (see `runway_suggestion`)
+    pub suggested: Vec<String>,
+    /// Ends in use with more tailwind than the suggestion allows
+    pub tailwind: Vec<String>,
+    /// No end in use has too much tailwind
+    pub agrees: bool,
+}
+
+/// Runway configuration detected from traffic
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunwayConfiguration {
+    pub icao: Option<String>,
+    /// Time of the traffic picture (Unix ms)
+    pub updated_at: Option<u64>,
+    pub window_minutes: u64,
+    /// Ends in use for landing (e.g., ["27L", "27R"])
+    pub arrivals: Vec<String>,
+    /// Ends in use for takeoff
+    pub departures: Vec<String>,
+    /// Every end used in the window
+    pub ends: Vec<RunwayEndUse>,
+    pub wind: Option<WindCheck>,
+}
+
+/// Runway configuration state (managed by Tauri)
+pub struct RunwayConfigState {
+    /// Movements by callsign and kind at the active airport
+    movements: RwLock<HashMap<(String, MovementKind), Movement>>,
+    configuration: RwLock<RunwayConfiguration>,
+}
+
+impl RunwayConfigState {
+    pub fn new() -> Self {
+        Self {
+            movements: RwLock::new(HashMap::new()),
+            configuration: RwLock::new(RunwayConfiguration::default()),
+        }
+    }
+}
+
+impl Default for RunwayConfigState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Runway end an aircraft is arriving to or departing from, if any
+pub fn classify(aircraft: &Aircraft, runways: &[Runway], elevation_ft: f64) -> Option<(MovementKind, String)> {
+    if aircraft.on_ground {
+        return None;
+    }
+    let agl = aircraft.altitude_ft - elevation_ft;
+    let climbing = aircraft.vertical_rate_fpm.is_some_and(|v| v >= CLIMB_MIN_FPM);
+    let alignment = runways::aligned_runway_end(
+        runways,
+        aircraft.latitude,
+        aircraft.longitude,
+        aircraft.track.unwrap_or(aircraft.heading),
+        MAX_OFFSET_NM,
+        ARRIVAL_MAX_BEFORE_NM,
+        DEPARTURE_MAX_AFTER_NM,
+    )?;
+    if alignment.along_nm <= 0.0 && agl <= ARRIVAL_MAX_AGL_FT && !climbing {
+        return Some((MovementKind::Arrival, alignment.end.ident.clone()));
+    }
+    if alignment.along_nm > 0.0 && agl <= DEPARTURE_MAX_AGL_FT && climbing {
+        return Some((MovementKind::Departure, alignment.end.ident.clone()));
+    }
+    None
+}
+
+/// Ends in use for a kind of movement: at least `MIN_MOVEMENTS`, and only the
+/// last used end of a runway
+fn in_use(ends: &BTreeMap<String, RunwayEndUse>, runways: &[Runway], kind: MovementKind) -> Vec<String> {
+    let count = |u: &RunwayEndUse| match kind {
+        MovementKind::Arrival => u.arrivals,
+        MovementKind::Departure => u.departures,
+    };
+    let opposite = |ident: &str| {
+        runways.iter().find_map(|r| {
+            if r.low_end.ident == ident {
+                Some(r.high_end.ident.as_str())
+            } else if r.high_end.ident == ident {
+                Some(r.low_end.ident.as_str())
+            } else {
+                None
+            }
+        })
+    };
+    ends.values()
+        .filter(|u| count(u) >= MIN_MOVEMENTS)
+        .filter(|u| {
+            !opposite(&u.ident)
+                .and_then(|o| ends.get(o))
+                .is_some_and(|o| count(o) >= MIN_MOVEMENTS && o.last_used > u.last_used)
+        })
+        .map(|u| u.ident.clone())
+        .collect()
+}
+
+/// Configuration from the movements in the window
+pub fn detect(
+    movements: &HashMap<(String, MovementKind), Movement>,
+    runways: &[Runway],
+    now: u64,
+) -> (Vec<String>, Vec<String>, Vec<RunwayEndUse>) {
+    let mut ends: BTreeMap<String, RunwayEndUse> = BTreeMap::new();
+    for ((_, kind), movement) in movements {
+        if now.saturating_sub(movement.time) > WINDOW_MS {
+            continue;
+        }
+        let end = ends.entry(movement.end.clone()).or_insert_with(|| RunwayEndUse {
+            ident: movement.end.clone(),
+            arrivals: 0,
+            departures: 0,
+            last_used: 0,
+        });
+        match kind {
+            MovementKind::Arrival => end.arrivals += 1,
+            MovementKind::Departure => end.departures += 1,
+        }
+        end.last_used = end.last_used.max(movement.time);
+    }
+    let arrivals = in_use(&ends, runways, MovementKind::Arrival);
+    let departures = in_use(&ends, runways, MovementKind::Departure);
+    (arrivals, departures, ends.into_values().collect())
+}
+
+/// Check the ends in use against the wind-based suggestion
+pub fn check_wind(in_use: &[String], suggestion: &RunwaySuggestion) -> WindCheck {
+    let mut tailwind: Vec<String> = suggestion
+        .ends
+        .iter()
+        .filter(|e| !e.suitable && in_use.contains(&e.ident))
+        .map(|e| e.ident.clone())
+        .collect();
+    tailwind.sort();
+    tailwind.dedup();
+    WindCheck {
+        metar: suggestion.metar.clone(),
+        calm: suggestion.calm,
+        suggested: suggestion.active.clone(),
+        agrees: tailwind.is_empty(),
+        tailwind,
+    }
+}
+
+/// Record movements from a traffic poll and update the configuration
+pub fn update(
+    app: &AppHandle,
+    snapshot: &TrafficSnapshot,
+    runways: &[Runway],
+    elevation_ft: f64,
+    suggestion: Option<&RunwaySuggestion>,
+) {
+    let Some(icao) = snapshot.icao.clone() else {
+        return;
+    };
+    let now = snapshot.updated_at.unwrap_or_default();
+    let state = app.state::<RunwayConfigState>();
+    let previous = state.configuration.read().clone();
+
+    let (arrivals, departures, ends) = {
+        let mut movements = state.movements.write();
+        if previous.icao.as_deref() != Some(icao.as_str()) {
+            movements.clear();
+        }
+        for a in &snapshot.aircraft {
+            if let Some((kind, end)) = classify(a, runways, elevation_ft) {
+                movements.insert((a.callsign.clone(), kind), Movement { end, time: now });
+            }
+        }
+        movements.retain(|_, m| now.saturating_sub(m.time) <= WINDOW_MS);
+        detect(&movements, runways, now)
+    };
+
+    let mut used: Vec<String> = arrivals.iter().chain(&departures).cloned().collect();
+    used.sort();
+    used.dedup();
+    let configuration = RunwayConfiguration {
+        icao: Some(icao.clone()),
+        updated_at: snapshot.updated_at,
+        window_minutes: WINDOW_MS / 60_000,
+        wind: suggestion.filter(|_| !used.is_empty()).map(|s| check_wind(&used, s)),
+        arrivals,
+        departures,
+        ends,
+    };
+
+    let changed = previous.icao != configuration.icao
+        || previous.arrivals != configuration.arrivals
+        || previous.departures != configuration.departures;
+    if changed {
+        info!(
+            "[RunwayConfig] {} arrivals {} departures {}",
+            icao,
+            configuration.arrivals.join(" "),
+            configuration.departures.join(" ")
+        );
+        let _ = app.emit("runway-config-changed", &configuration);
+    }
+    *state.configuration.write() = configuration;
+}
+
+/// Runway ends in use for landing and takeoff (empty until detected)
+pub fn active_runways(app: &AppHandle) -> Vec<String> {
+    let configuration = get_configuration(app);
+    let mut ends: Vec<String> = configuration
+        .arrivals
+        .into_iter()
+        .chain(configuration.departures)
+        .collect();
+    ends.sort();
+    ends.dedup();
+    ends
+}
+
+/// Forget movements (no active airport or traffic)
+pub fn clear(app: &AppHandle) {
+    let state = app.state::<RunwayConfigState>();
+    state.movements.write().clear();
+    *state.configuration.write() = RunwayConfiguration::default();
+}
+
+/// Current detected configuration
+pub fn get_configuration(app: &AppHandle) -> RunwayConfiguration {
+    app.state::<RunwayConfigState>().configuration.read().clone()
+}
+
+/// Initialize runway configuration state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_runway_config(app: &AppHandle) {
+    app.manage(RunwayConfigState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the runway configuration detected from traffic at the active airport
+#[tauri::command]
+pub fn get_runway_configuration(app: AppHandle) -> RunwayConfiguration {
+    get_configuration(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runway_suggestion::RunwayEndWind;
+    use crate::traffic::test_aircraft;
+
+    fn runways() -> Vec<Runway> {
+        vec![runways::test_runway()]
+    }
+
+    /// Aircraft nautical miles north of the runway 36 threshold
+    fn aircraft(callsign: &str, nm_north: f64, altitude_ft: f64, track: f64, vertical_rate_fpm: f64) -> Aircraft {
+        Aircraft {
+            latitude: 42.0 + nm_north / 60.0,
+            altitude_ft,
+            groundspeed_kt: 140.0,
+            heading: track,
+            track: Some(track),
+            vertical_rate_fpm: Some(vertical_rate_fpm),
+            distance_nm: 0.0,
+            ..test_aircraft(callsign)
+        }
+    }
+
+    #[test]
+    fn classifies_arrivals_and_departures() {
+        let runways = runways();
+        let arrival = aircraft("AAL1", -3.0, 1000.0, 0.0, -700.0);
+        assert_eq!(
+            classify(&arrival, &runways, 20.0),
+            Some((MovementKind::Arrival, "36".to_string()))
+        );
+        let departure = aircraft("AAL2", 2.5, 1200.0, 0.0, 2000.0);
+        assert_eq!(
+            classify(&departure, &runways, 20.0),
+            Some((MovementKind::Departure, "36".to_string()))
+        );
+        // Landing on 18 from the north
+        let opposite = aircraft("AAL3", 4.5, 900.0, 180.0, -600.0);
+        assert_eq!(
+            classify(&opposite, &runways, 20.0),
+            Some((MovementKind::Arrival, "18".to_string()))
+        );
+        // Overflight and traffic off the centerline
+        assert_eq!(classify(&aircraft("AAL4", -3.0, 5000.0, 0.0, 0.0), &runways, 20.0), None);
+        let mut abeam = aircraft("AAL5", -1.0, 1000.0, 0.0, -500.0);
+        abeam.longitude = -71.05;
+        assert_eq!(classify(&abeam, &runways, 20.0), None);
+    }
+
+    #[test]
+    fn detects_the_last_used_direction() {
+        let runways = runways();
+        let movement = |end: &str, time: u64| Movement {
+            end: end.to_string(),
+            time,
+        };
+        let mut movements = HashMap::from([
+            (("A1".to_string(), MovementKind::Arrival), movement("36", 1_000)),
+            (("A2".to_string(), MovementKind::Arrival), movement("36", 2_000)),
+            (("D1".to_string(), MovementKind::Departure), movement("36", 3_000)),
+        ]);
+        let (arrivals, departures, ends) = detect(&movements, &runways, 10_000);
+        assert_eq!(arrivals, vec!["36"]);
+        assert!(departures.is_empty(), "one departure isn't enough");
+        assert_eq!(ends[0].arrivals, 2);
+
+        // Two aircraft land the other way: flow change
+        movements.insert(("A3".to_string(), MovementKind::Arrival), movement("18", 5_000));
+        movements.insert(("A4".to_string(), MovementKind::Arrival), movement("18", 6_000));
+        let (arrivals, _, _) = detect(&movements, &runways, 10_000);
+        assert_eq!(arrivals, vec!["18"]);
+
+        // Everything ages out of the window
+        let (arrivals, _, ends) = detect(&movements, &runways, 6_000 + WINDOW_MS + 1);
+        assert!(arrivals.is_empty());
+        assert!(ends.is_empty());
+    }
+
+    #[test]
+    fn flags_tailwind_ends() {
+        let end = |ident: &str, suitable: bool| RunwayEndWind {
+            runway: "18/36".to_string(),
+            ident: ident.to_string(),
+            heading_true: 0.0,
+            length_ft: None,
+            headwind_kt: 0.0,
+            tailwind_kt: 0.0,
+            crosswind_kt: 0.0,
+            gust_crosswind_kt: None,
+            suitable,
+        };
+        let suggestion = RunwaySuggestion {
+            icao: "KXYZ".to_string(),
+            metar: "KXYZ 011200Z 18015KT".to_string(),
+            wind: None,
+            calm: false,
+            active: vec!["18".to_string()],
+            ends: vec![end("18", true), end("36", false)],
+        };
+        let check = check_wind(&["36".to_string()], &suggestion);
+        assert!(!check.agrees);
+        assert_eq!(check.tailwind, vec!["36"]);
+        assert!(check_wind(&["18".to_string()], &suggestion).agrees);
+    }
+}
@@ -0,0 +1,250 @@
+//! Hold-short and line-up-and-wait timers
+//!
+//! After every traffic poll, aircraft stopped on the ground are checked
+//! against the runways: stopped on a runway and pointing along it is lined up
+//! and waiting, stopped just beside it (outside the runway outline, within
+//! 600 ft of the centerline) is holding short. Each starts a timer that runs
+//! while the aircraft stays there. Timers carry the time they were started
+//! and the time after which the controller should be reminded, and are
+//! published as a whole list (Tauri event `hold-timers`, `/api/holds/ws`) so
+//! clients count up locally.
+
+use std::collections::HashMap;
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::info;
+
+use crate::geo;
+use crate::runways::{self, Runway};
+use crate::traffic::{Aircraft, TrafficSnapshot};
+
+/// Aircraft slower than this are considered stopped (knots)
+const STOPPED_MAX_GROUNDSPEED_KT: f64 = 3.0;
+
+/// Holding short: up to this far from the runway centerline (feet)
+const HOLD_SHORT_MAX_OFFSET_FT: f64 = 600.0;
+
+/// Lined up: heading within this of the runway heading (degrees)
+const LINED_UP_MAX_HEADING_DIFF: f64 = 20.0;
+
+/// Remind the controller after this long (seconds)
+const LINE_UP_REMINDER_SECS: u64 = 90;
+const HOLD_SHORT_REMINDER_SECS: u64 = 180;
+
+const FEET_PER_NM: f64 = 6076.12;
+
+/// Timer kinds
+pub const KIND_HOLD_SHORT: &str = "holdShort";
+pub const KIND_LINE_UP: &str = "lineUp";
+
+/// Aircraft holding short of or lined up on a runway
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HoldTimer {
+    pub callsign: String,
+    /// "holdShort" or "lineUp"
+    pub kind: String,
+    /// Runway end lined up on (e.g., "27R"), or the runway held short of (e.g., "09L/27R")
+    pub runway: String,
+    /// When the aircraft stopped there (Unix ms)
+    pub since: u64,
+    /// When the controller should be reminded (Unix ms)
+    pub remind_at: u64,
+}
+
+/// Running hold timers at the airport
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HoldTimers {
+    pub icao: Option<String>,
+    /// Time of the traffic picture (Unix ms)
+    pub updated_at: Option<u64>,
+    pub timers: Vec<HoldTimer>,
+}
+
+/// Hold timer state (managed by Tauri)
+pub struct HoldTimerState {
+    timers: RwLock<HoldTimers>,
+    /// Timer updates for WebSocket relay
+    pub events: broadcast::Sender<HoldTimers>,
+}
+
+impl HoldTimerState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            timers: RwLock::new(HoldTimers::default()),
+            events,
+        }
+    }
+}
+
+/// Whether a stopped aircraft is lined up on or holding short of a runway
+fn hold_position(aircraft: &Aircraft, runways: &[Runway]) -> Option<(&'static str, String)> {
+    for runway in runways {
+        let Some(outline) = runways::outline(runway) else {
+            continue;
+        };
+        if geo::point_in_ring(aircraft.latitude, aircraft.longitude, &outline) {
+            // On the runway: lined up if pointing along it
+            return [&runway.low_end, &runway.high_end]
+                .into_iter()
+                .find(|end| {
+                    end.heading_true
+                        .is_some_and(|h| geo::heading_difference(aircraft.heading, h) <= LINED_UP_MAX_HEADING_DIFF)
+                })
+                .map(|end| (KIND_LINE_UP, end.ident.clone()));
+        }
+    }
+    runways
+        .iter()
+        .find(|runway| {
+            runways::alignment(runway, &runway.low_end, aircraft.latitude, aircraft.longitude).is_some_and(|a| {
+                (0.0..=a.length_nm).contains(&a.along_nm) && a.offset_nm * FEET_PER_NM <= HOLD_SHORT_MAX_OFFSET_FT
+            })
+        })
+        .map(|runway| (KIND_HOLD_SHORT, runway.ident.clone()))
+}
+
+/// Timers for the aircraft holding now, keeping the start time of timers
+/// that were already running
+pub fn detect(aircraft: &[Aircraft], runways: &[Runway], now: u64, previous: &[HoldTimer]) -> Vec<HoldTimer> {
+    let running: HashMap<(&str, &str, &str), u64> = previous
+        .iter()
+        .map(|t| ((t.callsign.as_str(), t.kind.as_str(), t.runway.as_str()), t.since))
+        .collect();
+    let mut timers: Vec<HoldTimer> = aircraft
+        .iter()
+        .filter(|a| a.on_ground && a.groundspeed_kt <= STOPPED_MAX_GROUNDSPEED_KT)
+        .filter_map(|a| {
+            let (kind, runway) = hold_position(a, runways)?;
+            let since = running
+                .get(&(a.callsign.as_str(), kind, runway.as_str()))
+                .copied()
+                .unwrap_or(now);
+            let reminder_secs = if kind == KIND_LINE_UP {
+                LINE_UP_REMINDER_SECS
+            } else {
+                HOLD_SHORT_REMINDER_SECS
+            };
+            Some(HoldTimer {
+                callsign: a.callsign.clone(),
+                kind: kind.to_string(),
+                runway,
+                since,
+                remind_at: since + reminder_secs * 1000,
+            })
+        })
+        .collect();
+    timers.sort_by(|a, b| (a.since, &a.callsign).cmp(&(b.since, &b.callsign)));
+    timers
+}
+
+fn publish(app: &AppHandle, timers: HoldTimers) {
+    let _ = app.emit("hold-timers", &timers);
+    let state = app.state::<HoldTimerState>();
+    let _ = state.events.send(timers.clone());
+    *state.timers.write() = timers;
+}
+
+/// Update the timers from a traffic poll
+pub fn update(app: &AppHandle, snapshot: &TrafficSnapshot, runways: &[Runway]) {
+    let now = snapshot.updated_at.unwrap_or_default();
+    let current = get_timers(app);
+    let previous = if current.icao == snapshot.icao {
+        current.timers.as_slice()
+    } else {
+        &[]
+    };
+    let timers = detect(&snapshot.aircraft, runways, now, previous);
+    for timer in timers.iter().filter(|t| t.since == now) {
+        info!("[Holds] {} {} {}", timer.callsign, timer.kind, timer.runway);
+    }
+    if timers == current.timers && current.icao == snapshot.icao {
+        return;
+    }
+    publish(
+        app,
+        HoldTimers {
+            icao: snapshot.icao.clone(),
+            updated_at: snapshot.updated_at,
+            timers,
+        },
+    );
+}
+
+/// Drop all timers (no active airport or traffic)
+pub fn clear(app: &AppHandle) {
+    let current = get_timers(app);
+    if current.icao.is_some() || !current.timers.is_empty() {
+        publish(app, HoldTimers::default());
+    }
+}
+
+/// Current hold timers
+pub fn get_timers(app: &AppHandle) -> HoldTimers {
+    app.state::<HoldTimerState>().timers.read().clone()
+}
+
+/// Initialize hold timer state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_runway_holds(app: &AppHandle) {
+    app.manage(HoldTimerState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the running hold-short and line-up timers
+#[tauri::command]
+pub fn get_hold_timers(app: AppHandle) -> HoldTimers {
+    get_timers(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traffic::test_aircraft;
+
+    #[test]
+    fn times_lined_up_and_holding_aircraft() {
+        let runway = runways::test_runway();
+        let aircraft = |callsign: &str, latitude: f64, longitude: f64, heading: f64, groundspeed_kt: f64| Aircraft {
+            latitude,
+            longitude,
+            altitude_ft: 20.0,
+            groundspeed_kt,
+            heading,
+            on_ground: true,
+            ..test_aircraft(callsign)
+        };
+        let runways = [runway];
+        // About 250 ft east of the centerline
+        let hold_longitude = -71.0 + 250.0 / FEET_PER_NM / (60.0 * 42f64.to_radians().cos());
+
+        let traffic = vec![
+            aircraft("LUAW", 42.001, -71.0, 2.0, 0.0),
+            aircraft("HOLD", 42.01, hold_longitude, 270.0, 0.0),
+            aircraft("TAXI", 42.01, hold_longitude, 0.0, 15.0),
+            aircraft("RAMP", 42.01, -70.99, 90.0, 0.0),
+        ];
+        let timers = detect(&traffic, &runways, 1_000, &[]);
+        let summary: Vec<(&str, &str, &str)> = timers
+            .iter()
+            .map(|t| (t.callsign.as_str(), t.kind.as_str(), t.runway.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("HOLD", KIND_HOLD_SHORT, "18/36"), ("LUAW", KIND_LINE_UP, "36")]
+        );
+        assert_eq!(timers[1].remind_at, 91_000);
+
+        // Running timers keep their start time
+        let timers = detect(&traffic, &runways, 16_000, &timers);
+        assert!(timers.iter().all(|t| t.since == 1_000));
+    }
+}
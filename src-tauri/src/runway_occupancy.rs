@@ -0,0 +1,300 @@
+//! Runway occupancy detection
+//!
+//! After every traffic poll, aircraft on the ground or below 100 ft inside a
+//! runway's outline (the rectangle between both ends, as wide as the runway,
+//! see `runways::outline`) occupy that runway. Per-runway state lists the
+//! occupants and since when they are there; entries and exits are emitted as
+//! `runway-occupancy-changed` and relayed over `/api/runways/occupancy/ws`.
+//! Entries onto a runway that is already occupied list the other occupants,
+//! which clients treat as a possible incursion (e.g., for aural alerts).
+//! Occupancy timers and incursion checks build on this.
+
+use std::collections::{BTreeMap, HashMap};
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::info;
+
+use crate::geo;
+use crate::runways::{self, Runway};
+use crate::traffic::{Aircraft, TrafficSnapshot};
+
+/// Airborne aircraft below this height over a runway occupy it (flare, liftoff)
+const OCCUPANCY_MAX_AGL_FT: f64 = 100.0;
+
+/// Aircraft on a runway
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunwayOccupant {
+    pub callsign: String,
+    /// When the aircraft entered the runway (Unix ms)
+    pub since: u64,
+}
+
+/// Occupancy of one runway
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunwayStatus {
+    /// Combined designator (e.g., "09L/27R")
+    pub runway: String,
+    /// Aircraft on the runway, first to enter first
+    pub occupied_by: Vec<RunwayOccupant>,
+}
+
+/// Runway occupancy at the airport
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunwayOccupancy {
+    pub icao: Option<String>,
+    /// Time of the traffic picture (Unix ms)
+    pub updated_at: Option<u64>,
+    pub runways: Vec<RunwayStatus>,
+}
+
+/// Aircraft entering or leaving a runway, relayed to clients
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunwayOccupancyEvent {
+    pub icao: String,
+    pub runway: String,
+    pub callsign: String,
+    /// "entered" or "exited"
+    pub kind: String,
+    /// Unix ms
+    pub time: u64,
+    /// Time spent on the runway (exits only)
+    pub occupied_secs: Option<u64>,
+    /// Aircraft already on the runway (entries only; any means a possible incursion)
+    pub already_occupied_by: Vec<String>,
+}
+
+/// Runway occupancy state (managed by Tauri)
+pub struct RunwayOccupancyState {
+    occupancy: RwLock<RunwayOccupancy>,
+    /// Entries and exits for WebSocket relay
+    pub events: broadcast::Sender<RunwayOccupancyEvent>,
+}
+
+impl RunwayOccupancyState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(256);
+        Self {
+            occupancy: RwLock::new(RunwayOccupancy::default()),
+            events,
+        }
+    }
+}
+
+/// Callsigns inside each runway's outline, by runway
+pub fn occupants(aircraft: &[Aircraft], runways: &[Runway], elevation_ft: f64) -> HashMap<String, Vec<String>> {
+    let outlines: Vec<(&Runway, Vec<[f64; 2]>)> = runways
+        .iter()
+        .filter_map(|r| runways::outline(r).map(|o| (r, o)))
+        .collect();
+    let mut occupants: HashMap<String, Vec<String>> = HashMap::new();
+    for a in aircraft
+        .iter()
+        .filter(|a| a.on_ground || a.altitude_ft - elevation_ft < OCCUPANCY_MAX_AGL_FT)
+    {
+        for (runway, outline) in &outlines {
+            if geo::point_in_ring(a.latitude, a.longitude, outline) {
+                occupants
+                    .entry(runway.ident.clone())
+                    .or_default()
+                    .push(a.callsign.clone());
+            }
+        }
+    }
+    occupants
+}
+
+/// New per-runway state from the current occupants, with the entries and
+/// exits since the previous state
+pub fn advance(
+    icao: &str,
+    runways: &[Runway],
+    previous: &[RunwayStatus],
+    occupants: &HashMap<String, Vec<String>>,
+    now: u64,
+) -> (Vec<RunwayStatus>, Vec<RunwayOccupancyEvent>) {
+    let previous: HashMap<&str, &[RunwayOccupant]> = previous
+        .iter()
+        .map(|s| (s.runway.as_str(), s.occupied_by.as_slice()))
+        .collect();
+    let mut statuses = BTreeMap::new();
+    let mut events = Vec::new();
+    let event = |runway: &str, callsign: &str, kind: &str, occupied_secs: Option<u64>, already_occupied_by: Vec<String>| {
+        RunwayOccupancyEvent {
+            icao: icao.to_string(),
+            runway: runway.to_string(),
+            callsign: callsign.to_string(),
+            kind: kind.to_string(),
+            time: now,
+            occupied_secs,
+            already_occupied_by,
+        }
+    };
+
+    for runway in runways {
+        let before = previous.get(runway.ident.as_str()).copied().unwrap_or_default();
+        let current = occupants.get(&runway.ident).map(Vec::as_slice).unwrap_or_default();
+
+        let mut occupied_by: Vec<RunwayOccupant> = before
+            .iter()
+            .filter(|o| current.contains(&o.callsign))
+            .cloned()
+            .collect();
+        for o in before.iter().filter(|o| !current.contains(&o.callsign)) {
+            let secs = now.saturating_sub(o.since) / 1000;
+            events.push(event(&runway.ident, &o.callsign, "exited", Some(secs), Vec::new()));
+        }
+        for callsign in current.iter().filter(|c| !before.iter().any(|o| &o.callsign == *c)) {
+            let already: Vec<String> = occupied_by.iter().map(|o| o.callsign.clone()).collect();
+            occupied_by.push(RunwayOccupant {
+                callsign: callsign.clone(),
+                since: now,
+            });
+            events.push(event(&runway.ident, callsign, "entered", None, already));
+        }
+        statuses.insert(
+            runway.ident.clone(),
+            RunwayStatus {
+                runway: runway.ident.clone(),
+                occupied_by,
+            },
+        );
+    }
+    (statuses.into_values().collect(), events)
+}
+
+fn publish(app: &AppHandle, occupancy: RunwayOccupancy, events: Vec<RunwayOccupancyEvent>) {
+    let state = app.state::<RunwayOccupancyState>();
+    *state.occupancy.write() = occupancy;
+    for event in events {
+        let _ = app.emit("runway-occupancy-changed", &event);
+        let _ = state.events.send(event);
+    }
+}
+
+/// Update runway occupancy from a traffic poll (airport elevation in feet)
+pub fn update(app: &AppHandle, snapshot: &TrafficSnapshot, runways: &[Runway], elevation_ft: f64) {
+    let Some(icao) = snapshot.icao.clone() else {
+        return;
+    };
+    let now = snapshot.updated_at.unwrap_or_default();
+    let current = get_occupancy(app);
+    // Aircraft on another airport's runways don't exit this one's
+    let previous = if current.icao.as_deref() == Some(icao.as_str()) {
+        current.runways
+    } else {
+        Vec::new()
+    };
+
+    let occupants = occupants(&snapshot.aircraft, runways, elevation_ft);
+    let (statuses, events) = advance(&icao, runways, &previous, &occupants, now);
+    for event in &events {
+        info!(
+            "[RunwayOccupancy] {} {} runway {}{}",
+            event.callsign,
+            event.kind,
+            event.runway,
+            event
+                .occupied_secs
+                .map(|s| format!(" after {} s", s))
+                .unwrap_or_default()
+        );
+    }
+    publish(
+        app,
+        RunwayOccupancy {
+            icao: Some(icao),
+            updated_at: snapshot.updated_at,
+            runways: statuses,
+        },
+        events,
+    );
+}
+
+/// Drop all occupancy (no active airport or traffic)
+pub fn clear(app: &AppHandle) {
+    *app.state::<RunwayOccupancyState>().occupancy.write() = RunwayOccupancy::default();
+}
+
+/// Current runway occupancy
+pub fn get_occupancy(app: &AppHandle) -> RunwayOccupancy {
+    app.state::<RunwayOccupancyState>().occupancy.read().clone()
+}
+
+/// Initialize runway occupancy state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_runway_occupancy(app: &AppHandle) {
+    app.manage(RunwayOccupancyState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get runway occupancy at the active airport
+#[tauri::command]
+pub fn get_runway_occupancy(app: AppHandle) -> RunwayOccupancy {
+    get_occupancy(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traffic::test_aircraft;
+
+    #[test]
+    fn tracks_runway_entries_and_exits() {
+        let runway = runways::test_runway();
+        let aircraft = |callsign: &str, longitude: f64, altitude_ft: f64, on_ground: bool| Aircraft {
+            latitude: 42.01,
+            longitude,
+            altitude_ft,
+            groundspeed_kt: 120.0,
+            track: Some(0.0),
+            on_ground,
+            ..test_aircraft(callsign)
+        };
+        let runways = [runway];
+
+        // On the centerline, in the flare, holding short (~100 m off) and overflying
+        let traffic = vec![
+            aircraft("ROLL", -71.0, 20.0, true),
+            aircraft("FLARE", -71.0002, 60.0, false),
+            aircraft("HOLD", -71.0012, 20.0, true),
+            aircraft("OVER", -71.0, 1500.0, false),
+        ];
+        let occupants = occupants(&traffic, &runways, 20.0);
+        assert_eq!(occupants["18/36"], vec!["ROLL".to_string(), "FLARE".to_string()]);
+
+        let (statuses, events) = advance("KXYZ", &runways, &[], &occupants, 1_000);
+        assert_eq!(statuses[0].occupied_by.len(), 2);
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|e| e.kind == "entered"));
+        assert!(events[0].already_occupied_by.is_empty());
+        assert_eq!(events[1].already_occupied_by, vec!["ROLL".to_string()]);
+
+        // ROLL vacates 45 s later; FLARE keeps its entry time
+        let later = HashMap::from([("18/36".to_string(), vec!["FLARE".to_string()])]);
+        let (statuses, events) = advance("KXYZ", &runways, &statuses, &later, 46_000);
+        assert_eq!(
+            statuses[0].occupied_by,
+            vec![RunwayOccupant {
+                callsign: "FLARE".to_string(),
+                since: 1_000
+            }]
+        );
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            (events[0].callsign.as_str(), events[0].kind.as_str()),
+            ("ROLL", "exited")
+        );
+        assert_eq!(events[0].occupied_secs, Some(45));
+        assert!(events[0].already_occupied_by.is_empty());
+    }
+}
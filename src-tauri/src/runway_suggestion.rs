@@ -0,0 +1,210 @@
+//! Runway-in-use suggestion from the current METAR wind
+//!
+//! Computes headwind, crosswind and tailwind components for every runway end
+//! at an airport and suggests the configuration a tower would likely be using:
+//! the end with the most headwind, plus its parallels, as long as the tailwind
+//! stays within limits. In calm or variable wind no configuration is suggested,
+//! since the calm wind runway is a local procedure.
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::error::{Error, Result};
+use crate::geo;
+use crate::runways::{self, Runway, RunwayEnd};
+use crate::weather::{self, Wind};
+
+/// Wind speed at or below which the wind counts as calm (knots)
+const CALM_WIND_KT: u16 = 3;
+
+/// Maximum tailwind for a runway end to be suggested (knots)
+const MAX_TAILWIND_KT: f64 = 5.0;
+
+/// Ends within this many degrees of the best end are used together (parallels)
+const PARALLEL_TOLERANCE_DEG: f64 = 20.0;
+
+/// Wind components for one runway end (knots)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunwayEndWind {
+    /// Runway (e.g., "10L/28R")
+    pub runway: String,
+    /// Runway end (e.g., "28R")
+    pub ident: String,
+    pub heading_true: f64,
+    pub length_ft: Option<f64>,
+    /// Negative for a tailwind
+    pub headwind_kt: f64,
+    pub tailwind_kt: f64,
+    /// Positive from the right, negative from the left
+    pub crosswind_kt: f64,
+    /// Crosswind in gusts (magnitude)
+    pub gust_crosswind_kt: Option<f64>,
+    /// Within the tailwind limit
+    pub suitable: bool,
+}
+
+/// Suggested runway configuration for an airport
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunwaySuggestion {
+    pub icao: String,
+    /// METAR the suggestion is based on
+    pub metar: String,
+    pub wind: Option<Wind>,
+    /// Calm or variable wind (no configuration suggested)
+    pub calm: bool,
+    /// Suggested runway ends (e.g., ["28L", "28R"])
+    pub active: Vec<String>,
+    /// All runway ends, most headwind first
+    pub ends: Vec<RunwayEndWind>,
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// Wind components for a runway end, None if it has no heading (e.g., helipads)
+fn end_wind(runway: &Runway, end: &RunwayEnd, wind: Option<&Wind>) -> Option<RunwayEndWind> {
+    let heading = end.heading_true?;
+    let (headwind, crosswind, gust_crosswind) = match wind.and_then(|w| Some((w.direction?, w))) {
+        Some((direction, wind)) => {
+            let angle = (direction as f64 - heading).to_radians();
+            let crosswind_for = |speed: u16| speed as f64 * angle.sin();
+            (
+                wind.speed_kt as f64 * angle.cos(),
+                crosswind_for(wind.speed_kt),
+                wind.gust_kt.map(|g| round1(crosswind_for(g).abs())),
+            )
+        }
+        None => (0.0, 0.0, None),
+    };
+    let tailwind = (-headwind).max(0.0);
+
+    Some(RunwayEndWind {
+        runway: runway.ident.clone(),
+        ident: end.ident.clone(),
+        heading_true: heading,
+        length_ft: runway.length_ft,
+        headwind_kt: round1(headwind),
+        tailwind_kt: round1(tailwind),
+        crosswind_kt: round1(crosswind),
+        gust_crosswind_kt: gust_crosswind,
+        suitable: tailwind <= MAX_TAILWIND_KT,
+    })
+}
+
+/// Suggest the runway configuration for a set of runways and a wind
+pub fn suggest(runways: &[Runway], wind: Option<&Wind>) -> (bool, Vec<String>, Vec<RunwayEndWind>) {
+    let calm = !wind.is_some_and(|w| w.direction.is_some() && w.speed_kt > CALM_WIND_KT);
+
+    let mut ends: Vec<RunwayEndWind> = runways
+        .iter()
+        .flat_map(|r| [end_wind(r, &r.low_end, wind), end_wind(r, &r.high_end, wind)])
+        .flatten()
+        .collect();
+    ends.sort_by(|a, b| {
+        b.headwind_kt
+            .total_cmp(&a.headwind_kt)
+            .then(b.length_ft.unwrap_or(0.0).total_cmp(&a.length_ft.unwrap_or(0.0)))
+    });
+
+    let active = match ends.first() {
+        Some(best) if !calm && best.suitable => ends
+            .iter()
+            .filter(|e| {
+                e.suitable && geo::heading_difference(e.heading_true, best.heading_true) <= PARALLEL_TOLERANCE_DEG
+            })
+            .map(|e| e.ident.clone())
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    (calm, active, ends)
+}
+
+/// Suggest the runway configuration at an airport from its current METAR
+pub async fn get_suggestion(app: &AppHandle, icao: &str) -> Result<Option<RunwaySuggestion>> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    let Some(metar) = weather::get_metar(app, &icao, false).await? else {
+        return Ok(None);
+    };
+
+    let runways = runways::runways_for_airport(app, &icao).await.map_err(Error::io)?;
+    let wind = metar.conditions.wind;
+    let (calm, active, ends) = suggest(&runways, wind.as_ref());
+
+    Ok(Some(RunwaySuggestion {
+        icao,
+        metar: metar.raw,
+        wind,
+        calm,
+        active,
+        ends,
+    }))
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Suggest the runway configuration at an airport from the current METAR wind
+#[tauri::command]
+pub async fn get_runway_suggestion(app: AppHandle, icao: String) -> Result<Option<RunwaySuggestion>> {
+    get_suggestion(&app, &icao).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runway(low: &str, low_heading: f64, high: &str, length_ft: f64) -> Runway {
+        let end = |ident: &str, heading: f64| RunwayEnd {
+            ident: ident.to_string(),
+            latitude: None,
+            longitude: None,
+            heading_true: Some(heading),
+            elevation_ft: None,
+            displaced_threshold_ft: 0.0,
+        };
+        Runway {
+            ident: format!("{}/{}", low, high),
+            low_end: end(low, low_heading),
+            high_end: end(high, low_heading + 180.0),
+            length_ft: Some(length_ft),
+            width_ft: None,
+            surface: "ASPH".to_string(),
+            lighted: true,
+        }
+    }
+
+    fn wind(direction: Option<u16>, speed_kt: u16) -> Wind {
+        Wind {
+            direction,
+            speed_kt,
+            gust_kt: None,
+            variable_from: None,
+            variable_to: None,
+        }
+    }
+
+    #[test]
+    fn suggests_parallels_into_the_wind() {
+        let runways = [
+            runway("10L", 118.0, "28R", 11870.0),
+            runway("10R", 118.0, "28L", 11381.0),
+            runway("01L", 28.0, "19R", 7650.0),
+        ];
+
+        let (calm, active, ends) = suggest(&runways, Some(&wind(Some(290), 15)));
+        assert!(!calm);
+        assert_eq!(active, vec!["28R", "28L"]);
+        assert_eq!(ends[0].headwind_kt, 14.9);
+        assert!(ends[0].crosswind_kt < -2.0);
+        assert!(ends.iter().find(|e| e.ident == "10L").is_some_and(|e| !e.suitable));
+
+        let (calm, active, _) = suggest(&runways, Some(&wind(None, 4)));
+        assert!(calm);
+        assert!(active.is_empty());
+    }
+}
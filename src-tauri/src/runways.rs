@@ -0,0 +1,311 @@
+//! Runway data from the bundled OurAirports runways.csv
+//!
+//! Parsed into the airport database (see `airport_db`) and queried from there.
+//! Closed runways and runways without identifiers for both ends are skipped,
+//! matching the frontend's RunwayService.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::airport_db;
+use crate::geo;
+
+/// Runway end (threshold)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunwayEnd {
+    /// Designator (e.g., "09L")
+    pub ident: String,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// True heading (from the designator if not in the data)
+    pub heading_true: Option<f64>,
+    pub elevation_ft: Option<f64>,
+    pub displaced_threshold_ft: f64,
+}
+
+/// Runway with both ends
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Runway {
+    /// Combined designator (e.g., "09L/27R")
+    pub ident: String,
+    pub low_end: RunwayEnd,
+    pub high_end: RunwayEnd,
+    pub length_ft: Option<f64>,
+    pub width_ft: Option<f64>,
+    pub surface: String,
+    pub lighted: bool,
+}
+
+/// Width assumed for runways without one in the data (feet)
+const DEFAULT_WIDTH_FT: f64 = 150.0;
+
+const FEET_PER_NM: f64 = 6076.12;
+
+/// Runways by airport ident (uppercase)
+type RunwayIndex = HashMap<String, Vec<Runway>>;
+
+/// Split a CSV line, handling quoted fields
+pub fn parse_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in line.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut current).trim().to_string()),
+            _ => current.push(c),
+        }
+    }
+    fields.push(current.trim().to_string());
+    fields
+}
+
+/// Heading from a designator's number ("27R" -> 270)
+fn heading_from_ident(ident: &str) -> Option<f64> {
+    let digits: String = ident.chars().take_while(|c| c.is_ascii_digit()).collect();
+    if digits.is_empty() || digits.len() > 2 {
+        return None;
+    }
+    digits.parse::<f64>().ok().map(|n| n * 10.0)
+}
+
+/// Parse runways.csv into an index by airport ident
+pub fn parse_runways_csv(text: &str) -> RunwayIndex {
+    let mut lines = text.lines();
+    let Some(header) = lines.next() else {
+        return HashMap::new();
+    };
+    let columns: HashMap<String, usize> = parse_csv_line(header)
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| (name, i))
+        .collect();
+
+    let mut index: RunwayIndex = HashMap::new();
+    for line in lines {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let values = parse_csv_line(line);
+        let field = |name: &str| -> &str {
+            columns
+                .get(name)
+                .and_then(|&i| values.get(i))
+                .map(|v| v.as_str())
+                .unwrap_or("")
+        };
+        let number = |name: &str| field(name).parse::<f64>().ok();
+
+        if field("closed") == "1" {
+            continue;
+        }
+
+        let end = |prefix: &str| -> Option<RunwayEnd> {
+            let ident = field(&format!("{}_ident", prefix));
+            if ident.is_empty() {
+                return None;
+            }
+            Some(RunwayEnd {
+                ident: ident.to_string(),
+                latitude: number(&format!("{}_latitude_deg", prefix)),
+                longitude: number(&format!("{}_longitude_deg", prefix)),
+                heading_true: number(&format!("{}_heading_degT", prefix)).or_else(|| heading_from_ident(ident)),
+                elevation_ft: number(&format!("{}_elevation_ft", prefix)),
+                displaced_threshold_ft: number(&format!("{}_displaced_threshold_ft", prefix)).unwrap_or(0.0),
+            })
+        };
+        let (Some(low_end), Some(high_end)) = (end("le"), end("he")) else {
+            continue;
+        };
+
+        let airport = field("airport_ident").to_uppercase();
+        if airport.is_empty() {
+            continue;
+        }
+        index.entry(airport).or_default().push(Runway {
+            ident: format!("{}/{}", low_end.ident, high_end.ident),
+            low_end,
+            high_end,
+            length_ft: number("length_ft"),
+            width_ft: number("width_ft"),
+            surface: field("surface").to_string(),
+            lighted: field("lighted") == "1",
+        });
+    }
+    index
+}
+
+/// Aircraft position relative to a runway end
+#[derive(Debug, Clone, Copy)]
+pub struct RunwayAlignment<'a> {
+    pub end: &'a RunwayEnd,
+    /// Distance along the centerline from the threshold, negative before it (nm)
+    pub along_nm: f64,
+    /// Distance from the extended centerline (nm)
+    pub offset_nm: f64,
+    /// Threshold to opposite threshold (nm)
+    pub length_nm: f64,
+}
+
+/// Largest difference between an aircraft's track and the runway heading to count as lined up (degrees)
+const ALIGNED_MAX_TRACK_DIFF: f64 = 30.0;
+
+/// Short final: below this height above the threshold (feet)
+pub const SHORT_FINAL_MAX_AGL_FT: f64 = 1500.0;
+
+/// Short final: lined up within this of the centerline, up to this far before
+/// the threshold (nm)
+pub const SHORT_FINAL_MAX_OFFSET_NM: f64 = 0.5;
+pub const SHORT_FINAL_MAX_BEFORE_NM: f64 = 4.0;
+
+/// Position of a point relative to a runway end, if both ends have coordinates
+pub fn alignment<'a>(runway: &'a Runway, end: &'a RunwayEnd, lat: f64, lon: f64) -> Option<RunwayAlignment<'a>> {
+    let opposite = if std::ptr::eq(end, &runway.low_end) {
+        &runway.high_end
+    } else {
+        &runway.low_end
+    };
+    let (end_lat, end_lon) = (end.latitude?, end.longitude?);
+    let (length_nm, runway_bearing) =
+        geo::distance_and_bearing(end_lat, end_lon, opposite.latitude?, opposite.longitude?);
+    let (distance_nm, bearing) = geo::distance_and_bearing(end_lat, end_lon, lat, lon);
+    let angle = (bearing - runway_bearing).to_radians();
+    Some(RunwayAlignment {
+        end,
+        along_nm: distance_nm * angle.cos(),
+        offset_nm: (distance_nm * angle.sin()).abs(),
+        length_nm,
+    })
+}
+
+/// Runway end an aircraft is lined up with: tracking within 30 degrees of the
+/// runway heading, within `max_offset_nm` of the extended centerline and no more
+/// than `max_before_nm` before the threshold or `max_after_nm` past the far end.
+/// The closest to its centerline wins.
+pub fn aligned_runway_end(
+    runways: &[Runway],
+    lat: f64,
+    lon: f64,
+    track: f64,
+    max_offset_nm: f64,
+    max_before_nm: f64,
+    max_after_nm: f64,
+) -> Option<RunwayAlignment<'_>> {
+    runways
+        .iter()
+        .flat_map(|runway| [(runway, &runway.low_end), (runway, &runway.high_end)])
+        .filter_map(|(runway, end)| alignment(runway, end, lat, lon))
+        .filter(|a| {
+            let heading = a.end.heading_true.unwrap_or(0.0);
+            geo::heading_difference(track, heading) <= ALIGNED_MAX_TRACK_DIFF
+                && a.offset_nm <= max_offset_nm
+                && a.along_nm >= -max_before_nm
+                && a.along_nm <= a.length_nm + max_after_nm
+        })
+        .min_by(|a, b| a.offset_nm.total_cmp(&b.offset_nm))
+}
+
+/// Runway end an aircraft is lined up with for a short final approach (still
+/// matched up to `max_after_nm` past the far end), see `aligned_runway_end`
+pub fn final_approach_end(
+    runways: &[Runway],
+    lat: f64,
+    lon: f64,
+    track: f64,
+    max_after_nm: f64,
+) -> Option<RunwayAlignment<'_>> {
+    aligned_runway_end(
+        runways,
+        lat,
+        lon,
+        track,
+        SHORT_FINAL_MAX_OFFSET_NM,
+        SHORT_FINAL_MAX_BEFORE_NM,
+        max_after_nm,
+    )
+}
+
+/// Whether an aircraft lined up with a runway end is on short final: before
+/// the threshold and no higher than `SHORT_FINAL_MAX_AGL_FT` above it
+pub fn is_short_final(alignment: &RunwayAlignment, height_ft: f64) -> bool {
+    alignment.along_nm <= 0.0 && height_ft <= SHORT_FINAL_MAX_AGL_FT
+}
+
+/// Runway surface as a polygon ring of [longitude, latitude] pairs: the
+/// rectangle between both ends, as wide as the runway
+pub fn outline(runway: &Runway) -> Option<Vec<[f64; 2]>> {
+    let (low_lat, low_lon) = (runway.low_end.latitude?, runway.low_end.longitude?);
+    let (high_lat, high_lon) = (runway.high_end.latitude?, runway.high_end.longitude?);
+    let (_, bearing) = geo::distance_and_bearing(low_lat, low_lon, high_lat, high_lon);
+    let half_width_nm = runway.width_ft.filter(|w| *w > 0.0).unwrap_or(DEFAULT_WIDTH_FT) / 2.0 / FEET_PER_NM;
+
+    let corner = |lat: f64, lon: f64, side: f64| {
+        let (lat, lon) = geo::destination(lat, lon, bearing + side, half_width_nm);
+        [lon, lat]
+    };
+    Some(vec![
+        corner(low_lat, low_lon, -90.0),
+        corner(high_lat, high_lon, -90.0),
+        corner(high_lat, high_lon, 90.0),
+        corner(low_lat, low_lon, 90.0),
+    ])
+}
+
+/// Open runways at an airport (empty if unknown)
+pub async fn runways_for_airport(app: &AppHandle, icao: &str) -> Result<Vec<Runway>, String> {
+    Ok(airport_db::get_runways(app, icao.to_string())
+        .await?
+        .unwrap_or_default())
+}
+
+/// North-south runway 18/36 shared by tests: the runway 36 threshold at 42.0N 71.0W,
+/// runway 18 about 1.8 nm north
+#[cfg(test)]
+pub fn test_runway() -> Runway {
+    let end = |ident: &str, latitude: f64, heading: f64| RunwayEnd {
+        ident: ident.to_string(),
+        latitude: Some(latitude),
+        longitude: Some(-71.0),
+        heading_true: Some(heading),
+        elevation_ft: Some(20.0),
+        displaced_threshold_ft: 0.0,
+    };
+    Runway {
+        ident: "18/36".to_string(),
+        low_end: end("36", 42.0, 0.0),
+        high_end: end("18", 42.03, 180.0),
+        length_ft: Some(10000.0),
+        width_ft: Some(150.0),
+        surface: "ASPH".to_string(),
+        lighted: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_runways_csv() {
+        let csv = "\"id\",\"airport_ident\",\"length_ft\",\"width_ft\",\"surface\",\"lighted\",\"closed\",\"le_ident\",\"le_heading_degT\",\"he_ident\",\"he_heading_degT\"\n\
+            1,\"KSFO\",11870,200,\"ASPH\",1,0,\"10L\",117.8,\"28R\",297.8\n\
+            2,\"KSFO\",7650,200,\"ASPH\",1,0,\"01R\",,\"19L\",\n\
+            3,\"KSFO\",3000,75,\"ASPH\",0,1,\"13\",,\"31\",\n\
+            4,\"00A\",80,80,\"ASPH-G\",1,0,\"H1\",,,\n";
+        let index = parse_runways_csv(csv);
+
+        let ksfo = &index["KSFO"];
+        assert_eq!(ksfo.len(), 2);
+        assert_eq!(ksfo[0].ident, "10L/28R");
+        assert_eq!(ksfo[0].high_end.heading_true, Some(297.8));
+        assert_eq!(ksfo[1].low_end.heading_true, Some(10.0));
+        assert_eq!(ksfo[1].high_end.heading_true, Some(190.0));
+        assert!(!index.contains_key("00A"));
+    }
+}
@@ -0,0 +1,169 @@
+//! Path resolution for files served over HTTP
+//!
+//! Every file-serving route resolves request paths through a [`ServedRoot`]
+//! instead of joining and canonicalizing by hand:
+//!
+//! - Request paths must be plain relative paths: no `..`, `.`, absolute or
+//!   drive paths, backslashes, colons (Windows alternate streams) or NULs
+//! - Symlinks follow the root's [`SymlinkPolicy`]. Links that leave the root
+//!   are only followed into the extra allowed roots (`server.allowedFileRoots`
+//!   in global settings, e.g. a network share the mods folder links to)
+//! - The resolved file is canonicalized and must lie inside an allowed root.
+//!   Roots that can't be canonicalized (e.g. a disconnected network share)
+//!   serve nothing
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// How symlinks under a root are treated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymlinkPolicy {
+    /// Never follow symlinks (the app's own files)
+    Deny,
+    /// Follow symlinks that stay inside the root or an extra allowed root
+    WithinAllowed,
+}
+
+/// Why a path can't be served
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathError {
+    /// Malformed request path
+    Invalid,
+    NotFound,
+    /// Outside the allowed roots, or a symlink the policy rejects
+    Forbidden,
+}
+
+/// A folder files are served from
+#[derive(Debug, Clone)]
+pub struct ServedRoot {
+    /// The root as configured
+    base: PathBuf,
+    /// Canonical root
+    root: PathBuf,
+    /// Canonical roots resolved files may lie in (the root first)
+    allowed: Vec<PathBuf>,
+    policy: SymlinkPolicy,
+}
+
+/// Whether a request path is a plain relative path
+pub fn valid_relative(relative: &str) -> bool {
+    !relative.is_empty()
+        && !relative.contains(['\\', ':', '\0'])
+        && Path::new(relative)
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+}
+
+impl ServedRoot {
+    /// A root with extra roots symlinks may point into (missing extra roots are ignored)
+    pub fn new(base: &Path, extra_roots: &[PathBuf], policy: SymlinkPolicy) -> Result<Self, PathError> {
+        let root = fs::canonicalize(base).map_err(|_| PathError::NotFound)?;
+        let mut allowed = vec![root.clone()];
+        if policy == SymlinkPolicy::WithinAllowed {
+            allowed.extend(extra_roots.iter().filter_map(|r| fs::canonicalize(r).ok()));
+        }
+        Ok(ServedRoot {
+            base: base.to_path_buf(),
+            root,
+            allowed,
+            policy,
+        })
+    }
+
+    fn is_allowed(&self, canonical: &Path) -> bool {
+        self.allowed.iter().any(|root| canonical.starts_with(root))
+    }
+
+    /// Resolve a request path (relative to the root) to a canonical file path
+    pub fn resolve(&self, relative: &str) -> Result<PathBuf, PathError> {
+        if !valid_relative(relative) {
+            return Err(PathError::Invalid);
+        }
+
+        // Check every component, so a link can't be hidden behind a later `starts_with`
+        let mut current = self.root.clone();
+        for component in Path::new(relative).components() {
+            current.push(component);
+            let metadata = fs::symlink_metadata(&current).map_err(|_| PathError::NotFound)?;
+            if metadata.file_type().is_symlink() {
+                if self.policy == SymlinkPolicy::Deny {
+                    return Err(PathError::Forbidden);
+                }
+                let target = fs::canonicalize(&current).map_err(|_| PathError::NotFound)?;
+                if !self.is_allowed(&target) {
+                    return Err(PathError::Forbidden);
+                }
+            }
+        }
+
+        let canonical = fs::canonicalize(&current).map_err(|_| PathError::NotFound)?;
+        if !self.is_allowed(&canonical) {
+            return Err(PathError::Forbidden);
+        }
+        if !canonical.is_file() {
+            return Err(PathError::NotFound);
+        }
+        Ok(canonical)
+    }
+
+    /// Check a path built by the app (e.g. from an index) that should lie under the root
+    pub fn check(&self, path: &Path) -> Result<PathBuf, PathError> {
+        let relative = path
+            .strip_prefix(&self.base)
+            .or_else(|_| path.strip_prefix(&self.root))
+            .map_err(|_| PathError::Forbidden)?;
+        self.resolve(&relative.to_string_lossy().replace('\\', "/"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_traversal_and_links_outside_roots() {
+        let dir = std::env::temp_dir().join(format!("safe-path-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mods = dir.join("mods");
+        let share = dir.join("share");
+        let private = dir.join("private");
+        for folder in [&mods, &share, &private] {
+            fs::create_dir_all(folder.join("B738")).unwrap();
+            fs::write(folder.join("B738/model.glb"), "glb").unwrap();
+        }
+
+        let served = ServedRoot::new(&mods, std::slice::from_ref(&share), SymlinkPolicy::WithinAllowed).unwrap();
+        assert!(served.resolve("B738/model.glb").is_ok());
+        assert_eq!(
+            served.check(&mods.join("B738/model.glb")),
+            served.resolve("B738/model.glb")
+        );
+        assert_eq!(served.check(&private.join("B738/model.glb")), Err(PathError::Forbidden));
+        for bad in [
+            "../private/B738/model.glb",
+            "B738/../../private",
+            "/etc/passwd",
+            "B738\\model.glb",
+            "C:x",
+            "",
+        ] {
+            assert_eq!(served.resolve(bad), Err(PathError::Invalid), "{}", bad);
+        }
+        assert_eq!(served.resolve("B738/missing.glb"), Err(PathError::NotFound));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::symlink;
+            symlink(share.join("B738"), mods.join("shared")).unwrap();
+            symlink(private.join("B738"), mods.join("escape")).unwrap();
+            assert!(served.resolve("shared/model.glb").is_ok());
+            assert_eq!(served.resolve("escape/model.glb"), Err(PathError::Forbidden));
+
+            let strict = ServedRoot::new(&mods, std::slice::from_ref(&share), SymlinkPolicy::Deny).unwrap();
+            assert_eq!(strict.resolve("shared/model.glb"), Err(PathError::Forbidden));
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
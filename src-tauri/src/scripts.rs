@@ -0,0 +1,486 @@
+//! Scripting hooks
+//!
+//! Loads Rhai scripts from `mods/scripts/*.rhai` so facilities can add their
+//! own automation without forking the app. A script subscribes to backend
+//! events by defining a one-argument function named after the event:
+//!
+//! - `on_traffic(snapshot)`: every traffic poll around the active airport
+//! - `on_alert(alert)`: `#{kind, data}`, where kind is "squawk", "stca",
+//!   "weather", "lightning" or "go-around" and data the alert as sent to clients
+//! - `on_weather(metar)`: the active airport's METAR changed
+//!
+//! Scripts can only reach the host through a small API:
+//!
+//! - `emit(name, data)`: send a custom event to the app (`script-event`) and
+//!   remote browsers (`/api/scripts/ws`)
+//! - `webhook(url, data)`: POST `data` as JSON to an http(s) URL
+//! - `write_file(name, text)` / `append_file(name, text)`: write a file in
+//!   `mods/scripts/output/` (plain file names only)
+//! - `print(text)` / `debug(value)`: write to the app log
+//!
+//! Scripts run one event at a time on their own thread, with limits on
+//! operations, call depth and data sizes so a runaway script can't stall the
+//! app. Events arriving while the queue is full are dropped. Scripts are
+//! reloaded whenever something in `mods/scripts/` changes.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use parking_lot::{Mutex, RwLock};
+use rhai::{CallFnOptions, Dynamic, Engine, EvalAltResult, Scope, AST};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+use crate::supervisor::{self, Restart};
+use crate::{find_mods_root, http_client};
+
+/// Events scripts can subscribe to (`on_<event>`)
+pub const EVENTS: [&str; 3] = ["traffic", "alert", "weather"];
+
+/// Events waiting for the script thread
+const QUEUE_SIZE: usize = 64;
+
+/// Operations a single handler call may run
+const MAX_OPERATIONS: u64 = 500_000;
+
+/// Largest file a script may write (bytes)
+const MAX_OUTPUT_FILE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// A loaded script and the events it handles
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptInfo {
+    /// File name (e.g., "gate-board.rhai")
+    pub name: String,
+    /// Events the script subscribes to
+    pub handlers: Vec<String>,
+    /// Compile error or last runtime error
+    pub error: Option<String>,
+}
+
+/// Custom event emitted by a script
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptEvent {
+    /// Script that emitted it
+    pub script: String,
+    pub name: String,
+    pub data: Value,
+    /// Unix ms
+    pub time: u64,
+}
+
+enum Command {
+    Event { event: String, payload: Value },
+    Reload,
+}
+
+/// Scripting state (managed by Tauri)
+pub struct ScriptState {
+    queue: Mutex<Option<SyncSender<Command>>>,
+    /// Events at least one script handles (others aren't serialized or queued)
+    handled: RwLock<HashSet<String>>,
+    scripts: RwLock<Vec<ScriptInfo>>,
+    /// Custom events for remote browsers
+    pub events: broadcast::Sender<ScriptEvent>,
+}
+
+impl ScriptState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(256);
+        Self {
+            queue: Mutex::new(None),
+            handled: RwLock::new(HashSet::new()),
+            scripts: RwLock::new(Vec::new()),
+            events,
+        }
+    }
+}
+
+impl Default for ScriptState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Folder scripts are loaded from
+pub fn scripts_root(app: &AppHandle) -> PathBuf {
+    find_mods_root(app).join("scripts")
+}
+
+/// A file name scripts may write: no directories, not hidden
+pub fn valid_output_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 128
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Engine with execution limits and no host API
+pub fn new_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(32)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(1024 * 1024)
+        .set_max_array_size(100_000)
+        .set_max_map_size(100_000);
+    engine.disable_symbol("eval");
+    engine
+}
+
+/// Events a compiled script subscribes to
+pub fn handlers(ast: &AST) -> Vec<String> {
+    EVENTS
+        .iter()
+        .filter(|event| {
+            let name = format!("on_{}", event);
+            ast.iter_functions().any(|f| f.name == name && f.params.len() == 1)
+        })
+        .map(|event| event.to_string())
+        .collect()
+}
+
+fn to_value(data: &Dynamic) -> Result<Value, Box<EvalAltResult>> {
+    rhai::serde::from_dynamic::<Value>(data)
+}
+
+/// Write or append a file in the scripts output folder
+fn write_output(root: &Path, name: &str, text: &str, append: bool) -> Result<(), String> {
+    if !valid_output_name(name) {
+        return Err(format!("Invalid output file name: {}", name));
+    }
+    let dir = root.join("output");
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+    let path = dir.join(name);
+    let existing = if append {
+        fs::metadata(&path).map(|m| m.len()).unwrap_or(0)
+    } else {
+        0
+    };
+    if existing + text.len() as u64 > MAX_OUTPUT_FILE_BYTES {
+        return Err(format!("{} would exceed {} bytes", name, MAX_OUTPUT_FILE_BYTES));
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(&path)
+        .map_err(|e| format!("Failed to open {}: {}", name, e))?;
+    file.write_all(text.as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", name, e))
+}
+
+/// Register the host API (`emit`, `webhook`, `write_file`, `append_file`)
+fn register_api(engine: &mut Engine, app: &AppHandle, current: Arc<Mutex<String>>) {
+    let print_current = current.clone();
+    engine.on_print(move |text| info!("[Scripts] {}: {}", print_current.lock(), text));
+    let debug_current = current.clone();
+    engine.on_debug(move |text, _, position| debug!("[Scripts] {} ({}): {}", debug_current.lock(), position, text));
+
+    let emit_app = app.clone();
+    let emit_current = current.clone();
+    engine.register_fn("emit", move |name: &str, data: Dynamic| -> Result<(), Box<EvalAltResult>> {
+        let event = ScriptEvent {
+            script: emit_current.lock().clone(),
+            name: name.to_string(),
+            data: to_value(&data)?,
+            time: now_ms(),
+        };
+        let _ = emit_app.emit("script-event", &event);
+        let _ = emit_app.state::<ScriptState>().events.send(event);
+        Ok(())
+    });
+
+    let webhook_current = current.clone();
+    engine.register_fn("webhook", move |url: &str, data: Dynamic| -> Result<(), Box<EvalAltResult>> {
+        let parsed = url::Url::parse(url).map_err(|e| format!("Invalid webhook URL {}: {}", url, e))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(format!("Webhook URL must be http(s): {}", url).into());
+        }
+        let body = to_value(&data)?;
+        let script = webhook_current.lock().clone();
+        tauri::async_runtime::spawn(async move {
+            let result = http_client::client()
+                .post(parsed)
+                .timeout(Duration::from_secs(10))
+                .json(&body)
+                .send()
+                .await;
+            match result {
+                Ok(response) if !response.status().is_success() => {
+                    warn!("[Scripts] {}: webhook returned HTTP {}", script, response.status())
+                }
+                Ok(_) => {}
+                Err(e) => warn!("[Scripts] {}: failed to post webhook: {}", script, e),
+            }
+        });
+        Ok(())
+    });
+
+    let root = scripts_root(app);
+    let write_root = root.clone();
+    engine.register_fn("write_file", move |name: &str, text: &str| -> Result<(), Box<EvalAltResult>> {
+        write_output(&write_root, name, text, false).map_err(Into::into)
+    });
+    engine.register_fn("append_file", move |name: &str, text: &str| -> Result<(), Box<EvalAltResult>> {
+        write_output(&root, name, text, true).map_err(Into::into)
+    });
+}
+
+/// A compiled script with its top-level state
+struct LoadedScript {
+    info: ScriptInfo,
+    ast: AST,
+    scope: Scope<'static>,
+}
+
+/// Compile and run every script in the folder (sorted by file name)
+fn load_scripts(engine: &Engine, root: &Path, current: &Mutex<String>) -> Vec<LoadedScript> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(root)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| p.is_file() && p.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("rhai")))
+                .collect()
+        })
+        .unwrap_or_default();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            *current.lock() = name.clone();
+            let mut scope = Scope::new();
+            let compiled = fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read script: {}", e))
+                .and_then(|source| engine.compile(source).map_err(|e| e.to_string()))
+                .and_then(|ast| {
+                    engine
+                        .run_ast_with_scope(&mut scope, &ast)
+                        .map(|_| ast)
+                        .map_err(|e| e.to_string())
+                });
+            match compiled {
+                Ok(ast) => {
+                    let handlers = handlers(&ast);
+                    info!("[Scripts] Loaded {} (handles {})", name, handlers.join(", "));
+                    LoadedScript {
+                        info: ScriptInfo {
+                            name,
+                            handlers,
+                            error: None,
+                        },
+                        ast,
+                        scope,
+                    }
+                }
+                Err(e) => {
+                    warn!("[Scripts] {}: {}", name, e);
+                    LoadedScript {
+                        info: ScriptInfo {
+                            name,
+                            handlers: Vec::new(),
+                            error: Some(e),
+                        },
+                        ast: AST::empty(),
+                        scope,
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+fn publish_scripts(app: &AppHandle, scripts: &[LoadedScript]) {
+    let state = app.state::<ScriptState>();
+    *state.handled.write() = scripts.iter().flat_map(|s| s.info.handlers.iter().cloned()).collect();
+    *state.scripts.write() = scripts.iter().map(|s| s.info.clone()).collect();
+}
+
+/// Script thread: load scripts, then run handlers for each queued event
+fn run(app: AppHandle, commands: Receiver<Command>) {
+    let current = Arc::new(Mutex::new(String::new()));
+    let mut engine = new_engine();
+    register_api(&mut engine, &app, current.clone());
+    let root = scripts_root(&app);
+
+    let mut scripts = load_scripts(&engine, &root, &current);
+    publish_scripts(&app, &scripts);
+
+    while let Ok(command) = commands.recv() {
+        match command {
+            Command::Reload => {
+                scripts = load_scripts(&engine, &root, &current);
+                publish_scripts(&app, &scripts);
+            }
+            Command::Event { event, payload } => {
+                let function = format!("on_{}", event);
+                let Ok(argument) = rhai::serde::to_dynamic(&payload) else {
+                    continue;
+                };
+                let mut failed = false;
+                for script in scripts.iter_mut().filter(|s| s.info.handlers.contains(&event)) {
+                    *current.lock() = script.info.name.clone();
+                    let options = CallFnOptions::new().eval_ast(false).rewind_scope(true);
+                    let result = engine.call_fn_with_options::<Dynamic>(
+                        options,
+                        &mut script.scope,
+                        &script.ast,
+                        &function,
+                        (argument.clone(),),
+                    );
+                    if let Err(e) = result {
+                        warn!("[Scripts] {} {}: {}", script.info.name, function, e);
+                        script.info.error = Some(e.to_string());
+                        failed = true;
+                    }
+                }
+                if failed {
+                    publish_scripts(&app, &scripts);
+                }
+            }
+        }
+    }
+}
+
+/// Queue an event for scripts that handle it
+pub fn dispatch(app: &AppHandle, event: &str, payload: &impl Serialize) {
+    let Some(state) = app.try_state::<ScriptState>() else {
+        return;
+    };
+    if !state.handled.read().contains(event) {
+        return;
+    }
+    let Ok(payload) = serde_json::to_value(payload) else {
+        return;
+    };
+    let queue = state.queue.lock();
+    let Some(queue) = queue.as_ref() else {
+        return;
+    };
+    let command = Command::Event {
+        event: event.to_string(),
+        payload,
+    };
+    if let Err(TrySendError::Full(_)) = queue.try_send(command) {
+        warn!("[Scripts] Event queue full, dropped a {} event", event);
+    }
+}
+
+/// Queue an alert (`on_alert(#{kind, data})`)
+pub fn alert(app: &AppHandle, kind: &str, data: &impl Serialize) {
+    dispatch(app, "alert", &json!({ "kind": kind, "data": data }));
+}
+
+/// Reload every script (e.g., after `mods/scripts/` changed)
+pub fn reload(app: &AppHandle) {
+    if let Some(queue) = app.state::<ScriptState>().queue.lock().as_ref() {
+        let _ = queue.try_send(Command::Reload);
+    }
+}
+
+/// Loaded scripts and their errors
+pub fn list(app: &AppHandle) -> Vec<ScriptInfo> {
+    app.state::<ScriptState>().scripts.read().clone()
+}
+
+/// Initialize scripting state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_scripts(app: &AppHandle) {
+    app.manage(ScriptState::new());
+}
+
+/// Load scripts and start the script thread
+pub fn start_scripts_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn_blocking("scripts", Restart::Always, move || {
+        // A fresh queue for every start; events sent while restarting are dropped
+        let (sender, receiver) = mpsc::sync_channel(QUEUE_SIZE);
+        *app.state::<ScriptState>().queue.lock() = Some(sender);
+        run(app.clone(), receiver);
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// List loaded scripts, the events they handle and their errors
+#[tauri::command]
+pub fn list_scripts(app: AppHandle) -> Vec<ScriptInfo> {
+    list(&app)
+}
+
+/// Reload every script from `mods/scripts/`
+#[tauri::command]
+pub fn reload_scripts(app: AppHandle) {
+    reload(&app);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_event_handlers() {
+        let engine = new_engine();
+        let ast = engine
+            .compile("fn on_alert(alert) { alert.kind } fn on_weather() {} fn helper(x) { x }")
+            .unwrap();
+        assert_eq!(handlers(&ast), vec!["alert"]);
+    }
+
+    #[test]
+    fn stops_runaway_scripts() {
+        let engine = new_engine();
+        assert!(engine.run("loop { }").is_err());
+        assert!(engine.run("eval(\"1\")").is_err());
+    }
+
+    #[test]
+    fn converts_event_payloads() {
+        let engine = new_engine();
+        let ast = engine
+            .compile("fn on_alert(alert) { `${alert.kind}:${alert.data.callsign}` }")
+            .unwrap();
+        let payload = json!({ "kind": "squawk", "data": { "callsign": "DAL123" } });
+        let argument = rhai::serde::to_dynamic(&payload).unwrap();
+        let mut scope = Scope::new();
+        let options = CallFnOptions::new().eval_ast(false);
+        let result = engine
+            .call_fn_with_options::<String>(options, &mut scope, &ast, "on_alert", (argument,))
+            .unwrap();
+        assert_eq!(result, "squawk:DAL123");
+    }
+
+    #[test]
+    fn validates_output_names() {
+        assert!(valid_output_name("gate-board.json"));
+        assert!(!valid_output_name("../escape.txt"));
+        assert!(!valid_output_name("dir/file.txt"));
+        assert!(!valid_output_name(".hidden"));
+        assert!(!valid_output_name(""));
+    }
+}
@@ -0,0 +1,481 @@
+//! Unified content search
+//!
+//! Backs the quick switcher (Ctrl+K): one query searches airports, camera
+//! bookmarks, installed mods, converted FSLTL models and recently seen
+//! callsigns, and returns a single ranked list. Every candidate is scored the
+//! same way against its searchable fields (exact > prefix > word prefix >
+//! substring > in-order letters > typo), so results from different sources
+//! can be mixed. Airports come from the airport database's own fuzzy search.
+//!
+//! Scanning the FSLTL output folder can take a while, so converted models are
+//! scanned in the background and searched from the last scan.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+use crate::airport_db;
+use crate::error::Error;
+use crate::fsltl_scan;
+use crate::mods;
+use crate::traffic;
+use crate::trails;
+use crate::ScannedFSLTLModel;
+
+/// Default and maximum number of results
+const DEFAULT_LIMIT: usize = 20;
+const MAX_LIMIT: usize = 100;
+
+/// Minimum query length for typo-tolerant matching
+const FUZZY_MIN_QUERY_LEN: usize = 3;
+
+/// Minimum Jaro-Winkler similarity for a typo-tolerant match
+const FUZZY_THRESHOLD: f64 = 0.88;
+
+/// Rescan converted models after this long
+const MODEL_SCAN_MAX_AGE: Duration = Duration::from_secs(300);
+
+/// What a result is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SearchKind {
+    Airport,
+    Bookmark,
+    Mod,
+    Model,
+    Aircraft,
+}
+
+impl SearchKind {
+    /// Parse a kind name ("airport", "bookmark", ...)
+    pub fn parse(name: &str) -> Option<SearchKind> {
+        serde_json::from_value(serde_json::Value::String(name.trim().to_string())).ok()
+    }
+
+    /// Ranking boost (bookmarks are the user's own, airports the usual target)
+    fn bonus(self) -> f64 {
+        match self {
+            SearchKind::Bookmark => 60.0,
+            SearchKind::Airport => 40.0,
+            SearchKind::Aircraft => 20.0,
+            SearchKind::Mod | SearchKind::Model => 0.0,
+        }
+    }
+}
+
+/// A search result
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResult {
+    pub kind: SearchKind,
+    /// Airport ICAO, "{ICAO}/{slot}" for bookmarks, mod id, model path or callsign
+    pub id: String,
+    pub title: String,
+    pub subtitle: Option<String>,
+    /// Airport the result belongs to (airports, bookmarks, tower mods)
+    pub icao: Option<String>,
+    /// Relevance (higher is better)
+    pub score: f64,
+}
+
+/// Something that can be found, with the fields it is matched on
+struct Candidate {
+    result: SearchResult,
+    fields: Vec<String>,
+}
+
+impl Candidate {
+    fn new(kind: SearchKind, id: String, title: String, subtitle: Option<String>, icao: Option<String>) -> Self {
+        Candidate {
+            fields: vec![title.clone()],
+            result: SearchResult {
+                kind,
+                id,
+                title,
+                subtitle,
+                icao,
+                score: 0.0,
+            },
+        }
+    }
+
+    fn field(mut self, value: impl Into<String>) -> Self {
+        let value = value.into();
+        if !value.is_empty() {
+            self.fields.push(value);
+        }
+        self
+    }
+}
+
+/// Last scan of the FSLTL output folder
+#[derive(Default)]
+struct ModelScan {
+    output_path: String,
+    scanned_at: Option<Instant>,
+    models: Arc<Vec<ScannedFSLTLModel>>,
+    scanning: bool,
+}
+
+/// Search state (managed by Tauri)
+#[derive(Default)]
+pub struct SearchState {
+    models: Mutex<ModelScan>,
+}
+
+/// Whether the query's letters appear in order in `text` (e.g., "kbs" in "kbos");
+/// returns how spread out they are (0 = adjacent)
+fn subsequence_gap(query: &str, text: &str) -> Option<usize> {
+    let mut chars = text.char_indices();
+    let mut first = None;
+    let mut last = 0;
+    for q in query.chars().filter(|c| !c.is_whitespace()) {
+        let (i, _) = chars.find(|(_, c)| *c == q)?;
+        first.get_or_insert(i);
+        last = i;
+    }
+    let first = first?;
+    Some((last - first + 1).saturating_sub(query.chars().filter(|c| !c.is_whitespace()).count()))
+}
+
+/// Score one field against a lowercase query (None if it doesn't match)
+pub fn score_field(query: &str, field: &str) -> Option<f64> {
+    let field = field.to_lowercase();
+    let words = || field.split(|c: char| c.is_whitespace() || c == '/' || c == '-' || c == '_');
+
+    Some(if field == query {
+        1000.0
+    } else if field.starts_with(query) {
+        (800.0 - (field.len() - query.len()) as f64).max(700.0)
+    } else if words().any(|w| w.starts_with(query)) {
+        600.0
+    } else if field.contains(query) {
+        400.0
+    } else if let Some(gap) = subsequence_gap(query, &field).filter(|_| query.len() >= 2) {
+        (300.0 - 20.0 * gap as f64).max(200.0)
+    } else if query.len() >= FUZZY_MIN_QUERY_LEN {
+        // Typo tolerance: closest word, or the whole field for multi-word queries
+        let similarity = words()
+            .chain([field.as_str()])
+            .map(|w| strsim::jaro_winkler(query, w))
+            .fold(0.0, f64::max);
+        if similarity < FUZZY_THRESHOLD {
+            return None;
+        }
+        100.0 * similarity
+    } else {
+        return None;
+    })
+}
+
+/// Score candidates against a query, best first
+fn rank(query: &str, candidates: Vec<Candidate>, limit: usize) -> Vec<SearchResult> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let mut results: Vec<SearchResult> = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            // Later fields (ids, codes) count a little less than the title
+            let score = candidate
+                .fields
+                .iter()
+                .enumerate()
+                .filter_map(|(i, field)| Some(score_field(&query, field)? - if i == 0 { 0.0 } else { 10.0 }))
+                .fold(None, |best: Option<f64>, s| Some(best.map_or(s, |b| b.max(s))))?;
+            let mut result = candidate.result;
+            result.score = score + result.kind.bonus();
+            Some(result)
+        })
+        .collect();
+    results.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.title.cmp(&b.title)));
+    results.truncate(limit);
+    results
+}
+
+// =============================================================================
+// SOURCES
+// =============================================================================
+
+fn airport_candidates(app: &AppHandle, query: &str, limit: usize) -> Vec<Candidate> {
+    let airports = match airport_db::search_airports_blocking(app, query, Some(limit)) {
+        Ok(airports) => airports,
+        Err(e) => {
+            warn!("[Search] Airport search failed: {}", e);
+            return Vec::new();
+        }
+    };
+    airports
+        .into_iter()
+        .map(|found| {
+            let airport = found.airport;
+            let place = [airport.city.as_str(), airport.country.as_str()]
+                .into_iter()
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join(", ");
+            Candidate::new(
+                SearchKind::Airport,
+                airport.icao.clone(),
+                format!("{} {}", airport.icao, airport.name),
+                (!place.is_empty()).then_some(place),
+                Some(airport.icao.clone()),
+            )
+            .field(airport.icao)
+            .field(airport.iata)
+            .field(airport.name)
+            .field(airport.city)
+        })
+        .collect()
+}
+
+fn bookmark_candidates(app: &AppHandle) -> Vec<Candidate> {
+    let Ok(settings) = crate::read_global_settings(app.clone()) else {
+        return Vec::new();
+    };
+    let mut candidates = Vec::new();
+    for (icao, config) in settings.viewports.airport_configs {
+        for (slot, bookmark) in config.bookmarks {
+            let title = bookmark.name.clone().unwrap_or_else(|| format!(".{}", slot));
+            let view = if bookmark.view_mode == "topdown" { "2D" } else { "3D" };
+            candidates.push(
+                Candidate::new(
+                    SearchKind::Bookmark,
+                    format!("{}/{}", icao, slot),
+                    title,
+                    Some(format!("{} bookmark .{} ({})", icao, slot, view)),
+                    Some(icao.clone()),
+                )
+                .field(format!("{} {}", icao, bookmark.name.unwrap_or_default()))
+                .field(bookmark.runways.join(" ")),
+            );
+        }
+    }
+    candidates
+}
+
+fn mod_candidates(app: &AppHandle) -> Vec<Candidate> {
+    mods::get_registry(app)
+        .mods
+        .into_iter()
+        .map(|entry| {
+            let title = entry.display_name.clone().unwrap_or_else(|| entry.name.clone());
+            let kind = if entry.mod_type == "towers" { "Tower mod" } else { "Aircraft mod" };
+            let subtitle = match (&entry.author, entry.enabled) {
+                (_, false) => format!("{} (disabled)", kind),
+                (Some(author), true) => format!("{} by {}", kind, author),
+                (None, true) => kind.to_string(),
+            };
+            let icao = (entry.mod_type == "towers")
+                .then(|| entry.supported_icaos.first().cloned())
+                .flatten();
+            Candidate::new(SearchKind::Mod, entry.id.clone(), title, Some(subtitle), icao)
+                .field(entry.name)
+                .field(entry.supported_icaos.join(" "))
+                .field(entry.author.unwrap_or_default())
+        })
+        .collect()
+}
+
+/// Converted models from the last scan, starting a scan if it is missing or old
+fn scanned_models(app: &AppHandle) -> Arc<Vec<ScannedFSLTLModel>> {
+    let Some(output_path) = crate::read_global_settings(app.clone())
+        .ok()
+        .and_then(|s| s.fsltl.output_path)
+        .filter(|p| !p.is_empty())
+    else {
+        return Arc::new(Vec::new());
+    };
+
+    let state = app.state::<SearchState>();
+    let mut scan = state.models.lock();
+    let stale = scan.output_path != output_path
+        || scan.scanned_at.map_or(true, |t| t.elapsed() > MODEL_SCAN_MAX_AGE);
+    if stale && !scan.scanning {
+        scan.scanning = true;
+        let app = app.clone();
+        let output_path = output_path.clone();
+        std::thread::spawn(move || {
+            let models = fsltl_scan::scan(&output_path, |_| {}).unwrap_or_else(|e| {
+                warn!("[Search] Model scan failed: {}", e);
+                Vec::new()
+            });
+            info!("[Search] Indexed {} converted models", models.len());
+            let state = app.state::<SearchState>();
+            let mut scan = state.models.lock();
+            *scan = ModelScan {
+                output_path,
+                scanned_at: Some(Instant::now()),
+                models: Arc::new(models),
+                scanning: false,
+            };
+        });
+    }
+    if scan.output_path == output_path {
+        scan.models.clone()
+    } else {
+        Arc::new(Vec::new())
+    }
+}
+
+fn model_candidates(app: &AppHandle) -> Vec<Candidate> {
+    scanned_models(app)
+        .iter()
+        .map(|model| {
+            let livery = model.airline_code.as_deref().unwrap_or("base");
+            Candidate::new(
+                SearchKind::Model,
+                model.relative_path.clone(),
+                format!("{} {}", model.aircraft_type, livery),
+                Some("Converted FSLTL model".to_string()),
+                None,
+            )
+            .field(model.model_name.clone())
+        })
+        .collect()
+}
+
+fn aircraft_candidates(app: &AppHandle) -> Vec<Candidate> {
+    let snapshot = traffic::get_snapshot(app);
+    let mut recent = trails::recent_callsigns(app);
+    recent.sort_by_key(|(_, seen)| std::cmp::Reverse(*seen));
+
+    let mut candidates: Vec<Candidate> = snapshot
+        .aircraft
+        .iter()
+        .map(|aircraft| {
+            let route = match (&aircraft.departure, &aircraft.arrival) {
+                (Some(dep), Some(arr)) => format!("{} → {}", dep, arr),
+                _ => String::new(),
+            };
+            let subtitle = [aircraft.aircraft_type.clone().unwrap_or_default(), route]
+                .into_iter()
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ");
+            Candidate::new(
+                SearchKind::Aircraft,
+                aircraft.callsign.clone(),
+                aircraft.callsign.clone(),
+                (!subtitle.is_empty()).then_some(subtitle),
+                None,
+            )
+            .field(aircraft.aircraft_type.clone().unwrap_or_default())
+        })
+        .collect();
+    // Aircraft that have left the area (or only reported over vNAS)
+    for (callsign, _) in recent {
+        if !snapshot.aircraft.iter().any(|a| a.callsign == callsign) {
+            candidates.push(Candidate::new(
+                SearchKind::Aircraft,
+                callsign.clone(),
+                callsign,
+                Some("Seen recently".to_string()),
+                None,
+            ));
+        }
+    }
+    candidates
+}
+
+/// Search the given kinds of content (all if empty), best matches first
+pub fn search(app: &AppHandle, query: &str, kinds: &[SearchKind], limit: Option<usize>) -> Vec<SearchResult> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let query = query.trim();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let wanted = |kind: SearchKind| kinds.is_empty() || kinds.contains(&kind);
+
+    let mut candidates = Vec::new();
+    if wanted(SearchKind::Airport) {
+        candidates.extend(airport_candidates(app, query, limit));
+    }
+    if wanted(SearchKind::Bookmark) {
+        candidates.extend(bookmark_candidates(app));
+    }
+    if wanted(SearchKind::Mod) {
+        candidates.extend(mod_candidates(app));
+    }
+    if wanted(SearchKind::Model) {
+        candidates.extend(model_candidates(app));
+    }
+    if wanted(SearchKind::Aircraft) {
+        candidates.extend(aircraft_candidates(app));
+    }
+    rank(query, candidates, limit)
+}
+
+pub fn init_search(app: &AppHandle) {
+    app.manage(SearchState::default());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Search airports, bookmarks, mods, converted models and recent callsigns
+#[tauri::command]
+pub async fn search_content(
+    app: AppHandle,
+    query: String,
+    kinds: Option<Vec<SearchKind>>,
+    limit: Option<usize>,
+) -> Result<Vec<SearchResult>, Error> {
+    tauri::async_runtime::spawn_blocking(move || search(&app, &query, &kinds.unwrap_or_default(), limit))
+        .await
+        .map_err(|e| Error::Internal(format!("Search failed: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(kind: SearchKind, title: &str, fields: &[&str]) -> Candidate {
+        fields.iter().fold(
+            Candidate::new(kind, title.to_string(), title.to_string(), None, None),
+            |c, f| c.field(*f),
+        )
+    }
+
+    #[test]
+    fn scores_match_tiers() {
+        assert_eq!(score_field("kbos", "KBOS"), Some(1000.0));
+        assert!(score_field("kb", "KBOS").unwrap() >= 700.0);
+        assert_eq!(score_field("logan", "Boston Logan Intl"), Some(600.0));
+        assert_eq!(score_field("ston", "Boston"), Some(400.0));
+        assert!(score_field("kbs", "KBOS").is_some_and(|s| (200.0..400.0).contains(&s)));
+        assert!(score_field("bsoton", "Boston Logan").is_some_and(|s| s < 200.0));
+        assert_eq!(score_field("xyz", "KBOS"), None);
+        assert_eq!(score_field("q", "KBOS"), None);
+    }
+
+    #[test]
+    fn ranks_across_sources() {
+        let results = rank(
+            "bos",
+            vec![
+                candidate(SearchKind::Model, "B738 BOS", &[]),
+                candidate(SearchKind::Airport, "KBOS Boston Logan", &["KBOS", "BOS"]),
+                candidate(SearchKind::Bookmark, "Boston ramp", &[]),
+                candidate(SearchKind::Aircraft, "DAL123", &["A321"]),
+            ],
+            10,
+        );
+        let titles: Vec<&str> = results.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, vec!["KBOS Boston Logan", "Boston ramp", "B738 BOS"]);
+        assert!(rank("  ", vec![candidate(SearchKind::Mod, "x", &[])], 10).is_empty());
+    }
+
+    #[test]
+    fn parses_kinds() {
+        assert_eq!(SearchKind::parse("airport"), Some(SearchKind::Airport));
+        assert_eq!(SearchKind::parse(" model "), Some(SearchKind::Model));
+        assert_eq!(SearchKind::parse("nope"), None);
+    }
+}
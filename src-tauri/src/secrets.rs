@@ -0,0 +1,214 @@
+//! Secrets redaction
+//!
+//! Tokens and keys used to end up in log lines, crash reports and API error
+//! messages (an OAuth callback URL, a proxied URL with an API key, a provider's
+//! error response). Known secrets are registered here by source (global
+//! settings, vNAS OAuth, RealTraffic sessions) and [`redact`] replaces them
+//! wherever text leaves the app:
+//!
+//! - Log output, console and files (see [`Redacting`], used in logging.rs)
+//! - Crash reports and remote browser logs
+//! - Error responses from the HTTP API
+//!
+//! Values of well-known query parameters (`token=`, `code=`, `key=`, ...) and
+//! `Bearer` credentials are redacted even when they weren't registered.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::sync::RwLock;
+
+use tracing_subscriber::fmt::MakeWriter;
+
+use crate::GlobalSettings;
+
+/// Replacement text for a redacted value
+pub const REDACTED: &str = "[redacted]";
+
+/// Registered values shorter than this aren't redacted (too likely to match ordinary text)
+const MIN_SECRET_LEN: usize = 6;
+
+/// Query parameters whose values are always redacted
+const SECRET_PARAMS: [&str; 10] = [
+    "token",
+    "access_token",
+    "refresh_token",
+    "code",
+    "key",
+    "apikey",
+    "api_key",
+    "license",
+    "client_secret",
+    "password",
+];
+
+/// Registered secrets by source
+static SECRETS: RwLock<BTreeMap<&'static str, Vec<String>>> = RwLock::new(BTreeMap::new());
+
+/// Replace the secrets registered by a source
+pub fn set(source: &'static str, values: impl IntoIterator<Item = String>) {
+    let values: Vec<String> = values
+        .into_iter()
+        .map(|v| v.trim().to_string())
+        .filter(|v| v.len() >= MIN_SECRET_LEN)
+        .collect();
+    if let Ok(mut secrets) = SECRETS.write() {
+        secrets.insert(source, values);
+    }
+}
+
+/// Add one secret to a source's list
+pub fn add(source: &'static str, value: &str) {
+    let value = value.trim();
+    if value.len() < MIN_SECRET_LEN {
+        return;
+    }
+    if let Ok(mut secrets) = SECRETS.write() {
+        let values = secrets.entry(source).or_default();
+        if !values.iter().any(|v| v == value) {
+            values.push(value.to_string());
+        }
+    }
+}
+
+/// Register the secrets held in global settings (call after reading or saving them)
+pub fn register_settings(settings: &GlobalSettings) {
+    let mut values = vec![
+        settings.cesium_ion_token.clone(),
+        settings.airports.openaip_api_key.clone(),
+        settings.airports.notam_client_secret.clone(),
+        settings.realtraffic.license_key.clone(),
+        settings.map_providers.maptiler_key.clone(),
+        settings.map_providers.bing_key.clone(),
+    ];
+    values.extend(settings.server.auth_token.clone());
+    values.extend(settings.server.admin_token.clone());
+    values.extend(settings.atc_notifications.webhook_url.clone());
+    values.extend(settings.federation.token.clone());
+    set("settings", values);
+}
+
+/// Text with registered secrets, secret query parameters and bearer tokens replaced
+pub fn redact(text: &str) -> Cow<'_, str> {
+    let mut text = Cow::Borrowed(text);
+    if let Ok(secrets) = SECRETS.read() {
+        for secret in secrets.values().flatten() {
+            if text.contains(secret.as_str()) {
+                text = Cow::Owned(text.replace(secret.as_str(), REDACTED));
+            }
+        }
+    }
+    if let Some(scrubbed) = redact_patterns(&text) {
+        text = Cow::Owned(scrubbed);
+    }
+    text
+}
+
+/// Whether a byte ends a token or parameter value
+fn ends_value(b: u8) -> bool {
+    b.is_ascii_whitespace() || matches!(b, b'&' | b'#' | b'"' | b'\'' | b')' | b'>' | b',' | b';')
+}
+
+/// Redact secret query parameter values and bearer tokens (None if there were none)
+fn redact_patterns(text: &str) -> Option<String> {
+    let bytes = text.as_bytes();
+    let lower = text.to_ascii_lowercase();
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+
+    for (start, _) in lower.match_indices(['?', '&']) {
+        let rest = &lower[start + 1..];
+        let Some(name) = SECRET_PARAMS.iter().find(|name| {
+            rest.starts_with(*name) && rest.as_bytes().get(name.len()) == Some(&b'=')
+        }) else {
+            continue;
+        };
+        let value_start = start + 1 + name.len() + 1;
+        let value_end = (value_start..bytes.len()).find(|&i| ends_value(bytes[i])).unwrap_or(bytes.len());
+        spans.push((value_start, value_end));
+    }
+    for (start, matched) in lower.match_indices("bearer ") {
+        let value_start = start + matched.len();
+        let value_end = (value_start..bytes.len()).find(|&i| ends_value(bytes[i])).unwrap_or(bytes.len());
+        spans.push((value_start, value_end));
+    }
+
+    spans.retain(|(start, end)| end > start && &text[*start..*end] != REDACTED);
+    if spans.is_empty() {
+        return None;
+    }
+    spans.sort_unstable();
+    let mut result = String::with_capacity(text.len());
+    let mut copied = 0;
+    for (start, end) in spans {
+        if start < copied {
+            continue;
+        }
+        result.push_str(&text[copied..start]);
+        result.push_str(REDACTED);
+        copied = end;
+    }
+    result.push_str(&text[copied..]);
+    Some(result)
+}
+
+/// Log writer factory that redacts each formatted line before writing it
+pub struct Redacting<M>(pub M);
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for Redacting<M> {
+    type Writer = RedactingWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter(self.0.make_writer())
+    }
+}
+
+/// Writer wrapper created by [`Redacting`] (the formatter writes each event in one call)
+pub struct RedactingWriter<W>(W);
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match std::str::from_utf8(buf) {
+            Ok(text) => self.0.write_all(redact(text).as_bytes())?,
+            Err(_) => self.0.write_all(buf)?,
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_registered_secrets_and_patterns() {
+        set("test", vec!["ion-token-1234567".to_string(), "abc".to_string()]);
+        assert_eq!(redact("token is ion-token-1234567."), "token is [redacted].");
+        // Too short to register
+        assert_eq!(redact("abc"), "abc");
+
+        assert_eq!(
+            redact("GET /api/traffic?icao=KSFO&token=hunter2&x=1"),
+            "GET /api/traffic?icao=KSFO&token=[redacted]&x=1"
+        );
+        assert_eq!(
+            redact("Received OAuth callback: tc3d://oauth/callback?state=s&CODE=Abc-123"),
+            "Received OAuth callback: tc3d://oauth/callback?state=s&CODE=[redacted]"
+        );
+        assert_eq!(redact("Authorization: Bearer eyJhbGciOi, next"), "Authorization: Bearer [redacted], next");
+        // Parameters that only end in a secret name are kept
+        assert_eq!(redact("/tiles?monkey=1&keyboard=2"), "/tiles?monkey=1&keyboard=2");
+        assert_eq!(redact("nothing to see"), "nothing to see");
+        set("test", Vec::new());
+    }
+
+    #[test]
+    fn writer_redacts_whole_lines() {
+        let mut out = Vec::new();
+        RedactingWriter(&mut out).write_all(b"proxy https://api.example.com/v1?key=s3cr3t failed\n").unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "proxy https://api.example.com/v1?key=[redacted] failed\n");
+    }
+}
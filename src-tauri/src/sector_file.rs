@@ -0,0 +1,509 @@
+//! EuroScope/VRC sector file (.sct2/.ese) import
+//!
+//! Extracts geo lines (`[GEO]`, `[SID]`, `[STAR]` diagrams), fixes, navaids and
+//! airports, airways and labels (`[LABELS]`, plus `[FREETEXT]` from the .ese)
+//! around an airport, and writes them as a GeoJSON overlay in `mods/overlays/`
+//! so facilities without CRC video maps get equivalent overlays:
+//!
+//! ```text
+//! mods/overlays/egll-sector/
+//! ├── manifest.json
+//! ├── airways.geojson
+//! ├── fixes.geojson
+//! ├── geo.geojson
+//! └── labels.geojson
+//! ```
+//!
+//! Coordinates may be written as `N051.28.39.000 W000.27.41.000`, in decimal
+//! degrees, or as the name of a fix/navaid/airport defined elsewhere in the file.
+//! Colors (`#define NAME 12345` or inline) are Windows BGR integers.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serde_json::{json, Map, Value};
+use tauri::AppHandle;
+use tracing::info;
+
+use crate::error::{Error, Result};
+use crate::airport_db;
+use crate::geo;
+use crate::overlays::{self, OverlayInfo, OverlayManifest};
+
+/// Default radius around the airport to keep (nm)
+const DEFAULT_RADIUS_NM: f64 = 60.0;
+
+/// (latitude, longitude)
+type LatLon = (f64, f64);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Shape {
+    Point(LatLon),
+    Line(Vec<LatLon>),
+}
+
+/// Sector file element with its GeoJSON properties
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectorFeature {
+    shape: Shape,
+    properties: Map<String, Value>,
+}
+
+impl SectorFeature {
+    fn to_geojson(&self) -> Value {
+        let geometry = match &self.shape {
+            Shape::Point((lat, lon)) => json!({"type": "Point", "coordinates": [lon, lat]}),
+            Shape::Line(points) => json!({
+                "type": "LineString",
+                "coordinates": points.iter().map(|(lat, lon)| vec![*lon, *lat]).collect::<Vec<_>>(),
+            }),
+        };
+        json!({"type": "Feature", "geometry": geometry, "properties": self.properties})
+    }
+
+    fn points(&self) -> &[LatLon] {
+        match &self.shape {
+            Shape::Point(point) => std::slice::from_ref(point),
+            Shape::Line(points) => points,
+        }
+    }
+}
+
+/// Parsed sector file layers
+#[derive(Debug, Default)]
+pub struct SectorFile {
+    /// Sector name from [INFO]
+    pub name: String,
+    /// Default center from [INFO]
+    pub center: Option<LatLon>,
+    pub geo: Vec<SectorFeature>,
+    pub fixes: Vec<SectorFeature>,
+    pub airways: Vec<SectorFeature>,
+    pub labels: Vec<SectorFeature>,
+}
+
+/// Parse one coordinate ("N051.28.39.000" or decimal degrees)
+fn parse_coordinate(token: &str) -> Option<f64> {
+    let token = token.trim();
+    let (sign, rest) = match token.chars().next()? {
+        'N' | 'n' | 'E' | 'e' => (1.0, &token[1..]),
+        'S' | 's' | 'W' | 'w' => (-1.0, &token[1..]),
+        _ => return token.parse::<f64>().ok().filter(|v| v.abs() <= 180.0),
+    };
+    let parts: Vec<&str> = rest.split('.').collect();
+    let value = match parts.as_slice() {
+        [deg, min, sec, frac] => {
+            deg.parse::<f64>().ok()?
+                + min.parse::<f64>().ok()? / 60.0
+                + format!("{}.{}", sec, frac).parse::<f64>().ok()? / 3600.0
+        }
+        [deg, min, sec] => {
+            deg.parse::<f64>().ok()? + min.parse::<f64>().ok()? / 60.0 + sec.parse::<f64>().ok()? / 3600.0
+        }
+        _ => return None,
+    };
+    Some(sign * value)
+}
+
+/// Parse a color: a #define name or a BGR integer, as "#rrggbb"
+fn parse_color(token: &str, colors: &HashMap<String, String>) -> Option<String> {
+    if let Some(color) = colors.get(&token.to_uppercase()) {
+        return Some(color.clone());
+    }
+    let bgr: u32 = token.parse().ok()?;
+    Some(format!(
+        "#{:02x}{:02x}{:02x}",
+        bgr & 0xff,
+        (bgr >> 8) & 0xff,
+        (bgr >> 16) & 0xff
+    ))
+}
+
+/// Strip a ';' comment
+fn strip_comment(line: &str) -> &str {
+    line.split(';').next().unwrap_or_default().trim()
+}
+
+struct Parser {
+    colors: HashMap<String, String>,
+    /// Named positions (fixes, navaids, airports), uppercase
+    positions: HashMap<String, LatLon>,
+}
+
+impl Parser {
+    /// Resolve a lat/lon token pair (coordinates or a named position repeated twice)
+    fn position(&self, lat: &str, lon: &str) -> Option<LatLon> {
+        match (parse_coordinate(lat), parse_coordinate(lon)) {
+            (Some(lat), Some(lon)) => Some((lat, lon)),
+            _ if lat.eq_ignore_ascii_case(lon) => self.positions.get(&lat.to_uppercase()).copied(),
+            _ => None,
+        }
+    }
+
+    /// Find a segment (two positions) in a line, returning the tokens before it,
+    /// the segment and the tokens after it
+    fn segment<'a>(&self, tokens: &'a [&'a str]) -> Option<(&'a [&'a str], LatLon, LatLon, &'a [&'a str])> {
+        (0..tokens.len().saturating_sub(3)).find_map(|i| {
+            let from = self.position(tokens[i], tokens[i + 1])?;
+            let to = self.position(tokens[i + 2], tokens[i + 3])?;
+            Some((&tokens[..i], from, to, &tokens[i + 4..]))
+        })
+    }
+}
+
+/// Append a segment to a line layer, extending the previous line when it continues it
+fn push_segment(layer: &mut Vec<SectorFeature>, from: LatLon, to: LatLon, properties: Map<String, Value>) {
+    if let Some(SectorFeature {
+        shape: Shape::Line(points),
+        properties: last_properties,
+    }) = layer.last_mut()
+    {
+        if *last_properties == properties && points.last() == Some(&from) {
+            points.push(to);
+            return;
+        }
+    }
+    layer.push(SectorFeature {
+        shape: Shape::Line(vec![from, to]),
+        properties,
+    });
+}
+
+fn point(position: LatLon, properties: Map<String, Value>) -> SectorFeature {
+    SectorFeature {
+        shape: Shape::Point(position),
+        properties,
+    }
+}
+
+fn properties(pairs: &[(&str, Value)]) -> Map<String, Value> {
+    pairs.iter().map(|(k, v)| (k.to_string(), v.clone())).collect()
+}
+
+/// Split a file into (section, lines), lines before the first section under ""
+fn sections(text: &str) -> Vec<(String, Vec<&str>)> {
+    let mut sections: Vec<(String, Vec<&str>)> = vec![(String::new(), Vec::new())];
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') && trimmed.ends_with(']') {
+            sections.push((trimmed[1..trimmed.len() - 1].trim().to_uppercase(), Vec::new()));
+        } else if let Some((_, lines)) = sections.last_mut() {
+            lines.push(line);
+        }
+    }
+    sections
+}
+
+/// Parse a .sct2 file and optionally its .ese
+pub fn parse_sector_file(sct: &str, ese: Option<&str>) -> SectorFile {
+    let sct_sections = sections(sct);
+    let mut parser = Parser {
+        colors: HashMap::new(),
+        positions: HashMap::new(),
+    };
+    let mut sector = SectorFile::default();
+
+    // First pass: colors and named positions (referenced by later sections)
+    for (section, lines) in &sct_sections {
+        for line in lines.iter().map(|l| strip_comment(l)).filter(|l| !l.is_empty()) {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            match section.as_str() {
+                "" if tokens.len() >= 3 && tokens[0].eq_ignore_ascii_case("#define") => {
+                    if let Some(color) = parse_color(tokens[2], &parser.colors) {
+                        parser.colors.insert(tokens[1].to_uppercase(), color);
+                    }
+                }
+                "FIXES" if tokens.len() >= 3 => {
+                    if let Some(position) = parser.position(tokens[1], tokens[2]) {
+                        parser.positions.insert(tokens[0].to_uppercase(), position);
+                        sector.fixes.push(point(
+                            position,
+                            properties(&[("name", json!(tokens[0])), ("type", json!("fix"))]),
+                        ));
+                    }
+                }
+                "VOR" | "NDB" | "AIRPORT" if tokens.len() >= 4 => {
+                    if let Some(position) = parser.position(tokens[2], tokens[3]) {
+                        parser.positions.insert(tokens[0].to_uppercase(), position);
+                        sector.fixes.push(point(
+                            position,
+                            properties(&[
+                                ("name", json!(tokens[0])),
+                                ("type", json!(section.to_lowercase())),
+                                ("frequency", json!(tokens[1])),
+                            ]),
+                        ));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    for (section, lines) in &sct_sections {
+        let mut info_line = 0;
+        // SID/STAR diagram names only appear on the first line of each diagram
+        let mut diagram = String::new();
+        for line in lines.iter().map(|l| strip_comment(l)).filter(|l| !l.is_empty()) {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            match section.as_str() {
+                "INFO" => {
+                    match info_line {
+                        0 => sector.name = line.to_string(),
+                        3 => sector.center = parse_coordinate(line).map(|lat| (lat, 0.0)),
+                        4 => {
+                            if let (Some((lat, _)), Some(lon)) = (sector.center, parse_coordinate(line)) {
+                                sector.center = Some((lat, lon));
+                            } else {
+                                sector.center = None;
+                            }
+                        }
+                        _ => {}
+                    }
+                    info_line += 1;
+                }
+                "GEO" | "SID" | "STAR" => {
+                    let Some((name, from, to, rest)) = parser.segment(&tokens) else {
+                        continue;
+                    };
+                    if !name.is_empty() {
+                        diagram = name.join(" ");
+                    }
+                    let mut props = properties(&[("layer", json!(section.to_lowercase()))]);
+                    if !diagram.is_empty() {
+                        props.insert("name".to_string(), json!(diagram));
+                    }
+                    if let Some(color) = rest.first().and_then(|c| parse_color(c, &parser.colors)) {
+                        props.insert("color".to_string(), json!(color));
+                    }
+                    push_segment(&mut sector.geo, from, to, props);
+                }
+                "LOW AIRWAY" | "HIGH AIRWAY" => {
+                    let Some((name, from, to, _)) = parser.segment(&tokens) else {
+                        continue;
+                    };
+                    let level = if section.starts_with("LOW") { "low" } else { "high" };
+                    push_segment(
+                        &mut sector.airways,
+                        from,
+                        to,
+                        properties(&[("name", json!(name.join(" "))), ("level", json!(level))]),
+                    );
+                }
+                "LABELS" => {
+                    // "Text" lat lon color
+                    let Some(rest) = line.strip_prefix('"') else {
+                        continue;
+                    };
+                    let Some((text, rest)) = rest.split_once('"') else {
+                        continue;
+                    };
+                    let tokens: Vec<&str> = rest.split_whitespace().collect();
+                    let Some(position) = tokens.get(1).and_then(|lon| parser.position(tokens[0], lon)) else {
+                        continue;
+                    };
+                    let mut props = properties(&[("text", json!(text))]);
+                    if let Some(color) = tokens.get(2).and_then(|c| parse_color(c, &parser.colors)) {
+                        props.insert("color".to_string(), json!(color));
+                    }
+                    sector.labels.push(point(position, props));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // .ese free text labels: lat:lon:group:text
+    for (section, lines) in ese.map(sections).unwrap_or_default() {
+        if section != "FREETEXT" {
+            continue;
+        }
+        for line in lines.iter().map(|l| strip_comment(l)).filter(|l| !l.is_empty()) {
+            let parts: Vec<&str> = line.splitn(4, ':').collect();
+            let [lat, lon, group, text] = parts.as_slice() else {
+                continue;
+            };
+            if let Some(position) = parser.position(lat, lon) {
+                sector.labels.push(point(
+                    position,
+                    properties(&[("text", json!(text)), ("group", json!(group))]),
+                ));
+            }
+        }
+    }
+
+    sector
+}
+
+/// Keep only features with a point within a radius (nm) of a center
+fn clip(features: &[SectorFeature], center: LatLon, radius_nm: f64) -> Vec<Value> {
+    features
+        .iter()
+        .filter(|f| {
+            f.points()
+                .iter()
+                .any(|(lat, lon)| geo::distance_and_bearing(center.0, center.1, *lat, *lon).0 <= radius_nm)
+        })
+        .map(SectorFeature::to_geojson)
+        .collect()
+}
+
+fn read_text(path: &Path) -> Result<String> {
+    // Sector files are usually Windows-1252; keep what decodes
+    fs::read(path)
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .map_err(|e| Error::Io(format!("Failed to read {:?}: {}", path, e)))
+}
+
+/// Import a sector file as an overlay for an airport, keeping elements within
+/// a radius of the airport. Replaces a previous import for the same airport.
+pub fn import_sector_file(
+    app: &AppHandle,
+    icao: &str,
+    sct_path: &Path,
+    ese_path: Option<&Path>,
+    radius_nm: Option<f64>,
+) -> Result<OverlayInfo> {
+    let icao = icao.trim().to_uppercase();
+    let sct = read_text(sct_path)?;
+    let ese = ese_path.map(read_text).transpose()?;
+    let sector = parse_sector_file(&sct, ese.as_deref());
+
+    let center = airport_db::get_airport_blocking(app, &icao)
+        .ok()
+        .flatten()
+        .map(|a| (a.latitude, a.longitude))
+        .or(sector.center)
+        .ok_or_else(|| Error::NotFound(format!("Unknown airport: {}", icao)))?;
+    let radius_nm = radius_nm.unwrap_or(DEFAULT_RADIUS_NM);
+
+    let id = format!("{}-sector", icao.to_lowercase());
+    let dir = overlays::overlays_root(app).join(&id);
+    if dir.exists() {
+        fs::remove_dir_all(&dir).map_err(|e| Error::Io(format!("Failed to replace overlay {}: {}", id, e)))?;
+    }
+    fs::create_dir_all(&dir).map_err(|e| Error::Io(format!("Failed to create overlay folder: {}", e)))?;
+
+    let mut files = Vec::new();
+    for (file, features) in [
+        ("airways.geojson", &sector.airways),
+        ("fixes.geojson", &sector.fixes),
+        ("geo.geojson", &sector.geo),
+        ("labels.geojson", &sector.labels),
+    ] {
+        let clipped = clip(features, center, radius_nm);
+        if clipped.is_empty() {
+            continue;
+        }
+        let collection = json!({"type": "FeatureCollection", "features": clipped});
+        fs::write(dir.join(file), collection.to_string())
+            .map_err(|e| Error::Io(format!("Failed to write {}: {}", file, e)))?;
+        files.push(file.to_string());
+    }
+    if files.is_empty() {
+        let _ = fs::remove_dir_all(&dir);
+        return Err(Error::InvalidInput(format!("Sector file has nothing within {} nm of {}", radius_nm, icao)));
+    }
+
+    let source = sct_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let manifest = OverlayManifest {
+        name: Some(format!("{} sector file", icao)),
+        description: Some(if sector.name.is_empty() {
+            format!("Imported from {}", source)
+        } else {
+            format!("{} (imported from {})", sector.name, source)
+        }),
+        category: Some("sectorFile".to_string()),
+        airports: vec![icao.clone()],
+        render: Some("2d".to_string()),
+        files,
+        ..Default::default()
+    };
+    let content = serde_json::to_string_pretty(&manifest).map_err(|e| Error::Internal(e.to_string()))?;
+    fs::write(dir.join("manifest.json"), content)
+        .map_err(|e| Error::Io(format!("Failed to write overlay manifest: {}", e)))?;
+
+    info!("[SectorFile] Imported {} for {} as overlay {}", source, icao, id);
+    overlays::list_all(app)
+        .into_iter()
+        .find(|o| o.id == id)
+        .ok_or_else(|| Error::Internal(format!("Imported overlay {} could not be loaded", id)))
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Import a EuroScope/VRC sector file (.sct2, optionally with its .ese) as a
+/// GeoJSON overlay for an airport
+#[tauri::command]
+pub async fn import_sector_file_overlay(
+    app: AppHandle,
+    icao: String,
+    sct_path: String,
+    ese_path: Option<String>,
+    radius_nm: Option<f64>,
+) -> Result<OverlayInfo> {
+    tauri::async_runtime::spawn_blocking(move || {
+        import_sector_file(
+            &app,
+            &icao,
+            Path::new(&sct_path),
+            ese_path.as_deref().map(Path::new),
+            radius_nm,
+        )
+    })
+    .await
+    .map_err(|e| Error::Internal(format!("Sector file import failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sector_file() {
+        let sct = "#define TAXI 65280\n\
+            [INFO]\n\
+            London Heathrow\n\
+            LON_CTR\n\
+            EGLL\n\
+            N051.28.39.000\n\
+            W000.27.41.000\n\
+            [VOR]\n\
+            BNN 113.750 N051.43.34.000 W000.32.59.000\n\
+            [FIXES]\n\
+            LAM N051.38.46.000 W000.09.06.000 ; Lambourne\n\
+            [LOW AIRWAY]\n\
+            L9 BNN BNN LAM LAM\n\
+            [GEO]\n\
+            EGLL Taxiway A N051.28.00.000 W000.27.00.000 N051.28.00.000 W000.26.00.000 TAXI\n\
+            N051.28.00.000 W000.26.00.000 N051.28.30.000 W000.26.00.000 TAXI\n\
+            [LABELS]\n\
+            \"A1\" N051.28.00.000 W000.27.00.000 255\n";
+        let ese = "[FREETEXT]\nN051.28.10.000:W000.26.30.000:Stands:501\n";
+
+        let sector = parse_sector_file(sct, Some(ese));
+        assert_eq!(sector.name, "London Heathrow");
+        let (lat, lon) = sector.center.unwrap();
+        assert!((lat - 51.4775).abs() < 1e-4 && (lon + 0.46139).abs() < 1e-4);
+
+        assert_eq!(sector.fixes.len(), 2);
+        assert_eq!(sector.airways.len(), 1);
+        assert_eq!(sector.airways[0].properties["name"], "L9");
+
+        // Continuing segments with the same style are merged into one line
+        assert_eq!(sector.geo.len(), 1);
+        assert_eq!(sector.geo[0].points().len(), 3);
+        assert_eq!(sector.geo[0].properties["name"], "EGLL Taxiway A");
+        assert_eq!(sector.geo[0].properties["color"], "#00ff00");
+
+        assert_eq!(sector.labels.len(), 2);
+        assert_eq!(sector.labels[0].properties["color"], "#ff0000");
+        assert_eq!(sector.labels[1].properties["text"], "501");
+    }
+}
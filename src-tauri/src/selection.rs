@@ -0,0 +1,203 @@
+//! Shared aircraft selection
+//!
+//! The aircraft a display has selected (clicked in the aircraft list or
+//! followed) can be shared with every other display that opted in: a client
+//! sets it with `set_selection` or `PUT /api/selection`, the host keeps the
+//! latest one, and every change is emitted as `selection-changed` and relayed
+//! over `/api/selection/ws`. Pointing out an aircraft on an iPad highlights
+//! the same one on the wall display without moving its camera.
+
+use std::time::SystemTime;
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::info;
+
+use crate::error::{Error, Result};
+
+/// Longest accepted callsign
+const MAX_CALLSIGN_LEN: usize = 16;
+
+/// Longest accepted source label
+const MAX_SOURCE_LEN: usize = 64;
+
+/// Selection request (`PUT /api/selection` body)
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SelectionRequest {
+    pub callsign: String,
+    /// Display that made the selection (e.g., "iPad"), shown to the others
+    pub source: Option<String>,
+}
+
+/// Shared selection (`selection-changed` event payload)
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Selection {
+    /// `None` when nothing is selected
+    pub callsign: Option<String>,
+    pub source: Option<String>,
+    /// Unix ms
+    pub updated_at: u64,
+}
+
+/// Selection state (managed by Tauri)
+pub struct SelectionState {
+    selection: RwLock<Selection>,
+    /// Selection updates for WebSocket relay
+    pub events: broadcast::Sender<Selection>,
+}
+
+impl SelectionState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            selection: RwLock::new(Selection::default()),
+            events,
+        }
+    }
+}
+
+impl Default for SelectionState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Check and normalize a selection request
+pub fn validate(request: SelectionRequest, now: u64) -> Result<Selection> {
+    let callsign = request.callsign.trim().to_uppercase();
+    if callsign.is_empty() {
+        return Err(Error::invalid_input("Callsign is required"));
+    }
+    if callsign.len() > MAX_CALLSIGN_LEN
+        || !callsign.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(Error::invalid_input(format!("Invalid callsign: {}", callsign)));
+    }
+    let source = request
+        .source
+        .map(|s| s.trim().chars().take(MAX_SOURCE_LEN).collect::<String>())
+        .filter(|s| !s.is_empty());
+    Ok(Selection {
+        callsign: Some(callsign),
+        source,
+        updated_at: now,
+    })
+}
+
+fn publish(app: &AppHandle, selection: Selection) {
+    let state = app.state::<SelectionState>();
+    *state.selection.write() = selection.clone();
+    let _ = app.emit("selection-changed", &selection);
+    let _ = state.events.send(selection);
+}
+
+/// Current shared selection
+pub fn get(app: &AppHandle) -> Selection {
+    app.state::<SelectionState>().selection.read().clone()
+}
+
+/// Select an aircraft on every opted-in display
+pub fn set(app: &AppHandle, request: SelectionRequest) -> Result<Selection> {
+    let selection = validate(request, now_ms())?;
+    let current = get(app);
+    if current.callsign == selection.callsign && current.source == selection.source {
+        return Ok(current);
+    }
+    info!(
+        "[Selection] {} selected{}",
+        selection.callsign.as_deref().unwrap_or_default(),
+        selection.source.as_ref().map(|s| format!(" by {}", s)).unwrap_or_default()
+    );
+    publish(app, selection.clone());
+    Ok(selection)
+}
+
+/// Clear the shared selection
+pub fn clear(app: &AppHandle) {
+    if get(app).callsign.is_some() {
+        info!("[Selection] Cleared");
+        publish(
+            app,
+            Selection {
+                updated_at: now_ms(),
+                ..Default::default()
+            },
+        );
+    }
+}
+
+/// Initialize selection state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_selection(app: &AppHandle) {
+    app.manage(SelectionState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Aircraft currently selected across displays
+#[tauri::command]
+pub fn get_selection(app: AppHandle) -> Selection {
+    get(&app)
+}
+
+/// Share an aircraft selection with every opted-in display
+#[tauri::command]
+pub fn set_selection(app: AppHandle, request: SelectionRequest) -> Result<Selection> {
+    set(&app, request)
+}
+
+/// Clear the shared aircraft selection
+#[tauri::command]
+pub fn clear_selection(app: AppHandle) {
+    clear(&app);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(callsign: &str, source: Option<&str>) -> SelectionRequest {
+        SelectionRequest {
+            callsign: callsign.to_string(),
+            source: source.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn normalizes_selections() {
+        let selection = validate(request(" dal2 ", Some("  iPad  ")), 42).unwrap();
+        assert_eq!(selection.callsign.as_deref(), Some("DAL2"));
+        assert_eq!(selection.source.as_deref(), Some("iPad"));
+        assert_eq!(selection.updated_at, 42);
+
+        let anonymous = validate(request("N123AB", Some("   ")), 0).unwrap();
+        assert_eq!(anonymous.source, None);
+
+        let long_source = "x".repeat(100);
+        let truncated = validate(request("AAL1", Some(&long_source)), 0).unwrap();
+        assert_eq!(truncated.source.map(|s| s.len()), Some(MAX_SOURCE_LEN));
+    }
+
+    #[test]
+    fn rejects_invalid_callsigns() {
+        assert!(validate(request("", None), 0).is_err());
+        assert!(validate(request("   ", None), 0).is_err());
+        assert!(validate(request("AAL 1", None), 0).is_err());
+        assert!(validate(request("<script>", None), 0).is_err());
+        assert!(validate(request(&"A".repeat(17), None), 0).is_err());
+        assert!(validate(request("BOS_TWR", None), 0).is_ok());
+    }
+}
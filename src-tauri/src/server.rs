@@ -3,32 +3,144 @@
 //! Serves the React app and REST APIs to remote browsers (e.g., iPad Safari)
 //! when the server is enabled in global settings.
 
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::net::{IpAddr, SocketAddr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use axum::{
-    body::Body,
-    extract::{ConnectInfo, Path, Query, State, WebSocketUpgrade, ws::{Message, WebSocket}},
-    http::{header, HeaderValue, Request, Response, StatusCode},
+    body::{Body, Bytes, HttpBody},
+    extract::{ConnectInfo, DefaultBodyLimit, Path, Query, State, WebSocketUpgrade, ws::{Message, WebSocket}},
+    http::{header, HeaderMap, HeaderValue, Request, Response, StatusCode},
     middleware::{self, Next},
     response::IntoResponse,
-    routing::{get, post, put},
-    Json, Router,
+    routing::{delete, get, post, put},
+    Extension, Json, Router,
 };
+use axum_server::tls_rustls::RustlsConfig;
 use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
-use tokio::sync::broadcast;
+use socket2::{Domain, Protocol, Socket, Type};
+use tokio::sync::{broadcast, watch};
 use tower_http::cors::{AllowOrigin, Any, CorsLayer};
-use url::Url;
+use tracing::{debug, error, info, warn};
+use url::{Host, Url};
 
 use tauri::{Emitter, Manager};
 
+use crate::afv::{self, AfvState, FrequencyActivitySnapshot};
+use crate::aircraft_photos::{self, AircraftPhoto};
+use crate::aircraft_types::{self, AircraftType};
+use crate::airlines::{self, Airline, DecodedCallsign};
+use crate::airport_db::{self, AirportRecord, AirportSearchResult};
+use crate::airport_packs::{self, AirportPackExportOptions, AirportPackInstallResult};
+use crate::airspace;
+use crate::annotations::{self, Annotation};
+use crate::api_docs;
+use crate::approach::{self, ApproachPath};
+use crate::arrival_sequence::{self, ArrivalSequence, ArrivalSequenceState};
+use crate::atc_watch::{self, AtcWatchState, AtcWatchStatus};
+use crate::atpa::{self, AtpaSnapshot, AtpaState};
+use crate::audio_relay::{self, AudioStreamInfo};
+use crate::audit_log::{self, AuditSummary};
+use crate::auth_lockout::AuthLockout;
+use crate::charts::{self, AirportCharts};
+use crate::chunked_downloads;
+use crate::client_capabilities::{self, ClientCapabilities, ClientConfig};
+use crate::client_logs::{self, ClientLogBatch};
+use crate::clock::{self, ClockState, SimClock};
+use crate::datablocks::{self, DatablockLayout, DatablockState};
+use crate::datis::{self, AtisComparison};
+use crate::content_manifest::{self, ContentManifest};
+use crate::control::{self, ControlAction, ControlActions, ControlFeedback, ControlState};
+use crate::coordination::{self, CoordinationMessage, CoordinationState, MessageRequest};
+use crate::controllers::{self, ControllerInfo};
+use crate::coverage::{self, CoverageReport, RecentTrafficState};
+use crate::debug_stats::{self, StreamStats, TaskHealth};
+use crate::declutter::{self, DeclutterProfile, ReferenceCache};
+use crate::drawings::{self, Drawing, DrawingBoard, DrawingRequest, DrawingSessionInfo, DrawingState};
+use crate::density_altitude::{self, DensityAltitude};
+use crate::ephemeris::{self, Ephemeris};
+use crate::error::Error;
+use crate::event_schedule::{self, EventScheduleStatus, ScheduledEvent};
+use crate::event_prep::{self, EventPrepRequest, EventPrepStatus};
+use crate::federation::{self, FederationStatus};
+use crate::file_cache::{FileCache, FileCacheStats};
+use crate::follow::{self, FollowState, FollowStatus, FollowTarget};
+use crate::fsltl_scan;
+use crate::gate_suggestions::{self, GateSuggestion};
+use crate::gates::{self, Gate, GateState};
+use crate::go_arounds::{self, GoAroundState, GoArounds};
+use crate::http_client;
+use crate::imagery_vintages::{self, ImageryVintage};
+use crate::label_layout::{self, LabelLayout, LabelLayoutState};
+use crate::lan;
+use crate::lod::{self, LodPolicy, ModelDetail};
+use crate::lightning::{self, LightningState, LightningStatus};
+use crate::logging;
+use crate::map_providers::{self, ImageryProvider, MapProviderInfo};
+use crate::matching::{self, MatchQuery, MatchResult};
+use crate::mod_variants;
+use crate::model_usage::{self, ModelUsageReport, ModelUsageState};
+use crate::mods::{self, ModRegistry, ModRegistryEntry};
+use crate::notams::{self, AirportNotams};
+use crate::obs;
+use crate::osm_buildings::{self, BuildingsInfo};
+use crate::osm_layout;
+use crate::overlays::{self, OverlayInfo};
+use crate::placements::{self, StaticObject};
+use crate::remote_access::{self, ClientCertConnection, TrustedNetworks};
+use crate::replay::{self, ReplayControl, ReplayEvent, ReplayFile, ReplayState, ReplayStatus};
+use crate::replay_library::{self, ShareOptions, SharedReplay};
+use crate::runway_suggestion::{self, RunwaySuggestion};
+use crate::plugins::{self, PluginInfo, PluginResponse};
+use crate::safe_path::{PathError, ServedRoot, SymlinkPolicy};
+use crate::scripts::{self, ScriptInfo, ScriptState};
+use crate::search::{self, SearchKind, SearchResult};
+use crate::secrets;
+use crate::selection::{self, Selection, SelectionRequest, SelectionState};
+use crate::session_export;
+use crate::session_stats;
+use crate::runway_holds::{self, HoldTimerState, HoldTimers};
+use crate::runway_config::{self, RunwayConfiguration};
+use crate::runway_occupancy::{self, RunwayOccupancy, RunwayOccupancyState};
+use crate::runways::Runway;
+use crate::server_schedule::{self, ServerScheduleStatus};
+use crate::similar_types::{self, SimilarTypes};
+use crate::sound_packs::{self, SoundPackInfo};
+use crate::squawk_alerts::{self, SquawkAlertState, SquawkAlerts};
+use crate::startup::{self, StartupProfile};
+use crate::stca::{self, StcaAlerts, StcaState};
+use crate::supervisor::{self, TaskStatus};
+use crate::videomaps::{self, VideoMapIndex};
+use crate::view_presets::{self, ViewPresetSuggestion};
+use crate::vatspy::{self, AirportCoverage};
+use crate::vmr::{self, EffectiveVmrRule, VmrRule, VmrRuleInput, VmrStateFile};
+use crate::vmr_remote::{self, VmrSubscription};
+use crate::udp_output;
+use crate::vnas::{VnasState, VnasStatus};
+use crate::vnas_batch;
+use crate::strips::{self, StripBay};
+use crate::taf::{self, Taf};
+use crate::taxi_routes::{self, TaxiRoute};
+use crate::terrain_packages::{self, TerrainPackage};
+use crate::tile_cache::{self, PrefetchProgress, PrefetchRequest, Tile, TileCacheStatus};
+use crate::touchdown::{self, LandingPrediction};
+use crate::traffic::{self, TrafficSnapshot};
+use crate::trails::{self, AircraftTrail, TrailPoint};
+use crate::units::{self, Localized, UnitPreferences};
+use crate::wake_timers::{self, WakeTimerState, WakeTimers};
+use crate::weather::{self, Metar};
+use crate::weather_scene::{self, WeatherScene};
+use crate::winds_aloft::{self, WindsAloft};
+use crate::ws_encoding::{DeltaEncoder, Encoding, WireFormat};
+use crate::zstd_cache::{self, ZstdCache};
 use crate::{
-    find_mods_root, get_global_settings_file, normalize_path_string, read_tower_positions,
-    GlobalSettings, ScannedFSLTLModel, TowerPositionEntry,
+    find_mods_root, get_global_settings_file, read_tower_positions, GlobalClockSettings, GlobalSettings,
+    ScannedFSLTLModel, TowerPositionEntry,
 };
 
 /// vNAS aircraft update for WebSocket broadcast
@@ -42,6 +154,34 @@ pub struct VnasAircraftBroadcast {
     pub heading: f64,
     pub type_code: Option<String>,
     pub timestamp: u64,
+    /// Derived from position history
+    pub groundspeed_kt: Option<f64>,
+    /// Feet per minute, derived from position history
+    pub vertical_rate_fpm: Option<f64>,
+    /// Trail point added by this update (see trails.rs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trail_point: Option<TrailPoint>,
+    /// How the receiving client should draw the aircraft (see lod.rs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_detail: Option<ModelDetail>,
+}
+
+/// Test broadcast at the same position as `traffic::test_aircraft`
+#[cfg(test)]
+pub fn test_vnas_broadcast(callsign: &str, timestamp: u64) -> VnasAircraftBroadcast {
+    VnasAircraftBroadcast {
+        callsign: callsign.to_string(),
+        lat: 42.0,
+        lon: -71.0,
+        altitude: 3000.0,
+        heading: 0.0,
+        type_code: None,
+        timestamp,
+        groundspeed_kt: None,
+        vertical_rate_fpm: None,
+        trail_point: None,
+        model_detail: None,
+    }
 }
 
 /// Shared state for the HTTP server
@@ -52,39 +192,30 @@ pub struct ServerState {
     pub dist_path: PathBuf,
     /// Optional authentication token (if set, clients must provide Bearer token)
     pub auth_token: Option<String>,
+    /// Optional admin token for `/api/debug/*` (host requests don't need it)
+    pub admin_token: Option<String>,
     /// Whether to require connections from local network only
     pub require_local_network: bool,
+    /// Overlay networks (Tailscale, WireGuard) treated as local network
+    pub trusted_networks: TrustedNetworks,
+    /// Failed token attempts per client IP (see auth_lockout.rs)
+    pub auth_lockout: AuthLockout,
+    /// Extra folders symlinks under served folders may point into (see safe_path.rs)
+    pub allowed_file_roots: Vec<PathBuf>,
     /// Broadcast channel for vNAS aircraft updates (to relay to WebSocket clients)
     pub vnas_tx: broadcast::Sender<Vec<VnasAircraftBroadcast>>,
     /// Count of currently connected remote clients (WebSocket connections)
     pub connected_clients: AtomicUsize,
+    /// Recently served files
+    pub file_cache: FileCache,
+    /// zstd copies of models and JSON files
+    pub zstd_cache: ZstdCache,
 }
 
-/// Check if an IP address is from a local/private network
-fn is_local_network_ip(ip: &IpAddr) -> bool {
-    match ip {
-        IpAddr::V4(ipv4) => {
-            let octets = ipv4.octets();
-            // 127.x.x.x (localhost)
-            octets[0] == 127
-            // 10.x.x.x (Class A private)
-            || octets[0] == 10
-            // 172.16.x.x - 172.31.x.x (Class B private)
-            || (octets[0] == 172 && (16..=31).contains(&octets[1]))
-            // 192.168.x.x (Class C private)
-            || (octets[0] == 192 && octets[1] == 168)
-            // 169.254.x.x (link-local)
-            || (octets[0] == 169 && octets[1] == 254)
-        }
-        IpAddr::V6(ipv6) => {
-            // ::1 (localhost)
-            ipv6.is_loopback()
-            // fe80::/10 (link-local)
-            || (ipv6.segments()[0] & 0xffc0) == 0xfe80
-            // fc00::/7 (unique local address)
-            || (ipv6.segments()[0] & 0xfe00) == 0xfc00
-        }
-    }
+/// Client address, with IPv4-mapped addresses from the dual-stack listener
+/// unwrapped (so 127.0.0.1 is loopback and lockouts key on the IPv4 address)
+fn client_ip(addr: &SocketAddr) -> IpAddr {
+    addr.ip().to_canonical()
 }
 
 /// Middleware to check authentication and local network requirements
@@ -93,38 +224,50 @@ async fn auth_middleware(
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
     request: Request<Body>,
     next: Next,
-) -> Result<Response<Body>, (StatusCode, String)> {
+) -> Result<Response<Body>, Error> {
+    let ip = client_ip(&addr);
+    let is_api_route = request.uri().path().starts_with("/api/");
+    if let Some(response) = lockout_response(&state, ip, is_api_route) {
+        return Ok(response);
+    }
+
+    // Requests on the client certificate listener already proved who they are
+    let via_client_cert = request.extensions().get::<ClientCertConnection>().is_some();
+
     // Check local network requirement
-    if state.require_local_network && !is_local_network_ip(&addr.ip()) {
-        return Err((
-            StatusCode::FORBIDDEN,
-            format!("Access denied: connections only allowed from local network. Your IP: {}", addr.ip()),
-        ));
+    if state.require_local_network
+        && !via_client_cert
+        && !state.trusted_networks.allows_client(&ip)
+    {
+        return Err(Error::Forbidden(format!(
+            "Access denied: connections only allowed from local network. Your IP: {}",
+            ip
+        )));
     }
 
-    // Check authentication token if configured
-    if let Some(ref expected_token) = state.auth_token {
+    // Check authentication token if configured (a client certificate stands in for it)
+    if let Some(expected_token) = state.auth_token.as_ref().filter(|_| !via_client_cert) {
         let auth_header = request
             .headers()
             .get(header::AUTHORIZATION)
             .and_then(|v| v.to_str().ok());
 
-        let is_authenticated = match auth_header {
-            Some(header) if header.starts_with("Bearer ") => {
-                let provided_token = &header[7..];
-                provided_token == expected_token
-            }
-            _ => false,
+        let provided_token = match auth_header {
+            Some(header) if header.starts_with("Bearer ") => Some(header[7..].to_string()),
+            _ => query_token(request.uri()),
         };
+        let is_authenticated = provided_token.as_deref() == Some(expected_token.as_str());
+
+        if is_authenticated {
+            state.auth_lockout.record_success(ip);
+        } else if provided_token.is_some() {
+            record_auth_failure(&state, ip, "auth token");
+        }
 
         if !is_authenticated {
             // Allow unauthenticated access to static files (the app itself)
-            let path = request.uri().path();
-            let is_api_route = path.starts_with("/api/");
-
             if is_api_route {
-                return Err((
-                    StatusCode::UNAUTHORIZED,
+                return Err(Error::Unauthorized(
                     "Authentication required. Provide Bearer token in Authorization header.".to_string(),
                 ));
             }
@@ -134,6 +277,133 @@ async fn auth_middleware(
     Ok(next.run(request).await)
 }
 
+/// 429 response for a locked-out client's API requests (see auth_lockout.rs)
+fn lockout_response(state: &ServerState, ip: IpAddr, is_api_route: bool) -> Option<Response<Body>> {
+    if !is_api_route {
+        return None;
+    }
+    let left = state.auth_lockout.locked_for(ip, std::time::Instant::now())?;
+    let secs = left.as_secs().max(1);
+    Some(
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, secs.to_string())],
+            format!("Too many failed authentication attempts. Try again in {} seconds.", secs),
+        )
+            .into_response(),
+    )
+}
+
+/// Count a wrong token, telling the host UI if it locked the client out
+fn record_auth_failure(state: &ServerState, ip: IpAddr, what: &str) {
+    let Some(event) = state.auth_lockout.record_failure(ip, std::time::Instant::now()) else {
+        debug!("[Server] Wrong {} from {}", what, ip);
+        return;
+    };
+    warn!(
+        "[Server] Locked out {} for {} s after {} wrong tokens ({})",
+        ip, event.locked_for_secs, event.failures, what
+    );
+    let _ = state.app_handle.emit("auth-lockout", event);
+}
+
+/// Middleware recording requests that change host state in the audit log
+/// (see audit_log.rs), with the summary their handler added to the response
+async fn audit_middleware(
+    State(state): State<Arc<ServerState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request<Body>,
+    next: Next,
+) -> Response<Body> {
+    let method = request.method().to_string();
+    if !audit_log::audited(&method, request.uri().path()) {
+        return next.run(request).await;
+    }
+    let path = audit_log::request_path(request.uri());
+    let user_agent = request.headers().get(header::USER_AGENT).and_then(|v| v.to_str().ok());
+    let client = client_logs::describe_client(client_ip(&addr), user_agent, None);
+
+    let response = next.run(request).await;
+    let status = response.status().as_u16();
+    let summary = response
+        .extensions()
+        .get::<AuditSummary>()
+        .map(|s| s.0.clone())
+        .unwrap_or_default();
+    let app = state.app_handle.clone();
+    tokio::task::spawn_blocking(move || audit_log::record(&app, client, &method, &path, status, summary));
+    response
+}
+
+/// Largest error response body checked for secrets (bigger ones pass unchanged)
+const MAX_REDACTED_ERROR_BYTES: u64 = 64 * 1024;
+
+/// Middleware scrubbing secrets (see secrets.rs) from error responses, which
+/// often quote upstream URLs or provider responses
+async fn redact_errors_middleware(request: Request<Body>, next: Next) -> Response<Body> {
+    let response = next.run(request).await;
+    let is_error = response.status().is_client_error() || response.status().is_server_error();
+    let small = response
+        .body()
+        .size_hint()
+        .upper()
+        .is_some_and(|len| len <= MAX_REDACTED_ERROR_BYTES);
+    if !is_error || !small {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, MAX_REDACTED_ERROR_BYTES as usize).await {
+        Ok(bytes) => bytes,
+        Err(_) => return Response::from_parts(parts, Body::empty()),
+    };
+    let redacted = match std::str::from_utf8(&bytes) {
+        Ok(text) => secrets::redact(text).into_owned().into_bytes(),
+        Err(_) => bytes.to_vec(),
+    };
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(redacted))
+}
+
+/// Auth token from the `token` query parameter, for clients that can't set
+/// headers (browser WebSockets, OBS browser sources)
+fn query_token(uri: &axum::http::Uri) -> Option<String> {
+    url::form_urlencoded::parse(uri.query()?.as_bytes())
+        .find(|(key, _)| key == "token")
+        .map(|(_, value)| value.into_owned())
+}
+
+/// Middleware for admin endpoints: requests from the host itself, or with the
+/// configured admin token in the X-Admin-Token header
+async fn admin_middleware(
+    State(state): State<Arc<ServerState>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    request: Request<Body>,
+    next: Next,
+) -> Result<Response<Body>, Error> {
+    let ip = client_ip(&addr);
+    if let Some(response) = lockout_response(&state, ip, true) {
+        return Ok(response);
+    }
+    let provided = request.headers().get("x-admin-token").and_then(|v| v.to_str().ok());
+    let has_token = matches!((&state.admin_token, provided), (Some(expected), Some(provided)) if provided == expected);
+    if provided.is_some() && !has_token {
+        record_auth_failure(&state, ip, "admin token");
+    }
+    if !ip.is_loopback() && !has_token {
+        return Err(Error::Forbidden(
+            "Admin access required. Connect from the host or provide the admin token in X-Admin-Token.".to_string(),
+        ));
+    }
+    Ok(next.run(request).await)
+}
+
+/// Whether the `/api/debug/*` endpoints are available (dev builds, or with
+/// the TOWERCAB_DEBUG_API env var set)
+fn debug_api_enabled() -> bool {
+    cfg!(debug_assertions) || std::env::var("TOWERCAB_DEBUG_API").is_ok()
+}
+
 /// Server handles returned from start_server
 pub struct ServerHandles {
     /// Send to this channel to shut down the server
@@ -152,66 +422,108 @@ pub async fn start_server(
     let dist_path = find_dist_path(&app_handle)?;
 
     // Read auth settings from global settings
-    let (auth_token, require_local_network) = {
+    let (auth_token, admin_token, require_local_network, allowed_file_roots, remote_access) = {
         let settings_file = get_global_settings_file(&app_handle)?;
         if settings_file.exists() {
             let content = fs::read_to_string(&settings_file)
                 .map_err(|e| format!("Failed to read settings: {}", e))?;
             let settings: GlobalSettings = serde_json::from_str(&content)
                 .map_err(|e| format!("Failed to parse settings: {}", e))?;
-            (settings.server.auth_token, settings.server.require_local_network)
+            (
+                settings.server.auth_token,
+                settings.server.admin_token,
+                settings.server.require_local_network,
+                settings.server.allowed_file_roots.iter().map(PathBuf::from).collect(),
+                settings.server.remote_access,
+            )
         } else {
-            (None, false)
+            (None, None, false, Vec::new(), Default::default())
         }
     };
+    let trusted_networks = TrustedNetworks::from_settings(&remote_access);
 
-    println!(
+    info!(
         "[Server] Starting HTTP server on port {} (serving from {:?})",
         port, dist_path
     );
     if auth_token.is_some() {
-        println!("[Server] Authentication enabled");
+        info!("[Server] Authentication enabled");
     }
     if require_local_network {
-        println!("[Server] Restricted to local network only");
+        info!("[Server] Restricted to local network only");
     }
+    if remote_access.trust_tailscale || !remote_access.trusted_networks.is_empty() {
+        info!("[Server] Trusting overlay networks: {:?}", trusted_networks);
+    }
+    // Fail before binding anything if the client certificate listener is misconfigured
+    let client_cert_tls = if remote_access.client_cert.enabled {
+        Some(remote_access::tls_config(&remote_access.client_cert)?)
+    } else {
+        None
+    };
 
     // Create vNAS broadcast channel for relaying aircraft updates to WebSocket clients
     let (vnas_tx, _) = broadcast::channel::<Vec<VnasAircraftBroadcast>>(256);
     let vnas_tx_return = vnas_tx.clone();
 
+    let zstd_dir = app_handle.path().app_cache_dir().ok().map(|dir| dir.join("zstd-cache"));
+    let watcher_app = app_handle.clone();
     let state = Arc::new(ServerState {
         app_handle,
         dist_path,
         auth_token,
+        admin_token,
         require_local_network,
+        trusted_networks,
+        auth_lockout: AuthLockout::new(),
+        allowed_file_roots,
         vnas_tx,
         connected_clients: AtomicUsize::new(0),
+        file_cache: FileCache::new(),
+        zstd_cache: ZstdCache::new(zstd_dir),
     });
 
     // Build the router
-    let app = create_router(state);
+    let app = create_router(state.clone());
 
     // Create shutdown channel
     let (shutdown_tx, mut shutdown_rx) = broadcast::channel::<()>(1);
 
+    if let Some(tls) = client_cert_tls {
+        let port = remote_access.client_cert.port;
+        start_client_cert_listener(state, tls, port, shutdown_tx.subscribe())?;
+    }
+
     // Bind to the port
-    let addr = SocketAddr::from(([0, 0, 0, 0], port));
-    let listener = tokio::net::TcpListener::bind(addr)
-        .await
+    let listener = bind_dual_stack(port)
+        .and_then(tokio::net::TcpListener::from_std)
         .map_err(|e| format!("Failed to bind to port {}: {}", port, e))?;
 
-    println!("[Server] Listening on http://0.0.0.0:{}", port);
+    if let Ok(addr) = listener.local_addr() {
+        info!("[Server] Listening on http://{}", addr);
+    }
 
     // Spawn the server task
-    tokio::spawn(async move {
+    let server_task = tokio::spawn(async move {
         axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
             .with_graceful_shutdown(async move {
                 let _ = shutdown_rx.recv().await;
-                println!("[Server] Shutting down...");
+                info!("[Server] Shutting down...");
             })
             .await
-            .unwrap_or_else(|e| eprintln!("[Server] Error: {}", e));
+    });
+
+    // Watch for the server stopping on its own (a panic also writes a crash report)
+    tokio::spawn(async move {
+        let failure = match server_task.await {
+            Ok(Ok(())) => return,
+            Ok(Err(e)) => format!("Error: {}", e),
+            Err(e) if e.is_panic() => "Server task panicked (see crash report)".to_string(),
+            Err(e) => e.to_string(),
+        };
+        error!("[Server] Stopped unexpectedly: {}", failure);
+        crate::mark_http_server_stopped();
+        let _ = watcher_app.emit("http-server-stopped", failure);
     });
 
     Ok(ServerHandles {
@@ -220,6 +532,95 @@ pub async fn start_server(
     })
 }
 
+/// Serve the API on a free loopback port with default settings (no auth, no
+/// frontend), for tests driving it end to end
+#[cfg(all(test, feature = "mock-traffic"))]
+pub(crate) async fn serve_for_test(
+    app_handle: tauri::AppHandle,
+) -> (SocketAddr, broadcast::Sender<Vec<VnasAircraftBroadcast>>) {
+    let (vnas_tx, _) = broadcast::channel::<Vec<VnasAircraftBroadcast>>(256);
+    let state = Arc::new(ServerState {
+        app_handle,
+        dist_path: std::env::temp_dir(),
+        auth_token: None,
+        admin_token: None,
+        require_local_network: false,
+        trusted_networks: TrustedNetworks::from_settings(&Default::default()),
+        auth_lockout: AuthLockout::new(),
+        allowed_file_roots: Vec::new(),
+        vnas_tx: vnas_tx.clone(),
+        connected_clients: AtomicUsize::new(0),
+        file_cache: FileCache::new(),
+        zstd_cache: ZstdCache::new(None),
+    });
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = create_router(state);
+    tokio::spawn(async move {
+        axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await
+    });
+    (addr, vnas_tx)
+}
+
+/// Listen on all addresses: one dual-stack socket taking IPv6 and IPv4
+/// (as IPv4-mapped addresses), or IPv4 only where the host has no IPv6
+fn bind_dual_stack(port: u16) -> std::io::Result<std::net::TcpListener> {
+    match bind_listener(SocketAddr::from((Ipv6Addr::UNSPECIFIED, port))) {
+        Err(e) if e.kind() != std::io::ErrorKind::AddrInUse => {
+            warn!("[Server] IPv6 unavailable ({}), listening on IPv4 only", e);
+            bind_listener(SocketAddr::from((Ipv4Addr::UNSPECIFIED, port)))
+        }
+        result => result,
+    }
+}
+
+fn bind_listener(addr: SocketAddr) -> std::io::Result<std::net::TcpListener> {
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+    if addr.is_ipv6() {
+        // Windows and some BSDs default to IPv6 only
+        socket.set_only_v6(false)?;
+    }
+    #[cfg(not(windows))]
+    socket.set_reuse_address(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(1024)?;
+    socket.set_nonblocking(true)?;
+    Ok(socket.into())
+}
+
+/// Serve the same routes over HTTPS on a second port, only to clients with a
+/// certificate signed by the configured CA (see remote_access.rs)
+fn start_client_cert_listener(
+    state: Arc<ServerState>,
+    tls: Arc<rustls::ServerConfig>,
+    port: u16,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) -> Result<(), String> {
+    let listener =
+        bind_dual_stack(port).map_err(|e| format!("Failed to bind client certificate port {}: {}", port, e))?;
+    let app = create_router(state).layer(Extension(ClientCertConnection));
+
+    let handle = axum_server::Handle::new();
+    let shutdown_handle = handle.clone();
+    tokio::spawn(async move {
+        let _ = shutdown_rx.recv().await;
+        shutdown_handle.graceful_shutdown(Some(std::time::Duration::from_secs(5)));
+    });
+
+    if let Ok(addr) = listener.local_addr() {
+        info!("[Server] Listening for client certificate connections on https://{}", addr);
+    }
+    tokio::spawn(async move {
+        let server = axum_server::from_tcp_rustls(listener, RustlsConfig::from_config(tls))
+            .handle(handle)
+            .serve(app.into_make_service_with_connect_info::<SocketAddr>());
+        if let Err(e) = server.await {
+            error!("[Server] Client certificate listener stopped: {}", e);
+        }
+    });
+    Ok(())
+}
+
 /// Find the frontend dist folder
 fn find_dist_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
     // In production, the dist folder should be next to the executable
@@ -268,7 +669,7 @@ fn find_dist_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
 
     for candidate in &candidates {
         if candidate.exists() && candidate.join("index.html").exists() {
-            println!("[Server] Found dist folder at: {:?}", candidate);
+            debug!("[Server] Found dist folder at: {:?}", candidate);
             return Ok(candidate.clone());
         }
     }
@@ -281,59 +682,223 @@ fn find_dist_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
     ))
 }
 
-/// Validate CORS origin - only allow local network origins
-fn validate_cors_origin(origin: &HeaderValue, _request_parts: &axum::http::request::Parts) -> bool {
+/// Validate CORS origin - only allow local network (and trusted overlay network) origins
+fn validate_cors_origin(origin: &HeaderValue, trusted: &TrustedNetworks) -> bool {
     let origin_str = match origin.to_str() {
         Ok(s) => s,
         Err(_) => return false,
     };
 
     // Parse the origin URL to extract the host
-    if let Ok(url) = Url::parse(origin_str) {
-        if let Some(host) = url.host_str() {
-            // Allow localhost variations
-            if host == "localhost" || host == "127.0.0.1" || host == "::1" {
-                return true;
-            }
-
-            // Check if host is an IP and is local network
-            if let Ok(ip) = host.parse::<IpAddr>() {
-                return is_local_network_ip(&ip);
-            }
-
-            // Allow .local domains (mDNS)
-            if host.ends_with(".local") {
-                return true;
-            }
-        }
+    let Ok(url) = Url::parse(origin_str) else {
+        return false;
+    };
+    // Tailscale/WireGuard addresses and MagicDNS names, if trusted
+    if url.host_str().is_some_and(|host| trusted.allows_origin_host(host)) {
+        return true;
+    }
+    match url.host() {
+        // Allow localhost and .local domains (mDNS)
+        Some(Host::Domain(host)) => host == "localhost" || host.ends_with(".local"),
+        // IP origins must be on the local network (IPv6 hosts come bracketed, so
+        // they are matched here rather than parsed from the host string)
+        Some(Host::Ipv4(ip)) => lan::is_local_network_ip(&IpAddr::V4(ip)),
+        Some(Host::Ipv6(ip)) => lan::is_local_network_ip(&IpAddr::V6(ip)),
+        None => false,
     }
-
-    false
 }
 
 /// Create the axum router with all routes
 fn create_router(state: Arc<ServerState>) -> Router {
     // CORS layer with origin validation
     // Only allow origins from local network addresses
+    let trusted = state.trusted_networks.clone();
     let cors = CorsLayer::new()
-        .allow_origin(AllowOrigin::predicate(validate_cors_origin))
+        .allow_origin(AllowOrigin::predicate(move |origin, _| validate_cors_origin(origin, &trusted)))
         .allow_methods(Any)
         .allow_headers(Any);
 
     let state_clone = state.clone();
 
-    Router::new()
+    let mut router = Router::new()
         // API routes
         .route("/api/global-settings", get(get_global_settings).post(update_global_settings))
+        .route("/api/docs", get(serve_api_docs))
+        .route("/api/docs/openapi.json", get(get_openapi_spec))
+        .route("/api/mods", get(get_mod_registry))
+        .route(
+            "/api/client/capabilities",
+            get(get_client_config).post(negotiate_client_capabilities),
+        )
+        .route("/api/units", get(get_unit_settings))
+        .route("/api/units/profile", put(set_unit_profile))
         .route("/api/mods/aircraft", get(list_aircraft_mods))
         .route("/api/mods/towers", get(list_tower_mods))
         .route("/api/mods/aircraft/*path", get(serve_aircraft_mod))
         .route("/api/mods/towers/*path", get(serve_tower_mod))
+        .route("/api/mods/objects", get(list_static_object_models))
+        .route("/api/mods/objects/*path", get(serve_static_object_model))
+        .route("/api/placements/:icao", get(get_static_objects).put(set_static_objects))
+        .route("/api/placements/:icao/:id", put(save_static_object).delete(delete_static_object))
+        .route("/api/annotations/:icao", get(get_annotations).put(set_annotations))
+        .route("/api/annotations/:icao/:id", put(save_annotation).delete(delete_annotation))
         .route("/api/fsltl/models", get(list_fsltl_models))
         .route("/api/fsltl/*path", get(serve_fsltl_model))
+        .route("/api/chunked/*path", get(get_chunked_download))
         .route("/api/tower-positions", get(get_tower_positions))
         .route("/api/tower-positions/{icao}", put(update_tower_position))
-        .route("/api/vmr-rules", get(get_vmr_rules))
+        .route("/api/vmr-rules", get(get_vmr_rules).post(add_vmr_rule))
+        .route("/api/vmr-rules/rule", put(update_vmr_rule).delete(delete_vmr_rule))
+        .route("/api/vmr-rules/effective", get(get_effective_vmr_rules))
+        .route("/api/vmr-rules/enabled", put(set_vmr_enabled))
+        .route("/api/vmr-subscriptions", get(list_vmr_subscriptions))
+        .route("/api/match", get(match_model))
+        .route("/api/match/similar-types", get(get_similar_types))
+        .route("/api/match/coverage", get(get_model_coverage).post(post_model_coverage))
+        .route("/api/match/usage", get(get_model_usage).delete(reset_model_usage))
+        .route("/api/weather/:icao", get(get_weather))
+        .route("/api/weather/:icao/taf", get(get_taf))
+        .route("/api/weather/:icao/history", get(get_metar_history))
+        .route("/api/weather/:icao/scene", get(get_weather_scene))
+        .route("/api/weather/:icao/runways", get(get_runway_suggestion))
+        .route("/api/weather/:icao/density-altitude", get(get_density_altitude))
+        .route("/api/weather/:icao/winds-aloft", get(get_winds_aloft))
+        .route("/api/airports/search", get(search_airports))
+        .route("/api/search", get(search_content))
+        .route("/api/airports/:icao", get(get_airport))
+        .route("/api/airports/:icao/runways", get(get_airport_runways))
+        .route("/api/airports/:icao/approaches", get(get_airport_approaches))
+        .route("/api/airports/:icao/airspace", get(get_airspace_overlay))
+        .route("/api/airports/:icao/atc", get(get_airport_atc))
+        .route("/api/atc/changes", get(get_atc_changes))
+        .route("/api/atc/ws", get(atc_websocket_handler))
+        .route("/api/controllers", get(list_controllers))
+        .route("/api/controllers/:id", get(get_controller))
+        .route("/api/afv", get(get_frequency_activity))
+        .route("/api/afv/ws", get(afv_websocket_handler))
+        .route("/api/audio", get(list_audio_streams))
+        .route("/api/audio/:id", get(get_audio_stream))
+        .route("/api/notams/:icao", get(get_notams))
+        .route("/api/charts/:icao", get(get_airport_charts))
+        .route("/api/charts/:icao/:pdf", get(serve_chart))
+        .route("/api/airports/:icao/layout", get(get_osm_layout))
+        .route("/api/airports/:icao/gates", get(get_airport_gates))
+        .route("/api/airports/:icao/gates/suggestions", get(get_gate_suggestions))
+        .route("/api/airports/:icao/taxi-routes", get(get_taxi_routes))
+        .route("/api/airports/:icao/landing-predictions", get(get_landing_predictions))
+        .route("/api/airports/:icao/view-preset", get(get_view_preset))
+        .route("/api/atis/comparison", get(get_atis_comparison))
+        .route("/api/gates/ws", get(gates_websocket_handler))
+        .route("/api/traffic", get(get_traffic))
+        .route("/api/lod", get(get_lod_policy))
+        .route("/api/traffic.geojson", get(get_traffic_geojson))
+        .route("/api/aircraft/:callsign/trail", get(get_aircraft_trail))
+        .route("/api/aircraft/:callsign/photo", get(get_aircraft_photo))
+        .route("/api/aircraft-types/:code", get(get_aircraft_type))
+        .route("/api/airlines", get(list_airlines))
+        .route("/api/airlines/:code", get(get_airline))
+        .route("/api/callsigns/:callsign", get(get_callsign_info))
+        .route("/api/strips/:icao", get(get_strips))
+        .route("/api/clock", get(get_sim_clock).put(set_sim_clock))
+        .route("/api/clock/ws", get(clock_websocket_handler))
+        .route("/api/datablock", get(get_datablock_layout).put(set_datablock_layout))
+        .route("/api/datablock/ws", get(datablock_websocket_handler))
+        .route(
+            "/api/replay",
+            get(get_replay_status)
+                .post(load_replay)
+                .layer(DefaultBodyLimit::max(REPLAY_UPLOAD_LIMIT))
+                .delete(stop_replay),
+        )
+        .route("/api/replay/recording", get(get_replay_recording))
+        .route("/api/replay/control", post(control_replay))
+        .route("/api/replay/instant", post(start_instant_replay))
+        .route("/api/replay/export", get(export_session))
+        .route("/api/replay/share", get(share_replay))
+        .route(
+            "/api/replays",
+            get(list_shared_replays)
+                .post(import_shared_replay)
+                .layer(DefaultBodyLimit::max(REPLAY_UPLOAD_LIMIT)),
+        )
+        .route("/api/replays/:name", get(download_shared_replay).delete(delete_shared_replay))
+        .route("/api/replays/:name/load", post(load_shared_replay))
+        .route("/api/stats/session", get(get_session_stats))
+        .route("/api/metrics", get(get_metrics))
+        .route("/api/diagnostics/startup", get(get_startup_profile))
+        .route("/api/diagnostics/tasks", get(get_task_status))
+        .route("/api/schedule", get(get_server_schedule))
+        .route("/api/events", get(get_upcoming_events))
+        .route("/api/events/:id/record", post(record_event))
+        .route("/api/client-logs", post(post_client_logs))
+        .route("/api/control/actions", get(get_control_actions))
+        .route("/api/control/trigger", post(trigger_control_action))
+        .route("/api/control/state", get(get_control_state))
+        .route("/api/control/ws", get(control_websocket_handler))
+        .route("/api/replay/ws", get(replay_websocket_handler))
+        .route("/api/squawks", get(get_squawk_alerts))
+        .route("/api/squawks/ws", get(squawks_websocket_handler))
+        .route("/api/stca", get(get_stca_alerts))
+        .route("/api/stca/ws", get(stca_websocket_handler))
+        .route("/api/labels", get(get_label_layout))
+        .route("/api/labels/ws", get(labels_websocket_handler))
+        .route("/api/runways/occupancy", get(get_runway_occupancy))
+        .route("/api/runways/occupancy/ws", get(runway_occupancy_websocket_handler))
+        .route("/api/runways/config", get(get_runway_configuration))
+        .route("/api/holds", get(get_hold_timers))
+        .route("/api/go-arounds", get(get_go_arounds))
+        .route("/api/go-arounds/ws", get(go_arounds_websocket_handler))
+        .route("/api/holds/ws", get(holds_websocket_handler))
+        .route("/api/atpa", get(get_final_spacing))
+        .route("/api/atpa/ws", get(atpa_websocket_handler))
+        .route("/api/arrivals", get(get_arrival_sequence))
+        .route("/api/arrivals/ws", get(arrivals_websocket_handler))
+        .route("/api/wake-timers", get(get_wake_timers))
+        .route("/api/wake-timers/ws", get(wake_timers_websocket_handler))
+        .route("/api/follow", get(get_follow).put(set_follow).delete(stop_follow))
+        .route("/api/follow/ws", get(follow_websocket_handler))
+        .route("/api/selection", get(get_selection).put(set_selection).delete(clear_selection))
+        .route("/api/selection/ws", get(selection_websocket_handler))
+        .route("/api/drawings", get(get_drawings).post(add_drawing).delete(clear_drawings))
+        .route("/api/drawings/sessions", get(list_drawing_sessions))
+        .route("/api/drawings/session", put(set_drawing_session))
+        .route("/api/drawings/ws", get(drawings_websocket_handler))
+        .route("/api/drawings/:id", delete(delete_drawing))
+        .route("/api/messages", get(get_coordination_messages).post(send_coordination_message))
+        .route("/api/messages/ws", get(messages_websocket_handler))
+        .route("/api/celestial", get(get_celestial))
+        .route("/api/celestial/:icao", get(get_airport_celestial))
+        .route("/api/lightning", get(get_lightning_status))
+        .route("/api/lightning/ws", get(lightning_websocket_handler))
+        .route("/api/federation/status", get(get_federation_status))
+        .route("/api/scripts", get(list_scripts))
+        .route("/api/scripts/ws", get(scripts_websocket_handler))
+        .route("/api/manifest", get(get_content_manifest))
+        .route("/api/overlays", get(list_overlays))
+        .route("/api/overlays/*path", get(serve_overlay_file))
+        .route("/api/sounds", get(list_sound_packs))
+        .route("/api/sounds/*path", get(serve_sound_file))
+        .route("/api/ext", get(list_plugins))
+        .route("/api/ext/:plugin/*path", get(serve_plugin_route))
+        .route(
+            "/api/airport-packs",
+            post(install_airport_pack).layer(DefaultBodyLimit::max(AIRPORT_PACK_UPLOAD_LIMIT)),
+        )
+        .route("/api/airport-packs/:icao", get(export_airport_pack))
+        .route("/api/videomaps/:icao", get(get_video_maps))
+        .route("/api/videomaps/:icao/import", post(import_video_maps))
+        .route("/api/videomaps/:icao/:id", get(serve_video_map))
+        .route("/api/tiles", get(get_tile_cache_status).delete(clear_tile_cache))
+        .route("/api/tiles/prefetch", post(prefetch_tiles).delete(cancel_tile_prefetch))
+        .route("/api/event-prep", get(get_event_prep_status).post(prepare_event).delete(cancel_event_prep))
+        .route("/api/tiles/terrain/*path", get(serve_terrain_tile))
+        .route("/api/tiles/imagery/*path", get(serve_imagery_tile))
+        .route("/api/map-providers", get(get_map_providers))
+        .route("/api/map-providers/vintages", get(list_imagery_vintages))
+        .route("/api/terrain", get(list_terrain_packages))
+        .route("/api/terrain/:id/*path", get(serve_terrain_package_file))
+        .route("/api/buildings/:icao", get(get_osm_buildings))
+        .route("/api/buildings/:icao/*path", get(serve_osm_buildings_file))
         .route("/api/proxy", get(proxy_request))
         // RealTraffic proxy endpoints (to bypass CORS)
         .route("/api/realtraffic/auth", post(realtraffic_auth))
@@ -344,10 +909,30 @@ fn create_router(state: Arc<ServerState>) -> Router {
         .route("/api/vnas/ws", get(vnas_websocket_handler))
         // Presence WebSocket for tracking connected remote clients
         .route("/api/presence", get(presence_websocket_handler))
+        // OBS browser source overlays (the pages hold no data, their API calls carry the token)
+        .route("/obs/:page", get(serve_obs_page));
+
+    // Internal state for debugging (dev-only, admin access)
+    if debug_api_enabled() {
+        router = router.nest(
+            "/api/debug",
+            Router::new()
+                .route("/streams", get(debug_streams))
+                .route("/caches", get(debug_caches))
+                .route("/traffic", get(debug_traffic))
+                .route("/tasks", get(debug_tasks))
+                .route_layer(middleware::from_fn_with_state(state.clone(), admin_middleware)),
+        );
+    }
+
+    router
         // Static file serving (must be last - catches all other routes)
         .fallback(get(serve_static))
+        // Record writes (runs after auth, so rejected requests aren't logged)
+        .layer(middleware::from_fn_with_state(state.clone(), audit_middleware))
         // Apply auth middleware (checks auth token and local network requirement)
         .layer(middleware::from_fn_with_state(state_clone, auth_middleware))
+        .layer(middleware::from_fn(redact_errors_middleware))
         .layer(cors)
         .with_state(state)
 }
@@ -356,186 +941,376 @@ fn create_router(state: Arc<ServerState>) -> Router {
 // API Handlers
 // =============================================================================
 
-/// GET /api/global-settings - Return global settings JSON
-async fn get_global_settings(
-    State(state): State<Arc<ServerState>>,
-) -> Result<Json<GlobalSettings>, (StatusCode, String)> {
-    let settings_file = get_global_settings_file(&state.app_handle)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+/// Run blocking work (filesystem scans, mod registry, VMR files) off the async
+/// runtime so a slow disk can't stall other requests and WebSockets
+async fn blocking<T: Send + 'static>(work: impl FnOnce() -> T + Send + 'static) -> Result<T, Error> {
+    tokio::task::spawn_blocking(work)
+        .await
+        .map_err(|e| Error::Internal(format!("Background task failed: {}", e)))
+}
 
-    if !settings_file.exists() {
-        return Ok(Json(GlobalSettings::default()));
-    }
+/// Resolve a file to serve under `root` (see safe_path.rs), given the request
+/// path or a path the app built
+async fn resolve_served_file(
+    state: &ServerState,
+    root: PathBuf,
+    policy: SymlinkPolicy,
+    resolve: impl FnOnce(&ServedRoot) -> Result<PathBuf, PathError> + Send + 'static,
+) -> Result<PathBuf, Error> {
+    let extra_roots = state.allowed_file_roots.clone();
+    blocking(move || ServedRoot::new(&root, &extra_roots, policy).and_then(|served| resolve(&served)))
+        .await?
+        .map_err(|e| match e {
+            PathError::Invalid => Error::invalid_input("Invalid path"),
+            PathError::NotFound => Error::not_found("File not found"),
+            PathError::Forbidden => Error::Forbidden("Access denied".to_string()),
+        })
+}
+
+/// App data folder (video maps, charts)
+fn app_data_root(state: &ServerState) -> Result<PathBuf, Error> {
+    state
+        .app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))
+}
+
+/// Read global settings from disk (defaults if the file doesn't exist)
+async fn read_settings_file(state: &ServerState) -> Result<GlobalSettings, Error> {
+    let settings_file = get_global_settings_file(&state.app_handle)?;
 
-    let content = fs::read_to_string(&settings_file)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to read settings: {}", e)))?;
+    let content = match tokio::fs::read_to_string(&settings_file).await {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(GlobalSettings::default()),
+        Err(e) => return Err(Error::io(format!("Failed to read settings: {}", e))),
+    };
 
-    let settings: GlobalSettings = serde_json::from_str(&content)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to parse settings: {}", e)))?;
+    let mut value: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| Error::Internal(format!("Failed to parse settings: {}", e)))?;
+    datablocks::migrate_settings(&mut value);
+    serde_json::from_value(value)
+        .map_err(|e| Error::Internal(format!("Failed to parse settings: {}", e)))
+}
 
-    Ok(Json(settings))
+/// GET /api/global-settings - Return global settings JSON
+async fn get_global_settings(
+    State(state): State<Arc<ServerState>>,
+) -> Result<Json<GlobalSettings>, Error> {
+    read_settings_file(&state).await.map(Json)
 }
 
 /// POST /api/global-settings - Update global settings
 async fn update_global_settings(
     State(state): State<Arc<ServerState>>,
     Json(settings): Json<GlobalSettings>,
-) -> Result<Json<GlobalSettings>, (StatusCode, String)> {
-    let settings_file = get_global_settings_file(&state.app_handle)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+) -> Result<(Extension<AuditSummary>, Json<GlobalSettings>), Error> {
+    let settings_file = get_global_settings_file(&state.app_handle)?;
+    let previous = read_settings_file(&state).await.unwrap_or_default();
 
     // Ensure parent directory exists
     if let Some(parent) = settings_file.parent() {
-        fs::create_dir_all(parent)
-            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to create directory: {}", e)))?;
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| Error::io(format!("Failed to create directory: {}", e)))?;
     }
 
     // Write settings to file
     let content = serde_json::to_string_pretty(&settings)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to serialize settings: {}", e)))?;
+        .map_err(|e| Error::Internal(format!("Failed to serialize settings: {}", e)))?;
+
+    tokio::fs::write(&settings_file, content)
+        .await
+        .map_err(|e| Error::io(format!("Failed to write settings: {}", e)))?;
+
+    info!("[Server] Updated global settings via API");
+    secrets::register_settings(&settings);
+    clock::apply_settings(&state.app_handle, &settings.clock);
+    datablocks::apply_settings(&state.app_handle, &settings.datablock);
+    vnas_batch::apply_settings(&state.app_handle, &settings.server);
+    lod::apply_settings(&state.app_handle, &settings.lod);
+    udp_output::apply_settings(&state.app_handle, &settings.udp_output);
+    logging::apply_settings(&state.app_handle, &settings.diagnostics);
+    let changes = audit_log::diff_summary(
+        "",
+        &serde_json::to_value(&previous).unwrap_or_default(),
+        &serde_json::to_value(&settings).unwrap_or_default(),
+    );
+    Ok((Extension(AuditSummary(changes)), Json(settings)))
+}
 
-    fs::write(&settings_file, content)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to write settings: {}", e)))?;
+/// GET /api/client/capabilities - How the host serves this browser (desktop
+/// defaults if it never reported its capabilities)
+async fn get_client_config(headers: HeaderMap) -> Json<ClientConfig> {
+    Json(client_capabilities::config_for(&headers).unwrap_or_else(|| {
+        client_capabilities::negotiate(&ClientCapabilities::default(), zstd_cache::accepts_zstd(&headers))
+    }))
+}
 
-    println!("[Server] Updated global settings via API");
-    Ok(Json(settings))
+/// POST /api/client/capabilities - Report device class, bandwidth and codecs;
+/// returns the tailored configuration and remembers it in a cookie
+async fn negotiate_client_capabilities(
+    headers: HeaderMap,
+    Json(capabilities): Json<ClientCapabilities>,
+) -> impl IntoResponse {
+    let config = client_capabilities::negotiate(&capabilities, zstd_cache::accepts_zstd(&headers));
+    (
+        [(header::SET_COOKIE, client_capabilities::set_cookie(&capabilities))],
+        Json(config),
+    )
 }
 
-/// Mod directory info for API response
+/// Unit settings and the profile this browser uses
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
-struct ModInfo {
-    name: String,
-    path: String,
-    manifest: Option<serde_json::Value>,
+struct UnitSettingsResponse {
+    /// Selected profile (None: the default preferences)
+    profile: Option<String>,
+    preferences: UnitPreferences,
+    profiles: Vec<String>,
+}
+
+/// Body of PUT /api/units/profile
+#[derive(Deserialize)]
+struct UnitProfileRequest {
+    profile: Option<String>,
+}
+
+/// Unit preferences for a request's browser (its profile, or the default)
+async fn unit_preferences(state: &ServerState, headers: &HeaderMap) -> UnitPreferences {
+    read_settings_file(state)
+        .await
+        .map(|settings| units::resolve(&settings.units, units::profile_from_headers(headers).as_deref()))
+        .unwrap_or_default()
+}
+
+/// GET /api/units - Unit profiles and the preferences this browser gets
+async fn get_unit_settings(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> Result<Json<UnitSettingsResponse>, Error> {
+    let settings = read_settings_file(&state).await?.units;
+    let profile = units::profile_from_headers(&headers).filter(|p| settings.profiles.contains_key(p));
+    Ok(Json(UnitSettingsResponse {
+        preferences: units::resolve(&settings, profile.as_deref()),
+        profile,
+        profiles: settings.profiles.keys().cloned().collect(),
+    }))
+}
+
+/// PUT /api/units/profile - Pick the unit profile for this browser (null: the default)
+async fn set_unit_profile(
+    State(state): State<Arc<ServerState>>,
+    Json(request): Json<UnitProfileRequest>,
+) -> Result<impl IntoResponse, Error> {
+    let settings = read_settings_file(&state).await?.units;
+    if let Some(profile) = &request.profile {
+        if !units::valid_profile_name(profile) || !settings.profiles.contains_key(profile) {
+            return Err(Error::NotFound(format!("Unknown unit profile: {}", profile)));
+        }
+    }
+    let preferences = units::resolve(&settings, request.profile.as_deref());
+    Ok((
+        [(header::SET_COOKIE, units::set_cookie(request.profile.as_deref()))],
+        Json(preferences),
+    ))
 }
 
-/// GET /api/mods/aircraft - List aircraft mods with manifests
+/// GET /api/mods - Full mod registry (all types, including disabled mods)
+async fn get_mod_registry(
+    State(state): State<Arc<ServerState>>,
+) -> Result<Json<ModRegistry>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || mods::get_registry_with_variants(&app)).await.map(Json)
+}
+
+/// GET /api/mods/aircraft - List enabled aircraft mods (registry entries, in load order)
 async fn list_aircraft_mods(
     State(state): State<Arc<ServerState>>,
-) -> Result<Json<Vec<ModInfo>>, (StatusCode, String)> {
+) -> Result<Json<Vec<ModRegistryEntry>>, Error> {
     list_mods(&state, "aircraft").await
 }
 
-/// GET /api/mods/towers - List tower mods with manifests
+/// GET /api/mods/towers - List enabled tower mods (registry entries, in load order)
 async fn list_tower_mods(
     State(state): State<Arc<ServerState>>,
-) -> Result<Json<Vec<ModInfo>>, (StatusCode, String)> {
+) -> Result<Json<Vec<ModRegistryEntry>>, Error> {
     list_mods(&state, "towers").await
 }
 
-/// Common function to list mods of a given type
+/// Common function to list enabled mods of a given type from the registry
 async fn list_mods(
     state: &ServerState,
     mod_type: &str,
-) -> Result<Json<Vec<ModInfo>>, (StatusCode, String)> {
-    let mods_root = find_mods_root(&state.app_handle);
-    let mods_path = mods_root.join(mod_type);
-
-    if !mods_path.exists() {
-        return Ok(Json(Vec::new()));
-    }
-
-    let entries = fs::read_dir(&mods_path)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to read mods: {}", e)))?;
-
-    let mut mods = Vec::new();
-    for entry in entries.filter_map(|e| e.ok()) {
-        let path = entry.path();
-        if !path.is_dir() {
-            continue;
-        }
-
-        let name = match entry.file_name().into_string() {
-            Ok(n) => n,
-            Err(_) => continue,
-        };
-
-        // Try to read manifest
-        let manifest_path = path.join("manifest.json");
-        let manifest = if manifest_path.exists() {
-            fs::read_to_string(&manifest_path)
-                .ok()
-                .and_then(|c| serde_json::from_str(&c).ok())
-        } else {
-            None
-        };
-
-        mods.push(ModInfo {
-            name,
-            path: normalize_path_string(&path),
-            manifest,
-        });
-    }
-
-    Ok(Json(mods))
+) -> Result<Json<Vec<ModRegistryEntry>>, Error> {
+    let app = state.app_handle.clone();
+    let mod_type = mod_type.to_string();
+    blocking(move || mods::enabled_mods(&app, &mod_type)).await.map(Json)
 }
 
 /// GET /api/mods/aircraft/*path - Serve aircraft model file
 async fn serve_aircraft_mod(
     State(state): State<Arc<ServerState>>,
     Path(path): Path<String>,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
-    serve_mod_file(&state, "aircraft", &path).await
+    serve_mod_file(&state, &headers, "aircraft", &path).await
 }
 
 /// GET /api/mods/towers/*path - Serve tower model file
 async fn serve_tower_mod(
     State(state): State<Arc<ServerState>>,
     Path(path): Path<String>,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
-    serve_mod_file(&state, "towers", &path).await
+    serve_mod_file(&state, &headers, "towers", &path).await
 }
 
-/// Common function to serve mod files
-async fn serve_mod_file(
-    state: &ServerState,
-    mod_type: &str,
-    path: &str,
-) -> Result<Response<Body>, (StatusCode, String)> {
-    let mods_root = find_mods_root(&state.app_handle);
-    let file_path = mods_root.join(mod_type).join(path);
+/// GET /api/mods/objects - Static object models (paths relative to mods/objects)
+async fn list_static_object_models(
+    State(state): State<Arc<ServerState>>,
+) -> Result<Json<Vec<String>>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || placements::list_models(&app)).await.map(Json)
+}
+
+/// GET /api/mods/objects/*path - Serve static object model file
+async fn serve_static_object_model(
+    State(state): State<Arc<ServerState>>,
+    Path(path): Path<String>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    serve_mod_file(&state, &headers, placements::MODELS_DIR, &path).await
+}
 
-    // Security: ensure the path is within mods directory
-    let canonical = file_path.canonicalize().map_err(|_| {
-        (StatusCode::NOT_FOUND, "File not found".to_string())
-    })?;
+/// GET /api/placements/:icao - Static objects placed at an airport
+async fn get_static_objects(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+) -> Result<Json<Vec<StaticObject>>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || placements::get_objects(&app, &icao)).await?.map(Json)
+}
+
+/// PUT /api/placements/:icao - Replace every static object placed at an airport
+async fn set_static_objects(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+    Json(objects): Json<Vec<StaticObject>>,
+) -> Result<Json<Vec<StaticObject>>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || placements::set_objects(&app, &icao, objects)).await?.map(Json)
+}
 
-    let mods_canonical = mods_root.canonicalize().unwrap_or(mods_root.clone());
-    if !canonical.starts_with(&mods_canonical) {
-        return Err((StatusCode::FORBIDDEN, "Access denied".to_string()));
+/// PUT /api/placements/:icao/:id - Place (or move) a static object
+async fn save_static_object(
+    State(state): State<Arc<ServerState>>,
+    Path((icao, id)): Path<(String, String)>,
+    Json(object): Json<StaticObject>,
+) -> Result<Json<Vec<StaticObject>>, Error> {
+    if object.id != id {
+        return Err(Error::invalid_input("Object id doesn't match the URL"));
     }
+    let app = state.app_handle.clone();
+    blocking(move || placements::save_object(&app, &icao, object)).await?.map(Json)
+}
 
-    serve_file(&canonical).await
+/// DELETE /api/placements/:icao/:id - Remove a static object
+async fn delete_static_object(
+    State(state): State<Arc<ServerState>>,
+    Path((icao, id)): Path<(String, String)>,
+) -> Result<Json<Vec<StaticObject>>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || placements::delete_object(&app, &icao, &id)).await?.map(Json)
 }
 
-/// GET /api/fsltl/models - List converted FSLTL models
-async fn list_fsltl_models(
+/// GET /api/annotations/:icao - Hotspots, ILS critical areas and non-movement boundaries at an airport
+async fn get_annotations(
     State(state): State<Arc<ServerState>>,
-) -> Result<Json<Vec<ScannedFSLTLModel>>, (StatusCode, String)> {
-    // Get FSLTL output path from global settings
-    let settings_file = get_global_settings_file(&state.app_handle)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    Path(icao): Path<String>,
+) -> Result<Json<Vec<Annotation>>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || annotations::get_annotations(&app, &icao)).await?.map(Json)
+}
+
+/// PUT /api/annotations/:icao - Replace every annotation at an airport
+async fn set_annotations(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+    Json(list): Json<Vec<Annotation>>,
+) -> Result<Json<Vec<Annotation>>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || annotations::set_annotations(&app, &icao, list)).await?.map(Json)
+}
+
+/// PUT /api/annotations/:icao/:id - Add (or edit) an annotation
+async fn save_annotation(
+    State(state): State<Arc<ServerState>>,
+    Path((icao, id)): Path<(String, String)>,
+    Json(annotation): Json<Annotation>,
+) -> Result<Json<Vec<Annotation>>, Error> {
+    if annotation.id != id {
+        return Err(Error::invalid_input("Annotation id doesn't match the URL"));
+    }
+    let app = state.app_handle.clone();
+    blocking(move || annotations::save_annotation(&app, &icao, annotation)).await?.map(Json)
+}
 
-    let output_path = if settings_file.exists() {
-        let content = fs::read_to_string(&settings_file)
-            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to read settings: {}", e)))?;
+/// DELETE /api/annotations/:icao/:id - Remove an annotation
+async fn delete_annotation(
+    State(state): State<Arc<ServerState>>,
+    Path((icao, id)): Path<(String, String)>,
+) -> Result<Json<Vec<Annotation>>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || annotations::delete_annotation(&app, &icao, &id)).await?.map(Json)
+}
 
-        let settings: GlobalSettings = serde_json::from_str(&content)
-            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to parse settings: {}", e)))?;
+/// Common function to serve mod files
+async fn serve_mod_file(
+    state: &ServerState,
+    headers: &HeaderMap,
+    mod_type: &str,
+    path: &str,
+) -> Result<Response<Body>, Error> {
+    let file_path = resolve_mod_file(state, headers, mod_type, path).await?;
+    serve_file_compressed(state, headers, &file_path).await
+}
 
-        settings.fsltl.output_path
+/// The file a mod request is served from (active variant, client's model variant)
+async fn resolve_mod_file(
+    state: &ServerState,
+    headers: &HeaderMap,
+    mod_type: &str,
+    path: &str,
+) -> Result<PathBuf, Error> {
+    let mods_root = find_mods_root(&state.app_handle);
+    let path = if mods::MOD_TYPES.contains(&mod_type) {
+        let app = state.app_handle.clone();
+        let (mod_type, path) = (mod_type.to_string(), path.to_string());
+        blocking(move || mod_variants::resolve_file(&app, &mod_type, &path)).await?
     } else {
-        None
+        path.to_string()
     };
+    let relative = format!("{}/{}", mod_type, path);
+    let config = client_capabilities::config_for(headers);
+    resolve_served_file(state, mods_root, SymlinkPolicy::WithinAllowed, move |root| {
+        resolve_model_variant(root, &relative, config)
+    })
+    .await
+}
 
-    let Some(output_path) = output_path else {
+/// GET /api/fsltl/models - List converted FSLTL models
+async fn list_fsltl_models(
+    State(state): State<Arc<ServerState>>,
+) -> Result<Json<Vec<ScannedFSLTLModel>>, Error> {
+    // Get FSLTL output path from global settings
+    let Some(output_path) = read_settings_file(&state).await?.fsltl.output_path else {
         return Ok(Json(Vec::new()));
     };
 
-    // Use the existing scan_fsltl_models logic
-    let models = crate::scan_fsltl_models(output_path)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    let models = blocking(move || fsltl_scan::scan(&output_path, |_| {}))
+        .await?
+        .map_err(Error::Io)?;
 
     Ok(Json(models))
 }
@@ -544,50 +1319,119 @@ async fn list_fsltl_models(
 async fn serve_fsltl_model(
     State(state): State<Arc<ServerState>>,
     Path(path): Path<String>,
+    headers: HeaderMap,
 ) -> impl IntoResponse {
+    let file_path = resolve_fsltl_file(&state, &headers, path).await?;
+    serve_file_compressed(&state, &headers, &file_path).await
+}
+
+/// The file an FSLTL model request is served from (client's model variant)
+async fn resolve_fsltl_file(
+    state: &ServerState,
+    headers: &HeaderMap,
+    path: String,
+) -> Result<PathBuf, Error> {
     // Get FSLTL output path from global settings
-    let settings_file = match get_global_settings_file(&state.app_handle) {
-        Ok(f) => f,
-        Err(e) => return Err((StatusCode::INTERNAL_SERVER_ERROR, e)),
+    let Some(output_path) = read_settings_file(state).await?.fsltl.output_path else {
+        return Err(Error::not_found("FSLTL output path not configured"));
     };
 
-    let output_path = if settings_file.exists() {
-        let content = fs::read_to_string(&settings_file)
-            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to read settings: {}", e)))?;
+    let config = client_capabilities::config_for(headers);
+    resolve_served_file(
+        state,
+        PathBuf::from(&output_path),
+        SymlinkPolicy::WithinAllowed,
+        move |root| resolve_model_variant(root, &path, config),
+    )
+    .await
+}
 
-        let settings: GlobalSettings = serde_json::from_str(&content)
-            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to parse settings: {}", e)))?;
+/// Query parameters for chunked downloads
+#[derive(Deserialize)]
+struct ChunkQuery {
+    /// Chunk to download (default: the chunk manifest)
+    chunk: Option<usize>,
+    /// SHA-256 of the whole file from the manifest the download started with
+    hash: Option<String>,
+}
 
-        settings.fsltl.output_path
-    } else {
-        None
+/// GET /api/chunked/*path?chunk=&hash= - A mod or FSLTL model file in hashed chunks, for resumable downloads
+/// (`mods/aircraft/B738/model.glb` for `/api/mods/aircraft/B738/model.glb`; see chunked_downloads.rs)
+async fn get_chunked_download(
+    State(state): State<Arc<ServerState>>,
+    Path(path): Path<String>,
+    Query(query): Query<ChunkQuery>,
+    headers: HeaderMap,
+) -> Result<Response<Body>, Error> {
+    let file_path = match path.split_once('/') {
+        Some(("mods", rest)) => {
+            let (mod_type, rest) = rest
+                .split_once('/')
+                .ok_or_else(|| Error::NotFound(format!("Not found: {}", path)))?;
+            if !mods::MOD_TYPES.contains(&mod_type) && mod_type != placements::MODELS_DIR {
+                return Err(Error::NotFound(format!("Unknown mod type: {}", mod_type)));
+            }
+            resolve_mod_file(&state, &headers, mod_type, rest).await?
+        }
+        Some(("fsltl", rest)) => resolve_fsltl_file(&state, &headers, rest.to_string()).await?,
+        _ => return Err(Error::NotFound(format!("Not available in chunks: {}", path))),
     };
 
-    let Some(output_path) = output_path else {
-        return Err((StatusCode::NOT_FOUND, "FSLTL output path not configured".to_string()));
+    let manifest = {
+        let file_path = file_path.clone();
+        blocking(move || chunked_downloads::manifest(&file_path))
+            .await?
+            .map_err(|e| Error::io(format!("Failed to read {}: {}", path, e)))?
+    };
+    let Some(index) = query.chunk else {
+        let mut resp = Json(manifest.as_ref()).into_response();
+        resp.headers_mut()
+            .insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+        return Ok(resp);
     };
 
-    let file_path = PathBuf::from(&output_path).join(&path);
-
-    // Security: ensure the path is within output directory
-    let canonical = file_path.canonicalize().map_err(|_| {
-        (StatusCode::NOT_FOUND, "File not found".to_string())
-    })?;
-
-    let output_canonical = PathBuf::from(&output_path).canonicalize().unwrap_or(PathBuf::from(&output_path));
-    if !canonical.starts_with(&output_canonical) {
-        return Err((StatusCode::FORBIDDEN, "Access denied".to_string()));
+    if query.hash.as_deref().is_some_and(|hash| hash != manifest.hash) {
+        return Err(Error::conflict("File changed since the download started"));
     }
+    let Some(chunk_hash) = manifest.chunks.get(index).cloned() else {
+        return Err(Error::InvalidInput(format!("No chunk {}", index)));
+    };
+    let bytes = blocking(move || chunked_downloads::read_chunk(&file_path, &manifest, index))
+        .await?
+        .map_err(|e| Error::io(format!("Failed to read chunk {}: {}", index, e)))?;
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/octet-stream")
+        .header(header::CONTENT_LENGTH, bytes.len())
+        .header("X-Chunk-Sha256", chunk_hash)
+        // A chunk of a given file hash never changes
+        .header(header::CACHE_CONTROL, "private, max-age=31536000, immutable")
+        .body(Body::from(bytes))
+        .unwrap())
+}
 
-    serve_file(&canonical).await
+/// Resolve a model under `root`, or the variant the client negotiated (see
+/// client_capabilities.rs) if there is one
+fn resolve_model_variant(
+    root: &ServedRoot,
+    relative: &str,
+    config: Option<ClientConfig>,
+) -> Result<PathBuf, PathError> {
+    let path = root.resolve(relative)?;
+    match config {
+        Some(config) => Ok(root
+            .check(&client_capabilities::model_variant(&path, &config))
+            .unwrap_or(path)),
+        None => Ok(path),
+    }
 }
 
 /// GET /api/tower-positions - Custom tower positions JSON
 async fn get_tower_positions(
     State(state): State<Arc<ServerState>>,
-) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
-    let positions = read_tower_positions(state.app_handle.clone())
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+) -> Result<Json<serde_json::Value>, Error> {
+    let app = state.app_handle.clone();
+    let positions = blocking(move || read_tower_positions(app)).await??;
 
     Ok(Json(positions))
 }
@@ -597,32 +1441,33 @@ async fn update_tower_position(
     State(state): State<Arc<ServerState>>,
     Path(icao): Path<String>,
     Json(position): Json<TowerPositionEntry>,
-) -> Result<Json<TowerPositionEntry>, (StatusCode, String)> {
+) -> Result<(Extension<AuditSummary>, Json<TowerPositionEntry>), Error> {
     let mods_root = find_mods_root(&state.app_handle);
     let tower_positions_dir = mods_root.join("tower-positions");
 
     // Create tower-positions directory if it doesn't exist
-    fs::create_dir_all(&tower_positions_dir)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to create tower-positions directory: {}", e)))?;
+    tokio::fs::create_dir_all(&tower_positions_dir)
+        .await
+        .map_err(|e| Error::io(format!("Failed to create tower-positions directory: {}", e)))?;
 
     // Write to individual file named {ICAO}.json
     let file_path = tower_positions_dir.join(format!("{}.json", icao.to_uppercase()));
 
     // If file exists, merge with existing data (preserve other view if only updating one)
-    let mut entry = if file_path.exists() {
-        let content = fs::read_to_string(&file_path)
-            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to read existing position file: {}", e)))?;
-        serde_json::from_str::<TowerPositionEntry>(&content).unwrap_or(TowerPositionEntry {
-            view_3d: None,
-            view_2d: None,
-        })
-    } else {
-        TowerPositionEntry {
-            view_3d: None,
-            view_2d: None,
+    let empty = TowerPositionEntry {
+        view_3d: None,
+        view_2d: None,
+    };
+    let mut entry = match tokio::fs::read_to_string(&file_path).await {
+        Ok(content) => serde_json::from_str::<TowerPositionEntry>(&content).unwrap_or(empty),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => empty,
+        Err(e) => {
+            return Err(Error::io(format!("Failed to read existing position file: {}", e)))
         }
     };
 
+    let previous = serde_json::to_value(&entry).unwrap_or_default();
+
     // Update only the views that are provided
     if position.view_3d.is_some() {
         entry.view_3d = position.view_3d.clone();
@@ -633,84 +1478,827 @@ async fn update_tower_position(
 
     // Serialize and write
     let content = serde_json::to_string_pretty(&entry)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to serialize position: {}", e)))?;
+        .map_err(|e| Error::Internal(format!("Failed to serialize position: {}", e)))?;
 
-    fs::write(&file_path, content)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to write position file: {}", e)))?;
+    tokio::fs::write(&file_path, content)
+        .await
+        .map_err(|e| Error::io(format!("Failed to write position file: {}", e)))?;
 
-    println!("[Server] Updated tower position for {} via API", icao.to_uppercase());
-    Ok(Json(entry))
+    info!("[Server] Updated tower position for {} via API", icao.to_uppercase());
+    let changes = audit_log::diff_summary(
+        &icao.to_uppercase(),
+        &previous,
+        &serde_json::to_value(&entry).unwrap_or_default(),
+    );
+    Ok((Extension(AuditSummary(changes)), Json(entry)))
 }
 
-/// VMR rule entry for API response
-#[derive(Serialize)]
+/// Query parameters for listing VMR rules
+#[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct VmrRule {
-    type_code: String,
-    model_name: String,
-    callsign_prefix: Option<String>,
+struct VmrRulesQuery {
+    #[serde(default)]
+    include_disabled: bool,
 }
 
-/// GET /api/vmr-rules - Parsed VMR rules as JSON
+/// GET /api/vmr-rules - Parsed VMR rules as JSON (add ?includeDisabled=true to list disabled rules too)
 async fn get_vmr_rules(
     State(state): State<Arc<ServerState>>,
-) -> Result<Json<Vec<VmrRule>>, (StatusCode, String)> {
-    let mods_root = find_mods_root(&state.app_handle);
-    let mut rules = Vec::new();
-
-    // Scan for .vmr files
-    for dir in [&mods_root, &mods_root.join("aircraft")] {
-        if !dir.exists() {
-            continue;
+    Query(query): Query<VmrRulesQuery>,
+) -> Result<Json<Vec<VmrRule>>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || {
+        if query.include_disabled {
+            vmr::collect_all_rules(&app)
+        } else {
+            vmr::collect_mod_rules(&app)
         }
+    })
+    .await
+    .map(Json)
+}
 
-        let entries = fs::read_dir(dir)
-            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to read dir: {}", e)))?;
+/// GET /api/vmr-rules/effective - Merged ruleset showing which rule wins for each key
+async fn get_effective_vmr_rules(
+    State(state): State<Arc<ServerState>>,
+) -> Result<Json<Vec<EffectiveVmrRule>>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || vmr::effective_rules(vmr::collect_mod_rules(&app)))
+        .await
+        .map(Json)
+}
 
-        for entry in entries.filter_map(|e| e.ok()) {
-            let path = entry.path();
-            if path.extension().map_or(false, |ext| ext.eq_ignore_ascii_case("vmr")) {
-                if let Ok(content) = fs::read_to_string(&path) {
-                    // Simple VMR XML parsing (just extract ModelMatchRule entries)
-                    parse_vmr_content(&content, &mut rules);
-                }
-            }
-        }
-    }
+/// Request body for adding a VMR rule
+#[derive(Deserialize)]
+struct AddVmrRuleRequest {
+    file: String,
+    rule: VmrRuleInput,
+}
 
-    Ok(Json(rules))
+/// Query parameters identifying an existing VMR rule
+#[derive(Deserialize)]
+struct VmrRuleQuery {
+    file: String,
+    index: usize,
 }
 
-/// Parse VMR XML content and extract rules
-fn parse_vmr_content(content: &str, rules: &mut Vec<VmrRule>) {
-    // Simple regex-free parsing for ModelMatchRule entries
-    // Format: <ModelMatchRule TypeCode="..." ModelName="..." CallsignPrefix="..." />
-    for line in content.lines() {
-        let line = line.trim();
-        if !line.contains("ModelMatchRule") {
-            continue;
-        }
+/// Audit log description of a VMR rule (e.g. `B738 -> FSLTL_B738_AAL`)
+fn describe_vmr_rule(rule: &VmrRuleInput) -> String {
+    let selector: Vec<&str> = [&rule.type_code, &rule.callsign_prefix, &rule.flight_number_range]
+        .into_iter()
+        .filter_map(|s| s.as_deref())
+        .collect();
+    format!("{} -> {}", selector.join(" "), rule.model_names.join("//"))
+}
 
-        let type_code = extract_attr(line, "TypeCode");
-        let model_name = extract_attr(line, "ModelName");
+/// POST /api/vmr-rules - Add a rule to a VMR file (body: { file, rule })
+async fn add_vmr_rule(
+    State(state): State<Arc<ServerState>>,
+    Json(request): Json<AddVmrRuleRequest>,
+) -> Result<(Extension<AuditSummary>, Json<Vec<VmrRule>>), Error> {
+    let summary = AuditSummary(vec![format!("{}: added {}", request.file, describe_vmr_rule(&request.rule))]);
+    let app = state.app_handle.clone();
+    blocking(move || vmr::add_rule(&app, &request.file, &request.rule))
+        .await?
+        .map(|rules| (Extension(summary), Json(rules)))
+}
 
-        if let (Some(type_code), Some(model_name)) = (type_code, model_name) {
-            let callsign_prefix = extract_attr(line, "CallsignPrefix");
-            rules.push(VmrRule {
-                type_code,
-                model_name,
-                callsign_prefix,
-            });
-        }
+/// PUT /api/vmr-rules/rule?file=aircraft/custom.vmr&index=3 - Replace a rule
+async fn update_vmr_rule(
+    State(state): State<Arc<ServerState>>,
+    Query(query): Query<VmrRuleQuery>,
+    Json(rule): Json<VmrRuleInput>,
+) -> Result<(Extension<AuditSummary>, Json<Vec<VmrRule>>), Error> {
+    let summary = AuditSummary(vec![format!(
+        "{}: rule {} set to {}",
+        query.file,
+        query.index,
+        describe_vmr_rule(&rule)
+    )]);
+    let app = state.app_handle.clone();
+    blocking(move || vmr::update_rule(&app, &query.file, query.index, &rule))
+        .await?
+        .map(|rules| (Extension(summary), Json(rules)))
+}
+
+/// DELETE /api/vmr-rules/rule?file=aircraft/custom.vmr&index=3 - Delete a rule
+async fn delete_vmr_rule(
+    State(state): State<Arc<ServerState>>,
+    Query(query): Query<VmrRuleQuery>,
+) -> Result<Json<Vec<VmrRule>>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || vmr::delete_rule(&app, &query.file, query.index)).await?.map(Json)
+}
+
+/// Request body for enabling/disabling a VMR file or rule
+#[derive(Deserialize)]
+struct SetVmrEnabledRequest {
+    file: String,
+    /// Rule index within the file; omit to toggle the whole file
+    index: Option<usize>,
+    enabled: bool,
+}
+
+/// PUT /api/vmr-rules/enabled - Enable or disable a VMR file or a single rule (body: { file, index?, enabled })
+async fn set_vmr_enabled(
+    State(state): State<Arc<ServerState>>,
+    Json(request): Json<SetVmrEnabledRequest>,
+) -> Result<(Extension<AuditSummary>, Json<VmrStateFile>), Error> {
+    let target = match request.index {
+        Some(index) => format!("{} rule {}", request.file, index),
+        None => request.file.clone(),
+    };
+    let summary = AuditSummary(vec![format!(
+        "{}: {}",
+        target,
+        if request.enabled { "enabled" } else { "disabled" }
+    )]);
+    let app = state.app_handle.clone();
+    let result = blocking(move || match request.index {
+        Some(index) => vmr::set_rule_enabled(&app, &request.file, index, request.enabled),
+        None => vmr::set_file_enabled(&app, &request.file, request.enabled),
+    })
+    .await?;
+    result.map(|state| (Extension(summary), Json(state)))
+}
+
+/// GET /api/vmr-subscriptions - Remote VMR subscriptions and their status
+async fn list_vmr_subscriptions(
+    State(state): State<Arc<ServerState>>,
+) -> Result<Json<Vec<VmrSubscription>>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || vmr_remote::read_subscriptions(&app)).await.map(Json)
+}
+
+/// GET /api/match?typeCode=B738&callsign=AAL123 - Resolve a model with a match trace
+async fn match_model(
+    State(state): State<Arc<ServerState>>,
+    Query(query): Query<MatchQuery>,
+) -> Json<MatchResult> {
+    let result = matching::get_matcher(&state.app_handle).match_model(&query);
+    state.app_handle.state::<RecentTrafficState>().record(&query, &result);
+    state.app_handle.state::<ModelUsageState>().record(&result);
+    Json(result)
+}
+
+/// GET /api/match/coverage - Coverage report for recently seen traffic
+async fn get_model_coverage(
+    State(state): State<Arc<ServerState>>,
+) -> Result<Json<CoverageReport>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || coverage::build_report(&app, Vec::new())).await.map(Json)
+}
+
+/// POST /api/match/coverage - Coverage report for recently seen traffic plus the
+/// expected traffic in the body ([{ typeCode, callsign?, airline? }, ...])
+async fn post_model_coverage(
+    State(state): State<Arc<ServerState>>,
+    Json(traffic): Json<Vec<MatchQuery>>,
+) -> Result<Json<CoverageReport>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || coverage::build_report(&app, traffic)).await.map(Json)
+}
+
+/// GET /api/match/usage - Usage of the converted FSLTL models (never-used models
+/// to prune, missing liveries worth converting)
+async fn get_model_usage(State(state): State<Arc<ServerState>>) -> Result<Json<ModelUsageReport>, Error> {
+    let app = state.app_handle.clone();
+    Ok(Json(blocking(move || model_usage::build_report(&app)).await??))
+}
+
+/// DELETE /api/match/usage - Forget all model usage counts
+async fn reset_model_usage(State(state): State<Arc<ServerState>>) -> Result<StatusCode, Error> {
+    model_usage::reset(&state.app_handle).map(|()| StatusCode::NO_CONTENT)
+}
+
+/// GET /api/match/similar-types - Effective similar-type fallback table
+async fn get_similar_types(
+    State(state): State<Arc<ServerState>>,
+) -> Result<Json<SimilarTypes>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || similar_types::effective(&app)).await.map(Json)
+}
+
+/// GET /api/weather/:icao - Decoded METAR for a station (cached for a couple of minutes)
+async fn get_weather(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<Localized<Metar>>, Error> {
+    let icao = weather::normalize_icao(&icao).map_err(Error::InvalidInput)?;
+    let metar = weather::get_metar(&state.app_handle, &icao, false)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("No current METAR for {}", icao)))?;
+    let preferences = unit_preferences(&state, &headers).await;
+    Ok(Json(units::localize(metar, preferences, units::metar_display)))
+}
+
+/// GET /api/weather/:icao/taf - Decoded TAF with forecast periods and wind shifts
+async fn get_taf(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+) -> Result<Json<Taf>, Error> {
+    let icao = weather::normalize_icao(&icao).map_err(Error::InvalidInput)?;
+    taf::get_taf(&state.app_handle, &icao, false)
+        .await?
+        .map(Json)
+        .ok_or_else(|| Error::NotFound(format!("No current TAF for {}", icao)))
+}
+
+/// GET /api/notams/:icao - Parsed NOTAMs with the runways and taxiways closed now
+async fn get_notams(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+) -> Result<Json<AirportNotams>, Error> {
+    notams::get_notams(&state.app_handle, &icao, false).await.map(Json)
+}
+
+/// GET /api/charts/:icao - FAA d-TPP charts (airport diagram, approach plates, ...) for an airport
+async fn get_airport_charts(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+) -> Result<Json<AirportCharts>, Error> {
+    charts::get_charts(&state.app_handle, &icao)
+        .await?
+        .map(Json)
+        .ok_or_else(|| Error::NotFound(format!("No charts for {}", icao)))
+}
+
+/// GET /api/charts/:icao/:pdf - Serve a chart PDF (downloaded from the FAA on first use)
+async fn serve_chart(
+    State(state): State<Arc<ServerState>>,
+    Path((icao, pdf)): Path<(String, String)>,
+) -> Result<Response<Body>, Error> {
+    let file_path = charts::get_chart_file(&state.app_handle, &icao, &pdf)
+        .await?
+        .ok_or_else(|| Error::not_found("Chart not found"))?;
+    let file_path = resolve_served_file(&state, app_data_root(&state)?, SymlinkPolicy::Deny, move |root| {
+        root.check(&file_path)
+    })
+    .await?;
+    serve_file(&state.file_cache, &file_path).await
+}
+
+/// Query parameters for METAR history
+#[derive(Deserialize)]
+struct MetarHistoryQuery {
+    /// Unix ms
+    from: u64,
+    to: u64,
+}
+
+/// GET /api/weather/:icao/history?from=&to= - Archived METARs for a time range (Unix ms), oldest first
+async fn get_metar_history(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+    Query(query): Query<MetarHistoryQuery>,
+) -> Result<Json<Vec<Metar>>, Error> {
+    let icao = weather::normalize_icao(&icao).map_err(Error::InvalidInput)?;
+    weather::get_metar_history(&state.app_handle, &icao, query.from, query.to)
+        .await
+        .map(Json)
+}
+
+/// Query parameters for scene weather
+#[derive(Deserialize)]
+struct WeatherSceneQuery {
+    /// Past time (Unix ms) for replays
+    at: Option<u64>,
+}
+
+/// GET /api/weather/:icao/scene?at= - Renderable scene parameters (visibility, fog, clouds, precipitation)
+async fn get_weather_scene(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+    Query(query): Query<WeatherSceneQuery>,
+) -> Result<Json<WeatherScene>, Error> {
+    let icao = weather::normalize_icao(&icao).map_err(Error::InvalidInput)?;
+    weather_scene::get_scene(&state.app_handle, &icao, query.at)
+        .await?
+        .map(Json)
+        .ok_or_else(|| Error::NotFound(format!("No current METAR for {}", icao)))
+}
+
+/// GET /api/weather/:icao/runways - Suggested runway configuration with wind components per runway end
+async fn get_runway_suggestion(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+) -> Result<Json<RunwaySuggestion>, Error> {
+    let icao = weather::normalize_icao(&icao).map_err(Error::InvalidInput)?;
+    runway_suggestion::get_suggestion(&state.app_handle, &icao)
+        .await?
+        .map(Json)
+        .ok_or_else(|| Error::NotFound(format!("No current METAR for {}", icao)))
+}
+
+/// GET /api/weather/:icao/density-altitude - Pressure altitude, density altitude and ISA deviation
+async fn get_density_altitude(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<Localized<DensityAltitude>>, Error> {
+    let icao = weather::normalize_icao(&icao).map_err(Error::InvalidInput)?;
+    let density = density_altitude::get_density_altitude(&state.app_handle, &icao)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("No current METAR with temperature and altimeter for {}", icao)))?;
+    let preferences = unit_preferences(&state, &headers).await;
+    Ok(Json(units::localize(density, preferences, units::density_altitude_display)))
+}
+
+/// Query parameters for winds aloft
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WindsAloftQuery {
+    /// Tower cab height in meters AGL
+    tower_height: Option<f64>,
+    /// Pattern altitude in feet AGL
+    pattern_altitude: Option<f64>,
+}
+
+/// GET /api/weather/:icao/winds-aloft?towerHeight=40&patternAltitude=1000 - GFS wind profile
+/// with winds interpolated at tower height and pattern altitude
+async fn get_winds_aloft(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+    Query(query): Query<WindsAloftQuery>,
+) -> Result<Json<WindsAloft>, Error> {
+    let icao = weather::normalize_icao(&icao).map_err(Error::InvalidInput)?;
+    winds_aloft::get_airport_winds_aloft(&state.app_handle, &icao, query.tower_height, query.pattern_altitude)
+        .await
+        .map(Json)
+}
+
+/// Query parameters for airport search
+#[derive(Deserialize)]
+struct AirportSearchQuery {
+    q: Option<String>,
+    limit: Option<usize>,
+}
+
+/// GET /api/airports/search?q=&limit= - Fuzzy airport search by ICAO, IATA, name or city (with runways)
+async fn search_airports(
+    State(state): State<Arc<ServerState>>,
+    Query(query): Query<AirportSearchQuery>,
+) -> Result<Json<Vec<AirportSearchResult>>, Error> {
+    airport_db::search_airports(&state.app_handle, query.q.unwrap_or_default(), query.limit)
+        .await
+        .map(Json)
+        .map_err(Error::io)
+}
+
+/// Query parameters for content search
+#[derive(Deserialize)]
+struct ContentSearchQuery {
+    q: Option<String>,
+    /// Comma-separated kinds (airport, bookmark, mod, model, aircraft); default: all
+    kinds: Option<String>,
+    limit: Option<usize>,
+}
+
+/// GET /api/search?q=&kinds=&limit= - Fuzzy search across airports, bookmarks, mods, converted models and recent callsigns
+async fn search_content(
+    State(state): State<Arc<ServerState>>,
+    Query(query): Query<ContentSearchQuery>,
+) -> Result<Json<Vec<SearchResult>>, Error> {
+    let kinds = query
+        .kinds
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .filter(|k| !k.trim().is_empty())
+        .map(|k| SearchKind::parse(k).ok_or_else(|| Error::InvalidInput(format!("Unknown kind: {}", k))))
+        .collect::<Result<Vec<_>, _>>()?;
+    let app = state.app_handle.clone();
+    let (q, limit) = (query.q.unwrap_or_default(), query.limit);
+    blocking(move || search::search(&app, &q, &kinds, limit)).await.map(Json)
+}
+
+/// GET /api/airports/:icao - Airport with its runways from the airport database
+async fn get_airport(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+) -> Result<Json<AirportRecord>, Error> {
+    airport_db::get_airport(&state.app_handle, icao.clone())
+        .await
+        .map_err(Error::io)?
+        .map(Json)
+        .ok_or_else(|| Error::NotFound(format!("Unknown airport: {}", icao)))
+}
+
+/// GET /api/airports/:icao/runways - Runway thresholds, headings, lengths, widths and displaced thresholds
+async fn get_airport_runways(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+) -> Result<Json<Vec<Runway>>, Error> {
+    airport_db::get_runways(&state.app_handle, icao.clone())
+        .await
+        .map_err(Error::io)?
+        .map(Json)
+        .ok_or_else(|| Error::NotFound(format!("Unknown airport: {}", icao)))
+}
+
+/// GET /api/airports/:icao/approaches - Extended centerlines, approach cones and glidepaths per runway end
+async fn get_airport_approaches(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+) -> Result<Json<Vec<ApproachPath>>, Error> {
+    approach::get_approaches(&state.app_handle, &icao)
+        .await?
+        .map(Json)
+        .ok_or_else(|| Error::NotFound(format!("Unknown airport: {}", icao)))
+}
+
+/// GET /api/airports/:icao/airspace?refresh= - Class B/C/D airspace overlay (generated on first request;
+/// its GeoJSON is served through /api/overlays)
+async fn get_airspace_overlay(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+    Query(query): Query<OsmLayoutQuery>,
+) -> Result<Json<OverlayInfo>, Error> {
+    airspace::get_airspace_overlay(&state.app_handle, &icao, query.refresh.unwrap_or(false))
+        .await
+        .map(Json)
+}
+
+/// GET /api/airports/:icao/atc - TRACON/FIR/UIR of an airport and the VATSIM controllers online covering it
+async fn get_airport_atc(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+) -> Result<Json<AirportCoverage>, Error> {
+    vatspy::get_coverage(&state.app_handle, &icao).await.map(Json)
+}
+
+/// GET /api/atc/changes - Positions online at the active airport and recent online/offline changes
+async fn get_atc_changes(State(state): State<Arc<ServerState>>) -> Json<AtcWatchStatus> {
+    Json(atc_watch::get_status(&state.app_handle))
+}
+
+/// Query parameters for listing controllers
+#[derive(Deserialize)]
+struct ControllersQuery {
+    /// Airport whose covering positions to list (default: the active airport)
+    icao: Option<String>,
+}
+
+/// GET /api/controllers?icao= - Who is staffing the positions covering an airport (names, ratings, member details)
+async fn list_controllers(
+    State(state): State<Arc<ServerState>>,
+    Query(query): Query<ControllersQuery>,
+) -> Result<Json<Vec<ControllerInfo>>, Error> {
+    controllers::list(&state.app_handle, query.icao.as_deref()).await.map(Json)
+}
+
+/// GET /api/controllers/:id - An online controller by callsign or CID
+async fn get_controller(
+    State(state): State<Arc<ServerState>>,
+    Path(id): Path<String>,
+) -> Result<Json<ControllerInfo>, Error> {
+    controllers::lookup(&state.app_handle, &id).await.map(Json)
+}
+
+/// WebSocket handler relaying positions coming online or going offline at the active airport
+///
+/// ## Message Format
+/// ```json
+/// {"icao":"KBOS","callsign":"BOS_APP","name":"Boston Approach","frequency":"118.250","level":"approach","facility":"A90","online":true,"time":1234567890000}
+/// ```
+async fn atc_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_atc_websocket(socket, state))
+}
+
+/// Handle a controller change WebSocket connection
+async fn handle_atc_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<AtcWatchState>().events.subscribe();
+    relay_broadcast(socket, "atc", events, None).await;
+}
+
+/// GET /api/afv - Transmission activity on the active airport's frequencies
+async fn get_frequency_activity(State(state): State<Arc<ServerState>>) -> Json<FrequencyActivitySnapshot> {
+    Json(afv::get_snapshot(&state.app_handle))
+}
+
+/// WebSocket handler relaying frequency activity (TX indicators) to remote browsers.
+/// The host polls AFV only while clients are connected; the current activity is sent on connect.
+async fn afv_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_afv_websocket(socket, state))
+}
+
+/// Handle a frequency activity WebSocket connection
+async fn handle_afv_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<AfvState>().events.subscribe();
+    let current = afv::get_snapshot(&state.app_handle);
+    relay_broadcast(socket, "afv", events, Some(current)).await;
+}
+
+/// Query parameters for listing audio streams
+#[derive(Deserialize)]
+struct AudioStreamsQuery {
+    /// Only streams for this airport
+    icao: Option<String>,
+}
+
+/// GET /api/audio?icao= - Tower audio streams configured on the host
+async fn list_audio_streams(
+    State(state): State<Arc<ServerState>>,
+    Query(query): Query<AudioStreamsQuery>,
+) -> Json<Vec<AudioStreamInfo>> {
+    Json(audio_relay::list(&state.app_handle, query.icao.as_deref()))
+}
+
+/// GET /api/audio/:id - Relayed tower audio (a live stream; play it in an <audio> element)
+async fn get_audio_stream(
+    State(state): State<Arc<ServerState>>,
+    Path(id): Path<String>,
+) -> Result<Response<Body>, Error> {
+    if !audio_relay::valid_stream_id(&id) {
+        return Err(Error::InvalidInput(format!("Invalid stream id: {}", id)));
     }
+    if !audio_relay::list(&state.app_handle, None).iter().any(|s| s.id == id) {
+        return Err(Error::NotFound(format!("Unknown audio stream: {}", id)));
+    }
+    let (content_type, body) = audio_relay::listen(&state.app_handle, &id)
+        .await
+        .map_err(Error::Upstream)?;
+    Response::builder()
+        .header(header::CONTENT_TYPE, content_type)
+        .header(header::CACHE_CONTROL, "no-store")
+        .body(body)
+        .map_err(|e| Error::Internal(e.to_string()))
+}
+
+/// Query parameters for the OSM ground layout
+#[derive(Deserialize)]
+struct OsmLayoutQuery {
+    /// Refetch from OpenStreetMap instead of using the cache
+    refresh: Option<bool>,
+}
+
+/// GET /api/airports/:icao/layout?refresh= - Taxiways, aprons, gates and buildings from OpenStreetMap (GeoJSON)
+async fn get_osm_layout(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+    Query(query): Query<OsmLayoutQuery>,
+) -> Result<Response<Body>, Error> {
+    let layout = osm_layout::get_layout(&state.app_handle, &icao, query.refresh.unwrap_or(false)).await?;
+    Response::builder()
+        .header(header::CONTENT_TYPE, "application/geo+json")
+        .body(Body::from(layout.to_string()))
+        .map_err(|e| Error::Internal(e.to_string()))
+}
+
+/// Query parameters for sun/moon positions
+#[derive(Deserialize)]
+struct CelestialQuery {
+    lat: Option<f64>,
+    lon: Option<f64>,
+    /// Unix ms (default: simulated clock)
+    at: Option<u64>,
+}
+
+/// GET /api/celestial?lat=&lon=&at= - Sun and moon azimuth/elevation and moon phase
+async fn get_celestial(
+    State(state): State<Arc<ServerState>>,
+    Query(query): Query<CelestialQuery>,
+) -> Result<Json<Ephemeris>, Error> {
+    let (Some(lat), Some(lon)) = (query.lat, query.lon) else {
+        return Err(Error::invalid_input("lat and lon are required"));
+    };
+    ephemeris::get_ephemeris(&state.app_handle, lat, lon, query.at).map(Json)
+}
+
+/// GET /api/celestial/:icao?at= - Sun and moon at an airport
+async fn get_airport_celestial(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+    Query(query): Query<CelestialQuery>,
+) -> Result<Json<Ephemeris>, Error> {
+    let icao = weather::normalize_icao(&icao).map_err(Error::InvalidInput)?;
+    ephemeris::get_airport_ephemeris(&state.app_handle, &icao, query.at)
+        .await
+        .map(Json)
+}
+
+/// Query parameters for overlay listing
+/// GET /api/manifest - Every asset served from disk with its size and hash
+/// (304 if the client's `If-None-Match` is the current manifest hash)
+async fn get_content_manifest(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> Result<Response<Body>, Error> {
+    let app = state.app_handle.clone();
+    let manifest: ContentManifest = blocking(move || content_manifest::manifest(&app)).await?;
+    let etag = format!("\"{}\"", manifest.hash);
+    let unchanged = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|tag| tag.trim() == etag));
+
+    let builder = Response::builder()
+        .header(header::ETAG, &etag)
+        .header(header::CACHE_CONTROL, "no-cache");
+    let resp = if unchanged {
+        builder.status(StatusCode::NOT_MODIFIED).body(Body::empty())
+    } else {
+        let body = serde_json::to_vec(&manifest).map_err(|e| Error::Internal(e.to_string()))?;
+        builder
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+    };
+    resp.map_err(|e| Error::Internal(e.to_string()))
+}
+
+#[derive(Deserialize)]
+struct OverlayQuery {
+    icao: Option<String>,
+}
+
+/// GET /api/overlays?icao=KBOS - List GeoJSON overlays (optionally filtered by airport)
+async fn list_overlays(
+    State(state): State<Arc<ServerState>>,
+    Query(query): Query<OverlayQuery>,
+) -> Result<Json<Vec<OverlayInfo>>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || overlays::list_for_airport(&app, query.icao.as_deref()))
+        .await
+        .map(Json)
+}
+
+/// GET /api/overlays/{id}/{file} - Serve a GeoJSON overlay file
+async fn serve_overlay_file(
+    State(state): State<Arc<ServerState>>,
+    Path(path): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response<Body>, Error> {
+    let (id, file) = path
+        .split_once('/')
+        .ok_or_else(|| Error::not_found("File not found"))?;
+
+    let file_path = overlays::resolve_file(&state.app_handle, id, file)
+        .ok_or_else(|| Error::not_found("File not found"))?;
+    let file_path = resolve_served_file(
+        &state,
+        overlays::file_root(&state.app_handle, id),
+        SymlinkPolicy::WithinAllowed,
+        move |root| root.check(&file_path),
+    )
+    .await?;
+
+    let mut resp = serve_file_compressed(&state, &headers, &file_path).await?;
+    resp.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/geo+json"),
+    );
+    Ok(resp)
+}
+
+/// GET /api/sounds - List alert sound packs in mods/sounds
+async fn list_sound_packs(State(state): State<Arc<ServerState>>) -> Result<Json<Vec<SoundPackInfo>>, Error> {
+    let app = state.app_handle.clone();
+    Ok(Json(blocking(move || sound_packs::list_all(&app)).await?))
+}
+
+/// GET /api/sounds/{pack}/{file} - Serve an alert sound
+async fn serve_sound_file(
+    State(state): State<Arc<ServerState>>,
+    Path(path): Path<String>,
+) -> Result<Response<Body>, Error> {
+    let (id, file) = path
+        .split_once('/')
+        .ok_or_else(|| Error::not_found("File not found"))?;
+
+    let file_path = sound_packs::resolve_file(&state.app_handle, id, file)
+        .ok_or_else(|| Error::not_found("File not found"))?;
+    let file_path = resolve_served_file(
+        &state,
+        sound_packs::sounds_root(&state.app_handle),
+        SymlinkPolicy::WithinAllowed,
+        move |root| root.check(&file_path),
+    )
+    .await?;
+
+    Ok(serve_file(&state.file_cache, &file_path).await?)
+}
+
+/// GET /api/ext - Plugins in mods/plugins and the routes they serve
+async fn list_plugins(State(state): State<Arc<ServerState>>) -> Result<Json<Vec<PluginInfo>>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || plugins::list(&app)).await.map(Json)
+}
+
+/// GET /api/ext/:plugin/*path - A route declared by a plugin manifest (file, folder or JSON)
+async fn serve_plugin_route(
+    State(state): State<Arc<ServerState>>,
+    Path((plugin, path)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<Response<Body>, Error> {
+    let app = state.app_handle.clone();
+    let id = plugin.clone();
+    let manifest = blocking(move || plugins::load(&app, &id))
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("Unknown plugin: {}", plugin)))?;
+    let file = match plugins::match_route(&manifest.routes, &path) {
+        Some(PluginResponse::Json(value)) => return Ok(Json(value).into_response()),
+        Some(PluginResponse::File(file)) => file,
+        None => return Err(Error::not_found("Route not found")),
+    };
+    let root = plugins::plugins_root(&state.app_handle).join(&plugin);
+    let file_path = resolve_served_file(&state, root, SymlinkPolicy::WithinAllowed, move |root| root.resolve(&file)).await?;
+    serve_file_compressed(&state, &headers, &file_path).await
+}
+
+/// Largest airport pack accepted by POST /api/airport-packs
+const AIRPORT_PACK_UPLOAD_LIMIT: usize = 256 * 1024 * 1024;
+
+/// GET /api/airport-packs/:icao?name=&author=&version=&description= - Download an airport's pack (.tar.zst)
+async fn export_airport_pack(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+    Query(options): Query<AirportPackExportOptions>,
+) -> Result<impl IntoResponse, Error> {
+    let app = state.app_handle.clone();
+    let (file_name, bytes) = blocking(move || airport_packs::archive(&app, &icao, options)).await??;
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zstd".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", file_name)),
+        ],
+        bytes,
+    ))
+}
+
+/// POST /api/airport-packs - Install an airport pack (.tar.zst body)
+async fn install_airport_pack(
+    State(state): State<Arc<ServerState>>,
+    body: Bytes,
+) -> Result<(Extension<AuditSummary>, Json<AirportPackInstallResult>), Error> {
+    let app = state.app_handle.clone();
+    blocking(move || airport_packs::install_archive(&app, &body))
+        .await?
+        .map(|result| {
+            let summary = format!("{}: installed airport pack ({})", result.manifest.icao, result.installed.join(", "));
+            (Extension(AuditSummary(vec![summary])), Json(result))
+        })
+}
+
+/// GET /api/videomaps/:icao - Video maps imported from CRC for an airport
+async fn get_video_maps(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+) -> Result<Json<VideoMapIndex>, Error> {
+    let app = state.app_handle.clone();
+    let index = {
+        let icao = icao.clone();
+        blocking(move || videomaps::get_index(&app, &icao)).await?
+    };
+    index?
+        .map(Json)
+        .ok_or_else(|| Error::NotFound(format!("No video maps imported for {}", icao)))
 }
 
-/// Extract an attribute value from an XML element string
-fn extract_attr(line: &str, attr: &str) -> Option<String> {
-    let pattern = format!("{}=\"", attr);
-    let start = line.find(&pattern)? + pattern.len();
-    let end = line[start..].find('"')? + start;
-    Some(line[start..end].to_string())
+/// POST /api/videomaps/:icao/import - Import an airport's video maps from the host's CRC install
+async fn import_video_maps(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+) -> Result<(Extension<AuditSummary>, Json<VideoMapIndex>), Error> {
+    let app = state.app_handle.clone();
+    blocking(move || videomaps::import_from_crc(&app, &icao, None))
+        .await?
+        .map(|index| {
+            let summary = format!(
+                "{}: imported {} video maps from {}",
+                index.icao,
+                index.maps.len(),
+                index.facility_id
+            );
+            (Extension(AuditSummary(vec![summary])), Json(index))
+        })
+}
+
+/// GET /api/videomaps/:icao/:id - Serve an imported video map (GeoJSON)
+async fn serve_video_map(
+    State(state): State<Arc<ServerState>>,
+    Path((icao, id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Result<Response<Body>, Error> {
+    let file_path = videomaps::resolve_map(&state.app_handle, &icao, &id)?
+        .ok_or_else(|| Error::not_found("Video map not found"))?;
+    let file_path = resolve_served_file(&state, app_data_root(&state)?, SymlinkPolicy::Deny, move |root| {
+        root.check(&file_path)
+    })
+    .await?;
+
+    let mut resp = serve_file_compressed(&state, &headers, &file_path).await?;
+    resp.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/geo+json"),
+    );
+    Ok(resp)
 }
 
 /// Query parameters for proxy endpoint
@@ -747,8 +2335,9 @@ const REALTRAFFIC_API_URL: &str = "https://rtwa.flyrealtraffic.com/v5";
 /// POST /api/realtraffic/auth - Proxy RealTraffic authentication
 async fn realtraffic_auth(
     Json(request): Json<RealTrafficAuthRequest>,
-) -> Result<Response<Body>, (StatusCode, String)> {
-    let client = reqwest::Client::new();
+) -> Result<Response<Body>, Error> {
+    secrets::add("realtraffic", &request.license_key);
+    let client = http_client::client();
 
     // RealTraffic API expects form data, not JSON
     let mut form_data = std::collections::HashMap::new();
@@ -761,12 +2350,12 @@ async fn realtraffic_auth(
         .form(&form_data)
         .send()
         .await
-        .map_err(|e| (StatusCode::BAD_GATEWAY, format!("RealTraffic auth request failed: {}", e)))?;
+        .map_err(|e| Error::Upstream(format!("RealTraffic auth request failed: {}", e)))?;
 
     let body = response
         .bytes()
         .await
-        .map_err(|e| (StatusCode::BAD_GATEWAY, format!("Failed to read RealTraffic response: {}", e)))?;
+        .map_err(|e| Error::Upstream(format!("Failed to read RealTraffic response: {}", e)))?;
 
     let mut resp = Response::builder()
         .status(StatusCode::OK)
@@ -784,8 +2373,9 @@ async fn realtraffic_auth(
 /// POST /api/realtraffic/traffic - Proxy RealTraffic traffic data
 async fn realtraffic_traffic(
     Json(request): Json<RealTrafficTrafficRequest>,
-) -> Result<Response<Body>, (StatusCode, String)> {
-    let client = reqwest::Client::new();
+) -> Result<Response<Body>, Error> {
+    secrets::add("realtraffic", &request.guid);
+    let client = http_client::client();
 
     // RealTraffic API expects form data, not JSON
     // Field names: GUID (uppercase), querytype, top/bottom/left/right for bbox
@@ -809,12 +2399,12 @@ async fn realtraffic_traffic(
         .form(&form_data)
         .send()
         .await
-        .map_err(|e| (StatusCode::BAD_GATEWAY, format!("RealTraffic traffic request failed: {}", e)))?;
+        .map_err(|e| Error::Upstream(format!("RealTraffic traffic request failed: {}", e)))?;
 
     let body = response
         .bytes()
         .await
-        .map_err(|e| (StatusCode::BAD_GATEWAY, format!("Failed to read RealTraffic response: {}", e)))?;
+        .map_err(|e| Error::Upstream(format!("Failed to read RealTraffic response: {}", e)))?;
 
     let mut resp = Response::builder()
         .status(StatusCode::OK)
@@ -843,8 +2433,9 @@ struct RealTrafficParkedRequest {
 /// POST /api/realtraffic/parked-traffic - Proxy RealTraffic parked aircraft data
 async fn realtraffic_parked_traffic(
     Json(request): Json<RealTrafficParkedRequest>,
-) -> Result<Response<Body>, (StatusCode, String)> {
-    let client = reqwest::Client::new();
+) -> Result<Response<Body>, Error> {
+    secrets::add("realtraffic", &request.guid);
+    let client = http_client::client();
 
     // RealTraffic API expects form data, not JSON
     // Field names: GUID (uppercase), querytype=parkedtraffic, top/bottom/left/right for bbox
@@ -863,12 +2454,12 @@ async fn realtraffic_parked_traffic(
         .form(&form_data)
         .send()
         .await
-        .map_err(|e| (StatusCode::BAD_GATEWAY, format!("RealTraffic parked traffic request failed: {}", e)))?;
+        .map_err(|e| Error::Upstream(format!("RealTraffic parked traffic request failed: {}", e)))?;
 
     let body = response
         .bytes()
         .await
-        .map_err(|e| (StatusCode::BAD_GATEWAY, format!("Failed to read RealTraffic response: {}", e)))?;
+        .map_err(|e| Error::Upstream(format!("Failed to read RealTraffic response: {}", e)))?;
 
     let mut resp = Response::builder()
         .status(StatusCode::OK)
@@ -893,8 +2484,9 @@ struct RealTrafficDeauthRequest {
 /// POST /api/realtraffic/deauth - Proxy RealTraffic deauthentication
 async fn realtraffic_deauth(
     Json(request): Json<RealTrafficDeauthRequest>,
-) -> Result<Response<Body>, (StatusCode, String)> {
-    let client = reqwest::Client::new();
+) -> Result<Response<Body>, Error> {
+    secrets::add("realtraffic", &request.guid);
+    let client = http_client::client();
 
     // RealTraffic API expects form data, not JSON
     let mut form_data = std::collections::HashMap::new();
@@ -906,12 +2498,12 @@ async fn realtraffic_deauth(
         .form(&form_data)
         .send()
         .await
-        .map_err(|e| (StatusCode::BAD_GATEWAY, format!("RealTraffic deauth request failed: {}", e)))?;
+        .map_err(|e| Error::Upstream(format!("RealTraffic deauth request failed: {}", e)))?;
 
     let body = response
         .bytes()
         .await
-        .map_err(|e| (StatusCode::BAD_GATEWAY, format!("Failed to read RealTraffic response: {}", e)))?;
+        .map_err(|e| Error::Upstream(format!("Failed to read RealTraffic response: {}", e)))?;
 
     let mut resp = Response::builder()
         .status(StatusCode::OK)
@@ -929,7 +2521,7 @@ async fn realtraffic_deauth(
 /// GET /api/proxy?url=... - CORS proxy for external APIs
 async fn proxy_request(
     Query(query): Query<ProxyQuery>,
-) -> Result<Response<Body>, (StatusCode, String)> {
+) -> Result<Response<Body>, Error> {
     // Only allow specific trusted domains
     let allowed_domains = [
         "data.vatsim.net",
@@ -940,14 +2532,10 @@ async fn proxy_request(
     let url_str = &query.url;
 
     // Parse the URL properly to extract the host
-    let parsed_url = Url::parse(url_str).map_err(|e| {
-        (StatusCode::BAD_REQUEST, format!("Invalid URL: {}", e))
-    })?;
+    let parsed_url = Url::parse(url_str).map_err(|e| Error::InvalidInput(format!("Invalid URL: {}", e)))?;
 
     // Get the host from the parsed URL
-    let host = parsed_url.host_str().ok_or_else(|| {
-        (StatusCode::BAD_REQUEST, "URL has no host".to_string())
-    })?;
+    let host = parsed_url.host_str().ok_or_else(|| Error::invalid_input("URL has no host"))?;
 
     // Check if the host matches any allowed domain (exact match or subdomain)
     let is_allowed = allowed_domains.iter().any(|domain| {
@@ -955,19 +2543,16 @@ async fn proxy_request(
     });
 
     if !is_allowed {
-        return Err((
-            StatusCode::FORBIDDEN,
-            format!("Domain '{}' not allowed. Allowed: {:?}", host, allowed_domains),
-        ));
+        return Err(Error::Forbidden(format!("Domain '{}' not allowed. Allowed: {:?}", host, allowed_domains)));
     }
 
     // Make the request
-    let client = reqwest::Client::new();
+    let client = http_client::client();
     let response = client
         .get(url_str)
         .send()
         .await
-        .map_err(|e| (StatusCode::BAD_GATEWAY, format!("Proxy request failed: {}", e)))?;
+        .map_err(|e| Error::Upstream(format!("Proxy request failed: {}", e)))?;
 
     let status = response.status();
     let content_type = response
@@ -980,7 +2565,7 @@ async fn proxy_request(
     let body = response
         .bytes()
         .await
-        .map_err(|e| (StatusCode::BAD_GATEWAY, format!("Failed to read response: {}", e)))?;
+        .map_err(|e| Error::Upstream(format!("Failed to read response: {}", e)))?;
 
     let mut resp = Response::builder()
         .status(status)
@@ -1011,6 +2596,16 @@ async fn proxy_request(
 /// [{"callsign":"DAL123","lat":42.0,"lon":-71.0,"altitude":10000,"heading":90,"typeCode":"B738","timestamp":1234567890}]
 /// ```
 ///
+/// ## Decluttering
+/// Clients can limit the aircraft they receive with a declutter profile, as
+/// query parameters or `{"type":"declutter",...}` messages (see declutter.rs).
+///
+/// ## Binary / Delta Updates
+/// `?encoding=msgpack` sends MessagePack binary frames and `?delta=true` sends
+/// only changed fields per callsign (see ws_encoding.rs). Browsers that
+/// negotiated their capabilities get their configured format and update rate
+/// (see client_capabilities.rs).
+///
 /// ## Data Flow
 /// 1. vnas.rs connects to vNAS SignalR hub (requires OAuth credentials)
 /// 2. vNAS sends TowerCabAircraft updates via SignalR
@@ -1022,61 +2617,1198 @@ async fn proxy_request(
 /// Until those are available, this WebSocket will not receive any updates.
 async fn vnas_websocket_handler(
     ws: WebSocketUpgrade,
+    Query(profile): Query<DeclutterProfile>,
+    Query(format): Query<WireFormat>,
+    headers: HeaderMap,
     State(state): State<Arc<ServerState>>,
 ) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_vnas_websocket(socket, state))
+    // A negotiated configuration can only make the format smaller
+    let config = client_capabilities::config_for(&headers);
+    let (format, interval) = match &config {
+        Some(config) => (
+            WireFormat {
+                encoding: if config.wire_format.encoding == Encoding::Msgpack {
+                    Encoding::Msgpack
+                } else {
+                    format.encoding
+                },
+                delta: format.delta || config.wire_format.delta,
+            },
+            Duration::from_millis(config.update_interval_ms),
+        ),
+        None => (format, Duration::ZERO),
+    };
+    ws.on_upgrade(move |socket| handle_vnas_websocket(socket, state, profile, format, interval, config))
 }
 
 /// Handle a vNAS WebSocket connection
-async fn handle_vnas_websocket(socket: WebSocket, state: Arc<ServerState>) {
+async fn handle_vnas_websocket(
+    socket: WebSocket,
+    state: Arc<ServerState>,
+    profile: DeclutterProfile,
+    format: WireFormat,
+    interval: Duration,
+    config: Option<ClientConfig>,
+) {
     let (mut sender, mut receiver) = socket.split();
+    let _stream = debug_stats::stream_connected("vnas");
 
     // Subscribe to vNAS broadcast channel
     let mut vnas_rx = state.vnas_tx.subscribe();
+    let (profile_tx, profile_rx) = watch::channel(profile);
+    let app_handle = state.app_handle.clone();
 
-    println!("[vNAS WS] Client connected");
+    info!("[vNAS WS] Client connected");
 
     // Spawn a task to forward vNAS updates to the WebSocket
     let send_task = tokio::spawn(async move {
-        while let Ok(aircraft) = vnas_rx.recv().await {
+        let mut reference = ReferenceCache::default();
+        let mut deltas = format.delta.then(DeltaEncoder::default);
+        // Latest update per callsign while waiting out the client's update interval
+        let mut pending: HashMap<String, VnasAircraftBroadcast> = HashMap::new();
+        let mut last_sent: Option<Instant> = None;
+        loop {
+            // Slow clients skip the batches they missed instead of being dropped
+            let aircraft = match vnas_rx.recv().await {
+                Ok(aircraft) => aircraft,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    debug_stats::record_lag("vnas", skipped);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            let aircraft = if interval.is_zero() {
+                aircraft
+            } else {
+                pending.extend(aircraft.into_iter().map(|a| (a.callsign.clone(), a)));
+                if last_sent.is_some_and(|sent| sent.elapsed() < interval) {
+                    continue;
+                }
+                last_sent = Some(Instant::now());
+                std::mem::take(&mut pending).into_values().collect()
+            };
+            let profile = profile_rx.borrow().clone();
+            let mut aircraft = if profile.is_empty() {
+                aircraft
+            } else {
+                profile.vnas(&aircraft, reference.get(&app_handle).await)
+            };
+            lod::apply_vnas(&app_handle, config.as_ref(), &mut aircraft);
             // Serialize and send to WebSocket
-            match serde_json::to_string(&aircraft) {
-                Ok(json) => {
-                    if sender.send(Message::Text(json)).await.is_err() {
+            let message = match &mut deltas {
+                Some(encoder) => format.message(&encoder.encode(&aircraft)),
+                None => format.message(&aircraft),
+            };
+            match message {
+                Ok(message) => {
+                    if sender.send(message).await.is_err() {
                         break; // Client disconnected
                     }
-                }
-                Err(e) => {
-                    eprintln!("[vNAS WS] Serialization error: {}", e);
-                }
+                }
+                Err(e) => {
+                    warn!("[vNAS WS] Serialization error: {}", e);
+                }
+            }
+        }
+    });
+
+    // Handle incoming messages (mostly for keepalive/ping-pong)
+    while let Some(msg) = receiver.next().await {
+        match msg {
+            Ok(Message::Ping(data)) => {
+                // Ping/pong handled automatically by axum
+                debug!("[vNAS WS] Received ping: {:?}", data);
+            }
+            Ok(Message::Close(_)) => {
+                info!("[vNAS WS] Client requested close");
+                break;
+            }
+            Ok(Message::Text(text)) => {
+                if let Some(profile) = declutter::parse_message(&text) {
+                    let _ = profile_tx.send(profile);
+                }
+            }
+            Ok(_) => {
+                // Ignore other message types
+            }
+            Err(e) => {
+                warn!("[vNAS WS] Error: {}", e);
+                break;
+            }
+        }
+    }
+
+    // Clean up
+    send_task.abort();
+    info!("[vNAS WS] Client disconnected");
+}
+
+/// Relay a broadcast channel to a WebSocket client as JSON text messages until
+/// it disconnects, starting with `current` when the stream has a snapshot.
+async fn relay_broadcast<T: Clone + Serialize + Send + 'static>(
+    socket: WebSocket,
+    stream: &'static str,
+    mut events: broadcast::Receiver<T>,
+    current: Option<T>,
+) {
+    let (mut sender, mut receiver) = socket.split();
+    let _stream = debug_stats::stream_connected(stream);
+
+    let send_task = tokio::spawn(async move {
+        let mut next = current;
+        loop {
+            let event = match next.take() {
+                Some(event) => event,
+                None => match events.recv().await {
+                    Ok(event) => event,
+                    // Slow client missed some events; keep going
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        debug_stats::record_lag(stream, skipped);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+            };
+            let Ok(json) = serde_json::to_string(&event) else {
+                continue;
+            };
+            if sender.send(Message::Text(json)).await.is_err() {
+                break; // Client disconnected
+            }
+        }
+    });
+
+    // Keep connection alive until client disconnects
+    while let Some(msg) = receiver.next().await {
+        if matches!(msg, Ok(Message::Close(_)) | Err(_)) {
+            break;
+        }
+    }
+
+    send_task.abort();
+}
+
+// =============================================================================
+// Lightning
+// =============================================================================
+
+/// GET /api/lightning - Recent strikes and the "lightning within 5 nm" alert for the active airport
+async fn get_lightning_status(State(state): State<Arc<ServerState>>) -> Json<LightningStatus> {
+    Json(lightning::get_status(&state.app_handle))
+}
+
+/// GET /api/federation/status - Connection status to the data host this instance mirrors
+async fn get_federation_status(State(state): State<Arc<ServerState>>) -> Json<FederationStatus> {
+    Json(federation::get_status(&state.app_handle))
+}
+
+/// WebSocket handler relaying lightning strikes and alert changes to remote browsers
+///
+/// ## Message Format
+/// ```json
+/// {"type":"strike","time":1234567890000,"latitude":42.0,"longitude":-71.0,"distanceNm":4.2,"bearing":270}
+/// {"type":"alert","active":true,"lastStrikeAt":1234567890000,"clearsAt":1234568790000}
+/// ```
+async fn lightning_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_lightning_websocket(socket, state))
+}
+
+/// Handle a lightning WebSocket connection
+async fn handle_lightning_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<LightningState>().events.subscribe();
+    relay_broadcast(socket, "lightning", events, None).await;
+}
+
+/// GET /api/scripts - Loaded scripts (mods/scripts), the events they handle and their errors
+async fn list_scripts(State(state): State<Arc<ServerState>>) -> Json<Vec<ScriptInfo>> {
+    Json(scripts::list(&state.app_handle))
+}
+
+/// WebSocket handler relaying custom events emitted by scripts to remote browsers
+///
+/// ## Message Format
+/// ```json
+/// {"script":"gate-board.rhai","name":"gate-board","data":{...},"time":1234567890000}
+/// ```
+async fn scripts_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_scripts_websocket(socket, state))
+}
+
+/// Handle a scripts WebSocket connection
+async fn handle_scripts_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<ScriptState>().events.subscribe();
+    relay_broadcast(socket, "scripts", events, None).await;
+}
+
+// =============================================================================
+// Traffic and Gates
+// =============================================================================
+
+/// GET /api/strips/:icao - Departure and arrival strips from VATSIM flight plans and prefiles
+async fn get_strips(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+) -> Result<Json<StripBay>, Error> {
+    strips::get_strips(&state.app_handle, &icao).await.map(Json)
+}
+
+/// GET /api/traffic - Backend traffic picture around the active airport
+async fn get_traffic(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+) -> Json<Localized<TrafficSnapshot, BTreeMap<String, units::Display>>> {
+    let preferences = unit_preferences(&state, &headers).await;
+    let mut snapshot = traffic::get_snapshot(&state.app_handle);
+    lod::apply(&state.app_handle, client_capabilities::config_for(&headers).as_ref(), &mut snapshot);
+    Json(units::localize(snapshot, preferences, units::traffic_display))
+}
+
+/// GET /api/lod - Model detail (full, generic, billboard) of each aircraft for the requesting client
+async fn get_lod_policy(State(state): State<Arc<ServerState>>, headers: HeaderMap) -> Json<LodPolicy> {
+    Json(lod::policy(&state.app_handle, client_capabilities::config_for(&headers).as_ref()))
+}
+
+/// GET /api/traffic.geojson - Traffic around the active airport as a GeoJSON
+/// FeatureCollection, for GIS tools and mapping libraries (poll to refresh)
+async fn get_traffic_geojson(State(state): State<Arc<ServerState>>) -> Result<Response<Body>, Error> {
+    let geojson = traffic::to_geojson(&traffic::get_snapshot(&state.app_handle));
+    Response::builder()
+        .header(header::CONTENT_TYPE, "application/geo+json")
+        .header(header::CACHE_CONTROL, "no-store")
+        .body(Body::from(geojson.to_string()))
+        .map_err(|e| Error::Internal(e.to_string()))
+}
+
+/// GET /api/aircraft/:callsign/trail - Recent positions of an aircraft, oldest first
+async fn get_aircraft_trail(
+    State(state): State<Arc<ServerState>>,
+    Path(callsign): Path<String>,
+) -> Json<AircraftTrail> {
+    Json(trails::get_trail(&state.app_handle, &callsign))
+}
+
+/// Query parameters for aircraft photos
+#[derive(Deserialize)]
+struct AircraftPhotoQuery {
+    /// Registration to look up instead of resolving the callsign
+    registration: Option<String>,
+}
+
+/// GET /api/aircraft/:callsign/photo?registration= - Photo of the airframe for a spotting card
+async fn get_aircraft_photo(
+    State(state): State<Arc<ServerState>>,
+    Path(callsign): Path<String>,
+    Query(query): Query<AircraftPhotoQuery>,
+) -> Result<Json<AircraftPhoto>, Error> {
+    aircraft_photos::get_photo(&state.app_handle, &callsign, query.registration.as_deref())
+        .await?
+        .map(Json)
+        .ok_or_else(|| Error::NotFound(format!("No photo found for {}", callsign)))
+}
+
+/// GET /api/aircraft-types/:code - Wake category, approach speed and dimensions of an aircraft type
+async fn get_aircraft_type(
+    State(state): State<Arc<ServerState>>,
+    Path(code): Path<String>,
+) -> Result<Json<AircraftType>, Error> {
+    aircraft_types::get(&state.app_handle, &code)
+        .map(Json)
+        .ok_or_else(|| Error::NotFound(format!("Unknown aircraft type: {}", code)))
+}
+
+/// GET /api/airlines - Every airline with name, telephony and country
+async fn list_airlines(State(state): State<Arc<ServerState>>) -> Json<Vec<Airline>> {
+    Json(airlines::list(&state.app_handle))
+}
+
+/// GET /api/airlines/:code - Name, telephony and country of an airline
+async fn get_airline(
+    State(state): State<Arc<ServerState>>,
+    Path(code): Path<String>,
+) -> Result<Json<Airline>, Error> {
+    airlines::get(&state.app_handle, &code)
+        .map(Json)
+        .ok_or_else(|| Error::NotFound(format!("Unknown airline: {}", code)))
+}
+
+/// GET /api/callsigns/:callsign - Airline, flight number and spoken form of a callsign
+async fn get_callsign_info(
+    State(state): State<Arc<ServerState>>,
+    Path(callsign): Path<String>,
+) -> Json<DecodedCallsign> {
+    Json(airlines::decode_callsign(&state.app_handle, &callsign))
+}
+
+/// GET /api/airports/:icao/gates - Gate positions, with the aircraft occupying them at the active airport
+async fn get_airport_gates(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+) -> Result<Json<Vec<Gate>>, Error> {
+    gates::get_gates(&state.app_handle, &icao).await.map(Json)
+}
+
+/// GET /api/airports/:icao/gates/suggestions - Suggested gates for arrivals to the active airport
+async fn get_gate_suggestions(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+) -> Result<Json<Vec<GateSuggestion>>, Error> {
+    gate_suggestions::get_suggestions(&state.app_handle, &icao).map(Json)
+}
+
+/// GET /api/airports/:icao/taxi-routes - Predicted taxi routes for aircraft on the ground at the active airport
+async fn get_taxi_routes(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+) -> Result<Json<Vec<TaxiRoute>>, Error> {
+    taxi_routes::get_routes(&state.app_handle, &icao).await.map(Json)
+}
+
+/// GET /api/airports/:icao/landing-predictions - Predicted touchdown points and turn-offs for arrivals on short final
+async fn get_landing_predictions(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+) -> Result<Json<Vec<LandingPrediction>>, Error> {
+    touchdown::get_predictions(&state.app_handle, &icao).await.map(Json)
+}
+
+/// GET /api/airports/:icao/view-preset - Detected runway configuration and the bookmark tagged for it
+async fn get_view_preset(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+) -> Result<Json<Option<ViewPresetSuggestion>>, Error> {
+    view_presets::get_suggestion(&state.app_handle, &icao).map(Json)
+}
+
+/// GET /api/atis/comparison - VATSIM ATIS runways against the real-world D-ATIS at the active airport
+async fn get_atis_comparison(State(state): State<Arc<ServerState>>) -> Json<Option<AtisComparison>> {
+    Json(datis::get_comparison(&state.app_handle))
+}
+
+/// WebSocket handler relaying gate occupancy changes to remote browsers
+///
+/// ## Message Format
+/// ```json
+/// {"icao":"KBOS","gate":"B5","occupiedBy":"DAL123","vacatedBy":null,"time":1234567890000}
+/// ```
+async fn gates_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_gates_websocket(socket, state))
+}
+
+/// Handle a gate occupancy WebSocket connection
+async fn handle_gates_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<GateState>().events.subscribe();
+    relay_broadcast(socket, "gates", events, None).await;
+}
+
+// =============================================================================
+// Simulated Clock
+// =============================================================================
+
+/// GET /api/clock - Simulated clock shared by all clients
+async fn get_sim_clock(State(state): State<Arc<ServerState>>) -> Json<SimClock> {
+    Json(clock::get_clock(&state.app_handle))
+}
+
+/// PUT /api/clock - Set the simulated clock (real time, offset from UTC or fixed time)
+async fn set_sim_clock(
+    State(state): State<Arc<ServerState>>,
+    Json(settings): Json<GlobalClockSettings>,
+) -> Result<Json<SimClock>, Error> {
+    clock::set_clock(&state.app_handle, settings).map(Json)
+}
+
+/// WebSocket handler relaying simulated clock changes to remote browsers.
+/// The current clock is sent on connect.
+async fn clock_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_clock_websocket(socket, state))
+}
+
+/// Handle a clock WebSocket connection
+async fn handle_clock_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<ClockState>().events.subscribe();
+    let current = clock::get_clock(&state.app_handle);
+    relay_broadcast(socket, "clock", events, Some(current)).await;
+}
+
+// =============================================================================
+// Datablock Layout
+// =============================================================================
+
+/// GET /api/datablock - Datablock layout shared by all displays
+async fn get_datablock_layout(State(state): State<Arc<ServerState>>) -> Json<DatablockLayout> {
+    Json(datablocks::get_layout(&state.app_handle))
+}
+
+/// PUT /api/datablock - Set the datablock layout for all displays
+async fn set_datablock_layout(
+    State(state): State<Arc<ServerState>>,
+    Json(layout): Json<DatablockLayout>,
+) -> Result<Json<DatablockLayout>, Error> {
+    datablocks::set_layout(&state.app_handle, layout).map(Json)
+}
+
+/// WebSocket handler relaying datablock layout changes to remote browsers.
+/// The current layout is sent on connect.
+async fn datablock_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_datablock_websocket(socket, state))
+}
+
+/// Handle a datablock layout WebSocket connection
+async fn handle_datablock_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<DatablockState>().events.subscribe();
+    let current = datablocks::get_layout(&state.app_handle);
+    relay_broadcast(socket, "datablock", events, Some(current)).await;
+}
+
+// =============================================================================
+// Replay
+// =============================================================================
+
+/// Largest replay export accepted over HTTP (hours of busy-airport traffic)
+const REPLAY_UPLOAD_LIMIT: usize = 256 * 1024 * 1024;
+
+/// GET /api/replay - Replay status
+async fn get_replay_status(State(state): State<Arc<ServerState>>) -> Json<ReplayStatus> {
+    Json(replay::get_status(&state.app_handle))
+}
+
+/// GET /api/replay/recording - The loaded replay, for displays that play it in sync with the host
+async fn get_replay_recording(State(state): State<Arc<ServerState>>) -> Result<Json<ReplayFile>, Error> {
+    replay::loaded_file(&state.app_handle)
+        .map(Json)
+        .ok_or_else(|| Error::not_found("No replay loaded"))
+}
+
+/// POST /api/replay - Load a replay export and stream it to all clients
+async fn load_replay(
+    State(state): State<Arc<ServerState>>,
+    Json(file): Json<ReplayFile>,
+) -> Result<Json<ReplayStatus>, Error> {
+    replay::load(&state.app_handle, file).map(Json)
+}
+
+/// POST /api/replay/control - Play, pause, seek or change the speed of the loaded replay
+async fn control_replay(
+    State(state): State<Arc<ServerState>>,
+    Json(control): Json<ReplayControl>,
+) -> Result<Json<ReplayStatus>, Error> {
+    replay::control(&state.app_handle, control).map(Json)
+}
+
+/// Query parameters for instant replay
+#[derive(Deserialize)]
+struct InstantReplayQuery {
+    /// Minutes to replay (default: 2)
+    minutes: Option<u32>,
+}
+
+/// POST /api/replay/instant?minutes= - Replay the last minutes of traffic to all clients, then return to live
+async fn start_instant_replay(
+    State(state): State<Arc<ServerState>>,
+    Query(query): Query<InstantReplayQuery>,
+) -> Result<Json<ReplayStatus>, Error> {
+    replay::instant_replay(&state.app_handle, query.minutes).map(Json)
+}
+
+/// Query parameters for session export
+#[derive(Deserialize)]
+struct SessionExportQuery {
+    /// "replay" (loaded replay) or "buffer" (instant replay buffer)
+    source: String,
+    /// "kml", "geojson" or "csv"
+    format: String,
+}
+
+/// GET /api/replay/export?source=&format= - Download recorded tracks as KML, GeoJSON or CSV
+async fn export_session(
+    State(state): State<Arc<ServerState>>,
+    Query(query): Query<SessionExportQuery>,
+) -> Result<impl IntoResponse, Error> {
+    let export = session_export::export(&state.app_handle, &query.source, &query.format)?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, export.content_type.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", export.file_name),
+            ),
+        ],
+        export.content,
+    ))
+}
+
+/// Query parameters for sharing a recorded session
+#[derive(Deserialize)]
+struct ShareReplayQuery {
+    /// "replay" or "buffer" (default: the loaded replay, otherwise the buffer)
+    source: Option<String>,
+    #[serde(flatten)]
+    options: ShareOptions,
+}
+
+/// A shared replay download (.tcreplay)
+fn tcreplay_response(file_name: String, bytes: Vec<u8>) -> impl IntoResponse {
+    (
+        [
+            (header::CONTENT_TYPE, "application/zstd".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", file_name)),
+        ],
+        bytes,
+    )
+}
+
+/// GET /api/replay/share?source=&title=&description= - Download a recorded session as a shared replay (.tcreplay)
+async fn share_replay(
+    State(state): State<Arc<ServerState>>,
+    Query(query): Query<ShareReplayQuery>,
+) -> Result<impl IntoResponse, Error> {
+    let app = state.app_handle.clone();
+    let (file_name, bytes) =
+        blocking(move || replay_library::share(&app, query.source.as_deref(), &query.options)).await??;
+    Ok(tcreplay_response(file_name, bytes))
+}
+
+/// GET /api/replays - Shared replays in the host's library
+async fn list_shared_replays(State(state): State<Arc<ServerState>>) -> Result<Json<Vec<SharedReplay>>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || replay_library::list(&app)).await?.map(Json)
+}
+
+/// POST /api/replays - Add a shared replay (.tcreplay or JSON replay export body) to the host's library
+async fn import_shared_replay(
+    State(state): State<Arc<ServerState>>,
+    body: Bytes,
+) -> Result<(Extension<AuditSummary>, Json<SharedReplay>), Error> {
+    let app = state.app_handle.clone();
+    let replay = blocking(move || replay_library::import(&app, &body)).await??;
+    let summary = format!("Shared replay \"{}\" added as {}", replay.manifest.title, replay.name);
+    Ok((Extension(AuditSummary(vec![summary])), Json(replay)))
+}
+
+/// GET /api/replays/:name - Download a shared replay (.tcreplay)
+async fn download_shared_replay(
+    State(state): State<Arc<ServerState>>,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, Error> {
+    let app = state.app_handle.clone();
+    let (file_name, bytes) = blocking(move || replay_library::read(&app, &name)).await??;
+    Ok(tcreplay_response(file_name, bytes))
+}
+
+/// POST /api/replays/:name/load - Load a shared replay and stream it to all clients
+async fn load_shared_replay(
+    State(state): State<Arc<ServerState>>,
+    Path(name): Path<String>,
+) -> Result<Json<ReplayStatus>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || replay_library::load(&app, &name)).await?.map(Json)
+}
+
+/// DELETE /api/replays/:name - Remove a shared replay from the host's library
+async fn delete_shared_replay(
+    State(state): State<Arc<ServerState>>,
+    Path(name): Path<String>,
+) -> Result<(Extension<AuditSummary>, StatusCode), Error> {
+    replay_library::delete(&state.app_handle, &name)?;
+    let summary = format!("Shared replay {} deleted", name);
+    Ok((Extension(AuditSummary(vec![summary])), StatusCode::NO_CONTENT))
+}
+
+/// Query parameters for session statistics
+#[derive(Deserialize)]
+struct SessionStatsQuery {
+    /// "replay" or "buffer" (default: the loaded replay, otherwise the buffer)
+    source: Option<String>,
+    /// "json" (default) or "csv" (movements, as a download)
+    format: Option<String>,
+}
+
+/// GET /api/stats/session?source=&format= - Movements per runway, peak traffic, final spacing and busiest hours
+async fn get_session_stats(
+    State(state): State<Arc<ServerState>>,
+    Query(query): Query<SessionStatsQuery>,
+    headers: HeaderMap,
+) -> Result<Response<Body>, Error> {
+    let stats = session_stats::get_stats(&state.app_handle, query.source.as_deref()).await?;
+    match query.format.as_deref() {
+        None | Some("json") => {
+            let preferences = unit_preferences(&state, &headers).await;
+            Ok(Json(units::localize(stats, preferences, units::session_stats_display)).into_response())
+        }
+        Some("csv") => {
+            let file_name = format!("{}-movements.csv", stats.icao.as_deref().unwrap_or("session"));
+            Ok((
+                [
+                    (header::CONTENT_TYPE, "text/csv".to_string()),
+                    (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", file_name)),
+                ],
+                session_stats::movements_csv(&stats),
+            )
+                .into_response())
+        }
+        Some(other) => Err(Error::InvalidInput(format!("Unknown format: {}", other))),
+    }
+}
+
+/// DELETE /api/replay - Stop the replay and return all clients to live traffic
+async fn stop_replay(State(state): State<Arc<ServerState>>) -> Json<ReplayStatus> {
+    Json(replay::stop(&state.app_handle))
+}
+
+/// WebSocket handler relaying replay playback to remote browsers.
+/// The status and current snapshot are sent on connect.
+///
+/// ## Message Format
+/// ```json
+/// {"type":"status","loaded":true,"playing":true,"speed":2.0,"position":1234567890000,...}
+/// {"type":"snapshot","timestamp":1234567890000,"aircraftStates":[...],...}
+/// ```
+async fn replay_websocket_handler(
+    ws: WebSocketUpgrade,
+    Query(profile): Query<DeclutterProfile>,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_replay_websocket(socket, state, profile))
+}
+
+/// Handle a replay WebSocket connection
+async fn handle_replay_websocket(socket: WebSocket, state: Arc<ServerState>, profile: DeclutterProfile) {
+    let (mut sender, mut receiver) = socket.split();
+    let _stream = debug_stats::stream_connected("replay");
+    let mut events = state.app_handle.state::<ReplayState>().events.subscribe();
+    let mut pending = vec![ReplayEvent::Status(replay::get_status(&state.app_handle))];
+    pending.extend(replay::current_snapshot(&state.app_handle).map(ReplayEvent::Snapshot));
+    let (profile_tx, profile_rx) = watch::channel(profile);
+    let app_handle = state.app_handle.clone();
+
+    let send_task = tokio::spawn(async move {
+        let mut pending = pending.into_iter();
+        let mut reference = ReferenceCache::default();
+        loop {
+            let event = match pending.next() {
+                Some(event) => event,
+                None => match events.recv().await {
+                    Ok(event) => event,
+                    // Slow client missed some snapshots; the next one replaces them
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        debug_stats::record_lag("replay", skipped);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+            };
+            let profile = profile_rx.borrow().clone();
+            let event = if profile.is_empty() {
+                event
+            } else {
+                profile.replay(event, reference.get(&app_handle).await)
+            };
+            let Ok(json) = serde_json::to_string(&event) else {
+                continue;
+            };
+            if sender.send(Message::Text(json)).await.is_err() {
+                break; // Client disconnected
+            }
+        }
+    });
+
+    // Keep connection alive until client disconnects; apply declutter changes
+    while let Some(msg) = receiver.next().await {
+        match msg {
+            Ok(Message::Text(text)) => {
+                if let Some(profile) = declutter::parse_message(&text) {
+                    let _ = profile_tx.send(profile);
+                }
+            }
+            Ok(Message::Close(_)) | Err(_) => break,
+            Ok(_) => {}
+        }
+    }
+
+    send_task.abort();
+}
+
+// =============================================================================
+// Squawk alerts
+// =============================================================================
+
+/// GET /api/squawks - Active emergency and squawk mismatch alerts
+async fn get_squawk_alerts(State(state): State<Arc<ServerState>>) -> Json<SquawkAlerts> {
+    Json(squawk_alerts::get_alerts(&state.app_handle))
+}
+
+/// WebSocket handler relaying squawk alerts to remote browsers.
+/// The active alerts are sent on connect.
+async fn squawks_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_squawks_websocket(socket, state))
+}
+
+/// Handle a squawk alert WebSocket connection
+async fn handle_squawks_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<SquawkAlertState>().events.subscribe();
+    let current = squawk_alerts::get_alerts(&state.app_handle);
+    relay_broadcast(socket, "squawks", events, Some(current)).await;
+}
+
+// =============================================================================
+// Short-term conflict alert
+// =============================================================================
+
+/// GET /api/stca - Active short-term conflict alerts
+async fn get_stca_alerts(State(state): State<Arc<ServerState>>) -> Json<StcaAlerts> {
+    Json(stca::get_alerts(&state.app_handle))
+}
+
+/// WebSocket handler relaying conflict alerts to remote browsers.
+/// The current alerts are sent on connect.
+async fn stca_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_stca_websocket(socket, state))
+}
+
+/// Handle a conflict alert WebSocket connection
+async fn handle_stca_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<StcaState>().events.subscribe();
+    let current = stca::get_alerts(&state.app_handle);
+    relay_broadcast(socket, "stca", events, Some(current)).await;
+}
+
+// =============================================================================
+// Label deconfliction
+// =============================================================================
+
+/// GET /api/labels - Datablock placements worked out by the host
+async fn get_label_layout(State(state): State<Arc<ServerState>>) -> Json<LabelLayout> {
+    Json(label_layout::get_layout(&state.app_handle))
+}
+
+/// WebSocket handler relaying label placements to remote browsers.
+/// The current layout is sent on connect.
+async fn labels_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_labels_websocket(socket, state))
+}
+
+/// Handle a label layout WebSocket connection
+async fn handle_labels_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<LabelLayoutState>().events.subscribe();
+    let current = label_layout::get_layout(&state.app_handle);
+    relay_broadcast(socket, "labels", events, Some(current)).await;
+}
+
+// =============================================================================
+// Runway occupancy
+// =============================================================================
+
+/// GET /api/runways/occupancy - Runway occupancy at the active airport
+async fn get_runway_occupancy(State(state): State<Arc<ServerState>>) -> Json<RunwayOccupancy> {
+    Json(runway_occupancy::get_occupancy(&state.app_handle))
+}
+
+/// GET /api/runways/config - Runway configuration detected from traffic, checked against the wind
+async fn get_runway_configuration(State(state): State<Arc<ServerState>>) -> Json<RunwayConfiguration> {
+    Json(runway_config::get_configuration(&state.app_handle))
+}
+
+/// WebSocket handler relaying runway entries and exits to remote browsers
+///
+/// ## Message Format
+/// ```json
+/// {"icao":"KBOS","runway":"04R/22L","callsign":"DAL123","kind":"exited","time":1234567890000,"occupiedSecs":48}
+/// ```
+async fn runway_occupancy_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_runway_occupancy_websocket(socket, state))
+}
+
+/// Handle a runway occupancy WebSocket connection
+async fn handle_runway_occupancy_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<RunwayOccupancyState>().events.subscribe();
+    relay_broadcast(socket, "runway-occupancy", events, None).await;
+}
+
+// =============================================================================
+// Hold-short and line-up timers
+// =============================================================================
+
+/// GET /api/holds - Running hold-short and line-up timers
+async fn get_hold_timers(State(state): State<Arc<ServerState>>) -> Json<HoldTimers> {
+    Json(runway_holds::get_timers(&state.app_handle))
+}
+
+/// WebSocket handler relaying hold timers to remote browsers.
+/// The running timers are sent on connect.
+async fn holds_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_holds_websocket(socket, state))
+}
+
+/// Handle a hold timer WebSocket connection
+async fn handle_holds_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<HoldTimerState>().events.subscribe();
+    let current = runway_holds::get_timers(&state.app_handle);
+    relay_broadcast(socket, "holds", events, Some(current)).await;
+}
+
+// =============================================================================
+// Go-arounds
+// =============================================================================
+
+/// GET /api/go-arounds - Go-arounds seen in this session
+async fn get_go_arounds(State(state): State<Arc<ServerState>>) -> Json<GoArounds> {
+    Json(go_arounds::get_go_arounds(&state.app_handle))
+}
+
+/// WebSocket handler relaying go-arounds to remote browsers
+///
+/// ## Message Format
+/// ```json
+/// {"icao":"KBOS","callsign":"DAL123","aircraftType":"A321","runway":"04R","time":1234567890000,"lowestAltitudeFt":320.0}
+/// ```
+async fn go_arounds_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_go_arounds_websocket(socket, state))
+}
+
+/// Handle a go-around WebSocket connection
+async fn handle_go_arounds_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<GoAroundState>().events.subscribe();
+    relay_broadcast(socket, "go-arounds", events, None).await;
+}
+
+// =============================================================================
+// Final approach spacing (ATPA)
+// =============================================================================
+
+/// GET /api/atpa - Final approach spacing at the active airport
+async fn get_final_spacing(State(state): State<Arc<ServerState>>) -> Json<AtpaSnapshot> {
+    Json(atpa::get_snapshot(&state.app_handle))
+}
+
+/// WebSocket handler relaying final approach spacing to remote browsers.
+/// The current spacing is sent on connect.
+async fn atpa_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_atpa_websocket(socket, state))
+}
+
+/// Handle a final approach spacing WebSocket connection
+async fn handle_atpa_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<AtpaState>().events.subscribe();
+    let current = atpa::get_snapshot(&state.app_handle);
+    relay_broadcast(socket, "atpa", events, Some(current)).await;
+}
+
+// =============================================================================
+// Arrival sequence
+// =============================================================================
+
+/// GET /api/arrivals - Arrival sequence per runway with threshold ETAs
+async fn get_arrival_sequence(State(state): State<Arc<ServerState>>) -> Json<ArrivalSequence> {
+    Json(arrival_sequence::get_sequence(&state.app_handle))
+}
+
+/// WebSocket handler relaying the arrival sequence to remote browsers.
+/// The current sequence is sent on connect.
+async fn arrivals_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_arrivals_websocket(socket, state))
+}
+
+/// Handle an arrival sequence WebSocket connection
+async fn handle_arrivals_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<ArrivalSequenceState>().events.subscribe();
+    let current = arrival_sequence::get_sequence(&state.app_handle);
+    relay_broadcast(socket, "arrivals", events, Some(current)).await;
+}
+
+// =============================================================================
+// Departure wake timers
+// =============================================================================
+
+/// GET /api/wake-timers - Running departure wake timers
+async fn get_wake_timers(State(state): State<Arc<ServerState>>) -> Json<WakeTimers> {
+    Json(wake_timers::get_timers(&state.app_handle))
+}
+
+/// WebSocket handler relaying wake timers to remote browsers.
+/// The running timers are sent on connect.
+async fn wake_timers_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_wake_timers_websocket(socket, state))
+}
+
+/// Handle a wake timer WebSocket connection
+async fn handle_wake_timers_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<WakeTimerState>().events.subscribe();
+    let current = wake_timers::get_timers(&state.app_handle);
+    relay_broadcast(socket, "wake-timers", events, Some(current)).await;
+}
+
+// =============================================================================
+// Host-side aircraft following
+// =============================================================================
+
+/// GET /api/follow - Aircraft followed on every display, with its camera target
+async fn get_follow(State(state): State<Arc<ServerState>>) -> Json<FollowStatus> {
+    Json(follow::get_status(&state.app_handle))
+}
+
+/// PUT /api/follow - Follow an aircraft (callsign and camera offsets) on every display
+async fn set_follow(
+    State(state): State<Arc<ServerState>>,
+    Json(target): Json<FollowTarget>,
+) -> Result<Json<FollowStatus>, Error> {
+    follow::set(&state.app_handle, target).map(Json)
+}
+
+/// DELETE /api/follow - Stop following on every display
+async fn stop_follow(State(state): State<Arc<ServerState>>) -> StatusCode {
+    follow::stop(&state.app_handle);
+    StatusCode::NO_CONTENT
+}
+
+/// WebSocket handler relaying follow updates to remote browsers.
+/// The current status is sent on connect, so reloaded clients resume following.
+async fn follow_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_follow_websocket(socket, state))
+}
+
+/// Handle a follow WebSocket connection
+async fn handle_follow_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<FollowState>().events.subscribe();
+    let current = follow::get_status(&state.app_handle);
+    relay_broadcast(socket, "follow", events, Some(current)).await;
+}
+
+// =============================================================================
+// Shared aircraft selection
+// =============================================================================
+
+/// GET /api/selection - Aircraft selected across displays
+async fn get_selection(State(state): State<Arc<ServerState>>) -> Json<Selection> {
+    Json(selection::get(&state.app_handle))
+}
+
+/// PUT /api/selection - Share an aircraft selection with every opted-in display
+async fn set_selection(
+    State(state): State<Arc<ServerState>>,
+    Json(request): Json<SelectionRequest>,
+) -> Result<Json<Selection>, Error> {
+    selection::set(&state.app_handle, request).map(Json)
+}
+
+/// DELETE /api/selection - Clear the shared selection
+async fn clear_selection(State(state): State<Arc<ServerState>>) -> StatusCode {
+    selection::clear(&state.app_handle);
+    StatusCode::NO_CONTENT
+}
+
+/// WebSocket handler relaying selection changes to remote browsers.
+/// The current selection is sent on connect.
+async fn selection_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_selection_websocket(socket, state))
+}
+
+/// Handle a selection WebSocket connection
+async fn handle_selection_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<SelectionState>().events.subscribe();
+    let current = selection::get(&state.app_handle);
+    relay_broadcast(socket, "selection", events, Some(current)).await;
+}
+
+// =============================================================================
+// Shared drawings
+// =============================================================================
+
+/// Body of PUT /api/drawings/session
+#[derive(Deserialize)]
+struct DrawingSessionRequest {
+    session: String,
+}
+
+/// GET /api/drawings - Lines, labels and polygons drawn in the active session
+async fn get_drawings(State(state): State<Arc<ServerState>>) -> Json<DrawingBoard> {
+    Json(drawings::get(&state.app_handle))
+}
+
+/// POST /api/drawings - Draw a line, label or polygon on every display
+async fn add_drawing(
+    State(state): State<Arc<ServerState>>,
+    Json(request): Json<DrawingRequest>,
+) -> Result<Json<Drawing>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || drawings::add(&app, request)).await?.map(Json)
+}
+
+/// DELETE /api/drawings/:id - Remove a drawing
+async fn delete_drawing(
+    State(state): State<Arc<ServerState>>,
+    Path(id): Path<String>,
+) -> Result<Json<DrawingBoard>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || drawings::remove(&app, &id)).await?.map(Json)
+}
+
+/// DELETE /api/drawings - Remove every drawing in the active session
+async fn clear_drawings(State(state): State<Arc<ServerState>>) -> Result<Json<DrawingBoard>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || drawings::clear(&app)).await?.map(Json)
+}
+
+/// GET /api/drawings/sessions - Saved drawing sessions
+async fn list_drawing_sessions(State(state): State<Arc<ServerState>>) -> Result<Json<Vec<DrawingSessionInfo>>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || drawings::list_sessions(&app)).await?.map(Json)
+}
+
+/// PUT /api/drawings/session - Switch to (or start) a drawing session
+async fn set_drawing_session(
+    State(state): State<Arc<ServerState>>,
+    Json(request): Json<DrawingSessionRequest>,
+) -> Result<Json<DrawingBoard>, Error> {
+    let app = state.app_handle.clone();
+    blocking(move || drawings::switch_session(&app, &request.session))
+        .await?
+        .map(Json)
+}
+
+/// WebSocket handler relaying drawing changes to remote browsers.
+/// The current board is sent on connect.
+async fn drawings_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_drawings_websocket(socket, state))
+}
+
+/// Handle a drawings WebSocket connection
+async fn handle_drawings_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let events = state.app_handle.state::<DrawingState>().events.subscribe();
+    let current = drawings::get(&state.app_handle);
+    relay_broadcast(socket, "drawings", events, Some(current)).await;
+}
+
+// =============================================================================
+// Coordination messages
+// =============================================================================
+
+/// Query parameters for GET /api/messages
+#[derive(Deserialize)]
+struct MessagesQuery {
+    /// Only messages with a higher id
+    since: Option<u64>,
+}
+
+/// GET /api/messages - Recent coordination messages, oldest first
+async fn get_coordination_messages(
+    State(state): State<Arc<ServerState>>,
+    Query(query): Query<MessagesQuery>,
+) -> Json<Vec<CoordinationMessage>> {
+    Json(coordination::history(&state.app_handle, query.since))
+}
+
+/// POST /api/messages - Send a coordination message to every display
+async fn send_coordination_message(
+    State(state): State<Arc<ServerState>>,
+    Json(request): Json<MessageRequest>,
+) -> Result<Json<CoordinationMessage>, Error> {
+    coordination::send(&state.app_handle, request).map(Json)
+}
+
+/// WebSocket handler relaying coordination messages to remote browsers.
+/// The message history is sent on connect.
+async fn messages_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_messages_websocket(socket, state))
+}
+
+/// Handle a coordination message WebSocket connection
+async fn handle_messages_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let (mut sender, mut receiver) = socket.split();
+    let _stream = debug_stats::stream_connected("messages");
+    let mut events = state.app_handle.state::<CoordinationState>().events.subscribe();
+    let history = coordination::history(&state.app_handle, None);
+
+    let send_task = tokio::spawn(async move {
+        let mut pending = history.into_iter();
+        let mut last_id = 0;
+        loop {
+            let message = match pending.next() {
+                Some(message) => message,
+                None => match events.recv().await {
+                    Ok(message) => message,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        debug_stats::record_lag("messages", skipped);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+            };
+            // Messages sent while the history was read arrive twice
+            if message.id <= last_id {
+                continue;
+            }
+            last_id = message.id;
+            let Ok(json) = serde_json::to_string(&message) else {
+                continue;
+            };
+            if sender.send(Message::Text(json)).await.is_err() {
+                break; // Client disconnected
             }
         }
     });
 
-    // Handle incoming messages (mostly for keepalive/ping-pong)
+    // Keep connection alive until client disconnects
     while let Some(msg) = receiver.next().await {
-        match msg {
-            Ok(Message::Ping(data)) => {
-                // Ping/pong handled automatically by axum
-                println!("[vNAS WS] Received ping: {:?}", data);
-            }
-            Ok(Message::Close(_)) => {
-                println!("[vNAS WS] Client requested close");
-                break;
-            }
-            Ok(_) => {
-                // Ignore other message types (we don't expect client messages)
-            }
-            Err(e) => {
-                eprintln!("[vNAS WS] Error: {}", e);
-                break;
-            }
+        if matches!(msg, Ok(Message::Close(_)) | Err(_)) {
+            break;
         }
     }
 
-    // Clean up
     send_task.abort();
-    println!("[vNAS WS] Client disconnected");
 }
 
 // =============================================================================
@@ -1098,10 +3830,11 @@ async fn presence_websocket_handler(
 /// Handle a presence WebSocket connection
 async fn handle_presence_websocket(socket: WebSocket, state: Arc<ServerState>) {
     let (_sender, mut receiver) = socket.split();
+    let _stream = debug_stats::stream_connected("presence");
 
     // Increment connected client count and emit event
     let count = state.connected_clients.fetch_add(1, Ordering::SeqCst) + 1;
-    println!("[Presence] Remote client connected (total: {})", count);
+    info!("[Presence] Remote client connected (total: {})", count);
     let _ = state.app_handle.emit("remote-clients-changed", count);
 
     // Keep connection alive until client disconnects
@@ -1116,14 +3849,459 @@ async fn handle_presence_websocket(socket: WebSocket, state: Arc<ServerState>) {
 
     // Decrement connected client count and emit event
     let count = state.connected_clients.fetch_sub(1, Ordering::SeqCst) - 1;
-    println!("[Presence] Remote client disconnected (total: {})", count);
+    info!("[Presence] Remote client disconnected (total: {})", count);
     let _ = state.app_handle.emit("remote-clients-changed", count);
 }
 
+// =============================================================================
+// Offline Tile Cache
+// =============================================================================
+
+/// GET /api/tiles - Tile cache size and prefetch progress
+async fn get_tile_cache_status(State(state): State<Arc<ServerState>>) -> Json<TileCacheStatus> {
+    Json(tile_cache::get_status(&state.app_handle))
+}
+
+/// POST /api/tiles/prefetch - Download terrain and imagery around airports
+async fn prefetch_tiles(
+    State(state): State<Arc<ServerState>>,
+    Json(request): Json<PrefetchRequest>,
+) -> Result<Json<PrefetchProgress>, Error> {
+    tile_cache::prefetch(&state.app_handle, request).map(Json)
+}
+
+/// DELETE /api/tiles/prefetch - Stop a running tile download
+async fn cancel_tile_prefetch(State(state): State<Arc<ServerState>>) -> StatusCode {
+    tile_cache::cancel_prefetch(&state.app_handle);
+    StatusCode::NO_CONTENT
+}
+
+/// DELETE /api/tiles - Delete all cached tiles
+async fn clear_tile_cache(State(state): State<Arc<ServerState>>) -> Result<StatusCode, Error> {
+    tile_cache::clear(&state.app_handle)
+        .await
+        .map(|()| StatusCode::NO_CONTENT)
+}
+
+// =============================================================================
+// Event Preparation
+// =============================================================================
+
+/// GET /api/event-prep - Status of the current or last event preparation
+async fn get_event_prep_status(State(state): State<Arc<ServerState>>) -> Json<Option<EventPrepStatus>> {
+    Json(event_prep::get_status(&state.app_handle))
+}
+
+/// POST /api/event-prep - Warm every cache for an event at an airport
+async fn prepare_event(
+    State(state): State<Arc<ServerState>>,
+    Json(request): Json<EventPrepRequest>,
+) -> Result<Json<EventPrepStatus>, Error> {
+    event_prep::prepare(&state.app_handle, request).await.map(Json)
+}
+
+/// DELETE /api/event-prep - Stop a running event preparation
+async fn cancel_event_prep(State(state): State<Arc<ServerState>>) -> Result<StatusCode, Error> {
+    event_prep::cancel(&state.app_handle).map(|()| StatusCode::NO_CONTENT)
+}
+
+/// Serve a tile from the cache, downloading it on a miss
+async fn serve_tile(
+    state: &ServerState,
+    path: String,
+    vintage: Option<&str>,
+) -> Result<Response<Body>, Error> {
+    let tile = Tile::parse(&path).ok_or_else(|| Error::not_found("Unknown tile"))?;
+    let content = tile_cache::get_tile(&state.app_handle, &tile, vintage)
+        .await
+        .map_err(Error::Upstream)?
+        .ok_or_else(|| Error::not_found("No tile"))?;
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, tile.content_type())
+        .body(Body::from(content))
+        .unwrap())
+}
+
+/// GET /api/tiles/terrain/*path - Cesium World Terrain through the tile cache
+async fn serve_terrain_tile(
+    State(state): State<Arc<ServerState>>,
+    Path(path): Path<String>,
+) -> Result<Response<Body>, Error> {
+    serve_tile(&state, format!("terrain/{}", path), None).await
+}
+
+/// Query parameters for imagery tiles
+#[derive(Deserialize)]
+struct ImageryTileQuery {
+    /// Earlier imagery vintage (e.g. `wayback-12457`)
+    vintage: Option<String>,
+}
+
+/// GET /api/tiles/imagery/:z/:x/:y?vintage= - Aerial imagery through the tile cache
+async fn serve_imagery_tile(
+    State(state): State<Arc<ServerState>>,
+    Path(path): Path<String>,
+    Query(query): Query<ImageryTileQuery>,
+) -> Result<Response<Body>, Error> {
+    serve_tile(&state, format!("imagery/{}", path), query.vintage.as_deref()).await
+}
+
+/// GET /api/map-providers - Selected terrain and imagery providers and their attribution
+async fn get_map_providers(State(state): State<Arc<ServerState>>) -> Json<MapProviderInfo> {
+    Json(map_providers::info(&map_providers::settings(&state.app_handle)))
+}
+
+/// Query parameters for imagery vintages
+#[derive(Deserialize)]
+struct ImageryVintagesQuery {
+    /// Default: the selected imagery provider
+    provider: Option<ImageryProvider>,
+}
+
+/// GET /api/map-providers/vintages?provider= - Earlier vintages of an imagery provider, newest first
+async fn list_imagery_vintages(
+    State(state): State<Arc<ServerState>>,
+    Query(query): Query<ImageryVintagesQuery>,
+) -> Result<Json<Vec<ImageryVintage>>, Error> {
+    let provider = query
+        .provider
+        .unwrap_or_else(|| map_providers::settings(&state.app_handle).imagery);
+    imagery_vintages::list(provider).await.map(Json)
+}
+
+/// GET /api/terrain - Imported offline terrain packages
+async fn list_terrain_packages(State(state): State<Arc<ServerState>>) -> Json<Vec<TerrainPackage>> {
+    Json(terrain_packages::list(&state.app_handle))
+}
+
+/// GET /api/terrain/:id/*path - A terrain package as a Cesium terrain endpoint
+/// (`layer.json` and `z/x/y.terrain`)
+async fn serve_terrain_package_file(
+    State(state): State<Arc<ServerState>>,
+    Path((id, path)): Path<(String, String)>,
+) -> Result<Response<Body>, Error> {
+    let content = terrain_packages::read_file(&state.app_handle, &id, &path)
+        .await?
+        .ok_or_else(|| Error::not_found("No tile"))?;
+    let content_type = if path.ends_with("layer.json") {
+        "application/json"
+    } else {
+        "application/vnd.quantized-mesh"
+    };
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, content_type)
+        .body(Body::from(content))
+        .unwrap())
+}
+
+/// GET /api/buildings/:icao?refresh= - Generate (or get the cached) 3D building tileset from OpenStreetMap
+async fn get_osm_buildings(
+    State(state): State<Arc<ServerState>>,
+    Path(icao): Path<String>,
+    Query(query): Query<OsmLayoutQuery>,
+) -> Result<Json<BuildingsInfo>, Error> {
+    osm_buildings::get_buildings(&state.app_handle, &icao, query.refresh.unwrap_or(false))
+        .await
+        .map(Json)
+}
+
+/// GET /api/buildings/:icao/*path - A building tileset file (`tileset.json` and `tiles/{i}_{j}.glb`)
+async fn serve_osm_buildings_file(
+    State(state): State<Arc<ServerState>>,
+    Path((icao, path)): Path<(String, String)>,
+) -> Result<Response<Body>, Error> {
+    let content = osm_buildings::read_file(&state.app_handle, &icao, &path)
+        .await?
+        .ok_or_else(|| Error::not_found("Unknown file"))?;
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, osm_buildings::content_type(&path))
+        .body(Body::from(content))
+        .unwrap())
+}
+
+// =============================================================================
+// Metrics
+// =============================================================================
+
+/// Server health and cache counters
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ServerMetrics {
+    /// Connected remote clients (presence WebSockets)
+    connected_clients: usize,
+    file_cache: FileCacheStats,
+}
+
+/// GET /api/metrics - Connected clients and file cache hit/miss counts
+async fn get_metrics(State(state): State<Arc<ServerState>>) -> Json<ServerMetrics> {
+    Json(ServerMetrics {
+        connected_clients: state.connected_clients.load(Ordering::SeqCst),
+        file_cache: state.file_cache.stats(),
+    })
+}
+
+/// GET /api/diagnostics/startup - Startup phase timings
+async fn get_startup_profile() -> Json<StartupProfile> {
+    Json(startup::get_profile())
+}
+
+/// GET /api/diagnostics/tasks - Background task state, restarts and last failure
+async fn get_task_status() -> Json<Vec<TaskStatus>> {
+    Json(supervisor::status())
+}
+
+/// GET /api/schedule - Scheduled server window open now and the next one
+async fn get_server_schedule(State(state): State<Arc<ServerState>>) -> Result<Json<ServerScheduleStatus>, Error> {
+    let app = state.app_handle.clone();
+    Ok(Json(blocking(move || server_schedule::get_status(&app)).await?))
+}
+
+/// Query parameters for listing events
+#[derive(Deserialize)]
+struct EventsQuery {
+    /// Airport the events affect (default: the active airport)
+    icao: Option<String>,
+}
+
+/// GET /api/events?icao= - Ongoing and upcoming VATSIM and facility calendar events at an airport
+async fn get_upcoming_events(
+    State(state): State<Arc<ServerState>>,
+    Query(query): Query<EventsQuery>,
+) -> Result<Json<EventScheduleStatus>, Error> {
+    let app = state.app_handle.clone();
+    Ok(Json(blocking(move || event_schedule::get_status(&app, query.icao.as_deref())).await??))
+}
+
+/// POST /api/events/:id/record - Record the traffic until an event ends and save it to the replay library
+async fn record_event(
+    State(state): State<Arc<ServerState>>,
+    Path(id): Path<String>,
+) -> Result<(Extension<AuditSummary>, Json<ScheduledEvent>), Error> {
+    let event = event_schedule::record(&state.app_handle, &id)?;
+    let summary = format!("Recording started for \"{}\"", event.name);
+    Ok((Extension(AuditSummary(vec![summary])), Json(event)))
+}
+
+/// POST /api/client-logs - Console errors and warnings from a remote browser
+async fn post_client_logs(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(batch): Json<ClientLogBatch>,
+) -> StatusCode {
+    let user_agent = headers.get(header::USER_AGENT).and_then(|v| v.to_str().ok());
+    let client = client_logs::describe_client(client_ip(&addr), user_agent, batch.session_id.as_deref());
+    client_logs::ingest(&client, &batch);
+    StatusCode::NO_CONTENT
+}
+
+// =============================================================================
+// Control Surface (Companion / Stream Deck, see docs/control-api.md)
+// =============================================================================
+
+/// How often the control WebSocket re-checks replay and timelapse feedback
+const CONTROL_FEEDBACK_POLL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// GET /api/control/actions - Every action a button can trigger
+async fn get_control_actions(State(state): State<Arc<ServerState>>) -> Json<ControlActions> {
+    Json(control::actions(&state.app_handle))
+}
+
+/// POST /api/control/trigger - Run an action
+async fn trigger_control_action(
+    State(state): State<Arc<ServerState>>,
+    Json(action): Json<ControlAction>,
+) -> Result<StatusCode, Error> {
+    control::trigger(&state.app_handle, action).map(|_| StatusCode::NO_CONTENT)
+}
+
+/// GET /api/control/state - Feedback for button LEDs
+async fn get_control_state(State(state): State<Arc<ServerState>>) -> Json<ControlFeedback> {
+    Json(control::feedback(&state.app_handle))
+}
+
+/// WebSocket handler sending control feedback whenever it changes (current
+/// feedback on connect) and accepting actions as JSON messages
+async fn control_websocket_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<Arc<ServerState>>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_control_websocket(socket, state))
+}
+
+/// Handle a control WebSocket connection
+async fn handle_control_websocket(socket: WebSocket, state: Arc<ServerState>) {
+    let (mut sender, mut receiver) = socket.split();
+    let _stream = debug_stats::stream_connected("control");
+    let mut events = state.app_handle.state::<ControlState>().events.subscribe();
+    let app = state.app_handle.clone();
+
+    let send_task = tokio::spawn(async move {
+        let mut last_sent: Option<ControlFeedback> = None;
+        loop {
+            // View changes arrive as events; replay and timelapse state is polled
+            match tokio::time::timeout(CONTROL_FEEDBACK_POLL, events.recv()).await {
+                Ok(Err(broadcast::error::RecvError::Lagged(skipped))) => {
+                    debug_stats::record_lag("control", skipped);
+                }
+                Ok(Err(broadcast::error::RecvError::Closed)) => break,
+                Ok(Ok(_)) | Err(_) => {}
+            }
+            let feedback = control::feedback(&app);
+            if last_sent.as_ref() == Some(&feedback) {
+                continue;
+            }
+            let Ok(json) = serde_json::to_string(&feedback) else {
+                continue;
+            };
+            if sender.send(Message::Text(json)).await.is_err() {
+                break; // Client disconnected
+            }
+            last_sent = Some(feedback);
+        }
+    });
+
+    while let Some(msg) = receiver.next().await {
+        match msg {
+            Ok(Message::Text(text)) => match serde_json::from_str::<ControlAction>(&text) {
+                Ok(action) => {
+                    if let Err(e) = control::trigger(&state.app_handle, action) {
+                        warn!("[Control] {}", e);
+                    }
+                }
+                Err(e) => warn!("[Control] Ignoring invalid message: {}", e),
+            },
+            Ok(Message::Close(_)) | Err(_) => break,
+            _ => {}
+        }
+    }
+
+    send_task.abort();
+}
+
+// =============================================================================
+// Debug Introspection (dev-only, admin access)
+// =============================================================================
+
+/// Queue of a broadcast channel
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChannelStats {
+    /// Messages not yet received by every subscriber
+    queued: usize,
+    receivers: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DebugStreams {
+    /// Remote browsers connected to the presence WebSocket
+    presence_clients: usize,
+    /// Channel relaying vNAS batches to `/api/vnas/ws`
+    vnas_channel: ChannelStats,
+    streams: Vec<StreamStats>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DebugCaches {
+    file_cache: FileCacheStats,
+    /// Files being compressed to zstd copies
+    zstd_pending: usize,
+    tile_cache: TileCacheStatus,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DebugTraffic {
+    /// Selected traffic source (`vatsim` or `realtraffic`)
+    data_source: String,
+    /// Airport the VATSIM feed is polled for
+    icao: Option<String>,
+    /// Seconds since the last successful VATSIM poll
+    last_poll_age_s: Option<f64>,
+    aircraft: usize,
+    vnas: VnasStatus,
+    /// Seconds since the last aircraft update from vNAS
+    last_vnas_update_age_s: Option<f64>,
+    lightning: LightningStatus,
+}
+
+/// GET /api/debug/streams - WebSocket clients per stream and broadcast lag
+async fn debug_streams(State(state): State<Arc<ServerState>>) -> Json<DebugStreams> {
+    Json(DebugStreams {
+        presence_clients: state.connected_clients.load(Ordering::SeqCst),
+        vnas_channel: ChannelStats {
+            queued: state.vnas_tx.len(),
+            receivers: state.vnas_tx.receiver_count(),
+        },
+        streams: debug_stats::streams(),
+    })
+}
+
+/// GET /api/debug/caches - File, zstd and tile cache sizes
+async fn debug_caches(State(state): State<Arc<ServerState>>) -> Json<DebugCaches> {
+    Json(DebugCaches {
+        file_cache: state.file_cache.stats(),
+        zstd_pending: state.zstd_cache.pending_count(),
+        tile_cache: tile_cache::get_status(&state.app_handle),
+    })
+}
+
+/// GET /api/debug/traffic - Traffic source status and last vNAS update age
+async fn debug_traffic(State(state): State<Arc<ServerState>>) -> Json<DebugTraffic> {
+    let app = &state.app_handle;
+    let snapshot = traffic::get_snapshot(app);
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    Json(DebugTraffic {
+        data_source: crate::read_global_settings(app.clone())
+            .map(|s| s.realtraffic.data_source)
+            .unwrap_or_default(),
+        icao: snapshot.icao,
+        last_poll_age_s: snapshot
+            .updated_at
+            .map(|at| now_ms.saturating_sub(at) as f64 / 1000.0),
+        aircraft: snapshot.aircraft.len(),
+        vnas: app.state::<VnasState>().status(),
+        last_vnas_update_age_s: vnas_batch::last_update_age(app).map(|age| age.as_secs_f64()),
+        lightning: lightning::get_status(app),
+    })
+}
+
+/// GET /api/debug/tasks - Background task health
+async fn debug_tasks() -> Json<Vec<TaskHealth>> {
+    Json(debug_stats::tasks())
+}
+
 // =============================================================================
 // Static File Serving
 // =============================================================================
 
+/// GET /obs/:page - OBS overlay page (datablocks, arrivals, metar) or its shared script and styles
+async fn serve_obs_page(Path(page): Path<String>) -> Result<Response<Body>, Error> {
+    let asset = obs::asset(&page).ok_or_else(|| Error::NotFound(format!("No overlay '{}'", page)))?;
+    Response::builder()
+        .header(header::CONTENT_TYPE, asset.content_type)
+        .header(header::CACHE_CONTROL, "no-cache")
+        .body(Body::from(asset.content))
+        .map_err(|e| Error::Internal(e.to_string()))
+}
+
+/// GET /api/docs - Interactive API reference (open with ?token= if an auth token is set)
+async fn serve_api_docs() -> Response<Body> {
+    ([(header::CONTENT_TYPE, "text/html; charset=utf-8")], api_docs::PAGE).into_response()
+}
+
+/// GET /api/docs/openapi.json - OpenAPI 3.1 description of this API, generated from the routes
+async fn get_openapi_spec() -> Response<Body> {
+    ([(header::CONTENT_TYPE, "application/json")], api_docs::SPEC).into_response()
+}
+
 /// Serve static files from the dist folder
 async fn serve_static(
     State(state): State<Arc<ServerState>>,
@@ -1135,14 +4313,20 @@ async fn serve_static(
     let path = path.trim_start_matches('/');
     let path = if path.is_empty() { "index.html" } else { path };
 
-    let file_path = state.dist_path.join(path);
+    let relative = path.to_string();
+    let resolved = resolve_served_file(&state, state.dist_path.clone(), SymlinkPolicy::Deny, move |root| {
+        root.resolve(&relative)
+    })
+    .await;
 
     // Debug: log what we're looking for
-    println!("[Server] Request: {} -> {:?} (exists: {})", path, file_path, file_path.exists());
+    debug!("[Server] Request: {} -> {:?}", path, resolved);
 
     // Try the exact path first
-    if file_path.exists() && file_path.is_file() {
-        return serve_file(&file_path).await;
+    match resolved {
+        Ok(file_path) => return serve_file_compressed(&state, request.headers(), &file_path).await,
+        Err(e @ Error::Forbidden(_)) => return Err(e),
+        Err(_) => {}
     }
 
     // Check if this looks like a static asset request (has a file extension)
@@ -1162,23 +4346,67 @@ async fn serve_static(
 
     if has_extension {
         // Static asset not found - return 404, don't serve index.html
-        println!("[Server] Static file not found: {}", path);
-        return Err((StatusCode::NOT_FOUND, format!("File not found: {}", path)));
+        debug!("[Server] Static file not found: {}", path);
+        return Err(Error::NotFound(format!("File not found: {}", path)));
     }
 
     // For SPA routing, serve index.html for non-file paths (e.g., /settings, /about)
     let index_path = state.dist_path.join("index.html");
-    if index_path.exists() {
-        return serve_file(&index_path).await;
+    if tokio::fs::try_exists(&index_path).await.unwrap_or(false) {
+        return serve_file(&state.file_cache, &index_path).await;
+    }
+
+    Err(Error::not_found("Not found"))
+}
+
+/// Serve a model or JSON file as its zstd copy if the client accepts zstd and
+/// one is ready (starting one in the background if not). Other files are
+/// served as-is.
+async fn serve_file_compressed(
+    state: &ServerState,
+    headers: &HeaderMap,
+    path: &std::path::Path,
+) -> Result<Response<Body>, Error> {
+    if !zstd_cache::is_compressible(path) {
+        return serve_file(&state.file_cache, path).await;
     }
 
-    Err((StatusCode::NOT_FOUND, "Not found".to_string()))
+    let copy = if zstd_cache::accepts_zstd(headers) {
+        let copy = state.zstd_cache.compressed_copy(path).await;
+        if copy.is_none() {
+            state.zstd_cache.schedule(path);
+        }
+        copy
+    } else {
+        None
+    };
+
+    let mut resp = match copy {
+        Some(copy) => {
+            let mut resp = serve_file(&state.file_cache, &copy).await?;
+            let mime = mime_guess::from_path(path).first_or_octet_stream();
+            resp.headers_mut().insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_str(mime.as_ref()).unwrap_or(HeaderValue::from_static("application/octet-stream")),
+            );
+            resp.headers_mut()
+                .insert(header::CONTENT_ENCODING, HeaderValue::from_static("zstd"));
+            resp
+        }
+        None => serve_file(&state.file_cache, path).await?,
+    };
+    resp.headers_mut()
+        .insert(header::VARY, HeaderValue::from_static("Accept-Encoding"));
+    Ok(resp)
 }
 
-/// Serve a single file with correct MIME type
-async fn serve_file(path: &PathBuf) -> Result<Response<Body>, (StatusCode, String)> {
-    let content = fs::read(path)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to read file: {}", e)))?;
+/// Serve a single file with correct MIME type (through the file cache, or
+/// streamed from a memory map if too large to cache)
+async fn serve_file(cache: &FileCache, path: &std::path::Path) -> Result<Response<Body>, Error> {
+    let (body, len) = cache
+        .body(path)
+        .await
+        .map_err(Error::Io)?;
 
     let mime = mime_guess::from_path(path)
         .first_or_octet_stream()
@@ -1186,7 +4414,8 @@ async fn serve_file(path: &PathBuf) -> Result<Response<Body>, (StatusCode, Strin
 
     let mut resp = Response::builder()
         .status(StatusCode::OK)
-        .body(Body::from(content))
+        .header(header::CONTENT_LENGTH, len)
+        .body(body)
         .unwrap();
 
     resp.headers_mut().insert(
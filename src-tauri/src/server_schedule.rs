@@ -0,0 +1,408 @@
+//! Scheduled server operation windows
+//!
+//! A dedicated host machine can run unattended: the global settings list time
+//! windows (weekly, e.g. Fridays 23:00-03:00 for FNO, or a single date for an
+//! event) during which the HTTP server is started, and stopped again when the
+//! window closes. A window can also record the traffic it covers; the
+//! recording is saved to the shared replay library (see `replay_library`)
+//! when the window closes.
+//!
+//! The scheduler only undoes what it did: a server that was already running
+//! when a window opened keeps running after it, and a server stopped by hand
+//! during a window is not started again until the next window. Times are UTC
+//! (like VATSIM event schedules) unless `utc` is off, then host local time.
+
+use std::time::Duration;
+
+use chrono::{Datelike, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+use crate::replay;
+use crate::replay_library::{self, ShareOptions};
+use crate::supervisor::{self, Restart};
+use crate::{debug_stats, GlobalSettings};
+
+/// How often the schedule is checked (the first check waits as long, so the
+/// server auto-start at launch goes first)
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// One scheduled operation window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ServerWindow {
+    /// Shown in logs and used as the title of the recording (e.g., "FNO")
+    pub name: String,
+    /// Days of the week the window opens ("mon" ... "sun"); empty = every day
+    pub days: Vec<String>,
+    /// Single date (YYYY-MM-DD) the window opens on instead of weekly days
+    pub date: Option<String>,
+    /// Opening time ("HH:MM")
+    pub start: String,
+    /// Closing time ("HH:MM"); at or before the start means the next day
+    pub end: String,
+    /// Record the traffic while open and save it to the replay library
+    pub record: bool,
+}
+
+impl Default for ServerWindow {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            days: Vec::new(),
+            date: None,
+            start: "00:00".to_string(),
+            end: "00:00".to_string(),
+            record: false,
+        }
+    }
+}
+
+/// Server schedule settings in the global settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ServerScheduleSettings {
+    /// Start and stop the server on schedule (default: false)
+    pub enabled: bool,
+    /// Window times are UTC; off = host local time (default: true)
+    pub utc: bool,
+    pub windows: Vec<ServerWindow>,
+}
+
+impl Default for ServerScheduleSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            utc: true,
+            windows: Vec::new(),
+        }
+    }
+}
+
+/// Scheduler status
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerScheduleStatus {
+    pub enabled: bool,
+    /// Window open now
+    pub active_window: Option<String>,
+    /// When it closes (Unix ms)
+    pub active_until: Option<u64>,
+    /// The scheduler started the server for the open window
+    pub started_server: bool,
+    /// The open window is recording traffic
+    pub recording: bool,
+    /// Next window to open
+    pub next_window: Option<String>,
+    /// When it opens (Unix ms)
+    pub next_start: Option<u64>,
+}
+
+/// Window the scheduler opened
+struct OpenWindow {
+    /// Window index and start of this occurrence
+    key: (usize, NaiveDateTime),
+    name: String,
+    until: NaiveDateTime,
+    started_server: bool,
+    recording: bool,
+}
+
+/// Scheduler state (managed by Tauri)
+pub struct ServerScheduleState {
+    open: Mutex<Option<OpenWindow>>,
+}
+
+fn parse_time(time: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(time.trim(), "%H:%M").ok()
+}
+
+impl ServerWindow {
+    fn single_date(&self) -> Option<NaiveDate> {
+        let date = self.date.as_deref().map(str::trim).filter(|d| !d.is_empty())?;
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+    }
+
+    fn opens_on(&self, day: NaiveDate) -> bool {
+        if self.date.as_deref().is_some_and(|d| !d.trim().is_empty()) {
+            return self.single_date() == Some(day);
+        }
+        self.days.is_empty()
+            || self
+                .days
+                .iter()
+                .any(|d| d.trim().parse::<Weekday>().is_ok_and(|w| w == day.weekday()))
+    }
+
+    /// Opening and closing time of the occurrence opening on `day`
+    fn occurrence(&self, day: NaiveDate) -> Option<(NaiveDateTime, NaiveDateTime)> {
+        if !self.opens_on(day) {
+            return None;
+        }
+        let start = day.and_time(parse_time(&self.start)?);
+        let mut end = day.and_time(parse_time(&self.end)?);
+        if end <= start {
+            end += chrono::Duration::days(1);
+        }
+        Some((start, end))
+    }
+
+    fn label(&self, index: usize) -> String {
+        match self.name.trim() {
+            "" => format!("Window {}", index + 1),
+            name => name.to_string(),
+        }
+    }
+}
+
+/// Window open at `now`: index, opening and closing time (the first listed wins)
+fn open_window(windows: &[ServerWindow], now: NaiveDateTime) -> Option<(usize, NaiveDateTime, NaiveDateTime)> {
+    let today = now.date();
+    // Overnight windows opened yesterday may still be open
+    let days = [today.pred_opt(), Some(today)];
+    windows.iter().enumerate().find_map(|(i, window)| {
+        days.iter()
+            .flatten()
+            .filter_map(|day| window.occurrence(*day))
+            .find(|(start, end)| *start <= now && now < *end)
+            .map(|(start, end)| (i, start, end))
+    })
+}
+
+/// Next window to open after `now`: index and opening time
+fn next_window(windows: &[ServerWindow], now: NaiveDateTime) -> Option<(usize, NaiveDateTime)> {
+    let today = now.date();
+    windows
+        .iter()
+        .enumerate()
+        .filter_map(|(i, window)| {
+            let days: Vec<NaiveDate> = match window.single_date() {
+                Some(date) => vec![date],
+                None => (0..=7).filter_map(|n| today.checked_add_days(chrono::Days::new(n))).collect(),
+            };
+            days.into_iter()
+                .filter_map(|day| window.occurrence(day))
+                .map(|(start, _)| start)
+                .find(|start| *start > now)
+                .map(|start| (i, start))
+        })
+        .min_by_key(|(_, start)| *start)
+}
+
+impl ServerScheduleSettings {
+    /// Current time in the schedule's time zone
+    fn now(&self) -> NaiveDateTime {
+        if self.utc {
+            Utc::now().naive_utc()
+        } else {
+            Local::now().naive_local()
+        }
+    }
+
+    /// Unix ms of a time in the schedule's time zone
+    fn unix_ms(&self, time: NaiveDateTime) -> Option<u64> {
+        let ms = if self.utc {
+            time.and_utc().timestamp_millis()
+        } else {
+            Local.from_local_datetime(&time).earliest()?.timestamp_millis()
+        };
+        u64::try_from(ms).ok()
+    }
+}
+
+/// Start the server (unless running) and the recording for a window that opened
+async fn open(app: &AppHandle, settings: &GlobalSettings, index: usize, start: NaiveDateTime, end: NaiveDateTime) {
+    let window = &settings.server_schedule.windows[index];
+    let name = window.label(index);
+    info!("[Schedule] \"{}\" open until {}", name, end.format("%Y-%m-%d %H:%M"));
+
+    let started_server = if crate::get_http_server_status().running {
+        false
+    } else {
+        match crate::start_http_server(app.clone(), settings.server.port).await {
+            Ok(status) => {
+                info!("[Schedule] Started the HTTP server on port {}", status.port);
+                true
+            }
+            Err(e) => {
+                warn!("[Schedule] Failed to start the HTTP server: {}", e);
+                false
+            }
+        }
+    };
+    if window.record {
+        replay::start_recording(app);
+        info!("[Schedule] Recording traffic for \"{}\"", name);
+    }
+
+    *app.state::<ServerScheduleState>().open.lock() = Some(OpenWindow {
+        key: (index, start),
+        name,
+        until: end,
+        started_server,
+        recording: window.record,
+    });
+}
+
+/// Save the recording and stop the server the scheduler started for a window that closed
+async fn close(app: &AppHandle, window: OpenWindow) {
+    info!("[Schedule] \"{}\" closed", window.name);
+
+    if window.recording {
+        let (airport, snapshots) = replay::stop_recording(app);
+        if snapshots.is_empty() {
+            info!("[Schedule] No traffic recorded for \"{}\"", window.name);
+        } else {
+            let app = app.clone();
+            let options = ShareOptions {
+                title: Some(format!("{} {}", window.name, window.key.1.format("%Y-%m-%d"))),
+                description: Some(format!("Recorded during the scheduled \"{}\" window", window.name)),
+            };
+            let file = replay::recording_file(airport, snapshots);
+            let saved = tokio::task::spawn_blocking(move || replay_library::publish_file(&app, &file, &options)).await;
+            match saved {
+                Ok(Ok(shared)) => info!("[Schedule] Saved the recording as \"{}\"", shared.manifest.title),
+                Ok(Err(e)) => warn!("[Schedule] Failed to save the recording: {}", e),
+                Err(e) => warn!("[Schedule] Failed to save the recording: {}", e),
+            }
+        }
+    }
+
+    if window.started_server && crate::stop_http_server().is_ok() {
+        info!("[Schedule] Stopped the HTTP server");
+    }
+}
+
+/// Open and close windows to match the schedule
+async fn check(app: &AppHandle) -> Result<(), String> {
+    let settings = crate::read_global_settings(app.clone())?;
+    let schedule = &settings.server_schedule;
+    let current = if schedule.enabled {
+        open_window(&schedule.windows, schedule.now())
+    } else {
+        None
+    };
+
+    let closed = {
+        let state = app.state::<ServerScheduleState>();
+        let mut open = state.open.lock();
+        let still_open = matches!((open.as_ref(), current), (Some(w), Some((i, start, _))) if w.key == (i, start));
+        if still_open {
+            return Ok(());
+        }
+        open.take()
+    };
+    if let Some(window) = closed {
+        close(app, window).await;
+    }
+    if let Some((index, start, end)) = current {
+        open(app, &settings, index, start, end).await;
+    }
+    Ok(())
+}
+
+/// Current scheduler status
+pub fn get_status(app: &AppHandle) -> ServerScheduleStatus {
+    let schedule = crate::read_global_settings(app.clone())
+        .map(|s| s.server_schedule)
+        .unwrap_or_default();
+    let next = next_window(&schedule.windows, schedule.now()).filter(|_| schedule.enabled);
+    let state = app.state::<ServerScheduleState>();
+    let open = state.open.lock();
+    ServerScheduleStatus {
+        enabled: schedule.enabled,
+        active_window: open.as_ref().map(|w| w.name.clone()),
+        active_until: open.as_ref().and_then(|w| schedule.unix_ms(w.until)),
+        started_server: open.as_ref().is_some_and(|w| w.started_server),
+        recording: open.as_ref().is_some_and(|w| w.recording),
+        next_window: next.map(|(i, _)| schedule.windows[i].label(i)),
+        next_start: next.and_then(|(_, start)| schedule.unix_ms(start)),
+    }
+}
+
+/// Initialize scheduler state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_server_schedule(app: &AppHandle) {
+    app.manage(ServerScheduleState { open: Mutex::new(None) });
+}
+
+/// Check the schedule periodically
+pub fn start_server_schedule_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn("server-schedule", Restart::Always, move || {
+        let app = app.clone();
+        async move {
+            let start = tokio::time::Instant::now() + CHECK_INTERVAL;
+            let mut tick = tokio::time::interval_at(start, CHECK_INTERVAL);
+            loop {
+                tick.tick().await;
+                let error = check(&app).await.err();
+                debug_stats::task_ran("server-schedule", CHECK_INTERVAL, error);
+            }
+        }
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the server schedule status (open window, next window)
+#[tauri::command]
+pub fn get_server_schedule_status(app: AppHandle) -> ServerScheduleStatus {
+    get_status(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str, time: &str) -> NaiveDateTime {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .unwrap()
+            .and_time(parse_time(time).unwrap())
+    }
+
+    #[test]
+    fn finds_open_and_next_windows() {
+        let fno = ServerWindow {
+            name: "FNO".to_string(),
+            days: vec!["fri".to_string()],
+            start: "23:00".to_string(),
+            end: "03:00".to_string(),
+            record: true,
+            ..Default::default()
+        };
+        let event = ServerWindow {
+            date: Some("2026-10-24".to_string()),
+            start: "18:00".to_string(),
+            end: "22:00".to_string(),
+            ..Default::default()
+        };
+        let windows = vec![fno, event];
+
+        // 2026-10-16 is a Friday; the window runs past midnight into Saturday
+        assert_eq!(open_window(&windows, at("2026-10-16", "22:59")), None);
+        assert_eq!(
+            open_window(&windows, at("2026-10-17", "02:30")),
+            Some((0, at("2026-10-16", "23:00"), at("2026-10-17", "03:00")))
+        );
+        assert_eq!(open_window(&windows, at("2026-10-17", "03:00")), None);
+        assert_eq!(open_window(&windows, at("2026-10-24", "19:00")).map(|w| w.0), Some(1));
+        assert_eq!(open_window(&windows, at("2026-10-31", "19:00")), None);
+
+        assert_eq!(next_window(&windows, at("2026-10-17", "12:00")), Some((0, at("2026-10-23", "23:00"))));
+        assert_eq!(next_window(&windows, at("2026-10-24", "00:00")), Some((1, at("2026-10-24", "18:00"))));
+        assert_eq!(windows[1].label(1), "Window 2");
+
+        // Unparseable times never open
+        let broken = ServerWindow {
+            start: "25:00".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(open_window(&[broken.clone()], at("2026-10-17", "12:00")), None);
+        assert_eq!(next_window(&[broken], at("2026-10-17", "12:00")), None);
+    }
+}
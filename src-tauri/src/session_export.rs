@@ -0,0 +1,237 @@
+//! Session export to KML, GeoJSON and CSV
+//!
+//! Converts a recorded session (the loaded replay or the instant replay
+//! buffer) into one track per aircraft: KML `gx:Track`s for Google Earth,
+//! GeoJSON LineStrings, or CSV rows grouped by aircraft for spreadsheets and
+//! event staff.
+
+use std::path::Path;
+
+use chrono::{DateTime, SecondsFormat};
+use quick_xml::escape::escape;
+use tauri::AppHandle;
+use tracing::info;
+
+use crate::error::{Error, Result};
+use crate::replay::{self, ReplaySnapshot, Track};
+
+/// Export formats
+pub const FORMAT_KML: &str = "kml";
+pub const FORMAT_GEOJSON: &str = "geojson";
+pub const FORMAT_CSV: &str = "csv";
+
+/// Export sources
+pub const SOURCE_REPLAY: &str = "replay";
+pub const SOURCE_BUFFER: &str = "buffer";
+
+const METERS_TO_FEET: f64 = 1.0 / 0.3048;
+
+/// Exported session file
+pub struct SessionExport {
+    pub file_name: String,
+    pub content_type: &'static str,
+    pub content: String,
+}
+
+/// Unix ms as an ISO 8601 UTC time
+pub fn iso_time(ms: u64) -> String {
+    DateTime::from_timestamp_millis(ms as i64)
+        .map(|t| t.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .unwrap_or_default()
+}
+
+fn round(value: f64, decimals: i32) -> f64 {
+    let factor = 10f64.powi(decimals);
+    (value * factor).round() / factor
+}
+
+/// GeoJSON FeatureCollection with a LineString per aircraft
+fn to_geojson(tracks: &[Track]) -> String {
+    let features: Vec<serde_json::Value> = tracks
+        .iter()
+        .filter(|t| t.points.len() >= 2)
+        .map(|track| {
+            serde_json::json!({
+                "type": "Feature",
+                "geometry": {
+                    "type": "LineString",
+                    "coordinates": track.points.iter()
+                        .map(|p| [round(p.longitude, 6), round(p.latitude, 6), round(p.altitude_m, 1)])
+                        .collect::<Vec<_>>(),
+                },
+                "properties": {
+                    "callsign": track.callsign,
+                    "aircraftType": track.aircraft_type,
+                    "departure": track.departure,
+                    "arrival": track.arrival,
+                    "times": track.points.iter().map(|p| p.time).collect::<Vec<_>>(),
+                },
+            })
+        })
+        .collect();
+    serde_json::json!({ "type": "FeatureCollection", "features": features }).to_string()
+}
+
+/// KML document with a time-stamped gx:Track per aircraft
+fn to_kml(tracks: &[Track], name: &str) -> String {
+    let mut kml = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<kml xmlns=\"http://www.opengis.net/kml/2.2\" xmlns:gx=\"http://www.google.com/kml/ext/2.2\">\n",
+        "<Document>\n",
+    ));
+    kml.push_str(&format!("<name>{}</name>\n", escape(name)));
+    for track in tracks {
+        let description = [&track.aircraft_type, &track.departure, &track.arrival]
+            .iter()
+            .map(|v| v.as_deref().unwrap_or("?"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        kml.push_str(&format!(
+            "<Placemark>\n<name>{}</name>\n<description>{}</description>\n<gx:Track>\n<altitudeMode>absolute</altitudeMode>\n",
+            escape(&track.callsign),
+            escape(&description)
+        ));
+        for point in &track.points {
+            kml.push_str(&format!("<when>{}</when>\n", iso_time(point.time)));
+        }
+        for point in &track.points {
+            kml.push_str(&format!(
+                "<gx:coord>{} {} {}</gx:coord>\n",
+                round(point.longitude, 6),
+                round(point.latitude, 6),
+                round(point.altitude_m, 1)
+            ));
+        }
+        kml.push_str("</gx:Track>\n</Placemark>\n");
+    }
+    kml.push_str("</Document>\n</kml>\n");
+    kml
+}
+
+/// Quote a CSV field if needed
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// CSV with one row per aircraft position, grouped by aircraft
+fn to_csv(tracks: &[Track]) -> String {
+    let mut csv = String::from(
+        "callsign,aircraft_type,departure,arrival,time_utc,latitude,longitude,altitude_ft,groundspeed_kt,heading,on_ground\n",
+    );
+    for track in tracks {
+        for point in &track.points {
+            let row = [
+                csv_field(&track.callsign),
+                csv_field(track.aircraft_type.as_deref().unwrap_or("")),
+                csv_field(track.departure.as_deref().unwrap_or("")),
+                csv_field(track.arrival.as_deref().unwrap_or("")),
+                iso_time(point.time),
+                round(point.latitude, 6).to_string(),
+                round(point.longitude, 6).to_string(),
+                (point.altitude_m * METERS_TO_FEET).round().to_string(),
+                point.groundspeed_kt.round().to_string(),
+                point.heading.round().to_string(),
+                point.on_ground.map(|g| g.to_string()).unwrap_or_default(),
+            ];
+            csv.push_str(&row.join(","));
+            csv.push('\n');
+        }
+    }
+    csv
+}
+
+/// Convert snapshots to an export format
+fn convert(snapshots: &[ReplaySnapshot], format: &str, name: &str) -> Result<(String, &'static str)> {
+    let tracks = replay::build_tracks(snapshots);
+    match format {
+        FORMAT_KML => Ok((to_kml(&tracks, name), "application/vnd.google-earth.kml+xml")),
+        FORMAT_GEOJSON => Ok((to_geojson(&tracks), "application/geo+json")),
+        FORMAT_CSV => Ok((to_csv(&tracks), "text/csv")),
+        other => Err(Error::InvalidInput(format!("Unknown export format: {}", other))),
+    }
+}
+
+/// Export the loaded replay ("replay") or the instant replay buffer ("buffer")
+pub fn export(app: &AppHandle, source: &str, format: &str) -> Result<SessionExport> {
+    let (airport, snapshots) = match source {
+        SOURCE_REPLAY => replay::loaded_recording(app).ok_or_else(|| Error::conflict("No replay loaded"))?,
+        SOURCE_BUFFER => replay::buffered_recording(app),
+        other => return Err(Error::InvalidInput(format!("Unknown export source: {}", other))),
+    };
+    let (first, last) = match (snapshots.first(), snapshots.last()) {
+        (Some(first), Some(last)) => (first.timestamp, last.timestamp),
+        _ => return Err(Error::conflict("No traffic recorded")),
+    };
+
+    let start = DateTime::from_timestamp_millis(first as i64)
+        .map(|t| t.format("%Y%m%d-%H%MZ").to_string())
+        .unwrap_or_default();
+    let base_name = format!("{}-{}", airport.as_deref().unwrap_or("session"), start);
+    let name = format!("{} ({} - {})", base_name, iso_time(first), iso_time(last));
+    let (content, content_type) = convert(&snapshots, format, &name)?;
+    Ok(SessionExport {
+        file_name: format!("{}.{}", base_name, format),
+        content_type,
+        content,
+    })
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Export the loaded replay or the instant replay buffer as KML, GeoJSON or CSV tracks to a file
+#[tauri::command]
+pub fn export_session(app: AppHandle, source: String, format: String, path: String) -> Result<String> {
+    let export = export(&app, &source, &format)?;
+    std::fs::write(Path::new(&path), export.content)
+        .map_err(|e| Error::Io(format!("Failed to write {}: {}", path, e)))?;
+    info!("[Export] Wrote {} tracks to {}", format, path);
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_snapshots_to_tracks() {
+        let snapshot = |timestamp, latitude| ReplaySnapshot {
+            timestamp,
+            vatsim_timestamp: timestamp,
+            aircraft_states: vec![
+                serde_json::json!({"callsign": "DAL1", "latitude": latitude, "longitude": -71.0,
+                    "altitude": 304.8, "groundspeed": 180.0, "heading": 90.0, "aircraftType": "B738",
+                    "departure": "KBOS", "arrival": "KATL", "onGround": 0}),
+                serde_json::json!({"callsign": "N1, \"X\"", "latitude": 42.0, "longitude": -71.1}),
+            ],
+            last_update_interval: 15_000,
+        };
+        let snapshots = vec![snapshot(1_700_000_015_000, 42.1), snapshot(1_700_000_000_000, 42.0)];
+
+        let tracks = replay::build_tracks(&snapshots);
+        assert_eq!(tracks.len(), 2);
+        assert_eq!(tracks[0].callsign, "DAL1");
+        assert_eq!(tracks[0].points[0].latitude, 42.0);
+
+        let geojson: serde_json::Value = serde_json::from_str(&to_geojson(&tracks)).unwrap();
+        assert_eq!(
+            geojson["features"][0]["geometry"]["coordinates"][1],
+            serde_json::json!([-71.0, 42.1, 304.8])
+        );
+
+        let csv = to_csv(&tracks);
+        assert_eq!(csv.lines().count(), 5);
+        assert!(csv.contains("DAL1,B738,KBOS,KATL,2023-11-14T22:13:20Z,42,-71,1000,180,90,false"));
+        assert!(csv.contains("\"N1, \"\"X\"\"\""));
+
+        let kml = to_kml(&tracks, "KBOS");
+        assert!(kml.contains("<when>2023-11-14T22:13:35Z</when>"));
+        assert!(kml.contains("<name>N1, &quot;X&quot;</name>"));
+        assert!(convert(&snapshots, "shp", "x").is_err());
+    }
+}
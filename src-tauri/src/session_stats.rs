@@ -0,0 +1,526 @@
+//! Session statistics for post-event reporting
+//!
+//! Derives movements (arrivals, departures and go-arounds per runway), peak
+//! traffic counts, average final spacing and the busiest hours from a recorded
+//! session (the loaded replay or the instant replay buffer). Movements are
+//! detected where a track changes between on the ground and airborne near the
+//! airport, and assigned to the runway the aircraft is lined up with there.
+//! Go-arounds use the live detection rule (see `go_arounds`).
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::error::{Error, Result};
+use crate::airport_db;
+use crate::geo;
+use crate::go_arounds::{self, Sample};
+use crate::replay::{self, ReplaySnapshot, Track, TrackPoint};
+use crate::runways::{self, Runway};
+use crate::session_export::{self, SOURCE_BUFFER, SOURCE_REPLAY};
+use crate::traffic;
+use crate::weather;
+
+/// Movements further than this from the airport are not counted (nm)
+const MOVEMENT_RADIUS_NM: f64 = 5.0;
+
+/// Gaps longer than this in a track are a disconnect, not a movement (ms)
+const MAX_TRACK_GAP_MS: u64 = 120_000;
+
+/// Runway assignment tolerances at liftoff/touchdown (nm)
+const RUNWAY_MAX_OFFSET_NM: f64 = 0.3;
+const RUNWAY_MAX_BEYOND_NM: f64 = 3.0;
+
+/// Consecutive arrivals further apart than this are not in sequence (ms)
+const SPACING_MAX_GAP_MS: u64 = 600_000;
+
+const HOUR_MS: u64 = 3_600_000;
+
+const METERS_TO_FEET: f64 = 1.0 / 0.3048;
+
+/// Arrival, departure or go-around
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Movement {
+    pub callsign: String,
+    /// "arrival", "departure" or "goAround"
+    pub kind: String,
+    /// Runway end (e.g., "27R"), if the aircraft was lined up with one
+    pub runway: Option<String>,
+    /// Touchdown, takeoff or go-around time (Unix ms)
+    pub time: u64,
+    pub aircraft_type: Option<String>,
+    /// Where the transition was seen (touchdown or liftoff)
+    #[serde(skip)]
+    latitude: f64,
+    #[serde(skip)]
+    longitude: f64,
+}
+
+/// Movements on one runway end
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunwayMovements {
+    pub runway: String,
+    pub arrivals: usize,
+    pub departures: usize,
+    pub go_arounds: usize,
+}
+
+/// Movements in an hour
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeriodMovements {
+    /// Start of the hour (Unix ms)
+    pub start: u64,
+    pub arrivals: usize,
+    pub departures: usize,
+    pub go_arounds: usize,
+}
+
+/// Most aircraft seen at once
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrafficPeak {
+    /// Unix ms
+    pub time: u64,
+    pub aircraft: usize,
+}
+
+/// Statistics for a recorded session
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStats {
+    pub icao: Option<String>,
+    /// "replay" or "buffer"
+    pub source: String,
+    /// First and last recorded times (Unix ms)
+    pub start_time: u64,
+    pub end_time: u64,
+    pub arrivals: usize,
+    pub departures: usize,
+    pub go_arounds: usize,
+    /// Movements per runway end ("unknown" if not lined up with a runway)
+    pub runways: Vec<RunwayMovements>,
+    /// Most aircraft in the recording at once
+    pub peak_traffic: Option<TrafficPeak>,
+    /// Most aircraft on the ground at once
+    pub peak_ground: Option<TrafficPeak>,
+    /// Average distance of the next arrival from the touchdown point when the previous one landed (nm)
+    pub average_final_spacing_nm: Option<f64>,
+    /// Average time between consecutive arrivals on the same runway (seconds)
+    pub average_final_spacing_seconds: Option<f64>,
+    /// Movements per hour, in time order
+    pub hourly: Vec<PeriodMovements>,
+    pub busiest_hour: Option<PeriodMovements>,
+    pub movements: Vec<Movement>,
+}
+
+/// Airport position and elevation
+type AirportPosition = (f64, f64, f64);
+
+fn on_ground(point: &TrackPoint, elevation_ft: f64) -> bool {
+    point
+        .on_ground
+        .unwrap_or_else(|| traffic::is_on_ground(point.altitude_m * METERS_TO_FEET, point.groundspeed_kt, elevation_ft))
+}
+
+/// Arrivals and departures along a track
+fn track_movements(track: &Track, airport: AirportPosition, runways: &[Runway]) -> Vec<Movement> {
+    let (lat, lon, elevation_ft) = airport;
+    track
+        .points
+        .windows(2)
+        .filter(|pair| pair[1].time - pair[0].time <= MAX_TRACK_GAP_MS)
+        .filter_map(|pair| {
+            let (before, after) = (on_ground(&pair[0], elevation_ft), on_ground(&pair[1], elevation_ft));
+            // The airborne side of the transition is on the runway or just off its end
+            let (kind, airborne, time) = match (before, after) {
+                (true, false) => ("departure", &pair[1], pair[1].time),
+                (false, true) => ("arrival", &pair[0], pair[1].time),
+                _ => return None,
+            };
+            let (distance_nm, _) = geo::distance_and_bearing(lat, lon, airborne.latitude, airborne.longitude);
+            if distance_nm > MOVEMENT_RADIUS_NM {
+                return None;
+            }
+            let runway = runways::aligned_runway_end(
+                runways,
+                airborne.latitude,
+                airborne.longitude,
+                airborne.heading,
+                RUNWAY_MAX_OFFSET_NM,
+                RUNWAY_MAX_BEYOND_NM,
+                RUNWAY_MAX_BEYOND_NM,
+            )
+            .map(|a| a.end.ident.clone());
+            Some(Movement {
+                callsign: track.callsign.clone(),
+                kind: kind.to_string(),
+                runway,
+                time,
+                aircraft_type: track.aircraft_type.clone(),
+                latitude: pair[1].latitude,
+                longitude: pair[1].longitude,
+            })
+        })
+        .collect()
+}
+
+/// Go-arounds along a track
+fn track_go_arounds(track: &Track, airport: AirportPosition, runways: &[Runway]) -> Vec<Movement> {
+    let (_, _, elevation_ft) = airport;
+    let mut approach = None;
+    let mut previous_time = None;
+    let mut go_arounds = Vec::new();
+    for point in &track.points {
+        // A disconnect ends the approach
+        if previous_time.is_some_and(|t| point.time - t > MAX_TRACK_GAP_MS) {
+            approach = None;
+        }
+        previous_time = Some(point.time);
+        let sample = Sample {
+            latitude: point.latitude,
+            longitude: point.longitude,
+            altitude_ft: point.altitude_m * METERS_TO_FEET,
+            track: point.heading,
+            on_ground: on_ground(point, elevation_ft),
+        };
+        if let Some(missed) = go_arounds::observe(&mut approach, sample, runways, elevation_ft) {
+            go_arounds.push(Movement {
+                callsign: track.callsign.clone(),
+                kind: "goAround".to_string(),
+                runway: Some(missed.runway),
+                time: point.time,
+                aircraft_type: track.aircraft_type.clone(),
+                latitude: point.latitude,
+                longitude: point.longitude,
+            });
+        }
+    }
+    go_arounds
+}
+
+/// Interpolated position of a track at a time, if the track covers it
+fn position_at(track: &Track, time: u64) -> Option<(f64, f64)> {
+    let after = track.points.partition_point(|p| p.time < time);
+    let b = track.points.get(after)?;
+    if b.time == time {
+        return Some((b.latitude, b.longitude));
+    }
+    let a = track.points.get(after.checked_sub(1)?)?;
+    if b.time - a.time > MAX_TRACK_GAP_MS {
+        return None;
+    }
+    let t = (time - a.time) as f64 / (b.time - a.time) as f64;
+    Some((
+        a.latitude + (b.latitude - a.latitude) * t,
+        a.longitude + (b.longitude - a.longitude) * t,
+    ))
+}
+
+/// Average spacing between consecutive arrivals on each runway (nm, seconds)
+fn final_spacing(arrivals: &[&Movement], tracks: &HashMap<&str, &Track>) -> (Option<f64>, Option<f64>) {
+    let mut by_runway: BTreeMap<&str, Vec<&Movement>> = BTreeMap::new();
+    for arrival in arrivals {
+        if let Some(runway) = arrival.runway.as_deref() {
+            by_runway.entry(runway).or_default().push(arrival);
+        }
+    }
+
+    let (mut distances, mut seconds) = (Vec::new(), Vec::new());
+    for sequence in by_runway.values_mut() {
+        sequence.sort_by_key(|m| m.time);
+        for pair in sequence.windows(2) {
+            let (leader, follower) = (pair[0], pair[1]);
+            if follower.time - leader.time > SPACING_MAX_GAP_MS {
+                continue;
+            }
+            seconds.push((follower.time - leader.time) as f64 / 1000.0);
+            let position = tracks
+                .get(follower.callsign.as_str())
+                .and_then(|track| position_at(track, leader.time));
+            if let Some((lat, lon)) = position {
+                distances.push(geo::distance_and_bearing(leader.latitude, leader.longitude, lat, lon).0);
+            }
+        }
+    }
+
+    let average = |values: &[f64]| {
+        (!values.is_empty()).then(|| (values.iter().sum::<f64>() / values.len() as f64 * 10.0).round() / 10.0)
+    };
+    (average(&distances), average(&seconds))
+}
+
+/// Movements of all tracks near an airport, in time order
+fn all_movements(tracks: &[Track], airport: AirportPosition, runways: &[Runway]) -> Vec<Movement> {
+    let mut movements: Vec<Movement> = tracks
+        .iter()
+        .flat_map(|t| {
+            let mut movements = track_movements(t, airport, runways);
+            movements.extend(track_go_arounds(t, airport, runways));
+            movements
+        })
+        .collect();
+    movements.sort_by(|a, b| (a.time, &a.callsign).cmp(&(b.time, &b.callsign)));
+    movements
+}
+
+/// Most aircraft at once, in total and on the ground
+fn traffic_peaks(tracks: &[Track], elevation_ft: f64) -> (Option<TrafficPeak>, Option<TrafficPeak>) {
+    // Aircraft (and aircraft on the ground) per recorded time
+    let mut counts: BTreeMap<u64, (usize, usize)> = BTreeMap::new();
+    for point in tracks.iter().flat_map(|t| &t.points) {
+        let count = counts.entry(point.time).or_default();
+        count.0 += 1;
+        if on_ground(point, elevation_ft) {
+            count.1 += 1;
+        }
+    }
+    let peak = |select: fn(&(usize, usize)) -> usize| {
+        counts
+            .iter()
+            .map(|(&time, count)| TrafficPeak {
+                time,
+                aircraft: select(count),
+            })
+            .filter(|p| p.aircraft > 0)
+            // Earliest of equal peaks
+            .max_by(|a, b| a.aircraft.cmp(&b.aircraft).then(b.time.cmp(&a.time)))
+    };
+    (peak(|c| c.0), peak(|c| c.1))
+}
+
+/// Build the report for a recording
+pub fn build_stats(
+    icao: Option<String>,
+    source: &str,
+    snapshots: &[ReplaySnapshot],
+    airport: Option<AirportPosition>,
+    runways: &[Runway],
+) -> SessionStats {
+    let tracks = replay::build_tracks(snapshots);
+    let movements = airport.map_or_else(Vec::new, |airport| all_movements(&tracks, airport, runways));
+    let (peak_traffic, peak_ground) = traffic_peaks(&tracks, airport.map_or(0.0, |(_, _, elevation)| elevation));
+    let by_callsign: HashMap<&str, &Track> = tracks.iter().map(|t| (t.callsign.as_str(), t)).collect();
+    let arrivals: Vec<&Movement> = movements.iter().filter(|m| m.kind == "arrival").collect();
+    let (spacing_nm, spacing_seconds) = final_spacing(&arrivals, &by_callsign);
+
+    let mut per_runway: BTreeMap<String, RunwayMovements> = BTreeMap::new();
+    let mut hourly: BTreeMap<u64, PeriodMovements> = BTreeMap::new();
+    for movement in &movements {
+        let runway = movement.runway.clone().unwrap_or_else(|| "unknown".to_string());
+        let runway = per_runway.entry(runway.clone()).or_insert(RunwayMovements {
+            runway,
+            arrivals: 0,
+            departures: 0,
+            go_arounds: 0,
+        });
+        let start = movement.time - movement.time % HOUR_MS;
+        let hour = hourly.entry(start).or_insert(PeriodMovements {
+            start,
+            arrivals: 0,
+            departures: 0,
+            go_arounds: 0,
+        });
+        match movement.kind.as_str() {
+            "arrival" => {
+                runway.arrivals += 1;
+                hour.arrivals += 1;
+            }
+            "departure" => {
+                runway.departures += 1;
+                hour.departures += 1;
+            }
+            _ => {
+                runway.go_arounds += 1;
+                hour.go_arounds += 1;
+            }
+        }
+    }
+    let hourly: Vec<PeriodMovements> = hourly.into_values().collect();
+    let busiest_hour = hourly
+        .iter()
+        .max_by(|a, b| {
+            (a.arrivals + a.departures)
+                .cmp(&(b.arrivals + b.departures))
+                .then(b.start.cmp(&a.start))
+        })
+        .cloned();
+
+    SessionStats {
+        icao,
+        source: source.to_string(),
+        start_time: snapshots.first().map_or(0, |s| s.timestamp),
+        end_time: snapshots.last().map_or(0, |s| s.timestamp),
+        arrivals: movements.iter().filter(|m| m.kind == "arrival").count(),
+        departures: movements.iter().filter(|m| m.kind == "departure").count(),
+        go_arounds: movements.iter().filter(|m| m.kind == "goAround").count(),
+        runways: per_runway.into_values().collect(),
+        peak_traffic,
+        peak_ground,
+        average_final_spacing_nm: spacing_nm,
+        average_final_spacing_seconds: spacing_seconds,
+        hourly,
+        busiest_hour,
+        movements,
+    }
+}
+
+/// Statistics for the loaded replay ("replay") or the instant replay buffer ("buffer").
+/// Without a source, the loaded replay if there is one, otherwise the buffer.
+pub async fn get_stats(app: &AppHandle, source: Option<&str>) -> Result<SessionStats> {
+    let (source, (icao, snapshots)) = match source {
+        Some(SOURCE_REPLAY) => {
+            let recording = replay::loaded_recording(app).ok_or_else(|| Error::conflict("No replay loaded"))?;
+            (SOURCE_REPLAY, recording)
+        }
+        Some(SOURCE_BUFFER) => (SOURCE_BUFFER, replay::buffered_recording(app)),
+        Some(other) => return Err(Error::InvalidInput(format!("Unknown session source: {}", other))),
+        None => match replay::loaded_recording(app) {
+            Some(recording) => (SOURCE_REPLAY, recording),
+            None => (SOURCE_BUFFER, replay::buffered_recording(app)),
+        },
+    };
+    if snapshots.is_empty() {
+        return Err(Error::conflict("No traffic recorded"));
+    }
+
+    let icao = icao.or_else(|| weather::active_airport(app));
+    let (airport, runways) = match &icao {
+        Some(icao) => (
+            airport_db::get_airport(app, icao.clone())
+                .await
+                .map_err(Error::io)?
+                .map(|a| (a.latitude, a.longitude, a.elevation_ft)),
+            runways::runways_for_airport(app, icao).await.map_err(Error::io)?,
+        ),
+        None => (None, Vec::new()),
+    };
+    Ok(build_stats(icao, source, &snapshots, airport, &runways))
+}
+
+/// Movements as CSV, one row per arrival, departure or go-around
+pub fn movements_csv(stats: &SessionStats) -> String {
+    let mut csv = String::from("callsign,kind,runway,time_utc,aircraft_type\n");
+    for movement in &stats.movements {
+        let row = [
+            session_export::csv_field(&movement.callsign),
+            movement.kind.clone(),
+            movement.runway.clone().unwrap_or_default(),
+            session_export::iso_time(movement.time),
+            session_export::csv_field(movement.aircraft_type.as_deref().unwrap_or("")),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get movement, peak traffic and spacing statistics for a recorded session
+#[tauri::command]
+pub async fn get_session_stats(app: AppHandle, source: Option<String>) -> Result<SessionStats> {
+    get_stats(&app, source.as_deref()).await
+}
+
+/// Export session statistics to a file as JSON (full report) or CSV (movements)
+#[tauri::command]
+pub async fn export_session_stats(
+    app: AppHandle,
+    source: Option<String>,
+    format: String,
+    path: String,
+) -> Result<String> {
+    let stats = get_stats(&app, source.as_deref()).await?;
+    let content = match format.as_str() {
+        "json" => serde_json::to_string_pretty(&stats)
+            .map_err(|e| Error::Internal(format!("Failed to serialize stats: {}", e)))?,
+        "csv" => movements_csv(&stats),
+        other => return Err(Error::InvalidInput(format!("Unknown export format: {}", other))),
+    };
+    std::fs::write(Path::new(&path), content).map_err(|e| Error::Io(format!("Failed to write {}: {}", path, e)))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_movements_and_spacing() {
+        let runway = runways::test_runway();
+
+        // Two arrivals on 18 a minute apart, a departure off 36
+        let state = |callsign: &str, latitude: f64, heading: f64, ground: bool| {
+            serde_json::json!({"callsign": callsign, "latitude": latitude, "longitude": -71.0,
+                "altitude": if ground { 0.0 } else { 150.0 }, "groundspeed": if ground { 20.0 } else { 140.0 },
+                "heading": heading, "onGround": u8::from(ground)})
+        };
+        let snapshot = |timestamp: u64, states: Vec<serde_json::Value>| ReplaySnapshot {
+            timestamp,
+            vatsim_timestamp: timestamp,
+            aircraft_states: states,
+            last_update_interval: 15_000,
+        };
+        let snapshots = vec![
+            snapshot(
+                0,
+                vec![
+                    state("ARR1", 42.04, 180.0, false),
+                    state("ARR2", 42.07, 180.0, false),
+                    state("DEP1", 42.0, 0.0, true),
+                ],
+            ),
+            snapshot(
+                60_000,
+                vec![
+                    state("ARR1", 42.01, 180.0, true),
+                    state("ARR2", 42.04, 180.0, false),
+                    state("DEP1", 42.01, 0.0, false),
+                ],
+            ),
+            snapshot(
+                120_000,
+                vec![state("ARR1", 42.0, 90.0, true), state("ARR2", 42.01, 180.0, true)],
+            ),
+        ];
+
+        let stats = build_stats(
+            Some("KXXX".to_string()),
+            SOURCE_BUFFER,
+            &snapshots,
+            Some((42.015, -71.0, 20.0)),
+            &[runway],
+        );
+        assert_eq!((stats.arrivals, stats.departures), (2, 1));
+        assert_eq!(
+            stats.runways,
+            vec![
+                RunwayMovements {
+                    runway: "18".to_string(),
+                    arrivals: 2,
+                    departures: 0,
+                    go_arounds: 0
+                },
+                RunwayMovements {
+                    runway: "36".to_string(),
+                    arrivals: 0,
+                    departures: 1,
+                    go_arounds: 0
+                },
+            ]
+        );
+        assert_eq!(stats.peak_traffic, Some(TrafficPeak { time: 0, aircraft: 3 }));
+        assert_eq!(stats.peak_ground.as_ref().map(|p| p.aircraft), Some(2));
+        assert_eq!(stats.average_final_spacing_seconds, Some(60.0));
+        // ARR2 was 0.03 degrees of latitude (1.8 nm) behind when ARR1 landed
+        assert_eq!(stats.average_final_spacing_nm, Some(1.8));
+        assert_eq!(stats.busiest_hour.map(|h| h.arrivals + h.departures), Some(3));
+    }
+}
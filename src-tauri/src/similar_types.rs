@@ -0,0 +1,221 @@
+//! Similar-aircraft fallback table
+//!
+//! When no model exists for an aircraft type, the matching engine tries
+//! similar types in preference order (e.g., a 737 MAX 8 is shown as a
+//! 737-800). The built-in table can be overridden per type in
+//! `similar-types.json` in the app data directory:
+//!
+//! ```json
+//! { "B38M": ["B738", "B739"], "E75L": [] }
+//! ```
+//!
+//! An override replaces the built-in list for that type; an empty list
+//! disables the fallback.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use tauri::{AppHandle, Manager};
+
+use crate::error::{Error, Result};
+use crate::matching::ModelMatcherState;
+
+/// Type designator → similar types, in preference order
+pub type SimilarTypes = BTreeMap<String, Vec<String>>;
+
+/// Built-in similar-type table
+const DEFAULT_SIMILAR_TYPES: &[(&str, &[&str])] = &[
+    // Boeing 737 family
+    ("B736", &["B737", "B738"]),
+    ("B737", &["B738", "B736"]),
+    ("B739", &["B738"]),
+    ("B37M", &["B737", "B738"]),
+    ("B38M", &["B738", "B739"]),
+    ("B39M", &["B739", "B738"]),
+    ("B3XM", &["B739", "B738"]),
+    // Airbus A320 family
+    ("A318", &["A319", "A320"]),
+    ("A319", &["A320"]),
+    ("A321", &["A320"]),
+    ("A19N", &["A319", "A20N", "A320"]),
+    ("A20N", &["A320"]),
+    ("A21N", &["A321", "A20N", "A320"]),
+    // Airbus A220
+    ("BCS1", &["BCS3"]),
+    ("BCS3", &["BCS1"]),
+    // Embraer E-Jets
+    ("E170", &["E75L", "E75S"]),
+    ("E75L", &["E75S", "E170"]),
+    ("E75S", &["E75L", "E170"]),
+    ("E190", &["E195", "E290"]),
+    ("E195", &["E190", "E295"]),
+    ("E290", &["E190", "E295"]),
+    ("E295", &["E195", "E290"]),
+    // Bombardier CRJ
+    ("CRJ1", &["CRJ2"]),
+    ("CRJ2", &["CRJ1", "CRJ7"]),
+    ("CRJ7", &["CRJ9", "CRJ2"]),
+    ("CRJ9", &["CRJX", "CRJ7"]),
+    ("CRJX", &["CRJ9"]),
+    // Turboprops
+    ("AT72", &["AT76", "AT75"]),
+    ("AT75", &["AT76", "AT72"]),
+    ("AT76", &["AT75", "AT72"]),
+    ("DH8A", &["DH8B", "DH8C"]),
+    ("DH8B", &["DH8A", "DH8C"]),
+    ("DH8C", &["DH8D", "DH8B"]),
+    ("DH8D", &["DH8C"]),
+    // Boeing widebodies
+    ("B752", &["B753"]),
+    ("B753", &["B752"]),
+    ("B762", &["B763"]),
+    ("B763", &["B764", "B762"]),
+    ("B764", &["B763"]),
+    ("B744", &["B748"]),
+    ("B748", &["B744"]),
+    ("B772", &["B77L", "B77W"]),
+    ("B77L", &["B772", "B77W"]),
+    ("B773", &["B77W", "B772"]),
+    ("B77W", &["B773", "B772"]),
+    ("B788", &["B789"]),
+    ("B789", &["B788", "B78X"]),
+    ("B78X", &["B789"]),
+    // Airbus widebodies
+    ("A332", &["A333", "A339"]),
+    ("A333", &["A332", "A339"]),
+    ("A338", &["A339", "A332"]),
+    ("A339", &["A338", "A333"]),
+    ("A343", &["A346", "A333"]),
+    ("A346", &["A343"]),
+    ("A359", &["A35K"]),
+    ("A35K", &["A359"]),
+    // General aviation
+    ("C152", &["C172"]),
+    ("C172", &["C152", "C182"]),
+    ("C182", &["C172"]),
+    ("P28A", &["C172"]),
+    ("SR20", &["SR22", "C172"]),
+    ("SR22", &["SR20", "C172"]),
+];
+
+/// Get the path to the similar-type overrides file
+pub fn get_overrides_file(app: &AppHandle) -> Result<PathBuf> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))?;
+
+    fs::create_dir_all(&app_data).map_err(|e| Error::Io(format!("Failed to create app data directory: {}", e)))?;
+
+    Ok(app_data.join("similar-types.json"))
+}
+
+/// Normalize type codes to uppercase and drop blank entries
+fn normalize(table: SimilarTypes) -> SimilarTypes {
+    table
+        .into_iter()
+        .map(|(type_code, similar)| {
+            let similar = similar
+                .iter()
+                .map(|t| t.trim().to_uppercase())
+                .filter(|t| !t.is_empty())
+                .collect();
+            (type_code.trim().to_uppercase(), similar)
+        })
+        .filter(|(type_code, _)| !type_code.is_empty())
+        .collect()
+}
+
+/// Read user overrides (empty if missing or invalid)
+pub fn read_overrides(app: &AppHandle) -> SimilarTypes {
+    get_overrides_file(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .map(normalize)
+        .unwrap_or_default()
+}
+
+/// Built-in table merged with user overrides
+pub fn effective(app: &AppHandle) -> SimilarTypes {
+    merge(read_overrides(app))
+}
+
+/// Built-in table with overrides applied (an empty list removes the type)
+fn merge(overrides: SimilarTypes) -> SimilarTypes {
+    let mut table: SimilarTypes = DEFAULT_SIMILAR_TYPES
+        .iter()
+        .map(|(type_code, similar)| (type_code.to_string(), similar.iter().map(|t| t.to_string()).collect()))
+        .collect();
+
+    for (type_code, similar) in overrides {
+        if similar.is_empty() {
+            table.remove(&type_code);
+        } else {
+            table.insert(type_code, similar);
+        }
+    }
+
+    table
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the effective similar-type table (built-in + overrides)
+#[tauri::command]
+pub fn get_similar_types(app: AppHandle) -> SimilarTypes {
+    effective(&app)
+}
+
+/// Get the user's similar-type overrides
+#[tauri::command]
+pub fn get_similar_type_overrides(app: AppHandle) -> SimilarTypes {
+    read_overrides(&app)
+}
+
+/// Replace the user's similar-type overrides
+#[tauri::command]
+pub fn set_similar_type_overrides(app: AppHandle, overrides: SimilarTypes) -> Result<()> {
+    let path = get_overrides_file(&app)?;
+    let content = serde_json::to_string_pretty(&normalize(overrides))
+        .map_err(|e| Error::Internal(format!("Failed to serialize similar types: {}", e)))?;
+    fs::write(&path, content).map_err(|e| Error::Io(format!("Failed to write similar types: {}", e)))?;
+
+    app.state::<ModelMatcherState>().invalidate();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(entries: &[(&str, &[&str])]) -> SimilarTypes {
+        entries
+            .iter()
+            .map(|(type_code, similar)| (type_code.to_string(), similar.iter().map(|t| t.to_string()).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn normalizes_overrides() {
+        let overrides: SimilarTypes =
+            serde_json::from_str(r#"{" b38m ": ["b738", " ", "B739 "], "": ["A320"]}"#).unwrap();
+        assert_eq!(normalize(overrides), table(&[("B38M", &["B738", "B739"])]));
+    }
+
+    #[test]
+    fn overrides_replace_or_remove_built_in_types() {
+        let built_in = merge(SimilarTypes::new());
+        assert_eq!(built_in["B38M"], ["B738", "B739"]);
+        assert_eq!(built_in.len(), DEFAULT_SIMILAR_TYPES.len());
+
+        let merged = merge(table(&[("B38M", &["B739"]), ("E75L", &[]), ("C208", &["C172"])]));
+        assert_eq!(merged["B38M"], ["B739"]);
+        assert!(!merged.contains_key("E75L"));
+        assert_eq!(merged["C208"], ["C172"]);
+        assert_eq!(merged["A359"], built_in["A359"]);
+    }
+}
@@ -0,0 +1,276 @@
+//! Alert sound packs
+//!
+//! Aural alerts for conflict (STCA), runway incursion and emergency squawk
+//! events are played by every display from the sound pack selected in the
+//! global settings. Packs are folders in `mods/sounds/` with WAV, OGG or MP3
+//! files; a `manifest.json` maps event types to files, otherwise files named
+//! after the event type (`conflict.wav`, `incursion.ogg`, ...) are used.
+//!
+//! ```text
+//! mods/sounds/
+//! └── kbos-tower/
+//!     ├── manifest.json   {"name": "KBOS Tower", "sounds": {"conflict": "ca.ogg"}}
+//!     ├── ca.ogg
+//!     └── emergency.wav
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use tracing::warn;
+
+use crate::error::{Error, Result};
+use crate::find_mods_root;
+
+/// Event types a pack can have a sound for
+pub const EVENT_TYPES: [&str; 3] = ["conflict", "incursion", "emergency"];
+
+/// Playable audio file extensions
+const SOUND_EXTENSIONS: [&str; 3] = ["wav", "ogg", "mp3"];
+
+/// Alert sound settings in the global settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AlertSoundSettings {
+    /// Selected sound pack id (None = no aural alerts)
+    pub pack: Option<String>,
+    /// Playback volume, 0-1 (default: 0.8)
+    pub volume: f64,
+    /// Event types not to play even if the pack has a sound for them
+    pub muted: Vec<String>,
+}
+
+impl Default for AlertSoundSettings {
+    fn default() -> Self {
+        Self {
+            pack: None,
+            volume: 0.8,
+            muted: Vec::new(),
+        }
+    }
+}
+
+/// Sound pack manifest (mods/sounds/{name}/manifest.json)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SoundPackManifest {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Sound file by event type (relative to the pack folder)
+    #[serde(default)]
+    pub sounds: BTreeMap<String, String>,
+}
+
+/// Sound file played for an event type
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SoundFile {
+    pub name: String,
+    /// URL usable by remote clients (e.g., "/api/sounds/kbos-tower/ca.ogg")
+    pub url: String,
+    pub size: u64,
+    /// Absolute path on the host (for Tauri mode)
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+/// Sound pack entry returned by /api/sounds
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SoundPackInfo {
+    pub id: String,
+    pub name: String,
+    pub author: Option<String>,
+    pub version: Option<String>,
+    pub description: Option<String>,
+    /// Sound by event type ("conflict", "incursion", "emergency")
+    pub sounds: BTreeMap<String, SoundFile>,
+}
+
+/// Get the sounds mods directory
+pub fn sounds_root(app: &AppHandle) -> PathBuf {
+    find_mods_root(app).join("sounds")
+}
+
+fn is_sound_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|ext| SOUND_EXTENSIONS.iter().any(|s| ext.eq_ignore_ascii_case(s)))
+}
+
+fn sound_file(id: &str, name: String, path: PathBuf) -> SoundFile {
+    let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    SoundFile {
+        url: format!("/api/sounds/{}/{}", id, name),
+        name,
+        size,
+        path,
+    }
+}
+
+/// Sounds of a pack folder: the manifest's mapping, or files named after the event types
+fn pack_sounds(id: &str, dir: &Path, manifest: &SoundPackManifest) -> BTreeMap<String, SoundFile> {
+    if !manifest.sounds.is_empty() {
+        return manifest
+            .sounds
+            .iter()
+            .filter(|(event, _)| EVENT_TYPES.contains(&event.as_str()))
+            .map(|(event, file)| (event, file.replace('\\', "/")))
+            .filter(|(_, file)| !file.split('/').any(|part| part == ".."))
+            .map(|(event, file)| (event, dir.join(&file), file))
+            .filter(|(_, path, _)| is_sound_file(path))
+            .map(|(event, path, file)| (event.clone(), sound_file(id, file, path)))
+            .collect();
+    }
+
+    let mut sounds = BTreeMap::new();
+    for path in fs::read_dir(dir).into_iter().flatten().filter_map(|e| e.ok()).map(|e| e.path()) {
+        let Some(stem) = path.file_stem().map(|s| s.to_string_lossy().to_lowercase()) else {
+            continue;
+        };
+        if !EVENT_TYPES.contains(&stem.as_str()) || !is_sound_file(&path) || sounds.contains_key(&stem) {
+            continue;
+        }
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        sounds.insert(stem, sound_file(id, name, path));
+    }
+    sounds
+}
+
+/// Load a sound pack folder (manifest.json + sound files)
+fn load_pack(id: &str, dir: &Path) -> Option<SoundPackInfo> {
+    let manifest_path = dir.join("manifest.json");
+    let manifest: SoundPackManifest = if manifest_path.exists() {
+        match fs::read_to_string(&manifest_path)
+            .map_err(|e| e.to_string())
+            .and_then(|c| serde_json::from_str(&c).map_err(|e| e.to_string()))
+        {
+            Ok(m) => m,
+            Err(e) => {
+                warn!("[Sounds] Invalid manifest for {}: {}", id, e);
+                return None;
+            }
+        }
+    } else {
+        SoundPackManifest::default()
+    };
+
+    let sounds = pack_sounds(id, dir, &manifest);
+    if sounds.is_empty() {
+        return None;
+    }
+
+    Some(SoundPackInfo {
+        name: manifest.name.unwrap_or_else(|| id.to_string()),
+        id: id.to_string(),
+        author: manifest.author,
+        version: manifest.version,
+        description: manifest.description,
+        sounds,
+    })
+}
+
+/// List all sound packs in a sounds folder
+fn list_in(root: &Path) -> Vec<SoundPackInfo> {
+    let mut packs: Vec<SoundPackInfo> = fs::read_dir(root)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| {
+            let id = e.file_name().into_string().ok()?;
+            load_pack(&id, &e.path())
+        })
+        .collect();
+    packs.sort_by(|a, b| a.id.cmp(&b.id));
+    packs
+}
+
+/// List all sound packs in mods/sounds/
+pub fn list_all(app: &AppHandle) -> Vec<SoundPackInfo> {
+    list_in(&sounds_root(app))
+}
+
+/// Resolve a sound file by pack id and file name.
+/// Only files mapped to an event can be resolved, so arbitrary paths are rejected.
+pub fn resolve_file(app: &AppHandle, id: &str, file: &str) -> Option<PathBuf> {
+    list_all(app)
+        .into_iter()
+        .find(|p| p.id == id)?
+        .sounds
+        .into_values()
+        .find(|s| s.name == file)
+        .map(|s| s.path)
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// List alert sound packs
+#[tauri::command]
+pub fn list_sound_packs(app: AppHandle) -> Vec<SoundPackInfo> {
+    list_all(&app)
+}
+
+/// Get the path of a sound pack file (to play through the asset protocol)
+#[tauri::command]
+pub fn get_sound_file_path(app: AppHandle, id: String, file: String) -> Result<String> {
+    resolve_file(&app, &id, &file)
+        .map(|p| p.to_string_lossy().to_string())
+        .ok_or_else(|| Error::not_found(format!("Sound file not found: {}/{}", id, file)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_event_types_to_sound_files() {
+        let root = std::env::temp_dir().join(format!("sound-packs-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        // Manifest mapping: unknown events, missing files and escapes are dropped
+        let custom = root.join("custom");
+        fs::create_dir_all(&custom).unwrap();
+        fs::write(custom.join("ca.ogg"), b"OggS").unwrap();
+        fs::write(custom.join("notes.txt"), b"").unwrap();
+        fs::write(
+            custom.join("manifest.json"),
+            r#"{"name": "Custom", "sounds": {"conflict": "ca.ogg", "incursion": "missing.wav",
+                "emergency": "../other/emergency.wav", "bell": "ca.ogg"}}"#,
+        )
+        .unwrap();
+
+        // No manifest: files named after the event types
+        let named = root.join("named");
+        fs::create_dir_all(&named).unwrap();
+        fs::write(named.join("Emergency.WAV"), b"RIFF").unwrap();
+        fs::write(named.join("incursion.mp3"), b"ID3").unwrap();
+        fs::write(named.join("chime.wav"), b"RIFF").unwrap();
+
+        // No sounds at all
+        fs::create_dir_all(root.join("empty")).unwrap();
+
+        let packs = list_in(&root);
+        assert_eq!(packs.len(), 2);
+        assert_eq!(packs[0].name, "Custom");
+        assert_eq!(packs[0].sounds.keys().collect::<Vec<_>>(), ["conflict"]);
+        assert_eq!(packs[0].sounds["conflict"].url, "/api/sounds/custom/ca.ogg");
+        assert_eq!(packs[1].name, "named");
+        assert_eq!(packs[1].sounds.keys().collect::<Vec<_>>(), ["emergency", "incursion"]);
+        assert_eq!(packs[1].sounds["emergency"].name, "Emergency.WAV");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,221 @@
+//! Emergency and special squawk detection
+//!
+//! After every traffic poll, transponder codes from the data feed are checked
+//! for the emergency codes (7500 unlawful interference, 7600 radio failure,
+//! 7700 general emergency) and, for airborne aircraft, against the code
+//! assigned in the flight plan. Alerts are published as a whole list (Tauri
+//! event `squawk-alerts`, `/api/squawks/ws`) so every display can flag the
+//! aircraft; emergencies are high priority, mismatches normal priority.
+
+use std::collections::HashMap;
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::info;
+
+use crate::scripts;
+use crate::traffic::{Aircraft, TrafficSnapshot};
+
+/// Alert kinds
+pub const KIND_HIJACK: &str = "hijack";
+pub const KIND_RADIO_FAILURE: &str = "radioFailure";
+pub const KIND_EMERGENCY: &str = "emergency";
+pub const KIND_MISMATCH: &str = "mismatch";
+
+/// Special transponder code, or squawk not matching the assigned code
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SquawkAlert {
+    pub callsign: String,
+    /// "hijack" (7500), "radioFailure" (7600), "emergency" (7700) or "mismatch"
+    pub kind: String,
+    /// "high" for emergency codes, "normal" for mismatches
+    pub priority: String,
+    pub squawk: String,
+    /// Code assigned in the flight plan
+    pub assigned_squawk: Option<String>,
+    /// When the alert was first raised (Unix ms)
+    pub since: u64,
+}
+
+/// Active squawk alerts at the airport
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SquawkAlerts {
+    pub icao: Option<String>,
+    /// Time of the traffic picture the alerts are based on (Unix ms)
+    pub updated_at: Option<u64>,
+    pub alerts: Vec<SquawkAlert>,
+}
+
+/// Squawk alert state (managed by Tauri)
+pub struct SquawkAlertState {
+    alerts: RwLock<SquawkAlerts>,
+    /// Alert updates for WebSocket relay
+    pub events: broadcast::Sender<SquawkAlerts>,
+}
+
+impl SquawkAlertState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            alerts: RwLock::new(SquawkAlerts::default()),
+            events,
+        }
+    }
+}
+
+/// Alert kind for an aircraft's current squawk, if any
+fn alert_kind(aircraft: &Aircraft) -> Option<&'static str> {
+    match aircraft.squawk.as_str() {
+        "7500" => return Some(KIND_HIJACK),
+        "7600" => return Some(KIND_RADIO_FAILURE),
+        "7700" => return Some(KIND_EMERGENCY),
+        _ => {}
+    }
+    // Pilots often set the code only once airborne; unassigned codes are "0000"
+    let assigned = aircraft
+        .assigned_squawk
+        .as_deref()
+        .filter(|s| !s.is_empty() && *s != "0000")?;
+    (!aircraft.on_ground && aircraft.squawk != assigned).then_some(KIND_MISMATCH)
+}
+
+/// Squawk alerts for the aircraft, most urgent first
+pub fn detect(aircraft: &[Aircraft], now: u64, previous: &[SquawkAlert]) -> Vec<SquawkAlert> {
+    let since: HashMap<(&str, &str), u64> = previous
+        .iter()
+        .map(|a| ((a.callsign.as_str(), a.kind.as_str()), a.since))
+        .collect();
+    let mut alerts: Vec<SquawkAlert> = aircraft
+        .iter()
+        .filter_map(|a| {
+            let kind = alert_kind(a)?;
+            Some(SquawkAlert {
+                callsign: a.callsign.clone(),
+                kind: kind.to_string(),
+                priority: if kind == KIND_MISMATCH { "normal" } else { "high" }.to_string(),
+                squawk: a.squawk.clone(),
+                assigned_squawk: a.assigned_squawk.clone(),
+                since: since.get(&(a.callsign.as_str(), kind)).copied().unwrap_or(now),
+            })
+        })
+        .collect();
+    // "high" sorts before "normal"
+    alerts.sort_by(|a, b| (&a.priority, a.since, &a.callsign).cmp(&(&b.priority, b.since, &b.callsign)));
+    alerts
+}
+
+fn publish(app: &AppHandle, alerts: SquawkAlerts) {
+    let _ = app.emit("squawk-alerts", &alerts);
+    scripts::alert(app, "squawk", &alerts);
+    let state = app.state::<SquawkAlertState>();
+    let _ = state.events.send(alerts.clone());
+    *state.alerts.write() = alerts;
+}
+
+/// Update the alerts from a traffic poll
+pub fn update(app: &AppHandle, snapshot: &TrafficSnapshot) {
+    let now = snapshot.updated_at.unwrap_or_default();
+    let current = get_alerts(app);
+    let alerts = detect(&snapshot.aircraft, now, &current.alerts);
+    for alert in alerts.iter().filter(|a| a.since == now) {
+        info!(
+            "[Squawk] {} squawking {} ({}{})",
+            alert.callsign,
+            alert.squawk,
+            alert.kind,
+            alert
+                .assigned_squawk
+                .as_ref()
+                .filter(|_| alert.kind == KIND_MISMATCH)
+                .map(|s| format!(", assigned {}", s))
+                .unwrap_or_default()
+        );
+    }
+    if alerts == current.alerts && current.icao == snapshot.icao {
+        return;
+    }
+    publish(
+        app,
+        SquawkAlerts {
+            icao: snapshot.icao.clone(),
+            updated_at: snapshot.updated_at,
+            alerts,
+        },
+    );
+}
+
+/// Drop all alerts (no active airport or traffic)
+pub fn clear(app: &AppHandle) {
+    let current = get_alerts(app);
+    if current.icao.is_some() || !current.alerts.is_empty() {
+        publish(app, SquawkAlerts::default());
+    }
+}
+
+/// Current alerts
+pub fn get_alerts(app: &AppHandle) -> SquawkAlerts {
+    app.state::<SquawkAlertState>().alerts.read().clone()
+}
+
+/// Initialize squawk alert state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_squawk_alerts(app: &AppHandle) {
+    app.manage(SquawkAlertState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the active emergency and squawk mismatch alerts
+#[tauri::command]
+pub fn get_squawk_alerts(app: AppHandle) -> SquawkAlerts {
+    get_alerts(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traffic::test_aircraft;
+
+    #[test]
+    fn flags_emergencies_and_mismatches() {
+        let aircraft = |callsign: &str, squawk: &str, assigned: Option<&str>, on_ground: bool| Aircraft {
+            altitude_ft: if on_ground { 20.0 } else { 3000.0 },
+            groundspeed_kt: if on_ground { 0.0 } else { 200.0 },
+            squawk: squawk.to_string(),
+            assigned_squawk: assigned.map(str::to_string),
+            distance_nm: 5.0,
+            on_ground,
+            ..test_aircraft(callsign)
+        };
+        let traffic = vec![
+            aircraft("WRONG", "1200", Some("4521"), false),
+            aircraft("NORDO", "7600", Some("4522"), false),
+            aircraft("PARKED", "2000", Some("4523"), true),
+            aircraft("GOOD", "4524", Some("4524"), false),
+            aircraft("VFR", "1200", Some("0000"), false),
+        ];
+
+        let alerts = detect(&traffic, 1_000, &[]);
+        let summary: Vec<(&str, &str, &str)> = alerts
+            .iter()
+            .map(|a| (a.callsign.as_str(), a.kind.as_str(), a.priority.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("NORDO", KIND_RADIO_FAILURE, "high"),
+                ("WRONG", KIND_MISMATCH, "normal")
+            ]
+        );
+
+        // Alerts keep their start time while they last
+        let alerts = detect(&traffic, 16_000, &alerts);
+        assert!(alerts.iter().all(|a| a.since == 1_000));
+    }
+}
@@ -0,0 +1,126 @@
+//! Startup profiling
+//!
+//! Records how long each startup phase takes (subsystem init in the setup
+//! closure, settings load and server auto-start), plus the first build of the
+//! lazily created indexes (mod registry, FSLTL model matcher) when something
+//! first needs them. Expensive scans stay out of the setup closure so the
+//! window appears quickly; the profile shows where the time goes when it
+//! doesn't. Available via `get_startup_profile` and `/api/diagnostics/startup`.
+
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use serde::Serialize;
+use tracing::info;
+
+/// When `run()` was entered; phase start times are relative to this
+static PROCESS_START: OnceLock<Instant> = OnceLock::new();
+
+static PHASES: Mutex<Vec<StartupPhase>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupPhase {
+    pub name: String,
+    /// Milliseconds after launch the phase started
+    pub started_ms: f64,
+    pub duration_ms: f64,
+    /// Ran after the window appeared (first use or background work)
+    pub deferred: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartupProfile {
+    /// Milliseconds since launch
+    pub uptime_ms: f64,
+    /// Phases in the order they finished
+    pub phases: Vec<StartupPhase>,
+}
+
+fn millis_since_start(at: Instant) -> f64 {
+    let start = *PROCESS_START.get_or_init(Instant::now);
+    at.saturating_duration_since(start).as_secs_f64() * 1000.0
+}
+
+/// Mark the start of the process. Call first thing in `run()`.
+pub fn mark_process_start() {
+    PROCESS_START.get_or_init(Instant::now);
+}
+
+/// Record a phase that started at `started` and just finished
+pub fn record(name: &str, started: Instant, deferred: bool) {
+    let phase = StartupPhase {
+        name: name.to_string(),
+        started_ms: millis_since_start(started),
+        duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+        deferred,
+    };
+    info!(
+        "[Startup] {}: {:.1} ms{}",
+        phase.name,
+        phase.duration_ms,
+        if deferred { " (deferred)" } else { "" }
+    );
+    if let Ok(mut phases) = PHASES.lock() {
+        phases.push(phase);
+    }
+}
+
+/// Record a deferred phase only the first time it runs (e.g., the first mod
+/// index build, not every rebuild after a change)
+pub fn record_first(name: &str, started: Instant) {
+    let seen = PHASES
+        .lock()
+        .map(|phases| phases.iter().any(|p| p.name == name))
+        .unwrap_or(true);
+    if !seen {
+        record(name, started, true);
+    }
+}
+
+/// Run a startup phase and record how long it took
+pub fn time<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    let started = Instant::now();
+    let result = f();
+    record(name, started, false);
+    result
+}
+
+pub fn get_profile() -> StartupProfile {
+    StartupProfile {
+        uptime_ms: millis_since_start(Instant::now()),
+        phases: PHASES.lock().map(|phases| phases.clone()).unwrap_or_default(),
+    }
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get startup phase timings
+#[tauri::command]
+pub fn get_startup_profile() -> StartupProfile {
+    get_profile()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_first_build_once() {
+        mark_process_start();
+        assert_eq!(time("test phase", || 42), 42);
+        let started = Instant::now();
+        record_first("test lazy build", started);
+        record_first("test lazy build", started);
+
+        let profile = get_profile();
+        let lazy: Vec<_> = profile.phases.iter().filter(|p| p.name == "test lazy build").collect();
+        assert_eq!(lazy.len(), 1);
+        assert!(lazy[0].deferred);
+        assert!(profile.phases.iter().any(|p| p.name == "test phase" && !p.deferred));
+        assert!(profile.uptime_ms >= lazy[0].started_ms);
+    }
+}
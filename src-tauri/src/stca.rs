@@ -0,0 +1,283 @@
+//! Short-term conflict alert (STCA) for airborne traffic
+//!
+//! After every traffic poll, airborne aircraft inside the tower's airspace
+//! (a cylinder around the active airport) are projected up to two minutes
+//! ahead along their current track, groundspeed and vertical rate. Pairs that
+//! are predicted to come within the lateral and vertical thresholds at the
+//! same time raise an alert. Alerts are published as a whole list (Tauri
+//! event `stca-alerts`, `/api/stca/ws`) so clients can flash the datablocks
+//! of the callsigns involved.
+
+use std::collections::HashMap;
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::info;
+
+use crate::scripts;
+use crate::traffic::{Aircraft, TrafficSnapshot};
+
+/// Tower airspace: radius around the airport (nm) and ceiling above it (ft)
+const AIRSPACE_RADIUS_NM: f64 = 10.0;
+const AIRSPACE_CEILING_AGL_FT: f64 = 5000.0;
+
+/// Predicted separation below both of these is a conflict
+const LATERAL_THRESHOLD_NM: f64 = 1.0;
+const VERTICAL_THRESHOLD_FT: f64 = 500.0;
+
+/// How far ahead trajectories are projected, and in which steps (seconds)
+const LOOK_AHEAD_SECS: u32 = 120;
+const STEP_SECS: u32 = 5;
+
+/// Conflicts predicted within this time are warnings, later ones cautions (seconds)
+const WARNING_SECS: u32 = 60;
+
+/// Predicted conflict between two aircraft
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StcaAlert {
+    /// Callsigns involved (sorted)
+    pub callsigns: [String; 2],
+    /// "warning" (conflict within 60 s) or "caution"
+    pub severity: String,
+    /// Time until separation is first lost (0 = already lost)
+    pub time_to_conflict_secs: u32,
+    /// Closest predicted separation while in conflict
+    pub min_lateral_nm: f64,
+    pub min_vertical_ft: f64,
+    /// Current separation
+    pub lateral_nm: f64,
+    pub vertical_ft: f64,
+    /// When the alert was first raised (Unix ms)
+    pub since: u64,
+}
+
+/// Active alerts at the airport
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StcaAlerts {
+    pub icao: Option<String>,
+    /// Time of the traffic picture the alerts are based on (Unix ms)
+    pub updated_at: Option<u64>,
+    pub alerts: Vec<StcaAlert>,
+}
+
+/// STCA state (managed by Tauri)
+pub struct StcaState {
+    alerts: RwLock<StcaAlerts>,
+    /// Alert updates for WebSocket relay
+    pub events: broadcast::Sender<StcaAlerts>,
+}
+
+impl StcaState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            alerts: RwLock::new(StcaAlerts::default()),
+            events,
+        }
+    }
+}
+
+/// Projected position in a local plane around the airport
+#[derive(Debug, Clone, Copy)]
+struct Projection {
+    /// nm east / north of the airport
+    x: f64,
+    y: f64,
+    altitude_ft: f64,
+    /// nm per second east / north
+    vx: f64,
+    vy: f64,
+    /// Feet per second
+    vz: f64,
+}
+
+impl Projection {
+    fn new(aircraft: &Aircraft, airport_lat: f64, airport_lon: f64) -> Self {
+        let track = aircraft.track.unwrap_or(aircraft.heading).to_radians();
+        let speed = aircraft.groundspeed_kt / 3600.0;
+        Self {
+            x: (aircraft.longitude - airport_lon) * 60.0 * airport_lat.to_radians().cos(),
+            y: (aircraft.latitude - airport_lat) * 60.0,
+            altitude_ft: aircraft.altitude_ft,
+            vx: speed * track.sin(),
+            vy: speed * track.cos(),
+            vz: aircraft.vertical_rate_fpm.unwrap_or(0.0) / 60.0,
+        }
+    }
+
+    /// Lateral (nm) and vertical (ft) separation from another aircraft after `t` seconds
+    fn separation(&self, other: &Projection, t: f64) -> (f64, f64) {
+        let dx = (self.x + self.vx * t) - (other.x + other.vx * t);
+        let dy = (self.y + self.vy * t) - (other.y + other.vy * t);
+        let dz = (self.altitude_ft + self.vz * t) - (other.altitude_ft + other.vz * t);
+        (dx.hypot(dy), dz.abs())
+    }
+}
+
+fn round(value: f64, decimals: i32) -> f64 {
+    let factor = 10f64.powi(decimals);
+    (value * factor).round() / factor
+}
+
+/// Predict the conflict between two aircraft, if any
+fn predict(a: &Projection, b: &Projection) -> Option<(u32, f64, f64)> {
+    let mut conflict: Option<(u32, f64, f64)> = None;
+    for t in (0..=LOOK_AHEAD_SECS).step_by(STEP_SECS as usize) {
+        let (lateral, vertical) = a.separation(b, t as f64);
+        if lateral < LATERAL_THRESHOLD_NM && vertical < VERTICAL_THRESHOLD_FT {
+            let (start, min_lateral, min_vertical) = conflict.unwrap_or((t, lateral, vertical));
+            conflict = Some((start, min_lateral.min(lateral), min_vertical.min(vertical)));
+        }
+    }
+    conflict
+}
+
+/// Conflicts between airborne aircraft in the tower's airspace
+pub fn detect(aircraft: &[Aircraft], airport: (f64, f64, f64), now: u64, previous: &[StcaAlert]) -> Vec<StcaAlert> {
+    let (airport_lat, airport_lon, elevation_ft) = airport;
+    let airborne: Vec<(&Aircraft, Projection)> = aircraft
+        .iter()
+        .filter(|a| {
+            !a.on_ground
+                && a.distance_nm <= AIRSPACE_RADIUS_NM
+                && a.altitude_ft - elevation_ft <= AIRSPACE_CEILING_AGL_FT
+        })
+        .map(|a| (a, Projection::new(a, airport_lat, airport_lon)))
+        .collect();
+    let since: HashMap<&[String; 2], u64> = previous.iter().map(|a| (&a.callsigns, a.since)).collect();
+
+    let mut alerts = Vec::new();
+    for (i, (first, a)) in airborne.iter().enumerate() {
+        for (second, b) in &airborne[i + 1..] {
+            let Some((time, min_lateral, min_vertical)) = predict(a, b) else {
+                continue;
+            };
+            let mut callsigns = [first.callsign.clone(), second.callsign.clone()];
+            callsigns.sort();
+            let (lateral, vertical) = a.separation(b, 0.0);
+            alerts.push(StcaAlert {
+                severity: if time <= WARNING_SECS { "warning" } else { "caution" }.to_string(),
+                time_to_conflict_secs: time,
+                min_lateral_nm: round(min_lateral, 2),
+                min_vertical_ft: min_vertical.round(),
+                lateral_nm: round(lateral, 2),
+                vertical_ft: vertical.round(),
+                since: since.get(&callsigns).copied().unwrap_or(now),
+                callsigns,
+            });
+        }
+    }
+    alerts.sort_by(|a, b| (a.time_to_conflict_secs, &a.callsigns).cmp(&(b.time_to_conflict_secs, &b.callsigns)));
+    alerts
+}
+
+fn publish(app: &AppHandle, alerts: StcaAlerts) {
+    let _ = app.emit("stca-alerts", &alerts);
+    scripts::alert(app, "stca", &alerts);
+    let state = app.state::<StcaState>();
+    let _ = state.events.send(alerts.clone());
+    *state.alerts.write() = alerts;
+}
+
+/// Update the alerts from a traffic poll (airport position and elevation in feet)
+pub fn update(app: &AppHandle, snapshot: &TrafficSnapshot, airport: (f64, f64, f64)) {
+    let now = snapshot.updated_at.unwrap_or_default();
+    let current = get_alerts(app);
+    let alerts = detect(&snapshot.aircraft, airport, now, &current.alerts);
+    for alert in alerts.iter().filter(|a| a.since == now) {
+        info!(
+            "[STCA] {} / {}: {} in {} s",
+            alert.callsigns[0], alert.callsigns[1], alert.severity, alert.time_to_conflict_secs
+        );
+    }
+    // Nothing to tell clients
+    if alerts.is_empty() && current.alerts.is_empty() && current.icao == snapshot.icao {
+        return;
+    }
+    publish(
+        app,
+        StcaAlerts {
+            icao: snapshot.icao.clone(),
+            updated_at: snapshot.updated_at,
+            alerts,
+        },
+    );
+}
+
+/// Drop all alerts (no active airport or traffic)
+pub fn clear(app: &AppHandle) {
+    let current = get_alerts(app);
+    if current.icao.is_some() || !current.alerts.is_empty() {
+        publish(app, StcaAlerts::default());
+    }
+}
+
+/// Current alerts
+pub fn get_alerts(app: &AppHandle) -> StcaAlerts {
+    app.state::<StcaState>().alerts.read().clone()
+}
+
+/// Initialize STCA state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_stca(app: &AppHandle) {
+    app.manage(StcaState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the active short-term conflict alerts
+#[tauri::command]
+pub fn get_stca_alerts(app: AppHandle) -> StcaAlerts {
+    get_alerts(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traffic::test_aircraft;
+
+    #[test]
+    fn predicts_converging_traffic() {
+        let aircraft = |callsign: &str, latitude: f64, altitude_ft: f64, track: f64| Aircraft {
+            latitude,
+            altitude_ft,
+            heading: track,
+            track: Some(track),
+            vertical_rate_fpm: Some(0.0),
+            distance_nm: 3.0,
+            ..test_aircraft(callsign)
+        };
+        let airport = (42.0, -71.0, 20.0);
+
+        // Head-on, 5.4 nm apart at 180 kt each: separation lost after 44 s
+        let traffic = vec![
+            aircraft("NORTH", 41.95, 2000.0, 0.0),
+            aircraft("SOUTH", 42.04, 2200.0, 180.0),
+            aircraft("HIGH", 42.04, 4000.0, 180.0),
+        ];
+        let alerts = detect(&traffic, airport, 1_000, &[]);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].callsigns, ["NORTH".to_string(), "SOUTH".to_string()]);
+        assert_eq!(alerts[0].severity, "warning");
+        assert_eq!(alerts[0].time_to_conflict_secs, 45);
+        assert_eq!(alerts[0].min_vertical_ft, 200.0);
+        assert_eq!(alerts[0].since, 1_000);
+
+        // The alert keeps its start time while it lasts
+        let alerts = detect(&traffic, airport, 16_000, &alerts);
+        assert_eq!(alerts[0].since, 1_000);
+
+        // Diverging traffic is fine
+        let diverging = vec![
+            aircraft("NORTH", 42.05, 2000.0, 0.0),
+            aircraft("SOUTH", 41.95, 2000.0, 180.0),
+        ];
+        assert!(detect(&diverging, airport, 1_000, &[]).is_empty());
+    }
+}
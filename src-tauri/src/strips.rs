@@ -0,0 +1,259 @@
+//! Departure and arrival strips from VATSIM flight plans
+//!
+//! Joins the flight plans of connected pilots and prefiles in the VATSIM data
+//! feed with an airport to build a departure list (sorted by off-block time)
+//! and an arrival list (sorted by ETA) for a strip-bay panel. Departures that
+//! have already left the ground are dropped; arrivals stay until they
+//! disconnect.
+
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::error::{Error, Result};
+use crate::airport_db;
+use crate::geo;
+use crate::traffic::{self, VatsimData, VatsimFlightPlan};
+use crate::weather;
+
+/// Departures further than this from the airport are not at the gate (nm)
+const DEPARTURE_MAX_DISTANCE_NM: f64 = 5.0;
+
+/// Below this groundspeed the ETA comes from the flight plan rather than the distance to go (kt)
+const ETA_MIN_GROUNDSPEED_KT: f64 = 50.0;
+
+/// Flight strip
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Strip {
+    pub callsign: String,
+    /// "connected" or "prefiled"
+    pub status: String,
+    /// "I" (IFR) or "V" (VFR)
+    pub flight_rules: String,
+    pub aircraft_type: Option<String>,
+    pub departure: String,
+    pub arrival: String,
+    /// Filed cruise altitude (as filed, e.g., "35000" or "FL350")
+    pub cruise_altitude: String,
+    pub route: String,
+    pub assigned_squawk: Option<String>,
+    /// Estimated off-block time (Unix ms)
+    pub eobt: Option<u64>,
+    /// Estimated arrival time (Unix ms)
+    pub eta: Option<u64>,
+    /// Distance from the airport for connected pilots (nm)
+    pub distance_nm: Option<f64>,
+    pub groundspeed_kt: Option<f64>,
+    pub on_ground: bool,
+}
+
+/// Strips for an airport
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StripBay {
+    pub icao: String,
+    /// Time the strips were built (Unix ms)
+    pub updated_at: u64,
+    pub departures: Vec<Strip>,
+    pub arrivals: Vec<Strip>,
+}
+
+/// Airport position and elevation
+type AirportPosition = (f64, f64, f64);
+
+/// Parse an "HHMM" time or duration
+fn parse_hhmm(value: &str) -> Option<(i64, i64)> {
+    let value = value.trim();
+    if value.len() != 4 || !value.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = (value[..2].parse().ok()?, value[2..].parse().ok()?);
+    (hours < 100 && minutes < 60).then_some((hours, minutes))
+}
+
+/// Resolve a time of day to the occurrence closest to now
+fn closest_occurrence(hhmm: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let (hours, minutes) = parse_hhmm(hhmm)?;
+    let time = NaiveTime::from_hms_opt(hours as u32, minutes as u32, 0)?;
+    let today = now.date_naive().and_time(time).and_utc();
+    [today - Duration::days(1), today, today + Duration::days(1)]
+        .into_iter()
+        .min_by_key(|t| (*t - now).num_seconds().abs())
+}
+
+fn to_ms(time: DateTime<Utc>) -> u64 {
+    time.timestamp_millis().max(0) as u64
+}
+
+fn upper(value: &str) -> String {
+    value.trim().to_uppercase()
+}
+
+fn strip(callsign: &str, plan: &VatsimFlightPlan, connected: bool, now: DateTime<Utc>) -> Strip {
+    let eobt = closest_occurrence(&plan.deptime, now);
+    let eta = eobt
+        .zip(parse_hhmm(&plan.enroute_time))
+        .map(|(eobt, (hours, minutes))| eobt + Duration::minutes(hours * 60 + minutes));
+    Strip {
+        callsign: callsign.to_string(),
+        status: if connected { "connected" } else { "prefiled" }.to_string(),
+        flight_rules: upper(&plan.flight_rules),
+        aircraft_type: Some(upper(&plan.aircraft_short)).filter(|t| !t.is_empty()),
+        departure: upper(&plan.departure),
+        arrival: upper(&plan.arrival),
+        cruise_altitude: plan.altitude.trim().to_string(),
+        route: plan.route.trim().to_string(),
+        assigned_squawk: Some(plan.assigned_transponder.trim().to_string()).filter(|s| !s.is_empty() && s != "0000"),
+        eobt: eobt.map(to_ms),
+        eta: eta.map(to_ms),
+        distance_nm: None,
+        groundspeed_kt: None,
+        on_ground: !connected,
+    }
+}
+
+/// Build the departure and arrival lists for an airport from the data feed
+pub fn build_strips(icao: &str, data: &VatsimData, airport: Option<AirportPosition>, now: DateTime<Utc>) -> StripBay {
+    let mut departures = Vec::new();
+    let mut arrivals = Vec::new();
+
+    for pilot in &data.pilots {
+        let Some(plan) = pilot.flight_plan.as_ref() else {
+            continue;
+        };
+        let (is_departure, is_arrival) = (upper(&plan.departure) == icao, upper(&plan.arrival) == icao);
+        if !is_departure && !is_arrival {
+            continue;
+        }
+
+        let mut strip = strip(&pilot.callsign, plan, true, now);
+        let distance_nm =
+            airport.map(|(lat, lon, _)| geo::distance_and_bearing(lat, lon, pilot.latitude, pilot.longitude).0);
+        strip.on_ground = traffic::is_on_ground(
+            pilot.altitude,
+            pilot.groundspeed,
+            airport.map_or(pilot.altitude, |(_, _, elevation)| elevation),
+        );
+        strip.distance_nm = distance_nm.map(|d| (d * 10.0).round() / 10.0);
+        strip.groundspeed_kt = Some(pilot.groundspeed);
+
+        if is_departure && strip.on_ground && distance_nm.map_or(true, |d| d <= DEPARTURE_MAX_DISTANCE_NM) {
+            departures.push(strip.clone());
+        }
+        if is_arrival && !(is_departure && strip.on_ground) {
+            if let Some(distance_nm) = distance_nm.filter(|_| pilot.groundspeed >= ETA_MIN_GROUNDSPEED_KT) {
+                let to_go = Duration::seconds((distance_nm / pilot.groundspeed * 3600.0) as i64);
+                strip.eta = Some(to_ms(now + to_go));
+            }
+            arrivals.push(strip);
+        }
+    }
+
+    for prefile in &data.prefiles {
+        let Some(plan) = prefile.flight_plan.as_ref() else {
+            continue;
+        };
+        if upper(&plan.departure) == icao {
+            departures.push(strip(&prefile.callsign, plan, false, now));
+        }
+        if upper(&plan.arrival) == icao {
+            arrivals.push(strip(&prefile.callsign, plan, false, now));
+        }
+    }
+
+    // Unknown times last
+    departures.sort_by_key(|s| (s.eobt.is_none(), s.eobt, s.callsign.clone()));
+    arrivals.sort_by_key(|s| (s.eta.is_none(), s.eta, s.callsign.clone()));
+    StripBay {
+        icao: icao.to_string(),
+        updated_at: to_ms(now),
+        departures,
+        arrivals,
+    }
+}
+
+/// Departure and arrival strips for an airport
+pub async fn get_strips(app: &AppHandle, icao: &str) -> Result<StripBay> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    let data = traffic::vatsim_feed(app).await.map_err(Error::Upstream)?;
+    let airport = airport_db::get_airport(app, icao.clone())
+        .await
+        .ok()
+        .flatten()
+        .map(|a| (a.latitude, a.longitude, a.elevation_ft));
+    Ok(build_strips(&icao, &data, airport, Utc::now()))
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get departure and arrival strips for an airport from VATSIM flight plans
+#[tauri::command]
+pub async fn get_airport_strips(app: AppHandle, icao: String) -> Result<StripBay> {
+    get_strips(&app, &icao).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traffic::{VatsimPilot, VatsimPrefile};
+
+    #[test]
+    fn builds_departure_and_arrival_lists() {
+        let plan = |departure: &str, arrival: &str, deptime: &str| VatsimFlightPlan {
+            flight_rules: "I".to_string(),
+            aircraft_short: "A320".to_string(),
+            departure: departure.to_string(),
+            arrival: arrival.to_string(),
+            deptime: deptime.to_string(),
+            enroute_time: "0130".to_string(),
+            ..Default::default()
+        };
+        let pilot = |callsign: &str, latitude: f64, altitude: f64, groundspeed: f64, plan| VatsimPilot {
+            cid: 1_000_000,
+            callsign: callsign.to_string(),
+            latitude,
+            longitude: -71.0,
+            altitude,
+            groundspeed,
+            heading: 0.0,
+            transponder: "2000".to_string(),
+            flight_plan: Some(plan),
+            last_updated: String::new(),
+        };
+        let data = VatsimData {
+            pilots: vec![
+                pilot("GATE1", 42.36, 20.0, 0.0, plan("KBOS", "KJFK", "1230")),
+                pilot("GONE1", 42.9, 9000.0, 280.0, plan("KBOS", "KJFK", "1100")),
+                pilot("INBD1", 42.86, 11000.0, 300.0, plan("KJFK", "KBOS", "1100")),
+            ],
+            controllers: Vec::new(),
+            atis: Vec::new(),
+            prefiles: vec![VatsimPrefile {
+                callsign: "PRE1".to_string(),
+                flight_plan: Some(plan("KBOS", "KORD", "1215")),
+            }],
+        };
+        let now = DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let bay = build_strips("KBOS", &data, Some((42.36, -71.0, 20.0)), now);
+        let departures: Vec<&str> = bay.departures.iter().map(|s| s.callsign.as_str()).collect();
+        assert_eq!(departures, vec!["PRE1", "GATE1"]);
+        assert_eq!(bay.departures[0].status, "prefiled");
+        assert_eq!(bay.departures[0].eobt, Some(to_ms(now + Duration::minutes(15))));
+
+        // 30 nm to go at 300 kt: 6 minutes
+        assert_eq!(bay.arrivals.len(), 1);
+        assert_eq!(bay.arrivals[0].eta, Some(to_ms(now + Duration::minutes(6))));
+
+        // Off-block times resolve to the closest day
+        let late = DateTime::parse_from_rfc3339("2024-05-01T23:50:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(closest_occurrence("0010", late), Some(late + Duration::minutes(20)));
+    }
+}
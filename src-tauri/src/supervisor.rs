@@ -0,0 +1,305 @@
+//! Background task supervisor
+//!
+//! Owns the app's long-running background work: the traffic, weather and AFV
+//! pollers, the replay clock, the vNAS listener and batcher, the mods folder
+//! watcher, federation, the script engine, gamepad/MIDI input and mock
+//! traffic. Each task is started by name with [`spawn`] (async) or
+//! [`spawn_blocking`] (a loop on its own thread). When a
+//! task panics, or a loop that should run for the app's lifetime returns, the
+//! supervisor logs it and starts it again after an exponential backoff (1 s,
+//! doubling up to a minute, reset once a run lasts five minutes), so one bad
+//! feed response can't silently stop traffic updates until the next launch.
+//!
+//! Task state, restart counts and the last failure, joined with the run
+//! heartbeats tasks report to `debug_stats`, are available via
+//! `get_task_status` and `/api/diagnostics/tasks`.
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use tracing::{error, warn};
+
+use crate::crash_reports;
+use crate::debug_stats::{self, TaskHealth};
+
+/// Delay before the first restart
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Longest delay between restarts
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// A run lasting this long resets the backoff
+const STABLE_RUN: Duration = Duration::from_secs(300);
+
+/// When a task is started again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Restart {
+    /// A loop that runs for the app's lifetime; returning counts as a failure
+    Always,
+    /// Work that may finish (e.g., a listener ending with its connection); restarted only after a panic
+    OnPanic,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TaskState {
+    Running,
+    /// Failed, waiting for the backoff before starting again
+    Restarting,
+    /// Returned normally (`Restart::OnPanic` tasks only)
+    Finished,
+}
+
+struct Task {
+    /// Tells a task apart from an earlier one spawned under the same name
+    /// (e.g., the vNAS listener of a previous connection)
+    id: u64,
+    restart: Restart,
+    state: TaskState,
+    started: Instant,
+    restarts: u32,
+    /// Failures since the last stable run (sets the backoff)
+    failures: u32,
+    last_failure: Option<String>,
+    /// Unix ms
+    last_failure_at: Option<u64>,
+    restart_at: Option<Instant>,
+}
+
+static TASKS: Mutex<BTreeMap<&'static str, Task>> = Mutex::new(BTreeMap::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Supervised task status
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskStatus {
+    pub name: &'static str,
+    pub state: TaskState,
+    pub restart: Restart,
+    /// Times the task was started again after failing
+    pub restarts: u32,
+    /// Seconds since the task last started
+    pub uptime_s: f64,
+    pub last_failure: Option<String>,
+    /// Unix ms
+    pub last_failure_at: Option<u64>,
+    /// Seconds until the next start while restarting
+    pub restart_in_s: Option<f64>,
+    /// Run heartbeat, for tasks that report their runs
+    pub heartbeat: Option<TaskHealth>,
+    /// Not restarting, and running on schedule if it reports its runs
+    pub healthy: bool,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Delay before restarting after the given number of consecutive failures
+fn backoff(failures: u32) -> Duration {
+    let doublings = failures.saturating_sub(1).min(16);
+    (MIN_BACKOFF * 2u32.pow(doublings)).min(MAX_BACKOFF)
+}
+
+/// Register a new task under `name`, replacing any earlier one
+fn register(name: &'static str, restart: Restart) -> u64 {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    TASKS.lock().insert(
+        name,
+        Task {
+            id,
+            restart,
+            state: TaskState::Running,
+            started: Instant::now(),
+            restarts: 0,
+            failures: 0,
+            last_failure: None,
+            last_failure_at: None,
+            restart_at: None,
+        },
+    );
+    id
+}
+
+/// Mark a task as (re)started
+fn started(name: &'static str, id: u64) {
+    if let Some(task) = TASKS.lock().get_mut(name).filter(|t| t.id == id) {
+        task.state = TaskState::Running;
+        task.started = Instant::now();
+        task.restart_at = None;
+    }
+}
+
+/// Record a task ending after `ran_for` (`failure` unless it finished normally).
+/// Returns the delay before starting it again, or `None` when it is done
+/// (finished, or replaced by a newer task under the same name).
+fn exited(name: &'static str, id: u64, failure: Option<String>, ran_for: Duration) -> Option<Duration> {
+    let mut tasks = TASKS.lock();
+    let task = tasks.get_mut(name).filter(|t| t.id == id)?;
+    let Some(failure) = failure else {
+        task.state = TaskState::Finished;
+        return None;
+    };
+
+    if ran_for >= STABLE_RUN {
+        task.failures = 0;
+    }
+    task.failures += 1;
+    task.restarts += 1;
+    let delay = backoff(task.failures);
+    task.state = TaskState::Restarting;
+    task.last_failure = Some(failure);
+    task.last_failure_at = Some(now_ms());
+    task.restart_at = Some(Instant::now() + delay);
+    Some(delay)
+}
+
+/// Run `start` under supervision; it spawns one run of the task on the runtime
+fn supervise<S>(name: &'static str, restart: Restart, mut start: S)
+where
+    S: FnMut() -> tokio::task::JoinHandle<()> + Send + 'static,
+{
+    let id = register(name, restart);
+    tauri::async_runtime::spawn(async move {
+        loop {
+            started(name, id);
+            let run_started = Instant::now();
+            let failure = match start().await {
+                Ok(()) if restart == Restart::Always => Some("Exited unexpectedly".to_string()),
+                Ok(()) => None,
+                Err(e) if e.is_panic() => Some(format!(
+                    "Panicked: {}",
+                    crash_reports::panic_message(&*e.into_panic())
+                )),
+                Err(e) => Some(e.to_string()),
+            };
+            if let Some(failure) = &failure {
+                error!("[Supervisor] Task '{}' failed: {}", name, failure);
+            }
+            let Some(delay) = exited(name, id, failure, run_started.elapsed()) else {
+                break;
+            };
+            warn!("[Supervisor] Restarting '{}' in {} s", name, delay.as_secs());
+            tokio::time::sleep(delay).await;
+        }
+    });
+}
+
+/// Run an async task under supervision. `task` creates one run of it and is
+/// called again for every restart.
+pub fn spawn<F, Fut>(name: &'static str, restart: Restart, mut task: F)
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    supervise(name, restart, move || tokio::spawn(task()));
+}
+
+/// Run a blocking loop (e.g., polling a device) on its own thread under supervision
+pub fn spawn_blocking<F>(name: &'static str, restart: Restart, task: F)
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    let task = Arc::new(task);
+    supervise(name, restart, move || {
+        let task = task.clone();
+        tokio::task::spawn_blocking(move || task())
+    });
+}
+
+/// Status of every supervised task
+pub fn status() -> Vec<TaskStatus> {
+    let heartbeats: BTreeMap<&'static str, TaskHealth> =
+        debug_stats::tasks().into_iter().map(|h| (h.name, h)).collect();
+    TASKS
+        .lock()
+        .iter()
+        .map(|(&name, task)| {
+            let heartbeat = heartbeats.get(name).cloned();
+            TaskStatus {
+                name,
+                state: task.state,
+                restart: task.restart,
+                restarts: task.restarts,
+                uptime_s: task.started.elapsed().as_secs_f64(),
+                last_failure: task.last_failure.clone(),
+                last_failure_at: task.last_failure_at,
+                restart_in_s: task
+                    .restart_at
+                    .map(|at| at.saturating_duration_since(Instant::now()).as_secs_f64()),
+                healthy: task.state != TaskState::Restarting
+                    && (task.state == TaskState::Finished || heartbeat.as_ref().map_or(true, |h| h.healthy)),
+                heartbeat,
+            }
+        })
+        .collect()
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the state, restarts and last failure of every background task
+#[tauri::command]
+pub fn get_task_status() -> Vec<TaskStatus> {
+    status()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backs_off_exponentially() {
+        assert_eq!(backoff(1), Duration::from_secs(1));
+        assert_eq!(backoff(2), Duration::from_secs(2));
+        assert_eq!(backoff(4), Duration::from_secs(8));
+        assert_eq!(backoff(7), MAX_BACKOFF);
+        assert_eq!(backoff(100), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn restarts_failed_tasks() {
+        let id = register("test loop", Restart::Always);
+        let short = Duration::from_secs(1);
+        assert_eq!(exited("test loop", id, Some("Panicked: boom".to_string()), short), Some(backoff(1)));
+        started("test loop", id);
+        let returned = || Some("Exited unexpectedly".to_string());
+        assert_eq!(exited("test loop", id, returned(), short), Some(backoff(2)));
+        // A stable run resets the backoff
+        assert_eq!(exited("test loop", id, returned(), STABLE_RUN), Some(backoff(1)));
+
+        let task = status().into_iter().find(|t| t.name == "test loop").unwrap();
+        assert_eq!(task.state, TaskState::Restarting);
+        assert_eq!(task.restarts, 3);
+        assert_eq!(task.last_failure.as_deref(), Some("Exited unexpectedly"));
+        assert!(!task.healthy);
+    }
+
+    #[test]
+    fn finishes_tasks_that_may_end() {
+        let old = register("test listener", Restart::OnPanic);
+        let new = register("test listener", Restart::OnPanic);
+        let short = Duration::from_secs(1);
+
+        // The replaced task neither restarts nor touches the new one's state
+        assert_eq!(exited("test listener", old, Some("Panicked".to_string()), short), None);
+        assert_eq!(exited("test listener", new, Some("Panicked".to_string()), short), Some(backoff(1)));
+        started("test listener", new);
+        assert_eq!(exited("test listener", new, None, short), None);
+
+        let task = status().into_iter().find(|t| t.name == "test listener").unwrap();
+        assert_eq!(task.state, TaskState::Finished);
+        assert_eq!(task.restarts, 1);
+        assert!(task.healthy);
+    }
+}
@@ -0,0 +1,499 @@
+//! TAF (terminal aerodrome forecast) support
+//!
+//! Fetches TAFs from aviationweather.gov and decodes them into forecast
+//! periods (base, FM, BECMG, TEMPO, PROB) using the same wind, visibility,
+//! weather and sky groups as METARs. Prevailing wind changes of 60° or more are
+//! reported as wind shifts, which usually mean a runway change is coming.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Datelike, Months, Utc};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::warn;
+
+use crate::error::{Error, Result};
+use crate::http_client;
+use crate::weather::{self, Conditions};
+
+const TAF_API_URL: &str = "https://aviationweather.gov/api/data/taf";
+
+/// How long a fetched TAF is served from cache
+const TAF_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Minimum prevailing wind direction change reported as a wind shift
+const WIND_SHIFT_MIN_DEGREES: u16 = 60;
+
+/// Wind shifts below this speed (before and after) are ignored
+const WIND_SHIFT_MIN_SPEED_KT: u16 = 6;
+
+/// One forecast period
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TafPeriod {
+    /// "base", "FM", "BECMG", "TEMPO" or "PROB"
+    pub change: String,
+    /// Probability in percent (PROB30/PROB40)
+    pub probability: Option<u8>,
+    /// TEMPO and PROB periods are temporary; base, FM and BECMG conditions prevail
+    pub temporary: bool,
+    /// Period start (Unix ms)
+    pub from: u64,
+    /// Period end (Unix ms)
+    pub to: u64,
+    /// Forecast groups (BECMG/TEMPO periods only list what changes)
+    #[serde(flatten)]
+    pub conditions: Conditions,
+    pub ceiling_ft: Option<u32>,
+    /// Flight category, if the period forecasts visibility or sky condition
+    pub flight_category: Option<String>,
+}
+
+/// Forecast change of the prevailing wind direction
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindShift {
+    /// When the new wind takes effect (start of the FM/BECMG period, Unix ms)
+    pub at: u64,
+    pub from_direction: u16,
+    pub to_direction: u16,
+    pub speed_kt: u16,
+    pub gust_kt: Option<u16>,
+    /// "FM" or "BECMG"
+    pub change: String,
+}
+
+/// Decoded TAF
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Taf {
+    pub icao: String,
+    pub raw: String,
+    /// Issue time (Unix ms)
+    pub issued_at: Option<u64>,
+    /// Validity (Unix ms)
+    pub valid_from: u64,
+    pub valid_to: u64,
+    pub periods: Vec<TafPeriod>,
+    pub wind_shifts: Vec<WindShift>,
+}
+
+/// Resolve a day/hour/minute group to the instant closest to `reference`
+/// (TAF groups don't carry the month; hour 24 means midnight at the end of the day)
+fn resolve_time(
+    reference: DateTime<Utc>,
+    day: u32,
+    hour: u32,
+    minute: u32,
+) -> Option<DateTime<Utc>> {
+    if hour > 24 || minute > 59 {
+        return None;
+    }
+    let month_start = reference.date_naive().with_day(1)?;
+
+    [
+        month_start.checked_sub_months(Months::new(1)),
+        Some(month_start),
+        month_start.checked_add_months(Months::new(1)),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(|month| month.with_day(day))
+    .filter_map(|date| date.and_hms_opt(0, 0, 0))
+    .map(|midnight| {
+        (midnight + chrono::Duration::hours(hour as i64) + chrono::Duration::minutes(minute as i64))
+            .and_utc()
+    })
+    .min_by_key(|time| (*time - reference).num_seconds().abs())
+}
+
+fn to_ms(time: DateTime<Utc>) -> u64 {
+    time.timestamp_millis().max(0) as u64
+}
+
+/// Two-digit number at `start` in an ASCII token
+fn two_digits(token: &str, start: usize) -> Option<u32> {
+    token.get(start..start + 2)?.parse().ok()
+}
+
+/// Validity group: DDHH/DDHH
+fn parse_validity(token: &str, reference: DateTime<Utc>) -> Option<(u64, u64)> {
+    if token.len() != 9 || token.as_bytes()[4] != b'/' || !token.is_ascii() {
+        return None;
+    }
+    let from = resolve_time(reference, two_digits(token, 0)?, two_digits(token, 2)?, 0)?;
+    let to = resolve_time(from, two_digits(token, 5)?, two_digits(token, 7)?, 0)?;
+    Some((to_ms(from), to_ms(to)))
+}
+
+/// From group: FMDDHHMM
+fn parse_from_group(token: &str, reference: DateTime<Utc>) -> Option<u64> {
+    let time = token.strip_prefix("FM")?;
+    if time.len() != 6 || !time.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    resolve_time(
+        reference,
+        two_digits(time, 0)?,
+        two_digits(time, 2)?,
+        two_digits(time, 4)?,
+    )
+    .map(to_ms)
+}
+
+fn new_period(change: &str, probability: Option<u8>, from: u64, to: u64) -> TafPeriod {
+    TafPeriod {
+        change: change.to_string(),
+        probability,
+        temporary: matches!(change, "TEMPO" | "PROB"),
+        from,
+        to,
+        conditions: Conditions::default(),
+        ceiling_ft: None,
+        flight_category: None,
+    }
+}
+
+/// Smallest angle between two directions (degrees)
+fn direction_difference(a: u16, b: u16) -> u16 {
+    let diff = (a as i32 - b as i32).rem_euclid(360) as u16;
+    diff.min(360 - diff)
+}
+
+/// Prevailing wind direction changes across base, FM and BECMG periods
+fn find_wind_shifts(periods: &[TafPeriod]) -> Vec<WindShift> {
+    let mut shifts = Vec::new();
+    let mut current: Option<&weather::Wind> = None;
+
+    for period in periods.iter().filter(|p| !p.temporary) {
+        let Some(wind) = period.conditions.wind.as_ref() else {
+            continue;
+        };
+        if let (Some(previous), Some(from_direction), Some(to_direction)) =
+            (current, current.and_then(|w| w.direction), wind.direction)
+        {
+            if direction_difference(from_direction, to_direction) >= WIND_SHIFT_MIN_DEGREES
+                && previous.speed_kt.max(wind.speed_kt) >= WIND_SHIFT_MIN_SPEED_KT
+            {
+                shifts.push(WindShift {
+                    at: period.from,
+                    from_direction,
+                    to_direction,
+                    speed_kt: wind.speed_kt,
+                    gust_kt: wind.gust_kt,
+                    change: period.change.clone(),
+                });
+            }
+        }
+        current = Some(wind);
+    }
+
+    shifts
+}
+
+/// Decode a raw TAF. `reference` is any time within about two weeks of the
+/// forecast (e.g., its validity start); it supplies the month and year.
+pub fn decode_taf(raw: &str, reference: DateTime<Utc>) -> Taf {
+    let tokens: Vec<&str> = raw.split_whitespace().collect();
+    let mut i = 0;
+    while i < tokens.len() && matches!(tokens[i], "TAF" | "AMD" | "COR" | "RTD") {
+        i += 1;
+    }
+
+    let icao = tokens.get(i).map(|t| t.to_string()).unwrap_or_default();
+    i += 1;
+
+    let mut issued_at = None;
+    if let Some(token) = tokens.get(i).filter(|t| weather::is_time_group(t)) {
+        issued_at = resolve_time(
+            reference,
+            two_digits(token, 0).unwrap_or(0),
+            two_digits(token, 2).unwrap_or(0),
+            two_digits(token, 4).unwrap_or(0),
+        )
+        .map(to_ms);
+        i += 1;
+    }
+
+    let (valid_from, valid_to) = match tokens.get(i).and_then(|t| parse_validity(t, reference)) {
+        Some(validity) => {
+            i += 1;
+            validity
+        }
+        None => (to_ms(reference), to_ms(reference)),
+    };
+
+    let mut periods = Vec::new();
+    let mut current = new_period("base", None, valid_from, valid_to);
+
+    while i < tokens.len() {
+        let token = tokens[i];
+        if token == "RMK" {
+            break;
+        }
+
+        // Change groups start a new period
+        if let Some(from) = parse_from_group(token, reference) {
+            periods.push(std::mem::replace(
+                &mut current,
+                new_period("FM", None, from, valid_to),
+            ));
+            i += 1;
+            continue;
+        }
+        let probability = token
+            .strip_prefix("PROB")
+            .and_then(|p| p.parse::<u8>().ok());
+        if matches!(token, "BECMG" | "TEMPO") || probability.is_some() {
+            let change = if probability.is_some() { "PROB" } else { token };
+            let mut next = i + 1;
+            // PROB30 TEMPO 3120/3122
+            if probability.is_some() && tokens.get(next) == Some(&"TEMPO") {
+                next += 1;
+            }
+            let validity = tokens.get(next).and_then(|t| parse_validity(t, reference));
+            if validity.is_some() {
+                next += 1;
+            }
+            let (from, to) = validity.unwrap_or((current.from, current.to));
+            periods.push(std::mem::replace(
+                &mut current,
+                new_period(change, probability, from, to),
+            ));
+            i = next;
+            continue;
+        }
+
+        let consumed = weather::parse_condition_group(&mut current.conditions, &tokens, i);
+        i += consumed.max(1);
+    }
+    periods.push(current);
+
+    // Base and FM periods last until the next FM period
+    let fm_starts: Vec<u64> = periods
+        .iter()
+        .filter(|p| p.change == "FM")
+        .map(|p| p.from)
+        .collect();
+    for period in periods
+        .iter_mut()
+        .filter(|p| matches!(p.change.as_str(), "base" | "FM"))
+    {
+        if let Some(&next) = fm_starts.iter().find(|&&start| start > period.from) {
+            period.to = next;
+        }
+    }
+
+    for period in periods.iter_mut() {
+        let conditions = &period.conditions;
+        period.ceiling_ft = conditions.ceiling_ft();
+        if conditions.visibility_sm.is_some()
+            || !conditions.clouds.is_empty()
+            || conditions.vertical_visibility_ft.is_some()
+        {
+            period.flight_category = Some(
+                weather::flight_category(period.ceiling_ft, conditions.visibility_sm).to_string(),
+            );
+        }
+    }
+
+    let wind_shifts = find_wind_shifts(&periods);
+    Taf {
+        icao,
+        raw: raw.trim().to_string(),
+        issued_at,
+        valid_from,
+        valid_to,
+        periods,
+        wind_shifts,
+    }
+}
+
+/// TAF as returned by the aviationweather.gov JSON API (only the fields we use)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiTaf {
+    icao_id: Option<String>,
+    #[serde(rename = "rawTAF")]
+    raw_taf: Option<String>,
+    /// Unix seconds
+    valid_time_from: Option<i64>,
+}
+
+/// Cached fetch result (None = station has no current TAF)
+struct CachedTaf {
+    taf: Option<Taf>,
+    fetched_at: Instant,
+}
+
+/// TAF cache managed by Tauri
+pub struct TafState {
+    tafs: RwLock<HashMap<String, CachedTaf>>,
+}
+
+impl TafState {
+    pub fn new() -> Self {
+        Self {
+            tafs: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for TafState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fetch and decode the current TAF for a station
+async fn fetch_taf(icao: &str) -> Result<Option<Taf>> {
+    let url = format!("{}?ids={}&format=json", TAF_API_URL, icao);
+    let response = http_client::client()
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to fetch TAF: {}", e)))?;
+
+    if response.status() == reqwest::StatusCode::NO_CONTENT {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(Error::Upstream(format!("HTTP error: {}", response.status())));
+    }
+
+    let reports: Vec<ApiTaf> = response
+        .json()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to parse TAF response: {}", e)))?;
+
+    Ok(reports.into_iter().find_map(|report| {
+        let raw = report.raw_taf.filter(|r| !r.trim().is_empty())?;
+        let reference = report
+            .valid_time_from
+            .and_then(|t| DateTime::from_timestamp(t, 0))
+            .unwrap_or_else(Utc::now);
+        let mut taf = decode_taf(&raw, reference);
+        if let Some(icao_id) = report.icao_id {
+            taf.icao = icao_id;
+        }
+        Some(taf)
+    }))
+}
+
+/// Get the TAF for a station, from cache if fresh. Emits `taf-changed` when a new forecast is issued.
+pub async fn get_taf(app: &AppHandle, icao: &str, force: bool) -> Result<Option<Taf>> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    let state = app.state::<TafState>();
+
+    let previous = {
+        let tafs = state.tafs.read();
+        match tafs.get(&icao) {
+            Some(cached) if !force && cached.fetched_at.elapsed() < TAF_TTL => {
+                return Ok(cached.taf.clone())
+            }
+            Some(cached) => Some(cached.taf.clone()),
+            None => None,
+        }
+    };
+
+    let taf = match fetch_taf(&icao).await {
+        Ok(taf) => taf,
+        Err(e) => {
+            warn!("[Weather] {} TAF: {}", icao, e);
+            return match previous {
+                Some(stale) => Ok(stale),
+                None => Err(e),
+            };
+        }
+    };
+
+    state.tafs.write().insert(
+        icao.clone(),
+        CachedTaf {
+            taf: taf.clone(),
+            fetched_at: Instant::now(),
+        },
+    );
+
+    let previous_raw = previous.flatten().map(|t| t.raw);
+    if let Some(ref current) = taf {
+        if previous_raw.as_deref() != Some(current.raw.as_str()) {
+            let _ = app.emit("taf-changed", current);
+        }
+    }
+
+    Ok(taf)
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the decoded TAF for a station (None if it has no current forecast)
+#[tauri::command]
+pub async fn get_station_taf(
+    app: AppHandle,
+    icao: String,
+    force: Option<bool>,
+) -> Result<Option<Taf>> {
+    get_taf(&app, &icao, force.unwrap_or(false)).await
+}
+
+/// Initialize TAF cache for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_taf(app: &AppHandle) {
+    app.manage(TafState::new());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_periods_and_wind_shifts_across_month_end() {
+        let reference = DateTime::from_timestamp(1_706_702_400, 0).unwrap(); // 2024-01-31 12:00Z
+        let taf = decode_taf(
+            "TAF KBOS 311120Z 3112/0118 27012G20KT P6SM SCT040 \
+             TEMPO 3114/3116 5SM -SHRA BKN025 \
+             FM311800 18010KT P6SM BKN030 \
+             PROB30 TEMPO 3120/3122 2SM TSRA OVC010CB \
+             BECMG 0104/0106 VRB03KT \
+             FM010900 20008KT 9999 NSC",
+            reference,
+        );
+
+        assert_eq!(taf.icao, "KBOS");
+        assert_eq!(
+            taf.periods
+                .iter()
+                .map(|p| p.change.as_str())
+                .collect::<Vec<_>>(),
+            ["base", "TEMPO", "FM", "PROB", "BECMG", "FM"]
+        );
+
+        // Base period ends when the first FM period starts
+        let fm = &taf.periods[2];
+        assert_eq!(taf.periods[0].to, fm.from);
+        assert_eq!(fm.from, 1_706_724_000_000); // 2024-01-31 18:00Z
+                                                // Validity crosses into February
+        assert_eq!(taf.valid_to, 1_706_810_400_000); // 2024-02-01 18:00Z
+
+        let prob = &taf.periods[3];
+        assert_eq!(prob.probability, Some(30));
+        assert!(prob.temporary);
+        assert_eq!(prob.flight_category.as_deref(), Some("IFR"));
+
+        // 270 → 180 is a shift; the variable BECMG wind and the 200° FM wind are not
+        assert_eq!(taf.wind_shifts.len(), 1);
+        assert_eq!(
+            (
+                taf.wind_shifts[0].from_direction,
+                taf.wind_shifts[0].to_direction
+            ),
+            (270, 180)
+        );
+        assert_eq!(taf.wind_shifts[0].at, fm.from);
+    }
+}
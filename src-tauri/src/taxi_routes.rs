@@ -0,0 +1,524 @@
+//! Taxi route prediction
+//!
+//! Builds a graph from the OpenStreetMap taxiways and taxilanes of the active
+//! airport (ways that share a node are connected) and projects each taxiing
+//! aircraft's likely route along it: departures from their gate (including the
+//! pushback onto the taxilane) to the nearest active runway end, arrivals on
+//! the ground to the gate suggested for them (see gate_suggestions.rs). Routes
+//! are shortest paths, so they are a plausible guess, not a clearance.
+//!
+//! Served at `/api/airports/{ICAO}/taxi-routes` for drawing anticipated taxi
+//! paths on the 2D view. The same graph gives the runway exits used for
+//! rollout turn-off predictions (see touchdown.rs).
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+use tracing::info;
+
+use crate::error::{Error, Result};
+use crate::gate_suggestions;
+use crate::gates;
+use crate::geo;
+use crate::osm_layout;
+use crate::runway_suggestion;
+use crate::runways;
+use crate::traffic::{self, Aircraft};
+use crate::weather;
+
+const METERS_PER_NM: f64 = 1852.0;
+
+/// Aircraft farther than this from any taxiway aren't routed (meters)
+const MAX_START_DISTANCE_M: f64 = 300.0;
+
+/// Destinations farther than this from any taxiway can't be reached (meters)
+const MAX_END_DISTANCE_M: f64 = 500.0;
+
+/// Ground aircraft faster than this are taking off or landing, not taxiing (knots)
+const MAX_TAXI_SPEED_KT: f64 = 40.0;
+
+/// Arrivals slower than this are parked (knots)
+const PARKED_MAX_SPEED_KT: f64 = 3.0;
+
+const FEET_PER_NM: f64 = 6076.12;
+
+/// Width assumed for runways without one in the data (feet)
+const DEFAULT_RUNWAY_WIDTH_FT: f64 = 150.0;
+
+/// Taxiway nodes this far outside the runway edge still count as on it (feet)
+const RUNWAY_EDGE_MARGIN_FT: f64 = 50.0;
+
+/// Exits turning off more than this from the landing direction lead back
+/// toward the threshold (degrees)
+const MAX_EXIT_ANGLE: f64 = 120.0;
+
+/// Exits of the same taxiway closer than this along the runway are one (feet)
+const SAME_EXIT_FT: f64 = 200.0;
+
+/// Edge of the taxiway graph
+#[derive(Debug, Clone)]
+struct Edge {
+    to: usize,
+    length_m: f64,
+    /// Taxiway designator (e.g., "K")
+    taxiway: Option<String>,
+}
+
+/// Taxiway graph of an airport
+#[derive(Debug, Clone, Default)]
+pub struct TaxiGraph {
+    /// Node positions (latitude, longitude)
+    nodes: Vec<(f64, f64)>,
+    edges: Vec<Vec<Edge>>,
+}
+
+/// Predicted taxi route for an aircraft
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaxiRoute {
+    pub callsign: String,
+    /// Runway end a departure taxis to
+    pub runway: Option<String>,
+    /// Gate an arrival taxis to
+    pub gate: Option<String>,
+    /// Taxiways along the route, in order (e.g., ["B", "K", "A"])
+    pub taxiways: Vec<String>,
+    /// Route from the aircraft to the destination, as [longitude, latitude] pairs
+    pub path: Vec<[f64; 2]>,
+    pub distance_m: f64,
+}
+
+/// Taxiway leaving a runway
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunwayExit {
+    /// Taxiway designator (e.g., "K")
+    pub taxiway: Option<String>,
+    /// Where it leaves the runway centerline
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Distance past the threshold (feet)
+    pub along_ft: f64,
+    /// Turn from the landing direction (degrees, 30 for a high-speed exit, 90
+    /// for a right-angle one)
+    pub angle: f64,
+}
+
+/// Graph of the active airport (managed by Tauri)
+pub struct TaxiRouteState {
+    graph: RwLock<Option<(String, Arc<TaxiGraph>)>>,
+}
+
+impl TaxiRouteState {
+    pub fn new() -> Self {
+        Self {
+            graph: RwLock::new(None),
+        }
+    }
+}
+
+impl Default for TaxiRouteState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn distance_m(a: (f64, f64), b: (f64, f64)) -> f64 {
+    geo::distance_and_bearing(a.0, a.1, b.0, b.1).0 * METERS_PER_NM
+}
+
+/// Node key: OSM ways share exact node coordinates
+fn node_key(lat: f64, lon: f64) -> (i64, i64) {
+    ((lat * 1e7).round() as i64, (lon * 1e7).round() as i64)
+}
+
+/// Build the taxiway graph from an OSM ground layout (see osm_layout.rs)
+pub fn build_graph(layout: &Value) -> TaxiGraph {
+    let mut graph = TaxiGraph::default();
+    let mut index: HashMap<(i64, i64), usize> = HashMap::new();
+
+    let features = layout.get("features").and_then(Value::as_array).into_iter().flatten();
+    for feature in features {
+        let properties = feature.get("properties");
+        let kind = properties.and_then(|p| p.get("kind")).and_then(Value::as_str);
+        if !matches!(kind, Some("taxiway") | Some("taxilane")) {
+            continue;
+        }
+        let geometry = feature.get("geometry");
+        if geometry.and_then(|g| g.get("type")).and_then(Value::as_str) != Some("LineString") {
+            continue;
+        }
+        let taxiway = properties
+            .and_then(|p| p.get("ref").or_else(|| p.get("name")))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        let points: Vec<(f64, f64)> = geometry
+            .and_then(|g| g.get("coordinates"))
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|c| Some((c.get(1)?.as_f64()?, c.get(0)?.as_f64()?)))
+            .collect();
+
+        let mut previous: Option<usize> = None;
+        for (lat, lon) in points {
+            let node = *index.entry(node_key(lat, lon)).or_insert_with(|| {
+                graph.nodes.push((lat, lon));
+                graph.edges.push(Vec::new());
+                graph.nodes.len() - 1
+            });
+            if let Some(from) = previous.filter(|&p| p != node) {
+                let length_m = distance_m(graph.nodes[from], graph.nodes[node]);
+                graph.edges[from].push(Edge {
+                    to: node,
+                    length_m,
+                    taxiway: taxiway.clone(),
+                });
+                graph.edges[node].push(Edge {
+                    to: from,
+                    length_m,
+                    taxiway: taxiway.clone(),
+                });
+            }
+            previous = Some(node);
+        }
+    }
+    graph
+}
+
+impl TaxiGraph {
+    /// Nearest node to a position within a distance, with its distance (meters)
+    fn nearest_node(&self, position: (f64, f64), max_m: f64) -> Option<(usize, f64)> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(i, &node)| (i, distance_m(position, node)))
+            .filter(|&(_, d)| d <= max_m)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    }
+
+    /// Shortest distances and previous nodes from a start node (Dijkstra)
+    fn shortest_paths(&self, start: usize) -> (Vec<f64>, Vec<Option<(usize, usize)>>) {
+        let mut distances = vec![f64::INFINITY; self.nodes.len()];
+        // Previous node and the index of the edge taken from it
+        let mut previous: Vec<Option<(usize, usize)>> = vec![None; self.nodes.len()];
+        let mut queue = BinaryHeap::new();
+        distances[start] = 0.0;
+        queue.push(Visit { cost: 0.0, node: start });
+
+        while let Some(Visit { cost, node }) = queue.pop() {
+            if cost > distances[node] {
+                continue;
+            }
+            for (i, edge) in self.edges[node].iter().enumerate() {
+                let next = cost + edge.length_m;
+                if next < distances[edge.to] {
+                    distances[edge.to] = next;
+                    previous[edge.to] = Some((node, i));
+                    queue.push(Visit { cost: next, node: edge.to });
+                }
+            }
+        }
+        (distances, previous)
+    }
+
+    /// Taxiways leaving a runway for aircraft landing on one of its ends,
+    /// closest to the threshold first
+    pub fn runway_exits(&self, runway: &runways::Runway, end: &runways::RunwayEnd) -> Vec<RunwayExit> {
+        let half_width_ft = runway.width_ft.filter(|w| *w > 0.0).unwrap_or(DEFAULT_RUNWAY_WIDTH_FT) / 2.0;
+        let on_runway = |&(lat, lon): &(f64, f64)| {
+            runways::alignment(runway, end, lat, lon).filter(|a| {
+                a.offset_nm * FEET_PER_NM <= half_width_ft + RUNWAY_EDGE_MARGIN_FT
+                    && a.along_nm >= 0.0
+                    && a.along_nm <= a.length_nm
+            })
+        };
+        let opposite = if std::ptr::eq(end, &runway.low_end) {
+            &runway.high_end
+        } else {
+            &runway.low_end
+        };
+        let (Some(lat), Some(lon), Some(opposite_lat), Some(opposite_lon)) =
+            (end.latitude, end.longitude, opposite.latitude, opposite.longitude)
+        else {
+            return Vec::new();
+        };
+        let (_, runway_bearing) = geo::distance_and_bearing(lat, lon, opposite_lat, opposite_lon);
+
+        let mut exits: Vec<RunwayExit> = Vec::new();
+        for (node, position) in self.nodes.iter().enumerate() {
+            let Some(alignment) = on_runway(position) else {
+                continue;
+            };
+            // The edge to a node off the runway turning least from the landing direction
+            let exit = self.edges[node]
+                .iter()
+                .filter(|edge| on_runway(&self.nodes[edge.to]).is_none())
+                .map(|edge| {
+                    let to = self.nodes[edge.to];
+                    let (_, bearing) = geo::distance_and_bearing(position.0, position.1, to.0, to.1);
+                    (edge, geo::heading_difference(bearing, runway_bearing))
+                })
+                .filter(|(_, angle)| *angle <= MAX_EXIT_ANGLE)
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            if let Some((edge, angle)) = exit {
+                exits.push(RunwayExit {
+                    taxiway: edge.taxiway.clone(),
+                    latitude: position.0,
+                    longitude: position.1,
+                    along_ft: (alignment.along_nm * FEET_PER_NM).round(),
+                    angle: angle.round(),
+                });
+            }
+        }
+        exits.sort_by(|a, b| a.along_ft.total_cmp(&b.along_ft));
+        exits.dedup_by(|later, earlier| {
+            later.taxiway.is_some() && later.taxiway == earlier.taxiway && later.along_ft - earlier.along_ft < SAME_EXIT_FT
+        });
+        exits
+    }
+}
+
+/// Dijkstra queue entry (cheapest first)
+struct Visit {
+    cost: f64,
+    node: usize,
+}
+
+impl PartialEq for Visit {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+
+impl Eq for Visit {}
+
+impl PartialOrd for Visit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Visit {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+/// Where a route may end
+#[derive(Debug, Clone, PartialEq)]
+pub enum Destination {
+    Runway(String),
+    Gate(String),
+}
+
+/// Shortest route from a position to the closest of some destinations
+pub fn predict(
+    graph: &TaxiGraph,
+    callsign: &str,
+    from: (f64, f64),
+    destinations: &[(Destination, (f64, f64))],
+) -> Option<TaxiRoute> {
+    let (start, start_offset) = graph.nearest_node(from, MAX_START_DISTANCE_M)?;
+    let (distances, previous) = graph.shortest_paths(start);
+
+    let (destination, position, end, total) = destinations
+        .iter()
+        .filter_map(|(destination, position)| {
+            let (end, end_offset) = graph.nearest_node(*position, MAX_END_DISTANCE_M)?;
+            let total = start_offset + distances[end] + end_offset;
+            total.is_finite().then_some((destination, *position, end, total))
+        })
+        .min_by(|a, b| a.3.total_cmp(&b.3))?;
+
+    // Walk back from the end node
+    let mut nodes = vec![end];
+    let mut taxiways: Vec<String> = Vec::new();
+    let mut node = end;
+    while let Some((from_node, edge)) = previous[node] {
+        if let Some(taxiway) = &graph.edges[from_node][edge].taxiway {
+            if taxiways.last() != Some(taxiway) {
+                taxiways.push(taxiway.clone());
+            }
+        }
+        nodes.push(from_node);
+        node = from_node;
+    }
+    nodes.reverse();
+    taxiways.reverse();
+
+    let mut path = vec![[from.1, from.0]];
+    path.extend(nodes.iter().map(|&n| [graph.nodes[n].1, graph.nodes[n].0]));
+    path.push([position.1, position.0]);
+
+    let (runway, gate) = match destination {
+        Destination::Runway(ident) => (Some(ident.clone()), None),
+        Destination::Gate(id) => (None, Some(id.clone())),
+    };
+    Some(TaxiRoute {
+        callsign: callsign.to_string(),
+        runway,
+        gate,
+        taxiways,
+        path,
+        distance_m: total.round(),
+    })
+}
+
+/// Taxiway graph of an airport (cached for the active airport)
+pub async fn graph_for(app: &AppHandle, icao: &str) -> Result<Arc<TaxiGraph>> {
+    let state = app.state::<TaxiRouteState>();
+    if let Some((cached_icao, graph)) = state.graph.read().as_ref() {
+        if cached_icao == icao {
+            return Ok(graph.clone());
+        }
+    }
+
+    let layout = osm_layout::get_layout(app, icao, false).await?;
+    let graph = Arc::new(build_graph(&layout));
+    info!("[TaxiRoutes] Built taxiway graph for {} ({} nodes)", icao, graph.nodes.len());
+    *state.graph.write() = Some((icao.to_string(), graph.clone()));
+    Ok(graph)
+}
+
+fn is_taxiing(aircraft: &Aircraft) -> bool {
+    aircraft.on_ground && aircraft.groundspeed_kt <= MAX_TAXI_SPEED_KT
+}
+
+/// Predicted taxi routes for departures and arrivals on the ground at the
+/// active airport (none for other airports)
+pub async fn get_routes(app: &AppHandle, icao: &str) -> Result<Vec<TaxiRoute>> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    let snapshot = traffic::get_snapshot(app);
+    if snapshot.icao.as_deref() != Some(icao.as_str()) {
+        return Ok(Vec::new());
+    }
+    let graph = graph_for(app, &icao).await?;
+    if graph.nodes.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Departures head for the active runway ends (any end if none are known)
+    let runways = runways::runways_for_airport(app, &icao).await.unwrap_or_default();
+    let active = runway_suggestion::get_suggestion(app, &icao)
+        .await
+        .ok()
+        .flatten()
+        .map(|s| s.active)
+        .unwrap_or_default();
+    let ends: Vec<(Destination, (f64, f64))> = runways
+        .iter()
+        .flat_map(|r| [&r.low_end, &r.high_end])
+        .filter_map(|end| Some((Destination::Runway(end.ident.clone()), (end.latitude?, end.longitude?))))
+        .collect();
+    let runway_ends: Vec<(Destination, (f64, f64))> = if active.is_empty() {
+        ends
+    } else {
+        ends.into_iter()
+            .filter(|(d, _)| matches!(d, Destination::Runway(ident) if active.contains(ident)))
+            .collect()
+    };
+
+    // Arrivals head for their suggested gate
+    let gate_positions: HashMap<String, (f64, f64)> = gates::active_gates(app, &icao)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|g| (g.id, (g.latitude, g.longitude)))
+        .collect();
+    let suggested: HashMap<String, String> = gate_suggestions::get_suggestions(app, &icao)?
+        .into_iter()
+        .map(|s| (s.callsign, s.gate))
+        .collect();
+
+    let mut routes = Vec::new();
+    for aircraft in snapshot.aircraft.iter().filter(|a| is_taxiing(a)) {
+        let position = (aircraft.latitude, aircraft.longitude);
+        let route = if aircraft.departure.as_deref() == Some(icao.as_str()) {
+            predict(&graph, &aircraft.callsign, position, &runway_ends)
+        } else if aircraft.arrival.as_deref() == Some(icao.as_str())
+            && aircraft.groundspeed_kt > PARKED_MAX_SPEED_KT
+        {
+            suggested
+                .get(&aircraft.callsign)
+                .and_then(|gate| Some((gate, *gate_positions.get(gate)?)))
+                .and_then(|(gate, at)| {
+                    predict(&graph, &aircraft.callsign, position, &[(Destination::Gate(gate.clone()), at)])
+                })
+        } else {
+            None
+        };
+        routes.extend(route);
+    }
+    Ok(routes)
+}
+
+/// Initialize taxi route state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_taxi_routes(app: &AppHandle) {
+    app.manage(TaxiRouteState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get predicted taxi routes for aircraft on the ground at the active airport
+#[tauri::command]
+pub async fn get_taxi_routes(app: AppHandle, icao: String) -> Result<Vec<TaxiRoute>> {
+    get_routes(&app, &icao).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Two parallel taxiways (A south, B north) joined by connectors C1 (west) and C2 (east)
+    fn layout() -> Value {
+        json!({"features": [
+            {"geometry": {"type": "LineString", "coordinates": [[-71.030, 42.360], [-71.020, 42.360], [-71.010, 42.360]]},
+             "properties": {"kind": "taxiway", "ref": "A"}},
+            {"geometry": {"type": "LineString", "coordinates": [[-71.030, 42.362], [-71.020, 42.362], [-71.010, 42.362]]},
+             "properties": {"kind": "taxiway", "ref": "B"}},
+            {"geometry": {"type": "LineString", "coordinates": [[-71.030, 42.360], [-71.030, 42.362]]},
+             "properties": {"kind": "taxiway", "ref": "C1"}},
+            {"geometry": {"type": "LineString", "coordinates": [[-71.010, 42.360], [-71.010, 42.362]]},
+             "properties": {"kind": "taxiway", "ref": "C2"}},
+            {"geometry": {"type": "LineString", "coordinates": [[-71.030, 42.350], [-71.010, 42.350]]},
+             "properties": {"kind": "runway", "ref": "09/27"}}
+        ]})
+    }
+
+    #[test]
+    fn builds_connected_graph() {
+        let graph = build_graph(&layout());
+        // Runways aren't taxi routes; shared corners are one node
+        assert_eq!(graph.nodes.len(), 6);
+        assert_eq!(graph.edges.iter().map(Vec::len).sum::<usize>(), 12);
+    }
+
+    #[test]
+    fn predicts_shortest_route() {
+        let graph = build_graph(&layout());
+        let destinations = [
+            (Destination::Runway("27".to_string()), (42.3598, -71.0102)),
+            (Destination::Runway("09".to_string()), (42.3598, -71.0310)),
+        ];
+
+        // From the middle of B both connectors are as far; the 09 end is
+        // farther from the taxiways, so 27 is closer
+        let route = predict(&graph, "DAL123", (42.3621, -71.0190), &destinations).unwrap();
+        assert_eq!(route.runway.as_deref(), Some("27"));
+        assert_eq!(route.taxiways, ["B", "C2"]);
+        assert_eq!(route.path.first(), Some(&[-71.0190, 42.3621]));
+        assert_eq!(route.path.last(), Some(&[-71.0102, 42.3598]));
+
+        // Too far from any taxiway
+        assert!(predict(&graph, "DAL123", (42.40, -71.02), &destinations).is_none());
+    }
+}
@@ -0,0 +1,472 @@
+//! Offline terrain packages
+//!
+//! Pre-generated quantized-mesh terrain (e.g. from Cesium Terrain Builder) is
+//! imported from a folder or a `.tar`, `.tar.gz` or `.tar.zst` archive into
+//! `terrain-packages/<id>/` in the app data folder. Only `layer.json` and
+//! `<z>/<x>/<y>.terrain` files are kept; gzip-compressed tiles (the terrain
+//! builder's default) are decompressed on import so they can be served as is.
+//!
+//! Packages are served at `/api/terrain/<id>/` as a standard Cesium terrain
+//! endpoint, and one can be selected as the terrain provider
+//! (`mapProviders.terrain = "package"`, see map_providers.rs). With imagery
+//! from the tile cache, the cab then works without an internet connection.
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_dialog::DialogExt;
+use tracing::info;
+
+use crate::error::{Error, Result};
+use crate::tile_cache::Tile;
+
+/// Package metadata written next to its tiles
+const METADATA_NAME: &str = "package.json";
+
+/// How deep to look for layer.json in an imported folder or archive
+const MAX_ROOT_DEPTH: usize = 3;
+
+/// Progress is emitted after this many tiles
+const PROGRESS_EVERY: u64 = 1000;
+
+/// An imported terrain package
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerrainPackage {
+    pub id: String,
+    pub name: String,
+    /// Unix time (ms)
+    pub imported_at: u64,
+    pub tiles: u64,
+    pub bytes: u64,
+    pub max_zoom: Option<u32>,
+    /// West, south, east, north (degrees), from layer.json
+    pub bounds: Option<[f64; 4]>,
+}
+
+/// Emitted as `terrain-import-progress` while importing
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportProgress {
+    name: String,
+    tiles: u64,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn packages_dir(app: &AppHandle) -> Result<PathBuf> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))?;
+    Ok(app_data.join("terrain-packages"))
+}
+
+/// Package ID from a name: lowercase letters, digits and dashes
+fn package_id(name: &str) -> String {
+    let mut id = String::new();
+    for c in name.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            id.push(c.to_ascii_lowercase());
+        } else if !id.is_empty() && !id.ends_with('-') {
+            id.push('-');
+        }
+    }
+    let id = id.trim_end_matches('-');
+    if id.is_empty() {
+        "terrain".to_string()
+    } else {
+        id.to_string()
+    }
+}
+
+/// Whether an ID could have come from [`package_id`] (so it can't leave the packages folder)
+fn valid_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// Canonical path of a package file ("layer.json" or "z/x/y.terrain"), None for anything else
+fn package_path(relative: &str) -> Option<String> {
+    match Tile::parse(&format!("terrain/{}", relative))? {
+        Tile::TerrainLayer => Some("layer.json".to_string()),
+        Tile::Terrain { z, x, y } => Some(format!("{}/{}/{}.terrain", z, x, y)),
+        Tile::Imagery { .. } => None,
+    }
+}
+
+/// Package name from an imported folder or archive
+fn source_name(source: &Path) -> String {
+    let name = source
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    [".tar.zst", ".tar.gz", ".tgz", ".tar"]
+        .iter()
+        .find_map(|ext| name.strip_suffix(ext))
+        .unwrap_or(&name)
+        .to_string()
+}
+
+/// The folder holding layer.json, at most a few levels down
+fn find_root(dir: &Path, depth: usize) -> Option<PathBuf> {
+    if dir.join("layer.json").is_file() {
+        return Some(dir.to_path_buf());
+    }
+    if depth == 0 {
+        return None;
+    }
+    let mut subdirs: Vec<PathBuf> = fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+    subdirs.sort();
+    subdirs.into_iter().find_map(|sub| find_root(&sub, depth - 1))
+}
+
+/// Unpack a tar archive (optionally zstd or gzip compressed) into `dest`
+fn extract_archive(archive: &Path, dest: &Path) -> Result<()> {
+    let file = fs::File::open(archive).map_err(|e| Error::Io(format!("Failed to open {}: {}", archive.display(), e)))?;
+    let name = archive.to_string_lossy().to_lowercase();
+    let reader: Box<dyn Read> = if name.ends_with(".zst") {
+        Box::new(zstd::Decoder::new(file).map_err(|e| Error::InvalidInput(format!("Not a zstd archive: {}", e)))?)
+    } else if name.ends_with(".gz") || name.ends_with(".tgz") {
+        Box::new(flate2::read::GzDecoder::new(file))
+    } else {
+        Box::new(file)
+    };
+    fs::create_dir_all(dest).map_err(|e| Error::Io(format!("Failed to create {}: {}", dest.display(), e)))?;
+    let mut tar = tar::Archive::new(reader);
+    for entry in tar.entries().map_err(|e| Error::InvalidInput(format!("Not a tar archive: {}", e)))? {
+        let mut entry = entry.map_err(|e| Error::InvalidInput(format!("Archive is damaged: {}", e)))?;
+        if !entry.header().entry_type().is_file() && !entry.header().entry_type().is_dir() {
+            continue;
+        }
+        // unpack_in skips entries that would land outside dest
+        entry
+            .unpack_in(dest)
+            .map_err(|e| Error::Io(format!("Failed to extract archive: {}", e)))?;
+    }
+    Ok(())
+}
+
+/// Copy the package files under `root` into `dest`, decompressing gzipped
+/// files. Returns (tiles, bytes).
+fn copy_package(root: &Path, dest: &Path, mut progress: impl FnMut(u64)) -> Result<(u64, u64)> {
+    fn walk(dir: &Path, files: &mut Vec<PathBuf>) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                walk(&path, files);
+            } else {
+                files.push(path);
+            }
+        }
+    }
+    let mut files = Vec::new();
+    walk(root, &mut files);
+
+    let (mut tiles, mut bytes) = (0, 0);
+    for file in files {
+        let Ok(relative) = file.strip_prefix(root) else {
+            continue;
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        let Some(target) = package_path(&relative) else {
+            continue;
+        };
+        let mut content = fs::read(&file).map_err(|e| Error::Io(format!("Failed to read {}: {}", file.display(), e)))?;
+        if content.starts_with(&[0x1f, 0x8b]) {
+            let mut plain = Vec::new();
+            flate2::read::GzDecoder::new(content.as_slice())
+                .read_to_end(&mut plain)
+                .map_err(|e| Error::InvalidInput(format!("Failed to decompress {}: {}", relative, e)))?;
+            content = plain;
+        }
+        let target = dest.join(target);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| Error::Io(format!("Failed to create {}: {}", parent.display(), e)))?;
+        }
+        fs::write(&target, &content).map_err(|e| Error::Io(format!("Failed to write {}: {}", target.display(), e)))?;
+        bytes += content.len() as u64;
+        if relative != "layer.json" {
+            tiles += 1;
+            if tiles % PROGRESS_EVERY == 0 {
+                progress(tiles);
+            }
+        }
+    }
+    Ok((tiles, bytes))
+}
+
+/// Highest zoom and bounds from a package's layer.json
+fn layer_metadata(layer: &serde_json::Value) -> (Option<u32>, Option<[f64; 4]>) {
+    let max_zoom = layer["maxzoom"]
+        .as_u64()
+        .or_else(|| layer["available"].as_array().and_then(|a| a.len().checked_sub(1)).map(|z| z as u64))
+        .map(|z| z as u32);
+    let bounds = layer["bounds"].as_array().and_then(|b| {
+        let values: Vec<f64> = b.iter().filter_map(|v| v.as_f64()).collect();
+        <[f64; 4]>::try_from(values).ok()
+    });
+    (max_zoom, bounds)
+}
+
+/// Copy a package from a folder or archive into `staging/package`, with its metadata
+fn stage_package(
+    source: &Path,
+    staging: &Path,
+    id: &str,
+    name: String,
+    progress: impl FnMut(u64),
+) -> Result<TerrainPackage> {
+    let root = if source.is_dir() {
+        find_root(source, MAX_ROOT_DEPTH)
+    } else {
+        let extracted = staging.join("archive");
+        extract_archive(source, &extracted)?;
+        find_root(&extracted, MAX_ROOT_DEPTH)
+    }
+    .ok_or_else(|| Error::invalid_input("No layer.json found (not a quantized-mesh terrain package)"))?;
+
+    let package_dir = staging.join("package");
+    let (tiles, bytes) = copy_package(&root, &package_dir, progress)?;
+    if tiles == 0 {
+        return Err(Error::invalid_input("The package has no .terrain tiles"));
+    }
+    let layer: serde_json::Value = serde_json::from_slice(
+        &fs::read(package_dir.join("layer.json")).map_err(|e| Error::Io(format!("Failed to read layer.json: {}", e)))?,
+    )
+    .map_err(|e| Error::InvalidInput(format!("Invalid layer.json: {}", e)))?;
+    let (max_zoom, bounds) = layer_metadata(&layer);
+    let package = TerrainPackage {
+        id: id.to_string(),
+        name,
+        imported_at: now_ms(),
+        tiles,
+        bytes,
+        max_zoom,
+        bounds,
+    };
+    let metadata = serde_json::to_vec_pretty(&package).map_err(|e| Error::Internal(e.to_string()))?;
+    fs::write(package_dir.join(METADATA_NAME), metadata)
+        .map_err(|e| Error::Io(format!("Failed to write package metadata: {}", e)))?;
+    Ok(package)
+}
+
+/// Import a package from a folder or archive into `dir` (blocking)
+fn import_into(
+    dir: &Path,
+    source: &Path,
+    name: Option<String>,
+    progress: impl FnMut(u64),
+) -> Result<TerrainPackage> {
+    let name = name
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| source_name(source));
+    let base = package_id(&name);
+    let id = (1..)
+        .map(|n| if n == 1 { base.clone() } else { format!("{}-{}", base, n) })
+        .find(|id| !dir.join(id).exists())
+        .unwrap_or(base);
+
+    let staging = dir.join(format!(".import-{}", id));
+    let _ = fs::remove_dir_all(&staging);
+    let result = stage_package(source, &staging, &id, name, progress).and_then(|package| {
+        fs::rename(staging.join("package"), dir.join(&id))
+            .map_err(|e| Error::Io(format!("Failed to save package: {}", e)))?;
+        Ok(package)
+    });
+    let _ = fs::remove_dir_all(&staging);
+    result
+}
+
+/// Imported packages in `dir`, by name
+fn list_in(dir: &Path) -> Vec<TerrainPackage> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut packages: Vec<TerrainPackage> = entries
+        .flatten()
+        .filter_map(|e| fs::read(e.path().join(METADATA_NAME)).ok())
+        .filter_map(|bytes| serde_json::from_slice(&bytes).ok())
+        .collect();
+    packages.sort_by_key(|p| p.name.to_lowercase());
+    packages
+}
+
+/// Imported packages, by name
+pub fn list(app: &AppHandle) -> Vec<TerrainPackage> {
+    packages_dir(app).map(|dir| list_in(&dir)).unwrap_or_default()
+}
+
+/// A file of a package (`relative` like "layer.json" or "13/4412/2980.terrain").
+/// `None` if the package doesn't have it.
+pub async fn read_file(app: &AppHandle, id: &str, relative: &str) -> Result<Option<Vec<u8>>> {
+    if !valid_id(id) {
+        return Err(Error::NotFound(format!("Unknown terrain package {}", id)));
+    }
+    let package = packages_dir(app)?.join(id);
+    if !package.join(METADATA_NAME).is_file() {
+        return Err(Error::NotFound(format!("Terrain package {} is not installed", id)));
+    }
+    let Some(path) = package_path(relative) else {
+        return Ok(None);
+    };
+    match tokio::fs::read(package.join(path)).await {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(Error::Io(format!("Failed to read terrain package: {}", e))),
+    }
+}
+
+/// A terrain tile (or layer.json) of a package
+pub async fn read_tile(app: &AppHandle, id: &str, tile: &Tile) -> Result<Option<Vec<u8>>> {
+    let relative = match tile {
+        Tile::TerrainLayer => "layer.json".to_string(),
+        Tile::Terrain { z, x, y } => format!("{}/{}/{}.terrain", z, x, y),
+        Tile::Imagery { .. } => return Ok(None),
+    };
+    read_file(app, id, &relative).await
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// List imported terrain packages
+#[tauri::command]
+pub fn list_terrain_packages(app: AppHandle) -> Vec<TerrainPackage> {
+    list(&app)
+}
+
+/// Pick a terrain package folder or archive using native dialog
+#[tauri::command]
+pub async fn pick_terrain_package(app: AppHandle, folder: bool) -> Result<Option<String>> {
+    let dialog = app.dialog().file();
+    let picked = if folder {
+        dialog.blocking_pick_folder()
+    } else {
+        dialog
+            .add_filter("Terrain package", &["tar", "gz", "tgz", "zst"])
+            .blocking_pick_file()
+    };
+    Ok(picked.map(|path| path.to_string()))
+}
+
+/// Import a terrain package from a folder or archive on the host
+#[tauri::command]
+pub async fn import_terrain_package(
+    app: AppHandle,
+    path: String,
+    name: Option<String>,
+) -> Result<TerrainPackage> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let dir = packages_dir(&app)?;
+        fs::create_dir_all(&dir).map_err(|e| Error::Io(format!("Failed to create {}: {}", dir.display(), e)))?;
+        let source = PathBuf::from(&path);
+        let progress_name = name.clone().unwrap_or_else(|| source_name(&source));
+        let package = import_into(&dir, &source, name, |tiles| {
+            let _ = app.emit(
+                "terrain-import-progress",
+                ImportProgress {
+                    name: progress_name.clone(),
+                    tiles,
+                },
+            );
+        })?;
+        info!(
+            "[TerrainPackages] Imported {} ({} tiles, {} MB) from {}",
+            package.id,
+            package.tiles,
+            package.bytes / (1024 * 1024),
+            path
+        );
+        Ok(package)
+    })
+    .await
+    .map_err(|e| Error::Internal(format!("Terrain import failed: {}", e)))?
+}
+
+/// Delete an imported terrain package
+#[tauri::command]
+pub async fn delete_terrain_package(app: AppHandle, id: String) -> Result<()> {
+    if !valid_id(&id) {
+        return Err(Error::NotFound(format!("Unknown terrain package {}", id)));
+    }
+    let package = packages_dir(&app)?.join(&id);
+    tokio::fs::remove_dir_all(&package)
+        .await
+        .map_err(|e| Error::Io(format!("Failed to delete terrain package {}: {}", id, e)))?;
+    info!("[TerrainPackages] Deleted {}", id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn names_and_paths() {
+        assert_eq!(package_id("Bay Area (z0-16)"), "bay-area-z0-16");
+        assert_eq!(package_id("  ..  "), "terrain");
+        assert!(valid_id("bay-area-2") && !valid_id("../x") && !valid_id(""));
+        assert_eq!(source_name(Path::new("/data/ksfo-terrain.tar.zst")), "ksfo-terrain");
+        assert_eq!(package_path("13/4412/2980.terrain").as_deref(), Some("13/4412/2980.terrain"));
+        assert_eq!(package_path("layer.json").as_deref(), Some("layer.json"));
+        assert_eq!(package_path("../13/1/2.terrain"), None);
+        assert_eq!(package_path("README.md"), None);
+    }
+
+    #[test]
+    fn imports_folder_and_decompresses_tiles() {
+        let root = std::env::temp_dir().join(format!("terrain-package-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let source = root.join("source/tilesets/ksfo");
+        fs::create_dir_all(source.join("0/0")).unwrap();
+        fs::create_dir_all(source.join("1/1")).unwrap();
+        fs::write(
+            source.join("layer.json"),
+            r#"{"tilejson":"2.1.0","format":"quantized-mesh-1.0","maxzoom":1,"bounds":[-123,37,-122,38],"tiles":["{z}/{x}/{y}.terrain?v={version}"]}"#,
+        )
+        .unwrap();
+        fs::write(source.join("0/0/0.terrain"), b"plain tile").unwrap();
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gz.write_all(b"gzipped tile").unwrap();
+        fs::write(source.join("1/1/0.terrain"), gz.finish().unwrap()).unwrap();
+        fs::write(source.join("notes.txt"), b"ignored").unwrap();
+
+        let dir = root.join("packages");
+        fs::create_dir_all(&dir).unwrap();
+        let package = import_into(&dir, &root.join("source"), Some("KSFO Terrain".to_string()), |_| {}).unwrap();
+        assert_eq!(package.id, "ksfo-terrain");
+        assert_eq!(package.tiles, 2);
+        assert_eq!(package.max_zoom, Some(1));
+        assert_eq!(package.bounds, Some([-123.0, 37.0, -122.0, 38.0]));
+        assert_eq!(fs::read(dir.join("ksfo-terrain/1/1/0.terrain")).unwrap(), b"gzipped tile");
+        assert!(!dir.join("ksfo-terrain/notes.txt").exists());
+
+        // A second import with the same name gets its own ID
+        let again = import_into(&dir, &root.join("source"), Some("KSFO Terrain".to_string()), |_| {}).unwrap();
+        assert_eq!(again.id, "ksfo-terrain-2");
+        assert_eq!(list_in(&dir).len(), 2);
+
+        assert!(import_into(&dir, &root.join("source/tilesets/ksfo/0"), None, |_| {}).is_err());
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,1152 @@
+//! Offline terrain and imagery cache
+//!
+//! When `tileCache.enabled` is set in global settings, the viewer loads
+//! Cesium World Terrain and the default aerial imagery through the host
+//! (`tiles://` in the app, `/api/tiles/*` for remote browsers) instead of
+//! straight from the providers. Every tile served is kept on disk, so areas
+//! that were viewed once keep working without internet.
+//!
+//! Tiles for a radius around selected airports can also be downloaded ahead
+//! of time (`prefetch`), with progress emitted as `tile-cache-progress`. Full
+//! detail is fetched within the radius up to zoom 14; higher zooms cover half
+//! the radius per level. The cache is kept under `tileCache.maxSizeGb` by
+//! deleting the least recently used tiles.
+//!
+//! Downloads can also follow final approach corridors instead of a radius:
+//! the extended centerlines of runway ends out to `tileCache.approachCorridorNm`,
+//! full detail near the threshold and one zoom level less per doubling of the
+//! distance. With `tileCache.prefetchApproaches` on, the traffic task starts
+//! one whenever the arrival runways at the active airport change, so camera
+//! pans toward final don't wait for tiles during the busiest moments.
+//!
+//! Terrain and imagery from providers other than Ion (see map_providers.rs)
+//! are always proxied through here, cached in per-provider folders. Terrain
+//! from an imported package (terrain_packages.rs) is read straight from it.
+//! Imagery requests may name an earlier vintage (`?vintage=`, see
+//! imagery_vintages.rs), which gets its own folder too.
+
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::PI;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+use axum::body::Bytes;
+use futures_util::StreamExt;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+use crate::airport_db;
+use crate::error::Error;
+use crate::http_client;
+use crate::imagery_vintages;
+use crate::geo;
+use crate::map_providers::{self, MapProviderSettings, Source};
+use crate::runways::{self, Runway};
+use crate::terrain_packages;
+use crate::GlobalTileCacheSettings;
+
+const ION_ASSETS_URL: &str = "https://api.cesium.com/v1/assets";
+
+/// Bing Maps REST API, for imagery with the user's own key
+const BING_MAPS_URL: &str = "https://dev.virtualearth.net";
+
+/// Tile URL template in layer.json files served to the viewer (Tile::parse understands it)
+const SERVED_TERRAIN_TEMPLATE: &str = "{z}/{x}/{y}.terrain?v={version}";
+
+/// Cesium Ion assets: Cesium World Terrain and Bing Maps Aerial
+const TERRAIN_ASSET_ID: u32 = 1;
+const IMAGERY_ASSET_ID: u32 = 2;
+
+/// Terrain tiles are always fetched with all extensions; Cesium skips the
+/// ones it didn't ask for
+const TERRAIN_EXTENSIONS: &str = "octvertexnormals-watermask-metadata";
+const TERRAIN_ACCEPT: &str =
+    "application/vnd.quantized-mesh;extensions=octvertexnormals-watermask-metadata,application/octet-stream;q=0.9";
+
+/// Ion endpoint access tokens expire after an hour
+const ENDPOINT_TTL: Duration = Duration::from_secs(50 * 60);
+
+/// Highest zoom prefetched over the full radius; each level above covers half
+/// the radius of the one below
+const FULL_RADIUS_MAX_ZOOM: u32 = 14;
+
+/// Default prefetch zooms
+const TERRAIN_MAX_ZOOM: u32 = 14;
+const IMAGERY_MAX_ZOOM: u32 = 17;
+
+/// Approach corridors: full detail out to this distance from the threshold (nm)
+const CORRIDOR_FULL_DETAIL_NM: f64 = 2.0;
+
+/// Approach corridors: spacing of the points sampled along the centerline and
+/// the distance covered either side of it (nm)
+const CORRIDOR_STEP_NM: f64 = 1.0;
+const CORRIDOR_HALF_WIDTH_NM: f64 = 1.0;
+
+const CONCURRENT_DOWNLOADS: usize = 8;
+
+/// Progress is emitted after this many tiles
+const PROGRESS_EVERY: usize = 25;
+
+/// After eviction, the cache is this fraction of the quota
+const EVICT_TO_FRACTION: f64 = 0.9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Layer {
+    Terrain,
+    Imagery,
+}
+
+/// A tile (or terrain layer.json) in the cache
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Tile {
+    TerrainLayer,
+    /// Geographic tiling scheme, y from the south (TMS)
+    Terrain {
+        z: u32,
+        x: u32,
+        y: u32,
+    },
+    /// Web Mercator tiling scheme, y from the north
+    Imagery {
+        z: u32,
+        x: u32,
+        y: u32,
+    },
+}
+
+impl Tile {
+    /// Parse a request path like "terrain/layer.json", "terrain/13/4412/2980.terrain"
+    /// or "imagery/15/9913/12118". Anything else is rejected.
+    pub fn parse(path: &str) -> Option<Tile> {
+        let path = path.split('?').next()?.trim_start_matches('/');
+        let (layer, rest) = path.split_once('/')?;
+        if layer == "terrain" && rest == "layer.json" {
+            return Some(Tile::TerrainLayer);
+        }
+        let rest = match layer {
+            "terrain" => rest.strip_suffix(".terrain")?,
+            "imagery" => rest,
+            _ => return None,
+        };
+        let parts: Vec<u32> = rest.split('/').map(|p| p.parse().ok()).collect::<Option<_>>()?;
+        let [z, x, y] = parts[..] else {
+            return None;
+        };
+        match layer {
+            "terrain" => Some(Tile::Terrain { z, x, y }),
+            _ => Some(Tile::Imagery { z, x, y }),
+        }
+    }
+
+    pub fn layer(&self) -> Layer {
+        match self {
+            Tile::TerrainLayer | Tile::Terrain { .. } => Layer::Terrain,
+            Tile::Imagery { .. } => Layer::Imagery,
+        }
+    }
+
+    fn relative_path(&self) -> PathBuf {
+        match self {
+            Tile::TerrainLayer => PathBuf::from("terrain/layer.json"),
+            Tile::Terrain { z, x, y } => PathBuf::from(format!("terrain/{}/{}/{}.terrain", z, x, y)),
+            Tile::Imagery { z, x, y } => PathBuf::from(format!("imagery/{}/{}/{}.jpg", z, x, y)),
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Tile::TerrainLayer => "application/json",
+            Tile::Terrain { .. } => "application/vnd.quantized-mesh",
+            Tile::Imagery { .. } => "image/jpeg",
+        }
+    }
+}
+
+/// Terrain tiles covering a radius around a point at one zoom
+pub fn terrain_tiles(latitude: f64, longitude: f64, radius_nm: f64, z: u32) -> Vec<Tile> {
+    let columns = 2f64.powi(z as i32 + 1);
+    let rows = 2f64.powi(z as i32);
+    let column = |lon: f64| ((lon + 180.0) / 360.0 * columns).floor().clamp(0.0, columns - 1.0) as u32;
+    let row = |lat: f64| ((lat + 90.0) / 180.0 * rows).floor().clamp(0.0, rows - 1.0) as u32;
+    let (south, west, north, east) = bounds(latitude, longitude, radius_nm);
+    let mut tiles = Vec::new();
+    for x in column(west)..=column(east) {
+        for y in row(south)..=row(north) {
+            tiles.push(Tile::Terrain { z, x, y });
+        }
+    }
+    tiles
+}
+
+/// Imagery tiles covering a radius around a point at one zoom
+pub fn imagery_tiles(latitude: f64, longitude: f64, radius_nm: f64, z: u32) -> Vec<Tile> {
+    let n = 2f64.powi(z as i32);
+    let column = |lon: f64| ((lon + 180.0) / 360.0 * n).floor().clamp(0.0, n - 1.0) as u32;
+    let row = |lat: f64| {
+        let lat = lat.clamp(-85.05, 85.05).to_radians();
+        ((1.0 - (lat.tan() + 1.0 / lat.cos()).ln() / PI) / 2.0 * n)
+            .floor()
+            .clamp(0.0, n - 1.0) as u32
+    };
+    let (south, west, north, east) = bounds(latitude, longitude, radius_nm);
+    let mut tiles = Vec::new();
+    for x in column(west)..=column(east) {
+        for y in row(north)..=row(south) {
+            tiles.push(Tile::Imagery { z, x, y });
+        }
+    }
+    tiles
+}
+
+/// (south, west, north, east) of a box around a point
+fn bounds(latitude: f64, longitude: f64, radius_nm: f64) -> (f64, f64, f64, f64) {
+    let dlat = radius_nm / 60.0;
+    let dlon = radius_nm / (60.0 * latitude.to_radians().cos().max(0.01));
+    (
+        (latitude - dlat).max(-90.0),
+        (longitude - dlon).max(-180.0),
+        (latitude + dlat).min(90.0),
+        (longitude + dlon).min(180.0),
+    )
+}
+
+/// Radius prefetched at a zoom
+fn radius_at_zoom(radius_nm: f64, z: u32) -> f64 {
+    radius_nm / 2f64.powi(z.saturating_sub(FULL_RADIUS_MAX_ZOOM) as i32)
+}
+
+/// Highest zoom fetched at a distance from the threshold along an approach
+/// corridor: one level less per doubling of the distance past full detail
+fn corridor_zoom(max_zoom: u32, distance_nm: f64) -> u32 {
+    let levels = (distance_nm / CORRIDOR_FULL_DETAIL_NM).log2().ceil().max(0.0) as u32;
+    max_zoom.saturating_sub(levels)
+}
+
+/// Points on the extended centerline before a runway end, from the threshold
+/// out to `length_nm`: (latitude, longitude, distance from the threshold)
+fn approach_centerline(runways: &[Runway], end: &str, length_nm: f64) -> Option<Vec<(f64, f64, f64)>> {
+    let (threshold, opposite) = runways.iter().find_map(|r| {
+        if r.low_end.ident.eq_ignore_ascii_case(end) {
+            Some((&r.low_end, &r.high_end))
+        } else if r.high_end.ident.eq_ignore_ascii_case(end) {
+            Some((&r.high_end, &r.low_end))
+        } else {
+            None
+        }
+    })?;
+    let (lat, lon) = (threshold.latitude?, threshold.longitude?);
+    let (_, runway_bearing) = geo::distance_and_bearing(lat, lon, opposite.latitude?, opposite.longitude?);
+    let steps = (length_nm / CORRIDOR_STEP_NM).ceil() as usize;
+    Some(
+        (0..=steps)
+            .map(|i| (i as f64 * CORRIDOR_STEP_NM).min(length_nm))
+            .map(|distance| {
+                let (lat, lon) = geo::destination(lat, lon, runway_bearing + 180.0, distance);
+                (lat, lon, distance)
+            })
+            .collect(),
+    )
+}
+
+/// Tiles along the final approach corridors of runway ends, nearest the
+/// threshold first (unknown ends are skipped)
+pub fn approach_tiles(
+    runways: &[Runway],
+    ends: &[String],
+    length_nm: f64,
+    max_terrain_zoom: u32,
+    max_imagery_zoom: u32,
+) -> Vec<Tile> {
+    let mut seen = HashSet::new();
+    let mut tiles = vec![Tile::TerrainLayer];
+    for end in ends {
+        let Some(points) = approach_centerline(runways, end, length_nm) else {
+            continue;
+        };
+        for (lat, lon, distance) in points {
+            let terrain = (0..=corridor_zoom(max_terrain_zoom, distance))
+                .flat_map(|z| terrain_tiles(lat, lon, CORRIDOR_HALF_WIDTH_NM, z));
+            let imagery = (1..=corridor_zoom(max_imagery_zoom, distance))
+                .flat_map(|z| imagery_tiles(lat, lon, CORRIDOR_HALF_WIDTH_NM, z));
+            tiles.extend(terrain.chain(imagery).filter(|tile| seen.insert(tile.clone())));
+        }
+    }
+    tiles
+}
+
+/// Bing quadkey of a Web Mercator tile
+fn quadkey(z: u32, x: u32, y: u32) -> String {
+    (1..=z)
+        .rev()
+        .map(|i| {
+            let mask = 1 << (i - 1);
+            let digit = (x & mask != 0) as u8 + 2 * (y & mask != 0) as u8;
+            (b'0' + digit) as char
+        })
+        .collect()
+}
+
+/// Cached files to delete to bring the cache under its quota, least recently
+/// used first. Nothing is deleted while the cache is within the quota.
+pub fn select_evictions(mut files: Vec<(PathBuf, u64, SystemTime)>, max_bytes: u64) -> Vec<PathBuf> {
+    let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    if total <= max_bytes {
+        return Vec::new();
+    }
+    let target = (max_bytes as f64 * EVICT_TO_FRACTION) as u64;
+    files.sort_by_key(|(_, _, used)| *used);
+    let mut evict = Vec::new();
+    for (path, len, _) in files {
+        if total <= target {
+            break;
+        }
+        total -= len;
+        evict.push(path);
+    }
+    evict
+}
+
+/// Where upstream tiles come from, resolved through Cesium Ion or the
+/// configured map provider
+#[derive(Clone)]
+enum Upstream {
+    Terrain {
+        /// Ends with '/'
+        url: String,
+        /// Bearer token (Ion)
+        token: Option<String>,
+        /// `key` query parameter (MapTiler)
+        key: Option<String>,
+        /// Tile path template from the provider's layer.json (None for Ion)
+        tile_template: Option<String>,
+    },
+    Imagery {
+        /// Tile URL with {subdomain} and {quadkey} (Bing), or {z}, {x} and {y}
+        url_template: String,
+        subdomains: Vec<String>,
+    },
+}
+
+/// Prefetch request
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PrefetchRequest {
+    /// Airports to download around (default: `tileCache.airports`)
+    pub airports: Vec<String>,
+    /// Runway ends whose final approach corridors to download instead of the
+    /// radius around each airport
+    pub approaches: Vec<String>,
+    /// Default: `tileCache.radiusNm`, or `tileCache.approachCorridorNm` for approaches
+    pub radius_nm: Option<f64>,
+    pub max_terrain_zoom: Option<u32>,
+    pub max_imagery_zoom: Option<u32>,
+}
+
+/// Progress of the current or last prefetch
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrefetchProgress {
+    pub airports: Vec<String>,
+    /// Runway ends whose approach corridors are downloaded (empty: radius around the airports)
+    pub approaches: Vec<String>,
+    /// Airport being downloaded
+    pub current: Option<String>,
+    pub total_tiles: usize,
+    pub done_tiles: usize,
+    /// Tiles downloaded (the rest were already cached or are unavailable)
+    pub downloaded_tiles: usize,
+    pub failed_tiles: usize,
+    pub downloaded_bytes: u64,
+    pub running: bool,
+    pub error: Option<String>,
+}
+
+/// Cache size and prefetch progress
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TileCacheStatus {
+    pub enabled: bool,
+    pub bytes: u64,
+    pub max_bytes: u64,
+    pub prefetch: Option<PrefetchProgress>,
+}
+
+/// Tile cache state (managed by Tauri)
+pub struct TileCacheState {
+    /// Resolved upstreams with the source they were resolved for
+    upstreams: Mutex<HashMap<Layer, (Source, Upstream, Instant)>>,
+    /// Approximate size of the cache (bytes)
+    bytes: AtomicU64,
+    evicting: AtomicBool,
+    prefetch: RwLock<Option<PrefetchProgress>>,
+    cancel: AtomicBool,
+    /// Airport and arrival runways whose approaches were last downloaded automatically
+    approaches: Mutex<Option<(String, Vec<String>)>>,
+}
+
+fn settings(app: &AppHandle) -> GlobalTileCacheSettings {
+    crate::read_global_settings(app.clone())
+        .map(|s| s.tile_cache)
+        .unwrap_or_default()
+}
+
+fn max_bytes(settings: &GlobalTileCacheSettings) -> u64 {
+    (settings.max_size_gb.max(0.1) * 1024.0 * 1024.0 * 1024.0) as u64
+}
+
+fn cache_root(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data directory: {}", e))?;
+    Ok(app_data.join("tile-cache"))
+}
+
+/// All cached files with size and last use
+fn list_files(root: &Path) -> Vec<(PathBuf, u64, SystemTime)> {
+    let mut files = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir).into_iter().flatten().filter_map(|e| e.ok()) {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                dirs.push(entry.path());
+            } else {
+                let used = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                files.push((entry.path(), metadata.len(), used));
+            }
+        }
+    }
+    files
+}
+
+/// Delete least recently used tiles until the cache fits its quota
+fn enforce_quota(app: &AppHandle) {
+    let state = app.state::<TileCacheState>();
+    if state.evicting.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app.state::<TileCacheState>();
+        if let Ok(root) = cache_root(&app) {
+            let files = list_files(&root);
+            let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+            let lengths: HashMap<PathBuf, u64> = files.iter().map(|(p, len, _)| (p.clone(), *len)).collect();
+            let evict = select_evictions(files, max_bytes(&settings(&app)));
+            for path in &evict {
+                if fs::remove_file(path).is_ok() {
+                    total -= lengths[path];
+                }
+            }
+            if !evict.is_empty() {
+                info!("[TileCache] Evicted {} tiles", evict.len());
+            }
+            state.bytes.store(total, Ordering::Relaxed);
+        }
+        state.evicting.store(false, Ordering::Release);
+    });
+}
+
+async fn fetch_json(client: &reqwest::Client, url: &str) -> Result<serde_json::Value, String> {
+    let response = client
+        .get(url)
+        .timeout(Duration::from_secs(30))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach tile provider: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Tile provider returned HTTP {}", response.status()));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid tile provider response: {}", e))
+}
+
+/// Resolve where a layer's tiles come from (cached for the token lifetime,
+/// or until the provider settings change)
+async fn upstream(
+    app: &AppHandle,
+    layer: Layer,
+    providers: &MapProviderSettings,
+    vintage: Option<&str>,
+) -> Result<Upstream, String> {
+    let source = map_providers::source(providers, layer, vintage)?;
+    let state = app.state::<TileCacheState>();
+    if let Some((resolved_for, upstream, fetched)) = state.upstreams.lock().get(&layer) {
+        if *resolved_for == source && fetched.elapsed() < ENDPOINT_TTL {
+            return Ok(upstream.clone());
+        }
+    }
+
+    let upstream = match &source {
+        Source::Ion => ion_upstream(app, layer).await?,
+        Source::QuantizedMesh { url, key } => {
+            let mut request = http_client::client().get(format!("{}layer.json", url));
+            if let Some(key) = key {
+                request = request.query(&[("key", key)]);
+            }
+            let layer_json: serde_json::Value = request
+                .timeout(Duration::from_secs(30))
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| format!("Failed to reach terrain provider: {}", e))?
+                .json()
+                .await
+                .map_err(|e| format!("Invalid terrain provider layer.json: {}", e))?;
+            let version = layer_json["version"].as_str().unwrap_or("1.0.0");
+            let template = layer_json["tiles"][0].as_str().unwrap_or(SERVED_TERRAIN_TEMPLATE);
+            Upstream::Terrain {
+                url: url.clone(),
+                token: None,
+                key: key.clone(),
+                tile_template: Some(template.replace("{version}", version)),
+            }
+        }
+        Source::Bing { key } => bing_upstream(BING_MAPS_URL, "Aerial", key).await?,
+        Source::Xyz { template } => Upstream::Imagery {
+            url_template: template.clone(),
+            subdomains: Vec::new(),
+        },
+        Source::Package { .. } => return Err("Terrain packages are read from disk".to_string()),
+        Source::Disabled => return Err("Terrain is turned off in the map provider settings".to_string()),
+    };
+    state.upstreams.lock().insert(layer, (source, upstream.clone(), Instant::now()));
+    Ok(upstream)
+}
+
+/// Resolve a layer's endpoint through Cesium Ion
+async fn ion_upstream(app: &AppHandle, layer: Layer) -> Result<Upstream, String> {
+    let ion_token = crate::read_global_settings(app.clone())?.cesium_ion_token;
+    if ion_token.is_empty() {
+        return Err("Cesium Ion token not configured".to_string());
+    }
+    let asset = match layer {
+        Layer::Terrain => TERRAIN_ASSET_ID,
+        Layer::Imagery => IMAGERY_ASSET_ID,
+    };
+    let endpoint = fetch_json(
+        http_client::client(),
+        &format!("{}/{}/endpoint?access_token={}", ION_ASSETS_URL, asset, ion_token),
+    )
+    .await?;
+
+    match layer {
+        Layer::Terrain => {
+            let (Some(url), Some(token)) = (endpoint["url"].as_str(), endpoint["accessToken"].as_str()) else {
+                return Err("Unexpected Cesium Ion terrain endpoint".to_string());
+            };
+            let url = if url.ends_with('/') {
+                url.to_string()
+            } else {
+                format!("{}/", url)
+            };
+            Ok(Upstream::Terrain {
+                url,
+                token: Some(token.to_string()),
+                key: None,
+                tile_template: None,
+            })
+        }
+        Layer::Imagery => {
+            let options = &endpoint["options"];
+            let (Some("BING"), Some(url), Some(key)) = (
+                endpoint["externalType"].as_str(),
+                options["url"].as_str(),
+                options["key"].as_str(),
+            ) else {
+                return Err("Unsupported Cesium Ion imagery asset".to_string());
+            };
+            let style = options["mapStyle"].as_str().unwrap_or("Aerial");
+            bing_upstream(url, style, key).await
+        }
+    }
+}
+
+/// Resolve the Bing Maps tile URL template for an imagery style
+async fn bing_upstream(url: &str, style: &str, key: &str) -> Result<Upstream, String> {
+    let metadata = fetch_json(
+        http_client::client(),
+        &format!(
+            "{}/REST/v1/Imagery/Metadata/{}?incl=ImageryProviders&key={}&uriScheme=https",
+            url.trim_end_matches('/'),
+            style,
+            key
+        ),
+    )
+    .await?;
+    let resource = &metadata["resourceSets"][0]["resources"][0];
+    let Some(url_template) = resource["imageUrl"].as_str() else {
+        return Err("Unexpected Bing imagery metadata".to_string());
+    };
+    let subdomains = resource["imageUrlSubdomains"]
+        .as_array()
+        .map(|s| s.iter().filter_map(|d| d.as_str().map(str::to_string)).collect())
+        .unwrap_or_default();
+    Ok(Upstream::Imagery {
+        url_template: url_template.replace("{culture}", ""),
+        subdomains,
+    })
+}
+
+/// Request for a terrain file, with the provider's token or key
+fn terrain_request(url: String, token: &Option<String>, key: &Option<String>) -> reqwest::RequestBuilder {
+    let mut request = http_client::client().get(url);
+    if let Some(key) = key {
+        request = request.query(&[("key", key)]);
+    }
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    request
+}
+
+/// Download a tile. `None` if the provider has no tile there.
+async fn download(
+    app: &AppHandle,
+    tile: &Tile,
+    providers: &MapProviderSettings,
+    vintage: Option<&str>,
+) -> Result<Option<Bytes>, String> {
+    let upstream = upstream(app, tile.layer(), providers, vintage).await?;
+    let client = http_client::client();
+    let request = match (tile, &upstream) {
+        (Tile::TerrainLayer, Upstream::Terrain { url, token, key, .. }) => {
+            terrain_request(format!("{}layer.json", url), token, key)
+        }
+        (Tile::Terrain { z, x, y }, Upstream::Terrain { url, token, key, tile_template }) => {
+            let path = match tile_template {
+                Some(template) => template
+                    .replace("{z}", &z.to_string())
+                    .replace("{x}", &x.to_string())
+                    .replace("{y}", &y.to_string()),
+                None => format!("{}/{}/{}.terrain?extensions={}&v=1.2.0", z, x, y, TERRAIN_EXTENSIONS),
+            };
+            terrain_request(format!("{}{}", url, path), token, key).header("Accept", TERRAIN_ACCEPT)
+        }
+        (
+            Tile::Imagery { z, x, y },
+            Upstream::Imagery {
+                url_template,
+                subdomains,
+            },
+        ) => {
+            let subdomain = match subdomains.len() {
+                0 => "",
+                n => subdomains[(x + y) as usize % n].as_str(),
+            };
+            client.get(
+                url_template
+                    .replace("{subdomain}", subdomain)
+                    .replace("{quadkey}", &quadkey(*z, *x, *y))
+                    .replace("{z}", &z.to_string())
+                    .replace("{x}", &x.to_string())
+                    .replace("{y}", &y.to_string()),
+            )
+        }
+        _ => return Err("Tile does not match its provider".to_string()),
+    };
+
+    let response = request
+        .timeout(Duration::from_secs(30))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download tile: {}", e))?;
+    let status = response.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        // Token expired early; resolve the endpoint again next time
+        app.state::<TileCacheState>().upstreams.lock().remove(&tile.layer());
+    }
+    if status == reqwest::StatusCode::NOT_FOUND || status == reqwest::StatusCode::NO_CONTENT {
+        return Ok(None);
+    }
+    if !status.is_success() {
+        return Err(format!("Tile provider returned HTTP {}", status));
+    }
+    // Bing serves a placeholder where it has no imagery
+    if response
+        .headers()
+        .get("X-VE-Tile-Info")
+        .is_some_and(|v| v.as_bytes() == b"no-tile")
+    {
+        return Ok(None);
+    }
+    let content = response
+        .bytes()
+        .await
+        .map_err(|e| format!("Failed to download tile: {}", e))?;
+    if let (Tile::TerrainLayer, Upstream::Terrain { tile_template: Some(_), .. }) = (tile, &upstream) {
+        return served_layer_json(&content).map(Some);
+    }
+    Ok(Some(content))
+}
+
+/// A provider's layer.json with its tile URLs pointing at the paths this
+/// cache serves (the provider's own template is used upstream)
+fn served_layer_json(content: &[u8]) -> Result<Bytes, String> {
+    let mut layer: serde_json::Value =
+        serde_json::from_slice(content).map_err(|e| format!("Invalid terrain provider layer.json: {}", e))?;
+    layer["tiles"] = serde_json::json!([SERVED_TERRAIN_TEMPLATE]);
+    serde_json::to_vec(&layer)
+        .map(Bytes::from)
+        .map_err(|e| format!("Invalid terrain provider layer.json: {}", e))
+}
+
+/// Cache path of a tile, in its provider's folder
+fn tile_path(root: &Path, providers: &MapProviderSettings, tile: &Tile, vintage: Option<&str>) -> PathBuf {
+    root.join(map_providers::cache_folder(providers, tile.layer(), vintage))
+        .join(tile.relative_path())
+}
+
+/// Write a downloaded tile into the cache
+async fn store(app: &AppHandle, path: &Path, content: &Bytes) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create tile cache folder: {}", e))?;
+    }
+    // Written next to the final path, then renamed, so readers never see a partial tile
+    let partial = path.with_extension("part");
+    tokio::fs::write(&partial, content)
+        .await
+        .map_err(|e| format!("Failed to write tile: {}", e))?;
+    tokio::fs::rename(&partial, path)
+        .await
+        .map_err(|e| format!("Failed to write tile: {}", e))?;
+
+    let state = app.state::<TileCacheState>();
+    let bytes = state.bytes.fetch_add(content.len() as u64, Ordering::Relaxed) + content.len() as u64;
+    if bytes > max_bytes(&settings(app)) {
+        enforce_quota(app);
+    }
+    Ok(())
+}
+
+/// A tile from the cache, downloading it first if needed. `None` if the
+/// provider has no tile there.
+pub async fn get_tile(app: &AppHandle, tile: &Tile, vintage: Option<&str>) -> Result<Option<Bytes>, String> {
+    let settings = crate::read_global_settings(app.clone())?;
+    if let Ok(Source::Package { id }) = map_providers::source(&settings.map_providers, tile.layer(), None) {
+        return terrain_packages::read_tile(app, &id, tile)
+            .await
+            .map(|c| c.map(Bytes::from))
+            .map_err(|e| e.to_string());
+    }
+    let path = tile_path(&cache_root(app)?, &settings.map_providers, tile, vintage);
+    if let Ok(content) = tokio::fs::read(&path).await {
+        // Mark as recently used for eviction
+        tauri::async_runtime::spawn_blocking(move || {
+            let _ = fs::File::options()
+                .append(true)
+                .open(&path)
+                .and_then(|f| f.set_modified(SystemTime::now()));
+        });
+        return Ok(Some(Bytes::from(content)));
+    }
+    let Some(content) = download(app, tile, &settings.map_providers, vintage).await? else {
+        return Ok(None);
+    };
+    // Other providers' tiles are proxied even with the cache off, but not kept
+    if settings.tile_cache.enabled {
+        if let Err(e) = store(app, &path, &content).await {
+            warn!("[TileCache] {}", e);
+        }
+    }
+    Ok(Some(content))
+}
+
+/// The `vintage` query parameter of a tile request
+pub fn vintage_param(path_and_query: &str) -> Option<&str> {
+    let (_, query) = path_and_query.split_once('?')?;
+    query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("vintage="))
+        .filter(|vintage| !vintage.is_empty())
+}
+
+/// Response for the `tiles://` protocol used by the app's viewer
+pub async fn protocol_response(app: &AppHandle, path_and_query: &str) -> tauri::http::Response<Vec<u8>> {
+    let (status, content_type, body) = match Tile::parse(path_and_query) {
+        None => (404, "text/plain", b"Unknown tile".to_vec()),
+        Some(tile) => match get_tile(app, &tile, vintage_param(path_and_query)).await {
+            Ok(Some(content)) => (200, tile.content_type(), content.to_vec()),
+            Ok(None) => (404, "text/plain", b"No tile".to_vec()),
+            Err(e) => (502, "text/plain", e.into_bytes()),
+        },
+    };
+    tauri::http::Response::builder()
+        .status(status)
+        .header("Content-Type", content_type)
+        .header("Access-Control-Allow-Origin", "*")
+        .body(body)
+        .unwrap_or_default()
+}
+
+fn emit_progress(app: &AppHandle, progress: &PrefetchProgress) {
+    *app.state::<TileCacheState>().prefetch.write() = Some(progress.clone());
+    let _ = app.emit("tile-cache-progress", progress);
+}
+
+/// Tiles to prefetch around an airport
+fn tiles_around(
+    latitude: f64,
+    longitude: f64,
+    radius_nm: f64,
+    max_terrain_zoom: u32,
+    max_imagery_zoom: u32,
+) -> Vec<Tile> {
+    let mut tiles = vec![Tile::TerrainLayer];
+    for z in 0..=max_terrain_zoom {
+        tiles.extend(terrain_tiles(latitude, longitude, radius_at_zoom(radius_nm, z), z));
+    }
+    for z in 1..=max_imagery_zoom {
+        tiles.extend(imagery_tiles(latitude, longitude, radius_at_zoom(radius_nm, z), z));
+    }
+    tiles
+}
+
+async fn run_prefetch(
+    app: &AppHandle,
+    request: PrefetchRequest,
+    progress: &mut PrefetchProgress,
+) -> Result<(), String> {
+    let settings = settings(app);
+    let radius_nm = request.radius_nm.unwrap_or(settings.radius_nm).clamp(1.0, 50.0);
+    let corridor_nm = request
+        .radius_nm
+        .unwrap_or(settings.approach_corridor_nm)
+        .clamp(1.0, 30.0);
+    let max_terrain_zoom = request.max_terrain_zoom.unwrap_or(TERRAIN_MAX_ZOOM).min(16);
+    let max_imagery_zoom = request.max_imagery_zoom.unwrap_or(IMAGERY_MAX_ZOOM).min(19);
+    let root = cache_root(app)?;
+    let providers = map_providers::settings(app);
+    let state = app.state::<TileCacheState>();
+
+    let mut airports = Vec::new();
+    for icao in &progress.airports {
+        match airport_db::get_airport(app, icao.clone()).await? {
+            Some(airport) => {
+                let vintage = imagery_vintages::for_airport(&providers, icao).map(str::to_string);
+                let mut tiles = if request.approaches.is_empty() {
+                    tiles_around(
+                        airport.latitude,
+                        airport.longitude,
+                        radius_nm,
+                        max_terrain_zoom,
+                        max_imagery_zoom,
+                    )
+                } else {
+                    let runways = runways::runways_for_airport(app, icao).await?;
+                    approach_tiles(&runways, &request.approaches, corridor_nm, max_terrain_zoom, max_imagery_zoom)
+                };
+                // Nothing to download for terrain from a package or turned off
+                tiles.retain(|tile| {
+                    !matches!(
+                        map_providers::source(&providers, tile.layer(), None),
+                        Ok(Source::Disabled | Source::Package { .. })
+                    )
+                });
+                progress.total_tiles += tiles.len();
+                airports.push((icao.clone(), vintage, tiles));
+            }
+            None => return Err(format!("Unknown airport {}", icao)),
+        }
+    }
+    emit_progress(app, progress);
+
+    for (icao, vintage, tiles) in airports {
+        progress.current = Some(icao.clone());
+        // Imagery in the vintage chosen for the airport
+        let vintage = vintage.as_deref();
+        let mut results = futures_util::stream::iter(tiles)
+            .map(|tile| {
+                let path = tile_path(&root, &providers, &tile, vintage);
+                let providers = &providers;
+                async move {
+                    if tokio::fs::try_exists(&path).await.unwrap_or(false) {
+                        return Ok(None);
+                    }
+                    match download(app, &tile, providers, vintage).await? {
+                        Some(content) => {
+                            store(app, &path, &content).await?;
+                            Ok(Some(content.len() as u64))
+                        }
+                        None => Ok(None),
+                    }
+                }
+            })
+            .buffer_unordered(CONCURRENT_DOWNLOADS);
+
+        while let Some(result) = results.next().await {
+            progress.done_tiles += 1;
+            match result {
+                Ok(Some(len)) => {
+                    progress.downloaded_tiles += 1;
+                    progress.downloaded_bytes += len;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    progress.failed_tiles += 1;
+                    progress.error = Some(e);
+                }
+            }
+            if progress.done_tiles % PROGRESS_EVERY == 0 {
+                emit_progress(app, progress);
+            }
+            if state.cancel.load(Ordering::Relaxed) {
+                return Err("Cancelled".to_string());
+            }
+        }
+        if progress.approaches.is_empty() {
+            info!("[TileCache] Prefetched tiles around {}", icao);
+        } else {
+            info!("[TileCache] Prefetched {} approach corridors {}", icao, progress.approaches.join(" "));
+        }
+    }
+    Ok(())
+}
+
+/// Start downloading tiles around airports in the background
+pub fn prefetch(app: &AppHandle, request: PrefetchRequest) -> Result<PrefetchProgress, Error> {
+    let state = app.state::<TileCacheState>();
+    if state.prefetch.read().as_ref().is_some_and(|p| p.running) {
+        return Err(Error::conflict("A tile download is already running"));
+    }
+    let airports: Vec<String> = if request.airports.is_empty() {
+        settings(app).airports
+    } else {
+        request.airports.clone()
+    }
+    .iter()
+    .map(|icao| icao.trim().to_uppercase())
+    .filter(|icao| !icao.is_empty())
+    .collect();
+    if airports.is_empty() {
+        return Err(Error::invalid_input("No airports selected for tile download"));
+    }
+
+    let progress = PrefetchProgress {
+        airports,
+        approaches: request.approaches.iter().map(|end| end.trim().to_uppercase()).collect(),
+        running: true,
+        ..Default::default()
+    };
+    state.cancel.store(false, Ordering::Relaxed);
+    emit_progress(app, &progress);
+
+    let app = app.clone();
+    let mut running = progress.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = run_prefetch(&app, request, &mut running).await;
+        running.running = false;
+        running.current = None;
+        if let Err(e) = result {
+            warn!("[TileCache] Prefetch stopped: {}", e);
+            running.error = Some(e);
+        }
+        info!(
+            "[TileCache] Prefetch finished: {} downloaded, {} failed of {} tiles",
+            running.downloaded_tiles, running.failed_tiles, running.total_tiles
+        );
+        emit_progress(&app, &running);
+        enforce_quota(&app);
+    });
+    Ok(progress)
+}
+
+/// Download the final approach corridors of the arrival runways at the active
+/// airport, once per airport and runway set (tile cache and
+/// `tileCache.prefetchApproaches` on). Called by the traffic task after every poll.
+pub fn prefetch_approaches(app: &AppHandle, icao: &str, ends: &[String]) {
+    if ends.is_empty() {
+        return;
+    }
+    let settings = settings(app);
+    if !settings.enabled || !settings.prefetch_approaches {
+        return;
+    }
+    let mut ends = ends.to_vec();
+    ends.sort();
+    let key = (icao.to_string(), ends);
+    let state = app.state::<TileCacheState>();
+    if state.approaches.lock().as_ref() == Some(&key) {
+        return;
+    }
+
+    let request = PrefetchRequest {
+        airports: vec![key.0.clone()],
+        approaches: key.1.clone(),
+        ..Default::default()
+    };
+    // While another download runs, this is retried after the next poll
+    if prefetch(app, request).is_ok() {
+        info!("[TileCache] Runways {} in use at {}, fetching their approaches", key.1.join(" "), key.0);
+        *state.approaches.lock() = Some(key);
+    }
+}
+
+/// Stop a running prefetch
+pub fn cancel_prefetch(app: &AppHandle) {
+    app.state::<TileCacheState>().cancel.store(true, Ordering::Relaxed);
+}
+
+/// Delete all cached tiles
+pub async fn clear(app: &AppHandle) -> Result<(), Error> {
+    let root = cache_root(app).map_err(Error::Internal)?;
+    match tokio::fs::remove_dir_all(&root).await {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(Error::Io(format!("Failed to clear tile cache: {}", e))),
+    }
+    app.state::<TileCacheState>().bytes.store(0, Ordering::Relaxed);
+    info!("[TileCache] Cleared");
+    Ok(())
+}
+
+pub fn get_status(app: &AppHandle) -> TileCacheStatus {
+    let state = app.state::<TileCacheState>();
+    let settings = settings(app);
+    let status = TileCacheStatus {
+        enabled: settings.enabled,
+        bytes: state.bytes.load(Ordering::Relaxed),
+        max_bytes: max_bytes(&settings),
+        prefetch: state.prefetch.read().clone(),
+    };
+    status
+}
+
+/// Initialize the tile cache for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_tile_cache(app: &AppHandle) {
+    app.manage(TileCacheState {
+        upstreams: Mutex::new(HashMap::new()),
+        bytes: AtomicU64::new(0),
+        evicting: AtomicBool::new(false),
+        prefetch: RwLock::new(None),
+        cancel: AtomicBool::new(false),
+        approaches: Mutex::new(None),
+    });
+    // Measures the cache (and trims it if the quota was lowered)
+    enforce_quota(app);
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the tile cache size and prefetch progress
+#[tauri::command]
+pub fn get_tile_cache_status(app: AppHandle) -> TileCacheStatus {
+    get_status(&app)
+}
+
+/// Download terrain and imagery around airports for offline use
+#[tauri::command]
+pub fn prefetch_tiles(app: AppHandle, request: PrefetchRequest) -> Result<PrefetchProgress, Error> {
+    prefetch(&app, request)
+}
+
+/// Stop a running tile download
+#[tauri::command]
+pub fn cancel_tile_prefetch(app: AppHandle) {
+    cancel_prefetch(&app)
+}
+
+/// Delete all cached terrain and imagery
+#[tauri::command]
+pub async fn clear_tile_cache(app: AppHandle) -> Result<(), Error> {
+    clear(&app).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tiles_quadkeys_and_eviction() {
+        assert_eq!(Tile::parse("terrain/layer.json"), Some(Tile::TerrainLayer));
+        assert_eq!(
+            Tile::parse("/terrain/13/4412/2980.terrain?v=1.2.0&extensions=watermask"),
+            Some(Tile::Terrain {
+                z: 13,
+                x: 4412,
+                y: 2980
+            })
+        );
+        assert_eq!(Tile::parse("imagery/3/2/1"), Some(Tile::Imagery { z: 3, x: 2, y: 1 }));
+        assert_eq!(Tile::parse("terrain/../../secret"), None);
+        assert_eq!(Tile::parse("imagery/1/2"), None);
+        assert_eq!(vintage_param("imagery/3/2/1?vintage=wayback-10"), Some("wayback-10"));
+        assert_eq!(vintage_param("imagery/3/2/1?vintage="), None);
+        assert_eq!(vintage_param("imagery/3/2/1"), None);
+
+        // KBOS at zoom 0: western half of the geographic scheme, northern row
+        assert_eq!(
+            terrain_tiles(42.36, -71.0, 5.0, 0),
+            vec![Tile::Terrain { z: 0, x: 0, y: 0 }]
+        );
+        assert_eq!(
+            imagery_tiles(42.36, -71.0, 1.0, 3),
+            vec![Tile::Imagery { z: 3, x: 2, y: 2 }]
+        );
+        // 10 nm box at zoom 10 spans a couple of tiles each way
+        let tiles = imagery_tiles(42.36, -71.0, 10.0, 10);
+        assert!(tiles.len() >= 4 && tiles.len() <= 16);
+        assert_eq!(radius_at_zoom(10.0, 16), 2.5);
+
+        // Bing's documented example
+        assert_eq!(quadkey(3, 3, 5), "213");
+
+        let file = |name: &str, len: u64, age_secs: u64| {
+            (
+                PathBuf::from(name),
+                len,
+                SystemTime::UNIX_EPOCH + Duration::from_secs(1000 - age_secs),
+            )
+        };
+        let files = vec![file("new", 40, 1), file("old", 40, 100), file("mid", 40, 50)];
+        assert!(select_evictions(files.clone(), 120).is_empty());
+        assert_eq!(select_evictions(files.clone(), 100), vec![PathBuf::from("old")]);
+        assert_eq!(
+            select_evictions(files, 70),
+            vec![PathBuf::from("old"), PathBuf::from("mid")]
+        );
+    }
+
+    #[test]
+    fn follows_final_approach_corridors() {
+        let runways = vec![runways::test_runway()];
+
+        // Landing north on 36: the corridor extends south of its threshold
+        let points = approach_centerline(&runways, "36", 12.0).unwrap();
+        assert_eq!(points.len(), 13);
+        let (lat, lon, distance) = points[12];
+        assert!((lat - 41.8).abs() < 0.01 && (lon + 71.0).abs() < 0.001);
+        assert_eq!(distance, 12.0);
+        assert!(approach_centerline(&runways, "09", 12.0).is_none());
+
+        let tiles = approach_tiles(&runways, &["36".to_string(), "27".to_string()], 12.0, 14, 17);
+        assert_eq!(tiles[0], Tile::TerrainLayer);
+        assert_eq!(tiles.iter().collect::<HashSet<_>>().len(), tiles.len());
+
+        assert_eq!(corridor_zoom(17, 0.0), 17);
+        assert_eq!(corridor_zoom(17, 2.0), 17);
+        assert_eq!(corridor_zoom(17, 3.0), 16);
+        assert_eq!(corridor_zoom(17, 12.0), 14);
+
+        // Full detail at the threshold, three levels less 12 nm out
+        let at = |lat: f64, z: u32| imagery_tiles(lat, -71.0, 0.0, z)[0].clone();
+        assert!(tiles.contains(&at(42.0, 17)));
+        assert!(!tiles.contains(&at(41.8, 17)));
+        assert!(tiles.contains(&at(41.8, 14)));
+    }
+}
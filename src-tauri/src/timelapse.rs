@@ -0,0 +1,420 @@
+//! Scheduled screenshot / timelapse capture
+//!
+//! While a timelapse is running, the backend asks the host window to capture
+//! a frame every interval (`timelapse-capture` event). The window sends the
+//! PNG back as raw bytes (`save_timelapse_frame`) and it is stored as a
+//! numbered file (`frame_00001.png`, ...) in the chosen folder. When the
+//! timelapse stops, the frames can optionally be assembled into an MP4 with
+//! ffmpeg (which must be on the PATH); the outcome is emitted as
+//! `timelapse-finished`.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+use crate::error::Error;
+use crate::supervisor::{self, Restart};
+
+/// Shortest capture interval (seconds)
+const MIN_INTERVAL_SECS: u32 = 1;
+
+/// Video frame rate when none is given
+const DEFAULT_FPS: u32 = 30;
+
+/// PNG file signature
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Assembled video file name (in the frames folder)
+const VIDEO_FILE_NAME: &str = "timelapse.mp4";
+
+/// Timelapse to start
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelapseOptions {
+    /// Folder on the host to store frames in (created if missing)
+    pub folder: String,
+    /// Seconds between frames
+    pub interval_secs: u32,
+    /// Assemble the frames into a video with ffmpeg when stopped
+    #[serde(default)]
+    pub assemble_video: bool,
+    /// Video frame rate (default: 30)
+    pub fps: Option<u32>,
+}
+
+/// Current timelapse
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelapseStatus {
+    pub active: bool,
+    pub folder: Option<String>,
+    pub interval_secs: u32,
+    pub frames_saved: u32,
+    /// Unix ms
+    pub started_at: Option<u64>,
+    pub assemble_video: bool,
+}
+
+/// Frame capture request sent to the host window
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelapseCapture {
+    pub session_id: u64,
+    /// Requested frame number (starting at 1)
+    pub frame: u32,
+}
+
+/// Result of a finished timelapse
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelapseFinished {
+    pub folder: String,
+    pub frames_saved: u32,
+    /// Assembled video, if requested and ffmpeg succeeded
+    pub video_path: Option<String>,
+    pub error: Option<String>,
+}
+
+struct TimelapseSession {
+    id: u64,
+    folder: PathBuf,
+    interval_secs: u32,
+    assemble_video: bool,
+    fps: u32,
+    frames_requested: u32,
+    frames_saved: u32,
+    started_at: u64,
+}
+
+impl TimelapseSession {
+    fn status(&self) -> TimelapseStatus {
+        TimelapseStatus {
+            active: true,
+            folder: Some(self.folder.to_string_lossy().to_string()),
+            interval_secs: self.interval_secs,
+            frames_saved: self.frames_saved,
+            started_at: Some(self.started_at),
+            assemble_video: self.assemble_video,
+        }
+    }
+
+    /// Write the next frame, numbered in arrival order
+    fn save(&mut self, png: &[u8]) -> Result<u32, Error> {
+        if !png.starts_with(PNG_SIGNATURE) {
+            return Err(Error::invalid_input("Frame is not a PNG image"));
+        }
+        let frame = self.frames_saved + 1;
+        let path = frame_path(&self.folder, frame);
+        std::fs::write(&path, png).map_err(|e| Error::Io(format!("Failed to write {}: {}", path.display(), e)))?;
+        self.frames_saved = frame;
+        Ok(frame)
+    }
+}
+
+/// Timelapse state (managed by Tauri)
+pub struct TimelapseState {
+    session: RwLock<Option<TimelapseSession>>,
+    next_id: AtomicU64,
+}
+
+impl TimelapseState {
+    pub fn new() -> Self {
+        Self {
+            session: RwLock::new(None),
+            next_id: AtomicU64::new(1),
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn frame_path(folder: &Path, frame: u32) -> PathBuf {
+    folder.join(format!("frame_{:05}.png", frame))
+}
+
+/// Current timelapse status
+pub fn get_status(app: &AppHandle) -> TimelapseStatus {
+    app.state::<TimelapseState>()
+        .session
+        .read()
+        .as_ref()
+        .map(TimelapseSession::status)
+        .unwrap_or_default()
+}
+
+/// Check the options and prepare the frames folder, returning it and the frame rate
+fn prepare(options: &TimelapseOptions) -> Result<(PathBuf, u32), Error> {
+    if options.interval_secs < MIN_INTERVAL_SECS {
+        return Err(Error::InvalidInput(format!("Interval must be at least {} second", MIN_INTERVAL_SECS)));
+    }
+    let fps = options.fps.unwrap_or(DEFAULT_FPS);
+    if !(1..=120).contains(&fps) {
+        return Err(Error::InvalidInput(format!("Unsupported frame rate: {}", fps)));
+    }
+    let folder = PathBuf::from(options.folder.trim());
+    if folder.as_os_str().is_empty() {
+        return Err(Error::invalid_input("No folder selected"));
+    }
+    std::fs::create_dir_all(&folder).map_err(|e| Error::Io(format!("Failed to create {}: {}", folder.display(), e)))?;
+    if frame_path(&folder, 1).exists() {
+        return Err(Error::Conflict(format!("{} already contains timelapse frames", folder.display())));
+    }
+    Ok((folder, fps))
+}
+
+/// Start capturing frames, replacing any running timelapse
+pub fn start(app: &AppHandle, options: TimelapseOptions) -> Result<TimelapseStatus, Error> {
+    let (folder, fps) = prepare(&options)?;
+
+    let state = app.state::<TimelapseState>();
+    let id = state.next_id.fetch_add(1, Ordering::Relaxed);
+    let session = TimelapseSession {
+        id,
+        folder,
+        interval_secs: options.interval_secs,
+        assemble_video: options.assemble_video,
+        fps,
+        frames_requested: 0,
+        frames_saved: 0,
+        started_at: now_ms(),
+    };
+    let status = session.status();
+    *state.session.write() = Some(session);
+    info!(
+        "[Timelapse] Capturing a frame every {} s to {}",
+        options.interval_secs,
+        status.folder.as_deref().unwrap_or_default()
+    );
+
+    let app = app.clone();
+    let interval = Duration::from_secs(options.interval_secs as u64);
+    supervisor::spawn("timelapse", Restart::OnPanic, move || {
+        let app = app.clone();
+        async move {
+            let mut tick = tokio::time::interval(interval);
+            loop {
+                tick.tick().await;
+                let request = {
+                    let state = app.state::<TimelapseState>();
+                    let mut session = state.session.write();
+                    match session.as_mut() {
+                        Some(session) if session.id == id => {
+                            session.frames_requested += 1;
+                            TimelapseCapture {
+                                session_id: id,
+                                frame: session.frames_requested,
+                            }
+                        }
+                        // Stopped or replaced
+                        _ => break,
+                    }
+                };
+                let _ = app.emit("timelapse-capture", &request);
+            }
+        }
+    });
+    Ok(status)
+}
+
+/// Store a captured frame. Frames are numbered in the order they arrive so the
+/// sequence has no gaps even if a capture was skipped.
+pub fn save_frame(app: &AppHandle, session_id: u64, png: &[u8]) -> Result<u32, Error> {
+    let state = app.state::<TimelapseState>();
+    let mut session = state.session.write();
+    session
+        .as_mut()
+        .filter(|s| s.id == session_id)
+        .ok_or_else(|| Error::conflict("Timelapse is not running"))?
+        .save(png)
+}
+
+/// Assemble numbered frames into an MP4 with ffmpeg
+fn assemble_video(folder: &Path, fps: u32) -> Result<PathBuf, String> {
+    let output = folder.join(VIDEO_FILE_NAME);
+    let mut cmd = Command::new("ffmpeg");
+    cmd.current_dir(folder).args([
+        "-y",
+        "-loglevel",
+        "error",
+        "-framerate",
+        &fps.to_string(),
+        "-i",
+        "frame_%05d.png",
+        // H.264 needs even dimensions
+        "-vf",
+        "pad=ceil(iw/2)*2:ceil(ih/2)*2",
+        "-c:v",
+        "libx264",
+        "-pix_fmt",
+        "yuv420p",
+        VIDEO_FILE_NAME,
+    ]);
+
+    // Hide console window on Windows (CREATE_NO_WINDOW = 0x08000000)
+    #[cfg(windows)]
+    cmd.creation_flags(0x08000000);
+
+    let result = cmd
+        .output()
+        .map_err(|e| format!("Failed to run ffmpeg (is it installed?): {}", e))?;
+    if !result.status.success() {
+        return Err(format!(
+            "ffmpeg failed: {}",
+            String::from_utf8_lossy(&result.stderr).trim()
+        ));
+    }
+    Ok(output)
+}
+
+/// Stop capturing; assembles the video in the background if requested
+pub fn stop(app: &AppHandle) -> Result<TimelapseStatus, Error> {
+    let session = app
+        .state::<TimelapseState>()
+        .session
+        .write()
+        .take()
+        .ok_or_else(|| Error::conflict("Timelapse is not running"))?;
+    let mut status = session.status();
+    status.active = false;
+    info!(
+        "[Timelapse] Stopped after {} frames in {}",
+        session.frames_saved,
+        session.folder.display()
+    );
+
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let folder = session.folder.to_string_lossy().to_string();
+        let (video_path, error) = if session.assemble_video && session.frames_saved > 0 {
+            match assemble_video(&session.folder, session.fps) {
+                Ok(path) => {
+                    info!("[Timelapse] Assembled {}", path.display());
+                    (Some(path.to_string_lossy().to_string()), None)
+                }
+                Err(e) => {
+                    warn!("[Timelapse] {}", e);
+                    (None, Some(e))
+                }
+            }
+        } else {
+            (None, None)
+        };
+        let _ = app.emit(
+            "timelapse-finished",
+            TimelapseFinished {
+                folder,
+                frames_saved: session.frames_saved,
+                video_path,
+                error,
+            },
+        );
+    });
+    Ok(status)
+}
+
+/// Initialize timelapse state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_timelapse(app: &AppHandle) {
+    app.manage(TimelapseState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the timelapse status
+#[tauri::command]
+pub fn get_timelapse_status(app: AppHandle) -> TimelapseStatus {
+    get_status(&app)
+}
+
+/// Start capturing timelapse frames into a folder
+#[tauri::command]
+pub fn start_timelapse(app: AppHandle, options: TimelapseOptions) -> Result<TimelapseStatus, Error> {
+    start(&app, options)
+}
+
+/// Stop the timelapse (and assemble the video if requested)
+#[tauri::command]
+pub fn stop_timelapse(app: AppHandle) -> Result<TimelapseStatus, Error> {
+    stop(&app)
+}
+
+/// Store a captured frame (raw PNG body, session ID in the `x-timelapse-session` header).
+/// Returns the frame number.
+#[tauri::command]
+pub fn save_timelapse_frame(app: AppHandle, request: tauri::ipc::Request<'_>) -> Result<u32, Error> {
+    let tauri::ipc::InvokeBody::Raw(png) = request.body() else {
+        return Err(Error::invalid_input("Expected raw PNG bytes"));
+    };
+    let session_id = request
+        .headers()
+        .get("x-timelapse-session")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+        .ok_or_else(|| Error::invalid_input("Missing timelapse session"))?;
+    save_frame(&app, session_id, png)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(folder: &Path, interval_secs: u32, fps: Option<u32>) -> TimelapseOptions {
+        TimelapseOptions {
+            folder: folder.to_string_lossy().to_string(),
+            interval_secs,
+            assemble_video: false,
+            fps,
+        }
+    }
+
+    #[test]
+    fn validates_options_and_numbers_frames() {
+        let folder = std::env::temp_dir().join(format!("timelapse-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&folder);
+
+        assert!(matches!(prepare(&options(&folder, 0, None)), Err(Error::InvalidInput(_))));
+        assert!(matches!(prepare(&options(&folder, 5, Some(0))), Err(Error::InvalidInput(_))));
+        assert!(matches!(prepare(&options(Path::new(" "), 5, None)), Err(Error::InvalidInput(_))));
+        let (prepared, fps) = prepare(&options(&folder, 5, None)).unwrap();
+        assert_eq!((prepared.as_path(), fps), (folder.as_path(), DEFAULT_FPS));
+        assert!(folder.is_dir());
+
+        let mut session = TimelapseSession {
+            id: 1,
+            folder: folder.clone(),
+            interval_secs: 5,
+            assemble_video: false,
+            fps,
+            frames_requested: 3,
+            frames_saved: 0,
+            started_at: 0,
+        };
+        let png = [PNG_SIGNATURE, &b"frame"[..]].concat();
+        assert!(matches!(session.save(b"GIF89a"), Err(Error::InvalidInput(_))));
+        assert_eq!(session.save(&png).unwrap(), 1);
+        assert_eq!(session.save(&png).unwrap(), 2);
+        assert!(folder.join("frame_00002.png").is_file());
+        assert_eq!(session.status().frames_saved, 2);
+
+        // A folder that already holds a timelapse isn't overwritten
+        assert!(matches!(prepare(&options(&folder, 5, None)), Err(Error::Conflict(_))));
+
+        std::fs::remove_dir_all(&folder).unwrap();
+    }
+}
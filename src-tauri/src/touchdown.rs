@@ -0,0 +1,362 @@
+//! Touchdown point and rollout prediction
+//!
+//! For arrivals on short final (lined up with a runway end, before its
+//! threshold, below 1500 ft above it), predicts where they touch down and
+//! which exit they turn off at, like the projected landing marker of some
+//! ASDE displays:
+//!
+//! - Touchdown: where the current descent path (vertical rate over
+//!   groundspeed, plus a flare) meets the runway, kept within the touchdown
+//!   zone. Without a descent the aim point 1000 ft past the landing threshold.
+//! - Rollout: from the touchdown speed (groundspeed less what's lost in the
+//!   flare, or the type's approach speed) at a typical braking rate for the
+//!   wake category down to taxi speed.
+//! - Turn-off: the exits along the runway (taxiways leaving it, see
+//!   taxi_routes.rs), each with the likelihood it is the first one the
+//!   aircraft is slow enough for: 40 kt for high-speed exits, 15 kt for
+//!   right-angle ones. Braking varies between pilots, so the rate is taken as
+//!   normally distributed around the typical one.
+//!
+//! Served at `/api/airports/{ICAO}/landing-predictions` for the active airport.
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::aircraft_types::{self, AircraftType};
+use crate::airport_db;
+use crate::error::{Error, Result};
+use crate::geo;
+use crate::runways::{self, Runway, RunwayEnd};
+use crate::taxi_routes::{self, RunwayExit};
+use crate::traffic::{self, Aircraft};
+use crate::weather;
+
+const FEET_PER_NM: f64 = 6076.12;
+const FEET_PER_METER: f64 = 3.28084;
+const FEET_PER_SECOND_PER_KT: f64 = FEET_PER_NM / 3600.0;
+
+/// Aim point past the landing threshold (feet)
+const AIM_POINT_FT: f64 = 1000.0;
+
+/// Touchdown zone past the landing threshold (feet)
+const TOUCHDOWN_ZONE_FT: f64 = 3000.0;
+
+/// Distance floated in the flare (feet)
+const FLARE_FT: f64 = 300.0;
+
+/// Speed lost in the flare (knots)
+const FLARE_LOSS_KT: f64 = 5.0;
+
+/// Descending slower than this isn't a usable descent path (feet per minute)
+const MIN_DESCENT_FPM: f64 = 100.0;
+
+/// Groundspeeds below this aren't a real approach speed (knots)
+const MIN_APPROACH_SPEED_KT: f64 = 40.0;
+
+/// Speed taxiways are turned onto (knots)
+const TAXI_SPEED_KT: f64 = 15.0;
+const HIGH_SPEED_EXIT_KT: f64 = 40.0;
+
+/// Exits turning off at most this much are high-speed exits (degrees)
+const HIGH_SPEED_EXIT_MAX_ANGLE: f64 = 45.0;
+
+/// Spread of the braking rate between landings, relative to the typical one
+const BRAKING_SPREAD: f64 = 0.25;
+
+/// Typical rollout deceleration for a wake category (m/s²)
+fn deceleration(wake_category: Option<&str>) -> f64 {
+    match wake_category {
+        Some("J" | "H") => 1.5,
+        Some("L") => 2.0,
+        _ => 1.7,
+    }
+}
+
+/// Standard normal cumulative distribution (Abramowitz and Stegun 7.1.26)
+fn normal_cdf(z: f64) -> f64 {
+    let x = z.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-x * x).exp();
+    if z >= 0.0 {
+        (1.0 + erf) / 2.0
+    } else {
+        (1.0 - erf) / 2.0
+    }
+}
+
+/// Exit an arrival may turn off at
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TurnOff {
+    /// Taxiway designator (e.g., "K")
+    pub taxiway: Option<String>,
+    /// [longitude, latitude] where it leaves the runway centerline
+    pub position: [f64; 2],
+    /// Distance past the threshold (feet)
+    pub distance_ft: f64,
+    pub high_speed: bool,
+    /// Chance this is the exit taken (0-1); what's left over rolls past every exit
+    pub likelihood: f64,
+}
+
+/// Predicted landing of an arrival on short final
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LandingPrediction {
+    pub callsign: String,
+    pub aircraft_type: Option<String>,
+    /// Runway end (e.g., "27R")
+    pub runway: String,
+    /// [longitude, latitude] of the touchdown point
+    pub touchdown: [f64; 2],
+    /// Touchdown point past the threshold (feet)
+    pub touchdown_distance_ft: f64,
+    pub seconds_to_touchdown: f64,
+    pub touchdown_speed_kt: f64,
+    /// [longitude, latitude] where the aircraft is down to taxi speed
+    pub rollout_end: [f64; 2],
+    /// Touchdown to taxi speed (feet)
+    pub rollout_distance_ft: f64,
+    /// Exits past the touchdown point, closest first
+    pub turn_offs: Vec<TurnOff>,
+}
+
+/// Aircraft on short final to a runway end
+#[derive(Debug, Clone, Copy)]
+pub struct Final<'a> {
+    pub runway: &'a Runway,
+    pub end: &'a RunwayEnd,
+    /// Distance from the threshold, negative before it (feet)
+    pub along_ft: f64,
+    /// Height above the threshold (feet)
+    pub height_ft: f64,
+    pub groundspeed_kt: f64,
+    pub vertical_rate_fpm: Option<f64>,
+}
+
+/// The short final an aircraft is on, if any
+pub fn short_final<'a>(aircraft: &Aircraft, runways: &'a [Runway], elevation_ft: f64) -> Option<Final<'a>> {
+    if aircraft.on_ground {
+        return None;
+    }
+    let alignment = runways::final_approach_end(
+        runways,
+        aircraft.latitude,
+        aircraft.longitude,
+        aircraft.track.unwrap_or(aircraft.heading),
+        0.0,
+    )?;
+    let height_ft = aircraft.altitude_ft - alignment.end.elevation_ft.unwrap_or(elevation_ft);
+    if !runways::is_short_final(&alignment, height_ft) {
+        return None;
+    }
+    let runway = runways
+        .iter()
+        .find(|r| std::ptr::eq(&r.low_end, alignment.end) || std::ptr::eq(&r.high_end, alignment.end))?;
+    Some(Final {
+        runway,
+        end: alignment.end,
+        along_ft: alignment.along_nm * FEET_PER_NM,
+        height_ft: height_ft.max(0.0),
+        groundspeed_kt: aircraft.groundspeed_kt,
+        vertical_rate_fpm: aircraft.vertical_rate_fpm,
+    })
+}
+
+/// Predict the landing of an aircraft on short final
+pub fn predict(
+    callsign: &str,
+    approach: &Final,
+    performance: Option<&AircraftType>,
+    exits: &[RunwayExit],
+) -> Option<LandingPrediction> {
+    let end = approach.end;
+    let opposite = if std::ptr::eq(end, &approach.runway.low_end) {
+        &approach.runway.high_end
+    } else {
+        &approach.runway.low_end
+    };
+    let (lat, lon) = (end.latitude?, end.longitude?);
+    let (_, bearing) = geo::distance_and_bearing(lat, lon, opposite.latitude?, opposite.longitude?);
+    let position = |along_ft: f64| {
+        let (lat, lon) = geo::destination(lat, lon, bearing, along_ft / FEET_PER_NM);
+        [lon, lat]
+    };
+
+    let approach_speed_kt = if approach.groundspeed_kt >= MIN_APPROACH_SPEED_KT {
+        approach.groundspeed_kt
+    } else {
+        performance.and_then(|p| p.approach_speed_kt)?
+    };
+
+    // Follow the descent path down, within the touchdown zone
+    let threshold_ft = end.displaced_threshold_ft;
+    let descent_fpm = approach.vertical_rate_fpm.map(|v| -v).filter(|d| *d >= MIN_DESCENT_FPM);
+    let touchdown_ft = match descent_fpm {
+        Some(descent) => {
+            let path_ft = approach.height_ft * approach_speed_kt * FEET_PER_SECOND_PER_KT * 60.0 / descent;
+            (approach.along_ft + path_ft + FLARE_FT).clamp(threshold_ft, threshold_ft + TOUCHDOWN_ZONE_FT)
+        }
+        None => threshold_ft + AIM_POINT_FT,
+    }
+    .max(approach.along_ft);
+    let seconds_to_touchdown = (touchdown_ft - approach.along_ft) / (approach_speed_kt * FEET_PER_SECOND_PER_KT);
+
+    // Braking from touchdown speed (ft/s, ft/s²)
+    let touchdown_speed_kt = (approach_speed_kt - FLARE_LOSS_KT).max(TAXI_SPEED_KT);
+    let braking = deceleration(performance.and_then(|p| p.wake_category.as_deref())) * FEET_PER_METER;
+    let speed = touchdown_speed_kt * FEET_PER_SECOND_PER_KT;
+    let slow_to = |exit_kt: f64| speed.powi(2) - (exit_kt * FEET_PER_SECOND_PER_KT).powi(2);
+    let rollout_ft = slow_to(TAXI_SPEED_KT).max(0.0) / (2.0 * braking);
+
+    // The first exit the braking rate is enough for: exit i is taken when the
+    // rate reaches what it needs but not what any earlier exit needs
+    let braking_cdf = |rate: f64| normal_cdf((rate - braking) / (braking * BRAKING_SPREAD));
+    let mut earlier_needs = f64::INFINITY;
+    let mut turn_offs = Vec::new();
+    for exit in exits.iter().filter(|e| e.along_ft > touchdown_ft) {
+        let high_speed = exit.angle <= HIGH_SPEED_EXIT_MAX_ANGLE;
+        let exit_kt = if high_speed { HIGH_SPEED_EXIT_KT } else { TAXI_SPEED_KT };
+        let needs = slow_to(exit_kt).max(0.0) / (2.0 * (exit.along_ft - touchdown_ft));
+        let likelihood = if needs < earlier_needs {
+            (braking_cdf(earlier_needs) - braking_cdf(needs)).max(0.0)
+        } else {
+            0.0
+        };
+        earlier_needs = earlier_needs.min(needs);
+        turn_offs.push(TurnOff {
+            taxiway: exit.taxiway.clone(),
+            position: [exit.longitude, exit.latitude],
+            distance_ft: exit.along_ft,
+            high_speed,
+            likelihood: (likelihood * 100.0).round() / 100.0,
+        });
+    }
+
+    Some(LandingPrediction {
+        callsign: callsign.to_string(),
+        aircraft_type: performance.map(|p| p.icao.clone()),
+        runway: end.ident.clone(),
+        touchdown: position(touchdown_ft),
+        touchdown_distance_ft: touchdown_ft.round(),
+        seconds_to_touchdown: seconds_to_touchdown.round(),
+        touchdown_speed_kt: touchdown_speed_kt.round(),
+        rollout_end: position(touchdown_ft + rollout_ft),
+        rollout_distance_ft: rollout_ft.round(),
+        turn_offs,
+    })
+}
+
+/// Predicted landings of the arrivals on short final at the active airport
+/// (none for other airports)
+pub async fn get_predictions(app: &AppHandle, icao: &str) -> Result<Vec<LandingPrediction>> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    let snapshot = traffic::get_snapshot(app);
+    if snapshot.icao.as_deref() != Some(icao.as_str()) {
+        return Ok(Vec::new());
+    }
+    let Some(airport) = airport_db::get_airport(app, icao.clone()).await.map_err(Error::io)? else {
+        return Ok(Vec::new());
+    };
+    let runways = runways::runways_for_airport(app, &icao).await.unwrap_or_default();
+    let finals: Vec<(&Aircraft, Final)> = snapshot
+        .aircraft
+        .iter()
+        .filter_map(|a| Some((a, short_final(a, &runways, airport.elevation_ft)?)))
+        .collect();
+    if finals.is_empty() {
+        return Ok(Vec::new());
+    }
+    // Without a taxiway layout there are no exits, but touchdown and rollout still hold
+    let graph = taxi_routes::graph_for(app, &icao).await.unwrap_or_default();
+
+    Ok(finals
+        .iter()
+        .filter_map(|(aircraft, approach)| {
+            let performance = aircraft.aircraft_type.as_deref().and_then(|t| aircraft_types::get(app, t));
+            let exits = graph.runway_exits(approach.runway, approach.end);
+            let mut prediction = predict(&aircraft.callsign, approach, performance.as_ref(), &exits)?;
+            prediction.aircraft_type = aircraft.aircraft_type.clone();
+            Some(prediction)
+        })
+        .collect())
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get predicted touchdown points and turn-offs for arrivals on short final at the active airport
+#[tauri::command]
+pub async fn get_landing_predictions(app: AppHandle, icao: String) -> Result<Vec<LandingPrediction>> {
+    get_predictions(&app, &icao).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn predicts_touchdown_and_turn_off() {
+        let runway = runways::test_runway();
+        // Exits 3000 ft (right angle, west), 6000 ft (high-speed, east) and 9000 ft
+        // (right angle) past the runway 36 threshold; a parallel taxiway isn't one
+        let ft = |feet: f64| 42.0 + feet / FEET_PER_NM / 60.0;
+        let layout = json!({"features": [
+            {"geometry": {"type": "LineString", "coordinates": [[-71.0, ft(3000.0)], [-71.003, ft(3000.0)]]},
+             "properties": {"kind": "taxiway", "ref": "A"}},
+            {"geometry": {"type": "LineString", "coordinates": [[-71.0, ft(6000.0)], [-70.997, ft(7500.0)]]},
+             "properties": {"kind": "taxiway", "ref": "B"}},
+            {"geometry": {"type": "LineString", "coordinates": [[-71.0, ft(9000.0)], [-70.997, ft(9000.0)]]},
+             "properties": {"kind": "taxiway", "ref": "C"}},
+            {"geometry": {"type": "LineString", "coordinates": [[-71.005, ft(0.0)], [-71.005, ft(9000.0)]]},
+             "properties": {"kind": "taxiway", "ref": "P"}}
+        ]});
+        let exits = taxi_routes::build_graph(&layout).runway_exits(&runway, &runway.low_end);
+        let names: Vec<_> = exits.iter().filter_map(|e| e.taxiway.as_deref()).collect();
+        assert_eq!(names, ["A", "B", "C"]);
+        assert!(exits[1].angle < 45.0 && exits[0].angle > 80.0);
+
+        // 370 ft above the threshold 1 nm out at 140 kt on a 3 degree path
+        let approach = Final {
+            runway: &runway,
+            end: &runway.low_end,
+            along_ft: -FEET_PER_NM,
+            height_ft: 370.0,
+            groundspeed_kt: 140.0,
+            vertical_rate_fpm: Some(-745.0),
+        };
+        let a320 = AircraftType {
+            icao: "A320".to_string(),
+            manufacturer: None,
+            model: None,
+            wake_category: Some("M".to_string()),
+            approach_speed_kt: Some(138.0),
+            wingspan_m: None,
+            length_m: None,
+        };
+        let prediction = predict("DAL1", &approach, Some(&a320), &exits).unwrap();
+        assert_eq!(prediction.runway, "36");
+        assert!((800.0..1600.0).contains(&prediction.touchdown_distance_ft));
+        assert!(prediction.seconds_to_touchdown > 25.0 && prediction.seconds_to_touchdown < 35.0);
+        // Too early for A; the high-speed exit B is the likeliest
+        assert_eq!(prediction.turn_offs.len(), 3);
+        assert_eq!(prediction.turn_offs[0].taxiway.as_deref(), Some("A"));
+        assert!(prediction.turn_offs[0].likelihood < 0.05);
+        assert!(prediction.turn_offs[1].high_speed);
+        assert!(prediction.turn_offs[1].likelihood > prediction.turn_offs[2].likelihood);
+        let total: f64 = prediction.turn_offs.iter().map(|t| t.likelihood).sum();
+        assert!(total <= 1.01);
+
+        // Level at the threshold height: the aim point
+        let level = Final {
+            vertical_rate_fpm: Some(0.0),
+            ..approach
+        };
+        let prediction = predict("DAL1", &level, None, &[]).unwrap();
+        assert_eq!(prediction.touchdown_distance_ft, AIM_POINT_FT);
+        assert!(prediction.turn_offs.is_empty());
+    }
+}
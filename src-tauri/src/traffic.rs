@@ -0,0 +1,686 @@
+//! Backend traffic picture around the active airport
+//!
+//! While VATSIM is the data source and an airport is active, polls the VATSIM
+//! data feed (same 15 second cadence as the frontend) and keeps the pilots
+//! within 60 nm of the airport. Backend services that need positions (gate
+//! occupancy, ...) are updated from here after every poll, so every connected
+//! display sees the same derived state. The last feed is kept for services
+//! that need the rest of it (online controllers, prefiled flight plans).
+//! Ground track and vertical rate, which the feed doesn't report, are derived
+//! from each pilot's recent positions, and the controller each pilot is
+//! talking to from the frequencies it is tuned to (see `afv`).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use chrono::DateTime;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager};
+use tokio::sync::watch;
+use tracing::warn;
+
+use crate::afv::{self, FrequencyContact};
+use crate::airport_db::{self, AirportRecord};
+use crate::arrival_sequence;
+use crate::atpa;
+use crate::debug_stats;
+use crate::gate_suggestions;
+use crate::gates;
+use crate::geo;
+use crate::go_arounds;
+use crate::http_client;
+use crate::kinematics::{Kinematics, PositionFix, PositionHistory};
+use crate::label_layout;
+use crate::lod::{self, ModelDetail};
+use crate::replay;
+use crate::runway_holds;
+use crate::runway_config;
+use crate::runway_occupancy;
+use crate::runway_suggestion;
+use crate::runways::{self, Runway};
+use crate::scripts;
+use crate::squawk_alerts;
+use crate::stca;
+use crate::supervisor::{self, Restart};
+use crate::tile_cache;
+use crate::trails::{self, TrailPoint};
+use crate::udp_output;
+use crate::view_presets;
+use crate::wake_timers;
+use crate::weather;
+
+const VATSIM_DATA_URL: &str = "https://data.vatsim.net/v3/vatsim-data.json";
+
+/// How often the data feed is polled
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Traffic within this distance of the airport is kept (nautical miles)
+pub const TRAFFIC_RADIUS_NM: f64 = 60.0;
+
+/// Aircraft below this height above the field and groundspeed are on the ground
+const ON_GROUND_MAX_AGL_FT: f64 = 150.0;
+const ON_GROUND_MAX_GROUNDSPEED_KT: f64 = 40.0;
+
+/// Velocities are derived over this much position history (two feed updates)
+const KINEMATICS_WINDOW: Duration = Duration::from_secs(35);
+
+/// Aircraft near the active airport
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Aircraft {
+    pub callsign: String,
+    /// VATSIM member ID
+    pub cid: u64,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Feet MSL
+    pub altitude_ft: f64,
+    pub groundspeed_kt: f64,
+    /// Degrees true
+    pub heading: f64,
+    /// Ground track (degrees true), derived from position history
+    pub track: Option<f64>,
+    /// Feet per minute, derived from position history
+    pub vertical_rate_fpm: Option<f64>,
+    pub squawk: String,
+    /// ICAO type designator from the flight plan
+    pub aircraft_type: Option<String>,
+    pub departure: Option<String>,
+    pub arrival: Option<String>,
+    /// Squawk assigned by ATC (from the flight plan)
+    pub assigned_squawk: Option<String>,
+    /// Distance from the airport (nautical miles)
+    pub distance_nm: f64,
+    pub on_ground: bool,
+    /// Controller position the aircraft is tuned to (see `afv::contact`)
+    pub on_frequency: Option<FrequencyContact>,
+    /// How to draw the aircraft at the current traffic density (see `lod`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_detail: Option<ModelDetail>,
+}
+
+/// Traffic around the active airport
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrafficSnapshot {
+    pub icao: Option<String>,
+    /// Time of the last successful poll (Unix ms)
+    pub updated_at: Option<u64>,
+    pub aircraft: Vec<Aircraft>,
+    /// Trail points added by this poll, by callsign (see `trails`)
+    pub trail_points: HashMap<String, TrailPoint>,
+}
+
+/// Controller (or ATIS) online on VATSIM
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OnlineController {
+    pub callsign: String,
+    /// VATSIM CID
+    #[serde(default)]
+    pub cid: Option<u64>,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub frequency: String,
+    /// VATSIM controller rating (see `controllers::rating_name`)
+    #[serde(default)]
+    pub rating: Option<i32>,
+    /// Logon time (ISO 8601)
+    #[serde(default, alias = "logon_time")]
+    pub logon_time: Option<String>,
+    /// VATSIM facility type (0 = observer, 1 = FSS, 2 = DEL, 3 = GND, 4 = TWR, 5 = APP, 6 = CTR)
+    #[serde(default)]
+    pub facility: u8,
+    #[serde(default, alias = "text_atis")]
+    pub text_atis: Option<Vec<String>>,
+    /// Current ATIS letter (ATIS stations only)
+    #[serde(default, alias = "atis_code")]
+    pub atis_code: Option<String>,
+}
+
+/// VATSIM data feed (only the parts used by the backend)
+#[derive(Deserialize)]
+pub struct VatsimData {
+    #[serde(default)]
+    pub pilots: Vec<VatsimPilot>,
+    #[serde(default)]
+    pub controllers: Vec<OnlineController>,
+    #[serde(default)]
+    pub atis: Vec<OnlineController>,
+    #[serde(default)]
+    pub prefiles: Vec<VatsimPrefile>,
+}
+
+#[derive(Deserialize)]
+pub struct VatsimPilot {
+    #[serde(default)]
+    pub cid: u64,
+    pub callsign: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub altitude: f64,
+    pub groundspeed: f64,
+    pub heading: f64,
+    #[serde(default)]
+    pub transponder: String,
+    pub flight_plan: Option<VatsimFlightPlan>,
+    /// Time of the pilot's last position update (RFC 3339)
+    #[serde(default)]
+    pub last_updated: String,
+}
+
+/// Flight plan filed before connecting
+#[derive(Deserialize)]
+pub struct VatsimPrefile {
+    pub callsign: String,
+    pub flight_plan: Option<VatsimFlightPlan>,
+}
+
+#[derive(Default, Deserialize)]
+pub struct VatsimFlightPlan {
+    #[serde(default)]
+    pub flight_rules: String,
+    #[serde(default)]
+    pub aircraft_short: String,
+    #[serde(default)]
+    pub departure: String,
+    #[serde(default)]
+    pub arrival: String,
+    #[serde(default)]
+    pub altitude: String,
+    /// Off-block time ("HHMM" UTC)
+    #[serde(default)]
+    pub deptime: String,
+    /// Time en route ("HHMM")
+    #[serde(default)]
+    pub enroute_time: String,
+    #[serde(default)]
+    pub route: String,
+    #[serde(default)]
+    pub assigned_transponder: String,
+    /// Remarks (may include "REG/" with the registration)
+    #[serde(default)]
+    pub remarks: String,
+}
+
+/// Traffic state (managed by Tauri)
+pub struct TrafficState {
+    snapshot: RwLock<TrafficSnapshot>,
+    /// Last VATSIM data feed fetch
+    feed: RwLock<Option<(Instant, Arc<VatsimData>)>>,
+    /// Recent positions for derived velocities
+    history: RwLock<PositionHistory>,
+    /// The active airport as of the last poll (see `subscribe_polls`)
+    polled: watch::Sender<Option<Arc<PolledAirport>>>,
+}
+
+impl TrafficState {
+    pub fn new() -> Self {
+        Self {
+            snapshot: RwLock::new(TrafficSnapshot::default()),
+            feed: RwLock::new(None),
+            history: RwLock::new(PositionHistory::new(KINEMATICS_WINDOW)),
+            polled: watch::channel(None).0,
+        }
+    }
+}
+
+/// The active airport as a poll saw it, for follow-up work that calls other
+/// services and so runs in its own task instead of holding up the next poll
+#[derive(Debug, Clone)]
+pub struct PolledAirport {
+    pub icao: String,
+    /// ATIS stations online on VATSIM
+    pub atis: Vec<OnlineController>,
+    pub runways: Vec<Runway>,
+}
+
+impl Default for TrafficState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_uppercase())
+}
+
+/// Whether an aircraft is on the ground at an airport
+pub fn is_on_ground(altitude_ft: f64, groundspeed_kt: f64, elevation_ft: f64) -> bool {
+    altitude_ft - elevation_ft < ON_GROUND_MAX_AGL_FT && groundspeed_kt < ON_GROUND_MAX_GROUNDSPEED_KT
+}
+
+/// Pilots within the traffic radius of an airport, with velocities from their position history
+pub fn nearby_aircraft(
+    pilots: &[VatsimPilot],
+    latitude: f64,
+    longitude: f64,
+    elevation_ft: f64,
+    history: &mut PositionHistory,
+    now: u64,
+) -> Vec<Aircraft> {
+    let mut aircraft: Vec<Aircraft> = pilots
+        .iter()
+        .filter_map(|pilot| {
+            let (distance_nm, _) = geo::distance_and_bearing(latitude, longitude, pilot.latitude, pilot.longitude);
+            if distance_nm > TRAFFIC_RADIUS_NM {
+                return None;
+            }
+            let plan = pilot.flight_plan.as_ref();
+            let fix = PositionFix {
+                time_ms: DateTime::parse_from_rfc3339(&pilot.last_updated)
+                    .map(|t| t.timestamp_millis().max(0) as u64)
+                    .unwrap_or(now),
+                latitude: pilot.latitude,
+                longitude: pilot.longitude,
+                altitude_ft: pilot.altitude,
+            };
+            let reported = Kinematics {
+                groundspeed_kt: Some(pilot.groundspeed),
+                ..Default::default()
+            };
+            let kinematics = history.update(&pilot.callsign, fix, reported);
+            Some(Aircraft {
+                on_ground: is_on_ground(pilot.altitude, pilot.groundspeed, elevation_ft),
+                callsign: pilot.callsign.clone(),
+                cid: pilot.cid,
+                latitude: pilot.latitude,
+                longitude: pilot.longitude,
+                altitude_ft: pilot.altitude,
+                groundspeed_kt: pilot.groundspeed,
+                heading: pilot.heading,
+                track: kinematics.track,
+                vertical_rate_fpm: kinematics.vertical_rate_fpm,
+                squawk: pilot.transponder.clone(),
+                aircraft_type: plan.and_then(|p| non_empty(&p.aircraft_short)),
+                departure: plan.and_then(|p| non_empty(&p.departure)),
+                arrival: plan.and_then(|p| non_empty(&p.arrival)),
+                assigned_squawk: plan
+                    .and_then(|p| non_empty(&p.assigned_transponder))
+                    .filter(|code| code != "0000"),
+                distance_nm: (distance_nm * 100.0).round() / 100.0,
+                on_frequency: None,
+                model_detail: None,
+            })
+        })
+        .collect();
+    aircraft.sort_by(|a, b| a.callsign.cmp(&b.callsign));
+    aircraft
+}
+
+/// Fetch the VATSIM data feed and keep it for other services
+async fn fetch_feed(app: &AppHandle) -> Result<Arc<VatsimData>, String> {
+    // A mock traffic server stands in for VATSIM while one runs (see mock_traffic.rs)
+    #[cfg(feature = "mock-traffic")]
+    let url = crate::mock_traffic::feed_url(app).unwrap_or_else(|| VATSIM_DATA_URL.to_string());
+    #[cfg(not(feature = "mock-traffic"))]
+    let url = VATSIM_DATA_URL;
+    let response = http_client::client()
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch VATSIM data: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP error: {}", response.status()));
+    }
+    let data: VatsimData = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse VATSIM data: {}", e))?;
+    let data = Arc::new(data);
+    *app.state::<TrafficState>().feed.write() = Some((Instant::now(), data.clone()));
+    Ok(data)
+}
+
+/// Whether VATSIM is the selected traffic source
+fn vatsim_selected(app: &AppHandle) -> bool {
+    crate::read_global_settings(app.clone())
+        .map(|s| s.realtraffic.data_source == "vatsim")
+        .unwrap_or(true)
+}
+
+/// Fetch the feed and publish the aircraft around an airport as the traffic
+/// snapshot (the first half of a poll, before dependent services run)
+pub(crate) async fn ingest(
+    app: &AppHandle,
+    icao: &str,
+    airport: &AirportRecord,
+) -> Result<(Arc<VatsimData>, TrafficSnapshot), String> {
+    let data = fetch_feed(app).await?;
+    let now = now_ms();
+    let mut aircraft = {
+        let state = app.state::<TrafficState>();
+        let mut history = state.history.write();
+        let aircraft = nearby_aircraft(
+            &data.pilots,
+            airport.latitude,
+            airport.longitude,
+            airport.elevation_ft,
+            &mut history,
+            now,
+        );
+        history.prune(now);
+        aircraft
+    };
+    // Mock aircraft aren't on AFV (see mock_traffic.rs)
+    #[cfg(feature = "mock-traffic")]
+    let on_afv = crate::mock_traffic::feed_url(app).is_none();
+    #[cfg(not(feature = "mock-traffic"))]
+    let on_afv = true;
+    if on_afv {
+        match afv::tuned_and_positions(app, icao).await {
+            Ok((tuned, positions)) => {
+                for a in &mut aircraft {
+                    a.on_frequency = tuned.get(&a.callsign).and_then(|f| afv::contact(f, &positions));
+                }
+            }
+            Err(e) => warn!("[Traffic] Couldn't tell who aircraft are talking to: {}", e),
+        }
+    }
+    let trail_points = aircraft
+        .iter()
+        .filter_map(|a| {
+            let point = TrailPoint {
+                time: now,
+                latitude: a.latitude,
+                longitude: a.longitude,
+                altitude_ft: a.altitude_ft,
+            };
+            trails::record(app, &a.callsign, point).map(|p| (a.callsign.clone(), p))
+        })
+        .collect();
+    trails::prune(app, now);
+    lod::observe(app, aircraft.iter().map(|a| (a.callsign.as_str(), a.distance_nm)));
+
+    let mut snapshot = TrafficSnapshot {
+        icao: Some(icao.to_string()),
+        updated_at: Some(now),
+        aircraft,
+        trail_points,
+    };
+    lod::apply(app, None, &mut snapshot);
+    *app.state::<TrafficState>().snapshot.write() = snapshot.clone();
+    Ok((data, snapshot))
+}
+
+/// Poll the feed once for an airport and update dependent services
+async fn poll(app: &AppHandle, icao: &str) -> Result<(), String> {
+    let airport = airport_db::get_airport(app, icao.to_string())
+        .await?
+        .ok_or_else(|| format!("Unknown airport: {}", icao))?;
+    let (data, snapshot) = ingest(app, icao, &airport).await?;
+
+    gates::update(app, &snapshot).await;
+    gate_suggestions::update(app, &snapshot);
+    replay::record(app, &snapshot);
+    udp_output::send_traffic(app, &snapshot);
+    scripts::dispatch(app, "traffic", &snapshot);
+    squawk_alerts::update(app, &snapshot);
+    stca::update(
+        app,
+        &snapshot,
+        (airport.latitude, airport.longitude, airport.elevation_ft),
+    );
+    label_layout::update(
+        app,
+        &snapshot,
+        (airport.latitude, airport.longitude, airport.elevation_ft),
+    );
+    let runways = runways::runways_for_airport(app, icao).await.unwrap_or_default();
+    runway_occupancy::update(app, &snapshot, &runways, airport.elevation_ft);
+    runway_holds::update(app, &snapshot, &runways);
+    go_arounds::update(app, &snapshot, &runways, airport.elevation_ft);
+    atpa::update(app, &snapshot, &runways);
+    let suggestion = runway_suggestion::get_suggestion(app, icao).await.ok().flatten();
+    runway_config::update(app, &snapshot, &runways, airport.elevation_ft, suggestion.as_ref());
+    let observed = runway_config::get_configuration(app);
+    let suggested = suggestion.map(|s| s.active).unwrap_or_default();
+    let arrival_runways = if observed.arrivals.is_empty() {
+        &suggested
+    } else {
+        &observed.arrivals
+    };
+    arrival_sequence::update(app, &snapshot, &runways, arrival_runways);
+    tile_cache::prefetch_approaches(app, icao, arrival_runways);
+    view_presets::update(
+        app,
+        icao,
+        &data.atis,
+        &runways,
+        &runway_config::active_runways(app),
+        &suggested,
+    );
+    wake_timers::update(app, &snapshot, &runways);
+    app.state::<TrafficState>().polled.send_replace(Some(Arc::new(PolledAirport {
+        icao: icao.to_string(),
+        atis: data.atis.clone(),
+        runways,
+    })));
+    Ok(())
+}
+
+/// The active airport after every poll (`None` once there is none). Receivers
+/// only see the latest value, so slow follow-up work skips polls instead of
+/// falling behind.
+pub fn subscribe_polls(app: &AppHandle) -> watch::Receiver<Option<Arc<PolledAirport>>> {
+    app.state::<TrafficState>().polled.subscribe()
+}
+
+/// Current traffic around the active airport
+pub fn get_snapshot(app: &AppHandle) -> TrafficSnapshot {
+    app.state::<TrafficState>().snapshot.read().clone()
+}
+
+/// Traffic as a GeoJSON FeatureCollection: one Point per aircraft (altitude
+/// in meters as the third coordinate, per RFC 7946) with the rest of the
+/// aircraft as properties
+pub fn to_geojson(snapshot: &TrafficSnapshot) -> Value {
+    let features: Vec<Value> = snapshot
+        .aircraft
+        .iter()
+        .map(|a| {
+            let mut properties = serde_json::to_value(a).unwrap_or_default();
+            if let Some(properties) = properties.as_object_mut() {
+                properties.remove("latitude");
+                properties.remove("longitude");
+            }
+            json!({
+                "type": "Feature",
+                "id": a.callsign,
+                "geometry": {
+                    "type": "Point",
+                    "coordinates": [a.longitude, a.latitude, (a.altitude_ft * 0.3048 * 10.0).round() / 10.0],
+                },
+                "properties": properties,
+            })
+        })
+        .collect();
+    json!({
+        "type": "FeatureCollection",
+        "icao": snapshot.icao,
+        "updatedAt": snapshot.updated_at,
+        "features": features,
+    })
+}
+
+/// The VATSIM data feed, from the traffic task's last poll or fetched on demand
+/// when that is out of date (e.g., no active airport)
+pub async fn vatsim_feed(app: &AppHandle) -> Result<Arc<VatsimData>, String> {
+    if let Some((fetched, data)) = app.state::<TrafficState>().feed.read().as_ref() {
+        if fetched.elapsed() < POLL_INTERVAL * 2 {
+            return Ok(data.clone());
+        }
+    }
+    fetch_feed(app).await
+}
+
+/// Controllers and ATIS stations online on VATSIM
+pub async fn online_controllers(app: &AppHandle) -> Result<Vec<OnlineController>, String> {
+    let data = vatsim_feed(app).await?;
+    Ok(data.controllers.iter().chain(&data.atis).cloned().collect())
+}
+
+/// Keep the traffic picture around the active airport fresh
+pub fn start_traffic_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn("traffic", Restart::Always, move || {
+        let app = app.clone();
+        async move {
+            loop {
+                let mut error = None;
+                match weather::active_airport(&app).filter(|_| vatsim_selected(&app)) {
+                    Some(icao) => {
+                        if let Err(e) = poll(&app, &icao).await {
+                            warn!("[Traffic] {}", e);
+                            error = Some(e);
+                        }
+                    }
+                    None => {
+                        *app.state::<TrafficState>().snapshot.write() = TrafficSnapshot::default();
+                        gate_suggestions::clear(&app);
+                        squawk_alerts::clear(&app);
+                        stca::clear(&app);
+                        label_layout::clear(&app);
+                        runway_occupancy::clear(&app);
+                        runway_config::clear(&app);
+                        runway_holds::clear(&app);
+                        go_arounds::clear(&app);
+                        atpa::clear(&app);
+                        arrival_sequence::clear(&app);
+                        wake_timers::clear(&app);
+                        view_presets::clear(&app);
+                        app.state::<TrafficState>().polled.send_if_modified(|polled| polled.take().is_some());
+                    }
+                }
+                debug_stats::task_ran("traffic", POLL_INTERVAL, error);
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    });
+}
+
+/// Initialize traffic state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_traffic(app: &AppHandle) {
+    app.manage(TrafficState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the backend's traffic picture around the active airport
+#[tauri::command]
+pub fn get_traffic(app: AppHandle) -> TrafficSnapshot {
+    get_snapshot(&app)
+}
+
+/// Airborne aircraft at 42N 71W shared by tests, which override fields with struct update syntax
+#[cfg(test)]
+pub fn test_aircraft(callsign: &str) -> Aircraft {
+    Aircraft {
+        callsign: callsign.to_string(),
+        cid: 1_000_000,
+        latitude: 42.0,
+        longitude: -71.0,
+        altitude_ft: 3000.0,
+        groundspeed_kt: 180.0,
+        heading: 0.0,
+        track: None,
+        vertical_rate_fpm: None,
+        squawk: "1200".to_string(),
+        aircraft_type: None,
+        departure: None,
+        arrival: None,
+        assigned_squawk: None,
+        distance_nm: 1.0,
+        on_ground: false,
+        on_frequency: None,
+        model_detail: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_nearby_aircraft() {
+        let pilot = |callsign: &str, latitude: f64, altitude: f64, groundspeed: f64| VatsimPilot {
+            cid: 1_000_000,
+            callsign: callsign.to_string(),
+            latitude,
+            longitude: -71.0,
+            altitude,
+            groundspeed,
+            heading: 90.0,
+            transponder: "2200".to_string(),
+            last_updated: "2024-05-01T12:00:00Z".to_string(),
+            flight_plan: Some(VatsimFlightPlan {
+                aircraft_short: "b738".to_string(),
+                departure: "KBOS".to_string(),
+                assigned_transponder: "0000".to_string(),
+                ..Default::default()
+            }),
+        };
+        let pilots = vec![
+            pilot("DAL123", 42.36, 20.0, 0.0),
+            pilot("AAL1", 42.5, 3000.0, 210.0),
+            pilot("FAR", 45.0, 35000.0, 450.0),
+        ];
+
+        let mut history = PositionHistory::new(KINEMATICS_WINDOW);
+        let aircraft = nearby_aircraft(&pilots, 42.36, -71.0, 20.0, &mut history, 0);
+        assert_eq!(aircraft.len(), 2);
+        assert_eq!(aircraft[0].callsign, "AAL1");
+        assert!(!aircraft[0].on_ground);
+        assert!(aircraft[1].on_ground);
+        assert_eq!(aircraft[1].aircraft_type.as_deref(), Some("B738"));
+        assert_eq!(aircraft[1].arrival, None);
+        assert_eq!(aircraft[1].assigned_squawk, None);
+    }
+
+    #[test]
+    fn converts_traffic_to_geojson() {
+        let pilot = VatsimPilot {
+            cid: 1_000_000,
+            callsign: "DAL123".to_string(),
+            latitude: 42.36,
+            longitude: -71.0,
+            altitude: 1000.0,
+            groundspeed: 150.0,
+            heading: 40.0,
+            transponder: "4521".to_string(),
+            last_updated: "2024-05-01T12:00:00Z".to_string(),
+            flight_plan: None,
+        };
+        let mut history = PositionHistory::new(KINEMATICS_WINDOW);
+        let snapshot = TrafficSnapshot {
+            icao: Some("KBOS".to_string()),
+            updated_at: Some(1_000),
+            aircraft: nearby_aircraft(&[pilot], 42.36, -71.0, 20.0, &mut history, 0),
+            trail_points: HashMap::new(),
+        };
+
+        let geojson = to_geojson(&snapshot);
+        assert_eq!(geojson["type"], "FeatureCollection");
+        assert_eq!(geojson["icao"], "KBOS");
+        let feature = &geojson["features"][0];
+        assert_eq!(feature["id"], "DAL123");
+        assert_eq!(feature["geometry"]["coordinates"], json!([-71.0, 42.36, 304.8]));
+        assert_eq!(feature["properties"]["squawk"], "4521");
+        assert!(feature["properties"].get("latitude").is_none());
+    }
+}
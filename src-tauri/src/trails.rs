@@ -0,0 +1,199 @@
+//! Aircraft trail history
+//!
+//! Keeps the last positions of every aircraft seen by the traffic sources
+//! (VATSIM polls and vNAS updates) so trails don't start over when a client
+//! reloads and look the same on every display. Points are thinned to one per
+//! few seconds; the number kept per callsign comes from the display settings.
+//! Full trails are served at `/api/aircraft/:callsign/trail`, and the points
+//! added by an update are included in the traffic snapshot and vNAS broadcasts
+//! so clients can extend the trails they already have.
+
+use std::collections::{HashMap, VecDeque};
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+
+use crate::GlobalDisplaySettings;
+
+/// Minimum time between trail points of an aircraft (ms)
+const MIN_INTERVAL_MS: u64 = 5_000;
+
+/// Trails of aircraft not seen for this long are dropped (ms)
+const STALE_MS: u64 = 300_000;
+
+/// Upper limit for the configured trail length
+const MAX_LENGTH: u32 = 720;
+
+/// Position on an aircraft's trail
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrailPoint {
+    /// Unix ms
+    pub time: u64,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Feet MSL
+    pub altitude_ft: f64,
+}
+
+/// Trail of one aircraft, oldest point first
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AircraftTrail {
+    pub callsign: String,
+    pub points: Vec<TrailPoint>,
+}
+
+/// Recent positions per callsign
+pub struct Trails {
+    /// Points kept per callsign
+    length: usize,
+    points: HashMap<String, VecDeque<TrailPoint>>,
+}
+
+impl Trails {
+    pub fn new(length: u32) -> Self {
+        Self {
+            length: length.clamp(1, MAX_LENGTH) as usize,
+            points: HashMap::new(),
+        }
+    }
+
+    /// Add a position; returns it if it was kept as a new trail point
+    pub fn record(&mut self, callsign: &str, point: TrailPoint) -> Option<TrailPoint> {
+        let trail = self.points.entry(callsign.to_string()).or_default();
+        if trail
+            .back()
+            .is_some_and(|last| point.time < last.time + MIN_INTERVAL_MS)
+        {
+            return None;
+        }
+        trail.push_back(point);
+        while trail.len() > self.length {
+            trail.pop_front();
+        }
+        Some(point)
+    }
+
+    /// Trail of an aircraft (empty if unknown)
+    pub fn trail(&self, callsign: &str) -> Vec<TrailPoint> {
+        self.points
+            .get(callsign)
+            .map(|t| t.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Callsigns with a trail and the time of their last point (Unix ms)
+    pub fn callsigns(&self) -> Vec<(String, u64)> {
+        self.points
+            .iter()
+            .filter_map(|(callsign, trail)| Some((callsign.clone(), trail.back()?.time)))
+            .collect()
+    }
+
+    /// Change the number of points kept per callsign
+    pub fn set_length(&mut self, length: u32) {
+        self.length = length.clamp(1, MAX_LENGTH) as usize;
+        for trail in self.points.values_mut() {
+            while trail.len() > self.length {
+                trail.pop_front();
+            }
+        }
+    }
+
+    /// Drop trails without a point since a while before `now_ms`
+    pub fn prune(&mut self, now_ms: u64) {
+        self.points
+            .retain(|_, trail| trail.back().is_some_and(|p| now_ms.saturating_sub(p.time) <= STALE_MS));
+    }
+}
+
+/// Trail state (managed by Tauri)
+pub struct TrailState {
+    trails: RwLock<Trails>,
+}
+
+/// Record a position for an aircraft; returns the new trail point, if any
+pub fn record(app: &AppHandle, callsign: &str, point: TrailPoint) -> Option<TrailPoint> {
+    app.state::<TrailState>().trails.write().record(callsign, point)
+}
+
+/// Drop trails of aircraft no longer reported
+pub fn prune(app: &AppHandle, now_ms: u64) {
+    app.state::<TrailState>().trails.write().prune(now_ms);
+}
+
+/// Trail of an aircraft
+pub fn get_trail(app: &AppHandle, callsign: &str) -> AircraftTrail {
+    let callsign = callsign.trim().to_uppercase();
+    AircraftTrail {
+        points: app.state::<TrailState>().trails.read().trail(&callsign),
+        callsign,
+    }
+}
+
+/// Aircraft seen recently (trails not yet pruned), with the time last seen
+pub fn recent_callsigns(app: &AppHandle) -> Vec<(String, u64)> {
+    app.state::<TrailState>().trails.read().callsigns()
+}
+
+/// Apply changed display settings (trail length)
+pub fn apply_settings(app: &AppHandle, settings: &GlobalDisplaySettings) {
+    app.state::<TrailState>()
+        .trails
+        .write()
+        .set_length(settings.trail_length);
+}
+
+/// Initialize trail state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_trails(app: &AppHandle) {
+    let length = crate::read_global_settings(app.clone())
+        .map(|s| s.display.trail_length)
+        .unwrap_or_else(|_| GlobalDisplaySettings::default().trail_length);
+    app.manage(TrailState {
+        trails: RwLock::new(Trails::new(length)),
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the recent trail of an aircraft
+#[tauri::command]
+pub fn get_aircraft_trail(app: AppHandle, callsign: String) -> AircraftTrail {
+    get_trail(&app, &callsign)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_thinned_recent_points() {
+        let point = |seconds: u64| TrailPoint {
+            time: 1_700_000_000_000 + seconds * 1000,
+            latitude: 42.0 + seconds as f64 / 1000.0,
+            longitude: -71.0,
+            altitude_ft: 3000.0,
+        };
+        let mut trails = Trails::new(3);
+
+        // 1 Hz updates are thinned to one point per 5 seconds
+        let recorded: Vec<u64> = (0..=20)
+            .filter(|&s| trails.record("DAL1", point(s)).is_some())
+            .collect();
+        assert_eq!(recorded, vec![0, 5, 10, 15, 20]);
+        // Only the configured number of points is kept
+        assert_eq!(trails.trail("DAL1"), vec![point(10), point(15), point(20)]);
+
+        trails.set_length(2);
+        assert_eq!(trails.trail("DAL1"), vec![point(15), point(20)]);
+        assert!(trails.trail("UAL2").is_empty());
+
+        trails.prune(point(20).time + STALE_MS + 1);
+        assert!(trails.points.is_empty());
+    }
+}
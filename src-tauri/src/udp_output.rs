@@ -0,0 +1,387 @@
+//! UDP JSON output for external tools
+//!
+//! Sends the merged aircraft stream (VATSIM polls around the active airport
+//! and vNAS updates) to a configurable UDP address as newline-delimited JSON,
+//! one aircraft per line, so hardware datablock displays and FIDS boards can
+//! consume it without speaking WebSocket. A broadcast address (e.g.,
+//! 192.168.1.255) reaches every listener on the LAN.
+//!
+//! Aircraft are merged by callsign: vNAS positions win while they're fresh,
+//! and flight plan fields (type, squawk, route) come from the VATSIM feed.
+//! Lines are packed into datagrams of at most `MAX_DATAGRAM_BYTES` and never
+//! split across datagrams.
+//!
+//! ```json
+//! {"callsign":"DAL123","source":"vnas","latitude":42.36,"longitude":-71.01,"altitudeFt":1200.0,...}
+//! ```
+
+use std::collections::HashMap;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+use crate::traffic::TrafficSnapshot;
+use crate::vnas::VnasAircraft;
+
+/// Largest datagram sent (stays under a typical Ethernet MTU)
+const MAX_DATAGRAM_BYTES: usize = 1400;
+
+/// vNAS positions newer than this take precedence over the VATSIM feed (ms)
+const VNAS_FRESH_MS: u64 = 10_000;
+
+/// Aircraft without an update for this long are dropped (ms)
+const STALE_MS: u64 = 60_000;
+
+/// UDP output settings in the global settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UdpOutputSettings {
+    /// Send aircraft over UDP (default: false)
+    pub enabled: bool,
+    /// Destination host or IP; broadcast addresses are allowed (default: "127.0.0.1")
+    pub address: String,
+    /// Destination port (default: 49100)
+    pub port: u16,
+}
+
+impl Default for UdpOutputSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: "127.0.0.1".to_string(),
+            port: 49100,
+        }
+    }
+}
+
+/// One line of UDP output
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UdpAircraft {
+    pub callsign: String,
+    /// "vatsim" or "vnas"
+    pub source: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Feet MSL
+    pub altitude_ft: f64,
+    pub groundspeed_kt: Option<f64>,
+    /// Degrees true
+    pub heading: f64,
+    /// Ground track (degrees true)
+    pub track: Option<f64>,
+    pub vertical_rate_fpm: Option<f64>,
+    /// ICAO type designator
+    pub aircraft_type: Option<String>,
+    pub squawk: Option<String>,
+    pub departure: Option<String>,
+    pub arrival: Option<String>,
+    /// Only known from the VATSIM feed
+    pub on_ground: Option<bool>,
+    /// Position time (Unix ms)
+    pub time: u64,
+}
+
+/// Latest merged state per callsign
+#[derive(Default)]
+pub struct MergedAircraft {
+    aircraft: HashMap<String, UdpAircraft>,
+}
+
+impl MergedAircraft {
+    /// Merge a VATSIM poll, returning the aircraft to send. Aircraft missing
+    /// from the poll are dropped unless vNAS still reports them.
+    pub fn merge_traffic(&mut self, snapshot: &TrafficSnapshot) -> Vec<UdpAircraft> {
+        let Some(now) = snapshot.updated_at else {
+            return Vec::new();
+        };
+        let mut updated = Vec::with_capacity(snapshot.aircraft.len());
+        for a in &snapshot.aircraft {
+            let from_feed = UdpAircraft {
+                callsign: a.callsign.clone(),
+                source: "vatsim".to_string(),
+                latitude: a.latitude,
+                longitude: a.longitude,
+                altitude_ft: a.altitude_ft,
+                groundspeed_kt: Some(a.groundspeed_kt),
+                heading: a.heading,
+                track: a.track,
+                vertical_rate_fpm: a.vertical_rate_fpm,
+                aircraft_type: a.aircraft_type.clone(),
+                squawk: Some(a.squawk.clone()).filter(|s| !s.is_empty()),
+                departure: a.departure.clone(),
+                arrival: a.arrival.clone(),
+                on_ground: Some(a.on_ground),
+                time: now,
+            };
+            let merged = match self.aircraft.get(&a.callsign) {
+                Some(existing) if existing.source == "vnas" && now.saturating_sub(existing.time) < VNAS_FRESH_MS => {
+                    UdpAircraft {
+                        aircraft_type: from_feed.aircraft_type.or_else(|| existing.aircraft_type.clone()),
+                        squawk: from_feed.squawk,
+                        departure: from_feed.departure,
+                        arrival: from_feed.arrival,
+                        on_ground: from_feed.on_ground,
+                        ..existing.clone()
+                    }
+                }
+                _ => from_feed,
+            };
+            self.aircraft.insert(a.callsign.clone(), merged.clone());
+            updated.push(merged);
+        }
+        self.aircraft.retain(|callsign, a| {
+            snapshot.aircraft.iter().any(|s| &s.callsign == callsign)
+                || (a.source == "vnas" && now.saturating_sub(a.time) < VNAS_FRESH_MS)
+        });
+        updated
+    }
+
+    /// Merge a vNAS batch, returning the aircraft to send
+    pub fn merge_vnas(&mut self, batch: &[VnasAircraft]) -> Vec<UdpAircraft> {
+        let mut updated = Vec::with_capacity(batch.len());
+        let mut latest = 0;
+        for v in batch {
+            let existing = self.aircraft.get(&v.callsign);
+            let merged = UdpAircraft {
+                callsign: v.callsign.clone(),
+                source: "vnas".to_string(),
+                latitude: v.lat,
+                longitude: v.lon,
+                altitude_ft: v.altitude_true / 0.3048,
+                groundspeed_kt: v.groundspeed_kt.or_else(|| existing.and_then(|e| e.groundspeed_kt)),
+                heading: v.true_heading,
+                track: v.true_ground_track,
+                vertical_rate_fpm: v.vertical_rate_fpm,
+                aircraft_type: Some(v.type_code.clone())
+                    .filter(|t| !t.is_empty())
+                    .or_else(|| existing.and_then(|e| e.aircraft_type.clone())),
+                squawk: existing.and_then(|e| e.squawk.clone()),
+                departure: existing.and_then(|e| e.departure.clone()),
+                arrival: existing.and_then(|e| e.arrival.clone()),
+                on_ground: existing.and_then(|e| e.on_ground),
+                time: v.timestamp,
+            };
+            latest = latest.max(v.timestamp);
+            self.aircraft.insert(v.callsign.clone(), merged.clone());
+            updated.push(merged);
+        }
+        self.aircraft.retain(|_, a| latest.saturating_sub(a.time) < STALE_MS);
+        updated
+    }
+}
+
+/// Newline-delimited JSON lines packed into datagrams. A line longer than
+/// the limit is sent in a datagram of its own.
+pub fn datagrams(aircraft: &[UdpAircraft]) -> Vec<Vec<u8>> {
+    let mut datagrams = Vec::new();
+    let mut current: Vec<u8> = Vec::new();
+    for a in aircraft {
+        let Ok(mut line) = serde_json::to_vec(a) else {
+            continue;
+        };
+        line.push(b'\n');
+        if !current.is_empty() && current.len() + line.len() > MAX_DATAGRAM_BYTES {
+            datagrams.push(std::mem::take(&mut current));
+        }
+        current.extend_from_slice(&line);
+    }
+    if !current.is_empty() {
+        datagrams.push(current);
+    }
+    datagrams
+}
+
+/// Destination and the socket used to reach it
+struct Target {
+    address: SocketAddr,
+    socket: UdpSocket,
+}
+
+/// UDP output state (managed by Tauri)
+pub struct UdpOutputState {
+    settings: Mutex<UdpOutputSettings>,
+    target: Mutex<Option<Target>>,
+    merged: Mutex<MergedAircraft>,
+}
+
+/// Resolve the destination and open a socket that can reach it
+fn open_target(settings: &UdpOutputSettings) -> Result<Target, String> {
+    let address = (settings.address.trim(), settings.port)
+        .to_socket_addrs()
+        .map_err(|e| format!("Invalid UDP output address {}: {}", settings.address, e))?
+        .next()
+        .ok_or_else(|| format!("UDP output address {} did not resolve", settings.address))?;
+    let bind = if address.is_ipv6() { "[::]:0" } else { "0.0.0.0:0" };
+    let socket = UdpSocket::bind(bind).map_err(|e| format!("Failed to open UDP socket: {}", e))?;
+    socket
+        .set_broadcast(true)
+        .and_then(|_| socket.set_nonblocking(true))
+        .map_err(|e| format!("Failed to configure UDP socket: {}", e))?;
+    Ok(Target { address, socket })
+}
+
+/// Apply changed UDP output settings
+pub fn apply_settings(app: &AppHandle, settings: &UdpOutputSettings) {
+    let Some(state) = app.try_state::<UdpOutputState>() else {
+        return;
+    };
+    {
+        let mut current = state.settings.lock();
+        if *current == *settings && (state.target.lock().is_some() || !settings.enabled) {
+            return;
+        }
+        *current = settings.clone();
+    }
+
+    let target = if settings.enabled {
+        match open_target(settings) {
+            Ok(target) => {
+                info!("[UdpOutput] Sending aircraft to {}", target.address);
+                Some(target)
+            }
+            Err(e) => {
+                warn!("[UdpOutput] {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+    *state.target.lock() = target;
+    *state.merged.lock() = MergedAircraft::default();
+}
+
+fn send(app: &AppHandle, merge: impl FnOnce(&mut MergedAircraft) -> Vec<UdpAircraft>) {
+    let Some(state) = app.try_state::<UdpOutputState>() else {
+        return;
+    };
+    let target = state.target.lock();
+    let Some(target) = target.as_ref() else {
+        return;
+    };
+    let aircraft = merge(&mut state.merged.lock());
+    for datagram in datagrams(&aircraft) {
+        if let Err(e) = target.socket.send_to(&datagram, target.address) {
+            warn!("[UdpOutput] Failed to send to {}: {}", target.address, e);
+            return;
+        }
+    }
+}
+
+/// Send the aircraft from a VATSIM poll
+pub fn send_traffic(app: &AppHandle, snapshot: &TrafficSnapshot) {
+    send(app, |merged| merged.merge_traffic(snapshot));
+}
+
+/// Send the aircraft from a vNAS batch
+pub fn send_vnas(app: &AppHandle, batch: &[VnasAircraft]) {
+    send(app, |merged| merged.merge_vnas(batch));
+}
+
+/// Initialize UDP output state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_udp_output(app: &AppHandle) {
+    app.manage(UdpOutputState {
+        settings: Mutex::new(UdpOutputSettings::default()),
+        target: Mutex::new(None),
+        merged: Mutex::new(MergedAircraft::default()),
+    });
+    let settings = crate::read_global_settings(app.clone())
+        .map(|s| s.udp_output)
+        .unwrap_or_default();
+    apply_settings(app, &settings);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traffic::{test_aircraft, Aircraft};
+    use crate::vnas::test_vnas_aircraft;
+
+    fn feed_aircraft(callsign: &str) -> Aircraft {
+        Aircraft {
+            heading: 40.0,
+            track: Some(42.0),
+            squawk: "4521".to_string(),
+            aircraft_type: Some("A320".to_string()),
+            departure: Some("KBOS".to_string()),
+            arrival: Some("KJFK".to_string()),
+            distance_nm: 5.0,
+            ..test_aircraft(callsign)
+        }
+    }
+
+    fn vnas_aircraft(callsign: &str, timestamp: u64) -> VnasAircraft {
+        VnasAircraft {
+            type_code: String::new(),
+            lat: 42.01,
+            lon: -71.01,
+            true_heading: 41.0,
+            altitude_true: 1000.0,
+            altitude_agl: 1000.0,
+            voice_type: 0,
+            vertical_rate_fpm: Some(-700.0),
+            ..test_vnas_aircraft(callsign, timestamp)
+        }
+    }
+
+    fn snapshot(aircraft: Vec<Aircraft>, time: u64) -> TrafficSnapshot {
+        TrafficSnapshot {
+            icao: Some("KBOS".to_string()),
+            updated_at: Some(time),
+            aircraft,
+            trail_points: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn merges_vnas_positions_with_flight_plans() {
+        let mut merged = MergedAircraft::default();
+        merged.merge_traffic(&snapshot(vec![feed_aircraft("DAL123")], 1_000));
+
+        let updated = merged.merge_vnas(&[vnas_aircraft("DAL123", 2_000)]);
+        assert_eq!(updated[0].source, "vnas");
+        assert_eq!(updated[0].latitude, 42.01);
+        assert_eq!(updated[0].aircraft_type.as_deref(), Some("A320"));
+        assert_eq!(updated[0].squawk.as_deref(), Some("4521"));
+        assert_eq!(updated[0].groundspeed_kt, Some(180.0));
+
+        // A poll while vNAS is fresh keeps the vNAS position
+        let updated = merged.merge_traffic(&snapshot(vec![feed_aircraft("DAL123")], 5_000));
+        assert_eq!(updated[0].source, "vnas");
+        assert_eq!(updated[0].latitude, 42.01);
+
+        // Once vNAS goes quiet the feed takes over again
+        let updated = merged.merge_traffic(&snapshot(vec![feed_aircraft("DAL123")], 20_000));
+        assert_eq!(updated[0].source, "vatsim");
+        assert_eq!(updated[0].latitude, 42.0);
+
+        merged.merge_traffic(&snapshot(Vec::new(), 35_000));
+        assert!(merged.aircraft.is_empty());
+    }
+
+    #[test]
+    fn packs_lines_into_datagrams() {
+        let mut merged = MergedAircraft::default();
+        let aircraft: Vec<Aircraft> = (0..40).map(|i| feed_aircraft(&format!("N{}", i))).collect();
+        let updated = merged.merge_traffic(&snapshot(aircraft, 1_000));
+
+        let datagrams = datagrams(&updated);
+        assert!(datagrams.len() > 1);
+        let mut lines = 0;
+        for datagram in &datagrams {
+            assert!(datagram.len() <= MAX_DATAGRAM_BYTES);
+            assert_eq!(datagram.last(), Some(&b'\n'));
+            for line in datagram.split(|&b| b == b'\n').filter(|l| !l.is_empty()) {
+                let value: serde_json::Value = serde_json::from_slice(line).unwrap();
+                assert!(value["callsign"].as_str().unwrap().starts_with('N'));
+                lines += 1;
+            }
+        }
+        assert_eq!(lines, 40);
+    }
+}
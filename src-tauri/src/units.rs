@@ -0,0 +1,366 @@
+//! Display units
+//!
+//! Unit preferences (altitude, speed, pressure, time format) are stored in the
+//! global settings as a default plus named profiles, so a tower display and a
+//! tablet on the same host can read values the same way. API responses with
+//! derived quantities (weather, density altitude, session stats, traffic) carry
+//! a `display` map next to the raw values, converted and formatted for the
+//! requesting client's profile. A client picks its profile with
+//! `PUT /api/units/profile`, remembered in a cookie like the negotiated client
+//! capabilities; without one, the default preferences apply.
+
+use std::collections::BTreeMap;
+
+use axum::http::{header, HeaderMap};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::density_altitude::DensityAltitude;
+use crate::session_stats::SessionStats;
+use crate::traffic::TrafficSnapshot;
+use crate::weather::Metar;
+
+/// Cookie holding the client's unit profile
+pub const COOKIE_NAME: &str = "towercab-units";
+
+/// Cookie lifetime (s)
+const COOKIE_MAX_AGE: u64 = 365 * 24 * 3600;
+
+const FEET_TO_METERS: f64 = 0.3048;
+const KNOTS_TO_KPH: f64 = 1.852;
+const HPA_PER_INHG: f64 = 33.8639;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AltitudeUnit {
+    #[default]
+    Feet,
+    Meters,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpeedUnit {
+    #[default]
+    Knots,
+    Kph,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PressureUnit {
+    #[default]
+    Inhg,
+    Hpa,
+}
+
+/// Clock format for times (always UTC)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeFormat {
+    #[default]
+    #[serde(rename = "24h")]
+    H24,
+    #[serde(rename = "12h")]
+    H12,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UnitPreferences {
+    pub altitude: AltitudeUnit,
+    pub speed: SpeedUnit,
+    pub pressure: PressureUnit,
+    pub time_format: TimeFormat,
+}
+
+/// Unit preferences in the global settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UnitSettings {
+    /// Used by the desktop app and clients without a profile
+    pub default: UnitPreferences,
+    /// Named profiles clients can pick (e.g., "metric")
+    pub profiles: BTreeMap<String, UnitPreferences>,
+}
+
+/// A value converted for display
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayValue {
+    pub value: f64,
+    /// Unit symbol ("ft", "kt", "inHg", "Z")
+    pub unit: &'static str,
+    /// Formatted value with its unit ("29.92 inHg")
+    pub text: String,
+}
+
+/// Display values by field name
+pub type Display = BTreeMap<&'static str, DisplayValue>;
+
+/// A response with display values for the client's preferences
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Localized<T, D = Display> {
+    #[serde(flatten)]
+    pub data: T,
+    pub units: UnitPreferences,
+    pub display: D,
+}
+
+impl UnitPreferences {
+    /// Altitude or height given in feet
+    pub fn altitude(&self, feet: f64) -> DisplayValue {
+        match self.altitude {
+            AltitudeUnit::Feet => value(feet.round(), "ft", 0),
+            AltitudeUnit::Meters => value((feet * FEET_TO_METERS).round(), "m", 0),
+        }
+    }
+
+    /// Speed given in knots
+    pub fn speed(&self, knots: f64) -> DisplayValue {
+        match self.speed {
+            SpeedUnit::Knots => value(knots.round(), "kt", 0),
+            SpeedUnit::Kph => value((knots * KNOTS_TO_KPH).round(), "km/h", 0),
+        }
+    }
+
+    /// Distance given in nautical miles (kilometers alongside metric speeds)
+    pub fn distance(&self, nm: f64) -> DisplayValue {
+        match self.speed {
+            SpeedUnit::Knots => value(round_to(nm, 1), "nm", 1),
+            SpeedUnit::Kph => value(round_to(nm * KNOTS_TO_KPH, 1), "km", 1),
+        }
+    }
+
+    /// Pressure given in hPa
+    pub fn pressure(&self, hpa: f64) -> DisplayValue {
+        match self.pressure {
+            PressureUnit::Inhg => value(round_to(hpa / HPA_PER_INHG, 2), "inHg", 2),
+            PressureUnit::Hpa => value(hpa.round(), "hPa", 0),
+        }
+    }
+
+    /// Time given as Unix ms (value stays in ms)
+    pub fn time(&self, unix_ms: u64) -> Option<DisplayValue> {
+        let time = DateTime::<Utc>::from_timestamp_millis(unix_ms as i64)?;
+        let text = match self.time_format {
+            TimeFormat::H24 => time.format("%H:%MZ").to_string(),
+            TimeFormat::H12 => time.format("%-I:%M %p Z").to_string(),
+        };
+        Some(DisplayValue {
+            value: unix_ms as f64,
+            unit: "Z",
+            text,
+        })
+    }
+}
+
+fn round_to(value: f64, decimals: i32) -> f64 {
+    let factor = 10f64.powi(decimals);
+    (value * factor).round() / factor
+}
+
+fn value(value: f64, unit: &'static str, decimals: usize) -> DisplayValue {
+    DisplayValue {
+        value,
+        unit,
+        text: format!("{:.*} {}", decimals, value, unit),
+    }
+}
+
+/// Display values for a METAR
+pub fn metar_display(metar: &Metar, units: &UnitPreferences) -> Display {
+    let mut display = Display::new();
+    if let Some(wind) = &metar.conditions.wind {
+        display.insert("windSpeed", units.speed(wind.speed_kt as f64));
+        if let Some(gust) = wind.gust_kt {
+            display.insert("windGust", units.speed(gust as f64));
+        }
+    }
+    if let Some(visibility_m) = metar.conditions.visibility_m {
+        let visibility = if units.altitude == AltitudeUnit::Meters {
+            value(visibility_m.round(), "m", 0)
+        } else {
+            let sm = metar.conditions.visibility_sm.unwrap_or(visibility_m / 1609.344);
+            value(round_to(sm, 1), "SM", 1)
+        };
+        display.insert("visibility", visibility);
+    }
+    if let Some(altimeter) = &metar.altimeter {
+        display.insert("altimeter", units.pressure(altimeter.hpa));
+    }
+    if let Some(ceiling) = metar.ceiling_ft {
+        display.insert("ceiling", units.altitude(ceiling as f64));
+    }
+    if let Some(elevation_m) = metar.elevation_m {
+        display.insert("elevation", units.altitude(elevation_m / FEET_TO_METERS));
+    }
+    if let Some(observed) = metar.observed_at.and_then(|t| units.time(t)) {
+        display.insert("observedAt", observed);
+    }
+    display
+}
+
+/// Display values for a density altitude report
+pub fn density_altitude_display(density: &DensityAltitude, units: &UnitPreferences) -> Display {
+    let mut display = Display::from([
+        ("elevation", units.altitude(density.elevation_ft)),
+        ("altimeter", units.pressure(density.altimeter_hpa)),
+        ("stationPressure", units.pressure(density.station_pressure_hpa)),
+        ("pressureAltitude", units.altitude(density.pressure_altitude_ft)),
+        ("densityAltitude", units.altitude(density.density_altitude_ft)),
+    ]);
+    if let Some(observed) = density.observed_at.and_then(|t| units.time(t)) {
+        display.insert("observedAt", observed);
+    }
+    display
+}
+
+/// Display values for session statistics
+pub fn session_stats_display(stats: &SessionStats, units: &UnitPreferences) -> Display {
+    let mut display = Display::new();
+    if let Some(start) = units.time(stats.start_time) {
+        display.insert("startTime", start);
+    }
+    if let Some(end) = units.time(stats.end_time) {
+        display.insert("endTime", end);
+    }
+    if let Some(spacing) = stats.average_final_spacing_nm {
+        display.insert("averageFinalSpacing", units.distance(spacing));
+    }
+    display
+}
+
+/// Display values for each aircraft in a traffic snapshot, by callsign
+pub fn traffic_display(snapshot: &TrafficSnapshot, units: &UnitPreferences) -> BTreeMap<String, Display> {
+    snapshot
+        .aircraft
+        .iter()
+        .map(|aircraft| {
+            let mut display = Display::from([
+                ("altitude", units.altitude(aircraft.altitude_ft)),
+                ("groundspeed", units.speed(aircraft.groundspeed_kt)),
+                ("distance", units.distance(aircraft.distance_nm)),
+            ]);
+            if let Some(rate) = aircraft.vertical_rate_fpm {
+                let rate = match units.altitude {
+                    AltitudeUnit::Feet => value(rate.round(), "ft/min", 0),
+                    AltitudeUnit::Meters => value(round_to(rate * FEET_TO_METERS / 60.0, 1), "m/s", 1),
+                };
+                display.insert("verticalRate", rate);
+            }
+            (aircraft.callsign.clone(), display)
+        })
+        .collect()
+}
+
+/// Wrap a response with display values for the given preferences
+pub fn localize<T, D>(
+    data: T,
+    units: UnitPreferences,
+    display: impl FnOnce(&T, &UnitPreferences) -> D,
+) -> Localized<T, D> {
+    let display = display(&data, &units);
+    Localized { data, units, display }
+}
+
+/// Profile a request's browser picked, if any
+pub fn profile_from_headers(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(|cookie| cookie.trim().split_once('='))
+        .find(|(name, _)| *name == COOKIE_NAME)
+        .map(|(_, value)| value.to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Preferences of a profile (the default for `None` or an unknown profile)
+pub fn resolve(settings: &UnitSettings, profile: Option<&str>) -> UnitPreferences {
+    profile
+        .and_then(|name| settings.profiles.get(name))
+        .copied()
+        .unwrap_or(settings.default)
+}
+
+/// `Set-Cookie` value remembering a profile (`None` clears it)
+pub fn set_cookie(profile: Option<&str>) -> String {
+    match profile {
+        Some(profile) => format!(
+            "{}={}; Path=/; Max-Age={}; SameSite=Strict",
+            COOKIE_NAME, profile, COOKIE_MAX_AGE
+        ),
+        None => format!("{}=; Path=/; Max-Age=0; SameSite=Strict", COOKIE_NAME),
+    }
+}
+
+/// Whether a profile name can be stored in the cookie
+pub fn valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn converts_and_formats_values() {
+        let imperial = UnitPreferences::default();
+        assert_eq!(imperial.altitude(3500.4).text, "3500 ft");
+        assert_eq!(imperial.speed(14.6).text, "15 kt");
+        assert_eq!(imperial.pressure(1013.25).text, "29.92 inHg");
+        assert_eq!(imperial.time(1_700_000_000_000).unwrap().text, "22:13Z");
+
+        let metric = UnitPreferences {
+            altitude: AltitudeUnit::Meters,
+            speed: SpeedUnit::Kph,
+            pressure: PressureUnit::Hpa,
+            time_format: TimeFormat::H12,
+        };
+        assert_eq!(metric.altitude(1000.0).text, "305 m");
+        assert_eq!(metric.speed(10.0).text, "19 km/h");
+        assert_eq!(metric.distance(5.0).text, "9.3 km");
+        assert_eq!(metric.pressure(1013.25).value, 1013.0);
+        assert_eq!(metric.time(1_700_000_000_000).unwrap().text, "10:13 PM Z");
+    }
+
+    #[test]
+    fn picks_the_profile_from_the_cookie() {
+        let metric = UnitPreferences {
+            altitude: AltitudeUnit::Meters,
+            ..Default::default()
+        };
+        let settings = UnitSettings {
+            default: UnitPreferences::default(),
+            profiles: BTreeMap::from([("metric".to_string(), metric)]),
+        };
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            resolve(&settings, profile_from_headers(&headers).as_deref()),
+            settings.default
+        );
+
+        let cookie = set_cookie(Some("metric"));
+        let value = cookie.split(';').next().unwrap();
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_str(&format!("a=b; {}", value)).unwrap(),
+        );
+        assert_eq!(resolve(&settings, profile_from_headers(&headers).as_deref()), metric);
+
+        headers.insert(header::COOKIE, HeaderValue::from_static("towercab-units=gone"));
+        assert_eq!(
+            resolve(&settings, profile_from_headers(&headers).as_deref()),
+            settings.default
+        );
+        assert!(!valid_profile_name("a;b"));
+    }
+}
@@ -0,0 +1,343 @@
+//! App updates
+//!
+//! Wraps the Tauri updater plugin so updates follow the settings in
+//! `update` (global settings):
+//!
+//! - `channel`: "stable" uses the `latest.json` of the latest GitHub release
+//!   (the endpoint in tauri.conf.json); "beta" uses the one of the newest
+//!   release including pre-releases (tags like `v1.4.0-beta.1`)
+//! - `skippedVersion`: automatic checks don't offer this version again
+//! - `deferredUntil`: automatic checks don't offer anything before this time
+//!
+//! "Check now" ignores skipping and deferral. Release notes for every release
+//! between the running version and the offered one come from the GitHub
+//! releases API. The update found by the last check is kept here until it is
+//! installed; download progress is emitted as `update-progress`.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_updater::{Update, UpdaterExt};
+use tracing::info;
+
+use crate::error::{Error, Result};
+use crate::{http_client, GlobalUpdateSettings};
+
+const RELEASES_URL: &str = "https://api.github.com/repos/leftos/towercab-3d/releases?per_page=30";
+
+/// Release asset the updater reads
+const MANIFEST_ASSET: &str = "latest.json";
+
+/// An update offered to the user
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInfo {
+    pub version: String,
+    pub current_version: String,
+    pub date: Option<String>,
+    /// Release notes of this version
+    pub body: Option<String>,
+    pub channel: String,
+    /// The user skipped this version (only offered by a manual check)
+    pub skipped: bool,
+}
+
+/// Release notes of one release
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseNotes {
+    pub version: String,
+    pub name: Option<String>,
+    pub date: Option<String>,
+    pub body: String,
+    pub prerelease: bool,
+    pub url: String,
+}
+
+/// Download progress (`update-progress` event)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateProgress {
+    downloaded: u64,
+    total: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    name: Option<String>,
+    body: Option<String>,
+    published_at: Option<String>,
+    draft: bool,
+    prerelease: bool,
+    html_url: String,
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+pub struct UpdaterState {
+    /// Update found by the last check
+    pending: Mutex<Option<Update>>,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+fn parse_version(tag: &str) -> Option<semver::Version> {
+    semver::Version::parse(tag.trim_start_matches('v')).ok()
+}
+
+/// Releases on the channel, newest first
+fn channel_releases(releases: Vec<GithubRelease>, channel: &str) -> Vec<(semver::Version, GithubRelease)> {
+    let mut releases: Vec<_> = releases
+        .into_iter()
+        .filter(|r| !r.draft && (channel == "beta" || !r.prerelease))
+        .filter_map(|r| Some((parse_version(&r.tag_name)?, r)))
+        .collect();
+    releases.sort_by(|a, b| b.0.cmp(&a.0));
+    releases
+}
+
+async fn fetch_releases(channel: &str) -> Result<Vec<(semver::Version, GithubRelease)>> {
+    let releases: Vec<GithubRelease> = http_client::client()
+        .get(RELEASES_URL)
+        .header("Accept", "application/vnd.github+json")
+        .timeout(Duration::from_secs(20))
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| Error::Upstream(format!("Failed to fetch releases: {}", e)))?
+        .json()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to read releases: {}", e)))?;
+    Ok(channel_releases(releases, channel))
+}
+
+/// Updater manifest URL for the channel (None: the one in tauri.conf.json)
+async fn channel_endpoint(channel: &str) -> Result<Option<url::Url>> {
+    if channel != "beta" {
+        return Ok(None);
+    }
+    let releases = fetch_releases(channel).await?;
+    let asset = releases
+        .iter()
+        .find_map(|(_, r)| r.assets.iter().find(|a| a.name == MANIFEST_ASSET))
+        .ok_or_else(|| Error::Upstream("No beta release has an update manifest".to_string()))?;
+    url::Url::parse(&asset.browser_download_url)
+        .map(Some)
+        .map_err(|e| Error::Upstream(format!("Invalid update manifest URL: {}", e)))
+}
+
+/// Why an automatic check shouldn't offer the version, if it shouldn't
+fn suppressed(settings: &GlobalUpdateSettings, version: &str, now_ms: u64) -> Option<&'static str> {
+    if settings.skipped_version.as_deref() == Some(version) {
+        return Some("skipped");
+    }
+    if settings.deferred_until.is_some_and(|until| until > now_ms) {
+        return Some("deferred");
+    }
+    None
+}
+
+fn settings(app: &AppHandle) -> GlobalUpdateSettings {
+    crate::read_global_settings(app.clone())
+        .map(|s| s.update)
+        .unwrap_or_default()
+}
+
+fn update_settings(app: &AppHandle, change: impl FnOnce(&mut GlobalUpdateSettings)) -> Result<()> {
+    let mut settings = crate::read_global_settings(app.clone())?;
+    change(&mut settings.update);
+    crate::write_global_settings(app.clone(), settings)
+}
+
+/// Initialize updater state.
+/// Call this in the Tauri setup closure, after registering the updater plugin.
+pub fn init_updater(app: &AppHandle) {
+    app.manage(UpdaterState {
+        pending: Mutex::new(None),
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Check for an update on the configured channel. Automatic checks (`manual`
+/// false) don't offer skipped versions or anything while updates are deferred.
+#[tauri::command]
+pub async fn check_for_update(app: AppHandle, manual: bool) -> Result<Option<UpdateInfo>> {
+    let settings = settings(&app);
+    let mut builder = app.updater_builder();
+    if let Some(endpoint) = channel_endpoint(&settings.channel).await? {
+        builder = builder.endpoints(vec![endpoint]).map_err(|e| Error::Internal(e.to_string()))?;
+    }
+    let update = builder
+        .build()
+        .map_err(|e| Error::Internal(e.to_string()))?
+        .check()
+        .await
+        .map_err(|e| Error::Upstream(format!("Update check failed: {}", e)))?;
+
+    let state = app.state::<UpdaterState>();
+    let Some(update) = update else {
+        *state.pending.lock() = None;
+        return Ok(None);
+    };
+    let reason = suppressed(&settings, &update.version, now_ms());
+    if let (Some(reason), false) = (reason, manual) {
+        info!("[Updater] v{} available but {}", update.version, reason);
+        return Ok(None);
+    }
+    info!(
+        "[Updater] v{} available on the {} channel",
+        update.version, settings.channel
+    );
+    let info = UpdateInfo {
+        version: update.version.clone(),
+        current_version: update.current_version.clone(),
+        date: update
+            .raw_json
+            .get("pub_date")
+            .and_then(|d| d.as_str())
+            .map(str::to_string),
+        body: update.body.clone(),
+        channel: settings.channel.clone(),
+        skipped: reason == Some("skipped"),
+    };
+    *state.pending.lock() = Some(update);
+    Ok(Some(info))
+}
+
+/// Release notes of the releases on the channel newer than the running version, newest first
+#[tauri::command]
+pub async fn get_release_notes(app: AppHandle) -> Result<Vec<ReleaseNotes>> {
+    let current = parse_version(&app.package_info().version.to_string())
+        .ok_or_else(|| Error::Internal("Invalid app version".to_string()))?;
+    let releases = fetch_releases(&settings(&app).channel).await?;
+    Ok(releases
+        .into_iter()
+        .filter(|(version, _)| *version > current)
+        .map(|(version, r)| ReleaseNotes {
+            version: version.to_string(),
+            name: r.name,
+            date: r.published_at,
+            body: r.body.unwrap_or_default(),
+            prerelease: r.prerelease,
+            url: r.html_url,
+        })
+        .collect())
+}
+
+/// Download and install the update found by the last check (restart to apply)
+#[tauri::command]
+pub async fn install_update(app: AppHandle) -> Result<()> {
+    let update = app
+        .state::<UpdaterState>()
+        .pending
+        .lock()
+        .take()
+        .ok_or_else(|| Error::not_found("No update available"))?;
+    info!("[Updater] Installing v{}", update.version);
+    let mut downloaded = 0u64;
+    let progress_app = app.clone();
+    update
+        .download_and_install(
+            |chunk, total| {
+                downloaded += chunk as u64;
+                let _ = progress_app.emit("update-progress", UpdateProgress { downloaded, total });
+            },
+            || info!("[Updater] Download finished"),
+        )
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to install update: {}", e)))?;
+    // The skip and deferral were for the old version
+    update_settings(&app, |s| {
+        s.skipped_version = None;
+        s.deferred_until = None;
+    })
+}
+
+/// Set the update channel ("stable" or "beta")
+#[tauri::command]
+pub fn set_update_channel(app: AppHandle, channel: String) -> Result<()> {
+    if channel != "stable" && channel != "beta" {
+        return Err(Error::InvalidInput(format!("Unknown update channel: {}", channel)));
+    }
+    *app.state::<UpdaterState>().pending.lock() = None;
+    info!("[Updater] Channel set to {}", channel);
+    update_settings(&app, |s| s.channel = channel)
+}
+
+/// Don't offer updates on automatic checks for the given number of hours
+#[tauri::command]
+pub fn defer_updates(app: AppHandle, hours: u32) -> Result<()> {
+    info!("[Updater] Updates deferred for {} hours", hours);
+    update_settings(&app, |s| s.deferred_until = Some(now_ms() + hours as u64 * 3_600_000))
+}
+
+/// Don't offer this version on automatic checks (None: offer skipped versions again)
+#[tauri::command]
+pub fn skip_update_version(app: AppHandle, version: Option<String>) -> Result<()> {
+    info!("[Updater] Skipped version: {:?}", version);
+    update_settings(&app, |s| s.skipped_version = version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag: &str, prerelease: bool) -> GithubRelease {
+        GithubRelease {
+            tag_name: tag.to_string(),
+            name: None,
+            body: None,
+            published_at: None,
+            draft: false,
+            prerelease,
+            html_url: String::new(),
+            assets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn filters_releases_and_suppresses_updates() {
+        let releases = || {
+            vec![
+                release("v1.2.0", false),
+                release("v1.3.0-beta.2", true),
+                release("v1.3.0-beta.10", true),
+                release("nightly", true),
+            ]
+        };
+        let tags = |channel| -> Vec<String> {
+            channel_releases(releases(), channel)
+                .into_iter()
+                .map(|(v, _)| v.to_string())
+                .collect()
+        };
+        assert_eq!(tags("stable"), ["1.2.0"]);
+        assert_eq!(tags("beta"), ["1.3.0-beta.10", "1.3.0-beta.2", "1.2.0"]);
+
+        let settings = GlobalUpdateSettings {
+            channel: "stable".to_string(),
+            skipped_version: Some("1.2.0".to_string()),
+            deferred_until: Some(2_000),
+        };
+        assert_eq!(suppressed(&settings, "1.2.0", 0), Some("skipped"));
+        assert_eq!(suppressed(&settings, "1.2.1", 1_000), Some("deferred"));
+        assert_eq!(suppressed(&settings, "1.2.1", 3_000), None);
+    }
+}
@@ -0,0 +1,599 @@
+//! VATSpy/SimAware facility mapping
+//!
+//! Parses the VATSpy data project (`VATSpy.dat`, `Boundaries.geojson`) and the
+//! SimAware TRACON project (`TRACONBoundaries.geojson`) to map airports to their
+//! FIR/ARTCC, UIR and TRACON, and controller callsigns to those facilities. This
+//! answers "who covers this airport?": which positions above the tower (approach,
+//! center, FSS) are online right now.
+//!
+//! The data files are downloaded to `vatspy/` in the app data folder and
+//! refreshed weekly. A local VATSpy data folder can be imported instead.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+use crate::error::{Error, Result};
+use crate::airport_db;
+use crate::geo;
+use crate::http_client;
+use crate::traffic::{self, OnlineController};
+use crate::weather;
+
+const VATSPY_DAT_URL: &str = "https://raw.githubusercontent.com/vatsimnetwork/vatspy-data-project/master/VATSpy.dat";
+const BOUNDARIES_URL: &str =
+    "https://raw.githubusercontent.com/vatsimnetwork/vatspy-data-project/master/Boundaries.geojson";
+const TRACON_BOUNDARIES_URL: &str =
+    "https://github.com/vatsimnetwork/simaware-tracon-project/releases/latest/download/TRACONBoundaries.geojson";
+
+const VATSPY_DAT_FILE: &str = "VATSpy.dat";
+const BOUNDARIES_FILE: &str = "Boundaries.geojson";
+const TRACON_BOUNDARIES_FILE: &str = "TRACONBoundaries.geojson";
+
+/// Redownload data files older than this
+const DATA_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 3600);
+
+/// Polygons of a boundary, each a list of rings ([longitude, latitude] pairs, outer ring first)
+type Polygons = Vec<Vec<Vec<[f64; 2]>>>;
+
+struct VatSpyAirport {
+    name: String,
+    latitude: f64,
+    longitude: f64,
+    /// IATA code or FAA LID (used as callsign prefix, e.g., "BOS")
+    iata_lid: String,
+    fir: String,
+}
+
+struct FirEntry {
+    id: String,
+    name: String,
+    callsign_prefix: String,
+    boundary: String,
+}
+
+struct UirEntry {
+    id: String,
+    name: String,
+    firs: Vec<String>,
+}
+
+struct Tracon {
+    id: String,
+    name: String,
+    prefixes: Vec<String>,
+    polygons: Polygons,
+}
+
+/// Parsed VATSpy and SimAware data
+#[derive(Default)]
+pub struct VatSpyData {
+    airports: HashMap<String, VatSpyAirport>,
+    firs: Vec<FirEntry>,
+    uirs: Vec<UirEntry>,
+    /// FIR boundaries by id
+    boundaries: Vec<(String, Polygons)>,
+    tracons: Vec<Tracon>,
+}
+
+/// Facility covering an airport
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FacilityInfo {
+    pub id: String,
+    pub name: String,
+    /// Controller callsign prefixes for this facility (e.g., ["BOS"] for BOS_CTR)
+    pub callsign_prefixes: Vec<String>,
+}
+
+/// Facilities an airport belongs to
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AirportFacilities {
+    pub icao: String,
+    /// Airport name from VATSpy
+    pub name: Option<String>,
+    /// Callsign prefixes of the airport's own positions (ICAO and IATA/LID)
+    pub callsign_prefixes: Vec<String>,
+    pub tracon: Option<FacilityInfo>,
+    /// FIR or ARTCC
+    pub fir: Option<FacilityInfo>,
+    pub uir: Option<FacilityInfo>,
+}
+
+/// Online position covering an airport
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoveringPosition {
+    pub callsign: String,
+    pub name: String,
+    pub frequency: String,
+    /// "atis", "delivery", "ground", "tower", "approach", "center" or "fss"
+    pub level: String,
+    /// Facility id the position belongs to (airport ICAO, TRACON, FIR or UIR)
+    pub facility: String,
+}
+
+/// Who covers an airport
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AirportCoverage {
+    pub facilities: AirportFacilities,
+    /// Online positions, from the airport's own up to FSS
+    pub online: Vec<CoveringPosition>,
+}
+
+/// VATSpy state (managed by Tauri)
+pub struct VatSpyState {
+    data: RwLock<Option<Arc<VatSpyData>>>,
+}
+
+impl VatSpyState {
+    pub fn new() -> Self {
+        Self {
+            data: RwLock::new(None),
+        }
+    }
+}
+
+impl Default for VatSpyState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse VATSpy.dat ([Airports], [FIRs] and [UIRs] sections)
+fn parse_dat(text: &str, data: &mut VatSpyData) {
+    let mut section = String::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            section = line[1..line.len() - 1].to_uppercase();
+            continue;
+        }
+        let fields: Vec<&str> = line.split('|').map(str::trim).collect();
+        match (section.as_str(), fields.as_slice()) {
+            ("AIRPORTS", [icao, name, lat, lon, iata_lid, fir, pseudo, ..]) => {
+                // Pseudo airports are extra label positions for an existing airport
+                if *pseudo == "1" {
+                    continue;
+                }
+                let (Ok(latitude), Ok(longitude)) = (lat.parse(), lon.parse()) else {
+                    continue;
+                };
+                data.airports.insert(
+                    icao.to_uppercase(),
+                    VatSpyAirport {
+                        name: name.to_string(),
+                        latitude,
+                        longitude,
+                        iata_lid: iata_lid.to_uppercase(),
+                        fir: fir.to_uppercase(),
+                    },
+                );
+            }
+            ("FIRS", [id, name, prefix, boundary, ..]) => data.firs.push(FirEntry {
+                id: id.to_uppercase(),
+                name: name.to_string(),
+                callsign_prefix: prefix.to_uppercase(),
+                boundary: boundary.to_uppercase(),
+            }),
+            ("UIRS", [id, name, firs, ..]) => data.uirs.push(UirEntry {
+                id: id.to_uppercase(),
+                name: name.to_string(),
+                firs: firs.split(',').map(|f| f.trim().to_uppercase()).collect(),
+            }),
+            _ => {}
+        }
+    }
+}
+
+/// Polygons of a GeoJSON Polygon or MultiPolygon geometry
+fn polygons(geometry: &Value) -> Polygons {
+    let rings = |polygon: &Value| -> Vec<Vec<[f64; 2]>> {
+        polygon
+            .as_array()
+            .map(|rings| {
+                rings
+                    .iter()
+                    .filter_map(|ring| serde_json::from_value(ring.clone()).ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    match geometry["type"].as_str() {
+        Some("Polygon") => vec![rings(&geometry["coordinates"])],
+        Some("MultiPolygon") => geometry["coordinates"]
+            .as_array()
+            .map(|polygons| polygons.iter().map(rings).collect())
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+/// Whether a point is inside any polygon (outside its holes)
+fn contains(polygons: &Polygons, lat: f64, lon: f64) -> bool {
+    polygons.iter().any(|rings| {
+        rings.first().is_some_and(|outer| geo::point_in_ring(lat, lon, outer))
+            && !rings[1..].iter().any(|hole| geo::point_in_ring(lat, lon, hole))
+    })
+}
+
+/// Parse a boundaries FeatureCollection into (properties, polygons)
+fn parse_boundaries(text: &str) -> Vec<(Value, Polygons)> {
+    let Ok(collection) = serde_json::from_str::<Value>(text) else {
+        return Vec::new();
+    };
+    collection["features"]
+        .as_array()
+        .map(|features| {
+            features
+                .iter()
+                .map(|f| (f["properties"].clone(), polygons(&f["geometry"])))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Parse the data files (boundary files are optional)
+pub fn parse_data(dat: &str, boundaries: Option<&str>, tracons: Option<&str>) -> VatSpyData {
+    let mut data = VatSpyData::default();
+    parse_dat(dat, &mut data);
+    let id = |props: &Value| props["id"].as_str().unwrap_or_default().to_uppercase();
+    data.boundaries = parse_boundaries(boundaries.unwrap_or_default())
+        .into_iter()
+        .map(|(props, polygons)| (id(&props), polygons))
+        .collect();
+    data.tracons = parse_boundaries(tracons.unwrap_or_default())
+        .into_iter()
+        .map(|(props, polygons)| Tracon {
+            id: id(&props),
+            name: props["name"].as_str().unwrap_or_default().to_string(),
+            prefixes: match &props["prefix"] {
+                Value::Array(prefixes) => prefixes
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_uppercase)
+                    .collect(),
+                Value::String(prefix) => vec![prefix.to_uppercase()],
+                _ => Vec::new(),
+            },
+            polygons,
+        })
+        .collect();
+    data
+}
+
+impl VatSpyData {
+    /// Facilities for an airport; `position` is used when VATSpy doesn't list it
+    pub fn facilities(&self, icao: &str, position: Option<(f64, f64)>) -> AirportFacilities {
+        let airport = self.airports.get(icao);
+        let mut callsign_prefixes = vec![icao.to_string()];
+        if let Some(a) = airport.filter(|a| !a.iata_lid.is_empty() && a.iata_lid != icao) {
+            callsign_prefixes.push(a.iata_lid.clone());
+        }
+        let Some((lat, lon)) = airport.map(|a| (a.latitude, a.longitude)).or(position) else {
+            return AirportFacilities {
+                icao: icao.to_string(),
+                name: None,
+                callsign_prefixes,
+                tracon: None,
+                fir: None,
+                uir: None,
+            };
+        };
+
+        // FIR from VATSpy.dat, else from the boundary containing the airport
+        let fir_id = airport.map(|a| a.fir.clone()).filter(|f| !f.is_empty()).or_else(|| {
+            let (boundary, _) = self.boundaries.iter().find(|(_, p)| contains(p, lat, lon))?;
+            self.firs
+                .iter()
+                .find(|f| &f.boundary == boundary || &f.id == boundary)
+                .map(|f| f.id.clone())
+        });
+        let fir = fir_id.as_ref().and_then(|id| {
+            let entries: Vec<&FirEntry> = self.firs.iter().filter(|f| &f.id == id).collect();
+            let mut prefixes = vec![id.clone()];
+            for entry in &entries {
+                if !entry.callsign_prefix.is_empty() && !prefixes.contains(&entry.callsign_prefix) {
+                    prefixes.push(entry.callsign_prefix.clone());
+                }
+            }
+            Some(FacilityInfo {
+                id: id.clone(),
+                name: entries.first()?.name.clone(),
+                callsign_prefixes: prefixes,
+            })
+        });
+        let uir = fir_id.as_ref().and_then(|id| {
+            let uir = self.uirs.iter().find(|u| u.firs.contains(id))?;
+            Some(FacilityInfo {
+                id: uir.id.clone(),
+                name: uir.name.clone(),
+                callsign_prefixes: vec![uir.id.clone()],
+            })
+        });
+        let tracon = self
+            .tracons
+            .iter()
+            .find(|t| contains(&t.polygons, lat, lon))
+            .map(|t| FacilityInfo {
+                id: t.id.clone(),
+                name: t.name.clone(),
+                callsign_prefixes: t.prefixes.clone(),
+            });
+
+        AirportFacilities {
+            icao: icao.to_string(),
+            name: airport.map(|a| a.name.clone()),
+            callsign_prefixes,
+            tracon,
+            fir,
+            uir,
+        }
+    }
+}
+
+/// Position level from a callsign suffix (e.g., "BOS_APP" -> approach), with its sort rank
+fn position_level(suffix: &str) -> Option<(&'static str, u8)> {
+    Some(match suffix {
+        "ATIS" => ("atis", 0),
+        "DEL" => ("delivery", 1),
+        "GND" | "RMP" => ("ground", 2),
+        "TWR" => ("tower", 3),
+        "APP" | "DEP" => ("approach", 4),
+        "CTR" => ("center", 5),
+        "FSS" => ("fss", 6),
+        _ => return None,
+    })
+}
+
+/// Online positions covering an airport
+pub fn covering_positions(facilities: &AirportFacilities, controllers: &[OnlineController]) -> Vec<CoveringPosition> {
+    let matches = |facility: Option<&FacilityInfo>, prefix: &str| {
+        facility
+            .filter(|f| f.callsign_prefixes.iter().any(|p| p == prefix))
+            .map(|f| f.id.clone())
+    };
+    let local = |prefix: &str| {
+        facilities
+            .callsign_prefixes
+            .iter()
+            .any(|p| p == prefix)
+            .then(|| facilities.icao.clone())
+    };
+
+    let mut online: Vec<(u8, CoveringPosition)> = controllers
+        .iter()
+        .filter_map(|c| {
+            let callsign = c.callsign.to_uppercase();
+            let prefix = callsign.split('_').next()?;
+            let (level, rank) = position_level(callsign.rsplit('_').next()?)?;
+            let facility = match level {
+                "approach" => local(prefix).or_else(|| matches(facilities.tracon.as_ref(), prefix)),
+                "center" => matches(facilities.fir.as_ref(), prefix),
+                "fss" => matches(facilities.uir.as_ref(), prefix).or_else(|| matches(facilities.fir.as_ref(), prefix)),
+                _ => local(prefix),
+            }?;
+            Some((
+                rank,
+                CoveringPosition {
+                    callsign: c.callsign.clone(),
+                    name: c.name.clone(),
+                    frequency: c.frequency.clone(),
+                    level: level.to_string(),
+                    facility,
+                },
+            ))
+        })
+        .collect();
+    online.sort_by(|(a, pa), (b, pb)| a.cmp(b).then_with(|| pa.callsign.cmp(&pb.callsign)));
+    online.into_iter().map(|(_, p)| p).collect()
+}
+
+fn data_dir(app: &AppHandle) -> Result<PathBuf> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))?;
+    Ok(app_data.join("vatspy"))
+}
+
+fn is_stale(path: &Path) -> bool {
+    !fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < DATA_MAX_AGE)
+}
+
+async fn download(url: &str, path: &Path) -> Result<()> {
+    let response = http_client::client()
+        .get(url)
+        .timeout(Duration::from_secs(120))
+        .send()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to download {}: {}", url, e)))?;
+    if !response.status().is_success() {
+        return Err(Error::Upstream(format!("HTTP error {} for {}", response.status(), url)));
+    }
+    let bytes = response.bytes().await.map_err(|e| Error::Upstream(e.to_string()))?;
+    fs::write(path, &bytes).map_err(|e| Error::Io(format!("Failed to write {:?}: {}", path, e)))
+}
+
+fn load_from(dir: &Path) -> Result<VatSpyData> {
+    let dat = fs::read_to_string(dir.join(VATSPY_DAT_FILE))
+        .map_err(|e| Error::Io(format!("Failed to read VATSpy.dat: {}", e)))?;
+    let boundaries = fs::read_to_string(dir.join(BOUNDARIES_FILE)).ok();
+    let tracons = fs::read_to_string(dir.join(TRACON_BOUNDARIES_FILE)).ok();
+    Ok(parse_data(&dat, boundaries.as_deref(), tracons.as_deref()))
+}
+
+/// Parsed data, downloading or refreshing the files first when needed
+async fn get_data(app: &AppHandle) -> Result<Arc<VatSpyData>> {
+    let dir = data_dir(app)?;
+    let stale = is_stale(&dir.join(VATSPY_DAT_FILE));
+    if let Some(data) = app.state::<VatSpyState>().data.read().clone() {
+        if !stale {
+            return Ok(data);
+        }
+    }
+
+    if stale {
+        fs::create_dir_all(&dir).map_err(|e| Error::Io(format!("Failed to create VATSpy folder: {}", e)))?;
+        for (url, file) in [
+            (VATSPY_DAT_URL, VATSPY_DAT_FILE),
+            (BOUNDARIES_URL, BOUNDARIES_FILE),
+            (TRACON_BOUNDARIES_URL, TRACON_BOUNDARIES_FILE),
+        ] {
+            // Keep using the previous copy when the download fails
+            match download(url, &dir.join(file)).await {
+                Ok(()) => info!("[VatSpy] Downloaded {}", file),
+                Err(e) => warn!("[VatSpy] {}", e),
+            }
+        }
+    }
+
+    let data = tauri::async_runtime::spawn_blocking(move || load_from(&dir))
+        .await
+        .map_err(|e| Error::Internal(format!("VATSpy data load failed: {}", e)))??;
+    let data = Arc::new(data);
+    *app.state::<VatSpyState>().data.write() = Some(data.clone());
+    Ok(data)
+}
+
+/// Facilities for an airport and the positions covering it that are online now
+pub async fn get_coverage(app: &AppHandle, icao: &str) -> Result<AirportCoverage> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    let data = get_data(app).await?;
+    let position = airport_db::get_airport(app, icao.clone())
+        .await
+        .ok()
+        .flatten()
+        .map(|a| (a.latitude, a.longitude));
+    let facilities = data.facilities(&icao, position);
+    let controllers = traffic::online_controllers(app).await.map_err(Error::Upstream)?;
+    let online = covering_positions(&facilities, &controllers);
+    Ok(AirportCoverage { facilities, online })
+}
+
+/// Import data files from a local VATSpy data folder (VATSpy.dat, Boundaries.geojson,
+/// optionally TRACONBoundaries.geojson)
+pub fn import_folder(app: &AppHandle, folder: &Path) -> Result<()> {
+    let source = folder.join(VATSPY_DAT_FILE);
+    if !source.is_file() {
+        return Err(Error::InvalidInput(format!("No {} in {:?}", VATSPY_DAT_FILE, folder)));
+    }
+    let dir = data_dir(app)?;
+    fs::create_dir_all(&dir).map_err(|e| Error::Io(format!("Failed to create VATSpy folder: {}", e)))?;
+    for file in [VATSPY_DAT_FILE, BOUNDARIES_FILE, TRACON_BOUNDARIES_FILE] {
+        let source = folder.join(file);
+        if source.is_file() {
+            fs::copy(&source, dir.join(file)).map_err(|e| Error::Io(format!("Failed to copy {}: {}", file, e)))?;
+        }
+    }
+    let data = load_from(&dir)?;
+    info!(
+        "[VatSpy] Imported {} airports and {} FIRs from {:?}",
+        data.airports.len(),
+        data.firs.len(),
+        folder
+    );
+    *app.state::<VatSpyState>().data.write() = Some(Arc::new(data));
+    Ok(())
+}
+
+/// Initialize VATSpy state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_vatspy(app: &AppHandle) {
+    app.manage(VatSpyState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get an airport's TRACON/FIR/UIR and the controllers online covering it
+#[tauri::command]
+pub async fn get_airport_atc(app: AppHandle, icao: String) -> Result<AirportCoverage> {
+    get_coverage(&app, &icao).await
+}
+
+/// Import VATSpy data files from a local folder
+#[tauri::command]
+pub async fn import_vatspy_data(app: AppHandle, path: String) -> Result<()> {
+    tauri::async_runtime::spawn_blocking(move || import_folder(&app, Path::new(&path)))
+        .await
+        .map_err(|e| Error::Internal(format!("VATSpy import failed: {}", e)))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_airport_coverage() {
+        let dat = "[Countries]\nUnited States|K|Center\n\
+            [Airports]\n\
+            KBOS|General Edward Lawrence Logan Intl|42.36197|-71.0079|BOS|KZBW|0\n\
+            KBOS|Logan Pseudo|42.0|-71.0|BOS|KZBW|1\n\
+            [FIRs]\n\
+            KZBW|Boston|BOS|KZBW\n\
+            [UIRs]\n\
+            NAWE|North America East|KZBW,KZNY\n";
+        let tracons = r#"{"type": "FeatureCollection", "features": [
+            {"type": "Feature", "properties": {"id": "A90", "prefix": ["BOS", "A90"], "name": "Boston TRACON"},
+             "geometry": {"type": "Polygon", "coordinates": [[[-72, 42], [-70, 42], [-70, 43], [-72, 43], [-72, 42]]]}}
+        ]}"#;
+        let data = parse_data(dat, None, Some(tracons));
+        let facilities = data.facilities("KBOS", None);
+        assert_eq!(facilities.callsign_prefixes, vec!["KBOS", "BOS"]);
+        assert_eq!(facilities.tracon.as_ref().unwrap().id, "A90");
+        assert_eq!(facilities.fir.as_ref().unwrap().callsign_prefixes, vec!["KZBW", "BOS"]);
+        assert_eq!(facilities.uir.as_ref().unwrap().id, "NAWE");
+
+        let controller = |callsign: &str| OnlineController {
+            callsign: callsign.to_string(),
+            cid: None,
+            name: String::new(),
+            frequency: "199.998".to_string(),
+            rating: None,
+            logon_time: None,
+            facility: 0,
+            text_atis: None,
+            atis_code: None,
+        };
+        let controllers: Vec<OnlineController> = [
+            "BOS_CTR",
+            "A90_APP",
+            "KBOS_ATIS",
+            "BOS_TWR",
+            "NY_CTR",
+            "KJFK_TWR",
+            "NAWE_FSS",
+            "BOS_SUP",
+        ]
+        .into_iter()
+        .map(controller)
+        .collect();
+        let online = covering_positions(&facilities, &controllers);
+        let callsigns: Vec<&str> = online.iter().map(|p| p.callsign.as_str()).collect();
+        assert_eq!(
+            callsigns,
+            vec!["KBOS_ATIS", "BOS_TWR", "A90_APP", "BOS_CTR", "NAWE_FSS"]
+        );
+        assert_eq!(online[2].facility, "A90");
+        assert_eq!(online[3].facility, "KZBW");
+    }
+}
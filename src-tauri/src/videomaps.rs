@@ -0,0 +1,533 @@
+//! CRC/vNAS video maps per airport
+//!
+//! Imports a tower's video maps (its ASDE-X and tower cab maps, plus any STARS
+//! maps configured for the facility) from a local CRC install. The ARTCC
+//! definitions in `CRC/ARTCCs/*.json` name the maps, and
+//! `CRC/VideoMaps/{ARTCC}/{id}.geojson` holds the geometry.
+//!
+//! CRC map GeoJSON carries its styling defaults in special features
+//! (`isLineDefaults`, `isSymbolDefaults`, `isTextDefaults`). These are folded
+//! into every feature so clients get plain, self-describing GeoJSON. Imported
+//! maps are stored per airport in `videomaps/{ICAO}/` in the app data folder:
+//!
+//! ```text
+//! videomaps/KBOS/
+//! ├── index.json
+//! ├── 01GW...QX.geojson
+//! └── 01GW...ZB.geojson
+//! ```
+
+use crate::error::{Error, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+/// Map kinds, by the facility configuration that references them
+pub const KIND_ASDEX: &str = "asdex";
+pub const KIND_TOWER_CAB: &str = "towerCab";
+pub const KIND_STARS: &str = "stars";
+
+/// Video map imported for an airport
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VideoMapInfo {
+    /// CRC video map id
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub short_name: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// "asdex", "towerCab" or "stars"
+    pub kind: String,
+    pub feature_count: usize,
+    /// URL usable by remote clients (e.g., "/api/videomaps/KBOS/01GW...QX")
+    pub url: String,
+}
+
+/// Video maps imported for an airport (videomaps/{ICAO}/index.json)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VideoMapIndex {
+    pub icao: String,
+    /// CRC facility id (e.g., "BOS")
+    pub facility_id: String,
+    pub facility_name: String,
+    /// ARTCC the facility belongs to (e.g., "ZBW")
+    pub artcc: String,
+    /// Import time (Unix ms)
+    pub imported_at: u64,
+    pub maps: Vec<VideoMapInfo>,
+}
+
+/// Get the folder holding an airport's imported video maps
+pub fn airport_dir(app: &AppHandle, icao: &str) -> Result<PathBuf> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))?;
+    Ok(app_data.join("videomaps").join(icao))
+}
+
+/// Default CRC data folder (%LOCALAPPDATA%\CRC)
+fn default_crc_dir(app: &AppHandle) -> Option<PathBuf> {
+    app.path().local_data_dir().ok().map(|dir| dir.join("CRC"))
+}
+
+fn normalize_icao(icao: &str) -> Result<String> {
+    let icao = icao.trim().to_uppercase();
+    if icao.len() < 3 || icao.len() > 4 || !icao.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(Error::InvalidInput(format!("Invalid airport code: {}", icao)));
+    }
+    Ok(icao)
+}
+
+/// CRC facility ids that may identify an airport: the ICAO code, and the FAA
+/// identifier for US airports (KBOS -> BOS, PANC -> ANC, PHNL -> HNL)
+fn facility_ids(icao: &str) -> Vec<String> {
+    let mut ids = vec![icao.to_string()];
+    if icao.len() == 4 && (icao.starts_with('K') || icao.starts_with("PA") || icao.starts_with("PH")) {
+        ids.push(icao[1..].to_string());
+    }
+    ids
+}
+
+/// Find a facility by id in an ARTCC's facility tree
+fn find_facility<'a>(facility: &'a Value, ids: &[String]) -> Option<&'a Value> {
+    let id = facility.get("id").and_then(Value::as_str).unwrap_or_default();
+    if ids.iter().any(|i| i.eq_ignore_ascii_case(id)) {
+        return Some(facility);
+    }
+    facility
+        .get("childFacilities")
+        .and_then(Value::as_array)?
+        .iter()
+        .find_map(|child| find_facility(child, ids))
+}
+
+/// Video map ids referenced by a facility's ASDE-X, tower cab and STARS configurations
+fn facility_map_ids(facility: &Value) -> Vec<(String, &'static str)> {
+    let mut ids: Vec<(String, &'static str)> = Vec::new();
+    let mut push = |id: Option<&str>, kind: &'static str| {
+        if let Some(id) = id.filter(|id| !id.is_empty()) {
+            if !ids.iter().any(|(existing, _)| existing == id) {
+                ids.push((id.to_string(), kind));
+            }
+        }
+    };
+
+    push(
+        facility
+            .pointer("/asdexConfiguration/videoMapId")
+            .and_then(Value::as_str),
+        KIND_ASDEX,
+    );
+    push(
+        facility
+            .pointer("/towerCabConfiguration/videoMapId")
+            .and_then(Value::as_str),
+        KIND_TOWER_CAB,
+    );
+    for id in facility
+        .pointer("/starsConfiguration/videoMapIds")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        push(id.as_str(), KIND_STARS);
+    }
+    ids
+}
+
+/// Defaults feature kind ("line", "symbol" or "text"), if this is one
+fn defaults_kind(properties: &Map<String, Value>) -> Option<&'static str> {
+    let flag = |key: &str| properties.get(key).and_then(Value::as_bool) == Some(true);
+    if flag("isLineDefaults") {
+        Some("line")
+    } else if flag("isSymbolDefaults") {
+        Some("symbol")
+    } else if flag("isTextDefaults") {
+        Some("text")
+    } else {
+        None
+    }
+}
+
+/// Kind of defaults that apply to a feature
+fn feature_kind(feature: &Value) -> &'static str {
+    let geometry_type = feature
+        .pointer("/geometry/type")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    match geometry_type {
+        "Point" | "MultiPoint" if feature.pointer("/properties/text").is_some() => "text",
+        "Point" | "MultiPoint" => "symbol",
+        _ => "line",
+    }
+}
+
+/// Parse CRC video map GeoJSON: fold the style defaults features into every
+/// other feature and drop them. Returns the normalized map and its feature count.
+pub fn normalize_video_map(geojson: Value) -> Result<(Value, usize)> {
+    let Value::Object(mut collection) = geojson else {
+        return Err(Error::invalid_input("Video map is not a GeoJSON object"));
+    };
+    if collection.get("type").and_then(Value::as_str) != Some("FeatureCollection") {
+        return Err(Error::invalid_input("Video map is not a GeoJSON FeatureCollection"));
+    }
+    let Some(Value::Array(features)) = collection.remove("features") else {
+        return Err(Error::invalid_input("Video map has no features"));
+    };
+
+    let mut defaults: Vec<(&'static str, Map<String, Value>)> = Vec::new();
+    let mut drawn = Vec::with_capacity(features.len());
+    for feature in features {
+        let properties = feature.get("properties").and_then(Value::as_object);
+        match properties.and_then(|p| defaults_kind(p).map(|kind| (kind, p))) {
+            Some((kind, properties)) => {
+                let mut style = properties.clone();
+                style.retain(|key, _| !key.starts_with("is") || !key.ends_with("Defaults"));
+                defaults.push((kind, style));
+            }
+            None => drawn.push(feature),
+        }
+    }
+
+    for feature in &mut drawn {
+        let kind = feature_kind(feature);
+        let Some(object) = feature.as_object_mut() else {
+            continue;
+        };
+        let properties = object.entry("properties").or_insert_with(|| Value::Object(Map::new()));
+        if !properties.is_object() {
+            *properties = Value::Object(Map::new());
+        }
+        let Value::Object(properties) = properties else {
+            continue;
+        };
+        for (_, style) in defaults.iter().filter(|(k, _)| *k == kind) {
+            for (key, value) in style {
+                properties.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+        }
+    }
+
+    let count = drawn.len();
+    collection.insert("features".to_string(), Value::Array(drawn));
+    Ok((Value::Object(collection), count))
+}
+
+fn read_json(path: &Path) -> Result<Value> {
+    let content = fs::read_to_string(path).map_err(|e| Error::Io(format!("Failed to read {:?}: {}", path, e)))?;
+    serde_json::from_str(&content).map_err(|e| Error::InvalidInput(format!("Failed to parse {:?}: {}", path, e)))
+}
+
+/// Import an airport's video maps from a CRC data folder (default: the local
+/// CRC install), replacing any previously imported maps for that airport
+pub fn import_from_crc(app: &AppHandle, icao: &str, crc_dir: Option<PathBuf>) -> Result<VideoMapIndex> {
+    let icao = normalize_icao(icao)?;
+    let crc_dir = crc_dir
+        .or_else(|| default_crc_dir(app))
+        .filter(|dir| dir.join("ARTCCs").is_dir())
+        .ok_or_else(|| {
+            Error::not_found("CRC data folder not found (install CRC and open the facility once, or choose its folder)")
+        })?;
+
+    let ids = facility_ids(&icao);
+    let mut found = None;
+    for entry in fs::read_dir(crc_dir.join("ARTCCs"))
+        .map_err(|e| Error::Io(format!("Failed to read CRC ARTCCs: {}", e)))? {
+        let path = entry.map_err(|e| Error::Io(e.to_string()))?.path();
+        if !path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+            continue;
+        }
+        let artcc = match read_json(&path) {
+            Ok(artcc) => artcc,
+            Err(e) => {
+                warn!("[VideoMaps] {}", e);
+                continue;
+            }
+        };
+        if artcc.get("facility").and_then(|f| find_facility(f, &ids)).is_some() {
+            found = Some(artcc);
+            break;
+        }
+    }
+    let artcc = found.ok_or_else(|| Error::NotFound(format!("No CRC facility found for {}", icao)))?;
+    let facility = find_facility(&artcc["facility"], &ids).expect("facility found above");
+
+    let artcc_id = artcc.get("id").and_then(Value::as_str).unwrap_or_default().to_string();
+    let facility_id = facility
+        .get("id")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let map_ids = facility_map_ids(facility);
+    if map_ids.is_empty() {
+        return Err(Error::NotFound(format!("CRC facility {} has no video maps", facility_id)));
+    }
+
+    let dest = airport_dir(app, &icao)?;
+    if dest.exists() {
+        fs::remove_dir_all(&dest).map_err(|e| Error::Io(format!("Failed to clear old video maps: {}", e)))?;
+    }
+    fs::create_dir_all(&dest).map_err(|e| Error::Io(format!("Failed to create video maps folder: {}", e)))?;
+
+    let definitions = artcc
+        .get("videoMaps")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let mut maps = Vec::new();
+    for (id, kind) in map_ids {
+        let source = crc_dir
+            .join("VideoMaps")
+            .join(&artcc_id)
+            .join(format!("{}.geojson", id));
+        let (geojson, feature_count) = match read_json(&source).and_then(normalize_video_map) {
+            Ok(map) => map,
+            Err(e) => {
+                warn!("[VideoMaps] Skipping video map {}: {}", id, e);
+                continue;
+            }
+        };
+        let content = serde_json::to_string(&geojson).map_err(|e| Error::Internal(e.to_string()))?;
+        fs::write(dest.join(format!("{}.geojson", id)), content)
+            .map_err(|e| Error::Io(format!("Failed to save video map {}: {}", id, e)))?;
+
+        let definition = definitions
+            .iter()
+            .find(|d| d.get("id").and_then(Value::as_str) == Some(&id));
+        let text = |key: &str| {
+            definition
+                .and_then(|d| d.get(key))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        };
+        maps.push(VideoMapInfo {
+            name: text("name").unwrap_or_else(|| id.clone()),
+            short_name: text("shortName"),
+            tags: definition
+                .and_then(|d| d.get("tags"))
+                .and_then(Value::as_array)
+                .map(|tags| tags.iter().filter_map(Value::as_str).map(str::to_string).collect())
+                .unwrap_or_default(),
+            kind: kind.to_string(),
+            feature_count,
+            url: format!("/api/videomaps/{}/{}", icao, id),
+            id,
+        });
+    }
+    if maps.is_empty() {
+        return Err(Error::InvalidInput(format!("None of {}'s video maps could be read from CRC", facility_id)));
+    }
+
+    let index = VideoMapIndex {
+        icao: icao.clone(),
+        facility_name: facility
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or(&facility_id)
+            .to_string(),
+        facility_id,
+        artcc: artcc_id,
+        imported_at: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        maps,
+    };
+    let content = serde_json::to_string_pretty(&index).map_err(|e| Error::Internal(e.to_string()))?;
+    fs::write(dest.join("index.json"), content)
+        .map_err(|e| Error::Io(format!("Failed to save video map index: {}", e)))?;
+
+    info!(
+        "[VideoMaps] Imported {} video maps for {} from CRC ({} {})",
+        index.maps.len(),
+        icao,
+        index.artcc,
+        index.facility_id
+    );
+    Ok(index)
+}
+
+/// Video maps imported for an airport (None if none were imported)
+pub fn get_index(app: &AppHandle, icao: &str) -> Result<Option<VideoMapIndex>> {
+    let path = airport_dir(app, &normalize_icao(icao)?)?.join("index.json");
+    if !path.exists() {
+        return Ok(None);
+    }
+    serde_json::from_value(read_json(&path)?)
+        .map(Some)
+        .map_err(|e| Error::InvalidInput(format!("Invalid video map index: {}", e)))
+}
+
+/// Resolve an imported video map file. Only maps listed in the airport's index
+/// can be resolved, so arbitrary paths are rejected.
+pub fn resolve_map(app: &AppHandle, icao: &str, id: &str) -> Result<Option<PathBuf>> {
+    let Some(index) = get_index(app, icao)? else {
+        return Ok(None);
+    };
+    if !index.maps.iter().any(|m| m.id == id) {
+        return Ok(None);
+    }
+    Ok(Some(airport_dir(app, &index.icao)?.join(format!("{}.geojson", id))))
+}
+
+/// Install an airport's video maps from a content pack bundle, replacing any
+/// previously imported maps for that airport (see content_packs.rs). A bundle
+/// is `{facilityId, facilityName, artcc, maps: [{id, name, shortName, tags,
+/// kind, geojson}]}` with CRC-style GeoJSON.
+pub fn import_bundle(app: &AppHandle, icao: &str, bundle: Value) -> Result<VideoMapIndex> {
+    let icao = normalize_icao(icao)?;
+    let text = |value: &Value, key: &str| value.get(key).and_then(Value::as_str).map(str::to_string);
+    let maps = bundle
+        .get("maps")
+        .and_then(Value::as_array)
+        .filter(|maps| !maps.is_empty())
+        .ok_or_else(|| Error::invalid_input("Video map bundle has no maps"))?;
+
+    let dest = airport_dir(app, &icao)?;
+    if dest.exists() {
+        fs::remove_dir_all(&dest).map_err(|e| Error::Io(format!("Failed to clear old video maps: {}", e)))?;
+    }
+    fs::create_dir_all(&dest).map_err(|e| Error::Io(format!("Failed to create video maps folder: {}", e)))?;
+
+    let mut infos = Vec::new();
+    for map in maps {
+        let Some(id) = text(map, "id").filter(|id| {
+            !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        }) else {
+            warn!("[VideoMaps] Skipping bundled video map without a valid id");
+            continue;
+        };
+        let (geojson, feature_count) = match normalize_video_map(map.get("geojson").cloned().unwrap_or_default()) {
+            Ok(map) => map,
+            Err(e) => {
+                warn!("[VideoMaps] Skipping video map {}: {}", id, e);
+                continue;
+            }
+        };
+        let content = serde_json::to_string(&geojson).map_err(|e| Error::Internal(e.to_string()))?;
+        fs::write(dest.join(format!("{}.geojson", id)), content)
+            .map_err(|e| Error::Io(format!("Failed to save video map {}: {}", id, e)))?;
+        infos.push(VideoMapInfo {
+            name: text(map, "name").unwrap_or_else(|| id.clone()),
+            short_name: text(map, "shortName"),
+            tags: map
+                .get("tags")
+                .and_then(Value::as_array)
+                .map(|tags| tags.iter().filter_map(Value::as_str).map(str::to_string).collect())
+                .unwrap_or_default(),
+            kind: text(map, "kind").unwrap_or_else(|| KIND_TOWER_CAB.to_string()),
+            feature_count,
+            url: format!("/api/videomaps/{}/{}", icao, id),
+            id,
+        });
+    }
+    if infos.is_empty() {
+        let _ = fs::remove_dir_all(&dest);
+        return Err(Error::invalid_input("None of the bundled video maps could be read"));
+    }
+
+    let facility_id = text(&bundle, "facilityId").unwrap_or_else(|| icao.clone());
+    let index = VideoMapIndex {
+        icao: icao.clone(),
+        facility_name: text(&bundle, "facilityName").unwrap_or_else(|| facility_id.clone()),
+        facility_id,
+        artcc: text(&bundle, "artcc").unwrap_or_default(),
+        imported_at: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        maps: infos,
+    };
+    let content = serde_json::to_string_pretty(&index).map_err(|e| Error::Internal(e.to_string()))?;
+    fs::write(dest.join("index.json"), content)
+        .map_err(|e| Error::Io(format!("Failed to save video map index: {}", e)))?;
+    info!("[VideoMaps] Installed {} video maps for {}", index.maps.len(), icao);
+    Ok(index)
+}
+
+/// Remove an airport's imported video maps
+pub fn remove(app: &AppHandle, icao: &str) -> Result<()> {
+    let dest = airport_dir(app, &normalize_icao(icao)?)?;
+    if dest.exists() {
+        fs::remove_dir_all(&dest).map_err(|e| Error::Io(format!("Failed to remove video maps: {}", e)))?;
+    }
+    Ok(())
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Import an airport's video maps from CRC (optionally from a specific CRC data folder)
+#[tauri::command]
+pub async fn import_video_maps(
+    app: AppHandle,
+    icao: String,
+    crc_path: Option<String>,
+) -> Result<VideoMapIndex> {
+    tauri::async_runtime::spawn_blocking(move || import_from_crc(&app, &icao, crc_path.map(PathBuf::from)))
+        .await
+        .map_err(|e| Error::Internal(format!("Video map import failed: {}", e)))?
+}
+
+/// List the video maps imported for an airport
+#[tauri::command]
+pub fn list_video_maps(app: AppHandle, icao: String) -> Result<Option<VideoMapIndex>> {
+    get_index(&app, &icao)
+}
+
+/// Read an imported video map as GeoJSON
+#[tauri::command]
+pub fn read_video_map(app: AppHandle, icao: String, id: String) -> Result<Value> {
+    let path = resolve_map(&app, &icao, &id)?
+        .ok_or_else(|| Error::NotFound(format!("Video map not found: {}/{}", icao, id)))?;
+    read_json(&path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn normalizes_crc_video_map() {
+        let map = json!({
+            "type": "FeatureCollection",
+            "features": [
+                {"type": "Feature", "geometry": {"type": "Point", "coordinates": [0, 0]},
+                 "properties": {"isLineDefaults": true, "bcg": 1, "style": "solid", "thickness": 1}},
+                {"type": "Feature", "geometry": {"type": "Point", "coordinates": [0, 0]},
+                 "properties": {"isTextDefaults": true, "bcg": 2, "size": 1, "opaque": false}},
+                {"type": "Feature", "geometry": {"type": "LineString", "coordinates": [[-71.0, 42.3], [-71.1, 42.4]]},
+                 "properties": {"thickness": 2}},
+                {"type": "Feature", "geometry": {"type": "Point", "coordinates": [-71.0, 42.36]},
+                 "properties": {"text": ["TWR"]}}
+            ]
+        });
+
+        let (normalized, count) = normalize_video_map(map).unwrap();
+        assert_eq!(count, 2);
+        let features = normalized["features"].as_array().unwrap();
+        assert_eq!(
+            features[0]["properties"],
+            json!({"thickness": 2, "bcg": 1, "style": "solid"})
+        );
+        assert_eq!(
+            features[1]["properties"],
+            json!({"text": ["TWR"], "bcg": 2, "size": 1, "opaque": false})
+        );
+
+        assert!(normalize_video_map(json!({"type": "Feature"})).is_err());
+        assert_eq!(facility_ids("KBOS"), vec!["KBOS", "BOS"]);
+        assert_eq!(facility_ids("EGLL"), vec!["EGLL"]);
+    }
+}
@@ -0,0 +1,362 @@
+//! Runway configuration view presets
+//!
+//! Camera bookmarks can be tagged with the runways they're for (`runways` on a
+//! bookmark, e.g. `["04L", "04R"]` for north flow). On every traffic poll the
+//! active airport's runway configuration is detected, from the runways its
+//! ATIS names when one is online, the runways traffic is using
+//! (`runway_config`) or the wind-based suggestion otherwise
+//! (`runway_suggestion`). When it changes, the bookmark tagged with the most
+//! of the new runways is suggested as a `view-preset-suggestion` event and,
+//! with `autoSwitch` enabled, loaded in the main window like a control
+//! surface `bookmark` action.
+//!
+//! The first configuration seen for an airport only sets the baseline.
+
+use std::collections::{BTreeSet, HashMap};
+use std::time::SystemTime;
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{info, warn};
+
+use crate::error::{Error, Result};
+use crate::control::{self, ControlAction, ControlBookmark};
+use crate::runways::Runway;
+use crate::traffic::OnlineController;
+use crate::weather;
+use crate::GlobalCameraBookmark;
+
+/// Runway configuration view preset settings in the global settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ViewPresetSettings {
+    /// Load the matching bookmark when the configuration changes (default: false, suggest only)
+    pub auto_switch: bool,
+}
+
+/// Detected runway configuration and the bookmark matching it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewPresetSuggestion {
+    pub icao: String,
+    /// Runway ends in use (e.g., ["04L", "04R"])
+    pub runways: Vec<String>,
+    /// "atis", "traffic" or "wind"
+    pub source: String,
+    /// Bookmark tagged for the configuration, if any
+    pub bookmark: Option<ControlBookmark>,
+    /// The bookmark was loaded automatically
+    pub switched: bool,
+    /// Unix ms the configuration was detected
+    pub time: u64,
+}
+
+/// View preset state (managed by Tauri)
+pub struct ViewPresetState {
+    current: RwLock<Option<ViewPresetSuggestion>>,
+}
+
+impl ViewPresetState {
+    pub fn new() -> Self {
+        Self {
+            current: RwLock::new(None),
+        }
+    }
+}
+
+impl Default for ViewPresetState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Runway designator without leading zeros ("04L" and "4L" compare equal)
+fn designator_key(ident: &str) -> String {
+    let ident = ident.trim().to_uppercase();
+    let trimmed = ident.trim_start_matches('0');
+    if trimmed.is_empty() || trimmed.starts_with(|c: char| !c.is_ascii_digit()) {
+        ident
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Runway ends an ATIS names as in use ("DEPG RWY 4R, LDG RWYS 4L AND 4R").
+/// Only ends of the given runways count, and runways reported closed are skipped.
+pub fn atis_runways(text: &[String], runways: &[Runway]) -> Vec<String> {
+    let ends: HashMap<String, String> = runways
+        .iter()
+        .flat_map(|r| [&r.low_end.ident, &r.high_end.ident])
+        .map(|ident| (designator_key(ident), ident.clone()))
+        .collect();
+
+    // Words, with spelled-out sides joined to their number ("27 LEFT" -> "27L")
+    let mut words: Vec<String> = Vec::new();
+    let text = text.join(" ").to_uppercase();
+    for word in text.split(|c: char| !c.is_ascii_alphanumeric()).filter(|w| !w.is_empty()) {
+        let side = match word {
+            "LEFT" => Some('L'),
+            "RIGHT" => Some('R'),
+            "CENTER" | "CENTRE" => Some('C'),
+            _ => None,
+        };
+        match (side, words.last_mut()) {
+            (Some(side), Some(last)) if last.chars().all(|c| c.is_ascii_digit()) => last.push(side),
+            _ => words.push(word.to_string()),
+        }
+    }
+
+    let mut found = BTreeSet::new();
+    let mut i = 0;
+    while i < words.len() {
+        if !matches!(words[i].as_str(), "RWY" | "RWYS" | "RUNWAY" | "RUNWAYS") {
+            i += 1;
+            continue;
+        }
+        let mut group = Vec::new();
+        i += 1;
+        while i < words.len() {
+            if let Some(end) = ends.get(&designator_key(&words[i])) {
+                group.push(end.clone());
+            } else if words[i] != "AND" {
+                break;
+            }
+            i += 1;
+        }
+        let closed = words.get(i).is_some_and(|w| w == "CLSD" || w == "CLOSED");
+        if !closed {
+            found.extend(group);
+        }
+    }
+    found.into_iter().collect()
+}
+
+/// Detect the runway configuration: the ATIS runways if any ATIS names them,
+/// else the runways traffic is using, else the wind-based suggestion
+pub fn detect(
+    icao: &str,
+    stations: &[OnlineController],
+    runways: &[Runway],
+    observed: &[String],
+    suggested: &[String],
+) -> Option<(Vec<String>, &'static str)> {
+    let atis: BTreeSet<String> = stations
+        .iter()
+        .filter(|s| {
+            let callsign = s.callsign.to_uppercase();
+            callsign.ends_with("_ATIS") && callsign.split('_').next() == Some(icao)
+        })
+        .filter_map(|s| s.text_atis.as_ref())
+        .flat_map(|text| atis_runways(text, runways))
+        .collect();
+    if !atis.is_empty() {
+        return Some((atis.into_iter().collect(), "atis"));
+    }
+    if !observed.is_empty() {
+        let mut observed = observed.to_vec();
+        observed.sort();
+        return Some((observed, "traffic"));
+    }
+    if !suggested.is_empty() {
+        let mut suggested = suggested.to_vec();
+        suggested.sort();
+        return Some((suggested, "wind"));
+    }
+    None
+}
+
+/// The bookmark tagged with the most of the runways (lowest slot on ties)
+pub fn match_bookmark(bookmarks: &HashMap<String, GlobalCameraBookmark>, runways: &[String]) -> Option<ControlBookmark> {
+    let active: BTreeSet<String> = runways.iter().map(|r| designator_key(r)).collect();
+    bookmarks
+        .iter()
+        .filter_map(|(slot, bookmark)| {
+            let slot: u32 = slot.parse().ok()?;
+            let matched = bookmark
+                .runways
+                .iter()
+                .filter(|r| active.contains(&designator_key(r)))
+                .count();
+            (matched > 0).then_some((matched, slot, bookmark))
+        })
+        .max_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))
+        .map(|(_, slot, bookmark)| ControlBookmark {
+            slot,
+            name: bookmark.name.clone(),
+        })
+}
+
+/// Check the active airport's runway configuration after a traffic poll
+pub fn update(
+    app: &AppHandle,
+    icao: &str,
+    stations: &[OnlineController],
+    runways: &[Runway],
+    observed: &[String],
+    suggested: &[String],
+) {
+    let Some((detected, source)) = detect(icao, stations, runways, observed, suggested) else {
+        return;
+    };
+    let state = app.state::<ViewPresetState>();
+    let previous = state.current.read().clone();
+    let first = match &previous {
+        Some(previous) if previous.icao == icao => {
+            if previous.runways == detected {
+                return;
+            }
+            false
+        }
+        _ => true,
+    };
+
+    let settings = crate::read_global_settings(app.clone()).ok();
+    let bookmark = settings
+        .as_ref()
+        .and_then(|s| s.viewports.airport_configs.get(icao))
+        .and_then(|config| match_bookmark(&config.bookmarks, &detected));
+    let auto_switch = settings.is_some_and(|s| s.view_presets.auto_switch);
+
+    let mut suggestion = ViewPresetSuggestion {
+        icao: icao.to_string(),
+        runways: detected,
+        source: source.to_string(),
+        bookmark,
+        switched: false,
+        time: now_ms(),
+    };
+
+    if !first {
+        info!(
+            "[ViewPresets] {} runway configuration {} ({})",
+            icao,
+            suggestion.runways.join(" "),
+            source
+        );
+        if let (true, Some(bookmark)) = (auto_switch, &suggestion.bookmark) {
+            let action = ControlAction {
+                action: "bookmark".to_string(),
+                value: Some(bookmark.slot.to_string()),
+            };
+            match control::trigger(app, action) {
+                Ok(()) => suggestion.switched = true,
+                Err(e) => warn!("[ViewPresets] Failed to load bookmark .{}: {}", bookmark.slot, e),
+            }
+        }
+        let _ = app.emit("view-preset-suggestion", &suggestion);
+    }
+    *state.current.write() = Some(suggestion);
+}
+
+/// Current configuration and matching bookmark at an airport
+pub fn get_suggestion(app: &AppHandle, icao: &str) -> Result<Option<ViewPresetSuggestion>> {
+    let icao = weather::normalize_icao(icao).map_err(Error::InvalidInput)?;
+    Ok(app
+        .state::<ViewPresetState>()
+        .current
+        .read()
+        .clone()
+        .filter(|s| s.icao == icao))
+}
+
+/// Stop tracking (no active airport)
+pub fn clear(app: &AppHandle) {
+    *app.state::<ViewPresetState>().current.write() = None;
+}
+
+/// Initialize view preset state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_view_presets(app: &AppHandle) {
+    app.manage(ViewPresetState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the detected runway configuration at an airport and the bookmark tagged for it
+#[tauri::command]
+pub fn get_view_preset_suggestion(app: AppHandle, icao: String) -> Result<Option<ViewPresetSuggestion>> {
+    get_suggestion(&app, &icao)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runways::RunwayEnd;
+
+    fn runway(low: &str, high: &str) -> Runway {
+        let end = |ident: &str| RunwayEnd {
+            ident: ident.to_string(),
+            latitude: None,
+            longitude: None,
+            heading_true: None,
+            elevation_ft: None,
+            displaced_threshold_ft: 0.0,
+        };
+        Runway {
+            ident: format!("{}/{}", low, high),
+            low_end: end(low),
+            high_end: end(high),
+            length_ft: None,
+            width_ft: None,
+            surface: String::new(),
+            lighted: true,
+        }
+    }
+
+    fn kbos() -> Vec<Runway> {
+        vec![runway("04L", "22R"), runway("04R", "22L"), runway("09", "27"), runway("15R", "33L")]
+    }
+
+    fn lines(text: &str) -> Vec<String> {
+        vec![text.to_string()]
+    }
+
+    #[test]
+    fn reads_runways_from_atis() {
+        let text = lines("BOS ATIS INFO A 1554Z. ILS RWY 4R APCH IN USE. LDG RWYS 4L AND 4R, DEPG RWY 9.");
+        assert_eq!(atis_runways(&text, &kbos()), vec!["04L", "04R", "09"]);
+
+        let text = lines("LANDING RUNWAY 27 DEPARTING RUNWAY 33 LEFT. RWY 15R CLSD.");
+        assert_eq!(atis_runways(&text, &kbos()), vec!["27", "33L"]);
+
+        assert!(atis_runways(&lines("WIND 270 AT 10"), &kbos()).is_empty());
+    }
+
+    #[test]
+    fn matches_bookmarks_by_runways() {
+        let bookmark = |name: &str, runways: &[&str]| GlobalCameraBookmark {
+            name: Some(name.to_string()),
+            heading: 0.0,
+            pitch: 0.0,
+            fov: 60.0,
+            position_offset_x: 0.0,
+            position_offset_y: 0.0,
+            position_offset_z: 0.0,
+            view_mode: "3d".to_string(),
+            topdown_altitude: None,
+            runways: runways.iter().map(|r| r.to_string()).collect(),
+        };
+        let bookmarks = HashMap::from([
+            ("1".to_string(), bookmark("North", &["04L", "04R", "09"])),
+            ("2".to_string(), bookmark("South", &["22L", "22R"])),
+            ("3".to_string(), bookmark("West", &["27"])),
+            ("4".to_string(), bookmark("Untagged", &[])),
+        ]);
+
+        let matched = match_bookmark(&bookmarks, &["4L".to_string(), "4R".to_string()]).unwrap();
+        assert_eq!(matched.slot, 1);
+        let matched = match_bookmark(&bookmarks, &["22R".to_string(), "27".to_string()]).unwrap();
+        assert_eq!(matched.slot, 2);
+        assert!(match_bookmark(&bookmarks, &["15R".to_string()]).is_none());
+    }
+}
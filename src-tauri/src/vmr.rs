@@ -0,0 +1,1098 @@
+//! VMR (vPilot Model Rules) parsing
+//!
+//! VMR files are XML documents in the vPilot model matching dialect:
+//!
+//! ```xml
+//! <?xml version="1.0" encoding="utf-8"?>
+//! <ModelMatchRuleSet>
+//!   <ModelMatchRule TypeCode="B738" ModelName="FSLTL_B738_ZZZZ" />
+//!   <ModelMatchRule CallsignPrefix="AAL" TypeCode="B738" ModelName="Model_A//Model_B" />
+//! </ModelMatchRuleSet>
+//! ```
+//!
+//! Rules may span multiple lines, list several alternative models separated by
+//! `//`, be commented out, or carry a `FlightNumberRange`. The parser uses
+//! quick-xml and is deliberately lenient: unknown elements are ignored and a
+//! malformed document still yields the rules parsed before the error.
+//!
+//! Parsed files are cached by modification time (and dropped by the mods folder
+//! watcher), so repeated rule requests don't re-read every file.
+//!
+//! Whole files or individual rules can be disabled without deleting them
+//! (persisted in `vmr-state.json` in the app data directory), which helps
+//! isolate the ruleset behind a bad match.
+//!
+//! Rules can also be added, edited and deleted individually. Edits rewrite only
+//! the affected element, so comments and formatting elsewhere are preserved.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use parking_lot::RwLock;
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tracing::{info, warn};
+
+use crate::error::{Error, Result};
+use crate::content_packs;
+use crate::find_mods_root;
+use crate::matching::ModelMatcherState;
+use crate::vmr_remote;
+
+/// A single model matching rule
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VmrRule {
+    /// ICAO aircraft type designator (uppercase). None = applies to any type.
+    pub type_code: Option<String>,
+    /// Raw ModelName attribute (alternatives separated by `//`)
+    pub model_name: String,
+    /// Individual model alternatives, in order
+    pub model_names: Vec<String>,
+    /// Airline ICAO prefix (uppercase)
+    pub callsign_prefix: Option<String>,
+    /// Optional flight number range (e.g., "1-999")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flight_number_range: Option<String>,
+    /// VMR file the rule was loaded from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_file: Option<String>,
+    /// Position of the ModelMatchRule element within its file (used to edit/delete the rule)
+    pub index: usize,
+    /// False if the rule or its file has been disabled in vmr-state.json
+    pub enabled: bool,
+}
+
+/// Result of parsing a VMR document
+#[derive(Debug, Default)]
+pub struct VmrParseResult {
+    pub rules: Vec<VmrRule>,
+    /// XML error that stopped parsing early (rules before it are kept)
+    pub error: Option<String>,
+}
+
+fn is_rule_element(element: &BytesStart) -> bool {
+    element
+        .local_name()
+        .as_ref()
+        .eq_ignore_ascii_case(b"ModelMatchRule")
+}
+
+/// Read an attribute value, unescaping entities when possible.
+/// Falls back to the raw value for unescaped `&` (common in hand-edited files).
+fn attr_value(reader: &Reader<&[u8]>, element: &BytesStart, name: &str) -> Option<String> {
+    element
+        .attributes()
+        .with_checks(false)
+        .filter_map(|a| a.ok())
+        .find(|a| a.key.as_ref().eq_ignore_ascii_case(name.as_bytes()))
+        .map(|a| {
+            a.decode_and_unescape_value(reader.decoder())
+                .map(|v| v.into_owned())
+                .unwrap_or_else(|_| String::from_utf8_lossy(&a.value).into_owned())
+        })
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Build a rule from a ModelMatchRule element
+fn parse_rule(reader: &Reader<&[u8]>, element: &BytesStart, index: usize) -> Option<VmrRule> {
+    let model_name = attr_value(reader, element, "ModelName")?;
+    let model_names: Vec<String> = model_name
+        .split("//")
+        .map(|m| m.trim().to_string())
+        .filter(|m| !m.is_empty())
+        .collect();
+
+    if model_names.is_empty() {
+        return None;
+    }
+
+    Some(VmrRule {
+        type_code: attr_value(reader, element, "TypeCode").map(|t| t.to_uppercase()),
+        model_name,
+        model_names,
+        callsign_prefix: attr_value(reader, element, "CallsignPrefix").map(|c| c.to_uppercase()),
+        flight_number_range: attr_value(reader, element, "FlightNumberRange"),
+        source_file: None,
+        index,
+        enabled: true,
+    })
+}
+
+/// Parse VMR XML content into rules
+pub fn parse_vmr_content(content: &str) -> VmrParseResult {
+    let content = content.trim_start_matches('\u{feff}');
+    let mut reader = Reader::from_str(content);
+    reader.config_mut().trim_text(true);
+    reader.config_mut().check_end_names = false;
+
+    let mut result = VmrParseResult::default();
+    let mut index = 0;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) if is_rule_element(e) => {
+                if let Some(rule) = parse_rule(&reader, e, index) {
+                    result.rules.push(rule);
+                }
+                index += 1;
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(e) => {
+                result.error = Some(format!(
+                    "XML error at position {}: {}",
+                    reader.error_position(),
+                    e
+                ));
+                break;
+            }
+        }
+    }
+
+    result
+}
+
+/// Parse a VMR file from disk, tagging rules with their source file
+pub fn parse_vmr_file(path: &Path) -> Result<Vec<VmrRule>> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| Error::Io(format!("Failed to read VMR file {:?}: {}", path, e)))?;
+
+    let parsed = parse_vmr_content(&content);
+    if let Some(ref error) = parsed.error {
+        warn!(
+            "[VMR] {:?}: {} (kept {} rules)",
+            path,
+            error,
+            parsed.rules.len()
+        );
+    }
+
+    let source = path.to_string_lossy().to_string();
+    Ok(parsed
+        .rules
+        .into_iter()
+        .map(|mut rule| {
+            rule.source_file = Some(source.clone());
+            rule
+        })
+        .collect())
+}
+
+/// List all .vmr files in mods/ and mods/aircraft/, sorted for consistent load order
+pub fn list_vmr_paths(mods_root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+
+    for dir in [mods_root.to_path_buf(), mods_root.join("aircraft")] {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        files.extend(
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.is_file()
+                        && p.extension()
+                            .is_some_and(|ext| ext.eq_ignore_ascii_case("vmr"))
+                }),
+        );
+    }
+
+    files.sort();
+    files
+}
+
+/// All VMR files that contribute rules, in load order: local files first,
+/// then cached copies of enabled remote subscriptions, then content packs
+pub fn all_vmr_paths(app: &AppHandle) -> Vec<PathBuf> {
+    let mut paths = list_vmr_paths(&find_mods_root(app));
+    paths.extend(vmr_remote::enabled_cached_paths(app));
+    paths.extend(content_packs::installed_files(app, content_packs::KIND_VMR));
+    paths
+}
+
+/// Parsed VMR file with the modification time and size it was parsed at
+struct CachedVmrFile {
+    modified: Option<SystemTime>,
+    len: u64,
+    rules: Arc<Vec<VmrRule>>,
+}
+
+/// Parsed VMR files, keyed by path (managed by Tauri)
+pub struct VmrCacheState {
+    files: RwLock<HashMap<PathBuf, CachedVmrFile>>,
+}
+
+impl VmrCacheState {
+    pub fn new() -> Self {
+        Self {
+            files: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Drop all parsed files so they are re-read on next access
+    pub fn invalidate(&self) {
+        self.files.write().clear();
+    }
+}
+
+impl Default for VmrCacheState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse a VMR file, reusing the cached result if the file hasn't changed
+fn parse_vmr_file_cached(app: &AppHandle, path: &Path) -> Result<Arc<Vec<VmrRule>>> {
+    let metadata =
+        fs::metadata(path).map_err(|e| Error::Io(format!("Failed to read VMR file {:?}: {}", path, e)))?;
+    let modified = metadata.modified().ok();
+    let len = metadata.len();
+
+    let cache = app.state::<VmrCacheState>();
+    if let Some(cached) = cache.files.read().get(path) {
+        if cached.modified == modified && cached.len == len {
+            return Ok(cached.rules.clone());
+        }
+    }
+
+    let rules = Arc::new(parse_vmr_file(path)?);
+    cache.files.write().insert(
+        path.to_path_buf(),
+        CachedVmrFile {
+            modified,
+            len,
+            rules: rules.clone(),
+        },
+    );
+    Ok(rules)
+}
+
+/// Persisted VMR enablement (vmr-state.json in app data).
+/// Files are keyed relative to the mods folder ("aircraft/custom.vmr"); files
+/// outside it (remote subscription caches) by their full path.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VmrStateFile {
+    /// Files whose rules are all disabled
+    #[serde(default)]
+    pub disabled_files: BTreeSet<String>,
+    /// Disabled rule indices, per file
+    #[serde(default)]
+    pub disabled_rules: BTreeMap<String, BTreeSet<usize>>,
+}
+
+impl VmrStateFile {
+    fn is_rule_enabled(&self, file_key: &str, index: usize) -> bool {
+        !self.disabled_files.contains(file_key)
+            && !self
+                .disabled_rules
+                .get(file_key)
+                .is_some_and(|indices| indices.contains(&index))
+    }
+}
+
+/// Get the path to the persisted VMR state file
+pub fn get_vmr_state_file(app: &AppHandle) -> Result<PathBuf> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))?;
+
+    fs::create_dir_all(&app_data)
+        .map_err(|e| Error::Io(format!("Failed to create app data directory: {}", e)))?;
+
+    Ok(app_data.join("vmr-state.json"))
+}
+
+/// Read the persisted VMR state (defaults if missing or invalid)
+pub fn read_vmr_state(app: &AppHandle) -> VmrStateFile {
+    get_vmr_state_file(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Write the persisted VMR state and drop the model matcher so it picks up the change
+fn write_vmr_state(app: &AppHandle, vmr_state: &VmrStateFile) -> Result<()> {
+    let path = get_vmr_state_file(app)?;
+    let content = serde_json::to_string_pretty(vmr_state)
+        .map_err(|e| Error::Internal(format!("Failed to serialize VMR state: {}", e)))?;
+    fs::write(&path, content).map_err(|e| Error::Io(format!("Failed to write VMR state: {}", e)))?;
+
+    app.state::<ModelMatcherState>().invalidate();
+    Ok(())
+}
+
+/// Key identifying a VMR file in vmr-state.json
+fn file_key(mods_root: &Path, path: &Path) -> String {
+    path.strip_prefix(mods_root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Resolve a file reference (mods-relative path or `sourceFile`) to its state key
+fn resolve_file_key(app: &AppHandle, file: &str) -> Result<String> {
+    let mods_root = find_mods_root(app);
+    let normalized = file.replace('\\', "/");
+
+    all_vmr_paths(app)
+        .iter()
+        .find(|p| {
+            file_key(&mods_root, p) == normalized
+                || p.to_string_lossy().replace('\\', "/") == normalized
+        })
+        .map(|p| file_key(&mods_root, p))
+        .ok_or_else(|| Error::NotFound(format!("Unknown VMR file: {}", file)))
+}
+
+/// Enable or disable every rule in a VMR file
+pub fn set_file_enabled(
+    app: &AppHandle,
+    file: &str,
+    enabled: bool,
+) -> Result<VmrStateFile> {
+    let key = resolve_file_key(app, file)?;
+    let mut vmr_state = read_vmr_state(app);
+    if enabled {
+        vmr_state.disabled_files.remove(&key);
+    } else {
+        vmr_state.disabled_files.insert(key.clone());
+    }
+    write_vmr_state(app, &vmr_state)?;
+
+    info!(
+        "[VMR] {} {}",
+        if enabled { "Enabled" } else { "Disabled" },
+        key
+    );
+    Ok(vmr_state)
+}
+
+/// Enable or disable a single rule (by index within its file)
+pub fn set_rule_enabled(
+    app: &AppHandle,
+    file: &str,
+    index: usize,
+    enabled: bool,
+) -> Result<VmrStateFile> {
+    let key = resolve_file_key(app, file)?;
+    let mut vmr_state = read_vmr_state(app);
+    if enabled {
+        if let Some(indices) = vmr_state.disabled_rules.get_mut(&key) {
+            indices.remove(&index);
+            if indices.is_empty() {
+                vmr_state.disabled_rules.remove(&key);
+            }
+        }
+    } else {
+        vmr_state
+            .disabled_rules
+            .entry(key.clone())
+            .or_default()
+            .insert(index);
+    }
+    write_vmr_state(app, &vmr_state)?;
+
+    info!(
+        "[VMR] {} rule {} in {}",
+        if enabled { "Enabled" } else { "Disabled" },
+        index,
+        key
+    );
+    Ok(vmr_state)
+}
+
+/// Collect rules from every VMR file (mods folder + remote subscriptions) in load
+/// order, including disabled ones (with `enabled: false`)
+pub fn collect_all_rules(app: &AppHandle) -> Vec<VmrRule> {
+    let mods_root = find_mods_root(app);
+    let vmr_state = read_vmr_state(app);
+
+    all_vmr_paths(app)
+        .iter()
+        .filter_map(|path| {
+            let key = file_key(&mods_root, path);
+            parse_vmr_file_cached(app, path)
+                .map_err(|e| warn!("[VMR] {}", e))
+                .ok()
+                .map(|rules| {
+                    rules
+                        .iter()
+                        .map(|rule| VmrRule {
+                            enabled: vmr_state.is_rule_enabled(&key, rule.index),
+                            ..rule.clone()
+                        })
+                        .collect::<Vec<_>>()
+                })
+        })
+        .flatten()
+        .collect()
+}
+
+/// Collect enabled rules from every VMR file (mods folder + remote subscriptions), in load order
+pub fn collect_mod_rules(app: &AppHandle) -> Vec<VmrRule> {
+    collect_all_rules(app)
+        .into_iter()
+        .filter(|rule| rule.enabled)
+        .collect()
+}
+
+/// A rule key with the rule that wins and the rules it shadows
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveVmrRule {
+    /// "AAL B738" for airline rules, "B738" for type rules, "AAL *" for airline-only rules
+    pub key: String,
+    /// Rule used for matching (first in load order)
+    pub rule: VmrRule,
+    /// Later rules with the same key, which are ignored
+    pub shadowed: Vec<VmrRule>,
+}
+
+/// Merge rules by key in load order (first rule wins), keeping shadowed rules for debugging
+pub fn effective_rules(rules: Vec<VmrRule>) -> Vec<EffectiveVmrRule> {
+    let mut merged: Vec<EffectiveVmrRule> = Vec::new();
+    let mut positions: HashMap<String, usize> = HashMap::new();
+
+    for rule in rules {
+        let key = match (&rule.callsign_prefix, &rule.type_code) {
+            (Some(airline), Some(type_code)) => format!("{} {}", airline, type_code),
+            (Some(airline), None) => format!("{} *", airline),
+            (None, Some(type_code)) => type_code.clone(),
+            (None, None) => continue,
+        };
+        match positions.get(&key) {
+            Some(&position) => merged[position].shadowed.push(rule),
+            None => {
+                positions.insert(key.clone(), merged.len());
+                merged.push(EffectiveVmrRule {
+                    key,
+                    rule,
+                    shadowed: Vec::new(),
+                });
+            }
+        }
+    }
+
+    merged
+}
+
+/// Initialize the parsed VMR cache for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_vmr_cache(app: &AppHandle) {
+    app.manage(VmrCacheState::new());
+}
+
+// =============================================================================
+// RULE EDITING
+// =============================================================================
+
+/// Rule fields supplied when adding or editing a rule
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VmrRuleInput {
+    pub type_code: Option<String>,
+    pub callsign_prefix: Option<String>,
+    pub flight_number_range: Option<String>,
+    /// Model alternatives, in order (written as `Model_A//Model_B`)
+    pub model_names: Vec<String>,
+}
+
+/// Edit applied to a VMR file
+enum VmrEdit<'a> {
+    Add(&'a VmrRuleInput),
+    Update(usize, &'a VmrRuleInput),
+    Delete(usize),
+}
+
+/// Content for a VMR file created by the first added rule
+const NEW_VMR_TEMPLATE: &str =
+    "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<ModelMatchRuleSet>\n</ModelMatchRuleSet>\n";
+
+fn clean_value(value: &Option<String>) -> Option<String> {
+    value
+        .as_ref()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+impl VmrRuleInput {
+    /// Serialize as a self-closing ModelMatchRule element
+    fn to_xml(&self) -> Result<String> {
+        let model_names: Vec<&str> = self
+            .model_names
+            .iter()
+            .map(|m| m.trim())
+            .filter(|m| !m.is_empty())
+            .collect();
+        if model_names.is_empty() {
+            return Err(Error::invalid_input("A rule needs at least one model name"));
+        }
+        if model_names.iter().any(|m| m.contains("//")) {
+            return Err(Error::invalid_input("Model names cannot contain '//'"));
+        }
+
+        let type_code = clean_value(&self.type_code).map(|t| t.to_uppercase());
+        let callsign_prefix = clean_value(&self.callsign_prefix).map(|c| c.to_uppercase());
+        if type_code.is_none() && callsign_prefix.is_none() {
+            return Err(Error::invalid_input("A rule needs a type code or a callsign prefix"));
+        }
+
+        let attributes = [
+            ("CallsignPrefix", callsign_prefix),
+            ("FlightNumberRange", clean_value(&self.flight_number_range)),
+            ("TypeCode", type_code),
+            ("ModelName", Some(model_names.join("//"))),
+        ];
+
+        let mut xml = String::from("<ModelMatchRule");
+        for (name, value) in attributes {
+            if let Some(value) = value {
+                xml.push_str(&format!(" {}=\"{}\"", name, escape(value.as_str())));
+            }
+        }
+        xml.push_str(" />");
+        Ok(xml)
+    }
+}
+
+/// Byte ranges of every ModelMatchRule element, in document order.
+/// Strict (unlike `parse_vmr_content`): malformed files are not edited.
+fn rule_spans(content: &str) -> Result<Vec<Range<usize>>> {
+    let mut reader = Reader::from_str(content);
+    let mut spans = Vec::new();
+    let mut open_start = None;
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        match reader.read_event() {
+            Ok(Event::Empty(ref e)) if is_rule_element(e) => {
+                spans.push(start..reader.buffer_position() as usize);
+            }
+            Ok(Event::Start(ref e)) if is_rule_element(e) => open_start = Some(start),
+            Ok(Event::End(ref e))
+                if e.local_name()
+                    .as_ref()
+                    .eq_ignore_ascii_case(b"ModelMatchRule") =>
+            {
+                if let Some(start) = open_start.take() {
+                    spans.push(start..reader.buffer_position() as usize);
+                }
+            }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(e) => {
+                return Err(Error::InvalidInput(format!(
+                    "Cannot edit malformed VMR file (XML error at position {}): {}",
+                    reader.error_position(),
+                    e
+                )))
+            }
+        }
+    }
+
+    Ok(spans)
+}
+
+/// Start of the line containing `pos`
+fn line_start(content: &str, pos: usize) -> usize {
+    content[..pos].rfind('\n').map(|i| i + 1).unwrap_or(0)
+}
+
+/// Apply an edit to VMR text, leaving everything outside the edited element
+/// (comments, formatting, other rules) untouched
+fn edit_vmr_content(content: &str, edit: VmrEdit) -> Result<String> {
+    let spans = rule_spans(content)?;
+    let newline = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let span_at = |index: usize| {
+        spans
+            .get(index)
+            .cloned()
+            .ok_or_else(|| Error::NotFound(format!("Rule {} not found (file has {} rules)", index, spans.len())))
+    };
+
+    let mut updated = content.to_string();
+    match edit {
+        VmrEdit::Add(input) => {
+            let xml = input.to_xml()?;
+            let close = content
+                .to_ascii_lowercase()
+                .rfind("</modelmatchruleset")
+                .ok_or_else(|| Error::invalid_input("VMR file has no closing </ModelMatchRuleSet> tag"))?;
+
+            // Match the indentation of the last rule
+            let indent = spans
+                .last()
+                .map(|span| &content[line_start(content, span.start)..span.start])
+                .filter(|prefix| prefix.trim().is_empty())
+                .unwrap_or("  ");
+
+            let close_line = line_start(content, close);
+            if content[close_line..close].trim().is_empty() {
+                updated.insert_str(close_line, &format!("{}{}{}", indent, xml, newline));
+            } else {
+                updated.insert_str(close, &format!("{}{}{}{}", newline, indent, xml, newline));
+            }
+        }
+        VmrEdit::Update(index, input) => {
+            let xml = input.to_xml()?;
+            updated.replace_range(span_at(index)?, &xml);
+        }
+        VmrEdit::Delete(index) => {
+            let span = span_at(index)?;
+            // Remove the whole line if the rule is the only thing on it
+            let start = line_start(content, span.start);
+            let line_end = content[span.end..].find('\n').map(|i| span.end + i + 1);
+            match line_end {
+                Some(end)
+                    if content[start..span.start].trim().is_empty()
+                        && content[span.end..end].trim().is_empty() =>
+                {
+                    updated.replace_range(start..end, "")
+                }
+                _ => updated.replace_range(span, ""),
+            }
+        }
+    }
+
+    Ok(updated)
+}
+
+/// Resolve a VMR file for editing. Accepts a path relative to the mods folder
+/// ("custom.vmr" or "aircraft/custom.vmr") or a `sourceFile` reported by the API.
+pub fn resolve_vmr_file(mods_root: &Path, file: &str) -> Result<PathBuf> {
+    let normalized = file.replace('\\', "/");
+    if let Some(path) = list_vmr_paths(mods_root)
+        .into_iter()
+        .find(|p| p.to_string_lossy().replace('\\', "/") == normalized)
+    {
+        return Ok(path);
+    }
+
+    let (dir, name) = match normalized.rsplit_once('/') {
+        Some((dir, name)) => (Some(dir), name),
+        None => (None, normalized.as_str()),
+    };
+    if !matches!(dir, None | Some("aircraft")) {
+        return Err(Error::invalid_input("VMR files must be in mods/ or mods/aircraft/"));
+    }
+    if name.starts_with('.') || !name.to_ascii_lowercase().ends_with(".vmr") {
+        return Err(Error::InvalidInput(format!("Invalid VMR file name: {}", file)));
+    }
+
+    Ok(match dir {
+        Some(dir) => mods_root.join(dir).join(name),
+        None => mods_root.join(name),
+    })
+}
+
+/// Apply an edit to a VMR file on disk and return the file's rules afterwards
+fn modify_vmr_file(app: &AppHandle, file: &str, edit: VmrEdit) -> Result<Vec<VmrRule>> {
+    let path = resolve_vmr_file(&find_mods_root(app), file)?;
+
+    let raw = if path.exists() {
+        fs::read_to_string(&path)
+            .map_err(|e| Error::Io(format!("Failed to read VMR file {:?}: {}", path, e)))?
+    } else if matches!(edit, VmrEdit::Add(_)) {
+        NEW_VMR_TEMPLATE.to_string()
+    } else {
+        return Err(Error::NotFound(format!("VMR file not found: {}", file)));
+    };
+
+    let (bom, content) = match raw.strip_prefix('\u{feff}') {
+        Some(rest) => ("\u{feff}", rest),
+        None => ("", raw.as_str()),
+    };
+    let updated = edit_vmr_content(content, edit)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| Error::Io(format!("Failed to create directory {:?}: {}", parent, e)))?;
+    }
+    fs::write(&path, format!("{}{}", bom, updated))
+        .map_err(|e| Error::Io(format!("Failed to write VMR file {:?}: {}", path, e)))?;
+
+    info!("[VMR] Updated {:?}", path);
+    parse_vmr_file(&path)
+}
+
+/// Append a rule to a VMR file (created if it doesn't exist)
+pub fn add_rule(app: &AppHandle, file: &str, rule: &VmrRuleInput) -> Result<Vec<VmrRule>> {
+    modify_vmr_file(app, file, VmrEdit::Add(rule))
+}
+
+/// Replace the rule at `index` in a VMR file
+pub fn update_rule(
+    app: &AppHandle,
+    file: &str,
+    index: usize,
+    rule: &VmrRuleInput,
+) -> Result<Vec<VmrRule>> {
+    modify_vmr_file(app, file, VmrEdit::Update(index, rule))
+}
+
+/// Delete the rule at `index` from a VMR file
+pub fn delete_rule(app: &AppHandle, file: &str, index: usize) -> Result<Vec<VmrRule>> {
+    let rules = modify_vmr_file(app, file, VmrEdit::Delete(index))?;
+
+    // Later rules moved up one position; keep their disabled state attached to them
+    let mods_root = find_mods_root(app);
+    let key = file_key(&mods_root, &resolve_vmr_file(&mods_root, file)?);
+    let mut vmr_state = read_vmr_state(app);
+    if let Some(indices) = vmr_state.disabled_rules.get_mut(&key) {
+        *indices = shift_after_delete(indices, index);
+        if indices.is_empty() {
+            vmr_state.disabled_rules.remove(&key);
+        }
+        write_vmr_state(app, &vmr_state)?;
+    }
+
+    Ok(rules)
+}
+
+/// Rule indices after deleting the rule at `deleted`
+fn shift_after_delete(indices: &BTreeSet<usize>, deleted: usize) -> BTreeSet<usize> {
+    indices
+        .iter()
+        .filter(|&&i| i != deleted)
+        .map(|&i| if i > deleted { i - 1 } else { i })
+        .collect()
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get parsed rules from all enabled VMR files (disabled files and rules are excluded)
+#[tauri::command]
+pub fn get_vmr_rules(app: AppHandle) -> Vec<VmrRule> {
+    collect_mod_rules(&app)
+}
+
+/// Get parsed rules from all VMR files, including disabled ones
+#[tauri::command]
+pub fn get_all_vmr_rules(app: AppHandle) -> Vec<VmrRule> {
+    collect_all_rules(&app)
+}
+
+/// Get the persisted VMR file/rule enablement
+#[tauri::command]
+pub fn get_vmr_state(app: AppHandle) -> VmrStateFile {
+    read_vmr_state(&app)
+}
+
+/// Enable or disable a whole VMR file
+#[tauri::command]
+pub fn set_vmr_file_enabled(
+    app: AppHandle,
+    file: String,
+    enabled: bool,
+) -> Result<VmrStateFile> {
+    set_file_enabled(&app, &file, enabled)
+}
+
+/// Enable or disable a single VMR rule (by index within its file)
+#[tauri::command]
+pub fn set_vmr_rule_enabled(
+    app: AppHandle,
+    file: String,
+    index: usize,
+    enabled: bool,
+) -> Result<VmrStateFile> {
+    set_rule_enabled(&app, &file, index, enabled)
+}
+
+/// Get the effective merged ruleset (winning rule per key, plus shadowed rules)
+#[tauri::command]
+pub fn get_effective_vmr_rules(app: AppHandle) -> Vec<EffectiveVmrRule> {
+    effective_rules(collect_mod_rules(&app))
+}
+
+/// Add a rule to a VMR file in the mods folder; returns the file's rules
+#[tauri::command]
+pub fn add_vmr_rule(
+    app: AppHandle,
+    file: String,
+    rule: VmrRuleInput,
+) -> Result<Vec<VmrRule>> {
+    add_rule(&app, &file, &rule)
+}
+
+/// Replace a rule (by index within its file); returns the file's rules
+#[tauri::command]
+pub fn update_vmr_rule(
+    app: AppHandle,
+    file: String,
+    index: usize,
+    rule: VmrRuleInput,
+) -> Result<Vec<VmrRule>> {
+    update_rule(&app, &file, index, &rule)
+}
+
+/// Delete a rule (by index within its file); returns the file's rules
+#[tauri::command]
+pub fn delete_vmr_rule(app: AppHandle, file: String, index: usize) -> Result<Vec<VmrRule>> {
+    delete_rule(&app, &file, index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Trimmed FSLTL_Rules.vmr (comments, disabled rules, model alternatives)
+    const FSLTL_RULES: &str = include_str!("../tests/fixtures/vmr/FSLTL_Rules.vmr");
+
+    /// Trimmed AIG rule set as vPilot writes it (BOM, CRLF, namespaced root, flight number ranges)
+    const AIG_RULES: &str = include_str!("../tests/fixtures/vmr/AIG_Rules.vmr");
+
+    /// Hand-written rules spanning several lines or using open/close tags
+    const MULTILINE_RULES: &str = r#"<ModelMatchRuleSet>
+  <ModelMatchRule
+      CallsignPrefix="DAL"
+      TypeCode="A321"
+      ModelName="My_A321_DAL" />
+  <ModelMatchRule CallsignPrefix="BAW" TypeCode="A320" ModelName="My_A320_BAW"></ModelMatchRule>
+</ModelMatchRuleSet>"#;
+
+    #[test]
+    fn parses_fsltl_rules() {
+        let parsed = parse_vmr_content(FSLTL_RULES);
+        assert!(parsed.error.is_none());
+        assert_eq!(parsed.rules.len(), 8);
+
+        let dal = &parsed.rules[5];
+        assert_eq!(dal.type_code.as_deref(), Some("A321"));
+        assert_eq!(dal.callsign_prefix.as_deref(), Some("DAL"));
+        assert_eq!(dal.model_names, vec!["FSLTL_FAIB_A321_Delta"]);
+        // The commented-out B739 rule is skipped
+        assert!(parsed
+            .rules
+            .iter()
+            .all(|r| r.type_code.as_deref() != Some("B739")));
+    }
+
+    #[test]
+    fn splits_model_alternatives() {
+        let parsed = parse_vmr_content(FSLTL_RULES);
+        let aal = &parsed.rules[4];
+        assert_eq!(
+            aal.model_names,
+            vec!["FSLTL_FAIB_B738_American", "FSLTL_FAIB_B738_American_Retro"]
+        );
+        assert_eq!(
+            aal.model_name,
+            "FSLTL_FAIB_B738_American//FSLTL_FAIB_B738_American_Retro"
+        );
+        assert_eq!(parsed.rules[7].model_names.len(), 3);
+    }
+
+    #[test]
+    fn parses_aig_rules() {
+        let parsed = parse_vmr_content(AIG_RULES);
+        assert!(parsed.error.is_none());
+        assert_eq!(parsed.rules.len(), 7);
+        assert_eq!(parsed.rules[0].callsign_prefix.as_deref(), Some("AAL"));
+        assert_eq!(
+            parsed.rules[1].flight_number_range.as_deref(),
+            Some("1-999")
+        );
+        assert_eq!(
+            parsed.rules[2].flight_number_range.as_deref(),
+            Some("1000-9999")
+        );
+        // Airline-only rule (no TypeCode) applies to any type
+        assert_eq!(parsed.rules[4].type_code, None);
+        assert_eq!(parsed.rules[4].callsign_prefix.as_deref(), Some("FDX"));
+        assert_eq!(
+            parsed.rules[5].model_names,
+            vec!["AIG Airbus A320-200 JetBlue Airways & Friends N531JL"]
+        );
+    }
+
+    #[test]
+    fn parses_multiline_and_open_close_rules() {
+        let parsed = parse_vmr_content(MULTILINE_RULES);
+        assert!(parsed.error.is_none());
+        assert_eq!(parsed.rules.len(), 2);
+        assert_eq!(parsed.rules[0].callsign_prefix.as_deref(), Some("DAL"));
+        assert_eq!(parsed.rules[1].model_names, vec!["My_A320_BAW"]);
+    }
+
+    #[test]
+    fn tolerates_unescaped_ampersand_and_empty_alternatives() {
+        let content = r#"<ModelMatchRuleSet>
+  <ModelMatchRule TypeCode="c172" ModelName="//Cessna & Co//" />
+  <ModelMatchRule TypeCode="B744" ModelName="//" />
+</ModelMatchRuleSet>"#;
+        let parsed = parse_vmr_content(content);
+        assert_eq!(parsed.rules.len(), 1);
+        assert_eq!(parsed.rules[0].type_code.as_deref(), Some("C172"));
+        assert_eq!(parsed.rules[0].model_names, vec!["Cessna & Co"]);
+    }
+
+    #[test]
+    fn keeps_rules_before_malformed_xml() {
+        let content = r#"<ModelMatchRuleSet>
+  <ModelMatchRule TypeCode="A20N" ModelName="FSLTL_A20N_ZZZZ" />
+  <ModelMatchRule TypeCode="A21N ModelName="broken />
+</ModelMatchRuleSet>"#;
+        let parsed = parse_vmr_content(content);
+        assert_eq!(parsed.rules.len(), 1);
+        assert_eq!(parsed.rules[0].model_names, vec!["FSLTL_A20N_ZZZZ"]);
+    }
+
+    fn input(type_code: &str, callsign: Option<&str>, models: &[&str]) -> VmrRuleInput {
+        VmrRuleInput {
+            type_code: Some(type_code.to_string()),
+            callsign_prefix: callsign.map(str::to_string),
+            flight_number_range: None,
+            model_names: models.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn edits_preserve_comments_and_formatting() {
+        let added = edit_vmr_content(
+            FSLTL_RULES,
+            VmrEdit::Add(&input("e75l", Some("skw"), &["My_E175"])),
+        )
+        .unwrap();
+        assert!(added.contains("<!-- Base models -->"));
+        assert!(added.contains(
+            "  <ModelMatchRule CallsignPrefix=\"SKW\" TypeCode=\"E75L\" ModelName=\"My_E175\" />\n</ModelMatchRuleSet>"
+        ));
+
+        let updated = edit_vmr_content(
+            &added,
+            VmrEdit::Update(4, &input("B738", Some("AAL"), &["A", "B & C"])),
+        )
+        .unwrap();
+        let parsed = parse_vmr_content(&updated);
+        assert_eq!(parsed.rules.len(), 9);
+        assert_eq!(parsed.rules[4].model_names, vec!["A", "B & C"]);
+        assert!(updated.contains("<!-- <ModelMatchRule CallsignPrefix=\"AAL\" TypeCode=\"B739\""));
+
+        let deleted = edit_vmr_content(&updated, VmrEdit::Delete(0)).unwrap();
+        assert!(!deleted.contains("FSLTL_A20N_ZZZZ"));
+        assert!(deleted.contains("  <!-- Base models -->\n  <ModelMatchRule TypeCode=\"A321\""));
+        assert_eq!(parse_vmr_content(&deleted).rules.len(), 8);
+    }
+
+    #[test]
+    fn edits_keep_crlf_line_endings() {
+        // modify_vmr_file strips the BOM before editing
+        let content = AIG_RULES.trim_start_matches('\u{feff}');
+        let added = edit_vmr_content(
+            content,
+            VmrEdit::Add(&input("E75L", Some("SKW"), &["My_E175"])),
+        )
+        .unwrap();
+        assert!(added.contains("ModelName=\"My_E175\" />\r\n</ModelMatchRuleSet>\r\n"));
+        assert!(!added.replace("\r\n", "").contains('\n'));
+
+        let deleted = edit_vmr_content(&added, VmrEdit::Delete(4)).unwrap();
+        assert!(!deleted.contains("FDX"));
+        assert!(!deleted.replace("\r\n", "").contains('\n'));
+        assert_eq!(parse_vmr_content(&deleted).rules.len(), 7);
+    }
+
+    #[test]
+    fn edits_handle_multiline_and_open_close_rules() {
+        // Deleting a multi-line rule removes all of its lines
+        let deleted = edit_vmr_content(MULTILINE_RULES, VmrEdit::Delete(0)).unwrap();
+        assert!(!deleted.contains("DAL"));
+        assert!(
+            deleted.starts_with("<ModelMatchRuleSet>\n  <ModelMatchRule CallsignPrefix=\"BAW\"")
+        );
+
+        let updated = edit_vmr_content(
+            MULTILINE_RULES,
+            VmrEdit::Update(1, &input("A20N", Some("BAW"), &["My_A20N_BAW"])),
+        )
+        .unwrap();
+        assert!(!updated.contains("</ModelMatchRule>"));
+        assert_eq!(
+            parse_vmr_content(&updated).rules[1].type_code.as_deref(),
+            Some("A20N")
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_edits() {
+        assert!(edit_vmr_content(FSLTL_RULES, VmrEdit::Delete(10)).is_err());
+        assert!(edit_vmr_content(FSLTL_RULES, VmrEdit::Add(&input("B738", None, &[" "]))).is_err());
+        assert!(edit_vmr_content(
+            "<ModelMatchRuleSet><Broken></ModelMatchRuleSet>",
+            VmrEdit::Delete(0)
+        )
+        .is_err());
+        assert!(resolve_vmr_file(Path::new("/mods"), "../evil.vmr").is_err());
+        assert!(resolve_vmr_file(Path::new("/mods"), "towers/rules.vmr").is_err());
+        assert!(resolve_vmr_file(Path::new("/mods"), "aircraft/custom.vmr").is_ok());
+    }
+
+    #[test]
+    fn effective_rules_keep_first_rule_and_list_shadowed() {
+        let mut rules = parse_vmr_content(FSLTL_RULES).rules;
+        let mut overrides = parse_vmr_content(
+            r#"<ModelMatchRuleSet>
+  <ModelMatchRule CallsignPrefix="AAL" TypeCode="B738" ModelName="Override_AAL" />
+  <ModelMatchRule CallsignPrefix="AAL" ModelName="Any_AAL" />
+</ModelMatchRuleSet>"#,
+        )
+        .rules;
+        rules.append(&mut overrides);
+
+        let effective = effective_rules(rules);
+        let keys: Vec<&str> = effective.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "A20N", "A321", "B738", "AAL A321", "AAL B738", "DAL A321", "DAL B738", "SWA B738",
+                "AAL *"
+            ]
+        );
+        assert_eq!(effective[4].rule.model_names[0], "FSLTL_FAIB_B738_American");
+        assert_eq!(effective[4].shadowed.len(), 1);
+        assert_eq!(effective[4].shadowed[0].model_names, vec!["Override_AAL"]);
+    }
+
+    #[test]
+    fn disabled_state_applies_per_file_and_rule() {
+        let mut vmr_state = VmrStateFile::default();
+        vmr_state
+            .disabled_rules
+            .insert("aircraft/custom.vmr".to_string(), BTreeSet::from([1, 3]));
+        vmr_state.disabled_files.insert("old.vmr".to_string());
+
+        assert!(vmr_state.is_rule_enabled("aircraft/custom.vmr", 0));
+        assert!(!vmr_state.is_rule_enabled("aircraft/custom.vmr", 3));
+        assert!(!vmr_state.is_rule_enabled("old.vmr", 0));
+
+        // Deleting rule 2 moves rule 3 to index 2; deleting a disabled rule drops it
+        assert_eq!(
+            shift_after_delete(&BTreeSet::from([1, 3]), 2),
+            BTreeSet::from([1, 2])
+        );
+        assert_eq!(
+            shift_after_delete(&BTreeSet::from([1, 3]), 1),
+            BTreeSet::from([2])
+        );
+    }
+}
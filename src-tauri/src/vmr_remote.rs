@@ -0,0 +1,470 @@
+//! Remote VMR subscriptions
+//!
+//! Subscribes to VMR rule sets published online (like vPilot custom model
+//! matching sets). Each subscription is downloaded on startup and then on its
+//! refresh interval, cached in the app data directory (`vmr-cache/`) and
+//! revalidated with ETag/Last-Modified so unchanged files aren't downloaded
+//! again. Cached rules are collected after the local VMR files, so local rules
+//! take precedence.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use reqwest::header::{HeaderName, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+use crate::error::{Error, Result};
+use crate::debug_stats;
+use crate::http_client;
+use crate::supervisor::{self, Restart};
+use crate::vmr::parse_vmr_content;
+
+/// How often the background task checks for subscriptions that are due
+const CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Serializes refreshes so concurrent checks don't race on the cache
+static REFRESH_LOCK: Mutex<()> = Mutex::const_new(());
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_refresh_hours() -> u32 {
+    24
+}
+
+/// A remote VMR rule set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VmrSubscription {
+    pub id: String,
+    pub url: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Hours between checks for updates
+    #[serde(default = "default_refresh_hours")]
+    pub refresh_hours: u32,
+    #[serde(default)]
+    pub etag: Option<String>,
+    #[serde(default)]
+    pub last_modified: Option<String>,
+    /// Unix timestamp (ms) of the last successful check
+    #[serde(default)]
+    pub last_checked: Option<u64>,
+    #[serde(default)]
+    pub last_error: Option<String>,
+    /// Number of rules in the cached copy
+    #[serde(default)]
+    pub rule_count: usize,
+}
+
+/// Persisted subscriptions (vmr-subscriptions.json in app data)
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SubscriptionsFile {
+    #[serde(default)]
+    subscriptions: Vec<VmrSubscription>,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn get_app_data_dir(app: &AppHandle) -> Result<PathBuf> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))?;
+
+    fs::create_dir_all(&app_data).map_err(|e| Error::Io(format!("Failed to create app data directory: {}", e)))?;
+
+    Ok(app_data)
+}
+
+/// Cached copy of a subscription's VMR file
+fn cached_path(app: &AppHandle, id: &str) -> Result<PathBuf> {
+    Ok(get_app_data_dir(app)?.join("vmr-cache").join(format!("{}.vmr", id)))
+}
+
+/// Read persisted subscriptions (empty if missing or invalid)
+pub fn read_subscriptions(app: &AppHandle) -> Vec<VmrSubscription> {
+    get_app_data_dir(app)
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join("vmr-subscriptions.json")).ok())
+        .and_then(|content| serde_json::from_str::<SubscriptionsFile>(&content).ok())
+        .map(|file| file.subscriptions)
+        .unwrap_or_default()
+}
+
+fn write_subscriptions(app: &AppHandle, subscriptions: Vec<VmrSubscription>) -> Result<()> {
+    let path = get_app_data_dir(app)?.join("vmr-subscriptions.json");
+    let content = serde_json::to_string_pretty(&SubscriptionsFile { subscriptions })
+        .map_err(|e| Error::Internal(format!("Failed to serialize VMR subscriptions: {}", e)))?;
+    fs::write(&path, content).map_err(|e| Error::Io(format!("Failed to write VMR subscriptions: {}", e)))
+}
+
+/// Cached VMR files of enabled subscriptions, in subscription order
+pub fn enabled_cached_paths(app: &AppHandle) -> Vec<PathBuf> {
+    read_subscriptions(app)
+        .iter()
+        .filter(|s| s.enabled)
+        .filter_map(|s| cached_path(app, &s.id).ok())
+        .filter(|p| p.exists())
+        .collect()
+}
+
+/// Build a readable, unique id from the URL (e.g., "example-com-rules-vmr")
+fn make_id(url: &str, existing: &[VmrSubscription]) -> String {
+    let trimmed = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    let slug: String = trimmed
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect::<String>()
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let base: String = slug.chars().take(60).collect();
+    let base = if base.is_empty() {
+        "subscription".to_string()
+    } else {
+        base
+    };
+
+    let mut id = base.clone();
+    let mut suffix = 2;
+    while existing.iter().any(|s| s.id == id) {
+        id = format!("{}-{}", base, suffix);
+        suffix += 1;
+    }
+    id
+}
+
+/// Result of checking a subscription for updates
+enum FetchOutcome {
+    NotModified,
+    Updated {
+        content: String,
+        etag: Option<String>,
+        last_modified: Option<String>,
+    },
+}
+
+async fn fetch(
+    client: &reqwest::Client,
+    subscription: &VmrSubscription,
+    has_cache: bool,
+) -> Result<FetchOutcome> {
+    let mut request = client.get(&subscription.url);
+    if has_cache {
+        if let Some(ref etag) = subscription.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(ref last_modified) = subscription.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+
+    let response = request.send().await.map_err(|e| Error::Upstream(format!("Failed to fetch: {}", e)))?;
+    if response.status() == StatusCode::NOT_MODIFIED {
+        return Ok(FetchOutcome::NotModified);
+    }
+    if !response.status().is_success() {
+        return Err(Error::Upstream(format!("HTTP error: {}", response.status())));
+    }
+
+    let header = |name: HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    let etag = header(ETAG);
+    let last_modified = header(LAST_MODIFIED);
+
+    let content = response
+        .text()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to read response: {}", e)))?;
+    Ok(FetchOutcome::Updated {
+        content,
+        etag,
+        last_modified,
+    })
+}
+
+/// Download (or revalidate) one subscription, updating its cache and status fields
+async fn refresh_one(app: &AppHandle, client: &reqwest::Client, subscription: &mut VmrSubscription) {
+    let Ok(path) = cached_path(app, &subscription.id) else {
+        return;
+    };
+
+    let result = match fetch(client, subscription, path.exists()).await {
+        Ok(FetchOutcome::NotModified) => Ok(()),
+        Ok(FetchOutcome::Updated {
+            content,
+            etag,
+            last_modified,
+        }) => {
+            let parsed = parse_vmr_content(&content);
+            if parsed.rules.is_empty() {
+                Err(parsed
+                    .error
+                    .unwrap_or_else(|| "No ModelMatchRule entries found".to_string()))
+            } else {
+                path.parent()
+                    .map(fs::create_dir_all)
+                    .transpose()
+                    .and_then(|_| fs::write(&path, &content))
+                    .map_err(|e| format!("Failed to cache VMR file: {}", e))
+                    .map(|_| {
+                        subscription.etag = etag;
+                        subscription.last_modified = last_modified;
+                        subscription.rule_count = parsed.rules.len();
+                    })
+            }
+        }
+        Err(e) => Err(e.to_string()),
+    };
+
+    match result {
+        Ok(()) => {
+            subscription.last_checked = Some(now_ms());
+            subscription.last_error = None;
+            info!(
+                "[VMR] Subscription {} up to date ({} rules)",
+                subscription.id, subscription.rule_count
+            );
+        }
+        Err(e) => {
+            warn!("[VMR] Subscription {} failed: {}", subscription.id, e);
+            subscription.last_error = Some(e);
+        }
+    }
+}
+
+/// Refresh subscriptions that are due (or all enabled ones if `force`)
+pub async fn refresh_subscriptions(app: &AppHandle, force: bool) -> Vec<VmrSubscription> {
+    let _guard = REFRESH_LOCK.lock().await;
+
+    let now = now_ms();
+    let due: Vec<VmrSubscription> = read_subscriptions(app)
+        .into_iter()
+        .filter(|s| s.enabled)
+        .filter(|s| {
+            let interval_ms = s.refresh_hours as u64 * 3_600_000;
+            force || !s.last_checked.is_some_and(|t| now.saturating_sub(t) < interval_ms)
+        })
+        .collect();
+
+    if due.is_empty() {
+        return read_subscriptions(app);
+    }
+
+    let client = http_client::client();
+    let mut refreshed = Vec::with_capacity(due.len());
+    for mut subscription in due {
+        refresh_one(app, client, &mut subscription).await;
+        refreshed.push(subscription);
+    }
+
+    // Merge fetch results into the current file (subscriptions may have been edited meanwhile)
+    let mut subscriptions = read_subscriptions(app);
+    for current in subscriptions.iter_mut() {
+        if let Some(updated) = refreshed.iter().find(|s| s.id == current.id) {
+            current.etag = updated.etag.clone();
+            current.last_modified = updated.last_modified.clone();
+            current.last_checked = updated.last_checked;
+            current.last_error = updated.last_error.clone();
+            current.rule_count = updated.rule_count;
+        }
+    }
+    if let Err(e) = write_subscriptions(app, subscriptions.clone()) {
+        warn!("[VMR] {}", e);
+    }
+
+    subscriptions
+}
+
+/// Download subscriptions on startup, then check periodically for ones that are due
+pub fn start_refresh_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn("vmr-subscriptions", Restart::Always, move || {
+        let app = app.clone();
+        async move {
+            loop {
+                refresh_subscriptions(&app, false).await;
+                debug_stats::task_ran("vmr-subscriptions", CHECK_INTERVAL, None);
+                tokio::time::sleep(CHECK_INTERVAL).await;
+            }
+        }
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// List remote VMR subscriptions
+#[tauri::command]
+pub fn list_vmr_subscriptions(app: AppHandle) -> Vec<VmrSubscription> {
+    read_subscriptions(&app)
+}
+
+/// Subscribe to a remote VMR file and download it immediately
+#[tauri::command]
+pub async fn add_vmr_subscription(
+    app: AppHandle,
+    url: String,
+    name: Option<String>,
+    refresh_hours: Option<u32>,
+) -> Result<VmrSubscription> {
+    let url = url.trim().to_string();
+    let parsed = url::Url::parse(&url).map_err(|e| Error::InvalidInput(format!("Invalid URL: {}", e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(Error::invalid_input("Only http and https URLs are supported"));
+    }
+
+    let mut subscriptions = read_subscriptions(&app);
+    if subscriptions.iter().any(|s| s.url == url) {
+        return Err(Error::conflict("Already subscribed to this URL"));
+    }
+
+    let id = make_id(&url, &subscriptions);
+    subscriptions.push(VmrSubscription {
+        id: id.clone(),
+        url,
+        name: name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()),
+        enabled: true,
+        refresh_hours: refresh_hours.unwrap_or_else(default_refresh_hours).max(1),
+        etag: None,
+        last_modified: None,
+        last_checked: None,
+        last_error: None,
+        rule_count: 0,
+    });
+    write_subscriptions(&app, subscriptions)?;
+
+    refresh_subscriptions(&app, false)
+        .await
+        .into_iter()
+        .find(|s| s.id == id)
+        .ok_or_else(|| Error::not_found("Subscription was removed"))
+}
+
+/// Remove a subscription and its cached file
+#[tauri::command]
+pub fn remove_vmr_subscription(app: AppHandle, id: String) -> Result<()> {
+    let mut subscriptions = read_subscriptions(&app);
+    let count = subscriptions.len();
+    subscriptions.retain(|s| s.id != id);
+    if subscriptions.len() == count {
+        return Err(Error::NotFound(format!("Subscription not found: {}", id)));
+    }
+    write_subscriptions(&app, subscriptions)?;
+
+    if let Ok(path) = cached_path(&app, &id) {
+        let _ = fs::remove_file(path);
+    }
+    Ok(())
+}
+
+/// Include or exclude a subscription's rules without removing it
+#[tauri::command]
+pub fn set_vmr_subscription_enabled(app: AppHandle, id: String, enabled: bool) -> Result<()> {
+    let mut subscriptions = read_subscriptions(&app);
+    let subscription = subscriptions
+        .iter_mut()
+        .find(|s| s.id == id)
+        .ok_or_else(|| Error::NotFound(format!("Subscription not found: {}", id)))?;
+    subscription.enabled = enabled;
+    write_subscriptions(&app, subscriptions)
+}
+
+/// Check all enabled subscriptions for updates now
+#[tauri::command]
+pub async fn refresh_vmr_subscriptions(app: AppHandle) -> Vec<VmrSubscription> {
+    refresh_subscriptions(&app, true).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderMap;
+    use axum::routing::get;
+    use axum::Router;
+
+    fn subscription(id: &str, url: &str) -> VmrSubscription {
+        serde_json::from_value(serde_json::json!({ "id": id, "url": url })).unwrap()
+    }
+
+    #[test]
+    fn builds_readable_unique_ids() {
+        let existing = [subscription("example-com-rules-vmr", "https://example.com/rules.vmr")];
+        assert_eq!(make_id("https://Example.com/AIG/Rules.vmr", &[]), "example-com-aig-rules-vmr");
+        assert_eq!(make_id("https://example.com/rules.vmr", &existing), "example-com-rules-vmr-2");
+        assert_eq!(make_id("://", &[]), "subscription");
+        assert_eq!(make_id(&format!("https://{}", "a".repeat(100)), &[]).len(), 60);
+    }
+
+    #[test]
+    fn fills_subscription_defaults() {
+        let subscription = subscription("rules", "https://example.com/rules.vmr");
+        assert!(subscription.enabled);
+        assert_eq!(subscription.refresh_hours, 24);
+        assert_eq!((subscription.etag, subscription.last_checked, subscription.rule_count), (None, None, 0));
+    }
+
+    #[test]
+    fn revalidates_with_etag() {
+        async fn rules(headers: HeaderMap) -> (StatusCode, [(HeaderName, &'static str); 1], &'static str) {
+            let status = match headers.get(IF_NONE_MATCH) {
+                Some(etag) if etag == "\"v1\"" => StatusCode::NOT_MODIFIED,
+                _ => StatusCode::OK,
+            };
+            (status, [(ETAG, "\"v1\"")], "<ModelMatchRuleSet />")
+        }
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let app = Router::new()
+                .route("/rules.vmr", get(rules))
+                .route("/missing.vmr", get(|| async { StatusCode::NOT_FOUND }));
+            tokio::spawn(async move { axum::serve(listener, app).await });
+
+            let client = reqwest::Client::new();
+            let mut subscription = subscription("rules", &format!("http://{}/rules.vmr", addr));
+            let Ok(FetchOutcome::Updated { content, etag, .. }) = fetch(&client, &subscription, false).await else {
+                panic!("expected the rules to be downloaded");
+            };
+            assert_eq!(content, "<ModelMatchRuleSet />");
+            assert_eq!(etag.as_deref(), Some("\"v1\""));
+
+            // The ETag is only sent when a cached copy exists
+            subscription.etag = etag;
+            assert!(matches!(fetch(&client, &subscription, true).await, Ok(FetchOutcome::NotModified)));
+            assert!(matches!(fetch(&client, &subscription, false).await, Ok(FetchOutcome::Updated { .. })));
+
+            subscription.url = format!("http://{}/missing.vmr", addr);
+            assert!(matches!(fetch(&client, &subscription, false).await, Err(Error::Upstream(_))));
+        });
+    }
+}
@@ -18,6 +18,10 @@
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use tauri::{AppHandle, Manager, State};
+use tracing::info;
+
+use crate::error::Error;
+use crate::trails::TrailPoint;
 
 // =============================================================================
 // FRONTEND TYPES (JSON-serializable for Tauri commands)
@@ -68,6 +72,34 @@ pub struct VnasAircraft {
     pub altitude_agl: f64,
     pub voice_type: u8, // 0=Unknown, 1=Full, 2=ReceiveOnly, 3=TextOnly
     pub timestamp: u64, // Unix timestamp ms
+    /// Derived from position history (not reported by vNAS)
+    pub groundspeed_kt: Option<f64>,
+    /// Feet per minute, derived from position history (not reported by vNAS)
+    pub vertical_rate_fpm: Option<f64>,
+    /// Trail point added by this update (see `trails`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trail_point: Option<TrailPoint>,
+}
+
+/// vNAS update counterpart of `traffic::test_aircraft`
+#[cfg(test)]
+pub fn test_vnas_aircraft(callsign: &str, timestamp: u64) -> VnasAircraft {
+    VnasAircraft {
+        callsign: callsign.to_string(),
+        type_code: "B738".to_string(),
+        is_heavy: false,
+        lat: 42.0,
+        lon: -71.0,
+        true_heading: 0.0,
+        true_ground_track: None,
+        altitude_true: 3000.0,
+        altitude_agl: 3000.0,
+        voice_type: 1,
+        timestamp,
+        groundspeed_kt: None,
+        vertical_rate_fpm: None,
+        trail_point: None,
+    }
 }
 
 /// vNAS connection status for frontend
@@ -110,10 +142,13 @@ impl Default for VnasStatus {
 #[cfg(feature = "vnas")]
 mod real_impl {
     use super::*;
+    use crate::kinematics::{Kinematics, PositionFix, PositionHistory};
+    use crate::supervisor::{self, Restart};
     use std::sync::Arc;
-    use std::time::{SystemTime, UNIX_EPOCH};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
     use tauri::Emitter;
     use tokio::sync::{broadcast, RwLock as TokioRwLock};
+    use tracing::debug;
 
     // Import types from the vNAS crate
     use towercab_3d_vnas::{
@@ -164,6 +199,9 @@ mod real_impl {
                 altitude_agl: dto.altitude_agl,
                 voice_type: dto.voice_type as u8,
                 timestamp,
+                groundspeed_kt: None,
+                vertical_rate_fpm: None,
+                trail_point: None,
             }
         }
     }
@@ -240,7 +278,7 @@ mod real_impl {
         app: AppHandle,
         state: State<'_, VnasState>,
         environment: Environment,
-    ) -> Result<String, String> {
+    ) -> Result<String, Error> {
         // Update status
         let mut status = state.status();
         status.state = SessionState::Authenticating;
@@ -259,17 +297,17 @@ mod real_impl {
         let auth_url = service.start_oauth().await.map_err(|e| {
             state.set_error(Some(e.to_string()));
             state.update_state(SessionState::Disconnected);
-            e.to_string()
+            Error::Upstream(e.to_string())
         })?;
 
         // Store service for later use
         *state.service.write().await = Some(service);
 
-        println!(
+        info!(
             "[vNAS] OAuth flow started for {:?} environment",
             environment
         );
-        println!("[vNAS] Auth URL: {}", auth_url);
+        info!("[vNAS] Auth URL: {}", auth_url);
 
         Ok(auth_url)
     }
@@ -277,23 +315,23 @@ mod real_impl {
     /// Complete the OAuth flow after browser callback.
     /// This waits for the SSE callback from auth.vfsp.net.
     #[tauri::command]
-    pub async fn vnas_complete_auth(state: State<'_, VnasState>) -> Result<(), String> {
+    pub async fn vnas_complete_auth(state: State<'_, VnasState>) -> Result<(), Error> {
         // Get service reference
         let service_guard = state.service.read().await;
         let service = service_guard
             .as_ref()
-            .ok_or("OAuth not started - call vnas_start_auth first")?;
+            .ok_or_else(|| Error::conflict("OAuth not started - call vnas_start_auth first"))?;
 
         // Wait for OAuth callback (this blocks until user completes browser auth)
-        println!("[vNAS] Waiting for OAuth callback from browser...");
+        info!("[vNAS] Waiting for OAuth callback from browser...");
 
         service.complete_oauth().await.map_err(|e| {
             state.set_error(Some(e.to_string()));
             state.update_state(SessionState::Disconnected);
-            format!("OAuth failed: {}", e)
+            Error::Upstream(format!("OAuth failed: {}", e))
         })?;
 
-        println!("[vNAS] OAuth completed successfully");
+        info!("[vNAS] OAuth completed successfully");
         state.update_state(SessionState::Connecting);
 
         Ok(())
@@ -309,37 +347,38 @@ mod real_impl {
     pub async fn vnas_handle_oauth_callback(
         state: State<'_, VnasState>,
         callback_url: String,
-    ) -> Result<(), String> {
-        println!("[vNAS] Received OAuth callback: {}", callback_url);
+    ) -> Result<(), Error> {
+        info!("[vNAS] Received OAuth callback: {}", callback_url);
 
         // Parse the callback URL to extract the authorization code
         let url = url::Url::parse(&callback_url)
-            .map_err(|e| format!("Invalid callback URL: {}", e))?;
+            .map_err(|e| Error::InvalidInput(format!("Invalid callback URL: {}", e)))?;
 
         let code = url
             .query_pairs()
             .find(|(key, _)| key == "code")
             .map(|(_, value)| value.to_string())
-            .ok_or("No authorization code in callback URL")?;
+            .ok_or_else(|| Error::invalid_input("No authorization code in callback URL"))?;
+        crate::secrets::add("vnas", &code);
 
         let code_preview = if code.len() > 10 { &code[..10] } else { &code };
-        println!("[vNAS] Extracted authorization code: {}...", code_preview);
+        debug!("[vNAS] Extracted authorization code: {}...", code_preview);
 
         // Get service reference
         let service_guard = state.service.read().await;
         let service = service_guard
             .as_ref()
-            .ok_or("OAuth not started - call vnas_start_auth first")?;
+            .ok_or_else(|| Error::conflict("OAuth not started - call vnas_start_auth first"))?;
 
         // Complete OAuth with the authorization code from the deep link
         // The vNAS crate needs complete_oauth_with_code method for this to work
         service.complete_oauth_with_code(&code).await.map_err(|e| {
             state.set_error(Some(e.to_string()));
             state.update_state(SessionState::Disconnected);
-            format!("OAuth failed: {}", e)
+            Error::Upstream(format!("OAuth failed: {}", e))
         })?;
 
-        println!("[vNAS] OAuth completed successfully via deep link");
+        info!("[vNAS] OAuth completed successfully via deep link");
         state.update_state(SessionState::Connecting);
 
         Ok(())
@@ -348,15 +387,15 @@ mod real_impl {
     /// Connect to vNAS after successful authentication.
     /// This establishes the SignalR WebSocket connection.
     #[tauri::command]
-    pub async fn vnas_connect(state: State<'_, VnasState>) -> Result<(), String> {
+    pub async fn vnas_connect(state: State<'_, VnasState>) -> Result<(), Error> {
         // Check if authenticated
         let service_guard = state.service.read().await;
         let service = service_guard
             .as_ref()
-            .ok_or("Not authenticated - complete OAuth first")?;
+            .ok_or_else(|| Error::conflict("Not authenticated - complete OAuth first"))?;
 
         if !service.is_authenticated().await {
-            return Err("Not authenticated - complete OAuth first".into());
+            return Err(Error::conflict("Not authenticated - complete OAuth first"));
         }
 
         state.update_state(SessionState::Connecting);
@@ -365,68 +404,103 @@ mod real_impl {
         service.connect().await.map_err(|e| {
             state.set_error(Some(e.to_string()));
             state.update_state(SessionState::Disconnected);
-            format!("Connection failed: {}", e)
+            Error::Upstream(format!("Connection failed: {}", e))
         })?;
 
-        println!("[vNAS] Connected to SignalR hub");
+        info!("[vNAS] Connected to SignalR hub");
         state.update_state(SessionState::JoiningSession);
 
-        // Start listening for events
+        // Start listening for events (restarted if it panics, ends with the connection)
         let event_tx = state.event_tx.clone();
         let status_lock = Arc::new(RwLock::new(state.status()));
         let app_handle = state.app_handle.read().clone();
 
-        let mut events = service.events();
-        tokio::spawn(async move {
-            while let Ok(event) = events.recv().await {
-                match event {
-                    VnasEvent::AircraftUpdate(aircraft_list) => {
-                        // Batch aircraft for WebSocket broadcast to remote browsers
-                        let mut ws_batch = Vec::with_capacity(aircraft_list.len());
-
-                        for dto in aircraft_list {
-                            let aircraft = VnasAircraft::from(&dto);
-                            let _ = event_tx.send(aircraft.clone());
-
-                            // Emit to frontend via Tauri event
-                            if let Some(ref app) = app_handle {
-                                let _ = app.emit("vnas-aircraft-update", &aircraft);
+        let mut connection_events = Some(service.events());
+        supervisor::spawn("vnas-listener", Restart::OnPanic, move || {
+            let event_tx = event_tx.clone();
+            let status_lock = status_lock.clone();
+            let app_handle = app_handle.clone();
+            // The first run takes the stream opened with the connection; restarts reopen it
+            let first_events = connection_events.take();
+            async move {
+                let mut events = match first_events {
+                    Some(events) => events,
+                    None => {
+                        let Some(app) = app_handle.as_ref() else {
+                            return;
+                        };
+                        let state = app.state::<VnasState>();
+                        let service = state.service.read().await;
+                        let Some(events) = service.as_ref().map(|service| service.events()) else {
+                            return;
+                        };
+                        events
+                    }
+                };
+                // Velocities over the last few 1 Hz updates
+                let mut history = PositionHistory::new(Duration::from_secs(5));
+                while let Ok(event) = events.recv().await {
+                    match event {
+                        VnasEvent::AircraftUpdate(aircraft_list) => {
+                            let mut latest = None;
+                            for dto in aircraft_list {
+                                let mut aircraft = VnasAircraft::from(&dto);
+                                let fix = PositionFix {
+                                    time_ms: aircraft.timestamp,
+                                    latitude: aircraft.lat,
+                                    longitude: aircraft.lon,
+                                    altitude_ft: aircraft.altitude_true / 0.3048,
+                                };
+                                let reported = Kinematics {
+                                    track: aircraft.true_ground_track,
+                                    ..Default::default()
+                                };
+                                let kinematics = history.update(&aircraft.callsign, fix, reported);
+                                aircraft.groundspeed_kt = kinematics.groundspeed_kt;
+                                aircraft.vertical_rate_fpm = kinematics.vertical_rate_fpm;
+
+                                latest = latest.max(Some(aircraft.timestamp));
+
+                                // Queued for the next batch to the frontend and remote browsers
+                                if let Some(ref app) = app_handle {
+                                    let point = TrailPoint {
+                                        time: fix.time_ms,
+                                        latitude: fix.latitude,
+                                        longitude: fix.longitude,
+                                        altitude_ft: fix.altitude_ft,
+                                    };
+                                    aircraft.trail_point = crate::trails::record(app, &aircraft.callsign, point);
+                                    crate::vnas_batch::push(app, aircraft.clone());
+                                }
+                                let _ = event_tx.send(aircraft);
+                            }
+                            if let Some(latest) = latest {
+                                history.prune(latest);
+                                if let Some(ref app) = app_handle {
+                                    crate::trails::prune(app, latest);
+                                }
                             }
-
-                            // Add to WebSocket batch
-                            ws_batch.push(crate::server::VnasAircraftBroadcast {
-                                callsign: aircraft.callsign,
-                                lat: aircraft.lat,
-                                lon: aircraft.lon,
-                                altitude: aircraft.altitude_true,
-                                heading: aircraft.true_heading,
-                                type_code: Some(aircraft.type_code),
-                                timestamp: aircraft.timestamp,
-                            });
                         }
-
-                        // Broadcast to WebSocket clients (remote browsers)
-                        crate::broadcast_vnas_to_websocket(ws_batch);
-                    }
-                    VnasEvent::AircraftDisconnected(callsign) => {
-                        println!("[vNAS] Aircraft disconnected: {}", callsign);
-                        if let Some(ref app) = app_handle {
-                            let _ = app.emit("vnas-aircraft-disconnected", &callsign);
+                        VnasEvent::AircraftDisconnected(callsign) => {
+                            debug!("[vNAS] Aircraft disconnected: {}", callsign);
+                            if let Some(ref app) = app_handle {
+                                let _ = app.emit("vnas-aircraft-disconnected", &callsign);
+                            }
                         }
-                    }
-                    VnasEvent::SessionStateChanged(new_state) => {
-                        let frontend_state: SessionState = new_state.into();
-                        status_lock.write().state = frontend_state;
-                        println!("[vNAS] Session state changed: {:?}", frontend_state);
-                        if let Some(ref app) = app_handle {
-                            let _ = app.emit("vnas-state-changed", &frontend_state);
+                        VnasEvent::SessionStateChanged(new_state) => {
+                            let frontend_state: SessionState = new_state.into();
+                            status_lock.write().state = frontend_state;
+                            info!("[vNAS] Session state changed: {:?}", frontend_state);
+                            if let Some(ref app) = app_handle {
+                                let _ = app.emit("vnas-state-changed", &frontend_state);
+                            }
                         }
-                    }
-                    VnasEvent::Error(error) => {
-                        println!("[vNAS] Error: {}", error);
-                        status_lock.write().error = Some(error.to_string());
-                        if let Some(ref app) = app_handle {
-                            let _ = app.emit("vnas-error", error.to_string());
+                        VnasEvent::Error(error) => {
+                            info!("[vNAS] Error: {}", error);
+                            status_lock.write().error = Some(error.to_string());
+                            if let Some(ref app) = app_handle {
+                                let _ = app.emit("vnas-error", error.to_string());
+                            }
                         }
                     }
                 }
@@ -444,11 +518,11 @@ mod real_impl {
     pub async fn vnas_subscribe(
         state: State<'_, VnasState>,
         facility_id: String,
-    ) -> Result<(), String> {
+    ) -> Result<(), Error> {
         let service_guard = state.service.read().await;
         let service = service_guard
             .as_ref()
-            .ok_or("Not connected - call vnas_connect first")?;
+            .ok_or_else(|| Error::conflict("Not connected - call vnas_connect first"))?;
 
         state.update_state(SessionState::Subscribing);
 
@@ -458,23 +532,23 @@ mod real_impl {
             .await
             .map_err(|e| {
                 state.set_error(Some(e.to_string()));
-                format!("Subscription failed: {}", e)
+                Error::Upstream(format!("Subscription failed: {}", e))
             })?;
 
         state.set_facility(Some(facility_id.clone()));
         state.update_state(SessionState::Connected);
 
-        println!("[vNAS] Subscribed to TowerCabAircraft for {}", facility_id);
+        info!("[vNAS] Subscribed to TowerCabAircraft for {}", facility_id);
 
         Ok(())
     }
 
     /// Disconnect from vNAS.
     #[tauri::command]
-    pub async fn vnas_disconnect(state: State<'_, VnasState>) -> Result<(), String> {
+    pub async fn vnas_disconnect(state: State<'_, VnasState>) -> Result<(), Error> {
         // Disconnect service if connected
         if let Some(service) = state.service.write().await.take() {
-            service.disconnect().await.map_err(|e| e.to_string())?;
+            service.disconnect().await.map_err(|e| Error::Upstream(e.to_string()))?;
         }
 
         // Reset status
@@ -484,7 +558,7 @@ mod real_impl {
         status.error = None;
         state.set_status(status);
 
-        println!("[vNAS] Disconnected");
+        info!("[vNAS] Disconnected");
         Ok(())
     }
 
@@ -510,7 +584,7 @@ mod real_impl {
     /// Call this in the Tauri setup closure.
     pub fn init_vnas_state(app: &AppHandle) {
         app.manage(VnasState::new());
-        println!("[vNAS] State initialized (real implementation)");
+        info!("[vNAS] State initialized (real implementation)");
     }
 }
 
@@ -566,14 +640,14 @@ mod stub_impl {
         _app: AppHandle,
         _state: State<'_, VnasState>,
         _environment: Environment,
-    ) -> Result<String, String> {
-        Err(UNAVAILABLE_MSG.to_string())
+    ) -> Result<String, Error> {
+        Err(Error::Internal(UNAVAILABLE_MSG.to_string()))
     }
 
     /// Complete the OAuth flow (stub)
     #[tauri::command]
-    pub async fn vnas_complete_auth(_state: State<'_, VnasState>) -> Result<(), String> {
-        Err(UNAVAILABLE_MSG.to_string())
+    pub async fn vnas_complete_auth(_state: State<'_, VnasState>) -> Result<(), Error> {
+        Err(Error::Internal(UNAVAILABLE_MSG.to_string()))
     }
 
     /// Handle OAuth callback from deep link (stub)
@@ -581,14 +655,14 @@ mod stub_impl {
     pub async fn vnas_handle_oauth_callback(
         _state: State<'_, VnasState>,
         _callback_url: String,
-    ) -> Result<(), String> {
-        Err(UNAVAILABLE_MSG.to_string())
+    ) -> Result<(), Error> {
+        Err(Error::Internal(UNAVAILABLE_MSG.to_string()))
     }
 
     /// Connect to vNAS (stub)
     #[tauri::command]
-    pub async fn vnas_connect(_state: State<'_, VnasState>) -> Result<(), String> {
-        Err(UNAVAILABLE_MSG.to_string())
+    pub async fn vnas_connect(_state: State<'_, VnasState>) -> Result<(), Error> {
+        Err(Error::Internal(UNAVAILABLE_MSG.to_string()))
     }
 
     /// Subscribe to updates (stub)
@@ -596,13 +670,13 @@ mod stub_impl {
     pub async fn vnas_subscribe(
         _state: State<'_, VnasState>,
         _facility_id: String,
-    ) -> Result<(), String> {
-        Err(UNAVAILABLE_MSG.to_string())
+    ) -> Result<(), Error> {
+        Err(Error::Internal(UNAVAILABLE_MSG.to_string()))
     }
 
     /// Disconnect from vNAS (stub)
     #[tauri::command]
-    pub async fn vnas_disconnect(_state: State<'_, VnasState>) -> Result<(), String> {
+    pub async fn vnas_disconnect(_state: State<'_, VnasState>) -> Result<(), Error> {
         Ok(()) // No-op, always succeeds
     }
 
@@ -621,7 +695,7 @@ mod stub_impl {
     /// Initialize vNAS state for Tauri app (stub)
     pub fn init_vnas_state(app: &AppHandle) {
         app.manage(VnasState::new());
-        println!("[vNAS] State initialized (stub - feature not enabled)");
+        info!("[vNAS] State initialized (stub - feature not enabled)");
     }
 }
 
@@ -0,0 +1,196 @@
+//! Batching and rate shaping of vNAS aircraft updates
+//!
+//! vNAS updates arrive per aircraft. Instead of emitting a Tauri event and a
+//! WebSocket broadcast for each, updates are collected per callsign (a newer
+//! update replaces an older one still waiting) and sent as one batch per tick:
+//! `vnas-aircraft-batch` to the app and one message to `/api/vnas/ws` clients.
+//! The tick rate is `server.broadcastRateHz` in global settings, so busy events
+//! don't flood the IPC layer or slow remote clients.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::debug_stats;
+use crate::declutter::ReferenceCache;
+use crate::geo;
+use crate::lod;
+use crate::server::VnasAircraftBroadcast;
+use crate::supervisor::{self, Restart};
+use crate::udp_output;
+use crate::vnas::VnasAircraft;
+use crate::GlobalServerSettings;
+
+/// Allowed broadcast rates (Hz)
+const MIN_RATE_HZ: f64 = 0.2;
+const MAX_RATE_HZ: f64 = 10.0;
+
+/// Updates waiting for the next tick, one per callsign in arrival order
+#[derive(Default)]
+pub struct Batch {
+    aircraft: Vec<VnasAircraft>,
+    index: HashMap<String, usize>,
+}
+
+impl Batch {
+    /// Add an update, replacing a waiting one for the same callsign. A trail
+    /// point from the replaced update is kept if the new one has none.
+    pub fn push(&mut self, mut aircraft: VnasAircraft) {
+        match self.index.get(&aircraft.callsign) {
+            Some(&i) => {
+                let waiting = &mut self.aircraft[i];
+                if aircraft.trail_point.is_none() {
+                    aircraft.trail_point = waiting.trail_point;
+                }
+                *waiting = aircraft;
+            }
+            None => {
+                self.index.insert(aircraft.callsign.clone(), self.aircraft.len());
+                self.aircraft.push(aircraft);
+            }
+        }
+    }
+
+    pub fn take(&mut self) -> Vec<VnasAircraft> {
+        self.index.clear();
+        std::mem::take(&mut self.aircraft)
+    }
+}
+
+impl From<&VnasAircraft> for VnasAircraftBroadcast {
+    fn from(aircraft: &VnasAircraft) -> Self {
+        Self {
+            callsign: aircraft.callsign.clone(),
+            lat: aircraft.lat,
+            lon: aircraft.lon,
+            altitude: aircraft.altitude_true,
+            heading: aircraft.true_heading,
+            type_code: Some(aircraft.type_code.clone()),
+            timestamp: aircraft.timestamp,
+            groundspeed_kt: aircraft.groundspeed_kt,
+            vertical_rate_fpm: aircraft.vertical_rate_fpm,
+            trail_point: aircraft.trail_point,
+            model_detail: None,
+        }
+    }
+}
+
+/// Pending updates and tick interval (managed by Tauri)
+pub struct VnasBatchState {
+    pending: Mutex<Batch>,
+    interval_ms: AtomicU64,
+    /// When the last update arrived from vNAS
+    last_update: Mutex<Option<Instant>>,
+}
+
+fn interval_ms(rate_hz: f64) -> u64 {
+    let rate_hz = if rate_hz.is_finite() { rate_hz } else { 2.0 };
+    (1000.0 / rate_hz.clamp(MIN_RATE_HZ, MAX_RATE_HZ)).round() as u64
+}
+
+/// Queue an aircraft update for the next batch
+#[allow(dead_code)] // Only called by the vnas and mock-traffic features
+pub fn push(app: &AppHandle, aircraft: VnasAircraft) {
+    let state = app.state::<VnasBatchState>();
+    state.pending.lock().push(aircraft);
+    *state.last_update.lock() = Some(Instant::now());
+}
+
+/// Time since the last update arrived from vNAS (None if none yet)
+pub fn last_update_age(app: &AppHandle) -> Option<Duration> {
+    app.try_state::<VnasBatchState>()?.last_update.lock().map(|at| at.elapsed())
+}
+
+/// Apply a changed broadcast rate
+pub fn apply_settings(app: &AppHandle, settings: &GlobalServerSettings) {
+    app.state::<VnasBatchState>()
+        .interval_ms
+        .store(interval_ms(settings.broadcast_rate_hz), Ordering::Relaxed);
+}
+
+/// Send waiting updates at the configured rate
+pub fn start_vnas_batch_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn("vnas-batch", Restart::Always, move || {
+        let app = app.clone();
+        async move {
+            let mut reference = ReferenceCache::default();
+            loop {
+                let interval = app.state::<VnasBatchState>().interval_ms.load(Ordering::Relaxed);
+                tokio::time::sleep(Duration::from_millis(interval)).await;
+                debug_stats::task_ran("vnas-batch", Duration::from_millis(interval), None);
+
+                let batch = app.state::<VnasBatchState>().pending.lock().take();
+                if batch.is_empty() {
+                    continue;
+                }
+                if let Some(r) = reference.get(&app).await {
+                    lod::observe(
+                        &app,
+                        batch.iter().map(|a| {
+                            (a.callsign.as_str(), geo::distance_and_bearing(r.latitude, r.longitude, a.lat, a.lon).0)
+                        }),
+                    );
+                }
+                let _ = app.emit("vnas-aircraft-batch", &batch);
+                crate::broadcast_vnas_to_websocket(batch.iter().map(VnasAircraftBroadcast::from).collect());
+                udp_output::send_vnas(&app, &batch);
+            }
+        }
+    });
+}
+
+/// Initialize vNAS batching state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_vnas_batching(app: &AppHandle) {
+    let rate_hz = crate::read_global_settings(app.clone())
+        .map(|s| s.server.broadcast_rate_hz)
+        .unwrap_or(2.0);
+    app.manage(VnasBatchState {
+        pending: Mutex::new(Batch::default()),
+        interval_ms: AtomicU64::new(interval_ms(rate_hz)),
+        last_update: Mutex::new(None),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trails::TrailPoint;
+    use crate::vnas::test_vnas_aircraft;
+
+    #[test]
+    fn coalesces_updates_per_callsign() {
+        let aircraft = |callsign: &str, timestamp: u64, trail: bool| VnasAircraft {
+            true_heading: 90.0,
+            trail_point: trail.then_some(TrailPoint {
+                time: timestamp,
+                latitude: 42.0,
+                longitude: -71.0,
+                altitude_ft: 3000.0,
+            }),
+            ..test_vnas_aircraft(callsign, timestamp)
+        };
+        let mut batch = Batch::default();
+        batch.push(aircraft("DAL1", 1_000, true));
+        batch.push(aircraft("UAL2", 1_000, false));
+        batch.push(aircraft("DAL1", 2_000, false));
+
+        let sent = batch.take();
+        assert_eq!(
+            sent.iter()
+                .map(|a| (a.callsign.as_str(), a.timestamp))
+                .collect::<Vec<_>>(),
+            vec![("DAL1", 2_000), ("UAL2", 1_000)]
+        );
+        assert_eq!(sent[0].trail_point.map(|p| p.time), Some(1_000));
+        assert!(batch.take().is_empty());
+
+        assert_eq!(interval_ms(2.0), 500);
+        assert_eq!(interval_ms(100.0), 100);
+        assert_eq!(interval_ms(0.0), 5_000);
+    }
+}
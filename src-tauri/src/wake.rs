@@ -0,0 +1,58 @@
+//! ICAO wake turbulence categories and separation minima
+//!
+//! Categories come from the aircraft type database (see `aircraft_types`).
+//! Types that are not listed are treated as Medium. Distance-based minima
+//! follow ICAO Doc 4444 for aircraft on final approach; pairs without a wake
+//! minimum get the minimum radar separation.
+
+use tauri::AppHandle;
+
+use crate::aircraft_types;
+
+/// Category for unknown types
+const DEFAULT_CATEGORY: char = 'M';
+
+/// Minimum radar separation on final (nm)
+pub const MIN_RADAR_SEPARATION_NM: f64 = 3.0;
+
+/// ICAO wake category of an aircraft type: 'J' (Super), 'H', 'M' or 'L'
+pub fn category(app: &AppHandle, aircraft_type: Option<&str>) -> char {
+    aircraft_type
+        .and_then(|t| aircraft_types::get(app, t))
+        .and_then(|t| t.wake_category)
+        .and_then(|c| c.chars().next())
+        .unwrap_or(DEFAULT_CATEGORY)
+}
+
+/// Wake turbulence separation for a follower behind a leader (nm), if any
+pub fn wake_separation_nm(leader: char, follower: char) -> Option<f64> {
+    match (leader, follower) {
+        ('J', 'H') => Some(6.0),
+        ('J', 'M') => Some(7.0),
+        ('J', 'L') => Some(8.0),
+        ('H', 'H') => Some(4.0),
+        ('H', 'M') => Some(5.0),
+        ('H', 'L') => Some(6.0),
+        ('M', 'L') => Some(5.0),
+        _ => None,
+    }
+}
+
+/// Required separation on final: the wake minimum or minimum radar separation
+pub fn required_separation_nm(leader: char, follower: char) -> f64 {
+    wake_separation_nm(leader, follower).unwrap_or(MIN_RADAR_SEPARATION_NM)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wake_minima() {
+        assert_eq!(required_separation_nm('H', 'L'), 6.0);
+        assert_eq!(required_separation_nm('J', 'M'), 7.0);
+        // Lighter leaders only need radar separation
+        assert_eq!(required_separation_nm('M', 'H'), MIN_RADAR_SEPARATION_NM);
+        assert_eq!(wake_separation_nm('M', 'M'), None);
+    }
+}
@@ -0,0 +1,273 @@
+//! Departure wake turbulence timers
+//!
+//! After every traffic poll, aircraft that went from on the ground to
+//! airborne near the airport are assigned to the runway they lifted off from.
+//! A departing Heavy starts a two minute timer on that runway, a Super
+//! (A380) a three minute one (ICAO Doc 4444 departure minima). A newer
+//! timer replaces the runway's current one. Timers are published as a whole
+//! list (Tauri event `wake-timers`, `/api/wake-timers/ws`) with their expiry
+//! time, so the tower display can count down locally.
+
+use std::collections::HashMap;
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::broadcast;
+use tracing::info;
+
+use crate::runways::{self, Runway};
+use crate::traffic::{Aircraft, TrafficSnapshot};
+use crate::wake;
+
+/// Departures further than this from the airport are not timed (nm)
+const DEPARTURE_RADIUS_NM: f64 = 5.0;
+
+/// Runway assignment tolerances at liftoff (nm)
+const RUNWAY_MAX_OFFSET_NM: f64 = 0.3;
+const RUNWAY_MAX_BEYOND_NM: f64 = 3.0;
+
+/// Wake timer behind a departing aircraft of a category, if any (seconds)
+fn timer_secs(category: char) -> Option<u32> {
+    match category {
+        'J' => Some(180),
+        'H' => Some(120),
+        _ => None,
+    }
+}
+
+/// Running wake timer on a runway
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WakeTimer {
+    /// Runway end departed from (e.g., "27R")
+    pub runway: String,
+    /// Departure the timer runs behind
+    pub callsign: String,
+    pub aircraft_type: Option<String>,
+    /// ICAO wake category (J or H)
+    pub wake_category: String,
+    /// Liftoff time (Unix ms)
+    pub started_at: u64,
+    /// When the next departure may roll (Unix ms)
+    pub expires_at: u64,
+    pub duration_secs: u32,
+}
+
+/// Running wake timers at the airport
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WakeTimers {
+    pub icao: Option<String>,
+    /// Time of the traffic picture (Unix ms)
+    pub updated_at: Option<u64>,
+    pub timers: Vec<WakeTimer>,
+}
+
+/// Wake timer state (managed by Tauri)
+pub struct WakeTimerState {
+    timers: RwLock<WakeTimers>,
+    /// On-ground flag per callsign at the previous poll
+    on_ground: RwLock<HashMap<String, bool>>,
+    /// Timer updates for WebSocket relay
+    pub events: broadcast::Sender<WakeTimers>,
+}
+
+impl WakeTimerState {
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(16);
+        Self {
+            timers: RwLock::new(WakeTimers::default()),
+            on_ground: RwLock::new(HashMap::new()),
+            events,
+        }
+    }
+}
+
+/// Timers after a poll: unexpired ones, replaced by timers behind new departures.
+/// `was_on_ground` holds the on-ground flags of the previous poll and
+/// `categories` gives the wake category of an aircraft.
+pub fn advance(
+    timers: &[WakeTimer],
+    aircraft: &[Aircraft],
+    was_on_ground: &HashMap<String, bool>,
+    runways: &[Runway],
+    categories: impl Fn(&Aircraft) -> char,
+    now: u64,
+) -> Vec<WakeTimer> {
+    let mut by_runway: HashMap<String, WakeTimer> = timers
+        .iter()
+        .filter(|t| t.expires_at > now)
+        .map(|t| (t.runway.clone(), t.clone()))
+        .collect();
+
+    let departures = aircraft.iter().filter(|a| {
+        !a.on_ground && a.distance_nm <= DEPARTURE_RADIUS_NM && was_on_ground.get(&a.callsign) == Some(&true)
+    });
+    for a in departures {
+        let category = categories(a);
+        let Some(duration_secs) = timer_secs(category) else {
+            continue;
+        };
+        let Some(runway) = runways::aligned_runway_end(
+            runways,
+            a.latitude,
+            a.longitude,
+            a.track.unwrap_or(a.heading),
+            RUNWAY_MAX_OFFSET_NM,
+            RUNWAY_MAX_BEYOND_NM,
+            RUNWAY_MAX_BEYOND_NM,
+        )
+        .map(|al| al.end.ident.clone()) else {
+            continue;
+        };
+        by_runway.insert(
+            runway.clone(),
+            WakeTimer {
+                runway,
+                callsign: a.callsign.clone(),
+                aircraft_type: a.aircraft_type.clone(),
+                wake_category: category.to_string(),
+                started_at: now,
+                expires_at: now + duration_secs as u64 * 1000,
+                duration_secs,
+            },
+        );
+    }
+
+    let mut timers: Vec<WakeTimer> = by_runway.into_values().collect();
+    timers.sort_by(|a, b| a.runway.cmp(&b.runway));
+    timers
+}
+
+fn publish(app: &AppHandle, timers: WakeTimers) {
+    let _ = app.emit("wake-timers", &timers);
+    let state = app.state::<WakeTimerState>();
+    let _ = state.events.send(timers.clone());
+    *state.timers.write() = timers;
+}
+
+/// Update the timers from a traffic poll
+pub fn update(app: &AppHandle, snapshot: &TrafficSnapshot, runways: &[Runway]) {
+    let now = snapshot.updated_at.unwrap_or_default();
+    let state = app.state::<WakeTimerState>();
+    let current = get_timers(app);
+    let previous = if current.icao == snapshot.icao {
+        current.timers.clone()
+    } else {
+        Vec::new()
+    };
+
+    let on_ground: HashMap<String, bool> = snapshot
+        .aircraft
+        .iter()
+        .map(|a| (a.callsign.clone(), a.on_ground))
+        .collect();
+    let was_on_ground = std::mem::replace(&mut *state.on_ground.write(), on_ground);
+    let timers = advance(
+        &previous,
+        &snapshot.aircraft,
+        &was_on_ground,
+        runways,
+        |a| wake::category(app, a.aircraft_type.as_deref()),
+        now,
+    );
+
+    for timer in timers.iter().filter(|t| t.started_at == now) {
+        info!(
+            "[WakeTimer] {} departed {} ({}): {} s",
+            timer.callsign, timer.runway, timer.wake_category, timer.duration_secs
+        );
+    }
+    if timers == current.timers && current.icao == snapshot.icao {
+        return;
+    }
+    publish(
+        app,
+        WakeTimers {
+            icao: snapshot.icao.clone(),
+            updated_at: snapshot.updated_at,
+            timers,
+        },
+    );
+}
+
+/// Drop all timers (no active airport or traffic)
+pub fn clear(app: &AppHandle) {
+    app.state::<WakeTimerState>().on_ground.write().clear();
+    let current = get_timers(app);
+    if current.icao.is_some() || !current.timers.is_empty() {
+        publish(app, WakeTimers::default());
+    }
+}
+
+/// Current wake timers
+pub fn get_timers(app: &AppHandle) -> WakeTimers {
+    app.state::<WakeTimerState>().timers.read().clone()
+}
+
+/// Initialize wake timer state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_wake_timers(app: &AppHandle) {
+    app.manage(WakeTimerState::new());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the running departure wake timers
+#[tauri::command]
+pub fn get_wake_timers(app: AppHandle) -> WakeTimers {
+    get_timers(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traffic::test_aircraft;
+
+    #[test]
+    fn starts_timers_behind_heavy_departures() {
+        let runway = runways::test_runway();
+        let aircraft = |callsign: &str, aircraft_type: &str| Aircraft {
+            // Just past the departure end of 36
+            latitude: 42.035,
+            altitude_ft: 400.0,
+            groundspeed_kt: 160.0,
+            track: Some(0.0),
+            vertical_rate_fpm: Some(2000.0),
+            aircraft_type: Some(aircraft_type.to_string()),
+            distance_nm: 2.0,
+            ..test_aircraft(callsign)
+        };
+        let categories = |a: &Aircraft| match a.aircraft_type.as_deref() {
+            Some("A388") => 'J',
+            Some("B744") => 'H',
+            _ => 'M',
+        };
+        let runways = [runway];
+        let was_on_ground: HashMap<String, bool> = [("HVY1", true), ("MED1", true), ("SUP1", false)]
+            .map(|(c, g)| (c.to_string(), g))
+            .into();
+
+        // The heavy and the medium lifted off; the super was already airborne
+        let traffic = vec![
+            aircraft("HVY1", "B744"),
+            aircraft("MED1", "A320"),
+            aircraft("SUP1", "A388"),
+        ];
+        let timers = advance(&[], &traffic, &was_on_ground, &runways, categories, 1_000);
+        assert_eq!(timers.len(), 1);
+        assert_eq!(timers[0].runway, "36");
+        assert_eq!(timers[0].callsign, "HVY1");
+        assert_eq!(timers[0].expires_at, 121_000);
+
+        // Timers run out
+        assert_eq!(
+            advance(&timers, &[], &HashMap::new(), &runways, categories, 60_000),
+            timers
+        );
+        assert!(advance(&timers, &[], &HashMap::new(), &runways, categories, 121_000).is_empty());
+    }
+}
@@ -0,0 +1,850 @@
+//! METAR weather subsystem
+//!
+//! Fetches METARs from aviationweather.gov, decodes the raw report (wind,
+//! visibility, weather, clouds, temperature, altimeter) into typed structs and
+//! caches them with a short TTL, so the desktop webview and remote browsers
+//! share one fetch per station.
+//!
+//! The frontend reports the active airport via `set_weather_airport`; a
+//! background task keeps its METAR (and TAF, see `taf`) fresh and emits
+//! `weather-changed` whenever a new observation arrives.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime};
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::{debug, warn};
+
+use crate::debug_stats;
+use crate::error::Error;
+use crate::http_client;
+use crate::scripts;
+use crate::supervisor::{self, Restart};
+use crate::taf;
+use crate::weather_alerts;
+use crate::weather_scene;
+
+const METAR_API_URL: &str = "https://aviationweather.gov/api/data/metar";
+
+/// How long a fetched METAR is served from cache
+const METAR_TTL: Duration = Duration::from_secs(120);
+
+/// How often the active airport's METAR is checked
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How far back the METAR API keeps observations
+const HISTORY_LIMIT_HOURS: u64 = 15 * 24;
+
+/// How far before a requested time to look for the METAR in effect
+/// (covers stations that only report every few hours)
+const HISTORY_LOOKBACK_HOURS: u64 = 3;
+
+/// Maximum number of historical ranges kept in memory
+const MAX_HISTORY_RANGES: usize = 64;
+
+const HOUR_MS: u64 = 60 * 60 * 1000;
+
+/// Wind group (speeds in knots)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Wind {
+    /// Direction the wind is from (degrees true), None for variable (VRB)
+    pub direction: Option<u16>,
+    pub speed_kt: u16,
+    pub gust_kt: Option<u16>,
+    /// Variable direction range (e.g., 240V300)
+    pub variable_from: Option<u16>,
+    pub variable_to: Option<u16>,
+}
+
+/// Cloud layer (base in feet AGL)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloudLayer {
+    /// FEW, SCT, BKN or OVC
+    pub cover: String,
+    /// Base in feet AGL (None if not reported, e.g., "BKN///")
+    pub base_ft: Option<u32>,
+    /// Convective cloud type: CB or TCU
+    pub cloud_type: Option<String>,
+}
+
+/// Present weather group (e.g., "+TSRA")
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeatherPhenomenon {
+    /// Raw group
+    pub code: String,
+    /// "light", "moderate", "heavy" or "vicinity"
+    pub intensity: String,
+    /// MI, PR, BC, DR, BL, SH, TS or FZ
+    pub descriptor: Option<String>,
+    /// Two-letter phenomena (RA, SN, BR, FG, ...)
+    pub phenomena: Vec<String>,
+}
+
+/// Altimeter setting in both units
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Altimeter {
+    pub inhg: f64,
+    pub hpa: f64,
+}
+
+/// Wind, visibility, weather and sky groups (shared by METARs and TAF periods)
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Conditions {
+    pub wind: Option<Wind>,
+    /// Prevailing visibility in statute miles (6.21, i.e. 10 km, for CAVOK/9999)
+    pub visibility_sm: Option<f64>,
+    /// Prevailing visibility in meters
+    pub visibility_m: Option<f64>,
+    pub cavok: bool,
+    pub weather: Vec<WeatherPhenomenon>,
+    pub clouds: Vec<CloudLayer>,
+    /// Vertical visibility in feet (obscured sky, "VV002")
+    pub vertical_visibility_ft: Option<u32>,
+}
+
+impl Conditions {
+    /// Lowest BKN/OVC layer or vertical visibility, in feet AGL
+    pub fn ceiling_ft(&self) -> Option<u32> {
+        self.clouds
+            .iter()
+            .filter(|c| matches!(c.cover.as_str(), "BKN" | "OVC"))
+            .filter_map(|c| c.base_ft)
+            .chain(self.vertical_visibility_ft)
+            .min()
+    }
+
+    fn set_visibility_sm(&mut self, sm: f64) {
+        self.visibility_sm = Some(sm);
+        self.visibility_m = Some(sm * STATUTE_MILE_M);
+    }
+
+    fn set_visibility_m(&mut self, meters: f64) {
+        self.visibility_m = Some(meters);
+        self.visibility_sm = Some((meters / STATUTE_MILE_M * 100.0).round() / 100.0);
+    }
+}
+
+/// Decoded METAR
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Metar {
+    pub icao: String,
+    pub raw: String,
+    /// Observation time (Unix ms), if known
+    pub observed_at: Option<u64>,
+    #[serde(flatten)]
+    pub conditions: Conditions,
+    pub temperature_c: Option<i32>,
+    pub dewpoint_c: Option<i32>,
+    pub altimeter: Option<Altimeter>,
+    /// Lowest BKN/OVC layer or vertical visibility, in feet AGL
+    pub ceiling_ft: Option<u32>,
+    /// VFR, MVFR, IFR or LIFR
+    pub flight_category: String,
+    /// Station position and elevation (from the API)
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    pub elevation_m: Option<f64>,
+}
+
+const STATUTE_MILE_M: f64 = 1609.344;
+
+const DESCRIPTORS: [&str; 8] = ["MI", "PR", "BC", "DR", "BL", "SH", "TS", "FZ"];
+
+const PHENOMENA: [&str; 22] = [
+    "DZ", "RA", "SN", "SG", "IC", "PL", "GR", "GS", "UP", "BR", "FG", "FU", "VA", "DU", "SA", "HZ", "PY", "PO", "SQ",
+    "FC", "SS", "DS",
+];
+
+fn all_digits(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Wind group: 27015G25KT, VRB03KT, 00000KT, 12008MPS
+fn parse_wind(token: &str) -> Option<Wind> {
+    let (body, factor) = if let Some(body) = token.strip_suffix("KT") {
+        (body, 1.0)
+    } else if let Some(body) = token.strip_suffix("MPS") {
+        (body, 1.943_844)
+    } else if let Some(body) = token.strip_suffix("KMH") {
+        (body, 1.0 / 1.852)
+    } else {
+        return None;
+    };
+
+    if body.len() < 5 || !body.is_char_boundary(3) {
+        return None;
+    }
+    let (dir, rest) = body.split_at(3);
+    let direction = match dir {
+        "VRB" => None,
+        d if all_digits(d) => Some(d.parse::<u16>().ok()?),
+        _ => return None,
+    };
+    let (speed, gust) = match rest.split_once('G') {
+        Some((speed, gust)) => (speed, Some(gust)),
+        None => (rest, None),
+    };
+    if !all_digits(speed) || gust.is_some_and(|g| !all_digits(g)) {
+        return None;
+    }
+
+    let convert = |s: &str| s.parse::<f64>().ok().map(|v| (v * factor).round() as u16);
+    Some(Wind {
+        direction,
+        speed_kt: convert(speed)?,
+        gust_kt: gust.and_then(convert),
+        variable_from: None,
+        variable_to: None,
+    })
+}
+
+/// Statute mile visibility: 10SM, 1/2SM, M1/4SM, P6SM (whole part may be a separate token)
+fn parse_sm_visibility(token: &str) -> Option<f64> {
+    let value = token.strip_suffix("SM")?;
+    let value = value.trim_start_matches(['M', 'P']);
+    match value.split_once('/') {
+        Some((num, den)) => {
+            let num: f64 = num.parse().ok()?;
+            let den: f64 = den.parse().ok()?;
+            (den > 0.0).then(|| num / den)
+        }
+        None => value.parse().ok(),
+    }
+}
+
+/// Present weather group: -RA, +TSRA, VCSH, FZFG, BR
+fn parse_weather(token: &str) -> Option<WeatherPhenomenon> {
+    let (intensity, rest) = if let Some(rest) = token.strip_prefix('-') {
+        ("light", rest)
+    } else if let Some(rest) = token.strip_prefix('+') {
+        ("heavy", rest)
+    } else if let Some(rest) = token.strip_prefix("VC") {
+        ("vicinity", rest)
+    } else {
+        ("moderate", token)
+    };
+
+    if rest.is_empty() || rest.len() % 2 != 0 || !rest.is_ascii() {
+        return None;
+    }
+
+    let mut descriptor = None;
+    let mut phenomena = Vec::new();
+    for (i, chunk) in rest.as_bytes().chunks(2).enumerate() {
+        let code = std::str::from_utf8(chunk).ok()?;
+        if i == 0 && DESCRIPTORS.contains(&code) {
+            descriptor = Some(code.to_string());
+        } else if PHENOMENA.contains(&code) {
+            phenomena.push(code.to_string());
+        } else {
+            return None;
+        }
+    }
+
+    // A descriptor alone is only valid for thunderstorms and showers ("TS", "VCSH")
+    if phenomena.is_empty() && !matches!(descriptor.as_deref(), Some("TS") | Some("SH")) {
+        return None;
+    }
+
+    Some(WeatherPhenomenon {
+        code: token.to_string(),
+        intensity: intensity.to_string(),
+        descriptor,
+        phenomena,
+    })
+}
+
+/// Cloud group: BKN015, OVC008CB, SCT030TCU, BKN///
+fn parse_cloud(token: &str) -> Option<CloudLayer> {
+    let cover = ["FEW", "SCT", "BKN", "OVC"]
+        .into_iter()
+        .find(|c| token.starts_with(c))?;
+    let rest = &token[3..];
+    if rest.len() < 3 || !rest.is_char_boundary(3) {
+        return None;
+    }
+    let (base, cloud_type) = rest.split_at(3);
+    let base_ft = match base {
+        "///" => None,
+        b if all_digits(b) => Some(b.parse::<u32>().ok()? * 100),
+        _ => return None,
+    };
+    let cloud_type = match cloud_type {
+        "" | "///" => None,
+        "CB" | "TCU" => Some(cloud_type.to_string()),
+        _ => return None,
+    };
+
+    Some(CloudLayer {
+        cover: cover.to_string(),
+        base_ft,
+        cloud_type,
+    })
+}
+
+/// Temperature group: 18/12, M02/M05, 05/
+fn parse_temperature(token: &str) -> Option<(i32, Option<i32>)> {
+    let parse = |s: &str| -> Option<i32> {
+        let (negative, digits) = match s.strip_prefix('M') {
+            Some(digits) => (true, digits),
+            None => (false, s),
+        };
+        if digits.len() != 2 || !all_digits(digits) {
+            return None;
+        }
+        let value: i32 = digits.parse().ok()?;
+        Some(if negative { -value } else { value })
+    };
+
+    let (temp, dew) = token.split_once('/')?;
+    let temperature = parse(temp)?;
+    let dewpoint = if dew.is_empty() || dew == "//" {
+        None
+    } else {
+        Some(parse(dew)?)
+    };
+    Some((temperature, dewpoint))
+}
+
+/// Altimeter group: A2992 (inHg) or Q1013 (hPa)
+fn parse_altimeter(token: &str) -> Option<Altimeter> {
+    if token.len() != 5 || !token.is_ascii() || !all_digits(&token[1..]) {
+        return None;
+    }
+    let value: f64 = token[1..].parse().ok()?;
+    match &token[..1] {
+        "A" => Some(Altimeter {
+            inhg: value / 100.0,
+            hpa: (value / 100.0 * 33.8639).round(),
+        }),
+        "Q" => Some(Altimeter {
+            inhg: (value / 33.8639 * 100.0).round() / 100.0,
+            hpa: value,
+        }),
+        _ => None,
+    }
+}
+
+/// FAA flight category from ceiling (ft) and visibility (SM)
+pub fn flight_category(ceiling_ft: Option<u32>, visibility_sm: Option<f64>) -> &'static str {
+    let ceiling = ceiling_ft.unwrap_or(u32::MAX);
+    let visibility = visibility_sm.unwrap_or(f64::MAX);
+
+    if ceiling < 500 || visibility < 1.0 {
+        "LIFR"
+    } else if ceiling < 1000 || visibility < 3.0 {
+        "IFR"
+    } else if ceiling <= 3000 || visibility <= 5.0 {
+        "MVFR"
+    } else {
+        "VFR"
+    }
+}
+
+/// Whether a token is a DDHHMMZ time group
+pub(crate) fn is_time_group(token: &str) -> bool {
+    token.len() == 7 && token.ends_with('Z') && all_digits(&token[..6])
+}
+
+/// Parse a wind, visibility, weather or sky group starting at `tokens[i]` into
+/// `conditions`. Returns the number of tokens consumed (0 if it isn't one).
+pub(crate) fn parse_condition_group(conditions: &mut Conditions, tokens: &[&str], i: usize) -> usize {
+    let token = tokens[i];
+    if !token.is_ascii() {
+        return 0;
+    }
+
+    if conditions.wind.is_none() {
+        if let Some(wind) = parse_wind(token) {
+            conditions.wind = Some(wind);
+            return 1;
+        }
+    }
+    // Variable wind direction range: 240V300
+    if token.len() == 7 && token.as_bytes()[3] == b'V' && all_digits(&token[..3]) && all_digits(&token[4..]) {
+        if let Some(wind) = conditions.wind.as_mut() {
+            wind.variable_from = token[..3].parse().ok();
+            wind.variable_to = token[4..].parse().ok();
+        }
+        return 1;
+    }
+
+    if token == "CAVOK" {
+        conditions.cavok = true;
+        conditions.set_visibility_m(10_000.0);
+        return 1;
+    }
+    if conditions.visibility_sm.is_none() {
+        // Whole miles followed by a fraction: "1 1/2SM"
+        if all_digits(token) && token.len() == 1 {
+            if let Some(fraction) = tokens.get(i + 1).and_then(|t| parse_sm_visibility(t)) {
+                conditions.set_visibility_sm(token.parse::<f64>().unwrap_or(0.0) + fraction);
+                return 2;
+            }
+        }
+        if let Some(sm) = parse_sm_visibility(token) {
+            conditions.set_visibility_sm(sm);
+            return 1;
+        }
+        // Meters, optionally with NDV or a direction: 9999, 0800, 4000NE
+        if token.len() >= 4
+            && token.is_ascii()
+            && all_digits(&token[..4])
+            && matches!(
+                &token[4..],
+                "" | "NDV" | "N" | "NE" | "E" | "SE" | "S" | "SW" | "W" | "NW"
+            )
+        {
+            let meters: f64 = token[..4].parse().unwrap_or(0.0);
+            conditions.set_visibility_m(if meters >= 9999.0 { 10_000.0 } else { meters });
+            return 1;
+        }
+    }
+
+    if let Some(vv) = token.strip_prefix("VV") {
+        if all_digits(vv) {
+            conditions.vertical_visibility_ft = vv.parse::<u32>().ok().map(|v| v * 100);
+            return 1;
+        }
+    }
+    if let Some(cloud) = parse_cloud(token) {
+        conditions.clouds.push(cloud);
+        return 1;
+    }
+    if let Some(weather) = parse_weather(token) {
+        conditions.weather.push(weather);
+        return 1;
+    }
+
+    0
+}
+
+/// Decode a raw METAR (the report body; remarks and trend groups are ignored)
+pub fn decode_metar(raw: &str) -> Metar {
+    let tokens: Vec<&str> = raw.split_whitespace().collect();
+    let mut metar = Metar {
+        icao: String::new(),
+        raw: raw.trim().to_string(),
+        observed_at: None,
+        conditions: Conditions::default(),
+        temperature_c: None,
+        dewpoint_c: None,
+        altimeter: None,
+        ceiling_ft: None,
+        flight_category: String::new(),
+        latitude: None,
+        longitude: None,
+        elevation_m: None,
+    };
+
+    let mut i = 0;
+    while i < tokens.len() && matches!(tokens[i], "METAR" | "SPECI") {
+        i += 1;
+    }
+    if let Some(station) = tokens.get(i) {
+        metar.icao = station.to_string();
+        i += 1;
+    }
+
+    while i < tokens.len() {
+        let token = tokens[i];
+        if matches!(token, "RMK" | "TEMPO" | "BECMG" | "NOSIG") {
+            break;
+        }
+        if is_time_group(token) {
+            i += 1;
+            continue;
+        }
+
+        let consumed = parse_condition_group(&mut metar.conditions, &tokens, i);
+        if consumed > 0 {
+            i += consumed;
+            continue;
+        }
+        i += 1;
+
+        if !token.is_ascii() {
+            continue;
+        }
+        if let Some((temperature, dewpoint)) = parse_temperature(token) {
+            metar.temperature_c = Some(temperature);
+            metar.dewpoint_c = dewpoint;
+        } else if let Some(altimeter) = parse_altimeter(token) {
+            metar.altimeter.get_or_insert(altimeter);
+        }
+    }
+
+    metar.ceiling_ft = metar.conditions.ceiling_ft();
+    metar.flight_category = flight_category(metar.ceiling_ft, metar.conditions.visibility_sm).to_string();
+    metar
+}
+
+/// METAR as returned by the aviationweather.gov JSON API (only the fields we use)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiMetar {
+    icao_id: Option<String>,
+    raw_ob: Option<String>,
+    /// Unix seconds
+    obs_time: Option<i64>,
+    lat: Option<f64>,
+    lon: Option<f64>,
+    /// Meters
+    elev: Option<f64>,
+}
+
+/// Cached fetch result (None = station has no current METAR)
+struct CachedMetar {
+    metar: Option<Metar>,
+    fetched_at: Instant,
+}
+
+/// Weather state managed by Tauri
+pub struct WeatherState {
+    metars: RwLock<HashMap<String, CachedMetar>>,
+    /// Archived METARs by (station, hour-aligned from, to), oldest first
+    history: RwLock<HashMap<(String, u64, u64), Vec<Metar>>>,
+    /// Airport whose METAR is kept fresh in the background
+    active_airport: RwLock<Option<String>>,
+}
+
+impl WeatherState {
+    pub fn new() -> Self {
+        Self {
+            metars: RwLock::new(HashMap::new()),
+            history: RwLock::new(HashMap::new()),
+            active_airport: RwLock::new(None),
+        }
+    }
+}
+
+impl Default for WeatherState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Validate and normalize an ICAO station identifier
+pub fn normalize_icao(icao: &str) -> Result<String, String> {
+    let icao = icao.trim().to_uppercase();
+    if (3..=4).contains(&icao.len()) && icao.chars().all(|c| c.is_ascii_alphanumeric()) {
+        Ok(icao)
+    } else {
+        Err(format!("Invalid ICAO code: {}", icao))
+    }
+}
+
+/// Fetch and decode the latest METAR for a station
+async fn fetch_metar(icao: &str) -> Result<Option<Metar>, Error> {
+    let url = format!("{}?ids={}&format=json", METAR_API_URL, icao);
+    Ok(fetch_reports(&url).await?.into_iter().next())
+}
+
+/// Fetch and decode the METARs returned by an API query
+async fn fetch_reports(url: &str) -> Result<Vec<Metar>, Error> {
+    let response = http_client::client()
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to fetch METAR: {}", e)))?;
+
+    // The API answers 204 No Content for stations without a current report
+    if response.status() == reqwest::StatusCode::NO_CONTENT {
+        return Ok(Vec::new());
+    }
+    if !response.status().is_success() {
+        return Err(Error::Upstream(format!("HTTP error: {}", response.status())));
+    }
+
+    let reports: Vec<ApiMetar> = response
+        .json()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to parse METAR response: {}", e)))?;
+
+    Ok(reports
+        .into_iter()
+        .filter_map(|report| {
+            let raw = report.raw_ob.filter(|r| !r.trim().is_empty())?;
+            let mut metar = decode_metar(&raw);
+            if let Some(icao_id) = report.icao_id {
+                metar.icao = icao_id;
+            }
+            metar.observed_at = report.obs_time.map(|t| t.max(0) as u64 * 1000);
+            metar.latitude = report.lat;
+            metar.longitude = report.lon;
+            metar.elevation_m = report.elev;
+            Some(metar)
+        })
+        .collect())
+}
+
+/// Get the METAR for a station, from cache if fresh (or `force` is false and the fetch fails).
+/// Emits `weather-changed` and `weather-scene-changed` when a new observation arrives.
+pub async fn get_metar(app: &AppHandle, icao: &str, force: bool) -> Result<Option<Metar>, Error> {
+    let icao = normalize_icao(icao).map_err(Error::InvalidInput)?;
+    let weather = app.state::<WeatherState>();
+
+    let previous = {
+        let metars = weather.metars.read();
+        match metars.get(&icao) {
+            Some(cached) if !force && cached.fetched_at.elapsed() < METAR_TTL => return Ok(cached.metar.clone()),
+            Some(cached) => Some(cached.metar.clone()),
+            None => None,
+        }
+    };
+
+    let metar = match fetch_metar(&icao).await {
+        Ok(metar) => metar,
+        Err(e) => {
+            warn!("[Weather] {}: {}", icao, e);
+            // Serve the stale report rather than nothing
+            return match previous {
+                Some(stale) => Ok(stale),
+                None => Err(e),
+            };
+        }
+    };
+
+    weather.metars.write().insert(
+        icao.clone(),
+        CachedMetar {
+            metar: metar.clone(),
+            fetched_at: Instant::now(),
+        },
+    );
+
+    let previous_raw = previous.flatten().map(|m| m.raw);
+    if let Some(ref current) = metar {
+        if previous_raw.as_deref() != Some(current.raw.as_str()) {
+            debug!("[Weather] {}", current.raw);
+            let _ = app.emit("weather-changed", current);
+            let _ = app.emit("weather-scene-changed", weather_scene::scene_from_metar(current));
+            weather_alerts::check_metar(app, current);
+            scripts::dispatch(app, "weather", current);
+        }
+    }
+
+    Ok(metar)
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Get archived METARs for a station between two times (Unix ms), oldest first.
+/// Includes the report in effect at `from`. Only the last 15 days are available.
+pub async fn get_metar_history(app: &AppHandle, icao: &str, from: u64, to: u64) -> Result<Vec<Metar>, Error> {
+    let icao = normalize_icao(icao).map_err(Error::InvalidInput)?;
+    if from > to {
+        return Err(Error::invalid_input("Invalid time range"));
+    }
+
+    // Align to whole hours so scrubbing through a replay hits the cache
+    let start = (from / HOUR_MS).saturating_sub(HISTORY_LOOKBACK_HOURS) * HOUR_MS;
+    let end = to.div_ceil(HOUR_MS) * HOUR_MS;
+    let now = now_ms();
+    if end + HISTORY_LIMIT_HOURS * HOUR_MS < now {
+        return Err(Error::InvalidInput(format!(
+            "METAR archive only covers the last {} days",
+            HISTORY_LIMIT_HOURS / 24
+        )));
+    }
+
+    let weather = app.state::<WeatherState>();
+    let key = (icao.clone(), start, end);
+    if let Some(cached) = weather.history.read().get(&key) {
+        return Ok(filter_history(cached, from, to));
+    }
+
+    let hours = ((end - start) / HOUR_MS).clamp(1, HISTORY_LIMIT_HOURS);
+    let date = chrono::DateTime::from_timestamp_millis(end.min(now) as i64)
+        .ok_or_else(|| Error::invalid_input("Invalid time range"))?
+        .format("%Y-%m-%dT%H:%M:%SZ");
+    let url = format!(
+        "{}?ids={}&format=json&hours={}&date={}",
+        METAR_API_URL, icao, hours, date
+    );
+    let mut reports = fetch_reports(&url).await?;
+    reports.sort_by_key(|m| m.observed_at);
+    reports.dedup_by(|a, b| a.raw == b.raw);
+
+    // Ranges that ended over an hour ago won't gain new reports
+    if end + HOUR_MS < now {
+        let mut history = weather.history.write();
+        if history.len() >= MAX_HISTORY_RANGES {
+            history.clear();
+        }
+        history.insert(key, reports.clone());
+    }
+
+    Ok(filter_history(&reports, from, to))
+}
+
+/// Reports observed between `from` and `to`, plus the one in effect at `from`
+fn filter_history(reports: &[Metar], from: u64, to: u64) -> Vec<Metar> {
+    let in_effect = reports.iter().rposition(|m| m.observed_at.is_some_and(|t| t <= from));
+    reports
+        .iter()
+        .enumerate()
+        .filter(|(i, m)| Some(*i) == in_effect || m.observed_at.is_some_and(|t| t > from && t <= to))
+        .map(|(_, m)| m.clone())
+        .collect()
+}
+
+/// Get the METAR that was in effect at a time (Unix ms)
+pub async fn get_metar_at(app: &AppHandle, icao: &str, at: u64) -> Result<Option<Metar>, Error> {
+    Ok(get_metar_history(app, icao, at, at).await?.pop())
+}
+
+/// Last METAR fetched for a station, without fetching (None if never fetched or no report)
+pub fn cached_metar(app: &AppHandle, icao: &str) -> Option<Metar> {
+    let icao = normalize_icao(icao).ok()?;
+    app.state::<WeatherState>().metars.read().get(&icao)?.metar.clone()
+}
+
+/// Airport whose weather is kept fresh (the one the user is viewing), if any
+pub fn active_airport(app: &AppHandle) -> Option<String> {
+    app.state::<WeatherState>().active_airport.read().clone()
+}
+
+/// Keep the active airport's METAR fresh (emits `weather-changed` on new observations)
+pub fn start_weather_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn("weather", Restart::Always, move || {
+        let app = app.clone();
+        async move {
+            loop {
+                let active = active_airport(&app);
+                if let Some(icao) = active {
+                    let _ = get_metar(&app, &icao, false).await;
+                    let _ = taf::get_taf(&app, &icao, false).await;
+                }
+                debug_stats::task_ran("weather", POLL_INTERVAL, None);
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    });
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get the decoded METAR for a station (None if it has no current report)
+#[tauri::command]
+pub async fn get_station_metar(app: AppHandle, icao: String, force: Option<bool>) -> Result<Option<Metar>, Error> {
+    get_metar(&app, &icao, force.unwrap_or(false)).await
+}
+
+/// Get archived METARs for a station between two times (Unix ms), e.g., for a replay session
+#[tauri::command]
+pub async fn get_station_metar_history(
+    app: AppHandle,
+    icao: String,
+    from: u64,
+    to: u64,
+) -> Result<Vec<Metar>, Error> {
+    get_metar_history(&app, &icao, from, to).await
+}
+
+/// Set the airport whose METAR is kept fresh in the background (None to stop)
+#[tauri::command]
+pub async fn set_weather_airport(app: AppHandle, icao: Option<String>) -> Result<Option<Metar>, Error> {
+    let icao = icao.as_deref().map(normalize_icao).transpose().map_err(Error::InvalidInput)?;
+    *app.state::<WeatherState>().active_airport.write() = icao.clone();
+
+    match icao {
+        Some(icao) => get_metar(&app, &icao, false).await,
+        None => Ok(None),
+    }
+}
+
+/// Initialize weather state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_weather(app: &AppHandle) {
+    app.manage(WeatherState::new());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_us_metar() {
+        let metar =
+            decode_metar("KBOS 221854Z 27015G25KT 240V300 1 1/2SM -RA BR BKN008 OVC015 18/12 A2992 RMK AO2 SLP132");
+
+        assert_eq!(metar.icao, "KBOS");
+        let wind = metar.conditions.wind.unwrap();
+        assert_eq!((wind.direction, wind.speed_kt, wind.gust_kt), (Some(270), 15, Some(25)));
+        assert_eq!((wind.variable_from, wind.variable_to), (Some(240), Some(300)));
+        assert_eq!(metar.conditions.visibility_sm, Some(1.5));
+        assert_eq!(
+            metar
+                .conditions
+                .weather
+                .iter()
+                .map(|w| w.code.as_str())
+                .collect::<Vec<_>>(),
+            ["-RA", "BR"]
+        );
+        assert_eq!(metar.conditions.weather[0].intensity, "light");
+        assert_eq!(metar.conditions.clouds.len(), 2);
+        assert_eq!(metar.ceiling_ft, Some(800));
+        assert_eq!((metar.temperature_c, metar.dewpoint_c), (Some(18), Some(12)));
+        assert_eq!(metar.altimeter.unwrap().inhg, 29.92);
+        assert_eq!(metar.flight_category, "IFR");
+    }
+
+    #[test]
+    fn decodes_icao_metar() {
+        let metar = decode_metar("METAR EGLL 221850Z AUTO 12008MPS 9999 +TSRA FEW020CB SCT035TCU M02/M05 Q1013 NOSIG");
+
+        let wind = metar.conditions.wind.unwrap();
+        assert_eq!((wind.direction, wind.speed_kt), (Some(120), 16));
+        assert_eq!(metar.conditions.visibility_m, Some(10_000.0));
+        assert_eq!(metar.conditions.weather[0].descriptor.as_deref(), Some("TS"));
+        assert_eq!(metar.conditions.weather[0].phenomena, ["RA"]);
+        assert_eq!(metar.conditions.clouds[0].cloud_type.as_deref(), Some("CB"));
+        assert_eq!(metar.ceiling_ft, None);
+        assert_eq!((metar.temperature_c, metar.dewpoint_c), (Some(-2), Some(-5)));
+        assert_eq!(metar.altimeter.unwrap().hpa, 1013.0);
+        assert_eq!(metar.flight_category, "VFR");
+    }
+
+    #[test]
+    fn decodes_cavok_variable_wind_and_obscuration() {
+        let cavok = decode_metar("LFPG 221830Z VRB02KT CAVOK 21/09 Q1021");
+        assert_eq!(cavok.conditions.wind.unwrap().direction, None);
+        assert!(cavok.conditions.cavok);
+        assert_eq!(cavok.flight_category, "VFR");
+
+        let fog = decode_metar("KSFO 221856Z 00000KT 1/4SM FG VV002 12/12 A3001");
+        assert_eq!(fog.conditions.visibility_sm, Some(0.25));
+        assert_eq!(fog.ceiling_ft, Some(200));
+        assert_eq!(fog.flight_category, "LIFR");
+    }
+
+    #[test]
+    fn ignores_non_ascii_groups() {
+        assert!(parse_altimeter("\u{e9}A12").is_none());
+
+        let mut conditions = Conditions::default();
+        assert_eq!(parse_condition_group(&mut conditions, &["999\u{e9}"], 0), 0);
+        assert_eq!(conditions, Conditions::default());
+
+        let metar = decode_metar("EDDF 221850Z 24008KT 999\u{e9} Q\u{e9}012 12/08 Q1015");
+        assert_eq!(metar.altimeter.unwrap().hpa, 1015.0);
+    }
+}
@@ -0,0 +1,367 @@
+//! Weather and ATIS change notifications
+//!
+//! Watches the active airport for changes a tower controller acts on: the
+//! wind shifting or strengthening beyond a threshold, the altimeter setting
+//! changing (new METAR), a new ATIS letter being published on VATSIM
+//! (traffic poll), and the VATSIM ATIS runways no longer matching the
+//! real-world D-ATIS (see datis.rs). Each change is emitted as a `weather-alert` event and,
+//! when enabled in the global settings, shown as a desktop notification, with
+//! a toggle per kind of change.
+//!
+//! The first report seen for an airport only sets the baseline.
+
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+use tracing::info;
+
+use crate::notifications;
+use crate::scripts;
+use crate::supervisor::{self, Restart};
+use crate::traffic::{self, OnlineController};
+use crate::weather::{self, Metar};
+
+/// Alert kinds
+pub const KIND_WIND_SHIFT: &str = "windShift";
+pub const KIND_ALTIMETER: &str = "altimeter";
+pub const KIND_ATIS: &str = "atis";
+pub const KIND_DATIS_MISMATCH: &str = "datisMismatch";
+
+/// Weather notification settings in the global settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WeatherAlertSettings {
+    /// Show desktop notifications (default: false); `weather-alert` events are always sent
+    pub desktop: bool,
+    /// Notify when the wind shifts or strengthens beyond the thresholds (default: true)
+    pub wind_shift: bool,
+    /// Direction change that counts as a shift in degrees (default: 30)
+    pub wind_shift_threshold_deg: u16,
+    /// Speed change that counts as a shift in knots (default: 10)
+    pub wind_speed_threshold_kt: u16,
+    /// Notify when the altimeter setting changes (default: true)
+    pub altimeter: bool,
+    /// Notify when a new ATIS letter is published (default: true)
+    pub atis: bool,
+    /// Compare the VATSIM ATIS runways with the real-world D-ATIS and notify on a mismatch (default: true)
+    pub datis_mismatch: bool,
+}
+
+impl Default for WeatherAlertSettings {
+    fn default() -> Self {
+        Self {
+            desktop: false,
+            wind_shift: true,
+            wind_shift_threshold_deg: 30,
+            wind_speed_threshold_kt: 10,
+            altimeter: true,
+            atis: true,
+            datis_mismatch: true,
+        }
+    }
+}
+
+/// Weather or ATIS change at the active airport
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeatherAlert {
+    pub icao: String,
+    /// "windShift", "altimeter", "atis" or "datisMismatch"
+    pub kind: String,
+    pub title: String,
+    pub message: String,
+    /// Unix ms
+    pub time: u64,
+}
+
+/// Weather alert state (managed by Tauri)
+pub struct WeatherAlertState {
+    /// Last METAR seen for the active airport
+    metar: RwLock<Option<Metar>>,
+    /// Airport and ATIS letters by station callsign at the last traffic poll
+    atis: RwLock<Option<(String, HashMap<String, String>)>>,
+}
+
+impl WeatherAlertState {
+    pub fn new() -> Self {
+        Self {
+            metar: RwLock::new(None),
+            atis: RwLock::new(None),
+        }
+    }
+}
+
+impl Default for WeatherAlertState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Difference between two directions in degrees (0-180)
+fn angle_between(a: u16, b: u16) -> u16 {
+    let diff = (i32::from(a) - i32::from(b)).rem_euclid(360) as u16;
+    diff.min(360 - diff)
+}
+
+fn wind_text(metar: &Metar) -> String {
+    match &metar.conditions.wind {
+        Some(wind) => {
+            let direction = wind
+                .direction
+                .map(|d| format!("{:03}", d))
+                .unwrap_or_else(|| "VRB".to_string());
+            let gust = wind.gust_kt.map(|g| format!("G{}", g)).unwrap_or_default();
+            format!("{}{:02}{}KT", direction, wind.speed_kt, gust)
+        }
+        None => "calm".to_string(),
+    }
+}
+
+/// Changes between two METARs of the same station worth notifying about:
+/// (kind, title, message)
+pub fn metar_changes(previous: &Metar, current: &Metar, settings: &WeatherAlertSettings) -> Vec<(&'static str, String, String)> {
+    let mut changes = Vec::new();
+
+    if let (Some(before), Some(after)) = (&previous.conditions.wind, &current.conditions.wind) {
+        let shifted = match (before.direction, after.direction) {
+            (Some(from), Some(to)) => angle_between(from, to) >= settings.wind_shift_threshold_deg,
+            // Variable to steady (or back) only matters with some wind
+            (from, to) => from != to && before.speed_kt.max(after.speed_kt) >= 5,
+        };
+        let strengthened = before.speed_kt.abs_diff(after.speed_kt) >= settings.wind_speed_threshold_kt;
+        if settings.wind_shift && (shifted || strengthened) {
+            changes.push((
+                KIND_WIND_SHIFT,
+                format!("{} wind {}", current.icao, wind_text(current)),
+                format!("Wind changed from {} to {}", wind_text(previous), wind_text(current)),
+            ));
+        }
+    }
+
+    if let (Some(before), Some(after)) = (&previous.altimeter, &current.altimeter) {
+        if settings.altimeter && (before.inhg - after.inhg).abs() >= 0.005 {
+            changes.push((
+                KIND_ALTIMETER,
+                format!("{} altimeter {:.2}", current.icao, after.inhg),
+                format!(
+                    "Altimeter changed from {:.2} inHg ({:.0} hPa) to {:.2} inHg ({:.0} hPa)",
+                    before.inhg, before.hpa, after.inhg, after.hpa
+                ),
+            ));
+        }
+    }
+
+    changes
+}
+
+/// ATIS letters of an airport's ATIS stations, by callsign ("KBOS_ATIS", "KBOS_D_ATIS")
+pub fn atis_letters(icao: &str, stations: &[OnlineController]) -> HashMap<String, String> {
+    stations
+        .iter()
+        .filter(|s| {
+            let callsign = s.callsign.to_uppercase();
+            callsign.ends_with("_ATIS") && callsign.split('_').next() == Some(icao)
+        })
+        .filter_map(|s| {
+            let letter = s.atis_code.as_deref()?.trim().to_uppercase();
+            (!letter.is_empty()).then(|| (s.callsign.to_uppercase(), letter))
+        })
+        .collect()
+}
+
+pub fn settings(app: &AppHandle) -> WeatherAlertSettings {
+    crate::read_global_settings(app.clone())
+        .map(|s| s.weather_alerts)
+        .unwrap_or_default()
+}
+
+pub fn raise(app: &AppHandle, settings: &WeatherAlertSettings, icao: &str, kind: &str, title: String, message: String) {
+    info!("[WeatherAlerts] {}: {}", title, message);
+    let alert = WeatherAlert {
+        icao: icao.to_string(),
+        kind: kind.to_string(),
+        title,
+        message,
+        time: now_ms(),
+    };
+    let _ = app.emit("weather-alert", &alert);
+    scripts::alert(app, "weather", &alert);
+    if settings.desktop {
+        notifications::show(app, &alert.title, &alert.message);
+    }
+}
+
+/// Check a new METAR against the last one (only for the active airport)
+pub fn check_metar(app: &AppHandle, metar: &Metar) {
+    if weather::active_airport(app).as_deref() != Some(metar.icao.as_str()) {
+        return;
+    }
+    let previous = app.state::<WeatherAlertState>().metar.write().replace(metar.clone());
+    let Some(previous) = previous.filter(|p| p.icao == metar.icao && p.raw != metar.raw) else {
+        return;
+    };
+    let settings = settings(app);
+    for (kind, title, message) in metar_changes(&previous, metar, &settings) {
+        raise(app, &settings, &metar.icao, kind, title, message);
+    }
+}
+
+/// Check the airport's ATIS letters from a traffic poll
+pub fn check_atis(app: &AppHandle, icao: &str, stations: &[OnlineController]) {
+    let letters = atis_letters(icao, stations);
+    let previous = app
+        .state::<WeatherAlertState>()
+        .atis
+        .write()
+        .replace((icao.to_string(), letters.clone()));
+    let Some((previous_icao, previous)) = previous else {
+        return;
+    };
+    if previous_icao != icao {
+        return;
+    }
+    let settings = settings(app);
+    if !settings.atis {
+        return;
+    }
+    for (callsign, letter) in &letters {
+        // A station coming online isn't a new letter
+        let Some(before) = previous.get(callsign) else {
+            continue;
+        };
+        if before != letter {
+            raise(
+                app,
+                &settings,
+                icao,
+                KIND_ATIS,
+                format!("{} information {}", callsign.trim_end_matches("_ATIS"), letter),
+                format!("{} changed from {} to {}", callsign, before, letter),
+            );
+        }
+    }
+}
+
+/// Stop watching (no active airport)
+pub fn clear(app: &AppHandle) {
+    *app.state::<WeatherAlertState>().atis.write() = None;
+}
+
+/// Initialize weather alert state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_weather_alerts(app: &AppHandle) {
+    app.manage(WeatherAlertState::new());
+}
+
+/// Check the ATIS letters after every traffic poll
+pub fn start_atis_alerts_task(app: &AppHandle) {
+    let app = app.clone();
+    supervisor::spawn("atis-alerts", Restart::Always, move || {
+        let app = app.clone();
+        let mut polls = traffic::subscribe_polls(&app);
+        async move {
+            while polls.changed().await.is_ok() {
+                let polled = polls.borrow_and_update().clone();
+                match polled {
+                    Some(polled) => check_atis(&app, &polled.icao, &polled.atis),
+                    None => clear(&app),
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::{Altimeter, Conditions, Wind};
+
+    fn metar(direction: Option<u16>, speed_kt: u16, inhg: f64) -> Metar {
+        Metar {
+            icao: "KBOS".to_string(),
+            raw: format!("KBOS {:?} {} {}", direction, speed_kt, inhg),
+            observed_at: None,
+            conditions: Conditions {
+                wind: Some(Wind {
+                    direction,
+                    speed_kt,
+                    gust_kt: None,
+                    variable_from: None,
+                    variable_to: None,
+                }),
+                ..Default::default()
+            },
+            temperature_c: None,
+            dewpoint_c: None,
+            altimeter: Some(Altimeter {
+                inhg,
+                hpa: inhg * 33.8639,
+            }),
+            ceiling_ft: None,
+            flight_category: "VFR".to_string(),
+            latitude: None,
+            longitude: None,
+            elevation_m: None,
+        }
+    }
+
+    fn kinds(previous: &Metar, current: &Metar) -> Vec<&'static str> {
+        metar_changes(previous, current, &WeatherAlertSettings::default())
+            .into_iter()
+            .map(|(kind, _, _)| kind)
+            .collect()
+    }
+
+    #[test]
+    fn detects_wind_shifts_and_altimeter_changes() {
+        assert!(kinds(&metar(Some(270), 10, 29.92), &metar(Some(280), 12, 29.92)).is_empty());
+        // Across north
+        assert_eq!(kinds(&metar(Some(350), 10, 29.92), &metar(Some(30), 10, 29.92)), vec![KIND_WIND_SHIFT]);
+        assert_eq!(kinds(&metar(Some(270), 5, 29.92), &metar(Some(270), 18, 29.92)), vec![KIND_WIND_SHIFT]);
+        assert_eq!(kinds(&metar(Some(270), 10, 29.92), &metar(Some(270), 10, 29.89)), vec![KIND_ALTIMETER]);
+        assert!(kinds(&metar(None, 2, 29.92), &metar(Some(90), 3, 29.92)).is_empty());
+
+        let quiet = WeatherAlertSettings {
+            wind_shift: false,
+            ..Default::default()
+        };
+        assert!(metar_changes(&metar(Some(350), 10, 29.92), &metar(Some(90), 10, 29.92), &quiet).is_empty());
+    }
+
+    #[test]
+    fn reads_atis_letters() {
+        let station = |callsign: &str, code: Option<&str>| OnlineController {
+            callsign: callsign.to_string(),
+            cid: None,
+            name: String::new(),
+            frequency: "135.000".to_string(),
+            rating: None,
+            logon_time: None,
+            facility: 0,
+            text_atis: None,
+            atis_code: code.map(str::to_string),
+        };
+        let letters = atis_letters(
+            "KBOS",
+            &[
+                station("KBOS_ATIS", Some("d")),
+                station("KBOS_A_ATIS", Some("K")),
+                station("KJFK_ATIS", Some("A")),
+                station("KBOS_TWR", None),
+            ],
+        );
+        assert_eq!(letters.len(), 2);
+        assert_eq!(letters["KBOS_ATIS"], "D");
+        assert_eq!(letters["KBOS_A_ATIS"], "K");
+    }
+}
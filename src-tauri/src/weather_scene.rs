@@ -0,0 +1,274 @@
+//! Renderable scene parameters derived from decoded weather
+//!
+//! Turns a decoded METAR into the numbers the 3D scene applies directly:
+//! visibility in meters, fog density, cloud layers (base in meters, coverage
+//! fraction) and precipitation type/intensity. Mappings match the ones the
+//! frontend used when it parsed METARs itself, so switching over doesn't change
+//! how the scene looks.
+
+use std::time::SystemTime;
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+use crate::error::Result;
+use crate::weather::{self, Metar};
+
+const FEET_TO_METERS: f64 = 0.3048;
+
+/// Visibility used when none is reported (10 SM, unrestricted)
+const UNRESTRICTED_VISIBILITY_SM: f64 = 10.0;
+
+/// Metric visibility reported as unrestricted (9999)
+const UNRESTRICTED_VISIBILITY_M: f64 = 10_000.0;
+
+/// Visibility at and below which fog is at maximum density (1/4 SM)
+const DENSE_FOG_VISIBILITY_SM: f64 = 0.25;
+
+/// Cesium fog density at 1/4 SM
+const MAX_FOG_DENSITY: f64 = 0.015;
+
+/// Precipitation particle factor range (more particles in lower visibility)
+const PRECIP_VIS_THRESHOLD_HIGH: f64 = 6.0;
+const PRECIP_VIS_THRESHOLD_LOW: f64 = 1.0;
+const PRECIP_VIS_FACTOR_MIN: f64 = 0.5;
+const PRECIP_VIS_FACTOR_MAX: f64 = 2.0;
+
+/// Maximum cloud layers rendered
+const MAX_CLOUD_LAYERS: usize = 4;
+
+/// Requested times older than this use archived METARs (replays)
+const HISTORICAL_AFTER_MS: u64 = 10 * 60 * 1000;
+
+/// Cloud layer ready for rendering
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneCloudLayer {
+    /// Base in meters AGL
+    pub altitude_m: f64,
+    /// Coverage fraction (okta midpoint, 0-1)
+    pub coverage: f64,
+    /// FEW, SCT, BKN or OVC
+    pub cover: String,
+    /// CB or TCU
+    pub cloud_type: Option<String>,
+}
+
+/// Precipitation ready for rendering
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenePrecipitation {
+    /// "rain", "snow", "drizzle", "hail", "ice" or "unknown"
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// "light", "moderate" or "heavy"
+    pub intensity: String,
+    /// Original METAR group (e.g., "+RA")
+    pub code: String,
+}
+
+/// Scene wind (surface)
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SceneWind {
+    /// Direction the wind is from (degrees true), None for variable
+    pub direction: Option<u16>,
+    pub speed_kt: u16,
+    pub gust_kt: Option<u16>,
+}
+
+/// Scene parameters for one station
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeatherScene {
+    pub icao: String,
+    /// Observation time of the source METAR (Unix ms)
+    pub observed_at: Option<u64>,
+    pub visibility_m: f64,
+    /// Cesium fog density (0 to 0.015)
+    pub fog_density: f64,
+    /// Sorted by altitude, at most four layers
+    pub cloud_layers: Vec<SceneCloudLayer>,
+    pub precipitation: Vec<ScenePrecipitation>,
+    /// Precipitation particle multiplier (0.5-2.0, higher in low visibility)
+    pub precipitation_factor: f64,
+    pub thunderstorm: bool,
+    pub wind: Option<SceneWind>,
+}
+
+/// Cesium fog density for a visibility, on a log scale from 0.015 at 1/4 SM to 0 at 10 SM
+pub fn fog_density(visibility_sm: f64) -> f64 {
+    if visibility_sm >= UNRESTRICTED_VISIBILITY_SM {
+        return 0.0;
+    }
+    if visibility_sm <= DENSE_FOG_VISIBILITY_SM {
+        return MAX_FOG_DENSITY;
+    }
+
+    let log_min = DENSE_FOG_VISIBILITY_SM.log10();
+    let log_max = UNRESTRICTED_VISIBILITY_SM.log10();
+    let normalized = (visibility_sm.log10() - log_min) / (log_max - log_min);
+    MAX_FOG_DENSITY * (1.0 - normalized)
+}
+
+/// Coverage fraction for a cloud cover code (okta range midpoint)
+fn cover_fraction(cover: &str) -> f64 {
+    match cover {
+        "FEW" => 0.1875,
+        "SCT" => 0.4375,
+        "BKN" => 0.6875,
+        "OVC" => 1.0,
+        _ => 0.0,
+    }
+}
+
+/// Precipitation type for a phenomenon code
+fn precipitation_kind(code: &str) -> Option<&'static str> {
+    match code {
+        "RA" => Some("rain"),
+        "SN" => Some("snow"),
+        "DZ" => Some("drizzle"),
+        "GR" | "GS" => Some("hail"),
+        "PL" | "SG" | "IC" => Some("ice"),
+        "UP" => Some("unknown"),
+        _ => None,
+    }
+}
+
+fn precipitation_factor(visibility_sm: f64) -> f64 {
+    if visibility_sm >= PRECIP_VIS_THRESHOLD_HIGH {
+        return PRECIP_VIS_FACTOR_MIN;
+    }
+    if visibility_sm <= PRECIP_VIS_THRESHOLD_LOW {
+        return PRECIP_VIS_FACTOR_MAX;
+    }
+
+    let t = (visibility_sm - PRECIP_VIS_THRESHOLD_LOW) / (PRECIP_VIS_THRESHOLD_HIGH - PRECIP_VIS_THRESHOLD_LOW);
+    PRECIP_VIS_FACTOR_MAX - t * (PRECIP_VIS_FACTOR_MAX - PRECIP_VIS_FACTOR_MIN)
+}
+
+/// Derive scene parameters from a decoded METAR
+pub fn scene_from_metar(metar: &Metar) -> WeatherScene {
+    let conditions = &metar.conditions;
+    // 10 km or more (9999, CAVOK) is unrestricted as far as the scene is concerned
+    let visibility_sm = match conditions.visibility_m {
+        Some(meters) if meters >= UNRESTRICTED_VISIBILITY_M => UNRESTRICTED_VISIBILITY_SM,
+        _ => conditions.visibility_sm.unwrap_or(UNRESTRICTED_VISIBILITY_SM),
+    };
+    let visibility_m = conditions
+        .visibility_m
+        .unwrap_or(UNRESTRICTED_VISIBILITY_SM * 1609.344)
+        .round();
+
+    let mut cloud_layers: Vec<SceneCloudLayer> = conditions
+        .clouds
+        .iter()
+        .filter(|c| cover_fraction(&c.cover) > 0.0)
+        .filter_map(|c| {
+            Some(SceneCloudLayer {
+                altitude_m: (c.base_ft? as f64 * FEET_TO_METERS).round(),
+                coverage: cover_fraction(&c.cover),
+                cover: c.cover.clone(),
+                cloud_type: c.cloud_type.clone(),
+            })
+        })
+        .collect();
+    // An obscured sky renders as an overcast layer at the vertical visibility
+    if let Some(vv) = conditions.vertical_visibility_ft {
+        cloud_layers.push(SceneCloudLayer {
+            altitude_m: (vv as f64 * FEET_TO_METERS).round(),
+            coverage: 1.0,
+            cover: "OVC".to_string(),
+            cloud_type: None,
+        });
+    }
+    cloud_layers.sort_by(|a, b| a.altitude_m.total_cmp(&b.altitude_m));
+    cloud_layers.truncate(MAX_CLOUD_LAYERS);
+
+    // Vicinity weather isn't at the field, so it isn't rendered
+    let mut at_station = conditions.weather.iter().filter(|w| w.intensity != "vicinity");
+    let precipitation: Vec<ScenePrecipitation> = at_station
+        .clone()
+        .flat_map(|w| {
+            w.phenomena.iter().filter_map(|p| {
+                Some(ScenePrecipitation {
+                    kind: precipitation_kind(p)?.to_string(),
+                    intensity: w.intensity.clone(),
+                    code: w.code.clone(),
+                })
+            })
+        })
+        .collect();
+    let thunderstorm =
+        at_station.any(|w| w.descriptor.as_deref() == Some("TS") || w.phenomena.iter().any(|p| p == "TS"));
+
+    WeatherScene {
+        icao: metar.icao.clone(),
+        observed_at: metar.observed_at,
+        visibility_m,
+        fog_density: fog_density(visibility_sm),
+        cloud_layers,
+        precipitation,
+        precipitation_factor: precipitation_factor(visibility_sm),
+        thunderstorm,
+        wind: conditions.wind.as_ref().map(|w| SceneWind {
+            direction: w.direction,
+            speed_kt: w.speed_kt,
+            gust_kt: w.gust_kt,
+        }),
+    }
+}
+
+/// Scene parameters for a station, now or at a past time (Unix ms) when replaying.
+/// None if it has no report.
+pub async fn get_scene(app: &AppHandle, icao: &str, at: Option<u64>) -> Result<Option<WeatherScene>> {
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+    let metar = match at {
+        Some(at) if at + HISTORICAL_AFTER_MS < now => weather::get_metar_at(app, icao, at).await?,
+        _ => weather::get_metar(app, icao, false).await?,
+    };
+    Ok(metar.map(|metar| scene_from_metar(&metar)))
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get renderable scene parameters for a station (also emitted as `weather-scene-changed`),
+/// optionally at a past time (Unix ms) for replays
+#[tauri::command]
+pub async fn get_weather_scene(app: AppHandle, icao: String, at: Option<u64>) -> Result<Option<WeatherScene>> {
+    get_scene(&app, &icao, at).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::weather::decode_metar;
+
+    #[test]
+    fn derives_scene_from_metar() {
+        let scene = scene_from_metar(&decode_metar(
+            "KSFO 121756Z 28012KT 1/2SM -TSRA BR VCSH FEW008 BKN015CB OVC030 12/11 A2992",
+        ));
+
+        assert_eq!(scene.visibility_m, 805.0);
+        assert!(scene.fog_density > 0.011 && scene.fog_density < 0.013);
+        assert_eq!(scene.cloud_layers.len(), 3);
+        assert_eq!(scene.cloud_layers[1].altitude_m, 457.0);
+        assert_eq!(scene.cloud_layers[1].cloud_type.as_deref(), Some("CB"));
+        assert_eq!(scene.precipitation.len(), 1);
+        assert_eq!(scene.precipitation[0].kind, "rain");
+        assert_eq!(scene.precipitation[0].intensity, "light");
+        assert!(scene.thunderstorm);
+        assert_eq!(scene.precipitation_factor, 2.0);
+
+        let clear = scene_from_metar(&decode_metar("KSFO 121756Z 00000KT CAVOK 20/10 Q1013"));
+        assert_eq!(clear.fog_density, 0.0);
+        assert!(clear.cloud_layers.is_empty());
+        assert!(clear.precipitation.is_empty());
+    }
+}
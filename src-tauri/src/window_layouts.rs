@@ -0,0 +1,390 @@
+//! Window layout profiles
+//!
+//! A cab layout can span several windows on several monitors (the main window
+//! plus extra `cab-N` windows opened with `open_cab_window`). A named layout
+//! records, for each window, the monitor it is on, its position and size
+//! within that monitor, whether it is fullscreen or maximized, and the camera
+//! bookmark assigned to it. Applying a layout opens missing windows, closes
+//! extra ones, moves each window back to its monitor and sends
+//! `window-layout-bookmark` so each window loads its bookmark (windows opened
+//! by the layout ask for theirs with `get_window_bookmark` once loaded).
+//!
+//! Positions are stored relative to the monitor (found by name), so layouts
+//! survive monitors being rearranged. If a monitor is missing, its windows go
+//! to the primary monitor. Layouts are kept in `window-layouts.json` in the
+//! app data directory.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tauri::{
+    AppHandle, Emitter, Manager, Monitor, PhysicalPosition, PhysicalSize, WebviewUrl, WebviewWindow,
+    WebviewWindowBuilder,
+};
+use tracing::{info, warn};
+
+use crate::error::{Error, Result};
+use crate::control;
+
+/// Label prefix for extra cab windows
+const CAB_WINDOW_PREFIX: &str = "cab-";
+
+/// One window in a layout
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowPlacement {
+    /// Window label (`main` or `cab-N`)
+    pub label: String,
+    /// Monitor name (None if it couldn't be determined)
+    pub monitor: Option<String>,
+    /// Position of the window's top-left corner relative to the monitor (physical pixels)
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub fullscreen: bool,
+    pub maximized: bool,
+    /// Camera bookmark (name or slot) loaded in this window
+    #[serde(default)]
+    pub bookmark: Option<String>,
+}
+
+/// A named multi-window layout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowLayout {
+    pub name: String,
+    pub windows: Vec<WindowPlacement>,
+    /// When the layout was saved (Unix ms)
+    pub saved_at: u64,
+}
+
+/// Bookmark a window should load (`window-layout-bookmark` event)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowBookmark {
+    pub label: String,
+    pub bookmark: String,
+}
+
+/// Bookmarks assigned to open windows (managed by Tauri)
+#[derive(Default)]
+pub struct WindowLayoutState {
+    bookmarks: Mutex<HashMap<String, String>>,
+}
+
+/// A monitor's name, origin and size
+#[derive(Debug, Clone)]
+pub struct MonitorArea {
+    pub name: Option<String>,
+    pub position: PhysicalPosition<i32>,
+    pub size: PhysicalSize<u32>,
+}
+
+impl From<&Monitor> for MonitorArea {
+    fn from(monitor: &Monitor) -> Self {
+        Self {
+            name: monitor.name().cloned(),
+            position: *monitor.position(),
+            size: *monitor.size(),
+        }
+    }
+}
+
+fn layouts_file(app: &AppHandle) -> Result<PathBuf> {
+    let app_data = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| Error::Internal(format!("Failed to get app data directory: {}", e)))?;
+    fs::create_dir_all(&app_data).map_err(|e| Error::Io(format!("Failed to create app data directory: {}", e)))?;
+    Ok(app_data.join("window-layouts.json"))
+}
+
+fn read_layouts(app: &AppHandle) -> Vec<WindowLayout> {
+    layouts_file(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_layouts(app: &AppHandle, layouts: &[WindowLayout]) -> Result<()> {
+    let path = layouts_file(app)?;
+    let content = serde_json::to_string_pretty(layouts)
+        .map_err(|e| Error::Internal(format!("Failed to serialize window layouts: {}", e)))?;
+    fs::write(&path, content).map_err(|e| Error::Io(format!("Failed to write window layouts: {}", e)))
+}
+
+/// Absolute position for a placement: on its monitor if connected, otherwise
+/// on the fallback (primary) monitor. Kept inside the monitor.
+pub fn resolve_position(
+    placement: &WindowPlacement,
+    monitors: &[MonitorArea],
+    fallback: Option<&MonitorArea>,
+) -> PhysicalPosition<i32> {
+    let monitor = monitors
+        .iter()
+        .find(|m| m.name.is_some() && m.name == placement.monitor)
+        .or(fallback);
+    let Some(monitor) = monitor else {
+        return PhysicalPosition::new(placement.x, placement.y);
+    };
+    let max_x = (monitor.size.width as i32 - placement.width as i32).max(0);
+    let max_y = (monitor.size.height as i32 - placement.height as i32).max(0);
+    PhysicalPosition::new(
+        monitor.position.x + placement.x.clamp(0, max_x),
+        monitor.position.y + placement.y.clamp(0, max_y),
+    )
+}
+
+/// Current placement of a window
+fn capture(window: &WebviewWindow, bookmark: Option<String>) -> Result<WindowPlacement> {
+    let error = |e: tauri::Error| Error::Internal(format!("Failed to read window {}: {}", window.label(), e));
+    let position = window.outer_position().map_err(error)?;
+    let size = window.outer_size().map_err(error)?;
+    let monitor = window.current_monitor().map_err(error)?;
+    let origin = monitor.as_ref().map(|m| *m.position()).unwrap_or_default();
+    Ok(WindowPlacement {
+        label: window.label().to_string(),
+        monitor: monitor.as_ref().and_then(|m| m.name().cloned()),
+        x: position.x - origin.x,
+        y: position.y - origin.y,
+        width: size.width,
+        height: size.height,
+        fullscreen: window.is_fullscreen().map_err(error)?,
+        maximized: window.is_maximized().map_err(error)?,
+        bookmark,
+    })
+}
+
+fn is_layout_window(label: &str) -> bool {
+    label == "main" || label.starts_with(CAB_WINDOW_PREFIX)
+}
+
+fn build_cab_window(app: &AppHandle, label: &str) -> Result<WebviewWindow> {
+    WebviewWindowBuilder::new(app, label, WebviewUrl::App("index.html".into()))
+        .title(format!("TowerCab 3D ({})", label))
+        .inner_size(1280.0, 720.0)
+        .build()
+        .map_err(|e| Error::Internal(format!("Failed to open window {}: {}", label, e)))
+}
+
+/// Move a window to its placement
+fn place(window: &WebviewWindow, placement: &WindowPlacement, position: PhysicalPosition<i32>) -> tauri::Result<()> {
+    // Leave fullscreen/maximized first so the move applies to the window itself
+    window.set_fullscreen(false)?;
+    window.unmaximize()?;
+    window.set_position(position)?;
+    window.set_size(PhysicalSize::new(placement.width, placement.height))?;
+    if placement.maximized {
+        window.maximize()?;
+    }
+    // Fullscreen goes to the monitor the window is on
+    window.set_fullscreen(placement.fullscreen)
+}
+
+fn apply(app: &AppHandle, layout: &WindowLayout) -> Result<()> {
+    let monitors: Vec<MonitorArea> = app
+        .available_monitors()
+        .map_err(|e| Error::Internal(format!("Failed to list monitors: {}", e)))?
+        .iter()
+        .map(MonitorArea::from)
+        .collect();
+    let primary = app.primary_monitor().ok().flatten().map(|m| MonitorArea::from(&m));
+
+    // Close cab windows that aren't part of the layout
+    for (label, window) in app.webview_windows() {
+        if label.starts_with(CAB_WINDOW_PREFIX) && !layout.windows.iter().any(|w| w.label == label) {
+            let _ = window.close();
+        }
+    }
+
+    let state = app.state::<WindowLayoutState>();
+    for placement in &layout.windows {
+        let window = match app.get_webview_window(&placement.label) {
+            Some(window) => window,
+            None => build_cab_window(app, &placement.label)?,
+        };
+        if placement.monitor.is_some() && !monitors.iter().any(|m| m.name == placement.monitor) {
+            warn!(
+                "[Layouts] Monitor {:?} not connected, placing {} on the primary monitor",
+                placement.monitor, placement.label
+            );
+        }
+        let position = resolve_position(placement, &monitors, primary.as_ref());
+        if let Err(e) = place(&window, placement, position) {
+            warn!("[Layouts] Failed to place window {}: {}", placement.label, e);
+        }
+
+        let mut bookmarks = state.bookmarks.lock();
+        match &placement.bookmark {
+            Some(bookmark) => {
+                bookmarks.insert(placement.label.clone(), bookmark.clone());
+                let _ = app.emit(
+                    "window-layout-bookmark",
+                    WindowBookmark {
+                        label: placement.label.clone(),
+                        bookmark: bookmark.clone(),
+                    },
+                );
+            }
+            None => {
+                bookmarks.remove(&placement.label);
+            }
+        }
+    }
+    info!(
+        "[Layouts] Applied layout '{}' ({} windows)",
+        layout.name,
+        layout.windows.len()
+    );
+    Ok(())
+}
+
+/// Bookmark last loaded in a window (name or slot)
+pub fn assigned_bookmark(app: &AppHandle, label: &str) -> Option<String> {
+    app.try_state::<WindowLayoutState>()
+        .and_then(|state| state.bookmarks.lock().get(label).cloned())
+}
+
+/// Initialize window layout state for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_window_layouts(app: &AppHandle) {
+    app.manage(WindowLayoutState::default());
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// List saved window layouts
+#[tauri::command]
+pub fn list_window_layouts(app: AppHandle) -> Vec<WindowLayout> {
+    read_layouts(&app)
+}
+
+/// Save the current windows as a named layout (replacing one with the same name)
+#[tauri::command]
+pub fn save_window_layout(app: AppHandle, name: String) -> Result<WindowLayout> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(Error::invalid_input("Layout name is required"));
+    }
+    let bookmarks = app.state::<WindowLayoutState>().bookmarks.lock().clone();
+    let mut windows: Vec<WindowPlacement> = app
+        .webview_windows()
+        .into_iter()
+        .filter(|(label, _)| is_layout_window(label))
+        .map(|(label, window)| capture(&window, bookmarks.get(&label).cloned()))
+        .collect::<Result<_, _>>()?;
+    windows.sort_by(|a, b| (a.label != "main", &a.label).cmp(&(b.label != "main", &b.label)));
+
+    let layout = WindowLayout {
+        name,
+        windows,
+        saved_at: chrono::Utc::now().timestamp_millis() as u64,
+    };
+    let mut layouts = read_layouts(&app);
+    layouts.retain(|l| !l.name.eq_ignore_ascii_case(&layout.name));
+    layouts.push(layout.clone());
+    write_layouts(&app, &layouts)?;
+    info!(
+        "[Layouts] Saved layout '{}' ({} windows)",
+        layout.name,
+        layout.windows.len()
+    );
+    Ok(layout)
+}
+
+/// Restore a saved layout (async: creating windows in a sync command deadlocks on Windows)
+#[tauri::command]
+pub async fn apply_window_layout(app: AppHandle, name: String) -> Result<()> {
+    let layout = read_layouts(&app)
+        .into_iter()
+        .find(|l| l.name.eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| Error::NotFound(format!("Window layout '{}' not found", name)))?;
+    apply(&app, &layout)
+}
+
+/// Delete a saved layout
+#[tauri::command]
+pub fn delete_window_layout(app: AppHandle, name: String) -> Result<()> {
+    let mut layouts = read_layouts(&app);
+    let count = layouts.len();
+    layouts.retain(|l| !l.name.eq_ignore_ascii_case(name.trim()));
+    if layouts.len() == count {
+        return Err(Error::NotFound(format!("Window layout '{}' not found", name)));
+    }
+    write_layouts(&app, &layouts)
+}
+
+/// Open another cab window; returns its label
+#[tauri::command]
+pub async fn open_cab_window(app: AppHandle) -> Result<String> {
+    let label = (2..)
+        .map(|n| format!("{}{}", CAB_WINDOW_PREFIX, n))
+        .find(|label| app.get_webview_window(label).is_none())
+        .unwrap_or_default();
+    build_cab_window(&app, &label)?;
+    Ok(label)
+}
+
+/// Assign the bookmark the calling window shows (saved with layouts)
+#[tauri::command]
+pub fn assign_window_bookmark(app: AppHandle, window: WebviewWindow, bookmark: Option<String>) {
+    {
+        let state = app.state::<WindowLayoutState>();
+        let mut bookmarks = state.bookmarks.lock();
+        match bookmark.filter(|b| !b.trim().is_empty()) {
+            Some(bookmark) => bookmarks.insert(window.label().to_string(), bookmark),
+            None => bookmarks.remove(window.label()),
+        };
+    }
+    control::publish(&app);
+}
+
+/// Get the bookmark assigned to the calling window
+#[tauri::command]
+pub fn get_window_bookmark(app: AppHandle, window: WebviewWindow) -> Option<String> {
+    app.state::<WindowLayoutState>()
+        .bookmarks
+        .lock()
+        .get(window.label())
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn places_windows_on_their_monitor_or_the_primary() {
+        let monitor = |name: &str, x: i32, width: u32| MonitorArea {
+            name: Some(name.to_string()),
+            position: PhysicalPosition::new(x, 0),
+            size: PhysicalSize::new(width, 1080),
+        };
+        let monitors = vec![monitor("DISPLAY1", 0, 1920), monitor("DISPLAY2", 1920, 2560)];
+        let placement = |monitor: &str, x: i32| WindowPlacement {
+            label: "cab-2".to_string(),
+            monitor: Some(monitor.to_string()),
+            x,
+            y: 40,
+            width: 1280,
+            height: 720,
+            fullscreen: true,
+            maximized: false,
+            bookmark: Some("Tower North".to_string()),
+        };
+
+        let on_second = resolve_position(&placement("DISPLAY2", 100), &monitors, monitors.first());
+        assert_eq!((on_second.x, on_second.y), (2020, 40));
+
+        // Monitor gone: same offset on the primary, kept on screen
+        let moved = resolve_position(&placement("DISPLAY3", 1000), &monitors, monitors.first());
+        assert_eq!((moved.x, moved.y), (640, 40));
+    }
+}
@@ -0,0 +1,389 @@
+//! Winds aloft from the GFS model
+//!
+//! Surface METAR wind says little about the wind at the top of a tower or at
+//! pattern altitude. This module fetches GFS wind profiles (10 m/80 m above
+//! ground plus the lowest pressure levels) from the Open-Meteo API and
+//! interpolates the wind at tower height and pattern altitude, for driving the
+//! windsock and cloud drift in the 3D scene.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime};
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use tauri::{AppHandle, Manager};
+use tracing::warn;
+
+use crate::error::{Error, Result};
+use crate::http_client;
+use crate::weather;
+
+const GFS_API_URL: &str = "https://api.open-meteo.com/v1/gfs";
+
+/// How long a fetched profile is served from cache (GFS runs every 6 hours)
+const WINDS_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// Pressure levels fetched (roughly surface to 5,000 ft)
+const PRESSURE_LEVELS: [u16; 6] = [1000, 975, 950, 925, 900, 850];
+
+/// Heights above ground with direct model output (meters)
+const HEIGHT_LEVELS: [u16; 2] = [10, 80];
+
+/// Default tower cab height above ground (meters)
+pub const DEFAULT_TOWER_HEIGHT_M: f64 = 40.0;
+
+/// Default traffic pattern altitude above ground (feet)
+pub const DEFAULT_PATTERN_ALTITUDE_FT: f64 = 1000.0;
+
+const FEET_TO_METERS: f64 = 0.3048;
+
+/// Wind at a height above ground
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindLevel {
+    pub height_agl_m: f64,
+    /// Direction the wind is from (degrees true)
+    pub direction: f64,
+    pub speed_kt: f64,
+}
+
+/// Wind profile with interpolated tower and pattern winds
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindsAloft {
+    /// Model grid point used
+    pub latitude: f64,
+    pub longitude: f64,
+    pub elevation_m: f64,
+    /// Forecast hour used (Unix ms)
+    pub valid_at: u64,
+    /// Profile sorted by height
+    pub levels: Vec<WindLevel>,
+    /// Wind at tower cab height
+    pub tower: Option<WindLevel>,
+    /// Wind at pattern altitude
+    pub pattern: Option<WindLevel>,
+}
+
+/// Interpolate wind at a height from a profile sorted by height (u/v components,
+/// so directions either side of north blend correctly). Clamped to the profile.
+pub fn interpolate_wind(levels: &[WindLevel], height_agl_m: f64) -> Option<WindLevel> {
+    let first = levels.first()?;
+    let last = levels.last()?;
+
+    let (lower, upper) = if height_agl_m <= first.height_agl_m {
+        (first, first)
+    } else if height_agl_m >= last.height_agl_m {
+        (last, last)
+    } else {
+        levels
+            .windows(2)
+            .map(|pair| (&pair[0], &pair[1]))
+            .find(|(lower, upper)| {
+                lower.height_agl_m <= height_agl_m && height_agl_m <= upper.height_agl_m
+            })?
+    };
+
+    let span = upper.height_agl_m - lower.height_agl_m;
+    let t = if span > 0.0 {
+        (height_agl_m - lower.height_agl_m) / span
+    } else {
+        0.0
+    };
+
+    // Components of the wind vector pointing where the wind comes from
+    let components = |level: &WindLevel| {
+        let radians = level.direction.to_radians();
+        (
+            level.speed_kt * radians.sin(),
+            level.speed_kt * radians.cos(),
+        )
+    };
+    let (lower_u, lower_v) = components(lower);
+    let (upper_u, upper_v) = components(upper);
+    let u = lower_u + (upper_u - lower_u) * t;
+    let v = lower_v + (upper_v - lower_v) * t;
+
+    Some(WindLevel {
+        height_agl_m,
+        direction: u.atan2(v).to_degrees().round().rem_euclid(360.0),
+        speed_kt: ((u * u + v * v).sqrt() * 10.0).round() / 10.0,
+    })
+}
+
+/// Profile for one grid point, with the hour it is valid for
+struct CachedProfile {
+    latitude: f64,
+    longitude: f64,
+    elevation_m: f64,
+    valid_at: u64,
+    levels: Vec<WindLevel>,
+    fetched_at: Instant,
+}
+
+/// Winds aloft cache managed by Tauri (keyed by 0.25° grid cell, the GFS resolution)
+pub struct WindsAloftState {
+    profiles: RwLock<HashMap<(i32, i32), CachedProfile>>,
+}
+
+impl WindsAloftState {
+    pub fn new() -> Self {
+        Self {
+            profiles: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+impl Default for WindsAloftState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn grid_cell(latitude: f64, longitude: f64) -> (i32, i32) {
+    (
+        (latitude * 4.0).round() as i32,
+        (longitude * 4.0).round() as i32,
+    )
+}
+
+/// Hourly value of a variable at an index
+fn hourly_value(hourly: &serde_json::Value, name: &str, index: usize) -> Option<f64> {
+    hourly.get(name)?.get(index)?.as_f64()
+}
+
+/// Fetch the GFS wind profile for the current hour at a position
+async fn fetch_profile(latitude: f64, longitude: f64) -> Result<CachedProfile> {
+    let mut variables: Vec<String> = HEIGHT_LEVELS
+        .iter()
+        .flat_map(|h| {
+            [
+                format!("wind_speed_{}m", h),
+                format!("wind_direction_{}m", h),
+            ]
+        })
+        .collect();
+    for level in PRESSURE_LEVELS {
+        variables.push(format!("wind_speed_{}hPa", level));
+        variables.push(format!("wind_direction_{}hPa", level));
+        variables.push(format!("geopotential_height_{}hPa", level));
+    }
+
+    let url = format!(
+        "{}?latitude={:.4}&longitude={:.4}&hourly={}&wind_speed_unit=kn&timeformat=unixtime&forecast_days=2",
+        GFS_API_URL,
+        latitude,
+        longitude,
+        variables.join(",")
+    );
+    let response = http_client::client()
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to fetch winds aloft: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(Error::Upstream(format!("HTTP error: {}", response.status())));
+    }
+    let data: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| Error::Upstream(format!("Failed to parse winds aloft response: {}", e)))?;
+
+    let elevation_m = data
+        .get("elevation")
+        .and_then(|e| e.as_f64())
+        .unwrap_or(0.0);
+    let hourly = data
+        .get("hourly")
+        .ok_or_else(|| Error::Upstream("Winds aloft response has no hourly data".to_string()))?;
+    let times: Vec<i64> = hourly
+        .get("time")
+        .and_then(|t| t.as_array())
+        .map(|t| t.iter().filter_map(|v| v.as_i64()).collect())
+        .unwrap_or_default();
+
+    // Forecast hour closest to now
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+    let (index, valid_at) = times
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, t)| (**t - now).abs())
+        .map(|(i, t)| (i, *t))
+        .ok_or_else(|| Error::Upstream("Winds aloft response has no forecast hours".to_string()))?;
+
+    let mut levels: Vec<WindLevel> = Vec::new();
+    for height in HEIGHT_LEVELS {
+        if let (Some(speed), Some(direction)) = (
+            hourly_value(hourly, &format!("wind_speed_{}m", height), index),
+            hourly_value(hourly, &format!("wind_direction_{}m", height), index),
+        ) {
+            levels.push(WindLevel {
+                height_agl_m: height as f64,
+                direction,
+                speed_kt: speed,
+            });
+        }
+    }
+    for level in PRESSURE_LEVELS {
+        if let (Some(speed), Some(direction), Some(height_msl)) = (
+            hourly_value(hourly, &format!("wind_speed_{}hPa", level), index),
+            hourly_value(hourly, &format!("wind_direction_{}hPa", level), index),
+            hourly_value(hourly, &format!("geopotential_height_{}hPa", level), index),
+        ) {
+            // Pressure levels below (or too close to) the ground are extrapolated; skip them
+            let height_agl_m = height_msl - elevation_m;
+            if height_agl_m > 100.0 {
+                levels.push(WindLevel {
+                    height_agl_m: height_agl_m.round(),
+                    direction,
+                    speed_kt: speed,
+                });
+            }
+        }
+    }
+    levels.sort_by(|a, b| a.height_agl_m.total_cmp(&b.height_agl_m));
+
+    if levels.is_empty() {
+        return Err(Error::Upstream("Winds aloft response has no wind data".to_string()));
+    }
+
+    Ok(CachedProfile {
+        latitude: data
+            .get("latitude")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(latitude),
+        longitude: data
+            .get("longitude")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(longitude),
+        elevation_m,
+        valid_at: valid_at.max(0) as u64 * 1000,
+        levels,
+        fetched_at: Instant::now(),
+    })
+}
+
+/// Winds aloft at a position, with winds interpolated at tower height (m AGL)
+/// and pattern altitude (ft AGL)
+pub async fn get_winds_aloft(
+    app: &AppHandle,
+    latitude: f64,
+    longitude: f64,
+    tower_height_m: f64,
+    pattern_altitude_ft: f64,
+) -> Result<WindsAloft> {
+    if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+        return Err(Error::InvalidInput(format!("Invalid position: {}, {}", latitude, longitude)));
+    }
+
+    let state = app.state::<WindsAloftState>();
+    let cell = grid_cell(latitude, longitude);
+    let fresh = state
+        .profiles
+        .read()
+        .get(&cell)
+        .is_some_and(|p| p.fetched_at.elapsed() < WINDS_TTL);
+    if !fresh {
+        match fetch_profile(latitude, longitude).await {
+            Ok(profile) => {
+                state.profiles.write().insert(cell, profile);
+            }
+            // Serve the stale profile rather than nothing
+            Err(e) if state.profiles.read().contains_key(&cell) => warn!("[Weather] {}", e),
+            Err(e) => return Err(e),
+        }
+    }
+
+    let profiles = state.profiles.read();
+    let profile = profiles.get(&cell).ok_or_else(|| Error::Upstream("Winds aloft not available".to_string()))?;
+    Ok(WindsAloft {
+        latitude: profile.latitude,
+        longitude: profile.longitude,
+        elevation_m: profile.elevation_m,
+        valid_at: profile.valid_at,
+        tower: interpolate_wind(&profile.levels, tower_height_m),
+        pattern: interpolate_wind(&profile.levels, pattern_altitude_ft * FEET_TO_METERS),
+        levels: profile.levels.clone(),
+    })
+}
+
+/// Winds aloft at an airport (position taken from its METAR station)
+pub async fn get_airport_winds_aloft(
+    app: &AppHandle,
+    icao: &str,
+    tower_height_m: Option<f64>,
+    pattern_altitude_ft: Option<f64>,
+) -> Result<WindsAloft> {
+    let metar = weather::get_metar(app, icao, false)
+        .await?
+        .ok_or_else(|| Error::NotFound(format!("No METAR station position for {}", icao)))?;
+    let (Some(latitude), Some(longitude)) = (metar.latitude, metar.longitude) else {
+        return Err(Error::NotFound(format!("No METAR station position for {}", icao)));
+    };
+
+    get_winds_aloft(
+        app,
+        latitude,
+        longitude,
+        tower_height_m.unwrap_or(DEFAULT_TOWER_HEIGHT_M),
+        pattern_altitude_ft.unwrap_or(DEFAULT_PATTERN_ALTITUDE_FT),
+    )
+    .await
+}
+
+// =============================================================================
+// TAURI COMMANDS
+// =============================================================================
+
+/// Get winds aloft at an airport, interpolated at tower height (m AGL) and pattern altitude (ft AGL)
+#[tauri::command]
+pub async fn get_station_winds_aloft(
+    app: AppHandle,
+    icao: String,
+    tower_height_m: Option<f64>,
+    pattern_altitude_ft: Option<f64>,
+) -> Result<WindsAloft> {
+    get_airport_winds_aloft(&app, &icao, tower_height_m, pattern_altitude_ft).await
+}
+
+/// Initialize winds aloft cache for Tauri app.
+/// Call this in the Tauri setup closure.
+pub fn init_winds_aloft(app: &AppHandle) {
+    app.manage(WindsAloftState::new());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(height_agl_m: f64, direction: f64, speed_kt: f64) -> WindLevel {
+        WindLevel {
+            height_agl_m,
+            direction,
+            speed_kt,
+        }
+    }
+
+    #[test]
+    fn interpolates_between_levels_and_across_north() {
+        let profile = [
+            level(10.0, 350.0, 10.0),
+            level(80.0, 10.0, 10.0),
+            level(300.0, 90.0, 30.0),
+        ];
+
+        let across_north = interpolate_wind(&profile, 45.0).unwrap();
+        assert_eq!(across_north.direction, 0.0);
+
+        let below = interpolate_wind(&profile, 2.0).unwrap();
+        assert_eq!((below.direction, below.speed_kt), (350.0, 10.0));
+
+        let above = interpolate_wind(&profile, 1000.0).unwrap();
+        assert_eq!((above.direction, above.speed_kt), (90.0, 30.0));
+
+        assert!(interpolate_wind(&[], 40.0).is_none());
+    }
+}
@@ -0,0 +1,174 @@
+//! Wire format for the vNAS aircraft WebSocket
+//!
+//! By default `/api/vnas/ws` sends JSON arrays of full aircraft updates.
+//! Clients can opt into a smaller format when connecting:
+//!
+//! - `encoding=msgpack`: MessagePack (same field names) in binary frames
+//! - `delta=true`: per callsign, only the fields that changed since the last
+//!   update sent to this client (`callsign` and `timestamp` are always sent).
+//!   The first update for a callsign is complete; clients merge the rest into
+//!   what they have.
+//!
+//! e.g., `/api/vnas/ws?encoding=msgpack&delta=true`
+
+use std::collections::HashMap;
+
+use axum::extract::ws::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::lod::ModelDetail;
+use crate::server::VnasAircraftBroadcast;
+use crate::trails::TrailPoint;
+
+/// Callsigns not updated for this long are forgotten, so their next update is
+/// sent complete (ms)
+const STALE_MS: u64 = 120_000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Encoding {
+    #[default]
+    Json,
+    Msgpack,
+}
+
+/// Format negotiated by a client at connect time (query parameters)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WireFormat {
+    pub encoding: Encoding,
+    pub delta: bool,
+}
+
+impl WireFormat {
+    /// WebSocket message carrying a value in this format
+    pub fn message<T: Serialize>(&self, value: &T) -> Result<Message, String> {
+        match self.encoding {
+            Encoding::Json => serde_json::to_string(value)
+                .map(Message::Text)
+                .map_err(|e| e.to_string()),
+            Encoding::Msgpack => rmp_serde::to_vec_named(value)
+                .map(Message::Binary)
+                .map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// Changed fields of an aircraft update. Absent fields are unchanged; `null`
+/// means the value is now unknown.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AircraftDelta {
+    pub callsign: String,
+    pub timestamp: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lat: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lon: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub altitude: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub heading: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_code: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groundspeed_kt: Option<Option<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vertical_rate_fpm: Option<Option<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trail_point: Option<TrailPoint>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model_detail: Option<Option<ModelDetail>>,
+}
+
+/// Fields of `next` that differ from `previous` (all of them without one)
+pub fn diff(previous: Option<&VnasAircraftBroadcast>, next: &VnasAircraftBroadcast) -> AircraftDelta {
+    fn changed<T: PartialEq + Clone>(previous: Option<&T>, next: &T) -> Option<T> {
+        (previous != Some(next)).then(|| next.clone())
+    }
+    AircraftDelta {
+        callsign: next.callsign.clone(),
+        timestamp: next.timestamp,
+        lat: changed(previous.map(|p| &p.lat), &next.lat),
+        lon: changed(previous.map(|p| &p.lon), &next.lon),
+        altitude: changed(previous.map(|p| &p.altitude), &next.altitude),
+        heading: changed(previous.map(|p| &p.heading), &next.heading),
+        type_code: changed(previous.map(|p| &p.type_code), &next.type_code),
+        groundspeed_kt: changed(previous.map(|p| &p.groundspeed_kt), &next.groundspeed_kt),
+        vertical_rate_fpm: changed(previous.map(|p| &p.vertical_rate_fpm), &next.vertical_rate_fpm),
+        trail_point: next.trail_point,
+        model_detail: changed(previous.map(|p| &p.model_detail), &next.model_detail),
+    }
+}
+
+/// Last update sent to one client per callsign
+#[derive(Default)]
+pub struct DeltaEncoder {
+    last: HashMap<String, VnasAircraftBroadcast>,
+}
+
+impl DeltaEncoder {
+    pub fn encode(&mut self, aircraft: &[VnasAircraftBroadcast]) -> Vec<AircraftDelta> {
+        let deltas = aircraft
+            .iter()
+            .map(|a| {
+                let delta = diff(self.last.get(&a.callsign), a);
+                self.last.insert(a.callsign.clone(), a.clone());
+                delta
+            })
+            .collect();
+        if let Some(latest) = aircraft.iter().map(|a| a.timestamp).max() {
+            self.last.retain(|_, a| latest.saturating_sub(a.timestamp) <= STALE_MS);
+        }
+        deltas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::test_vnas_broadcast;
+
+    #[test]
+    fn sends_only_changed_fields() {
+        let aircraft = |altitude: f64, timestamp: u64| VnasAircraftBroadcast {
+            altitude,
+            heading: 90.0,
+            type_code: Some("B738".to_string()),
+            groundspeed_kt: Some(250.0),
+            ..test_vnas_broadcast("DAL123", timestamp)
+        };
+        let mut encoder = DeltaEncoder::default();
+
+        let first = encoder.encode(&[aircraft(10000.0, 1_000)]);
+        assert_eq!(first[0].type_code, Some(Some("B738".to_string())));
+        assert_eq!(first[0].vertical_rate_fpm, Some(None));
+
+        let second = encoder.encode(&[aircraft(9900.0, 2_000)]);
+        assert_eq!(
+            serde_json::to_string(&second).unwrap(),
+            r#"[{"callsign":"DAL123","timestamp":2000,"altitude":9900.0}]"#
+        );
+
+        // Forgotten after a long gap, so the next update is complete again
+        let other = VnasAircraftBroadcast {
+            callsign: "UAL1".to_string(),
+            ..aircraft(5000.0, 2_000 + STALE_MS + 1)
+        };
+        encoder.encode(&[other]);
+        assert_eq!(
+            encoder.encode(&[aircraft(9900.0, 2_000 + STALE_MS + 2)])[0].lat,
+            Some(42.0)
+        );
+
+        let format = WireFormat {
+            encoding: Encoding::Msgpack,
+            delta: true,
+        };
+        let Message::Binary(bytes) = format.message(&second).unwrap() else {
+            panic!("expected a binary message");
+        };
+        let decoded: serde_json::Value = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(decoded[0]["altitude"], 9900.0);
+    }
+}
@@ -0,0 +1,170 @@
+//! Pre-compressed zstd copies of served models and JSON
+//!
+//! GLB models and JSON files (overlays, video maps) often shrink to a third of
+//! their size, which matters for remote clients on Wi-Fi. Browsers that send
+//! `Accept-Encoding: zstd` get a compressed copy with `Content-Encoding: zstd`.
+//!
+//! Copies are made in the background the first time a file is requested (that
+//! request is served uncompressed) and kept in `zstd-cache` in the app cache
+//! directory, named by a hash of the source path. A copy is used while it is
+//! newer than its source. Files that barely shrink (e.g., Draco-compressed
+//! models) are served as-is.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use axum::http::{header, HeaderMap};
+use parking_lot::Mutex;
+use tracing::warn;
+
+/// Compression level: slow to compress, but each file is compressed once and
+/// decompression speed doesn't depend on it
+const LEVEL: i32 = 15;
+
+/// A copy is only served if it is at most this fraction of the original size
+const MAX_RATIO: f64 = 0.9;
+
+/// Extensions of files worth compressing
+const EXTENSIONS: &[&str] = &["glb", "gltf", "bin", "json", "geojson"];
+
+/// Whether the client accepts zstd (`Accept-Encoding: gzip, br, zstd`)
+pub fn accepts_zstd(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|coding| {
+            let mut parts = coding.split(';').map(str::trim);
+            parts.next().is_some_and(|name| name.eq_ignore_ascii_case("zstd"))
+                && parts.all(|param| param.strip_prefix("q=").and_then(|q| q.parse::<f64>().ok()) != Some(0.0))
+        })
+}
+
+/// Whether a file is a model or JSON file served compressed
+pub fn is_compressible(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| EXTENSIONS.iter().any(|x| x.eq_ignore_ascii_case(e)))
+}
+
+/// Write a zstd copy of `source` to `target` (via a temporary file)
+pub fn compress_file(source: &Path, target: &Path) -> Result<(), String> {
+    let input = fs::File::open(source).map_err(|e| format!("Failed to open {}: {}", source.display(), e))?;
+    let partial = target.with_extension("part");
+    let output = fs::File::create(&partial).map_err(|e| format!("Failed to create {}: {}", partial.display(), e))?;
+    zstd::stream::copy_encode(BufReader::new(input), output, LEVEL)
+        .and_then(|()| fs::rename(&partial, target))
+        .map_err(|e| {
+            let _ = fs::remove_file(&partial);
+            format!("Failed to compress {}: {}", source.display(), e)
+        })
+}
+
+/// On-disk zstd copies and the files currently being compressed
+pub struct ZstdCache {
+    /// None if the app cache directory is unavailable (compression disabled)
+    dir: Option<PathBuf>,
+    pending: Arc<Mutex<HashSet<PathBuf>>>,
+}
+
+impl ZstdCache {
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self {
+            dir,
+            pending: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    fn copy_path(&self, source: &Path) -> Option<PathBuf> {
+        let mut hasher = DefaultHasher::new();
+        source.hash(&mut hasher);
+        Some(self.dir.as_ref()?.join(format!("{:016x}.zst", hasher.finish())))
+    }
+
+    /// The compressed copy of a file, if it is current and worth serving
+    pub async fn compressed_copy(&self, source: &Path) -> Option<PathBuf> {
+        let copy = self.copy_path(source)?;
+        let source_meta = tokio::fs::metadata(source).await.ok()?;
+        let copy_meta = tokio::fs::metadata(&copy).await.ok()?;
+        let current = copy_meta.modified().ok()? >= source_meta.modified().ok()?;
+        let smaller = (copy_meta.len() as f64) <= source_meta.len() as f64 * MAX_RATIO;
+        (current && smaller).then_some(copy)
+    }
+
+    /// Files being compressed now
+    pub fn pending_count(&self) -> usize {
+        self.pending.lock().len()
+    }
+
+    /// Compress a file in the background (once at a time per file)
+    pub fn schedule(&self, source: &Path) {
+        let Some(copy) = self.copy_path(source) else {
+            return;
+        };
+        if !self.pending.lock().insert(source.to_path_buf()) {
+            return;
+        }
+        let source = source.to_path_buf();
+        let pending = self.pending.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            let modified = || fs::metadata(&source).and_then(|m| m.modified()).ok();
+            let before = modified();
+            let result = copy
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .map_err(|e| format!("Failed to create zstd cache: {}", e))
+                .and_then(|()| compress_file(&source, &copy));
+            match result {
+                // Changed while compressing: the copy is of an older version
+                Ok(()) if modified() != before => {
+                    let _ = fs::remove_file(&copy);
+                }
+                Ok(()) => {}
+                Err(e) => warn!("[Server] {}", e),
+            }
+            pending.lock().remove(&source);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn negotiates_and_compresses() {
+        let headers = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::ACCEPT_ENCODING, HeaderValue::from_static(value));
+            headers
+        };
+        assert!(accepts_zstd(&headers("gzip, deflate, br, zstd")));
+        assert!(accepts_zstd(&headers("ZSTD;q=0.5")));
+        assert!(!accepts_zstd(&headers("gzip, zstd;q=0")));
+        assert!(!accepts_zstd(&headers("gzip, br")));
+        assert!(!accepts_zstd(&HeaderMap::new()));
+
+        assert!(is_compressible(Path::new("B738/UAL/model.GLB")));
+        assert!(!is_compressible(Path::new("index.html")));
+
+        let dir = std::env::temp_dir().join(format!("zstd-cache-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("data.json");
+        let content = r#"{"type":"FeatureCollection","features":[]}"#.repeat(100);
+        fs::write(&source, &content).unwrap();
+        let target = dir.join("data.zst");
+        compress_file(&source, &target).unwrap();
+        let compressed = fs::read(&target).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(compressed.len() < content.len() / 10);
+        assert_eq!(zstd::decode_all(compressed.as_slice()).unwrap(), content.as_bytes());
+    }
+}
@@ -10,6 +10,7 @@ import CommandInput from './components/UI/CommandInput'
 import TouchControls, { TouchCommandInput } from './components/UI/TouchControls'
 import DeviceOptimizationPrompt from './components/UI/DeviceOptimizationPrompt'
 import MeasuringTool from './components/UI/MeasuringTool'
+import SharedDrawings from './components/UI/SharedDrawings'
 import MetarOverlay from './components/UI/MetarOverlay'
 import DataLoadingOverlay from './components/UI/DataLoadingOverlay'
 import UpdateNotification from './components/UI/UpdateNotification'
@@ -33,6 +34,7 @@ import { useUIFeedbackStore } from './stores/uiFeedbackStore'
 import { useRunwayStore } from './stores/runwayStore'
 import { useVnasStore } from './stores/vnasStore'
 import { useAircraftTimelineStore } from './stores/aircraftTimelineStore'
+import { useReplayStore } from './stores/replayStore'
 import { airportService } from './services/AirportService'
 import { aircraftDimensionsService } from './services/AircraftDimensionsService'
 import { fsltlService } from './services/FSLTLService'
@@ -42,7 +44,26 @@ import { modService } from './services/ModService'
 import { realTrafficService } from './services/RealTrafficService'
 import { isOrbitWithoutAirport } from './utils/viewingContext'
 import { isRemoteMode } from './utils/remoteMode'
+import { applyStartupArgs } from './utils/startupArgs'
 import { usePresenceWebSocket } from './hooks/usePresenceWebSocket'
+import { useSimClock } from './hooks/useSimClock'
+import { useDatablockLayout } from './hooks/useDatablockLayout'
+import { useHostFollow } from './hooks/useHostFollow'
+import { useSelectionSync } from './hooks/useSelectionSync'
+import { useSharedDrawings } from './hooks/useSharedDrawings'
+import { useCoordinationMessages } from './hooks/useCoordinationMessages'
+import { useHostReplay } from './hooks/useHostReplay'
+import { useSquawkAlerts } from './hooks/useSquawkAlerts'
+import { useStcaAlerts } from './hooks/useStcaAlerts'
+import { useLabelLayout } from './hooks/useLabelLayout'
+import { useAlertSounds } from './hooks/useAlertSounds'
+import { useEventReminders } from './hooks/useEventReminders'
+import { useWakeTimers } from './hooks/useWakeTimers'
+import { applyAssignedWindowBookmark, useWindowLayoutBookmark } from './hooks/useWindowLayoutBookmark'
+import { useControlActions } from './hooks/useControlActions'
+import { useViewPresetSuggestions } from './hooks/useViewPresetSuggestions'
+import { useFederation } from './hooks/useFederation'
+import { useReplayWeather } from './hooks/useReplayWeather'
 
 function App() {
   const startPolling = useVatsimStore((state) => state.startPolling)
@@ -100,6 +121,57 @@ function App() {
   // Connect to presence WebSocket in remote mode (registers this client with the server)
   usePresenceWebSocket()
 
+  // Keep the shared simulated clock in sync with the host
+  useSimClock()
+
+  // Keep the shared datablock layout in sync with the host
+  useDatablockLayout()
+
+  // Receive emergency and squawk mismatch alerts from the host
+  useSquawkAlerts()
+
+  // Receive short-term conflict alerts from the host
+  useStcaAlerts()
+
+  // Receive datablock placements from the host
+  useLabelLayout()
+
+  // Play the selected sound pack for conflict, incursion and emergency alerts
+  useAlertSounds()
+
+  // Offer to record VATSIM events at the active airport shortly before they start
+  useEventReminders()
+
+  // Receive departure wake timers from the host
+  useWakeTimers()
+
+  // Follow the aircraft the host follows on every display
+  useHostFollow()
+
+  // Share the selected aircraft with other displays (opt-in)
+  useSelectionSync()
+
+  // Receive lines, labels and polygons drawn on any display
+  useSharedDrawings()
+
+  // Receive coordination messages from other displays
+  useCoordinationMessages()
+
+  // Play the host's replay in sync with every other display
+  useHostReplay()
+
+  // Load camera bookmarks assigned by window layouts
+  useWindowLayoutBookmark()
+
+  // Run actions from control surfaces and system-wide hotkeys
+  useControlActions()
+
+  // Suggest the bookmark tagged for a new runway configuration
+  useViewPresetSuggestions()
+
+  // Mirror the data host's aircraft and settings (federation)
+  useFederation()
+
   const handleViewerReady = useCallback((viewer: Viewer | null) => {
     setCesiumViewer(viewer)
   }, [])
@@ -117,7 +189,7 @@ function App() {
           setLoadingStatus('Checking for previous installation...')
           const migrationResult = await migrateFromElectron()
           if (migrationResult.settingsFound) {
-            console.log('Migrated settings from Electron version')
+            console.log('Migrated settings from Electron version:', migrationResult.migrated.join(', '))
           }
         }
 
@@ -220,6 +292,12 @@ function App() {
 
         setIsLoading(false)
 
+        // Launch straight into the view given on the command line
+        await applyStartupArgs()
+
+        // Cab windows opened by a window layout load their bookmark
+        await applyAssignedWindowBookmark()
+
         // Show token prompt if no Cesium Ion token is set (check global settings)
         const globalToken = useGlobalSettingsStore.getState().cesiumIonToken
         if (!globalToken) {
@@ -310,6 +388,10 @@ function App() {
   // When no airport is selected but orbit-following an aircraft, use nearest METAR mode
   const currentIcao = currentAirport?.icao
   const orbitWithoutAirport = isOrbitWithoutAirport(currentAirport, followMode, followingCallsign)
+  const isReplaying = useReplayStore((state) => state.playbackMode !== 'live')
+
+  // While replaying, show the archived weather for the replayed time instead
+  useReplayWeather(currentIcao ?? null, showWeatherEffects)
 
   useEffect(() => {
     if (!showWeatherEffects) {
@@ -318,6 +400,12 @@ function App() {
       return
     }
 
+    if (isReplaying && currentIcao) {
+      // Live weather would overwrite the replayed weather
+      stopAutoRefresh()
+      return
+    }
+
     if (currentIcao) {
       // Airport selected - use airport's METAR
       fetchWeather(currentIcao)
@@ -334,7 +422,7 @@ function App() {
     return () => {
       stopAutoRefresh()
     }
-  }, [currentIcao, showWeatherEffects, orbitWithoutAirport, fetchWeather, startAutoRefresh, startNearestAutoRefresh, stopAutoRefresh, clearWeather])
+  }, [currentIcao, showWeatherEffects, isReplaying, orbitWithoutAirport, fetchWeather, startAutoRefresh, startNearestAutoRefresh, stopAutoRefresh, clearWeather])
 
   // Register modals with UI feedback store for keyboard blocking
   useEffect(() => {
@@ -485,6 +573,7 @@ function App() {
       {!isVRActive && import.meta.env.DEV && <VnasPanel />}
       {!isVRActive && <AirportSelector />}
       {!isVRActive && <MeasuringTool cesiumViewer={cesiumViewer} />}
+      {!isVRActive && <SharedDrawings cesiumViewer={cesiumViewer} />}
       <PerformanceHUD visible={showPerformanceHUD} />
       {!isVRActive && showModelMatchingModal && (
         <ModelMatchingModal onClose={() => setShowModelMatchingModal(false)} />
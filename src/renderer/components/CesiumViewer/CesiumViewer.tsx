@@ -6,10 +6,12 @@ import { useGlobalSettingsStore } from '../../stores/globalSettingsStore'
 import { useViewportStore } from '../../stores/viewportStore'
 import { useVatsimStore } from '../../stores/vatsimStore'
 import { useWeatherStore } from '../../stores/weatherStore'
-import { useMeasureStore } from '../../stores/measureStore'
+import { useMeasureStore, toDrawingPoint } from '../../stores/measureStore'
+import { finishPolygon, shareDrawing } from '../../hooks/useSharedDrawings'
 import { useAircraftFilterStore } from '../../stores/aircraftFilterStore'
 import { useDatablockPositionStore } from '../../stores/datablockPositionStore'
 import { useUIFeedbackStore } from '../../stores/uiFeedbackStore'
+import { useReplayStore } from '../../stores/replayStore'
 import { useAircraftInterpolation, setInterpolationTerrainData } from '../../hooks/useAircraftInterpolation'
 import { useCesiumCamera } from '../../hooks/useCesiumCamera'
 import { useBabylonOverlay } from '../../hooks/useBabylonOverlay'
@@ -23,12 +25,21 @@ import { useCesiumWeather } from '../../hooks/useCesiumWeather'
 import { useAircraftModels } from '../../hooks/useAircraftModels'
 import { useCesiumLabels } from '../../hooks/useCesiumLabels'
 import { useGroundAircraftTerrain } from '../../hooks/useGroundAircraftTerrain'
+import { useGateAssignments } from '../../hooks/useGateAssignments'
+import { useFrequencyContacts } from '../../hooks/useFrequencyContacts'
 import { useAutoAirportSwitch } from '../../hooks/useAutoAirportSwitch'
+import { useTimelapseCapture } from '../../hooks/useTimelapseCapture'
+import { useStaticObjects } from '../../hooks/useStaticObjects'
+import { useTaxiRoutes } from '../../hooks/useTaxiRoutes'
+import { useLandingPredictions } from '../../hooks/useLandingPredictions'
 import { getTowerPosition } from '../../utils/towerHeight'
+import { getSimulatedTime } from '../../utils/simClock'
 import { performanceMonitor } from '../../utils/performanceMonitor'
 import { hasViewingContext, isOrbitFollowing, isOrbitWithoutAirport } from '../../utils/viewingContext'
 import { getServiceWorkerCacheStats } from '../../utils/serviceWorkerRegistration'
 import { getMemoryCounters } from '../../hooks/useBabylonOverlay'
+import { buildingsApi, mapProvidersApi, tileCacheApi } from '../../utils/tauriApi'
+import type { MapProviderInfo } from '../../types/mapProviders'
 import './CesiumViewer.css'
 
 // Import Cesium CSS
@@ -78,15 +89,35 @@ function CesiumViewer({ viewportId = 'main', isInset = false, onViewerReady }: C
   // Store state
   // Cesium token from global settings (shared across browsers)
   const cesiumIonToken = useGlobalSettingsStore((state) => state.cesiumIonToken)
+  const useTileCache = useGlobalSettingsStore((state) => state.tileCache.enabled)
+  // Serialized so settings refreshes that don't change the providers don't recreate the viewer
+  // (imagery vintages only swap the imagery layer, see below)
+  const mapProviderSettings = useGlobalSettingsStore((state) =>
+    JSON.stringify({ ...state.mapProviders, imageryVintages: undefined }))
+  const [mapProviders, setMapProviders] = useState<MapProviderInfo | null>(null)
   const currentAirport = useAirportStore((state) => state.currentAirport)
+  const imageryVintage = useGlobalSettingsStore((state) =>
+    currentAirport ? state.mapProviders.imageryVintages?.[currentAirport.icao] ?? '' : '')
   const towerHeight = useAirportStore((state) => state.towerHeight)
   const customTowerPosition = useAirportStore((state) => state.customTowerPosition)
   const datablockMode = useGlobalSettingsStore((state) => state.display.datablockMode)
+  const datablockLines = useGlobalSettingsStore((state) => state.datablock.lines)
+  const colorByFrequency = useGlobalSettingsStore((state) => state.datablock.colorByFrequency)
   const terrainQuality = useSettingsStore((state) => state.cesium.terrainQuality)
   const show3DBuildings = useSettingsStore((state) => state.cesium.show3DBuildings)
   const buildingQuality = useSettingsStore((state) => state.cesium.buildingQuality)
+  const buildingSource = useSettingsStore((state) => state.cesium.buildingSource)
   const timeMode = useSettingsStore((state) => state.cesium.timeMode)
   const fixedTimeHour = useSettingsStore((state) => state.cesium.fixedTimeHour)
+  // Shared simulated clock and replay time take precedence over the local time setting
+  const simClock = useGlobalSettingsStore((state) => state.clock)
+  const replayTime = useReplayStore((state) => {
+    if (state.playbackMode === 'live') return null
+    const timestamp = state.getCurrentSnapshot()?.timestamp
+    // Minute resolution is plenty for lighting and avoids re-rendering on every snapshot
+    return timestamp !== undefined ? Math.floor(timestamp / 60000) * 60000 : null
+  })
+  const sharedTimeActive = replayTime !== null || simClock.mode !== 'real'
   const inMemoryTileCacheSize = useSettingsStore((state) => state.memory.inMemoryTileCacheSize)
   const showWeatherEffects = useSettingsStore((state) => state.weather.showWeatherEffects)
   const showCesiumFog = useSettingsStore((state) => state.weather.showCesiumFog)
@@ -128,6 +159,7 @@ function CesiumViewer({ viewportId = 'main', isInset = false, onViewerReady }: C
   const fogDensity = useWeatherStore((state) => state.fogDensity)
   const updateCameraPosition = useWeatherStore((state) => state.updateCameraPosition)
   const cloudLayers = useWeatherStore((state) => state.cloudLayers)
+  const celestial = useGlobalSettingsStore((state) => state.celestial)
   const setUseInterpolation = useWeatherStore((state) => state.setUseInterpolation)
   const startInterpolatedAutoRefresh = useWeatherStore((state) => state.startInterpolatedAutoRefresh)
 
@@ -143,6 +175,8 @@ function CesiumViewer({ viewportId = 'main', isInset = false, onViewerReady }: C
   const completeMeasurement = useMeasureStore((state) => state.completeMeasurement)
   const cancelPendingMeasurement = useMeasureStore((state) => state.cancelPendingMeasurement)
   const removeMeasurement = useMeasureStore((state) => state.removeMeasurement)
+  const measureTool = useMeasureStore((state) => state.tool)
+  const addPolygonPoint = useMeasureStore((state) => state.addPolygonPoint)
 
   // Viewport store for follow highlighting and view mode (read from this viewport)
   const viewports = useViewportStore((state) => state.viewports)
@@ -199,6 +233,20 @@ function CesiumViewer({ viewportId = 'main', isInset = false, onViewerReady }: C
     refAltitudeFeet = (currentAirport.elevation || 0) + (towerHeight / 0.3048)
   }
 
+  // Providers are resolved by the host (remote browsers can't see the keys)
+  useEffect(() => {
+    let cancelled = false
+    mapProvidersApi.getInfo()
+      .then((info) => { if (!cancelled) setMapProviders(info) })
+      .catch((error) => {
+        console.error('[CesiumViewer] Failed to get map providers, using Cesium Ion:', error)
+        if (!cancelled) {
+          setMapProviders({ terrain: 'ion', imagery: 'ion', terrainProxied: false, imageryProxied: false, imageryMaxZoom: 19, hasImageryVintages: false, credits: [] })
+        }
+      })
+    return () => { cancelled = true }
+  }, [mapProviderSettings])
+
   // =========================================================================
   // 1. Core Cesium Viewer Initialization
   // =========================================================================
@@ -221,7 +269,9 @@ function CesiumViewer({ viewportId = 'main', isInset = false, onViewerReady }: C
     shadowFadingEnabled,
     shadowNormalOffset,
     inMemoryTileCacheSize,
-    modelBrightness: builtinModelBrightness  // Initial pool uses built-in brightness
+    modelBrightness: builtinModelBrightness,  // Initial pool uses built-in brightness
+    useTileCache,
+    mapProviders
   })
 
   // =========================================================================
@@ -229,6 +279,26 @@ function CesiumViewer({ viewportId = 'main', isInset = false, onViewerReady }: C
   // =========================================================================
   useTerrainQuality(viewer, terrainQuality, inMemoryTileCacheSize)
 
+  // Imagery vintage chosen for the current airport: swap the base imagery
+  // layer for one requesting that vintage from the host
+  const appliedVintageRef = useRef<{ viewer: Cesium.Viewer | null, vintage: string }>({ viewer: null, vintage: '' })
+  useEffect(() => {
+    if (!viewer || viewer.isDestroyed() || !mapProviders?.hasImageryVintages) return
+    const vintage = imageryVintage.startsWith(`${mapProviders.imagery}-`) ? imageryVintage : ''
+    const applied = appliedVintageRef.current.viewer === viewer ? appliedVintageRef.current.vintage : ''
+    if (vintage === applied) return
+
+    const layers = viewer.imageryLayers
+    const previous = layers.length > 0 ? layers.get(0) : null
+    layers.addImageryProvider(new Cesium.UrlTemplateImageryProvider({
+      url: `${tileCacheApi.getBaseUrl()}imagery/{z}/{x}/{y}${vintage ? `?vintage=${encodeURIComponent(vintage)}` : ''}`,
+      minimumLevel: 1,
+      maximumLevel: mapProviders.imageryMaxZoom
+    }), 0)
+    if (previous) layers.remove(previous)
+    appliedVintageRef.current = { viewer, vintage }
+  }, [viewer, mapProviders, imageryVintage])
+
   // =========================================================================
   // 3. Lighting and Shadow Configuration
   // =========================================================================
@@ -254,7 +324,8 @@ function CesiumViewer({ viewportId = 'main', isInset = false, onViewerReady }: C
   // 3a. Night-Time Darkening (requires enableLighting)
   // =========================================================================
   // Calculate sun elevation angle at camera position
-  const sunElevation = useSunElevation(viewer, { timeMode, fixedTimeHour })
+  // With a shared or replay time the viewer clock already holds the time to use
+  const sunElevation = useSunElevation(viewer, { timeMode: sharedTimeActive ? 'real' : timeMode, fixedTimeHour })
 
   // Darken satellite imagery based on sun position
   useCesiumNightDarkening(viewer, sunElevation, {
@@ -268,6 +339,8 @@ function CesiumViewer({ viewportId = 'main', isInset = false, onViewerReady }: C
   // Babylon.js clouds render on a transparent canvas overlay, so they can't
   // truly block Cesium's stars. Instead, we hide Cesium's skyBox when there's
   // an OVC (overcast) cloud layer that would obscure the sky.
+  // The global celestial settings can also hide the moon and stars or turn off
+  // the sun's glare on every client.
   useEffect(() => {
     if (!viewer || viewer.isDestroyed()) return
 
@@ -276,16 +349,17 @@ function CesiumViewer({ viewportId = 'main', isInset = false, onViewerReady }: C
 
     // Toggle Cesium's star rendering
     if (viewer.scene.skyBox) {
-      viewer.scene.skyBox.show = !hasOvcLayer
+      viewer.scene.skyBox.show = !hasOvcLayer && celestial.showStars
     }
     // Also toggle sun/moon for consistency
     if (viewer.scene.sun) {
       viewer.scene.sun.show = !hasOvcLayer
+      viewer.scene.sun.glowFactor = celestial.sunGlare ? 1.0 : 0.0
     }
     if (viewer.scene.moon) {
-      viewer.scene.moon.show = !hasOvcLayer
+      viewer.scene.moon.show = !hasOvcLayer && celestial.showMoon
     }
-  }, [viewer, cloudLayers])
+  }, [viewer, cloudLayers, celestial])
 
   // =========================================================================
   // 3b. Camera Near Plane (for depth precision)
@@ -372,16 +446,41 @@ function CesiumViewer({ viewportId = 'main', isInset = false, onViewerReady }: C
     canvas: babylonCanvas
   })
 
+  // Answer timelapse frame requests from the host (main viewport only)
+  useTimelapseCapture(viewer, babylonCanvas, viewportId === 'main' && !isInset)
+
+  // Static objects placed at the current airport (mods/placements)
+  useStaticObjects(viewer, currentAirport?.icao?.toUpperCase() ?? null)
+
+  // Predicted taxi routes in the 2D view
+  useTaxiRoutes(viewer, currentAirport?.icao?.toUpperCase() ?? null, viewMode === 'topdown')
+
+  // Predicted touchdown points and turn-offs of arrivals on short final (main viewport only)
+  useLandingPredictions(viewer, currentAirport?.icao?.toUpperCase() ?? null, viewportId === 'main' && !isInset)
+
   // Adjust Babylon.js lighting based on sun position
   useBabylonNightLighting(babylonOverlay?.scene ?? null, sunElevation, {
     enabled: enableNightDarkening && enableLighting
   })
 
+  const gateAssignments = useGateAssignments(
+    currentAirport?.icao?.toUpperCase() ?? null,
+    datablockLines.some((line) => line.includes('gate'))
+  )
+
+  const frequencyContacts = useFrequencyContacts(
+    colorByFrequency || datablockLines.some((line) => line.includes('freq'))
+  )
+
   useCesiumLabels({
     viewer,
     babylonOverlay, // Now passes actual babylonOverlay (may be null initially, but will update)
     interpolatedAircraft, // Culling happens inside the hook on every frame
     datablockMode,
+    datablockLines,
+    gateAssignments,
+    frequencyContacts,
+    colorByFrequency,
     viewMode,
     followingCallsign,
     currentAirportIcao: currentAirport?.icao?.toUpperCase() ?? null,
@@ -493,11 +592,19 @@ function CesiumViewer({ viewportId = 'main', isInset = false, onViewerReady }: C
     ;(viewer as any).targetFrameRate = maxFramerate > 0 ? maxFramerate : undefined
   }, [viewer, maxFramerate])
 
-  // Time of day control (real time vs fixed time)
+  // Time of day control (replay time, shared simulated clock, real time or fixed time)
   useEffect(() => {
     if (!viewer) return
 
-    if (timeMode === 'fixed' && currentAirport) {
+    if (replayTime !== null) {
+      // Replays render at the recorded time
+      viewer.clock.currentTime = Cesium.JulianDate.fromDate(new Date(replayTime))
+      viewer.clock.shouldAnimate = false
+    } else if (simClock.mode !== 'real') {
+      // Shared simulated clock (same lighting on the host and every remote browser)
+      viewer.clock.currentTime = Cesium.JulianDate.fromDate(new Date(getSimulatedTime(simClock)))
+      viewer.clock.shouldAnimate = simClock.mode === 'offset'
+    } else if (timeMode === 'fixed' && currentAirport) {
       // Calculate the specified local time at the tower location
       const towerPos = getTowerPosition(currentAirport, towerHeight, customTowerPosition ?? undefined)
       const now = new Date()
@@ -518,10 +625,11 @@ function CesiumViewer({ viewportId = 'main', isInset = false, onViewerReady }: C
       viewer.clock.currentTime = Cesium.JulianDate.now()
       viewer.clock.shouldAnimate = true
     }
-  }, [viewer, timeMode, fixedTimeHour, currentAirport, towerHeight, customTowerPosition])
+  }, [viewer, timeMode, fixedTimeHour, currentAirport, towerHeight, customTowerPosition, simClock, replayTime])
 
   // Manage OSM 3D Buildings tileset
   // Skip loading buildings for inset viewports to reduce memory usage and prevent WebGL context issues
+  const buildingsAirport = buildingSource === 'osm' ? currentAirport?.icao ?? null : null
   useEffect(() => {
     if (!viewer) return
     // Skip buildings for insets - they use reduced quality and don't need buildings
@@ -533,7 +641,29 @@ function CesiumViewer({ viewportId = 'main', isInset = false, onViewerReady }: C
     const loadBuildings = async () => {
       if (show3DBuildings) {
         try {
-          const tileset = await Cesium.createOsmBuildingsAsync()
+          let tileset: Cesium.Cesium3DTileset
+          if (buildingSource === 'osm') {
+            // Generated by the host from OpenStreetMap footprints around the airport
+            if (!buildingsAirport) return
+            const info = await buildingsApi.get(buildingsAirport)
+            if (isCancelled) return
+            tileset = await Cesium.Cesium3DTileset.fromUrl(buildingsApi.getTilesetUrl(info.icao), {
+              credit: info.attribution
+            })
+            if (isCancelled) return
+
+            // The tileset's ground level is the airport elevation; move it onto this terrain
+            const origin = Cesium.Cartographic.fromDegrees(info.origin.longitude, info.origin.latitude)
+            const [ground] = await Cesium.sampleTerrainMostDetailed(viewer.terrainProvider, [origin])
+            if (isCancelled) return
+            const offset = (ground.height ?? info.origin.elevationM) - info.origin.elevationM
+            const up = Cesium.Ellipsoid.WGS84.geodeticSurfaceNormalCartographic(origin)
+            tileset.modelMatrix = Cesium.Matrix4.fromTranslation(
+              Cesium.Cartesian3.multiplyByScalar(up, offset, new Cesium.Cartesian3())
+            )
+          } else {
+            tileset = await Cesium.createOsmBuildingsAsync()
+          }
           if (isCancelled) return
 
           // Configure LOD based on buildingQuality setting
@@ -571,7 +701,7 @@ function CesiumViewer({ viewportId = 'main', isInset = false, onViewerReady }: C
         setBuildingsTileset(null)
       }
     }
-  }, [viewer, show3DBuildings, buildingQuality, isInset, aircraftShadowsOnly])
+  }, [viewer, show3DBuildings, buildingQuality, buildingSource, buildingsAirport, isInset, aircraftShadowsOnly])
 
   // Update building shadows when aircraftShadowsOnly changes
   useEffect(() => {
@@ -843,14 +973,37 @@ function CesiumViewer({ viewportId = 'main', isInset = false, onViewerReady }: C
       return null
     }
 
-    // Left-click: Set pending point or complete measurement
+    // Left-click: Set pending point or complete measurement (or draw on every display)
     handler.setInputAction((click: { position: Cesium.Cartesian2 }) => {
       const point = pickTerrainPosition(click.position)
       if (!point) return
 
+      if (measureTool === 'label') {
+        // Place the label text typed in the measuring panel
+        const { labelText, drawColor } = useMeasureStore.getState()
+        if (labelText.trim()) {
+          shareDrawing({ kind: 'label', points: [toDrawingPoint(point)], text: labelText, color: drawColor })
+        }
+        return
+      }
+
+      if (measureTool === 'polygon') {
+        // Corners are collected until the polygon is finished in the panel (or with Enter)
+        addPolygonPoint(point)
+        return
+      }
+
       if (!pendingPoint) {
         // First click - set pending point
         setPendingPoint(point)
+      } else if (measureTool === 'line') {
+        // Second click - draw the range/bearing line on every display
+        shareDrawing({
+          kind: 'line',
+          points: [toDrawingPoint(pendingPoint), toDrawingPoint(point)],
+          color: useMeasureStore.getState().drawColor
+        })
+        cancelPendingMeasurement()
       } else {
         // Second click - complete the measurement
         completeMeasurement(point)
@@ -876,10 +1029,12 @@ function CesiumViewer({ viewportId = 'main', isInset = false, onViewerReady }: C
       }
     }, Cesium.ScreenSpaceEventType.RIGHT_CLICK)
 
-    // Escape key: Cancel pending measurement
+    // Escape key: Cancel pending measurement; Enter: finish the polygon being drawn
     const handleKeyDown = (event: KeyboardEvent) => {
-      if (event.key === 'Escape' && pendingPoint) {
+      if (event.key === 'Escape') {
         cancelPendingMeasurement()
+      } else if (event.key === 'Enter' && measureTool === 'polygon') {
+        finishPolygon()
       }
     }
     window.addEventListener('keydown', handleKeyDown)
@@ -888,7 +1043,7 @@ function CesiumViewer({ viewportId = 'main', isInset = false, onViewerReady }: C
       handler.destroy()
       window.removeEventListener('keydown', handleKeyDown)
     }
-  }, [viewer, isMeasuring, measurements, pendingPoint, setPendingPoint, setPreviewPoint, completeMeasurement, cancelPendingMeasurement, removeMeasurement])
+  }, [viewer, isMeasuring, measureTool, measurements, pendingPoint, setPendingPoint, setPreviewPoint, completeMeasurement, cancelPendingMeasurement, removeMeasurement, addPolygonPoint])
 
   // Datablock SLEW mode - click on aircraft to move its label position
   const pendingDirection = useDatablockPositionStore((state) => state.pendingDirection)
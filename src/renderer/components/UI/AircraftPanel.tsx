@@ -4,9 +4,11 @@ import { useSettingsStore } from '../../stores/settingsStore'
 import { useAircraftFilterStore } from '../../stores/aircraftFilterStore'
 import { useRunwayStore } from '../../stores/runwayStore'
 import { useVnasStore } from '../../stores/vnasStore'
+import { useSelectionStore } from '../../stores/selectionStore'
 import { useActiveViewportCamera } from '../../hooks/useActiveViewportCamera'
 import { useAircraftInterpolation } from '../../hooks/useAircraftInterpolation'
 import { useAircraftFiltering } from '../../hooks/useAircraftFiltering'
+import { shareSelection } from '../../hooks/useSelectionSync'
 import { calculateBearing, calculateDistanceNM } from '../../utils/interpolation'
 import { formatAltitude, formatGroundspeed, formatHeading, getTowerPosition } from '../../utils/towerHeight'
 import { applyPositionOffsets, calculatePitchToTarget } from '../../utils/cameraGeometry'
@@ -156,6 +158,9 @@ function AircraftPanel() {
     positionOffsetZ
   } = useActiveViewportCamera()
 
+  // Aircraft selected on another display (when selection sync is on)
+  const sharedSelection = useSelectionStore((state) => state.selection)
+
   // Custom tower position for bearing calculation
   const customTowerPosition = useAirportStore((state) => state.customTowerPosition)
 
@@ -290,6 +295,7 @@ function AircraftPanel() {
    * Uses the actual camera position (tower + WASD offsets) to calculate accurate bearing.
    */
   const handleLookAt = (aircraft: AircraftListItem) => {
+    shareSelection(aircraft.callsign)
     if (!currentAirport) return
 
     // Get the aircraft's current interpolated position (real-time, not cached)
@@ -486,12 +492,13 @@ function AircraftPanel() {
         ) : (
           nearbyAircraft.map((aircraft) => {
             const isFollowing = followingCallsign === aircraft.callsign
+            const isShared = sharedSelection?.callsign === aircraft.callsign
             const phaseLabel = aircraft.phase ? getPhaseLabel(aircraft.phase) : null
             const tierClass = aircraft.tier ? getTierClass(aircraft.tier) : ''
             return (
               <div
                 key={aircraft.callsign}
-                className={`aircraft-item ${isFollowing ? 'following' : ''} ${tierClass} clickable`}
+                className={`aircraft-item ${isFollowing ? 'following' : ''} ${isShared ? 'shared-selection' : ''} ${tierClass} clickable`}
                 onClick={() => handleLookAt(aircraft)}
                 title="Click to look at aircraft"
               >
@@ -505,6 +512,11 @@ function AircraftPanel() {
                       </span>
                     )}
                     <span className="callsign">{aircraft.callsign}</span>
+                    {isShared && sharedSelection?.source && (
+                      <span className="selection-source" title="Selected on another display">
+                        {sharedSelection.source}
+                      </span>
+                    )}
                     {phaseLabel && (
                       <span className={`phase-badge ${tierClass}`}>
                         {phaseLabel}
@@ -2,9 +2,26 @@ import { useState, useEffect, useRef, useCallback } from 'react'
 import { useAirportStore } from '../../stores/airportStore'
 import { useGlobalSettingsStore } from '../../stores/globalSettingsStore'
 import { useVnasStore } from '../../stores/vnasStore'
-import type { Airport } from '../../types/airport'
+import { airportsApi } from '../../utils/tauriApi'
+import type { Airport, AirportDbRecord } from '../../types/airport'
 import './AirportSelector.css'
 
+/** Convert a host airport database record to the frontend airport shape */
+function toAirport(record: AirportDbRecord): Airport {
+  return {
+    icao: record.icao,
+    iata: record.iata,
+    name: record.name,
+    city: record.city,
+    state: record.state,
+    country: record.country,
+    elevation: record.elevationFt,
+    lat: record.latitude,
+    lon: record.longitude,
+    tz: record.tz
+  }
+}
+
 function AirportSelector() {
   const isOpen = useAirportStore((state) => state.isAirportSelectorOpen)
   const setOpen = useAirportStore((state) => state.setAirportSelectorOpen)
@@ -26,13 +43,26 @@ function AirportSelector() {
     }
   }, [isOpen])
 
-  // Search as user types
+  // Search as user types: instant local results, then the host's ranked fuzzy search
   useEffect(() => {
-    if (query.trim()) {
-      const searchResults = searchAirports(query)
-      setResults(searchResults)
-    } else {
+    if (!query.trim()) {
       setResults([])
+      return
+    }
+
+    setResults(searchAirports(query))
+
+    let cancelled = false
+    airportsApi.search(query, 50)
+      .then((hostResults) => {
+        if (!cancelled) setResults(hostResults.map(toAirport))
+      })
+      .catch((error) => {
+        console.warn('[AirportSelector] Host airport search unavailable, using local results:', error)
+      })
+
+    return () => {
+      cancelled = true
     }
   }, [query, searchAirports])
 
@@ -0,0 +1,145 @@
+/**
+ * Backup Panel
+ *
+ * Creates backups of the app data folder (settings, bookmarks, layouts,
+ * video maps, tower positions, VMR files) and restores them. The host
+ * (backup.rs) verifies every file of a backup before restoring it and backs
+ * up the current data first; the app restarts after a restore.
+ */
+
+import { useState, useEffect, useCallback } from 'react'
+import { appApi, isTauri } from '../../utils/tauriApi'
+import type { BackupInfo } from '../../utils/tauriApi'
+import * as fsltlApi from '../../services/fsltlApi'
+import CollapsibleSection from './settings/CollapsibleSection'
+import './BackupPanel.css'
+
+const formatBytes = (bytes: number): string => {
+  if (bytes < 1024) return `${bytes} B`
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`
+}
+
+function BackupPanel() {
+  const [backups, setBackups] = useState<BackupInfo[]>([])
+  const [includeLogs, setIncludeLogs] = useState(false)
+  const [busy, setBusy] = useState<string | null>(null)
+  const [message, setMessage] = useState<string | null>(null)
+  const [error, setError] = useState<string | null>(null)
+
+  const refresh = useCallback(() => {
+    appApi.listBackups().then(setBackups).catch((err) => setError(String(err)))
+  }, [])
+
+  useEffect(() => {
+    refresh()
+  }, [refresh])
+
+  const run = async (label: string, action: () => Promise<string | null>) => {
+    setBusy(label)
+    setError(null)
+    setMessage(null)
+    try {
+      setMessage(await action())
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err))
+    } finally {
+      setBusy(null)
+      refresh()
+    }
+  }
+
+  const handleCreate = (pickDestination: boolean) => run('Backing up...', async () => {
+    let destination: string | undefined
+    if (pickDestination) {
+      const folder = await fsltlApi.pickFolder()
+      if (!folder) return null
+      destination = folder
+    }
+    const backup = await appApi.createBackup(includeLogs, destination)
+    return `Backed up ${backup.fileCount ?? 0} files to ${backup.path}`
+  })
+
+  const handleVerify = (path: string) => run('Verifying...', async () => {
+    const backup = await appApi.verifyBackup(path)
+    return `${backup.fileName} is intact (${backup.fileCount ?? 0} files, v${backup.appVersion})`
+  })
+
+  const handleRestore = (path?: string) => run('Restoring...', async () => {
+    const target = path ?? await appApi.pickBackupFile()
+    if (!target) return null
+    const confirmed = window.confirm(
+      'Restore this backup?\n\n' +
+      'Your current settings, bookmarks, layouts and tower positions will be replaced. ' +
+      'They are backed up first, so the restore can be undone. The app restarts afterwards.'
+    )
+    if (!confirmed) return null
+    await appApi.restoreBackup(target)
+    const { relaunch } = await import('@tauri-apps/plugin-process')
+    await relaunch()
+    return null
+  })
+
+  if (!isTauri()) {
+    return null
+  }
+
+  return (
+    <CollapsibleSection title="Backup & Restore">
+      <p className="setting-hint" style={{ marginBottom: '12px' }}>
+        Back up settings, bookmarks, layouts, video maps, tower positions and model matching rules,
+        e.g. before an event. Caches and downloaded content are not included.
+      </p>
+
+      <div className="setting-item">
+        <label className="setting-label">
+          <input
+            type="checkbox"
+            checked={includeLogs}
+            onChange={(e) => setIncludeLogs(e.target.checked)}
+          />
+          Include logs and crash reports
+        </label>
+      </div>
+
+      <div className="setting-item">
+        <div className="setting-row">
+          <button className="control-button" onClick={() => handleCreate(false)} disabled={busy !== null}>
+            Back Up Now
+          </button>
+          <button className="control-button" onClick={() => handleCreate(true)} disabled={busy !== null}>
+            Back Up To...
+          </button>
+          <button className="control-button" onClick={() => handleRestore()} disabled={busy !== null}>
+            Restore From File...
+          </button>
+        </div>
+        {busy && <p className="setting-hint">{busy}</p>}
+        {message && <p className="setting-hint backup-message">{message}</p>}
+        {error && <p className="setting-hint backup-error">{error}</p>}
+      </div>
+
+      {backups.length > 0 && (
+        <div className="setting-item">
+          <label>Backups</label>
+          {backups.map((backup) => (
+            <div key={backup.path} className="setting-row">
+              <span className="setting-hint backup-name">
+                {new Date(backup.createdAt).toLocaleString()} - {formatBytes(backup.size)}
+                {backup.fileName.includes('-pre-restore') && ' (before restore)'}
+              </span>
+              <button className="control-button" onClick={() => handleVerify(backup.path)} disabled={busy !== null}>
+                Verify
+              </button>
+              <button className="control-button" onClick={() => handleRestore(backup.path)} disabled={busy !== null}>
+                Restore
+              </button>
+            </div>
+          ))}
+        </div>
+      )}
+    </CollapsibleSection>
+  )
+}
+
+export default BackupPanel
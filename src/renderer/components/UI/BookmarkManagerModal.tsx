@@ -12,6 +12,7 @@ function BookmarkManagerModal({ onClose }: BookmarkManagerModalProps) {
   const loadBookmark = useViewportStore((state) => state.loadBookmark)
   const deleteBookmark = useViewportStore((state) => state.deleteBookmark)
   const renameBookmark = useViewportStore((state) => state.renameBookmark)
+  const setBookmarkRunways = useViewportStore((state) => state.setBookmarkRunways)
   const currentAirportIcao = useViewportStore((state) => state.currentAirportIcao)
   const showFeedback = useUIFeedbackStore((state) => state.showFeedback)
 
@@ -25,6 +26,8 @@ function BookmarkManagerModal({ onClose }: BookmarkManagerModalProps) {
   const [selectedSlot, setSelectedSlot] = useState<number>(0)
   const [editingSlot, setEditingSlot] = useState<number | null>(null)
   const [editingName, setEditingName] = useState('')
+  // Whether the edit input renames the bookmark or tags its runways
+  const [editingField, setEditingField] = useState<'name' | 'runways'>('name')
   const listRef = useRef<HTMLDivElement>(null)
   const inputRef = useRef<HTMLInputElement>(null)
 
@@ -37,12 +40,24 @@ function BookmarkManagerModal({ onClose }: BookmarkManagerModalProps) {
   const handleSaveRename = useCallback(() => {
     if (editingSlot === null) return
     const bookmark = bookmarks?.[editingSlot]
-    if (bookmark) {
+    const label = editingSlot.toString().padStart(2, '0')
+    if (bookmark && editingField === 'runways') {
+      setBookmarkRunways(editingSlot, editingName.split(/[\s,/]+/))
+      showFeedback(`Tagged bookmark .${label} with runways`, 'success')
+    } else if (bookmark) {
       renameBookmark(editingSlot, editingName.trim() || undefined)
-      showFeedback(`Renamed bookmark .${editingSlot.toString().padStart(2, '0')}`, 'success')
+      showFeedback(`Renamed bookmark .${label}`, 'success')
     }
     setEditingSlot(null)
-  }, [editingSlot, editingName, bookmarks, renameBookmark, showFeedback])
+  }, [editingSlot, editingName, editingField, bookmarks, renameBookmark, setBookmarkRunways, showFeedback])
+
+  const startEditing = useCallback((slot: number, field: 'name' | 'runways') => {
+    setEditingSlot(slot)
+    setEditingField(field)
+    setEditingName(field === 'runways'
+      ? (bookmarks?.[slot]?.runways ?? []).join(' ')
+      : bookmarks?.[slot]?.name || '')
+  }, [bookmarks])
 
   // Keyboard navigation
   useEffect(() => {
@@ -108,8 +123,14 @@ function BookmarkManagerModal({ onClose }: BookmarkManagerModalProps) {
         case 'R':
           e.preventDefault()
           if (bookmarks?.[selectedSlot]) {
-            setEditingSlot(selectedSlot)
-            setEditingName(bookmarks[selectedSlot].name || '')
+            startEditing(selectedSlot, 'name')
+          }
+          break
+        case 't':
+        case 'T':
+          e.preventDefault()
+          if (bookmarks?.[selectedSlot]) {
+            startEditing(selectedSlot, 'runways')
           }
           break
         case 'Escape':
@@ -121,7 +142,7 @@ function BookmarkManagerModal({ onClose }: BookmarkManagerModalProps) {
 
     window.addEventListener('keydown', handleKeyDown)
     return () => window.removeEventListener('keydown', handleKeyDown)
-  }, [selectedSlot, editingSlot, bookmarks, loadBookmark, deleteBookmark, showFeedback, onClose, handleSaveRename])
+  }, [selectedSlot, editingSlot, bookmarks, loadBookmark, deleteBookmark, showFeedback, onClose, handleSaveRename, startEditing])
 
   // Scroll selected slot into view
   useEffect(() => {
@@ -153,8 +174,12 @@ function BookmarkManagerModal({ onClose }: BookmarkManagerModalProps) {
 
   const handleRename = (slot: number, e: React.MouseEvent) => {
     e.stopPropagation()
-    setEditingSlot(slot)
-    setEditingName(bookmarks?.[slot]?.name || '')
+    startEditing(slot, 'name')
+  }
+
+  const handleTagRunways = (slot: number, e: React.MouseEvent) => {
+    e.stopPropagation()
+    startEditing(slot, 'runways')
   }
 
   const formatViewMode = (bookmark: CameraBookmark) => {
@@ -187,6 +212,7 @@ function BookmarkManagerModal({ onClose }: BookmarkManagerModalProps) {
           <span><kbd>&uarr;</kbd><kbd>&darr;</kbd> Navigate</span>
           <span><kbd>Enter</kbd> Load</span>
           <span><kbd>R</kbd> Rename</span>
+          <span><kbd>T</kbd> Runways</span>
           <span><kbd>Del</kbd> Delete</span>
           <span><kbd>Esc</kbd> Close</span>
         </div>
@@ -221,7 +247,7 @@ function BookmarkManagerModal({ onClose }: BookmarkManagerModalProps) {
                     }
                     e.stopPropagation()
                   }}
-                  placeholder="Enter name..."
+                  placeholder={editingField === 'runways' ? 'Runways, e.g. 04L 04R 09' : 'Enter name...'}
                 />
               ) : (
                 <span className="bookmark-name">
@@ -233,6 +259,7 @@ function BookmarkManagerModal({ onClose }: BookmarkManagerModalProps) {
                 <>
                   <span className="bookmark-preview">
                     {formatViewMode(bookmark)} | HDG {formatHeading(bookmark.heading)} | PIT {formatPitch(bookmark.pitch)} | FOV {Math.round(bookmark.fov)}&deg;
+                    {bookmark.runways?.length ? ` | RWY ${bookmark.runways.join(' ')}` : ''}
                   </span>
 
                   <div className="bookmark-actions">
@@ -255,6 +282,16 @@ function BookmarkManagerModal({ onClose }: BookmarkManagerModalProps) {
                         <path d="M18.5 2.5a2.121 2.121 0 0 1 3 3L12 15l-4 1 1-4 9.5-9.5z" />
                       </svg>
                     </button>
+                    <button
+                      className="bookmark-action-btn"
+                      onClick={(e) => handleTagRunways(slot, e)}
+                      title="Runway configuration (T)"
+                    >
+                      <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
+                        <path d="M20.59 13.41l-7.17 7.17a2 2 0 0 1-2.83 0L2 12V2h10l8.59 8.59a2 2 0 0 1 0 2.82z" />
+                        <line x1="7" y1="7" x2="7.01" y2="7" />
+                      </svg>
+                    </button>
                     <button
                       className="bookmark-action-btn delete"
                       onClick={(e) => handleDelete(slot, e)}
@@ -0,0 +1,151 @@
+/**
+ * Content Packs Panel
+ *
+ * Lists the content packs (VMR rules, tower positions, video maps, airport
+ * data) published in the configured index files, with per-pack opt-in.
+ * Opted-in packs are installed and kept up to date by the host
+ * (content_packs.rs), checked on startup and with "Check Now".
+ */
+
+import { useState, useEffect, useCallback } from 'react'
+import { appApi, isTauri } from '../../utils/tauriApi'
+import type { ContentPackKind, ContentPacks } from '../../utils/tauriApi'
+import { useGlobalSettingsStore } from '../../stores/globalSettingsStore'
+import CollapsibleSection from './settings/CollapsibleSection'
+import './ContentPacksPanel.css'
+
+const KIND_LABELS: Record<ContentPackKind, string> = {
+  'vmr': 'Model matching rules',
+  'tower-positions': 'Tower positions',
+  'video-maps': 'Video maps',
+  'airport-db': 'Airport database'
+}
+
+function ContentPacksPanel() {
+  const contentPacks = useGlobalSettingsStore((state) => state.contentPacks)
+  const updateContentPacks = useGlobalSettingsStore((state) => state.updateContentPacks)
+  const refreshGlobalSettings = useGlobalSettingsStore((state) => state.refresh)
+  const [packs, setPacks] = useState<ContentPacks | null>(null)
+  const [busy, setBusy] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+  const [newIndexUrl, setNewIndexUrl] = useState('')
+
+  useEffect(() => {
+    appApi.listContentPacks().then(setPacks).catch((err) => setError(String(err)))
+  }, [])
+
+  const run = useCallback(async (action: () => Promise<ContentPacks>) => {
+    setBusy(true)
+    setError(null)
+    try {
+      setPacks(await action())
+      await refreshGlobalSettings()
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err))
+    } finally {
+      setBusy(false)
+    }
+  }, [refreshGlobalSettings])
+
+  const handleAddIndex = async () => {
+    const url = newIndexUrl.trim()
+    if (!url || contentPacks.indexUrls.includes(url)) return
+    await updateContentPacks({ indexUrls: [...contentPacks.indexUrls, url] })
+    setNewIndexUrl('')
+    await run(appApi.checkContentPacks)
+  }
+
+  const handleRemoveIndex = async (url: string) => {
+    await updateContentPacks({ indexUrls: contentPacks.indexUrls.filter((u) => u !== url) })
+  }
+
+  if (!isTauri()) {
+    return null
+  }
+
+  return (
+    <CollapsibleSection title="Content Packs">
+      <p className="setting-hint" style={{ marginBottom: '12px' }}>
+        Model matching rules, tower positions, video maps and airport data published online.
+        Packs you enable are downloaded, verified and kept up to date automatically.
+      </p>
+
+      <div className="setting-item">
+        <label>Pack Indexes</label>
+        {contentPacks.indexUrls.map((url) => (
+          <div key={url} className="setting-row">
+            <span className="setting-hint content-pack-url">
+              {url}
+              {packs?.indexErrors[url] && <span className="content-pack-error"> - {packs.indexErrors[url]}</span>}
+            </span>
+            <button className="control-button" onClick={() => handleRemoveIndex(url)} disabled={busy}>
+              Remove
+            </button>
+          </div>
+        ))}
+        <div className="setting-row">
+          <input
+            type="url"
+            placeholder="https://example.com/towercab-packs/index.json"
+            value={newIndexUrl}
+            onChange={(e) => setNewIndexUrl(e.target.value)}
+            style={{ flex: 1 }}
+          />
+          <button className="control-button" onClick={handleAddIndex} disabled={busy || !newIndexUrl.trim()}>
+            Add
+          </button>
+        </div>
+      </div>
+
+      <div className="setting-item">
+        <label className="setting-label">
+          <input
+            type="checkbox"
+            checked={contentPacks.checkOnStartup}
+            onChange={(e) => updateContentPacks({ checkOnStartup: e.target.checked })}
+          />
+          Check for pack updates on startup
+        </label>
+      </div>
+
+      <div className="setting-item">
+        <div className="setting-row">
+          <label>Available Packs</label>
+          <button className="control-button" onClick={() => run(appApi.checkContentPacks)} disabled={busy}>
+            {busy ? 'Checking...' : 'Check Now'}
+          </button>
+        </div>
+        {packs && packs.packs.length === 0 && (
+          <p className="setting-hint">No packs found. Add an index and check for packs.</p>
+        )}
+        {packs?.packs.map((pack) => (
+          <div key={pack.id} className="setting-item">
+            <label className="setting-label">
+              <input
+                type="checkbox"
+                checked={pack.enabled}
+                disabled={busy}
+                onChange={(e) => run(() => appApi.setContentPackEnabled(pack.id, e.target.checked))}
+              />
+              {pack.name}
+            </label>
+            <p className="setting-hint">
+              {KIND_LABELS[pack.kind] ?? pack.kind}
+              {pack.installedVersion ? ` - v${pack.installedVersion} installed` : ''}
+              {pack.version && pack.installedVersion && pack.version !== pack.installedVersion
+                ? ` (v${pack.version} available)`
+                : ''}
+              {!pack.indexUrl && ' - no longer listed'}
+            </p>
+            {pack.description && <p className="setting-hint">{pack.description}</p>}
+            {pack.error && <p className="setting-hint content-pack-error">{pack.error}</p>}
+          </div>
+        ))}
+      </div>
+
+      {error && <p className="setting-hint content-pack-error">{error}</p>}
+    </CollapsibleSection>
+  )
+}
+
+export default ContentPacksPanel
@@ -310,11 +310,14 @@ function ControlsBar() {
     // Use the migration service to re-attempt Electron migration
     const { migrateFromElectron } = await import('../../services/MigrationService')
 
-    // Reset migration flag to allow re-migration
-    localStorage.removeItem('electron-migration-complete')
+    const result = await migrateFromElectron(true)
+    if (!result.success) {
+      throw new Error(result.message)
+    }
+    if (result.settingsFound) {
+      const skipped = result.skipped.length > 0 ? `\n\nSkipped:\n- ${result.skipped.join('\n- ')}` : ''
+      alert(`Imported from the Electron version:\n- ${result.migrated.join('\n- ')}${skipped}`)
 
-    const migrated = await migrateFromElectron()
-    if (migrated) {
       // Close the import modal and show success
       setShowImportModal(false)
       setImportStatus('success')
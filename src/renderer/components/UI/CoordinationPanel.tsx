@@ -0,0 +1,112 @@
+/**
+ * Coordination Panel
+ *
+ * Top bar button and drop-down for the messages displays send each other
+ * (see coordination.rs): the history with sender and time, one-click canned
+ * messages and a free-text box. The button shows how many messages arrived
+ * while the panel was closed.
+ */
+
+import { useEffect, useRef, useState } from 'react'
+import { useCoordinationStore } from '../../stores/coordinationStore'
+import { sendCoordinationMessage } from '../../hooks/useCoordinationMessages'
+import './CoordinationPanel.css'
+
+/** One-click messages */
+const CANNED_MESSAGES = [
+  'RWY change in 10',
+  'Break please',
+  'Relief briefing',
+  'Check frequency',
+  'Release?',
+  'Copy'
+]
+
+function formatZulu(ms: number): string {
+  const date = new Date(ms)
+  return `${String(date.getUTCHours()).padStart(2, '0')}${String(date.getUTCMinutes()).padStart(2, '0')}Z`
+}
+
+function CoordinationPanel() {
+  const messages = useCoordinationStore((state) => state.messages)
+  const unreadCount = useCoordinationStore((state) => state.unreadCount)
+  const isOpen = useCoordinationStore((state) => state.isOpen)
+  const setOpen = useCoordinationStore((state) => state.setOpen)
+  const [text, setText] = useState('')
+  const [error, setError] = useState<string | null>(null)
+  const listRef = useRef<HTMLDivElement>(null)
+
+  // Keep the newest message in view
+  useEffect(() => {
+    if (isOpen && listRef.current) listRef.current.scrollTop = listRef.current.scrollHeight
+  }, [isOpen, messages])
+
+  const send = (message: string) => {
+    if (!message.trim()) return
+    setError(null)
+    sendCoordinationMessage(message.trim())
+      .then(() => setText(''))
+      .catch((e) => setError(e instanceof Error ? e.message : String(e)))
+  }
+
+  return (
+    <div className="coordination">
+      <button
+        className={`coordination-button ${unreadCount > 0 ? 'unread' : ''}`}
+        onClick={() => setOpen(!isOpen)}
+        title="Coordination messages"
+      >
+        <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
+          <path d="M21 15a2 2 0 0 1-2 2H7l-4 4V5a2 2 0 0 1 2-2h14a2 2 0 0 1 2 2z" />
+        </svg>
+        {unreadCount > 0 && <span className="coordination-unread">{unreadCount}</span>}
+      </button>
+
+      {isOpen && (
+        <div className="coordination-panel">
+          <div className="coordination-messages" ref={listRef}>
+            {messages.length === 0 && <div className="coordination-empty">No messages yet</div>}
+            {messages.map((message) => (
+              <div key={`${message.id}@${message.sentAt}`} className="coordination-message">
+                <span className="coordination-time">{formatZulu(message.sentAt)}</span>
+                {message.from && <span className="coordination-from">{message.from}</span>}
+                <span className="coordination-text">{message.text}</span>
+              </div>
+            ))}
+          </div>
+
+          <div className="coordination-canned">
+            {CANNED_MESSAGES.map((canned) => (
+              <button key={canned} onClick={() => send(canned)}>
+                {canned}
+              </button>
+            ))}
+          </div>
+
+          <form
+            className="coordination-compose"
+            onSubmit={(e) => {
+              e.preventDefault()
+              send(text)
+            }}
+          >
+            <input
+              type="text"
+              value={text}
+              maxLength={500}
+              placeholder="Message all displays"
+              onChange={(e) => setText(e.target.value)}
+              onKeyDown={(e) => e.stopPropagation()}
+            />
+            <button type="submit" disabled={!text.trim()}>
+              Send
+            </button>
+          </form>
+          {error && <div className="coordination-error">{error}</div>}
+        </div>
+      )}
+    </div>
+  )
+}
+
+export default CoordinationPanel
@@ -4,11 +4,29 @@ import { useVatsimStore } from '../../stores/vatsimStore'
 import { useVnasStore } from '../../stores/vnasStore'
 import { useAirportStore } from '../../stores/airportStore'
 import { useGlobalSettingsStore } from '../../stores/globalSettingsStore'
+import { useViewportStore } from '../../stores/viewportStore'
 import { useActiveViewportCamera } from '../../hooks/useActiveViewportCamera'
+import { searchApi, type SearchKind, type SearchResult as ContentResult } from '../../utils/tauriApi'
 import { calculateDistanceNM } from '../../utils/interpolation'
 import { getTowerPosition } from '../../utils/towerHeight'
 import './GlobalSearchPanel.css'
 
+/** Content searched on the host (aircraft are searched locally across all of VATSIM) */
+const CONTENT_KINDS: SearchKind[] = ['airport', 'bookmark', 'mod', 'model']
+
+const CONTENT_LIMIT = 8
+
+/** Wait for typing to pause before asking the host */
+const CONTENT_SEARCH_DEBOUNCE_MS = 150
+
+const KIND_LABELS: Record<SearchKind, string> = {
+  airport: 'Airport',
+  bookmark: 'Bookmark',
+  mod: 'Mod',
+  model: 'Model',
+  aircraft: 'Aircraft'
+}
+
 interface SearchResult {
   callsign: string
   aircraftType: string | null
@@ -25,6 +43,7 @@ interface SearchResult {
 function GlobalSearchPanel() {
   const [isOpen, setIsOpen] = useState(false)
   const [query, setQuery] = useState('')
+  const [contentResults, setContentResults] = useState<ContentResult[]>([])
   const inputRef = useRef<HTMLInputElement>(null)
 
   const allPilots = useVatsimStore((state) => state.allPilots)
@@ -32,6 +51,9 @@ function GlobalSearchPanel() {
 
   // Airport and camera position for distance calculation
   const currentAirport = useAirportStore((state) => state.currentAirport)
+  const airports = useAirportStore((state) => state.airports)
+  const selectAirport = useAirportStore((state) => state.selectAirport)
+  const loadBookmark = useViewportStore((state) => state.loadBookmark)
   const towerHeight = useAirportStore((state) => state.towerHeight)
   const customTowerPosition = useAirportStore((state) => state.customTowerPosition)
   const labelVisibilityDistance = useGlobalSettingsStore((state) => state.display.labelVisibilityDistance)
@@ -70,6 +92,25 @@ function GlobalSearchPanel() {
     return () => window.removeEventListener('keydown', handleKeyDown)
   }, [isOpen])
 
+  // Airports, bookmarks, mods and converted models from the host's search
+  useEffect(() => {
+    const trimmed = query.trim()
+    if (!isOpen || !trimmed) {
+      setContentResults([])
+      return
+    }
+    let cancelled = false
+    const timer = setTimeout(() => {
+      searchApi.search(trimmed, CONTENT_KINDS, CONTENT_LIMIT)
+        .then((results) => { if (!cancelled) setContentResults(results) })
+        .catch((error) => console.warn('[Search] Content search failed:', error))
+    }, CONTENT_SEARCH_DEBOUNCE_MS)
+    return () => {
+      cancelled = true
+      clearTimeout(timer)
+    }
+  }, [query, isOpen])
+
   const searchResults = useMemo((): SearchResult[] => {
     if (!query.trim()) return []
 
@@ -146,12 +187,74 @@ function GlobalSearchPanel() {
     setQuery('')
   }, [searchResults, currentAirport, labelVisibilityDistance, followAircraft, followAircraftInOrbit])
 
+  const close = useCallback(() => {
+    setIsOpen(false)
+    setQuery('')
+  }, [])
+
+  const handleSelectContent = useCallback((result: ContentResult) => {
+    const switchTo = (icao: string | null) => {
+      if (icao && icao !== currentAirport?.icao && airports.has(icao)) selectAirport(icao)
+    }
+    switch (result.kind) {
+      case 'airport':
+        switchTo(result.icao)
+        break
+      case 'bookmark':
+        switchTo(result.icao)
+        loadBookmark(Number(result.id.split('/')[1]))
+        break
+      case 'mod':
+        if (result.id.startsWith('aircraft/')) {
+          // Aircraft mods: list the flights using the type
+          setQuery(result.id.slice('aircraft/'.length))
+          return
+        }
+        switchTo(result.icao)
+        break
+      case 'model':
+        setQuery(result.title.split(' ')[0])
+        return
+    }
+    close()
+  }, [airports, currentAirport, selectAirport, loadBookmark, close])
+
+  // Aircraft first when the query looks like a callsign, otherwise content first
+  const term = query.trim().toLowerCase()
+  const aircraftFirst = searchResults.some((r) => r.callsign.toLowerCase().startsWith(term))
+
   const handleKeyDown = (e: React.KeyboardEvent) => {
-    if (e.key === 'Enter' && searchResults.length > 0) {
+    if (e.key !== 'Enter') return
+    if (searchResults.length > 0 && (aircraftFirst || contentResults.length === 0)) {
       handleSelect(searchResults[0].callsign)
+    } else if (contentResults.length > 0) {
+      handleSelectContent(contentResults[0])
     }
   }
 
+  const contentSection = contentResults.length > 0 && (
+    <div className="search-section">
+      {contentResults.map((result) => (
+        <button
+          type="button"
+          key={`${result.kind}:${result.id}`}
+          className="search-result-item"
+          onClick={() => handleSelectContent(result)}
+        >
+          <div className="result-main">
+            <span className="result-title">{result.title}</span>
+            <span className="result-type">{KIND_LABELS[result.kind]}</span>
+          </div>
+          {result.subtitle && (
+            <div className="result-details">
+              <span className="result-route">{result.subtitle}</span>
+            </div>
+          )}
+        </button>
+      ))}
+    </div>
+  )
+
   const handleOpenSearch = (e: React.MouseEvent) => {
     e.preventDefault()
     e.stopPropagation()
@@ -160,85 +263,89 @@ function GlobalSearchPanel() {
 
   return (
     <>
-      <button
-        type="button"
-        className="global-search-btn"
-        onClick={handleOpenSearch}
-        title="Global Aircraft Search (Ctrl+K)"
-      >
-        <svg width="16" height="16" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
-          <circle cx="11" cy="11" r="8" />
-          <path d="M21 21l-4.35-4.35" />
-        </svg>
-        <span className="search-btn-text">Search Flights</span>
-        <span className="search-shortcut">Ctrl+K</span>
-      </button>
-
-      {isOpen && createPortal(
-        <div className="global-search-overlay" onClick={() => setIsOpen(false)}>
-          <div className="global-search-modal" onClick={(e) => e.stopPropagation()}>
-            <div className="search-header">
-              <svg width="20" height="20" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
-                <circle cx="11" cy="11" r="8" />
-                <path d="M21 21l-4.35-4.35" />
-              </svg>
-              <input
-                ref={inputRef}
-                type="text"
-                className="global-search-input"
-                placeholder="Search all aircraft by callsign, type, or route..."
-                value={query}
-                onChange={(e) => setQuery(e.target.value)}
-                onKeyDown={handleKeyDown}
-              />
-              <button type="button" className="close-search-btn" onClick={() => setIsOpen(false)}>
-                <svg width="16" height="16" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
-                  <path d="M18 6L6 18M6 6l12 12" />
+        <button
+          type="button"
+          className="global-search-btn"
+          onClick={handleOpenSearch}
+          title="Search aircraft, airports, bookmarks and mods (Ctrl+K)"
+        >
+          <svg width="16" height="16" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
+            <circle cx="11" cy="11" r="8" />
+            <path d="M21 21l-4.35-4.35" />
+          </svg>
+          <span className="search-btn-text">Search</span>
+          <span className="search-shortcut">Ctrl+K</span>
+        </button>
+
+        {isOpen && createPortal(
+          <div className="global-search-overlay" onClick={() => setIsOpen(false)}>
+            <div className="global-search-modal" onClick={(e) => e.stopPropagation()}>
+              <div className="search-header">
+                <svg width="20" height="20" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
+                  <circle cx="11" cy="11" r="8" />
+                  <path d="M21 21l-4.35-4.35" />
                 </svg>
-              </button>
-            </div>
+                <input
+                  ref={inputRef}
+                  type="text"
+                  className="global-search-input"
+                  placeholder="Search aircraft, airports, bookmarks, mods..."
+                  value={query}
+                  onChange={(e) => setQuery(e.target.value)}
+                  onKeyDown={handleKeyDown}
+                />
+                <button type="button" className="close-search-btn" onClick={() => setIsOpen(false)}>
+                  <svg width="16" height="16" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="2">
+                    <path d="M18 6L6 18M6 6l12 12" />
+                  </svg>
+                </button>
+              </div>
 
-            <div className="search-results">
-              {query.trim() === '' ? (
-                <div className="search-hint">
-                  Start typing to search all {allPilots.length} aircraft on VATSIM
-                </div>
-              ) : searchResults.length === 0 ? (
-                <div className="no-results">No aircraft found matching &quot;{query}&quot;</div>
-              ) : (
-                searchResults.map((result) => (
-                  <button
-                    type="button"
-                    key={result.callsign}
-                    className={`search-result-item ${followingCallsign === result.callsign ? 'following' : ''}`}
-                    onClick={() => handleSelect(result.callsign)}
-                  >
-                    <div className="result-main">
-                      {result.isLive && (
-                        <span className="live-indicator" title="1Hz live updates">
-                          <svg width="6" height="6" viewBox="0 0 6 6">
-                            <circle cx="3" cy="3" r="3" fill="#0c7" />
-                          </svg>
-                        </span>
-                      )}
-                      <span className="result-callsign">{result.callsign}</span>
-                      <span className="result-type">{result.aircraftType || '???'}</span>
-                    </div>
-                    <div className="result-details">
-                      {(result.departure && result.departure !== 'null') || (result.arrival && result.arrival !== 'null') ? (
-                        <span className="result-route">
-                          {result.departure && result.departure !== 'null' ? result.departure : '????'} → {result.arrival && result.arrival !== 'null' ? result.arrival : '????'}
-                        </span>
-                      ) : null}
-                      <span className="result-info">
-                        {cameraPosition && result.distance !== Infinity && (
-                          <>{result.distance.toFixed(1)}nm • </>
+              <div className="search-results">
+                {query.trim() === '' ? (
+                  <div className="search-hint">
+                    Start typing to search all {allPilots.length} aircraft on VATSIM, airports, bookmarks and mods
+                  </div>
+                ) : searchResults.length === 0 && contentResults.length === 0 ? (
+                  <div className="no-results">Nothing found matching &quot;{query}&quot;</div>
+                ) : (
+                  <>
+                  {!aircraftFirst && contentSection}
+                  {searchResults.map((result) => (
+                    <button
+                      type="button"
+                      key={result.callsign}
+                      className={`search-result-item ${followingCallsign === result.callsign ? 'following' : ''}`}
+                      onClick={() => handleSelect(result.callsign)}
+                    >
+                      <div className="result-main">
+                        {result.isLive && (
+                          <span className="live-indicator" title="1Hz live updates">
+                            <svg width="6" height="6" viewBox="0 0 6 6">
+                              <circle cx="3" cy="3" r="3" fill="#0c7" />
+                            </svg>
+                          </span>
                         )}
-                        FL{Math.round((result.altitude / 0.3048) / 100).toString().padStart(3, '0')} • {result.groundspeed}kts
-                      </span>
-                    </div>
-                  </button>
-                ))
+                        <span className="result-callsign">{result.callsign}</span>
+                        <span className="result-type">{result.aircraftType || '???'}</span>
+                      </div>
+                      <div className="result-details">
+                        {(result.departure && result.departure !== 'null') || (result.arrival && result.arrival !== 'null') ? (
+                          <span className="result-route">
+                            {result.departure && result.departure !== 'null' ? result.departure : '????'} → {result.arrival && result.arrival !== 'null' ? result.arrival : '????'}
+                          </span>
+                        ) : null}
+                        <span className="result-info">
+                          {cameraPosition && result.distance !== Infinity && (
+                            <>{result.distance.toFixed(1)}nm • </>
+                          )}
+                          FL{Math.round((result.altitude / 0.3048) / 100).toString().padStart(3, '0')} • {result.groundspeed}kts
+                        </span>
+                      </div>
+                    </button>
+                  ))}
+                  {aircraftFirst && contentSection}
+                </>
               )}
             </div>
 
@@ -258,7 +365,7 @@ function GlobalSearchPanel() {
                 )}
               </div>
               <div className="footer-right">
-                <span className="footer-hint">Enter to follow</span>
+                <span className="footer-hint">Enter to open</span>
                 <span className="footer-hint">Esc to close</span>
               </div>
             </div>
@@ -188,7 +188,7 @@ function ImportModal({ onClose, onSuccess, onElectronImport }: ImportModalProps)
                   Re-import from Electron version
                 </button>
                 <p className="setting-hint" style={{ marginTop: '8px' }}>
-                  Reads settings from the old Electron app data folder.
+                  Reads settings, camera bookmarks and tower positions from the old Electron app data folder. Anything already set here is kept.
                 </p>
               </div>
 
@@ -1,8 +1,32 @@
 import { useEffect, useRef } from 'react'
 import * as Cesium from 'cesium'
-import { useMeasureStore } from '../../stores/measureStore'
+import { useMeasureStore, type MeasureTool } from '../../stores/measureStore'
+import { useDrawingStore } from '../../stores/drawingStore'
+import { finishPolygon } from '../../hooks/useSharedDrawings'
+import { drawingsApi } from '../../utils/tauriApi'
+import { formatLineMeasure } from './SharedDrawings'
+import type { Drawing } from '../../types/drawings'
 import './MeasuringTool.css'
 
+const TOOLS: { id: MeasureTool; label: string; hint: string }[] = [
+  { id: 'measure', label: 'Measure', hint: 'Click on the terrain to start measuring' },
+  { id: 'line', label: 'Line', hint: 'Click two points to draw a range/bearing line on every display' },
+  { id: 'label', label: 'Label', hint: 'Type the text, then click on the terrain to place it on every display' },
+  { id: 'polygon', label: 'Area', hint: 'Click the corners, then Finish (or Enter) to draw the area on every display' }
+]
+
+function describeDrawing(drawing: Drawing): string {
+  const text = drawing.text ? ` "${drawing.text}"` : ''
+  switch (drawing.kind) {
+    case 'line':
+      return `${formatLineMeasure(drawing)}${text}`
+    case 'label':
+      return `Label${text}`
+    case 'polygon':
+      return `Area (${drawing.points.length} corners)${text}`
+  }
+}
+
 interface MeasuringToolProps {
   cesiumViewer: Cesium.Viewer | null
 }
@@ -15,6 +39,14 @@ function MeasuringTool({ cesiumViewer }: MeasuringToolProps) {
   const previewDistance = useMeasureStore((state) => state.previewDistance)
   const stopMeasuring = useMeasureStore((state) => state.stopMeasuring)
   const clearAllMeasurements = useMeasureStore((state) => state.clearAllMeasurements)
+  const tool = useMeasureStore((state) => state.tool)
+  const setTool = useMeasureStore((state) => state.setTool)
+  const drawColor = useMeasureStore((state) => state.drawColor)
+  const setDrawColor = useMeasureStore((state) => state.setDrawColor)
+  const labelText = useMeasureStore((state) => state.labelText)
+  const setLabelText = useMeasureStore((state) => state.setLabelText)
+  const polygonPoints = useMeasureStore((state) => state.polygonPoints)
+  const board = useDrawingStore((state) => state.board)
 
   // Refs for Cesium entities - keyed by measurement id
   const measurementEntitiesRef = useRef<Map<string, {
@@ -28,6 +60,7 @@ function MeasuringTool({ cesiumViewer }: MeasuringToolProps) {
   const pendingPointEntityRef = useRef<Cesium.Entity | null>(null)
   const previewLineEntityRef = useRef<Cesium.Entity | null>(null)
   const previewLabelEntityRef = useRef<Cesium.Entity | null>(null)
+  const polygonEntitiesRef = useRef<Cesium.Entity[]>([])
 
   // Create/update Cesium entities for completed measurements
   useEffect(() => {
@@ -253,6 +286,63 @@ function MeasuringTool({ cesiumViewer }: MeasuringToolProps) {
     return cleanup
   }, [cesiumViewer, pendingPoint, previewPoint, previewDistance])
 
+  // Corners and outline of the polygon being drawn
+  useEffect(() => {
+    if (!cesiumViewer || cesiumViewer.isDestroyed()) return
+
+    const positions = polygonPoints.map((p) => p.cartesian)
+    const entities = positions.map((position, i) =>
+      cesiumViewer.entities.add({
+        id: `measure_polygon_p${i}`,
+        position,
+        point: {
+          pixelSize: 10,
+          color: Cesium.Color.YELLOW,
+          outlineColor: Cesium.Color.WHITE,
+          outlineWidth: 2,
+          disableDepthTestDistance: Number.POSITIVE_INFINITY
+        }
+      })
+    )
+    if (positions.length >= 2) {
+      entities.push(
+        cesiumViewer.entities.add({
+          id: 'measure_polygon_outline',
+          polyline: {
+            positions: positions.length >= 3 ? [...positions, positions[0]] : positions,
+            width: 2,
+            material: new Cesium.PolylineDashMaterialProperty({
+              color: Cesium.Color.YELLOW.withAlpha(0.7),
+              dashLength: 12
+            }),
+            clampToGround: true
+          }
+        })
+      )
+    }
+    polygonEntitiesRef.current = entities
+
+    return () => {
+      if (!cesiumViewer.isDestroyed()) {
+        polygonEntitiesRef.current.forEach((entity) => cesiumViewer.entities.remove(entity))
+      }
+      polygonEntitiesRef.current = []
+    }
+  }, [cesiumViewer, polygonPoints])
+
+  const handleSessionChange = (session: string) => {
+    if (!session || session === board?.session) return
+    drawingsApi.setSession(session).catch((error) => console.warn('[Drawings] Failed to switch session:', error))
+  }
+
+  const handleRemoveDrawing = (id: string) => {
+    drawingsApi.remove(id).catch((error) => console.warn('[Drawings] Failed to remove drawing:', error))
+  }
+
+  const handleClearDrawings = () => {
+    drawingsApi.clear().catch((error) => console.warn('[Drawings] Failed to clear drawings:', error))
+  }
+
   // Format distance for display
   function formatDistance(meters: number): string {
     const nm = meters / 1852
@@ -268,21 +358,71 @@ function MeasuringTool({ cesiumViewer }: MeasuringToolProps) {
     }
   }
 
+  const drawings = board?.drawings ?? []
+  const toolHint = TOOLS.find((t) => t.id === tool)?.hint
+
   // Don't render UI if not active and no measurements
   if (!isActive && measurements.length === 0) return null
 
   return (
     <div className="measuring-tool">
       <div className="measuring-header">
-        <span className="measuring-title">Measure Distance</span>
+        <span className="measuring-title">Measure &amp; Draw</span>
         <button className="measuring-close" onClick={stopMeasuring} title="Close measuring tool">
           &times;
         </button>
       </div>
 
       <div className="measuring-content">
-        {isActive && !pendingPoint && (
-          <p className="measuring-hint">Click on the terrain to start measuring</p>
+        {isActive && (
+          <div className="measuring-tools">
+            {TOOLS.map((t) => (
+              <button
+                key={t.id}
+                className={`measuring-tool-btn ${tool === t.id ? 'active' : ''}`}
+                onClick={() => setTool(t.id)}
+              >
+                {t.label}
+              </button>
+            ))}
+            {tool !== 'measure' && (
+              <input
+                type="color"
+                className="measuring-color"
+                value={drawColor}
+                onChange={(e) => setDrawColor(e.target.value)}
+                title="Drawing color"
+              />
+            )}
+          </div>
+        )}
+
+        {isActive && (tool === 'label' || tool === 'polygon') && (
+          <input
+            type="text"
+            className="measuring-text"
+            placeholder={tool === 'label' ? 'Label text' : 'Caption (optional)'}
+            value={labelText}
+            maxLength={200}
+            onChange={(e) => setLabelText(e.target.value)}
+          />
+        )}
+
+        {isActive && !pendingPoint && polygonPoints.length === 0 && (
+          <p className="measuring-hint">{toolHint}</p>
+        )}
+
+        {isActive && tool === 'polygon' && polygonPoints.length > 0 && (
+          <div className="measuring-polygon">
+            <span className="measuring-hint">{polygonPoints.length} corner(s)</span>
+            <button
+              className="measuring-finish"
+              onClick={finishPolygon}
+              disabled={polygonPoints.length < 3}
+            >
+              Finish
+            </button>
+          </div>
         )}
 
         {isActive && pendingPoint && !previewPoint && (
@@ -313,6 +453,41 @@ function MeasuringTool({ cesiumViewer }: MeasuringToolProps) {
             ))}
           </div>
         )}
+
+        {isActive && (
+          <div className="measuring-list">
+            <div className="measuring-list-header">
+              <span>Shared ({drawings.length})</span>
+              {drawings.length > 0 && (
+                <button className="measuring-clear-all" onClick={handleClearDrawings} title="Clear all drawings on every display">
+                  Clear All
+                </button>
+              )}
+            </div>
+            <input
+              key={board?.session}
+              type="text"
+              className="measuring-text"
+              placeholder="Session"
+              defaultValue={board?.session ?? ''}
+              title="Drawing session (a new name starts an empty board; earlier sessions are kept)"
+              onBlur={(e) => handleSessionChange(e.target.value.trim())}
+              onKeyDown={(e) => {
+                if (e.key === 'Enter') handleSessionChange(e.currentTarget.value.trim())
+              }}
+            />
+            {drawings.map((d) => (
+              <div key={`${d.id}@${d.createdAt}`} className="measuring-item">
+                <span className="measuring-swatch" style={{ background: d.color ?? '#ff4081' }} />
+                <span className="measuring-item-value">{describeDrawing(d)}</span>
+                {d.author && <span className="measuring-item-hint">{d.author}</span>}
+                <button className="measuring-remove" onClick={() => handleRemoveDrawing(d.id)} title="Remove drawing">
+                  &times;
+                </button>
+              </div>
+            ))}
+          </div>
+        )}
       </div>
     </div>
   )
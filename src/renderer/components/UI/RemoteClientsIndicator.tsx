@@ -1,7 +1,8 @@
 /**
  * Remote Clients Indicator
  *
- * Shows the number of remote clients (browsers/tablets/phones) connected to the server.
+ * Shows the number of remote clients (browsers/tablets/phones) connected to the server,
+ * and a warning while a client is locked out after repeated wrong auth tokens.
  * Only visible on the desktop app when the server is running and clients are connected.
  */
 
@@ -10,8 +11,23 @@ import { isRemoteMode } from '../../utils/remoteMode'
 import { isTauri } from '../../utils/tauriApi'
 import './RemoteClientsIndicator.css'
 
+/** Payload of the `auth-lockout` event (see auth_lockout.rs) */
+interface AuthLockoutEvent {
+  ip: string
+  failures: number
+  lockedForSecs: number
+}
+
 function RemoteClientsIndicator() {
   const [clientCount, setClientCount] = useState(0)
+  const [lockout, setLockout] = useState<AuthLockoutEvent | null>(null)
+
+  // Hide the lockout warning when the lockout ends
+  useEffect(() => {
+    if (!lockout) return
+    const timer = setTimeout(() => setLockout(null), lockout.lockedForSecs * 1000)
+    return () => clearTimeout(timer)
+  }, [lockout])
 
   useEffect(() => {
     // Only show on desktop app (not in remote/browser mode)
@@ -26,6 +42,7 @@ function RemoteClientsIndicator() {
 
     console.log('[RemoteClientsIndicator] Setting up Tauri event listener...')
     let unlisten: (() => void) | null = null
+    let unlistenLockout: (() => void) | null = null
 
     const setupListener = async () => {
       try {
@@ -35,6 +52,10 @@ function RemoteClientsIndicator() {
           console.log('[RemoteClientsIndicator] Received event, payload:', event.payload)
           setClientCount(event.payload)
         })
+        unlistenLockout = await listen<AuthLockoutEvent>('auth-lockout', (event) => {
+          console.warn('[RemoteClientsIndicator] Client locked out:', event.payload)
+          setLockout(event.payload)
+        })
         console.log('[RemoteClientsIndicator] Listener setup complete')
       } catch (error) {
         console.error('[RemoteClientsIndicator] Failed to setup listener:', error)
@@ -48,11 +69,25 @@ function RemoteClientsIndicator() {
       if (unlisten) {
         unlisten()
       }
+      if (unlistenLockout) {
+        unlistenLockout()
+      }
     }
   }, [])
 
   // Don't render in remote mode or if no clients connected
-  if (isRemoteMode() || clientCount === 0) return null
+  if (isRemoteMode() || (clientCount === 0 && !lockout)) return null
+
+  if (lockout) {
+    return (
+      <div
+        className="remote-clients-indicator lockout"
+        title={`${lockout.ip} sent ${lockout.failures} wrong auth tokens and is locked out for ${lockout.lockedForSecs} s`}
+      >
+        <span>Locked out {lockout.ip}</span>
+      </div>
+    )
+  }
 
   return (
     <div className="remote-clients-indicator" title={`${clientCount} remote ${clientCount === 1 ? 'client' : 'clients'} connected`}>
@@ -34,6 +34,8 @@ function ReplayControls({ onSettingsClick }: ReplayControlsProps) {
   const stepBackward = useReplayStore((state) => state.stepBackward)
   const stepForward = useReplayStore((state) => state.stepForward)
   const setPlaybackSpeed = useReplayStore((state) => state.setPlaybackSpeed)
+  // Playback controls drive the host's replay on every display
+  const followingHost = useReplayStore((state) => state.hostStatus !== null)
 
   // Derive active snapshots for replay
   const activeSnapshots = playbackMode === 'imported' && importedSnapshots
@@ -178,7 +180,7 @@ function ReplayControls({ onSettingsClick }: ReplayControlsProps) {
             </>
           )}
           <span className="time-total">
-            Buffer: {formatDuration(totalDuration)}
+            {followingHost ? 'Host replay' : 'Buffer'}: {formatDuration(totalDuration)}
           </span>
         </div>
       </div>
@@ -200,7 +202,7 @@ function ReplayControls({ onSettingsClick }: ReplayControlsProps) {
         <button
           className={`live-btn ${isLive ? 'active' : ''}`}
           onClick={goLive}
-          title="Return to live"
+          title={followingHost ? 'Stop the host replay on every display' : 'Return to live'}
         >
           LIVE
         </button>
@@ -1,6 +1,7 @@
 import { useSettingsStore } from '../../stores/settingsStore'
 import { useGlobalSettingsStore } from '../../stores/globalSettingsStore'
 import CollapsibleSection from './settings/CollapsibleSection'
+import { DATABLOCK_FIELDS, parseDatablockLine } from '../../utils/datablockFormat'
 import './ControlsBar.css'
 
 import type { GroundLabelMode } from '../../types'
@@ -12,12 +13,27 @@ function SettingsDisplayTab() {
   const showGroundTraffic = useGlobalSettingsStore((state) => state.display.showGroundTraffic)
   const showAirborneTraffic = useGlobalSettingsStore((state) => state.display.showAirborneTraffic)
   const autoAvoidOverlaps = useGlobalSettingsStore((state) => state.display.autoAvoidOverlaps)
-  const leaderDistance = useGlobalSettingsStore((state) => state.display.leaderDistance)
   const defaultDatablockDirection = useGlobalSettingsStore((state) => state.display.defaultDatablockDirection)
   const groundLabelMode = useGlobalSettingsStore((state) => state.display.groundLabelMode)
   const groundLabelMinSpeed = useGlobalSettingsStore((state) => state.display.groundLabelMinSpeed)
+  const trailLength = useGlobalSettingsStore((state) => state.display.trailLength)
   const updateDisplay = useGlobalSettingsStore((state) => state.updateDisplay)
 
+  // Global datablock layout (identical on every display)
+  const datablockLines = useGlobalSettingsStore((state) => state.datablock.lines)
+  const leaderLength = useGlobalSettingsStore((state) => state.datablock.leaderLength)
+  const datablockFontScale = useGlobalSettingsStore((state) => state.datablock.fontScale)
+  const colorByFrequency = useGlobalSettingsStore((state) => state.datablock.colorByFrequency)
+  const updateDatablock = useGlobalSettingsStore((state) => state.updateDatablock)
+
+  // Replace one datablock line (empty lines are dropped, at least one is kept)
+  const updateDatablockLine = (index: number, text: string) => {
+    const lines = [0, 1, 2, 3]
+      .map((i) => (i === index ? parseDatablockLine(text) : datablockLines[i] ?? []))
+      .filter((line) => line.length > 0)
+    if (lines.length > 0) updateDatablock({ lines })
+  }
+
   // Local settings (per-device)
   const maxAircraftDisplay = useSettingsStore((state) => state.aircraft.maxAircraftDisplay)
   const orientationEmulation = useSettingsStore((state) => state.aircraft.orientationEmulation)
@@ -25,6 +41,8 @@ function SettingsDisplayTab() {
   const datablockFontSize = useSettingsStore((state) => state.aircraft.datablockFontSize)
   const updateAircraftSettings = useSettingsStore((state) => state.updateAircraftSettings)
   const showAircraftPanel = useSettingsStore((state) => state.ui.showAircraftPanel)
+  const syncSelection = useSettingsStore((state) => state.ui.syncSelection)
+  const selectionSourceName = useSettingsStore((state) => state.ui.selectionSourceName)
   const updateUISettings = useSettingsStore((state) => state.updateUISettings)
 
   return (
@@ -55,7 +73,7 @@ function SettingsDisplayTab() {
                 checked={datablockMode === 'full'}
                 onChange={() => updateDisplay({ datablockMode: 'full' })}
               />
-              Full (callsign + type + altitude + speed)
+              Full (fields of the datablock lines below)
             </label>
             <label>
               <input
@@ -94,6 +112,37 @@ function SettingsDisplayTab() {
           </p>
         </div>
 
+        <div className="setting-item">
+          <label>Datablock Lines</label>
+          {[0, 1, 2, 3].map((index) => (
+            <input
+              key={`${index}:${(datablockLines[index] ?? []).join(' ')}`}
+              type="text"
+              placeholder={`Line ${index + 1}`}
+              defaultValue={(datablockLines[index] ?? []).join(' ')}
+              onBlur={(e) => updateDatablockLine(index, e.target.value)}
+            />
+          ))}
+          <p className="setting-hint">
+            Fields per line, separated by spaces: {DATABLOCK_FIELDS.join(', ')}. Same on all displays.
+          </p>
+        </div>
+
+        <div className="setting-item">
+          <label>
+            <input
+              type="checkbox"
+              checked={colorByFrequency}
+              onChange={(e) => updateDatablock({ colorByFrequency: e.target.checked })}
+            />
+            Color by Frequency
+          </label>
+          <p className="setting-hint">
+            Color datablocks by the position each aircraft is talking to: red tower, orange ground,
+            blue approach, green center, purple delivery. Same on all displays.
+          </p>
+        </div>
+
         <div className="setting-item">
           <label>Leader Line Length</label>
           <div className="slider-with-value">
@@ -102,10 +151,10 @@ function SettingsDisplayTab() {
               min="0.5"
               max="5"
               step="0.5"
-              value={leaderDistance}
-              onChange={(e) => updateDisplay({ leaderDistance: Number(e.target.value) })}
+              value={leaderLength}
+              onChange={(e) => updateDatablock({ leaderLength: Number(e.target.value) })}
             />
-            <span>{leaderDistance}</span>
+            <span>{leaderLength}</span>
           </div>
           <p className="setting-hint">
             Length of leader lines connecting datablocks to aircraft. 1=short, 5=long.
@@ -129,6 +178,24 @@ function SettingsDisplayTab() {
           </p>
         </div>
 
+        <div className="setting-item">
+          <label>Datablock Font Scale</label>
+          <div className="slider-with-value">
+            <input
+              type="range"
+              min="0.5"
+              max="2"
+              step="0.1"
+              value={datablockFontScale}
+              onChange={(e) => updateDatablock({ fontScale: Number(e.target.value) })}
+            />
+            <span>{datablockFontScale.toFixed(1)}x</span>
+          </div>
+          <p className="setting-hint">
+            Scales the datablock font size on all displays.
+          </p>
+        </div>
+
         <div className="setting-item">
           <label>Default Datablock Direction</label>
           <select
@@ -228,6 +295,24 @@ function SettingsDisplayTab() {
           </label>
         </div>
 
+        <div className="setting-item">
+          <label>Trail Length</label>
+          <div className="slider-with-value">
+            <input
+              type="range"
+              min="10"
+              max="720"
+              step="10"
+              value={trailLength ?? 60}
+              onChange={(e) => updateDisplay({ trailLength: Number(e.target.value) })}
+            />
+            <span>{trailLength ?? 60} points</span>
+          </div>
+          <p className="setting-hint">
+            Positions kept per aircraft on the host, shared by all connected displays.
+          </p>
+        </div>
+
         <div className="setting-item">
           <label>
             <input
@@ -274,6 +359,33 @@ function SettingsDisplayTab() {
             Show Aircraft Panel
           </label>
         </div>
+
+        <div className="setting-item">
+          <label>
+            <input
+              type="checkbox"
+              checked={syncSelection}
+              onChange={(e) => updateUISettings({ syncSelection: e.target.checked })}
+            />
+            Share Aircraft Selection
+          </label>
+          <p className="setting-hint">
+            Highlight the aircraft selected on other displays, and share the aircraft clicked or followed here.
+          </p>
+        </div>
+
+        <div className="setting-item">
+          <label>Display Name</label>
+          <input
+            type="text"
+            placeholder="e.g., iPad"
+            defaultValue={selectionSourceName}
+            onBlur={(e) => updateUISettings({ selectionSourceName: e.target.value.trim() })}
+          />
+          <p className="setting-hint">
+            Shown on other displays with the aircraft this display selects, its drawings and its messages.
+          </p>
+        </div>
       </CollapsibleSection>
     </>
   )
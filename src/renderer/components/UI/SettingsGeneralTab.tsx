@@ -6,10 +6,15 @@ import { useVatsimStore } from '../../stores/vatsimStore'
 import { useViewportStore } from '../../stores/viewportStore'
 import { useAirportStore } from '../../stores/airportStore'
 import { useAircraftTimelineStore } from '../../stores/aircraftTimelineStore'
-import { shellApi } from '../../utils/tauriApi'
+import { mapProvidersApi, shellApi } from '../../utils/tauriApi'
 import FSLTLImportPanel from './FSLTLImportPanel'
+import ContentPacksPanel from './ContentPacksPanel'
+import BackupPanel from './BackupPanel'
+import TerrainPackagesPanel from './TerrainPackagesPanel'
 import CollapsibleSection from './settings/CollapsibleSection'
 import type { DataSourceType } from '../../types/realtraffic'
+import type { ImageryProviderType, TerrainProviderType } from '../../types'
+import type { ImageryVintage } from '../../types/mapProviders'
 import './ControlsBar.css'
 
 interface SettingsGeneralTabProps {
@@ -23,6 +28,19 @@ function SettingsGeneralTab({ onShowImportModal, onShowExportModal, importStatus
   const cesiumIonToken = useGlobalSettingsStore((state) => state.cesiumIonToken)
   const setCesiumIonToken = useGlobalSettingsStore((state) => state.setCesiumIonToken)
 
+  // Terrain and imagery providers (global settings, resolved by the host)
+  const mapProviders = useGlobalSettingsStore((state) => state.mapProviders)
+  const updateMapProviders = useGlobalSettingsStore((state) => state.updateMapProviders)
+  const needsMaptilerKey = mapProviders.terrain === 'maptiler' || mapProviders.imagery === 'maptiler'
+  const hasCustomProvider = mapProviders.terrain === 'custom' || mapProviders.terrain === 'package' ||
+    mapProviders.imagery === 'custom'
+
+  // Imagery vintage for the current airport (Sentinel-2 years, Wayback releases)
+  const currentAirportIcao = useAirportStore((state) => state.currentAirport?.icao ?? null)
+  const hasVintages = mapProviders.imagery === 'sentinel' || mapProviders.imagery === 'wayback'
+  const [vintages, setVintages] = useState<ImageryVintage[]>([])
+  const [vintagesError, setVintagesError] = useState<string | null>(null)
+
   // Settings store - Local settings
   const theme = useSettingsStore((state) => state.ui.theme)
   const updateUISettings = useSettingsStore((state) => state.updateUISettings)
@@ -59,6 +77,26 @@ function SettingsGeneralTab({ onShowImportModal, onShowExportModal, importStatus
   const [rtLicenseInput, setRtLicenseInput] = useState('')
   const [rtLicenseSaved, setRtLicenseSaved] = useState(false)
 
+  useEffect(() => {
+    if (!hasVintages) return
+    let cancelled = false
+    setVintagesError(null)
+    mapProvidersApi.listVintages(mapProviders.imagery)
+      .then((list) => { if (!cancelled) setVintages(list) })
+      .catch((err) => { if (!cancelled) setVintagesError(String(err)) })
+    return () => { cancelled = true }
+  }, [hasVintages, mapProviders.imagery])
+
+  const setImageryVintage = (icao: string, vintage: string) => {
+    const imageryVintages = { ...mapProviders.imageryVintages }
+    if (vintage) {
+      imageryVintages[icao] = vintage
+    } else {
+      delete imageryVintages[icao]
+    }
+    updateMapProviders({ imageryVintages })
+  }
+
   // Sync token input with store value when component mounts or store changes
   useEffect(() => {
     setTokenInput(cesiumIonToken)
@@ -206,6 +244,131 @@ function SettingsGeneralTab({ onShowImportModal, onShowExportModal, importStatus
         </div>
       </CollapsibleSection>
 
+      <CollapsibleSection title="Terrain & Imagery">
+        <div className="setting-item">
+          <label>Terrain</label>
+          <select
+            value={mapProviders.terrain}
+            onChange={(e) => updateMapProviders({ terrain: e.target.value as TerrainProviderType })}
+          >
+            <option value="ion">Cesium World Terrain (Ion)</option>
+            <option value="maptiler">MapTiler</option>
+            <option value="custom">Custom terrain server</option>
+            <option value="package">Offline terrain package</option>
+            <option value="ellipsoid">None (flat)</option>
+          </select>
+          {mapProviders.terrain === 'package' && !mapProviders.terrainPackage && (
+            <p className="setting-hint">Import a package and select it under Offline Terrain Packages.</p>
+          )}
+        </div>
+        <div className="setting-item">
+          <label>Imagery</label>
+          <select
+            value={mapProviders.imagery}
+            onChange={(e) => updateMapProviders({ imagery: e.target.value as ImageryProviderType })}
+          >
+            <option value="ion">Bing Maps Aerial (Ion)</option>
+            <option value="bing">Bing Maps Aerial (own key)</option>
+            <option value="maptiler">MapTiler Satellite</option>
+            <option value="sentinel">Sentinel-2 cloudless (no key)</option>
+            <option value="wayback">Esri World Imagery / Wayback (no key)</option>
+            <option value="custom">Custom XYZ tiles</option>
+          </select>
+          <p className="setting-hint">
+            Alternatives to Cesium Ion. Terrain and imagery from other providers are loaded through the host,
+            so their keys stay on this computer.
+          </p>
+        </div>
+        {hasVintages && currentAirportIcao && (
+          <div className="setting-item">
+            <label>Imagery Date for {currentAirportIcao}</label>
+            <select
+              value={mapProviders.imageryVintages?.[currentAirportIcao]?.startsWith(`${mapProviders.imagery}-`)
+                ? mapProviders.imageryVintages[currentAirportIcao]
+                : ''}
+              onChange={(e) => setImageryVintage(currentAirportIcao, e.target.value)}
+            >
+              <option value="">Latest</option>
+              {vintages.map((vintage) => (
+                <option key={vintage.id} value={vintage.id}>{vintage.date}</option>
+              ))}
+            </select>
+            <p className="setting-hint">
+              Earlier imagery for this airport, e.g. to match current construction or to avoid snow-covered
+              imagery in summer. Remembered per airport.
+            </p>
+            {vintagesError && <p className="setting-hint">{vintagesError}</p>}
+          </div>
+        )}
+        {needsMaptilerKey && (
+          <div className="setting-item">
+            <label>MapTiler API Key</label>
+            <input
+              type="text"
+              className="text-input token-input"
+              defaultValue={mapProviders.maptilerKey}
+              onBlur={(e) => updateMapProviders({ maptilerKey: e.target.value.trim() })}
+              placeholder="Enter your MapTiler API key"
+            />
+          </div>
+        )}
+        {mapProviders.imagery === 'bing' && (
+          <div className="setting-item">
+            <label>Bing Maps Key</label>
+            <input
+              type="text"
+              className="text-input token-input"
+              defaultValue={mapProviders.bingKey}
+              onBlur={(e) => updateMapProviders({ bingKey: e.target.value.trim() })}
+              placeholder="Enter your Bing Maps key"
+            />
+          </div>
+        )}
+        {mapProviders.terrain === 'custom' && (
+          <div className="setting-item">
+            <label>Terrain Server URL</label>
+            <input
+              type="text"
+              className="text-input"
+              defaultValue={mapProviders.customTerrainUrl}
+              onBlur={(e) => updateMapProviders({ customTerrainUrl: e.target.value })}
+              placeholder="http://nas:8080/tilesets/terrain"
+              style={{ width: '100%' }}
+            />
+            <p className="setting-hint">Quantized-mesh terrain (the folder containing layer.json).</p>
+          </div>
+        )}
+        {mapProviders.imagery === 'custom' && (
+          <div className="setting-item">
+            <label>Imagery URL Template</label>
+            <input
+              type="text"
+              className="text-input"
+              defaultValue={mapProviders.customImageryUrl}
+              onBlur={(e) => updateMapProviders({ customImageryUrl: e.target.value })}
+              placeholder="https://tiles.example.com/{z}/{x}/{y}.jpg"
+              style={{ width: '100%' }}
+            />
+          </div>
+        )}
+        {hasCustomProvider && (
+          <div className="setting-item">
+            <label>Attribution</label>
+            <input
+              type="text"
+              className="text-input"
+              defaultValue={mapProviders.customAttribution}
+              onBlur={(e) => updateMapProviders({ customAttribution: e.target.value.trim() })}
+              placeholder="&copy; Tile provider"
+              style={{ width: '100%' }}
+            />
+            <p className="setting-hint">Shown in the viewer as required by the tile provider&apos;s license.</p>
+          </div>
+        )}
+      </CollapsibleSection>
+
+      <TerrainPackagesPanel />
+
       <CollapsibleSection title="Data Source">
         <div className="setting-item">
           <label>Traffic Source</label>
@@ -434,6 +597,10 @@ function SettingsGeneralTab({ onShowImportModal, onShowExportModal, importStatus
 
       <FSLTLImportPanel />
 
+      <ContentPacksPanel />
+
+      <BackupPanel />
+
       <CollapsibleSection title="Advanced / Debugging">
         <div className="setting-item">
           <label className="setting-label">
@@ -1,12 +1,18 @@
 import { useState } from 'react'
 import { useUpdateStore } from '../../stores/updateStore'
-import { checkForUpdates } from '../../services/UpdateService'
-import { repairSettingsMigration } from '../../stores/globalSettingsStore'
+import { checkForUpdates, setUpdateChannel } from '../../services/UpdateService'
+import { repairSettingsMigration, useGlobalSettingsStore } from '../../stores/globalSettingsStore'
+import { appApi, shellApi } from '../../utils/tauriApi'
+import type { UpdateChannel } from '../../types'
 import CollapsibleSection from './settings/CollapsibleSection'
 import './ControlsBar.css'
 
 function SettingsHelpTab() {
   const updateStatus = useUpdateStore((state) => state.status)
+  const updateInfo = useUpdateStore((state) => state.updateInfo)
+  const releaseNotes = useUpdateStore((state) => state.releaseNotes)
+  const updateSettings = useGlobalSettingsStore((state) => state.update)
+  const refreshGlobalSettings = useGlobalSettingsStore((state) => state.refresh)
   const [repairStatus, setRepairStatus] = useState<'idle' | 'running' | 'done'>('idle')
   const [repairResult, setRepairResult] = useState<{ recovered: string[]; errors: string[] } | null>(null)
 
@@ -143,10 +149,21 @@ function SettingsHelpTab() {
       </CollapsibleSection>
 
       <CollapsibleSection title="Updates">
+        <div className="setting-item">
+          <label>Release Channel</label>
+          <select
+            value={updateSettings.channel}
+            onChange={(e) => setUpdateChannel(e.target.value as UpdateChannel)}
+            disabled={updateStatus === 'downloading'}
+          >
+            <option value="stable">Stable</option>
+            <option value="beta">Beta (includes pre-releases)</option>
+          </select>
+        </div>
         <div className="setting-row">
           <button
             className="control-button"
-            onClick={() => checkForUpdates()}
+            onClick={() => checkForUpdates(true)}
             disabled={updateStatus === 'checking' || updateStatus === 'downloading'}
           >
             {updateStatus === 'checking' ? 'Checking...' : 'Check for Updates'}
@@ -154,7 +171,43 @@ function SettingsHelpTab() {
         </div>
         <p className="setting-hint" style={{ marginTop: '8px' }}>
           Current version: v{APP_VERSION}
+          {updateStatus === 'up-to-date' && ' (up to date)'}
         </p>
+        {updateSettings.skippedVersion && (
+          <p className="setting-hint">
+            Skipped version: v{updateSettings.skippedVersion}{' '}
+            <button
+              className="control-button"
+              onClick={() => appApi.skipUpdateVersion(null).then(refreshGlobalSettings)}
+            >
+              Offer again
+            </button>
+          </p>
+        )}
+        {updateSettings.deferredUntil !== undefined && updateSettings.deferredUntil > Date.now() && (
+          <p className="setting-hint">
+            Updates deferred until {new Date(updateSettings.deferredUntil).toLocaleString()}
+          </p>
+        )}
+        {updateInfo && releaseNotes.length > 0 && (
+          <div className="setting-item">
+            <label>What's new in v{updateInfo.version}</label>
+            {releaseNotes.map((notes) => (
+              <details key={notes.version}>
+                <summary>
+                  v{notes.version}{notes.prerelease && ' (beta)'}
+                  {notes.date && ` - ${new Date(notes.date).toLocaleDateString()}`}
+                </summary>
+                <p className="setting-hint" style={{ whiteSpace: 'pre-wrap' }}>
+                  {notes.body || 'No release notes'}
+                </p>
+                <button className="control-button" onClick={() => shellApi.openExternal(notes.url)}>
+                  Open release page
+                </button>
+              </details>
+            ))}
+          </div>
+        )}
       </CollapsibleSection>
 
       <CollapsibleSection title="Troubleshooting">
@@ -1,6 +1,7 @@
 import { useState, useEffect, useCallback } from 'react'
 import { useGlobalSettingsStore } from '../../stores/globalSettingsStore'
-import { httpServerApi, type ServerStatus, isTauri } from '../../utils/tauriApi'
+import { httpServerApi, type AuditEntry, type ServerStatus, isTauri } from '../../utils/tauriApi'
+import { DEFAULT_GLOBAL_REMOTE_ACCESS_SETTINGS, type GlobalRemoteAccessSettings } from '../../types'
 import CollapsibleSection from './settings/CollapsibleSection'
 import './ControlsBar.css'
 
@@ -11,6 +12,21 @@ function SettingsServerTab() {
   const [serverStatus, setServerStatus] = useState<ServerStatus | null>(null)
   const [serverError, setServerError] = useState<string | null>(null)
   const [serverLoading, setServerLoading] = useState(false)
+  const [auditLog, setAuditLog] = useState<AuditEntry[] | null>(null)
+
+  const remoteAccess = serverSettings.remoteAccess ?? DEFAULT_GLOBAL_REMOTE_ACCESS_SETTINGS
+
+  const updateRemoteAccess = useCallback((updates: Partial<GlobalRemoteAccessSettings>) => {
+    updateServer({ remoteAccess: { ...remoteAccess, ...updates } })
+  }, [remoteAccess, updateServer])
+
+  const updateClientCert = useCallback((updates: Partial<GlobalRemoteAccessSettings['clientCert']>) => {
+    updateRemoteAccess({ clientCert: { ...remoteAccess.clientCert, ...updates } })
+  }, [remoteAccess, updateRemoteAccess])
+
+  const loadAuditLog = useCallback(() => {
+    httpServerApi.getAuditLog(100).then(setAuditLog).catch(console.error)
+  }, [])
 
   // Get server status on mount (only in Tauri)
   useEffect(() => {
@@ -28,7 +44,7 @@ function SettingsServerTab() {
     try {
       if (serverStatus?.running) {
         await httpServerApi.stop()
-        setServerStatus({ running: false, port: serverSettings.port, localUrl: null, lanUrl: null })
+        setServerStatus({ running: false, port: serverSettings.port, localUrl: null, lanUrl: null, lanUrlV6: null })
         await updateServer({ enabled: false })
       } else {
         const status = await httpServerApi.start(serverSettings.port)
@@ -93,6 +109,23 @@ function SettingsServerTab() {
           </div>
           <p className="setting-hint">Port for the HTTP server (default: 8765). Change requires restart.</p>
         </div>
+        <div className="setting-item">
+          <label>Allowed Linked Folders</label>
+          <textarea
+            className="text-input"
+            rows={3}
+            placeholder={'\\\\nas\\towercab-models'}
+            defaultValue={(serverSettings.allowedFileRoots ?? []).join('\n')}
+            onBlur={(e) => updateServer({
+              allowedFileRoots: e.target.value.split('\n').map((line) => line.trim()).filter(Boolean)
+            })}
+            style={{ width: '100%' }}
+          />
+          <p className="setting-hint">
+            Folders (one per line, e.g. a network share) that symlinks in the mods and FSLTL folders may point to.
+            Links to anywhere else are not served. Change requires restart.
+          </p>
+        </div>
         <div className="setting-item">
           <button
             className={`control-button ${serverStatus?.running ? 'active' : ''}`}
@@ -141,6 +174,21 @@ function SettingsServerTab() {
                   <span className="setting-hint" style={{ marginLeft: '4px' }}>(Use this for other devices)</span>
                 </div>
               )}
+              {serverStatus.lanUrlV6 && (
+                <div style={{ display: 'flex', alignItems: 'center', gap: '8px' }}>
+                  <code style={{ background: 'rgba(255,255,255,0.1)', padding: '4px 8px', borderRadius: '4px' }}>
+                    {serverStatus.lanUrlV6}
+                  </code>
+                  <button
+                    className="control-button"
+                    onClick={() => handleCopyUrl(serverStatus.lanUrlV6!)}
+                    style={{ padding: '4px 8px' }}
+                  >
+                    Copy
+                  </button>
+                  <span className="setting-hint" style={{ marginLeft: '4px' }}>(IPv6)</span>
+                </div>
+              )}
             </div>
             <p className="setting-hint" style={{ marginTop: '8px' }}>
               Open one of these URLs in Safari on your iPad to access TowerCab 3D remotely.
@@ -148,6 +196,126 @@ function SettingsServerTab() {
           </div>
         )}
       </CollapsibleSection>
+
+      <CollapsibleSection title="Access From Outside the LAN">
+        <p className="setting-hint" style={{ marginBottom: '12px' }}>
+          Let facility staff view the cab remotely without port forwarding the server. All changes require a server restart.
+        </p>
+        <div className="setting-item">
+          <label>
+            <input
+              type="checkbox"
+              checked={serverSettings.requireLocalNetwork}
+              onChange={(e) => updateServer({ requireLocalNetwork: e.target.checked })}
+            />
+            Only allow the local network and trusted networks below
+          </label>
+        </div>
+        <div className="setting-item">
+          <label>
+            <input
+              type="checkbox"
+              checked={remoteAccess.trustTailscale}
+              onChange={(e) => updateRemoteAccess({ trustTailscale: e.target.checked })}
+            />
+            Trust Tailscale devices
+          </label>
+          <p className="setting-hint">
+            Treats Tailscale addresses (100.64.0.0/10) and MagicDNS names (*.ts.net) like the local network.
+          </p>
+        </div>
+        <div className="setting-item">
+          <label>Trusted Networks</label>
+          <textarea
+            className="text-input"
+            rows={2}
+            placeholder="10.8.0.0/24"
+            defaultValue={remoteAccess.trustedNetworks.join('\n')}
+            onBlur={(e) => updateRemoteAccess({
+              trustedNetworks: e.target.value.split('\n').map((line) => line.trim()).filter(Boolean)
+            })}
+            style={{ width: '100%' }}
+          />
+          <p className="setting-hint">
+            Extra address ranges (one per line, e.g. a WireGuard subnet) treated like the local network.
+          </p>
+        </div>
+        <div className="setting-item">
+          <label>
+            <input
+              type="checkbox"
+              checked={remoteAccess.clientCert.enabled}
+              onChange={(e) => updateClientCert({ enabled: e.target.checked })}
+            />
+            Require client certificates on an HTTPS port
+          </label>
+          <p className="setting-hint">
+            Serves TowerCab 3D over HTTPS to devices with a certificate signed by your CA. These devices don&apos;t
+            need the auth token.
+          </p>
+        </div>
+        {remoteAccess.clientCert.enabled && (
+          <>
+            <div className="setting-item">
+              <label>HTTPS Port</label>
+              <input
+                type="number"
+                min="1024"
+                max="65535"
+                value={remoteAccess.clientCert.port}
+                onChange={(e) => updateClientCert({ port: Math.max(1024, Math.min(65535, Number(e.target.value))) })}
+                className="text-input"
+                style={{ width: '100px' }}
+              />
+            </div>
+            {([
+              ['certPath', 'Server Certificate (PEM)'],
+              ['keyPath', 'Server Private Key (PEM)'],
+              ['clientCaPath', 'Client CA Certificate (PEM)']
+            ] as const).map(([key, label]) => (
+              <div key={key} className="setting-item">
+                <label>{label}</label>
+                <input
+                  type="text"
+                  className="text-input"
+                  defaultValue={remoteAccess.clientCert[key]}
+                  onBlur={(e) => updateClientCert({ [key]: e.target.value.trim() })}
+                  style={{ width: '100%' }}
+                />
+              </div>
+            ))}
+          </>
+        )}
+      </CollapsibleSection>
+
+      <CollapsibleSection title="Audit Log">
+        <p className="setting-hint" style={{ marginBottom: '12px' }}>
+          Changes remote clients made to settings, tower positions, model matching rules, video maps
+          and the tile cache.
+        </p>
+        <div className="setting-item">
+          <button className="control-button" onClick={loadAuditLog}>
+            {auditLog ? 'Refresh' : 'Show Audit Log'}
+          </button>
+        </div>
+        {auditLog && auditLog.length === 0 && (
+          <p className="setting-hint">No changes recorded yet.</p>
+        )}
+        {auditLog?.map((entry, index) => (
+          <div key={`${entry.timestamp}-${index}`} className="setting-item">
+            <label>
+              {new Date(entry.timestamp).toLocaleString()} - {entry.client}
+            </label>
+            <p className="setting-hint">
+              <code>{entry.method} {entry.path}</code>
+              {entry.status >= 400 && <span style={{ color: '#f44336' }}> (failed: {entry.status})</span>}
+            </p>
+            {entry.summary.map((line, i) => (
+              <p key={i} className="setting-hint" style={{ marginLeft: '12px' }}>{line}</p>
+            ))}
+          </div>
+        ))}
+      </CollapsibleSection>
     </>
   )
 }
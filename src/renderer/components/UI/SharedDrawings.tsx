@@ -0,0 +1,173 @@
+import { useEffect, useRef } from 'react'
+import * as Cesium from 'cesium'
+import { useDrawingStore } from '../../stores/drawingStore'
+import type { Drawing } from '../../types/drawings'
+
+/** Color for drawings made without one */
+const DEFAULT_DRAWING_COLOR = '#ff4081'
+
+interface SharedDrawingsProps {
+  cesiumViewer: Cesium.Viewer | null
+}
+
+/**
+ * Format a line's range and bearing (e.g., "2.35 nm 045°/225°")
+ */
+export function formatLineMeasure(drawing: Drawing): string {
+  if (!drawing.measure) return ''
+  const { distanceNm, bearing, reciprocal } = drawing.measure
+  const pad = (deg: number) => String(Math.round(deg) % 360).padStart(3, '0')
+  return `${distanceNm.toFixed(2)} nm ${pad(bearing)}°/${pad(reciprocal)}°`
+}
+
+function labelGraphics(text: string, color: Cesium.Color): Cesium.LabelGraphics.ConstructorOptions {
+  return {
+    text,
+    font: '14px sans-serif',
+    fillColor: color,
+    outlineColor: Cesium.Color.BLACK,
+    outlineWidth: 2,
+    style: Cesium.LabelStyle.FILL_AND_OUTLINE,
+    showBackground: true,
+    backgroundColor: new Cesium.Color(0, 0, 0, 0.6),
+    backgroundPadding: new Cesium.Cartesian2(6, 4),
+    verticalOrigin: Cesium.VerticalOrigin.BOTTOM,
+    heightReference: Cesium.HeightReference.CLAMP_TO_GROUND,
+    pixelOffset: new Cesium.Cartesian2(0, -10),
+    disableDepthTestDistance: Number.POSITIVE_INFINITY
+  }
+}
+
+function pointGraphics(color: Cesium.Color): Cesium.PointGraphics.ConstructorOptions {
+  return {
+    pixelSize: 8,
+    color,
+    outlineColor: Cesium.Color.WHITE,
+    outlineWidth: 2,
+    heightReference: Cesium.HeightReference.CLAMP_TO_GROUND,
+    disableDepthTestDistance: Number.POSITIVE_INFINITY
+  }
+}
+
+/**
+ * Cesium entities for one drawing
+ */
+function createEntities(viewer: Cesium.Viewer, drawing: Drawing): Cesium.Entity[] {
+  const color = Cesium.Color.fromCssColorString(drawing.color ?? DEFAULT_DRAWING_COLOR)
+  const positions = drawing.points.map((p) => Cesium.Cartesian3.fromDegrees(p.longitude, p.latitude))
+  const caption = [drawing.text, drawing.author ? `(${drawing.author})` : null].filter(Boolean).join(' ')
+  const entities: Cesium.Entity[] = []
+
+  switch (drawing.kind) {
+    case 'line': {
+      entities.push(
+        viewer.entities.add({
+          id: `drawing_${drawing.id}_line`,
+          polyline: { positions, width: 3, material: color, clampToGround: true }
+        })
+      )
+      positions.forEach((position, i) => {
+        entities.push(
+          viewer.entities.add({ id: `drawing_${drawing.id}_p${i}`, position, point: pointGraphics(color) })
+        )
+      })
+      const midpoint = Cesium.Cartesian3.midpoint(positions[0], positions[1], new Cesium.Cartesian3())
+      const text = [formatLineMeasure(drawing), caption].filter(Boolean).join('\n')
+      entities.push(
+        viewer.entities.add({ id: `drawing_${drawing.id}_label`, position: midpoint, label: labelGraphics(text, Cesium.Color.WHITE) })
+      )
+      break
+    }
+    case 'label': {
+      entities.push(
+        viewer.entities.add({
+          id: `drawing_${drawing.id}_label`,
+          position: positions[0],
+          point: pointGraphics(color),
+          label: labelGraphics(caption, color)
+        })
+      )
+      break
+    }
+    case 'polygon': {
+      entities.push(
+        viewer.entities.add({
+          id: `drawing_${drawing.id}_polygon`,
+          polygon: {
+            hierarchy: new Cesium.PolygonHierarchy(positions),
+            material: color.withAlpha(0.25),
+            classificationType: Cesium.ClassificationType.BOTH
+          }
+        })
+      )
+      entities.push(
+        viewer.entities.add({
+          id: `drawing_${drawing.id}_outline`,
+          polyline: { positions: [...positions, positions[0]], width: 2, material: color, clampToGround: true }
+        })
+      )
+      if (caption) {
+        const center = Cesium.BoundingSphere.fromPoints(positions).center
+        const cartographic = Cesium.Cartographic.fromCartesian(center)
+        entities.push(
+          viewer.entities.add({
+            id: `drawing_${drawing.id}_label`,
+            position: Cesium.Cartesian3.fromRadians(cartographic.longitude, cartographic.latitude),
+            label: labelGraphics(caption, color)
+          })
+        )
+      }
+      break
+    }
+  }
+  return entities
+}
+
+/**
+ * Renders the shared drawing board (lines, labels and polygons drawn on any display)
+ */
+function SharedDrawings({ cesiumViewer }: SharedDrawingsProps) {
+  const drawings = useDrawingStore((state) => state.board?.drawings)
+
+  // Entities keyed by drawing id and creation time (ids restart in a new session)
+  const entitiesRef = useRef<Map<string, Cesium.Entity[]>>(new Map())
+
+  useEffect(() => {
+    if (!cesiumViewer || cesiumViewer.isDestroyed()) return
+
+    const entitiesMap = entitiesRef.current
+    const current = new Map((drawings ?? []).map((d) => [`${d.id}@${d.createdAt}`, d]))
+
+    // Remove entities for deleted drawings
+    for (const [key, entities] of entitiesMap) {
+      if (!current.has(key)) {
+        entities.forEach((entity) => cesiumViewer.entities.remove(entity))
+        entitiesMap.delete(key)
+      }
+    }
+
+    // Create entities for new drawings (drawings never change once added)
+    for (const [key, drawing] of current) {
+      if (!entitiesMap.has(key)) {
+        entitiesMap.set(key, createEntities(cesiumViewer, drawing))
+      }
+    }
+  }, [cesiumViewer, drawings])
+
+  // Remove everything when the viewer goes away
+  useEffect(() => {
+    const entitiesMap = entitiesRef.current
+    return () => {
+      if (cesiumViewer && !cesiumViewer.isDestroyed()) {
+        for (const entities of entitiesMap.values()) {
+          entities.forEach((entity) => cesiumViewer.entities.remove(entity))
+        }
+      }
+      entitiesMap.clear()
+    }
+  }, [cesiumViewer])
+
+  return null
+}
+
+export default SharedDrawings
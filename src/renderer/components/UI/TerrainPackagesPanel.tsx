@@ -0,0 +1,147 @@
+/**
+ * Terrain Packages Panel
+ *
+ * Imports pre-generated quantized-mesh terrain (e.g. from Cesium Terrain
+ * Builder) into the host's app data folder and selects one as the terrain
+ * provider, so terrain works without an internet connection. Importing and
+ * deleting run on the host (terrain_packages.rs); remote browsers can only
+ * pick an imported package.
+ */
+
+import { useState, useEffect, useCallback } from 'react'
+import { isTauri, terrainPackagesApi } from '../../utils/tauriApi'
+import { useGlobalSettingsStore } from '../../stores/globalSettingsStore'
+import type { TerrainImportProgress, TerrainPackage } from '../../types/mapProviders'
+import CollapsibleSection from './settings/CollapsibleSection'
+import './BackupPanel.css'
+
+const formatBytes = (bytes: number): string => {
+  if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`
+  if (bytes < 1024 * 1024 * 1024) return `${(bytes / (1024 * 1024)).toFixed(1)} MB`
+  return `${(bytes / (1024 * 1024 * 1024)).toFixed(2)} GB`
+}
+
+function TerrainPackagesPanel() {
+  const terrain = useGlobalSettingsStore((state) => state.mapProviders.terrain)
+  const terrainPackage = useGlobalSettingsStore((state) => state.mapProviders.terrainPackage)
+  const updateMapProviders = useGlobalSettingsStore((state) => state.updateMapProviders)
+
+  const [packages, setPackages] = useState<TerrainPackage[]>([])
+  const [busy, setBusy] = useState<string | null>(null)
+  const [message, setMessage] = useState<string | null>(null)
+  const [error, setError] = useState<string | null>(null)
+
+  const refresh = useCallback(() => {
+    terrainPackagesApi.list().then(setPackages).catch((err) => setError(String(err)))
+  }, [])
+
+  useEffect(() => {
+    refresh()
+  }, [refresh])
+
+  // Import progress from the host
+  useEffect(() => {
+    if (!isTauri()) return
+    let unlisten: (() => void) | null = null
+    import('@tauri-apps/api/event').then(async ({ listen }) => {
+      unlisten = await listen<TerrainImportProgress>('terrain-import-progress', (event) => {
+        setBusy(`Importing ${event.payload.name}... ${event.payload.tiles.toLocaleString()} tiles`)
+      })
+    })
+    return () => {
+      if (unlisten) unlisten()
+    }
+  }, [])
+
+  const run = async (label: string, action: () => Promise<string | null>) => {
+    setBusy(label)
+    setError(null)
+    setMessage(null)
+    try {
+      setMessage(await action())
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err))
+    } finally {
+      setBusy(null)
+      refresh()
+    }
+  }
+
+  const handleImport = (folder: boolean) => run('Importing...', async () => {
+    const path = await terrainPackagesApi.pick(folder)
+    if (!path) return null
+    const imported = await terrainPackagesApi.import(path)
+    return `Imported ${imported.name} (${imported.tiles.toLocaleString()} tiles, ${formatBytes(imported.bytes)})`
+  })
+
+  const handleDelete = (pkg: TerrainPackage) => run('Deleting...', async () => {
+    if (!window.confirm(`Delete the terrain package "${pkg.name}"?`)) return null
+    await terrainPackagesApi.delete(pkg.id)
+    if (terrain === 'package' && terrainPackage === pkg.id) {
+      await updateMapProviders({ terrain: 'ion', terrainPackage: '' })
+    }
+    return `Deleted ${pkg.name}`
+  })
+
+  if (!isTauri() && packages.length === 0) {
+    return null
+  }
+
+  return (
+    <CollapsibleSection title="Offline Terrain Packages">
+      <p className="setting-hint" style={{ marginBottom: '12px' }}>
+        Quantized-mesh terrain generated ahead of time (e.g. with Cesium Terrain Builder), imported from a
+        folder or a .tar, .tar.gz or .tar.zst archive. Together with the tile cache for imagery, the cab works
+        without an internet connection.
+      </p>
+
+      {isTauri() && (
+        <div className="setting-item">
+          <div className="setting-row">
+            <button className="control-button" onClick={() => handleImport(true)} disabled={busy !== null}>
+              Import Folder...
+            </button>
+            <button className="control-button" onClick={() => handleImport(false)} disabled={busy !== null}>
+              Import Archive...
+            </button>
+          </div>
+          {busy && <p className="setting-hint">{busy}</p>}
+          {message && <p className="setting-hint backup-message">{message}</p>}
+          {error && <p className="setting-hint backup-error">{error}</p>}
+        </div>
+      )}
+
+      {packages.length > 0 && (
+        <div className="setting-item">
+          <label>Packages</label>
+          {packages.map((pkg) => {
+            const inUse = terrain === 'package' && terrainPackage === pkg.id
+            return (
+              <div key={pkg.id} className="setting-row">
+                <span className="setting-hint backup-name">
+                  {pkg.name} - {pkg.tiles.toLocaleString()} tiles, {formatBytes(pkg.bytes)}
+                  {pkg.maxZoom !== null && `, zoom ${pkg.maxZoom}`}
+                  {inUse && ' (in use)'}
+                </span>
+                <button
+                  className="control-button"
+                  onClick={() => updateMapProviders({ terrain: 'package', terrainPackage: pkg.id })}
+                  disabled={busy !== null || inUse}
+                >
+                  Use
+                </button>
+                {isTauri() && (
+                  <button className="control-button" onClick={() => handleDelete(pkg)} disabled={busy !== null}>
+                    Delete
+                  </button>
+                )}
+              </div>
+            )
+          })}
+        </div>
+      )}
+    </CollapsibleSection>
+  )
+}
+
+export default TerrainPackagesPanel
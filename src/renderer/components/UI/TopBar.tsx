@@ -7,6 +7,8 @@ import { useViewportStore } from '../../stores/viewportStore'
 import { useIsMobileLayout } from '../../hooks/useIsMobileLayout'
 import RemoteIndicator from './RemoteIndicator'
 import RemoteClientsIndicator from './RemoteClientsIndicator'
+import WakeTimersIndicator from './WakeTimersIndicator'
+import CoordinationPanel from './CoordinationPanel'
 import MobileToolsFlyout from './MobileToolsFlyout'
 import './TopBar.css'
 
@@ -103,6 +105,10 @@ function TopBar({ onCommandClick }: TopBarProps) {
       </div>
 
       <div className="top-bar-right">
+        {/* Departure wake timer countdowns - shown while a timer runs */}
+        <WakeTimersIndicator />
+        {/* Messages between displays */}
+        <CoordinationPanel />
         {/* Remote clients indicator - always shown on desktop when clients connected */}
         <RemoteClientsIndicator />
         {/* Status info and remote indicator - hidden on mobile (shown in flyout) */}
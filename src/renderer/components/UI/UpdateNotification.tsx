@@ -1,8 +1,10 @@
 import { useEffect, useCallback } from 'react'
 import { useUpdateStore } from '../../stores/updateStore'
 import {
+  deferUpdates,
   downloadAndInstallUpdate,
   restartApp,
+  skipUpdate,
   startAutoUpdateCheck,
   stopAutoUpdateCheck
 } from '../../services/UpdateService'
@@ -13,7 +15,7 @@ import './UpdateNotification.css'
  * Update notification component that displays at top of screen
  *
  * Shows notifications for:
- * - Update available (with download button)
+ * - Update available (with download, later and skip buttons)
  * - Download progress
  * - Ready to restart
  * - Errors
@@ -50,6 +52,14 @@ function UpdateNotification() {
     reset()
   }, [reset])
 
+  const handleLater = useCallback(() => {
+    deferUpdates(24)
+  }, [])
+
+  const handleSkip = useCallback(() => {
+    skipUpdate()
+  }, [])
+
   // Don't render in remote mode - updates are handled by host
   // Don't render for idle, checking, or up-to-date states
   if (inRemoteMode || status === 'idle' || status === 'checking' || status === 'up-to-date') {
@@ -69,13 +79,17 @@ function UpdateNotification() {
           <>
             <span className="update-message">
               Update available: v{updateInfo.version}
+              {updateInfo.channel === 'beta' && ' (beta)'}
             </span>
             <button className="update-button primary" onClick={handleDownload}>
               Download & Install
             </button>
-            <button className="update-button secondary" onClick={handleDismiss}>
+            <button className="update-button secondary" onClick={handleLater} title="Remind me in 24 hours">
               Later
             </button>
+            <button className="update-button secondary" onClick={handleSkip} title="Don't offer this version again">
+              Skip
+            </button>
           </>
         )}
 
@@ -0,0 +1,52 @@
+/**
+ * Wake Timers Indicator
+ *
+ * Counts down the departure wake timers running on the host's runways
+ * (two minutes behind a departing Heavy, three behind a Super).
+ * Hidden while no timer runs.
+ */
+
+import { useEffect, useState } from 'react'
+import { useWakeTimerStore } from '../../stores/wakeTimerStore'
+import './WakeTimersIndicator.css'
+
+function formatRemaining(ms: number): string {
+  const seconds = Math.ceil(ms / 1000)
+  return `${Math.floor(seconds / 60)}:${String(seconds % 60).padStart(2, '0')}`
+}
+
+function WakeTimersIndicator() {
+  const timers = useWakeTimerStore((state) => state.timers)
+  const clockOffsetMs = useWakeTimerStore((state) => state.clockOffsetMs)
+  const [now, setNow] = useState(() => Date.now())
+
+  // Tick once a second while a timer runs
+  useEffect(() => {
+    if (timers.length === 0) return
+    const interval = setInterval(() => setNow(Date.now()), 1000)
+    return () => clearInterval(interval)
+  }, [timers])
+
+  const running = timers
+    .map((timer) => ({ timer, remainingMs: timer.expiresAt + clockOffsetMs - now }))
+    .filter(({ remainingMs }) => remainingMs > 0)
+  if (running.length === 0) return null
+
+  return (
+    <div className="wake-timers-indicator">
+      {running.map(({ timer, remainingMs }) => (
+        <span
+          key={timer.runway}
+          className="wake-timer"
+          title={`Wake timer behind ${timer.callsign}${timer.aircraftType ? ` (${timer.aircraftType})` : ''}`}
+        >
+          <span className="wake-timer-runway">{timer.runway}</span>
+          <span className="wake-timer-category">{timer.wakeCategory}</span>
+          <span className="wake-timer-remaining">{formatRemaining(remainingMs)}</span>
+        </span>
+      ))}
+    </div>
+  )
+}
+
+export default WakeTimersIndicator
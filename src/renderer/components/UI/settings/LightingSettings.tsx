@@ -1,4 +1,5 @@
 import { useSettingsStore } from '../../../stores/settingsStore'
+import { useGlobalSettingsStore } from '../../../stores/globalSettingsStore'
 import { formatTimeHour } from '../../../utils/formatting'
 import CollapsibleSection from './CollapsibleSection'
 import '../ControlsBar.css'
@@ -12,6 +13,9 @@ function LightingSettings() {
   const aircraftNightVisibility = useSettingsStore((state) => state.graphics.aircraftNightVisibility)
   const updateCesiumSettings = useSettingsStore((state) => state.updateCesiumSettings)
   const updateGraphicsSettings = useSettingsStore((state) => state.updateGraphicsSettings)
+  const simClock = useGlobalSettingsStore((state) => state.clock)
+  const updateClock = useGlobalSettingsStore((state) => state.updateClock)
+  const offsetHours = simClock.offsetMs / 3600000
 
   return (
     <CollapsibleSection title="Lighting">
@@ -58,6 +62,62 @@ function LightingSettings() {
         </div>
       )}
 
+      <div className="setting-item">
+        <label>Shared Clock</label>
+        <div className="radio-group">
+          <label>
+            <input
+              type="radio"
+              name="simClockMode"
+              value="real"
+              checked={simClock.mode === 'real'}
+              onChange={() => updateClock({ mode: 'real' })}
+            />
+            Real Time
+          </label>
+          <label>
+            <input
+              type="radio"
+              name="simClockMode"
+              value="offset"
+              checked={simClock.mode === 'offset'}
+              onChange={() => updateClock({ mode: 'offset' })}
+            />
+            Offset
+          </label>
+          <label>
+            <input
+              type="radio"
+              name="simClockMode"
+              value="fixed"
+              checked={simClock.mode === 'fixed'}
+              onChange={() => updateClock({ mode: 'fixed', fixedTime: Date.now() })}
+            />
+            Frozen
+          </label>
+        </div>
+        <p className="setting-hint">
+          Applies to the host and every remote browser, overriding Time of Day (e.g., force daytime during a night event).
+        </p>
+      </div>
+
+      {simClock.mode === 'offset' && (
+        <div className="setting-item">
+          <label>Clock Offset</label>
+          <div className="slider-with-value">
+            <input
+              type="range"
+              min="-12"
+              max="12"
+              step="0.5"
+              value={offsetHours}
+              onChange={(e) => updateClock({ offsetMs: Number(e.target.value) * 3600000 })}
+            />
+            <span>{offsetHours > 0 ? '+' : ''}{offsetHours}h</span>
+          </div>
+        </div>
+      )}
+
       <div className="setting-item">
         <label>
           <input
@@ -1,12 +1,13 @@
 import { useSettingsStore } from '../../../stores/settingsStore'
 import CollapsibleSection from './CollapsibleSection'
 import '../ControlsBar.css'
-import type { BuildingQuality } from '../../../types'
+import type { BuildingQuality, BuildingSource } from '../../../types'
 
 function TerrainSettings() {
   const terrainQuality = useSettingsStore((state) => state.cesium.terrainQuality)
   const show3DBuildings = useSettingsStore((state) => state.cesium.show3DBuildings)
   const buildingQuality = useSettingsStore((state) => state.cesium.buildingQuality)
+  const buildingSource = useSettingsStore((state) => state.cesium.buildingSource)
   const updateCesiumSettings = useSettingsStore((state) => state.updateCesiumSettings)
 
   return (
@@ -43,6 +44,23 @@ function TerrainSettings() {
         </p>
       </div>
 
+      {show3DBuildings && (
+        <div className="setting-item">
+          <label>Building Source</label>
+          <select
+            value={buildingSource ?? 'ion'}
+            onChange={(e) => updateCesiumSettings({ buildingSource: e.target.value as BuildingSource })}
+          >
+            <option value="ion">Cesium OSM Buildings (Ion)</option>
+            <option value="osm">Generated from OpenStreetMap (current airport)</option>
+          </select>
+          <p className="setting-hint">
+            Generated buildings are extruded from OpenStreetMap footprints within about 5 km of the airport
+            and hosted by this app. Use them where Ion buildings are missing or inaccurate.
+          </p>
+        </div>
+      )}
+
       {show3DBuildings && (
         <div className="setting-item">
           <label>Building Quality</label>
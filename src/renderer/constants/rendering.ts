@@ -343,6 +343,22 @@ export const DATABLOCK_HEIGHT_MULTIPLIER = 0.4
  */
 export const DATABLOCK_LEADER_LINE_HEIGHT_MULTIPLIER = 0.25
 
+/**
+ * Datablock colors by the level of the position an aircraft is talking to
+ *
+ * Used when the shared datablock layout has `colorByFrequency` on, so
+ * aircraft on tower, ground and approach are told apart at a glance.
+ * RGB components are 0-1.
+ */
+export const FREQUENCY_LEVEL_COLORS: Record<string, { r: number; g: number; b: number }> = {
+  delivery: { r: 0.7, g: 0.5, b: 1 },
+  ground: { r: 1, g: 0.5, b: 0 },
+  tower: { r: 1, g: 0.3, b: 0.3 },
+  approach: { r: 0.3, g: 0.6, b: 1 },
+  center: { r: 0, g: 1, b: 0 },
+  fss: { r: 0.6, g: 0.6, b: 0.6 }
+}
+
 /**
  * Horizontal offset for datablock attachment point in meters
  *
@@ -88,6 +88,9 @@ const sharedTimelineLastFrameTimeRef = { current: 0 }
 /** Track playback mode to detect mode changes */
 const sharedLastPlaybackModeRef = { current: 'live' as string }
 
+/** Imported snapshots loaded into the timeline (a new host replay replaces them without a mode change) */
+const sharedLoadedSnapshotsRef = { current: null as unknown[] | null }
+
 // Store subscribers for triggering re-renders
 const subscribers = new Set<() => void>()
 
@@ -301,13 +304,16 @@ function updateInterpolation() {
   // ============================================================================
   const currentMode = source.playbackMode
   const previousMode = sharedLastPlaybackModeRef.current
+  const importedSnapshots = currentMode === 'imported' ? useReplayStore.getState().importedSnapshots : null
+  const importedChanged = importedSnapshots !== null && importedSnapshots !== sharedLoadedSnapshotsRef.current
 
-  if (currentMode !== previousMode) {
+  if (currentMode !== previousMode || importedChanged) {
     sharedLastPlaybackModeRef.current = currentMode
+    sharedLoadedSnapshotsRef.current = importedSnapshots
     const timelineStore = useAircraftTimelineStore.getState()
 
     if (currentMode === 'imported') {
-      // Entering imported replay mode - load external snapshots into timeline store
+      // Entering imported replay mode (or a new host replay) - load external snapshots into timeline store
       // This replaces the timeline with data from an imported file
       const replayState = useReplayStore.getState()
       const snapshots = replayState.getActiveSnapshots()
@@ -0,0 +1,156 @@
+/**
+ * Aural Alerts Hook
+ *
+ * Plays the sound pack selected in the global settings (mods/sounds, see
+ * sound_packs.rs) when an alert is raised:
+ * - 'conflict': a new STCA warning pair
+ * - 'emergency': a new emergency, hijack or radio failure squawk
+ * - 'incursion': an aircraft entering a runway that is already occupied
+ *
+ * Conflict and emergency alerts come from the STCA and squawk stores. Runway
+ * entries arrive as `runway-occupancy-changed` events on the desktop app and
+ * over the `/api/runways/occupancy/ws` WebSocket in remote browsers.
+ */
+
+import { useCallback, useEffect, useRef } from 'react'
+import { useGlobalSettingsStore } from '../stores/globalSettingsStore'
+import { useStcaStore } from '../stores/stcaStore'
+import { useSquawkStore } from '../stores/squawkStore'
+import { isTauri, soundsApi } from '../utils/tauriApi'
+import { isRemoteMode } from '../utils/remoteMode'
+import type { AlertSoundEvent } from '../types/settings'
+import type { RunwayOccupancyEvent, StcaAlert } from '../types/vatsim'
+
+/** The same event type is not played again within this time (ms) */
+const REPEAT_INTERVAL_MS = 2000
+
+const warningPairs = (alerts: StcaAlert[]) =>
+  new Set(alerts.filter((alert) => alert.severity === 'warning').map((alert) => alert.callsigns.join('|')))
+
+/**
+ * Play alert sounds from the selected sound pack.
+ * Call once at the app root.
+ */
+export function useAlertSounds() {
+  const pack = useGlobalSettingsStore((state) => state.alertSounds.pack)
+  const urlsRef = useRef<Partial<Record<AlertSoundEvent, string>>>({})
+  const lastPlayedRef = useRef<Partial<Record<AlertSoundEvent, number>>>({})
+
+  // Resolve playable URLs for the selected pack's sounds
+  useEffect(() => {
+    urlsRef.current = {}
+    if (!pack) return
+
+    let cancelled = false
+    soundsApi.list()
+      .then(async (packs) => {
+        const selected = packs.find((p) => p.id === pack)
+        if (!selected) {
+          console.warn(`[AlertSounds] Sound pack '${pack}' not found in mods/sounds`)
+          return
+        }
+        const urls: Partial<Record<AlertSoundEvent, string>> = {}
+        for (const [event, sound] of Object.entries(selected.sounds)) {
+          if (sound) urls[event as AlertSoundEvent] = await soundsApi.getSoundUrl(selected, sound)
+        }
+        if (!cancelled) urlsRef.current = urls
+      })
+      .catch((error) => console.error('[AlertSounds] Failed to load sound packs:', error))
+
+    return () => {
+      cancelled = true
+    }
+  }, [pack])
+
+  const play = useCallback((event: AlertSoundEvent) => {
+    const url = urlsRef.current[event]
+    const { volume, muted } = useGlobalSettingsStore.getState().alertSounds
+    if (!url || muted.includes(event)) return
+
+    const now = Date.now()
+    if (now - (lastPlayedRef.current[event] ?? 0) < REPEAT_INTERVAL_MS) return
+    lastPlayedRef.current[event] = now
+
+    const audio = new Audio(url)
+    audio.volume = Math.min(1, Math.max(0, volume))
+    // Browsers refuse to play before the first user interaction
+    audio.play().catch((error) => console.warn(`[AlertSounds] Could not play '${event}':`, error))
+  }, [])
+
+  // Conflict and emergency alerts
+  useEffect(() => {
+    const unsubscribeStca = useStcaStore.subscribe((state, previous) => {
+      const before = warningPairs(previous.alerts)
+      if ([...warningPairs(state.alerts)].some((pair) => !before.has(pair))) play('conflict')
+    })
+    const unsubscribeSquawk = useSquawkStore.subscribe((state, previous) => {
+      if ([...state.emergencyCallsigns].some((callsign) => !previous.emergencyCallsigns.has(callsign))) {
+        play('emergency')
+      }
+    })
+
+    return () => {
+      unsubscribeStca()
+      unsubscribeSquawk()
+    }
+  }, [play])
+
+  // Runway entries onto an occupied runway
+  useEffect(() => {
+    const onOccupancyEvent = (event: RunwayOccupancyEvent) => {
+      if (event.kind === 'entered' && event.alreadyOccupiedBy.length > 0) play('incursion')
+    }
+
+    if (isTauri()) {
+      let unlisten: (() => void) | null = null
+      let cancelled = false
+
+      import('@tauri-apps/api/event')
+        .then(({ listen }) => listen<RunwayOccupancyEvent>('runway-occupancy-changed', (event) => onOccupancyEvent(event.payload)))
+        .then((fn) => {
+          if (cancelled) fn()
+          else unlisten = fn
+        })
+        .catch((error) => console.error('[AlertSounds] Failed to listen for runway occupancy:', error))
+
+      return () => {
+        cancelled = true
+        unlisten?.()
+      }
+    }
+
+    // Only remote browsers have a host to listen to
+    if (!isRemoteMode()) return
+
+    let ws: WebSocket | null = null
+    let reconnectTimeout: ReturnType<typeof setTimeout> | null = null
+    let closed = false
+
+    const connect = () => {
+      const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:'
+      ws = new WebSocket(`${protocol}//${window.location.host}/api/runways/occupancy/ws`)
+
+      ws.onmessage = (event) => {
+        try {
+          onOccupancyEvent(JSON.parse(event.data) as RunwayOccupancyEvent)
+        } catch (error) {
+          console.warn('[AlertSounds] Ignoring invalid runway occupancy event:', error)
+        }
+      }
+
+      ws.onclose = () => {
+        ws = null
+        // Attempt to reconnect after 5 seconds
+        if (!closed) reconnectTimeout = setTimeout(connect, 5000)
+      }
+    }
+
+    connect()
+
+    return () => {
+      closed = true
+      if (reconnectTimeout) clearTimeout(reconnectTimeout)
+      ws?.close()
+    }
+  }, [play])
+}
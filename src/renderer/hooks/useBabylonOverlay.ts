@@ -8,6 +8,7 @@ import { useBabylonCameraSync } from './useBabylonCameraSync'
 import { useBabylonPrecipitation } from './useBabylonPrecipitation'
 import { useViewportStore } from '../stores/viewportStore'
 import { useSettingsStore } from '../stores/settingsStore'
+import { useGlobalSettingsStore } from '../stores/globalSettingsStore'
 
 /**
  * Orchestrator hook for Babylon.js overlay rendering synchronized with Cesium.
@@ -180,8 +181,9 @@ export function useBabylonOverlay({ cesiumViewer, canvas }: BabylonOverlayOption
   const viewMode = useViewportStore((state) => state.viewports.find(v => v.id === 'main')?.cameraState.viewMode)
   const isTopDownView = viewMode === 'topdown'
 
-  // Get datablock font size setting
+  // Get datablock font size: this device's size scaled by the shared datablock layout
   const datablockFontSize = useSettingsStore((state) => state.aircraft.datablockFontSize)
+  const datablockFontScale = useGlobalSettingsStore((state) => state.datablock.fontScale)
 
   // 1. Initialize scene (engine, scene, camera, GUI, lighting)
   const { engine, scene, camera, guiTexture, sceneReady } = useBabylonScene({
@@ -213,7 +215,7 @@ export function useBabylonOverlay({ cesiumViewer, canvas }: BabylonOverlayOption
   } = useBabylonLabels({
     guiTexture,
     isTopDownView,
-    fontSize: datablockFontSize
+    fontSize: Math.round(datablockFontSize * datablockFontScale)
   })
 
   // 5. Initialize ENU root node (coordinate system, transforms)
@@ -14,8 +14,10 @@ import {
   accelerateVelocity,
   calculateEffectiveMoveSpeed,
   calculateTargetVelocities,
+  addGamepadTargets,
   applyWheelImpulse
 } from '../utils/inputVelocity'
+import { getGamepadAxes } from '../utils/gamepadInput'
 import {
   PITCH_MIN,
   PITCH_MAX,
@@ -635,8 +637,14 @@ export function useCameraInput(
         return
       }
 
-      // Calculate target velocities based on pressed keys
-      const targets = calculateTargetVelocities(keys, viewModeRef.current, followingCallsignRef.current, followModeRef.current)
+      // Calculate target velocities based on pressed keys and gamepad sticks
+      const targets = addGamepadTargets(
+        calculateTargetVelocities(keys, viewModeRef.current, followingCallsignRef.current, followModeRef.current),
+        getGamepadAxes(),
+        viewModeRef.current,
+        followingCallsignRef.current,
+        followModeRef.current
+      )
 
       // WASD movement (shift = sprint)
       const shiftHeld = keys.has('shift')
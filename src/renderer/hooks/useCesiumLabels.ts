@@ -1,15 +1,21 @@
 import { useEffect, useCallback } from 'react'
 import * as Cesium from 'cesium'
-import type { InterpolatedAircraftState } from '../types/vatsim'
+import type { FrequencyContact, InterpolatedAircraftState } from '../types/vatsim'
 import type { ViewMode } from '../types'
 import { aircraftModelService } from '../services/AircraftModelService'
 import { calculateDistanceNM } from '../utils/interpolation'
 import { useDatablockPositionStore } from '../stores/datablockPositionStore'
 import { useViewportStore } from '../stores/viewportStore'
 import { useGlobalSettingsStore } from '../stores/globalSettingsStore'
-import { GROUNDSPEED_THRESHOLD_KNOTS, DATABLOCK_LEADER_LINE_HEIGHT_MULTIPLIER } from '../constants/rendering'
+import { useStcaStore } from '../stores/stcaStore'
+import { useLabelLayoutStore } from '../stores/labelLayoutStore'
+import { useSquawkStore } from '../stores/squawkStore'
+import { useSelectionStore } from '../stores/selectionStore'
+import { GROUNDSPEED_THRESHOLD_KNOTS, DATABLOCK_LEADER_LINE_HEIGHT_MULTIPLIER, FREQUENCY_LEVEL_COLORS } from '../constants/rendering'
 import { filterAircraftForRendering } from './useRenderCulling'
 import { layoutLabels, layoutLabelsSimple, type LabelAircraftData, type LayoutConfig } from '../utils/labelLayout'
+import { formatDatablock } from '../utils/datablockFormat'
+import type { DatablockField } from '../types'
 
 export type DatablockMode = 'none' | 'full' | 'airline'
 
@@ -27,6 +33,14 @@ interface UseCesiumLabelsParams {
   babylonOverlay: BabylonOverlay | null
   interpolatedAircraft: Map<string, InterpolatedAircraftState>
   datablockMode: DatablockMode
+  /** Fields on each datablock line (shared datablock layout) */
+  datablockLines: DatablockField[][]
+  /** Gate id by callsign, for the `gate` field */
+  gateAssignments: Map<string, string>
+  /** Position each aircraft is talking to, for the `freq` field and frequency coloring */
+  frequencyContacts: Map<string, FrequencyContact>
+  /** Color datablocks by the level of the position the aircraft is talking to */
+  colorByFrequency: boolean
   viewMode: ViewMode
   followingCallsign: string | null
   currentAirportIcao: string | null
@@ -53,7 +67,7 @@ interface UseCesiumLabelsParams {
  * Manages aircraft label rendering with filtering and overlap detection
  *
  * ## Responsibilities
- * - Generate datablock text (callsign, type, altitude, speed)
+ * - Generate datablock text from the shared datablock layout (see datablockFormat)
  * - Filter labels by distance, ground/airborne, search query, airport traffic
  * - Calculate label positions with overlap avoidance
  * - Apply weather-based visibility culling (clouds, fog)
@@ -77,8 +91,8 @@ interface UseCesiumLabelsParams {
  *
  * ## Datablock Modes
  * - **none**: No labels shown
- * - **full**: Full callsign + type + altitude/speed
- * - **airline**: 3-letter airline code (AAL123 → AAL) + type + altitude/speed
+ * - **full**: Fields of the datablock layout (default: callsign / type altitude speed)
+ * - **airline**: Same, with the callsign shortened to the airline code (AAL123 → AAL)
  *
  * ## Weather Visibility Culling
  * Labels hidden when:
@@ -103,6 +117,10 @@ interface UseCesiumLabelsParams {
  *   babylonOverlay,
  *   interpolatedAircraft,
  *   datablockMode: 'full',
+ *   datablockLines: [['callsign'], ['type', 'altitude', 'speed']],
+ *   gateAssignments: new Map(),
+ *   frequencyContacts: new Map(),
+ *   colorByFrequency: false,
  *   viewMode: '3d',
  *   followingCallsign: null,
  *   currentAirportIcao: 'KBOS',
@@ -124,6 +142,10 @@ export function useCesiumLabels(params: UseCesiumLabelsParams) {
     babylonOverlay,
     interpolatedAircraft,
     datablockMode,
+    datablockLines,
+    gateAssignments,
+    frequencyContacts,
+    colorByFrequency,
     viewMode,
     followingCallsign,
     currentAirportIcao,
@@ -176,6 +198,14 @@ export function useCesiumLabels(params: UseCesiumLabelsParams) {
 
     const seenCallsigns = new Set<string>()
 
+    // Aircraft in a short-term conflict flash red (twice per second)
+    const conflictCallsigns = useStcaStore.getState().conflictCallsigns
+    // ...as do aircraft squawking 7500/7600/7700
+    const emergencyCallsigns = useSquawkStore.getState().emergencyCallsigns
+    // Aircraft selected on another display stands out in magenta
+    const sharedCallsign = useSelectionStore.getState().selection?.callsign ?? null
+    const conflictFlashOn = Math.floor(performance.now() / 500) % 2 === 0
+
     // Iterate over culled/filtered aircraft (closest to camera, up to max limit)
     for (const aircraft of filteredAircraft.values()) {
       seenCallsigns.add(aircraft.callsign)
@@ -191,6 +221,7 @@ export function useCesiumLabels(params: UseCesiumLabelsParams) {
       )
 
       const isFollowed = aircraft.callsign === followingCallsign
+      const isShared = aircraft.callsign === sharedCallsign
       const altitudeMeters = aircraft.interpolatedAltitude  // Altitude is in METERS
       // Aircraft is on ground if groundspeed < threshold (40 knots)
       const isAirborne = aircraft.interpolatedGroundspeed >= GROUNDSPEED_THRESHOLD_KNOTS
@@ -198,8 +229,8 @@ export function useCesiumLabels(params: UseCesiumLabelsParams) {
       // Determine if this aircraft should have a datablock shown
       let showDatablock = false
 
-      if (isFollowed) {
-        // Always show followed aircraft datablock
+      if (isFollowed || isShared) {
+        // Always show followed and shared-selection aircraft datablocks
         showDatablock = true
       } else {
         // Apply datablock filters
@@ -268,37 +299,39 @@ export function useCesiumLabels(params: UseCesiumLabelsParams) {
         }
       }
 
-      // Format datablock text based on datablockMode setting
+      // Format datablock text from the shared layout and datablockMode setting
       let labelText = ''
       if (datablockMode !== 'none' && showDatablock) {
-        const type = aircraft.aircraftType || '????'
-        const speedTens = Math.round(aircraft.interpolatedGroundspeed / 10).toString().padStart(2, '0')
-        // Convert altitude to feet for FL display (METERS → FEET), clamp to 0 minimum
-        const altitudeFeet = Math.max(0, aircraft.interpolatedAltitude / 0.3048)
-        const dataLine = isAirborne
-          ? `${Math.round(altitudeFeet / 100).toString().padStart(3, '0')} ${speedTens}`
-          : speedTens
-
-        // Format callsign based on mode
-        let displayCallsign = aircraft.callsign
-        if (datablockMode === 'airline') {
-          // Check if callsign matches airline pattern: exactly 3 letters followed by 1-4 digits
-          const airlinePattern = /^([A-Z]{3})\d{1,4}$/
-          const match = aircraft.callsign.match(airlinePattern)
-          if (match) {
-            // Show only the airline ICAO code (first 3 letters)
-            displayCallsign = match[1]
-          }
-          // If doesn't match pattern, show full callsign (e.g., N12345)
-        }
-
-        labelText = `${displayCallsign}\n${type} ${dataLine}`
+        labelText = formatDatablock(datablockLines, {
+          callsign: aircraft.callsign,
+          aircraftType: aircraft.aircraftType,
+          // Convert altitude to feet for FL display (METERS → FEET)
+          altitudeFeet: aircraft.interpolatedAltitude / 0.3048,
+          groundspeedKnots: aircraft.interpolatedGroundspeed,
+          // Vertical rate is in METERS/MINUTE
+          verticalRateFpm: aircraft.verticalRate / 0.3048,
+          isAirborne,
+          gate: gateAssignments.get(aircraft.callsign),
+          departure: aircraft.departure,
+          arrival: aircraft.arrival,
+          squawk: aircraft.transponder,
+          frequency: frequencyContacts.get(aircraft.callsign)
+        }, datablockMode)
       }
 
       // Get color
+      const frequencyColor = colorByFrequency
+        ? FREQUENCY_LEVEL_COLORS[frequencyContacts.get(aircraft.callsign)?.level ?? '']
+        : undefined
       let babylonColor: { r: number; g: number; b: number }
-      if (isFollowed) {
+      if (conflictFlashOn && (conflictCallsigns.has(aircraft.callsign) || emergencyCallsigns.has(aircraft.callsign))) {
+        babylonColor = { r: 1, g: 0, b: 0 } // Red for short-term conflict alert or emergency squawk
+      } else if (isFollowed) {
         babylonColor = { r: 0, g: 1, b: 1 } // Cyan for followed
+      } else if (isShared) {
+        babylonColor = { r: 1, g: 0, b: 1 } // Magenta for selected on another display
+      } else if (frequencyColor) {
+        babylonColor = frequencyColor // By the position the aircraft is talking to
       } else if (!aircraft.isInterpolated) {
         babylonColor = { r: 1, g: 1, b: 0 } // Yellow for not interpolated (new/stale)
       } else if (isAirborne) {
@@ -366,11 +399,12 @@ export function useCesiumLabels(params: UseCesiumLabelsParams) {
 
     // Second pass: Project aircraft to screen and prepare for layout algorithm
     const labelWidth = 90
-    const labelHeight = 36
+    // 18px per datablock line
+    const labelHeight = 18 * Math.max(1, datablockLines.length)
     const modelRadius = viewMode === 'topdown' ? 15 : 15
-    // Leader distance setting: 4px per unit distance
+    // Leader length setting: 10px per unit distance
     // On mobile (< 1200px width), scale down leader lines to appear proportional
-    const leaderDistance = useGlobalSettingsStore.getState().display.leaderDistance ?? 2
+    const leaderDistance = useGlobalSettingsStore.getState().datablock.leaderLength ?? 2
     const screenWidth = typeof window !== 'undefined' ? window.innerWidth : 1920
     const screenHeight = typeof window !== 'undefined' ? window.innerHeight : 1080
     const mobileScale = screenWidth < 1200 ? 0.6 : 1.0  // 60% on mobile for proportional appearance
@@ -381,6 +415,13 @@ export function useCesiumLabels(params: UseCesiumLabelsParams) {
     const viewportStore = useViewportStore.getState()
     const globalPos = viewportStore.getDatablockPosition()
 
+    // Placements from the host (see label_layout.rs) resolve clutter the same
+    // way on every display. They are laid out in the tower view, so the 2D
+    // view keeps laying out its own labels
+    const hostLayout = useLabelLayoutStore.getState()
+    const useHostLayout = autoAvoidOverlaps && viewMode === '3d' &&
+      hostLayout.icao !== null && hostLayout.icao.toUpperCase() === currentAirportIcao?.toUpperCase()
+
     // Build data for layout algorithm
     const labelAircraftData: LabelAircraftData[] = []
 
@@ -414,7 +455,8 @@ export function useCesiumLabels(params: UseCesiumLabelsParams) {
 
       // Get custom position (per-aircraft override → global default)
       const perAircraftPos = datablockPositionStore.getAircraftPosition(data.callsign)
-      const preferredPosition = perAircraftPos ?? globalPos
+      const hostPlacement = useHostLayout && !perAircraftPos ? hostLayout.placements.get(data.callsign) : undefined
+      const preferredPosition = perAircraftPos ?? hostPlacement?.direction ?? globalPos
 
       // Calculate leader line Y attachment point based on position row
       const positionRow = Math.floor((preferredPosition - 1) / 3)
@@ -436,6 +478,7 @@ export function useCesiumLabels(params: UseCesiumLabelsParams) {
         aircraftScreenY,
         modelRadius,
         preferredPosition,
+        leaderScale: hostPlacement?.leaderScale,
         hasCustomPosition: perAircraftPos !== undefined,
         isFollowed: data.isFollowed,
         isAirborne: data.isAirborne,
@@ -453,8 +496,8 @@ export function useCesiumLabels(params: UseCesiumLabelsParams) {
       screenHeight
     }
 
-    // Run layout algorithm
-    const labelPositions = autoAvoidOverlaps
+    // Run layout algorithm (the host's placements only need drawing)
+    const labelPositions = autoAvoidOverlaps && !useHostLayout
       ? layoutLabels(labelAircraftData, layoutConfig)
       : layoutLabelsSimple(labelAircraftData, layoutConfig)
 
@@ -475,6 +518,10 @@ export function useCesiumLabels(params: UseCesiumLabelsParams) {
     babylonOverlay,
     interpolatedAircraft,
     datablockMode,
+    datablockLines,
+    gateAssignments,
+    frequencyContacts,
+    colorByFrequency,
     viewMode,
     followingCallsign,
     currentAirportIcao,
@@ -1,5 +1,7 @@
 import { useEffect, useRef, useState } from 'react'
 import * as Cesium from 'cesium'
+import { tileCacheApi } from '../utils/tauriApi'
+import type { MapProviderInfo } from '../types/mapProviders'
 import {
   AIRCRAFT_POOL_SIZE,
   getModelColorRgb,
@@ -45,6 +47,10 @@ export interface CesiumViewerSettings {
   inMemoryTileCacheSize: number
   /** Model brightness multiplier (0.5-1.5) */
   modelBrightness: number
+  /** Load terrain and imagery through the host's offline tile cache */
+  useTileCache: boolean
+  /** Selected terrain and imagery providers (null until loaded from the host) */
+  mapProviders: MapProviderInfo | null
 }
 
 export interface ModelPoolRefs {
@@ -148,7 +154,9 @@ export function useCesiumViewer(
     shadowFadingEnabled,
     shadowNormalOffset,
     inMemoryTileCacheSize,
-    modelBrightness
+    modelBrightness,
+    useTileCache,
+    mapProviders
   } = settings
 
   const viewerRef = useRef<Cesium.Viewer | null>(null)
@@ -168,9 +176,12 @@ export function useCesiumViewer(
   // Initialize Cesium viewer
   // This effect re-runs when MSAA changes, recreating the viewer with new settings
   useEffect(() => {
-    // Require a valid Cesium Ion token before creating the viewer
-    // Without a token, terrain and imagery loading will fail
-    if (!containerRef.current || !cesiumIonToken) return
+    // Require a valid Cesium Ion token before creating the viewer, unless
+    // neither terrain nor imagery comes from Ion
+    // Without a token, Ion terrain and imagery loading will fail
+    if (!containerRef.current || !mapProviders) return
+    const needsIon = mapProviders.terrain === 'ion' || mapProviders.imagery === 'ion'
+    if (needsIon && !cesiumIonToken) return
 
     // If viewer already exists, destroy it before recreating
     // This handles token changes (e.g., user entering token after first launch)
@@ -193,10 +204,30 @@ export function useCesiumViewer(
     // Set Ion access token
     Cesium.Ion.defaultAccessToken = cesiumIonToken
 
-    // Create viewer with default terrain and imagery
+    // Create viewer with default terrain and imagery, or tiles served by the
+    // host: from its offline cache (which fetches them from Ion) or from the
+    // other providers selected in settings, which are always proxied
     // Insets use reduced quality for performance
+    const hostTileUrl = tileCacheApi.getBaseUrl()
+    const terrainUrl = useTileCache || mapProviders.terrainProxied ? hostTileUrl : null
+    const imageryUrl = useTileCache || mapProviders.imageryProxied ? hostTileUrl : null
+    let terrain: Cesium.Terrain
+    if (mapProviders.terrain === 'ellipsoid') {
+      terrain = new Cesium.Terrain(Promise.resolve(new Cesium.EllipsoidTerrainProvider()))
+    } else if (terrainUrl) {
+      terrain = new Cesium.Terrain(Cesium.CesiumTerrainProvider.fromUrl(`${terrainUrl}terrain/`, { requestVertexNormals: true, requestWaterMask: true }))
+    } else {
+      terrain = Cesium.Terrain.fromWorldTerrain()
+    }
     const newViewer = new Cesium.Viewer(containerRef.current, {
-      terrain: Cesium.Terrain.fromWorldTerrain(),
+      terrain,
+      baseLayer: imageryUrl
+        ? new Cesium.ImageryLayer(new Cesium.UrlTemplateImageryProvider({
+          url: `${imageryUrl}imagery/{z}/{x}/{y}`,
+          minimumLevel: 1,
+          maximumLevel: mapProviders.imageryMaxZoom
+        }))
+        : undefined,
       animation: false,
       baseLayerPicker: false,
       fullscreenButton: false,
@@ -291,6 +322,11 @@ export function useCesiumViewer(
       }
     }
 
+    // Attribution for terrain and imagery not from Ion (Ion layers credit themselves)
+    for (const credit of mapProviders.credits) {
+      newViewer.creditDisplay.addStaticCredit(new Cesium.Credit(credit))
+    }
+
     // In-memory tile cache - reduced for insets (50 vs user setting)
     newViewer.scene.globe.tileCacheSize = isInset ? 50 : inMemoryTileCacheSize
 
@@ -397,7 +433,7 @@ export function useCesiumViewer(
       silhouetteStageRef.current = null
     }
   // eslint-disable-next-line react-hooks/exhaustive-deps -- graphics settings used at init only; runtime updates handled by separate hooks
-  }, [cesiumIonToken, isInset, msaaSamples, viewportId])
+  }, [cesiumIonToken, isInset, msaaSamples, viewportId, useTileCache, mapProviders])
 
   // Update model colors and blend amount when brightness setting changes
   useEffect(() => {
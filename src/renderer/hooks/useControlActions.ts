@@ -0,0 +1,105 @@
+/**
+ * Control Actions Hook
+ *
+ * Runs actions from control surfaces (Stream Deck / Companion via
+ * `/api/control/*`) and system-wide hotkeys. The host handles replay actions
+ * itself and sends camera and airport actions to the main window as
+ * `control-action` events. The main window also reports its airport, view
+ * mode and bookmarks back to the host, which lists them as button actions and
+ * feedback.
+ */
+
+import { useEffect } from 'react'
+import { useAirportStore } from '../stores/airportStore'
+import { useViewportStore } from '../stores/viewportStore'
+import { controlApi, isTauri, type ControlAction, type ControlView } from '../utils/tauriApi'
+import { findBookmarkSlot } from '../utils/startupArgs'
+import type { ViewMode } from '../types'
+
+function runAction({ action, value }: ControlAction) {
+  const viewport = useViewportStore.getState()
+  switch (action) {
+    case 'toggleViewMode':
+      viewport.toggleViewMode()
+      break
+    case 'viewMode':
+      viewport.setViewMode(value as ViewMode)
+      break
+    case 'airport': {
+      const icao = value?.toUpperCase()
+      const airportStore = useAirportStore.getState()
+      if (icao && airportStore.airports.has(icao)) airportStore.selectAirport(icao)
+      else console.warn(`[Control] Unknown airport ${value}`)
+      break
+    }
+    case 'bookmark': {
+      const icao = viewport.currentAirportIcao
+      if (!icao || !value) return
+      const slot = findBookmarkSlot(icao, value)
+      if (slot === null || !viewport.loadBookmark(slot)) {
+        console.warn(`[Control] No bookmark "${value}" at ${icao}`)
+      }
+      break
+    }
+  }
+}
+
+/** What the main window shows, as reported to the host */
+function currentView(): ControlView {
+  const state = useViewportStore.getState()
+  const icao = state.currentAirportIcao
+  const bookmarks = icao ? state.airportViewportConfigs[icao]?.bookmarks ?? {} : {}
+  return {
+    airport: icao,
+    viewMode: state.getActiveCameraState().viewMode,
+    bookmarks: Object.entries(bookmarks)
+      .map(([slot, bookmark]) => ({ slot: Number(slot), name: bookmark.name ?? null }))
+      .sort((a, b) => a.slot - b.slot)
+  }
+}
+
+/**
+ * Run control actions sent by the host and report the view back.
+ * Call once at the app root.
+ */
+export function useControlActions() {
+  useEffect(() => {
+    if (!isTauri()) return
+
+    let unlisten: (() => void) | null = null
+    let unsubscribe: (() => void) | null = null
+    let cancelled = false
+
+    // Only the main window is controlled (cab windows keep their own view)
+    import('@tauri-apps/api/webviewWindow')
+      .then(({ getCurrentWebviewWindow }) => {
+        const current = getCurrentWebviewWindow()
+        if (current.label !== 'main') return null
+
+        let published = ''
+        const publish = () => {
+          const view = currentView()
+          const json = JSON.stringify(view)
+          if (json === published) return
+          published = json
+          controlApi.publishView(view).catch((error) => console.warn('[Control] Failed to publish view:', error))
+        }
+        publish()
+        unsubscribe = useViewportStore.subscribe(publish)
+
+        return current.listen<ControlAction>('control-action', (event) => runAction(event.payload))
+      })
+      .then((fn) => {
+        if (!fn) return
+        if (cancelled) fn()
+        else unlisten = fn
+      })
+      .catch((error) => console.error('[Control] Failed to listen for control actions:', error))
+
+    return () => {
+      cancelled = true
+      unlisten?.()
+      unsubscribe?.()
+    }
+  }, [])
+}
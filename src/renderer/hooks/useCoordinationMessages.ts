@@ -0,0 +1,109 @@
+/**
+ * Coordination Messages Hook
+ *
+ * Keeps the coordination store in sync with the host's message channel
+ * (see coordination.rs). The desktop app loads the history and listens for
+ * `coordination-message` events; remote browsers receive the same messages
+ * over the `/api/messages/ws` WebSocket, which replays the history on connect.
+ * Messages from other displays raise a toast while the panel is closed.
+ */
+
+import { useEffect } from 'react'
+import { useCoordinationStore } from '../stores/coordinationStore'
+import { useSettingsStore } from '../stores/settingsStore'
+import { useUIFeedbackStore } from '../stores/uiFeedbackStore'
+import { coordinationApi, isTauri } from '../utils/tauriApi'
+import { isRemoteMode } from '../utils/remoteMode'
+import type { CoordinationMessage } from '../types/coordination'
+
+/**
+ * Send a message to every display, signed with this display's name
+ */
+export function sendCoordinationMessage(text: string): Promise<void> {
+  const from = useSettingsStore.getState().ui.selectionSourceName || undefined
+  return coordinationApi
+    .send(text, from)
+    .then((message) => {
+      useCoordinationStore.getState().addMessages([message], false)
+    })
+}
+
+function receive(message: CoordinationMessage) {
+  const added = useCoordinationStore.getState().addMessages([message], true)
+  if (added.length > 0 && !useCoordinationStore.getState().isOpen) {
+    useUIFeedbackStore.getState().showFeedback(`${message.from ?? 'Message'}: ${message.text}`, 'success')
+  }
+}
+
+/**
+ * Subscribe to coordination messages.
+ * Call once at the app root.
+ */
+export function useCoordinationMessages() {
+  useEffect(() => {
+    if (isTauri()) {
+      let unlisten: (() => void) | null = null
+      let cancelled = false
+
+      coordinationApi
+        .getMessages()
+        .then((messages) => {
+          if (!cancelled) useCoordinationStore.getState().addMessages(messages, false)
+        })
+        .catch((error) => console.warn('[Coordination] Failed to load messages:', error))
+
+      import('@tauri-apps/api/event')
+        .then(({ listen }) => listen<CoordinationMessage>('coordination-message', (event) => receive(event.payload)))
+        .then((fn) => {
+          if (cancelled) fn()
+          else unlisten = fn
+        })
+        .catch((error) => console.error('[Coordination] Failed to listen for messages:', error))
+
+      return () => {
+        cancelled = true
+        unlisten?.()
+      }
+    }
+
+    // Only remote browsers have a host to listen to
+    if (!isRemoteMode()) return
+
+    let ws: WebSocket | null = null
+    let reconnectTimeout: ReturnType<typeof setTimeout> | null = null
+    let closed = false
+
+    const connect = () => {
+      if (closed) return
+      const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:'
+      ws = new WebSocket(`${protocol}//${window.location.host}/api/messages/ws`)
+
+      ws.onmessage = (event) => {
+        try {
+          receive(JSON.parse(event.data) as CoordinationMessage)
+        } catch (error) {
+          console.warn('[Coordination] Ignoring invalid message:', error)
+        }
+      }
+
+      ws.onclose = () => {
+        ws = null
+        // Attempt to reconnect after 5 seconds
+        if (!closed) reconnectTimeout = setTimeout(connect, 5000)
+      }
+    }
+
+    // Load the history first so the replay on connect isn't taken for news
+    coordinationApi
+      .getMessages()
+      .then((messages) => useCoordinationStore.getState().addMessages(messages, false))
+      .catch((error) => console.warn('[Coordination] Failed to load messages:', error))
+      .finally(connect)
+
+    return () => {
+      closed = true
+      if (reconnectTimeout) clearTimeout(reconnectTimeout)
+      ws?.close()
+    }
+  }, [])
+}
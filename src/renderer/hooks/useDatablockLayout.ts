@@ -0,0 +1,78 @@
+/**
+ * Datablock Layout Hook
+ *
+ * Keeps the shared datablock layout in the global settings store in sync
+ * with the host, so every display renders identical datablocks. The desktop
+ * app listens for `datablock-layout-changed` events; remote browsers receive
+ * the same updates over the `/api/datablock/ws` WebSocket.
+ */
+
+import { useEffect } from 'react'
+import { useGlobalSettingsStore } from '../stores/globalSettingsStore'
+import { isTauri } from '../utils/tauriApi'
+import { isRemoteMode } from '../utils/remoteMode'
+import type { GlobalDatablockLayout } from '../types'
+
+function applyLayout(datablock: GlobalDatablockLayout) {
+  useGlobalSettingsStore.setState({ datablock })
+}
+
+/**
+ * Subscribe to datablock layout changes from the host.
+ * Call once at the app root.
+ */
+export function useDatablockLayout() {
+  useEffect(() => {
+    if (isTauri()) {
+      let unlisten: (() => void) | null = null
+      let cancelled = false
+
+      import('@tauri-apps/api/event')
+        .then(({ listen }) => listen<GlobalDatablockLayout>('datablock-layout-changed', (event) => applyLayout(event.payload)))
+        .then((fn) => {
+          if (cancelled) fn()
+          else unlisten = fn
+        })
+        .catch((error) => console.error('[Datablocks] Failed to listen for layout changes:', error))
+
+      return () => {
+        cancelled = true
+        unlisten?.()
+      }
+    }
+
+    // Only remote browsers have a host to listen to
+    if (!isRemoteMode()) return
+
+    let ws: WebSocket | null = null
+    let reconnectTimeout: ReturnType<typeof setTimeout> | null = null
+    let closed = false
+
+    const connect = () => {
+      const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:'
+      ws = new WebSocket(`${protocol}//${window.location.host}/api/datablock/ws`)
+
+      ws.onmessage = (event) => {
+        try {
+          applyLayout(JSON.parse(event.data) as GlobalDatablockLayout)
+        } catch (error) {
+          console.warn('[Datablocks] Ignoring invalid layout update:', error)
+        }
+      }
+
+      ws.onclose = () => {
+        ws = null
+        // Attempt to reconnect after 5 seconds
+        if (!closed) reconnectTimeout = setTimeout(connect, 5000)
+      }
+    }
+
+    connect()
+
+    return () => {
+      closed = true
+      if (reconnectTimeout) clearTimeout(reconnectTimeout)
+      ws?.close()
+    }
+  }, [])
+}
@@ -0,0 +1,48 @@
+/**
+ * Event Reminders Hook
+ *
+ * The host sends an `event-reminder` event shortly before a VATSIM or
+ * facility calendar event at the active airport starts (see
+ * event_schedule.rs). Unless traffic is already being recorded, this asks
+ * whether to record the event and, if so, starts a recording that is saved
+ * to the replay library when the event ends.
+ *
+ * Desktop app only: the host decides about recording.
+ */
+
+import { useEffect } from 'react'
+import { eventsApi, isTauri } from '../utils/tauriApi'
+import type { EventReminder } from '../types/eventSchedule'
+
+/**
+ * Offer to record upcoming events at the active airport.
+ * Call once at the app root.
+ */
+export function useEventReminders() {
+  useEffect(() => {
+    if (!isTauri()) return
+
+    const onReminder = (reminder: EventReminder) => {
+      if (reminder.recording || !window.confirm(reminder.message)) return
+      eventsApi.record(reminder.event.id)
+        .then((event) => console.log(`[Events] Recording "${event.name}"`))
+        .catch((error) => console.error('[Events] Failed to start recording:', error))
+    }
+
+    let unlisten: (() => void) | null = null
+    let cancelled = false
+
+    import('@tauri-apps/api/event')
+      .then(({ listen }) => listen<EventReminder>('event-reminder', (event) => onReminder(event.payload)))
+      .then((fn) => {
+        if (cancelled) fn()
+        else unlisten = fn
+      })
+      .catch((error) => console.error('[Events] Failed to listen for event reminders:', error))
+
+    return () => {
+      cancelled = true
+      unlisten?.()
+    }
+  }, [])
+}
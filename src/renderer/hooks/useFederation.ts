@@ -0,0 +1,50 @@
+/**
+ * Federation Hook
+ *
+ * On a display host that mirrors another instance (federation.rs), feeds the
+ * data host's aircraft into the vNAS store (`vnas-aircraft-batch` events) and
+ * reloads the global settings when the host copied the data host's
+ * (`federation-settings-mirrored`). Desktop only; remote browsers of a
+ * display host get both through its HTTP server like any other.
+ */
+
+import { useEffect } from 'react'
+import { useGlobalSettingsStore } from '../stores/globalSettingsStore'
+import { useVnasStore } from '../stores/vnasStore'
+import { isTauri } from '../utils/tauriApi'
+import type { VnasAircraft } from '../types/vnas'
+
+/**
+ * Mirror the data host's aircraft and settings while federation is enabled.
+ * Call once at the app root.
+ */
+export function useFederation() {
+  const enabled = useGlobalSettingsStore((state) => state.federation.enabled)
+
+  useEffect(() => {
+    if (!isTauri() || !enabled) return
+
+    const unlisteners: (() => void)[] = []
+    let cancelled = false
+
+    import('@tauri-apps/api/event')
+      .then(({ listen }) => Promise.all([
+        listen<VnasAircraft[]>('vnas-aircraft-batch', (event) => {
+          useVnasStore.getState().handleBatchUpdate(event.payload)
+        }),
+        listen('federation-settings-mirrored', () => {
+          void useGlobalSettingsStore.getState().refresh()
+        })
+      ]))
+      .then((fns) => {
+        if (cancelled) fns.forEach((fn) => fn())
+        else unlisteners.push(...fns)
+      })
+      .catch((error) => console.error('[Federation] Failed to listen for data host updates:', error))
+
+    return () => {
+      cancelled = true
+      unlisteners.forEach((fn) => fn())
+    }
+  }, [enabled])
+}
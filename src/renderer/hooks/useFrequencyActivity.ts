@@ -0,0 +1,91 @@
+/**
+ * Frequency Activity Hook
+ *
+ * Tracks which of the active airport's frequencies carry a transmission
+ * (Audio for VATSIM), for blinking a TX indicator per frequency. The host
+ * only polls AFV while someone is watching: the desktop app turns updates on
+ * with `watch_frequency_activity` and listens for `frequency-activity`
+ * events; remote browsers connect to the `/api/afv/ws` WebSocket.
+ *
+ * Unlike the alert hooks this is used by the components that show the
+ * indicators, so polling stops when none are on screen.
+ */
+
+import { useEffect, useState } from 'react'
+import { invokeCommand } from '@/utils/apiError'
+import { isTauri } from '../utils/tauriApi'
+import { isRemoteMode } from '../utils/remoteMode'
+import type { FrequencyActivitySnapshot } from '../types/vatsim'
+
+/**
+ * Subscribe to frequency activity from the host while mounted.
+ * Returns null until the first update arrives.
+ */
+export function useFrequencyActivity(): FrequencyActivitySnapshot | null {
+  const [activity, setActivity] = useState<FrequencyActivitySnapshot | null>(null)
+
+  useEffect(() => {
+    if (isTauri()) {
+      let unlisten: (() => void) | null = null
+      let cancelled = false
+
+      invokeCommand<FrequencyActivitySnapshot>('get_frequency_activity')
+        .then((snapshot) => {
+          if (!cancelled) setActivity(snapshot)
+        })
+        .catch((error) => console.error('[Afv] Failed to load frequency activity:', error))
+      invokeCommand('watch_frequency_activity', { enabled: true })
+        .catch((error) => console.error('[Afv] Failed to watch frequency activity:', error))
+
+      import('@tauri-apps/api/event')
+        .then(({ listen }) => listen<FrequencyActivitySnapshot>('frequency-activity', (event) => setActivity(event.payload)))
+        .then((fn) => {
+          if (cancelled) fn()
+          else unlisten = fn
+        })
+        .catch((error) => console.error('[Afv] Failed to listen for frequency activity:', error))
+
+      return () => {
+        cancelled = true
+        unlisten?.()
+        invokeCommand('watch_frequency_activity', { enabled: false }).catch(() => {})
+      }
+    }
+
+    // Only remote browsers have a host to listen to
+    if (!isRemoteMode()) return
+
+    let ws: WebSocket | null = null
+    let reconnectTimeout: ReturnType<typeof setTimeout> | null = null
+    let closed = false
+
+    const connect = () => {
+      const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:'
+      ws = new WebSocket(`${protocol}//${window.location.host}/api/afv/ws`)
+
+      ws.onmessage = (event) => {
+        try {
+          setActivity(JSON.parse(event.data) as FrequencyActivitySnapshot)
+        } catch (error) {
+          console.warn('[Afv] Ignoring invalid activity update:', error)
+        }
+      }
+
+      ws.onclose = () => {
+        ws = null
+        // Attempt to reconnect after 5 seconds
+        if (!closed) reconnectTimeout = setTimeout(connect, 5000)
+      }
+    }
+
+    connect()
+
+    return () => {
+      closed = true
+      if (reconnectTimeout) clearTimeout(reconnectTimeout)
+      ws?.close()
+    }
+  }, [])
+
+  return activity
+}
@@ -0,0 +1,53 @@
+/**
+ * Frequency Contacts Hook
+ *
+ * Position each aircraft is talking to, from the host's traffic picture
+ * (`onFrequency`, joined from the VATSIM transceivers feed), for the
+ * datablock `freq` field and frequency coloring. Only polled while the
+ * datablock layout uses it.
+ */
+
+import { useEffect, useState } from 'react'
+import { trafficApi } from '../utils/tauriApi'
+import type { FrequencyContact } from '../types/vatsim'
+
+/** How often contacts are refreshed (ms), matching the host's traffic poll */
+const REFRESH_INTERVAL_MS = 15_000
+
+/**
+ * Position by callsign (empty when disabled or unknown)
+ */
+export function useFrequencyContacts(enabled: boolean): Map<string, FrequencyContact> {
+  const [contacts, setContacts] = useState<Map<string, FrequencyContact>>(() => new Map())
+
+  useEffect(() => {
+    if (!enabled) {
+      setContacts(new Map())
+      return
+    }
+
+    let cancelled = false
+    const refresh = () => {
+      trafficApi
+        .getSnapshot()
+        .then((snapshot) => {
+          if (cancelled) return
+          const byCallsign = new Map<string, FrequencyContact>()
+          for (const aircraft of snapshot.aircraft) {
+            if (aircraft.onFrequency) byCallsign.set(aircraft.callsign, aircraft.onFrequency)
+          }
+          setContacts(byCallsign)
+        })
+        .catch((error) => console.warn('[Frequency] Failed to load traffic:', error))
+    }
+
+    refresh()
+    const interval = setInterval(refresh, REFRESH_INTERVAL_MS)
+    return () => {
+      cancelled = true
+      clearInterval(interval)
+    }
+  }, [enabled])
+
+  return contacts
+}
@@ -0,0 +1,58 @@
+/**
+ * Gate Assignments Hook
+ *
+ * Gate or stand each aircraft at the airport is parked at, from the host's
+ * gate occupancy (`trafficApi.getGates`), for the datablock `gate` field.
+ * Arrivals that haven't parked yet show the gate the host suggests ("→B7").
+ * Only polled while the datablock layout shows gates.
+ */
+
+import { useEffect, useState } from 'react'
+import { trafficApi } from '../utils/tauriApi'
+
+/** How often gate occupancy is refreshed (ms) */
+const REFRESH_INTERVAL_MS = 30_000
+
+/**
+ * Gate id by callsign at an airport, "→{gate}" for suggested gates
+ * (empty when disabled or unknown)
+ */
+export function useGateAssignments(icao: string | null, enabled: boolean): Map<string, string> {
+  const [assignments, setAssignments] = useState<Map<string, string>>(() => new Map())
+
+  useEffect(() => {
+    if (!icao || !enabled) {
+      setAssignments(new Map())
+      return
+    }
+
+    let cancelled = false
+    const refresh = () => {
+      Promise.all([
+        trafficApi.getGates(icao),
+        trafficApi.getGateSuggestions(icao).catch(() => [])
+      ])
+        .then(([gates, suggestions]) => {
+          if (cancelled) return
+          const byCallsign = new Map<string, string>()
+          for (const suggestion of suggestions) {
+            byCallsign.set(suggestion.callsign, `→${suggestion.gate}`)
+          }
+          for (const gate of gates) {
+            if (gate.occupiedBy) byCallsign.set(gate.occupiedBy, gate.id)
+          }
+          setAssignments(byCallsign)
+        })
+        .catch((error) => console.warn('[Gates] Failed to load gate occupancy:', error))
+    }
+
+    refresh()
+    const interval = setInterval(refresh, REFRESH_INTERVAL_MS)
+    return () => {
+      cancelled = true
+      clearInterval(interval)
+    }
+  }, [icao, enabled])
+
+  return assignments
+}
@@ -0,0 +1,105 @@
+/**
+ * Host Follow Hook
+ *
+ * Follows the aircraft the host says to follow (set with `set_follow` or
+ * `PUT /api/follow`), with the host's camera offsets, in the active viewport.
+ * The desktop app listens for `follow-changed` events; remote browsers receive
+ * the same updates over the `/api/follow/ws` WebSocket, which sends the
+ * current follow on connect so a reloaded display picks it back up.
+ *
+ * A target is applied when it changes, so local camera adjustments while
+ * following are kept until the host sets a new one.
+ */
+
+import { useEffect } from 'react'
+import { useViewportStore } from '../stores/viewportStore'
+import { isTauri } from '../utils/tauriApi'
+import { isRemoteMode } from '../utils/remoteMode'
+import type { HostFollowStatus, HostFollowTarget } from '../types/camera'
+
+let applied: HostFollowTarget | null = null
+
+function sameTarget(a: HostFollowTarget | null, b: HostFollowTarget | null): boolean {
+  return JSON.stringify(a) === JSON.stringify(b)
+}
+
+function applyFollow(status: HostFollowStatus) {
+  const { target } = status
+  if (sameTarget(target, applied)) return
+
+  const store = useViewportStore.getState()
+  if (target) {
+    if (target.mode === 'orbit') {
+      store.followAircraftInOrbit(target.callsign)
+    } else {
+      store.followAircraft(target.callsign)
+    }
+    store.setFollowZoom(target.zoom)
+    store.setOrbitDistance(target.orbitDistance)
+    store.setOrbitHeading(target.orbitHeading)
+    store.setOrbitPitch(target.orbitPitch)
+  } else if (applied && store.getActiveCameraState().followingCallsign === applied.callsign) {
+    store.stopFollowing()
+  }
+  applied = target
+}
+
+/**
+ * Subscribe to host-side following.
+ * Call once at the app root.
+ */
+export function useHostFollow() {
+  useEffect(() => {
+    if (isTauri()) {
+      let unlisten: (() => void) | null = null
+      let cancelled = false
+
+      import('@tauri-apps/api/event')
+        .then(({ listen }) => listen<HostFollowStatus>('follow-changed', (event) => applyFollow(event.payload)))
+        .then((fn) => {
+          if (cancelled) fn()
+          else unlisten = fn
+        })
+        .catch((error) => console.error('[Follow] Failed to listen for follow changes:', error))
+
+      return () => {
+        cancelled = true
+        unlisten?.()
+      }
+    }
+
+    // Only remote browsers have a host to listen to
+    if (!isRemoteMode()) return
+
+    let ws: WebSocket | null = null
+    let reconnectTimeout: ReturnType<typeof setTimeout> | null = null
+    let closed = false
+
+    const connect = () => {
+      const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:'
+      ws = new WebSocket(`${protocol}//${window.location.host}/api/follow/ws`)
+
+      ws.onmessage = (event) => {
+        try {
+          applyFollow(JSON.parse(event.data) as HostFollowStatus)
+        } catch (error) {
+          console.warn('[Follow] Ignoring invalid follow update:', error)
+        }
+      }
+
+      ws.onclose = () => {
+        ws = null
+        // Attempt to reconnect after 5 seconds
+        if (!closed) reconnectTimeout = setTimeout(connect, 5000)
+      }
+    }
+
+    connect()
+
+    return () => {
+      closed = true
+      if (reconnectTimeout) clearTimeout(reconnectTimeout)
+      ws?.close()
+    }
+  }, [])
+}
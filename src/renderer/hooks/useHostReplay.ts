@@ -0,0 +1,193 @@
+/**
+ * Host Replay Hook
+ *
+ * Plays the host's replay on this display in lockstep with every other
+ * display (see replay.rs). When the host loads a replay, the whole recording
+ * is fetched once and played locally, but the playback clock is slaved to the
+ * host: each `replay-status` carries the recorded position at a host time, and
+ * the local position is extrapolated from it every frame. Play, pause, seek
+ * and speed changes made here are sent to the host (see replayStore), so a
+ * wall display and the tablets around it stay on the same recorded second.
+ *
+ * Remote browsers estimate the host clock offset from a few status requests
+ * (keeping the fastest round trip) before following the `/api/replay/ws`
+ * status messages; the desktop app shares the host's clock.
+ */
+
+import { useEffect } from 'react'
+import { useReplayStore } from '../stores/replayStore'
+import { replayApi, isTauri } from '../utils/tauriApi'
+import { isRemoteMode } from '../utils/remoteMode'
+import type { ReplayServerEvent, ReplayServerStatus } from '../types/replay'
+
+/** Status requests used to estimate the host clock offset */
+const CLOCK_SAMPLES = 5
+
+/** Recording the store follows, to notice when the host loads another one */
+let followedKey: string | null = null
+
+/** Latest status while the recording is being fetched */
+let pendingStatus: ReplayServerStatus | null = null
+
+function recordingKey(status: ReplayServerStatus): string {
+  return `${status.startTime}-${status.endTime}-${status.snapshotCount}-${status.instant}`
+}
+
+function applyStatus(status: ReplayServerStatus) {
+  const store = useReplayStore.getState()
+
+  if (!status.loaded) {
+    followedKey = null
+    pendingStatus = null
+    store.leaveHost()
+    return
+  }
+
+  const key = recordingKey(status)
+  if (store.hostStatus && key === followedKey) {
+    store.setHostStatus(status)
+    return
+  }
+
+  // Already fetching this recording: apply the newest status once it arrives
+  const fetching = pendingStatus !== null && recordingKey(pendingStatus) === key
+  pendingStatus = status
+  if (fetching) return
+
+  replayApi
+    .getRecording()
+    .then((recording) => {
+      const latest = pendingStatus
+      pendingStatus = null
+      if (!recording || !latest || recordingKey(latest) !== key) return
+      followedKey = key
+      useReplayStore.getState().followHost(recording.snapshots, latest)
+    })
+    .catch((error) => {
+      pendingStatus = null
+      console.error('[Replay] Failed to load host replay:', error)
+    })
+}
+
+/**
+ * Estimate host clock minus local clock from the fastest of a few status
+ * requests, assuming the host answered halfway through the round trip.
+ * Returns the last status received.
+ */
+async function measureClockOffset(): Promise<ReplayServerStatus> {
+  let best: { rtt: number; offset: number } | null = null
+  let status: ReplayServerStatus | null = null
+
+  for (let i = 0; i < CLOCK_SAMPLES; i++) {
+    const sent = Date.now()
+    status = await replayApi.getStatus()
+    const received = Date.now()
+    const rtt = received - sent
+    if (!best || rtt < best.rtt) {
+      best = { rtt, offset: status.realTime - (sent + rtt / 2) }
+    }
+  }
+
+  if (best) useReplayStore.getState().setHostClockOffset(best.offset)
+  return status as ReplayServerStatus
+}
+
+/**
+ * Follow the host's replay playback.
+ * Call once at the app root.
+ */
+export function useHostReplay() {
+  const hostPlaying = useReplayStore((state) => state.hostStatus?.playing ?? false)
+
+  // Advance with the host clock while it plays
+  useEffect(() => {
+    if (!hostPlaying) return
+
+    let frame: number | null = null
+    const tick = () => {
+      useReplayStore.getState().syncToHost()
+      frame = requestAnimationFrame(tick)
+    }
+    frame = requestAnimationFrame(tick)
+
+    return () => {
+      if (frame !== null) cancelAnimationFrame(frame)
+    }
+  }, [hostPlaying])
+
+  useEffect(() => {
+    if (isTauri()) {
+      let unlisten: (() => void) | null = null
+      let cancelled = false
+
+      replayApi
+        .getStatus()
+        .then((status) => {
+          if (!cancelled) applyStatus(status)
+        })
+        .catch((error) => console.warn('[Replay] Failed to load host replay status:', error))
+
+      import('@tauri-apps/api/event')
+        .then(({ listen }) => listen<ReplayServerStatus>('replay-status', (event) => applyStatus(event.payload)))
+        .then((fn) => {
+          if (cancelled) fn()
+          else unlisten = fn
+        })
+        .catch((error) => console.error('[Replay] Failed to listen for replay status:', error))
+
+      return () => {
+        cancelled = true
+        unlisten?.()
+      }
+    }
+
+    // Only remote browsers have a host to listen to
+    if (!isRemoteMode()) return
+
+    let ws: WebSocket | null = null
+    let reconnectTimeout: ReturnType<typeof setTimeout> | null = null
+    let closed = false
+
+    const connect = () => {
+      if (closed) return
+      const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:'
+      ws = new WebSocket(`${protocol}//${window.location.host}/api/replay/ws`)
+
+      ws.onmessage = (event) => {
+        try {
+          const message = JSON.parse(event.data) as ReplayServerEvent
+          // Snapshots are played from the fetched recording
+          if (message.type === 'status') {
+            const { type: _type, ...status } = message
+            applyStatus(status)
+          }
+        } catch (error) {
+          console.warn('[Replay] Ignoring invalid replay message:', error)
+        }
+      }
+
+      ws.onclose = () => {
+        ws = null
+        // Re-measure the clock and reconnect after 5 seconds
+        if (!closed) reconnectTimeout = setTimeout(start, 5000)
+      }
+    }
+
+    const start = () => {
+      measureClockOffset()
+        .then((status) => {
+          if (!closed) applyStatus(status)
+        })
+        .catch((error) => console.warn('[Replay] Failed to measure host clock:', error))
+        .finally(connect)
+    }
+
+    start()
+
+    return () => {
+      closed = true
+      if (reconnectTimeout) clearTimeout(reconnectTimeout)
+      ws?.close()
+    }
+  }, [])
+}
@@ -0,0 +1,82 @@
+/**
+ * Label Layout Hook
+ *
+ * Keeps the label layout store in sync with the datablock placements the
+ * host works out after every traffic poll (see label_layout.rs). The desktop
+ * app listens for `label-layout` events; remote browsers receive the same
+ * updates over the `/api/labels/ws` WebSocket.
+ */
+
+import { useEffect } from 'react'
+import { useLabelLayoutStore } from '../stores/labelLayoutStore'
+import { isTauri, trafficApi } from '../utils/tauriApi'
+import { isRemoteMode } from '../utils/remoteMode'
+import type { LabelLayout } from '../types/vatsim'
+
+/**
+ * Subscribe to label placements from the host.
+ * Call once at the app root.
+ */
+export function useLabelLayout() {
+  useEffect(() => {
+    const { setLayout } = useLabelLayoutStore.getState()
+
+    if (isTauri()) {
+      let unlisten: (() => void) | null = null
+      let cancelled = false
+
+      trafficApi.getLabelLayout()
+        .then((layout) => {
+          if (!cancelled) setLayout(layout)
+        })
+        .catch((error) => console.error('[Labels] Failed to load label layout:', error))
+
+      import('@tauri-apps/api/event')
+        .then(({ listen }) => listen<LabelLayout>('label-layout', (event) => setLayout(event.payload)))
+        .then((fn) => {
+          if (cancelled) fn()
+          else unlisten = fn
+        })
+        .catch((error) => console.error('[Labels] Failed to listen for label layout:', error))
+
+      return () => {
+        cancelled = true
+        unlisten?.()
+      }
+    }
+
+    // Only remote browsers have a host to listen to
+    if (!isRemoteMode()) return
+
+    let ws: WebSocket | null = null
+    let reconnectTimeout: ReturnType<typeof setTimeout> | null = null
+    let closed = false
+
+    const connect = () => {
+      const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:'
+      ws = new WebSocket(`${protocol}//${window.location.host}/api/labels/ws`)
+
+      ws.onmessage = (event) => {
+        try {
+          setLayout(JSON.parse(event.data) as LabelLayout)
+        } catch (error) {
+          console.warn('[Labels] Ignoring invalid label layout:', error)
+        }
+      }
+
+      ws.onclose = () => {
+        ws = null
+        // Attempt to reconnect after 5 seconds
+        if (!closed) reconnectTimeout = setTimeout(connect, 5000)
+      }
+    }
+
+    connect()
+
+    return () => {
+      closed = true
+      if (reconnectTimeout) clearTimeout(reconnectTimeout)
+      ws?.close()
+    }
+  }, [])
+}
@@ -0,0 +1,114 @@
+/**
+ * Landing Predictions Hook
+ *
+ * Draws the touchdown points the host predicts for arrivals on short final
+ * (`trafficApi.getLandingPredictions`), like the projected landing marker of
+ * an ASDE display: a marker at the touchdown point, the rollout along the
+ * runway, and the likeliest turn-off with its chance.
+ */
+
+import { useEffect } from 'react'
+import * as Cesium from 'cesium'
+import { trafficApi } from '../utils/tauriApi'
+import type { LandingPrediction, TurnOff } from '../types/airport'
+
+/** How often predictions are refreshed (ms) */
+const REFRESH_INTERVAL_MS = 5_000
+
+const TOUCHDOWN_COLOR = Cesium.Color.fromCssColorString('#ffeb3b')
+const ROLLOUT_COLOR = Cesium.Color.fromCssColorString('#ffeb3b').withAlpha(0.6)
+const TURN_OFF_COLOR = Cesium.Color.fromCssColorString('#81c784')
+
+/**
+ * Draw predicted touchdown points, rollouts and turn-offs at an airport
+ */
+export function useLandingPredictions(viewer: Cesium.Viewer | null, icao: string | null, enabled: boolean): void {
+  useEffect(() => {
+    if (!viewer || !icao || !enabled) return
+
+    const dataSource = new Cesium.CustomDataSource('landing-predictions')
+    viewer.dataSources.add(dataSource)
+    let cancelled = false
+
+    const draw = (predictions: LandingPrediction[]) => {
+      dataSource.entities.removeAll()
+      for (const prediction of predictions) {
+        dataSource.entities.add({
+          id: `touchdown_${prediction.callsign}`,
+          name: `${prediction.callsign} touchdown ${prediction.runway}`,
+          position: Cesium.Cartesian3.fromDegrees(...prediction.touchdown),
+          point: {
+            pixelSize: 10,
+            color: TOUCHDOWN_COLOR,
+            outlineColor: Cesium.Color.BLACK,
+            outlineWidth: 1,
+            heightReference: Cesium.HeightReference.CLAMP_TO_GROUND,
+            disableDepthTestDistance: Number.POSITIVE_INFINITY
+          },
+          label: {
+            text: `${prediction.callsign} ${prediction.secondsToTouchdown}s`,
+            font: '12px monospace',
+            fillColor: TOUCHDOWN_COLOR,
+            style: Cesium.LabelStyle.FILL_AND_OUTLINE,
+            outlineWidth: 2,
+            pixelOffset: new Cesium.Cartesian2(0, -16),
+            heightReference: Cesium.HeightReference.CLAMP_TO_GROUND,
+            disableDepthTestDistance: Number.POSITIVE_INFINITY
+          }
+        })
+        dataSource.entities.add({
+          id: `rollout_${prediction.callsign}`,
+          polyline: {
+            positions: Cesium.Cartesian3.fromDegreesArray([...prediction.touchdown, ...prediction.rolloutEnd]),
+            width: 4,
+            material: new Cesium.PolylineDashMaterialProperty({ color: ROLLOUT_COLOR, dashLength: 12 }),
+            clampToGround: true
+          }
+        })
+
+        const likeliest = prediction.turnOffs.reduce<TurnOff | null>(
+          (best, turnOff) => (!best || turnOff.likelihood > best.likelihood ? turnOff : best),
+          null
+        )
+        if (likeliest && likeliest.likelihood > 0) {
+          dataSource.entities.add({
+            id: `turn_off_${prediction.callsign}`,
+            position: Cesium.Cartesian3.fromDegrees(...likeliest.position),
+            point: {
+              pixelSize: 8,
+              color: TURN_OFF_COLOR,
+              heightReference: Cesium.HeightReference.CLAMP_TO_GROUND,
+              disableDepthTestDistance: Number.POSITIVE_INFINITY
+            },
+            label: {
+              text: `${likeliest.taxiway ?? 'exit'} ${Math.round(likeliest.likelihood * 100)}%`,
+              font: '11px monospace',
+              fillColor: TURN_OFF_COLOR,
+              style: Cesium.LabelStyle.FILL_AND_OUTLINE,
+              outlineWidth: 2,
+              pixelOffset: new Cesium.Cartesian2(0, -14),
+              heightReference: Cesium.HeightReference.CLAMP_TO_GROUND,
+              disableDepthTestDistance: Number.POSITIVE_INFINITY
+            }
+          })
+        }
+      }
+    }
+
+    const refresh = () => {
+      trafficApi.getLandingPredictions(icao)
+        .then((predictions) => {
+          if (!cancelled && !viewer.isDestroyed()) draw(predictions)
+        })
+        .catch((error) => console.warn('[LandingPredictions] Failed to load landing predictions:', error))
+    }
+
+    refresh()
+    const interval = setInterval(refresh, REFRESH_INTERVAL_MS)
+    return () => {
+      cancelled = true
+      clearInterval(interval)
+      if (!viewer.isDestroyed()) viewer.dataSources.remove(dataSource, true)
+    }
+  }, [viewer, icao, enabled])
+}
@@ -0,0 +1,45 @@
+import { useEffect } from 'react'
+import { useReplayStore } from '../stores/replayStore'
+import { useWeatherStore } from '../stores/weatherStore'
+import { weatherApi } from '../utils/tauriApi'
+
+/** Replay time is bucketed so scrubbing doesn't request weather for every snapshot */
+const REPLAY_WEATHER_BUCKET_MS = 5 * 60 * 1000
+
+/**
+ * Hook that shows the weather that existed at the replayed time.
+ *
+ * While a replay (recorded or imported) is active, fetches the scene
+ * parameters derived from the archived METAR in effect at the current
+ * snapshot's timestamp and applies them to the weather store. Live weather
+ * refresh is stopped by the caller while replaying.
+ *
+ * @param icao - Airport whose METARs are used (null to do nothing)
+ * @param enabled - Whether weather effects are enabled
+ */
+export function useReplayWeather(icao: string | null, enabled: boolean): void {
+  const playbackMode = useReplayStore((state) => state.playbackMode)
+  const snapshotTime = useReplayStore((state) => state.getCurrentSnapshot()?.timestamp ?? null)
+  const applyScene = useWeatherStore((state) => state.applyScene)
+
+  const bucket = snapshotTime !== null
+    ? Math.floor(snapshotTime / REPLAY_WEATHER_BUCKET_MS) * REPLAY_WEATHER_BUCKET_MS
+    : null
+
+  useEffect(() => {
+    if (playbackMode === 'live' || !enabled || !icao || bucket === null) return
+
+    let cancelled = false
+    weatherApi.getScene(icao, bucket)
+      .then((scene) => {
+        if (!cancelled && scene) applyScene(scene)
+      })
+      .catch((error) => {
+        console.warn(`[Weather] Failed to load historical weather for ${icao}:`, error)
+      })
+
+    return () => {
+      cancelled = true
+    }
+  }, [playbackMode, enabled, icao, bucket, applyScene])
+}
@@ -0,0 +1,126 @@
+/**
+ * Selection Sync Hook
+ *
+ * Shares the selected aircraft between displays that opted in with the
+ * "Share Aircraft Selection" setting. The aircraft this display clicks in the
+ * aircraft list or starts following is sent to the host (`set_selection` or
+ * `PUT /api/selection`), and the host's selection, from any display, is kept
+ * in the selection store for labels and the aircraft list to highlight.
+ *
+ * The desktop app listens for `selection-changed` events; remote browsers
+ * receive the same updates over the `/api/selection/ws` WebSocket, which sends
+ * the current selection on connect. Receiving a selection never moves the
+ * camera.
+ */
+
+import { useEffect } from 'react'
+import { useSettingsStore } from '../stores/settingsStore'
+import { useSelectionStore } from '../stores/selectionStore'
+import { useViewportStore } from '../stores/viewportStore'
+import { isTauri, selectionApi } from '../utils/tauriApi'
+import { isRemoteMode } from '../utils/remoteMode'
+import type { SharedSelection } from '../types/camera'
+
+/**
+ * Share an aircraft selection with other displays (no-op while sync is off)
+ */
+export function shareSelection(callsign: string) {
+  const { syncSelection, selectionSourceName } = useSettingsStore.getState().ui
+  if (!syncSelection) return
+  if (useSelectionStore.getState().selection?.callsign === callsign) return
+  selectionApi
+    .set(callsign, selectionSourceName || undefined)
+    .catch((error) => console.warn('[Selection] Failed to share selection:', error))
+}
+
+function applySelection(selection: SharedSelection) {
+  useSelectionStore.getState().setSelection(selection)
+}
+
+/**
+ * Sync the selected aircraft with other displays while enabled.
+ * Call once at the app root.
+ */
+export function useSelectionSync() {
+  const syncSelection = useSettingsStore((state) => state.ui.syncSelection)
+
+  // Share aircraft this display starts following
+  useEffect(() => {
+    if (!syncSelection) return
+
+    let following = useViewportStore.getState().getActiveCameraState().followingCallsign
+    return useViewportStore.subscribe((state) => {
+      const callsign = state.getActiveCameraState().followingCallsign
+      if (callsign === following) return
+      following = callsign
+      if (callsign) shareSelection(callsign)
+    })
+  }, [syncSelection])
+
+  // Receive selections from the host
+  useEffect(() => {
+    if (!syncSelection) {
+      useSelectionStore.getState().setSelection(null)
+      return
+    }
+
+    if (isTauri()) {
+      let unlisten: (() => void) | null = null
+      let cancelled = false
+
+      selectionApi
+        .get()
+        .then((selection) => {
+          if (!cancelled) applySelection(selection)
+        })
+        .catch((error) => console.warn('[Selection] Failed to load selection:', error))
+
+      import('@tauri-apps/api/event')
+        .then(({ listen }) => listen<SharedSelection>('selection-changed', (event) => applySelection(event.payload)))
+        .then((fn) => {
+          if (cancelled) fn()
+          else unlisten = fn
+        })
+        .catch((error) => console.error('[Selection] Failed to listen for selection changes:', error))
+
+      return () => {
+        cancelled = true
+        unlisten?.()
+      }
+    }
+
+    // Only remote browsers have a host to listen to
+    if (!isRemoteMode()) return
+
+    let ws: WebSocket | null = null
+    let reconnectTimeout: ReturnType<typeof setTimeout> | null = null
+    let closed = false
+
+    const connect = () => {
+      const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:'
+      ws = new WebSocket(`${protocol}//${window.location.host}/api/selection/ws`)
+
+      ws.onmessage = (event) => {
+        try {
+          applySelection(JSON.parse(event.data) as SharedSelection)
+        } catch (error) {
+          console.warn('[Selection] Ignoring invalid selection update:', error)
+        }
+      }
+
+      ws.onclose = () => {
+        ws = null
+        // Attempt to reconnect after 5 seconds
+        if (!closed) reconnectTimeout = setTimeout(connect, 5000)
+      }
+    }
+
+    connect()
+
+    return () => {
+      closed = true
+      if (reconnectTimeout) clearTimeout(reconnectTimeout)
+      ws?.close()
+    }
+  }, [syncSelection])
+}
@@ -0,0 +1,113 @@
+/**
+ * Shared Drawings Hook
+ *
+ * Keeps the drawing store in sync with the host's drawing board (lines,
+ * labels and polygons drawn on any display, see drawings.rs). The desktop app
+ * loads the board and listens for `drawings-changed` events; remote browsers
+ * receive the same updates over the `/api/drawings/ws` WebSocket, which sends
+ * the current board on connect.
+ */
+
+import { useEffect } from 'react'
+import { useDrawingStore } from '../stores/drawingStore'
+import { toDrawingPoint, useMeasureStore } from '../stores/measureStore'
+import { useSettingsStore } from '../stores/settingsStore'
+import { drawingsApi, isTauri } from '../utils/tauriApi'
+import { isRemoteMode } from '../utils/remoteMode'
+import type { DrawingBoard, DrawingRequest } from '../types/drawings'
+
+/**
+ * Add a drawing to the shared board, signed with this display's name
+ */
+export function shareDrawing(request: DrawingRequest) {
+  const author = useSettingsStore.getState().ui.selectionSourceName || null
+  drawingsApi
+    .add({ author, ...request })
+    .catch((error) => console.warn('[Drawings] Failed to add drawing:', error))
+}
+
+/**
+ * Share the polygon being drawn with the measuring tool (needs three corners)
+ */
+export function finishPolygon() {
+  const { polygonPoints, labelText, drawColor, clearPolygon } = useMeasureStore.getState()
+  if (polygonPoints.length < 3) return
+  shareDrawing({
+    kind: 'polygon',
+    points: polygonPoints.map(toDrawingPoint),
+    text: labelText.trim() || null,
+    color: drawColor
+  })
+  clearPolygon()
+}
+
+function applyBoard(board: DrawingBoard) {
+  useDrawingStore.getState().setBoard(board)
+}
+
+/**
+ * Subscribe to the shared drawing board.
+ * Call once at the app root.
+ */
+export function useSharedDrawings() {
+  useEffect(() => {
+    if (isTauri()) {
+      let unlisten: (() => void) | null = null
+      let cancelled = false
+
+      drawingsApi
+        .get()
+        .then((board) => {
+          if (!cancelled) applyBoard(board)
+        })
+        .catch((error) => console.warn('[Drawings] Failed to load drawings:', error))
+
+      import('@tauri-apps/api/event')
+        .then(({ listen }) => listen<DrawingBoard>('drawings-changed', (event) => applyBoard(event.payload)))
+        .then((fn) => {
+          if (cancelled) fn()
+          else unlisten = fn
+        })
+        .catch((error) => console.error('[Drawings] Failed to listen for drawing changes:', error))
+
+      return () => {
+        cancelled = true
+        unlisten?.()
+      }
+    }
+
+    // Only remote browsers have a host to listen to
+    if (!isRemoteMode()) return
+
+    let ws: WebSocket | null = null
+    let reconnectTimeout: ReturnType<typeof setTimeout> | null = null
+    let closed = false
+
+    const connect = () => {
+      const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:'
+      ws = new WebSocket(`${protocol}//${window.location.host}/api/drawings/ws`)
+
+      ws.onmessage = (event) => {
+        try {
+          applyBoard(JSON.parse(event.data) as DrawingBoard)
+        } catch (error) {
+          console.warn('[Drawings] Ignoring invalid drawing update:', error)
+        }
+      }
+
+      ws.onclose = () => {
+        ws = null
+        // Attempt to reconnect after 5 seconds
+        if (!closed) reconnectTimeout = setTimeout(connect, 5000)
+      }
+    }
+
+    connect()
+
+    return () => {
+      closed = true
+      if (reconnectTimeout) clearTimeout(reconnectTimeout)
+      ws?.close()
+    }
+  }, [])
+}
@@ -0,0 +1,88 @@
+/**
+ * Simulated Clock Hook
+ *
+ * Keeps the shared simulated clock in the global settings store in sync with
+ * the host. The desktop app listens for `sim-clock-changed` events; remote
+ * browsers receive the same updates over the `/api/clock/ws` WebSocket.
+ */
+
+import { useEffect } from 'react'
+import { useGlobalSettingsStore } from '../stores/globalSettingsStore'
+import { isTauri } from '../utils/tauriApi'
+import { isRemoteMode } from '../utils/remoteMode'
+import type { GlobalClockSettings } from '../types'
+
+/** Clock update sent by the host */
+interface SimClockUpdate extends GlobalClockSettings {
+  simulatedTime: number
+  realTime: number
+}
+
+function applyClock(update: SimClockUpdate) {
+  const clock: GlobalClockSettings = {
+    mode: update.mode,
+    offsetMs: update.offsetMs,
+    fixedTime: update.fixedTime
+  }
+  useGlobalSettingsStore.setState({ clock })
+}
+
+/**
+ * Subscribe to simulated clock changes from the host.
+ * Call once at the app root.
+ */
+export function useSimClock() {
+  useEffect(() => {
+    if (isTauri()) {
+      let unlisten: (() => void) | null = null
+      let cancelled = false
+
+      import('@tauri-apps/api/event')
+        .then(({ listen }) => listen<SimClockUpdate>('sim-clock-changed', (event) => applyClock(event.payload)))
+        .then((fn) => {
+          if (cancelled) fn()
+          else unlisten = fn
+        })
+        .catch((error) => console.error('[SimClock] Failed to listen for clock changes:', error))
+
+      return () => {
+        cancelled = true
+        unlisten?.()
+      }
+    }
+
+    // Only remote browsers have a host to listen to
+    if (!isRemoteMode()) return
+
+    let ws: WebSocket | null = null
+    let reconnectTimeout: ReturnType<typeof setTimeout> | null = null
+    let closed = false
+
+    const connect = () => {
+      const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:'
+      ws = new WebSocket(`${protocol}//${window.location.host}/api/clock/ws`)
+
+      ws.onmessage = (event) => {
+        try {
+          applyClock(JSON.parse(event.data) as SimClockUpdate)
+        } catch (error) {
+          console.warn('[SimClock] Ignoring invalid clock update:', error)
+        }
+      }
+
+      ws.onclose = () => {
+        ws = null
+        // Attempt to reconnect after 5 seconds
+        if (!closed) reconnectTimeout = setTimeout(connect, 5000)
+      }
+    }
+
+    connect()
+
+    return () => {
+      closed = true
+      if (reconnectTimeout) clearTimeout(reconnectTimeout)
+      ws?.close()
+    }
+  }, [])
+}
@@ -0,0 +1,75 @@
+/**
+ * Squawk Alert Hook
+ *
+ * Keeps the squawk store in sync with the host's emergency and squawk
+ * mismatch alerts. The desktop app listens for `squawk-alerts` events; remote
+ * browsers receive the same updates over the `/api/squawks/ws` WebSocket.
+ */
+
+import { useEffect } from 'react'
+import { useSquawkStore } from '../stores/squawkStore'
+import { isTauri } from '../utils/tauriApi'
+import { isRemoteMode } from '../utils/remoteMode'
+import type { SquawkAlerts } from '../types/vatsim'
+
+/**
+ * Subscribe to squawk alerts from the host.
+ * Call once at the app root.
+ */
+export function useSquawkAlerts() {
+  useEffect(() => {
+    const { setAlerts } = useSquawkStore.getState()
+
+    if (isTauri()) {
+      let unlisten: (() => void) | null = null
+      let cancelled = false
+
+      import('@tauri-apps/api/event')
+        .then(({ listen }) => listen<SquawkAlerts>('squawk-alerts', (event) => setAlerts(event.payload)))
+        .then((fn) => {
+          if (cancelled) fn()
+          else unlisten = fn
+        })
+        .catch((error) => console.error('[Squawk] Failed to listen for squawk alerts:', error))
+
+      return () => {
+        cancelled = true
+        unlisten?.()
+      }
+    }
+
+    // Only remote browsers have a host to listen to
+    if (!isRemoteMode()) return
+
+    let ws: WebSocket | null = null
+    let reconnectTimeout: ReturnType<typeof setTimeout> | null = null
+    let closed = false
+
+    const connect = () => {
+      const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:'
+      ws = new WebSocket(`${protocol}//${window.location.host}/api/squawks/ws`)
+
+      ws.onmessage = (event) => {
+        try {
+          setAlerts(JSON.parse(event.data) as SquawkAlerts)
+        } catch (error) {
+          console.warn('[Squawk] Ignoring invalid alert update:', error)
+        }
+      }
+
+      ws.onclose = () => {
+        ws = null
+        // Attempt to reconnect after 5 seconds
+        if (!closed) reconnectTimeout = setTimeout(connect, 5000)
+      }
+    }
+
+    connect()
+
+    return () => {
+      closed = true
+      if (reconnectTimeout) clearTimeout(reconnectTimeout)
+      ws?.close()
+    }
+  }, [])
+}
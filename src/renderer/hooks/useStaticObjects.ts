@@ -0,0 +1,111 @@
+/**
+ * Static Objects Hook
+ *
+ * Renders the static GLB models placed at the current airport
+ * (`mods/placements/{ICAO}.json`): jet bridges, hangars, ground equipment and
+ * the like. Placements reload when the host emits `static-objects-changed`;
+ * remote browsers refresh them periodically.
+ */
+
+import { useEffect, useState } from 'react'
+import * as Cesium from 'cesium'
+import { isTauri, placementsApi } from '../utils/tauriApi'
+import type { StaticObject } from '../types'
+
+/** How often remote browsers refresh placements (ms) */
+const REMOTE_REFRESH_INTERVAL_MS = 60_000
+
+interface PlacementsChanged {
+  icao: string
+  objects: StaticObject[]
+}
+
+/**
+ * Load the objects placed at an airport into the Cesium scene
+ */
+export function useStaticObjects(viewer: Cesium.Viewer | null, icao: string | null): void {
+  const [objects, setObjects] = useState<StaticObject[]>([])
+
+  // Track the airport's placements
+  useEffect(() => {
+    setObjects([])
+    if (!icao) return
+
+    let cancelled = false
+    const refresh = () => {
+      placementsApi.getObjects(icao)
+        .then((loaded) => {
+          if (!cancelled) setObjects(loaded)
+        })
+        .catch((error) => console.warn('[Placements] Failed to load static objects:', error))
+    }
+    refresh()
+
+    if (isTauri()) {
+      let unlisten: (() => void) | null = null
+      import('@tauri-apps/api/event')
+        .then(({ listen }) => listen<PlacementsChanged>('static-objects-changed', (event) => {
+          if (event.payload.icao === icao) setObjects(event.payload.objects)
+        }))
+        .then((fn) => {
+          if (cancelled) fn()
+          else unlisten = fn
+        })
+        .catch((error) => console.error('[Placements] Failed to listen for changes:', error))
+
+      return () => {
+        cancelled = true
+        unlisten?.()
+      }
+    }
+
+    const interval = setInterval(refresh, REMOTE_REFRESH_INTERVAL_MS)
+    return () => {
+      cancelled = true
+      clearInterval(interval)
+    }
+  }, [icao])
+
+  // Add the placed models to the scene, replacing them whenever placements change
+  useEffect(() => {
+    if (!viewer || objects.length === 0) return
+
+    let cancelled = false
+    const models: Cesium.Model[] = []
+
+    for (const object of objects) {
+      placementsApi.getModelUrl(object.model)
+        .then((url) => {
+          const position = Cesium.Cartesian3.fromDegrees(object.longitude, object.latitude, object.heightM)
+          // Same convention as aircraft models: compass heading, models facing +X
+          const hpr = new Cesium.HeadingPitchRoll(Cesium.Math.toRadians(object.heading - 90), 0, 0)
+          const modelMatrix = Cesium.Transforms.headingPitchRollToFixedFrame(position, hpr)
+          return Cesium.Model.fromGltfAsync({
+            url,
+            modelMatrix,
+            scale: object.scale,
+            scene: viewer.scene,
+            heightReference: Cesium.HeightReference.RELATIVE_TO_GROUND,
+            shadows: Cesium.ShadowMode.ENABLED
+          })
+        })
+        .then((model) => {
+          if (cancelled || viewer.isDestroyed()) {
+            model.destroy()
+            return
+          }
+          viewer.scene.primitives.add(model)
+          models.push(model)
+        })
+        .catch((error) => console.warn(`[Placements] Failed to load ${object.model} for ${object.id}:`, error))
+    }
+
+    return () => {
+      cancelled = true
+      if (viewer.isDestroyed()) return
+      for (const model of models) {
+        viewer.scene.primitives.remove(model)
+      }
+    }
+  }, [viewer, objects])
+}
@@ -0,0 +1,75 @@
+/**
+ * Short-Term Conflict Alert Hook
+ *
+ * Keeps the STCA store in sync with the host's conflict alerts. The desktop
+ * app listens for `stca-alerts` events; remote browsers receive the same
+ * updates over the `/api/stca/ws` WebSocket.
+ */
+
+import { useEffect } from 'react'
+import { useStcaStore } from '../stores/stcaStore'
+import { isTauri } from '../utils/tauriApi'
+import { isRemoteMode } from '../utils/remoteMode'
+import type { StcaAlerts } from '../types/vatsim'
+
+/**
+ * Subscribe to conflict alerts from the host.
+ * Call once at the app root.
+ */
+export function useStcaAlerts() {
+  useEffect(() => {
+    const { setAlerts } = useStcaStore.getState()
+
+    if (isTauri()) {
+      let unlisten: (() => void) | null = null
+      let cancelled = false
+
+      import('@tauri-apps/api/event')
+        .then(({ listen }) => listen<StcaAlerts>('stca-alerts', (event) => setAlerts(event.payload)))
+        .then((fn) => {
+          if (cancelled) fn()
+          else unlisten = fn
+        })
+        .catch((error) => console.error('[STCA] Failed to listen for conflict alerts:', error))
+
+      return () => {
+        cancelled = true
+        unlisten?.()
+      }
+    }
+
+    // Only remote browsers have a host to listen to
+    if (!isRemoteMode()) return
+
+    let ws: WebSocket | null = null
+    let reconnectTimeout: ReturnType<typeof setTimeout> | null = null
+    let closed = false
+
+    const connect = () => {
+      const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:'
+      ws = new WebSocket(`${protocol}//${window.location.host}/api/stca/ws`)
+
+      ws.onmessage = (event) => {
+        try {
+          setAlerts(JSON.parse(event.data) as StcaAlerts)
+        } catch (error) {
+          console.warn('[STCA] Ignoring invalid alert update:', error)
+        }
+      }
+
+      ws.onclose = () => {
+        ws = null
+        // Attempt to reconnect after 5 seconds
+        if (!closed) reconnectTimeout = setTimeout(connect, 5000)
+      }
+    }
+
+    connect()
+
+    return () => {
+      closed = true
+      if (reconnectTimeout) clearTimeout(reconnectTimeout)
+      ws?.close()
+    }
+  }, [])
+}
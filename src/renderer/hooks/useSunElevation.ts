@@ -90,8 +90,8 @@ export function useSunElevation(
 
         currentTime = Cesium.JulianDate.fromDate(targetTime)
       } else {
-        // Real time mode - use current system time
-        currentTime = Cesium.JulianDate.now()
+        // Real time mode - use the viewer clock (current time, or the shared simulated/replay time)
+        currentTime = viewer.clock.currentTime
       }
 
       // Calculate sun position in Earth-Centered Inertial (ECI) frame
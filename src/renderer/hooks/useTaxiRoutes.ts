@@ -0,0 +1,65 @@
+/**
+ * Taxi Routes Hook
+ *
+ * Draws the taxi routes the host predicts for aircraft on the ground
+ * (`trafficApi.getTaxiRoutes`): departures to their runway, arrivals to their
+ * suggested gate. Only polled while enabled (the 2D view).
+ */
+
+import { useEffect } from 'react'
+import * as Cesium from 'cesium'
+import { trafficApi } from '../utils/tauriApi'
+import type { TaxiRoute } from '../types/airport'
+
+/** How often predicted routes are refreshed (ms) */
+const REFRESH_INTERVAL_MS = 10_000
+
+const DEPARTURE_COLOR = Cesium.Color.fromCssColorString('#4fc3f7')
+const ARRIVAL_COLOR = Cesium.Color.fromCssColorString('#ffb74d')
+
+/**
+ * Draw predicted taxi routes at an airport as dashed ground lines
+ */
+export function useTaxiRoutes(viewer: Cesium.Viewer | null, icao: string | null, enabled: boolean): void {
+  useEffect(() => {
+    if (!viewer || !icao || !enabled) return
+
+    const dataSource = new Cesium.CustomDataSource('taxi-routes')
+    viewer.dataSources.add(dataSource)
+    let cancelled = false
+
+    const draw = (routes: TaxiRoute[]) => {
+      dataSource.entities.removeAll()
+      for (const route of routes) {
+        if (route.path.length < 2) continue
+        const color = route.runway ? DEPARTURE_COLOR : ARRIVAL_COLOR
+        dataSource.entities.add({
+          id: `taxi_route_${route.callsign}`,
+          name: `${route.callsign} → ${route.runway ?? route.gate ?? ''} via ${route.taxiways.join(' ')}`,
+          polyline: {
+            positions: Cesium.Cartesian3.fromDegreesArray(route.path.flat()),
+            width: 3,
+            material: new Cesium.PolylineDashMaterialProperty({ color, dashLength: 16 }),
+            clampToGround: true
+          }
+        })
+      }
+    }
+
+    const refresh = () => {
+      trafficApi.getTaxiRoutes(icao)
+        .then((routes) => {
+          if (!cancelled && !viewer.isDestroyed()) draw(routes)
+        })
+        .catch((error) => console.warn('[TaxiRoutes] Failed to load taxi routes:', error))
+    }
+
+    refresh()
+    const interval = setInterval(refresh, REFRESH_INTERVAL_MS)
+    return () => {
+      cancelled = true
+      clearInterval(interval)
+      if (!viewer.isDestroyed()) viewer.dataSources.remove(dataSource, true)
+    }
+  }, [viewer, icao, enabled])
+}
@@ -0,0 +1,80 @@
+/**
+ * Timelapse Capture Hook
+ *
+ * Answers the host's `timelapse-capture` events: renders the next frame,
+ * composites the Cesium canvas and the Babylon.js overlay into one image and
+ * sends it back as a PNG. Only the desktop window captures frames.
+ */
+
+import { useEffect } from 'react'
+import type * as Cesium from 'cesium'
+import { isTauri, timelapseApi } from '../utils/tauriApi'
+import type { TimelapseCaptureRequest } from '../types/timelapse'
+
+/** Draw the scene and overlay into a single PNG */
+function compositeFrame(sceneCanvas: HTMLCanvasElement, overlay: HTMLCanvasElement | null): Promise<Blob | null> {
+  const frame = document.createElement('canvas')
+  frame.width = sceneCanvas.width
+  frame.height = sceneCanvas.height
+  const ctx = frame.getContext('2d')
+  if (!ctx) return Promise.resolve(null)
+
+  ctx.drawImage(sceneCanvas, 0, 0)
+  if (overlay && overlay.width > 0 && overlay.height > 0) {
+    ctx.drawImage(overlay, 0, 0, frame.width, frame.height)
+  }
+  return new Promise((resolve) => frame.toBlob(resolve, 'image/png'))
+}
+
+/**
+ * Capture timelapse frames on request from the host.
+ *
+ * @param viewer - Cesium viewer to capture
+ * @param overlayCanvas - Babylon.js overlay canvas drawn on top
+ * @param enabled - Only the main viewport should capture
+ */
+export function useTimelapseCapture(
+  viewer: Cesium.Viewer | null,
+  overlayCanvas: HTMLCanvasElement | null,
+  enabled: boolean
+) {
+  useEffect(() => {
+    if (!viewer || !enabled || !isTauri()) return
+
+    let unlisten: (() => void) | null = null
+    let removePostRender: (() => void) | null = null
+    let cancelled = false
+
+    const capture = (request: TimelapseCaptureRequest) => {
+      if (viewer.isDestroyed() || removePostRender) return
+
+      // Read the canvas right after a render, while the drawing buffer is valid
+      removePostRender = viewer.scene.postRender.addEventListener(() => {
+        removePostRender?.()
+        removePostRender = null
+        compositeFrame(viewer.scene.canvas, overlayCanvas)
+          .then((blob) => {
+            if (!blob) throw new Error('Failed to encode frame')
+            return blob.arrayBuffer()
+          })
+          .then((png) => timelapseApi.saveFrame(request.sessionId, png))
+          .catch((error) => console.warn(`[Timelapse] Frame ${request.frame} not saved:`, error))
+      })
+      viewer.scene.requestRender()
+    }
+
+    import('@tauri-apps/api/event')
+      .then(({ listen }) => listen<TimelapseCaptureRequest>('timelapse-capture', (event) => capture(event.payload)))
+      .then((fn) => {
+        if (cancelled) fn()
+        else unlisten = fn
+      })
+      .catch((error) => console.error('[Timelapse] Failed to listen for capture requests:', error))
+
+    return () => {
+      cancelled = true
+      unlisten?.()
+      removePostRender?.()
+    }
+  }, [viewer, overlayCanvas, enabled])
+}
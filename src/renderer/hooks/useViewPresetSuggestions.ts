@@ -0,0 +1,54 @@
+/**
+ * View Preset Suggestions Hook
+ *
+ * Shows the bookmark tagged for the airport's new runway configuration when
+ * the host detects a change (`view-preset-suggestion` events, see
+ * view_presets.rs). With auto-switch enabled the host has already loaded it
+ * through a `control-action`; otherwise the suggestion names the slot to load.
+ */
+
+import { useEffect } from 'react'
+import { useUIFeedbackStore } from '../stores/uiFeedbackStore'
+import { isTauri, type ViewPresetSuggestion } from '../utils/tauriApi'
+
+function describe(suggestion: ViewPresetSuggestion): string {
+  const source = suggestion.source === 'atis' ? ' (ATIS)' : suggestion.source === 'traffic' ? ' (traffic)' : ''
+  const runways = `Runway ${suggestion.runways.join(' ')} in use${source}`
+  const bookmark = suggestion.bookmark
+  if (!bookmark) return runways
+  const label = `.${bookmark.slot.toString().padStart(2, '0')}${bookmark.name ? ` ${bookmark.name}` : ''}`
+  return suggestion.switched ? `${runways}: loaded ${label}` : `${runways}: bookmark ${label} matches`
+}
+
+/**
+ * Show runway configuration view preset suggestions in the main window.
+ * Call once at the app root.
+ */
+export function useViewPresetSuggestions() {
+  useEffect(() => {
+    if (!isTauri()) return
+
+    let unlisten: (() => void) | null = null
+    let cancelled = false
+
+    // Only the main window shows them (cab windows keep their own view)
+    Promise.all([import('@tauri-apps/api/webviewWindow'), import('@tauri-apps/api/event')])
+      .then(([{ getCurrentWebviewWindow }, { listen }]) => {
+        if (getCurrentWebviewWindow().label !== 'main') return null
+        return listen<ViewPresetSuggestion>('view-preset-suggestion', (event) => {
+          useUIFeedbackStore.getState().showFeedback(describe(event.payload), 'success')
+        })
+      })
+      .then((fn) => {
+        if (!fn) return
+        if (cancelled) fn()
+        else unlisten = fn
+      })
+      .catch((error) => console.error('[ViewPresets] Failed to listen for suggestions:', error))
+
+    return () => {
+      cancelled = true
+      unlisten?.()
+    }
+  }, [])
+}
@@ -0,0 +1,75 @@
+/**
+ * Wake Timer Hook
+ *
+ * Keeps the wake timer store in sync with the host's departure wake timers.
+ * The desktop app listens for `wake-timers` events; remote browsers receive
+ * the same updates over the `/api/wake-timers/ws` WebSocket.
+ */
+
+import { useEffect } from 'react'
+import { useWakeTimerStore } from '../stores/wakeTimerStore'
+import { isTauri } from '../utils/tauriApi'
+import { isRemoteMode } from '../utils/remoteMode'
+import type { WakeTimers } from '../types/vatsim'
+
+/**
+ * Subscribe to wake timers from the host.
+ * Call once at the app root.
+ */
+export function useWakeTimers() {
+  useEffect(() => {
+    const { setTimers } = useWakeTimerStore.getState()
+
+    if (isTauri()) {
+      let unlisten: (() => void) | null = null
+      let cancelled = false
+
+      import('@tauri-apps/api/event')
+        .then(({ listen }) => listen<WakeTimers>('wake-timers', (event) => setTimers(event.payload)))
+        .then((fn) => {
+          if (cancelled) fn()
+          else unlisten = fn
+        })
+        .catch((error) => console.error('[WakeTimers] Failed to listen for wake timers:', error))
+
+      return () => {
+        cancelled = true
+        unlisten?.()
+      }
+    }
+
+    // Only remote browsers have a host to listen to
+    if (!isRemoteMode()) return
+
+    let ws: WebSocket | null = null
+    let reconnectTimeout: ReturnType<typeof setTimeout> | null = null
+    let closed = false
+
+    const connect = () => {
+      const protocol = window.location.protocol === 'https:' ? 'wss:' : 'ws:'
+      ws = new WebSocket(`${protocol}//${window.location.host}/api/wake-timers/ws`)
+
+      ws.onmessage = (event) => {
+        try {
+          setTimers(JSON.parse(event.data) as WakeTimers)
+        } catch (error) {
+          console.warn('[WakeTimers] Ignoring invalid timer update:', error)
+        }
+      }
+
+      ws.onclose = () => {
+        ws = null
+        // Attempt to reconnect after 5 seconds
+        if (!closed) reconnectTimeout = setTimeout(connect, 5000)
+      }
+    }
+
+    connect()
+
+    return () => {
+      closed = true
+      if (reconnectTimeout) clearTimeout(reconnectTimeout)
+      ws?.close()
+    }
+  }, [])
+}
@@ -0,0 +1,72 @@
+/**
+ * Window Layout Bookmark Hook
+ *
+ * When a saved window layout is applied, the host tells each window which
+ * camera bookmark to load (`window-layout-bookmark`). Windows the layout had
+ * to open ask for their bookmark with `applyAssignedWindowBookmark` once
+ * loading finishes, since they weren't listening yet when the event was sent.
+ */
+
+import { useEffect } from 'react'
+import { useViewportStore } from '../stores/viewportStore'
+import { isTauri, windowLayoutApi } from '../utils/tauriApi'
+import { findBookmarkSlot } from '../utils/startupArgs'
+
+/** Bookmark a window should load */
+interface WindowBookmark {
+  label: string
+  bookmark: string
+}
+
+function loadWindowBookmark(bookmark: string) {
+  const icao = useViewportStore.getState().currentAirportIcao
+  if (!icao) return
+  const slot = findBookmarkSlot(icao, bookmark)
+  if (slot === null || !useViewportStore.getState().loadBookmark(slot)) {
+    console.warn(`[WindowLayout] No bookmark "${bookmark}" at ${icao}`)
+  }
+}
+
+/**
+ * Load the bookmark a layout assigned to this window (desktop only)
+ */
+export async function applyAssignedWindowBookmark(): Promise<void> {
+  if (!isTauri()) return
+  try {
+    const bookmark = await windowLayoutApi.getBookmark()
+    if (bookmark) loadWindowBookmark(bookmark)
+  } catch (error) {
+    console.warn('[WindowLayout] Failed to read assigned bookmark:', error)
+  }
+}
+
+/**
+ * Load bookmarks sent to this window by window layouts.
+ * Call once at the app root.
+ */
+export function useWindowLayoutBookmark() {
+  useEffect(() => {
+    if (!isTauri()) return
+
+    let unlisten: (() => void) | null = null
+    let cancelled = false
+
+    Promise.all([import('@tauri-apps/api/event'), import('@tauri-apps/api/webviewWindow')])
+      .then(([{ listen }, { getCurrentWebviewWindow }]) => {
+        const label = getCurrentWebviewWindow().label
+        return listen<WindowBookmark>('window-layout-bookmark', (event) => {
+          if (event.payload.label === label) loadWindowBookmark(event.payload.bookmark)
+        })
+      })
+      .then((fn) => {
+        if (cancelled) fn()
+        else unlisten = fn
+      })
+      .catch((error) => console.error('[WindowLayout] Failed to listen for layout bookmarks:', error))
+
+    return () => {
+      cancelled = true
+      unlisten?.()
+    }
+  }, [])
+}
@@ -2,7 +2,12 @@ import React from 'react'
 import ReactDOM from 'react-dom/client'
 import App from './App'
 import './assets/styles/global.css'
-import { registerTileCacheServiceWorker } from './utils/serviceWorkerRegistration'
+import { registerTileCacheServiceWorker, syncContentManifest } from './utils/serviceWorkerRegistration'
+import { isRemoteMode } from './utils/remoteMode'
+import { installClientLogForwarding } from './utils/clientLogForwarding'
+import { installGamepadInput } from './utils/gamepadInput'
+import { installMidiInput } from './utils/midiInput'
+import { negotiateClientCapabilities } from './utils/clientCapabilities'
 
 // Suppress Cesium render loop console spam
 // Cesium logs on every requestAnimationFrame which clutters the console
@@ -14,9 +19,27 @@ console.log = (...args: unknown[]) => {
   originalLog.apply(console, args)
 }
 
+// Send console errors and warnings from remote browsers to the host log
+installClientLogForwarding()
+
+// Receive gamepad camera input read by the host (desktop app)
+installGamepadInput()
+
+// Receive MIDI controller knob positions from the host (desktop app)
+installMidiInput()
+
+// Tell the host what this device can handle (remote browsers only), so it
+// tailors models, aircraft updates and compression to it
+negotiateClientCapabilities()
+
 // Register service worker for tile caching
-// This caches tiles at the HTTP layer, transparent to Cesium
-registerTileCacheServiceWorker()
+// This caches tiles at the HTTP layer, transparent to Cesium. Remote browsers
+// also keep the host's models and overlays, dropping those that changed.
+registerTileCacheServiceWorker().then((registration) => {
+  if (registration && isRemoteMode()) {
+    syncContentManifest()
+  }
+})
 
 ReactDOM.createRoot(document.getElementById('root') as HTMLElement).render(
   <React.StrictMode>
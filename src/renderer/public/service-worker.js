@@ -13,6 +13,24 @@ const TILE_URL_PATTERNS = [
   /\.openstreetmap\.org.*\/\d+\/\d+\/\d+/, // OSM tiles
 ]
 
+// Assets served by the host (mods, FSLTL models, overlays), kept until the
+// content manifest says they changed
+const CONTENT_CACHE_NAME = 'content-v1'
+const CONTENT_HASHES_URL = '/__content-hashes'
+const CONTENT_URL_PATTERN = /^\/api\/(mods\/(aircraft|towers)|fsltl|overlays)\/.+/
+
+// Large mods and models are downloaded in hashed chunks from /api/chunked/...
+// Verified chunks are kept until the file is complete, so a dropped
+// connection only costs the chunk in flight
+const CHUNKS_CACHE_NAME = 'content-chunks-v1'
+const CONTENT_SIZES_URL = '/__content-sizes'
+const CHUNKED_URL_PATTERN = /^\/api\/(mods\/(aircraft|towers)|fsltl)\/.+/
+const CHUNKED_MIN_BYTES = 16 * 1024 * 1024
+const CHUNK_ATTEMPTS = 6
+
+// Chunked downloads in progress by path (concurrent requests share one)
+const chunkedDownloads = new Map()
+
 // Max cache size in bytes (2GB default)
 const MAX_CACHE_SIZE = 2 * 1024 * 1024 * 1024
 
@@ -29,6 +47,11 @@ self.addEventListener('activate', (event) => {
 self.addEventListener('fetch', (event) => {
   const url = event.request.url
 
+  if (isContentRequest(event.request)) {
+    event.respondWith(handleContentRequest(event.request))
+    return
+  }
+
   // Only cache tile requests
   const isTileRequest = TILE_URL_PATTERNS.some(pattern => pattern.test(url))
   if (!isTileRequest) {
@@ -100,6 +123,150 @@ async function handleTileRequest(request) {
   }
 }
 
+function isContentRequest(request) {
+  if (request.method !== 'GET') return false
+  const url = new URL(request.url)
+  return url.origin === self.location.origin && CONTENT_URL_PATTERN.test(url.pathname)
+}
+
+// Cache-first: entries are removed by syncContentManifest when they change
+async function handleContentRequest(request) {
+  const cache = await caches.open(CONTENT_CACHE_NAME)
+  const key = new URL(request.url).pathname
+  const cachedResponse = await cache.match(key)
+  if (cachedResponse) {
+    return cachedResponse
+  }
+
+  if (CHUNKED_URL_PATTERN.test(key) && await contentSize(cache, key) >= CHUNKED_MIN_BYTES) {
+    try {
+      return await downloadChunked(cache, request, key)
+    } catch (error) {
+      console.warn('[ContentCache] Chunked download failed, fetching whole file:', key, error)
+    }
+  }
+
+  const networkResponse = await fetch(request)
+  if (networkResponse.ok) {
+    cache.put(key, networkResponse.clone()).catch(() => {})
+  }
+  return networkResponse
+}
+
+// Size of an asset from the last synced content manifest (0 if unknown)
+async function contentSize(cache, path) {
+  const stored = await cache.match(CONTENT_SIZES_URL)
+  const sizes = stored ? await stored.json() : {}
+  return sizes[path] ?? 0
+}
+
+function downloadChunked(cache, request, key) {
+  let download = chunkedDownloads.get(key)
+  if (!download) {
+    download = fetchChunked(cache, request, key).finally(() => chunkedDownloads.delete(key))
+    chunkedDownloads.set(key, download)
+  }
+  return download.then((response) => response.clone())
+}
+
+async function sha256Hex(buffer) {
+  const digest = await crypto.subtle.digest('SHA-256', buffer)
+  return Array.from(new Uint8Array(digest), (b) => b.toString(16).padStart(2, '0')).join('')
+}
+
+const delay = (ms) => new Promise((resolve) => setTimeout(resolve, ms))
+
+// One chunk, retried with backoff while the connection is down
+async function fetchChunk(request, chunkedUrl, manifest, index) {
+  let lastError
+  for (let attempt = 0; attempt < CHUNK_ATTEMPTS; attempt++) {
+    if (attempt > 0) await delay(1000 * 2 ** (attempt - 1))
+    try {
+      const response = await fetch(`${chunkedUrl}?chunk=${index}&hash=${manifest.hash}`, {
+        headers: request.headers,
+        credentials: 'same-origin'
+      })
+      if (response.status === 409) throw new Error('File changed on the host')
+      if (!response.ok) throw new Error(`HTTP ${response.status}`)
+      const buffer = await response.arrayBuffer()
+      if (await sha256Hex(buffer) === manifest.chunks[index]) return buffer
+      lastError = new Error(`Chunk ${index} failed verification`)
+    } catch (error) {
+      if (error.message === 'File changed on the host') throw error
+      lastError = error
+    }
+  }
+  throw lastError
+}
+
+async function fetchChunked(cache, request, key) {
+  const chunkedUrl = key.replace(/^\/api\//, '/api/chunked/')
+  const manifestResponse = await fetch(chunkedUrl, { headers: request.headers, credentials: 'same-origin' })
+  if (!manifestResponse.ok) throw new Error(`HTTP ${manifestResponse.status}`)
+  const manifest = await manifestResponse.json()
+
+  const chunkCache = await caches.open(CHUNKS_CACHE_NAME)
+  const chunkKey = (index) => `/__chunks/${manifest.hash}/${index}`
+  const parts = []
+  for (let index = 0; index < manifest.chunks.length; index++) {
+    const stored = await chunkCache.match(chunkKey(index))
+    if (stored) {
+      parts.push(await stored.arrayBuffer())
+      continue
+    }
+    const buffer = await fetchChunk(request, chunkedUrl, manifest, index)
+    await chunkCache.put(chunkKey(index), new Response(buffer)).catch(() => {})
+    parts.push(buffer)
+  }
+
+  const response = new Response(new Blob(parts, { type: manifest.contentType }), {
+    headers: { 'Content-Type': manifest.contentType, 'Content-Length': String(manifest.size) }
+  })
+  await cache.put(key, response.clone()).catch(() => {})
+  await Promise.all(manifest.chunks.map((_, index) => chunkCache.delete(chunkKey(index))))
+  return response
+}
+
+// Drop cached assets whose hash changed or that the host no longer serves
+async function syncContentManifest(manifest) {
+  const cache = await caches.open(CONTENT_CACHE_NAME)
+  const stored = await cache.match(CONTENT_HASHES_URL)
+  const previous = stored ? await stored.json() : {}
+
+  const current = {}
+  const sizes = {}
+  for (const entry of manifest.entries) {
+    current[entry.url] = entry.hash
+    sizes[entry.url] = entry.size
+  }
+
+  let removed = 0
+  for (const request of await cache.keys()) {
+    const path = new URL(request.url).pathname
+    if (path === CONTENT_HASHES_URL || path === CONTENT_SIZES_URL) continue
+    const hash = current[path]
+    if (hash === undefined || previous[path] !== hash) {
+      if (await cache.delete(request)) removed++
+    }
+  }
+
+  await cache.put(CONTENT_HASHES_URL, new Response(JSON.stringify(current), {
+    headers: { 'Content-Type': 'application/json' }
+  }))
+  await cache.put(CONTENT_SIZES_URL, new Response(JSON.stringify(sizes), {
+    headers: { 'Content-Type': 'application/json' }
+  }))
+
+  // Chunks of unfinished downloads of files that have since changed
+  const hashes = new Set(Object.values(current))
+  const chunkCache = await caches.open(CHUNKS_CACHE_NAME)
+  for (const request of await chunkCache.keys()) {
+    const hash = new URL(request.url).pathname.split('/')[2]
+    if (!hashes.has(hash)) await chunkCache.delete(request)
+  }
+  return removed
+}
+
 async function cleanupCache() {
   const cache = await caches.open(CACHE_NAME)
   const keys = await cache.keys()
@@ -148,6 +315,12 @@ async function cleanupCache() {
 
 // Listen for messages from the main thread
 self.addEventListener('message', (event) => {
+  if (event.data.type === 'SYNC_CONTENT_MANIFEST') {
+    syncContentManifest(event.data.manifest)
+      .then((removed) => event.ports[0]?.postMessage({ success: true, removed }))
+      .catch(() => event.ports[0]?.postMessage({ success: false, removed: 0 }))
+    return
+  }
   if (event.data.type === 'CLEAR_CACHE') {
     caches.delete(CACHE_NAME).then(() => {
       if (event.ports[0]) {
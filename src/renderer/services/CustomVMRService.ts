@@ -19,16 +19,9 @@
  * ```
  */
 
-import { convertToAssetUrlSync, modApi, isTauri } from '../utils/tauriApi'
+import { convertToAssetUrlSync, modApi } from '../utils/tauriApi'
 import type { CustomVMRRule, CustomVMRMatch } from '../types/mod'
 
-/** VMR rule from HTTP API */
-interface ApiVmrRule {
-  typeCode: string
-  modelName: string
-  callsignPrefix: string | null
-}
-
 /** Rule entry with base path for model resolution */
 interface RuleEntry {
   rule: CustomVMRRule
@@ -59,63 +52,19 @@ class CustomVMRServiceClass {
   private manifestCache = new Map<string, ManifestCache>()
 
   /**
-   * Initialize by loading all VMR files from mods folder
+   * Initialize by loading the parsed rules of all enabled VMR files.
+   * Parsing (and per-file/per-rule enablement) is handled by the backend.
    */
   async loadVMRFiles(): Promise<void> {
     if (this.loaded) return
 
     try {
-      if (isTauri()) {
-        // Tauri mode: load VMR files directly from disk
-        const vmrPaths = await modApi.listVMRFiles()
-
-        for (const vmrPath of vmrPaths) {
-          try {
-            const content = await modApi.readTextFile(vmrPath)
-            const basePath = this.getBasePath(vmrPath)
-            this.parseVMRContent(content, vmrPath, basePath)
-            // Pre-load manifests for all models in this VMR file
-            await this.preloadManifestsForVMR(basePath)
-            this.loadedFiles.push(vmrPath)
-          } catch (error) {
-            console.warn(`[CustomVMRService] Failed to load VMR: ${vmrPath}`, error)
-          }
-        }
-      } else {
-        // Browser mode: fetch pre-parsed VMR rules from HTTP API
-        await this.loadVMRRulesFromAPI()
-      }
-
-      this.loaded = true
-      if (this.defaultRules.size > 0 || this.airlineRules.size > 0) {
-        console.log(
-          `[CustomVMRService] Loaded ${this.loadedFiles.length} VMR file(s), ` +
-          `${this.defaultRules.size} default rules, ${this.airlineRules.size} airline rules`
-        )
-      }
-    } catch (error) {
-      console.error('[CustomVMRService] Failed to load VMR files:', error)
-      this.loaded = true
-    }
-  }
-
-  /**
-   * Load VMR rules from HTTP API (browser mode)
-   * The server returns pre-parsed rules from all VMR files
-   */
-  private async loadVMRRulesFromAPI(): Promise<void> {
-    try {
-      const response = await fetch('/api/vmr-rules')
-      if (!response.ok) {
-        console.warn('[CustomVMRService] Failed to fetch VMR rules from API:', response.status)
-        return
-      }
-
-      const rules: ApiVmrRule[] = await response.json()
-      // Base path for models served via HTTP API
-      const basePath = '/api/mods/aircraft'
+      const rules = await modApi.getVMRRules()
+      // Models are resolved relative to mods/aircraft/
+      const basePath = await modApi.getModsPath('aircraft')
 
       for (const apiRule of rules) {
+        if (!apiRule.typeCode) continue
         const typeCode = apiRule.typeCode.toUpperCase()
         const modelNames = apiRule.modelName.split('//').filter(name => name.trim())
         const callsignPrefix = apiRule.callsignPrefix?.toUpperCase()
@@ -128,6 +77,7 @@ class CustomVMRServiceClass {
           callsignPrefix
         }
 
+        // First rule wins for conflicts
         if (callsignPrefix) {
           const key = `${callsignPrefix}_${typeCode}`
           if (!this.airlineRules.has(key)) {
@@ -138,141 +88,28 @@ class CustomVMRServiceClass {
             this.defaultRules.set(typeCode, { rule, basePath })
           }
         }
+
+        const sourceFile = apiRule.sourceFile ?? '(HTTP API)'
+        if (!this.loadedFiles.includes(sourceFile)) {
+          this.loadedFiles.push(sourceFile)
+        }
       }
 
       // Pre-load manifests for all models
       if (rules.length > 0) {
         await this.preloadManifestsForVMR(basePath)
-        this.loadedFiles.push('(HTTP API)')
       }
-    } catch (error) {
-      console.warn('[CustomVMRService] Error fetching VMR rules from API:', error)
-    }
-  }
-
-  /**
-   * Get the base path for model resolution from VMR file path
-   * Models are resolved relative to mods/aircraft/
-   */
-  private getBasePath(vmrPath: string): string {
-    // Normalize path separators
-    const normalized = vmrPath.replace(/\\/g, '/')
-    const modsIndex = normalized.lastIndexOf('/mods/')
-    if (modsIndex >= 0) {
-      return normalized.substring(0, modsIndex) + '/mods/aircraft'
-    }
-    // Fallback: use parent directory of VMR file
-    const lastSlash = normalized.lastIndexOf('/')
-    return lastSlash >= 0 ? normalized.substring(0, lastSlash) : normalized
-  }
-
-  /**
-   * Parse VMR XML content
-   */
-  private parseVMRContent(content: string, sourcePath: string, basePath: string): void {
-    const parser = new DOMParser()
-    const doc = parser.parseFromString(content, 'text/xml')
-
-    const parseError = doc.querySelector('parsererror')
-    if (parseError) {
-      console.warn(`[CustomVMRService] XML parse error in ${sourcePath}, using regex fallback`)
-      this.parseVMRContentFallback(content, basePath)
-      return
-    }
-
-    const rules = doc.querySelectorAll('ModelMatchRule')
-
-    for (const ruleEl of rules) {
-      const typeCode = ruleEl.getAttribute('TypeCode')?.toUpperCase()
-      const modelName = ruleEl.getAttribute('ModelName')
-      const callsignPrefix = ruleEl.getAttribute('CallsignPrefix')?.toUpperCase()
 
-      if (!typeCode || !modelName) continue
-
-      // Split alternatives (e.g., "Model1//Model2") and filter empty strings
-      const modelNames = modelName.split('//').filter(name => name.trim())
-
-      // Warn if all model names are empty
-      if (modelNames.length === 0) {
-        console.warn(`[CustomVMRService] VMR rule for ${typeCode} has no valid model names`)
-        continue
-      }
-
-      const rule: CustomVMRRule = {
-        typeCode,
-        modelNames,
-        callsignPrefix: callsignPrefix || undefined
-      }
-
-      if (callsignPrefix) {
-        const key = `${callsignPrefix}_${typeCode}`
-        // First rule wins for conflicts
-        if (!this.airlineRules.has(key)) {
-          this.airlineRules.set(key, { rule, basePath })
-        }
-      } else {
-        // First rule wins for conflicts
-        if (!this.defaultRules.has(typeCode)) {
-          this.defaultRules.set(typeCode, { rule, basePath })
-        }
-      }
-    }
-  }
-
-  /**
-   * Fallback regex parsing for malformed XML
-   * Matches both self-closing (<ModelMatchRule ... />) and open/close (<ModelMatchRule ...></ModelMatchRule>) tags
-   */
-  private parseVMRContentFallback(content: string, basePath: string): void {
-    // Match self-closing tags: <ModelMatchRule ... />
-    const selfClosingRegex = /<ModelMatchRule\s+([^>]+)\s*\/>/g
-    // Match open/close tags: <ModelMatchRule ...>...</ModelMatchRule>
-    const openCloseRegex = /<ModelMatchRule\s+([^>]+)>[\s\S]*?<\/ModelMatchRule>/g
-
-    const allMatches: Array<{ attrs: string }> = []
-
-    let match: RegExpExecArray | null
-    while ((match = selfClosingRegex.exec(content)) !== null) {
-      allMatches.push({ attrs: match[1] })
-    }
-
-    while ((match = openCloseRegex.exec(content)) !== null) {
-      allMatches.push({ attrs: match[1] })
-    }
-
-    for (const { attrs } of allMatches) {
-      const typeCodeMatch = attrs.match(/TypeCode\s*=\s*"([^"]+)"/)
-      const modelNameMatch = attrs.match(/ModelName\s*=\s*"([^"]+)"/)
-      const callsignMatch = attrs.match(/CallsignPrefix\s*=\s*"([^"]+)"/)
-
-      if (!typeCodeMatch || !modelNameMatch) continue
-
-      const typeCode = typeCodeMatch[1].toUpperCase()
-      const modelNames = modelNameMatch[1].split('//').filter(name => name.trim())
-      const callsignPrefix = callsignMatch?.[1]?.toUpperCase()
-
-      // Warn if all model names are empty
-      if (modelNames.length === 0) {
-        console.warn(`[CustomVMRService] VMR rule for ${typeCode} has no valid model names`)
-        continue
-      }
-
-      const rule: CustomVMRRule = {
-        typeCode,
-        modelNames,
-        callsignPrefix
-      }
-
-      if (callsignPrefix) {
-        const key = `${callsignPrefix}_${typeCode}`
-        if (!this.airlineRules.has(key)) {
-          this.airlineRules.set(key, { rule, basePath })
-        }
-      } else {
-        if (!this.defaultRules.has(typeCode)) {
-          this.defaultRules.set(typeCode, { rule, basePath })
-        }
+      this.loaded = true
+      if (this.defaultRules.size > 0 || this.airlineRules.size > 0) {
+        console.log(
+          `[CustomVMRService] Loaded ${this.loadedFiles.length} VMR file(s), ` +
+          `${this.defaultRules.size} default rules, ${this.airlineRules.size} airline rules`
+        )
       }
+    } catch (error) {
+      console.error('[CustomVMRService] Failed to load VMR files:', error)
+      this.loaded = true
     }
   }
 
@@ -305,6 +305,9 @@ export function importData(data: ExportData | SelectiveExportData, options: Impo
           if (data.globalSettings.server) {
             globalStore.updateServer(data.globalSettings.server as Parameters<typeof globalStore.updateServer>[0])
           }
+          if (data.globalSettings.datablock) {
+            globalStore.updateDatablock(data.globalSettings.datablock as Parameters<typeof globalStore.updateDatablock>[0])
+          }
         }
       } else {
         // v2: globalSettings contains all local settings
@@ -1,7 +1,7 @@
 // METAR weather service
 // Handles fetching and parsing METAR data from Aviation Weather API
 
-import { invoke } from '@tauri-apps/api/core'
+import { invokeCommand } from '@/utils/apiError'
 import { isTauri } from '@/utils/tauriApi'
 import type { Precipitation, PrecipitationType, PrecipitationIntensity, WindState, CloudLayer, PrecipitationState, DistancedMetar } from '@/types'
 import { METAR_PRECIP_CODES, INTERPOLATION_STATION_COUNT, INTERPOLATION_RADIUS_NM } from '@/constants'
@@ -14,7 +14,7 @@ const METAR_API_URL = 'https://aviationweather.gov/api/data/metar'
 async function fetchUrl(url: string): Promise<string> {
   if (isTauri()) {
     // Use Tauri command (available in desktop mode)
-    const response = await invoke<string>('fetch_url', { url })
+    const response = await invokeCommand<string>('fetch_url', { url })
     return response
   } else {
     // In browser mode, use the CORS proxy endpoint
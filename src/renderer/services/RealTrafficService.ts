@@ -8,7 +8,7 @@
  * @see types/realtraffic.ts - Type definitions for API responses
  */
 
-import { invoke } from '@tauri-apps/api/core'
+import { invokeCommand } from '@/utils/apiError'
 import { isTauri } from '@/utils/tauriApi'
 import type {
   RTAuthResponse,
@@ -114,7 +114,7 @@ class RealTrafficService {
     // In Tauri mode, use Tauri command (bypasses CORS)
     if (isTauri()) {
       try {
-        const result = await invoke<TauriAuthResult>('realtraffic_auth', { licenseKey })
+        const result = await invokeCommand<TauriAuthResult>('realtraffic_auth', { licenseKey })
 
         if (!result.success) {
           return { success: false, error: result.error || 'Authentication failed' }
@@ -223,7 +223,7 @@ class RealTrafficService {
           timeOffset: (timeOffset > 0 && this.isPro) ? timeOffset : undefined
         }
 
-        const responseText = await invoke<string>('realtraffic_traffic', { params })
+        const responseText = await invokeCommand<string>('realtraffic_traffic', { params })
         const data: RTTrafficResponse = JSON.parse(responseText)
 
         // Check for API error status (e.g., 406 rate limit violation)
@@ -451,7 +451,7 @@ class RealTrafficService {
           lonMax
         }
 
-        const responseText = await invoke<string>('realtraffic_parked_traffic', { params })
+        const responseText = await invokeCommand<string>('realtraffic_parked_traffic', { params })
         const data: RTParkedTrafficResponse = JSON.parse(responseText)
 
         // Check for API error status
@@ -694,7 +694,7 @@ class RealTrafficService {
     // In Tauri mode, use Tauri command
     if (isTauri()) {
       try {
-        await invoke<void>('realtraffic_deauth', { guid })
+        await invokeCommand<void>('realtraffic_deauth', { guid })
         console.log('[RealTraffic] Deauthenticated successfully')
         this.clearSession()
         return true
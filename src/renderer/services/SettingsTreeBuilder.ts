@@ -183,7 +183,8 @@ export const SUBCATEGORY_MAPPINGS: Record<string, Record<string, { label: string
 export const GLOBAL_SUBCATEGORY_MAPPINGS: Record<string, { label: string; path: string }> = {
   fsltl: { label: 'FSLTL Configuration', path: 'fsltl' },
   airports: { label: 'Default/Recent Airports', path: 'airports' },
-  server: { label: 'Server Configuration', path: 'server' }
+  server: { label: 'Server Configuration', path: 'server' },
+  datablock: { label: 'Datablock Layout', path: 'datablock' }
 }
 
 /**
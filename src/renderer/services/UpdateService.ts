@@ -1,27 +1,27 @@
 // Update service for checking and installing app updates
-// Uses Tauri's updater plugin with GitHub Releases
+// Uses Tauri's updater plugin with GitHub Releases, through the host (updater.rs)
+// so the release channel, skipped version and deferral in global settings apply
 // Note: Updates are only available in Tauri (desktop) mode, not in browser mode
 
-import type { Update } from '@tauri-apps/plugin-updater'
-import { isTauri } from '@/utils/tauriApi'
+import { appApi, isTauri } from '@/utils/tauriApi'
 import { useUpdateStore } from '@/stores/updateStore'
+import { useGlobalSettingsStore } from '@/stores/globalSettingsStore'
+import type { UpdateChannel } from '@/types'
 
 // Auto-check interval: 4 hours in milliseconds
 const AUTO_CHECK_INTERVAL = 4 * 60 * 60 * 1000
 
-// Store reference to current update for download/install
-let currentUpdate: Update | null = null
-
 // Timer IDs for auto-update checks
 let initialDelayTimer: ReturnType<typeof setTimeout> | null = null
 let autoCheckTimer: ReturnType<typeof setInterval> | null = null
 
 /**
  * Check for available updates
+ * @param manual "Check for Updates" button: also offers skipped versions and ignores deferral
  * @returns true if an update is available
  * Note: Only works in Tauri (desktop) mode
  */
-export async function checkForUpdates(): Promise<boolean> {
+export async function checkForUpdates(manual = false): Promise<boolean> {
   // Updates not available in browser mode
   if (!isTauri()) {
     console.log('[Update] Skipping update check (browser mode)')
@@ -39,29 +39,25 @@ export async function checkForUpdates(): Promise<boolean> {
     store.setStatus('checking')
     store.setError(null)
 
-    // Dynamic import of Tauri updater plugin
-    const { check } = await import('@tauri-apps/plugin-updater')
-    const update = await check()
+    const update = await appApi.checkForUpdate(manual)
 
     if (update) {
-      currentUpdate = update
-      store.setUpdateInfo({
-        version: update.version,
-        currentVersion: update.currentVersion,
-        date: update.date ?? null,
-        body: update.body ?? null
-      })
+      store.setUpdateInfo(update)
       store.setStatus('available')
-      console.log(`[Update] Update available: v${update.version}`)
+      console.log(`[Update] Update available: v${update.version} (${update.channel})`)
+      // Notes of every release since the running one (best effort)
+      appApi.getReleaseNotes()
+        .then((notes) => store.setReleaseNotes(notes))
+        .catch((error) => console.warn('[Update] Failed to load release notes:', error))
       return true
     } else {
       store.setStatus('up-to-date')
-      console.log('[Update] App is up to date')
+      console.log('[Update] No update to offer')
       return false
     }
   } catch (error) {
     console.error('[Update] Check failed:', error)
-    store.setError(error instanceof Error ? error.message : 'Failed to check for updates')
+    store.setError(error instanceof Error ? error.message : String(error))
     return false
   }
 }
@@ -77,38 +73,71 @@ export async function downloadAndInstallUpdate(): Promise<void> {
 
   const store = useUpdateStore.getState()
 
-  if (!currentUpdate) {
+  if (!store.updateInfo) {
     store.setError('No update available')
     return
   }
 
+  const { listen } = await import('@tauri-apps/api/event')
+  const unlisten = await listen<{ downloaded: number; total: number | null }>('update-progress', (event) => {
+    store.setProgress(event.payload)
+  })
+
   try {
     store.setStatus('downloading')
     store.setProgress({ downloaded: 0, total: null })
 
-    let totalBytes = 0
-    let downloadedBytes = 0
-
-    await currentUpdate.downloadAndInstall((event) => {
-      if (event.event === 'Started') {
-        totalBytes = event.data.contentLength ?? 0
-        store.setProgress({ downloaded: 0, total: totalBytes || null })
-        console.log(`[Update] Started downloading ${totalBytes} bytes`)
-      } else if (event.event === 'Progress') {
-        downloadedBytes += event.data.chunkLength
-        store.setProgress({ downloaded: downloadedBytes, total: totalBytes || null })
-      } else if (event.event === 'Finished') {
-        store.setProgress({ downloaded: totalBytes, total: totalBytes || null })
-        console.log('[Update] Download finished')
-      }
-    })
+    await appApi.installUpdate()
 
     store.setStatus('ready')
     console.log('[Update] Update installed, ready to restart')
 
   } catch (error) {
     console.error('[Update] Download/install failed:', error)
-    store.setError(error instanceof Error ? error.message : 'Failed to download update')
+    store.setError(error instanceof Error ? error.message : String(error))
+  } finally {
+    unlisten()
+  }
+}
+
+/**
+ * Don't offer updates again for a while ("Later")
+ */
+export async function deferUpdates(hours: number): Promise<void> {
+  useUpdateStore.getState().reset()
+  try {
+    await appApi.deferUpdates(hours)
+    await useGlobalSettingsStore.getState().refresh()
+  } catch (error) {
+    console.error('[Update] Failed to defer updates:', error)
+  }
+}
+
+/**
+ * Don't offer the available version again on automatic checks
+ */
+export async function skipUpdate(): Promise<void> {
+  const version = useUpdateStore.getState().updateInfo?.version
+  useUpdateStore.getState().reset()
+  if (!version) return
+  try {
+    await appApi.skipUpdateVersion(version)
+    await useGlobalSettingsStore.getState().refresh()
+  } catch (error) {
+    console.error('[Update] Failed to skip version:', error)
+  }
+}
+
+/**
+ * Switch the release channel and check it for updates
+ */
+export async function setUpdateChannel(channel: UpdateChannel): Promise<void> {
+  await appApi.setUpdateChannel(channel)
+  await useGlobalSettingsStore.getState().refresh()
+  const store = useUpdateStore.getState()
+  if (store.status !== 'downloading' && store.status !== 'ready') {
+    store.reset()
+    await checkForUpdates(true)
   }
 }
 
@@ -12,8 +12,8 @@
  * @see FSLTLService - Frontend service that uses this API
  */
 
-import { invoke } from '@tauri-apps/api/core'
 import { isTauri } from '../utils/tauriApi'
+import { invokeCommand, responseError } from '../utils/apiError'
 import type { ConversionProgress } from '../types/fsltl'
 
 /**
@@ -26,7 +26,7 @@ export async function pickFolder(): Promise<string | null> {
     console.warn('[fsltlApi] pickFolder not available in browser mode')
     return null
   }
-  return invoke<string | null>('pick_folder')
+  return invokeCommand<string | null>('pick_folder')
 }
 
 /**
@@ -39,7 +39,7 @@ export async function readTextFile(path: string): Promise<string> {
   if (!isTauri()) {
     throw new Error('[fsltlApi] readTextFile not available in browser mode')
   }
-  return invoke<string>('read_text_file', { path })
+  return invokeCommand<string>('read_text_file', { path })
 }
 
 /**
@@ -52,7 +52,7 @@ export async function writeTextFile(path: string, content: string): Promise<void
   if (!isTauri()) {
     throw new Error('[fsltlApi] writeTextFile not available in browser mode')
   }
-  return invoke<void>('write_text_file', { path, content })
+  return invokeCommand<void>('write_text_file', { path, content })
 }
 
 /**
@@ -64,7 +64,7 @@ export async function getFsltlOutputPath(): Promise<string> {
   if (!isTauri()) {
     return ''
   }
-  return invoke<string>('get_fsltl_output_path')
+  return invokeCommand<string>('get_fsltl_output_path')
 }
 
 /**
@@ -76,7 +76,7 @@ export async function getFsltlDefaultOutputPath(): Promise<[string, boolean]> {
   if (!isTauri()) {
     return ['', false]
   }
-  return invoke<[string, boolean]>('get_fsltl_default_output_path')
+  return invokeCommand<[string, boolean]>('get_fsltl_default_output_path')
 }
 
 /**
@@ -90,7 +90,7 @@ export async function validateFsltlSource(sourcePath: string): Promise<boolean>
   if (!isTauri()) {
     return false
   }
-  return invoke<boolean>('validate_fsltl_source', { sourcePath })
+  return invokeCommand<boolean>('validate_fsltl_source', { sourcePath })
 }
 
 /**
@@ -103,7 +103,7 @@ export async function listFsltlAircraft(sourcePath: string): Promise<string[]> {
   if (!isTauri()) {
     return []
   }
-  return invoke<string[]>('list_fsltl_aircraft', { sourcePath })
+  return invokeCommand<string[]>('list_fsltl_aircraft', { sourcePath })
 }
 
 /**
@@ -115,7 +115,7 @@ export async function getConverterPath(): Promise<string> {
   if (!isTauri()) {
     return ''
   }
-  return invoke<string>('get_converter_path')
+  return invokeCommand<string>('get_converter_path')
 }
 
 /**
@@ -137,7 +137,7 @@ export async function startFsltlConversion(
   if (!isTauri()) {
     throw new Error('[fsltlApi] startFsltlConversion not available in browser mode')
   }
-  return invoke<void>('start_fsltl_conversion', {
+  return invokeCommand<void>('start_fsltl_conversion', {
     sourcePath,
     outputPath,
     textureScale,
@@ -156,7 +156,7 @@ export async function cancelFsltlConversion(): Promise<void> {
   if (!isTauri()) {
     throw new Error('[fsltlApi] cancelFsltlConversion not available in browser mode')
   }
-  return invoke<void>('cancel_fsltl_conversion')
+  return invokeCommand<void>('cancel_fsltl_conversion')
 }
 
 /**
@@ -169,7 +169,7 @@ export async function readConversionProgress(progressFile: string): Promise<Conv
   if (!isTauri()) {
     return { status: 'idle', total: 0, completed: 0, current: null, errors: [] }
   }
-  return invoke<ConversionProgress>('read_conversion_progress', { progressFile })
+  return invokeCommand<ConversionProgress>('read_conversion_progress', { progressFile })
 }
 
 /**
@@ -186,7 +186,7 @@ export async function checkFsltlModelExists(
   if (!isTauri()) {
     return false
   }
-  return invoke<boolean>('check_fsltl_model_exists', { outputPath, modelName })
+  return invokeCommand<boolean>('check_fsltl_model_exists', { outputPath, modelName })
 }
 
 /**
@@ -198,7 +198,7 @@ export async function deleteFile(path: string): Promise<void> {
   if (!isTauri()) {
     throw new Error('[fsltlApi] deleteFile not available in browser mode')
   }
-  return invoke<void>('delete_file', { path })
+  return invokeCommand<void>('delete_file', { path })
 }
 
 /**
@@ -212,7 +212,7 @@ export async function readVmrFile(sourcePath: string): Promise<string> {
     throw new Error('[fsltlApi] readVmrFile not available in browser mode')
   }
   const vmrPath = `${sourcePath}\\FSLTL_Rules.vmr`
-  return invoke<string>('read_text_file', { path: vmrPath })
+  return invokeCommand<string>('read_text_file', { path: vmrPath })
 }
 
 /**
@@ -228,7 +228,7 @@ export async function readVmrFromOutput(outputPath: string): Promise<string | nu
   }
   try {
     const vmrPath = `${outputPath}\\FSLTL_Rules.vmr`
-    return await invoke<string>('read_text_file', { path: vmrPath })
+    return await invokeCommand<string>('read_text_file', { path: vmrPath })
   } catch {
     return null
   }
@@ -246,13 +246,31 @@ export interface ScannedFSLTLModel {
   fileSize: number
 }
 
+/**
+ * Scan progress emitted as fsltl-scan-progress after each aircraft type directory
+ */
+export interface FsltlScanProgress {
+  outputPath: string
+  /** Type directories scanned so far */
+  scannedTypes: number
+  totalTypes: number
+  /** Models found so far */
+  modelsFound: number
+  /** Models found in the type directory that just finished */
+  models: ScannedFSLTLModel[]
+}
+
 /**
  * Scan an FSLTL output directory for existing converted models
  * @param outputPath - Path to scan for model.glb files
+ * @param onProgress - Called as type directories finish (Tauri only)
  * @returns Array of scanned model info
  * In browser mode, fetches from HTTP API
  */
-export async function scanFsltlModels(outputPath: string): Promise<ScannedFSLTLModel[]> {
+export async function scanFsltlModels(
+  outputPath: string,
+  onProgress?: (progress: FsltlScanProgress) => void
+): Promise<ScannedFSLTLModel[]> {
   if (!isTauri()) {
     // In browser mode, fetch from server API
     const response = await fetch('/api/fsltl/models')
@@ -262,5 +280,87 @@ export async function scanFsltlModels(outputPath: string): Promise<ScannedFSLTLM
     }
     return response.json()
   }
-  return invoke<ScannedFSLTLModel[]>('scan_fsltl_models', { outputPath })
+  if (!onProgress) {
+    return invokeCommand<ScannedFSLTLModel[]>('scan_fsltl_models', { outputPath })
+  }
+  const { listen } = await import('@tauri-apps/api/event')
+  const unlisten = await listen<FsltlScanProgress>('fsltl-scan-progress', (event) => {
+    if (event.payload.outputPath === outputPath) onProgress(event.payload)
+  })
+  try {
+    return await invokeCommand<ScannedFSLTLModel[]>('scan_fsltl_models', { outputPath })
+  } finally {
+    unlisten()
+  }
+}
+
+/**
+ * A converted FSLTL model and how often it was handed out (see model_usage.rs)
+ */
+export interface ModelUsageEntry {
+  modelName: string
+  aircraftType: string
+  /** null for the base livery */
+  airlineCode: string | null
+  fileSize: number
+  requests: number
+  /** App runs the model was used in */
+  sessions: number
+  /** Unix ms; null if never used since tracking started */
+  lastUsed: number | null
+}
+
+/**
+ * An airline livery traffic asked for that isn't converted
+ */
+export interface WantedLivery {
+  modelName: string
+  aircraftType: string
+  airlineCode: string
+  requests: number
+  sessions: number
+  lastUsed: number
+  /** The FSLTL source has this livery, so converting it would fill the gap */
+  inSource: boolean
+}
+
+/**
+ * Usage of the converted FSLTL models
+ */
+export interface ModelUsageReport {
+  /** When tracking started (Unix ms) */
+  trackingSince: number
+  convertedModels: number
+  usedModels: number
+  totalBytes: number
+  /** Size of the models never used (what pruning would free) */
+  unusedBytes: number
+  /** Never-used models first (largest first), then least used */
+  models: ModelUsageEntry[]
+  /** Missing liveries, most wanted first */
+  wanted: WantedLivery[]
+}
+
+/**
+ * Get the usage report for converted FSLTL models: never-used models to prune
+ * and missing liveries worth converting
+ */
+export async function getModelUsageReport(): Promise<ModelUsageReport> {
+  if (isTauri()) {
+    return invokeCommand<ModelUsageReport>('get_model_usage_report')
+  }
+  const response = await fetch('/api/match/usage')
+  if (!response.ok) throw await responseError(response, 'Failed to get model usage')
+  return response.json()
+}
+
+/**
+ * Forget all model usage counts and start tracking again
+ */
+export async function resetModelUsage(): Promise<void> {
+  if (isTauri()) {
+    return invokeCommand('reset_model_usage')
+  }
+  const response = await fetch('/api/match/usage', { method: 'DELETE' })
+  if (!response.ok) throw await responseError(response, 'Failed to reset model usage')
 }
@@ -0,0 +1,38 @@
+import { create } from 'zustand'
+import type { CoordinationMessage } from '../types/coordination'
+
+/** Messages kept on this display (matches the host's history) */
+const MAX_MESSAGES = 200
+
+interface CoordinationState {
+  // Messages from every display, oldest first
+  messages: CoordinationMessage[]
+  // Messages received while the panel was closed
+  unreadCount: number
+  isOpen: boolean
+
+  // Actions
+  /** Add messages not seen yet, returning them */
+  addMessages: (messages: CoordinationMessage[], markUnread: boolean) => CoordinationMessage[]
+  setOpen: (isOpen: boolean) => void
+}
+
+export const useCoordinationStore = create<CoordinationState>((set, get) => ({
+  messages: [],
+  unreadCount: 0,
+  isOpen: false,
+
+  addMessages: (messages, markUnread) => {
+    // Keyed by id and send time (ids restart with the host)
+    const known = new Set(get().messages.map((message) => `${message.id}@${message.sentAt}`))
+    const added = messages.filter((message) => !known.has(`${message.id}@${message.sentAt}`))
+    if (added.length === 0) return added
+    set((state) => ({
+      messages: [...state.messages, ...added].slice(-MAX_MESSAGES),
+      unreadCount: markUnread && !state.isOpen ? state.unreadCount + added.length : state.unreadCount
+    }))
+    return added
+  },
+
+  setOpen: (isOpen) => set({ isOpen, unreadCount: isOpen ? 0 : get().unreadCount })
+}))
@@ -0,0 +1,16 @@
+import { create } from 'zustand'
+import type { DrawingBoard } from '../types/drawings'
+
+interface DrawingState {
+  // Shared drawings in the host's active session (null until loaded)
+  board: DrawingBoard | null
+
+  // Actions
+  setBoard: (board: DrawingBoard) => void
+}
+
+export const useDrawingStore = create<DrawingState>((set) => ({
+  board: null,
+
+  setBoard: (board) => set({ board })
+}))
@@ -17,8 +17,8 @@
  */
 
 import { create } from 'zustand'
-import type { GlobalSettings, GlobalViewportSettings, GlobalDisplaySettings, FSLTLTextureScale, DataSourceType, DatablockMode, DatablockDirection } from '@/types'
-import { DEFAULT_GLOBAL_SETTINGS, DEFAULT_GLOBAL_DISPLAY_SETTINGS } from '@/types'
+import type { GlobalSettings, GlobalViewportSettings, GlobalDisplaySettings, GlobalWeatherSettings, GlobalCelestialSettings, GlobalClockSettings, GlobalReplaySettings, GlobalDiagnosticsSettings, GlobalPowerSettings, GlobalAutostartSettings, GlobalContentPackSettings, GlobalHotkeySettings, GlobalGamepadSettings, GlobalTileCacheSettings, GlobalMapProviderSettings, GlobalMidiSettings, GlobalUnitSettings, GlobalAtcNotificationSettings, GlobalAudioRelaySettings, GlobalWeatherAlertSettings, GlobalViewPresetSettings, GlobalFederationSettings, GlobalUdpOutputSettings, GlobalAlertSoundSettings, GlobalServerScheduleSettings, GlobalEventScheduleSettings, GlobalLodSettings, GlobalDatablockLayout, FSLTLTextureScale, DataSourceType, DatablockMode, DatablockDirection } from '@/types'
+import { DEFAULT_GLOBAL_SETTINGS, DEFAULT_GLOBAL_DISPLAY_SETTINGS, DEFAULT_GLOBAL_WEATHER_SETTINGS, DEFAULT_GLOBAL_CELESTIAL_SETTINGS, DEFAULT_GLOBAL_CLOCK_SETTINGS, DEFAULT_GLOBAL_REPLAY_SETTINGS, DEFAULT_GLOBAL_DIAGNOSTICS_SETTINGS, DEFAULT_GLOBAL_POWER_SETTINGS, DEFAULT_GLOBAL_AUTOSTART_SETTINGS, DEFAULT_GLOBAL_UPDATE_SETTINGS, DEFAULT_GLOBAL_CONTENT_PACK_SETTINGS, DEFAULT_GLOBAL_HOTKEY_SETTINGS, DEFAULT_GLOBAL_GAMEPAD_SETTINGS, DEFAULT_GLOBAL_TILE_CACHE_SETTINGS, DEFAULT_GLOBAL_MAP_PROVIDER_SETTINGS, DEFAULT_GLOBAL_MIDI_SETTINGS, DEFAULT_GLOBAL_UNIT_SETTINGS, DEFAULT_GLOBAL_ATC_NOTIFICATION_SETTINGS, DEFAULT_GLOBAL_AUDIO_RELAY_SETTINGS, DEFAULT_GLOBAL_WEATHER_ALERT_SETTINGS, DEFAULT_GLOBAL_VIEW_PRESET_SETTINGS, DEFAULT_GLOBAL_FEDERATION_SETTINGS, DEFAULT_GLOBAL_UDP_OUTPUT_SETTINGS, DEFAULT_GLOBAL_ALERT_SOUND_SETTINGS, DEFAULT_GLOBAL_SERVER_SCHEDULE_SETTINGS, DEFAULT_GLOBAL_EVENT_SCHEDULE_SETTINGS, DEFAULT_GLOBAL_LOD_SETTINGS, DEFAULT_GLOBAL_DATABLOCK_LAYOUT } from '@/types'
 import { globalSettingsApi, isTauri } from '@/utils/tauriApi'
 
 // Key used to track if migration from localStorage has been done
@@ -115,10 +115,6 @@ function migrateDisplaySettings(): Partial<GlobalDisplaySettings> | null {
     let hasMigration = false
 
     // Migrate display-related aircraft settings
-    if (aircraft.leaderDistance !== undefined) {
-      migrated.leaderDistance = aircraft.leaderDistance
-      hasMigration = true
-    }
     if (aircraft.defaultDatablockDirection !== undefined) {
       migrated.defaultDatablockDirection = aircraft.defaultDatablockDirection
       hasMigration = true
@@ -198,6 +194,81 @@ interface GlobalSettingsState extends GlobalSettings {
   /** Update display settings (datablocks, labels, filtering - synced across devices) */
   updateDisplay: (updates: Partial<GlobalDisplaySettings>) => Promise<void>
 
+  /** Update weather feature settings (lightning feed) */
+  updateWeather: (updates: Partial<GlobalWeatherSettings>) => Promise<void>
+
+  /** Update sky rendering settings (moon, stars, sun glare - synced across devices) */
+  updateCelestial: (updates: Partial<GlobalCelestialSettings>) => Promise<void>
+
+  /** Update the simulated clock (broadcast by the host to all clients) */
+  updateClock: (updates: Partial<GlobalClockSettings>) => Promise<void>
+
+  /** Update the host's instant replay buffer length */
+  updateReplay: (updates: Partial<GlobalReplaySettings>) => Promise<void>
+
+  /** Update the host log level (applied immediately) */
+  updateDiagnostics: (updates: Partial<GlobalDiagnosticsSettings>) => Promise<void>
+
+  /** Update when the host keeps the display awake (applied immediately) */
+  updatePower: (updates: Partial<GlobalPowerSettings>) => Promise<void>
+
+  /** Update whether the desktop app starts at login (registered with the OS by the host) */
+  updateAutostart: (updates: Partial<GlobalAutostartSettings>) => Promise<void>
+
+  /** Update content pack index URLs and startup check (opt in with appApi.setContentPackEnabled) */
+  updateContentPacks: (updates: Partial<GlobalContentPackSettings>) => Promise<void>
+
+  /** Update the system-wide hotkeys (re-registered by the host immediately) */
+  updateHotkeys: (updates: Partial<GlobalHotkeySettings>) => Promise<void>
+
+  /** Update gamepad camera input (applied by the host immediately) */
+  updateGamepad: (updates: Partial<GlobalGamepadSettings>) => Promise<void>
+
+  /** Update the offline terrain and imagery cache */
+  updateTileCache: (updates: Partial<GlobalTileCacheSettings>) => Promise<void>
+
+  /** Update the terrain and imagery providers */
+  updateMapProviders: (updates: Partial<GlobalMapProviderSettings>) => Promise<void>
+
+  /** Update MIDI controller input (applied by the host immediately) */
+  updateMidi: (updates: Partial<GlobalMidiSettings>) => Promise<void>
+
+  /** Update display units and unit profiles */
+  updateUnits: (updates: Partial<GlobalUnitSettings>) => Promise<void>
+
+  /** Update controller-online notifications */
+  updateAtcNotifications: (updates: Partial<GlobalAtcNotificationSettings>) => Promise<void>
+
+  /** Update the relayed tower audio streams */
+  updateAudio: (updates: Partial<GlobalAudioRelaySettings>) => Promise<void>
+
+  /** Update weather and ATIS notification settings */
+  updateWeatherAlerts: (updates: Partial<GlobalWeatherAlertSettings>) => Promise<void>
+
+  /** Update the datablock layout */
+  updateDatablock: (updates: Partial<GlobalDatablockLayout>) => Promise<void>
+
+  /** Update runway configuration view presets */
+  updateViewPresets: (updates: Partial<GlobalViewPresetSettings>) => Promise<void>
+
+  /** Update multi-host federation settings */
+  updateFederation: (updates: Partial<GlobalFederationSettings>) => Promise<void>
+
+  /** Update UDP JSON output settings */
+  updateUdpOutput: (updates: Partial<GlobalUdpOutputSettings>) => Promise<void>
+
+  /** Update aural alert sound settings */
+  updateAlertSounds: (updates: Partial<GlobalAlertSoundSettings>) => Promise<void>
+
+  /** Update scheduled server windows */
+  updateServerSchedule: (updates: Partial<GlobalServerScheduleSettings>) => Promise<void>
+
+  /** Update VATSIM event reminder settings */
+  updateEventSchedule: (updates: Partial<GlobalEventScheduleSettings>) => Promise<void>
+
+  /** Update aircraft level of detail budgets */
+  updateLod: (updates: Partial<GlobalLodSettings>) => Promise<void>
+
   /** Reset to default settings */
   resetToDefaults: () => Promise<void>
 
@@ -260,7 +331,33 @@ export const useGlobalSettingsStore = create<GlobalSettingsState>()((set, get) =
           airportConfigs: settings.viewports?.airportConfigs ?? DEFAULT_GLOBAL_SETTINGS.viewports.airportConfigs,
           orbitSettings: settings.viewports?.orbitSettings ?? DEFAULT_GLOBAL_SETTINGS.viewports.orbitSettings
         },
-        display: { ...DEFAULT_GLOBAL_DISPLAY_SETTINGS, ...settings.display }
+        display: { ...DEFAULT_GLOBAL_DISPLAY_SETTINGS, ...settings.display },
+        weather: { ...DEFAULT_GLOBAL_WEATHER_SETTINGS, ...settings.weather },
+        celestial: { ...DEFAULT_GLOBAL_CELESTIAL_SETTINGS, ...settings.celestial },
+        clock: { ...DEFAULT_GLOBAL_CLOCK_SETTINGS, ...settings.clock },
+        replay: { ...DEFAULT_GLOBAL_REPLAY_SETTINGS, ...settings.replay },
+        diagnostics: { ...DEFAULT_GLOBAL_DIAGNOSTICS_SETTINGS, ...settings.diagnostics },
+        power: { ...DEFAULT_GLOBAL_POWER_SETTINGS, ...settings.power },
+        autostart: { ...DEFAULT_GLOBAL_AUTOSTART_SETTINGS, ...settings.autostart },
+        update: { ...DEFAULT_GLOBAL_UPDATE_SETTINGS, ...settings.update },
+        contentPacks: { ...DEFAULT_GLOBAL_CONTENT_PACK_SETTINGS, ...settings.contentPacks },
+        hotkeys: { ...DEFAULT_GLOBAL_HOTKEY_SETTINGS, ...settings.hotkeys },
+        gamepad: { ...DEFAULT_GLOBAL_GAMEPAD_SETTINGS, ...settings.gamepad },
+        tileCache: { ...DEFAULT_GLOBAL_TILE_CACHE_SETTINGS, ...settings.tileCache },
+        mapProviders: { ...DEFAULT_GLOBAL_MAP_PROVIDER_SETTINGS, ...settings.mapProviders },
+        midi: { ...DEFAULT_GLOBAL_MIDI_SETTINGS, ...settings.midi },
+        units: { ...DEFAULT_GLOBAL_UNIT_SETTINGS, ...settings.units },
+        atcNotifications: { ...DEFAULT_GLOBAL_ATC_NOTIFICATION_SETTINGS, ...settings.atcNotifications },
+        audio: { ...DEFAULT_GLOBAL_AUDIO_RELAY_SETTINGS, ...settings.audio },
+        weatherAlerts: { ...DEFAULT_GLOBAL_WEATHER_ALERT_SETTINGS, ...settings.weatherAlerts },
+        datablock: { ...DEFAULT_GLOBAL_DATABLOCK_LAYOUT, ...settings.datablock },
+        viewPresets: { ...DEFAULT_GLOBAL_VIEW_PRESET_SETTINGS, ...settings.viewPresets },
+        federation: { ...DEFAULT_GLOBAL_FEDERATION_SETTINGS, ...settings.federation },
+        udpOutput: { ...DEFAULT_GLOBAL_UDP_OUTPUT_SETTINGS, ...settings.udpOutput },
+        alertSounds: { ...DEFAULT_GLOBAL_ALERT_SOUND_SETTINGS, ...settings.alertSounds },
+        serverSchedule: { ...DEFAULT_GLOBAL_SERVER_SCHEDULE_SETTINGS, ...settings.serverSchedule },
+        eventSchedule: { ...DEFAULT_GLOBAL_EVENT_SCHEDULE_SETTINGS, ...settings.eventSchedule },
+        lod: { ...DEFAULT_GLOBAL_LOD_SETTINGS, ...settings.lod }
       }
 
       // Check if we need to migrate from localStorage (one-time migration)
@@ -410,10 +507,6 @@ export const useGlobalSettingsStore = create<GlobalSettingsState>()((set, get) =
     const newDisplay: GlobalDisplaySettings = {
       ...state.display,
       ...updates,
-      // Validate leaderDistance (0.5-5)
-      leaderDistance: (updates.leaderDistance !== undefined
-        ? Math.max(0.5, Math.min(5, updates.leaderDistance))
-        : state.display.leaderDistance),
       // Validate defaultDatablockDirection (1-9, excluding 5)
       defaultDatablockDirection: (updates.defaultDatablockDirection !== undefined
         ? (updates.defaultDatablockDirection >= 1 && updates.defaultDatablockDirection <= 9
@@ -433,6 +526,181 @@ export const useGlobalSettingsStore = create<GlobalSettingsState>()((set, get) =
     await saveSettings(get().getSettings())
   },
 
+  updateWeather: async (updates: Partial<GlobalWeatherSettings>) => {
+    const state = get()
+    const newWeather: GlobalWeatherSettings = {
+      ...state.weather,
+      ...updates,
+      // Validate lightningRadiusNm (5-200)
+      lightningRadiusNm: updates.lightningRadiusNm !== undefined
+        ? Math.max(5, Math.min(200, updates.lightningRadiusNm))
+        : state.weather.lightningRadiusNm
+    }
+    set({ weather: newWeather })
+    await saveSettings(get().getSettings())
+  },
+
+  updateCelestial: async (updates: Partial<GlobalCelestialSettings>) => {
+    set({ celestial: { ...get().celestial, ...updates } })
+    await saveSettings(get().getSettings())
+  },
+
+  updateClock: async (updates: Partial<GlobalClockSettings>) => {
+    const newClock: GlobalClockSettings = { ...get().clock, ...updates }
+    // Fixed mode needs a time to freeze at
+    if (newClock.mode === 'fixed' && newClock.fixedTime === null) {
+      newClock.fixedTime = Date.now()
+    }
+    set({ clock: newClock })
+    await saveSettings(get().getSettings())
+  },
+
+  updateReplay: async (updates: Partial<GlobalReplaySettings>) => {
+    const newReplay: GlobalReplaySettings = { ...get().replay, ...updates }
+    newReplay.instantReplayMinutes = Math.max(1, Math.min(60, Math.round(newReplay.instantReplayMinutes)))
+    set({ replay: newReplay })
+    await saveSettings(get().getSettings())
+  },
+
+  updateDiagnostics: async (updates: Partial<GlobalDiagnosticsSettings>) => {
+    const newDiagnostics: GlobalDiagnosticsSettings = { ...get().diagnostics, ...updates }
+    newDiagnostics.logLevel = newDiagnostics.logLevel.trim() || 'info'
+    set({ diagnostics: newDiagnostics })
+    await saveSettings(get().getSettings())
+  },
+
+  updatePower: async (updates: Partial<GlobalPowerSettings>) => {
+    set({ power: { ...get().power, ...updates } })
+    await saveSettings(get().getSettings())
+  },
+
+  updateAutostart: async (updates: Partial<GlobalAutostartSettings>) => {
+    set({ autostart: { ...get().autostart, ...updates } })
+    await saveSettings(get().getSettings())
+  },
+
+  updateContentPacks: async (updates: Partial<GlobalContentPackSettings>) => {
+    set({ contentPacks: { ...get().contentPacks, ...updates } })
+    await saveSettings(get().getSettings())
+  },
+
+  updateHotkeys: async (updates: Partial<GlobalHotkeySettings>) => {
+    const newHotkeys: GlobalHotkeySettings = { ...get().hotkeys, ...updates }
+    newHotkeys.bindings = newHotkeys.bindings
+      .map((binding) => ({ ...binding, shortcut: binding.shortcut.trim() }))
+      .filter((binding) => binding.shortcut)
+    set({ hotkeys: newHotkeys })
+    await saveSettings(get().getSettings())
+  },
+
+  updateGamepad: async (updates: Partial<GlobalGamepadSettings>) => {
+    const newGamepad: GlobalGamepadSettings = { ...get().gamepad, ...updates }
+    newGamepad.deadzone = Math.max(0, Math.min(0.95, newGamepad.deadzone))
+    set({ gamepad: newGamepad })
+    await saveSettings(get().getSettings())
+  },
+
+  updateTileCache: async (updates: Partial<GlobalTileCacheSettings>) => {
+    const newTileCache: GlobalTileCacheSettings = { ...get().tileCache, ...updates }
+    newTileCache.maxSizeGb = Math.max(0.1, newTileCache.maxSizeGb)
+    newTileCache.radiusNm = Math.max(1, Math.min(50, newTileCache.radiusNm))
+    newTileCache.airports = [...new Set(newTileCache.airports.map((icao) => icao.trim().toUpperCase()).filter(Boolean))]
+    set({ tileCache: newTileCache })
+    await saveSettings(get().getSettings())
+  },
+
+  updateMapProviders: async (updates: Partial<GlobalMapProviderSettings>) => {
+    const newMapProviders: GlobalMapProviderSettings = { ...get().mapProviders, ...updates }
+    newMapProviders.customTerrainUrl = newMapProviders.customTerrainUrl.trim()
+    newMapProviders.customImageryUrl = newMapProviders.customImageryUrl.trim()
+    set({ mapProviders: newMapProviders })
+    await saveSettings(get().getSettings())
+  },
+
+  updateMidi: async (updates: Partial<GlobalMidiSettings>) => {
+    const newMidi: GlobalMidiSettings = { ...get().midi, ...updates }
+    newMidi.port = newMidi.port?.trim() || null
+    set({ midi: newMidi })
+    await saveSettings(get().getSettings())
+  },
+
+  updateUnits: async (updates: Partial<GlobalUnitSettings>) => {
+    set({ units: { ...get().units, ...updates } })
+    await saveSettings(get().getSettings())
+  },
+
+  updateAtcNotifications: async (updates: Partial<GlobalAtcNotificationSettings>) => {
+    set({ atcNotifications: { ...get().atcNotifications, ...updates } })
+    await saveSettings(get().getSettings())
+  },
+
+  updateAudio: async (updates: Partial<GlobalAudioRelaySettings>) => {
+    set({ audio: { ...get().audio, ...updates } })
+    await saveSettings(get().getSettings())
+  },
+
+  updateWeatherAlerts: async (updates: Partial<GlobalWeatherAlertSettings>) => {
+    set({ weatherAlerts: { ...get().weatherAlerts, ...updates } })
+    await saveSettings(get().getSettings())
+  },
+
+  updateDatablock: async (updates: Partial<GlobalDatablockLayout>) => {
+    const state = get()
+    const newDatablock: GlobalDatablockLayout = {
+      ...state.datablock,
+      ...updates,
+      // Validate lines (1-4 lines of up to 6 fields)
+      lines: updates.lines !== undefined && updates.lines.length >= 1 && updates.lines.length <= 4
+        ? updates.lines.map((line) => line.slice(0, 6))
+        : state.datablock.lines,
+      // Validate leaderLength (0.5-5)
+      leaderLength: updates.leaderLength !== undefined
+        ? Math.max(0.5, Math.min(5, updates.leaderLength))
+        : state.datablock.leaderLength,
+      // Validate fontScale (0.5-2)
+      fontScale: updates.fontScale !== undefined
+        ? Math.max(0.5, Math.min(2, updates.fontScale))
+        : state.datablock.fontScale
+    }
+    set({ datablock: newDatablock })
+    await saveSettings(get().getSettings())
+  },
+
+  updateViewPresets: async (updates: Partial<GlobalViewPresetSettings>) => {
+    set({ viewPresets: { ...get().viewPresets, ...updates } })
+    await saveSettings(get().getSettings())
+  },
+
+  updateFederation: async (updates: Partial<GlobalFederationSettings>) => {
+    set({ federation: { ...get().federation, ...updates } })
+    await saveSettings(get().getSettings())
+  },
+
+  updateUdpOutput: async (updates: Partial<GlobalUdpOutputSettings>) => {
+    set({ udpOutput: { ...get().udpOutput, ...updates } })
+    await saveSettings(get().getSettings())
+  },
+
+  updateAlertSounds: async (updates: Partial<GlobalAlertSoundSettings>) => {
+    set({ alertSounds: { ...get().alertSounds, ...updates } })
+    await saveSettings(get().getSettings())
+  },
+
+  updateServerSchedule: async (updates: Partial<GlobalServerScheduleSettings>) => {
+    set({ serverSchedule: { ...get().serverSchedule, ...updates } })
+    await saveSettings(get().getSettings())
+  },
+
+  updateEventSchedule: async (updates: Partial<GlobalEventScheduleSettings>) => {
+    set({ eventSchedule: { ...get().eventSchedule, ...updates } })
+    await saveSettings(get().getSettings())
+  },
+
+  updateLod: async (updates: Partial<GlobalLodSettings>) => {
+    set({ lod: { ...get().lod, ...updates } })
+    await saveSettings(get().getSettings())
+  },
+
   resetToDefaults: async () => {
     set({ ...DEFAULT_GLOBAL_SETTINGS })
     await saveSettings(DEFAULT_GLOBAL_SETTINGS)
@@ -447,7 +715,33 @@ export const useGlobalSettingsStore = create<GlobalSettingsState>()((set, get) =
       server: state.server,
       realtraffic: state.realtraffic,
       viewports: state.viewports,
-      display: state.display
+      display: state.display,
+      weather: state.weather,
+      celestial: state.celestial,
+      clock: state.clock,
+      replay: state.replay,
+      diagnostics: state.diagnostics,
+      power: state.power,
+      autostart: state.autostart,
+      update: state.update,
+      contentPacks: state.contentPacks,
+      hotkeys: state.hotkeys,
+      gamepad: state.gamepad,
+      tileCache: state.tileCache,
+      mapProviders: state.mapProviders,
+      midi: state.midi,
+      units: state.units,
+      atcNotifications: state.atcNotifications,
+      audio: state.audio,
+      weatherAlerts: state.weatherAlerts,
+      datablock: state.datablock,
+      viewPresets: state.viewPresets,
+      federation: state.federation,
+      udpOutput: state.udpOutput,
+      alertSounds: state.alertSounds,
+      serverSchedule: state.serverSchedule,
+      eventSchedule: state.eventSchedule,
+      lod: state.lod
     }
   },
 
@@ -469,7 +763,33 @@ export const useGlobalSettingsStore = create<GlobalSettingsState>()((set, get) =
           airportConfigs: settings.viewports?.airportConfigs ?? DEFAULT_GLOBAL_SETTINGS.viewports.airportConfigs,
           orbitSettings: settings.viewports?.orbitSettings ?? DEFAULT_GLOBAL_SETTINGS.viewports.orbitSettings
         },
-        display: { ...DEFAULT_GLOBAL_DISPLAY_SETTINGS, ...settings.display }
+        display: { ...DEFAULT_GLOBAL_DISPLAY_SETTINGS, ...settings.display },
+        weather: { ...DEFAULT_GLOBAL_WEATHER_SETTINGS, ...settings.weather },
+        celestial: { ...DEFAULT_GLOBAL_CELESTIAL_SETTINGS, ...settings.celestial },
+        clock: { ...DEFAULT_GLOBAL_CLOCK_SETTINGS, ...settings.clock },
+        replay: { ...DEFAULT_GLOBAL_REPLAY_SETTINGS, ...settings.replay },
+        diagnostics: { ...DEFAULT_GLOBAL_DIAGNOSTICS_SETTINGS, ...settings.diagnostics },
+        power: { ...DEFAULT_GLOBAL_POWER_SETTINGS, ...settings.power },
+        autostart: { ...DEFAULT_GLOBAL_AUTOSTART_SETTINGS, ...settings.autostart },
+        update: { ...DEFAULT_GLOBAL_UPDATE_SETTINGS, ...settings.update },
+        contentPacks: { ...DEFAULT_GLOBAL_CONTENT_PACK_SETTINGS, ...settings.contentPacks },
+        hotkeys: { ...DEFAULT_GLOBAL_HOTKEY_SETTINGS, ...settings.hotkeys },
+        gamepad: { ...DEFAULT_GLOBAL_GAMEPAD_SETTINGS, ...settings.gamepad },
+        tileCache: { ...DEFAULT_GLOBAL_TILE_CACHE_SETTINGS, ...settings.tileCache },
+        mapProviders: { ...DEFAULT_GLOBAL_MAP_PROVIDER_SETTINGS, ...settings.mapProviders },
+        midi: { ...DEFAULT_GLOBAL_MIDI_SETTINGS, ...settings.midi },
+        units: { ...DEFAULT_GLOBAL_UNIT_SETTINGS, ...settings.units },
+        atcNotifications: { ...DEFAULT_GLOBAL_ATC_NOTIFICATION_SETTINGS, ...settings.atcNotifications },
+        audio: { ...DEFAULT_GLOBAL_AUDIO_RELAY_SETTINGS, ...settings.audio },
+        weatherAlerts: { ...DEFAULT_GLOBAL_WEATHER_ALERT_SETTINGS, ...settings.weatherAlerts },
+        datablock: { ...DEFAULT_GLOBAL_DATABLOCK_LAYOUT, ...settings.datablock },
+        viewPresets: { ...DEFAULT_GLOBAL_VIEW_PRESET_SETTINGS, ...settings.viewPresets },
+        federation: { ...DEFAULT_GLOBAL_FEDERATION_SETTINGS, ...settings.federation },
+        udpOutput: { ...DEFAULT_GLOBAL_UDP_OUTPUT_SETTINGS, ...settings.udpOutput },
+        alertSounds: { ...DEFAULT_GLOBAL_ALERT_SOUND_SETTINGS, ...settings.alertSounds },
+        serverSchedule: { ...DEFAULT_GLOBAL_SERVER_SCHEDULE_SETTINGS, ...settings.serverSchedule },
+        eventSchedule: { ...DEFAULT_GLOBAL_EVENT_SCHEDULE_SETTINGS, ...settings.eventSchedule },
+        lod: { ...DEFAULT_GLOBAL_LOD_SETTINGS, ...settings.lod }
       }
       set(mergedSettings)
       console.log('[GlobalSettings] Refreshed from server')
@@ -656,7 +976,7 @@ export function useDisplaySettings(): GlobalDisplaySettings {
  * More efficient than useDisplaySettings when only one value is needed
  */
 export function useLeaderDistance(): number {
-  return useGlobalSettingsStore((state) => state.display.leaderDistance)
+  return useGlobalSettingsStore((state) => state.datablock.leaderLength)
 }
 
 export function useDatablockMode(): DatablockMode {
@@ -0,0 +1,22 @@
+import { create } from 'zustand'
+import type { LabelLayout, LabelPlacement } from '../types/vatsim'
+
+interface LabelLayoutState {
+  // Airport the host laid the labels out for
+  icao: string | null
+  // Datablock placements from the host, by callsign
+  placements: Map<string, LabelPlacement>
+
+  // Actions
+  setLayout: (layout: LabelLayout) => void
+}
+
+export const useLabelLayoutStore = create<LabelLayoutState>((set) => ({
+  icao: null,
+  placements: new Map(),
+
+  setLayout: (layout) => set({
+    icao: layout.icao,
+    placements: new Map(Object.entries(layout.placements))
+  })
+}))
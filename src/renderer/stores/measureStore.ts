@@ -1,5 +1,6 @@
 import { create } from 'zustand'
 import * as Cesium from 'cesium'
+import type { DrawingPoint } from '../types/drawings'
 
 export interface MeasurePoint {
   cartesian: Cesium.Cartesian3
@@ -17,9 +18,21 @@ export interface Measurement {
   distanceMeters: number
 }
 
+/**
+ * What clicks on the terrain do while measuring: measure locally, or draw a
+ * line, label or polygon on every display (see drawings.rs)
+ */
+export type MeasureTool = 'measure' | 'line' | 'label' | 'polygon'
+
 interface MeasureState {
   // Measuring mode state
   isActive: boolean
+  tool: MeasureTool
+
+  // Shared drawing options
+  drawColor: string
+  labelText: string
+  polygonPoints: MeasurePoint[]  // Corners of the polygon being drawn
 
   // Completed measurements (can have multiple)
   measurements: Measurement[]
@@ -33,6 +46,11 @@ interface MeasureState {
   startMeasuring: () => void
   stopMeasuring: () => void
   toggleMeasuring: () => void
+  setTool: (tool: MeasureTool) => void
+  setDrawColor: (color: string) => void
+  setLabelText: (text: string) => void
+  addPolygonPoint: (point: MeasurePoint) => void
+  clearPolygon: () => void
   setPendingPoint: (point: MeasurePoint) => void
   setPreviewPoint: (point: MeasurePoint | null) => void
   completeMeasurement: (point: MeasurePoint) => void
@@ -52,6 +70,13 @@ export function calculateGeodesicDistance(point1: MeasurePoint, point2: MeasureP
   return geodesic.surfaceDistance
 }
 
+/**
+ * Position of a picked point for a shared drawing
+ */
+export function toDrawingPoint(point: MeasurePoint): DrawingPoint {
+  return { latitude: point.cartographic.latitude, longitude: point.cartographic.longitude }
+}
+
 /**
  * Generate a unique ID for measurements
  */
@@ -62,6 +87,10 @@ function generateId(): string {
 export const useMeasureStore = create<MeasureState>((set, get) => ({
   // Initial state
   isActive: false,
+  tool: 'measure',
+  drawColor: '#ff4081',
+  labelText: '',
+  polygonPoints: [],
   measurements: [],
   pendingPoint: null,
   previewPoint: null,
@@ -81,7 +110,8 @@ export const useMeasureStore = create<MeasureState>((set, get) => ({
       isActive: false,
       pendingPoint: null,
       previewPoint: null,
-      previewDistance: null
+      previewDistance: null,
+      polygonPoints: []
     })
   },
 
@@ -94,6 +124,26 @@ export const useMeasureStore = create<MeasureState>((set, get) => ({
     }
   },
 
+  setTool: (tool: MeasureTool) => {
+    set({
+      tool,
+      pendingPoint: null,
+      previewPoint: null,
+      previewDistance: null,
+      polygonPoints: []
+    })
+  },
+
+  setDrawColor: (color: string) => set({ drawColor: color }),
+
+  setLabelText: (text: string) => set({ labelText: text }),
+
+  addPolygonPoint: (point: MeasurePoint) => {
+    set({ polygonPoints: [...get().polygonPoints, point] })
+  },
+
+  clearPolygon: () => set({ polygonPoints: [] }),
+
   setPendingPoint: (point: MeasurePoint) => {
     set({
       pendingPoint: point,
@@ -142,7 +192,8 @@ export const useMeasureStore = create<MeasureState>((set, get) => ({
     set({
       pendingPoint: null,
       previewPoint: null,
-      previewDistance: null
+      previewDistance: null,
+      polygonPoints: []
     })
   },
 
@@ -1,9 +1,18 @@
 import { create } from 'zustand'
-import type { VatsimSnapshot, PlaybackMode, PlaybackSpeed, ReplayExportData } from '../types/replay'
+import type {
+  VatsimSnapshot,
+  PlaybackMode,
+  PlaybackSpeed,
+  ReplayExportData,
+  ReplayServerControl,
+  ReplayServerStatus
+} from '../types/replay'
 import { serializeAircraftStates, deserializeAircraftStates } from '../types/replay'
 import type { AircraftState } from '../types/vatsim'
 import { useSettingsStore } from './settingsStore'
 import { useAirportStore } from './airportStore'
+import { replayApi } from '../utils/tauriApi'
+import { isApiError } from '../utils/apiError'
 import {
   SNAPSHOT_INTERVAL_MS,
   DEFAULT_PLAYBACK_SPEED,
@@ -40,6 +49,12 @@ interface ReplayState {
   // Imported replay
   /** Imported replay data (separate from live recording) */
   importedSnapshots: VatsimSnapshot[] | null
+
+  // Host replay
+  /** Host replay this display follows (null when playing locally) */
+  hostStatus: ReplayServerStatus | null
+  /** Host clock minus local clock (ms), to place the host's playback clock */
+  hostClockOffset: number
 }
 
 /**
@@ -84,6 +99,18 @@ interface ReplayActions {
   /** Clear imported replay data */
   clearImportedReplay: () => void
 
+  // Host replay
+  /** Play the host's loaded replay in sync with the host */
+  followHost: (snapshots: VatsimSnapshot[], status: ReplayServerStatus) => void
+  /** Apply a host replay status (play, pause, seek or speed change) */
+  setHostStatus: (status: ReplayServerStatus) => void
+  /** Set the measured host clock offset */
+  setHostClockOffset: (offset: number) => void
+  /** Stop following the host replay and return to live */
+  leaveHost: () => void
+  /** Move the playback position to the host's current position */
+  syncToHost: () => void
+
   // Getters
   /** Get the snapshots array for current mode (live or imported) */
   getActiveSnapshots: () => VatsimSnapshot[]
@@ -109,6 +136,8 @@ type ReplayStore = ReplayState & ReplayActions
  * - Supports playback at various speeds
  * - Handles import/export of replay data
  * - Maintains live recording even when viewing imported replays
+ * - Follows the host's replay clock so every display shows the same moment;
+ *   playback controls are then sent to the host instead of applied locally
  */
 export const useReplayStore = create<ReplayStore>((set, get) => ({
   // Initial state
@@ -122,6 +151,8 @@ export const useReplayStore = create<ReplayStore>((set, get) => ({
   playbackStartTime: 0,
   playbackStartIndex: 0,
   importedSnapshots: null,
+  hostStatus: null,
+  hostClockOffset: 0,
 
   // ========================================================================
   // RECORDING
@@ -163,6 +194,11 @@ export const useReplayStore = create<ReplayStore>((set, get) => ({
   // ========================================================================
 
   play: () => {
+    if (get().hostStatus) {
+      controlHost({ playing: true })
+      return
+    }
+
     const { playbackMode, getActiveSnapshots, currentIndex } = get()
     const snapshots = getActiveSnapshots()
 
@@ -182,10 +218,24 @@ export const useReplayStore = create<ReplayStore>((set, get) => ({
   },
 
   pause: () => {
+    if (get().hostStatus) {
+      controlHost({ playing: false })
+      return
+    }
+
     set({ isPlaying: false })
   },
 
   goLive: () => {
+    if (get().hostStatus) {
+      // Returns every display to live traffic
+      replayApi
+        .stop()
+        .then(() => get().leaveHost())
+        .catch((error) => console.error('[Replay] Failed to stop host replay:', error))
+      return
+    }
+
     set({
       playbackMode: 'live',
       isPlaying: false,
@@ -198,6 +248,12 @@ export const useReplayStore = create<ReplayStore>((set, get) => ({
     const snapshots = get().getActiveSnapshots()
     const clampedIndex = Math.max(0, Math.min(snapshots.length - 1, index))
 
+    if (get().hostStatus) {
+      const position = snapshots[clampedIndex]?.timestamp
+      if (position !== undefined) controlHost({ playing: false, position })
+      return
+    }
+
     set({
       currentIndex: clampedIndex,
       segmentProgress: 0,
@@ -221,6 +277,11 @@ export const useReplayStore = create<ReplayStore>((set, get) => ({
       ? Math.max(0, snapshots.length - 2)
       : Math.max(0, currentIndex - 1)
 
+    if (get().hostStatus) {
+      get().seekTo(newIndex)
+      return
+    }
+
     set({
       currentIndex: newIndex,
       segmentProgress: 0,
@@ -234,6 +295,11 @@ export const useReplayStore = create<ReplayStore>((set, get) => ({
     const snapshots = getActiveSnapshots()
     const newIndex = Math.min(snapshots.length - 1, currentIndex + 1)
 
+    if (get().hostStatus) {
+      get().seekTo(newIndex)
+      return
+    }
+
     set({
       currentIndex: newIndex,
       segmentProgress: 0,
@@ -243,6 +309,11 @@ export const useReplayStore = create<ReplayStore>((set, get) => ({
   },
 
   setPlaybackSpeed: (speed) => {
+    if (get().hostStatus) {
+      controlHost({ speed })
+      return
+    }
+
     // Reset timing when speed changes
     const { currentIndex } = get()
     set({
@@ -253,9 +324,10 @@ export const useReplayStore = create<ReplayStore>((set, get) => ({
   },
 
   updatePlayback: (deltaMs) => {
-    const { isPlaying, playbackSpeed, currentIndex, segmentProgress, getActiveSnapshots } = get()
+    const { isPlaying, playbackSpeed, currentIndex, segmentProgress, getActiveSnapshots, hostStatus } = get()
 
-    if (!isPlaying) return
+    // The host's clock drives playback (see useHostReplay)
+    if (!isPlaying || hostStatus) return
 
     const snapshots = getActiveSnapshots()
     if (snapshots.length < 2) return
@@ -364,7 +436,8 @@ export const useReplayStore = create<ReplayStore>((set, get) => ({
       playbackMode: 'imported',
       currentIndex: 0,
       segmentProgress: 0,
-      isPlaying: false
+      isPlaying: false,
+      hostStatus: null
     })
 
     console.log(`[Replay] Imported ${data.snapshots.length} snapshots from ${data.airport || 'unknown airport'}`)
@@ -379,8 +452,57 @@ export const useReplayStore = create<ReplayStore>((set, get) => ({
       playbackMode: playbackMode === 'imported' ? 'live' : playbackMode,
       currentIndex: 0,
       segmentProgress: 0,
-      isPlaying: false
+      isPlaying: false,
+      hostStatus: null
+    })
+  },
+
+  // ========================================================================
+  // HOST REPLAY
+  // ========================================================================
+
+  followHost: (snapshots, status) => {
+    if (snapshots.length === 0) return
+
+    set({
+      importedSnapshots: snapshots,
+      playbackMode: 'imported',
+      hostStatus: status,
+      isPlaying: status.playing,
+      playbackSpeed: status.speed
+    })
+    get().syncToHost()
+
+    console.log(`[Replay] Following host replay (${snapshots.length} snapshots)`)
+  },
+
+  setHostStatus: (status) => {
+    if (!get().hostStatus) return
+
+    set({
+      hostStatus: status,
+      isPlaying: status.playing,
+      playbackSpeed: status.speed
     })
+    get().syncToHost()
+  },
+
+  setHostClockOffset: (offset) => {
+    set({ hostClockOffset: offset })
+  },
+
+  leaveHost: () => {
+    if (!get().hostStatus) return
+    get().clearImportedReplay()
+  },
+
+  syncToHost: () => {
+    const { hostStatus, hostClockOffset, importedSnapshots } = get()
+    if (!hostStatus || !importedSnapshots) return
+
+    const position = hostPlaybackPosition(hostStatus, Date.now() + hostClockOffset)
+    const { index, progress } = snapshotPositionAt(importedSnapshots, position)
+    set({ currentIndex: index, segmentProgress: progress })
   },
 
   // ========================================================================
@@ -424,6 +546,59 @@ export const useReplayStore = create<ReplayStore>((set, get) => ({
   }
 }))
 
+/**
+ * Send a playback change to the host and apply the resulting status right away
+ * (the `replay-status` broadcast brings the other displays along)
+ */
+function controlHost(control: ReplayServerControl): void {
+  replayApi
+    .control(control)
+    .then((status) => useReplayStore.getState().setHostStatus(status))
+    .catch((error) => {
+      // The host stopped the replay before the status reached this display
+      if (isApiError(error, 'conflict')) {
+        useReplayStore.getState().leaveHost()
+        return
+      }
+      console.error('[Replay] Host replay control failed:', error)
+    })
+}
+
+/**
+ * Host playback position (recorded Unix ms) at a host time, extrapolated from
+ * the last status while playing
+ */
+export function hostPlaybackPosition(status: ReplayServerStatus, hostNow: number): number {
+  if (!status.playing) return status.position
+  const elapsed = Math.max(0, hostNow - status.realTime)
+  return Math.min(status.endTime, status.position + elapsed * status.speed)
+}
+
+/**
+ * Snapshot index and progress towards the next snapshot at a recorded time
+ */
+export function snapshotPositionAt(
+  snapshots: VatsimSnapshot[],
+  position: number
+): { index: number; progress: number } {
+  // Last snapshot at or before the position (binary search, runs every frame)
+  let low = 0
+  let high = snapshots.length - 1
+  while (low < high) {
+    const mid = Math.ceil((low + high) / 2)
+    if (snapshots[mid].timestamp <= position) low = mid
+    else high = mid - 1
+  }
+  const index = low
+  const current = snapshots[index]
+  const next = snapshots[index + 1]
+  if (!current || !next || position <= current.timestamp) {
+    return { index, progress: 0 }
+  }
+  const progress = (position - current.timestamp) / (next.timestamp - current.timestamp)
+  return { index, progress: Math.min(1, progress) }
+}
+
 /**
  * Helper to convert snapshot to injectable state
  * Returns Map that can be used for aircraft state
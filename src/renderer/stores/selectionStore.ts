@@ -0,0 +1,16 @@
+import { create } from 'zustand'
+import type { SharedSelection } from '../types/camera'
+
+interface SelectionState {
+  // Aircraft selected across displays (null while sync is off or nothing is selected)
+  selection: SharedSelection | null
+
+  // Actions
+  setSelection: (selection: SharedSelection | null) => void
+}
+
+export const useSelectionStore = create<SelectionState>((set) => ({
+  selection: null,
+
+  setSelection: (selection) => set({ selection: selection?.callsign ? selection : null })
+}))
@@ -439,7 +439,7 @@ export const useSettingsStore = create<SettingsStoreWithPresets>()(
     }),
     {
       name: 'settings-store',
-      version: 28, // Added advanced settings (interpolation debug logs)
+      version: 30, // Added shared aircraft selection
       migrate: (persistedState: unknown, version: number) => {
         // eslint-disable-next-line @typescript-eslint/no-explicit-any
         let state: any = persistedState
@@ -678,6 +678,24 @@ export const useSettingsStore = create<SettingsStoreWithPresets>()(
           }
         }
 
+        // Migrate v28 to v29: add 3D building source
+        if (version < 29) {
+          console.log('[Settings] Migrating v28 to v29: adding building source')
+          state = {
+            ...state,
+            cesium: { ...DEFAULT_SETTINGS.cesium, ...state.cesium }
+          }
+        }
+
+        // Migrate v29 to v30: add shared aircraft selection
+        if (version < 30) {
+          console.log('[Settings] Migrating v29 to v30: adding selection sync')
+          state = {
+            ...state,
+            ui: { ...DEFAULT_SETTINGS.ui, ...state.ui }
+          }
+        }
+
         // Repair step: ensure all settings groups have defaults filled in
         // This catches any settings that were missed by migrations
         const repaired = {
@@ -831,7 +849,9 @@ function migrateOldSettings(oldSettings: any): typeof DEFAULT_SETTINGS {
       askToContributePositions: oldSettings.askToContributePositions ?? DEFAULT_SETTINGS.ui.askToContributePositions,
       deviceOptimizationPromptDismissed: oldSettings.deviceOptimizationPromptDismissed ?? DEFAULT_SETTINGS.ui.deviceOptimizationPromptDismissed,
       aircraftPanelWidth: oldSettings.aircraftPanelWidth ?? DEFAULT_SETTINGS.ui.aircraftPanelWidth,
-      aircraftPanelHeight: oldSettings.aircraftPanelHeight ?? DEFAULT_SETTINGS.ui.aircraftPanelHeight
+      aircraftPanelHeight: oldSettings.aircraftPanelHeight ?? DEFAULT_SETTINGS.ui.aircraftPanelHeight,
+      syncSelection: DEFAULT_SETTINGS.ui.syncSelection,
+      selectionSourceName: DEFAULT_SETTINGS.ui.selectionSourceName
     },
     fsltl: {
       sourcePath: oldSettings.fsltl?.sourcePath ?? DEFAULT_SETTINGS.fsltl.sourcePath,
@@ -0,0 +1,24 @@
+import { create } from 'zustand'
+import type { SquawkAlert, SquawkAlerts } from '../types/vatsim'
+
+interface SquawkState {
+  // Active squawk alerts from the host, most urgent first
+  alerts: SquawkAlert[]
+  // Callsigns squawking an emergency code (for flashing datablocks)
+  emergencyCallsigns: Set<string>
+
+  // Actions
+  setAlerts: (update: SquawkAlerts) => void
+}
+
+export const useSquawkStore = create<SquawkState>((set) => ({
+  alerts: [],
+  emergencyCallsigns: new Set(),
+
+  setAlerts: (update) => set({
+    alerts: update.alerts,
+    emergencyCallsigns: new Set(
+      update.alerts.filter((alert) => alert.priority === 'high').map((alert) => alert.callsign)
+    )
+  })
+}))
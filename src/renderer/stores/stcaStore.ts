@@ -0,0 +1,22 @@
+import { create } from 'zustand'
+import type { StcaAlert, StcaAlerts } from '../types/vatsim'
+
+interface StcaState {
+  // Active short-term conflict alerts from the host
+  alerts: StcaAlert[]
+  // Callsigns involved in any alert (for flashing datablocks)
+  conflictCallsigns: Set<string>
+
+  // Actions
+  setAlerts: (update: StcaAlerts) => void
+}
+
+export const useStcaStore = create<StcaState>((set) => ({
+  alerts: [],
+  conflictCallsigns: new Set(),
+
+  setAlerts: (update) => set({
+    alerts: update.alerts,
+    conflictCallsigns: new Set(update.alerts.flatMap((alert) => alert.callsigns))
+  })
+}))
@@ -1,4 +1,5 @@
 import { create } from 'zustand'
+import type { ReleaseNotes } from '../utils/tauriApi'
 
 export type UpdateStatus =
   | 'idle'
@@ -14,6 +15,9 @@ export interface UpdateInfo {
   currentVersion: string
   date: string | null
   body: string | null  // Release notes
+  channel: 'stable' | 'beta'
+  /** The user skipped this version (offered by "Check for Updates" only) */
+  skipped: boolean
 }
 
 export interface UpdateProgress {
@@ -26,11 +30,14 @@ interface UpdateStore {
   updateInfo: UpdateInfo | null
   progress: UpdateProgress | null
   error: string | null
+  /** Notes of every release between the running version and the update */
+  releaseNotes: ReleaseNotes[]
 
   setStatus: (status: UpdateStatus) => void
   setUpdateInfo: (info: UpdateInfo | null) => void
   setProgress: (progress: UpdateProgress | null) => void
   setError: (error: string | null) => void
+  setReleaseNotes: (notes: ReleaseNotes[]) => void
   reset: () => void
 }
 
@@ -39,10 +46,12 @@ export const useUpdateStore = create<UpdateStore>((set) => ({
   updateInfo: null,
   progress: null,
   error: null,
+  releaseNotes: [],
 
   setStatus: (status) => set({ status }),
   setUpdateInfo: (updateInfo) => set({ updateInfo }),
   setProgress: (progress) => set({ progress }),
   setError: (error) => set({ error, status: error ? 'error' : 'idle' }),
-  reset: () => set({ status: 'idle', updateInfo: null, progress: null, error: null })
+  setReleaseNotes: (releaseNotes) => set({ releaseNotes }),
+  reset: () => set({ status: 'idle', updateInfo: null, progress: null, error: null, releaseNotes: [] })
 }))
@@ -73,7 +73,8 @@ export const toGlobalCameraBookmark = (local: CameraBookmark): GlobalCameraBookm
   positionOffsetY: local.positionOffsetY,
   positionOffsetZ: local.positionOffsetZ,
   viewMode: local.viewMode,
-  topdownAltitude: local.topdownAltitude
+  topdownAltitude: local.topdownAltitude,
+  ...(local.runways?.length ? { runways: local.runways } : {})
 })
 
 /**
@@ -162,7 +163,8 @@ export const fromGlobalCameraBookmark = (global: GlobalCameraBookmark): CameraBo
   positionOffsetY: global.positionOffsetY,
   positionOffsetZ: global.positionOffsetZ,
   viewMode: validateViewMode(global.viewMode),
-  topdownAltitude: global.topdownAltitude ?? TOPDOWN_ALTITUDE_DEFAULT
+  topdownAltitude: global.topdownAltitude ?? TOPDOWN_ALTITUDE_DEFAULT,
+  ...(Array.isArray(global.runways) && global.runways.length > 0 ? { runways: global.runways } : {})
 })
 
 /**
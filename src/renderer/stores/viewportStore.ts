@@ -6,6 +6,7 @@ import { useAirportStore } from './airportStore'
 import { useGlobalSettingsStore } from './globalSettingsStore'
 import { useDatablockPositionStore, type DatablockPosition } from './datablockPositionStore'
 import { modService } from '../services/ModService'
+import { windowLayoutApi } from '../utils/tauriApi'
 import {
   HEADING_DEFAULT,
   PITCH_DEFAULT,
@@ -135,6 +136,7 @@ interface ViewportStore {
   loadBookmark: (slot: number) => boolean  // Returns true if bookmark exists
   deleteBookmark: (slot: number) => void
   renameBookmark: (slot: number, name: string | undefined) => void
+  setBookmarkRunways: (slot: number, runways: string[]) => void  // Tag for a runway configuration
   getBookmarks: () => { [slot: number]: CameraBookmark } | undefined
 
   // Datablock position actions
@@ -1028,7 +1030,9 @@ export const useViewportStore = create<ViewportStore>()(
               positionOffsetX: cam.positionOffsetX,
               positionOffsetY: cam.positionOffsetY,
               positionOffsetZ: cam.positionOffsetZ,
-              topdownAltitude: cam.topdownAltitude
+              topdownAltitude: cam.topdownAltitude,
+              // Re-saving a slot keeps its runway configuration tag
+              runways: state.airportViewportConfigs[icao]?.bookmarks?.[slot]?.runways
             }
 
             const airportViewportConfigs = { ...state.airportViewportConfigs }
@@ -1073,6 +1077,11 @@ export const useViewportStore = create<ViewportStore>()(
               }))
             })
 
+            // Remember it for saved window layouts (desktop only)
+            windowLayoutApi.assignBookmark(bookmark.name ?? String(slot)).catch((error) => {
+              console.warn('[WindowLayout] Failed to assign bookmark:', error)
+            })
+
             return true
           },
 
@@ -1114,6 +1123,26 @@ export const useViewportStore = create<ViewportStore>()(
             set({ airportViewportConfigs })
           },
 
+          // Tag a bookmark with the runway ends it's for (empty list removes the tag)
+          setBookmarkRunways: (slot: number, runways: string[]) => {
+            if (slot < 0 || slot > 99) return
+
+            const state = get()
+            const icao = state.currentAirportIcao
+            if (!icao) return
+
+            const config = state.airportViewportConfigs[icao]
+            if (!config?.bookmarks?.[slot]) return
+
+            const tags = [...new Set(runways.map((r) => r.trim().toUpperCase()).filter(Boolean))]
+            const airportViewportConfigs = { ...state.airportViewportConfigs }
+            const newBookmarks = { ...config.bookmarks }
+            newBookmarks[slot] = { ...newBookmarks[slot], runways: tags.length > 0 ? tags : undefined }
+            airportViewportConfigs[icao] = { ...config, bookmarks: newBookmarks }
+
+            set({ airportViewportConfigs })
+          },
+
           // Get all bookmarks for current airport
           getBookmarks: () => {
             const state = get()
@@ -3,6 +3,7 @@ import type { VnasAircraft, VnasStatus, VnasEnvironment } from '../types/vnas'
 import type { AircraftState } from '../types/vatsim'
 import type { AircraftObservation, AircraftMetadata } from '../types/aircraft-timeline'
 import { isRemoteMode } from '../utils/remoteMode'
+import { invokeCommand } from '../utils/apiError'
 import { useAircraftTimelineStore } from './aircraftTimelineStore'
 import { SOURCE_DISPLAY_DELAYS } from '../constants/aircraft-timeline'
 
@@ -19,7 +20,7 @@ import { SOURCE_DISPLAY_DELAYS } from '../constants/aircraft-timeline'
  * 3. After callback, completeAuth() is called
  * 4. connect() establishes SignalR WebSocket
  * 5. subscribe() registers for TowerCabAircraft updates
- * 6. Aircraft updates arrive via handleAircraftUpdate()
+ * 6. Aircraft updates arrive batched (vnas-aircraft-batch) via handleBatchUpdate()
  */
 
 interface VnasStore {
@@ -80,8 +81,7 @@ export const useVnasStore = create<VnasStore>((set, get) => ({
     }
 
     try {
-      const { invoke } = await import('@tauri-apps/api/core')
-      const authUrl = await invoke<string>('vnas_start_auth', { environment })
+      const authUrl = await invokeCommand<string>('vnas_start_auth', { environment })
       return authUrl
     } catch (error) {
       const message = error instanceof Error ? error.message : String(error)
@@ -105,8 +105,7 @@ export const useVnasStore = create<VnasStore>((set, get) => ({
     }
 
     try {
-      const { invoke } = await import('@tauri-apps/api/core')
-      await invoke('vnas_complete_auth')
+      await invokeCommand('vnas_complete_auth')
     } catch (error) {
       const message = error instanceof Error ? error.message : String(error)
       set(state => ({
@@ -132,8 +131,7 @@ export const useVnasStore = create<VnasStore>((set, get) => ({
     console.log('[vNAS] Handling OAuth callback:', callbackUrl)
 
     try {
-      const { invoke } = await import('@tauri-apps/api/core')
-      await invoke('vnas_handle_oauth_callback', { callbackUrl })
+      await invokeCommand('vnas_handle_oauth_callback', { callbackUrl })
 
       // After successful OAuth, automatically connect
       console.log('[vNAS] OAuth callback handled, connecting...')
@@ -161,8 +159,7 @@ export const useVnasStore = create<VnasStore>((set, get) => ({
     }
 
     try {
-      const { invoke } = await import('@tauri-apps/api/core')
-      await invoke('vnas_connect')
+      await invokeCommand('vnas_connect')
     } catch (error) {
       const message = error instanceof Error ? error.message : String(error)
       set(state => ({
@@ -186,8 +183,7 @@ export const useVnasStore = create<VnasStore>((set, get) => ({
     }
 
     try {
-      const { invoke } = await import('@tauri-apps/api/core')
-      await invoke('vnas_subscribe', { facilityId })
+      await invokeCommand('vnas_subscribe', { facilityId })
       set(state => ({
         status: {
           ...state.status,
@@ -215,8 +211,7 @@ export const useVnasStore = create<VnasStore>((set, get) => ({
     }
 
     try {
-      const { invoke } = await import('@tauri-apps/api/core')
-      await invoke('vnas_disconnect')
+      await invokeCommand('vnas_disconnect')
       set({
         status: DEFAULT_STATUS,
         aircraftStates: new Map(),
@@ -253,7 +248,7 @@ export const useVnasStore = create<VnasStore>((set, get) => ({
       latitude: aircraft.lat,
       longitude: aircraft.lon,
       altitude: aircraft.altitudeTrue,
-      groundspeed: 0, // Calculated from position changes or not available
+      groundspeed: aircraft.groundspeedKt ?? 0,  // Derived by the backend from position history
       heading: aircraft.trueHeading,
       groundTrack: aircraft.trueGroundTrack, // Use for extrapolation if set
       transponder: '', // Not provided by vNAS
@@ -299,13 +294,13 @@ export const useVnasStore = create<VnasStore>((set, get) => ({
       longitude: aircraft.lon,
       altitude: aircraft.altitudeTrue,
       heading: aircraft.trueHeading,
-      groundspeed: 0,  // vNAS doesn't provide groundspeed directly
+      groundspeed: aircraft.groundspeedKt ?? 0,  // vNAS doesn't provide groundspeed; derived by the backend
       groundTrack: aircraft.trueGroundTrack ?? null,
       headingIsTrue: true,  // vNAS heading is always reliable (from simulator)
       // Extended ADS-B data (not available from vNAS)
       onGround: null,
       roll: null,
-      verticalRate: null,
+      verticalRate: aircraft.verticalRateFpm ?? null,  // Derived by the backend
       observedAt: now,  // vNAS data is real-time
       receivedAt: now,
       source: 'vnas',
@@ -341,7 +336,7 @@ export const useVnasStore = create<VnasStore>((set, get) => ({
         latitude: ac.lat,
         longitude: ac.lon,
         altitude: ac.altitudeTrue,
-        groundspeed: 0,
+        groundspeed: ac.groundspeedKt ?? 0,
         heading: ac.trueHeading,
         groundTrack: ac.trueGroundTrack, // Use for extrapolation if set
         transponder: '',
@@ -388,13 +383,13 @@ export const useVnasStore = create<VnasStore>((set, get) => ({
         longitude: ac.lon,
         altitude: ac.altitudeTrue,
         heading: ac.trueHeading,
-        groundspeed: 0,  // vNAS doesn't provide groundspeed directly
+        groundspeed: ac.groundspeedKt ?? 0,  // vNAS doesn't provide groundspeed; derived by the backend
         groundTrack: ac.trueGroundTrack ?? null,
         headingIsTrue: true,  // vNAS heading is always reliable (from simulator)
         // Extended ADS-B data (not available from vNAS)
         onGround: null,
         roll: null,
-        verticalRate: null,
+        verticalRate: ac.verticalRateFpm ?? null,  // Derived by the backend
         observedAt: now,  // vNAS data is real-time
         receivedAt: now,
         source: 'vnas',
@@ -426,8 +421,7 @@ export const useVnasStore = create<VnasStore>((set, get) => ({
     }
 
     try {
-      const { invoke } = await import('@tauri-apps/api/core')
-      const status = await invoke<VnasStatus>('vnas_get_status')
+      const status = await invokeCommand<VnasStatus>('vnas_get_status')
       set({ status })
       return status
     } catch (error) {
@@ -445,8 +439,7 @@ export const useVnasStore = create<VnasStore>((set, get) => ({
     }
 
     try {
-      const { invoke } = await import('@tauri-apps/api/core')
-      const available = await invoke<boolean>('vnas_is_available')
+      const available = await invokeCommand<boolean>('vnas_is_available')
       set(state => ({
         status: {
           ...state.status,
@@ -0,0 +1,23 @@
+import { create } from 'zustand'
+import type { WakeTimer, WakeTimers } from '../types/vatsim'
+
+interface WakeTimerState {
+  // Running departure wake timers from the host
+  timers: WakeTimer[]
+  // Local clock minus host clock (ms), to count down in local time
+  clockOffsetMs: number
+
+  // Actions
+  setTimers: (update: WakeTimers) => void
+}
+
+export const useWakeTimerStore = create<WakeTimerState>((set) => ({
+  timers: [],
+  clockOffsetMs: 0,
+
+  setTimers: (update) => set({
+    timers: update.timers,
+    // Updates are published right after the traffic poll they're based on
+    clockOffsetMs: update.updatedAt !== null ? Date.now() - update.updatedAt : 0
+  })
+}))
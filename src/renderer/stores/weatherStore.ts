@@ -1,7 +1,9 @@
 import { create } from 'zustand'
 import { metarService, type MetarData } from '../services/MetarService'
 import type { CloudLayer, PrecipitationState, WindState, DistancedMetar, InterpolatedWeather } from '../types'
+import type { WeatherScene } from '../types/weather'
 import { interpolateWeather } from '../utils/weatherInterpolation'
+import { weatherApi } from '../utils/tauriApi'
 import {
   WEATHER_REFRESH_INTERVAL,
   NEAREST_METAR_THROTTLE,
@@ -59,6 +61,8 @@ interface WeatherState {
   stopAutoRefresh: () => void
   clearWeather: () => void
   setUseInterpolation: (useInterpolation: boolean) => void
+  /** Apply host-derived scene parameters (e.g., historical weather during replay) */
+  applyScene: (scene: WeatherScene) => void
 
   // Debug overrides (for development)
   setDebugOverriding: (isOverriding: boolean) => void
@@ -482,6 +486,11 @@ export const useWeatherStore = create<WeatherState>((set, get) => ({
     }, WEATHER_REFRESH_INTERVAL)
 
     set({ refreshIntervalId: intervalId, useNearestMetar: false })
+
+    // Let the host keep this airport's METAR fresh for remote clients
+    weatherApi.setActiveAirport(icao).catch(error => {
+      console.warn('[Weather] Failed to set host weather airport:', error)
+    })
   },
 
   startNearestAutoRefresh: () => {
@@ -583,6 +592,37 @@ export const useWeatherStore = create<WeatherState>((set, get) => ({
     }
   },
 
+  applyScene: (scene: WeatherScene) => {
+    // When debug override is active, only update fog density
+    if (get().isDebugOverriding) {
+      set({ fogDensity: scene.fogDensity })
+      return
+    }
+
+    set({
+      fogDensity: scene.fogDensity,
+      cloudLayers: scene.cloudLayers.map(layer => ({
+        altitude: layer.altitudeM,
+        coverage: layer.coverage,
+        type: layer.cover
+      })),
+      precipitation: {
+        active: scene.precipitation.length > 0,
+        types: scene.precipitation,
+        visibilityFactor: scene.precipitationFactor,
+        hasThunderstorm: scene.thunderstorm
+      },
+      wind: scene.wind
+        ? {
+            direction: scene.wind.direction ?? 0,
+            speed: scene.wind.speedKt,
+            gustSpeed: scene.wind.gustKt,
+            isVariable: scene.wind.direction === null
+          }
+        : DEFAULT_WIND
+    })
+  },
+
   setPrecipitation: (precipitation: PrecipitationState) => {
     set({ precipitation })
   },
@@ -95,6 +95,103 @@ export interface AirportDatabase {
   [icao: string]: Airport
 }
 
+/**
+ * Runway end from the host's airport database
+ */
+export interface AirportDbRunwayEnd {
+  ident: string
+  latitude: number | null
+  longitude: number | null
+  headingTrue: number | null
+  elevationFt: number | null
+  displacedThresholdFt: number
+}
+
+/**
+ * Runway from the host's airport database
+ */
+export interface AirportDbRunway {
+  /** Combined designator (e.g., "09L/27R") */
+  ident: string
+  lowEnd: AirportDbRunwayEnd
+  highEnd: AirportDbRunwayEnd
+  lengthFt: number | null
+  widthFt: number | null
+  surface: string
+  lighted: boolean
+}
+
+/**
+ * ILS/localizer for a runway end (imported from X-Plane navdata)
+ */
+export interface AirportDbIls {
+  airport: string
+  runway: string
+  ident: string
+  frequencyMhz: number
+  /** Localizer course (degrees true) */
+  courseTrue: number
+  /** null for localizer-only approaches */
+  glideslopeAngle: number | null
+}
+
+/**
+ * Glidepath altitude at a distance from the threshold
+ */
+export interface GlidepathPoint {
+  distanceNm: number
+  latitude: number
+  longitude: number
+  /** Feet MSL */
+  altitudeFt: number
+}
+
+/**
+ * Approach path to a runway end (`/api/airports/{ICAO}/approaches`)
+ */
+export interface ApproachPath {
+  /** Runway end (e.g., "27R") */
+  runway: string
+  ils: AirportDbIls | null
+  /** Final approach course (degrees true): localizer course, else runway heading */
+  courseTrue: number
+  glideslopeAngle: number
+  thresholdLatitude: number
+  thresholdLongitude: number
+  thresholdElevationFt: number
+  /** Extended centerline, [longitude, latitude] pairs */
+  centerline: [number, number][]
+  /** Approach cone polygon (closed ring), [longitude, latitude] pairs */
+  cone: [number, number][]
+  /** Glidepath altitudes every nautical mile */
+  glidepath: GlidepathPoint[]
+}
+
+/**
+ * Airport from the host's airport database (`/api/airports/{ICAO}`)
+ */
+export interface AirportDbRecord {
+  icao: string
+  iata: string
+  name: string
+  city: string
+  state: string
+  country: string
+  elevationFt: number
+  latitude: number
+  longitude: number
+  tz: string
+  runways: AirportDbRunway[]
+}
+
+/**
+ * Airport search hit from the host (`/api/airports/search?q=`), best matches first
+ */
+export interface AirportSearchResult extends AirportDbRecord {
+  /** Relevance (higher is better) */
+  score: number
+}
+
 // Tower configuration for specific airports
 export interface TowerConfig {
   icao: string
@@ -205,3 +302,213 @@ export function getEstimatedTowerHeight(airport: Airport): number {
       return 35  // default height
   }
 }
+
+/**
+ * Video map imported from CRC for an airport
+ */
+export interface VideoMapInfo {
+  /** CRC video map id */
+  id: string
+  name: string
+  shortName: string | null
+  tags: string[]
+  /** Facility configuration that uses the map */
+  kind: 'asdex' | 'towerCab' | 'stars'
+  featureCount: number
+  /** GeoJSON URL (e.g., "/api/videomaps/KBOS/01GW...") */
+  url: string
+}
+
+/**
+ * Video maps imported from CRC for an airport (`/api/videomaps/{ICAO}`)
+ */
+export interface VideoMapIndex {
+  icao: string
+  /** CRC facility id (e.g., "BOS") */
+  facilityId: string
+  facilityName: string
+  /** ARTCC the facility belongs to (e.g., "ZBW") */
+  artcc: string
+  /** Import time (Unix ms) */
+  importedAt: number
+  maps: VideoMapInfo[]
+}
+
+/**
+ * Gate or stand with occupancy (`/api/airports/{ICAO}/gates`)
+ */
+export interface Gate {
+  /** Gate designator (e.g., "B5") */
+  id: string
+  name: string | null
+  latitude: number
+  longitude: number
+  source: 'imported' | 'osm'
+  /** ICAO airline designators using the gate (imported gates only) */
+  airlines: string[]
+  /** Largest wingspan the gate takes, meters (imported gates only) */
+  maxWingspanM: number | null
+  /** Callsign of the aircraft at the gate (active airport only) */
+  occupiedBy: string | null
+  /** When the aircraft was first seen at the gate (Unix ms) */
+  occupiedSince: number | null
+}
+
+/**
+ * Gate suggested for an arrival that hasn't parked yet
+ */
+export interface GateSuggestion {
+  callsign: string
+  gate: string
+  /** Why this gate: listed for the airline, used by it earlier, next to its other aircraft, or any free gate */
+  reason: 'airline' | 'history' | 'nearby' | 'free'
+}
+
+/**
+ * Predicted taxi route along the taxiway graph (departures to a runway, arrivals to a gate)
+ */
+export interface TaxiRoute {
+  callsign: string
+  /** Runway end a departure taxis to */
+  runway: string | null
+  /** Gate an arrival taxis to */
+  gate: string | null
+  /** Taxiways along the route, in order */
+  taxiways: string[]
+  /** Route from the aircraft to the destination, as [longitude, latitude] pairs */
+  path: [number, number][]
+  distanceM: number
+}
+
+/**
+ * Exit an arrival may turn off at after landing
+ */
+export interface TurnOff {
+  taxiway: string | null
+  /** [longitude, latitude] where it leaves the runway centerline */
+  position: [number, number]
+  /** Distance past the threshold (feet) */
+  distanceFt: number
+  highSpeed: boolean
+  /** Chance this is the exit taken (0-1) */
+  likelihood: number
+}
+
+/**
+ * Predicted touchdown point and rollout of an arrival on short final
+ */
+export interface LandingPrediction {
+  callsign: string
+  aircraftType: string | null
+  /** Runway end (e.g., "27R") */
+  runway: string
+  /** [longitude, latitude] of the touchdown point */
+  touchdown: [number, number]
+  /** Touchdown point past the threshold (feet) */
+  touchdownDistanceFt: number
+  secondsToTouchdown: number
+  touchdownSpeedKt: number
+  /** [longitude, latitude] where the aircraft is down to taxi speed */
+  rolloutEnd: [number, number]
+  rolloutDistanceFt: number
+  /** Exits past the touchdown point, closest first */
+  turnOffs: TurnOff[]
+}
+
+/**
+ * Gate occupancy change (`gate-occupancy-changed` event, `/api/gates/ws`)
+ */
+export interface GateOccupancyEvent {
+  icao: string
+  gate: string
+  occupiedBy: string | null
+  vacatedBy: string | null
+  /** Unix ms */
+  time: number
+}
+
+/**
+ * Parsed NOTAM
+ */
+export interface Notam {
+  /** NOTAM number (e.g., "01/234") */
+  number: string
+  text: string
+  kind: 'runwayClosure' | 'taxiwayClosure' | 'runway' | 'taxiway' | 'apron' | 'navaid' | 'other'
+  /** Runway ends the NOTAM applies to (e.g., ["04R", "22L"]) */
+  runways: string[]
+  taxiways: string[]
+  /** Effective from (Unix ms) */
+  startsAt: number | null
+  /** Effective until (Unix ms); null = permanent or unknown */
+  endsAt: number | null
+  endsEstimated: boolean
+}
+
+/**
+ * NOTAMs for an airport (`/api/notams/{ICAO}`)
+ */
+export interface AirportNotams {
+  icao: string
+  source: 'faa' | 'notamSearch'
+  /** Fetch time (Unix ms) */
+  fetchedAt: number
+  notams: Notam[]
+  /** Runway ends closed right now */
+  closedRunways: string[]
+  /** Taxiways closed right now */
+  closedTaxiways: string[]
+}
+
+/**
+ * Chart from the FAA d-TPP
+ */
+export interface Chart {
+  /** d-TPP chart code ("APD", "IAP", "DP", "STAR", ...) */
+  code: string
+  /** Readable chart type for grouping (e.g., "Approach") */
+  category: string
+  /** Chart name (e.g., "ILS OR LOC RWY 04R") */
+  name: string
+  pdfName: string
+  /** PDF URL (e.g., "/api/charts/KBOS/00058AD.PDF") */
+  url: string
+}
+
+/**
+ * FAA d-TPP charts for an airport (`/api/charts/{ICAO}`)
+ */
+export interface AirportCharts {
+  icao: string
+  /** d-TPP cycle (e.g., "2410") */
+  cycle: string
+  /** URL of the airport diagram, if the airport has one */
+  airportDiagram: string | null
+  charts: Chart[]
+}
+
+/**
+ * What an airport annotation marks
+ */
+export type AnnotationKind = 'hotspot' | 'ilsCriticalArea' | 'nonMovementBoundary'
+
+/**
+ * Facility-drawn airport annotation (`/api/annotations/{ICAO}`), also listed
+ * as the `{icao}-annotations` overlay
+ */
+export interface Annotation {
+  /** Unique per airport (e.g., "hs1") */
+  id: string
+  kind: AnnotationKind
+  /** Short label (e.g., "HS 1") */
+  name?: string | null
+  /** What to watch out for */
+  description?: string | null
+  /** GeoJSON geometry: hotspots are points or polygons, ILS critical areas polygons,
+   *  non-movement boundaries lines or polygons */
+  geometry: {
+    type: 'Point' | 'LineString' | 'Polygon'
+    /** [lon, lat] positions, nested per the geometry type */
+    coordinates: number[] | number[][] | number[][][]
+  }
+}
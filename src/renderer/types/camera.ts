@@ -199,4 +199,60 @@ export interface CameraBookmark {
   positionOffsetZ: number
   /** Top-down view altitude in meters above airport */
   topdownAltitude: number
+  /** Runway ends the view is for (e.g., ["04L", "04R"]); suggested when the airport switches to them */
+  runways?: string[]
+}
+
+/**
+ * Aircraft followed on every display (set on the host, see follow.rs)
+ */
+export interface HostFollowTarget {
+  callsign: string
+  mode: FollowMode
+  /** Zoom factor (0.5-10) */
+  zoom: number
+  /** Orbit distance in meters (50-5000) */
+  orbitDistance: number
+  /** Orbit heading in degrees, measured from behind the aircraft */
+  orbitHeading: number
+  /** Orbit pitch in degrees (-89 to 89) */
+  orbitPitch: number
+}
+
+/**
+ * Host follow update (`follow-changed` event, `/api/follow/ws`)
+ */
+export interface HostFollowStatus {
+  /** Null when the host isn't following an aircraft */
+  target: HostFollowTarget | null
+  /** Followed aircraft dead-reckoned to now (null while not in the traffic picture) */
+  aircraft: {
+    latitude: number
+    longitude: number
+    altitudeFt: number
+    heading: number
+    groundspeedKt: number
+    onGround: boolean
+    extrapolatedMs: number
+  } | null
+  /** Orbit camera pose (null in tower mode: each client aims from its own tower) */
+  camera: {
+    latitude: number
+    longitude: number
+    heightM: number
+    heading: number
+    pitch: number
+  } | null
+  updatedAt: number
+}
+
+/**
+ * Aircraft selected across displays (`selection-changed` event, `/api/selection/ws`)
+ */
+export interface SharedSelection {
+  /** Null when nothing is selected */
+  callsign: string | null
+  /** Display that made the selection (e.g., "iPad") */
+  source: string | null
+  updatedAt: number
 }
@@ -0,0 +1,19 @@
+/**
+ * Coordination message types (see coordination.rs)
+ *
+ * Free-text or canned messages sent between the displays of one host, so
+ * positions can coordinate without leaving the app.
+ */
+
+/**
+ * Coordination message (`coordination-message` event payload)
+ */
+export interface CoordinationMessage {
+  /** Increasing message number (restarts with the host) */
+  id: number
+  text: string
+  /** Display that sent it (e.g., "Ground iPad") */
+  from: string | null
+  /** Unix ms */
+  sentAt: number
+}
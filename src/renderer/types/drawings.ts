@@ -0,0 +1,63 @@
+/**
+ * Shared drawing types (see drawings.rs)
+ *
+ * Range/bearing lines, text labels and polygons drawn on any display and
+ * shown on every display, kept per drawing session on the host.
+ */
+
+export type DrawingKind = 'line' | 'label' | 'polygon'
+
+export interface DrawingPoint {
+  latitude: number
+  longitude: number
+}
+
+/**
+ * New drawing (`POST /api/drawings` body)
+ */
+export interface DrawingRequest {
+  kind: DrawingKind
+  /** Two points for a line, one for a label, three or more for a polygon */
+  points: DrawingPoint[]
+  /** Label text (optional caption for lines and polygons) */
+  text?: string | null
+  /** Hex color (e.g., "#ff4081") */
+  color?: string | null
+  /** Who drew it (e.g., "Instructor") */
+  author?: string | null
+}
+
+export interface Drawing {
+  /** Unique per session (e.g., "d12") */
+  id: string
+  kind: DrawingKind
+  points: DrawingPoint[]
+  text: string | null
+  color: string | null
+  author: string | null
+  /** Unix ms */
+  createdAt: number
+  /** Range and bearing (lines only) */
+  measure: {
+    distanceNm: number
+    /** Degrees true from the first point to the second */
+    bearing: number
+    /** Degrees true from the second point back to the first */
+    reciprocal: number
+  } | null
+}
+
+/**
+ * Every drawing in the active session (`drawings-changed` event, `/api/drawings/ws`)
+ */
+export interface DrawingBoard {
+  session: string
+  drawings: Drawing[]
+  updatedAt: number
+}
+
+export interface DrawingSessionInfo {
+  name: string
+  drawings: number
+  active: boolean
+}
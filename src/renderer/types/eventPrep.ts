@@ -0,0 +1,42 @@
+/**
+ * Event preparation types
+ *
+ * Before a busy event the host can warm every cache for an airport (weather,
+ * charts, overlays, aircraft models and terrain/imagery tiles) so nothing is
+ * downloading during the rush.
+ */
+
+export type EventPrepStepId = 'weather' | 'charts' | 'overlays' | 'models' | 'tiles'
+
+export type EventPrepStepState = 'pending' | 'running' | 'done' | 'skipped' | 'failed'
+
+/** Event to prepare for */
+export interface EventPrepRequest {
+  icao: string
+  /** Event date (YYYY-MM-DD, UTC) */
+  date: string
+}
+
+/** One preparation step */
+export interface EventPrepStep {
+  id: EventPrepStepId
+  label: string
+  state: EventPrepStepState
+  /** Items done of `total` (charts, tiles, models, ...) */
+  done: number
+  total: number
+  /** What was done, or why the step was skipped or failed */
+  detail: string | null
+}
+
+/** Status of the current or last preparation (also sent as `event-prep-progress` events) */
+export interface EventPrepStatus {
+  icao: string
+  date: string
+  running: boolean
+  cancelled: boolean
+  /** Unix ms */
+  startedAt: number
+  finishedAt: number | null
+  steps: EventPrepStep[]
+}
@@ -0,0 +1,51 @@
+/**
+ * VATSIM event schedule types
+ *
+ * The host fetches VATSIM events (and an optional facility calendar), lists
+ * those affecting an airport and reminds before they start, offering to
+ * record the traffic to the replay library.
+ */
+
+/** Ongoing or upcoming event */
+export interface ScheduledEvent {
+  /** "vatsim-{id}" or "calendar-{uid}" */
+  id: string
+  source: 'vatsim' | 'calendar'
+  name: string
+  /** Unix ms */
+  start: number
+  /** Unix ms */
+  end: number
+  /** Airports the event affects (ICAO) */
+  airports: string[]
+  description: string | null
+  link: string | null
+  /** Banner image URL (VATSIM events) */
+  banner: string | null
+}
+
+/** Events affecting an airport (`/api/events`) */
+export interface EventScheduleStatus {
+  enabled: boolean
+  icao: string | null
+  /** Ongoing and upcoming events, soonest first */
+  events: ScheduledEvent[]
+  /** Last successful fetch (Unix ms) */
+  updatedAt: number | null
+  /** Last fetch error */
+  error: string | null
+  /** Id of the event being recorded */
+  recording: string | null
+}
+
+/** Reminder for an event at the active airport (`event-reminder` event) */
+export interface EventReminder {
+  icao: string
+  event: ScheduledEvent
+  /** Minutes until the event starts */
+  minutes: number
+  /** Traffic is already being recorded */
+  recording: boolean
+  /** e.g., "Boston FNO at KBOS starts in 30 minutes — start recording?" */
+  message: string
+}
@@ -84,6 +84,7 @@ export interface SelectiveExportData {
     fsltl?: Record<string, unknown>
     airports?: Record<string, unknown>
     server?: Record<string, unknown>
+    datablock?: Record<string, unknown>
   }
 
   /** Per-airport data */
@@ -141,6 +141,7 @@ export type {
   FSLTLTextureScale,
   DataSourceType,
   BuildingQuality,
+  BuildingSource,
   GroundLabelMode,
 
   // Settings groups
@@ -163,6 +164,103 @@ export type {
   // Global display settings (shared across browsers for consistent appearance)
   GlobalDisplaySettings,
 
+  // Global weather settings (lightning feed)
+  GlobalWeatherSettings,
+
+  // Global celestial settings (moon, stars, sun glare)
+  GlobalCelestialSettings,
+
+  // Global simulated clock settings
+  SimClockMode,
+  GlobalClockSettings,
+
+  // Global instant replay settings
+  GlobalReplaySettings,
+
+  // Global diagnostics settings
+  GlobalDiagnosticsSettings,
+
+  // Global power settings
+  KeepAwakeMode,
+  GlobalPowerSettings,
+
+  // Global autostart settings
+  GlobalAutostartSettings,
+
+  // Global update settings
+  UpdateChannel,
+  GlobalUpdateSettings,
+
+  // Global content pack settings
+  GlobalContentPackSettings,
+
+  // Global hotkey settings
+  ControlActionType,
+  HotkeyBinding,
+  GlobalHotkeySettings,
+
+  // Global gamepad settings
+  GamepadAxisBinding,
+  GamepadButtonBinding,
+  GlobalGamepadSettings,
+
+  // Global offline tile cache settings
+  GlobalTileCacheSettings,
+
+  // Global terrain and imagery provider settings
+  TerrainProviderType,
+  ImageryProviderType,
+  GlobalMapProviderSettings,
+
+  // Global remote access settings (Tailscale/WireGuard, client certificates)
+  GlobalRemoteAccessSettings,
+
+  // Global MIDI settings
+  MidiControlBinding,
+  MidiButtonBinding,
+  GlobalMidiSettings,
+
+  // Display units
+  UnitPreferences,
+  GlobalUnitSettings,
+  DisplayValue,
+  Localized,
+
+  // Controller-online notifications
+  GlobalAtcNotificationSettings,
+
+  // Tower audio relay
+  AudioStreamConfig,
+  GlobalAudioRelaySettings,
+
+  // Weather and ATIS change notifications
+  GlobalWeatherAlertSettings,
+  GlobalViewPresetSettings,
+
+  // Multi-host federation
+  GlobalFederationSettings,
+
+  // UDP JSON output for external tools
+  GlobalUdpOutputSettings,
+
+  // Aural alert sound packs
+  AlertSoundEvent,
+  GlobalAlertSoundSettings,
+
+  // Scheduled server operation windows
+  ServerWindow,
+  GlobalServerScheduleSettings,
+
+  // VATSIM event reminders
+  GlobalEventScheduleSettings,
+
+  // Aircraft level of detail
+  GlobalLodSettings,
+
+  // Datablock layout (stored on host, identical on every display)
+  DatablockField,
+  GlobalDatablockLayout,
+
   // Global viewport settings (stored on host, shared across browsers)
   GlobalViewModeDefaults,
   GlobalCameraBookmark,
@@ -182,6 +280,63 @@ export {
   // Default global display settings values
   DEFAULT_GLOBAL_DISPLAY_SETTINGS,
 
+  // Default global weather settings values
+  DEFAULT_GLOBAL_WEATHER_SETTINGS,
+
+  // Default global celestial settings values
+  DEFAULT_GLOBAL_CELESTIAL_SETTINGS,
+
+  // Default global clock settings values
+  DEFAULT_GLOBAL_CLOCK_SETTINGS,
+
+  // Default global replay settings values
+  DEFAULT_GLOBAL_REPLAY_SETTINGS,
+
+  // Default global diagnostics settings values
+  DEFAULT_GLOBAL_DIAGNOSTICS_SETTINGS,
+
+  // Default global power settings values
+  DEFAULT_GLOBAL_POWER_SETTINGS,
+
+  // Default global autostart settings values
+  DEFAULT_GLOBAL_AUTOSTART_SETTINGS,
+
+  // Default global update settings values
+  DEFAULT_GLOBAL_UPDATE_SETTINGS,
+
+  // Default global content pack settings values
+  DEFAULT_GLOBAL_CONTENT_PACK_SETTINGS,
+
+  // Default global hotkey settings values
+  DEFAULT_GLOBAL_HOTKEY_SETTINGS,
+
+  // Default global gamepad settings values
+  DEFAULT_GLOBAL_GAMEPAD_SETTINGS,
+
+  // Default global tile cache settings values
+  DEFAULT_GLOBAL_TILE_CACHE_SETTINGS,
+
+  // Default global map provider settings values
+  DEFAULT_GLOBAL_MAP_PROVIDER_SETTINGS,
+
+  // Default global remote access settings values
+  DEFAULT_GLOBAL_REMOTE_ACCESS_SETTINGS,
+
+  // Default global MIDI settings values
+  DEFAULT_GLOBAL_MIDI_SETTINGS,
+  DEFAULT_GLOBAL_UNIT_SETTINGS,
+  DEFAULT_GLOBAL_ATC_NOTIFICATION_SETTINGS,
+  DEFAULT_GLOBAL_AUDIO_RELAY_SETTINGS,
+  DEFAULT_GLOBAL_WEATHER_ALERT_SETTINGS,
+  DEFAULT_GLOBAL_VIEW_PRESET_SETTINGS,
+  DEFAULT_GLOBAL_FEDERATION_SETTINGS,
+  DEFAULT_GLOBAL_UDP_OUTPUT_SETTINGS,
+  DEFAULT_GLOBAL_ALERT_SOUND_SETTINGS,
+  DEFAULT_GLOBAL_SERVER_SCHEDULE_SETTINGS,
+  DEFAULT_GLOBAL_EVENT_SCHEDULE_SETTINGS,
+  DEFAULT_GLOBAL_LOD_SETTINGS,
+  DEFAULT_GLOBAL_DATABLOCK_LAYOUT,
+
   // Default global viewport settings values
   DEFAULT_GLOBAL_VIEWPORT_SETTINGS
 } from './settings'
@@ -284,6 +439,7 @@ export type {
 
   // Mod registry
   LoadedMod,
+  ModVariant,
   ModRegistry,
 
   // Custom VMR (Visual Model Rules)
@@ -292,7 +448,10 @@ export type {
 
   // Tower position types
   View3dPosition,
-  ResolvedView2dPosition
+  ResolvedView2dPosition,
+
+  // Static object placements
+  StaticObject
 } from './mod'
 
 export {
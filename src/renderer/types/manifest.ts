@@ -0,0 +1,41 @@
+/**
+ * Content manifest types
+ *
+ * The host lists every asset it serves from disk (mods, converted FSLTL
+ * models, overlays) with its size and hash, so remote browsers can keep them
+ * cached between sessions and only download what changed.
+ */
+
+export type ContentKind = 'aircraft' | 'towers' | 'fsltl' | 'overlay'
+
+/** A servable asset */
+export interface ManifestEntry {
+  /** Path to request it from (e.g. "/api/mods/aircraft/B738/model.glb") */
+  url: string
+  kind: ContentKind
+  size: number
+  /** SHA-256 of the content (hex) */
+  hash: string
+}
+
+/** GET /api/manifest */
+export interface ContentManifest {
+  /** Unix timestamp (ms) */
+  generatedAt: number
+  /** Hash over all entries (also the response's ETag) */
+  hash: string
+  entries: ManifestEntry[]
+}
+
+/** GET /api/chunked/{asset path}: a large asset split into hashed chunks for resumable downloads */
+export interface ChunkManifest {
+  size: number
+  /** Unix timestamp (ms) */
+  modifiedMs: number
+  contentType: string
+  chunkSize: number
+  /** SHA-256 of the whole file (hex) */
+  hash: string
+  /** SHA-256 of each chunk (hex); fetch chunk N with `?chunk=N&hash={hash}` */
+  chunks: string[]
+}
@@ -0,0 +1,67 @@
+/**
+ * Terrain and imagery provider types
+ *
+ * The host resolves the providers selected in `mapProviders` global settings;
+ * clients only learn which layers to load through it and what to credit.
+ */
+
+import type { ImageryProviderType, TerrainProviderType } from './settings'
+
+/** Selected providers as served by the host (no keys) */
+export interface MapProviderInfo {
+  terrain: TerrainProviderType
+  imagery: ImageryProviderType
+  /** Terrain is loaded through the host's tile endpoints */
+  terrainProxied: boolean
+  /** Imagery is loaded through the host's tile endpoints */
+  imageryProxied: boolean
+  /** Highest imagery zoom level the provider has */
+  imageryMaxZoom: number
+  /** The imagery provider has earlier vintages to choose from per airport */
+  hasImageryVintages: boolean
+  /** Attribution HTML to show in the viewer (Ion layers credit themselves) */
+  credits: string[]
+}
+
+/** An earlier version of a provider's imagery */
+export interface ImageryVintage {
+  /** Stored in `mapProviders.imageryVintages` and sent with imagery tile requests */
+  id: string
+  /** Capture year or release date (YYYY-MM-DD) */
+  date: string
+}
+
+/** An imported offline terrain package (served at `/api/terrain/<id>/`) */
+export interface TerrainPackage {
+  id: string
+  name: string
+  /** Unix time (ms) */
+  importedAt: number
+  tiles: number
+  bytes: number
+  maxZoom: number | null
+  /** West, south, east, north in degrees */
+  bounds: [number, number, number, number] | null
+}
+
+/** Progress of a terrain package import (`terrain-import-progress` event) */
+export interface TerrainImportProgress {
+  name: string
+  tiles: number
+}
+
+/** A 3D building tileset generated from OpenStreetMap (served at `/api/buildings/<ICAO>/`) */
+export interface BuildingsInfo {
+  icao: string
+  buildings: number
+  tiles: number
+  /** Unix time (ms) */
+  fetchedAt: number
+  /** Reference point of the tileset's local frame; its ground level is the airport elevation */
+  origin: {
+    latitude: number
+    longitude: number
+    elevationM: number
+  }
+  attribution: string
+}
@@ -18,6 +18,21 @@ export function getModelFormat(filename: string): SupportedModelFormat | null {
   return SUPPORTED_MODEL_FORMATS.includes(ext) ? ext : null
 }
 
+/**
+ * Conditional manifest variant (winter textures, night-lit signs, event banners)
+ * Resolved by the host: the first variant whose conditions all hold is active
+ */
+export interface ModVariant {
+  name: string
+  modelFile?: string  // model used instead of the manifest's modelFile
+  files?: Record<string, string>  // other replaced files (base path -> variant path)
+  months?: number[]  // 1-12
+  from?: string  // first day, "MM-DD" (every year) or "YYYY-MM-DD"
+  until?: string  // last day, "MM-DD" (every year) or "YYYY-MM-DD"
+  time?: 'day' | 'night'  // at the airport
+  weather?: string[]  // any of "snow", "rain", "fog", "thunderstorm" or METAR codes
+}
+
 export interface AircraftModManifest {
   name: string
   author: string
@@ -31,6 +46,8 @@ export interface AircraftModManifest {
     y: number  // yaw offset in degrees
     z: number  // roll offset in degrees
   }
+  variants?: ModVariant[]  // seasonal/time-based variants
+  activeVariant?: string  // variant the host resolved (desktop manifests only)
 }
 
 export interface TowerModManifest {
@@ -53,6 +70,8 @@ export interface TowerModManifest {
     aglHeight: number  // height above ground level in meters
   }
   cabHeading?: number  // default camera heading in degrees (0=north, 90=east)
+  variants?: ModVariant[]  // seasonal/time-based variants
+  activeVariant?: string  // variant the host resolved (desktop manifests only)
 }
 
 /**
@@ -151,6 +170,21 @@ export function convertLegacyToNewFormat(legacy: LegacyTowerPosition): CustomTow
   }
 }
 
+/**
+ * Static model placed at an airport (mods/placements/{ICAO}.json)
+ * The model is a GLB path relative to mods/objects/
+ */
+export interface StaticObject {
+  id: string  // unique per airport (e.g., "bridge-b5")
+  model: string  // e.g., "jetbridges/standard.glb"
+  name?: string | null
+  latitude: number
+  longitude: number
+  heading: number  // degrees true the model's front faces
+  heightM: number  // meters above the ground
+  scale: number
+}
+
 export interface LoadedMod<T extends AircraftModManifest | TowerModManifest> {
   manifest: T
   modelUrl: string  // URL to the loaded model
@@ -184,6 +218,20 @@ export interface CustomVMRRule {
   callsignPrefix?: string
 }
 
+/**
+ * Parsed VMR rule as returned by the backend (Tauri command or /api/vmr-rules)
+ */
+export interface ApiVmrRule {
+  /** ICAO type code, null for airline-only rules (not supported by the renderer yet) */
+  typeCode: string | null
+  /** Raw ModelName attribute (alternatives separated by "//") */
+  modelName: string
+  /** Callsign prefix for airline-specific rules */
+  callsignPrefix: string | null
+  /** VMR file the rule was loaded from */
+  sourceFile?: string
+}
+
 /**
  * A matched model from custom VMR rules
  */
@@ -78,6 +78,149 @@ export type PlaybackMode = 'live' | 'replay' | 'imported'
  */
 export type PlaybackSpeed = 0.5 | 1 | 2 | 4
 
+/**
+ * Replay streamed by the host to all clients (see replay.rs)
+ */
+export interface ReplayServerStatus {
+  loaded: boolean
+  airport: string | null
+  playing: boolean
+  speed: PlaybackSpeed
+  /** First and last recorded times (Unix ms) */
+  startTime: number
+  endTime: number
+  /** Current recorded time (Unix ms) */
+  position: number
+  snapshotCount: number
+  /** Replaying the host's instant replay buffer (returns to live traffic at the end) */
+  instant: boolean
+  /** Host time when the status was produced (Unix ms), to extrapolate the position */
+  realTime: number
+}
+
+/**
+ * Playback change for the host replay; omitted fields are unchanged
+ */
+export interface ReplayServerControl {
+  playing?: boolean
+  speed?: PlaybackSpeed
+  /** Seek to this recorded time (Unix ms) */
+  position?: number
+}
+
+/**
+ * Recorded session to export: the host's loaded replay or its instant replay buffer
+ */
+export type SessionExportSource = 'replay' | 'buffer'
+
+/**
+ * Recorded session in the host's shared replay library (`.tcreplay` file, see replay_library.rs)
+ */
+export interface SharedReplay {
+  /** File name without the extension (e.g., "kbos-fno-replay") */
+  name: string
+  /** Archive size in bytes */
+  size: number
+  format: number
+  /** E.g., "KBOS FNO replay" */
+  title: string
+  description: string
+  airport: string | null
+  /** First and last recorded times (Unix ms) */
+  startTime: number
+  endTime: number
+  snapshotCount: number
+  exportDate: string
+  appVersion: string
+}
+
+/**
+ * Title and description of a shared replay (default title: airport and start time)
+ */
+export interface ShareReplayOptions {
+  title?: string
+  description?: string
+}
+
+/**
+ * Track export format (KML for Google Earth, GeoJSON LineStrings, CSV per aircraft)
+ */
+export type SessionExportFormat = 'kml' | 'geojson' | 'csv'
+
+/**
+ * Arrival, departure or go-around detected in a recorded session
+ */
+export interface SessionMovement {
+  callsign: string
+  kind: 'arrival' | 'departure' | 'goAround'
+  /** Runway end (e.g., "27R"), if the aircraft was lined up with one */
+  runway: string | null
+  /** Touchdown, takeoff or go-around time (Unix ms) */
+  time: number
+  aircraftType: string | null
+}
+
+/**
+ * Movements on one runway end ("unknown" if not lined up with a runway)
+ */
+export interface SessionRunwayMovements {
+  runway: string
+  arrivals: number
+  departures: number
+  goArounds: number
+}
+
+/**
+ * Movements in an hour
+ */
+export interface SessionPeriodMovements {
+  /** Start of the hour (Unix ms) */
+  start: number
+  arrivals: number
+  departures: number
+  goArounds: number
+}
+
+/**
+ * Most aircraft seen at once
+ */
+export interface SessionTrafficPeak {
+  /** Unix ms */
+  time: number
+  aircraft: number
+}
+
+/**
+ * Post-event statistics for a recorded session (/api/stats/session)
+ */
+export interface SessionStats {
+  icao: string | null
+  source: SessionExportSource
+  /** First and last recorded times (Unix ms) */
+  startTime: number
+  endTime: number
+  arrivals: number
+  departures: number
+  goArounds: number
+  runways: SessionRunwayMovements[]
+  peakTraffic: SessionTrafficPeak | null
+  peakGround: SessionTrafficPeak | null
+  /** Average distance of the next arrival from touchdown when the previous one landed (nm) */
+  averageFinalSpacingNm: number | null
+  /** Average time between consecutive arrivals on the same runway (seconds) */
+  averageFinalSpacingSeconds: number | null
+  hourly: SessionPeriodMovements[]
+  busiestHour: SessionPeriodMovements | null
+  movements: SessionMovement[]
+}
+
+/**
+ * Message on the host replay WebSocket (/api/replay/ws)
+ */
+export type ReplayServerEvent =
+  | ({ type: 'status' } & ReplayServerStatus)
+  | ({ type: 'snapshot' } & VatsimSnapshot)
+
 /**
  * Helper functions for serializing/deserializing aircraft states
  */
@@ -112,6 +112,15 @@ export type AircraftTintColor = 'white' | 'lightBlue' | 'tan' | 'yellow' | 'oran
  */
 export type BuildingQuality = 'low' | 'medium' | 'high'
 
+/**
+ * Where 3D buildings come from
+ *
+ * - 'ion': Cesium OSM Buildings (worldwide, needs a Cesium Ion token)
+ * - 'osm': Extruded from OpenStreetMap footprints around the current airport
+ *   by the host (for airports with poor Ion/Google coverage, no token needed)
+ */
+export type BuildingSource = 'ion' | 'osm'
+
 /**
  * Ground traffic label display mode
  *
@@ -154,6 +163,9 @@ export interface CesiumSettings {
    */
   buildingQuality: BuildingQuality
 
+  /** Source of the 3D buildings (default: 'ion') */
+  buildingSource: BuildingSource
+
   /** Time mode for sun position: real-time or fixed (default: 'real') */
   timeMode: TimeMode
 
@@ -583,6 +595,17 @@ export interface UISettings {
    * 0 means auto height (fills available space up to max-height).
    */
   aircraftPanelHeight: number
+
+  /**
+   * Share aircraft selection with other displays (default: false)
+   *
+   * When enabled, the aircraft this display clicks or follows is sent to the
+   * host, and the aircraft other opted-in displays select is highlighted here.
+   */
+  syncSelection: boolean
+
+  /** Name shown to other displays for this display's selections (e.g., "iPad") */
+  selectionSourceName: string
 }
 
 /**
@@ -649,6 +672,7 @@ export interface GlobalCameraBookmark {
   positionOffsetZ: number
   viewMode: '3d' | 'topdown'
   topdownAltitude?: number
+  runways?: string[]
 }
 
 /**
@@ -716,13 +740,6 @@ export const DEFAULT_GLOBAL_VIEWPORT_SETTINGS: GlobalViewportSettings = {
  * controller experience.
  */
 export interface GlobalDisplaySettings {
-  /**
-   * Leader line distance (0.5-5 in 0.5 increments, default: 2)
-   * Same across all devices for consistent datablock appearance.
-   * Multiplied by 3 to get pixel gap.
-   */
-  leaderDistance: number
-
   /**
    * Default datablock direction (numpad-style position, default: 7)
    * Same across all devices for consistent layout.
@@ -779,13 +796,20 @@ export interface GlobalDisplaySettings {
    * when groundLabelMode is 'moving'. Range: 1-10 kts.
    */
   groundLabelMinSpeed: number
+
+  /**
+   * Positions the host keeps per aircraft for trails (default: 60)
+   *
+   * Points are at least 5 seconds apart, so 60 points cover about 5 minutes
+   * of VATSIM traffic. Range: 10-720.
+   */
+  trailLength: number
 }
 
 /**
  * Default global display settings
  */
 export const DEFAULT_GLOBAL_DISPLAY_SETTINGS: GlobalDisplaySettings = {
-  leaderDistance: 2,
   defaultDatablockDirection: 7,
   datablockMode: 'full',
   labelVisibilityDistance: 30,
@@ -793,7 +817,901 @@ export const DEFAULT_GLOBAL_DISPLAY_SETTINGS: GlobalDisplaySettings = {
   showAirborneTraffic: true,
   autoAvoidOverlaps: true,
   groundLabelMode: 'all',
-  groundLabelMinSpeed: 2
+  groundLabelMinSpeed: 2,
+  trailLength: 60
+}
+
+/**
+ * Weather feature settings shared across all devices
+ */
+export interface GlobalWeatherSettings {
+  /**
+   * Track lightning strikes around the active airport (default: false)
+   * Uses the Blitzortung.org community network.
+   */
+  lightningEnabled: boolean
+
+  /**
+   * Radius around the airport to track strikes in nautical miles (default: 50)
+   */
+  lightningRadiusNm: number
+}
+
+/**
+ * Default global weather settings
+ */
+export const DEFAULT_GLOBAL_WEATHER_SETTINGS: GlobalWeatherSettings = {
+  lightningEnabled: false,
+  lightningRadiusNm: 50
+}
+
+/**
+ * Sky rendering settings shared across all devices so every client shows the same sky
+ */
+export interface GlobalCelestialSettings {
+  /** Show the moon (default: true) */
+  showMoon: boolean
+
+  /** Show stars at night (default: true) */
+  showStars: boolean
+
+  /** Render the sun's glare/glow (default: true) */
+  sunGlare: boolean
+}
+
+/**
+ * Default global celestial settings
+ */
+export const DEFAULT_GLOBAL_CELESTIAL_SETTINGS: GlobalCelestialSettings = {
+  showMoon: true,
+  showStars: true,
+  sunGlare: true
+}
+
+/**
+ * Simulated clock mode
+ * - 'real': current UTC time
+ * - 'offset': current UTC time plus an offset (e.g., force daytime during a night event)
+ * - 'fixed': frozen at a fixed time
+ */
+export type SimClockMode = 'real' | 'offset' | 'fixed'
+
+/**
+ * Simulated clock shared by the host and all remote browsers
+ *
+ * Changes are broadcast by the host (`sim-clock-changed` event, `/api/clock/ws`)
+ * so every client renders the same lighting.
+ */
+export interface GlobalClockSettings {
+  /** Clock mode (default: 'real') */
+  mode: SimClockMode
+
+  /** Offset from UTC in milliseconds for 'offset' mode (default: 0) */
+  offsetMs: number
+
+  /** Frozen time (epoch ms) for 'fixed' mode */
+  fixedTime: number | null
+}
+
+/**
+ * Default global clock settings
+ */
+export const DEFAULT_GLOBAL_CLOCK_SETTINGS: GlobalClockSettings = {
+  mode: 'real',
+  offsetMs: 0,
+  fixedTime: null
+}
+
+/**
+ * Instant replay settings
+ *
+ * The host keeps a rolling buffer of the traffic around the active airport so
+ * the last few minutes can be replayed to every client (e.g., right after a
+ * runway incursion).
+ */
+export interface GlobalReplaySettings {
+  /** Minutes of traffic kept in the instant replay buffer (default: 10, range: 1-60) */
+  instantReplayMinutes: number
+}
+
+/**
+ * Default global replay settings
+ */
+export const DEFAULT_GLOBAL_REPLAY_SETTINGS: GlobalReplaySettings = {
+  instantReplayMinutes: 10
+}
+
+/**
+ * Backend diagnostics
+ */
+export interface GlobalDiagnosticsSettings {
+  /**
+   * Host log level filter: a level (`error`, `warn`, `info`, `debug`, `trace`)
+   * or per-module directives such as `info,app_lib::vnas=debug` (default: 'info')
+   */
+  logLevel: string
+}
+
+/**
+ * Default global diagnostics settings
+ */
+export const DEFAULT_GLOBAL_DIAGNOSTICS_SETTINGS: GlobalDiagnosticsSettings = {
+  logLevel: 'info'
+}
+
+/**
+ * When the host keeps the display and system from sleeping
+ * - 'off': normal power management
+ * - 'always': while the app is running
+ * - 'whileActive': during replay playback or a timelapse recording
+ */
+export type KeepAwakeMode = 'off' | 'always' | 'whileActive'
+
+/**
+ * Host power settings
+ */
+export interface GlobalPowerSettings {
+  /** Keep the display and system awake (default: 'off') */
+  keepAwake: KeepAwakeMode
+}
+
+/**
+ * Default global power settings
+ */
+export const DEFAULT_GLOBAL_POWER_SETTINGS: GlobalPowerSettings = {
+  keepAwake: 'off'
+}
+
+/**
+ * Start the desktop app at login (for a dedicated display or server machine)
+ */
+export interface GlobalAutostartSettings {
+  /** Start the app when the user logs in (default: false) */
+  enabled: boolean
+  /** Start without a window, with the HTTP server on (default: false) */
+  headless: boolean
+}
+
+/**
+ * Default global autostart settings
+ */
+export const DEFAULT_GLOBAL_AUTOSTART_SETTINGS: GlobalAutostartSettings = {
+  enabled: false,
+  headless: false
+}
+
+/**
+ * Content packs: auto-updated VMR rules, tower positions, video maps and
+ * airport data published in remote index files (desktop app only)
+ */
+export interface GlobalContentPackSettings {
+  /** Index files listing the available packs */
+  indexUrls: string[]
+  /** Ids of the packs to install and keep up to date */
+  enabled: string[]
+  /** Check the indexes for updates on startup (default: true) */
+  checkOnStartup: boolean
+}
+
+/**
+ * Default global content pack settings
+ */
+export const DEFAULT_GLOBAL_CONTENT_PACK_SETTINGS: GlobalContentPackSettings = {
+  indexUrls: [],
+  enabled: [],
+  checkOnStartup: true
+}
+
+/**
+ * Update release channel
+ * - 'stable': releases only
+ * - 'beta': pre-releases too
+ */
+export type UpdateChannel = 'stable' | 'beta'
+
+/**
+ * App update settings (desktop app only)
+ */
+export interface GlobalUpdateSettings {
+  /** Release channel (default: 'stable') */
+  channel: UpdateChannel
+  /** Version not offered again by automatic checks */
+  skippedVersion?: string
+  /** No updates offered by automatic checks before this time (Unix ms) */
+  deferredUntil?: number
+}
+
+/**
+ * Default global update settings
+ */
+export const DEFAULT_GLOBAL_UPDATE_SETTINGS: GlobalUpdateSettings = {
+  channel: 'stable'
+}
+
+/**
+ * Action run by a control surface (Stream Deck / Companion) or system-wide hotkey
+ * - 'bookmark': load a camera bookmark in the main window (value: name or slot)
+ * - 'airport': select an airport (value: ICAO)
+ * - 'viewMode': switch to a view mode (value: '3d' or 'topdown')
+ * - 'toggleViewMode': switch between 3D and 2D top-down
+ * - 'instantReplay': replay the last few minutes (see GlobalReplaySettings)
+ * - 'replayPlayPause', 'stopReplay': control the loaded replay
+ */
+export type ControlActionType =
+  | 'bookmark'
+  | 'airport'
+  | 'viewMode'
+  | 'toggleViewMode'
+  | 'instantReplay'
+  | 'replayPlayPause'
+  | 'stopReplay'
+
+/**
+ * One system-wide hotkey
+ */
+export interface HotkeyBinding {
+  /** Accelerator such as 'CommandOrControl+Alt+1' */
+  shortcut: string
+  action: ControlActionType
+  /** Action value, e.g. the bookmark name or slot for 'bookmark' */
+  value?: string | null
+}
+
+/**
+ * System-wide hotkeys registered by the host, working while the window isn't focused
+ */
+export interface GlobalHotkeySettings {
+  /** Register the hotkeys (default: false, they take the keys from other apps) */
+  enabled: boolean
+  bindings: HotkeyBinding[]
+}
+
+/**
+ * Default global hotkey settings
+ */
+export const DEFAULT_GLOBAL_HOTKEY_SETTINGS: GlobalHotkeySettings = {
+  enabled: false,
+  bindings: [
+    { shortcut: 'CommandOrControl+Alt+1', action: 'bookmark', value: '1' },
+    { shortcut: 'CommandOrControl+Alt+2', action: 'bookmark', value: '2' },
+    { shortcut: 'CommandOrControl+Alt+3', action: 'bookmark', value: '3' },
+    { shortcut: 'CommandOrControl+Alt+4', action: 'bookmark', value: '4' },
+    { shortcut: 'CommandOrControl+Alt+T', action: 'toggleViewMode' },
+    { shortcut: 'CommandOrControl+Alt+R', action: 'instantReplay' }
+  ]
+}
+
+/**
+ * Gamepad stick or analog trigger driving a camera motion
+ */
+export interface GamepadAxisBinding {
+  /** Axis ('LeftStickX', 'RightStickY', ...) or analog button ('RightTrigger2') */
+  input: string
+  /** Camera motion (positive 'zoom' zooms in) */
+  target: 'forward' | 'right' | 'up' | 'heading' | 'pitch' | 'zoom'
+  /** Multiplier, negative to invert (default: 1) */
+  scale: number
+}
+
+/**
+ * Gamepad button running a control action
+ */
+export interface GamepadButtonBinding {
+  /** Button ('South', 'North', 'DPadUp', ...) */
+  button: string
+  action: ControlActionType
+  value?: string | null
+}
+
+/**
+ * USB joystick / game controller camera input, read by the host
+ */
+export interface GlobalGamepadSettings {
+  /** Read gamepads on the host (default: false) */
+  enabled: boolean
+  /** Stick deflection ignored around center, 0-1 (default: 0.15) */
+  deadzone: number
+  axes: GamepadAxisBinding[]
+  buttons: GamepadButtonBinding[]
+}
+
+/**
+ * Default global gamepad settings
+ */
+export const DEFAULT_GLOBAL_GAMEPAD_SETTINGS: GlobalGamepadSettings = {
+  enabled: false,
+  deadzone: 0.15,
+  axes: [
+    { input: 'LeftStickY', target: 'forward', scale: 1 },
+    { input: 'LeftStickX', target: 'right', scale: 1 },
+    { input: 'RightStickX', target: 'heading', scale: 1 },
+    { input: 'RightStickY', target: 'pitch', scale: 1 },
+    { input: 'RightTrigger2', target: 'zoom', scale: 1 },
+    { input: 'LeftTrigger2', target: 'zoom', scale: -1 }
+  ],
+  buttons: [
+    { button: 'DPadUp', action: 'bookmark', value: '1' },
+    { button: 'DPadRight', action: 'bookmark', value: '2' },
+    { button: 'DPadDown', action: 'bookmark', value: '3' },
+    { button: 'DPadLeft', action: 'bookmark', value: '4' },
+    { button: 'North', action: 'toggleViewMode' }
+  ]
+}
+
+/**
+ * MIDI knob or fader setting a camera value
+ */
+export interface MidiControlBinding {
+  /** MIDI channel 1-16 (null: any) */
+  channel?: number | null
+  /** Control change number */
+  control: number
+  /** Camera value (fader up zooms in) */
+  target: 'heading' | 'pitch' | 'zoom'
+}
+
+/**
+ * MIDI button (note or control change) running a control action
+ */
+export interface MidiButtonBinding {
+  /** MIDI channel 1-16 (null: any) */
+  channel?: number | null
+  kind: 'note' | 'cc'
+  /** Note or control change number */
+  number: number
+  action: ControlActionType
+  value?: string | null
+}
+
+/**
+ * MIDI controller camera input, read by the host
+ */
+export interface GlobalMidiSettings {
+  /** Listen to a MIDI controller (default: false) */
+  enabled: boolean
+  /** Input port name, or part of it (null: the first port) */
+  port: string | null
+  controls: MidiControlBinding[]
+  buttons: MidiButtonBinding[]
+}
+
+/**
+ * Default global MIDI settings (KORG nanoKONTROL2 factory scene:
+ * knobs 1-3 for heading, pitch and zoom, solo buttons for bookmarks 1-8)
+ */
+export const DEFAULT_GLOBAL_MIDI_SETTINGS: GlobalMidiSettings = {
+  enabled: false,
+  port: null,
+  controls: [
+    { control: 16, target: 'heading' },
+    { control: 17, target: 'pitch' },
+    { control: 18, target: 'zoom' }
+  ],
+  buttons: [1, 2, 3, 4, 5, 6, 7, 8].map((slot) => ({
+    kind: 'cc' as const,
+    number: 31 + slot,
+    action: 'bookmark' as const,
+    value: String(slot)
+  }))
+}
+
+/**
+ * Display units for API responses with derived quantities (weather,
+ * density altitude, session stats, traffic)
+ */
+export interface UnitPreferences {
+  altitude: 'feet' | 'meters'
+  speed: 'knots' | 'kph'
+  pressure: 'inhg' | 'hpa'
+  /** Clock format for UTC times */
+  timeFormat: '24h' | '12h'
+}
+
+/**
+ * Unit preferences: a default plus named profiles browsers can pick
+ * (PUT /api/units/profile, remembered per browser)
+ */
+export interface GlobalUnitSettings {
+  /** Used by the desktop app and browsers without a profile */
+  default: UnitPreferences
+  /** Named profiles (e.g., "metric") */
+  profiles: Record<string, UnitPreferences>
+}
+
+/**
+ * Default unit settings (feet, knots, inHg, 24-hour clock)
+ */
+export const DEFAULT_GLOBAL_UNIT_SETTINGS: GlobalUnitSettings = {
+  default: {
+    altitude: 'feet',
+    speed: 'knots',
+    pressure: 'inhg',
+    timeFormat: '24h'
+  },
+  profiles: {}
+}
+
+/**
+ * A value converted for display (`display` maps in API responses)
+ */
+export interface DisplayValue {
+  value: number
+  /** Unit symbol ("ft", "kt", "inHg", "Z") */
+  unit: string
+  /** Formatted value with its unit ("29.92 inHg") */
+  text: string
+}
+
+/**
+ * An API response with display values for the browser's unit profile
+ */
+export type Localized<T, D = Record<string, DisplayValue>> = T & {
+  units: UnitPreferences
+  display: D
+}
+
+/**
+ * Offline terrain and imagery cache
+ *
+ * When enabled, Cesium terrain and imagery tiles are loaded through the host,
+ * which keeps them on disk so airports downloaded ahead of time work without
+ * an internet connection (e.g., at an event with poor Wi-Fi).
+ */
+export interface GlobalTileCacheSettings {
+  /** Load terrain and imagery through the host's disk cache (default: false) */
+  enabled: boolean
+  /** Disk quota in gigabytes; least recently used tiles are removed beyond it (default: 2) */
+  maxSizeGb: number
+  /** Radius downloaded around each airport in nautical miles (default: 10) */
+  radiusNm: number
+  /** Airports (ICAO codes) to download tiles for */
+  airports: string[]
+  /**
+   * Download the final approach corridors of the runways in use at the active
+   * airport whenever they change (default: true)
+   */
+  prefetchApproaches: boolean
+  /** Length of the approach corridors from the threshold in nautical miles (default: 12) */
+  approachCorridorNm: number
+}
+
+/**
+ * Default global tile cache settings
+ */
+export const DEFAULT_GLOBAL_TILE_CACHE_SETTINGS: GlobalTileCacheSettings = {
+  enabled: false,
+  maxSizeGb: 2,
+  radiusNm: 10,
+  airports: [],
+  prefetchApproaches: true,
+  approachCorridorNm: 12
+}
+
+/** Terrain source: Cesium World Terrain, MapTiler, a self-hosted quantized-mesh server, an imported package, or none */
+export type TerrainProviderType = 'ion' | 'maptiler' | 'custom' | 'package' | 'ellipsoid'
+
+/** Imagery source: Bing through Cesium Ion, Bing with an own key, MapTiler, Sentinel-2 cloudless, Esri World Imagery (Wayback), or an XYZ template */
+export type ImageryProviderType = 'ion' | 'bing' | 'maptiler' | 'sentinel' | 'wayback' | 'custom'
+
+/**
+ * Terrain and imagery providers
+ *
+ * Alternatives to Cesium Ion for users without a subscription. Layers from
+ * other providers are loaded through the host, so keys stay on the host.
+ */
+export interface GlobalMapProviderSettings {
+  /** Terrain source (default: 'ion') */
+  terrain: TerrainProviderType
+  /** Imagery source (default: 'ion') */
+  imagery: ImageryProviderType
+  /** MapTiler API key (MapTiler terrain or imagery) */
+  maptilerKey: string
+  /** Bing Maps key (Bing imagery without Ion) */
+  bingKey: string
+  /** Base URL of a quantized-mesh terrain server (the folder with layer.json) */
+  customTerrainUrl: string
+  /** ID of the imported offline terrain package to use */
+  terrainPackage: string
+  /** Imagery URL template with {z}, {x} and {y} */
+  customImageryUrl: string
+  /** Attribution shown for custom terrain or imagery (HTML allowed) */
+  customAttribution: string
+  /**
+   * Imagery vintage per airport (ICAO to vintage ID, e.g. 'sentinel-2020' or
+   * 'wayback-12457'); ignored while another imagery provider is selected
+   */
+  imageryVintages: Record<string, string>
+}
+
+/**
+ * Default global map provider settings
+ */
+export const DEFAULT_GLOBAL_MAP_PROVIDER_SETTINGS: GlobalMapProviderSettings = {
+  terrain: 'ion',
+  imagery: 'ion',
+  maptilerKey: '',
+  bingKey: '',
+  customTerrainUrl: '',
+  terrainPackage: '',
+  customImageryUrl: '',
+  customAttribution: '',
+  imageryVintages: {}
+}
+
+/**
+ * Remote access over an overlay network or with client certificates
+ */
+export interface GlobalRemoteAccessSettings {
+  /** Treat Tailscale addresses (100.64.0.0/10, fd7a:115c:a1e0::/48) as local network */
+  trustTailscale: boolean
+  /** Extra CIDR ranges treated as local network, e.g. a WireGuard subnet ("10.8.0.0/24") */
+  trustedNetworks: string[]
+  /** HTTPS listener that only accepts clients with a certificate signed by the client CA */
+  clientCert: {
+    enabled: boolean
+    /** HTTPS port (default: 8766) */
+    port: number
+    /** PEM server certificate chain */
+    certPath: string
+    /** PEM server private key */
+    keyPath: string
+    /** PEM CA certificate(s) client certificates must be signed by */
+    clientCaPath: string
+  }
+}
+
+/**
+ * Default global remote access settings
+ */
+export const DEFAULT_GLOBAL_REMOTE_ACCESS_SETTINGS: GlobalRemoteAccessSettings = {
+  trustTailscale: false,
+  trustedNetworks: [],
+  clientCert: {
+    enabled: false,
+    port: 8766,
+    certPath: '',
+    keyPath: '',
+    clientCaPath: ''
+  }
+}
+
+/**
+ * Controller-online notifications
+ *
+ * The host watches the positions covering the active airport and reports the
+ * ones coming online or going offline (`atc-change` event, /api/atc/ws).
+ */
+export interface GlobalAtcNotificationSettings {
+  /** Show a desktop notification on the host for each change (default: false) */
+  desktop: boolean
+  /** POST each change to this URL (Discord/Slack incoming webhooks work) */
+  webhookUrl: string | null
+  /** Position levels to notify about; events are sent for every level */
+  levels: Array<'atis' | 'delivery' | 'ground' | 'tower' | 'approach' | 'center' | 'fss'>
+}
+
+/**
+ * Default controller notification settings (no desktop notification or webhook)
+ */
+export const DEFAULT_GLOBAL_ATC_NOTIFICATION_SETTINGS: GlobalAtcNotificationSettings = {
+  desktop: false,
+  webhookUrl: null,
+  levels: ['delivery', 'ground', 'tower', 'approach', 'center', 'fss']
+}
+
+/**
+ * Tower audio stream configured for an airport (LiveATC or any Icecast stream)
+ */
+export interface AudioStreamConfig {
+  /** Identifier used in the relay URL (letters, digits, '_' and '-'; e.g., "kbos_twr") */
+  id: string
+  icao: string
+  /** Display name (e.g., "Boston Tower") */
+  name: string
+  /** Upstream stream URL (e.g., "http://d.liveatc.net/kbos_twr") */
+  url: string
+}
+
+/**
+ * Tower audio relay
+ *
+ * The host relays these streams at /api/audio/{id}, so remote tablets play
+ * them from the same origin without CORS or mixed-content problems.
+ */
+export interface GlobalAudioRelaySettings {
+  streams: AudioStreamConfig[]
+}
+
+/**
+ * Default tower audio settings (no streams)
+ */
+export const DEFAULT_GLOBAL_AUDIO_RELAY_SETTINGS: GlobalAudioRelaySettings = {
+  streams: []
+}
+
+/**
+ * Weather and ATIS change notifications
+ *
+ * Changes at the active airport (wind shift, altimeter, new ATIS letter,
+ * VATSIM runways differing from the real-world D-ATIS) are always sent as `weather-alert` events; `desktop` also shows them as
+ * notifications on the host.
+ */
+export interface GlobalWeatherAlertSettings {
+  /** Show desktop notifications on the host */
+  desktop: boolean
+  /** Notify when the wind shifts or strengthens beyond the thresholds */
+  windShift: boolean
+  /** Direction change that counts as a shift (degrees) */
+  windShiftThresholdDeg: number
+  /** Speed change that counts as a shift (knots) */
+  windSpeedThresholdKt: number
+  /** Notify when the altimeter setting changes */
+  altimeter: boolean
+  /** Notify when a new ATIS letter is published */
+  atis: boolean
+  /** Compare the VATSIM ATIS runways with the real-world D-ATIS and notify on a mismatch */
+  datisMismatch: boolean
+}
+
+/**
+ * Default weather notification settings (events only, no desktop notifications)
+ */
+export const DEFAULT_GLOBAL_WEATHER_ALERT_SETTINGS: GlobalWeatherAlertSettings = {
+  desktop: false,
+  windShift: true,
+  windShiftThresholdDeg: 30,
+  windSpeedThresholdKt: 10,
+  altimeter: true,
+  atis: true,
+  datisMismatch: true
+}
+
+/**
+ * Runway configuration view presets
+ *
+ * Bookmarks tagged with runways are suggested (`view-preset-suggestion`
+ * events) when the detected runway configuration changes; `autoSwitch` also
+ * loads the matching bookmark in the main window.
+ */
+export interface GlobalViewPresetSettings {
+  /** Load the matching bookmark automatically instead of only suggesting it */
+  autoSwitch: boolean
+}
+
+/**
+ * Default view preset settings (suggest only)
+ */
+export const DEFAULT_GLOBAL_VIEW_PRESET_SETTINGS: GlobalViewPresetSettings = {
+  autoSwitch: false
+}
+
+/**
+ * Multi-host federation
+ *
+ * A display host mirrors another TowerCab instance (the data host): its vNAS
+ * aircraft stream and, with `mirrorSettings`, its shared display settings.
+ * Only the data host needs vNAS credentials.
+ */
+export interface GlobalFederationSettings {
+  /** Mirror the data host */
+  enabled: boolean
+  /** Data host address (e.g., "http://192.168.1.10:8765") */
+  url: string
+  /** The data host's server auth token, if it requires one */
+  token: string | null
+  /** Copy the data host's datablock, label, sky, clock, unit and bookmark settings */
+  mirrorSettings: boolean
+}
+
+/**
+ * Default federation settings (standalone)
+ */
+export const DEFAULT_GLOBAL_FEDERATION_SETTINGS: GlobalFederationSettings = {
+  enabled: false,
+  url: '',
+  token: null,
+  mirrorSettings: true
+}
+
+/**
+ * UDP JSON output for external tools
+ *
+ * Sends the merged aircraft stream (VATSIM and vNAS) as newline-delimited
+ * JSON datagrams, one aircraft per line, for hardware datablock displays and
+ * FIDS boards. A broadcast address reaches every listener on the LAN.
+ */
+export interface GlobalUdpOutputSettings {
+  /** Send aircraft over UDP */
+  enabled: boolean
+  /** Destination host or IP (e.g., "127.0.0.1" or "192.168.1.255") */
+  address: string
+  /** Destination port */
+  port: number
+}
+
+/**
+ * Default UDP output settings (off, localhost:49100)
+ */
+export const DEFAULT_GLOBAL_UDP_OUTPUT_SETTINGS: GlobalUdpOutputSettings = {
+  enabled: false,
+  address: '127.0.0.1',
+  port: 49100
+}
+
+/**
+ * Alert sound event types
+ * - 'conflict': new short-term conflict warning
+ * - 'incursion': aircraft entering an occupied runway
+ * - 'emergency': emergency, hijack or radio failure squawk
+ */
+export type AlertSoundEvent = 'conflict' | 'incursion' | 'emergency'
+
+/**
+ * Aural alerts
+ *
+ * Every display plays the selected sound pack from `mods/sounds/` when an
+ * alert is raised. Facilities ship their own tones as packs.
+ */
+export interface GlobalAlertSoundSettings {
+  /** Sound pack id (folder name in mods/sounds), or null for no aural alerts */
+  pack: string | null
+  /** Playback volume (0-1) */
+  volume: number
+  /** Event types not to play */
+  muted: AlertSoundEvent[]
+}
+
+/**
+ * Default alert sound settings (no pack, 80% volume)
+ */
+export const DEFAULT_GLOBAL_ALERT_SOUND_SETTINGS: GlobalAlertSoundSettings = {
+  pack: null,
+  volume: 0.8,
+  muted: []
+}
+
+/**
+ * Time window during which the host runs the HTTP server
+ */
+export interface ServerWindow {
+  /** Shown in logs and used as the recording's title (e.g., "FNO") */
+  name: string
+  /** Days of the week the window opens ('mon' ... 'sun'); empty = every day */
+  days: string[]
+  /** Single date (YYYY-MM-DD) the window opens on instead of weekly days */
+  date: string | null
+  /** Opening time ("HH:MM") */
+  start: string
+  /** Closing time ("HH:MM"); at or before the start means the next day */
+  end: string
+  /** Record the traffic while open and save it to the replay library */
+  record: boolean
+}
+
+/**
+ * Scheduled server operation
+ *
+ * A dedicated host starts the HTTP server when a window opens and stops it
+ * when the window closes (only if the schedule started it). Checked every
+ * 30 seconds.
+ */
+export interface GlobalServerScheduleSettings {
+  /** Start and stop the server on schedule */
+  enabled: boolean
+  /** Window times are UTC (false: host local time) */
+  utc: boolean
+  windows: ServerWindow[]
+}
+
+/**
+ * Default server schedule settings (off, UTC, no windows)
+ */
+export const DEFAULT_GLOBAL_SERVER_SCHEDULE_SETTINGS: GlobalServerScheduleSettings = {
+  enabled: false,
+  utc: true,
+  windows: []
+}
+
+/**
+ * VATSIM event schedule
+ *
+ * The host fetches VATSIM events (and an optional facility calendar) and
+ * reminds before events at the active airport start (`event-reminder`
+ * event), offering to record them to the replay library.
+ */
+export interface GlobalEventScheduleSettings {
+  /** Fetch events and remind before those at the active airport */
+  enabled: boolean
+  /** Facility calendar (iCal, or JSON like the VATSIM events API) */
+  calendarUrl: string | null
+  /** Minutes before an event starts to remind */
+  remindMinutes: number
+  /** Show reminders as desktop notifications on the host */
+  desktop: boolean
+}
+
+/**
+ * Default event schedule settings (on, 30 minute reminders with desktop notifications)
+ */
+export const DEFAULT_GLOBAL_EVENT_SCHEDULE_SETTINGS: GlobalEventScheduleSettings = {
+  enabled: true,
+  calendarUrl: null,
+  remindMinutes: 30,
+  desktop: true
+}
+
+/**
+ * Density-based aircraft level of detail
+ *
+ * The host ranks aircraft by distance from the active airport and tells each
+ * client how to draw them (`modelDetail`): full livery models for the
+ * closest, generic models further out, billboards beyond. Budgets are for
+ * desktops and scaled down for tablets, phones and reduced model quality.
+ */
+export interface GlobalLodSettings {
+  /** Decide model detail by traffic density */
+  enabled: boolean
+  /** Aircraft drawn with their full livery model */
+  fullModels: number
+  /** Aircraft drawn with a 3D model (full or generic); the rest are billboards */
+  genericModels: number
+}
+
+/**
+ * Default level of detail settings (on, 100 full and 250 3D models on a desktop)
+ */
+export const DEFAULT_GLOBAL_LOD_SETTINGS: GlobalLodSettings = {
+  enabled: true,
+  fullModels: 100,
+  genericModels: 250
+}
+
+/**
+ * Field shown on a datablock line
+ * - 'callsign': callsign (airline code only in 'airline' datablock mode)
+ * - 'altitude': hundreds of feet (airborne only)
+ * - 'speed': groundspeed in tens of knots
+ * - 'gate': gate or stand the aircraft is at (when known)
+ * - 'verticalTrend': climb/descent arrow (airborne only)
+ * - 'freq': position the aircraft is talking to (e.g., "TWR", when known)
+ */
+export type DatablockField =
+  | 'callsign'
+  | 'type'
+  | 'altitude'
+  | 'speed'
+  | 'gate'
+  | 'departure'
+  | 'arrival'
+  | 'squawk'
+  | 'verticalTrend'
+  | 'freq'
+
+/**
+ * Datablock layout shared by every display
+ *
+ * Stored on the host and served at `/api/datablock`; changes are pushed to
+ * every connected display (`datablock-layout-changed`, `/api/datablock/ws`).
+ */
+export interface GlobalDatablockLayout {
+  /** Fields on each line, top to bottom (1-4 lines of up to 6 fields) */
+  lines: DatablockField[][]
+  /** Leader line length (0.5-5 in 0.5 increments, default: 2) */
+  leaderLength: number
+  /** Multiplier for each device's datablock font size (0.5-2, default: 1) */
+  fontScale: number
+  /** Color datablocks by the position the aircraft is talking to (default: false) */
+  colorByFrequency: boolean
+}
+
+/**
+ * Default datablock layout (callsign / type altitude speed)
+ */
+export const DEFAULT_GLOBAL_DATABLOCK_LAYOUT: GlobalDatablockLayout = {
+  lines: [['callsign'], ['type', 'altitude', 'speed']],
+  leaderLength: 2,
+  fontScale: 1,
+  colorByFrequency: false
 }
 
 /**
@@ -862,6 +1780,18 @@ export interface GlobalSettings {
      * Shared across all browser sessions
      */
     recentAirports: string[]
+    /**
+     * OpenAIP API key, used for airspace boundaries outside the US
+     * Empty string = US (FAA) airspace only
+     */
+    openaipApiKey: string
+
+    /**
+     * FAA NOTAM API credentials
+     * Empty = NOTAMs come from the public FAA NOTAM Search
+     */
+    notamClientId: string
+    notamClientSecret: string
   }
 
   /**
@@ -884,10 +1814,32 @@ export interface GlobalSettings {
      */
     authToken?: string
 
+    /**
+     * Optional token for admin endpoints (`/api/debug/*`), sent in the X-Admin-Token header
+     * Requests from the host PC itself don't need it
+     */
+    adminToken?: string
+
     /**
      * If true, only allow connections from local network (192.168.x.x, 10.x.x.x, 172.16-31.x.x)
      */
     requireLocalNetwork: boolean
+
+    /**
+     * How often batched vNAS aircraft updates are sent to the app and remote clients (Hz, 0.2-10, default: 2)
+     */
+    broadcastRateHz: number
+
+    /**
+     * Extra folders that symlinks in the mods and FSLTL folders may point into (e.g. a network share)
+     * Symlinks leading anywhere else are not served. Server restart required.
+     */
+    allowedFileRoots?: string[]
+
+    /**
+     * Access from outside the LAN without port forwarding (server restart required)
+     */
+    remoteAccess?: GlobalRemoteAccessSettings
   }
 
   /**
@@ -930,6 +1882,162 @@ export interface GlobalSettings {
    * Shared across all browsers/devices for consistent appearance
    */
   display: GlobalDisplaySettings
+
+  /**
+   * Weather feature settings (lightning feed)
+   * Shared across all browsers/devices
+   */
+  weather: GlobalWeatherSettings
+
+  /**
+   * Sky rendering settings (moon, stars, sun glare)
+   * Shared across all browsers/devices
+   */
+  celestial: GlobalCelestialSettings
+
+  /**
+   * Simulated clock (real time, offset from UTC or fixed time)
+   * Shared across all browsers/devices
+   */
+  clock: GlobalClockSettings
+
+  /**
+   * Instant replay buffer kept by the host
+   * Shared across all browsers/devices
+   */
+  replay: GlobalReplaySettings
+
+  /**
+   * Host log level
+   * Shared across all browsers/devices
+   */
+  diagnostics: GlobalDiagnosticsSettings
+
+  /**
+   * Keep the host's display awake during events
+   * Shared across all browsers/devices
+   */
+  power: GlobalPowerSettings
+
+  /**
+   * Start the desktop app at login
+   * Shared across all browsers/devices
+   */
+  autostart: GlobalAutostartSettings
+
+  /**
+   * App update channel, skipped version and deferral
+   * Shared across all browsers/devices
+   */
+  update: GlobalUpdateSettings
+
+  /**
+   * Content pack indexes and opted-in packs
+   * Shared across all browsers/devices
+   */
+  contentPacks: GlobalContentPackSettings
+
+  /**
+   * System-wide hotkeys (desktop app only)
+   * Shared across all browsers/devices
+   */
+  hotkeys: GlobalHotkeySettings
+
+  /**
+   * Gamepad camera input (desktop app only)
+   * Shared across all browsers/devices
+   */
+  gamepad: GlobalGamepadSettings
+
+  /**
+   * Offline terrain and imagery cache kept by the host
+   * Shared across all browsers/devices
+   */
+  tileCache: GlobalTileCacheSettings
+
+  /**
+   * Terrain and imagery providers (alternatives to Cesium Ion)
+   * Shared across all browsers/devices
+   */
+  mapProviders: GlobalMapProviderSettings
+
+  /**
+   * MIDI controller camera input (desktop app only)
+   * Shared across all browsers/devices
+   */
+  midi: GlobalMidiSettings
+
+  /**
+   * Display units, with named profiles browsers can pick
+   * Shared across all browsers/devices
+   */
+  units: GlobalUnitSettings
+
+  /**
+   * Notifications when VATSIM positions covering the airport come online or go offline
+   * Shared across all browsers/devices
+   */
+  atcNotifications: GlobalAtcNotificationSettings
+
+  /**
+   * Tower audio streams relayed through the host (/api/audio)
+   * Shared across all browsers/devices
+   */
+  audio: GlobalAudioRelaySettings
+
+  /**
+   * Weather and ATIS change notifications
+   * Shared across all browsers/devices
+   */
+  weatherAlerts: GlobalWeatherAlertSettings
+
+  /**
+   * Datablock lines, leader line length and font scale
+   * Shared across all browsers/devices (/api/datablock)
+   */
+  datablock: GlobalDatablockLayout
+
+  /**
+   * Runway configuration view presets
+   * Shared across all browsers/devices
+   */
+  viewPresets: GlobalViewPresetSettings
+
+  /**
+   * Multi-host federation (mirror another instance)
+   * Shared across all browsers/devices
+   */
+  federation: GlobalFederationSettings
+
+  /**
+   * UDP JSON output for external tools
+   * Shared across all browsers/devices
+   */
+  udpOutput: GlobalUdpOutputSettings
+
+  /**
+   * Aural alert sound pack
+   * Shared across all browsers/devices
+   */
+  alertSounds: GlobalAlertSoundSettings
+
+  /**
+   * Scheduled server operation windows
+   * Shared across all browsers/devices
+   */
+  serverSchedule: GlobalServerScheduleSettings
+
+  /**
+   * VATSIM event reminders
+   * Shared across all browsers/devices
+   */
+  eventSchedule: GlobalEventScheduleSettings
+
+  /**
+   * Density-based aircraft level of detail
+   * Shared across all browsers/devices
+   */
+  lod: GlobalLodSettings
 }
 
 /**
@@ -945,13 +2053,17 @@ export const DEFAULT_GLOBAL_SETTINGS: GlobalSettings = {
   },
   airports: {
     defaultIcao: '',
-    recentAirports: []
+    recentAirports: [],
+    openaipApiKey: '',
+    notamClientId: '',
+    notamClientSecret: ''
   },
   server: {
     port: 8765,
     enabled: false,
     authToken: undefined,
-    requireLocalNetwork: false
+    requireLocalNetwork: false,
+    broadcastRateHz: 2
   },
   realtraffic: {
     dataSource: 'vatsim',
@@ -960,7 +2072,33 @@ export const DEFAULT_GLOBAL_SETTINGS: GlobalSettings = {
     maxParkedAircraft: 50
   },
   viewports: DEFAULT_GLOBAL_VIEWPORT_SETTINGS,
-  display: DEFAULT_GLOBAL_DISPLAY_SETTINGS
+  display: DEFAULT_GLOBAL_DISPLAY_SETTINGS,
+  weather: DEFAULT_GLOBAL_WEATHER_SETTINGS,
+  celestial: DEFAULT_GLOBAL_CELESTIAL_SETTINGS,
+  clock: DEFAULT_GLOBAL_CLOCK_SETTINGS,
+  replay: DEFAULT_GLOBAL_REPLAY_SETTINGS,
+  diagnostics: DEFAULT_GLOBAL_DIAGNOSTICS_SETTINGS,
+  power: DEFAULT_GLOBAL_POWER_SETTINGS,
+  autostart: DEFAULT_GLOBAL_AUTOSTART_SETTINGS,
+  update: DEFAULT_GLOBAL_UPDATE_SETTINGS,
+  contentPacks: DEFAULT_GLOBAL_CONTENT_PACK_SETTINGS,
+  hotkeys: DEFAULT_GLOBAL_HOTKEY_SETTINGS,
+  gamepad: DEFAULT_GLOBAL_GAMEPAD_SETTINGS,
+  tileCache: DEFAULT_GLOBAL_TILE_CACHE_SETTINGS,
+  mapProviders: DEFAULT_GLOBAL_MAP_PROVIDER_SETTINGS,
+  midi: DEFAULT_GLOBAL_MIDI_SETTINGS,
+  units: DEFAULT_GLOBAL_UNIT_SETTINGS,
+  atcNotifications: DEFAULT_GLOBAL_ATC_NOTIFICATION_SETTINGS,
+  audio: DEFAULT_GLOBAL_AUDIO_RELAY_SETTINGS,
+  weatherAlerts: DEFAULT_GLOBAL_WEATHER_ALERT_SETTINGS,
+  datablock: DEFAULT_GLOBAL_DATABLOCK_LAYOUT,
+  viewPresets: DEFAULT_GLOBAL_VIEW_PRESET_SETTINGS,
+  federation: DEFAULT_GLOBAL_FEDERATION_SETTINGS,
+  udpOutput: DEFAULT_GLOBAL_UDP_OUTPUT_SETTINGS,
+  alertSounds: DEFAULT_GLOBAL_ALERT_SOUND_SETTINGS,
+  serverSchedule: DEFAULT_GLOBAL_SERVER_SCHEDULE_SETTINGS,
+  eventSchedule: DEFAULT_GLOBAL_EVENT_SCHEDULE_SETTINGS,
+  lod: DEFAULT_GLOBAL_LOD_SETTINGS
 }
 
 /**
@@ -1178,6 +2316,7 @@ export const DEFAULT_SETTINGS: Omit<SettingsStore, keyof {
     enableLighting: true,
     show3DBuildings: false,
     buildingQuality: 'low',
+    buildingSource: 'ion',
     timeMode: 'real',
     fixedTimeHour: 12
   },
@@ -1256,7 +2395,9 @@ export const DEFAULT_SETTINGS: Omit<SettingsStore, keyof {
     askToContributePositions: true,
     deviceOptimizationPromptDismissed: false,
     aircraftPanelWidth: 280,
-    aircraftPanelHeight: 0
+    aircraftPanelHeight: 0,
+    syncSelection: false,
+    selectionSourceName: ''
   },
   fsltl: {
     sourcePath: null,
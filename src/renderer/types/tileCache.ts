@@ -0,0 +1,49 @@
+/**
+ * Offline terrain and imagery cache types
+ *
+ * The host keeps Cesium terrain and imagery tiles on disk and can download
+ * them ahead of time for a radius around selected airports, or along the
+ * final approach corridors of runway ends.
+ */
+
+/** Tile download to start on the host (omitted fields use global settings) */
+export interface TilePrefetchRequest {
+  /** ICAO codes (default: `tileCache.airports`) */
+  airports?: string[]
+  /** Runway ends (e.g., "27R") whose final approach corridors to download instead of the radius */
+  approaches?: string[]
+  /**
+   * Radius around each airport in nautical miles (default: `tileCache.radiusNm`),
+   * or corridor length for approaches (default: `tileCache.approachCorridorNm`)
+   */
+  radiusNm?: number
+  /** Highest terrain zoom level to download (default: 14) */
+  maxTerrainZoom?: number
+  /** Highest imagery zoom level to download (default: 17) */
+  maxImageryZoom?: number
+}
+
+/** Progress of a tile download (also sent as `tile-cache-progress` events) */
+export interface TilePrefetchProgress {
+  airports: string[]
+  /** Runway ends whose approach corridors are downloaded (empty: radius around the airports) */
+  approaches: string[]
+  /** Airport currently being downloaded */
+  current: string | null
+  totalTiles: number
+  /** Tiles processed so far (cached, downloaded, missing upstream or failed) */
+  doneTiles: number
+  downloadedTiles: number
+  failedTiles: number
+  downloadedBytes: number
+  running: boolean
+  error: string | null
+}
+
+/** Cache size and last download on the host */
+export interface TileCacheStatus {
+  enabled: boolean
+  bytes: number
+  maxBytes: number
+  prefetch: TilePrefetchProgress | null
+}
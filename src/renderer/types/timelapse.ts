@@ -0,0 +1,43 @@
+/**
+ * Timelapse capture types
+ *
+ * The host schedules frame captures; the desktop window renders each frame
+ * and sends it back as a PNG.
+ */
+
+/** Timelapse to start on the host */
+export interface TimelapseOptions {
+  /** Folder on the host to store numbered PNG frames in */
+  folder: string
+  /** Seconds between frames */
+  intervalSecs: number
+  /** Assemble the frames into timelapse.mp4 with ffmpeg when stopped */
+  assembleVideo?: boolean
+  /** Video frame rate (default: 30) */
+  fps?: number
+}
+
+/** Current timelapse on the host */
+export interface TimelapseStatus {
+  active: boolean
+  folder: string | null
+  intervalSecs: number
+  framesSaved: number
+  /** Unix ms */
+  startedAt: number | null
+  assembleVideo: boolean
+}
+
+/** Frame capture request (`timelapse-capture` event) */
+export interface TimelapseCaptureRequest {
+  sessionId: number
+  frame: number
+}
+
+/** Result of a finished timelapse (`timelapse-finished` event) */
+export interface TimelapseFinished {
+  folder: string
+  framesSaved: number
+  videoPath: string | null
+  error: string | null
+}
@@ -227,3 +227,658 @@ export interface InterpolatedAircraftState extends AircraftState {
   // True if we have previous state data for interpolation, false if showing raw data
   isInterpolated: boolean
 }
+
+/**
+ * Aircraft in the host's traffic picture (`/api/traffic`)
+ */
+export interface BackendAircraft {
+  callsign: string
+  /** VATSIM member ID */
+  cid: number
+  latitude: number
+  longitude: number
+  /** Feet MSL */
+  altitudeFt: number
+  groundspeedKt: number
+  heading: number
+  /** Ground track (degrees true), derived from position history */
+  track: number | null
+  /** Feet per minute, derived from position history */
+  verticalRateFpm: number | null
+  squawk: string
+  aircraftType: string | null
+  departure: string | null
+  arrival: string | null
+  /** Squawk assigned by ATC */
+  assignedSquawk: string | null
+  /** Distance from the active airport (nm) */
+  distanceNm: number
+  onGround: boolean
+  /** Controller position the aircraft is tuned to (from the AFV transceivers feed) */
+  onFrequency: FrequencyContact | null
+  /** How this client should draw the aircraft (absent when level of detail is off) */
+  modelDetail?: ModelDetail
+}
+
+/**
+ * How an aircraft is drawn under the host's density-based level of detail:
+ * its full livery model, the type's generic model, or a billboard sprite
+ */
+export type ModelDetail = 'full' | 'generic' | 'billboard'
+
+/**
+ * Aircraft each detail is used for, closest first, on this client (`/api/lod`)
+ */
+export interface LodBudget {
+  full: number
+  /** Aircraft drawn with any 3D model (including `full`) */
+  models: number
+}
+
+/**
+ * Level of detail decisions for this client (`/api/lod`)
+ */
+export interface LodPolicy {
+  enabled: boolean
+  /** Aircraft seen in the last minute */
+  aircraftCount: number
+  budget: LodBudget | null
+  /** Detail by callsign */
+  aircraft: Record<string, ModelDetail>
+}
+
+/**
+ * Controller position an aircraft is talking to
+ */
+export interface FrequencyContact {
+  /** Position callsign (e.g., "BOS_TWR") */
+  callsign: string
+  /** MHz (e.g., "128.800") */
+  frequency: string
+  level: 'delivery' | 'ground' | 'tower' | 'approach' | 'center' | 'fss'
+}
+
+/**
+ * Host's traffic picture around the active airport (VATSIM data source only)
+ */
+export interface BackendTrafficSnapshot {
+  icao: string | null
+  /** Last successful poll (Unix ms) */
+  updatedAt: number | null
+  aircraft: BackendAircraft[]
+  /** Trail points added by this poll, by callsign */
+  trailPoints: Record<string, TrailPoint>
+}
+
+/**
+ * Position on an aircraft's trail (kept by the host)
+ */
+export interface TrailPoint {
+  /** Unix ms */
+  time: number
+  latitude: number
+  longitude: number
+  altitudeFt: number
+}
+
+/**
+ * Performance and dimensions of an aircraft type (/api/aircraft-types/{code})
+ */
+export interface AircraftTypeInfo {
+  /** ICAO type designator (e.g., "B738") */
+  icao: string
+  manufacturer: string | null
+  model: string | null
+  /** ICAO wake category */
+  wakeCategory: 'J' | 'H' | 'M' | 'L' | null
+  /** Typical speed over the threshold (knots) */
+  approachSpeedKt: number | null
+  /** Meters */
+  wingspanM: number | null
+  lengthM: number | null
+}
+
+/**
+ * Airline from the host's airline database (/api/airlines/{code})
+ */
+export interface Airline {
+  /** ICAO airline designator (e.g., "BAW") */
+  icao: string
+  iata: string | null
+  name: string
+  /** Radio telephony designator (e.g., "SPEEDBIRD") */
+  telephony: string | null
+  country: string | null
+}
+
+/**
+ * Callsign split into airline and flight number (/api/callsigns/{callsign})
+ */
+export interface DecodedCallsign {
+  callsign: string
+  airline: Airline | null
+  /** Flight number after the airline designator ("123" in "BAW123") */
+  flightNumber: string | null
+  /** Callsign as spoken on frequency ("SPEEDBIRD 123"; the callsign itself for non-airline flights) */
+  spoken: string
+}
+
+/**
+ * Photo of the airframe flying a callsign (/api/aircraft/{callsign}/photo)
+ * Credit the photographer and link back to the photo page when shown
+ */
+export interface AircraftPhoto {
+  callsign: string
+  registration: string
+  thumbnailUrl: string
+  largeUrl: string | null
+  /** Thumbnail size in pixels */
+  width: number | null
+  height: number | null
+  photographer: string
+  /** Photo page on the source site */
+  link: string
+  source: string
+}
+
+/**
+ * Recent trail of an aircraft, oldest point first
+ */
+export interface AircraftTrail {
+  callsign: string
+  points: TrailPoint[]
+}
+
+/**
+ * Emergency code (7500/7600/7700) or squawk not matching the assigned code
+ */
+export interface SquawkAlert {
+  callsign: string
+  kind: 'hijack' | 'radioFailure' | 'emergency' | 'mismatch'
+  /** Emergency codes are high priority */
+  priority: 'high' | 'normal'
+  squawk: string
+  /** Code assigned in the flight plan */
+  assignedSquawk: string | null
+  /** When the alert was first raised (Unix ms) */
+  since: number
+}
+
+/**
+ * Active squawk alerts at the host's airport, most urgent first
+ */
+export interface SquawkAlerts {
+  icao: string | null
+  /** Traffic picture the alerts are based on (Unix ms) */
+  updatedAt: number | null
+  alerts: SquawkAlert[]
+}
+
+/**
+ * Predicted conflict between two airborne aircraft (short-term conflict alert)
+ */
+export interface StcaAlert {
+  /** Callsigns involved (sorted) */
+  callsigns: [string, string]
+  /** Conflict within 60 seconds, or later */
+  severity: 'warning' | 'caution'
+  /** Seconds until separation is lost (0 = already lost) */
+  timeToConflictSecs: number
+  /** Closest predicted separation while in conflict */
+  minLateralNm: number
+  minVerticalFt: number
+  /** Current separation */
+  lateralNm: number
+  verticalFt: number
+  /** When the alert was first raised (Unix ms) */
+  since: number
+}
+
+/**
+ * Active short-term conflict alerts at the host's airport
+ */
+export interface StcaAlerts {
+  icao: string | null
+  /** Traffic picture the alerts are based on (Unix ms) */
+  updatedAt: number | null
+  alerts: StcaAlert[]
+}
+
+/**
+ * Where the host puts an aircraft's datablock (see label_layout.rs)
+ */
+export interface LabelPlacement {
+  /** Numpad direction from the aircraft (1-9 without 5) */
+  direction: 1 | 2 | 3 | 4 | 6 | 7 | 8 | 9
+  /** Leader line length as a multiple of the configured length */
+  leaderScale: number
+}
+
+/**
+ * Datablock placements worked out by the host, so every display resolves
+ * label clutter the same way
+ */
+export interface LabelLayout {
+  icao: string | null
+  /** Traffic picture the layout is based on (Unix ms) */
+  updatedAt: number | null
+  /** By callsign */
+  placements: Record<string, LabelPlacement>
+}
+
+/**
+ * Aircraft on a runway
+ */
+export interface RunwayOccupant {
+  callsign: string
+  /** When the aircraft entered the runway (Unix ms) */
+  since: number
+}
+
+/**
+ * Use of one runway end in the detection window
+ */
+export interface RunwayEndUse {
+  ident: string
+  arrivals: number
+  departures: number
+  /** Unix ms */
+  lastUsed: number
+}
+
+/**
+ * Runway configuration the host detected from traffic
+ * (`runway-config-changed` event when it changes)
+ */
+export interface RunwayConfiguration {
+  icao: string | null
+  /** Traffic picture the detection is based on (Unix ms) */
+  updatedAt: number | null
+  windowMinutes: number
+  /** Ends in use for landing (e.g., ["27L", "27R"]) */
+  arrivals: string[]
+  /** Ends in use for takeoff */
+  departures: string[]
+  /** Every end used in the window */
+  ends: RunwayEndUse[]
+  /** Cross-check against the METAR wind (null until a configuration is detected) */
+  wind: {
+    metar: string
+    calm: boolean
+    /** Ends the wind suggests */
+    suggested: string[]
+    /** Ends in use with too much tailwind */
+    tailwind: string[]
+    agrees: boolean
+  } | null
+}
+
+/**
+ * Runway occupancy at the host's airport
+ */
+export interface RunwayOccupancy {
+  icao: string | null
+  /** Traffic picture the occupancy is based on (Unix ms) */
+  updatedAt: number | null
+  runways: {
+    /** Combined designator (e.g., "09L/27R") */
+    runway: string
+    /** Aircraft on the runway, first to enter first */
+    occupiedBy: RunwayOccupant[]
+  }[]
+}
+
+/**
+ * Aircraft entering or leaving a runway (`runway-occupancy-changed`)
+ */
+export interface RunwayOccupancyEvent {
+  icao: string
+  runway: string
+  callsign: string
+  kind: 'entered' | 'exited'
+  /** Unix ms */
+  time: number
+  /** Time spent on the runway (exits only) */
+  occupiedSecs: number | null
+  /** Aircraft already on the runway (entries only; any means a possible incursion) */
+  alreadyOccupiedBy: string[]
+}
+
+/**
+ * Server-side declutter profile for the aircraft WebSockets (/api/vnas/ws,
+ * /api/replay/ws), sent as query parameters or `{ type: 'declutter', ... }`
+ */
+export interface DeclutterProfile {
+  /** Only aircraft within this distance of the active airport (nm) */
+  rangeNm?: number
+  /** Altitude band (feet MSL) */
+  minAltitudeFt?: number
+  maxAltitudeFt?: number
+  /** Only aircraft on the ground */
+  groundOnly?: boolean
+}
+
+/**
+ * Changed fields of a vNAS aircraft update, sent by /api/vnas/ws?delta=true
+ * (JSON, or MessagePack with encoding=msgpack). Absent fields are unchanged;
+ * the first update for a callsign is complete.
+ */
+export interface VnasAircraftDelta {
+  callsign: string
+  timestamp: number
+  lat?: number
+  lon?: number
+  altitude?: number
+  heading?: number
+  typeCode?: string | null
+  groundspeedKt?: number | null
+  verticalRateFpm?: number | null
+  trailPoint?: TrailPoint
+  modelDetail?: ModelDetail | null
+}
+
+/**
+ * Aircraft going around from short final (`go-around`, /api/go-arounds/ws)
+ */
+export interface GoAround {
+  icao: string
+  callsign: string
+  aircraftType: string | null
+  /** Runway end the approach was to (e.g., "27R") */
+  runway: string
+  /** Unix ms */
+  time: number
+  /** Lowest altitude before climbing away (feet MSL) */
+  lowestAltitudeFt: number
+}
+
+/**
+ * Go-arounds seen by the host in this session
+ */
+export interface GoArounds {
+  goArounds: GoAround[]
+}
+
+/**
+ * Aircraft holding short of or lined up on a runway, timed since it stopped there
+ */
+export interface HoldTimer {
+  callsign: string
+  kind: 'holdShort' | 'lineUp'
+  /** Runway end lined up on (e.g., "27R"), or the runway held short of (e.g., "09L/27R") */
+  runway: string
+  /** When the aircraft stopped there (Unix ms) */
+  since: number
+  /** When the controller should be reminded (Unix ms) */
+  remindAt: number
+}
+
+/**
+ * Running hold-short and line-up timers at the host's airport (`hold-timers`)
+ */
+export interface HoldTimers {
+  icao: string | null
+  /** Traffic picture the timers are based on (Unix ms) */
+  updatedAt: number | null
+  timers: HoldTimer[]
+}
+
+/**
+ * Arrival on a final approach, with its spacing behind the aircraft ahead
+ */
+export interface FinalSpacing {
+  callsign: string
+  aircraftType: string | null
+  /** ICAO wake category */
+  wakeCategory: 'J' | 'H' | 'M' | 'L'
+  distanceToThresholdNm: number
+  groundspeedKt: number
+  /** Aircraft ahead on the same final (null for the first arrival) */
+  leader: string | null
+  /** Distance behind the leader along the final */
+  inTrailNm: number | null
+  /** Wake minimum or minimum radar separation behind the leader */
+  requiredNm: number | null
+  /** Spacing when the leader crosses the threshold at current groundspeeds */
+  predictedNm: number | null
+  /** Below minima ("warning"), compression below minima predicted ("caution") */
+  status: 'ok' | 'caution' | 'warning'
+}
+
+/**
+ * Arrivals on one runway end's final, closest first
+ */
+export interface FinalApproach {
+  runway: string
+  arrivals: FinalSpacing[]
+}
+
+/**
+ * Final approach spacing at the host's airport
+ */
+export interface AtpaSnapshot {
+  icao: string | null
+  /** Traffic picture the spacing is based on (Unix ms) */
+  updatedAt: number | null
+  finals: FinalApproach[]
+}
+
+/**
+ * Arrival in a runway's sequence
+ */
+export interface SequencedArrival {
+  callsign: string
+  aircraftType: string | null
+  /** Distance still to fly to the threshold */
+  distanceNm: number
+  groundspeedKt: number
+  /** Estimated time over the threshold (host clock, Unix ms) */
+  eta: number
+  /** Seconds until the threshold */
+  etaSecs: number
+  /** Lined up on the runway's final */
+  onFinal: boolean
+  /** Seconds behind the previous arrival */
+  intervalSecs: number | null
+}
+
+/**
+ * Arrival sequence per runway end at the host's airport, first to land first
+ */
+export interface ArrivalSequence {
+  icao: string | null
+  /** Traffic picture the sequence is based on (host clock, Unix ms) */
+  updatedAt: number | null
+  runways: {
+    runway: string
+    arrivals: SequencedArrival[]
+  }[]
+}
+
+/**
+ * Departure wake timer running on a runway
+ */
+export interface WakeTimer {
+  /** Runway end departed from */
+  runway: string
+  /** Departure the timer runs behind */
+  callsign: string
+  aircraftType: string | null
+  /** ICAO wake category of the departure */
+  wakeCategory: 'J' | 'H'
+  /** Liftoff time (host clock, Unix ms) */
+  startedAt: number
+  /** When the next departure may roll (host clock, Unix ms) */
+  expiresAt: number
+  durationSecs: number
+}
+
+/**
+ * Running departure wake timers at the host's airport
+ */
+export interface WakeTimers {
+  icao: string | null
+  /** Traffic picture the timers are based on (host clock, Unix ms) */
+  updatedAt: number | null
+  timers: WakeTimer[]
+}
+
+/**
+ * Facility covering an airport (from VATSpy/SimAware data)
+ */
+export interface AtcFacility {
+  id: string
+  name: string
+  /** Controller callsign prefixes (e.g., ["BOS"] for BOS_CTR) */
+  callsignPrefixes: string[]
+}
+
+/**
+ * Online position covering an airport
+ */
+export interface CoveringPosition {
+  callsign: string
+  name: string
+  frequency: string
+  level: 'atis' | 'delivery' | 'ground' | 'tower' | 'approach' | 'center' | 'fss'
+  /** Facility id the position belongs to (airport ICAO, TRACON, FIR or UIR) */
+  facility: string
+}
+
+/**
+ * Transmission activity on one of the active airport's frequencies (AFV)
+ */
+export interface FrequencyActivity {
+  /** MHz as published by the controller (e.g., "118.250") */
+  frequency: string
+  /** Controller positions on the frequency */
+  positions: string[]
+  transmitting: boolean
+  /** Callsigns transmitting on the frequency */
+  transmitters: string[]
+}
+
+/**
+ * Transmission activity at the active airport (`frequency-activity` event, /api/afv/ws)
+ */
+export interface FrequencyActivitySnapshot {
+  icao: string | null
+  /** Unix ms */
+  updatedAt: number | null
+  frequencies: FrequencyActivity[]
+}
+
+/**
+ * Position covering the active airport that came online or went offline
+ * (`atc-change` event, /api/atc/ws)
+ */
+export interface AtcChange extends CoveringPosition {
+  icao: string
+  /** true: came online, false: went offline */
+  online: boolean
+  /** Unix ms */
+  time: number
+}
+
+/**
+ * Positions online at the active airport with recent changes (/api/atc/changes)
+ */
+export interface AtcWatchStatus {
+  icao: string | null
+  online: CoveringPosition[]
+  /** Most recent first */
+  changes: AtcChange[]
+}
+
+/**
+ * Who covers an airport (`/api/airports/{ICAO}/atc`)
+ */
+export interface AirportAtcCoverage {
+  facilities: {
+    icao: string
+    name: string | null
+    /** Callsign prefixes of the airport's own positions (ICAO and IATA/LID) */
+    callsignPrefixes: string[]
+    tracon: AtcFacility | null
+    /** FIR or ARTCC */
+    fir: AtcFacility | null
+    uir: AtcFacility | null
+  }
+  /** Online positions, from the airport's own up to FSS */
+  online: CoveringPosition[]
+}
+
+/**
+ * VATSIM member details from the member API (names are not published there)
+ */
+export interface VatsimMemberInfo {
+  cid: number
+  /** Controller rating id */
+  rating: number | null
+  /** Pilot rating bit flags */
+  pilotRating: number | null
+  region: string | null
+  division: string | null
+  subdivision: string | null
+  /** Registration date (ISO 8601) */
+  registered: string | null
+}
+
+/**
+ * Who is staffing a position (`/api/controllers`, `/api/controllers/{callsign or CID}`)
+ */
+export interface ControllerInfo {
+  callsign: string
+  cid: number | null
+  name: string | null
+  /** Rating short name (e.g., "S3", "C1") */
+  rating: string | null
+  /** Rating long name (e.g., "Senior Student") */
+  ratingName: string | null
+  frequency: string
+  /** Positions covering an airport only */
+  level: CoveringPosition['level'] | null
+  /** Facility id the position belongs to (positions covering an airport only) */
+  facility: string | null
+  /** Logon time (ISO 8601) */
+  logonTime: string | null
+  member: VatsimMemberInfo | null
+}
+
+/**
+ * Flight strip built from a VATSIM flight plan
+ */
+export interface Strip {
+  callsign: string
+  status: 'connected' | 'prefiled'
+  /** "I" (IFR) or "V" (VFR) */
+  flightRules: string
+  aircraftType: string | null
+  departure: string
+  arrival: string
+  /** Filed cruise altitude, as filed (e.g., "35000" or "FL350") */
+  cruiseAltitude: string
+  route: string
+  assignedSquawk: string | null
+  /** Estimated off-block time (Unix ms) */
+  eobt: number | null
+  /** Estimated arrival time (Unix ms) */
+  eta: number | null
+  /** Distance from the airport for connected pilots (nm) */
+  distanceNm: number | null
+  groundspeedKt: number | null
+  onGround: boolean
+}
+
+/**
+ * Departure and arrival strips for an airport (`/api/strips/{ICAO}`)
+ */
+export interface StripBay {
+  icao: string
+  /** Unix ms */
+  updatedAt: number
+  /** Sorted by off-block time */
+  departures: Strip[]
+  /** Sorted by ETA */
+  arrivals: Strip[]
+}
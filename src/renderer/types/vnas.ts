@@ -1,6 +1,8 @@
 // vNAS (Virtual Network Air Traffic Control System) Types
 // These types match the Rust types in src-tauri/src/vnas.rs
 
+import type { ModelDetail, TrailPoint } from './vatsim'
+
 /**
  * vNAS environment for connecting to different VATSIM environments
  */
@@ -35,6 +37,10 @@ export interface VnasAircraft {
   altitudeAgl: number       // Altitude above ground in meters
   voiceType: number         // 0=Unknown, 1=Full, 2=ReceiveOnly, 3=TextOnly
   timestamp: number         // Unix timestamp in ms
+  groundspeedKt: number | null    // Derived by the backend from position history
+  verticalRateFpm: number | null  // Derived by the backend from position history
+  trailPoint?: TrailPoint         // Trail point added by this update
+  modelDetail?: ModelDetail       // Level of detail for this client (remote clients only)
 }
 
 /**
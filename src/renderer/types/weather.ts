@@ -337,3 +337,100 @@ export interface InterpolatedWeather {
   /** Timestamp when interpolation was calculated (epoch ms) */
   calculatedAt: number
 }
+
+// ============================================================================
+// HOST SCENE PARAMETERS
+// ============================================================================
+
+/**
+ * Renderable weather parameters derived by the host from a decoded METAR
+ * (`/api/weather/{ICAO}/scene`, also emitted as the `weather-scene-changed` event)
+ */
+export interface WeatherScene {
+  icao: string
+  /** Observation time of the source METAR (epoch ms) */
+  observedAt: number | null
+  visibilityM: number
+  /** Cesium fog density (0 to 0.015) */
+  fogDensity: number
+  /** Sorted by altitude, at most four layers */
+  cloudLayers: {
+    /** Base in meters AGL */
+    altitudeM: number
+    /** Coverage fraction (0-1) */
+    coverage: number
+    cover: string
+    cloudType: string | null
+  }[]
+  precipitation: Precipitation[]
+  /** Precipitation particle multiplier (0.5-2.0) */
+  precipitationFactor: number
+  thunderstorm: boolean
+  wind: { direction: number | null; speedKt: number; gustKt: number | null } | null
+}
+
+/**
+ * Pressure altitude, density altitude and ISA deviation computed by the host
+ * from the current METAR (`/api/weather/{ICAO}/density-altitude`)
+ */
+export interface DensityAltitude {
+  icao: string
+  /** Observation time of the source METAR (epoch ms) */
+  observedAt: number | null
+  elevationFt: number
+  temperatureC: number
+  dewpointC: number | null
+  altimeterHpa: number
+  /** Pressure at the field (hPa) */
+  stationPressureHpa: number
+  pressureAltitudeFt: number
+  /** ISA temperature at the pressure altitude (°C) */
+  isaTemperatureC: number
+  /** Temperature above (positive) or below ISA (°C) */
+  isaDeviationC: number
+  densityAltitudeFt: number
+}
+
+// ============================================================================
+// CELESTIAL
+// ============================================================================
+
+/**
+ * Sun and moon positions computed by the host for an airport and time
+ * (`/api/celestial/{ICAO}` or `/api/celestial?lat=&lon=`)
+ */
+export interface Ephemeris {
+  latitude: number
+  longitude: number
+  /** Time the positions are for (epoch ms) */
+  time: number
+  /** Degrees true and degrees above the horizon */
+  sun: { azimuth: number; elevation: number }
+  moon: {
+    azimuth: number
+    elevation: number
+    /** Illuminated fraction (0 = new, 1 = full) */
+    illumination: number
+    /** Position in the lunar cycle (0 = new, 0.5 = full) */
+    phase: number
+    phaseName: string
+    distanceKm: number
+  }
+  twilight: 'day' | 'civil' | 'nautical' | 'astronomical' | 'night'
+}
+
+// ============================================================================
+// ALERTS
+// ============================================================================
+
+/**
+ * Weather or ATIS change at the active airport (`weather-alert` event)
+ */
+export interface WeatherAlert {
+  icao: string
+  kind: 'windShift' | 'altimeter' | 'atis' | 'datisMismatch'
+  title: string
+  message: string
+  /** Epoch ms */
+  time: number
+}
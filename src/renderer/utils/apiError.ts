@@ -0,0 +1,111 @@
+/**
+ * Typed API Errors
+ *
+ * Failed Tauri commands and HTTP API requests carry a machine-readable code
+ * next to the message (see error.rs): commands reject with
+ * `{ code, message }` and the API answers with the same JSON body and a
+ * matching status code. Both are turned into an `ApiError` so callers can
+ * branch on `error.code` instead of matching English messages.
+ *
+ * Commands and endpoints that still fail with a plain string become
+ * `internal` (or a code derived from the HTTP status).
+ */
+
+import { invoke, type InvokeArgs, type InvokeOptions } from '@tauri-apps/api/core'
+
+/** Error kinds, matching `Error::code()` in error.rs */
+export type ApiErrorCode =
+  | 'invalidInput'
+  | 'notFound'
+  | 'conflict'
+  | 'unauthorized'
+  | 'forbidden'
+  | 'upstream'
+  | 'io'
+  | 'internal'
+
+const CODES: readonly ApiErrorCode[] = [
+  'invalidInput',
+  'notFound',
+  'conflict',
+  'unauthorized',
+  'forbidden',
+  'upstream',
+  'io',
+  'internal',
+]
+
+export class ApiError extends Error {
+  readonly code: ApiErrorCode
+  /** HTTP status (API requests only) */
+  readonly status?: number
+
+  constructor(code: ApiErrorCode, message: string, status?: number) {
+    super(message)
+    this.name = 'ApiError'
+    this.code = code
+    this.status = status
+  }
+}
+
+function isCode(value: unknown): value is ApiErrorCode {
+  return typeof value === 'string' && (CODES as readonly string[]).includes(value)
+}
+
+/** Code for an HTTP status from an endpoint without a typed body */
+function codeForStatus(status: number): ApiErrorCode {
+  if (status === 400 || status === 413 || status === 422) return 'invalidInput'
+  if (status === 401) return 'unauthorized'
+  if (status === 403) return 'forbidden'
+  if (status === 404) return 'notFound'
+  if (status === 409) return 'conflict'
+  if (status === 502 || status === 503 || status === 504) return 'upstream'
+  return 'internal'
+}
+
+/**
+ * Normalize anything a command or request threw into an `ApiError`
+ */
+export function toApiError(error: unknown): ApiError {
+  if (error instanceof ApiError) return error
+  if (error && typeof error === 'object' && 'code' in error && isCode(error.code)) {
+    const message = 'message' in error ? String(error.message) : error.code
+    return new ApiError(error.code, message)
+  }
+  if (error instanceof Error) return new ApiError('internal', error.message)
+  return new ApiError('internal', String(error))
+}
+
+/**
+ * Invoke a Tauri command, rejecting with an `ApiError`
+ */
+export async function invokeCommand<T>(command: string, args?: InvokeArgs, options?: InvokeOptions): Promise<T> {
+  try {
+    return await invoke<T>(command, args, options)
+  } catch (error) {
+    throw toApiError(error)
+  }
+}
+
+/**
+ * `ApiError` for a failed HTTP API response (`fallback` when it has no message)
+ */
+export async function responseError(response: Response, fallback: string): Promise<ApiError> {
+  const text = await response.text().catch(() => '')
+  try {
+    const body = JSON.parse(text) as { code?: unknown; message?: unknown }
+    if (isCode(body.code)) {
+      return new ApiError(body.code, String(body.message ?? fallback), response.status)
+    }
+  } catch {
+    // Plain-text error
+  }
+  return new ApiError(codeForStatus(response.status), text || `${fallback}: ${response.status}`, response.status)
+}
+
+/**
+ * Check an error's kind (false for anything that isn't an `ApiError`)
+ */
+export function isApiError(error: unknown, code: ApiErrorCode): error is ApiError {
+  return error instanceof ApiError && error.code === code
+}
@@ -0,0 +1,85 @@
+/**
+ * Client Capability Negotiation
+ *
+ * In remote browser mode, reports this device's class, estimated bandwidth
+ * and the model codecs Cesium can decode to the host's
+ * `/api/client/capabilities`. The host remembers them in a cookie and tailors
+ * model variants, aircraft WebSocket format and update rate, and compression
+ * to this device (client_capabilities.rs).
+ */
+
+import { isRemoteMode } from './remoteMode'
+import { isIPad, isMobileDevice } from './deviceDetection'
+
+type DeviceClass = 'desktop' | 'tablet' | 'phone'
+
+type Codec = 'ktx2' | 'draco' | 'zstd'
+
+interface ClientCapabilities {
+  deviceClass: DeviceClass
+  /** Estimated downlink (kbit/s) */
+  bandwidthKbps: number | null
+  codecs: Codec[]
+}
+
+/** How the host serves this browser */
+export interface ClientConfig {
+  deviceClass: DeviceClass
+  modelQuality: 'full' | 'reduced'
+  /** Model variant suffixes tried in order (e.g. ["low", "ktx2"]) */
+  modelVariants: string[]
+  /** Minimum time between aircraft WebSocket updates (ms, 0 = every update) */
+  updateIntervalMs: number
+  wireFormat: { encoding: 'json' | 'msgpack'; delta: boolean }
+  zstd: boolean
+}
+
+function deviceClass(): DeviceClass {
+  if (isIPad()) return 'tablet'
+  if (!isMobileDevice()) return 'desktop'
+  return Math.min(window.screen.width, window.screen.height) >= 600 ? 'tablet' : 'phone'
+}
+
+function bandwidthKbps(): number | null {
+  // Network Information API (Chromium only); downlink is in Mbit/s
+  const connection = (navigator as unknown as { connection?: { downlink?: number } }).connection
+  return connection?.downlink ? Math.round(connection.downlink * 1000) : null
+}
+
+function codecs(): Codec[] {
+  // Cesium decodes Draco meshes in a worker and transcodes KTX2 (Basis)
+  // textures, which needs WebGL 2 for the compressed formats to be useful
+  const supported: Codec[] = ['draco']
+  if (document.createElement('canvas').getContext('webgl2')) {
+    supported.push('ktx2')
+  }
+  return supported
+}
+
+/**
+ * Report this device's capabilities to the host (remote mode only).
+ * Returns the configuration the host will use, or null.
+ */
+export async function negotiateClientCapabilities(): Promise<ClientConfig | null> {
+  if (!isRemoteMode()) return null
+
+  const capabilities: ClientCapabilities = {
+    deviceClass: deviceClass(),
+    bandwidthKbps: bandwidthKbps(),
+    codecs: codecs()
+  }
+  try {
+    const response = await fetch('/api/client/capabilities', {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify(capabilities)
+    })
+    if (!response.ok) return null
+    const config: ClientConfig = await response.json()
+    console.info('[Capabilities] Negotiated:', config)
+    return config
+  } catch (error) {
+    console.warn('[Capabilities] Negotiation failed:', error)
+    return null
+  }
+}
@@ -0,0 +1,108 @@
+/**
+ * Client Log Forwarding
+ *
+ * In remote browser mode, console errors and warnings (plus uncaught errors
+ * and unhandled promise rejections) are batched and posted to the host's
+ * `/api/client-logs`, which writes them into the host log with this device's
+ * address, browser and tab session ID. That makes issues on devices without
+ * developer tools (iPad Safari) visible in the host's log files.
+ */
+
+import { isRemoteMode } from './remoteMode'
+
+type ClientLogLevel = 'error' | 'warn'
+
+interface ClientLogEntry {
+  level: ClientLogLevel
+  message: string
+}
+
+/** How often queued messages are sent */
+const FLUSH_INTERVAL_MS = 5000
+
+/** Send immediately once this many messages are queued */
+const FLUSH_THRESHOLD = 20
+
+/** Messages kept while the host is unreachable (oldest are dropped) */
+const MAX_QUEUED = 200
+
+const sessionId = Math.random().toString(36).slice(2, 10)
+let queue: ClientLogEntry[] = []
+let installed = false
+
+function formatArg(arg: unknown): string {
+  if (typeof arg === 'string') return arg
+  if (arg instanceof Error) return arg.stack ?? `${arg.name}: ${arg.message}`
+  try {
+    return JSON.stringify(arg)
+  } catch {
+    return String(arg)
+  }
+}
+
+function enqueue(level: ClientLogLevel, args: unknown[]): void {
+  queue.push({ level, message: args.map(formatArg).join(' ') })
+  if (queue.length > MAX_QUEUED) {
+    queue = queue.slice(queue.length - MAX_QUEUED)
+  }
+  if (queue.length >= FLUSH_THRESHOLD) {
+    void flush()
+  }
+}
+
+function takeBatch(): string | null {
+  if (queue.length === 0) return null
+  const entries = queue
+  queue = []
+  return JSON.stringify({ sessionId, page: window.location.pathname, entries })
+}
+
+async function flush(): Promise<void> {
+  const body = takeBatch()
+  if (!body) return
+  try {
+    await fetch('/api/client-logs', {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body
+    })
+  } catch {
+    // Host unreachable: drop the batch rather than logging (which would queue again)
+  }
+}
+
+/**
+ * Start forwarding console errors and warnings to the host (remote mode only)
+ */
+export function installClientLogForwarding(): void {
+  if (installed || !isRemoteMode()) return
+  installed = true
+
+  const originalError = console.error
+  const originalWarn = console.warn
+  console.error = (...args: unknown[]) => {
+    enqueue('error', args)
+    originalError.apply(console, args)
+  }
+  console.warn = (...args: unknown[]) => {
+    enqueue('warn', args)
+    originalWarn.apply(console, args)
+  }
+
+  window.addEventListener('error', (event) => {
+    enqueue('error', [`Uncaught ${formatArg(event.error ?? event.message)} (${event.filename}:${event.lineno})`])
+  })
+  window.addEventListener('unhandledrejection', (event) => {
+    enqueue('error', ['Unhandled rejection:', event.reason])
+  })
+
+  // Send what's left when the page is hidden or closed
+  window.addEventListener('pagehide', () => {
+    const body = takeBatch()
+    if (body) {
+      navigator.sendBeacon('/api/client-logs', new Blob([body], { type: 'application/json' }))
+    }
+  })
+
+  setInterval(() => void flush(), FLUSH_INTERVAL_MS)
+}
@@ -0,0 +1,100 @@
+/**
+ * Datablock text formatting from the shared datablock layout
+ *
+ * @see GlobalDatablockLayout - Which fields each line shows
+ */
+
+import type { DatablockField, DatablockMode } from '../types'
+import type { FrequencyContact } from '../types/vatsim'
+
+/** Every field a datablock line can show, in the order offered in settings */
+export const DATABLOCK_FIELDS: DatablockField[] = [
+  'callsign',
+  'type',
+  'altitude',
+  'speed',
+  'gate',
+  'departure',
+  'arrival',
+  'squawk',
+  'verticalTrend',
+  'freq'
+]
+
+/** Vertical rate (ft/min) above which the climb/descent arrow shows */
+const VERTICAL_TREND_THRESHOLD_FPM = 300
+
+/** Values a datablock is built from */
+export interface DatablockValues {
+  callsign: string
+  aircraftType: string | null
+  altitudeFeet: number
+  groundspeedKnots: number
+  verticalRateFpm: number
+  isAirborne: boolean
+  gate?: string | null
+  departure: string | null
+  arrival: string | null
+  squawk: string | null
+  frequency?: FrequencyContact | null
+}
+
+/**
+ * Text of one datablock field (empty when it doesn't apply)
+ */
+export function formatDatablockField(field: DatablockField, values: DatablockValues, mode: DatablockMode): string {
+  switch (field) {
+    case 'callsign': {
+      if (mode === 'airline') {
+        // Airline flights (3 letters + 1-4 digits) show only the airline code; others (N12345) in full
+        const match = values.callsign.match(/^([A-Z]{3})\d{1,4}$/)
+        if (match) return match[1]
+      }
+      return values.callsign
+    }
+    case 'type':
+      return values.aircraftType || '????'
+    case 'altitude':
+      return values.isAirborne
+        ? Math.round(Math.max(0, values.altitudeFeet) / 100).toString().padStart(3, '0')
+        : ''
+    case 'speed':
+      return Math.round(values.groundspeedKnots / 10).toString().padStart(2, '0')
+    case 'gate':
+      return values.gate ?? ''
+    case 'departure':
+      return values.departure ?? ''
+    case 'arrival':
+      return values.arrival ?? ''
+    case 'squawk':
+      return values.squawk ?? ''
+    case 'verticalTrend':
+      if (!values.isAirborne) return ''
+      if (values.verticalRateFpm > VERTICAL_TREND_THRESHOLD_FPM) return '↑'
+      if (values.verticalRateFpm < -VERTICAL_TREND_THRESHOLD_FPM) return '↓'
+      return ''
+    case 'freq':
+      // Position suffix: BOS_TWR -> TWR, BOS_N_APP -> APP
+      return values.frequency?.callsign.split('_').pop() ?? ''
+  }
+}
+
+/**
+ * Datablock text for a layout: fields separated by spaces, lines by newlines.
+ * Fields that don't apply are skipped and empty lines dropped.
+ */
+export function formatDatablock(lines: DatablockField[][], values: DatablockValues, mode: DatablockMode): string {
+  return lines
+    .map((line) => line.map((field) => formatDatablockField(field, values, mode)).filter(Boolean).join(' '))
+    .filter(Boolean)
+    .join('\n')
+}
+
+/**
+ * Parse a space- or comma-separated list of field names, ignoring unknown ones
+ */
+export function parseDatablockLine(text: string): DatablockField[] {
+  return text
+    .split(/[\s,]+/)
+    .filter((name): name is DatablockField => DATABLOCK_FIELDS.includes(name as DatablockField))
+}
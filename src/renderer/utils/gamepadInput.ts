@@ -0,0 +1,51 @@
+/**
+ * Gamepad camera input
+ *
+ * The host reads USB joysticks and game controllers (the WebView's Gamepad
+ * API is unreliable) and sends the mapped stick deflection to the main window
+ * as `gamepad-input` events while a stick is moved. The latest values are kept
+ * here for the camera input loop (see addGamepadTargets in inputVelocity.ts).
+ */
+
+import { isTauri } from './tauriApi'
+
+/** Camera motion rates from the host, each -1 to 1 */
+export interface GamepadAxes {
+  forward: number
+  right: number
+  up: number
+  heading: number
+  pitch: number
+  /** Positive zooms in */
+  zoom: number
+}
+
+const NO_INPUT: GamepadAxes = { forward: 0, right: 0, up: 0, heading: 0, pitch: 0, zoom: 0 }
+
+let axes: GamepadAxes = NO_INPUT
+let installed = false
+
+/**
+ * Current gamepad deflection (all zero without a gamepad)
+ */
+export function getGamepadAxes(): GamepadAxes {
+  return axes
+}
+
+/**
+ * Start receiving gamepad input from the host (desktop main window only)
+ */
+export function installGamepadInput(): void {
+  if (installed || !isTauri()) return
+  installed = true
+
+  import('@tauri-apps/api/webviewWindow')
+    .then(({ getCurrentWebviewWindow }) => {
+      const current = getCurrentWebviewWindow()
+      if (current.label !== 'main') return
+      return current.listen<GamepadAxes>('gamepad-input', (event) => {
+        axes = event.payload
+      })
+    })
+    .catch((error) => console.error('[Gamepad] Failed to listen for gamepad input:', error))
+}
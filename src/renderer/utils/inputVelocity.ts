@@ -1,5 +1,7 @@
 // Input velocity utilities for smooth keyboard/mouse controls
 
+import type { GamepadAxes } from './gamepadInput'
+
 /**
  * State for velocity-based smooth movement
  */
@@ -212,6 +214,41 @@ export function calculateTargetVelocities(
   }
 }
 
+/**
+ * Add gamepad stick deflection (each -1 to 1, see utils/gamepadInput.ts) to
+ * the key targets, mapped like the keys for the current mode
+ */
+export function addGamepadTargets(
+  targets: TargetVelocities,
+  gamepad: GamepadAxes,
+  viewMode: '3d' | 'topdown',
+  followingCallsign: string | null,
+  followMode: 'tower' | 'orbit'
+): TargetVelocities {
+  const clamp = (value: number) => Math.max(-1, Math.min(1, value))
+  const inOrbitMode = followingCallsign !== null && followMode === 'orbit'
+  const result = { ...targets }
+
+  result.forward = clamp(result.forward + gamepad.forward)
+  result.right = clamp(result.right + gamepad.right)
+  result.up = clamp(result.up + gamepad.up)
+  if (inOrbitMode) {
+    result.orbitHeading = clamp(result.orbitHeading + gamepad.heading)
+    result.orbitPitch = clamp(result.orbitPitch + gamepad.pitch)
+  } else {
+    result.heading = clamp(result.heading + gamepad.heading)
+    result.pitch = clamp(result.pitch + gamepad.pitch)
+  }
+
+  // Positive gamepad zoom = zoom in (same conventions as the +/- keys)
+  if (viewMode === 'topdown') result.altitude = clamp(result.altitude - gamepad.zoom)
+  else if (inOrbitMode) result.orbitDistance = clamp(result.orbitDistance - gamepad.zoom)
+  else if (followingCallsign) result.zoom = clamp(result.zoom + gamepad.zoom)
+  else result.zoom = clamp(result.zoom - gamepad.zoom)
+
+  return result
+}
+
 /**
  * Apply wheel impulse to velocity state
  * Returns the decayed wheel impulse value
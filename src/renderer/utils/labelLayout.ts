@@ -38,6 +38,7 @@ export interface LabelAircraftData {
   aircraftScreenY: number  // Screen Y for leader line attachment
   modelRadius: number      // Radius of aircraft model on screen
   preferredPosition: DatablockPosition  // User's numpad choice
+  leaderScale?: number     // Leader length multiple (from the host's layout)
   hasCustomPosition: boolean  // True if user explicitly set position for this aircraft
   isFollowed: boolean      // True if camera is following this aircraft
   isAirborne: boolean      // True if aircraft is in the air
@@ -387,6 +388,7 @@ export function layoutLabels(
 
 /**
  * Simple layout without overlap avoidance - just use preferred positions
+ * (and leader scales, for placements the host already deconflicted)
  */
 export function layoutLabelsSimple(
   aircraftData: LabelAircraftData[],
@@ -396,7 +398,7 @@ export function layoutLabelsSimple(
     const preferredAngle = numpadToAngle(data.preferredPosition)
     // Distance = model radius + user's gap + label edge distance (varies by angle)
     const edgeDist = labelEdgeDistance(preferredAngle, config.labelWidth, config.labelHeight)
-    const distance = data.modelRadius + config.labelGap + edgeDist
+    const distance = data.modelRadius + config.labelGap * (data.leaderScale ?? 1) + edgeDist
 
     const pos = calculateLabelPosition(
       data.aircraftScreenX,
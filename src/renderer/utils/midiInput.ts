@@ -0,0 +1,59 @@
+/**
+ * MIDI controller camera input
+ *
+ * The host listens to a MIDI controller (see `midi` in global settings) and
+ * sends knob and fader positions to the main window as `midi-camera` events.
+ * Unlike gamepad sticks these are absolute: each position maps onto the full
+ * heading, pitch or zoom range of the active viewport.
+ */
+
+import { isTauri } from './tauriApi'
+import { useViewportStore } from '../stores/viewportStore'
+import { FOV_MAX, FOV_MIN, PITCH_MAX, PITCH_MIN, TOPDOWN_ALTITUDE_MAX, TOPDOWN_ALTITUDE_MIN } from '../constants/camera'
+
+/** Knob or fader position from the host */
+interface MidiCameraInput {
+  target: 'heading' | 'pitch' | 'zoom'
+  /** 0-1 */
+  value: number
+}
+
+let installed = false
+
+function applyInput({ target, value }: MidiCameraInput) {
+  const viewport = useViewportStore.getState()
+  switch (target) {
+    case 'heading':
+      viewport.setHeading(value * 360)
+      break
+    case 'pitch':
+      viewport.setPitch(PITCH_MIN + value * (PITCH_MAX - PITCH_MIN))
+      break
+    case 'zoom':
+      // Fader up zooms in
+      if (viewport.getActiveCameraState().viewMode === 'topdown') {
+        // Logarithmic so the low altitudes aren't squeezed into the bottom of the travel
+        const ratio = TOPDOWN_ALTITUDE_MIN / TOPDOWN_ALTITUDE_MAX
+        viewport.setTopdownAltitude(TOPDOWN_ALTITUDE_MAX * Math.pow(ratio, value))
+      } else {
+        viewport.setFov(FOV_MAX - value * (FOV_MAX - FOV_MIN))
+      }
+      break
+  }
+}
+
+/**
+ * Start receiving MIDI knob positions from the host (desktop main window only)
+ */
+export function installMidiInput(): void {
+  if (installed || !isTauri()) return
+  installed = true
+
+  import('@tauri-apps/api/webviewWindow')
+    .then(({ getCurrentWebviewWindow }) => {
+      const current = getCurrentWebviewWindow()
+      if (current.label !== 'main') return
+      return current.listen<MidiCameraInput>('midi-camera', (event) => applyInput(event.payload))
+    })
+    .catch((error) => console.error('[MIDI] Failed to listen for MIDI input:', error))
+}
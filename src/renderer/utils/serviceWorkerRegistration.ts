@@ -1,3 +1,5 @@
+import type { ContentManifest } from '../types/manifest'
+
 /**
  * Registers the tile caching service worker.
  * The service worker intercepts tile requests at the HTTP layer,
@@ -97,3 +99,38 @@ export async function getServiceWorkerCacheStats(): Promise<{ count: number; siz
     return { count: 0, sizeBytes: 0 }
   }
 }
+
+/**
+ * Fetches the host's content manifest and has the service worker drop cached
+ * mods, FSLTL models and overlays that changed since the last session.
+ * Returns the number of assets removed from the cache.
+ */
+export async function syncContentManifest(): Promise<number> {
+  const controller = navigator.serviceWorker?.controller
+  if (!controller) return 0
+
+  let manifest: ContentManifest
+  try {
+    const response = await fetch('/api/manifest')
+    if (!response.ok) return 0
+    manifest = await response.json()
+  } catch (error) {
+    console.warn('[ContentCache] Failed to fetch content manifest:', error)
+    return 0
+  }
+
+  return new Promise((resolve) => {
+    const channel = new MessageChannel()
+    channel.port1.onmessage = (event) => {
+      resolve(event.data.removed ?? 0)
+    }
+
+    controller.postMessage(
+      { type: 'SYNC_CONTENT_MANIFEST', manifest },
+      [channel.port2]
+    )
+
+    // Timeout after 30 seconds
+    setTimeout(() => resolve(0), 30000)
+  })
+}
@@ -0,0 +1,18 @@
+import type { GlobalClockSettings } from '../types'
+
+/**
+ * Current time on the shared simulated clock (epoch ms)
+ *
+ * @param clock - Simulated clock settings from global settings
+ * @param realNow - Real time to base the result on (default: now)
+ */
+export function getSimulatedTime(clock: GlobalClockSettings, realNow: number = Date.now()): number {
+  switch (clock.mode) {
+    case 'offset':
+      return realNow + clock.offsetMs
+    case 'fixed':
+      return clock.fixedTime ?? realNow
+    default:
+      return realNow
+  }
+}
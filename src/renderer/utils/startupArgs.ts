@@ -0,0 +1,59 @@
+/**
+ * Command-line startup state
+ *
+ * Applies `--airport` and `--bookmark` from the desktop app's command line
+ * once loading finishes, so shortcuts and scripts can launch straight into a
+ * configured view. (`--port`, `--fullscreen`, `--headless` and `--profile` are
+ * applied by the host before the window appears.)
+ */
+
+import { appApi } from './tauriApi'
+import { isRemoteMode } from './remoteMode'
+import { useAirportStore } from '../stores/airportStore'
+import { useViewportStore } from '../stores/viewportStore'
+
+/**
+ * Find a bookmark slot at the current airport by name (case-insensitive) or slot number
+ */
+export function findBookmarkSlot(icao: string, bookmark: string): number | null {
+  const bookmarks = useViewportStore.getState().airportViewportConfigs[icao]?.bookmarks ?? {}
+  const wanted = bookmark.trim().toLowerCase()
+  for (const [slot, saved] of Object.entries(bookmarks)) {
+    if (saved.name?.trim().toLowerCase() === wanted) return Number(slot)
+  }
+  const slot = Number(wanted)
+  return Number.isInteger(slot) && bookmarks[slot] ? slot : null
+}
+
+/**
+ * Select the airport and load the bookmark given on the command line (desktop only)
+ */
+export async function applyStartupArgs(): Promise<void> {
+  if (isRemoteMode()) return
+
+  let args
+  try {
+    args = await appApi.getStartupArgs()
+  } catch (error) {
+    console.warn('[StartupArgs] Failed to read command-line arguments:', error)
+    return
+  }
+  if (!args.airport) {
+    if (args.bookmark) console.warn('[StartupArgs] --bookmark needs --airport')
+    return
+  }
+
+  const airportStore = useAirportStore.getState()
+  if (!airportStore.airports.has(args.airport)) {
+    console.warn(`[StartupArgs] Unknown airport ${args.airport}`)
+    return
+  }
+  airportStore.selectAirport(args.airport)
+
+  if (args.bookmark) {
+    const slot = findBookmarkSlot(args.airport, args.bookmark)
+    if (slot === null || !useViewportStore.getState().loadBookmark(slot)) {
+      console.warn(`[StartupArgs] No bookmark "${args.bookmark}" at ${args.airport}`)
+    }
+  }
+}
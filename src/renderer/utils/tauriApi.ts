@@ -3,11 +3,47 @@
  * Provides a unified interface for Tauri commands and plugins
  */
 
-import { invoke } from '@tauri-apps/api/core'
 import { open } from '@tauri-apps/plugin-shell'
 import { getVersion } from '@tauri-apps/api/app'
-import type { GlobalSettings } from '@/types'
+import type { AlertSoundEvent, ControlActionType, GlobalSettings, HotkeyBinding, ImageryProviderType, KeepAwakeMode, UpdateChannel, ViewMode } from '@/types'
+import type { ApiVmrRule, StaticObject } from '../types/mod'
+import type { AirportCharts, AirportDbRecord, Annotation, AirportDbRunway, AirportNotams, AirportSearchResult, ApproachPath, Gate, GateSuggestion, LandingPrediction, TaxiRoute, VideoMapIndex } from '../types/airport'
+import type {
+  AircraftPhoto,
+  AircraftTrail,
+  AircraftTypeInfo,
+  Airline,
+  AirportAtcCoverage,
+  ArrivalSequence,
+  AtcWatchStatus,
+  AtpaSnapshot,
+  BackendTrafficSnapshot,
+  ControllerInfo,
+  DecodedCallsign,
+  GoArounds,
+  HoldTimers,
+  LabelLayout,
+  LodPolicy,
+  RunwayConfiguration,
+  RunwayOccupancy,
+  SquawkAlerts,
+  StcaAlerts,
+  StripBay,
+  WakeTimers
+} from '../types/vatsim'
+import type { ReplayExportData, ReplayServerControl, ReplayServerStatus, SessionExportFormat, SessionExportSource, SessionStats, SharedReplay, ShareReplayOptions } from '../types/replay'
+import type { TimelapseOptions, TimelapseStatus } from '../types/timelapse'
+import type { TilePrefetchProgress, TilePrefetchRequest, TileCacheStatus } from '../types/tileCache'
+import type { EventPrepRequest, EventPrepStatus } from '../types/eventPrep'
+import type { EventScheduleStatus, ScheduledEvent } from '../types/eventSchedule'
+import type { BuildingsInfo, ImageryVintage, MapProviderInfo, TerrainPackage } from '../types/mapProviders'
+import type { DensityAltitude, Ephemeris, WeatherScene } from '../types/weather'
+import type { HostFollowStatus, HostFollowTarget, SharedSelection } from '../types/camera'
+import type { Drawing, DrawingBoard, DrawingRequest, DrawingSessionInfo } from '../types/drawings'
+import type { CoordinationMessage } from '../types/coordination'
+import type { UnitPreferences } from '../types/settings'
 import { getApiBaseUrl } from './remoteMode'
+import { invokeCommand, isApiError, responseError } from './apiError'
 
 /**
  * Check if running in Tauri environment
@@ -36,7 +72,7 @@ export const modApi = {
    */
   getModsPath: async (modType: 'aircraft' | 'towers'): Promise<string> => {
     if (isTauri()) {
-      return invoke<string>('get_mods_path', { modType })
+      return invokeCommand<string>('get_mods_path', { modType })
     }
     // In browser mode, return virtual path that the server understands
     return `/api/mods/${modType}`
@@ -47,7 +83,7 @@ export const modApi = {
    */
   listModDirectories: async (modType: 'aircraft' | 'towers'): Promise<string[]> => {
     if (isTauri()) {
-      return invoke<string[]>('list_mod_directories', { modType })
+      return invokeCommand<string[]>('list_mod_directories', { modType })
     }
     // Fetch from HTTP API
     const response = await fetch(`/api/mods/${modType}`)
@@ -61,7 +97,7 @@ export const modApi = {
    */
   readModManifest: async <T = unknown>(path: string): Promise<T> => {
     if (isTauri()) {
-      return invoke<T>('read_mod_manifest', { path })
+      return invokeCommand<T>('read_mod_manifest', { path })
     }
     // In browser mode, fetch from API
     // Path is like /api/mods/aircraft/B738, we need to get the manifest
@@ -76,18 +112,31 @@ export const modApi = {
    */
   listVMRFiles: async (): Promise<string[]> => {
     if (isTauri()) {
-      return invoke<string[]>('list_vmr_files')
+      return invokeCommand<string[]>('list_vmr_files')
     }
     // In browser mode, VMR files are returned as parsed rules from the API
     return [] // Empty - VMR rules will be fetched separately
   },
 
+  /**
+   * Get parsed rules from all enabled VMR files (mods folder + remote subscriptions)
+   * Disabled files and rules are excluded
+   */
+  getVMRRules: async (): Promise<ApiVmrRule[]> => {
+    if (isTauri()) {
+      return invokeCommand<ApiVmrRule[]>('get_vmr_rules')
+    }
+    const response = await fetch('/api/vmr-rules')
+    if (!response.ok) throw new Error(`Failed to load VMR rules: ${response.status}`)
+    return response.json()
+  },
+
   /**
    * Read a text file (used for reading VMR files)
    */
   readTextFile: async (path: string): Promise<string> => {
     if (isTauri()) {
-      return invoke<string>('read_text_file', { path })
+      return invokeCommand<string>('read_text_file', { path })
     }
     // In browser mode, fetch from server
     const response = await fetch(path)
@@ -101,7 +150,7 @@ export const modApi = {
    */
   loadModelManifest: async <T = unknown>(modelPath: string): Promise<T | null> => {
     if (isTauri()) {
-      return invoke<T | null>('load_model_manifest', { modelPath })
+      return invokeCommand<T | null>('load_model_manifest', { modelPath })
     }
     try {
       const response = await fetch(`${modelPath}/manifest.json`)
@@ -120,7 +169,7 @@ export const modApi = {
    */
   readTowerPositions: async (): Promise<Record<string, unknown>> => {
     if (isTauri()) {
-      return invoke<Record<string, unknown>>('read_tower_positions')
+      return invokeCommand<Record<string, unknown>>('read_tower_positions')
     }
     const response = await fetch('/api/tower-positions')
     if (!response.ok) return {}
@@ -149,7 +198,7 @@ export const modApi = {
     }
   }): Promise<void> => {
     if (isTauri()) {
-      return invoke<void>('update_tower_position', { icao, position })
+      return invokeCommand<void>('update_tower_position', { icao, position })
     }
     // In browser mode, PUT to server API
     const response = await fetch(`/api/tower-positions/${encodeURIComponent(icao)}`, {
@@ -163,6 +212,126 @@ export const modApi = {
   }
 }
 
+/**
+ * Static object placements API
+ * GLB models from mods/objects placed per airport in mods/placements/{ICAO}.json
+ */
+export const placementsApi = {
+  /**
+   * List the models in mods/objects (paths relative to it)
+   */
+  listModels: async (): Promise<string[]> => {
+    if (isTauri()) {
+      return invokeCommand<string[]>('list_static_object_models')
+    }
+    const response = await fetch('/api/mods/objects')
+    if (!response.ok) return []
+    return response.json()
+  },
+
+  /**
+   * URL a placed object's model loads from
+   */
+  getModelUrl: async (model: string): Promise<string> => {
+    if (isTauri()) {
+      const objectsPath = await invokeCommand<string>('get_mods_path', { modType: 'objects' })
+      return convertToAssetUrl(`${objectsPath}/${model}`)
+    }
+    return `${getApiBaseUrl()}/api/mods/objects/${model.split('/').map(encodeURIComponent).join('/')}`
+  },
+
+  /**
+   * Get the static objects placed at an airport
+   */
+  getObjects: async (icao: string): Promise<StaticObject[]> => {
+    if (isTauri()) {
+      return invokeCommand<StaticObject[]>('get_static_objects', { icao })
+    }
+    const response = await fetch(`/api/placements/${encodeURIComponent(icao)}`)
+    if (!response.ok) throw new Error(`Failed to load placements: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Place (or move) a static object, returning the airport's objects
+   */
+  saveObject: async (icao: string, object: StaticObject): Promise<StaticObject[]> => {
+    if (isTauri()) {
+      return invokeCommand<StaticObject[]>('save_static_object', { icao, object })
+    }
+    const response = await fetch(`/api/placements/${encodeURIComponent(icao)}/${encodeURIComponent(object.id)}`, {
+      method: 'PUT',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify(object)
+    })
+    if (!response.ok) throw await responseError(response, 'Failed to save object')
+    return response.json()
+  },
+
+  /**
+   * Remove a static object, returning the airport's remaining objects
+   */
+  deleteObject: async (icao: string, id: string): Promise<StaticObject[]> => {
+    if (isTauri()) {
+      return invokeCommand<StaticObject[]>('delete_static_object', { icao, id })
+    }
+    const response = await fetch(`/api/placements/${encodeURIComponent(icao)}/${encodeURIComponent(id)}`, {
+      method: 'DELETE'
+    })
+    if (!response.ok) throw await responseError(response, 'Failed to delete object')
+    return response.json()
+  }
+}
+
+/**
+ * Airport Annotations API
+ * Hotspots, ILS critical areas and non-movement boundaries drawn by the facility,
+ * stored on the host and also listed by the overlays API
+ */
+export const annotationsApi = {
+  /**
+   * Get the annotations drawn at an airport
+   */
+  getAnnotations: async (icao: string): Promise<Annotation[]> => {
+    if (isTauri()) {
+      return invokeCommand<Annotation[]>('get_airport_annotations', { icao })
+    }
+    const response = await fetch(`/api/annotations/${encodeURIComponent(icao)}`)
+    if (!response.ok) throw new Error(`Failed to load annotations: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Add (or edit) an annotation, returning the airport's annotations
+   */
+  saveAnnotation: async (icao: string, annotation: Annotation): Promise<Annotation[]> => {
+    if (isTauri()) {
+      return invokeCommand<Annotation[]>('save_airport_annotation', { icao, annotation })
+    }
+    const response = await fetch(`/api/annotations/${encodeURIComponent(icao)}/${encodeURIComponent(annotation.id)}`, {
+      method: 'PUT',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify(annotation)
+    })
+    if (!response.ok) throw await responseError(response, 'Failed to save annotation')
+    return response.json()
+  },
+
+  /**
+   * Remove an annotation, returning the airport's remaining annotations
+   */
+  deleteAnnotation: async (icao: string, id: string): Promise<Annotation[]> => {
+    if (isTauri()) {
+      return invokeCommand<Annotation[]>('delete_airport_annotation', { icao, id })
+    }
+    const response = await fetch(`/api/annotations/${encodeURIComponent(icao)}/${encodeURIComponent(id)}`, {
+      method: 'DELETE'
+    })
+    if (!response.ok) throw await responseError(response, 'Failed to delete annotation')
+    return response.json()
+  }
+}
+
 /**
  * Global Settings API
  * Settings stored on host file system, shared across all browsers/devices
@@ -174,7 +343,7 @@ export const globalSettingsApi = {
    */
   getPath: async (): Promise<string> => {
     if (isTauri()) {
-      return invoke<string>('get_global_settings_path')
+      return invokeCommand<string>('get_global_settings_path')
     }
     return '(remote browser - settings on host)'
   },
@@ -185,7 +354,7 @@ export const globalSettingsApi = {
    */
   read: async (): Promise<GlobalSettings> => {
     if (isTauri()) {
-      return invoke<GlobalSettings>('read_global_settings')
+      return invokeCommand<GlobalSettings>('read_global_settings')
     }
     // Fetch from HTTP API
     const response = await fetch('/api/global-settings')
@@ -201,7 +370,7 @@ export const globalSettingsApi = {
    */
   write: async (settings: GlobalSettings): Promise<void> => {
     if (isTauri()) {
-      return invoke<void>('write_global_settings', { settings })
+      return invokeCommand<void>('write_global_settings', { settings })
     }
     // In browser mode, POST to server API
     const response = await fetch('/api/global-settings', {
@@ -215,6 +384,460 @@ export const globalSettingsApi = {
   }
 }
 
+/**
+ * Command-line arguments the desktop app was started with
+ * (`--airport KBOS --bookmark "Tower North" --port 9000 --fullscreen --profile event`)
+ */
+export interface StartupArgs {
+  airport: string | null
+  /** Bookmark name or slot number */
+  bookmark: string | null
+  port: number | null
+  fullscreen: boolean
+  /** Started without a window, with the HTTP server on */
+  headless: boolean
+  profile: string | null
+}
+
+/**
+ * Whether the desktop app starts at login
+ */
+export interface AutostartStatus {
+  enabled: boolean
+  headless: boolean
+  /** The OS has an up-to-date entry for this executable */
+  registered: boolean
+  /** Last error registering with the OS */
+  error: string | null
+}
+
+/**
+ * Content pack kind (where its content is used)
+ */
+export type ContentPackKind = 'vmr' | 'tower-positions' | 'video-maps' | 'airport-db'
+
+/**
+ * A content pack listed in an index or installed
+ */
+export interface ContentPackStatus {
+  id: string
+  name: string
+  description: string | null
+  kind: ContentPackKind
+  /** Index the pack is listed in (null: installed but no longer listed) */
+  indexUrl: string | null
+  /** Version in the index */
+  version: string | null
+  installedVersion: string | null
+  /** Opted in */
+  enabled: boolean
+  /** Last install error */
+  error: string | null
+}
+
+/**
+ * Content packs and index errors
+ */
+export interface ContentPacks {
+  packs: ContentPackStatus[]
+  /** Indexes that couldn't be read at the last check, with the error */
+  indexErrors: Record<string, string>
+}
+
+/**
+ * Update found on the configured channel
+ */
+export interface AvailableUpdate {
+  version: string
+  currentVersion: string
+  date: string | null
+  /** Release notes of this version */
+  body: string | null
+  channel: UpdateChannel
+  /** The user skipped this version (only offered by a manual check) */
+  skipped: boolean
+}
+
+/**
+ * Release notes of one GitHub release
+ */
+export interface ReleaseNotes {
+  version: string
+  name: string | null
+  date: string | null
+  /** Markdown */
+  body: string
+  prerelease: boolean
+  /** Release page */
+  url: string
+}
+
+/**
+ * Result of importing from the old Electron version
+ */
+export interface ElectronImportReport {
+  /** Electron data folder, if one was found */
+  source: string | null
+  /** One line per imported item */
+  migrated: string[]
+  /** Items found but not imported, with the reason */
+  skipped: string[]
+  /** The Electron settings store, for the per-browser settings */
+  localSettings: Record<string, unknown> | null
+}
+
+/**
+ * An app data backup archive
+ */
+export interface BackupInfo {
+  path: string
+  fileName: string
+  /** Archive size in bytes */
+  size: number
+  /** Unix ms */
+  createdAt: number
+  /** From the manifest, once the archive was created or verified */
+  appVersion: string | null
+  includeLogs: boolean | null
+  fileCount: number | null
+}
+
+/**
+ * A completed restore
+ */
+export interface RestoreResult {
+  restored: BackupInfo
+  /** Backup of the data that was replaced */
+  previous: BackupInfo
+}
+
+/**
+ * Whether the host is currently keeping the display awake
+ */
+export interface KeepAwakeStatus {
+  mode: KeepAwakeMode
+  active: boolean
+  /** Why, when active (e.g. 'replay playing') */
+  reason: string | null
+  /** Last error acquiring the sleep inhibitor */
+  error: string | null
+}
+
+/**
+ * Registration result for one system-wide hotkey
+ */
+export interface HotkeyStatus extends HotkeyBinding {
+  registered: boolean
+  /** Why it couldn't be registered (invalid shortcut, taken by another app) */
+  error: string | null
+}
+
+/**
+ * Host gamepad reader status
+ */
+export interface GamepadStatus {
+  enabled: boolean
+  /** Names of connected controllers */
+  gamepads: string[]
+  /** Why gamepads can't be read on this system */
+  error: string | null
+}
+
+/**
+ * Host MIDI input status
+ */
+export interface MidiStatus {
+  enabled: boolean
+  /** Available input ports */
+  ports: string[]
+  /** Port currently listened to */
+  connected: string | null
+  error: string | null
+}
+
+/**
+ * Action run by a control surface or hotkey (`control-action` event for window actions)
+ */
+export interface ControlAction {
+  action: ControlActionType
+  /** Bookmark name or slot, airport ICAO or view mode */
+  value: string | null
+}
+
+/**
+ * What the main window shows, reported to the host for control surfaces
+ */
+export interface ControlView {
+  airport: string | null
+  viewMode: ViewMode | null
+  bookmarks: { slot: number; name: string | null }[]
+}
+
+/**
+ * Detected runway configuration and the bookmark tagged for it
+ * (`view-preset-suggestion` event when it changes)
+ */
+export interface ViewPresetSuggestion {
+  icao: string
+  /** Runway ends in use (e.g., ["04L", "04R"]) */
+  runways: string[]
+  /** Named by the ATIS, seen in traffic or suggested from the wind */
+  source: 'atis' | 'traffic' | 'wind'
+  bookmark: { slot: number; name: string | null } | null
+  /** The bookmark was loaded automatically (autoSwitch) */
+  switched: boolean
+  /** Unix ms */
+  time: number
+}
+
+/**
+ * VATSIM ATIS runways against the real-world D-ATIS at the active airport
+ * (`atis-comparison` event when it changes, null when there is nothing to compare)
+ */
+export interface AtisComparison {
+  icao: string
+  /** Runway ends the VATSIM ATIS names as in use */
+  vatsimRunways: string[]
+  /** VATSIM ATIS letter(s), e.g. "A" or "A/C" */
+  vatsimLetter: string | null
+  /** Runway ends the real-world D-ATIS names as in use */
+  realRunways: string[]
+  /** D-ATIS letter(s), e.g. "B" or "B/D" for separate arrival and departure ATIS */
+  realLetter: string | null
+  matches: boolean
+  vatsimOnly: string[]
+  realOnly: string[]
+  /** Unix ms */
+  checkedAt: number
+}
+
+/**
+ * Connection status to the data host this instance mirrors (federation)
+ */
+export interface FederationStatus {
+  enabled: boolean
+  /** Data host address */
+  url: string | null
+  connected: boolean
+  /** Unix ms of the last aircraft batch from the data host */
+  lastUpdate: number | null
+  /** Aircraft in the last batch */
+  aircraft: number
+  /** Unix ms the data host's settings were last copied */
+  settingsMirroredAt: number | null
+  error: string | null
+}
+
+/**
+ * Scheduled server operation windows (see `serverSchedule` in the global settings)
+ */
+export interface ServerScheduleStatus {
+  enabled: boolean
+  /** Window open now */
+  activeWindow: string | null
+  /** Unix ms the open window closes */
+  activeUntil: number | null
+  /** The scheduler started the server for the open window (and stops it afterwards) */
+  startedServer: boolean
+  /** The open window is recording traffic into the replay library */
+  recording: boolean
+  nextWindow: string | null
+  /** Unix ms the next window opens */
+  nextStart: number | null
+}
+
+/**
+ * A script loaded from mods/scripts (see docs/scripting.md)
+ */
+export interface ScriptInfo {
+  /** File name (e.g., "gate-board.rhai") */
+  name: string
+  /** Events the script subscribes to ('traffic', 'alert', 'weather') */
+  handlers: string[]
+  /** Compile error or last runtime error */
+  error: string | null
+}
+
+/**
+ * Custom event emitted by a script (`script-event`, /api/scripts/ws)
+ */
+export interface ScriptEvent {
+  /** Script that emitted it */
+  script: string
+  name: string
+  data: unknown
+  /** Unix ms */
+  time: number
+}
+
+/**
+ * A plugin in mods/plugins and the routes it serves under /api/ext/{plugin}/
+ */
+export interface PluginInfo {
+  id: string
+  name: string
+  author: string | null
+  version: string | null
+  description: string | null
+  routes: { url: string; kind: 'file' | 'dir' | 'json' }[]
+  /** Manifest or route error (the plugin serves nothing) */
+  error?: string
+}
+
+/**
+ * What a content search result is
+ */
+export type SearchKind = 'airport' | 'bookmark' | 'mod' | 'model' | 'aircraft'
+
+/**
+ * Content search result (quick switcher)
+ */
+export interface SearchResult {
+  kind: SearchKind
+  /** Airport ICAO, "{ICAO}/{slot}" for bookmarks, mod id, model path or callsign */
+  id: string
+  title: string
+  subtitle: string | null
+  /** Airport the result belongs to (airports, bookmarks, tower mods) */
+  icao: string | null
+  /** Relevance (higher is better) */
+  score: number
+}
+
+/**
+ * Airport pack manifest (see airport_packs.rs)
+ */
+export interface AirportPackManifest {
+  format: number
+  icao: string
+  name: string | null
+  author: string | null
+  version: string | null
+  description: string | null
+  /** Mod ids enabled on install (e.g., "towers/KBOS") */
+  preferredMods: string[]
+  /** Mod ids disabled on install */
+  excludedMods: string[]
+  /** Unix ms */
+  createdAt: number
+}
+
+/**
+ * Manifest metadata for an exported airport pack
+ */
+export interface AirportPackExportOptions {
+  name?: string
+  author?: string
+  version?: string
+  description?: string
+  /** Default: enabled tower mods for the airport */
+  preferredMods?: string[]
+  excludedMods?: string[]
+}
+
+/**
+ * An exported airport pack
+ */
+export interface AirportPackInfo {
+  /** Archive or folder */
+  path: string
+  manifest: AirportPackManifest
+  /** Files in the pack (relative paths) */
+  files: string[]
+}
+
+/**
+ * An installed airport pack (also emitted as `airport-pack-installed`)
+ */
+export interface AirportPackInstallResult {
+  manifest: AirportPackManifest
+  /** Pieces installed ("tower position", "3 overlays", ...) */
+  installed: string[]
+  /** Preferred or excluded mods that aren't installed on the host */
+  missingMods: string[]
+}
+
+/**
+ * Where one window sits in a saved window layout
+ */
+export interface WindowPlacement {
+  /** Window label ('main' or 'cab-N') */
+  label: string
+  monitor: string | null
+  /** Top-left corner relative to the monitor (physical pixels) */
+  x: number
+  y: number
+  width: number
+  height: number
+  fullscreen: boolean
+  maximized: boolean
+  /** Camera bookmark (name or slot) loaded in this window */
+  bookmark: string | null
+}
+
+/**
+ * A named multi-window layout
+ */
+export interface WindowLayout {
+  name: string
+  windows: WindowPlacement[]
+  /** Unix ms */
+  savedAt: number
+}
+
+/**
+ * One timed startup phase on the host
+ */
+export interface StartupPhase {
+  name: string
+  /** Milliseconds after launch the phase started */
+  startedMs: number
+  durationMs: number
+  /** Ran after the window appeared (first use or background work) */
+  deferred: boolean
+}
+
+/**
+ * Startup timings on the host, in the order phases finished
+ */
+export interface StartupProfile {
+  uptimeMs: number
+  phases: StartupPhase[]
+}
+
+/**
+ * Run heartbeat a background task reports on the host
+ */
+export interface TaskHeartbeat {
+  name: string
+  runs: number
+  /** Seconds since the task last ran */
+  lastRunAgeS: number
+  expectedIntervalS: number
+  healthy: boolean
+  lastError: string | null
+}
+
+/**
+ * Background task on the host, restarted by its supervisor when it fails (see supervisor.rs)
+ */
+export interface TaskStatus {
+  name: string
+  state: 'running' | 'restarting' | 'finished'
+  /** 'always' for loops that run for the app's lifetime, 'onPanic' for work that may finish */
+  restart: 'always' | 'onPanic'
+  restarts: number
+  /** Seconds since the task last started */
+  uptimeS: number
+  lastFailure: string | null
+  /** Unix ms */
+  lastFailureAt: number | null
+  /** Seconds until the next start while restarting */
+  restartInS: number | null
+  heartbeat: TaskHeartbeat | null
+  healthy: boolean
+}
+
 /**
  * HTTP Server status info
  */
@@ -223,6 +846,25 @@ export interface ServerStatus {
   port: number
   localUrl: string | null
   lanUrl: string | null
+  /** IPv6 LAN URL, when the LAN URL is IPv4 and the host also has IPv6 */
+  lanUrlV6: string | null
+}
+
+/**
+ * A write made through the HTTP API (see audit_log.rs)
+ */
+export interface AuditEntry {
+  /** Unix ms */
+  timestamp: number
+  /** IP and browser of the client */
+  client: string
+  method: string
+  /** Path and query */
+  path: string
+  /** HTTP status of the response */
+  status: number
+  /** What changed, one line per change */
+  summary: string[]
 }
 
 /**
@@ -233,46 +875,1994 @@ export const httpServerApi = {
    * Start the HTTP server on the specified port
    */
   start: (port: number): Promise<ServerStatus> =>
-    invoke<ServerStatus>('start_http_server', { port }),
+    invokeCommand<ServerStatus>('start_http_server', { port }),
 
   /**
    * Stop the HTTP server
    */
   stop: (): Promise<void> =>
-    invoke<void>('stop_http_server'),
+    invokeCommand<void>('stop_http_server'),
 
   /**
    * Get the current server status
    */
   getStatus: (): Promise<ServerStatus> =>
-    invoke<ServerStatus>('get_http_server_status')
-}
+    invokeCommand<ServerStatus>('get_http_server_status'),
 
-/**
- * Shell/external link API
- */
-export const shellApi = {
   /**
-   * Open a URL in the system default browser
-   * In browser mode, uses window.open as fallback
+   * Get the latest writes made by remote clients, newest first
    */
-  openExternal: async (url: string): Promise<void> => {
+  getAuditLog: (limit?: number): Promise<AuditEntry[]> =>
+    invokeCommand<AuditEntry[]>('get_audit_log', { limit: limit ?? null }),
+
+  /**
+   * Get the connection status to the data host this instance mirrors
+   */
+  getFederationStatus: async (): Promise<FederationStatus> => {
     if (isTauri()) {
-      return open(url)
+      return invokeCommand<FederationStatus>('get_federation_status')
     }
-    // In browser mode, use window.open
-    window.open(url, '_blank', 'noopener,noreferrer')
+    const response = await fetch('/api/federation/status')
+    if (!response.ok) throw new Error(`Failed to get federation status: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get the scheduled server window open now and the next one
+   */
+  getScheduleStatus: async (): Promise<ServerScheduleStatus> => {
+    if (isTauri()) {
+      return invokeCommand<ServerScheduleStatus>('get_server_schedule_status')
+    }
+    const response = await fetch('/api/schedule')
+    if (!response.ok) throw await responseError(response, 'Failed to get server schedule')
+    return response.json()
   }
 }
 
 /**
- * App info API
+ * Host weather API
  */
-export const appApi = {
+export const weatherApi = {
   /**
-   * Get the app version from Tauri config
+   * Tell the host which airport's METAR to keep fresh (emits 'weather-changed' events)
+   * No-op in browser mode
    */
-  getVersion
+  setActiveAirport: async (icao: string | null): Promise<void> => {
+    if (isTauri()) {
+      await invokeCommand('set_weather_airport', { icao })
+    }
+  },
+
+  /**
+   * Get renderable scene parameters (visibility, fog, clouds, precipitation) for a station,
+   * optionally at a past time (epoch ms) from archived METARs when replaying
+   * Returns null if the station has no METAR
+   */
+  getScene: async (icao: string, at?: number): Promise<WeatherScene | null> => {
+    if (isTauri()) {
+      return invokeCommand<WeatherScene | null>('get_weather_scene', { icao, at: at ?? null })
+    }
+    const query = at !== undefined ? `?at=${at}` : ''
+    const response = await fetch(`/api/weather/${encodeURIComponent(icao)}/scene${query}`)
+    if (response.status === 404) return null
+    if (!response.ok) throw new Error(`Failed to load weather scene: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get pressure altitude, density altitude and ISA deviation for a station
+   * Returns null if the station has no usable METAR (missing temperature or altimeter)
+   */
+  getDensityAltitude: async (icao: string): Promise<DensityAltitude | null> => {
+    if (isTauri()) {
+      return invokeCommand<DensityAltitude | null>('get_station_density_altitude', { icao })
+    }
+    const response = await fetch(`/api/weather/${encodeURIComponent(icao)}/density-altitude`)
+    if (response.status === 404) return null
+    if (!response.ok) throw new Error(`Failed to load density altitude: ${response.status}`)
+    return response.json()
+  }
+}
+
+/**
+ * Airport database API (host-side SQLite cache of the bundled airport and runway data)
+ */
+export const airportsApi = {
+  /**
+   * Fuzzy search airports by ICAO, IATA, name or city (tolerates typos), best matches first
+   */
+  search: async (query: string, limit?: number): Promise<AirportSearchResult[]> => {
+    if (isTauri()) {
+      return invokeCommand<AirportSearchResult[]>('search_airport_db', { query, limit: limit ?? null })
+    }
+    const params = new URLSearchParams({ q: query })
+    if (limit !== undefined) params.set('limit', String(limit))
+    const response = await fetch(`/api/airports/search?${params}`)
+    if (!response.ok) throw new Error(`Airport search failed: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get an airport with its runways
+   * Returns null if the airport is unknown
+   */
+  get: async (icao: string): Promise<AirportDbRecord | null> => {
+    if (isTauri()) {
+      return invokeCommand<AirportDbRecord | null>('get_airport_info', { icao })
+    }
+    const response = await fetch(`/api/airports/${encodeURIComponent(icao)}`)
+    if (response.status === 404) return null
+    if (!response.ok) throw new Error(`Failed to load airport: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get the airport's taxiways, aprons, gates and buildings from OpenStreetMap (GeoJSON, cached on the host)
+   */
+  getLayout: async (icao: string, refresh = false): Promise<object> => {
+    if (isTauri()) {
+      return invokeCommand<object>('get_osm_layout', { icao, refresh })
+    }
+    const response = await fetch(`/api/airports/${encodeURIComponent(icao)}/layout?refresh=${refresh}`)
+    if (!response.ok) throw new Error(`Failed to load ground layout: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get runway thresholds, headings, lengths, widths and displaced thresholds
+   * Returns null if the airport is unknown
+   */
+  getRunways: async (icao: string): Promise<AirportDbRunway[] | null> => {
+    if (isTauri()) {
+      return invokeCommand<AirportDbRunway[] | null>('get_airport_runways', { icao })
+    }
+    const response = await fetch(`/api/airports/${encodeURIComponent(icao)}/runways`)
+    if (response.status === 404) return null
+    if (!response.ok) throw new Error(`Failed to load runways: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get extended centerlines, approach cones and glidepaths for every runway end
+   * Returns null if the airport is unknown
+   */
+  getApproaches: async (icao: string): Promise<ApproachPath[] | null> => {
+    if (isTauri()) {
+      return invokeCommand<ApproachPath[] | null>('get_airport_approaches', { icao })
+    }
+    const response = await fetch(`/api/airports/${encodeURIComponent(icao)}/approaches`)
+    if (response.status === 404) return null
+    if (!response.ok) throw new Error(`Failed to load approaches: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Generate the class B/C/D airspace overlay for an airport (FAA in the US, OpenAIP elsewhere)
+   * The overlay is cached on the host and its GeoJSON is served through the overlays API
+   * Returns the overlay id
+   */
+  getAirspaceOverlay: async (icao: string, refresh = false): Promise<string> => {
+    if (isTauri()) {
+      const overlay = await invokeCommand<{ id: string }>('generate_airspace_overlay', { icao, refresh })
+      return overlay.id
+    }
+    const response = await fetch(`/api/airports/${encodeURIComponent(icao)}/airspace?refresh=${refresh}`)
+    if (!response.ok) throw new Error(`Failed to load airspace: ${response.status}`)
+    const overlay: { id: string } = await response.json()
+    return overlay.id
+  },
+
+  /**
+   * Get parsed NOTAMs for an airport, with the runways and taxiways closed now
+   */
+  getNotams: async (icao: string): Promise<AirportNotams> => {
+    if (isTauri()) {
+      return invokeCommand<AirportNotams>('get_airport_notams', { icao })
+    }
+    const response = await fetch(`/api/notams/${encodeURIComponent(icao)}`)
+    if (!response.ok) throw new Error(`Failed to load NOTAMs: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get the FAA d-TPP charts (airport diagram, approach plates, ...) for an airport
+   * Returns null if the airport has none (e.g., outside the US)
+   */
+  getCharts: async (icao: string): Promise<AirportCharts | null> => {
+    if (isTauri()) {
+      return invokeCommand<AirportCharts | null>('get_airport_charts', { icao })
+    }
+    const response = await fetch(`/api/charts/${encodeURIComponent(icao)}`)
+    if (response.status === 404) return null
+    if (!response.ok) throw new Error(`Failed to load charts: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get a loadable URL for a chart PDF (downloaded by the host on first use)
+   */
+  getChartUrl: async (icao: string, pdfName: string): Promise<string> => {
+    if (isTauri()) {
+      const path = await invokeCommand<string>('get_chart_pdf_path', { icao, pdfName })
+      const { convertFileSrc } = await import('@tauri-apps/api/core')
+      return convertFileSrc(path)
+    }
+    return `/api/charts/${encodeURIComponent(icao)}/${encodeURIComponent(pdfName)}`
+  },
+
+  /**
+   * Get the airport's TRACON/FIR/UIR and the VATSIM controllers online covering it
+   */
+  getAtc: async (icao: string): Promise<AirportAtcCoverage> => {
+    if (isTauri()) {
+      return invokeCommand<AirportAtcCoverage>('get_airport_atc', { icao })
+    }
+    const response = await fetch(`/api/airports/${encodeURIComponent(icao)}/atc`)
+    if (!response.ok) throw new Error(`Failed to load ATC coverage: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get the positions online at the active airport and recent online/offline changes
+   */
+  getAtcChanges: async (): Promise<AtcWatchStatus> => {
+    if (isTauri()) {
+      return invokeCommand<AtcWatchStatus>('get_atc_changes')
+    }
+    const response = await fetch('/api/atc/changes')
+    if (!response.ok) throw new Error(`Failed to load ATC changes: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get who is staffing the positions covering an airport (default: the active airport)
+   */
+  getControllers: async (icao?: string): Promise<ControllerInfo[]> => {
+    if (isTauri()) {
+      return invokeCommand<ControllerInfo[]>('get_controllers', { icao: icao ?? null })
+    }
+    const query = icao ? `?icao=${encodeURIComponent(icao)}` : ''
+    const response = await fetch(`/api/controllers${query}`)
+    if (!response.ok) throw await responseError(response, 'Failed to load controllers')
+    return response.json()
+  },
+
+  /**
+   * Look up an online controller by callsign or CID (null when not online)
+   */
+  getController: async (id: string): Promise<ControllerInfo | null> => {
+    try {
+      if (isTauri()) {
+        return await invokeCommand<ControllerInfo>('get_controller', { id })
+      }
+      const response = await fetch(`/api/controllers/${encodeURIComponent(id)}`)
+      if (!response.ok) throw await responseError(response, 'Failed to load controller')
+      return await response.json()
+    } catch (error) {
+      if (isApiError(error, 'notFound')) return null
+      throw error
+    }
+  },
+
+  /**
+   * Import VATSpy data files (VATSpy.dat, Boundaries.geojson) from a local folder (host only)
+   */
+  importVatSpyData: async (path: string): Promise<void> => {
+    if (!isTauri()) {
+      throw new Error('VATSpy data can only be imported on the host')
+    }
+    return invokeCommand<void>('import_vatspy_data', { path })
+  },
+
+  /**
+   * Import localizer courses and glideslope angles from X-Plane earth_nav.dat (host only)
+   * Returns the number of approaches imported
+   */
+  importIlsNavdata: async (path: string): Promise<number> => {
+    if (!isTauri()) {
+      throw new Error('Navdata can only be imported on the host')
+    }
+    return invokeCommand<number>('import_ils_navdata', { path })
+  }
+}
+
+/**
+ * Host traffic picture and gate occupancy API
+ */
+export const trafficApi = {
+  /**
+   * Get the host's traffic picture around the active airport
+   */
+  getSnapshot: async (): Promise<BackendTrafficSnapshot> => {
+    if (isTauri()) {
+      return invokeCommand<BackendTrafficSnapshot>('get_traffic')
+    }
+    const response = await fetch('/api/traffic')
+    if (!response.ok) throw new Error(`Failed to load traffic: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get the host's recent trail of an aircraft (oldest point first)
+   */
+  getAircraftTrail: async (callsign: string): Promise<AircraftTrail> => {
+    if (isTauri()) {
+      return invokeCommand<AircraftTrail>('get_aircraft_trail', { callsign })
+    }
+    const response = await fetch(`/api/aircraft/${encodeURIComponent(callsign)}/trail`)
+    if (!response.ok) throw new Error(`Failed to load trail for ${callsign}: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get a photo of the aircraft flying a callsign for the spotting card (null if none is found)
+   * Pass the registration when it is known to skip resolving it from the flight plan
+   */
+  getAircraftPhoto: async (callsign: string, registration?: string): Promise<AircraftPhoto | null> => {
+    if (isTauri()) {
+      return invokeCommand<AircraftPhoto | null>('get_aircraft_photo', { callsign, registration: registration ?? null })
+    }
+    const query = registration ? `?registration=${encodeURIComponent(registration)}` : ''
+    const response = await fetch(`/api/aircraft/${encodeURIComponent(callsign)}/photo${query}`)
+    if (response.status === 404) return null
+    if (!response.ok) throw new Error(`Failed to load photo for ${callsign}: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get wake category, approach speed and dimensions of an aircraft type (null if unknown)
+   */
+  getAircraftType: async (code: string): Promise<AircraftTypeInfo | null> => {
+    if (isTauri()) {
+      return invokeCommand<AircraftTypeInfo | null>('get_aircraft_type', { code })
+    }
+    const response = await fetch(`/api/aircraft-types/${encodeURIComponent(code)}`)
+    if (response.status === 404) return null
+    if (!response.ok) throw new Error(`Failed to load aircraft type ${code}: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get the name, telephony and country of an airline (null if unknown)
+   */
+  getAirline: async (code: string): Promise<Airline | null> => {
+    if (isTauri()) {
+      return invokeCommand<Airline | null>('get_airline', { code })
+    }
+    const response = await fetch(`/api/airlines/${encodeURIComponent(code)}`)
+    if (response.status === 404) return null
+    if (!response.ok) throw new Error(`Failed to load airline ${code}: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get the host's whole airline database
+   */
+  listAirlines: async (): Promise<Airline[]> => {
+    if (isTauri()) {
+      return invokeCommand<Airline[]>('list_airlines')
+    }
+    const response = await fetch('/api/airlines')
+    if (!response.ok) throw new Error(`Failed to load airlines: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Decode a callsign into airline, flight number and spoken form ("BAW123" -> "SPEEDBIRD 123")
+   */
+  getCallsignInfo: async (callsign: string): Promise<DecodedCallsign> => {
+    if (isTauri()) {
+      return invokeCommand<DecodedCallsign>('get_callsign_info', { callsign })
+    }
+    const response = await fetch(`/api/callsigns/${encodeURIComponent(callsign)}`)
+    if (!response.ok) throw new Error(`Failed to decode callsign ${callsign}: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get the active emergency and squawk mismatch alerts
+   */
+  getSquawkAlerts: async (): Promise<SquawkAlerts> => {
+    if (isTauri()) {
+      return invokeCommand<SquawkAlerts>('get_squawk_alerts')
+    }
+    const response = await fetch('/api/squawks')
+    if (!response.ok) throw new Error(`Failed to load squawk alerts: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get the active short-term conflict alerts
+   */
+  getStcaAlerts: async (): Promise<StcaAlerts> => {
+    if (isTauri()) {
+      return invokeCommand<StcaAlerts>('get_stca_alerts')
+    }
+    const response = await fetch('/api/stca')
+    if (!response.ok) throw new Error(`Failed to load conflict alerts: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get the datablock placements worked out by the host
+   */
+  getLabelLayout: async (): Promise<LabelLayout> => {
+    if (isTauri()) {
+      return invokeCommand<LabelLayout>('get_label_layout')
+    }
+    const response = await fetch('/api/labels')
+    if (!response.ok) throw new Error(`Failed to load label layout: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get runway occupancy (aircraft on each runway and since when)
+   */
+  getRunwayOccupancy: async (): Promise<RunwayOccupancy> => {
+    if (isTauri()) {
+      return invokeCommand<RunwayOccupancy>('get_runway_occupancy')
+    }
+    const response = await fetch('/api/runways/occupancy')
+    if (!response.ok) throw new Error(`Failed to load runway occupancy: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get the runway configuration detected from traffic, checked against the wind
+   */
+  getRunwayConfiguration: async (): Promise<RunwayConfiguration> => {
+    if (isTauri()) {
+      return invokeCommand<RunwayConfiguration>('get_runway_configuration')
+    }
+    const response = await fetch('/api/runways/config')
+    if (!response.ok) throw new Error(`Failed to load runway configuration: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get the go-arounds seen in this session
+   */
+  getGoArounds: async (): Promise<GoArounds> => {
+    if (isTauri()) {
+      return invokeCommand<GoArounds>('get_session_go_arounds')
+    }
+    const response = await fetch('/api/go-arounds')
+    if (!response.ok) throw new Error(`Failed to load go-arounds: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get hold-short and line-up-and-wait timers
+   */
+  getHoldTimers: async (): Promise<HoldTimers> => {
+    if (isTauri()) {
+      return invokeCommand<HoldTimers>('get_hold_timers')
+    }
+    const response = await fetch('/api/holds')
+    if (!response.ok) throw new Error(`Failed to load hold timers: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get final approach spacing (in-trail and required wake separation per runway)
+   */
+  getFinalSpacing: async (): Promise<AtpaSnapshot> => {
+    if (isTauri()) {
+      return invokeCommand<AtpaSnapshot>('get_final_spacing')
+    }
+    const response = await fetch('/api/atpa')
+    if (!response.ok) throw new Error(`Failed to load final approach spacing: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get the arrival sequence per runway, with threshold ETAs
+   */
+  getArrivalSequence: async (): Promise<ArrivalSequence> => {
+    if (isTauri()) {
+      return invokeCommand<ArrivalSequence>('get_arrival_sequence')
+    }
+    const response = await fetch('/api/arrivals')
+    if (!response.ok) throw new Error(`Failed to load arrival sequence: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get the running departure wake timers
+   */
+  getWakeTimers: async (): Promise<WakeTimers> => {
+    if (isTauri()) {
+      return invokeCommand<WakeTimers>('get_wake_timers')
+    }
+    const response = await fetch('/api/wake-timers')
+    if (!response.ok) throw new Error(`Failed to load wake timers: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get gate positions at an airport, with the aircraft occupying them
+   */
+  getGates: async (icao: string): Promise<Gate[]> => {
+    if (isTauri()) {
+      return invokeCommand<Gate[]>('get_airport_gates', { icao })
+    }
+    const response = await fetch(`/api/airports/${encodeURIComponent(icao)}/gates`)
+    if (!response.ok) throw new Error(`Failed to load gates: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get suggested gates for arrivals to the active airport
+   */
+  getGateSuggestions: async (icao: string): Promise<GateSuggestion[]> => {
+    if (isTauri()) {
+      return invokeCommand<GateSuggestion[]>('get_gate_suggestions', { icao })
+    }
+    const response = await fetch(`/api/airports/${encodeURIComponent(icao)}/gates/suggestions`)
+    if (!response.ok) throw new Error(`Failed to load gate suggestions: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get predicted taxi routes for aircraft on the ground at the active airport
+   */
+  getTaxiRoutes: async (icao: string): Promise<TaxiRoute[]> => {
+    if (isTauri()) {
+      return invokeCommand<TaxiRoute[]>('get_taxi_routes', { icao })
+    }
+    const response = await fetch(`/api/airports/${encodeURIComponent(icao)}/taxi-routes`)
+    if (!response.ok) throw new Error(`Failed to load taxi routes: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get predicted touchdown points and turn-offs for arrivals on short final at the active airport
+   */
+  getLandingPredictions: async (icao: string): Promise<LandingPrediction[]> => {
+    if (isTauri()) {
+      return invokeCommand<LandingPrediction[]>('get_landing_predictions', { icao })
+    }
+    const response = await fetch(`/api/airports/${encodeURIComponent(icao)}/landing-predictions`)
+    if (!response.ok) throw new Error(`Failed to load landing predictions: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Import gate positions for an airport from a GeoJSON file on the host
+   * Returns the number of gates imported
+   */
+  importGates: async (icao: string, path: string): Promise<number> => {
+    if (!isTauri()) {
+      throw new Error('Gates can only be imported on the host')
+    }
+    return invokeCommand<number>('import_gates', { icao, path })
+  },
+
+  /**
+   * Get departure and arrival strips for an airport from VATSIM flight plans and prefiles
+   */
+  getStrips: async (icao: string): Promise<StripBay> => {
+    if (isTauri()) {
+      return invokeCommand<StripBay>('get_airport_strips', { icao })
+    }
+    const response = await fetch(`/api/strips/${encodeURIComponent(icao)}`)
+    if (!response.ok) throw new Error(`Failed to load strips: ${response.status}`)
+    return response.json()
+  }
+}
+
+/**
+ * Replay server API (the host streams a recorded session to all clients)
+ */
+export const replayApi = {
+  /**
+   * Get the host replay status
+   */
+  getStatus: async (): Promise<ReplayServerStatus> => {
+    if (isTauri()) {
+      return invokeCommand<ReplayServerStatus>('get_replay_status')
+    }
+    const response = await fetch('/api/replay')
+    if (!response.ok) throw new Error(`Failed to load replay status: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get the host's loaded replay (null when none is loaded) to play it in sync with the host
+   */
+  getRecording: async (): Promise<ReplayExportData | null> => {
+    if (isTauri()) {
+      return invokeCommand<ReplayExportData | null>('get_replay_recording')
+    }
+    const response = await fetch('/api/replay/recording')
+    if (response.status === 404) return null
+    if (!response.ok) throw new Error(`Failed to load replay: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Load a replay export file from the host's disk and stream it to all clients
+   */
+  loadFile: async (path: string): Promise<ReplayServerStatus> => {
+    if (!isTauri()) {
+      throw new Error('Replay files can only be loaded from disk on the host')
+    }
+    return invokeCommand<ReplayServerStatus>('load_replay_file', { path })
+  },
+
+  /**
+   * Upload a replay export to the host and stream it to all clients
+   */
+  upload: async (data: ReplayExportData): Promise<ReplayServerStatus> => {
+    if (isTauri()) {
+      throw new Error('Use loadFile on the host')
+    }
+    const response = await fetch('/api/replay', {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify(data)
+    })
+    if (!response.ok) throw await responseError(response, 'Failed to load replay')
+    return response.json()
+  },
+
+  /**
+   * Replay the last minutes of traffic to all clients (default: 2),
+   * then return them to live traffic
+   */
+  instantReplay: async (minutes?: number): Promise<ReplayServerStatus> => {
+    if (isTauri()) {
+      return invokeCommand<ReplayServerStatus>('start_instant_replay', { minutes: minutes ?? null })
+    }
+    const query = minutes !== undefined ? `?minutes=${minutes}` : ''
+    const response = await fetch(`/api/replay/instant${query}`, { method: 'POST' })
+    if (!response.ok) throw await responseError(response, 'Failed to start instant replay')
+    return response.json()
+  },
+
+  /**
+   * Play, pause, seek or change the speed of the host replay
+   */
+  control: async (control: ReplayServerControl): Promise<ReplayServerStatus> => {
+    if (isTauri()) {
+      return invokeCommand<ReplayServerStatus>('control_replay', { control })
+    }
+    const response = await fetch('/api/replay/control', {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify(control)
+    })
+    if (!response.ok) throw await responseError(response, 'Failed to control replay')
+    return response.json()
+  },
+
+  /**
+   * Export recorded tracks to a file on the host (Tauri only)
+   * Returns the written path
+   */
+  exportToFile: async (source: SessionExportSource, format: SessionExportFormat, path: string): Promise<string> => {
+    if (!isTauri()) {
+      throw new Error('Sessions can only be exported to a file on the host; use getExportUrl')
+    }
+    return invokeCommand<string>('export_session', { source, format, path })
+  },
+
+  /**
+   * URL that downloads recorded tracks from the host (remote browsers)
+   */
+  getExportUrl: (source: SessionExportSource, format: SessionExportFormat): string => {
+    return `/api/replay/export?source=${source}&format=${format}`
+  },
+
+  /**
+   * Get movement, peak traffic and final spacing statistics for a recorded session
+   * (default: the loaded replay, otherwise the instant replay buffer)
+   */
+  getSessionStats: async (source?: SessionExportSource): Promise<SessionStats> => {
+    if (isTauri()) {
+      return invokeCommand<SessionStats>('get_session_stats', { source: source ?? null })
+    }
+    const query = source ? `?source=${source}` : ''
+    const response = await fetch(`/api/stats/session${query}`)
+    if (!response.ok) throw await responseError(response, 'Failed to load session stats')
+    return response.json()
+  },
+
+  /**
+   * Export session statistics to a file on the host as JSON (full report) or CSV (movements)
+   * Returns the written path
+   */
+  exportSessionStats: async (format: 'json' | 'csv', path: string, source?: SessionExportSource): Promise<string> => {
+    if (!isTauri()) {
+      throw new Error('Statistics can only be exported to a file on the host; use getSessionStatsExportUrl')
+    }
+    return invokeCommand<string>('export_session_stats', { source: source ?? null, format, path })
+  },
+
+  /**
+   * URL that downloads the session's movements as CSV (remote browsers)
+   */
+  getSessionStatsExportUrl: (source?: SessionExportSource): string => {
+    return `/api/stats/session?format=csv${source ? `&source=${source}` : ''}`
+  },
+
+  /**
+   * List the shared replays in the host's library
+   */
+  listShared: async (): Promise<SharedReplay[]> => {
+    if (isTauri()) {
+      return invokeCommand<SharedReplay[]>('list_shared_replays')
+    }
+    const response = await fetch('/api/replays')
+    if (!response.ok) throw await responseError(response, 'Failed to list shared replays')
+    return response.json()
+  },
+
+  /**
+   * Export a recorded session as a .tcreplay file on the host (Tauri only);
+   * without a path it is published to the host's library
+   */
+  share: async (options: ShareReplayOptions, source?: SessionExportSource, path?: string): Promise<SharedReplay> => {
+    if (!isTauri()) {
+      throw new Error('Replays can only be shared to a file on the host; use getShareUrl')
+    }
+    return invokeCommand<SharedReplay>('share_replay', { source: source ?? null, options, path: path ?? null })
+  },
+
+  /**
+   * URL that downloads a recorded session as a .tcreplay file (remote browsers)
+   */
+  getShareUrl: (options: ShareReplayOptions = {}, source?: SessionExportSource): string => {
+    const params = new URLSearchParams()
+    if (source) params.set('source', source)
+    if (options.title) params.set('title', options.title)
+    if (options.description) params.set('description', options.description)
+    const query = params.toString()
+    return `/api/replay/share${query ? `?${query}` : ''}`
+  },
+
+  /**
+   * Add a .tcreplay file (or a JSON replay export) to the host's library:
+   * a path on the host's disk, or the file itself from a remote browser
+   */
+  importShared: async (file: string | Blob): Promise<SharedReplay> => {
+    if (isTauri()) {
+      if (typeof file !== 'string') throw new Error('Import shared replays from a path on the host')
+      return invokeCommand<SharedReplay>('import_shared_replay', { path: file })
+    }
+    const response = await fetch('/api/replays', { method: 'POST', body: file })
+    if (!response.ok) throw await responseError(response, 'Failed to import replay')
+    return response.json()
+  },
+
+  /**
+   * URL that downloads a shared replay from the host's library
+   */
+  getSharedUrl: (name: string): string => {
+    return `/api/replays/${encodeURIComponent(name)}`
+  },
+
+  /**
+   * Load a shared replay from the host's library and stream it to all clients
+   */
+  loadShared: async (name: string): Promise<ReplayServerStatus> => {
+    if (isTauri()) {
+      return invokeCommand<ReplayServerStatus>('load_shared_replay', { name })
+    }
+    const response = await fetch(`/api/replays/${encodeURIComponent(name)}/load`, { method: 'POST' })
+    if (!response.ok) throw await responseError(response, 'Failed to load shared replay')
+    return response.json()
+  },
+
+  /**
+   * Remove a shared replay from the host's library
+   */
+  deleteShared: async (name: string): Promise<void> => {
+    if (isTauri()) {
+      return invokeCommand<void>('delete_shared_replay', { name })
+    }
+    const response = await fetch(`/api/replays/${encodeURIComponent(name)}`, { method: 'DELETE' })
+    if (!response.ok) throw await responseError(response, 'Failed to delete shared replay')
+  },
+
+  /**
+   * Stop the host replay and return all clients to live traffic
+   */
+  stop: async (): Promise<ReplayServerStatus> => {
+    if (isTauri()) {
+      return invokeCommand<ReplayServerStatus>('stop_replay')
+    }
+    const response = await fetch('/api/replay', { method: 'DELETE' })
+    if (!response.ok) throw new Error(`Failed to stop replay: ${response.status}`)
+    return response.json()
+  }
+}
+
+/**
+ * Timelapse capture API (host only; frames are rendered by the desktop window)
+ */
+export const timelapseApi = {
+  /**
+   * Get the timelapse status
+   */
+  getStatus: async (): Promise<TimelapseStatus> => {
+    if (!isTauri()) {
+      throw new Error('Timelapse capture is only available on the host')
+    }
+    return invokeCommand<TimelapseStatus>('get_timelapse_status')
+  },
+
+  /**
+   * Start capturing a frame every interval into a folder
+   */
+  start: async (options: TimelapseOptions): Promise<TimelapseStatus> => {
+    if (!isTauri()) {
+      throw new Error('Timelapse capture is only available on the host')
+    }
+    return invokeCommand<TimelapseStatus>('start_timelapse', { options })
+  },
+
+  /**
+   * Stop capturing (the video is assembled in the background if requested)
+   */
+  stop: async (): Promise<TimelapseStatus> => {
+    if (!isTauri()) {
+      throw new Error('Timelapse capture is only available on the host')
+    }
+    return invokeCommand<TimelapseStatus>('stop_timelapse')
+  },
+
+  /**
+   * Send a captured PNG frame to the host, returns the saved frame number
+   */
+  saveFrame: async (sessionId: number, png: ArrayBuffer): Promise<number> => {
+    return invokeCommand<number>('save_timelapse_frame', new Uint8Array(png), {
+      headers: { 'x-timelapse-session': String(sessionId) }
+    })
+  }
+}
+
+/**
+ * CRC video maps API (ASDE-X, tower cab and STARS maps imported per airport)
+ */
+export const videoMapsApi = {
+  /**
+   * Import an airport's video maps from the host's CRC install
+   * (optionally from a specific CRC data folder, Tauri only)
+   */
+  import: async (icao: string, crcPath?: string): Promise<VideoMapIndex> => {
+    if (isTauri()) {
+      return invokeCommand<VideoMapIndex>('import_video_maps', { icao, crcPath: crcPath ?? null })
+    }
+    const response = await fetch(`/api/videomaps/${encodeURIComponent(icao)}/import`, { method: 'POST' })
+    if (!response.ok) throw await responseError(response, 'Failed to import video maps')
+    return response.json()
+  },
+
+  /**
+   * List the video maps imported for an airport
+   * Returns null if none were imported
+   */
+  list: async (icao: string): Promise<VideoMapIndex | null> => {
+    if (isTauri()) {
+      return invokeCommand<VideoMapIndex | null>('list_video_maps', { icao })
+    }
+    const response = await fetch(`/api/videomaps/${encodeURIComponent(icao)}`)
+    if (response.status === 404) return null
+    if (!response.ok) throw new Error(`Failed to list video maps: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Read an imported video map as GeoJSON
+   */
+  read: async (icao: string, id: string): Promise<object> => {
+    if (isTauri()) {
+      return invokeCommand<object>('read_video_map', { icao, id })
+    }
+    const response = await fetch(`/api/videomaps/${encodeURIComponent(icao)}/${encodeURIComponent(id)}`)
+    if (!response.ok) throw new Error(`Failed to load video map: ${response.status}`)
+    return response.json()
+  }
+}
+
+/**
+ * Offline terrain and imagery cache API
+ * Progress of a running download is also emitted as `tile-cache-progress` events (Tauri only)
+ */
+export const tileCacheApi = {
+  /**
+   * Get cache size, quota and the progress of the last download
+   */
+  getStatus: async (): Promise<TileCacheStatus> => {
+    if (isTauri()) {
+      return invokeCommand<TileCacheStatus>('get_tile_cache_status')
+    }
+    const response = await fetch('/api/tiles')
+    if (!response.ok) throw new Error(`Failed to get tile cache status: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Start downloading tiles around airports (defaults to the airports in settings)
+   */
+  prefetch: async (request: TilePrefetchRequest = {}): Promise<TilePrefetchProgress> => {
+    if (isTauri()) {
+      return invokeCommand<TilePrefetchProgress>('prefetch_tiles', { request })
+    }
+    const response = await fetch('/api/tiles/prefetch', {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify(request)
+    })
+    if (!response.ok) throw await responseError(response, 'Failed to prefetch tiles')
+    return response.json()
+  },
+
+  /**
+   * Stop a running download
+   */
+  cancelPrefetch: async (): Promise<void> => {
+    if (isTauri()) {
+      return invokeCommand('cancel_tile_prefetch')
+    }
+    const response = await fetch('/api/tiles/prefetch', { method: 'DELETE' })
+    if (!response.ok) throw new Error(`Failed to cancel tile download: ${response.status}`)
+  },
+
+  /**
+   * Delete all cached tiles
+   */
+  clear: async (): Promise<void> => {
+    if (isTauri()) {
+      return invokeCommand('clear_tile_cache')
+    }
+    const response = await fetch('/api/tiles', { method: 'DELETE' })
+    if (!response.ok) throw await responseError(response, 'Failed to clear the tile cache')
+  },
+
+  /**
+   * Base URL of the cached tiles: the `tiles` protocol in the app, the HTTP API in remote browsers
+   */
+  getBaseUrl: (): string => {
+    if (!isTauri()) return '/api/tiles/'
+    return navigator.userAgent.includes('Windows') ? 'http://tiles.localhost/' : 'tiles://localhost/'
+  }
+}
+
+/**
+ * Event preparation API (warms weather, charts, overlays, models and tiles for an airport)
+ * Progress is also emitted as `event-prep-progress` events (Tauri only)
+ */
+export const eventPrepApi = {
+  /**
+   * Get the status of the current or last preparation
+   */
+  getStatus: async (): Promise<EventPrepStatus | null> => {
+    if (isTauri()) {
+      return invokeCommand<EventPrepStatus | null>('get_event_prep_status')
+    }
+    const response = await fetch('/api/event-prep')
+    if (!response.ok) throw new Error(`Failed to get event preparation status: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Start preparing for an event (rejects with `conflict` while one is running)
+   */
+  prepare: async (request: EventPrepRequest): Promise<EventPrepStatus> => {
+    if (isTauri()) {
+      return invokeCommand<EventPrepStatus>('prepare_event', { request })
+    }
+    const response = await fetch('/api/event-prep', {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify(request)
+    })
+    if (!response.ok) throw await responseError(response, 'Failed to start event preparation')
+    return response.json()
+  },
+
+  /**
+   * Stop a running preparation after the current item
+   */
+  cancel: async (): Promise<void> => {
+    if (isTauri()) {
+      return invokeCommand('cancel_event_prep')
+    }
+    const response = await fetch('/api/event-prep', { method: 'DELETE' })
+    if (!response.ok) throw await responseError(response, 'Failed to cancel event preparation')
+  }
+}
+
+/**
+ * VATSIM event schedule API (events at an airport, recording them)
+ * Reminders are emitted as `event-reminder` events (Tauri only)
+ */
+export const eventsApi = {
+  /**
+   * Get the ongoing and upcoming events affecting an airport (default: the active airport)
+   */
+  getUpcoming: async (icao?: string): Promise<EventScheduleStatus> => {
+    if (isTauri()) {
+      return invokeCommand<EventScheduleStatus>('get_upcoming_events', { icao: icao ?? null })
+    }
+    const query = icao ? `?icao=${encodeURIComponent(icao)}` : ''
+    const response = await fetch(`/api/events${query}`)
+    if (!response.ok) throw await responseError(response, 'Failed to load events')
+    return response.json()
+  },
+
+  /**
+   * Record the traffic until an event ends and save it to the replay library
+   * (rejects with `conflict` while traffic is already being recorded)
+   */
+  record: async (id: string): Promise<ScheduledEvent> => {
+    if (isTauri()) {
+      return invokeCommand<ScheduledEvent>('record_event', { id })
+    }
+    const response = await fetch(`/api/events/${encodeURIComponent(id)}/record`, { method: 'POST' })
+    if (!response.ok) throw await responseError(response, 'Failed to start recording')
+    return response.json()
+  }
+}
+
+/**
+ * Aircraft level of detail API (how the host wants this client to draw each aircraft)
+ */
+export const lodApi = {
+  /**
+   * Get this client's model detail budget and the detail of every aircraft
+   */
+  getPolicy: async (): Promise<LodPolicy> => {
+    if (isTauri()) {
+      return invokeCommand<LodPolicy>('get_lod_policy')
+    }
+    const response = await fetch('/api/lod')
+    if (!response.ok) throw new Error(`Failed to get level of detail policy: ${response.status}`)
+    return response.json()
+  }
+}
+
+/**
+ * Terrain and imagery provider API
+ */
+export const mapProvidersApi = {
+  /**
+   * Get the selected providers, which layers load through the host, and their attribution
+   */
+  getInfo: async (): Promise<MapProviderInfo> => {
+    if (isTauri()) {
+      return invokeCommand<MapProviderInfo>('get_map_providers')
+    }
+    const response = await fetch('/api/map-providers')
+    if (!response.ok) throw new Error(`Failed to get map providers: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Earlier imagery vintages of a provider (default: the selected one), newest first
+   */
+  listVintages: async (provider?: ImageryProviderType): Promise<ImageryVintage[]> => {
+    if (isTauri()) {
+      return invokeCommand<ImageryVintage[]>('list_imagery_vintages', { provider: provider ?? null })
+    }
+    const response = await fetch(`/api/map-providers/vintages${provider ? `?provider=${provider}` : ''}`)
+    if (!response.ok) throw await responseError(response, 'Failed to load imagery vintages')
+    return response.json()
+  }
+}
+
+/**
+ * Offline terrain package API
+ * Import progress is emitted as `terrain-import-progress` events (Tauri only)
+ */
+export const terrainPackagesApi = {
+  /**
+   * List imported terrain packages
+   */
+  list: async (): Promise<TerrainPackage[]> => {
+    if (isTauri()) {
+      return invokeCommand<TerrainPackage[]>('list_terrain_packages')
+    }
+    const response = await fetch('/api/terrain')
+    if (!response.ok) throw new Error(`Failed to list terrain packages: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Pick a package folder or a .tar/.tar.gz/.tar.zst archive with the native dialog (Tauri only)
+   */
+  pick: async (folder: boolean): Promise<string | null> => {
+    return invokeCommand<string | null>('pick_terrain_package', { folder })
+  },
+
+  /**
+   * Import a package from a folder or archive on the host (Tauri only)
+   */
+  import: async (path: string, name?: string): Promise<TerrainPackage> => {
+    return invokeCommand<TerrainPackage>('import_terrain_package', { path, name: name ?? null })
+  },
+
+  /**
+   * Delete an imported package (Tauri only)
+   */
+  delete: async (id: string): Promise<void> => {
+    return invokeCommand('delete_terrain_package', { id })
+  }
+}
+
+/**
+ * 3D buildings generated from OpenStreetMap footprints by the host
+ */
+export const buildingsApi = {
+  /**
+   * Generate (or get the cached) building tileset around an airport
+   */
+  get: async (icao: string, refresh = false): Promise<BuildingsInfo> => {
+    if (isTauri()) {
+      return invokeCommand<BuildingsInfo>('get_osm_buildings', { icao, refresh })
+    }
+    const response = await fetch(`/api/buildings/${encodeURIComponent(icao)}${refresh ? '?refresh=true' : ''}`)
+    if (!response.ok) throw await responseError(response, 'Failed to load buildings')
+    return response.json()
+  },
+
+  /**
+   * URL of an airport's tileset.json: the `buildings` protocol in the app, the HTTP API in remote browsers
+   */
+  getTilesetUrl: (icao: string): string => {
+    const path = `${encodeURIComponent(icao.toUpperCase())}/tileset.json`
+    if (!isTauri()) return `/api/buildings/${path}`
+    return navigator.userAgent.includes('Windows')
+      ? `http://buildings.localhost/${path}`
+      : `buildings://localhost/${path}`
+  }
+}
+
+/**
+ * Sector file import API (host only: the files are read from the host's disk)
+ */
+export const sectorFileApi = {
+  /**
+   * Import a EuroScope/VRC sector file (.sct2, optionally with its .ese) as a GeoJSON
+   * overlay for an airport, keeping elements within radiusNm of it (default 60 nm)
+   * Returns the overlay id
+   */
+  import: async (icao: string, sctPath: string, esePath?: string, radiusNm?: number): Promise<string> => {
+    if (!isTauri()) {
+      throw new Error('Sector files can only be imported on the host')
+    }
+    const overlay = await invokeCommand<{ id: string }>('import_sector_file_overlay', {
+      icao,
+      sctPath,
+      esePath: esePath ?? null,
+      radiusNm: radiusNm ?? null
+    })
+    return overlay.id
+  }
+}
+
+/**
+ * Sun and moon ephemeris API
+ */
+export const celestialApi = {
+  /**
+   * Get sun and moon azimuth/elevation and moon phase for a position,
+   * at a time (epoch ms) or now
+   */
+  getEphemeris: async (latitude: number, longitude: number, at?: number): Promise<Ephemeris> => {
+    if (isTauri()) {
+      return invokeCommand<Ephemeris>('get_celestial_ephemeris', { latitude, longitude, at: at ?? null })
+    }
+    const query = `lat=${latitude}&lon=${longitude}${at !== undefined ? `&at=${at}` : ''}`
+    const response = await fetch(`/api/celestial?${query}`)
+    if (!response.ok) throw new Error(`Failed to load ephemeris: ${response.status}`)
+    return response.json()
+  }
+}
+
+/**
+ * Host-side aircraft following API (follows on every display)
+ */
+export const followApi = {
+  /**
+   * Get the aircraft followed on every display
+   */
+  get: async (): Promise<HostFollowStatus> => {
+    if (isTauri()) {
+      return invokeCommand<HostFollowStatus>('get_follow')
+    }
+    const response = await fetch('/api/follow')
+    if (!response.ok) throw new Error(`Failed to load follow status: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Follow an aircraft on every display (omitted offsets use defaults)
+   */
+  set: async (target: Partial<HostFollowTarget> & { callsign: string }): Promise<HostFollowStatus> => {
+    if (isTauri()) {
+      return invokeCommand<HostFollowStatus>('set_follow', { target })
+    }
+    const response = await fetch('/api/follow', {
+      method: 'PUT',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify(target)
+    })
+    if (!response.ok) throw await responseError(response, 'Failed to set follow target')
+    return response.json()
+  },
+
+  /**
+   * Stop following on every display
+   */
+  stop: async (): Promise<void> => {
+    if (isTauri()) {
+      return invokeCommand<void>('stop_follow')
+    }
+    const response = await fetch('/api/follow', { method: 'DELETE' })
+    if (!response.ok) throw new Error(`Failed to stop following: ${response.status}`)
+  }
+}
+
+/**
+ * Shared aircraft selection API (highlighted on every opted-in display)
+ */
+export const selectionApi = {
+  /**
+   * Get the aircraft selected across displays
+   */
+  get: async (): Promise<SharedSelection> => {
+    if (isTauri()) {
+      return invokeCommand<SharedSelection>('get_selection')
+    }
+    const response = await fetch('/api/selection')
+    if (!response.ok) throw new Error(`Failed to load selection: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Share an aircraft selection, labelled with the display that made it
+   */
+  set: async (callsign: string, source?: string): Promise<SharedSelection> => {
+    const request = { callsign, source: source ?? null }
+    if (isTauri()) {
+      return invokeCommand<SharedSelection>('set_selection', { request })
+    }
+    const response = await fetch('/api/selection', {
+      method: 'PUT',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify(request)
+    })
+    if (!response.ok) throw await responseError(response, 'Failed to share selection')
+    return response.json()
+  },
+
+  /**
+   * Clear the shared selection
+   */
+  clear: async (): Promise<void> => {
+    if (isTauri()) {
+      return invokeCommand<void>('clear_selection')
+    }
+    const response = await fetch('/api/selection', { method: 'DELETE' })
+    if (!response.ok) throw new Error(`Failed to clear selection: ${response.status}`)
+  }
+}
+
+/**
+ * Coordination messages API (messages between displays)
+ */
+export const coordinationApi = {
+  /**
+   * Get recent messages, oldest first (only those after `since` when given)
+   */
+  getMessages: async (since?: number): Promise<CoordinationMessage[]> => {
+    if (isTauri()) {
+      return invokeCommand<CoordinationMessage[]>('get_coordination_messages', { since: since ?? null })
+    }
+    const query = since !== undefined ? `?since=${since}` : ''
+    const response = await fetch(`/api/messages${query}`)
+    if (!response.ok) throw new Error(`Failed to load messages: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Send a message to every display, labelled with the display that sent it
+   */
+  send: async (text: string, from?: string): Promise<CoordinationMessage> => {
+    const request = { text, from: from ?? null }
+    if (isTauri()) {
+      return invokeCommand<CoordinationMessage>('send_coordination_message', { request })
+    }
+    const response = await fetch('/api/messages', {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify(request)
+    })
+    if (!response.ok) throw await responseError(response, 'Failed to send message')
+    return response.json()
+  }
+}
+
+/**
+ * Shared drawings API (lines, labels and polygons shown on every display)
+ */
+export const drawingsApi = {
+  /**
+   * Get the drawings in the active session
+   */
+  get: async (): Promise<DrawingBoard> => {
+    if (isTauri()) {
+      return invokeCommand<DrawingBoard>('get_drawings')
+    }
+    const response = await fetch('/api/drawings')
+    if (!response.ok) throw new Error(`Failed to load drawings: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Draw a line, label or polygon on every display
+   */
+  add: async (request: DrawingRequest): Promise<Drawing> => {
+    if (isTauri()) {
+      return invokeCommand<Drawing>('add_drawing', { request })
+    }
+    const response = await fetch('/api/drawings', {
+      method: 'POST',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify(request)
+    })
+    if (!response.ok) throw await responseError(response, 'Failed to add drawing')
+    return response.json()
+  },
+
+  /**
+   * Remove a drawing
+   */
+  remove: async (id: string): Promise<DrawingBoard> => {
+    if (isTauri()) {
+      return invokeCommand<DrawingBoard>('delete_drawing', { id })
+    }
+    const response = await fetch(`/api/drawings/${encodeURIComponent(id)}`, { method: 'DELETE' })
+    if (!response.ok) throw await responseError(response, 'Failed to remove drawing')
+    return response.json()
+  },
+
+  /**
+   * Remove every drawing in the active session
+   */
+  clear: async (): Promise<DrawingBoard> => {
+    if (isTauri()) {
+      return invokeCommand<DrawingBoard>('clear_drawings')
+    }
+    const response = await fetch('/api/drawings', { method: 'DELETE' })
+    if (!response.ok) throw await responseError(response, 'Failed to clear drawings')
+    return response.json()
+  },
+
+  /**
+   * Switch to (or start) a drawing session
+   */
+  setSession: async (session: string): Promise<DrawingBoard> => {
+    if (isTauri()) {
+      return invokeCommand<DrawingBoard>('set_drawing_session', { session })
+    }
+    const response = await fetch('/api/drawings/session', {
+      method: 'PUT',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ session })
+    })
+    if (!response.ok) throw await responseError(response, 'Failed to switch drawing session')
+    return response.json()
+  },
+
+  /**
+   * List saved drawing sessions
+   */
+  listSessions: async (): Promise<DrawingSessionInfo[]> => {
+    if (isTauri()) {
+      return invokeCommand<DrawingSessionInfo[]>('list_drawing_sessions')
+    }
+    const response = await fetch('/api/drawings/sessions')
+    if (!response.ok) throw await responseError(response, 'Failed to list drawing sessions')
+    return response.json()
+  }
+}
+
+/**
+ * Unit profile of this browser (remote mode; the desktop app uses the default units)
+ */
+export interface UnitProfileStatus {
+  /** Selected profile (null: the default units) */
+  profile: string | null
+  preferences: UnitPreferences
+  profiles: string[]
+}
+
+/**
+ * Display unit API (remote browsers pick one of the host's unit profiles)
+ */
+export const unitsApi = {
+  /**
+   * Get the unit profiles and the units this browser gets
+   */
+  get: async (): Promise<UnitProfileStatus> => {
+    const response = await fetch('/api/units')
+    if (!response.ok) throw new Error(`Failed to load unit profiles: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Pick the unit profile for this browser (null: the default units)
+   */
+  setProfile: async (profile: string | null): Promise<UnitPreferences> => {
+    const response = await fetch('/api/units/profile', {
+      method: 'PUT',
+      headers: { 'Content-Type': 'application/json' },
+      body: JSON.stringify({ profile })
+    })
+    if (!response.ok) throw await responseError(response, 'Failed to set unit profile')
+    return response.json()
+  }
+}
+
+/**
+ * Tower audio stream relayed by the host
+ */
+export interface AudioStreamInfo {
+  id: string
+  icao: string
+  name: string
+  /** Relay URL path on the host's server */
+  path: string
+  /** Whether the host is currently connected upstream */
+  live: boolean
+  listeners: number
+}
+
+/**
+ * Tower audio relay API (LiveATC/Icecast streams played through the host)
+ */
+export const audioApi = {
+  /**
+   * List the tower audio streams configured for an airport (every stream without one)
+   */
+  list: async (icao?: string): Promise<AudioStreamInfo[]> => {
+    if (isTauri()) {
+      return invokeCommand<AudioStreamInfo[]>('list_audio_streams', { icao: icao ?? null })
+    }
+    const query = icao ? `?icao=${encodeURIComponent(icao)}` : ''
+    const response = await fetch(`/api/audio${query}`)
+    if (!response.ok) throw new Error(`Failed to load audio streams: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * URL to play a relayed stream from (e.g., in an <audio> element)
+   */
+  streamUrl: (stream: AudioStreamInfo): string => `${getApiBaseUrl()}${stream.path}`
+}
+
+/**
+ * Alert sound file in a sound pack
+ */
+export interface SoundFile {
+  name: string
+  /** URL path on the host's server (e.g., "/api/sounds/kbos-tower/ca.ogg") */
+  url: string
+  size: number
+}
+
+/**
+ * Alert sound pack in mods/sounds
+ */
+export interface SoundPackInfo {
+  id: string
+  name: string
+  author: string | null
+  version: string | null
+  description: string | null
+  /** Sound by event type */
+  sounds: Partial<Record<AlertSoundEvent, SoundFile>>
+}
+
+/**
+ * Alert sound pack API (aural alerts from mods/sounds)
+ */
+export const soundsApi = {
+  /**
+   * List the alert sound packs on the host
+   */
+  list: async (): Promise<SoundPackInfo[]> => {
+    if (isTauri()) {
+      return invokeCommand<SoundPackInfo[]>('list_sound_packs')
+    }
+    const response = await fetch('/api/sounds')
+    if (!response.ok) throw await responseError(response, 'Failed to load sound packs')
+    return response.json()
+  },
+
+  /**
+   * Get a playable URL for a pack's sound (e.g., for an Audio element)
+   */
+  getSoundUrl: async (pack: SoundPackInfo, sound: SoundFile): Promise<string> => {
+    if (isTauri()) {
+      const path = await invokeCommand<string>('get_sound_file_path', { id: pack.id, file: sound.name })
+      const { convertFileSrc } = await import('@tauri-apps/api/core')
+      return convertFileSrc(path)
+    }
+    return sound.url
+  }
+}
+
+/**
+ * Shell/external link API
+ */
+export const shellApi = {
+  /**
+   * Open a URL in the system default browser
+   * In browser mode, uses window.open as fallback
+   */
+  openExternal: async (url: string): Promise<void> => {
+    if (isTauri()) {
+      return open(url)
+    }
+    // In browser mode, use window.open
+    window.open(url, '_blank', 'noopener,noreferrer')
+  }
+}
+
+/**
+ * Window layout API (desktop only): extra cab windows and named layouts that
+ * restore every window's monitor, size, fullscreen state and bookmark
+ */
+export const windowLayoutApi = {
+  list: async (): Promise<WindowLayout[]> => {
+    if (!isTauri()) return []
+    return invokeCommand<WindowLayout[]>('list_window_layouts')
+  },
+
+  /**
+   * Save the open windows as a layout (replaces a layout with the same name)
+   */
+  save: async (name: string): Promise<WindowLayout> => {
+    if (!isTauri()) throw new Error('Window layouts are only available in the desktop app')
+    return invokeCommand<WindowLayout>('save_window_layout', { name })
+  },
+
+  /**
+   * Open, close and move windows to match a saved layout
+   */
+  apply: async (name: string): Promise<void> => {
+    if (!isTauri()) throw new Error('Window layouts are only available in the desktop app')
+    return invokeCommand('apply_window_layout', { name })
+  },
+
+  delete: async (name: string): Promise<void> => {
+    if (!isTauri()) return
+    return invokeCommand('delete_window_layout', { name })
+  },
+
+  /**
+   * Open another cab window; returns its label
+   */
+  openCabWindow: async (): Promise<string> => {
+    if (!isTauri()) throw new Error('Cab windows are only available in the desktop app')
+    return invokeCommand<string>('open_cab_window')
+  },
+
+  /**
+   * Remember the bookmark (name or slot) shown in this window, for saved layouts
+   */
+  assignBookmark: async (bookmark: string | null): Promise<void> => {
+    if (!isTauri()) return
+    return invokeCommand('assign_window_bookmark', { bookmark })
+  },
+
+  /**
+   * Bookmark assigned to this window by a layout (null if none)
+   */
+  getBookmark: async (): Promise<string | null> => {
+    if (!isTauri()) return null
+    return invokeCommand<string | null>('get_window_bookmark')
+  }
+}
+
+/**
+ * Control surface API (Stream Deck / Companion, see docs/control-api.md)
+ */
+export const controlApi = {
+  /**
+   * Report what the main window shows (desktop only)
+   */
+  publishView: async (view: ControlView): Promise<void> => {
+    if (!isTauri()) return
+    return invokeCommand('publish_control_view', { view })
+  },
+
+  /**
+   * Get the detected runway configuration at an airport and the bookmark tagged for it
+   */
+  getViewPreset: async (icao: string): Promise<ViewPresetSuggestion | null> => {
+    if (isTauri()) {
+      return invokeCommand<ViewPresetSuggestion | null>('get_view_preset_suggestion', { icao })
+    }
+    const response = await fetch(`/api/airports/${encodeURIComponent(icao)}/view-preset`)
+    if (!response.ok) throw new Error(`Failed to get view preset: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Compare the VATSIM ATIS runways at the active airport with the real-world D-ATIS
+   */
+  getAtisComparison: async (): Promise<AtisComparison | null> => {
+    if (isTauri()) {
+      return invokeCommand<AtisComparison | null>('get_atis_comparison')
+    }
+    const response = await fetch('/api/atis/comparison')
+    if (!response.ok) throw new Error(`Failed to get ATIS comparison: ${response.status}`)
+    return response.json()
+  }
+}
+
+/**
+ * Scripting hooks API (mods/scripts, see docs/scripting.md)
+ */
+export const scriptsApi = {
+  /**
+   * List loaded scripts, the events they handle and their errors
+   */
+  list: async (): Promise<ScriptInfo[]> => {
+    if (isTauri()) {
+      return invokeCommand<ScriptInfo[]>('list_scripts')
+    }
+    const response = await fetch('/api/scripts')
+    if (!response.ok) throw new Error(`Failed to list scripts: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Reload every script (desktop only; the host also reloads on file changes)
+   */
+  reload: async (): Promise<void> => {
+    if (!isTauri()) return
+    return invokeCommand('reload_scripts')
+  }
+}
+
+/**
+ * Plugin routes API (mods/plugins)
+ */
+export const pluginsApi = {
+  /**
+   * List plugins and the routes they serve
+   */
+  list: async (): Promise<PluginInfo[]> => {
+    if (isTauri()) {
+      return invokeCommand<PluginInfo[]>('list_plugins')
+    }
+    const response = await fetch('/api/ext')
+    if (!response.ok) throw new Error(`Failed to list plugins: ${response.status}`)
+    return response.json()
+  }
+}
+
+/**
+ * Content search API (airports, bookmarks, mods, converted models, recent callsigns)
+ */
+export const searchApi = {
+  /**
+   * Fuzzy search across content, best matches first (all kinds if none given)
+   */
+  search: async (query: string, kinds?: SearchKind[], limit?: number): Promise<SearchResult[]> => {
+    if (isTauri()) {
+      return invokeCommand<SearchResult[]>('search_content', { query, kinds: kinds ?? null, limit: limit ?? null })
+    }
+    const params = new URLSearchParams({ q: query })
+    if (kinds?.length) params.set('kinds', kinds.join(','))
+    if (limit !== undefined) params.set('limit', String(limit))
+    const response = await fetch(`/api/search?${params}`)
+    if (!response.ok) throw new Error(`Search failed: ${response.status}`)
+    return response.json()
+  }
+}
+
+/**
+ * Airport packs API (tower position, views, overlays, video maps and mods for one airport)
+ */
+export const airportPacksApi = {
+  /**
+   * Export an airport's pack as a .tar.zst archive or a folder (default folder:
+   * airport-packs/ in app data). Remote browsers download the archive instead.
+   */
+  export: async (
+    icao: string,
+    asArchive = true,
+    options: AirportPackExportOptions = {},
+    destination?: string
+  ): Promise<AirportPackInfo | null> => {
+    if (isTauri()) {
+      return invokeCommand<AirportPackInfo>('export_airport_pack', {
+        icao,
+        destination: destination ?? null,
+        asArchive,
+        options
+      })
+    }
+    const params = new URLSearchParams()
+    for (const key of ['name', 'author', 'version', 'description'] as const) {
+      const value = options[key]
+      if (value) params.set(key, value)
+    }
+    window.location.href = `/api/airport-packs/${encodeURIComponent(icao)}?${params}`
+    return null
+  },
+
+  /**
+   * Pick an airport pack archive with the native file dialog (Tauri only)
+   */
+  pickFile: async (): Promise<string | null> => {
+    if (!isTauri()) return null
+    return invokeCommand<string | null>('pick_airport_pack_file')
+  },
+
+  /**
+   * Install an airport pack: a path to an archive or folder on the host, or an
+   * uploaded archive. Reload global settings afterwards for its views and bookmarks.
+   */
+  install: async (pack: string | Blob): Promise<AirportPackInstallResult> => {
+    if (typeof pack === 'string') {
+      return invokeCommand<AirportPackInstallResult>('install_airport_pack', { path: pack })
+    }
+    const response = await fetch('/api/airport-packs', { method: 'POST', body: pack })
+    if (!response.ok) throw await responseError(response, 'Failed to install airport pack')
+    return response.json()
+  }
+}
+
+/**
+ * App info API
+ */
+export const appApi = {
+  /**
+   * Get the app version from Tauri config
+   */
+  getVersion,
+
+  /**
+   * Get how long each startup phase took on the host
+   */
+  getStartupProfile: async (): Promise<StartupProfile> => {
+    if (isTauri()) {
+      return invokeCommand<StartupProfile>('get_startup_profile')
+    }
+    const response = await fetch('/api/diagnostics/startup')
+    if (!response.ok) throw new Error(`Failed to get startup profile: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get the state, restarts and last failure of the host's background tasks
+   */
+  getTaskStatus: async (): Promise<TaskStatus[]> => {
+    if (isTauri()) {
+      return invokeCommand<TaskStatus[]>('get_task_status')
+    }
+    const response = await fetch('/api/diagnostics/tasks')
+    if (!response.ok) throw new Error(`Failed to get task status: ${response.status}`)
+    return response.json()
+  },
+
+  /**
+   * Get the command-line arguments the app was started with
+   * (remote browsers have none)
+   */
+  getStartupArgs: async (): Promise<StartupArgs> => {
+    if (isTauri()) {
+      return invokeCommand<StartupArgs>('get_startup_args')
+    }
+    return { airport: null, bookmark: null, port: null, fullscreen: false, headless: false, profile: null }
+  },
+
+  /**
+   * Change the host log level now and save it to global settings
+   * (a level such as 'debug', or directives such as 'info,app_lib::vnas=debug')
+   */
+  setLogLevel: async (level: string): Promise<void> => {
+    if (isTauri()) {
+      return invokeCommand('set_log_level', { level })
+    }
+    const settings = await globalSettingsApi.read()
+    await globalSettingsApi.write({ ...settings, diagnostics: { ...settings.diagnostics, logLevel: level } })
+  },
+
+  /**
+   * Open the host's log folder in the file manager (Tauri only)
+   */
+  openLogFolder: async (): Promise<void> => {
+    if (!isTauri()) {
+      throw new Error('Opening the log folder is only available on the host')
+    }
+    return invokeCommand('open_log_folder')
+  },
+
+  /**
+   * Get the keep-awake mode and whether sleep is currently prevented (Tauri only)
+   */
+  getKeepAwakeStatus: async (): Promise<KeepAwakeStatus | null> => {
+    if (!isTauri()) return null
+    return invokeCommand<KeepAwakeStatus>('get_keep_awake_status')
+  },
+
+  /**
+   * Get each configured hotkey and whether it is registered (Tauri only)
+   */
+  getHotkeyStatus: async (): Promise<HotkeyStatus[]> => {
+    if (!isTauri()) return []
+    return invokeCommand<HotkeyStatus[]>('get_hotkey_status')
+  },
+
+  /**
+   * Get whether gamepad input is enabled and which controllers are connected (Tauri only)
+   */
+  getGamepadStatus: async (): Promise<GamepadStatus | null> => {
+    if (!isTauri()) return null
+    return invokeCommand<GamepadStatus>('get_gamepad_status')
+  },
+
+  /**
+   * Get the MIDI input ports and which one is listened to (Tauri only)
+   */
+  getMidiStatus: async (): Promise<MidiStatus | null> => {
+    if (!isTauri()) return null
+    return invokeCommand<MidiStatus>('get_midi_status')
+  },
+
+  /**
+   * Check for an update on the configured channel (Tauri only).
+   * Automatic checks don't offer skipped versions or anything while updates are deferred.
+   */
+  checkForUpdate: async (manual: boolean): Promise<AvailableUpdate | null> => {
+    if (!isTauri()) return null
+    return invokeCommand<AvailableUpdate | null>('check_for_update', { manual })
+  },
+
+  /**
+   * Release notes of the releases on the channel newer than the running version, newest first
+   */
+  getReleaseNotes: async (): Promise<ReleaseNotes[]> => {
+    if (!isTauri()) return []
+    return invokeCommand<ReleaseNotes[]>('get_release_notes')
+  },
+
+  /**
+   * Download and install the update found by the last check (progress: `update-progress` event)
+   */
+  installUpdate: async (): Promise<void> => {
+    await invokeCommand('install_update')
+  },
+
+  /**
+   * Set the update channel and save it to global settings
+   */
+  setUpdateChannel: async (channel: UpdateChannel): Promise<void> => {
+    await invokeCommand('set_update_channel', { channel })
+  },
+
+  /**
+   * Don't offer updates on automatic checks for the given number of hours
+   */
+  deferUpdates: async (hours: number): Promise<void> => {
+    await invokeCommand('defer_updates', { hours })
+  },
+
+  /**
+   * Don't offer a version on automatic checks (null: offer skipped versions again)
+   */
+  skipUpdateVersion: async (version: string | null): Promise<void> => {
+    await invokeCommand('skip_update_version', { version })
+  },
+
+  /**
+   * List packs from the last index check and installed packs (Tauri only)
+   */
+  listContentPacks: async (): Promise<ContentPacks | null> => {
+    if (!isTauri()) return null
+    return invokeCommand<ContentPacks>('list_content_packs')
+  },
+
+  /**
+   * Check the content pack indexes now and install updates of opted-in packs
+   */
+  checkContentPacks: async (): Promise<ContentPacks> => {
+    return invokeCommand<ContentPacks>('check_content_packs')
+  },
+
+  /**
+   * Opt in to (install) or out of (remove) a content pack
+   */
+  setContentPackEnabled: async (id: string, enabled: boolean): Promise<ContentPacks> => {
+    return invokeCommand<ContentPacks>('set_content_pack_enabled', { id, enabled })
+  },
+
+  /**
+   * Import settings, bookmarks and tower positions from the old Electron version (Tauri only)
+   */
+  importElectronSettings: async (): Promise<ElectronImportReport | null> => {
+    if (!isTauri()) return null
+    return invokeCommand<ElectronImportReport>('import_electron_settings')
+  },
+
+  /**
+   * Back up app data, tower positions and VMR files (default folder: backups/ in app data)
+   */
+  createBackup: async (includeLogs: boolean, destination?: string): Promise<BackupInfo> => {
+    return invokeCommand<BackupInfo>('create_backup', { includeLogs, destination: destination ?? null })
+  },
+
+  /**
+   * List backups in the default backups folder, newest first
+   */
+  listBackups: async (): Promise<BackupInfo[]> => {
+    if (!isTauri()) return []
+    return invokeCommand<BackupInfo[]>('list_backups')
+  },
+
+  /**
+   * Pick a backup archive with the native file dialog
+   */
+  pickBackupFile: async (): Promise<string | null> => {
+    return invokeCommand<string | null>('pick_backup_file')
+  },
+
+  /**
+   * Check every file of a backup against its manifest
+   */
+  verifyBackup: async (path: string): Promise<BackupInfo> => {
+    return invokeCommand<BackupInfo>('verify_backup', { path })
+  },
+
+  /**
+   * Verify a backup, back up the current data, then restore it (restart afterwards)
+   */
+  restoreBackup: async (path: string): Promise<RestoreResult> => {
+    return invokeCommand<RestoreResult>('restore_backup', { path })
+  },
+
+  /**
+   * Get whether the app starts at login (Tauri only)
+   */
+  getAutostartStatus: async (): Promise<AutostartStatus | null> => {
+    if (!isTauri()) return null
+    return invokeCommand<AutostartStatus>('get_autostart_status')
+  },
+
+  /**
+   * Register or unregister the app to start at login and save it to global settings
+   */
+  setAutostart: async (enabled: boolean, headless: boolean): Promise<void> => {
+    if (isTauri()) {
+      await invokeCommand('set_autostart', { enabled, headless })
+      return
+    }
+    const settings = await globalSettingsApi.read()
+    await globalSettingsApi.write({ ...settings, autostart: { enabled, headless } })
+  },
+
+  /**
+   * Change when the host keeps the display awake and save it to global settings
+   */
+  setKeepAwake: async (mode: KeepAwakeMode): Promise<void> => {
+    if (isTauri()) {
+      await invokeCommand('set_keep_awake', { mode })
+      return
+    }
+    const settings = await globalSettingsApi.read()
+    await globalSettingsApi.write({ ...settings, power: { ...settings.power, keepAwake: mode } })
+  }
 }
 
 /**
@@ -384,10 +2974,28 @@ export function convertToAssetUrlSync(filePath: string): string {
  */
 export const tauriApi = {
   mod: modApi,
+  placements: placementsApi,
+  annotations: annotationsApi,
   globalSettings: globalSettingsApi,
   httpServer: httpServerApi,
+  weather: weatherApi,
+  airports: airportsApi,
+  traffic: trafficApi,
+  replay: replayApi,
+  timelapse: timelapseApi,
+  videoMaps: videoMapsApi,
+  sectorFile: sectorFileApi,
+  celestial: celestialApi,
   shell: shellApi,
   app: appApi,
+  scripts: scriptsApi,
+  plugins: pluginsApi,
+  airportPacks: airportPacksApi,
+  search: searchApi,
+  selection: selectionApi,
+  drawings: drawingsApi,
+  coordination: coordinationApi,
+  windowLayout: windowLayoutApi,
   isTauri,
   convertToAssetUrl,
   convertToAssetUrlSync